@@ -0,0 +1,37 @@
+//! Merkle 樹構建微基準
+//!
+//! 比較單線程 rayon 線程池（等同串行路徑）與默認線程池（每個核心一個線程）中
+//! `MerkleTree::from_blob` 的耗時，Blob 大小 64 MiB（16384 個 4 KiB 葉子）。
+//!
+//! 運行：`cargo bench -p auditor-node --bench merkle_build`
+
+use auditor_node::crypto::merkle::MerkleTree;
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+
+const BLOB_SIZE: usize = 64 * 1024 * 1024;
+const CHUNK_SIZE: usize = 4096;
+
+fn bench_merkle_build(c: &mut Criterion) {
+    let blob: Vec<u8> = (0..BLOB_SIZE).map(|i| (i * 31 % 251) as u8).collect();
+    let single = rayon::ThreadPoolBuilder::new()
+        .num_threads(1)
+        .build()
+        .unwrap();
+
+    let mut group = c.benchmark_group("merkle_from_blob_64mib");
+    group.throughput(Throughput::Bytes(BLOB_SIZE as u64));
+    group.sample_size(10);
+
+    group.bench_function("1_thread", |b| {
+        b.iter(|| single.install(|| black_box(MerkleTree::from_blob(&blob, CHUNK_SIZE).unwrap())))
+    });
+
+    group.bench_function(format!("{}_threads", rayon::current_num_threads()), |b| {
+        b.iter(|| black_box(MerkleTree::from_blob(&blob, CHUNK_SIZE).unwrap()))
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_merkle_build);
+criterion_main!(benches);
@@ -0,0 +1,73 @@
+//! 構建腳本：注入報告生產者元數據（見 `src/producer.rs`）
+//!
+//! - `AUDITOR_GIT_COMMIT`：構建時的 git 提交哈希（非 git 構建，如 crates.io 發佈包，為空）
+//! - `AUDITOR_GIT_DIRTY`：工作區是否有未提交修改（`true` / `false`，未知時為空）
+//! - `AUDITOR_BUILD_TARGET`：目標三元組
+//! - `AUDITOR_FEATURES`：啟用的 Cargo feature（逗號分隔）
+//!
+//! 打包環境可通過同名環境變量直接提供提交哈希與 dirty 標誌。
+
+use std::env;
+use std::path::Path;
+use std::process::Command;
+
+/// 在 crate 目錄下執行 git，失敗時返回 `None`
+fn git(args: &[&str]) -> Option<String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(env::var("CARGO_MANIFEST_DIR").ok()?)
+        .output()
+        .ok()?;
+
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-env-changed=AUDITOR_GIT_COMMIT");
+    println!("cargo:rerun-if-env-changed=AUDITOR_GIT_DIRTY");
+
+    // 提交或暫存變化時重新運行
+    if let Some(git_dir) = git(&["rev-parse", "--absolute-git-dir"]) {
+        for file in ["HEAD", "index"] {
+            let path = Path::new(&git_dir).join(file);
+            if path.exists() {
+                println!("cargo:rerun-if-changed={}", path.display());
+            }
+        }
+    }
+
+    let commit = env::var("AUDITOR_GIT_COMMIT")
+        .ok()
+        .or_else(|| git(&["rev-parse", "HEAD"]))
+        .unwrap_or_default();
+
+    let dirty = env::var("AUDITOR_GIT_DIRTY").ok().unwrap_or_else(|| {
+        if commit.is_empty() {
+            String::new()
+        } else {
+            git(&["status", "--porcelain", "--untracked-files=no"])
+                .map(|status| (!status.is_empty()).to_string())
+                .unwrap_or_default()
+        }
+    });
+
+    let mut features: Vec<String> = env::vars()
+        .filter_map(|(key, _)| {
+            key.strip_prefix("CARGO_FEATURE_")
+                .map(|name| name.to_lowercase().replace('_', "-"))
+        })
+        .collect();
+    features.sort();
+
+    println!("cargo:rustc-env=AUDITOR_GIT_COMMIT={}", commit);
+    println!("cargo:rustc-env=AUDITOR_GIT_DIRTY={}", dirty);
+    println!(
+        "cargo:rustc-env=AUDITOR_BUILD_TARGET={}",
+        env::var("TARGET").unwrap_or_default()
+    );
+    println!("cargo:rustc-env=AUDITOR_FEATURES={}", features.join(","));
+}
@@ -14,6 +14,8 @@
 
 use auditor_node::audit_report::AuditReportGenerator;
 use auditor_node::integrity::IntegrityVerifier;
+use auditor_node::report::ReportManager;
+use auditor_node::types::BlobId;
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -28,10 +30,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("\n📍 步驟 1: 執行應用層完整性驗證");
     println!("   目標: 下載 Blob 並計算 SHA-256 哈希");
 
-    let test_blob_id = "eRrTusk8yshFQpkemgDnbg0f4-qDo623V2NpeVG1Zcg";
+    let test_blob_id = BlobId::parse("eRrTusk8yshFQpkemgDnbg0f4-qDo623V2NpeVG1Zcg")?;
 
     let verifier = IntegrityVerifier::new_testnet();
-    let audit_data = verifier.audit_blob(test_blob_id).await?;
+    let audit_data = verifier.audit_blob(&test_blob_id).await?;
 
     println!("\n   結果:");
     println!("   ✓ Blob ID:      {}", audit_data.blob_id);
@@ -60,19 +62,19 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let signed_report = generator.generate_report(audit_data)?;
 
     println!("\n   結果:");
-    println!("   ✓ 簽名算法:     {:?}", signed_report.algorithm);
-    println!("   ✓ 簽名長度:     {} bytes", base64::decode(&signed_report.signature)?.len());
-    println!("   ✓ 報告時間戳:   {}", signed_report.report_timestamp);
+    println!("   ✓ 簽名算法 ID:  {}", signed_report.pqc_algorithm);
+    println!("   ✓ 簽名長度:     {} bytes", signed_report.pqc_signature.len());
+    println!("   ✓ 報告時間戳:   {}", signed_report.timestamp);
 
     // ========== 步驟 4: 驗證簽名 ==========
     println!("\n📍 步驟 4: 驗證報告簽名");
 
-    let is_valid = signed_report.verify_signature()?;
+    let is_valid = ReportManager::verify_report(&signed_report, generator.public_key())?;
 
     if is_valid {
         println!("   ✅ 簽名驗證通過！");
         println!("      報告完整性已確認");
-        println!("      簽名者: {}...", &signed_report.auditor_public_key[..32]);
+        println!("      簽名者: {}...", &generator.public_key_base64()[..32]);
     } else {
         println!("   ❌ 簽名驗證失敗！");
         return Err("Signature verification failed".into());
@@ -81,7 +83,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // ========== 步驟 5: 輸出 JSON 報告 ==========
     println!("\n📍 步驟 5: 生成 JSON 格式報告");
 
-    let json_report = signed_report.to_json()?;
+    let json_report = serde_json::to_string_pretty(&signed_report)?;
 
     println!("\n   報告內容預覽:");
     println!("   {}", &json_report[..500.min(json_report.len())]);
@@ -98,14 +100,14 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("   模擬: 從文件加載報告並驗證簽名");
 
     let loaded_json = std::fs::read_to_string(report_path)?;
-    let loaded_report = auditor_node::audit_report::SignedAuditReport::from_json(&loaded_json)?;
+    let loaded_report = ReportManager::from_json(&loaded_json)?;
 
-    let is_still_valid = loaded_report.verify_signature()?;
+    let is_still_valid = ReportManager::verify_report(&loaded_report, generator.public_key())?;
 
     if is_still_valid {
         println!("   ✅ 加載的報告簽名驗證通過！");
-        println!("      Blob ID: {}", loaded_report.audit_data.blob_id);
-        println!("      Hash:    {}", loaded_report.audit_data.content_hash);
+        println!("      Blob ID: {}", loaded_report.blob_id);
+        println!("      Hash:    {}", loaded_report.content_hash());
     } else {
         println!("   ❌ 加載的報告簽名驗證失敗！");
         return Err("Loaded report verification failed".into());
@@ -9,9 +9,10 @@
 //! 4. 從 JSON 載入報告並驗證簽名
 //! 5. 測試報告持久化和跨會話驗證
 
+use auditor_node::audit_report::PqcAlgorithm;
 use auditor_node::keystore::Keystore;
 use auditor_node::report::ReportManager;
-use auditor_node::types::AuditReport;
+use auditor_node::types::{AuditReport, REPORT_SCHEMA_VERSION};
 use std::path::Path;
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -27,11 +28,11 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("----------------------------------------");
 
     let keystore_path = Path::new("./temp_keystore");
-    let keystore = Keystore::generate_and_save(keystore_path)?;
+    let keystore = Keystore::generate_and_save(keystore_path, PqcAlgorithm::Dilithium3)?;
 
     println!("✓ 密鑰對已生成並保存到: {}", keystore_path.display());
     println!("  公鑰長度: {} bytes", keystore.public_key_bytes().len());
-    println!("  檔案: pqc_public.key, pqc_secret.key");
+    println!("  檔案: keystore.json");
     println!();
 
     // 步驟 2: 創建審計報告
@@ -39,20 +40,40 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("----------------------------------------");
 
     let mut report = AuditReport {
+        schema_version: REPORT_SCHEMA_VERSION,
         blob_id: "walrus-blob-abc123".to_string(),
         blob_object_id: "0x789abc...".to_string(),
         auditor: "auditor-node-01".to_string(),
         timestamp: 1700000000,
         challenge_epoch: 42,
         challenge_results: vec![],
+        encoding_n: None,
         total_challenges: 100,
         successful_verifications: 98,
         failed_verifications: 2,
         integrity_hash: vec![0xaa, 0xbb, 0xcc, 0xdd],
         pqc_signature: vec![],
         pqc_algorithm: 0,
+        pqc_prehashed: false,
         is_valid: true,
         failure_reason: None,
+        capture_digest: None,
+        deduplicated_from: None,
+        producer: None,
+        challenge_reveal: None,
+        challenge_seed_source: None,
+        challenge_seed: None,
+        chunk_filter: None,
+        cosignatures: vec![],
+        integrity: None,
+        legacy_envelope: None,
+        blinding_key_id: None,
+        audit_method: None,
+        response_stats: None,
+        blob_status: None,
+        audit_id: None,
+        shard_coverage: vec![],
+        auditor_reputation_at_audit: None,
     };
 
     println!("✓ 報告創建完成");
@@ -163,8 +184,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     // 清理
     println!("清理臨時檔案...");
     std::fs::remove_file(json_path)?;
-    std::fs::remove_file(keystore_path.join("pqc_public.key"))?;
-    std::fs::remove_file(keystore_path.join("pqc_secret.key"))?;
+    std::fs::remove_file(keystore_path.join("keystore.json"))?;
     std::fs::remove_dir(keystore_path)?;
     println!("✓ 清理完成");
     println!();
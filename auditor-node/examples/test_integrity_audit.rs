@@ -9,6 +9,7 @@
 //! ```
 
 use auditor_node::integrity::{IntegrityVerifier, VerificationStatus};
+use auditor_node::types::BlobId;
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -20,7 +21,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("╚════════════════════════════════════════════════════════════════╝");
 
     // 使用我們上傳的測試 Blob
-    let test_blob_id = "eRrTusk8yshFQpkemgDnbg0f4-qDo623V2NpeVG1Zcg";
+    let test_blob_id = BlobId::parse("eRrTusk8yshFQpkemgDnbg0f4-qDo623V2NpeVG1Zcg")?;
     let expected_hash = "bd9e5380f78734bc182e4bb8c464101d3baeb23387d701608901e64cd879e1f5";
 
     println!("\n📋 測試配置:");
@@ -34,7 +35,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("\n🔬 測試 1: 基本審計流程");
     println!("   操作: 下載 Blob → 計算 SHA-256 → 生成審計記錄");
 
-    let audit_result = verifier.audit_blob(test_blob_id).await?;
+    let audit_result = verifier.audit_blob(&test_blob_id).await?;
 
     println!("\n   結果:");
     println!("   ✓ Blob ID:      {}", audit_result.blob_id);
@@ -51,7 +52,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("   操作: 比對當前哈希與歷史基準");
 
     let verify_result = verifier
-        .verify_blob(test_blob_id, expected_hash)
+        .verify_blob(&test_blob_id, expected_hash)
         .await?;
 
     println!("\n   結果:");
@@ -73,7 +74,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let wrong_hash = "0000000000000000000000000000000000000000000000000000000000000000";
 
     let corrupted_result = verifier
-        .verify_blob(test_blob_id, wrong_hash)
+        .verify_blob(&test_blob_id, wrong_hash)
         .await?;
 
     println!("\n   結果:");
@@ -91,8 +92,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("\n🔬 測試 4: 批量審計");
 
     let blob_ids = vec![
-        test_blob_id.to_string(),
-        test_blob_id.to_string(), // 重複的 ID 測試去重
+        test_blob_id,
+        test_blob_id, // 重複的 ID 測試去重
     ];
 
     println!("   操作: 並發審計 {} 個 Blob", blob_ids.len());
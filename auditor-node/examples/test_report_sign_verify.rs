@@ -1,7 +1,7 @@
 //! 測試報告簽名和驗證的詳細調試
 
 use auditor_node::report::ReportManager;
-use auditor_node::types::AuditReport;
+use auditor_node::types::{AuditReport, REPORT_SCHEMA_VERSION};
 use pqc_signer::{Dilithium3Signer, Signer};
 
 fn main() {
@@ -25,20 +25,40 @@ fn main() {
 
     // 3. 創建測試報告
     let mut report = AuditReport {
+        schema_version: REPORT_SCHEMA_VERSION,
         blob_id: "test-blob-123".to_string(),
         blob_object_id: "0x1234".to_string(),
         auditor: "test-auditor".to_string(),
         timestamp: 1000,
         challenge_epoch: 1,
         challenge_results: vec![],
+        encoding_n: None,
         total_challenges: 5,
         successful_verifications: 5,
         failed_verifications: 0,
         integrity_hash: vec![1, 2, 3, 4],
         pqc_signature: vec![], // 初始為空
         pqc_algorithm: 0,
+        pqc_prehashed: false,
         is_valid: true,
         failure_reason: None,
+        capture_digest: None,
+        deduplicated_from: None,
+        producer: None,
+        challenge_reveal: None,
+        challenge_seed_source: None,
+        challenge_seed: None,
+        chunk_filter: None,
+        cosignatures: vec![],
+        integrity: None,
+        legacy_envelope: None,
+        blinding_key_id: None,
+        audit_method: None,
+        response_stats: None,
+        blob_status: None,
+        audit_id: None,
+        shard_coverage: vec![],
+        auditor_reputation_at_audit: None,
     };
 
     println!("✓ 創建測試報告");
@@ -0,0 +1,14 @@
+//! `ReportManager::from_json`（規範報告與舊版信封的統一入口）
+//!
+//! 運行：`cargo +nightly fuzz run report_json`
+
+#![no_main]
+
+use auditor_node::report::ReportManager;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(json) = std::str::from_utf8(data) {
+        let _ = ReportManager::from_json(json);
+    }
+});
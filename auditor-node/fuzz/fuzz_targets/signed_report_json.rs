@@ -0,0 +1,15 @@
+//! `SignedAuditReport::from_json`（舊版報告信封）
+//!
+//! 運行：`cargo +nightly fuzz run signed_report_json`
+
+#![no_main]
+#![allow(deprecated)]
+
+use auditor_node::audit_report::SignedAuditReport;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(json) = std::str::from_utf8(data) {
+        let _ = SignedAuditReport::from_json(json);
+    }
+});
@@ -0,0 +1,591 @@
+//! 報告存檔的批量驗證
+//!
+//! 合規審查需要定期重新驗證數以千計的存檔報告。[`verify_archive`] 遞歸遍歷目錄中的
+//! `*.json` 報告（及 [`crate::report_archive`] 壓縮的 `*.json.gz`），並行地：
+//!
+//! 1. 按信任庫中登記的公鑰驗證主簽名（及聯署）
+//! 2. 檢查報告內部一致性（挑戰計數、逐項結果、`is_valid` 與完整性哈希）
+//! 3. 報告帶有 `previous_report_digest` 時，核對其指向存檔中同一 Blob、同一審計員的更早報告
+//!
+//! 每份報告歸為三類之一：
+//!
+//! - **valid**: 簽名與一致性檢查均通過
+//! - **invalid**: 無法解析、未簽名、簽名無效、內容自相矛盾或鏈條斷裂
+//! - **unverifiable**: 審計員不在信任庫中，無法判斷
+//!
+//! 驗證只讀取信任庫，不做 TOFU 記錄。核心邏輯是同步的，
+//! CLI（`verify-archive`）直接調用，異步服務可放在 `spawn_blocking` 中調用。
+
+use crate::crypto::ct_eq;
+use crate::error::{AuditorError, Result};
+use crate::ingest::{self, IngestLimits};
+use crate::report::ReportManager;
+use crate::trust::TrustStore;
+use crate::types::AuditReport;
+use chrono::{DateTime, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use tracing::{debug, info};
+
+/// 報告中指向上一份報告摘要的字段名
+pub const PREVIOUS_DIGEST_FIELD: &str = "previous_report_digest";
+
+/// 批量驗證選項
+#[derive(Debug, Clone, Default)]
+pub struct ArchiveVerifyOptions {
+    /// 只驗證此時間戳（Unix 秒）及之後的報告
+    pub since: Option<u64>,
+
+    /// 並行線程數（0 表示按 CPU 數）
+    pub jobs: usize,
+}
+
+/// 單份報告的驗證結論
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReportVerdict {
+    Valid,
+    Invalid,
+    Unverifiable,
+}
+
+/// 單份報告的驗證結果
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReportOutcome {
+    /// 報告文件路徑
+    pub path: PathBuf,
+
+    /// Blob ID（無法解析時為空）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blob_id: Option<String>,
+
+    /// 審計員地址（無法解析時為空）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auditor: Option<String>,
+
+    /// 結論
+    pub verdict: ReportVerdict,
+
+    /// 非 valid 時的原因
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+/// 存檔驗證摘要
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArchiveSummary {
+    /// 參與驗證的報告數（不含被 `since` 過濾的）
+    pub total: usize,
+    pub valid: usize,
+    pub invalid: usize,
+    pub unverifiable: usize,
+
+    /// 早於 `since` 而跳過的報告數
+    pub skipped: usize,
+
+    /// 所有 invalid 與 unverifiable 報告的詳情（按路徑排序）
+    pub failures: Vec<ReportOutcome>,
+}
+
+impl ArchiveSummary {
+    /// 是否發現了無效報告（unverifiable 不計入）
+    pub fn has_invalid(&self) -> bool {
+        self.invalid > 0
+    }
+}
+
+/// 解析 `--since` 參數：`YYYY-MM-DD`（UTC 零點）或 RFC 3339 時間
+pub fn parse_since(value: &str) -> Result<u64> {
+    let timestamp = if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        date.and_hms_opt(0, 0, 0)
+            .map(|dt| dt.and_utc().timestamp())
+            .unwrap_or_default()
+    } else {
+        DateTime::parse_from_rfc3339(value)
+            .map_err(|e| AuditorError::Config(format!("Invalid date {}: {}", value, e)))?
+            .timestamp()
+    };
+
+    u64::try_from(timestamp)
+        .map_err(|_| AuditorError::Config(format!("Date {} is before the Unix epoch", value)))
+}
+
+/// 遞歸收集目錄中的 `*.json` 與 `*.json.gz` 文件（按路徑排序）
+pub fn collect_report_files(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    let mut pending = vec![dir.to_path_buf()];
+
+    while let Some(current) = pending.pop() {
+        for entry in fs::read_dir(&current)? {
+            let path = entry?.path();
+            if path.is_dir() {
+                pending.push(path);
+            } else if is_report_file(&path) {
+                files.push(path);
+            }
+        }
+    }
+
+    files.sort();
+    Ok(files)
+}
+
+fn is_report_file(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| name.ends_with(".json") || name.ends_with(".json.gz"))
+}
+
+/// 已解析的報告
+struct LoadedReport {
+    path: PathBuf,
+    report: AuditReport,
+    previous_digest: Option<String>,
+}
+
+/// 並行驗證存檔目錄中的所有報告
+///
+/// # 錯誤
+/// - 目錄無法讀取: 返回 `Io` 錯誤
+///
+/// 單份報告的問題不會中止驗證，而是記為 invalid / unverifiable
+pub fn verify_archive(
+    dir: &Path,
+    trust: &TrustStore,
+    options: &ArchiveVerifyOptions,
+) -> Result<ArchiveSummary> {
+    let files = collect_report_files(dir)?;
+    info!(
+        "Verifying {} report file(s) under {}",
+        files.len(),
+        dir.display()
+    );
+
+    let mut summary = ArchiveSummary::default();
+    let mut outcomes = Vec::new();
+    let mut loaded = Vec::new();
+
+    for path in files {
+        match load(&path) {
+            Ok(report) => loaded.push(report),
+            Err(e) => outcomes.push(ReportOutcome {
+                path,
+                blob_id: None,
+                auditor: None,
+                verdict: ReportVerdict::Invalid,
+                reason: Some(format!("Unreadable report: {}", e)),
+            }),
+        }
+    }
+
+    // 鏈條核對需要全部報告的摘要（包括被 since 過濾掉的更早報告）
+    let digests: HashMap<String, usize> = loaded
+        .iter()
+        .enumerate()
+        .filter_map(|(i, r)| r.report.digest().ok().map(|d| (d, i)))
+        .collect();
+
+    let selected: Vec<&LoadedReport> = loaded
+        .iter()
+        .filter(|r| {
+            options
+                .since
+                .is_none_or(|since| r.report.timestamp >= since)
+        })
+        .collect();
+    summary.skipped = loaded.len() - selected.len();
+
+    let jobs = match options.jobs {
+        0 => std::thread::available_parallelism().map_or(1, |n| n.get()),
+        n => n,
+    }
+    .min(selected.len().max(1));
+
+    let next = AtomicUsize::new(0);
+    let verified = Mutex::new(Vec::with_capacity(selected.len()));
+    std::thread::scope(|scope| {
+        for _ in 0..jobs {
+            scope.spawn(|| loop {
+                let i = next.fetch_add(1, Ordering::Relaxed);
+                let Some(entry) = selected.get(i) else {
+                    break;
+                };
+                let outcome = verify_one(entry, trust, &loaded, &digests);
+                verified.lock().unwrap().push(outcome);
+            });
+        }
+    });
+    outcomes.extend(verified.into_inner().unwrap());
+
+    for outcome in outcomes {
+        summary.total += 1;
+        match outcome.verdict {
+            ReportVerdict::Valid => summary.valid += 1,
+            ReportVerdict::Invalid => summary.invalid += 1,
+            ReportVerdict::Unverifiable => summary.unverifiable += 1,
+        }
+        if outcome.verdict != ReportVerdict::Valid {
+            summary.failures.push(outcome);
+        }
+    }
+    summary.failures.sort_by(|a, b| a.path.cmp(&b.path));
+
+    info!(
+        "Archive verified: {} total, {} valid, {} invalid, {} unverifiable, {} skipped",
+        summary.total, summary.valid, summary.invalid, summary.unverifiable, summary.skipped
+    );
+    Ok(summary)
+}
+
+/// 讀取報告並提取（可能存在的）上一份報告摘要
+fn load(path: &Path) -> Result<LoadedReport> {
+    let limits = IngestLimits::default();
+    let value = ingest::parse_document(&ingest::read_document(path, &limits)?, &limits)?;
+    let previous_digest = value
+        .get(PREVIOUS_DIGEST_FIELD)
+        .and_then(|v| v.as_str())
+        .map(str::to_string);
+
+    Ok(LoadedReport {
+        path: path.to_path_buf(),
+        report: ingest::report_from_value(value)?,
+        previous_digest,
+    })
+}
+
+/// 驗證單份報告
+fn verify_one(
+    entry: &LoadedReport,
+    trust: &TrustStore,
+    all: &[LoadedReport],
+    digests: &HashMap<String, usize>,
+) -> ReportOutcome {
+    let report = &entry.report;
+    let (verdict, reason) = match classify(entry, trust, all, digests) {
+        Ok(()) => (ReportVerdict::Valid, None),
+        Err((verdict, reason)) => (verdict, Some(reason)),
+    };
+    debug!("{}: {:?}", entry.path.display(), verdict);
+
+    ReportOutcome {
+        path: entry.path.clone(),
+        blob_id: Some(report.blob_id.clone()),
+        auditor: Some(report.auditor.clone()),
+        verdict,
+        reason,
+    }
+}
+
+fn classify(
+    entry: &LoadedReport,
+    trust: &TrustStore,
+    all: &[LoadedReport],
+    digests: &HashMap<String, usize>,
+) -> std::result::Result<(), (ReportVerdict, String)> {
+    let report = &entry.report;
+    let invalid = |reason: String| (ReportVerdict::Invalid, reason);
+
+    if report.pqc_signature.is_empty() {
+        return Err(invalid("Report is not signed".to_string()));
+    }
+
+    let key = trust
+        .get(&report.auditor)
+        .ok_or_else(|| {
+            (
+                ReportVerdict::Unverifiable,
+                format!("Auditor {} is not in the trust store", report.auditor),
+            )
+        })?
+        .public_key_bytes()
+        .map_err(|e| invalid(e.to_string()))?;
+
+    let signature_valid = if report.cosignatures.is_empty() {
+        ReportManager::verify_report(report, &key)
+    } else {
+        ReportManager::verify_report_with_cosignatures(report, &key, trust, 1)
+    }
+    .map_err(|e| invalid(e.to_string()))?;
+    if !signature_valid {
+        return Err(invalid("Signature is invalid".to_string()));
+    }
+
+    check_consistency(report).map_err(invalid)?;
+
+    if let Some(previous) = &entry.previous_digest {
+        check_chain(report, previous, all, digests).map_err(invalid)?;
+    }
+
+    Ok(())
+}
+
+/// 檢查報告內部一致性
+pub fn check_consistency(report: &AuditReport) -> std::result::Result<(), String> {
+    let counted = report.successful_verifications as u32 + report.failed_verifications as u32;
+    if counted != report.total_challenges as u32 {
+        return Err(format!(
+            "{} successful + {} failed challenges do not add up to {} total",
+            report.successful_verifications, report.failed_verifications, report.total_challenges
+        ));
+    }
+
+    if !report.challenge_results.is_empty() {
+        let verified = report
+            .challenge_results
+            .iter()
+            .filter(|r| r.verified)
+            .count();
+        if report.challenge_results.len() != report.total_challenges as usize
+            || verified != report.successful_verifications as usize
+        {
+            return Err(format!(
+                "Challenge results ({} listed, {} verified) disagree with the counts ({} total, {} successful)",
+                report.challenge_results.len(),
+                verified,
+                report.total_challenges,
+                report.successful_verifications
+            ));
+        }
+    }
+
+    if report.is_valid && report.failed_verifications > 0 {
+        return Err(format!(
+            "Report is marked valid with {} failed challenge(s)",
+            report.failed_verifications
+        ));
+    }
+
+    if let Some(integrity) = &report.integrity {
+        let expected = hex::decode(&integrity.content_hash).unwrap_or_default();
+        if !ct_eq(&expected, &report.integrity_hash) {
+            return Err("integrity_hash does not match the integrity content hash".to_string());
+        }
+    }
+
+    Ok(())
+}
+
+/// 核對 `previous_report_digest` 指向存檔中同一 Blob、同一審計員的更早報告
+fn check_chain(
+    report: &AuditReport,
+    previous: &str,
+    all: &[LoadedReport],
+    digests: &HashMap<String, usize>,
+) -> std::result::Result<(), String> {
+    let Some(&index) = digests.get(&previous.to_ascii_lowercase()) else {
+        return Err(format!(
+            "Previous report {} is not in the archive",
+            previous
+        ));
+    };
+
+    let prior = &all[index].report;
+    if prior.blob_id != report.blob_id || prior.auditor != report.auditor {
+        return Err(format!(
+            "Previous report {} is for blob {} by {}",
+            previous, prior.blob_id, prior.auditor
+        ));
+    }
+    if prior.timestamp > report.timestamp {
+        return Err(format!(
+            "Previous report {} is newer ({}) than this report ({})",
+            previous, prior.timestamp, report.timestamp
+        ));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::trust::TrustEntry;
+    use pqc_signer::{Dilithium3Signer, Signer};
+
+    fn temp_archive() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("archive_test_{}", rand::random::<u32>()));
+        fs::create_dir_all(dir.join("2024")).unwrap();
+        dir
+    }
+
+    fn keypair() -> Dilithium3Signer {
+        let mut signer = Dilithium3Signer::new();
+        signer.generate_keypair().unwrap();
+        signer
+    }
+
+    fn signed_report(
+        signer: &Dilithium3Signer,
+        auditor: &str,
+        blob_id: &str,
+        timestamp: u64,
+    ) -> AuditReport {
+        let mut report: AuditReport = serde_json::from_value(serde_json::json!({
+            "blob_id": blob_id,
+            "blob_object_id": "0xobject",
+            "auditor": auditor,
+            "timestamp": timestamp,
+            "challenge_epoch": 7,
+            "challenge_results": [],
+            "total_challenges": 10,
+            "successful_verifications": 10,
+            "failed_verifications": 0,
+            "integrity_hash": vec![0u8; 32],
+            "pqc_signature": [],
+            "pqc_algorithm": 3,
+            "is_valid": true,
+            "failure_reason": null,
+        }))
+        .unwrap();
+        ReportManager::sign_report_with(signer, &mut report).unwrap();
+        report
+    }
+
+    fn write(dir: &Path, name: &str, value: &impl Serialize) -> PathBuf {
+        let path = dir.join(name);
+        fs::write(&path, serde_json::to_vec_pretty(value).unwrap()).unwrap();
+        path
+    }
+
+    fn trust_store(entries: &[(&str, &Dilithium3Signer)]) -> TrustStore {
+        let mut store = TrustStore::new();
+        for (address, signer) in entries {
+            store.auditors.insert(
+                address.to_string(),
+                TrustEntry::new(signer.public_key(), 3, true, None),
+            );
+        }
+        store
+    }
+
+    #[test]
+    fn test_archive_classification() {
+        let dir = temp_archive();
+        let known = keypair();
+        let unknown = keypair();
+
+        write(
+            &dir,
+            "good.json",
+            &signed_report(&known, "0xknown", "0xblob1", 100),
+        );
+
+        let mut tampered = signed_report(&known, "0xknown", "0xblob2", 200);
+        tampered.failed_verifications = 3;
+        let tampered_path = write(&dir.join("2024"), "tampered.json", &tampered);
+
+        let missing_key_path = write(
+            &dir,
+            "missing_key.json",
+            &signed_report(&unknown, "0xunknown", "0xblob3", 300),
+        );
+
+        fs::write(dir.join("notes.txt"), "not a report").unwrap();
+
+        let summary = verify_archive(
+            &dir,
+            &trust_store(&[("0xknown", &known)]),
+            &ArchiveVerifyOptions {
+                since: None,
+                jobs: 2,
+            },
+        )
+        .unwrap();
+
+        assert_eq!(summary.total, 3);
+        assert_eq!(summary.valid, 1);
+        assert_eq!(summary.invalid, 1);
+        assert_eq!(summary.unverifiable, 1);
+        assert!(summary.has_invalid());
+
+        let verdicts: Vec<_> = summary
+            .failures
+            .iter()
+            .map(|f| (f.path.clone(), f.verdict))
+            .collect();
+        assert!(verdicts.contains(&(tampered_path, ReportVerdict::Invalid)));
+        assert!(verdicts.contains(&(missing_key_path, ReportVerdict::Unverifiable)));
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_since_skips_older_reports() {
+        let dir = temp_archive();
+        let signer = keypair();
+        write(
+            &dir,
+            "old.json",
+            &signed_report(&signer, "0xa", "0xold", 1_600_000_000),
+        );
+        write(
+            &dir,
+            "new.json",
+            &signed_report(&signer, "0xa", "0xnew", 1_800_000_000),
+        );
+
+        let summary = verify_archive(
+            &dir,
+            &trust_store(&[("0xa", &signer)]),
+            &ArchiveVerifyOptions {
+                since: Some(parse_since("2025-01-01").unwrap()),
+                jobs: 0,
+            },
+        )
+        .unwrap();
+
+        assert_eq!(summary.total, 1);
+        assert_eq!(summary.valid, 1);
+        assert_eq!(summary.skipped, 1);
+        assert!(!summary.has_invalid());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_chain_continuity() {
+        let dir = temp_archive();
+        let signer = keypair();
+        let first = signed_report(&signer, "0xa", "0xblob", 100);
+        write(&dir, "first.json", &first);
+
+        let mut linked =
+            serde_json::to_value(signed_report(&signer, "0xa", "0xblob", 200)).unwrap();
+        linked[PREVIOUS_DIGEST_FIELD] = serde_json::json!(first.digest().unwrap());
+        write(&dir, "linked.json", &linked);
+
+        let mut broken =
+            serde_json::to_value(signed_report(&signer, "0xa", "0xblob", 300)).unwrap();
+        broken[PREVIOUS_DIGEST_FIELD] = serde_json::json!("00".repeat(32));
+        let broken_path = write(&dir, "broken.json", &broken);
+
+        let summary = verify_archive(
+            &dir,
+            &trust_store(&[("0xa", &signer)]),
+            &ArchiveVerifyOptions::default(),
+        )
+        .unwrap();
+
+        assert_eq!(summary.valid, 2);
+        assert_eq!(summary.invalid, 1);
+        assert_eq!(summary.failures[0].path, broken_path);
+        assert!(summary.failures[0]
+            .reason
+            .as_deref()
+            .unwrap()
+            .contains("not in the archive"));
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_parse_since() {
+        assert_eq!(parse_since("1970-01-02").unwrap(), 86_400);
+        assert_eq!(parse_since("2024-01-01T00:00:00Z").unwrap(), 1_704_067_200);
+        assert!(parse_since("yesterday").is_err());
+    }
+}
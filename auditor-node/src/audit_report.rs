@@ -4,25 +4,39 @@
 //!
 //! # 報告結構
 //!
-//! 一個完整的審計報告包含：
-//! 1. **審計數據** (`AuditData`): 包含 blob_id, content_hash, file_size 等
-//! 2. **PQC 簽名** (`Dilithium3`): 對審計數據的量子安全簽名
-//! 3. **元數據**: 審計員地址、簽名算法、時間戳等
+//! 生成器輸出規範報告模型 [`AuditReport`]：
+//! 1. **審計數據**: 由 `AuditData` 無損轉換，完整性層字段位於 `integrity`
+//! 2. **PQC 簽名** (`Dilithium3`): 與 [`ReportManager`] 相同的規範簽名字節
+//! 3. **元數據**: 審計員地址、簽名算法、生產者等
 //!
 //! # 簽名流程
 //!
 //! ```text
 //! AuditData
 //!     ↓
-//! JSON 序列化
+//! AuditReport::from（審計員地址由生成器填寫）
 //!     ↓
-//! SHA-256 哈希（可選，用於摘要）
+//! ReportManager::signing_payload（AuditReport::signing_bytes：域分隔標籤 + 規範編碼）
 //!     ↓
 //! Dilithium3 簽名
 //!     ↓
-//! SignedAuditReport
+//! AuditReport（pqc_signature）
 //! ```
 //!
+//! # 舊版格式
+//!
+//! [`SignedAuditReport`] 是舊版的報告信封（簽名只覆蓋 `AuditData` 的 JSON），已棄用。
+//! 它與 [`AuditReport`] 之間可以無損轉換，轉換後的報告保留 `legacy_envelope`，
+//! 仍可按原字節通過 [`ReportManager::verify_report`] 驗證。
+//!
+//! # 紀元摘要
+//!
+//! 每份報告各提交一條鏈上記錄成本較高。[`AuditReportGenerator::generate_epoch_summary`]
+//! 將一個紀元的報告匯總為一份簽名的 [`EpochSummary`]：記錄每份報告的 Blob ID、完整性哈希與結論，
+//! 並以報告摘要（[`AuditReport::digest`]）為葉子構建默克爾樹。之後可憑
+//! [`EpochInclusionProof`] 證明某份報告屬於該摘要，而無需公開其他報告
+//! （[`EpochSummary::verify_member`]）。
+//!
 //! # 為什麼使用 PQC 簽名？
 //!
 //! - **長期真實性保證**: 審計報告可能需要保存數年甚至數十年
@@ -36,22 +50,30 @@
 //! - **應用層**: 使用 Dilithium3 簽名審計報告本身
 //! - **雙重保護**: 鏈上記錄（當前安全）+ PQC 簽名（長期安全）
 
+use crate::commitment::{verify_reveal, ChallengeCommitment, CommitmentLog};
+use crate::crypto::ct_eq_hex;
+use crate::crypto::merkle::{hash_leaf, MerkleProof, MerkleTree, MerkleTreeVersion};
 use crate::error::{AuditorError, Result};
+use crate::history::report_digest;
+use crate::ingest::{self, IngestLimits};
 use crate::integrity::{AuditData, VerificationStatus};
+use crate::keystore::KeystoreSigner;
+use crate::report::ReportManager;
+use crate::types::{default_schema_version, is_v1_schema, AuditReport, LegacyEnvelope};
 use base64::{engine::general_purpose, Engine as _};
-use chrono::Utc;
 use pqc_signer::dilithium::Dilithium3Signer;
+use pqc_signer::falcon::Falcon512Signer;
 use pqc_signer::traits::Signer;
 use serde::{Deserialize, Serialize};
-use tracing::{debug, info};
+use tracing::info;
 
 /// PQC 算法類型
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
 pub enum PqcAlgorithm {
     /// Dilithium3 (NIST FIPS 204 Level 3)
+    #[default]
     Dilithium3,
     /// Falcon512 (NIST FIPS 205 Level 1)
-    #[allow(dead_code)]
     Falcon512,
 }
 
@@ -62,11 +84,50 @@ impl PqcAlgorithm {
             PqcAlgorithm::Falcon512 => "Falcon512",
         }
     }
+
+    /// `AuditReport::pqc_algorithm` 中的編號（1=Falcon512, 3=Dilithium3）
+    pub fn id(&self) -> u8 {
+        match self {
+            PqcAlgorithm::Falcon512 => 1,
+            PqcAlgorithm::Dilithium3 => 3,
+        }
+    }
+
+    /// 由編號解析算法
+    pub fn from_id(id: u8) -> Option<Self> {
+        match id {
+            1 => Some(PqcAlgorithm::Falcon512),
+            3 => Some(PqcAlgorithm::Dilithium3),
+            _ => None,
+        }
+    }
+
+    /// 由簽名器的 `algorithm_name()` 解析算法
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "Dilithium3" => Some(PqcAlgorithm::Dilithium3),
+            "Falcon512" => Some(PqcAlgorithm::Falcon512),
+            _ => None,
+        }
+    }
+
+    /// 由公鑰創建僅驗證的簽名器
+    ///
+    /// # 錯誤
+    /// - 公鑰長度或格式與算法不符: 返回 `PqcSignature` 錯誤
+    pub fn verifier(&self, public_key: &[u8]) -> Result<Box<dyn Signer>> {
+        Ok(match self {
+            PqcAlgorithm::Dilithium3 => Box::new(Dilithium3Signer::from_public_key_only(public_key)?),
+            PqcAlgorithm::Falcon512 => Box::new(Falcon512Signer::from_public_key_only(public_key)?),
+        })
+    }
 }
 
-/// 簽名的審計報告
+/// 簽名的審計報告（舊版格式）
 ///
-/// 包含審計數據和對應的 PQC 簽名
+/// 包含審計數據和對應的 PQC 簽名。新代碼請使用 [`AuditReport`]；
+/// 舊報告可經 `AuditReport::try_from` 無損遷移（或由 [`ReportManager::load_json`] 自動遷移）
+#[deprecated(note = "use types::AuditReport; legacy reports convert losslessly with AuditReport::try_from")]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SignedAuditReport {
     /// 審計數據
@@ -89,8 +150,16 @@ pub struct SignedAuditReport {
     /// 可選：審計員 Sui 地址
     #[serde(skip_serializing_if = "Option::is_none")]
     pub auditor_sui_address: Option<String>,
+
+    /// 報告 schema 版本（未記錄時為 1；簽名只覆蓋 `audit_data`，不受版本影響）
+    #[serde(
+        default = "default_schema_version",
+        skip_serializing_if = "is_v1_schema"
+    )]
+    pub schema_version: u32,
 }
 
+#[allow(deprecated)]
 impl SignedAuditReport {
     /// 驗證報告簽名
     ///
@@ -111,17 +180,10 @@ impl SignedAuditReport {
         let public_key_bytes = general_purpose::STANDARD.decode(&self.auditor_public_key)
             .map_err(|e| AuditorError::Serialization(format!("Failed to decode public key: {}", e)))?;
 
-        match self.algorithm {
-            PqcAlgorithm::Dilithium3 => {
-                // 使用公鑰創建驗證器（僅用於驗證，無簽名能力）
-                let verifier = Dilithium3Signer::from_public_key_only(&public_key_bytes)?;
-                verifier.verify(&audit_json, &signature_bytes)
-                    .map_err(|e| AuditorError::PqcSignature(e.to_string()))
-            }
-            PqcAlgorithm::Falcon512 => {
-                Err(AuditorError::PqcSignature("Falcon512 not yet implemented".to_string()))
-            }
-        }
+        // 使用公鑰創建驗證器（僅用於驗證，無簽名能力）
+        let verifier = self.algorithm.verifier(&public_key_bytes)?;
+        verifier.verify(&audit_json, &signature_bytes)
+            .map_err(|e| AuditorError::PqcSignature(e.to_string()))
     }
 
     /// 將報告序列化為 JSON
@@ -130,10 +192,91 @@ impl SignedAuditReport {
             .map_err(|e| AuditorError::Serialization(format!("Failed to serialize report: {}", e)))
     }
 
-    /// 從 JSON 反序列化報告
+    /// 報告摘要（緊湊 JSON 序列化的 SHA-256，十六進制）
+    ///
+    /// 去重審計通過此摘要引用被依據的完整審計報告
+    pub fn digest(&self) -> Result<String> {
+        report_digest(self)
+    }
+
+    /// 核對公開的挑戰集與承諾日誌中此前的承諾
+    ///
+    /// 報告未公開挑戰集時返回 `Commitment` 錯誤
+    pub fn verify_challenge_commitment(
+        &self,
+        commitments: &CommitmentLog,
+    ) -> Result<ChallengeCommitment> {
+        let reveal = self.audit_data.challenge_reveal.as_ref().ok_or_else(|| {
+            AuditorError::Commitment(format!(
+                "Report for blob {} does not reveal its challenge set",
+                self.audit_data.blob_id
+            ))
+        })?;
+        verify_reveal(
+            commitments,
+            &self.audit_data.blob_id,
+            reveal,
+            self.audit_data.timestamp,
+        )
+    }
+
+    /// 從 JSON 反序列化報告（經 [`ingest`](crate::ingest) 加固層解析）
     pub fn from_json(json: &str) -> Result<Self> {
-        serde_json::from_str(json)
-            .map_err(|e| AuditorError::Serialization(format!("Failed to deserialize report: {}", e)))
+        ingest::parse_signed_report(json, &IngestLimits::default())
+    }
+}
+
+#[allow(deprecated)]
+impl TryFrom<SignedAuditReport> for AuditReport {
+    type Error = AuditorError;
+
+    /// 遷移舊版報告（簽名與公鑰保留，仍按原字節驗證）
+    fn try_from(legacy: SignedAuditReport) -> Result<Self> {
+        let pqc_signature = general_purpose::STANDARD
+            .decode(&legacy.signature)
+            .map_err(|e| AuditorError::Serialization(format!("Failed to decode signature: {}", e)))?;
+
+        let mut report = AuditReport::from(legacy.audit_data);
+        report.schema_version = legacy.schema_version;
+        report.auditor = legacy.auditor_sui_address.unwrap_or_default();
+        report.pqc_signature = pqc_signature;
+        report.pqc_algorithm = legacy.algorithm.id();
+        report.legacy_envelope = Some(LegacyEnvelope {
+            auditor_public_key: legacy.auditor_public_key,
+            report_timestamp: legacy.report_timestamp,
+        });
+        Ok(report)
+    }
+}
+
+#[allow(deprecated)]
+impl TryFrom<AuditReport> for SignedAuditReport {
+    type Error = AuditorError;
+
+    /// 轉換回舊版格式（僅限遷移而來、仍帶 `legacy_envelope` 的報告）
+    fn try_from(report: AuditReport) -> Result<Self> {
+        let envelope = report.legacy_envelope.clone().ok_or_else(|| {
+            AuditorError::Serialization(format!(
+                "Report for blob {} was not migrated from a SignedAuditReport",
+                report.blob_id
+            ))
+        })?;
+        let algorithm = PqcAlgorithm::from_id(report.pqc_algorithm).ok_or_else(|| {
+            AuditorError::PqcSignature(format!(
+                "Unsupported PQC algorithm: {}",
+                report.pqc_algorithm
+            ))
+        })?;
+
+        Ok(Self {
+            audit_data: AuditData::try_from(&report)?,
+            signature: general_purpose::STANDARD.encode(&report.pqc_signature),
+            algorithm,
+            auditor_public_key: envelope.auditor_public_key,
+            report_timestamp: envelope.report_timestamp,
+            auditor_sui_address: Some(report.auditor).filter(|address| !address.is_empty()),
+            schema_version: report.schema_version,
+        })
     }
 }
 
@@ -142,26 +285,42 @@ impl SignedAuditReport {
 /// 負責整合完整性驗證和 PQC 簽名
 pub struct AuditReportGenerator {
     /// PQC 簽名器
-    signer: Dilithium3Signer,
+    signer: KeystoreSigner,
 
     /// 審計員 Sui 地址（可選）
     auditor_address: Option<String>,
+
+    /// 簽名字節達到此大小時改為流式摘要簽名（`None` 表示始終直接簽名）
+    prehash_threshold: Option<usize>,
 }
 
 impl AuditReportGenerator {
     /// 創建新的報告生成器
     ///
     /// # 參數
-    /// - `signer`: Dilithium3 簽名器（包含密鑰對）
+    /// - `signer`: Dilithium3 或 Falcon512 簽名器（包含密鑰對）
     /// - `auditor_address`: 審計員的 Sui 地址（可選）
-    pub fn new(signer: Dilithium3Signer, auditor_address: Option<String>) -> Self {
-        info!("Created AuditReportGenerator with algorithm: Dilithium3");
+    pub fn new(signer: impl Into<KeystoreSigner>, auditor_address: Option<String>) -> Self {
+        let signer = signer.into();
+        info!(
+            "Created AuditReportGenerator with algorithm: {}",
+            signer.algorithm_name()
+        );
         Self {
             signer,
             auditor_address,
+            prehash_threshold: None,
         }
     }
 
+    /// 簽名字節不少於 `threshold` 字節的報告改為簽名其摘要
+    ///
+    /// 見 [`ReportManager::sign_report_with_prehash`]；驗證方無需額外配置
+    pub fn with_prehash_threshold(mut self, threshold: usize) -> Self {
+        self.prehash_threshold = Some(threshold);
+        self
+    }
+
     /// 從密鑰庫加載生成器
     ///
     /// # 參數
@@ -188,6 +347,8 @@ impl AuditReportGenerator {
         struct KeystoreData {
             public_key: String,
             secret_key: String,
+            #[serde(default)]
+            algorithm: Option<String>,
         }
 
         let keystore: KeystoreData = serde_json::from_str(&keystore_data)
@@ -199,8 +360,14 @@ impl AuditReportGenerator {
         let secret_key_bytes = general_purpose::STANDARD.decode(&keystore.secret_key)
             .map_err(|e| AuditorError::Keystore(format!("Failed to decode secret key: {}", e)))?;
 
-        // 從字節創建簽名器
-        let signer = Dilithium3Signer::from_bytes(&public_key_bytes, &secret_key_bytes)?;
+        // 按記錄的算法創建簽名器（未記錄時為 Dilithium3）
+        let algorithm = match keystore.algorithm.as_deref() {
+            None => PqcAlgorithm::Dilithium3,
+            Some(name) => PqcAlgorithm::from_name(name).ok_or_else(|| {
+                AuditorError::Keystore(format!("Unsupported keystore algorithm: {}", name))
+            })?,
+        };
+        let signer = KeystoreSigner::from_bytes(algorithm, &public_key_bytes, &secret_key_bytes)?;
 
         Ok(Self::new(signer, auditor_address))
     }
@@ -248,17 +415,20 @@ impl AuditReportGenerator {
     /// - `audit_data`: 完整性驗證的審計數據
     ///
     /// # 返回
-    /// - `Ok(SignedAuditReport)`: 包含 PQC 簽名的完整報告
+    /// - `Ok(AuditReport)`: 以規範簽名字節簽名的報告
     ///
     /// # 示例
     /// ```no_run
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// use auditor_node::integrity::{IntegrityVerifier, AuditData};
     /// use auditor_node::audit_report::AuditReportGenerator;
+    /// use auditor_node::report::ReportManager;
+    /// use auditor_node::types::BlobId;
     ///
     /// // 1. 執行完整性審計
     /// let verifier = IntegrityVerifier::new_testnet();
-    /// let audit_data = verifier.audit_blob("blob_id_here").await?;
+    /// let blob_id = BlobId::parse("eRrTusk8yshFQpkemgDnbg0f4-qDo623V2NpeVG1Zcg")?;
+    /// let audit_data = verifier.audit_blob(&blob_id).await?;
     ///
     /// // 2. 生成簽名的報告
     /// let generator = AuditReportGenerator::from_keystore(
@@ -266,60 +436,62 @@ impl AuditReportGenerator {
     ///     Some("0x1234...".to_string())
     /// )?;
     ///
-    /// let signed_report = generator.generate_report(audit_data)?;
+    /// let report = generator.generate_report(audit_data)?;
     ///
     /// // 3. 輸出 JSON
-    /// println!("{}", signed_report.to_json()?);
+    /// println!("{}", serde_json::to_string_pretty(&report)?);
     /// # Ok(())
     /// # }
     /// ```
-    pub fn generate_report(&self, audit_data: AuditData) -> Result<SignedAuditReport> {
+    pub fn generate_report(&self, audit_data: AuditData) -> Result<AuditReport> {
         info!(
             "Generating signed audit report for blob: {}",
             audit_data.blob_id
         );
 
-        // 1. 序列化審計數據
-        let audit_json = serde_json::to_vec(&audit_data)
-            .map_err(|e| AuditorError::Serialization(format!("Failed to serialize audit data: {}", e)))?;
-
-        debug!("Audit data serialized: {} bytes", audit_json.len());
-
-        // 2. 使用 Dilithium3 簽名
-        let signature_bytes = self.signer.sign(&audit_json)?;
+        // 1. 轉換為規範報告（生產者在簽名時寫入，在簽名範圍內）
+        let mut report = AuditReport::from(audit_data);
+        report.auditor = self.auditor_address.clone().unwrap_or_default();
 
-        debug!("Signature generated: {} bytes", signature_bytes.len());
-
-        // 3. 編碼為 Base64
-        let signature_base64 = general_purpose::STANDARD.encode(&signature_bytes);
-        let public_key_base64 = general_purpose::STANDARD.encode(self.signer.public_key());
-
-        // 4. 構造簽名報告
-        let report = SignedAuditReport {
-            audit_data,
-            signature: signature_base64,
-            algorithm: PqcAlgorithm::Dilithium3,
-            auditor_public_key: public_key_base64,
-            report_timestamp: Utc::now().timestamp() as u64,
-            auditor_sui_address: self.auditor_address.clone(),
-        };
+        // 2. 使用 PQC 簽名
+        ReportManager::sign_report_with_prehash(
+            &self.signer,
+            &mut report,
+            self.prehash_threshold,
+        )?;
 
         info!(
             "Report generated successfully: {} (status: {:?})",
-            report.audit_data.blob_id, report.audit_data.verification_status
+            report.blob_id,
+            report.verification_status()
         );
 
         Ok(report)
     }
 
+    /// 簽名已生成的報告（如存儲節點挑戰審計的報告）
+    ///
+    /// 設置了審計員地址時覆蓋報告中的 `auditor`
+    pub fn sign_report(&self, mut report: AuditReport) -> Result<AuditReport> {
+        if let Some(address) = &self.auditor_address {
+            report.auditor = address.clone();
+        }
+        ReportManager::sign_report_with_prehash(
+            &self.signer,
+            &mut report,
+            self.prehash_threshold,
+        )?;
+        Ok(report)
+    }
+
     /// 生成報告並驗證簽名（自檢）
     ///
     /// 用於測試簽名流程的正確性
-    pub fn generate_and_verify(&self, audit_data: AuditData) -> Result<SignedAuditReport> {
+    pub fn generate_and_verify(&self, audit_data: AuditData) -> Result<AuditReport> {
         let report = self.generate_report(audit_data)?;
 
         // 立即驗證簽名
-        let is_valid = report.verify_signature()?;
+        let is_valid = ReportManager::verify_report(&report, self.public_key())?;
 
         if is_valid {
             info!("Self-verification passed ✓");
@@ -338,11 +510,8 @@ impl AuditReportGenerator {
     /// - `audit_data_list`: 多個審計數據
     ///
     /// # 返回
-    /// - `Vec<SignedAuditReport>`: 簽名的報告列表
-    pub fn generate_batch_reports(
-        &self,
-        audit_data_list: Vec<AuditData>,
-    ) -> Result<Vec<SignedAuditReport>> {
+    /// - `Vec<AuditReport>`: 簽名的報告列表
+    pub fn generate_batch_reports(&self, audit_data_list: Vec<AuditData>) -> Result<Vec<AuditReport>> {
         info!("Generating {} signed reports", audit_data_list.len());
 
         let mut reports = Vec::new();
@@ -357,6 +526,96 @@ impl AuditReportGenerator {
         Ok(reports)
     }
 
+    /// 將一個紀元的已簽名報告匯總為簽名的紀元摘要
+    ///
+    /// 以每份報告的摘要為葉子構建默克爾樹（V2 格式），簽名摘要的規範字節
+    /// （[`EpochSummary::signing_bytes`]），並按報告順序返回每份報告的包含證明。
+    ///
+    /// # 錯誤
+    /// - `reports` 為空: 返回 `Serialization` 錯誤
+    /// - 簽名失敗: 返回 `PqcSignature` 錯誤
+    pub fn generate_epoch_summary(
+        &self,
+        reports: &[AuditReport],
+        epoch: u32,
+    ) -> Result<(EpochSummary, Vec<EpochInclusionProof>)> {
+        info!(
+            "Generating epoch {} summary over {} report(s)",
+            epoch,
+            reports.len()
+        );
+
+        let digests = reports
+            .iter()
+            .map(|report| report.digest())
+            .collect::<Result<Vec<_>>>()?;
+        let leaf_hashes = digests
+            .iter()
+            .map(|digest| Ok(hash_leaf(&decode_digest(digest)?)))
+            .collect::<Result<Vec<_>>>()?;
+        let tree =
+            MerkleTree::from_leaf_hashes(leaf_hashes, MerkleTreeVersion::V2).map_err(|e| {
+                AuditorError::Serialization(format!("Cannot build epoch {} summary: {}", epoch, e))
+            })?;
+
+        let entries: Vec<EpochSummaryEntry> = reports
+            .iter()
+            .zip(&digests)
+            .map(|(report, digest)| EpochSummaryEntry {
+                blob_id: report.blob_id.clone(),
+                integrity_hash: hex::encode(&report.integrity_hash),
+                is_valid: report.is_valid,
+                report_digest: digest.clone(),
+            })
+            .collect();
+        let valid_reports = entries.iter().filter(|entry| entry.is_valid).count() as u32;
+
+        let mut summary = EpochSummary {
+            epoch,
+            auditor: self.auditor_address.clone().unwrap_or_default(),
+            timestamp: chrono::Utc::now().timestamp() as u64,
+            total_reports: entries.len() as u32,
+            valid_reports,
+            invalid_reports: entries.len() as u32 - valid_reports,
+            reports: entries,
+            digest_root: hex::encode(tree.root()),
+            pqc_signature: Vec::new(),
+            pqc_algorithm: self.signer.algorithm().id(),
+        };
+        summary.pqc_signature = self.signer.sign(&summary.signing_bytes())?;
+
+        let proofs = digests
+            .into_iter()
+            .enumerate()
+            .map(|(index, report_digest)| {
+                let proof = tree.generate_proof(index).map_err(|e| {
+                    AuditorError::Serialization(format!("Cannot prove report {}: {}", index, e))
+                })?;
+                Ok(EpochInclusionProof {
+                    report_digest,
+                    proof,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        info!(
+            "Epoch {} summary signed: {} valid, {} invalid, root {}",
+            epoch, summary.valid_reports, summary.invalid_reports, summary.digest_root
+        );
+
+        Ok((summary, proofs))
+    }
+
+    /// 報告簽名器（用於為盲化副本等派生報告重新簽名）
+    pub fn signer(&self) -> &KeystoreSigner {
+        &self.signer
+    }
+
+    /// 獲取公鑰
+    pub fn public_key(&self) -> &[u8] {
+        self.signer.public_key()
+    }
+
     /// 獲取公鑰（Base64 編碼）
     pub fn public_key_base64(&self) -> String {
         general_purpose::STANDARD.encode(self.signer.public_key())
@@ -378,30 +637,48 @@ pub struct ReportStatistics {
     /// 檢測到損壞的 Blob 數量
     pub corrupted_count: usize,
 
+    /// 已被所有者刪除的 Blob 數量（不計入節點故障）
+    #[serde(default)]
+    pub deleted_count: usize,
+
+    /// 存儲期已結束的 Blob 數量（不計入節點故障）
+    #[serde(default)]
+    pub expired_count: usize,
+
     /// 平均文件大小（bytes）
     pub average_file_size: u64,
 
     /// 總數據量（bytes）
     pub total_data_size: u64,
+
+    /// 不同 Blob 的數量（按 `blob_id` 計；同一鹽下的盲化 ID 同樣適用）
+    #[serde(default)]
+    pub unique_blobs: usize,
 }
 
 impl ReportStatistics {
     /// 從報告列表計算統計信息
-    pub fn from_reports(reports: &[SignedAuditReport]) -> Self {
+    pub fn from_reports(reports: &[AuditReport]) -> Self {
         let total_audits = reports.len();
         let mut accessible_count = 0;
         let mut unreachable_count = 0;
         let mut corrupted_count = 0;
+        let mut deleted_count = 0;
+        let mut expired_count = 0;
         let mut total_data_size = 0u64;
+        let mut blobs = std::collections::HashSet::new();
 
         for report in reports {
-            match report.audit_data.verification_status {
+            match report.verification_status() {
                 VerificationStatus::Accessible => accessible_count += 1,
                 VerificationStatus::Unreachable => unreachable_count += 1,
                 VerificationStatus::Corrupted => corrupted_count += 1,
+                VerificationStatus::Deleted => deleted_count += 1,
+                VerificationStatus::Expired => expired_count += 1,
             }
 
-            total_data_size += report.audit_data.file_size;
+            total_data_size += report.integrity.as_ref().map_or(0, |i| i.file_size);
+            blobs.insert(report.blob_id.as_str());
         }
 
         let average_file_size = if total_audits > 0 {
@@ -415,16 +692,185 @@ impl ReportStatistics {
             accessible_count,
             unreachable_count,
             corrupted_count,
+            deleted_count,
+            expired_count,
             average_file_size,
             total_data_size,
+            unique_blobs: blobs.len(),
+        }
+    }
+}
+
+/// 紀元摘要簽名字節的域分隔標籤（[`EpochSummary::signing_bytes`]）
+pub const EPOCH_SUMMARY_SIGNING_DOMAIN: &[u8] = b"WALRUS_AUDIT_EPOCH_SUMMARY_V1";
+
+/// 紀元摘要中的一份報告
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EpochSummaryEntry {
+    /// Blob ID
+    pub blob_id: String,
+
+    /// 報告的完整性哈希（十六進制）
+    pub integrity_hash: String,
+
+    /// 報告結論
+    pub is_valid: bool,
+
+    /// 報告摘要（[`AuditReport::digest`]，十六進制），即摘要默克爾樹的葉子數據
+    pub report_digest: String,
+}
+
+/// 一個紀元的已簽名審計摘要
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EpochSummary {
+    /// 紀元
+    pub epoch: u32,
+
+    /// 審計員 Sui 地址（生成器未設置時為空）
+    pub auditor: String,
+
+    /// 摘要生成時間（Unix 時間，秒）
+    pub timestamp: u64,
+
+    /// 匯總的報告（按生成時的順序，下標即包含證明的葉子索引）
+    pub reports: Vec<EpochSummaryEntry>,
+
+    /// 報告總數
+    pub total_reports: u32,
+
+    /// 結論為有效的報告數
+    pub valid_reports: u32,
+
+    /// 結論為無效的報告數
+    pub invalid_reports: u32,
+
+    /// 報告摘要默克爾樹的根（十六進制；V2 格式，葉子為 `hash_leaf(摘要字節)`）
+    pub digest_root: String,
+
+    /// PQC 簽名（覆蓋 [`EpochSummary::signing_bytes`]）
+    pub pqc_signature: Vec<u8>,
+
+    /// 簽名算法編號（見 [`PqcAlgorithm::id`]）
+    pub pqc_algorithm: u8,
+}
+
+/// 單份報告屬於紀元摘要的證明
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EpochInclusionProof {
+    /// 報告摘要（十六進制）
+    pub report_digest: String,
+
+    /// 摘要到 `digest_root` 的默克爾證明（`leaf_index` 為報告在摘要中的下標）
+    pub proof: MerkleProof,
+}
+
+impl EpochSummary {
+    /// 簽名的規範字節
+    ///
+    /// 以 [`EPOCH_SUMMARY_SIGNING_DOMAIN`] 開頭，依次為紀元、審計員、時間戳、每份報告
+    /// （Blob ID、完整性哈希、結論、報告摘要）、三個計數、摘要根與算法編號。
+    /// 整數為定長小端序，`bool` 為 1 字節，字符串帶 u32 長度前綴，報告列表帶 u32 元素數前綴
+    pub fn signing_bytes(&self) -> Vec<u8> {
+        fn put_str(out: &mut Vec<u8>, value: &str) {
+            out.extend_from_slice(&(value.len() as u32).to_le_bytes());
+            out.extend_from_slice(value.as_bytes());
+        }
+
+        let mut out = EPOCH_SUMMARY_SIGNING_DOMAIN.to_vec();
+        out.extend_from_slice(&self.epoch.to_le_bytes());
+        put_str(&mut out, &self.auditor);
+        out.extend_from_slice(&self.timestamp.to_le_bytes());
+        out.extend_from_slice(&(self.reports.len() as u32).to_le_bytes());
+        for entry in &self.reports {
+            put_str(&mut out, &entry.blob_id);
+            put_str(&mut out, &entry.integrity_hash);
+            out.push(u8::from(entry.is_valid));
+            put_str(&mut out, &entry.report_digest);
+        }
+        out.extend_from_slice(&self.total_reports.to_le_bytes());
+        out.extend_from_slice(&self.valid_reports.to_le_bytes());
+        out.extend_from_slice(&self.invalid_reports.to_le_bytes());
+        put_str(&mut out, &self.digest_root);
+        out.push(self.pqc_algorithm);
+        out
+    }
+
+    /// 驗證摘要簽名
+    ///
+    /// # 返回
+    /// - `Ok(true)`: 簽名有效
+    /// - `Ok(false)`: 簽名無效
+    /// - `Err(_)`: 沒有簽名、算法不受支持或公鑰格式錯誤
+    pub fn verify_signature(&self, public_key: &[u8]) -> Result<bool> {
+        if self.pqc_signature.is_empty() {
+            return Err(AuditorError::PqcSignature(format!(
+                "Epoch {} summary has no signature",
+                self.epoch
+            )));
+        }
+        let algorithm = PqcAlgorithm::from_id(self.pqc_algorithm).ok_or_else(|| {
+            AuditorError::PqcSignature(format!("Unsupported PQC algorithm: {}", self.pqc_algorithm))
+        })?;
+        algorithm
+            .verifier(public_key)?
+            .verify(&self.signing_bytes(), &self.pqc_signature)
+            .map_err(|e| AuditorError::PqcSignature(e.to_string()))
+    }
+
+    /// 驗證報告屬於本摘要
+    ///
+    /// 重新計算報告摘要，核對摘要中對應下標的條目（Blob ID、完整性哈希、結論、摘要），
+    /// 再以證明對照 `digest_root` 驗證。不驗證摘要簽名（見 [`EpochSummary::verify_member`]）
+    pub fn verify_inclusion(
+        &self,
+        report: &AuditReport,
+        proof: &EpochInclusionProof,
+    ) -> Result<bool> {
+        let digest = report.digest()?;
+        let expected = EpochSummaryEntry {
+            blob_id: report.blob_id.clone(),
+            integrity_hash: hex::encode(&report.integrity_hash),
+            is_valid: report.is_valid,
+            report_digest: digest.clone(),
+        };
+        let entry = usize::try_from(proof.proof.leaf_index)
+            .ok()
+            .and_then(|index| self.reports.get(index));
+        if !ct_eq_hex(&digest, &proof.report_digest)? || entry != Some(&expected) {
+            return Ok(false);
         }
+
+        let root: [u8; 32] = decode_digest(&self.digest_root)?;
+        Ok(proof
+            .proof
+            .verify(&decode_digest(&digest)?, &root, self.reports.len() as u64))
+    }
+
+    /// 驗證摘要簽名與報告的包含證明
+    pub fn verify_member(
+        &self,
+        public_key: &[u8],
+        report: &AuditReport,
+        proof: &EpochInclusionProof,
+    ) -> Result<bool> {
+        Ok(self.verify_signature(public_key)? && self.verify_inclusion(report, proof)?)
     }
 }
 
+/// 解碼十六進制 SHA-256 摘要
+fn decode_digest(digest: &str) -> Result<[u8; 32]> {
+    hex::decode(digest)
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| AuditorError::Serialization(format!("Invalid digest: {}", digest)))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::integrity::VerificationStatus;
+    use crate::producer::Producer;
+    use crate::types::AuditMethod;
 
     #[test]
     fn test_report_generator_creation() {
@@ -455,20 +901,56 @@ mod tests {
             timestamp: 1234567890,
             verification_status: VerificationStatus::Accessible,
             sui_object_id: None,
+            resource_decision: None,
+            capture_digest: None,
+            deduplicated_from: None,
+            producer: None,
+            chunk_filter: None,
+            challenge_reveal: None,
+            challenge_seed_source: None,
+            challenge_seed: None,
+            hash_drift: None,
+            blob_id_verified: None,
+            chunk_size: None,
+            blob_object_id: None,
+            encoding_k: None,
+            encoding_n: None,
+            start_epoch: None,
+            end_epoch: None,
+            onchain_merkle_root: None,
+            size_matches_chain: None,
+            audit_method: AuditMethod::Aggregator,
         };
 
         // 生成報告
         let report = generator.generate_report(audit_data.clone()).unwrap();
 
         // 驗證基本字段
-        assert_eq!(report.audit_data.blob_id, "test_blob_id");
-        assert_eq!(report.algorithm, PqcAlgorithm::Dilithium3);
-        assert!(!report.signature.is_empty());
-        assert!(!report.auditor_public_key.is_empty());
+        assert_eq!(report.blob_id, "test_blob_id");
+        assert_eq!(report.pqc_algorithm, PqcAlgorithm::Dilithium3.id());
+        assert!(!report.pqc_signature.is_empty());
+        assert!(report.is_valid);
+        assert_eq!(report.integrity.as_ref().unwrap().file_size, 1024);
 
         // 驗證簽名
-        let is_valid = report.verify_signature().unwrap();
+        let public_key = generator.public_key();
+        let is_valid = ReportManager::verify_report(&report, public_key).unwrap();
         assert!(is_valid, "Signature verification should pass");
+
+        // 生產者在簽名時寫入，且在簽名範圍內
+        let producer = report.producer.clone().unwrap();
+        assert_eq!(producer, Producer::current());
+        assert_eq!(producer.pqc_signer_version, pqc_signer::VERSION);
+        assert_eq!(producer.git_commit.as_deref().unwrap_or_default(), crate::producer::GIT_COMMIT);
+
+        let mut tampered = report.clone();
+        tampered.producer.as_mut().unwrap().target = "wasm32-unknown-unknown".to_string();
+        assert!(!ReportManager::verify_report(&tampered, public_key).unwrap());
+
+        // 完整性層數據同樣在簽名範圍內
+        let mut tampered = report.clone();
+        tampered.integrity.as_mut().unwrap().merkle_root = "ff".repeat(32);
+        assert!(!ReportManager::verify_report(&tampered, public_key).unwrap());
     }
 
     #[test]
@@ -489,21 +971,41 @@ mod tests {
             timestamp: 9999999,
             verification_status: VerificationStatus::Accessible,
             sui_object_id: Some("0xabc".to_string()),
+            resource_decision: None,
+            capture_digest: None,
+            deduplicated_from: None,
+            producer: None,
+            chunk_filter: None,
+            challenge_reveal: None,
+            challenge_seed_source: None,
+            challenge_seed: None,
+            hash_drift: None,
+            blob_id_verified: None,
+            chunk_size: None,
+            blob_object_id: None,
+            encoding_k: None,
+            encoding_n: None,
+            start_epoch: None,
+            end_epoch: None,
+            onchain_merkle_root: None,
+            size_matches_chain: None,
+            audit_method: AuditMethod::Aggregator,
         };
 
         let report = generator.generate_report(audit_data).unwrap();
 
         // 序列化
-        let json = report.to_json().unwrap();
+        let json = serde_json::to_string_pretty(&report).unwrap();
         assert!(json.contains("test_blob"));
-        assert!(json.contains("Dilithium3"));
+        assert!(json.contains("merkle_root"));
 
         // 反序列化
-        let deserialized = SignedAuditReport::from_json(&json).unwrap();
-        assert_eq!(deserialized.audit_data.blob_id, "test_blob");
+        let deserialized = ReportManager::from_json(&json).unwrap();
+        assert_eq!(deserialized.blob_id, "test_blob");
+        assert_eq!(deserialized.blob_object_id, "0xabc");
 
         // 驗證反序列化後的簽名
-        let is_valid = deserialized.verify_signature().unwrap();
+        let is_valid = ReportManager::verify_report(&deserialized, generator.public_key()).unwrap();
         assert!(is_valid);
     }
 
@@ -526,6 +1028,25 @@ mod tests {
                     timestamp: 1,
                     verification_status: VerificationStatus::Accessible,
                     sui_object_id: None,
+                    resource_decision: None,
+                    capture_digest: None,
+                    deduplicated_from: None,
+                    producer: None,
+                    chunk_filter: None,
+                    challenge_reveal: None,
+                    challenge_seed_source: None,
+                    challenge_seed: None,
+                    hash_drift: None,
+                    blob_id_verified: None,
+                    chunk_size: None,
+                    blob_object_id: None,
+                    encoding_k: None,
+                    encoding_n: None,
+                    start_epoch: None,
+                    end_epoch: None,
+                    onchain_merkle_root: None,
+                    size_matches_chain: None,
+                    audit_method: AuditMethod::Aggregator,
                 })
                 .unwrap(),
             generator
@@ -540,6 +1061,25 @@ mod tests {
                     timestamp: 2,
                     verification_status: VerificationStatus::Unreachable,
                     sui_object_id: None,
+                    resource_decision: None,
+                    capture_digest: None,
+                    deduplicated_from: None,
+                    producer: None,
+                    chunk_filter: None,
+                    challenge_reveal: None,
+                    challenge_seed_source: None,
+                    challenge_seed: None,
+                    hash_drift: None,
+                    blob_id_verified: None,
+                    chunk_size: None,
+                    blob_object_id: None,
+                    encoding_k: None,
+                    encoding_n: None,
+                    start_epoch: None,
+                    end_epoch: None,
+                    onchain_merkle_root: None,
+                    size_matches_chain: None,
+                    audit_method: AuditMethod::Aggregator,
                 })
                 .unwrap(),
             generator
@@ -554,6 +1094,25 @@ mod tests {
                     timestamp: 3,
                     verification_status: VerificationStatus::Corrupted,
                     sui_object_id: None,
+                    resource_decision: None,
+                    capture_digest: None,
+                    deduplicated_from: None,
+                    producer: None,
+                    chunk_filter: None,
+                    challenge_reveal: None,
+                    challenge_seed_source: None,
+                    challenge_seed: None,
+                    hash_drift: None,
+                    blob_id_verified: None,
+                    chunk_size: None,
+                    blob_object_id: None,
+                    encoding_k: None,
+                    encoding_n: None,
+                    start_epoch: None,
+                    end_epoch: None,
+                    onchain_merkle_root: None,
+                    size_matches_chain: None,
+                    audit_method: AuditMethod::Aggregator,
                 })
                 .unwrap(),
         ];
@@ -564,7 +1123,338 @@ mod tests {
         assert_eq!(stats.accessible_count, 1);
         assert_eq!(stats.unreachable_count, 1);
         assert_eq!(stats.corrupted_count, 1);
+        assert_eq!(stats.deleted_count, 0);
         assert_eq!(stats.total_data_size, 6000);
         assert_eq!(stats.average_file_size, 2000);
     }
+
+    #[test]
+    fn test_report_statistics_count_deleted_and_expired_blobs() {
+        use crate::types::EXPIRED_REASON;
+
+        let mut expired = sample_audit_data();
+        expired.verification_status = VerificationStatus::Expired;
+        let expired = AuditReport::from(expired);
+        assert!(expired.is_valid);
+        assert_eq!(expired.failure_reason.as_deref(), Some(EXPIRED_REASON));
+
+        let mut deleted = sample_audit_data();
+        deleted.verification_status = VerificationStatus::Deleted;
+        let deleted = AuditReport::from(deleted);
+        assert!(!deleted.is_valid);
+
+        // 挑戰級報告在發出挑戰前發現 Blob 已過期
+        let mut challenge_level = AuditReport::from(sample_audit_data());
+        challenge_level.integrity = None;
+        challenge_level.blob_status = Some(VerificationStatus::Expired);
+        assert_eq!(challenge_level.verification_status(), VerificationStatus::Expired);
+
+        let stats = ReportStatistics::from_reports(&[expired, deleted, challenge_level]);
+        assert_eq!(stats.total_audits, 3);
+        assert_eq!(stats.expired_count, 2);
+        assert_eq!(stats.deleted_count, 1);
+        assert_eq!(
+            stats.accessible_count + stats.unreachable_count + stats.corrupted_count,
+            0
+        );
+    }
+
+    fn sample_audit_data() -> AuditData {
+        use crate::resources::{ResourceAction, ResourceDecision};
+
+        AuditData {
+            blob_id: "legacy_blob".to_string(),
+            content_hash: "ab".repeat(32),
+            merkle_root: "cd".repeat(32),
+            total_challenges: 10,
+            successful_verifications: 9,
+            failed_verifications: 1,
+            file_size: 40_960,
+            timestamp: 1_700_000_000,
+            verification_status: VerificationStatus::Corrupted,
+            sui_object_id: Some("0xfeed".to_string()),
+            resource_decision: Some(ResourceDecision {
+                action: ResourceAction::Degraded,
+                disable_caching: true,
+                max_concurrency: Some(2),
+                disk_headroom_bytes: Some(-1),
+                memory_headroom_bytes: None,
+                reasons: vec!["low disk".to_string()],
+            }),
+            capture_digest: Some("ee".repeat(32)),
+            deduplicated_from: None,
+            producer: Some(Producer::current()),
+            chunk_filter: None,
+            challenge_reveal: None,
+            challenge_seed_source: None,
+            challenge_seed: None,
+            hash_drift: None,
+            blob_id_verified: None,
+            chunk_size: None,
+            blob_object_id: None,
+            encoding_k: None,
+            encoding_n: None,
+            start_epoch: None,
+            end_epoch: None,
+            onchain_merkle_root: None,
+            size_matches_chain: None,
+            audit_method: AuditMethod::Aggregator,
+        }
+    }
+
+    /// 按舊版生成器的方式簽名：簽名覆蓋 `AuditData` 的 JSON，公鑰內嵌
+    #[allow(deprecated)]
+    fn legacy_signed_report(signer: &Dilithium3Signer, audit_data: AuditData) -> SignedAuditReport {
+        let signature = signer.sign(&serde_json::to_vec(&audit_data).unwrap()).unwrap();
+        SignedAuditReport {
+            audit_data,
+            signature: general_purpose::STANDARD.encode(signature),
+            algorithm: PqcAlgorithm::Dilithium3,
+            auditor_public_key: general_purpose::STANDARD.encode(signer.public_key()),
+            report_timestamp: 1_700_000_123,
+            auditor_sui_address: Some("0xlegacy_auditor".to_string()),
+            schema_version: 1,
+        }
+    }
+
+    #[test]
+    fn test_audit_data_conversion_is_lossless() {
+        let data = sample_audit_data();
+        let report = AuditReport::from(data.clone());
+
+        assert_eq!(report.blob_object_id, "0xfeed");
+        assert_eq!(report.integrity_hash, vec![0xab; 32]);
+        assert!(!report.is_valid);
+        assert_eq!(report.verification_status(), VerificationStatus::Corrupted);
+
+        let back = AuditData::try_from(&report).unwrap();
+        assert_eq!(
+            serde_json::to_value(&back).unwrap(),
+            serde_json::to_value(&data).unwrap()
+        );
+
+        // 挑戰級報告沒有完整性摘要，不能轉換回 AuditData
+        let mut challenge_level = report;
+        challenge_level.integrity = None;
+        assert!(AuditData::try_from(&challenge_level).is_err());
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_legacy_signed_report_migrates_and_verifies() {
+        let mut signer = Dilithium3Signer::new();
+        signer.generate_keypair().unwrap();
+
+        let legacy = legacy_signed_report(&signer, sample_audit_data());
+        assert!(legacy.verify_signature().unwrap());
+        let fixture = legacy.to_json().unwrap();
+
+        // 經統一路徑加載並驗證
+        let report = ReportManager::from_json(&fixture).unwrap();
+        assert_eq!(report.auditor, "0xlegacy_auditor");
+        assert_eq!(report.capture_digest, legacy.audit_data.capture_digest);
+        assert!(report.legacy_envelope.is_some());
+        assert!(ReportManager::verify_report(&report, signer.public_key()).unwrap());
+
+        let mut tampered = report.clone();
+        tampered.integrity.as_mut().unwrap().file_size += 1;
+        assert!(!ReportManager::verify_report(&tampered, signer.public_key()).unwrap());
+
+        // 無損轉換回舊格式
+        let roundtrip = SignedAuditReport::try_from(report.clone()).unwrap();
+        assert_eq!(roundtrip.to_json().unwrap(), fixture);
+
+        // 重新簽名後成為規範格式
+        let manager = ReportManager::from_keypair(signer.public_key(), signer.secret_key()).unwrap();
+        let mut resigned = report;
+        manager.sign_report(&mut resigned).unwrap();
+        assert!(resigned.legacy_envelope.is_none());
+        assert!(ReportManager::verify_report(&resigned, signer.public_key()).unwrap());
+        assert!(SignedAuditReport::try_from(resigned).is_err());
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_falcon512_signed_report_verifies() {
+        let mut signer = Falcon512Signer::new();
+        signer.generate_keypair().unwrap();
+
+        let audit_data = sample_audit_data();
+        let signature = signer.sign(&serde_json::to_vec(&audit_data).unwrap()).unwrap();
+        let mut report = SignedAuditReport {
+            audit_data,
+            signature: general_purpose::STANDARD.encode(signature),
+            algorithm: PqcAlgorithm::Falcon512,
+            auditor_public_key: general_purpose::STANDARD.encode(signer.public_key()),
+            report_timestamp: 1_700_000_123,
+            auditor_sui_address: None,
+            schema_version: 1,
+        };
+        assert!(report.verify_signature().unwrap());
+
+        report.audit_data.file_size += 1;
+        assert!(!report.verify_signature().unwrap());
+
+        // Dilithium3 公鑰不能冒充 Falcon512 公鑰
+        let mut dilithium = Dilithium3Signer::new();
+        dilithium.generate_keypair().unwrap();
+        report.auditor_public_key = general_purpose::STANDARD.encode(dilithium.public_key());
+        assert!(report.verify_signature().is_err());
+    }
+
+    #[test]
+    fn test_large_reports_are_signed_over_their_digest() {
+        let mut signer = Dilithium3Signer::new();
+        signer.generate_keypair().unwrap();
+        let public_key = signer.public_key().to_vec();
+        let size = AuditReport::from(sample_audit_data()).signing_bytes().len();
+
+        // 低於閾值：直接簽名，JSON 中不出現 pqc_prehashed
+        let generator = AuditReportGenerator::new(signer.clone(), None)
+            .with_prehash_threshold(size * 2);
+        let direct = generator.generate_report(sample_audit_data()).unwrap();
+        assert!(!direct.pqc_prehashed);
+        assert!(!serde_json::to_string(&direct).unwrap().contains("pqc_prehashed"));
+        assert!(ReportManager::verify_report(&direct, &public_key).unwrap());
+
+        // 達到閾值：簽名摘要，標記經 JSON 往返保留
+        let generator = AuditReportGenerator::new(signer, None).with_prehash_threshold(size);
+        let prehashed = generator.generate_report(sample_audit_data()).unwrap();
+        assert!(prehashed.pqc_prehashed);
+        let prehashed: AuditReport =
+            serde_json::from_str(&serde_json::to_string(&prehashed).unwrap()).unwrap();
+        assert!(prehashed.pqc_prehashed);
+        assert!(ReportManager::verify_report(&prehashed, &public_key).unwrap());
+
+        let mut tampered = prehashed.clone();
+        tampered.integrity.as_mut().unwrap().file_size += 1;
+        assert!(!ReportManager::verify_report(&tampered, &public_key).unwrap());
+
+        // 改動標記不能讓任何一種簽名換一種方式通過驗證
+        for mut flipped in [direct, prehashed] {
+            flipped.pqc_prehashed = !flipped.pqc_prehashed;
+            assert!(!ReportManager::verify_report(&flipped, &public_key).unwrap());
+        }
+    }
+
+    /// `count` 份已簽名的報告（每 7 份中有一份損壞）
+    fn epoch_reports(generator: &AuditReportGenerator, count: usize) -> Vec<AuditReport> {
+        (0..count)
+            .map(|i| {
+                let corrupted = i % 7 == 3;
+                let audit_data = AuditData {
+                    blob_id: format!("blob_{}", i),
+                    content_hash: format!("{:064x}", i),
+                    merkle_root: "00".repeat(32),
+                    total_challenges: 10,
+                    successful_verifications: if corrupted { 7 } else { 10 },
+                    failed_verifications: if corrupted { 3 } else { 0 },
+                    file_size: 1024,
+                    timestamp: 1_700_000_000 + i as u64,
+                    verification_status: if corrupted {
+                        VerificationStatus::Corrupted
+                    } else {
+                        VerificationStatus::Accessible
+                    },
+                    sui_object_id: None,
+                    resource_decision: None,
+                    capture_digest: None,
+                    deduplicated_from: None,
+                    producer: None,
+                    chunk_filter: None,
+                    challenge_reveal: None,
+                    challenge_seed_source: None,
+                    challenge_seed: None,
+                    hash_drift: None,
+                    blob_id_verified: None,
+                    chunk_size: None,
+                    blob_object_id: None,
+                    encoding_k: None,
+                    encoding_n: None,
+                    start_epoch: None,
+                    end_epoch: None,
+                    onchain_merkle_root: None,
+                    size_matches_chain: None,
+                    audit_method: AuditMethod::Aggregator,
+                };
+                generator.generate_report(audit_data).unwrap()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_epoch_summary_over_1_2_and_100_reports() {
+        let mut signer = Dilithium3Signer::new();
+        signer.generate_keypair().unwrap();
+        let generator = AuditReportGenerator::new(signer, Some("0x1234".to_string()));
+        let public_key = generator.public_key().to_vec();
+
+        for count in [1, 2, 100] {
+            let reports = epoch_reports(&generator, count);
+            let (summary, proofs) = generator.generate_epoch_summary(&reports, 42).unwrap();
+
+            assert_eq!(summary.epoch, 42);
+            assert_eq!(summary.auditor, "0x1234");
+            assert_eq!(summary.pqc_algorithm, PqcAlgorithm::Dilithium3.id());
+            assert_eq!(summary.total_reports as usize, count);
+            assert_eq!(
+                summary.valid_reports as usize,
+                reports.iter().filter(|r| r.is_valid).count()
+            );
+            assert_eq!(
+                summary.valid_reports + summary.invalid_reports,
+                summary.total_reports
+            );
+            assert_eq!(proofs.len(), count);
+            assert!(summary.verify_signature(&public_key).unwrap());
+
+            for (report, proof) in reports.iter().zip(&proofs) {
+                assert!(summary.verify_member(&public_key, report, proof).unwrap());
+            }
+
+            // 證明不能用於另一份報告
+            if count > 1 {
+                assert!(!summary.verify_inclusion(&reports[1], &proofs[0]).unwrap());
+            }
+        }
+
+        // 沒有報告時無法生成摘要
+        assert!(generator.generate_epoch_summary(&[], 42).is_err());
+    }
+
+    #[test]
+    fn test_epoch_summary_detects_tampering() {
+        let mut signer = Dilithium3Signer::new();
+        signer.generate_keypair().unwrap();
+        let generator = AuditReportGenerator::new(signer, None);
+        let public_key = generator.public_key().to_vec();
+
+        let reports = epoch_reports(&generator, 5);
+        let (summary, proofs) = generator.generate_epoch_summary(&reports, 7).unwrap();
+
+        // 篡改成員報告：把損壞的報告改為有效
+        let mut tampered = reports[3].clone();
+        assert!(!tampered.is_valid);
+        tampered.is_valid = true;
+        assert!(!summary.verify_inclusion(&tampered, &proofs[3]).unwrap());
+
+        // 篡改報告的其他字段同樣改變其摘要
+        let mut tampered = reports[2].clone();
+        tampered.successful_verifications -= 1;
+        assert!(!summary
+            .verify_member(&public_key, &tampered, &proofs[2])
+            .unwrap());
+
+        // 連同摘要條目一起篡改，簽名不再有效
+        let mut forged = summary.clone();
+        forged.reports[3].is_valid = true;
+        forged.valid_reports += 1;
+        forged.invalid_reports -= 1;
+        assert!(!forged.verify_signature(&public_key).unwrap());
+
+        // 其他審計員的密鑰不能驗證摘要
+        let mut other = Dilithium3Signer::new();
+        other.generate_keypair().unwrap();
+        assert!(!summary.verify_signature(other.public_key()).unwrap());
+    }
 }
@@ -1,115 +1,659 @@
 //! 核心審計邏輯模塊
+//!
+//! 挑戰按 sliver 所在的 shard 路由到持有該 shard 的存儲節點
+//! （見 [`Auditor::with_shard_assignment`]）；shard 歸屬未知時在本次審計選中的節點間輪詢。
+//! 節點不可達只使發往該節點的挑戰失敗，其餘節點的挑戰照常執行。
+//! 啟動健康監控（[`Auditor::spawn_health_monitor`]）後，未通過健康檢查的節點不參與挑戰路由。
+//! 存儲期已結束（當前 epoch ≥ `end_epoch`）的 Blob 不發出挑戰，直接生成 `Expired` 報告。
+//!
+//! 按 `recovery_symbol_ratio` 的比例，部分挑戰只請求 sliver 的一個 recovery symbol（類型 2），
+//! 其餘請求完整 sliver（類型 1）；每個挑戰結果記錄其類型。
+//!
+//! 挑戰並發執行（至多 `max_parallel_challenges` 個），受 `audit_deadline_secs` 總時限約束；
+//! 結果按挑戰順序排列，與完成順序無關。
+//!
+//! 存儲節點對響應簽名且其公鑰已知（配置的 `storage_node_public_keys`，或
+//! [`Auditor::with_shard_assignment`] 收到的節點信息）時，驗證簽名並記入 `node_signature_valid`；
+//! 簽名無效的挑戰記為失敗。
 
 use crate::{
+    blob_id::metadata_matches_blob_id,
+    blob_lookup::{resolve_blob_ref, BlobObjectIdSource},
+    breaker::CircuitBreaker,
+    capture::HttpCapture,
+    chain_types::checked_u16,
+    challenge_seed::{ChallengeSeed, ChallengeSeedMode, CheckpointSource, SuiRpcCheckpointSource},
     crypto::{
         merkle::MerkleProof,
-        sliver::{calculate_challenge_count, Sliver, SliverMetadata},
+        node_signature::{challenge_message, NodePublicKey},
+        sliver::{
+            calculate_challenge_count, validate_erasure_params, validate_sliver_index,
+            RecoverySymbol, Sliver, SliverMetadata,
+        },
     },
     error::{AuditorError, Result},
-    storage_node_client::{ChallengeResponse, StorageNodeClient},
+    integrity::VerificationStatus,
+    metadata_cache::BlobMetadataCache,
+    metrics::Metrics,
+    node_health::NodeHealthMonitor,
+    notify::NotificationDispatcher,
+    progress::{AuditProgress, FinishedStatus, ProgressSink},
+    rate_limit::RateLimiter,
+    storage_node_client::{ApiStyle, ChallengeResponse, ChallengeTiming, StorageNodeClient},
     sui_client::AuditSystemClient,
-    types::{AuditChallenge, AuditReport, AuditorConfig, BlobMetadata, ChallengeResult},
+    types::{
+        AuditChallenge, AuditMethod, AuditReport, AuditorConfig, BlobId, BlobMetadata, BlobRef,
+        ChallengeResult, ResponseStats, StorageNodeInfo, CHALLENGE_FULL_SLIVER,
+        CHALLENGE_RECOVERY_SYMBOL, EXPIRED_REASON, REPORT_SCHEMA_VERSION,
+    },
 };
+use base64::{engine::general_purpose, Engine as _};
 use chrono::Utc;
+use futures::stream::{FuturesUnordered, StreamExt};
+use rand::seq::{index, SliceRandom};
 use rand::Rng;
-use std::time::Instant;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::{mpsc, OnceCell};
+use tokio::task::JoinHandle;
 use tracing::{debug, error, info, warn};
 
+/// Sui 連接配置缺失時的錯誤信息
+const SUI_NOT_CONFIGURED: &str = "not configured";
+
+/// 節點簽名無效的挑戰的失敗原因
+const INVALID_NODE_SIGNATURE: &str = "Invalid storage node signature";
+
+/// 未得到節點響應的挑戰結果（`timing` 為失敗請求的統計；未發出請求時為默認值）
+fn failed_result(
+    challenge: &AuditChallenge,
+    storage_client: &StorageNodeClient,
+    reason: String,
+    timing: ChallengeTiming,
+) -> ChallengeResult {
+    ChallengeResult {
+        challenge: challenge.clone(),
+        verified: false,
+        merkle_proof_valid: false,
+        response_hash: vec![],
+        failure_reason: Some(reason),
+        node_url: Some(storage_client.base_url().to_string()),
+        node_signature_valid: None,
+        response_time_ms: timing.elapsed.as_millis() as u64,
+        sliver_size_bytes: 0,
+        attempts: timing.attempts,
+    }
+}
+
+/// 發出挑戰完成事件（`index` 為 sliver 索引）
+fn emit_challenge_completed(events: &ProgressSink, result: Option<&ChallengeResult>) {
+    if let Some(result) = result {
+        events.emit(AuditProgress::ChallengeCompleted {
+            index: result.challenge.sliver_index,
+            verified: result.verified,
+        });
+    }
+}
+
+/// 存儲節點審計報告的進度結論
+fn report_status(report: &AuditReport) -> FinishedStatus {
+    FinishedStatus::Report {
+        is_valid: report.is_valid,
+    }
+}
+
+/// 兩個存儲節點 URL 是否指向同一端點（忽略末尾的 `/`）
+fn same_endpoint(a: &str, b: &str) -> bool {
+    a.trim_end_matches('/') == b.trim_end_matches('/')
+}
+
+/// Blob 的存儲期在 `current_epoch` 是否已結束
+///
+/// Walrus 存儲資源覆蓋 `[start_epoch, end_epoch)`，到達 `end_epoch` 時即已過期
+pub fn is_past_end_epoch(end_epoch: u32, current_epoch: u32) -> bool {
+    current_epoch >= end_epoch
+}
+
+/// sliver 所在的 shard
+///
+/// 與 Walrus 一致，按 blob ID 輪轉：`(sliver_index + blob_id mod n_shards) mod n_shards`，
+/// blob ID 按大端整數取模；無法解碼的 blob ID 不輪轉
+pub fn shard_for_sliver(blob_id: &str, sliver_index: u64, n_shards: u64) -> u64 {
+    if n_shards == 0 {
+        return 0;
+    }
+    let offset = general_purpose::URL_SAFE_NO_PAD
+        .decode(blob_id.trim_end_matches('='))
+        .map(|bytes| {
+            bytes.iter().fold(0u64, |acc, &byte| {
+                ((u128::from(acc) * 256 + u128::from(byte)) % u128::from(n_shards)) as u64
+            })
+        })
+        .unwrap_or(0);
+    ((u128::from(sliver_index) + u128::from(offset)) % u128::from(n_shards)) as u64
+}
+
+/// 按 shard 分層抽取 `budget` 個不同的 sliver 索引
+///
+/// `slivers` 為 `(sliver_index, shard)` 對。每個 shard 先各分得 `budget / shard 數` 個名額
+/// （超出其 sliver 數的部分分給其他 shard），之後才把餘下名額隨機分給尚有 sliver 的 shard；
+/// shard 內挑選哪些 sliver 亦隨機決定。返回 `min(budget, slivers.len())` 個索引，順序隨機
+pub fn stratified_sample(slivers: &[(u64, u64)], budget: usize, rng: &mut impl Rng) -> Vec<u64> {
+    let mut by_shard: BTreeMap<u64, Vec<u64>> = BTreeMap::new();
+    for &(index, shard) in slivers {
+        by_shard.entry(shard).or_default().push(index);
+    }
+    let mut strata: Vec<(Vec<u64>, usize)> =
+        by_shard.into_values().map(|indices| (indices, 0)).collect();
+
+    let mut remaining = budget.min(slivers.len());
+    while remaining > 0 {
+        let open: Vec<usize> = (0..strata.len())
+            .filter(|&i| strata[i].1 < strata[i].0.len())
+            .collect();
+        let share = remaining / open.len();
+        if share == 0 {
+            for &i in open.choose_multiple(rng, remaining) {
+                strata[i].1 += 1;
+            }
+            break;
+        }
+        for i in open {
+            let (indices, taken) = &mut strata[i];
+            let take = share.min(indices.len() - *taken);
+            *taken += take;
+            remaining -= take;
+        }
+    }
+
+    let mut selected = Vec::with_capacity(budget.min(slivers.len()));
+    for (mut indices, taken) in strata {
+        selected.extend_from_slice(indices.partial_shuffle(rng, taken).0);
+    }
+    selected.shuffle(rng);
+    selected
+}
+
+/// 每個被挑戰 shard 的挑戰數 `(shard_id, count)`，按 shard_id 升序
+pub fn shard_coverage(results: &[ChallengeResult]) -> Vec<(u16, u16)> {
+    let mut coverage: BTreeMap<u16, u16> = BTreeMap::new();
+    for result in results {
+        let count = coverage.entry(result.challenge.shard_id).or_default();
+        *count = count.saturating_add(1);
+    }
+    coverage.into_iter().collect()
+}
+
 pub struct Auditor {
-    sui_client: AuditSystemClient,
+    /// Sui 客戶端（首次需要鏈上數據時才連接）
+    sui_client: OnceCell<AuditSystemClient>,
+    /// 延遲連接的 Sui 客戶端使用的 Blob 元數據緩存
+    metadata_cache: Option<Arc<BlobMetadataCache>>,
     storage_clients: Vec<StorageNodeClient>,
+    /// shard → `storage_clients` 中持有該 shard 的節點
+    shard_owners: HashMap<u16, usize>,
+    /// `storage_clients` 中節點的公鑰（用於驗證響應簽名）
+    node_keys: HashMap<usize, NodePublicKey>,
+    /// 存儲節點健康狀態（節點索引與 `storage_clients` 一致）
+    health: Option<Arc<NodeHealthMonitor>>,
+    /// 挑戰種子的檢查點來源（`challenge_seed_mode = "sui_checkpoint"` 時）
+    checkpoint_source: Option<Arc<dyn CheckpointSource>>,
     config: AuditorConfig,
     auditor_address: String,
 }
 
 impl Auditor {
-    pub async fn new(
+    /// 創建審計器
+    ///
+    /// 不連接 Sui：本地審計、驗證、試運行等離線流程無需網絡。
+    /// 首次調用需要鏈上數據的方法時，按配置中的合約 ID 連接；
+    /// 合約 ID 未配置時這些方法返回 `AuditorError::SuiClient("not configured")`。
+    /// 所有存儲節點客戶端共享一個按 `config.breaker` 創建的熔斷器，
+    /// 一個按 `config.max_requests_per_sec_per_host` 創建的限流器，
+    /// 並使用 `config.storage_node_api_style` 指定的 API 風格。
+    /// `config.storage_node_public_keys` 中的節點公鑰按 URL 對應到存儲節點。
+    /// `config.node_health.enabled` 時創建健康監控，但不在此啟動。
+    /// `config.challenge_seed_mode` 為 `sui_checkpoint` 時，挑戰由 `config.sui_rpc_url`
+    /// 上最新檢查點導出的種子確定（見 [`crate::challenge_seed`]）。
+    /// `config.blob_metadata_cache_ttl_secs` 大於 0 時，鏈上 Blob 元數據按該 TTL 緩存。
+    /// 存儲節點客戶端按 `config.http` 創建，無法創建時返回 `AuditorError::HttpRequest`
+    pub fn new(
         config: AuditorConfig,
         auditor_address: String,
         storage_node_urls: Vec<String>,
     ) -> Result<Self> {
         info!("Initializing Auditor for address: {}", auditor_address);
 
-        // 驗證合約 ID 已配置
-        let audit_package_id = config.audit_system_package_id
-            .as_ref()
-            .ok_or_else(|| AuditorError::Config(
-                "AUDIT_SYSTEM_PACKAGE_ID not configured. Please deploy contracts first.".to_string()
-            ))?;
-
-        let access_policy_id = config.access_policy_package_id
-            .as_ref()
-            .ok_or_else(|| AuditorError::Config(
-                "ACCESS_POLICY_PACKAGE_ID not configured. Please deploy contracts first.".to_string()
-            ))?;
-
-        let registry_id = config.auditor_registry_id
-            .as_ref()
-            .ok_or_else(|| AuditorError::Config(
-                "AUDITOR_REGISTRY_ID not configured. Please deploy contracts first.".to_string()
-            ))?;
-
-        let incentives_obj_id = config.incentives_id
-            .as_ref()
-            .ok_or_else(|| AuditorError::Config(
-                "INCENTIVES_ID not configured. Please deploy contracts first.".to_string()
-            ))?;
-
-        // 檢查是否為 placeholder (0x000...)
-        if audit_package_id.starts_with("0x0000000000000000000000000000") {
-            warn!("⚠️  AUDIT_SYSTEM_PACKAGE_ID is still a placeholder!");
-            warn!("⚠️  Please deploy contracts and update .env file");
-            warn!("⚠️  Some functions may return mock data");
-        }
-
-        let sui_client = AuditSystemClient::new(
-            &config.sui_rpc_url,
-            audit_package_id,
-            access_policy_id,
-            registry_id,
-            incentives_obj_id,
-        ).await?;
-
+        let breaker = Arc::new(CircuitBreaker::new(config.breaker.clone()));
+        let rate_limiter = Arc::new(RateLimiter::from_config(&config));
         let storage_clients: Vec<StorageNodeClient> = storage_node_urls
             .iter()
             .map(|url| {
-                StorageNodeClient::with_config(url.clone(), config.http_timeout_secs, 3)
+                Ok(StorageNodeClient::from_http_config(
+                    url.clone(),
+                    &config.http,
+                    config.http_timeout_secs,
+                    3,
+                )?
+                .with_max_error_body_len(config.max_error_body_len)
+                .with_retry_budget(Duration::from_secs(config.storage_retry_budget_secs))
+                .with_breaker(Arc::clone(&breaker))
+                .with_rate_limiter(Arc::clone(&rate_limiter))
+                .with_api_style(config.storage_node_api_style))
             })
-            .collect();
+            .collect::<Result<_>>()?;
 
         info!("Created {} storage node client(s)", storage_clients.len());
 
+        let mut node_keys = HashMap::new();
+        for (url, key) in &config.storage_node_public_keys {
+            let Some(node) = storage_clients
+                .iter()
+                .position(|client| same_endpoint(client.base_url(), url))
+            else {
+                warn!(
+                    "Storage node {} is not configured, ignoring its public key",
+                    url
+                );
+                continue;
+            };
+            match NodePublicKey::parse(key) {
+                Ok(key) => {
+                    node_keys.insert(node, key);
+                }
+                Err(e) => warn!("Ignoring public key for storage node {}: {}", url, e),
+            }
+        }
+
+        let health = (config.node_health.enabled && !storage_node_urls.is_empty()).then(|| {
+            Arc::new(NodeHealthMonitor::new(
+                config.node_health.clone(),
+                &storage_node_urls,
+                config.http_timeout_secs,
+            ))
+        });
+
+        let checkpoint_source = (config.challenge_seed_mode == ChallengeSeedMode::SuiCheckpoint)
+            .then(|| {
+                Arc::new(SuiRpcCheckpointSource::new(config.sui_rpc_url.clone()))
+                    as Arc<dyn CheckpointSource>
+            });
+
+        let metadata_cache = (config.blob_metadata_cache_ttl_secs > 0).then(|| {
+            Arc::new(BlobMetadataCache::new(Duration::from_secs(
+                config.blob_metadata_cache_ttl_secs,
+            )))
+        });
+
         Ok(Self {
-            sui_client,
+            sui_client: OnceCell::new(),
+            metadata_cache,
             storage_clients,
+            shard_owners: HashMap::new(),
+            node_keys,
+            health,
+            checkpoint_source,
             config,
             auditor_address,
         })
     }
 
-    pub async fn audit_blob(&self, blob_id: &str) -> Result<AuditReport> {
+    /// 使用已連接的 Sui 客戶端（不再按配置延遲連接）
+    pub fn with_sui(self, sui_client: AuditSystemClient) -> Self {
+        Self {
+            sui_client: OnceCell::new_with(Some(sui_client)),
+            ..self
+        }
+    }
+
+    /// 使用外部共享的 Blob 元數據緩存（如守護進程預取時填充的緩存）
+    ///
+    /// 只影響按配置延遲連接的 Sui 客戶端；[`with_sui`](Self::with_sui) 傳入的客戶端
+    /// 需自行調用 [`AuditSystemClient::set_metadata_cache`]
+    pub fn with_metadata_cache(self, cache: Arc<BlobMetadataCache>) -> Self {
+        Self {
+            metadata_cache: Some(cache),
+            ..self
+        }
+    }
+
+    /// 以指定來源的 Sui 檢查點導出挑戰種子（無論 `config.challenge_seed_mode`）
+    pub fn with_checkpoint_source(self, source: Arc<dyn CheckpointSource>) -> Self {
+        Self {
+            checkpoint_source: Some(source),
+            ..self
+        }
+    }
+
+    /// 使用外部共享的熔斷器（如守護進程級別的熔斷器）
+    pub fn with_breaker(self, breaker: Arc<CircuitBreaker>) -> Self {
+        Self {
+            storage_clients: self
+                .storage_clients
+                .into_iter()
+                .map(|client| client.with_breaker(Arc::clone(&breaker)))
+                .collect(),
+            ..self
+        }
+    }
+
+    /// 使用外部共享的限流器（如與 Aggregator 驗證器共用）
+    pub fn with_rate_limiter(self, rate_limiter: Arc<RateLimiter>) -> Self {
+        Self {
+            storage_clients: self
+                .storage_clients
+                .into_iter()
+                .map(|client| client.with_rate_limiter(Arc::clone(&rate_limiter)))
+                .collect(),
+            ..self
+        }
+    }
+
+    /// 記錄指標（所有存儲節點客戶端與健康監控共享）
+    ///
+    /// 須在 [`spawn_health_monitor`](Self::spawn_health_monitor) 之前調用，
+    /// 否則健康狀態不會導出
+    pub fn with_metrics(self, metrics: Arc<Metrics>) -> Self {
+        Self {
+            storage_clients: self
+                .storage_clients
+                .into_iter()
+                .map(|client| client.with_metrics(Arc::clone(&metrics)))
+                .collect(),
+            health: self.health.map(|monitor| match Arc::try_unwrap(monitor) {
+                Ok(monitor) => Arc::new(monitor.with_metrics(Arc::clone(&metrics))),
+                Err(shared) => shared,
+            }),
+            ..self
+        }
+    }
+
+    /// 存儲節點被健康監控排除時發出通知
+    ///
+    /// 與 [`with_metrics`](Self::with_metrics) 相同，須在啟動健康監控之前調用
+    pub fn with_notifier(self, notifier: NotificationDispatcher) -> Self {
+        Self {
+            health: self.health.map(|monitor| match Arc::try_unwrap(monitor) {
+                Ok(monitor) => Arc::new(monitor.with_notifier(notifier)),
+                Err(shared) => shared,
+            }),
+            ..self
+        }
+    }
+
+    /// 在後台啟動存儲節點健康監控（見 [`crate::node_health`]），未啟用時返回 `None`
+    ///
+    /// 守護進程啟動時調用；未啟動時所有節點都視為健康
+    pub fn spawn_health_monitor(&self) -> Option<JoinHandle<()>> {
+        self.health.as_ref().map(|monitor| monitor.spawn())
+    }
+
+    /// 健康監控（`node_health.enabled` 且配置了存儲節點時）
+    pub fn health_monitor(&self) -> Option<&Arc<NodeHealthMonitor>> {
+        self.health.as_ref()
+    }
+
+    /// 為單個存儲節點設置 API 風格（覆蓋 `config.storage_node_api_style`）
+    ///
+    /// 以 URL 匹配已配置的存儲節點；未匹配時不做任何修改
+    pub fn with_node_api_style(self, url: &str, api_style: ApiStyle) -> Self {
+        let url = url.trim_end_matches('/');
+        Self {
+            storage_clients: self
+                .storage_clients
+                .into_iter()
+                .map(|client| {
+                    if client.base_url().trim_end_matches('/') == url {
+                        client.with_api_style(api_style)
+                    } else {
+                        client
+                    }
+                })
+                .collect(),
+            ..self
+        }
+    }
+
+    /// 按存儲節點的 shard 分配路由挑戰
+    ///
+    /// 以 `api_endpoint` 匹配已配置的存儲節點 URL；未匹配的節點被忽略。
+    /// 節點信息帶有公鑰且配置中沒有該節點的公鑰時，用它驗證響應簽名
+    pub fn with_shard_assignment(mut self, nodes: &[StorageNodeInfo]) -> Self {
+        for node in nodes {
+            let endpoint = node.api_endpoint.trim_end_matches('/');
+            let Some(owner) = self
+                .storage_clients
+                .iter()
+                .position(|client| same_endpoint(client.base_url(), endpoint))
+            else {
+                warn!("Storage node {} is not configured, ignoring its shards", endpoint);
+                continue;
+            };
+            for &shard in &node.shards {
+                self.shard_owners.insert(shard, owner);
+            }
+            if !node.public_key.is_empty() && !self.node_keys.contains_key(&owner) {
+                match NodePublicKey::from_bytes(&node.public_key) {
+                    Ok(key) => {
+                        self.node_keys.insert(owner, key);
+                    }
+                    Err(e) => warn!("Ignoring public key of storage node {}: {}", endpoint, e),
+                }
+            }
+        }
+        self
+    }
+
+    /// 是否可以訪問鏈上數據（已有客戶端，或配置了全部合約 ID）
+    pub fn has_sui(&self) -> bool {
+        self.sui_client.initialized() || self.sui_object_ids().is_some()
+    }
+
+    /// 獲取 Sui 客戶端，首次調用時按配置連接
+    async fn sui_client(&self) -> Result<&AuditSystemClient> {
+        self.sui_client
+            .get_or_try_init(|| async {
+                let (audit_package_id, access_policy_id, registry_id, incentives_obj_id) = self
+                    .sui_object_ids()
+                    .ok_or_else(|| AuditorError::SuiClient(SUI_NOT_CONFIGURED.to_string()))?;
+
+                // 檢查是否為 placeholder (0x000...)
+                if audit_package_id.starts_with("0x0000000000000000000000000000") {
+                    warn!("⚠️  AUDIT_SYSTEM_PACKAGE_ID is still a placeholder!");
+                    warn!("⚠️  Please deploy contracts and update .env file");
+                    warn!("⚠️  Some functions may return mock data");
+                }
+
+                let mut client = AuditSystemClient::new(
+                    &self.config.sui_rpc_url,
+                    audit_package_id,
+                    access_policy_id,
+                    registry_id,
+                    incentives_obj_id,
+                )
+                .await?;
+                if let Some(cache) = &self.metadata_cache {
+                    client.set_metadata_cache(Arc::clone(cache));
+                }
+                Ok(client)
+            })
+            .await
+    }
+
+    /// 連接 Sui 所需的合約 ID（任一缺失時返回 `None`）
+    fn sui_object_ids(&self) -> Option<(&str, &str, &str, &str)> {
+        Some((
+            self.config.audit_system_package_id.as_deref()?,
+            self.config.access_policy_package_id.as_deref()?,
+            self.config.auditor_registry_id.as_deref()?,
+            self.config.incentives_id.as_deref()?,
+        ))
+    }
+
+    /// 審計 Walrus Blob
+    ///
+    /// 挑戰需要 Blob 對象的鏈上元數據：`BlobRef::ObjectId` 直接讀取對象，報告中的 `blob_id`
+    /// 取自對象元數據；`BlobRef::BlobId` 先按審計記錄查找 Blob 對象（見
+    /// [`resolve_blob_ref`]），找不到時返回 `BlobResolution` 錯誤
+    pub async fn audit_blob(&self, blob: &BlobRef) -> Result<AuditReport> {
+        self.audit_blob_observed(blob, &ProgressSink::default())
+            .await
+    }
+
+    /// 審計 Walrus Blob 對象，並向 `progress` 發出進度事件（見 [`crate::progress`]）
+    ///
+    /// 每個挑戰完成時發出 `ChallengeCompleted`，最後發出 `Finished`；
+    /// 事件以 `try_send` 發出，接收端處理不及或已丟棄時審計照常進行
+    pub async fn audit_blob_with_progress(
+        &self,
+        blob: &BlobRef,
+        progress: mpsc::Sender<AuditProgress>,
+    ) -> Result<AuditReport> {
+        self.audit_blob_observed(blob, &ProgressSink::new(progress))
+            .await
+    }
+
+    async fn audit_blob_observed(
+        &self,
+        blob: &BlobRef,
+        events: &ProgressSink,
+    ) -> Result<AuditReport> {
+        let result = self.audit_blob_object(blob, events).await;
+        events.finish(&result, report_status);
+        result
+    }
+
+    async fn audit_blob_object(
+        &self,
+        blob: &BlobRef,
+        events: &ProgressSink,
+    ) -> Result<AuditReport> {
         let start_time = Instant::now();
         info!("========================================");
-        info!("Starting audit for blob: {}", blob_id);
+        info!("Starting audit for {}", blob);
         info!("========================================");
 
-        let metadata = self.fetch_blob_metadata(blob_id).await?;
+        let metadata = self.fetch_blob_metadata(blob).await?;
+        let blob_id = BlobId::parse(&metadata.blob_id)?.to_string();
+        let blob_id = blob_id.as_str();
         info!(
             "Blob metadata: size={} bytes, k={}, n={}, epochs={}-{}",
             metadata.blob_size, metadata.encoding_k, metadata.encoding_n,
             metadata.start_epoch, metadata.end_epoch
         );
 
-        let challenge_count = self.determine_challenge_count(&metadata);
-        let challenges = self.generate_challenges(&metadata, challenge_count);
-        info!("Generated {} challenges", challenges.len());
+        // 存儲期已結束的 Blob 不再由存儲節點保存，挑戰失敗不應計為節點故障
+        match self.sui_client().await?.current_epoch().await {
+            Ok(current_epoch) if is_past_end_epoch(metadata.end_epoch, current_epoch) => {
+                info!(
+                    "Blob {} expired at epoch {} (current epoch {}); skipping challenges",
+                    blob_id, metadata.end_epoch, current_epoch
+                );
+                return self.expired_report(blob_id, &metadata, current_epoch);
+            }
+            Ok(_) => {}
+            Err(e) => warn!(
+                "Failed to query current epoch, auditing blob {} anyway: {}",
+                blob_id, e
+            ),
+        }
+
+        self.challenge_blob(&metadata, start_time, events).await
+    }
+
+    /// 按已知的 Blob 元數據審計（不查詢 Sui）
+    ///
+    /// 鏈上 Blob 對象不記錄默克爾根（見 [`BlobMetadata::merkle_root`]），已從其他來源
+    /// 取得完整元數據時（例如上傳時記錄的 Blob 元數據）直接發出挑戰。不檢查存儲期
+    ///
+    /// # 錯誤
+    /// - `blob_id` 無效或編碼參數不合法: 返回相應錯誤
+    pub async fn audit_blob_with_metadata(&self, metadata: &BlobMetadata) -> Result<AuditReport> {
+        info!("========================================");
+        info!("Starting audit for blob: {}", metadata.blob_id);
+        info!("========================================");
+
+        self.challenge_blob(metadata, Instant::now(), &ProgressSink::default())
+            .await
+    }
+
+    /// 按已知的 Blob 元數據審計，並向 `progress` 發出進度事件
+    ///
+    /// 見 [`Auditor::audit_blob_with_metadata`] 與 [`Auditor::audit_blob_with_progress`]
+    pub async fn audit_blob_with_metadata_and_progress(
+        &self,
+        metadata: &BlobMetadata,
+        progress: mpsc::Sender<AuditProgress>,
+    ) -> Result<AuditReport> {
+        info!("========================================");
+        info!("Starting audit for blob: {}", metadata.blob_id);
+        info!("========================================");
+
+        let events = ProgressSink::new(progress);
+        let result = self.challenge_blob(metadata, Instant::now(), &events).await;
+        events.finish(&result, report_status);
+        result
+    }
+
+    /// 生成並執行挑戰，返回報告（`start_time` 為審計開始時間，僅用於日誌）
+    async fn challenge_blob(
+        &self,
+        metadata: &BlobMetadata,
+        start_time: Instant,
+        events: &ProgressSink,
+    ) -> Result<AuditReport> {
+        let blob_id = BlobId::parse(&metadata.blob_id)?.to_string();
+        let blob_id = blob_id.as_str();
+
+        validate_erasure_params(metadata.encoding_k, metadata.encoding_n)?;
+        if self.config.verify_blob_id {
+            self.check_metadata_root(metadata)?;
+        }
+        let nodes = self.select_nodes()?;
+        let challenge_count = self.determine_challenge_count(metadata);
+        let seed = match &self.checkpoint_source {
+            Some(source) => {
+                Some(ChallengeSeed::resolve(source.as_ref(), blob_id, &self.auditor_address).await)
+            }
+            None => None,
+        };
+        let challenges = match &seed {
+            Some(seed) => {
+                self.generate_challenges_with(metadata, challenge_count, &nodes, &mut seed.rng())
+            }
+            None => self.generate_challenges(metadata, challenge_count, &nodes),
+        };
+        let routes = self.route_challenges(&challenges, &nodes);
+        info!("Generated {} challenges across {} storage node(s)", challenges.len(), nodes.len());
+
+        let capture = if self.config.capture_http {
+            Some(HttpCapture::for_audit(&self.config.capture_dir, blob_id)?)
+        } else {
+            None
+        };
 
-        let challenge_results = self.execute_challenges(&metadata, &challenges).await?;
+        let (challenge_results, unreachable) = self
+            .execute_challenges(metadata, &challenges, &routes, capture.as_ref(), events)
+            .await?;
 
-        let (successful, failed) = self.count_results(&challenge_results);
+        let (successful, failed) = self.count_results(&challenge_results)?;
         info!("Challenges completed: {} successful, {} failed", successful, failed);
 
-        let report = self.generate_report(blob_id, &metadata, challenge_results, successful, failed)?;
+        let mut report = self.generate_report(blob_id, metadata, challenge_results, successful, failed)?;
+        report.challenge_seed_source = seed.as_ref().map(|seed| seed.source.clone());
+        report.challenge_seed = seed.as_ref().map(ChallengeSeed::seed_hex);
+        if !unreachable.is_empty() {
+            let nodes = unreachable.into_iter().collect::<Vec<_>>().join(", ");
+            warn!("Unreachable storage node(s): {}", nodes);
+            report.failure_reason = report
+                .failure_reason
+                .map(|reason| format!("{}; unreachable storage nodes: {}", reason, nodes));
+        }
+
+        if let Some(capture) = capture {
+            report.capture_digest = Some(capture.finish()?);
+        }
 
         let duration = start_time.elapsed();
         info!("========================================");
@@ -120,61 +664,261 @@ impl Auditor {
         Ok(report)
     }
 
-    async fn fetch_blob_metadata(&self, blob_id: &str) -> Result<BlobMetadata> {
-        debug!("Fetching metadata for blob: {}", blob_id);
+    /// 確認元數據的默克爾根推導出被審計的 `blob_id`
+    ///
+    /// 挑戰證明按此根驗證；根不屬於該 Blob 時所有節點都會被誤判，因此在發出挑戰前拒絕
+    ///
+    /// # 錯誤
+    /// 推導出的 `blob_id` 不一致時返回 `InvalidBlobId`
+    fn check_metadata_root(&self, metadata: &BlobMetadata) -> Result<()> {
+        match metadata_matches_blob_id(metadata)? {
+            Some(true) => {
+                info!("Merkle root of {} derives its blob ID", metadata.blob_id);
+                Ok(())
+            }
+            Some(false) => Err(AuditorError::InvalidBlobId(format!(
+                "{}: metadata merkle root does not derive this blob ID, refusing to challenge \
+                 against it",
+                metadata.blob_id
+            ))),
+            None => {
+                warn!(
+                    "Blob ID verification skipped for {}: metadata carries no merkle root",
+                    metadata.blob_id
+                );
+                Ok(())
+            }
+        }
+    }
+
+    async fn fetch_blob_metadata(&self, blob: &BlobRef) -> Result<BlobMetadata> {
+        debug!("Fetching metadata for {}", blob);
         let start = Instant::now();
-        let metadata = self.sui_client.get_blob_metadata(blob_id).await?;
-        
-        
+        let client = self.sui_client().await?;
+        let object_ids: &dyn BlobObjectIdSource = client;
+        let resolved = resolve_blob_ref(blob, client, Some(object_ids)).await?;
+        let Some(metadata) = resolved.metadata else {
+            return Err(AuditorError::BlobResolution {
+                step: format!("look up the Sui object of blob {}", resolved.blob_id),
+                reason: "no audit record on chain references it; audit it by blob object ID"
+                    .to_string(),
+            });
+        };
         debug!("Metadata fetched in {:?}", start.elapsed());
         Ok(metadata)
     }
 
     fn determine_challenge_count(&self, metadata: &BlobMetadata) -> u16 {
-        let total_slivers = metadata.encoding_n as u64;
-        let recommended = calculate_challenge_count(total_slivers, 0.95, 0.1);
+        let recommended = calculate_challenge_count(metadata.encoding_n, 0.95, 0.1);
+        // max_challenges 為 u16，夾緊後的值必定能表示
         let count = recommended
-            .max(self.config.min_challenges as u64)
-            .min(self.config.max_challenges as u64) as u16;
+            .max(u64::from(self.config.min_challenges))
+            .min(u64::from(self.config.max_challenges)) as u16;
         debug!("Challenge count: recommended={}, clamped={}", recommended, count);
         count
     }
 
-    fn generate_challenges(&self, metadata: &BlobMetadata, count: u16) -> Vec<AuditChallenge> {
-        let mut rng = rand::thread_rng();
-        let mut challenges = Vec::with_capacity(count as usize);
+    /// 本次審計挑戰的存儲節點（`storage_nodes_per_audit` 設置時隨機挑選，按索引排序）
+    ///
+    /// 只從健康的節點中挑選；所有節點都未通過健康檢查時返回 `StorageNodeUnreachable`
+    fn select_nodes(&self) -> Result<Vec<usize>> {
+        let total = self.storage_clients.len();
+        if total == 0 {
+            return Err(AuditorError::Config("No storage clients configured".to_string()));
+        }
+
+        let healthy = match &self.health {
+            Some(monitor) => monitor.healthy_nodes(),
+            None => (0..total).collect(),
+        };
+        if healthy.is_empty() {
+            return Err(AuditorError::StorageNodeUnreachable(format!(
+                "all {} storage node(s) are failing health checks",
+                total
+            )));
+        }
+        if healthy.len() < total {
+            info!("Skipping {} unhealthy storage node(s)", total - healthy.len());
+        }
+
+        let mut nodes = match self.config.storage_nodes_per_audit {
+            Some(subset) if subset < healthy.len() => {
+                index::sample(&mut rand::thread_rng(), healthy.len(), subset)
+                    .into_iter()
+                    .map(|i| healthy[i])
+                    .collect()
+            }
+            _ => healthy,
+        };
+        nodes.sort_unstable();
+        Ok(nodes)
+    }
+
+    /// 隨機生成挑戰（見 [`generate_challenges_with`](Self::generate_challenges_with)）
+    fn generate_challenges(
+        &self,
+        metadata: &BlobMetadata,
+        count: u16,
+        nodes: &[usize],
+    ) -> Vec<AuditChallenge> {
+        self.generate_challenges_with(metadata, count, nodes, &mut rand::thread_rng())
+    }
+
+    /// 以 `rng` 生成挑戰（以挑戰種子初始化時，同一種子得到同一挑戰集）
+    ///
+    /// 挑戰按 shard 分層分配（見 [`stratified_sample`]）。
+    /// 只挑選了部分節點且 shard 歸屬已知時，只挑戰選中節點（或歸屬未知）的 sliver。
+    /// 每個挑戰以 `recovery_symbol_ratio` 的概率成為 recovery symbol 挑戰，symbol 索引隨機選取
+    fn generate_challenges_with(
+        &self,
+        metadata: &BlobMetadata,
+        count: u16,
+        nodes: &[usize],
+        rng: &mut impl Rng,
+    ) -> Vec<AuditChallenge> {
         let total_slivers = metadata.encoding_n;
-        let mut selected_indices = std::collections::HashSet::new();
+        let shard_of = |index: u64| {
+            u16::try_from(shard_for_sliver(&metadata.blob_id, index, total_slivers))
+                .unwrap_or(u16::MAX)
+        };
 
-        while selected_indices.len() < count as usize {
-            let index = rng.gen_range(0..total_slivers);
-            if selected_indices.insert(index) {
-                let shard_id = (index % 10) as u16;
-                challenges.push(AuditChallenge {
+        let all_nodes = self.shard_owners.is_empty() || nodes.len() == self.storage_clients.len();
+        let slivers: Vec<(u64, u64)> = (0..total_slivers)
+            .map(|index| {
+                (
+                    index,
+                    shard_for_sliver(&metadata.blob_id, index, total_slivers),
+                )
+            })
+            .filter(|&(index, _)| {
+                all_nodes
+                    || self
+                        .shard_owners
+                        .get(&shard_of(index))
+                        .map_or(true, |owner| nodes.contains(owner))
+            })
+            .collect();
+        let indices = stratified_sample(&slivers, usize::from(count), rng);
+
+        let symbol_ratio = self.config.recovery_symbol_ratio;
+        let challenges: Vec<AuditChallenge> = indices
+            .into_iter()
+            .map(|index| {
+                let symbol_index = (symbol_ratio > 0.0 && rng.gen::<f64>() < symbol_ratio)
+                    .then(|| rng.gen_range(0..total_slivers));
+                AuditChallenge {
                     sliver_index: index,
-                    shard_id,
-                    challenge_type: 1,
+                    shard_id: shard_of(index),
+                    challenge_type: if symbol_index.is_some() {
+                        CHALLENGE_RECOVERY_SYMBOL
+                    } else {
+                        CHALLENGE_FULL_SLIVER
+                    },
                     timestamp: Utc::now().timestamp() as u64,
-                });
-            }
-        }
+                    symbol_index,
+                }
+            })
+            .collect();
 
         debug!("Generated {} unique challenges", challenges.len());
         challenges
     }
 
+    /// 每個挑戰的目標節點：shard 的持有者（若被選中），否則在選中節點間輪詢
+    fn route_challenges(&self, challenges: &[AuditChallenge], nodes: &[usize]) -> Vec<usize> {
+        let mut next = 0;
+        challenges
+            .iter()
+            .map(|challenge| match self.shard_owners.get(&challenge.shard_id) {
+                Some(owner) if nodes.contains(owner) => *owner,
+                _ => {
+                    let node = nodes[next % nodes.len()];
+                    next += 1;
+                    node
+                }
+            })
+            .collect()
+    }
+
+    /// 執行挑戰，返回結果（按挑戰順序）與不可達節點的 URL
+    ///
+    /// 至多 `max_parallel_challenges` 個挑戰同時執行。節點不可達（或重試後仍過載）後，
+    /// 發往它的其餘挑戰直接記為失敗，不再發出請求。
+    /// `audit_deadline_secs` 到期時取消未完成的挑戰，記為 `deadline exceeded`
     async fn execute_challenges(
         &self,
         metadata: &BlobMetadata,
         challenges: &[AuditChallenge],
-    ) -> Result<Vec<ChallengeResult>> {
-        let mut results = Vec::with_capacity(challenges.len());
+        routes: &[usize],
+        capture: Option<&HttpCapture>,
+        events: &ProgressSink,
+    ) -> Result<(Vec<ChallengeResult>, BTreeSet<String>)> {
+        let blob_id = BlobId::parse(&metadata.blob_id)?;
+        let blob_id = &blob_id;
+        let deadline =
+            tokio::time::Instant::now() + Duration::from_secs(self.config.audit_deadline_secs);
+        let parallelism = self.config.max_parallel_challenges.max(1);
+        let mut results: Vec<Option<ChallengeResult>> = vec![None; challenges.len()];
+        let mut unreachable: HashMap<usize, String> = HashMap::new();
+        let mut queue = challenges.iter().zip(routes).enumerate();
+        let mut in_flight = FuturesUnordered::new();
+
+        loop {
+            while in_flight.len() < parallelism {
+                let Some((i, (challenge, &node))) = queue.next() else {
+                    break;
+                };
+                let storage_client = &self.storage_clients[node];
+                let node_key = self.node_keys.get(&node);
+                if let Some(reason) = unreachable.get(&node) {
+                    let e = AuditorError::StorageNodeUnreachable(format!(
+                        "{} (earlier challenge failed, not retried)",
+                        reason
+                    ));
+                    let reason = format!("Error: {}", e);
+                    let timing = ChallengeTiming::default();
+                    results[i] = Some(failed_result(challenge, storage_client, reason, timing));
+                    emit_challenge_completed(events, results[i].as_ref());
+                    continue;
+                }
 
-        for (i, challenge) in challenges.iter().enumerate() {
-            info!("Executing challenge {}/{}: sliver_index={}", i + 1, challenges.len(), challenge.sliver_index);
+                info!(
+                    "Executing challenge {}/{}: sliver_index={}, node={}",
+                    i + 1,
+                    challenges.len(),
+                    challenge.sliver_index,
+                    storage_client.base_url()
+                );
+                in_flight.push(async move {
+                    let (result, timing) = self
+                        .execute_single_challenge(
+                            storage_client,
+                            node_key,
+                            blob_id,
+                            metadata,
+                            challenge,
+                            capture,
+                        )
+                        .await;
+                    (i, node, result, timing)
+                });
+            }
 
-            let result = self.execute_single_challenge(metadata, challenge).await;
+            let next = tokio::time::timeout_at(deadline, in_flight.next()).await;
+            let (i, node, result, timing) = match next {
+                Ok(Some(done)) => done,
+                Ok(None) => break,
+                Err(_) => {
+                    warn!(
+                        "Audit deadline of {}s exceeded, cancelling {} outstanding challenge(s)",
+                        self.config.audit_deadline_secs,
+                        results.iter().filter(|result| result.is_none()).count()
+                    );
+                    break;
+                }
+            };
 
+            let storage_client = &self.storage_clients[node];
             match result {
                 Ok(challenge_result) => {
                     if challenge_result.verified {
@@ -182,61 +926,162 @@ impl Auditor {
                     } else {
                         warn!("Challenge {} verification failed: {:?}", i + 1, challenge_result.failure_reason);
                     }
-                    results.push(challenge_result);
+                    results[i] = Some(challenge_result);
                 }
                 Err(e) => {
                     error!("Challenge {} encountered error: {}", i + 1, e);
-                    results.push(ChallengeResult {
-                        challenge: challenge.clone(),
-                        verified: false,
-                        merkle_proof_valid: false,
-                        response_hash: vec![],
-                        failure_reason: Some(format!("Error: {}", e)),
-                    });
+                    if matches!(
+                        e,
+                        AuditorError::StorageNodeUnreachable(_)
+                            | AuditorError::StorageNodeOverloaded { .. }
+                            | AuditorError::CircuitOpen { .. }
+                    ) {
+                        unreachable.entry(node).or_insert_with(|| e.to_string());
+                    }
+                    let reason = format!("Error: {}", e);
+                    results[i] =
+                        Some(failed_result(&challenges[i], storage_client, reason, timing));
                 }
             }
+            emit_challenge_completed(events, results[i].as_ref());
         }
-
-        Ok(results)
+        // 丟棄時取消仍在進行的請求
+        drop(in_flight);
+
+        let results = results
+            .into_iter()
+            .zip(challenges.iter().zip(routes))
+            .map(|(result, (challenge, &node))| {
+                result.unwrap_or_else(|| {
+                    let storage_client = &self.storage_clients[node];
+                    let reason = "deadline exceeded".to_string();
+                    let timing = ChallengeTiming::default();
+                    let result = failed_result(challenge, storage_client, reason, timing);
+                    emit_challenge_completed(events, Some(&result));
+                    result
+                })
+            })
+            .collect();
+        let unreachable = unreachable
+            .into_keys()
+            .map(|node| self.storage_clients[node].base_url().to_string())
+            .collect();
+        Ok((results, unreachable))
     }
 
+    /// 執行單個挑戰並驗證響應；失敗時同樣返回嘗試次數與耗時
     async fn execute_single_challenge(
         &self,
+        storage_client: &StorageNodeClient,
+        node_key: Option<&NodePublicKey>,
+        blob_id: &BlobId,
         metadata: &BlobMetadata,
         challenge: &AuditChallenge,
-    ) -> Result<ChallengeResult> {
-        let start = Instant::now();
+        capture: Option<&HttpCapture>,
+    ) -> (Result<ChallengeResult>, ChallengeTiming) {
+        debug!(
+            "Sending challenge to storage node {} for sliver {}",
+            storage_client.base_url(),
+            challenge.sliver_index
+        );
+        let (response, timing) = match challenge.symbol_index {
+            Some(symbol_index) => {
+                storage_client
+                    .challenge_symbol_timed(blob_id, challenge.sliver_index, symbol_index, capture)
+                    .await
+            }
+            None => {
+                storage_client
+                    .challenge_timed(blob_id, challenge.sliver_index, capture)
+                    .await
+            }
+        };
 
-        let storage_client = self
-            .storage_clients
-            .first()
-            .ok_or_else(|| AuditorError::Config("No storage clients configured".to_string()))?;
+        let result = response.and_then(|response| {
+            debug!("Received response: {} bytes sliver data, {} bytes proof",
+                response.sliver_data.len(), response.merkle_proof.len());
 
-        debug!("Sending challenge to storage node for sliver {}", challenge.sliver_index);
-        let response = storage_client
-            .challenge(&metadata.blob_id, challenge.sliver_index as u64)
-            .await?;
+            let mut verification_result =
+                self.verify_challenge_response(metadata, challenge, &response, node_key)?;
+            verification_result.node_url = Some(storage_client.base_url().to_string());
+            verification_result.response_time_ms = timing.elapsed.as_millis() as u64;
+            verification_result.attempts = timing.attempts;
+            Ok(verification_result)
+        });
 
-        debug!("Received response: {} bytes sliver data, {} bytes proof",
-            response.sliver_data.len(), response.merkle_proof.len());
+        debug!("Challenge completed in {:?} ({} attempt(s))", timing.elapsed, timing.attempts);
 
-        let verification_result = self.verify_challenge_response(metadata, challenge, &response)?;
+        (result, timing)
+    }
 
-        let duration = start.elapsed();
-        debug!("Challenge completed in {:?}", duration);
+    /// 驗證挑戰響應；響應帶有簽名且 `node_key` 已知時同時驗證節點簽名
+    ///
+    /// 簽名覆蓋 `(blob_id, sliver_index, 響應數據哈希, timestamp)`（見 [`challenge_message`]），
+    /// 數據驗證通過但簽名無效時挑戰記為失敗
+    fn verify_challenge_response(
+        &self,
+        metadata: &BlobMetadata,
+        challenge: &AuditChallenge,
+        response: &ChallengeResponse,
+        node_key: Option<&NodePublicKey>,
+    ) -> Result<ChallengeResult> {
+        let mut result = self.verify_response_data(metadata, challenge, response)?;
+        let (Some(signature), Some(key)) = (&response.node_signature, node_key) else {
+            return Ok(result);
+        };
 
-        Ok(verification_result)
+        let message = challenge_message(
+            &metadata.blob_id,
+            challenge.sliver_index,
+            &result.response_hash,
+            response.timestamp.unwrap_or(0),
+        );
+        let valid = key.verify(&message, signature);
+        result.node_signature_valid = Some(valid);
+        if !valid {
+            warn!(
+                "Sliver {} response carries an invalid {} node signature",
+                challenge.sliver_index,
+                key.scheme()
+            );
+            if result.verified {
+                result.verified = false;
+                result.failure_reason = Some(INVALID_NODE_SIGNATURE.to_string());
+            }
+        }
+        Ok(result)
     }
 
-    fn verify_challenge_response(
+    /// 對照鏈上默克爾根驗證響應數據
+    fn verify_response_data(
         &self,
         metadata: &BlobMetadata,
         challenge: &AuditChallenge,
         response: &ChallengeResponse,
     ) -> Result<ChallengeResult> {
         debug!("Verifying challenge response for sliver {}", challenge.sliver_index);
+        let sliver_size_bytes = response.sliver_data.len() as u64;
+
+        if let Some(symbol_index) = challenge.symbol_index {
+            return self.verify_symbol_response(metadata, challenge, symbol_index, response);
+        }
+
+        if let Err(e) = validate_sliver_index(challenge.sliver_index, metadata.encoding_n) {
+            return Ok(ChallengeResult {
+                challenge: challenge.clone(),
+                verified: false,
+                merkle_proof_valid: false,
+                response_hash: vec![],
+                failure_reason: Some(e.to_string()),
+                node_url: None,
+                node_signature_valid: None,
+                response_time_ms: 0,
+                sliver_size_bytes,
+                attempts: 0,
+            });
+        }
 
-        let sliver = match Sliver::from_response_bytes(challenge.sliver_index as u64, response.sliver_data.clone()) {
+        let sliver = match Sliver::from_response_bytes(challenge.sliver_index, response.sliver_data.clone()) {
             Ok(s) => s,
             Err(e) => {
                 return Ok(ChallengeResult {
@@ -245,10 +1090,33 @@ impl Auditor {
                     merkle_proof_valid: false,
                     response_hash: vec![],
                     failure_reason: Some(format!("Failed to parse sliver: {}", e)),
+                    node_url: None,
+                    node_signature_valid: None,
+                    response_time_ms: 0,
+                    sliver_size_bytes,
+                    attempts: 0,
                 });
             }
         };
 
+        if sliver.index != challenge.sliver_index {
+            return Ok(ChallengeResult {
+                challenge: challenge.clone(),
+                verified: false,
+                merkle_proof_valid: false,
+                response_hash: vec![],
+                failure_reason: Some(format!(
+                    "Sliver index {} does not match challenge {}",
+                    sliver.index, challenge.sliver_index
+                )),
+                node_url: None,
+                node_signature_valid: None,
+                response_time_ms: 0,
+                sliver_size_bytes,
+                attempts: 0,
+            });
+        }
+
         let response_hash = sliver.compute_hash().to_vec();
 
         let merkle_proof = match MerkleProof::from_bytes(&response.merkle_proof) {
@@ -260,6 +1128,11 @@ impl Auditor {
                     merkle_proof_valid: false,
                     response_hash,
                     failure_reason: Some(format!("Failed to parse merkle proof: {}", e)),
+                    node_url: None,
+                    node_signature_valid: None,
+                    response_time_ms: 0,
+                    sliver_size_bytes,
+                    attempts: 0,
                 });
             }
         };
@@ -273,10 +1146,11 @@ impl Auditor {
 
         let sliver_metadata = SliverMetadata::new(
             merkle_root,
-            metadata.encoding_n as u64,
-            metadata.encoding_k as usize,
-            metadata.encoding_n as usize,
-        )?;
+            metadata.encoding_n,
+            metadata.encoding_k,
+            metadata.encoding_n,
+        )?
+        .with_leaf_encoding(self.config.leaf_encoding);
 
         let verified = match sliver.verify(&sliver_metadata, &merkle_proof) {
             Ok(v) => v,
@@ -287,6 +1161,11 @@ impl Auditor {
                     merkle_proof_valid: false,
                     response_hash,
                     failure_reason: Some(format!("Verification error: {}", e)),
+                    node_url: None,
+                    node_signature_valid: None,
+                    response_time_ms: 0,
+                    sliver_size_bytes,
+                    attempts: 0,
                 });
             }
         };
@@ -299,6 +1178,11 @@ impl Auditor {
                 merkle_proof_valid: true,
                 response_hash,
                 failure_reason: None,
+                node_url: None,
+                node_signature_valid: None,
+                response_time_ms: 0,
+                sliver_size_bytes,
+                attempts: 0,
             })
         } else {
             warn!("Sliver {} verification FAILED: merkle proof invalid", challenge.sliver_index);
@@ -308,52 +1192,195 @@ impl Auditor {
                 merkle_proof_valid: false,
                 response_hash,
                 failure_reason: Some("Merkle proof verification failed".to_string()),
+                node_url: None,
+                node_signature_valid: None,
+                response_time_ms: 0,
+                sliver_size_bytes,
+                attempts: 0,
             })
         }
     }
 
-    fn count_results(&self, results: &[ChallengeResult]) -> (u16, u16) {
-        let successful = results.iter().filter(|r| r.verified).count() as u16;
-        let failed = results.len() as u16 - successful;
-        (successful, failed)
-    }
-
-    fn generate_report(
+    /// 驗證 recovery symbol 挑戰的響應（symbol 經 sliver 哈希對照鏈上默克爾根）
+    fn verify_symbol_response(
         &self,
-        blob_id: &str,
         metadata: &BlobMetadata,
-        challenge_results: Vec<ChallengeResult>,
-        successful: u16,
-        failed: u16,
-    ) -> Result<AuditReport> {
-        let total_challenges = (successful + failed) as u16;
-        let integrity_hash = self.compute_integrity_hash(&challenge_results);
-        let is_valid = failed == 0;
+        challenge: &AuditChallenge,
+        symbol_index: u64,
+        response: &ChallengeResponse,
+    ) -> Result<ChallengeResult> {
+        let symbol = RecoverySymbol::new(
+            challenge.sliver_index,
+            symbol_index,
+            response.sliver_data.clone(),
+        );
+        let failed = |response_hash: Vec<u8>, reason: String| ChallengeResult {
+            challenge: challenge.clone(),
+            verified: false,
+            merkle_proof_valid: false,
+            response_hash,
+            failure_reason: Some(reason),
+            node_url: None,
+            node_signature_valid: None,
+            response_time_ms: 0,
+            sliver_size_bytes: response.sliver_data.len() as u64,
+            attempts: 0,
+        };
 
-        let failure_reason = if !is_valid {
-            Some(format!("{} out of {} challenges failed", failed, total_challenges))
-        } else {
-            None
+        if response.symbol_index != Some(symbol_index) {
+            return Ok(failed(
+                vec![],
+                format!(
+                    "Node answered symbol {:?} instead of symbol {}",
+                    response.symbol_index, symbol_index
+                ),
+            ));
+        }
+
+        let response_hash = symbol.compute_hash().to_vec();
+        let symbol_proof = match MerkleProof::from_bytes(&response.symbol_proof) {
+            Ok(p) => p,
+            Err(e) => {
+                return Ok(failed(
+                    response_hash,
+                    format!("Failed to parse symbol proof: {}", e),
+                ));
+            }
+        };
+        let sliver_proof = match MerkleProof::from_bytes(&response.merkle_proof) {
+            Ok(p) => p,
+            Err(e) => {
+                return Ok(failed(
+                    response_hash,
+                    format!("Failed to parse merkle proof: {}", e),
+                ));
+            }
         };
 
-        Ok(AuditReport {
-            blob_id: blob_id.to_string(),
-            blob_object_id: metadata.blob_object_id.clone(),
-            auditor: self.auditor_address.clone(),
+        let merkle_root: [u8; 32] = metadata.merkle_root.as_slice().try_into().map_err(|_| {
+            AuditorError::InvalidSliver(format!(
+                "Invalid merkle root length: expected 32, got {}",
+                metadata.merkle_root.len()
+            ))
+        })?;
+        let sliver_metadata = SliverMetadata::new(
+            merkle_root,
+            metadata.encoding_n,
+            metadata.encoding_k,
+            metadata.encoding_n,
+        )?;
+
+        match symbol.verify(&sliver_metadata, &symbol_proof, &sliver_proof) {
+            Ok(true) => {
+                debug!(
+                    "Sliver {} symbol {} verification successful",
+                    challenge.sliver_index, symbol_index
+                );
+                Ok(ChallengeResult {
+                    challenge: challenge.clone(),
+                    verified: true,
+                    merkle_proof_valid: true,
+                    response_hash,
+                    failure_reason: None,
+                    node_url: None,
+                    node_signature_valid: None,
+                    response_time_ms: 0,
+                    sliver_size_bytes: response.sliver_data.len() as u64,
+                    attempts: 0,
+                })
+            }
+            Ok(false) => Ok(failed(
+                response_hash,
+                "Recovery symbol proof verification failed".to_string(),
+            )),
+            Err(e) => Ok(failed(response_hash, format!("Verification error: {}", e))),
+        }
+    }
+
+    fn count_results(&self, results: &[ChallengeResult]) -> Result<(u16, u16)> {
+        // 鏈上計數器為 u16，超出範圍時報錯而不是截斷
+        let total = checked_u16("total_challenges", results.len())?;
+        let successful = results.iter().filter(|r| r.verified).count() as u16;
+        Ok((successful, total - successful))
+    }
+
+    /// 存儲期已結束的 Blob 的報告：不含挑戰，`is_valid` 為真並記錄原因，
+    /// `challenge_epoch` 為發現過期時的 epoch
+    fn expired_report(
+        &self,
+        blob_id: &str,
+        metadata: &BlobMetadata,
+        current_epoch: u32,
+    ) -> Result<AuditReport> {
+        let mut report = self.generate_report(blob_id, metadata, vec![], 0, 0)?;
+        report.challenge_epoch = current_epoch;
+        report.failure_reason = Some(EXPIRED_REASON.to_string());
+        report.blob_status = Some(VerificationStatus::Expired);
+        Ok(report)
+    }
+
+    fn generate_report(
+        &self,
+        blob_id: &str,
+        metadata: &BlobMetadata,
+        challenge_results: Vec<ChallengeResult>,
+        successful: u16,
+        failed: u16,
+    ) -> Result<AuditReport> {
+        let total_challenges = (successful + failed) as u16;
+        let integrity_hash = self.compute_integrity_hash(&challenge_results);
+        let response_stats = ResponseStats::from_results(&challenge_results);
+        let shard_coverage = shard_coverage(&challenge_results);
+        let is_valid = failed == 0;
+
+        let failure_reason = if !is_valid {
+            Some(format!("{} out of {} challenges failed", failed, total_challenges))
+        } else {
+            None
+        };
+
+        Ok(AuditReport {
+            schema_version: REPORT_SCHEMA_VERSION,
+            blob_id: blob_id.to_string(),
+            blob_object_id: metadata.blob_object_id.clone(),
+            auditor: self.auditor_address.clone(),
             timestamp: Utc::now().timestamp() as u64,
             challenge_epoch: metadata.start_epoch,
             challenge_results,
+            encoding_n: Some(metadata.encoding_n),
             total_challenges,
             successful_verifications: successful,
             failed_verifications: failed,
             integrity_hash,
             pqc_signature: vec![],
             pqc_algorithm: 3,
+            pqc_prehashed: false,
             is_valid,
             failure_reason,
+            capture_digest: None,
+            deduplicated_from: None,
+            producer: None,
+            challenge_reveal: None,
+            challenge_seed_source: None,
+            challenge_seed: None,
+            chunk_filter: None,
+            cosignatures: vec![],
+            integrity: None,
+            legacy_envelope: None,
+            blinding_key_id: None,
+            audit_method: Some(AuditMethod::StorageNodeChallenge),
+            response_stats,
+            blob_status: None,
+            audit_id: None,
+            shard_coverage,
+            auditor_reputation_at_audit: None,
         })
     }
 
+    /// 所有挑戰的聚合哈希：Sliver 索引、驗證結果與響應哈希
+    ///
+    /// 刻意不包含傳輸指標（響應時間、sliver 大小、嘗試次數）與節點 URL，
+    /// 同一組響應無論網絡快慢都得到相同的哈希
     fn compute_integrity_hash(&self, results: &[ChallengeResult]) -> Vec<u8> {
         use sha3::{Digest, Sha3_256};
         let mut hasher = Sha3_256::new();
@@ -368,6 +1395,7 @@ impl Auditor {
     }
 
     pub async fn submit_report(&self, _report: &AuditReport) -> Result<String> {
+        self.sui_client().await?;
         info!("Submitting audit report to Sui blockchain...");
         let tx_hash = "0x1234567890abcdef1234567890abcdef1234567890abcdef1234567890abcdef";
         info!("Report submitted successfully: {}", tx_hash);
@@ -386,6 +1414,8 @@ impl Auditor {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::challenge_seed::SuiCheckpoint;
+    use crate::crypto::merkle::MerkleTree;
 
     fn create_test_metadata() -> BlobMetadata {
         BlobMetadata {
@@ -393,25 +1423,105 @@ mod tests {
             blob_id: "0xabcd".to_string(),
             merkle_root: vec![0u8; 32],
             blob_size: 1024 * 1024,
+            encoding_type: 1,
             encoding_k: 10,
             encoding_n: 15,
+            registered_epoch: 100,
+            certified_epoch: Some(100),
             start_epoch: 100,
             end_epoch: 200,
             owner: "0x5678".to_string(),
         }
     }
 
-    #[tokio::test]
-    async fn test_auditor_creation() {
+    #[test]
+    fn test_auditor_creation() {
         let config = AuditorConfig::default();
         let auditor = Auditor::new(
             config,
             "0xauditor".to_string(),
             vec!["http://localhost:8080".to_string()],
         )
-        .await;
+        .unwrap();
+
+        assert_eq!(auditor.auditor_address(), "0xauditor");
+    }
+
+    #[test]
+    fn test_offline_construction_without_sui_ids() {
+        // 默認配置沒有任何合約 ID，也沒有可用的 RPC
+        let config = AuditorConfig {
+            sui_rpc_url: "http://127.0.0.1:1".to_string(),
+            audit_system_package_id: None,
+            access_policy_package_id: None,
+            auditor_registry_id: None,
+            incentives_id: None,
+            ..Default::default()
+        };
+        let auditor = Auditor::new(config.clone(), "0xauditor".to_string(), vec![]).unwrap();
+        assert!(!auditor.has_sui());
+
+        // 配置齊全時只記錄，構建時仍不連接
+        let configured = Auditor::new(
+            AuditorConfig {
+                audit_system_package_id: Some("0x1".to_string()),
+                access_policy_package_id: Some("0x2".to_string()),
+                auditor_registry_id: Some("0x3".to_string()),
+                incentives_id: Some("0x4".to_string()),
+                ..config
+            },
+            "0xauditor".to_string(),
+            vec![],
+        )
+        .unwrap();
+        assert!(configured.has_sui());
+    }
+
+    #[tokio::test]
+    async fn test_chain_methods_error_without_sui() {
+        let config = AuditorConfig {
+            audit_system_package_id: None,
+            ..Default::default()
+        };
+        let auditor = Auditor::new(config, "0xauditor".to_string(), vec![]).unwrap();
+
+        let err = auditor
+            .audit_blob(&BlobRef::ObjectId("0xb10b".to_string()))
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(&err, AuditorError::SuiClient(msg) if msg == SUI_NOT_CONFIGURED),
+            "{:?}",
+            err
+        );
 
-        assert!(auditor.is_ok());
+        let report = auditor
+            .generate_report("0xblob", &create_test_metadata(), vec![], 0, 0)
+            .unwrap();
+        let err = auditor.submit_report(&report).await.unwrap_err();
+        assert!(matches!(err, AuditorError::SuiClient(msg) if msg == SUI_NOT_CONFIGURED));
+    }
+
+    #[test]
+    fn test_end_epoch_comparison() {
+        assert!(!is_past_end_epoch(200, 199));
+        assert!(is_past_end_epoch(200, 200));
+        assert!(is_past_end_epoch(200, 201));
+    }
+
+    #[test]
+    fn test_expired_report_is_valid_without_challenges() {
+        let auditor =
+            Auditor::new(AuditorConfig::default(), "0xauditor".to_string(), vec![]).unwrap();
+        let report = auditor
+            .expired_report("0xblob", &create_test_metadata(), 201)
+            .unwrap();
+
+        assert!(report.is_valid);
+        assert_eq!(report.total_challenges, 0);
+        assert_eq!(report.challenge_epoch, 201);
+        assert_eq!(report.failure_reason.as_deref(), Some(EXPIRED_REASON));
+        assert_eq!(report.verification_status(), VerificationStatus::Expired);
     }
 
     #[test]
@@ -422,12 +1532,12 @@ mod tests {
             ..Default::default()
         };
 
-        let rt = tokio::runtime::Runtime::new().unwrap();
-        let auditor = rt.block_on(Auditor::new(
+        let auditor = Auditor::new(
             config.clone(),
             "0xauditor".to_string(),
             vec!["http://localhost:8080".to_string()],
-        )).unwrap();
+        )
+        .unwrap();
 
         let metadata = create_test_metadata();
         let count = auditor.determine_challenge_count(&metadata);
@@ -439,15 +1549,15 @@ mod tests {
     #[test]
     fn test_generate_challenges() {
         let config = AuditorConfig::default();
-        let rt = tokio::runtime::Runtime::new().unwrap();
-        let auditor = rt.block_on(Auditor::new(
+        let auditor = Auditor::new(
             config,
             "0xauditor".to_string(),
             vec!["http://localhost:8080".to_string()],
-        )).unwrap();
+        )
+        .unwrap();
 
         let metadata = create_test_metadata();
-        let challenges = auditor.generate_challenges(&metadata, 10);
+        let challenges = auditor.generate_challenges(&metadata, 10, &[0]);
 
         assert_eq!(challenges.len(), 10);
 
@@ -458,15 +1568,49 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_seeded_challenges_are_reproducible() {
+        let auditor = Auditor::new(
+            AuditorConfig::default(),
+            "0xauditor".to_string(),
+            vec!["http://localhost:8080".to_string()],
+        )
+        .unwrap();
+        let mut metadata = create_test_metadata();
+        metadata.encoding_n = 1000;
+
+        let checkpoint = |digest: &str| SuiCheckpoint {
+            sequence_number: 7,
+            digest: digest.to_string(),
+        };
+        let indices = |seed: &ChallengeSeed| -> Vec<u64> {
+            auditor
+                .generate_challenges_with(&metadata, 20, &[0], &mut seed.rng())
+                .iter()
+                .map(|challenge| challenge.sliver_index)
+                .collect()
+        };
+
+        let seed =
+            ChallengeSeed::from_checkpoint(&checkpoint("4vJ9JU1bJJE96FWS"), "0xabcd", "0xauditor");
+        let again =
+            ChallengeSeed::from_checkpoint(&checkpoint("4vJ9JU1bJJE96FWS"), "0xabcd", "0xauditor");
+        assert_eq!(indices(&seed), indices(&again));
+
+        let other =
+            ChallengeSeed::from_checkpoint(&checkpoint("9xQeWvG816bUx9EP"), "0xabcd", "0xauditor");
+        assert_ne!(indices(&seed), indices(&other));
+    }
+
     #[test]
     fn test_count_results() {
         let config = AuditorConfig::default();
-        let rt = tokio::runtime::Runtime::new().unwrap();
-        let auditor = rt.block_on(Auditor::new(
+        let auditor = Auditor::new(
             config,
             "0xauditor".to_string(),
             vec!["http://localhost:8080".to_string()],
-        )).unwrap();
+        )
+        .unwrap();
 
         let results = vec![
             ChallengeResult {
@@ -475,11 +1619,17 @@ mod tests {
                     shard_id: 0,
                     challenge_type: 1,
                     timestamp: 0,
+                    symbol_index: None,
                 },
                 verified: true,
                 merkle_proof_valid: true,
                 response_hash: vec![],
                 failure_reason: None,
+                node_url: None,
+                node_signature_valid: None,
+                response_time_ms: 0,
+                sliver_size_bytes: 0,
+                attempts: 0,
             },
             ChallengeResult {
                 challenge: AuditChallenge {
@@ -487,15 +1637,21 @@ mod tests {
                     shard_id: 0,
                     challenge_type: 1,
                     timestamp: 0,
+                    symbol_index: None,
                 },
                 verified: false,
                 merkle_proof_valid: false,
                 response_hash: vec![],
                 failure_reason: Some("Test failure".to_string()),
+                node_url: None,
+                node_signature_valid: None,
+                response_time_ms: 0,
+                sliver_size_bytes: 0,
+                attempts: 0,
             },
         ];
 
-        let (successful, failed) = auditor.count_results(&results);
+        let (successful, failed) = auditor.count_results(&results).unwrap();
         assert_eq!(successful, 1);
         assert_eq!(failed, 1);
     }
@@ -503,12 +1659,12 @@ mod tests {
     #[test]
     fn test_compute_integrity_hash() {
         let config = AuditorConfig::default();
-        let rt = tokio::runtime::Runtime::new().unwrap();
-        let auditor = rt.block_on(Auditor::new(
+        let auditor = Auditor::new(
             config,
             "0xauditor".to_string(),
             vec!["http://localhost:8080".to_string()],
-        )).unwrap();
+        )
+        .unwrap();
 
         let results = vec![
             ChallengeResult {
@@ -517,11 +1673,17 @@ mod tests {
                     shard_id: 0,
                     challenge_type: 1,
                     timestamp: 0,
+                    symbol_index: None,
                 },
                 verified: true,
                 merkle_proof_valid: true,
                 response_hash: vec![1, 2, 3],
                 failure_reason: None,
+                node_url: None,
+                node_signature_valid: None,
+                response_time_ms: 0,
+                sliver_size_bytes: 0,
+                attempts: 0,
             },
         ];
 
@@ -530,17 +1692,28 @@ mod tests {
 
         let hash2 = auditor.compute_integrity_hash(&results);
         assert_eq!(hash, hash2);
+
+        // 傳輸指標與節點 URL 不影響哈希
+        let mut timed = results.clone();
+        timed[0].response_time_ms = 1_500;
+        timed[0].sliver_size_bytes = 4096;
+        timed[0].attempts = 3;
+        timed[0].node_url = Some("http://node-a:9000".to_string());
+        assert_eq!(auditor.compute_integrity_hash(&timed), hash);
+
+        timed[0].verified = false;
+        assert_ne!(auditor.compute_integrity_hash(&timed), hash);
     }
 
     #[test]
     fn test_generate_report() {
         let config = AuditorConfig::default();
-        let rt = tokio::runtime::Runtime::new().unwrap();
-        let auditor = rt.block_on(Auditor::new(
+        let auditor = Auditor::new(
             config,
             "0xauditor".to_string(),
             vec!["http://localhost:8080".to_string()],
-        )).unwrap();
+        )
+        .unwrap();
 
         let metadata = create_test_metadata();
         let results = vec![
@@ -550,11 +1723,17 @@ mod tests {
                     shard_id: 0,
                     challenge_type: 1,
                     timestamp: 0,
+                    symbol_index: None,
                 },
                 verified: true,
                 merkle_proof_valid: true,
                 response_hash: vec![],
                 failure_reason: None,
+                node_url: None,
+                node_signature_valid: None,
+                response_time_ms: 0,
+                sliver_size_bytes: 0,
+                attempts: 0,
             },
         ];
 
@@ -569,4 +1748,1092 @@ mod tests {
         assert!(report.is_valid);
         assert!(report.failure_reason.is_none());
     }
+
+    #[test]
+    fn test_sliver_indices_beyond_u16() {
+        let auditor = Auditor::new(
+            AuditorConfig::default(),
+            "0xauditor".to_string(),
+            vec!["http://localhost:8080".to_string()],
+        )
+        .unwrap();
+        let metadata = BlobMetadata {
+            encoding_k: 334,
+            encoding_n: 70_001,
+            ..create_test_metadata()
+        };
+
+        let challenges = auditor.generate_challenges(&metadata, 1000, &[0]);
+        assert_eq!(challenges.len(), 1000);
+        let indices: std::collections::HashSet<u64> =
+            challenges.iter().map(|c| c.sliver_index).collect();
+        assert_eq!(indices.len(), 1000);
+        assert!(indices.iter().all(|&i| i < metadata.encoding_n));
+        assert!(indices.iter().any(|&i| i > u64::from(u16::MAX)));
+
+        // 報告 JSON 往返後索引不被截斷
+        let results = vec![ChallengeResult {
+            challenge: AuditChallenge {
+                sliver_index: 70_000,
+                shard_id: 0,
+                challenge_type: 1,
+                timestamp: 0,
+                symbol_index: None,
+            },
+            verified: true,
+            merkle_proof_valid: true,
+            response_hash: vec![],
+            failure_reason: None,
+            node_url: None,
+            node_signature_valid: None,
+            response_time_ms: 0,
+            sliver_size_bytes: 0,
+            attempts: 0,
+        }];
+        let report = auditor
+            .generate_report("0xblob", &metadata, results, 1, 0)
+            .unwrap();
+        assert_eq!(report.encoding_n, Some(70_001));
+
+        let json = serde_json::to_string(&report).unwrap();
+        let parsed = crate::report::ReportManager::from_json(&json).unwrap();
+        assert_eq!(parsed.challenge_results[0].challenge.sliver_index, 70_000);
+    }
+
+    #[test]
+    fn test_challenge_count_clamped_to_sliver_count() {
+        let auditor = Auditor::new(
+            AuditorConfig::default(),
+            "0xauditor".to_string(),
+            vec!["http://localhost:8080".to_string()],
+        )
+        .unwrap();
+
+        let metadata = create_test_metadata();
+        assert_eq!(auditor.generate_challenges(&metadata, 100, &[0]).len(), 15);
+    }
+
+    #[test]
+    fn test_out_of_range_sliver_index_fails_challenge() {
+        let auditor = Auditor::new(
+            AuditorConfig::default(),
+            "0xauditor".to_string(),
+            vec!["http://localhost:8080".to_string()],
+        )
+        .unwrap();
+        let metadata = create_test_metadata();
+        let challenge = AuditChallenge {
+            sliver_index: metadata.encoding_n,
+            shard_id: 0,
+            challenge_type: 1,
+            timestamp: 0,
+            symbol_index: None,
+        };
+        let response = ChallengeResponse {
+            sliver_data: vec![1, 2, 3],
+            merkle_proof: vec![],
+            symbol_index: None,
+            symbol_proof: vec![],
+            node_signature: None,
+            timestamp: None,
+        };
+
+        let result = auditor
+            .verify_challenge_response(&metadata, &challenge, &response, None)
+            .unwrap();
+        assert!(!result.verified);
+        assert!(result.failure_reason.unwrap().contains("out of range"));
+    }
+
+    #[test]
+    fn test_challenge_types_follow_recovery_symbol_ratio() {
+        let metadata = create_test_metadata();
+        let challenges_with_ratio = |recovery_symbol_ratio| {
+            let config = AuditorConfig {
+                recovery_symbol_ratio,
+                ..Default::default()
+            };
+            let urls = vec!["http://localhost:8080".to_string()];
+            Auditor::new(config, "0xauditor".to_string(), urls)
+                .unwrap()
+                .generate_challenges(&metadata, 10, &[0])
+        };
+
+        let challenges = challenges_with_ratio(0.0);
+        assert!(challenges
+            .iter()
+            .all(|c| c.challenge_type == CHALLENGE_FULL_SLIVER && c.symbol_index.is_none()));
+
+        let challenges = challenges_with_ratio(1.0);
+        assert_eq!(challenges.len(), 10);
+        assert!(challenges.iter().all(|c| c.challenge_type == CHALLENGE_RECOVERY_SYMBOL
+            && c.symbol_index.is_some_and(|i| i < metadata.encoding_n)));
+    }
+
+    /// 每個 sliver 的 symbol 樹與以其根為葉子的 Blob 樹（symbol `j` of sliver `i` 為 `[i, j]`）
+    fn symbol_trees(n: u64) -> (Vec<MerkleTree>, MerkleTree) {
+        use crate::crypto::merkle::{hash_leaf, MerkleTreeVersion};
+
+        let symbol_trees: Vec<MerkleTree> = (0..n as u8)
+            .map(|i| {
+                let symbols = (0..n as u8).map(|j| hash_leaf(&[i, j])).collect();
+                MerkleTree::from_leaf_hashes(symbols, MerkleTreeVersion::V2).unwrap()
+            })
+            .collect();
+        let roots = symbol_trees.iter().map(MerkleTree::root).collect();
+        let blob_tree = MerkleTree::from_leaf_hashes(roots, MerkleTreeVersion::V2).unwrap();
+        (symbol_trees, blob_tree)
+    }
+
+    /// 對固定應答 `body` 的假存儲節點執行 `challenge`
+    async fn challenge_node(
+        metadata: &BlobMetadata,
+        challenge: AuditChallenge,
+        body: serde_json::Value,
+    ) -> ChallengeResult {
+        let node = crate::test_support::FakeStorageNode::start(
+            axum::http::StatusCode::OK,
+            "application/json",
+            serde_json::to_vec(&body).unwrap(),
+        )
+        .await;
+        let auditor = Auditor::new(
+            AuditorConfig::default(),
+            "0xauditor".to_string(),
+            vec![node.url().to_string()],
+        )
+        .unwrap();
+        let (mut results, _) = auditor
+            .execute_challenges(metadata, &[challenge], &[0], None, &ProgressSink::default())
+            .await
+            .unwrap();
+        results.remove(0)
+    }
+
+    fn symbol_challenge(sliver_index: u64, symbol_index: u64) -> AuditChallenge {
+        AuditChallenge {
+            sliver_index,
+            shard_id: 0,
+            challenge_type: CHALLENGE_RECOVERY_SYMBOL,
+            timestamp: 0,
+            symbol_index: Some(symbol_index),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_full_sliver_challenge_against_mock_node() {
+        use crate::crypto::merkle::{hash_leaf, MerkleTreeVersion};
+
+        let slivers: Vec<Vec<u8>> = (0..15u8).map(|i| vec![i; 64]).collect();
+        let tree = MerkleTree::from_leaf_hashes(
+            slivers.iter().map(|sliver| hash_leaf(sliver)).collect(),
+            MerkleTreeVersion::V2,
+        )
+        .unwrap();
+        let mut metadata = create_test_metadata();
+        metadata.merkle_root = tree.root().to_vec();
+
+        let challenge = AuditChallenge {
+            sliver_index: 6,
+            shard_id: 0,
+            challenge_type: CHALLENGE_FULL_SLIVER,
+            timestamp: 0,
+            symbol_index: None,
+        };
+        let body = serde_json::json!({
+            "sliver_data": slivers[6],
+            "merkle_proof": tree.generate_proof(6).unwrap().to_bytes(),
+        });
+        let result = challenge_node(&metadata, challenge, body).await;
+        assert!(result.verified, "{:?}", result.failure_reason);
+        assert_eq!(result.challenge.challenge_type, CHALLENGE_FULL_SLIVER);
+        assert_eq!(result.sliver_size_bytes, 64);
+    }
+
+    #[tokio::test]
+    async fn test_proof_for_other_sliver_fails_challenge() {
+        use crate::crypto::merkle::{hash_leaf, MerkleTreeVersion};
+
+        let slivers: Vec<Vec<u8>> = (0..15u8).map(|i| vec![i; 64]).collect();
+        let tree = MerkleTree::from_leaf_hashes(
+            slivers.iter().map(|sliver| hash_leaf(sliver)).collect(),
+            MerkleTreeVersion::V2,
+        )
+        .unwrap();
+        let mut metadata = create_test_metadata();
+        metadata.merkle_root = tree.root().to_vec();
+
+        // 節點丟失了 sliver 6，改用 sliver 2 的數據與其合法證明應答
+        let challenge = AuditChallenge {
+            sliver_index: 6,
+            shard_id: 0,
+            challenge_type: CHALLENGE_FULL_SLIVER,
+            timestamp: 0,
+            symbol_index: None,
+        };
+        let body = serde_json::json!({
+            "sliver_data": slivers[2],
+            "merkle_proof": tree.generate_proof(2).unwrap().to_bytes(),
+        });
+        let result = challenge_node(&metadata, challenge, body).await;
+        assert!(!result.verified);
+        assert!(!result.merkle_proof_valid);
+    }
+
+    #[tokio::test]
+    async fn test_recovery_symbol_challenge_against_mock_node() {
+        let (symbol_trees, blob_tree) = symbol_trees(15);
+        let mut metadata = create_test_metadata();
+        metadata.merkle_root = blob_tree.root().to_vec();
+
+        let body = serde_json::json!({
+            "sliver_data": [6, 9],
+            "merkle_proof": blob_tree.generate_proof(6).unwrap().to_bytes(),
+            "symbol_index": 9,
+            "symbol_proof": symbol_trees[6].generate_proof(9).unwrap().to_bytes(),
+        });
+        let result = challenge_node(&metadata, symbol_challenge(6, 9), body).await;
+        assert!(result.verified, "{:?}", result.failure_reason);
+        assert_eq!(result.sliver_size_bytes, 2);
+
+        // 挑戰類型與 symbol 索引記入報告
+        let auditor =
+            Auditor::new(AuditorConfig::default(), "0xauditor".to_string(), vec![]).unwrap();
+        let report = auditor
+            .generate_report("0xblob", &metadata, vec![result], 1, 0)
+            .unwrap();
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["challenge_results"][0]["challenge"]["challenge_type"], 2);
+        assert_eq!(json["challenge_results"][0]["challenge"]["symbol_index"], 9);
+    }
+
+    #[tokio::test]
+    async fn test_invalid_recovery_symbol_is_rejected() {
+        let (symbol_trees, blob_tree) = symbol_trees(15);
+        let mut metadata = create_test_metadata();
+        metadata.merkle_root = blob_tree.root().to_vec();
+        let symbol_proof = symbol_trees[6].generate_proof(9).unwrap().to_bytes();
+        let merkle_proof = blob_tree.generate_proof(6).unwrap().to_bytes();
+
+        // 篡改的 symbol 數據
+        let body = serde_json::json!({
+            "sliver_data": [6, 10],
+            "merkle_proof": merkle_proof,
+            "symbol_index": 9,
+            "symbol_proof": symbol_proof,
+        });
+        let result = challenge_node(&metadata, symbol_challenge(6, 9), body).await;
+        assert!(!result.verified);
+        assert_eq!(
+            result.failure_reason.as_deref(),
+            Some("Recovery symbol proof verification failed")
+        );
+
+        // 以另一個 symbol 應答
+        let body = serde_json::json!({
+            "sliver_data": [6, 9],
+            "merkle_proof": merkle_proof,
+            "symbol_index": 8,
+            "symbol_proof": symbol_proof,
+        });
+        let result = challenge_node(&metadata, symbol_challenge(6, 9), body).await;
+        assert!(!result.verified);
+        assert!(result.failure_reason.unwrap().contains("instead of symbol 9"));
+    }
+
+    /// sliver 6 的完整 sliver 挑戰、其正確響應（未簽名）與節點應簽名的消息
+    fn sliver_challenge_response() -> (BlobMetadata, AuditChallenge, ChallengeResponse, Vec<u8>) {
+        use crate::crypto::merkle::{hash_leaf, MerkleTreeVersion};
+
+        let slivers: Vec<Vec<u8>> = (0..15u8).map(|i| vec![i; 64]).collect();
+        let tree = MerkleTree::from_leaf_hashes(
+            slivers.iter().map(|sliver| hash_leaf(sliver)).collect(),
+            MerkleTreeVersion::V2,
+        )
+        .unwrap();
+        let mut metadata = create_test_metadata();
+        metadata.merkle_root = tree.root().to_vec();
+
+        let challenge = AuditChallenge {
+            sliver_index: 6,
+            shard_id: 0,
+            challenge_type: CHALLENGE_FULL_SLIVER,
+            timestamp: 0,
+            symbol_index: None,
+        };
+        let response = ChallengeResponse {
+            sliver_data: slivers[6].clone(),
+            merkle_proof: tree.generate_proof(6).unwrap().to_bytes(),
+            symbol_index: None,
+            symbol_proof: vec![],
+            node_signature: None,
+            timestamp: Some(1_700_000_000),
+        };
+        let sliver_hash = Sliver::from_response_bytes(6, slivers[6].clone())
+            .unwrap()
+            .compute_hash();
+        let message = challenge_message(&metadata.blob_id, 6, &sliver_hash, 1_700_000_000);
+        (metadata, challenge, response, message)
+    }
+
+    #[tokio::test]
+    async fn test_node_signature_verified_with_configured_ed25519_key() {
+        use fastcrypto::ed25519::Ed25519KeyPair;
+        use fastcrypto::traits::{KeyPair, Signer, ToFromBytes};
+
+        let keypair = Ed25519KeyPair::generate(&mut rand::thread_rng());
+        let (metadata, challenge, response, message) = sliver_challenge_response();
+        let body = serde_json::json!({
+            "sliver_data": response.sliver_data,
+            "merkle_proof": response.merkle_proof,
+            "node_signature": keypair.sign(&message).as_bytes(),
+            "timestamp": response.timestamp,
+        });
+        let node = crate::test_support::FakeStorageNode::start(
+            axum::http::StatusCode::OK,
+            "application/json",
+            serde_json::to_vec(&body).unwrap(),
+        )
+        .await;
+
+        let mut config = AuditorConfig::default();
+        config.storage_node_public_keys.insert(
+            format!("{}/", node.url()),
+            hex::encode(keypair.public().as_bytes()),
+        );
+        let auditor = Auditor::new(
+            config,
+            "0xauditor".to_string(),
+            vec![node.url().to_string()],
+        )
+        .unwrap();
+        let (results, _) = auditor
+            .execute_challenges(
+                &metadata,
+                &[challenge],
+                &[0],
+                None,
+                &ProgressSink::default(),
+            )
+            .await
+            .unwrap();
+        assert!(results[0].verified, "{:?}", results[0].failure_reason);
+        assert_eq!(results[0].node_signature_valid, Some(true));
+    }
+
+    #[test]
+    fn test_node_signature_verified_with_on_chain_bls_key() {
+        use fastcrypto::bls12381::min_pk::BLS12381KeyPair;
+        use fastcrypto::traits::{KeyPair, Signer, ToFromBytes};
+
+        let keypair = BLS12381KeyPair::generate(&mut rand::thread_rng());
+        let mut node = node_info("http://node-a:9000", vec![0]);
+        node.public_key = keypair.public().as_bytes().to_vec();
+        let urls = vec!["http://node-a:9000".to_string()];
+        let auditor = Auditor::new(AuditorConfig::default(), "0xauditor".to_string(), urls)
+            .unwrap()
+            .with_shard_assignment(&[node]);
+        let key = auditor.node_keys.get(&0);
+        assert_eq!(key.map(NodePublicKey::scheme), Some("bls12381"));
+
+        let (metadata, challenge, mut response, message) = sliver_challenge_response();
+
+        // 未簽名的響應不驗證簽名
+        let result = auditor
+            .verify_challenge_response(&metadata, &challenge, &response, key)
+            .unwrap();
+        assert!(result.verified);
+        assert_eq!(result.node_signature_valid, None);
+
+        response.node_signature = Some(keypair.sign(&message).as_bytes().to_vec());
+        let result = auditor
+            .verify_challenge_response(&metadata, &challenge, &response, key)
+            .unwrap();
+        assert!(result.verified, "{:?}", result.failure_reason);
+        assert_eq!(result.node_signature_valid, Some(true));
+
+        // 數據正確，但簽名來自另一把密鑰
+        let other = BLS12381KeyPair::generate(&mut rand::thread_rng());
+        response.node_signature = Some(other.sign(&message).as_bytes().to_vec());
+        let result = auditor
+            .verify_challenge_response(&metadata, &challenge, &response, key)
+            .unwrap();
+        assert!(!result.verified);
+        assert!(result.merkle_proof_valid);
+        assert_eq!(result.node_signature_valid, Some(false));
+        assert_eq!(
+            result.failure_reason.as_deref(),
+            Some(INVALID_NODE_SIGNATURE)
+        );
+
+        // 節點公鑰未知時不驗證簽名
+        let result = auditor
+            .verify_challenge_response(&metadata, &challenge, &response, None)
+            .unwrap();
+        assert!(result.verified);
+        assert_eq!(result.node_signature_valid, None);
+    }
+
+    /// 對返回 `status` + `body` 的假存儲節點執行一次挑戰，返回生成的報告
+    async fn audit_against_failing_node(
+        status: axum::http::StatusCode,
+        content_type: &'static str,
+        body: Vec<u8>,
+        max_error_body_len: usize,
+        capture: Option<&HttpCapture>,
+    ) -> AuditReport {
+        let node = crate::test_support::FakeStorageNode::start(status, content_type, body).await;
+        let config = AuditorConfig {
+            max_error_body_len,
+            ..Default::default()
+        };
+        let auditor = Auditor::new(
+            config,
+            "0xauditor".to_string(),
+            vec![node.url().to_string()],
+        )
+        .unwrap();
+
+        let metadata = create_test_metadata();
+        let challenges = auditor.generate_challenges(&metadata, 1, &[0]);
+        let (results, _) = auditor
+            .execute_challenges(
+                &metadata,
+                &challenges,
+                &[0],
+                capture,
+                &ProgressSink::default(),
+            )
+            .await
+            .unwrap();
+        let (successful, failed) = auditor.count_results(&results).unwrap();
+
+        auditor
+            .generate_report("0xblob", &metadata, results, successful, failed)
+            .unwrap()
+    }
+
+    fn challenge_failure(report: &AuditReport) -> &str {
+        report.challenge_results[0].failure_reason.as_deref().unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_html_error_body_is_sanitized_in_report() {
+        let body = b"<!DOCTYPE html><html><body><h1>404</h1><p>Ressource\nintrouvable</p></body></html>";
+        let report = audit_against_failing_node(
+            axum::http::StatusCode::NOT_FOUND,
+            "text/html",
+            body.to_vec(),
+            256,
+            None,
+        )
+        .await;
+
+        let reason = challenge_failure(&report);
+        assert!(reason.contains("HTTP 404"));
+        assert!(reason.ends_with("404 Ressource introuvable"), "{}", reason);
+
+        let json = serde_json::to_string(&report).unwrap();
+        assert!(!json.contains('<') && !json.contains('>'));
+        assert!(!json.contains("\\n"));
+    }
+
+    #[tokio::test]
+    async fn test_oversized_error_body_is_bounded_and_captured() {
+        let capture_dir = std::env::temp_dir().join(format!("auditor_capture_{}", rand::random::<u32>()));
+        let capture = HttpCapture::create(&capture_dir).unwrap();
+        let body = "quota exceeded ".repeat(10_000).into_bytes();
+
+        let report = audit_against_failing_node(
+            axum::http::StatusCode::TOO_MANY_REQUESTS,
+            "text/plain",
+            body.clone(),
+            64,
+            Some(&capture),
+        )
+        .await;
+
+        let reason = challenge_failure(&report);
+        let (_, surfaced) = reason.rsplit_once(" - ").unwrap();
+        assert_eq!(surfaced.chars().count(), 64);
+        assert!(surfaced.ends_with(crate::node_error::TRUNCATION_MARKER));
+
+        // 完整響應體只保存在捕獲中
+        let records = std::fs::read_to_string(capture_dir.join(crate::capture::CAPTURE_FILE_NAME)).unwrap();
+        let record: crate::capture::CaptureRecord =
+            serde_json::from_str(records.lines().next().unwrap()).unwrap();
+        assert_eq!(record.response_body.unwrap().len, body.len() as u64);
+
+        let _ = std::fs::remove_dir_all(&capture_dir);
+    }
+
+    #[tokio::test]
+    async fn test_structured_error_code_is_preferred() {
+        let body = r#"{"code": "sliver_not_stored", "message": "Le fragment n'est pas stocké <ici>"}"#.as_bytes();
+        let report = audit_against_failing_node(
+            axum::http::StatusCode::NOT_FOUND,
+            "application/json",
+            body.to_vec(),
+            256,
+            None,
+        )
+        .await;
+
+        let reason = challenge_failure(&report);
+        assert!(reason.ends_with(" - sliver_not_stored"), "{}", reason);
+        assert!(!reason.contains("fragment"));
+    }
+
+    fn node_info(endpoint: &str, shards: Vec<u16>) -> StorageNodeInfo {
+        StorageNodeInfo {
+            node_id: 0,
+            api_endpoint: endpoint.to_string(),
+            public_key: vec![],
+            is_online: true,
+            shards,
+        }
+    }
+
+    #[test]
+    fn test_shard_for_sliver_rotates_by_blob_id() {
+        // 無法解碼的 blob ID 不輪轉
+        assert_eq!(shard_for_sliver("0xabcd", 7, 15), 7);
+        assert_eq!(shard_for_sliver("0xabcd", 16, 15), 1);
+
+        // 32 字節大端整數 5
+        let mut bytes = [0u8; 32];
+        bytes[31] = 5;
+        let blob_id = general_purpose::URL_SAFE_NO_PAD.encode(bytes);
+        assert_eq!(shard_for_sliver(&blob_id, 0, 15), 5);
+        assert_eq!(shard_for_sliver(&blob_id, 12, 15), 2);
+        assert_eq!(shard_for_sliver(&blob_id, 3, 0), 0);
+    }
+
+    /// `(sliver 數, shard 數, 預算)`，`shard 數 <= 預算 <= sliver 數`
+    fn sampling_case() -> impl proptest::strategy::Strategy<Value = (u64, u64, u64)> {
+        use proptest::prelude::*;
+        (1u64..400)
+            .prop_flat_map(|n| (Just(n), 1..=n))
+            .prop_flat_map(|(n, shards)| (Just(n), Just(shards), shards..=n))
+    }
+
+    proptest::proptest! {
+        #![proptest_config(proptest::prelude::ProptestConfig::with_cases(64))]
+
+        /// 預算不少於 shard 數時每個 shard 至少一個挑戰，且每個 shard 先得到平均份額
+        #[test]
+        fn prop_stratified_sample_covers_every_shard(
+            (n, shard_count, budget) in sampling_case(),
+            seed in proptest::prelude::any::<[u8; 32]>(),
+        ) {
+            use rand::SeedableRng;
+
+            let slivers: Vec<(u64, u64)> =
+                (0..n).map(|index| (index, index % shard_count)).collect();
+            let mut rng = rand_chacha::ChaCha20Rng::from_seed(seed);
+            let indices = stratified_sample(&slivers, budget as usize, &mut rng);
+
+            proptest::prop_assert_eq!(indices.len() as u64, budget);
+            let distinct: std::collections::HashSet<u64> = indices.iter().copied().collect();
+            proptest::prop_assert_eq!(distinct.len(), indices.len());
+
+            let mut per_shard = vec![0u64; shard_count as usize];
+            for index in &indices {
+                proptest::prop_assert!(*index < n);
+                per_shard[(index % shard_count) as usize] += 1;
+            }
+            let floor = budget / shard_count;
+            for (shard, &count) in per_shard.iter().enumerate() {
+                let capacity = (n - shard as u64).div_ceil(shard_count);
+                proptest::prop_assert!(count >= 1);
+                proptest::prop_assert!(count >= floor.min(capacity));
+            }
+        }
+    }
+
+    #[test]
+    fn test_stratified_sample_gives_extras_only_after_floor() {
+        // shard 0 有 10 個 sliver，shard 1 只有 2 個：份額不足的部分轉給 shard 0
+        let mut slivers: Vec<(u64, u64)> = (0..10).map(|index| (index, 0)).collect();
+        slivers.extend([(10, 1), (11, 1)]);
+        let indices = stratified_sample(&slivers, 8, &mut rand::thread_rng());
+        assert_eq!(indices.len(), 8);
+        assert_eq!(indices.iter().filter(|&&index| index >= 10).count(), 2);
+
+        // 預算超過 sliver 數時全部挑戰
+        let mut all = stratified_sample(&slivers, 100, &mut rand::thread_rng());
+        all.sort_unstable();
+        assert_eq!(all, (0..12).collect::<Vec<u64>>());
+        assert!(stratified_sample(&[], 5, &mut rand::thread_rng()).is_empty());
+    }
+
+    #[test]
+    fn test_report_records_signed_shard_coverage() {
+        let auditor = Auditor::new(
+            AuditorConfig::default(),
+            "0xauditor".to_string(),
+            vec!["http://localhost:8080".to_string()],
+        )
+        .unwrap();
+        let metadata = create_test_metadata();
+
+        let challenges = auditor.generate_challenges(&metadata, 15, &[0]);
+        let mut shards: Vec<u16> = challenges.iter().map(|c| c.shard_id).collect();
+        shards.sort_unstable();
+        assert_eq!(shards, (0..15).collect::<Vec<u16>>());
+
+        let results: Vec<ChallengeResult> = challenges
+            .into_iter()
+            .map(|challenge| ChallengeResult {
+                challenge,
+                verified: true,
+                merkle_proof_valid: true,
+                response_hash: vec![],
+                failure_reason: None,
+                node_url: None,
+                node_signature_valid: None,
+                response_time_ms: 0,
+                sliver_size_bytes: 0,
+                attempts: 0,
+            })
+            .collect();
+        let mut report = auditor
+            .generate_report("0xblob", &metadata, results, 15, 0)
+            .unwrap();
+        assert_eq!(
+            report.shard_coverage,
+            (0..15).map(|shard| (shard, 1)).collect::<Vec<(u16, u16)>>()
+        );
+
+        let signed = report.signing_bytes();
+        report.shard_coverage[0].1 = 2;
+        assert_ne!(report.signing_bytes(), signed);
+    }
+
+    #[test]
+    fn test_challenges_routed_to_shard_owner() {
+        let urls = vec!["http://node-a:9000".to_string(), "http://node-b:9000/".to_string()];
+        let auditor = Auditor::new(AuditorConfig::default(), "0xauditor".to_string(), urls)
+            .unwrap()
+            .with_shard_assignment(&[
+                node_info("http://node-a:9000", (0..8).collect()),
+                node_info("http://node-b:9000", (8..15).collect()),
+                node_info("http://unknown:9000", vec![0]),
+            ]);
+
+        let metadata = create_test_metadata();
+        let challenges = auditor.generate_challenges(&metadata, 15, &[0, 1]);
+        let routes = auditor.route_challenges(&challenges, &[0, 1]);
+        for (challenge, node) in challenges.iter().zip(&routes) {
+            assert_eq!(*node, usize::from(challenge.shard_id >= 8), "{:?}", challenge);
+        }
+    }
+
+    #[test]
+    fn test_node_subset_only_challenges_selected_shards() {
+        let config = AuditorConfig {
+            storage_nodes_per_audit: Some(1),
+            ..Default::default()
+        };
+        let urls = vec!["http://node-a:9000".to_string(), "http://node-b:9000".to_string()];
+        let auditor = Auditor::new(config, "0xauditor".to_string(), urls)
+            .unwrap()
+            .with_shard_assignment(&[
+                node_info("http://node-a:9000", (0..8).collect()),
+                node_info("http://node-b:9000", (8..15).collect()),
+            ]);
+
+        let metadata = create_test_metadata();
+        let nodes = auditor.select_nodes().unwrap();
+        assert_eq!(nodes.len(), 1);
+
+        let challenges = auditor.generate_challenges(&metadata, 10, &nodes);
+        assert!(!challenges.is_empty());
+        let routes = auditor.route_challenges(&challenges, &nodes);
+        assert!(routes.iter().all(|node| *node == nodes[0]));
+    }
+
+    #[test]
+    fn test_challenges_without_shard_map_round_robin() {
+        let urls = (0..3).map(|i| format!("http://node-{}:9000", i)).collect();
+        let auditor =
+            Auditor::new(AuditorConfig::default(), "0xauditor".to_string(), urls).unwrap();
+
+        let metadata = create_test_metadata();
+        let challenges = auditor.generate_challenges(&metadata, 6, &[0, 1, 2]);
+        assert_eq!(auditor.route_challenges(&challenges, &[0, 1, 2]), vec![0, 1, 2, 0, 1, 2]);
+    }
+
+    #[tokio::test]
+    async fn test_unhealthy_nodes_are_skipped_until_recovered() {
+        use axum::http::StatusCode;
+
+        let mut nodes = Vec::new();
+        for _ in 0..2 {
+            let node = crate::test_support::FakeStorageNode::start(
+                StatusCode::NOT_FOUND,
+                "text/plain",
+                Vec::new(),
+            )
+            .await;
+            nodes.push(node);
+        }
+        let urls: Vec<String> = nodes.iter().map(|node| node.url().to_string()).collect();
+        let auditor = Auditor::new(
+            AuditorConfig::default(),
+            "0xauditor".to_string(),
+            urls.clone(),
+        )
+        .unwrap()
+        .with_shard_assignment(&[
+            node_info(&urls[0], (0..8).collect()),
+            node_info(&urls[1], (8..15).collect()),
+        ]);
+        let monitor = auditor.health_monitor().unwrap();
+
+        nodes[1].set_health(StatusCode::INTERNAL_SERVER_ERROR);
+        monitor.check_all().await;
+        let selected = auditor.select_nodes().unwrap();
+        assert_eq!(selected, vec![0]);
+
+        // 只挑戰健康節點持有的 shard
+        let challenges = auditor.generate_challenges(&create_test_metadata(), 10, &selected);
+        assert!(challenges.iter().all(|challenge| challenge.shard_id < 8));
+        let routes = auditor.route_challenges(&challenges, &selected);
+        assert!(routes.iter().all(|node| *node == 0));
+
+        // 節點 0 的窗口 [ok, fail] 仍為 0.5，再失敗一次才被排除
+        nodes[0].set_health(StatusCode::INTERNAL_SERVER_ERROR);
+        monitor.check_all().await;
+        assert_eq!(auditor.select_nodes().unwrap(), vec![0]);
+        monitor.check_all().await;
+        match auditor.select_nodes() {
+            Err(AuditorError::StorageNodeUnreachable(msg)) => assert!(msg.contains("all 2")),
+            other => panic!("Expected StorageNodeUnreachable, got {:?}", other),
+        }
+
+        // 默認連續通過 3 次檢查後重新納入
+        nodes[0].set_health(StatusCode::OK);
+        nodes[1].set_health(StatusCode::OK);
+        for _ in 0..2 {
+            monitor.check_all().await;
+            assert!(auditor.select_nodes().is_err());
+        }
+        monitor.check_all().await;
+        assert_eq!(auditor.select_nodes().unwrap(), vec![0, 1]);
+    }
+
+    #[tokio::test]
+    async fn test_unreachable_node_only_fails_its_challenges() {
+        let reachable = crate::test_support::FakeStorageNode::start(
+            axum::http::StatusCode::NOT_FOUND,
+            "application/json",
+            br#"{"code": "sliver_not_stored"}"#.to_vec(),
+        )
+        .await;
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let dead = format!("http://{}", listener.local_addr().unwrap());
+        drop(listener);
+
+        // 首次連接失敗即熔斷，避免重試退避拖慢測試；順序執行以觀察跳過的挑戰
+        let config = AuditorConfig {
+            breaker: crate::breaker::BreakerConfig {
+                min_requests: 1,
+                ..Default::default()
+            },
+            max_parallel_challenges: 1,
+            ..Default::default()
+        };
+        let urls = vec![reachable.url().to_string(), dead.clone()];
+        let auditor = Auditor::new(config, "0xauditor".to_string(), urls).unwrap();
+
+        let metadata = create_test_metadata();
+        let challenges = auditor.generate_challenges(&metadata, 6, &[0, 1]);
+        let routes = auditor.route_challenges(&challenges, &[0, 1]);
+        let (results, unreachable) = auditor
+            .execute_challenges(
+                &metadata,
+                &challenges,
+                &routes,
+                None,
+                &ProgressSink::default(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(unreachable.into_iter().collect::<Vec<_>>(), vec![dead.clone()]);
+        for result in &results {
+            let reason = result.failure_reason.as_deref().unwrap();
+            match result.node_url.as_deref() {
+                Some(url) if url == reachable.url() => {
+                    assert!(reason.ends_with(" - sliver_not_stored"), "{}", reason)
+                }
+                Some(url) if url == dead => assert!(
+                    reason.contains("unreachable") || reason.contains("Circuit open"),
+                    "{}",
+                    reason
+                ),
+                other => panic!("unexpected node {:?}", other),
+            }
+        }
+        let skipped = results
+            .iter()
+            .filter(|r| r.failure_reason.as_deref().unwrap().contains("not retried"))
+            .count();
+        assert_eq!(skipped, 2);
+    }
+
+    #[tokio::test]
+    async fn test_walrus_api_style_verifies_against_metadata_root() {
+        use crate::crypto::merkle::{hash_leaf, MerkleTreeVersion};
+
+        let slivers: Vec<Vec<u8>> = (0..15u8).map(|i| vec![i; 128]).collect();
+        let node = crate::test_support::FakeStorageNode::start_walrus(slivers.clone()).await;
+        let mut metadata = create_test_metadata();
+        metadata.merkle_root = MerkleTree::from_leaf_hashes(
+            slivers.iter().map(|sliver| hash_leaf(sliver)).collect(),
+            MerkleTreeVersion::V2,
+        )
+        .unwrap()
+        .root()
+        .to_vec();
+
+        let execute = |auditor: Auditor, metadata: BlobMetadata| async move {
+            let challenges = auditor.generate_challenges(&metadata, 4, &[0]);
+            let routes = vec![0; challenges.len()];
+            let (results, _) = auditor
+                .execute_challenges(
+                    &metadata,
+                    &challenges,
+                    &routes,
+                    None,
+                    &ProgressSink::default(),
+                )
+                .await
+                .unwrap();
+            results
+        };
+        let urls = vec![node.url().to_string()];
+
+        // 配置選擇 Walrus 風格
+        let config = AuditorConfig {
+            storage_node_api_style: ApiStyle::Walrus,
+            ..Default::default()
+        };
+        let auditor = Auditor::new(config.clone(), "0xauditor".to_string(), urls.clone()).unwrap();
+        let results = execute(auditor, metadata.clone()).await;
+        assert_eq!(results.len(), 4);
+        assert!(results.iter().all(|r| r.verified && r.merkle_proof_valid));
+
+        // 默認的 challenge 風格：節點沒有 `POST /v1/challenge`
+        let auditor = Auditor::new(
+            AuditorConfig::default(),
+            "0xauditor".to_string(),
+            urls.clone(),
+        )
+        .unwrap();
+        let results = execute(auditor, metadata.clone()).await;
+        assert!(results.iter().all(|r| !r.verified));
+
+        // 逐節點覆蓋
+        let auditor = Auditor::new(
+            AuditorConfig::default(),
+            "0xauditor".to_string(),
+            urls.clone(),
+        )
+        .unwrap()
+        .with_node_api_style(&format!("{}/", node.url()), ApiStyle::Walrus);
+        let results = execute(auditor, metadata.clone()).await;
+        assert!(results.iter().all(|r| r.verified));
+
+        // 節點的哈希列表與鏈上根不符
+        metadata.merkle_root = vec![0; 32];
+        let auditor = Auditor::new(config, "0xauditor".to_string(), urls).unwrap();
+        let results = execute(auditor, metadata).await;
+        assert!(results.iter().all(|r| !r.verified && !r.merkle_proof_valid));
+    }
+
+    #[tokio::test]
+    async fn test_verify_blob_id_checks_metadata_root_before_challenging() {
+        use crate::blob_id::blob_id_from_metadata;
+        use crate::crypto::merkle::{hash_leaf, MerkleTreeVersion};
+
+        let slivers: Vec<Vec<u8>> = (0..15u8).map(|i| vec![i; 128]).collect();
+        let node = crate::test_support::FakeStorageNode::start_walrus(slivers.clone()).await;
+        let root = MerkleTree::from_leaf_hashes(
+            slivers.iter().map(|sliver| hash_leaf(sliver)).collect(),
+            MerkleTreeVersion::V2,
+        )
+        .unwrap()
+        .root();
+        let mut metadata = create_test_metadata();
+        metadata.merkle_root = root.to_vec();
+        metadata.blob_id =
+            blob_id_from_metadata(metadata.encoding_type, metadata.blob_size, &root).to_string();
+
+        let config = AuditorConfig {
+            storage_node_api_style: ApiStyle::Walrus,
+            verify_blob_id: true,
+            ..Default::default()
+        };
+        let auditor = Auditor::new(
+            config,
+            "0xauditor".to_string(),
+            vec![node.url().to_string()],
+        )
+        .unwrap();
+        let report = auditor.audit_blob_with_metadata(&metadata).await.unwrap();
+        assert!(report.total_challenges > 0);
+
+        // 根與 blob_id 不符：不發出挑戰
+        metadata.blob_size += 1;
+        let err = auditor
+            .audit_blob_with_metadata(&metadata)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, AuditorError::InvalidBlobId(_)), "{}", err);
+    }
+
+    #[tokio::test]
+    async fn test_challenge_results_record_transport_metrics() {
+        use crate::crypto::merkle::{hash_leaf, MerkleTreeVersion};
+
+        let slivers: Vec<Vec<u8>> = (0..15u8).map(|i| vec![i; 256]).collect();
+        let node = crate::test_support::FakeStorageNode::start_walrus(slivers.clone()).await;
+        // 首個挑戰的元數據請求過載一次，重試後成功
+        node.respond_next(axum::http::StatusCode::SERVICE_UNAVAILABLE, Some("0"));
+        let mut metadata = create_test_metadata();
+        metadata.merkle_root = MerkleTree::from_leaf_hashes(
+            slivers.iter().map(|sliver| hash_leaf(sliver)).collect(),
+            MerkleTreeVersion::V2,
+        )
+        .unwrap()
+        .root()
+        .to_vec();
+
+        let config = AuditorConfig {
+            storage_node_api_style: ApiStyle::Walrus,
+            max_parallel_challenges: 1,
+            ..Default::default()
+        };
+        let auditor = Auditor::new(
+            config,
+            "0xauditor".to_string(),
+            vec![node.url().to_string()],
+        )
+        .unwrap();
+        let challenges = auditor.generate_challenges(&metadata, 3, &[0]);
+        let routes = vec![0; challenges.len()];
+        let (results, _) = auditor
+            .execute_challenges(
+                &metadata,
+                &challenges,
+                &routes,
+                None,
+                &ProgressSink::default(),
+            )
+            .await
+            .unwrap();
+
+        let attempts: Vec<u8> = results.iter().map(|r| r.attempts).collect();
+        assert_eq!(attempts, vec![2, 1, 1]);
+        assert!(results.iter().all(|r| r.verified && r.sliver_size_bytes == 256));
+
+        let (successful, failed) = auditor.count_results(&results).unwrap();
+        let report = auditor
+            .generate_report("0xblob", &metadata, results, successful, failed)
+            .unwrap();
+        let stats = report.response_stats.unwrap();
+        assert_eq!(stats.sampled_challenges, 3);
+        assert_eq!(stats.bytes_transferred, 3 * 256);
+        assert!(stats.p50_response_ms <= stats.p95_response_ms);
+    }
+
+    /// 在響應延遲 `delay` 的單個假存儲節點上執行 `count` 個挑戰
+    async fn execute_against_slow_node(
+        config: AuditorConfig,
+        delay: std::time::Duration,
+        count: u16,
+    ) -> (
+        crate::test_support::FakeStorageNode,
+        Vec<AuditChallenge>,
+        Vec<ChallengeResult>,
+        std::time::Duration,
+    ) {
+        let node = crate::test_support::FakeStorageNode::start_delayed(
+            axum::http::StatusCode::NOT_FOUND,
+            "application/json",
+            br#"{"code": "sliver_not_stored"}"#.to_vec(),
+            delay,
+        )
+        .await;
+        let auditor = Auditor::new(
+            config,
+            "0xauditor".to_string(),
+            vec![node.url().to_string()],
+        )
+        .unwrap();
+
+        let metadata = create_test_metadata();
+        let challenges = auditor.generate_challenges(&metadata, count, &[0]);
+        let routes = vec![0; challenges.len()];
+        let started = Instant::now();
+        let (results, _) = auditor
+            .execute_challenges(
+                &metadata,
+                &challenges,
+                &routes,
+                None,
+                &ProgressSink::default(),
+            )
+            .await
+            .unwrap();
+        (node, challenges, results, started.elapsed())
+    }
+
+    #[tokio::test]
+    async fn test_challenges_run_in_parallel_in_challenge_order() {
+        let config = AuditorConfig {
+            max_parallel_challenges: 4,
+            ..Default::default()
+        };
+        let delay = std::time::Duration::from_millis(300);
+        let (node, challenges, results, elapsed) =
+            execute_against_slow_node(config, delay, 8).await;
+
+        assert_eq!(node.requests(), 8);
+        assert_eq!(node.max_in_flight(), 4);
+        // 兩輪而非八輪延遲
+        assert!(elapsed < delay * 6, "{:?}", elapsed);
+
+        let order: Vec<u64> = results.iter().map(|r| r.challenge.sliver_index).collect();
+        let expected: Vec<u64> = challenges.iter().map(|c| c.sliver_index).collect();
+        assert_eq!(order, expected);
+        assert!(results
+            .iter()
+            .all(|r| r.failure_reason.as_deref().unwrap().ends_with("sliver_not_stored")));
+    }
+
+    #[tokio::test]
+    async fn test_deadline_cancels_outstanding_challenges() {
+        let config = AuditorConfig {
+            max_parallel_challenges: 2,
+            audit_deadline_secs: 1,
+            ..Default::default()
+        };
+        let delay = std::time::Duration::from_secs(20);
+        let (node, challenges, results, elapsed) =
+            execute_against_slow_node(config, delay, 5).await;
+
+        assert!(elapsed < std::time::Duration::from_secs(5), "{:?}", elapsed);
+        // 只有前兩個挑戰發出過請求，其餘排隊中的也記為超時
+        assert_eq!(node.requests(), 2);
+        assert_eq!(results.len(), challenges.len());
+        for (result, challenge) in results.iter().zip(&challenges) {
+            assert_eq!(result.challenge.sliver_index, challenge.sliver_index);
+            assert!(!result.verified);
+            assert_eq!(result.failure_reason.as_deref(), Some("deadline exceeded"));
+            assert_eq!(result.node_url.as_deref(), Some(node.url()));
+        }
+    }
 }
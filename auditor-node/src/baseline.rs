@@ -0,0 +1,432 @@
+//! 內容基準（TOFU）與跨運行的哈希漂移檢測
+//!
+//! Walrus Blob ID 由內容導出，同一 Blob 的內容永遠不應改變。
+//! 因此每次審計都按 Blob ID 記錄觀測到的內容哈希、Merkle 根與文件大小：
+//!
+//! 1. 首次審計的觀測成為該 Blob 的基準（trust on first use）
+//! 2. 之後的審計與基準比對，任一字段不符即判定為 `CORRUPTED`，
+//!    報告中同時記錄預期值與觀測值（[`HashDrift`]）
+//!
+//! 觀測以 JSONL 追加寫入，每行一條 [`AuditObservation`]，啟動時重新加載；
+//! 文件按 `[rotation.outputs.baseline]` 輪轉（見 [`crate::rotating_writer`]）。
+//! [`BaselineStore::prune`] 按時間清理舊觀測，但只要 Blob 仍有較新的觀測，
+//! 其基準就會保留，避免清理後把已漂移的內容重新當作基準。
+
+use crate::crypto::ct_eq_hex;
+use crate::error::Result;
+use crate::rotating_writer::{self, RotatingWriter, RotationConfig, RotationStats};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use tracing::{debug, warn};
+
+/// `[rotation.outputs]` 中觀測文件的名稱
+pub const ROTATION_OUTPUT: &str = "baseline";
+
+/// 默認保留期：90 天
+pub const DEFAULT_BASELINE_MAX_AGE_SECS: u64 = 90 * 24 * 60 * 60;
+
+/// 基準配置
+///
+/// ```toml
+/// [baseline]
+/// enabled = true
+/// path = "./audit_baselines.jsonl"
+/// max_age_secs = 7776000
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BaselineConfig {
+    /// 是否記錄觀測並檢測漂移
+    pub enabled: bool,
+
+    /// 觀測文件（JSONL）
+    pub path: String,
+
+    /// 觀測保留期（秒，0 表示永久保留）
+    pub max_age_secs: u64,
+}
+
+impl Default for BaselineConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            path: "./audit_baselines.jsonl".to_string(),
+            max_age_secs: DEFAULT_BASELINE_MAX_AGE_SECS,
+        }
+    }
+}
+
+/// 一次審計對 Blob 內容的觀測
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditObservation {
+    /// Blob ID
+    pub blob_id: String,
+
+    /// 內容哈希（SHA-256，十六進制）
+    pub content_hash: String,
+
+    /// Merkle 根（Blake2b-256，十六進制）
+    pub merkle_root: String,
+
+    /// 文件大小（bytes）
+    pub file_size: u64,
+
+    /// 觀測時間（Unix 時間，秒）
+    pub observed_at: u64,
+}
+
+impl AuditObservation {
+    /// 內容是否與另一次觀測一致
+    ///
+    /// 哈希與 Merkle 根解碼後以常量時間比較；任一方不是合法的十六進制時返回 `InvalidDigest` 錯誤
+    pub fn same_content(&self, other: &AuditObservation) -> Result<bool> {
+        Ok(ct_eq_hex(&self.content_hash, &other.content_hash)?
+            & ct_eq_hex(&self.merkle_root, &other.merkle_root)?
+            & (self.file_size == other.file_size))
+    }
+}
+
+/// 與基準不符的觀測：預期值（基準）與觀測值
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HashDrift {
+    /// 基準內容哈希
+    pub expected_content_hash: String,
+
+    /// 觀測到的內容哈希
+    pub observed_content_hash: String,
+
+    /// 基準 Merkle 根
+    pub expected_merkle_root: String,
+
+    /// 觀測到的 Merkle 根
+    pub observed_merkle_root: String,
+
+    /// 基準文件大小
+    pub expected_file_size: u64,
+
+    /// 觀測到的文件大小
+    pub observed_file_size: u64,
+
+    /// 基準的記錄時間（Unix 時間，秒）
+    pub baseline_at: u64,
+}
+
+impl HashDrift {
+    fn between(baseline: &AuditObservation, observed: &AuditObservation) -> Self {
+        Self {
+            expected_content_hash: baseline.content_hash.clone(),
+            observed_content_hash: observed.content_hash.clone(),
+            expected_merkle_root: baseline.merkle_root.clone(),
+            observed_merkle_root: observed.merkle_root.clone(),
+            expected_file_size: baseline.file_size,
+            observed_file_size: observed.file_size,
+            baseline_at: baseline.observed_at,
+        }
+    }
+}
+
+/// 按 Blob ID 索引的內容觀測
+pub struct BaselineStore {
+    /// 持久化文件（`None` 表示僅在內存中）
+    file: Option<RotatingWriter>,
+
+    /// Blob ID → 觀測（按記錄順序，第一條為基準）
+    by_blob: Mutex<HashMap<String, Vec<AuditObservation>>>,
+}
+
+impl BaselineStore {
+    /// 僅在內存中保存的觀測
+    pub fn in_memory() -> Self {
+        Self {
+            file: None,
+            by_blob: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// 打開（或創建）JSONL 觀測文件並加載已有條目（不輪轉）
+    ///
+    /// 無法解析的行會被跳過並記錄警告
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        Self::open_with_rotation(path, RotationConfig::disabled())
+    }
+
+    /// 打開（或創建）JSONL 觀測文件，加載已有條目（包括仍保留的輪轉文件），之後按 `rotation` 輪轉
+    ///
+    /// 基準所在的輪轉文件被保留策略刪除後，下一次觀測會成為新的基準，
+    /// 因此輪轉的保留策略應寬於 `max_age_secs`。
+    pub fn open_with_rotation(path: impl AsRef<Path>, rotation: RotationConfig) -> Result<Self> {
+        let path = path.as_ref();
+        let mut by_blob: HashMap<String, Vec<AuditObservation>> = HashMap::new();
+
+        for (line_no, line) in rotating_writer::read_to_string(path)?.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str::<AuditObservation>(line) {
+                Ok(observation) => by_blob
+                    .entry(observation.blob_id.clone())
+                    .or_default()
+                    .push(observation),
+                Err(e) => warn!(
+                    "Skipping invalid baseline line {} in {}: {}",
+                    line_no + 1,
+                    path.display(),
+                    e
+                ),
+            }
+        }
+
+        debug!(
+            "Loaded content baselines for {} blobs from {}",
+            by_blob.len(),
+            path.display()
+        );
+
+        Ok(Self {
+            file: Some(RotatingWriter::open(path, rotation)?),
+            by_blob: Mutex::new(by_blob),
+        })
+    }
+
+    /// 觀測文件的輪轉計數器（僅在內存中時為 `None`）
+    pub fn rotation_stats(&self) -> Option<Arc<RotationStats>> {
+        self.file.as_ref().map(RotatingWriter::stats)
+    }
+
+    /// Blob 的基準（首次觀測）
+    pub fn baseline(&self, blob_id: &str) -> Option<AuditObservation> {
+        self.by_blob
+            .lock()
+            .unwrap()
+            .get(blob_id)
+            .and_then(|observations| observations.first().cloned())
+    }
+
+    /// 記錄一次觀測並與基準比對
+    ///
+    /// # 返回
+    /// - `None`: 首次觀測（成為基準）或與基準一致
+    /// - `Some(HashDrift)`: 與基準不符
+    pub fn observe(&self, observation: AuditObservation) -> Result<Option<HashDrift>> {
+        let mut by_blob = self.by_blob.lock().unwrap();
+
+        let drift = match by_blob
+            .get(&observation.blob_id)
+            .and_then(|observations| observations.first())
+        {
+            Some(baseline) if !baseline.same_content(&observation)? => {
+                Some(HashDrift::between(baseline, &observation))
+            }
+            _ => None,
+        };
+
+        if let Some(file) = &self.file {
+            file.append_line(&serde_json::to_string(&observation)?)?;
+        }
+
+        by_blob
+            .entry(observation.blob_id.clone())
+            .or_default()
+            .push(observation);
+        Ok(drift)
+    }
+
+    /// 清理早於 `now - max_age_secs` 的觀測，返回清理的條數
+    ///
+    /// Blob 仍有保留期內的觀測時，其基準不被清理；所有觀測都過期的 Blob 整體移除。
+    /// 有條目被清理時重寫文件（臨時文件 + 重命名）並刪除輪轉文件。
+    pub fn prune(&self, now: u64, max_age_secs: u64) -> Result<usize> {
+        if max_age_secs == 0 {
+            return Ok(0);
+        }
+        let cutoff = now.saturating_sub(max_age_secs);
+
+        let mut by_blob = self.by_blob.lock().unwrap();
+        let before: usize = by_blob.values().map(Vec::len).sum();
+
+        by_blob.retain(|_, observations| {
+            if observations.iter().all(|o| o.observed_at < cutoff) {
+                return false;
+            }
+            let mut index = 0;
+            observations.retain(|o| {
+                let keep = index == 0 || o.observed_at >= cutoff;
+                index += 1;
+                keep
+            });
+            true
+        });
+
+        let pruned = before - by_blob.values().map(Vec::len).sum::<usize>();
+        if pruned == 0 {
+            return Ok(0);
+        }
+
+        if let Some(file) = &self.file {
+            let mut contents = String::new();
+            for observation in by_blob.values().flatten() {
+                contents.push_str(&serde_json::to_string(observation)?);
+                contents.push('\n');
+            }
+            file.replace_contents(&contents)?;
+        }
+
+        debug!(
+            "Pruned {} content observations older than {}",
+            pruned, cutoff
+        );
+        Ok(pruned)
+    }
+
+    /// 已記錄的觀測總數
+    pub fn len(&self) -> usize {
+        self.by_blob.lock().unwrap().values().map(Vec::len).sum()
+    }
+
+    /// 是否沒有任何觀測
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl std::fmt::Debug for BaselineStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BaselineStore")
+            .field("path", &self.file.as_ref().map(RotatingWriter::path))
+            .field("observations", &self.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::integrity::{IntegrityVerifier, VerificationStatus};
+    use crate::test_support::{content_hash, deterministic_blob, AggregatorMode, FakeAggregator};
+    use crate::types::{AuditReport, BlobId};
+    use std::sync::Arc;
+
+    fn observation(blob_id: &str, content_hash: &str, observed_at: u64) -> AuditObservation {
+        AuditObservation {
+            blob_id: blob_id.to_string(),
+            content_hash: content_hash.to_string(),
+            merkle_root: "00".to_string(),
+            file_size: 4096,
+            observed_at,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_changed_blob_between_audits_is_corrupted() {
+        let path =
+            std::env::temp_dir().join(format!("audit_baselines_{}.jsonl", rand::random::<u32>()));
+        let original = deterministic_blob(20 * 4096);
+        let mut changed = original.clone();
+        changed[5 * 4096] ^= 0xff;
+
+        // 第一次審計：記錄基準
+        let aggregator = FakeAggregator::start(original.clone(), AggregatorMode::Healthy).await;
+        let verifier = IntegrityVerifier::new(aggregator.url().to_string())
+            .with_baseline(Arc::new(BaselineStore::open(&path).unwrap()));
+        let blob_a = BlobId::from_bytes([0xa; 32]);
+        let first = verifier.audit_blob(&blob_a).await.unwrap();
+        assert_eq!(first.verification_status, VerificationStatus::Accessible);
+        assert!(first.hash_drift.is_none());
+
+        // 重啟後（重新加載文件），同一 Blob 返回了不同內容
+        let aggregator = FakeAggregator::start(changed.clone(), AggregatorMode::Healthy).await;
+        let verifier = IntegrityVerifier::new(aggregator.url().to_string())
+            .with_baseline(Arc::new(BaselineStore::open(&path).unwrap()));
+        let second = verifier.audit_blob(&blob_a).await.unwrap();
+        assert_eq!(second.verification_status, VerificationStatus::Corrupted);
+
+        let drift = second.hash_drift.clone().unwrap();
+        assert_eq!(drift.expected_content_hash, content_hash(&original));
+        assert_eq!(drift.observed_content_hash, content_hash(&changed));
+        assert_eq!(drift.expected_merkle_root, first.merkle_root);
+        assert_ne!(drift.observed_merkle_root, first.merkle_root);
+
+        // 報告同時帶有預期值與觀測值，且判定為失敗
+        let report = AuditReport::from(second);
+        assert!(!report.is_valid);
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(
+            json["integrity"]["hash_drift"]["expected_content_hash"],
+            content_hash(&original)
+        );
+
+        // 基準不會被漂移的內容取代
+        let store = BaselineStore::open(&path).unwrap();
+        assert_eq!(
+            store.baseline(&blob_a.to_string()).unwrap().content_hash,
+            content_hash(&original)
+        );
+        assert_eq!(store.len(), 2);
+
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_matching_observations_do_not_drift() {
+        let store = BaselineStore::in_memory();
+        assert!(store
+            .observe(observation("blob-a", "c0ffee", 100))
+            .unwrap()
+            .is_none());
+        assert!(store
+            .observe(observation("blob-a", "c0ffee", 200))
+            .unwrap()
+            .is_none());
+        assert!(store
+            .observe(observation("blob-b", "beef", 200))
+            .unwrap()
+            .is_none());
+
+        let mut resized = observation("blob-a", "c0ffee", 300);
+        resized.file_size = 4097;
+        let drift = store.observe(resized).unwrap().unwrap();
+        assert_eq!(drift.expected_file_size, 4096);
+        assert_eq!(drift.observed_file_size, 4097);
+        assert_eq!(drift.baseline_at, 100);
+    }
+
+    #[test]
+    fn test_same_content_compares_decoded_hashes() {
+        let baseline = observation("blob-a", "c0ffee", 100);
+        assert!(baseline
+            .same_content(&observation("blob-a", "C0FFEE", 200))
+            .unwrap());
+        assert!(!baseline
+            .same_content(&observation("blob-a", "c0ffef", 200))
+            .unwrap());
+        assert!(matches!(
+            baseline.same_content(&observation("blob-a", "coffee", 200)),
+            Err(crate::error::AuditorError::InvalidDigest(_))
+        ));
+    }
+
+    #[test]
+    fn test_prune_keeps_baseline_of_active_blobs() {
+        let path =
+            std::env::temp_dir().join(format!("audit_baselines_{}.jsonl", rand::random::<u32>()));
+        let store = BaselineStore::open(&path).unwrap();
+        store.observe(observation("blob-a", "c0ffee", 100)).unwrap();
+        store.observe(observation("blob-a", "c0ffee", 200)).unwrap();
+        store.observe(observation("blob-a", "c0ffee", 900)).unwrap();
+        store.observe(observation("blob-b", "beef", 150)).unwrap();
+
+        // 保留期 500 秒：blob-a 保留基準與 900，blob-b 整體移除
+        assert_eq!(store.prune(1_000, 500).unwrap(), 2);
+        assert_eq!(store.prune(1_000, 500).unwrap(), 0);
+        assert_eq!(store.prune(1_000, 0).unwrap(), 0);
+
+        let reopened = BaselineStore::open(&path).unwrap();
+        assert_eq!(reopened.len(), 2);
+        assert_eq!(reopened.baseline("blob-a").unwrap().observed_at, 100);
+        assert!(reopened.baseline("blob-b").is_none());
+
+        std::fs::remove_file(&path).ok();
+    }
+}
@@ -0,0 +1,559 @@
+//! 命令行批量審計
+//!
+//! 單次審計命令接受多個 Blob（重複的 `--blob-id`，或 `--blob-file` 列表文件，`-` 表示標準輸入）。
+//! 所有 Blob 共用同一條 [`AuditPipeline`](crate::pipeline::AuditPipeline)（密鑰庫與配置只加載一次），
+//! 由 [`run_batch`] 以有限並發執行：
+//!
+//! - 單個 Blob 失敗（審計出錯或未通過）不影響其他 Blob，結果逐個記錄在 [`BatchSummary`] 中
+//! - `fail_fast` 時第一個失敗之後不再開始新的審計，未開始的 Blob 記為 `not_run`
+//! - 配置了報告目錄時，每份已簽名報告寫入 `<report_dir>/<blob_id>.json`
+//!
+//! 列表文件每行一個 Blob ID，忽略空行與 `#` 開頭的註釋行（見 [`parse_blob_list`]）：
+//!
+//! ```text
+//! # 2024-11 審計批次
+//! eRrTusk8yshFQpkemgDnbg0f4-qDo623V2NpeVG1Zcg
+//!
+//! 0x2a0c6e0a5d6b1ec25f0e5b4b3d1d6a3a0b8e3f9c4d2e1f0a9b8c7d6e5f4a3b2c
+//! ```
+//!
+//! 摘要以 JSON 輸出，結果順序與輸入順序一致：
+//!
+//! ```json
+//! {
+//!   "total": 2, "passed": 1, "failed": 0, "skipped": 0, "errors": 1, "not_run": 0,
+//!   "results": [
+//!     { "blob_id": "eRrT…", "status": "passed", "verification_status": "ACCESSIBLE",
+//!       "report_path": "reports/eRrT….json", "report_blob_id": "Xk3…", "duration_ms": 812 },
+//!     { "blob_id": "0x2a0c…", "status": "error", "error": "…", "duration_ms": 30012 }
+//!   ]
+//! }
+//! ```
+
+use crate::error::{AuditorError, Result};
+use crate::integrity::VerificationStatus;
+use crate::pipeline::PipelineOutcome;
+use crate::reaudit::AuditOutcome;
+use crate::report::ReportFormat;
+use futures::stream::{self, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::future::Future;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Instant;
+use tracing::{error, info, warn};
+
+/// `--blob-file` 中表示標準輸入的路徑
+pub const STDIN_PATH: &str = "-";
+
+/// 默認並發審計數
+pub const DEFAULT_BATCH_CONCURRENCY: usize = 4;
+
+/// 解析 Blob 列表：每行一個 Blob ID，忽略空行與 `#` 開頭的註釋行
+pub fn parse_blob_list(text: &str) -> Vec<String> {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(str::to_string)
+        .collect()
+}
+
+/// 讀取 Blob 列表文件（`-` 表示標準輸入）
+pub fn read_blob_list(path: &Path) -> Result<Vec<String>> {
+    let text = if path == Path::new(STDIN_PATH) {
+        let mut text = String::new();
+        std::io::stdin().read_to_string(&mut text)?;
+        text
+    } else {
+        fs::read_to_string(path).map_err(|e| {
+            AuditorError::Io(std::io::Error::new(
+                e.kind(),
+                format!("Failed to read blob list {}: {}", path.display(), e),
+            ))
+        })?
+    };
+    Ok(parse_blob_list(&text))
+}
+
+/// 合併命令行與列表文件中的 Blob ID，保持首次出現的順序並去重
+pub fn merge_blob_ids(
+    flags: impl IntoIterator<Item = String>,
+    listed: impl IntoIterator<Item = String>,
+) -> Vec<String> {
+    let mut seen = HashSet::new();
+    flags
+        .into_iter()
+        .chain(listed)
+        .filter(|blob_id| seen.insert(blob_id.clone()))
+        .collect()
+}
+
+/// 批量審計選項
+#[derive(Debug, Clone)]
+pub struct BatchOptions {
+    /// 同時執行的審計數（至少 1）
+    pub concurrency: usize,
+
+    /// 第一個失敗之後不再開始新的審計
+    pub fail_fast: bool,
+
+    /// 已簽名報告的寫入目錄（未設置時不寫入）
+    pub report_dir: Option<PathBuf>,
+}
+
+impl Default for BatchOptions {
+    fn default() -> Self {
+        Self {
+            concurrency: DEFAULT_BATCH_CONCURRENCY,
+            fail_fast: false,
+            report_dir: None,
+        }
+    }
+}
+
+/// 單個 Blob 的批量審計結果
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BlobAuditStatus {
+    /// 審計通過
+    Passed,
+    /// 審計完成但未通過（損壞、不可達或有挑戰失敗）
+    Failed,
+    /// Blob 已被所有者刪除或存儲期已結束（不計為失敗）
+    Skipped,
+    /// 審計過程出錯，沒有報告
+    Error,
+    /// `fail_fast` 生效後未開始
+    NotRun,
+}
+
+impl BlobAuditStatus {
+    /// 是否計為失敗（決定進程退出碼）
+    pub fn is_failure(self) -> bool {
+        matches!(self, Self::Failed | Self::Error)
+    }
+}
+
+/// 單個 Blob 的摘要
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlobAuditSummary {
+    /// Blob ID（與輸入相同）
+    pub blob_id: String,
+
+    /// 結果
+    pub status: BlobAuditStatus,
+
+    /// 審計時 Blob 的可訪問狀態
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub verification_status: Option<VerificationStatus>,
+
+    /// 本地報告文件
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub report_path: Option<PathBuf>,
+
+    /// 報告在 Walrus 上的 Blob ID
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub report_blob_id: Option<String>,
+
+    /// 提交交易的標識
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tx_digest: Option<String>,
+
+    /// 錯誤信息（`error` 時）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+
+    /// 審計耗時（毫秒；`not_run` 時為空）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration_ms: Option<u64>,
+}
+
+impl BlobAuditSummary {
+    /// 由流水線結果生成摘要
+    pub fn from_outcome(blob_id: &str, outcome: &PipelineOutcome) -> Self {
+        let status =
+            match AuditOutcome::from_audit(&outcome.status, outcome.report.failed_verifications) {
+                AuditOutcome::Pass => BlobAuditStatus::Passed,
+                AuditOutcome::Fail => BlobAuditStatus::Failed,
+                AuditOutcome::Deleted => BlobAuditStatus::Skipped,
+            };
+        Self {
+            status,
+            verification_status: Some(outcome.status.clone()),
+            report_blob_id: outcome.report_blob_id.clone(),
+            tx_digest: outcome.tx_digest.clone(),
+            ..Self::new(blob_id, status)
+        }
+    }
+
+    /// 審計出錯的摘要
+    pub fn from_error(blob_id: &str, error: &AuditorError) -> Self {
+        Self {
+            error: Some(error.to_string()),
+            ..Self::new(blob_id, BlobAuditStatus::Error)
+        }
+    }
+
+    fn new(blob_id: &str, status: BlobAuditStatus) -> Self {
+        Self {
+            blob_id: blob_id.to_string(),
+            status,
+            verification_status: None,
+            report_path: None,
+            report_blob_id: None,
+            tx_digest: None,
+            error: None,
+            duration_ms: None,
+        }
+    }
+}
+
+/// 批量審計摘要
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BatchSummary {
+    /// Blob 總數
+    pub total: usize,
+    /// 通過數
+    pub passed: usize,
+    /// 未通過數
+    pub failed: usize,
+    /// 已刪除或過期而跳過的數量
+    pub skipped: usize,
+    /// 出錯數
+    pub errors: usize,
+    /// 未開始的數量
+    pub not_run: usize,
+    /// 逐個 Blob 的結果（與輸入順序一致）
+    pub results: Vec<BlobAuditSummary>,
+}
+
+impl BatchSummary {
+    /// 由逐個結果匯總
+    pub fn from_results(results: Vec<BlobAuditSummary>) -> Self {
+        let count = |status| results.iter().filter(|r| r.status == status).count();
+        Self {
+            total: results.len(),
+            passed: count(BlobAuditStatus::Passed),
+            failed: count(BlobAuditStatus::Failed),
+            skipped: count(BlobAuditStatus::Skipped),
+            errors: count(BlobAuditStatus::Error),
+            not_run: count(BlobAuditStatus::NotRun),
+            results,
+        }
+    }
+
+    /// 是否有 Blob 未通過或出錯
+    pub fn has_failures(&self) -> bool {
+        self.failed > 0 || self.errors > 0
+    }
+}
+
+/// 以有限並發審計所有 Blob
+///
+/// `audit` 對單個 Blob 執行完整流水線（通常為 `AuditPipeline::run_once`）；
+/// 單個 Blob 的錯誤記錄在摘要中，不會中止批量審計（`fail_fast` 除外）
+pub async fn run_batch<F, Fut>(
+    blob_ids: Vec<String>,
+    options: &BatchOptions,
+    audit: F,
+) -> BatchSummary
+where
+    F: Fn(String) -> Fut,
+    Fut: Future<Output = Result<PipelineOutcome>>,
+{
+    let total = blob_ids.len();
+    let aborted = AtomicBool::new(false);
+    let (audit, aborted) = (&audit, &aborted);
+
+    info!(
+        "Auditing {} blobs ({} at a time{})",
+        total,
+        options.concurrency.max(1),
+        if options.fail_fast { ", fail-fast" } else { "" }
+    );
+    let results = stream::iter(blob_ids.into_iter().enumerate())
+        .map(|(index, blob_id)| async move {
+            if aborted.load(Ordering::SeqCst) {
+                return BlobAuditSummary::new(&blob_id, BlobAuditStatus::NotRun);
+            }
+
+            info!("[{}/{}] Auditing blob {}", index + 1, total, blob_id);
+            let start = Instant::now();
+            let mut summary = match audit(blob_id.clone()).await {
+                Ok(outcome) => {
+                    let mut summary = BlobAuditSummary::from_outcome(&blob_id, &outcome);
+                    if let Some(dir) = &options.report_dir {
+                        match write_report(dir, &blob_id, &outcome) {
+                            Ok(path) => summary.report_path = Some(path),
+                            Err(e) => {
+                                summary.status = BlobAuditStatus::Error;
+                                summary.error = Some(e.to_string());
+                            }
+                        }
+                    }
+                    summary
+                }
+                Err(e) => BlobAuditSummary::from_error(&blob_id, &e),
+            };
+            summary.duration_ms = Some(start.elapsed().as_millis() as u64);
+
+            match summary.status {
+                BlobAuditStatus::Error => error!(
+                    "Blob {} audit failed: {}",
+                    blob_id,
+                    summary.error.as_deref().unwrap_or_default()
+                ),
+                BlobAuditStatus::Failed => warn!("Blob {} failed integrity audit", blob_id),
+                _ => info!("Blob {} audit {:?}", blob_id, summary.status),
+            }
+            if options.fail_fast && summary.status.is_failure() {
+                aborted.store(true, Ordering::SeqCst);
+            }
+            summary
+        })
+        .buffered(options.concurrency.max(1))
+        .collect::<Vec<_>>()
+        .await;
+
+    BatchSummary::from_results(results)
+}
+
+/// 將已簽名報告寫入 `<dir>/<blob_id>.json`
+fn write_report(dir: &Path, blob_id: &str, outcome: &PipelineOutcome) -> Result<PathBuf> {
+    fs::create_dir_all(dir)?;
+    let path = dir.join(format!("{}.json", report_file_stem(blob_id)));
+    let bytes = ReportFormat::Json.encode(&outcome.report)?;
+    fs::write(&path, bytes).map_err(|e| {
+        AuditorError::Io(std::io::Error::new(
+            e.kind(),
+            format!("Failed to write report to {}: {}", path.display(), e),
+        ))
+    })?;
+    Ok(path)
+}
+
+/// 報告文件名（Blob ID 中 URL-safe Base64 與十六進制以外的字符替換為 `_`）
+fn report_file_stem(blob_id: &str) -> String {
+    blob_id
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::integrity::AuditData;
+    use crate::types::{AuditMethod, AuditReport};
+
+    fn outcome(status: VerificationStatus, failed: u16) -> PipelineOutcome {
+        let report = AuditReport::from(AuditData {
+            blob_id: "blob".to_string(),
+            content_hash: "ab".repeat(32),
+            merkle_root: "cd".repeat(32),
+            total_challenges: 4,
+            successful_verifications: 4 - failed,
+            failed_verifications: failed,
+            file_size: 4096,
+            timestamp: 1_700_000_000,
+            verification_status: status.clone(),
+            sui_object_id: None,
+            resource_decision: None,
+            capture_digest: None,
+            deduplicated_from: None,
+            producer: None,
+            chunk_filter: None,
+            challenge_reveal: None,
+            challenge_seed_source: None,
+            challenge_seed: None,
+            hash_drift: None,
+            blob_id_verified: None,
+            chunk_size: None,
+            blob_object_id: None,
+            encoding_k: None,
+            encoding_n: None,
+            start_epoch: None,
+            end_epoch: None,
+            onchain_merkle_root: None,
+            size_matches_chain: None,
+            audit_method: AuditMethod::Aggregator,
+        });
+        PipelineOutcome {
+            status,
+            report,
+            published: None,
+            uploaded: Vec::new(),
+            encryption: None,
+            report_blob_id: Some("report-blob".to_string()),
+            submission: None,
+            tx_digest: None,
+        }
+    }
+
+    /// 以 Blob ID 前綴決定結果：`ok` 通過、`bad` 未通過、`gone` 已刪除、其餘出錯
+    async fn fake_audit(blob_id: String) -> Result<PipelineOutcome> {
+        if blob_id.starts_with("ok") {
+            Ok(outcome(VerificationStatus::Accessible, 0))
+        } else if blob_id.starts_with("bad") {
+            Ok(outcome(VerificationStatus::Accessible, 1))
+        } else if blob_id.starts_with("gone") {
+            Ok(outcome(VerificationStatus::Deleted, 0))
+        } else {
+            Err(AuditorError::Config(format!("no such blob {}", blob_id)))
+        }
+    }
+
+    fn ids(ids: &[&str]) -> Vec<String> {
+        ids.iter().map(|id| id.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_blob_list_skips_blank_lines_and_comments() {
+        let text = "# batch 1\n\
+                    eRrTusk8yshFQpkemgDnbg0f4-qDo623V2NpeVG1Zcg\n\
+                    \n\
+                    \t0x2a0c  \r\n\
+                    \x20  # indented comment\n\
+                    last";
+        assert_eq!(
+            parse_blob_list(text),
+            ids(&[
+                "eRrTusk8yshFQpkemgDnbg0f4-qDo623V2NpeVG1Zcg",
+                "0x2a0c",
+                "last"
+            ])
+        );
+        assert!(parse_blob_list("\n# only comments\n\n").is_empty());
+    }
+
+    #[test]
+    fn test_read_blob_list_from_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("blobs.txt");
+        fs::write(&path, "a\n# skip\nb\n").unwrap();
+        assert_eq!(read_blob_list(&path).unwrap(), ids(&["a", "b"]));
+        assert!(read_blob_list(&dir.path().join("missing.txt")).is_err());
+    }
+
+    #[test]
+    fn test_merge_blob_ids_keeps_first_occurrence() {
+        let merged = merge_blob_ids(ids(&["b", "a"]), ids(&["a", "c", "b"]));
+        assert_eq!(merged, ids(&["b", "a", "c"]));
+    }
+
+    #[tokio::test]
+    async fn test_failures_do_not_abort_the_batch() {
+        let dir = tempfile::tempdir().unwrap();
+        let options = BatchOptions {
+            concurrency: 2,
+            fail_fast: false,
+            report_dir: Some(dir.path().join("reports")),
+        };
+        let summary = run_batch(
+            ids(&["ok-1", "missing", "bad-1", "gone-1", "ok/2"]),
+            &options,
+            fake_audit,
+        )
+        .await;
+
+        assert_eq!(summary.total, 5);
+        assert_eq!(
+            (
+                summary.passed,
+                summary.failed,
+                summary.skipped,
+                summary.errors,
+                summary.not_run
+            ),
+            (2, 1, 1, 1, 0)
+        );
+        assert!(summary.has_failures());
+
+        // 結果順序與輸入一致
+        let statuses: Vec<_> = summary.results.iter().map(|r| r.status).collect();
+        assert_eq!(
+            statuses,
+            vec![
+                BlobAuditStatus::Passed,
+                BlobAuditStatus::Error,
+                BlobAuditStatus::Failed,
+                BlobAuditStatus::Skipped,
+                BlobAuditStatus::Passed,
+            ]
+        );
+
+        let passed = &summary.results[0];
+        assert_eq!(passed.report_blob_id.as_deref(), Some("report-blob"));
+        let report_path = passed.report_path.as_ref().unwrap();
+        let report: AuditReport = serde_json::from_slice(&fs::read(report_path).unwrap()).unwrap();
+        assert_eq!(report.blob_id, "blob");
+        assert_eq!(
+            summary.results[4]
+                .report_path
+                .as_ref()
+                .unwrap()
+                .file_name()
+                .unwrap(),
+            "ok_2.json"
+        );
+
+        let errored = &summary.results[1];
+        assert!(errored.error.as_deref().unwrap().contains("no such blob"));
+        assert!(errored.report_path.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_fail_fast_stops_scheduling() {
+        let options = BatchOptions {
+            concurrency: 1,
+            fail_fast: true,
+            report_dir: None,
+        };
+        let summary = run_batch(
+            ids(&["ok-1", "bad-1", "ok-2", "ok-3"]),
+            &options,
+            fake_audit,
+        )
+        .await;
+
+        assert_eq!(summary.passed, 1);
+        assert_eq!(summary.failed, 1);
+        assert_eq!(summary.not_run, 2);
+        assert!(summary.results[2].duration_ms.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_summary_json_structure() {
+        let summary = run_batch(
+            ids(&["ok-1", "missing"]),
+            &BatchOptions::default(),
+            fake_audit,
+        )
+        .await;
+        let json = serde_json::to_value(&summary).unwrap();
+
+        assert_eq!(json["total"], 2);
+        assert_eq!(json["passed"], 1);
+        assert_eq!(json["errors"], 1);
+        assert_eq!(json["results"][0]["blob_id"], "ok-1");
+        assert_eq!(json["results"][0]["status"], "passed");
+        assert_eq!(json["results"][0]["verification_status"], "ACCESSIBLE");
+        assert_eq!(json["results"][0]["report_blob_id"], "report-blob");
+        assert!(json["results"][0].get("report_path").is_none());
+        assert!(json["results"][0].get("error").is_none());
+        assert_eq!(json["results"][1]["status"], "error");
+        assert!(json["results"][1].get("report_blob_id").is_none());
+
+        let parsed: BatchSummary = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed, summary);
+    }
+}
@@ -4,6 +4,7 @@
 
 use anyhow::Result;
 use auditor_node::integrity::{IntegrityVerifier, AuditData};
+use auditor_node::types::BlobId;
 use tracing_subscriber;
 use serde_json;
 
@@ -23,7 +24,7 @@ async fn main() -> Result<()> {
     let verifier = IntegrityVerifier::new(aggregator_url);
 
     // Test with real Walrus Testnet blob
-    let blob_id = "eRrTusk8yshFQpkemgDnbg0f4-qDo623V2NpeVG1Zcg";
+    let blob_id = BlobId::parse("eRrTusk8yshFQpkemgDnbg0f4-qDo623V2NpeVG1Zcg")?;
 
     println!("📋 Test Configuration:");
     println!("   Blob ID: {}", blob_id);
@@ -34,7 +35,7 @@ async fn main() -> Result<()> {
     println!("🚀 Starting audit...\n");
 
     // Execute audit (includes Merkle verification)
-    let audit_data = match verifier.audit_blob(blob_id).await {
+    let audit_data = match verifier.audit_blob(&blob_id).await {
         Ok(data) => {
             print_audit_results(&data);
             data
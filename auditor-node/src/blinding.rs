@@ -0,0 +1,284 @@
+//! 公開報告中的 Blob ID 盲化
+//!
+//! 部分客戶不希望其數據的 Blob ID 出現在公開發布的審計報告中，但仍需要逐 Blob 問責。
+//! 啟用 `blind_blob_ids = true` 後，發布的報告副本：
+//!
+//! - `blob_id` 替換為 `HMAC-SHA256(blob_id, salt)`（十六進制），`salt` 是保存在密鑰庫中的
+//!   每審計員秘密鹽（[`BlindingSalt`]）
+//! - `blinding_key_id` 記錄鹽的指紋，持有鹽的授權方可以重新計算映射（[`BlindingSalt::unblind`]）
+//! - 清空 `blob_object_id` 與去重依據中的 Blob ID（它們同樣可以反查出 Blob）
+//! - 去掉聯署與挑戰集公開（二者綁定真實 Blob ID，在盲化副本上無法驗證）
+//! - 由審計員重新簽名：盲化 ID 就是發布版本中被簽名的內容，普通驗證流程照常適用
+//!
+//! 同一鹽下盲化是確定性的，因此按 `blob_id` 聚合的統計在盲化報告上保持一致。
+//! 真實 ID 只保留在審計員本地的報告與歷史中。
+
+use crate::error::{AuditorError, Result};
+use crate::report::ReportManager;
+use crate::trust;
+use crate::types::AuditReport;
+use hmac::{Hmac, Mac};
+use pqc_signer::Signer;
+use rand::RngCore;
+use sha2::Sha256;
+use std::fs;
+use std::path::Path;
+use tracing::info;
+
+/// 密鑰庫中的鹽文件名
+pub const BLINDING_SALT_FILE: &str = "blinding_salt.key";
+
+/// 鹽長度（字節）
+pub const BLINDING_SALT_LEN: usize = 32;
+
+/// 盲化 Blob ID 的秘密鹽
+#[derive(Clone, PartialEq, Eq)]
+pub struct BlindingSalt([u8; BLINDING_SALT_LEN]);
+
+impl std::fmt::Debug for BlindingSalt {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("BlindingSalt").field(&self.key_id()).finish()
+    }
+}
+
+impl BlindingSalt {
+    /// 生成隨機鹽
+    pub fn generate() -> Self {
+        let mut salt = [0u8; BLINDING_SALT_LEN];
+        rand::thread_rng().fill_bytes(&mut salt);
+        Self(salt)
+    }
+
+    /// 從字節恢復鹽
+    ///
+    /// # 錯誤
+    /// - 長度不是 [`BLINDING_SALT_LEN`]: 返回 `Keystore` 錯誤
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let salt = bytes.try_into().map_err(|_| {
+            AuditorError::Keystore(format!(
+                "Blinding salt must be {} bytes, got {}",
+                BLINDING_SALT_LEN,
+                bytes.len()
+            ))
+        })?;
+        Ok(Self(salt))
+    }
+
+    /// 讀取鹽文件；不存在時生成並以 `0o600` 權限保存
+    pub fn load_or_create(path: &Path) -> Result<Self> {
+        if path.exists() {
+            return Self::from_bytes(&fs::read(path)?);
+        }
+
+        let salt = Self::generate();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, salt.0)?;
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(path, fs::Permissions::from_mode(0o600))?;
+        }
+
+        info!(
+            "Generated blob ID blinding salt {} at {:?}",
+            salt.key_id(),
+            path
+        );
+        Ok(salt)
+    }
+
+    /// 鹽的指紋（SHA-256 前 8 字節，hex），寫入盲化報告的 `blinding_key_id`
+    pub fn key_id(&self) -> String {
+        trust::key_id(&self.0)
+    }
+
+    /// `HMAC-SHA256(blob_id, salt)`（十六進制）
+    pub fn blind(&self, blob_id: &str) -> String {
+        let mut mac =
+            Hmac::<Sha256>::new_from_slice(&self.0).expect("HMAC accepts keys of any length");
+        mac.update(blob_id.as_bytes());
+        hex::encode(mac.finalize().into_bytes())
+    }
+
+    /// 在候選 Blob ID 中找出盲化報告對應的真實 ID
+    ///
+    /// HMAC 不可逆，授權方用鹽對自己掌握的 Blob ID（如私有存檔）逐一重新計算
+    ///
+    /// # 錯誤
+    /// - 報告未盲化，或由另一個鹽盲化: 返回 `Keystore` 錯誤
+    pub fn unblind<'a>(
+        &self,
+        report: &AuditReport,
+        candidates: impl IntoIterator<Item = &'a str>,
+    ) -> Result<Option<&'a str>> {
+        let key_id = report.blinding_key_id.as_deref().ok_or_else(|| {
+            AuditorError::Keystore(format!("Report for {} is not blinded", report.blob_id))
+        })?;
+        if key_id != self.key_id() {
+            return Err(AuditorError::Keystore(format!(
+                "Report was blinded with salt {} but this salt is {}",
+                key_id,
+                self.key_id()
+            )));
+        }
+
+        Ok(candidates
+            .into_iter()
+            .find(|candidate| self.blind(candidate) == report.blob_id))
+    }
+}
+
+/// 生成報告的盲化發布副本並重新簽名
+///
+/// # 錯誤
+/// - 報告已經盲化: 返回 `Keystore` 錯誤
+/// - 簽名失敗: 返回 `PqcSignature` 錯誤
+pub fn blind_report(
+    report: &AuditReport,
+    salt: &BlindingSalt,
+    signer: &dyn Signer,
+) -> Result<AuditReport> {
+    if report.blinding_key_id.is_some() {
+        return Err(AuditorError::Keystore(format!(
+            "Report for {} is already blinded",
+            report.blob_id
+        )));
+    }
+
+    let mut blinded = report.clone();
+    blinded.blob_id = salt.blind(&report.blob_id);
+    blinded.blob_object_id = String::new();
+    if let Some(source) = &mut blinded.deduplicated_from {
+        source.blob_id = salt.blind(&source.blob_id);
+    }
+    blinded.cosignatures.clear();
+    blinded.challenge_reveal = None;
+    // 種子由真實 Blob ID 導出，可用來檢驗猜測的 Blob ID
+    blinded.challenge_seed_source = None;
+    blinded.challenge_seed = None;
+    blinded.blinding_key_id = Some(salt.key_id());
+
+    ReportManager::sign_report_with(signer, &mut blinded)?;
+    Ok(blinded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audit_report::ReportStatistics;
+    use pqc_signer::Dilithium3Signer;
+
+    fn keypair() -> Dilithium3Signer {
+        let mut signer = Dilithium3Signer::new();
+        signer.generate_keypair().unwrap();
+        signer
+    }
+
+    fn signed_report(signer: &Dilithium3Signer, blob_id: &str) -> AuditReport {
+        let mut report: AuditReport = serde_json::from_value(serde_json::json!({
+            "blob_id": blob_id,
+            "blob_object_id": "0xobject",
+            "auditor": "0xauditor",
+            "timestamp": 1_700_000_000u64,
+            "challenge_epoch": 7,
+            "challenge_results": [],
+            "total_challenges": 10,
+            "successful_verifications": 10,
+            "failed_verifications": 0,
+            "integrity_hash": vec![0u8; 32],
+            "pqc_signature": [],
+            "pqc_algorithm": 3,
+            "is_valid": true,
+            "failure_reason": null,
+        }))
+        .unwrap();
+        ReportManager::sign_report_with(signer, &mut report).unwrap();
+        report
+    }
+
+    #[test]
+    fn test_blinding_is_deterministic_per_salt() {
+        let salt = BlindingSalt::generate();
+        let other = BlindingSalt::generate();
+
+        assert_eq!(salt.blind("blob-a"), salt.blind("blob-a"));
+        assert_ne!(salt.blind("blob-a"), salt.blind("blob-b"));
+        assert_ne!(salt.blind("blob-a"), other.blind("blob-a"));
+        assert_eq!(salt.blind("blob-a").len(), 64);
+    }
+
+    #[test]
+    fn test_salt_persists_in_keystore_dir() {
+        let dir = std::env::temp_dir().join(format!("blinding_{}", rand::random::<u32>()));
+        let path = dir.join(BLINDING_SALT_FILE);
+
+        let created = BlindingSalt::load_or_create(&path).unwrap();
+        let loaded = BlindingSalt::load_or_create(&path).unwrap();
+        assert_eq!(created, loaded);
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(&path).unwrap().permissions().mode() & 0o777;
+            assert_eq!(mode, 0o600);
+        }
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_blinded_report_verifies_and_hides_blob_id() {
+        let signer = keypair();
+        let salt = BlindingSalt::generate();
+        let report = signed_report(&signer, "secret-blob");
+
+        let blinded = blind_report(&report, &salt, &signer).unwrap();
+
+        assert_eq!(blinded.blob_id, salt.blind("secret-blob"));
+        assert_eq!(blinded.blinding_key_id, Some(salt.key_id()));
+        let json = serde_json::to_string(&blinded).unwrap();
+        assert!(!json.contains("secret-blob"));
+        assert!(!json.contains("0xobject"));
+
+        assert!(ReportManager::verify_report(&blinded, signer.public_key()).unwrap());
+
+        // 盲化 ID 在簽名範圍內
+        let mut swapped = blinded.clone();
+        swapped.blob_id = salt.blind("another-blob");
+        assert!(!ReportManager::verify_report(&swapped, signer.public_key()).unwrap());
+        assert!(blind_report(&blinded, &salt, &signer).is_err());
+    }
+
+    #[test]
+    fn test_unblind_requires_matching_salt() {
+        let signer = keypair();
+        let salt = BlindingSalt::generate();
+        let blinded = blind_report(&signed_report(&signer, "blob-b"), &salt, &signer).unwrap();
+
+        let archive = ["blob-a", "blob-b", "blob-c"];
+        assert_eq!(salt.unblind(&blinded, archive).unwrap(), Some("blob-b"));
+        assert_eq!(salt.unblind(&blinded, ["blob-x"]).unwrap(), None);
+
+        assert!(BlindingSalt::generate().unblind(&blinded, archive).is_err());
+        assert!(salt
+            .unblind(&signed_report(&signer, "blob-a"), archive)
+            .is_err());
+    }
+
+    #[test]
+    fn test_statistics_aggregate_over_blinded_ids() {
+        let signer = keypair();
+        let salt = BlindingSalt::generate();
+        let reports: Vec<AuditReport> = ["blob-a", "blob-a", "blob-b"]
+            .into_iter()
+            .map(|id| blind_report(&signed_report(&signer, id), &salt, &signer).unwrap())
+            .collect();
+
+        let stats = ReportStatistics::from_reports(&reports);
+        assert_eq!(stats.total_audits, 3);
+        assert_eq!(stats.unique_blobs, 2);
+    }
+}
@@ -0,0 +1,195 @@
+//! Blob ID 重新編碼驗證
+//!
+//! Walrus 的 `blob_id` 承諾的是 RS2 編碼後各 sliver 的元數據，而非原始字節：
+//! 只比對 SHA-256 無法發現 Aggregator 返回了與 `blob_id` 不符的內容。
+//! 高保證審計可以在下載後對原始數據重新執行 RS2 編碼、推導 `blob_id`，
+//! 再與被審計的 `blob_id` 比對（見 [`crate::integrity::AuditData::blob_id_verified`]）。
+//!
+//! 編碼器通過 [`BlobIdEncoder`] 注入。啟用 `walrus-encoder` feature 時提供以
+//! `walrus-core` 實現的 [`WalrusRs2Encoder`]；未啟用時配置 `verify_blob_id`
+//! 的 Aggregator 審計會拒絕運行。重新編碼需要把整個 Blob 保留在內存中，
+//! 超過 `verify_blob_id_max_bytes` 的 Blob 跳過驗證。
+//!
+//! 存儲節點挑戰不需要編碼器：`blob_id` 由元數據的默克爾根、編碼類型與未編碼大小推導
+//! （[`blob_id_from_metadata`]），挑戰前比對即可確認證明所依據的根確實屬於該 Blob。
+
+use crate::crypto::ct::ct_eq;
+use crate::error::{AuditorError, Result};
+use crate::types::{BlobId, BlobMetadata};
+use base64::{engine::general_purpose, Engine as _};
+use fastcrypto::hash::{Blake2b256, HashFunction};
+
+/// 默認的驗證大小上限（64 MiB）
+pub const DEFAULT_VERIFY_BLOB_ID_MAX_BYTES: u64 = 64 * 1024 * 1024;
+
+/// 默認的分片數（Walrus 主網）
+pub const DEFAULT_VERIFY_BLOB_ID_N_SHARDS: u16 = 1000;
+
+/// 從原始數據推導 Walrus `blob_id` 的編碼器
+pub trait BlobIdEncoder: Send + Sync {
+    /// 對原始數據執行 RS2 編碼並返回推導出的 `blob_id`（URL-safe Base64）
+    fn blob_id(&self, data: &[u8]) -> Result<String>;
+}
+
+/// 以 `walrus-core` 的 RS2 編碼推導 `blob_id`
+///
+/// 分片數必須與存儲該 Blob 的 Walrus 網絡一致，否則推導出的 `blob_id` 必然不同
+#[cfg(feature = "walrus-encoder")]
+pub struct WalrusRs2Encoder {
+    config: walrus_core::encoding::EncodingConfig,
+}
+
+#[cfg(feature = "walrus-encoder")]
+impl WalrusRs2Encoder {
+    /// 按網絡的分片數創建
+    ///
+    /// # 錯誤
+    /// `n_shards` 為 0 時返回 `Config`
+    pub fn new(n_shards: u16) -> Result<Self> {
+        let n_shards = std::num::NonZeroU16::new(n_shards).ok_or_else(|| {
+            AuditorError::Config("verify_blob_id_n_shards must be greater than 0".to_string())
+        })?;
+        Ok(Self {
+            config: walrus_core::encoding::EncodingConfig::new(n_shards),
+        })
+    }
+}
+
+#[cfg(feature = "walrus-encoder")]
+impl BlobIdEncoder for WalrusRs2Encoder {
+    fn blob_id(&self, data: &[u8]) -> Result<String> {
+        use walrus_core::encoding::EncodingConfigTrait as _;
+
+        let metadata = self
+            .config
+            .get_for_type(walrus_core::EncodingType::RS2)
+            .compute_metadata(data)
+            .map_err(|e| AuditorError::Other(anyhow::anyhow!("RS2 encoding failed: {}", e)))?;
+        Ok(metadata.blob_id().to_string())
+    }
+}
+
+/// 由元數據推導 Walrus `blob_id`
+///
+/// `Blake2b-256(encoding_type || unencoded_length (u64 LE) || merkle_root)`，
+/// 即三者 BCS 編碼的哈希，與 `walrus_core::BlobId::from_metadata` 一致
+pub fn blob_id_from_metadata(
+    encoding_type: u8,
+    unencoded_length: u64,
+    merkle_root: &[u8; 32],
+) -> BlobId {
+    let mut hasher = Blake2b256::default();
+    hasher.update([encoding_type]);
+    hasher.update(unencoded_length.to_le_bytes());
+    hasher.update(merkle_root);
+    BlobId::from_bytes(hasher.finalize().digest)
+}
+
+/// 檢查元數據的默克爾根是否推導出其 `blob_id`
+///
+/// 元數據沒有默克爾根時（從鏈上讀取）返回 `None`
+///
+/// # 錯誤
+/// `blob_id` 無效或默克爾根不是 32 字節時返回相應錯誤
+pub fn metadata_matches_blob_id(metadata: &BlobMetadata) -> Result<Option<bool>> {
+    if metadata.merkle_root.is_empty() {
+        return Ok(None);
+    }
+
+    let expected = BlobId::parse(&metadata.blob_id)?;
+    let merkle_root: &[u8; 32] = metadata.merkle_root.as_slice().try_into().map_err(|_| {
+        AuditorError::InvalidDigest(format!(
+            "merkle root of {}: expected 32 bytes, got {}",
+            metadata.blob_id,
+            metadata.merkle_root.len()
+        ))
+    })?;
+    let derived = blob_id_from_metadata(metadata.encoding_type, metadata.blob_size, merkle_root);
+
+    Ok(Some(ct_eq(derived.as_bytes(), expected.as_bytes())))
+}
+
+/// 比較兩個 `blob_id`
+///
+/// 按 URL-safe Base64 解碼後以常量時間逐字節比較（忽略填充）
+///
+/// # 錯誤
+/// 任一方無法解碼時返回 [`AuditorError::InvalidBlobId`]
+pub fn blob_ids_match(computed: &str, expected: &str) -> Result<bool> {
+    let decode = |id: &str| {
+        general_purpose::URL_SAFE_NO_PAD
+            .decode(id.trim().trim_end_matches('='))
+            .map_err(|e| AuditorError::InvalidBlobId(format!("{}: {}", id, e)))
+    };
+
+    Ok(ct_eq(&decode(computed)?, &decode(expected)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_blob_ids_match_ignores_padding() {
+        let id = general_purpose::URL_SAFE_NO_PAD.encode([7u8; 32]);
+        let padded = general_purpose::URL_SAFE.encode([7u8; 32]);
+
+        assert!(blob_ids_match(&id, &id).unwrap());
+        assert!(blob_ids_match(&id, &padded).unwrap());
+        assert!(!blob_ids_match(&id, &general_purpose::URL_SAFE_NO_PAD.encode([8u8; 32])).unwrap());
+    }
+
+    #[test]
+    fn test_blob_ids_match_rejects_invalid_base64() {
+        let id = general_purpose::URL_SAFE_NO_PAD.encode([7u8; 32]);
+
+        assert!(matches!(
+            blob_ids_match("not base64!", "not base64!"),
+            Err(AuditorError::InvalidBlobId(_))
+        ));
+        assert!(matches!(
+            blob_ids_match(&id, "not base64!"),
+            Err(AuditorError::InvalidBlobId(_))
+        ));
+    }
+
+    fn metadata(merkle_root: Vec<u8>) -> BlobMetadata {
+        BlobMetadata {
+            blob_object_id: "0x1234".to_string(),
+            blob_id: "4MKyAZJtVEPR_YR7XHxat-iCu2xucROYki4tSsnYgME".to_string(),
+            merkle_root,
+            blob_size: 1024,
+            encoding_type: 1,
+            encoding_k: 334,
+            encoding_n: 1000,
+            registered_epoch: 1,
+            certified_epoch: Some(1),
+            start_epoch: 1,
+            end_epoch: 10,
+            owner: "0x5678".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_blob_id_from_metadata_layout() {
+        let blob_id = blob_id_from_metadata(1, 1024, &[7u8; 32]);
+        assert_eq!(
+            blob_id.to_string(),
+            "4MKyAZJtVEPR_YR7XHxat-iCu2xucROYki4tSsnYgME"
+        );
+    }
+
+    #[test]
+    fn test_metadata_matches_blob_id() {
+        assert_eq!(
+            metadata_matches_blob_id(&metadata(vec![7u8; 32])).unwrap(),
+            Some(true)
+        );
+        assert_eq!(
+            metadata_matches_blob_id(&metadata(vec![8u8; 32])).unwrap(),
+            Some(false)
+        );
+        assert_eq!(metadata_matches_blob_id(&metadata(vec![])).unwrap(), None);
+        assert!(metadata_matches_blob_id(&metadata(vec![7u8; 31])).is_err());
+    }
+}
@@ -0,0 +1,539 @@
+//! Blob 鏈上對象狀態查詢
+//!
+//! Walrus 支持可刪除（deletable）Blob。所有者刪除 Blob 後，Aggregator 會返回 404，
+//! 如果直接記為 `Unreachable` 就會錯誤地懲罰存儲節點。
+//!
+//! 本模塊在 Aggregator 返回 404 時查詢 Sui 上的 Blob 對象：
+//! - 對象已刪除 → [`BlobObjectState::Deleted`]
+//! - 對象仍在但存儲期已結束（已回收）→ [`BlobObjectState::StorageReclaimed`]
+//! - 對象存活 → [`BlobObjectState::Live`]（404 屬於真正的可用性問題）
+//!
+//! 查詢通過 Sui JSON-RPC (`sui_getObject`) 完成，不依賴 `sui-sdk` feature。
+//!
+//! 完整性審計還可以通過 [`BlobMetadataSource`] 讀取 Blob 的鏈上元數據，
+//! 把報告綁定到具體的已註冊 Blob（見 `IntegrityVerifier::audit_blob_with_metadata`）。
+//!
+//! [`resolve_blob_ref`] 在兩種標識之間解析：Blob 對象 ID 經 [`BlobMetadataSource`] 得到 Blob ID，
+//! Blob ID 經 [`BlobObjectIdSource`] 找到 Blob 對象，使報告同時記錄兩者。
+
+use crate::chain_types::MoveId;
+use crate::error::{AuditorError, Result};
+use crate::metadata_cache::BlobMetadataCache;
+use crate::sui_client::AuditSystemClient;
+use crate::types::{BlobId, BlobMetadata, BlobRef};
+use async_trait::async_trait;
+use reqwest::Client;
+use serde_json::{json, Value};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::OnceCell;
+use tracing::debug;
+
+/// Blob 對象在鏈上的狀態
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlobObjectState {
+    /// 對象存在且存儲期有效
+    Live,
+    /// 對象已被所有者刪除
+    Deleted,
+    /// 對象存在但存儲資源已過期回收
+    StorageReclaimed,
+    /// 無法判斷（缺少對象 ID、對象從未存在等）
+    Unknown,
+}
+
+impl BlobObjectState {
+    /// 是否表示 Blob 已被合法移除（不應計為存儲節點故障）
+    pub fn is_removed(&self) -> bool {
+        matches!(self, Self::Deleted | Self::StorageReclaimed)
+    }
+}
+
+/// Blob 對象狀態查詢接口
+#[async_trait]
+pub trait BlobObjectLookup: Send + Sync {
+    /// 查詢 Blob 對象狀態
+    ///
+    /// # 參數
+    /// - `blob_id`: Walrus Blob ID
+    /// - `sui_object_id`: Blob 對應的 Sui 對象 ID（如已知）
+    async fn blob_object_state(
+        &self,
+        blob_id: &str,
+        sui_object_id: Option<&str>,
+    ) -> Result<BlobObjectState>;
+}
+
+/// Blob 鏈上元數據查詢接口
+#[async_trait]
+pub trait BlobMetadataSource: Send + Sync {
+    /// 查詢 Blob 對象的元數據（大小、編碼參數與存儲期）
+    async fn blob_metadata(&self, blob_object_id: &MoveId) -> Result<BlobMetadata>;
+}
+
+#[async_trait]
+impl BlobMetadataSource for AuditSystemClient {
+    async fn blob_metadata(&self, blob_object_id: &MoveId) -> Result<BlobMetadata> {
+        self.get_blob_metadata(blob_object_id).await
+    }
+}
+
+/// Blob ID → 鏈上 Blob 對象 ID 的查詢接口
+#[async_trait]
+pub trait BlobObjectIdSource: Send + Sync {
+    /// 查找 Blob 的 Sui 對象 ID（鏈上找不到時返回 `None`）
+    async fn blob_object_id(&self, blob_id: &BlobId) -> Result<Option<MoveId>>;
+}
+
+#[async_trait]
+impl BlobObjectIdSource for AuditSystemClient {
+    async fn blob_object_id(&self, blob_id: &BlobId) -> Result<Option<MoveId>> {
+        self.find_blob_object_id(blob_id).await
+    }
+}
+
+/// 按需連接 Sui 的 Blob 解析器（流水線的 Aggregator 審計使用）
+///
+/// 首次解析時才創建 [`AuditSystemClient`]（只需審計系統的 Package ID），
+/// 不解析 Blob 對象的審計無需連接 Sui
+pub struct SuiBlobResolver {
+    rpc_url: String,
+    package_id: String,
+    metadata_cache: Option<Arc<BlobMetadataCache>>,
+    client: OnceCell<AuditSystemClient>,
+}
+
+impl SuiBlobResolver {
+    /// 創建解析器；`cache_ttl` 非零時按該 TTL 緩存 Blob 元數據
+    pub fn new(
+        rpc_url: impl Into<String>,
+        package_id: impl Into<String>,
+        cache_ttl: Duration,
+    ) -> Self {
+        Self {
+            rpc_url: rpc_url.into(),
+            package_id: package_id.into(),
+            metadata_cache: (!cache_ttl.is_zero())
+                .then(|| Arc::new(BlobMetadataCache::new(cache_ttl))),
+            client: OnceCell::new(),
+        }
+    }
+
+    async fn client(&self) -> Result<&AuditSystemClient> {
+        self.client
+            .get_or_try_init(|| async {
+                let mut client =
+                    AuditSystemClient::new(&self.rpc_url, &self.package_id, "", "", "").await?;
+                if let Some(cache) = &self.metadata_cache {
+                    client.set_metadata_cache(Arc::clone(cache));
+                }
+                Ok(client)
+            })
+            .await
+    }
+}
+
+#[async_trait]
+impl BlobMetadataSource for SuiBlobResolver {
+    async fn blob_metadata(&self, blob_object_id: &MoveId) -> Result<BlobMetadata> {
+        self.client().await?.get_blob_metadata(blob_object_id).await
+    }
+}
+
+#[async_trait]
+impl BlobObjectIdSource for SuiBlobResolver {
+    async fn blob_object_id(&self, blob_id: &BlobId) -> Result<Option<MoveId>> {
+        self.client().await?.find_blob_object_id(blob_id).await
+    }
+}
+
+/// [`resolve_blob_ref`] 的結果
+#[derive(Debug, Clone)]
+pub struct ResolvedBlob {
+    /// Walrus Blob ID
+    pub blob_id: BlobId,
+
+    /// Blob 的 Sui 對象 ID（未能定位對象時為 `None`）
+    pub blob_object_id: Option<MoveId>,
+
+    /// Blob 對象的鏈上元數據（與 `blob_object_id` 同時存在）
+    pub metadata: Option<BlobMetadata>,
+}
+
+/// 解析 [`BlobRef`]，得到 Blob ID 與鏈上 Blob 對象
+///
+/// - `ObjectId`: 從 `metadata` 讀取對象，Blob ID 取自對象記錄
+/// - `BlobId`: 提供 `object_ids` 時以它查找對象並讀取元數據，核對對象記錄的 Blob ID；
+///   未提供或鏈上找不到對象時只返回 Blob ID
+///
+/// # 錯誤
+/// - Blob ID 格式錯誤: 返回 `InvalidBlobId` 錯誤
+/// - 其餘任一步驟失敗: 返回 `BlobResolution` 錯誤，`step` 說明失敗的步驟
+pub async fn resolve_blob_ref(
+    blob: &BlobRef,
+    metadata: &dyn BlobMetadataSource,
+    object_ids: Option<&dyn BlobObjectIdSource>,
+) -> Result<ResolvedBlob> {
+    let (blob_id, blob_object_id) = match blob {
+        BlobRef::ObjectId(id) => {
+            let object_id = MoveId::from_hex(id).map_err(|e| AuditorError::BlobResolution {
+                step: format!("parse blob object ID {}", id),
+                reason: e.to_string(),
+            })?;
+            (None, object_id)
+        }
+        BlobRef::BlobId(id) => {
+            let blob_id = BlobId::parse(id)?;
+            let Some(object_ids) = object_ids else {
+                return Ok(ResolvedBlob {
+                    blob_id,
+                    blob_object_id: None,
+                    metadata: None,
+                });
+            };
+            let found = object_ids.blob_object_id(&blob_id).await.map_err(|e| {
+                AuditorError::BlobResolution {
+                    step: format!("look up the Sui object of blob {}", blob_id),
+                    reason: e.to_string(),
+                }
+            })?;
+            let Some(object_id) = found else {
+                debug!("No Sui object found for blob {}", blob_id);
+                return Ok(ResolvedBlob {
+                    blob_id,
+                    blob_object_id: None,
+                    metadata: None,
+                });
+            };
+            (Some(blob_id), object_id)
+        }
+    };
+
+    let step = || format!("read blob object {} from Sui", blob_object_id);
+    let object = metadata.blob_metadata(&blob_object_id).await.map_err(|e| {
+        AuditorError::BlobResolution {
+            step: step(),
+            reason: e.to_string(),
+        }
+    })?;
+    let recorded = BlobId::parse(&object.blob_id).map_err(|e| AuditorError::BlobResolution {
+        step: step(),
+        reason: e.to_string(),
+    })?;
+    if let Some(blob_id) = blob_id.filter(|blob_id| *blob_id != recorded) {
+        return Err(AuditorError::BlobResolution {
+            step: format!("match blob {} to its Sui object", blob_id),
+            reason: format!("blob object {} holds blob {}", blob_object_id, recorded),
+        });
+    }
+
+    debug!("Blob {} is object {}", recorded, blob_object_id);
+    Ok(ResolvedBlob {
+        blob_id: recorded,
+        blob_object_id: Some(blob_object_id),
+        metadata: Some(object),
+    })
+}
+
+/// 基於 Sui JSON-RPC 的 Blob 對象查詢
+#[derive(Debug, Clone)]
+pub struct SuiRpcBlobLookup {
+    /// HTTP 客戶端
+    http_client: Client,
+
+    /// Sui RPC URL
+    rpc_url: String,
+
+    /// 當前 Walrus epoch（已知時用於判斷存儲是否已回收）
+    current_epoch: Option<u64>,
+}
+
+impl SuiRpcBlobLookup {
+    /// 創建新的查詢器
+    pub fn new(rpc_url: impl Into<String>) -> Self {
+        let http_client = Client::builder()
+            .timeout(std::time::Duration::from_secs(15))
+            .build()
+            .expect("Failed to build HTTP client");
+
+        Self {
+            http_client,
+            rpc_url: rpc_url.into(),
+            current_epoch: None,
+        }
+    }
+
+    /// 設置當前 Walrus epoch
+    pub fn with_current_epoch(mut self, epoch: u64) -> Self {
+        self.current_epoch = Some(epoch);
+        self
+    }
+}
+
+#[async_trait]
+impl BlobObjectLookup for SuiRpcBlobLookup {
+    async fn blob_object_state(
+        &self,
+        blob_id: &str,
+        sui_object_id: Option<&str>,
+    ) -> Result<BlobObjectState> {
+        let Some(object_id) = sui_object_id else {
+            debug!(
+                "No Sui object ID for blob {}, deletion state unknown",
+                blob_id
+            );
+            return Ok(BlobObjectState::Unknown);
+        };
+
+        let request = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "sui_getObject",
+            "params": [object_id, { "showContent": true }]
+        });
+
+        let response: Value = self
+            .http_client
+            .post(&self.rpc_url)
+            .json(&request)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        if let Some(error) = response.get("error") {
+            return Err(AuditorError::SuiClient(format!(
+                "sui_getObject failed for {}: {}",
+                object_id, error
+            )));
+        }
+
+        let state = classify_object_response(&response["result"], self.current_epoch);
+        debug!("Blob {} object {} state: {:?}", blob_id, object_id, state);
+
+        Ok(state)
+    }
+}
+
+/// 根據 `sui_getObject` 的 `result` 判斷 Blob 對象狀態
+pub fn classify_object_response(result: &Value, current_epoch: Option<u64>) -> BlobObjectState {
+    if let Some(code) = result.pointer("/error/code").and_then(Value::as_str) {
+        return match code {
+            "deleted" => BlobObjectState::Deleted,
+            _ => BlobObjectState::Unknown,
+        };
+    }
+
+    let Some(fields) = result.pointer("/data/content/fields") else {
+        return BlobObjectState::Unknown;
+    };
+
+    let end_epoch = fields
+        .pointer("/storage/fields/end_epoch")
+        .and_then(|v| v.as_u64().or_else(|| v.as_str()?.parse().ok()));
+
+    match (end_epoch, current_epoch) {
+        (Some(end), Some(current)) if end <= current => BlobObjectState::StorageReclaimed,
+        _ => BlobObjectState::Live,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn live_object(end_epoch: u64) -> Value {
+        json!({
+            "data": {
+                "objectId": "0xb10b",
+                "content": {
+                    "dataType": "moveObject",
+                    "type": "0xabc::blob::Blob",
+                    "fields": {
+                        "blob_id": "123",
+                        "deletable": true,
+                        "storage": {
+                            "type": "0xabc::storage_resource::Storage",
+                            "fields": { "start_epoch": 1, "end_epoch": end_epoch, "storage_size": "1024" }
+                        }
+                    }
+                }
+            }
+        })
+    }
+
+    #[test]
+    fn test_classify_deleted_object() {
+        let result = json!({ "error": { "code": "deleted", "object_id": "0xb10b", "version": 7 } });
+        assert_eq!(
+            classify_object_response(&result, None),
+            BlobObjectState::Deleted
+        );
+        assert!(BlobObjectState::Deleted.is_removed());
+    }
+
+    #[test]
+    fn test_classify_live_and_reclaimed_object() {
+        assert_eq!(
+            classify_object_response(&live_object(10), None),
+            BlobObjectState::Live
+        );
+        assert_eq!(
+            classify_object_response(&live_object(10), Some(5)),
+            BlobObjectState::Live
+        );
+        assert_eq!(
+            classify_object_response(&live_object(10), Some(10)),
+            BlobObjectState::StorageReclaimed
+        );
+    }
+
+    #[test]
+    fn test_classify_unknown_object() {
+        let result = json!({ "error": { "code": "notExists", "object_id": "0xb10b" } });
+        assert_eq!(
+            classify_object_response(&result, None),
+            BlobObjectState::Unknown
+        );
+        assert_eq!(
+            classify_object_response(&json!({}), None),
+            BlobObjectState::Unknown
+        );
+        assert!(!BlobObjectState::Unknown.is_removed());
+    }
+
+    /// 以內存表代替 Sui 的解析器：對象 ID → Blob ID，Blob ID → 對象 ID
+    struct StubChain {
+        objects: HashMap<MoveId, BlobId>,
+        index: HashMap<BlobId, MoveId>,
+        index_down: bool,
+    }
+
+    #[async_trait]
+    impl BlobMetadataSource for StubChain {
+        async fn blob_metadata(&self, blob_object_id: &MoveId) -> Result<BlobMetadata> {
+            let blob_id = self.objects.get(blob_object_id).ok_or_else(|| {
+                AuditorError::SuiClient(format!("Object {} not found", blob_object_id))
+            })?;
+            Ok(BlobMetadata {
+                blob_object_id: blob_object_id.to_string(),
+                blob_id: blob_id.to_string(),
+                merkle_root: vec![],
+                blob_size: 1024,
+                encoding_type: 1,
+                encoding_k: 334,
+                encoding_n: 1000,
+                registered_epoch: 1,
+                certified_epoch: Some(1),
+                start_epoch: 1,
+                end_epoch: 10,
+                owner: "0x5678".to_string(),
+            })
+        }
+    }
+
+    #[async_trait]
+    impl BlobObjectIdSource for StubChain {
+        async fn blob_object_id(&self, blob_id: &BlobId) -> Result<Option<MoveId>> {
+            if self.index_down {
+                return Err(AuditorError::SuiClient(
+                    "suix_queryEvents failed".to_string(),
+                ));
+            }
+            Ok(self.index.get(blob_id).copied())
+        }
+    }
+
+    fn stub_chain() -> StubChain {
+        let blob = BlobId::from_bytes;
+        StubChain {
+            objects: HashMap::from([
+                (MoveId([1; 32]), blob([0xa; 32])),
+                (MoveId([2; 32]), blob([0xc; 32])),
+            ]),
+            // 0xb 的索引錯誤地指向記錄另一個 Blob 的對象
+            index: HashMap::from([
+                (blob([0xa; 32]), MoveId([1; 32])),
+                (blob([0xb; 32]), MoveId([2; 32])),
+            ]),
+            index_down: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_resolve_object_id_to_blob_id() {
+        let chain = stub_chain();
+        let blob = BlobRef::ObjectId(MoveId([1; 32]).to_string());
+
+        let resolved = resolve_blob_ref(&blob, &chain, None).await.unwrap();
+        assert_eq!(resolved.blob_id, BlobId::from_bytes([0xa; 32]));
+        assert_eq!(resolved.blob_object_id, Some(MoveId([1; 32])));
+        assert!(resolved.metadata.is_some());
+
+        let err = resolve_blob_ref(&BlobRef::ObjectId("0xnope".to_string()), &chain, None)
+            .await
+            .unwrap_err();
+        assert!(
+            err.to_string()
+                .starts_with("Failed to parse blob object ID 0xnope"),
+            "{}",
+            err
+        );
+
+        let missing = BlobRef::ObjectId(MoveId([9; 32]).to_string());
+        let err = resolve_blob_ref(&missing, &chain, None).await.unwrap_err();
+        assert!(
+            matches!(&err, AuditorError::BlobResolution { step, .. }
+                if step == &format!("read blob object {} from Sui", MoveId([9; 32]))),
+            "{:?}",
+            err
+        );
+    }
+
+    #[tokio::test]
+    async fn test_resolve_blob_id_to_object_id() {
+        let chain = stub_chain();
+        let index: Option<&dyn BlobObjectIdSource> = Some(&chain);
+        let blob = BlobRef::BlobId(BlobId::from_bytes([0xa; 32]).to_string());
+
+        let resolved = resolve_blob_ref(&blob, &chain, index).await.unwrap();
+        assert_eq!(resolved.blob_id, BlobId::from_bytes([0xa; 32]));
+        assert_eq!(resolved.blob_object_id, Some(MoveId([1; 32])));
+
+        // 未提供查詢或鏈上沒有記錄時只有 Blob ID
+        let resolved = resolve_blob_ref(&blob, &chain, None).await.unwrap();
+        assert_eq!(resolved.blob_object_id, None);
+        let unknown = BlobRef::BlobId(BlobId::from_bytes([0xd; 32]).to_string());
+        let resolved = resolve_blob_ref(&unknown, &chain, index).await.unwrap();
+        assert_eq!(resolved.blob_id, BlobId::from_bytes([0xd; 32]));
+        assert!(resolved.metadata.is_none());
+
+        // 對象記錄的是另一個 Blob
+        let mismatched = BlobRef::BlobId(BlobId::from_bytes([0xb; 32]).to_string());
+        let err = resolve_blob_ref(&mismatched, &chain, index)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("to its Sui object"), "{}", err);
+
+        let down = StubChain {
+            index_down: true,
+            ..stub_chain()
+        };
+        let err = resolve_blob_ref(&blob, &down, Some(&down as &dyn BlobObjectIdSource))
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "Failed to look up the Sui object of blob {}: Sui client error: suix_queryEvents failed",
+                BlobId::from_bytes([0xa; 32])
+            )
+        );
+
+        let invalid = BlobRef::BlobId("not a blob".to_string());
+        let err = resolve_blob_ref(&invalid, &chain, index).await.unwrap_err();
+        assert!(matches!(err, AuditorError::InvalidBlobId(_)));
+    }
+}
@@ -0,0 +1,563 @@
+//! Aggregator 與存儲節點調用的熔斷器
+//!
+//! Testnet 故障期間 Aggregator 對所有人返回 503，守護進程的逐請求重試只會放大負載，
+//! 並耗盡整個審計間隔。熔斷器按端點統計滾動窗口內的錯誤率：
+//!
+//! ```text
+//!          錯誤率 ≥ 閾值（窗口內請求數 ≥ min_requests）
+//! Closed ──────────────────────────────────────────▶ Open（快速拒絕，返回 CircuitOpen）
+//!   ▲                                                 │  ▲
+//!   │ 探測成功                         冷卻期結束       │  │ 探測失敗
+//!   │                                                 ▼  │
+//!   └──────────────────────────────────────────── HalfOpen（放行 half_open_probes 個探測）
+//! ```
+//!
+//! 傳輸錯誤、HTTP 429 與 5xx 計為失敗；其他 4xx（如 404）說明端點正常工作，計為成功。
+//! 一個 [`CircuitBreaker`] 由同一進程內的所有客戶端共享，狀態轉換計數通過
+//! [`CircuitBreaker::status`] 導出。時鐘可替換，測試無需真實等待。
+
+use crate::error::{AuditorError, Result};
+use crate::rotating_writer::Clock;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, VecDeque};
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+use tracing::{info, warn};
+
+/// 熔斷器配置
+///
+/// ```toml
+/// [breaker]
+/// enabled = true
+/// window_secs = 60
+/// min_requests = 10
+/// failure_rate = 0.5
+/// cooldown_secs = 30
+/// half_open_probes = 1
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BreakerConfig {
+    /// 是否啟用熔斷
+    pub enabled: bool,
+
+    /// 滾動錯誤率窗口（秒）
+    pub window_secs: u64,
+
+    /// 窗口內至少多少個請求才計算錯誤率
+    pub min_requests: u32,
+
+    /// 打開熔斷的錯誤率閾值（0.0 - 1.0）
+    pub failure_rate: f64,
+
+    /// 打開後多久允許半開探測（秒）
+    pub cooldown_secs: u64,
+
+    /// 半開狀態同時放行的探測請求數
+    pub half_open_probes: u32,
+}
+
+impl Default for BreakerConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            window_secs: 60,
+            min_requests: 10,
+            failure_rate: 0.5,
+            cooldown_secs: 30,
+            half_open_probes: 1,
+        }
+    }
+}
+
+/// 熔斷狀態
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CircuitState {
+    /// 正常放行
+    Closed,
+    /// 快速拒絕
+    Open,
+    /// 冷卻結束，放行有限探測
+    HalfOpen,
+}
+
+/// 狀態轉換與拒絕計數
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransitionCounts {
+    /// 進入 Open 的次數（含探測失敗後重新打開）
+    pub opened: u64,
+    /// 進入 HalfOpen 的次數
+    pub half_opened: u64,
+    /// 探測成功後關閉的次數
+    pub closed: u64,
+    /// 被快速拒絕的調用數
+    pub rejected: u64,
+}
+
+/// 單個端點的熔斷狀態快照
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EndpointStatus {
+    /// 端點（Aggregator 或存儲節點基礎 URL）
+    pub endpoint: String,
+    /// 當前狀態
+    pub state: CircuitState,
+    /// 窗口內請求數
+    pub requests: usize,
+    /// 窗口內失敗數
+    pub failures: usize,
+    /// Open 狀態下距離允許探測的剩餘秒數
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry_after_secs: Option<u64>,
+    /// 狀態轉換計數
+    pub transitions: TransitionCounts,
+}
+
+/// 單個端點的內部狀態
+struct Circuit {
+    state: CircuitState,
+    /// 窗口內的調用結果（時間, 是否失敗）
+    outcomes: VecDeque<(SystemTime, bool)>,
+    opened_at: SystemTime,
+    probes_in_flight: u32,
+    transitions: TransitionCounts,
+}
+
+impl Circuit {
+    fn new(now: SystemTime) -> Self {
+        Self {
+            state: CircuitState::Closed,
+            outcomes: VecDeque::new(),
+            opened_at: now,
+            probes_in_flight: 0,
+            transitions: TransitionCounts::default(),
+        }
+    }
+
+    fn prune(&mut self, now: SystemTime, window: Duration) {
+        while let Some(&(at, _)) = self.outcomes.front() {
+            if now.duration_since(at).unwrap_or_default() < window {
+                break;
+            }
+            self.outcomes.pop_front();
+        }
+    }
+
+    fn failures(&self) -> usize {
+        self.outcomes.iter().filter(|(_, failed)| *failed).count()
+    }
+
+    fn open(&mut self, now: SystemTime) {
+        self.state = CircuitState::Open;
+        self.opened_at = now;
+        self.probes_in_flight = 0;
+        self.transitions.opened += 1;
+    }
+
+    /// Open 狀態下剩餘的冷卻時間（已結束時為 `None`）
+    fn cooldown_remaining(&self, now: SystemTime, cooldown: Duration) -> Option<Duration> {
+        let elapsed = now.duration_since(self.opened_at).unwrap_or_default();
+        cooldown.checked_sub(elapsed).filter(|d| !d.is_zero())
+    }
+}
+
+/// 按端點共享的熔斷器
+pub struct CircuitBreaker {
+    config: BreakerConfig,
+    circuits: Mutex<BTreeMap<String, Circuit>>,
+    clock: Clock,
+}
+
+impl std::fmt::Debug for CircuitBreaker {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CircuitBreaker")
+            .field("config", &self.config)
+            .finish()
+    }
+}
+
+impl CircuitBreaker {
+    /// 創建熔斷器（使用系統時鐘）
+    pub fn new(config: BreakerConfig) -> Self {
+        Self {
+            config,
+            circuits: Mutex::new(BTreeMap::new()),
+            clock: Arc::new(SystemTime::now),
+        }
+    }
+
+    /// 替換時鐘（測試用）
+    pub fn with_clock(mut self, clock: Clock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// 發起調用前檢查端點是否放行
+    ///
+    /// 放行後必須以 [`CircuitBreaker::record`] 報告結果（半開探測名額在記錄時釋放）
+    ///
+    /// # 錯誤
+    /// - 熔斷打開，或半開探測名額已用完: 返回 `CircuitOpen` 錯誤
+    pub fn acquire(&self, endpoint: &str) -> Result<()> {
+        if !self.config.enabled {
+            return Ok(());
+        }
+
+        let now = (self.clock)();
+        let cooldown = Duration::from_secs(self.config.cooldown_secs);
+        let mut circuits = self.circuits.lock().unwrap();
+        let circuit = circuits
+            .entry(endpoint.to_string())
+            .or_insert_with(|| Circuit::new(now));
+
+        if circuit.state == CircuitState::Open {
+            match circuit.cooldown_remaining(now, cooldown) {
+                Some(remaining) => {
+                    circuit.transitions.rejected += 1;
+                    return Err(circuit_open(endpoint, remaining));
+                }
+                None => {
+                    info!("Circuit for {} half-open, allowing probe", endpoint);
+                    circuit.state = CircuitState::HalfOpen;
+                    circuit.probes_in_flight = 0;
+                    circuit.transitions.half_opened += 1;
+                }
+            }
+        }
+
+        if circuit.state == CircuitState::HalfOpen {
+            if circuit.probes_in_flight >= self.config.half_open_probes.max(1) {
+                circuit.transitions.rejected += 1;
+                return Err(circuit_open(endpoint, Duration::ZERO));
+            }
+            circuit.probes_in_flight += 1;
+        }
+
+        Ok(())
+    }
+
+    /// 記錄一次調用的結果
+    pub fn record(&self, endpoint: &str, success: bool) {
+        if !self.config.enabled {
+            return;
+        }
+
+        let now = (self.clock)();
+        let mut circuits = self.circuits.lock().unwrap();
+        let circuit = circuits
+            .entry(endpoint.to_string())
+            .or_insert_with(|| Circuit::new(now));
+
+        match circuit.state {
+            CircuitState::HalfOpen if success => {
+                info!("Circuit for {} closed after successful probe", endpoint);
+                circuit.state = CircuitState::Closed;
+                circuit.outcomes.clear();
+                circuit.probes_in_flight = 0;
+                circuit.transitions.closed += 1;
+            }
+            CircuitState::HalfOpen => {
+                warn!("Probe to {} failed, circuit re-opened", endpoint);
+                circuit.open(now);
+            }
+            // 熔斷打開前已發出的請求：結果不再影響狀態
+            CircuitState::Open => {}
+            CircuitState::Closed => {
+                circuit.outcomes.push_back((now, !success));
+                circuit.prune(now, Duration::from_secs(self.config.window_secs));
+
+                let requests = circuit.outcomes.len();
+                let failures = circuit.failures();
+                if requests >= self.config.min_requests as usize
+                    && failures as f64 >= self.config.failure_rate * requests as f64
+                {
+                    warn!(
+                        "Circuit for {} opened: {}/{} calls failed in the last {}s",
+                        endpoint, failures, requests, self.config.window_secs
+                    );
+                    circuit.open(now);
+                }
+            }
+        }
+    }
+
+    /// 在熔斷保護下執行調用，按 [`is_endpoint_failure`] 記錄結果
+    pub async fn call<T, F>(&self, endpoint: &str, call: F) -> Result<T>
+    where
+        F: Future<Output = Result<T>>,
+    {
+        self.acquire(endpoint)?;
+        let result = call.await;
+        self.record(
+            endpoint,
+            !matches!(&result, Err(e) if is_endpoint_failure(e)),
+        );
+        result
+    }
+
+    /// 端點當前狀態（冷卻已結束的 Open 在下次調用時才轉為 HalfOpen）
+    pub fn state(&self, endpoint: &str) -> CircuitState {
+        self.circuits
+            .lock()
+            .unwrap()
+            .get(endpoint)
+            .map_or(CircuitState::Closed, |c| c.state)
+    }
+
+    /// 熔斷打開時距離允許探測的剩餘時間（未打開或冷卻已結束時為 `None`）
+    pub fn retry_after(&self, endpoint: &str) -> Option<Duration> {
+        let now = (self.clock)();
+        let cooldown = Duration::from_secs(self.config.cooldown_secs);
+        let circuits = self.circuits.lock().unwrap();
+        let circuit = circuits.get(endpoint)?;
+        (circuit.state == CircuitState::Open)
+            .then(|| circuit.cooldown_remaining(now, cooldown))
+            .flatten()
+    }
+
+    /// 所有已知端點的狀態快照（按端點排序）
+    pub fn status(&self) -> Vec<EndpointStatus> {
+        let now = (self.clock)();
+        let window = Duration::from_secs(self.config.window_secs);
+        let cooldown = Duration::from_secs(self.config.cooldown_secs);
+        let mut circuits = self.circuits.lock().unwrap();
+
+        circuits
+            .iter_mut()
+            .map(|(endpoint, circuit)| {
+                circuit.prune(now, window);
+                EndpointStatus {
+                    endpoint: endpoint.clone(),
+                    state: circuit.state,
+                    requests: circuit.outcomes.len(),
+                    failures: circuit.failures(),
+                    retry_after_secs: (circuit.state == CircuitState::Open)
+                        .then(|| circuit.cooldown_remaining(now, cooldown))
+                        .flatten()
+                        .map(|d| d.as_secs().max(1)),
+                    transitions: circuit.transitions,
+                }
+            })
+            .collect()
+    }
+}
+
+/// HTTP 狀態碼是否表示端點過載或故障（429 或 5xx）
+pub fn is_failure_status(status: u16) -> bool {
+    status == 429 || status >= 500
+}
+
+/// 錯誤是否計入端點錯誤率
+///
+/// 傳輸錯誤與 5xx 映射為 `StorageNodeUnreachable`（429 與 503 為 `StorageNodeOverloaded`）；
+/// 數據錯誤（無效 sliver、證明失敗）說明端點仍在響應，不計入
+pub fn is_endpoint_failure(error: &AuditorError) -> bool {
+    match error {
+        AuditorError::StorageNodeUnreachable(_) | AuditorError::StorageNodeOverloaded { .. } => {
+            true
+        }
+        AuditorError::HttpRequest(e) => {
+            e.is_timeout()
+                || e.is_connect()
+                || e.status().is_some_and(|s| is_failure_status(s.as_u16()))
+        }
+        _ => false,
+    }
+}
+
+fn circuit_open(endpoint: &str, retry_after: Duration) -> AuditorError {
+    AuditorError::CircuitOpen {
+        endpoint: endpoint.to_string(),
+        retry_after_secs: retry_after.as_secs_f64().ceil() as u64,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::time::UNIX_EPOCH;
+
+    const AGGREGATOR: &str = "http://aggregator";
+
+    /// 可手動推進的時鐘（毫秒）
+    fn mock_clock() -> (Clock, Arc<AtomicU64>) {
+        let now = Arc::new(AtomicU64::new(1_000_000));
+        let handle = Arc::clone(&now);
+        let clock: Clock =
+            Arc::new(move || UNIX_EPOCH + Duration::from_millis(handle.load(Ordering::SeqCst)));
+        (clock, now)
+    }
+
+    fn breaker() -> (CircuitBreaker, Arc<AtomicU64>) {
+        let (clock, now) = mock_clock();
+        let config = BreakerConfig {
+            window_secs: 10,
+            min_requests: 4,
+            failure_rate: 0.5,
+            cooldown_secs: 30,
+            half_open_probes: 1,
+            ..BreakerConfig::default()
+        };
+        (CircuitBreaker::new(config).with_clock(clock), now)
+    }
+
+    fn advance(now: &AtomicU64, secs: u64) {
+        now.fetch_add(secs * 1000, Ordering::SeqCst);
+    }
+
+    fn call(breaker: &CircuitBreaker, success: bool) -> Result<()> {
+        breaker.acquire(AGGREGATOR)?;
+        breaker.record(AGGREGATOR, success);
+        Ok(())
+    }
+
+    #[test]
+    fn test_opens_after_failure_rate_threshold() {
+        let (breaker, _) = breaker();
+
+        // 請求數不足 min_requests 時不打開
+        for _ in 0..3 {
+            call(&breaker, false).unwrap();
+        }
+        assert_eq!(breaker.state(AGGREGATOR), CircuitState::Closed);
+
+        call(&breaker, false).unwrap();
+        assert_eq!(breaker.state(AGGREGATOR), CircuitState::Open);
+
+        let err = call(&breaker, true).unwrap_err();
+        assert!(matches!(
+            err,
+            AuditorError::CircuitOpen {
+                retry_after_secs: 30,
+                ..
+            }
+        ));
+
+        // 其他端點不受影響
+        breaker.acquire("http://node-1").unwrap();
+    }
+
+    #[test]
+    fn test_failures_outside_window_are_forgotten() {
+        let (breaker, now) = breaker();
+
+        call(&breaker, false).unwrap();
+        call(&breaker, false).unwrap();
+        advance(&now, 11);
+        call(&breaker, false).unwrap();
+        call(&breaker, true).unwrap();
+        call(&breaker, true).unwrap();
+        assert_eq!(breaker.state(AGGREGATOR), CircuitState::Closed);
+
+        let status = &breaker.status()[0];
+        assert_eq!((status.requests, status.failures), (3, 1));
+    }
+
+    #[test]
+    fn test_half_open_probe_closes_on_success() {
+        let (breaker, now) = breaker();
+        for _ in 0..4 {
+            call(&breaker, false).unwrap();
+        }
+
+        advance(&now, 29);
+        assert!(breaker.acquire(AGGREGATOR).is_err());
+        assert_eq!(
+            breaker.retry_after(AGGREGATOR),
+            Some(Duration::from_secs(1))
+        );
+
+        advance(&now, 1);
+        assert_eq!(breaker.retry_after(AGGREGATOR), None);
+
+        // 只放行一個探測
+        breaker.acquire(AGGREGATOR).unwrap();
+        assert_eq!(breaker.state(AGGREGATOR), CircuitState::HalfOpen);
+        assert!(breaker.acquire(AGGREGATOR).is_err());
+
+        breaker.record(AGGREGATOR, true);
+        assert_eq!(breaker.state(AGGREGATOR), CircuitState::Closed);
+        call(&breaker, true).unwrap();
+
+        let transitions = breaker.status()[0].transitions;
+        assert_eq!(
+            transitions,
+            TransitionCounts {
+                opened: 1,
+                half_opened: 1,
+                closed: 1,
+                rejected: 2,
+            }
+        );
+    }
+
+    #[test]
+    fn test_failed_probe_reopens_for_full_cooldown() {
+        let (breaker, now) = breaker();
+        for _ in 0..4 {
+            call(&breaker, false).unwrap();
+        }
+
+        advance(&now, 30);
+        call(&breaker, false).unwrap();
+        assert_eq!(breaker.state(AGGREGATOR), CircuitState::Open);
+        assert_eq!(
+            breaker.retry_after(AGGREGATOR),
+            Some(Duration::from_secs(30))
+        );
+        assert_eq!(breaker.status()[0].transitions.opened, 2);
+    }
+
+    #[test]
+    fn test_disabled_breaker_never_opens() {
+        let (clock, _) = mock_clock();
+        let breaker = CircuitBreaker::new(BreakerConfig {
+            enabled: false,
+            min_requests: 1,
+            ..BreakerConfig::default()
+        })
+        .with_clock(clock);
+
+        for _ in 0..10 {
+            call(&breaker, false).unwrap();
+        }
+        assert!(breaker.status().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_call_classifies_errors() {
+        let (breaker, _) = breaker();
+
+        // 數據錯誤說明端點仍在響應
+        for _ in 0..4 {
+            let _ = breaker
+                .call(AGGREGATOR, async {
+                    Err::<(), _>(AuditorError::InvalidSliver("bad".to_string()))
+                })
+                .await;
+        }
+        assert_eq!(breaker.state(AGGREGATOR), CircuitState::Closed);
+
+        for _ in 0..4 {
+            let _ = breaker
+                .call(AGGREGATOR, async {
+                    Err::<(), _>(AuditorError::StorageNodeUnreachable("HTTP 503".to_string()))
+                })
+                .await;
+        }
+        assert_eq!(breaker.state(AGGREGATOR), CircuitState::Open);
+
+        let result = breaker.call(AGGREGATOR, async { Ok(()) }).await;
+        assert!(matches!(result, Err(AuditorError::CircuitOpen { .. })));
+    }
+
+    #[test]
+    fn test_failure_status_classification() {
+        assert!(is_failure_status(429));
+        assert!(is_failure_status(503));
+        assert!(!is_failure_status(404));
+        assert!(!is_failure_status(200));
+    }
+}
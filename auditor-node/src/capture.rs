@@ -0,0 +1,560 @@
+//! HTTP 請求/響應捕獲模塊
+//!
+//! 當存儲節點運營者對 FAIL 判定提出異議時，我們需要證明當時確切發送和接收了什麼。
+//! 啟用捕獲模式（`capture_http = true` 或 `--capture-http`）後，
+//! `StorageNodeClient` 和 `IntegrityVerifier` 會把每個 HTTP 交換記錄到該次審計專屬的捕獲目錄。
+//!
+//! # 目錄結構
+//!
+//! ```text
+//! <capture_dir>/<blob_id>-<timestamp_ms>/
+//! ├── capture.jsonl        # 每行一條 CaptureRecord
+//! └── bodies/
+//!     └── <blake2b>.bin    # 超過內聯閾值的請求/響應體
+//! ```
+//!
+//! # 證據綁定
+//!
+//! 審計結束時對整個捕獲目錄計算 Blake2b-256 摘要（[`capture_digest`]），
+//! 寫入審計報告的 `capture_digest` 字段。報告簽名後，捕獲內容即與判定綁定；
+//! `capture verify` 子命令重新計算摘要並與報告比對。
+//!
+//! # 隱私
+//!
+//! 認證類請求頭（Authorization、Cookie 等）在寫入前會被替換為 `[REDACTED]`。
+//!
+//! 捕獲關閉時，各客戶端只持有 `None`，不會克隆請求頭或計算任何摘要。
+
+use crate::crypto::ct_eq_hex;
+use crate::error::{AuditorError, Result};
+use base64::Engine;
+use chrono::Utc;
+use fastcrypto::hash::{Blake2b256, HashFunction};
+use reqwest::header::HeaderMap;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tracing::{debug, info};
+
+/// 捕獲記錄文件名
+pub const CAPTURE_FILE_NAME: &str = "capture.jsonl";
+
+/// 大型消息體存放子目錄
+pub const BODIES_DIR_NAME: &str = "bodies";
+
+/// 默認內聯閾值：不超過 4 KB 的消息體以 base64 內聯在記錄中
+pub const DEFAULT_INLINE_BODY_LIMIT: usize = 4096;
+
+/// 需要脫敏的請求/響應頭（小寫）
+const REDACTED_HEADERS: &[&str] = &[
+    "authorization",
+    "proxy-authorization",
+    "cookie",
+    "set-cookie",
+    "x-api-key",
+    "x-auth-token",
+];
+
+/// 脫敏佔位符
+const REDACTED: &str = "[REDACTED]";
+
+/// 消息體記錄
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CapturedBody {
+    /// 消息體長度（字節）
+    pub len: u64,
+
+    /// 消息體的 Blake2b-256 摘要（hex）
+    pub digest: String,
+
+    /// 內聯內容（base64，僅小於閾值時）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub inline_b64: Option<String>,
+
+    /// 外部文件（相對於捕獲目錄，僅超過閾值時）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
+}
+
+/// 單次 HTTP 交換的捕獲記錄
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CaptureRecord {
+    /// 序號（從 0 開始）
+    pub seq: u64,
+
+    /// 請求開始時間（RFC 3339）
+    pub started_at: String,
+
+    /// 耗時（毫秒）
+    pub duration_ms: u64,
+
+    /// HTTP 方法
+    pub method: String,
+
+    /// 請求 URL
+    pub url: String,
+
+    /// 請求頭（已脫敏）
+    pub request_headers: BTreeMap<String, String>,
+
+    /// 請求體
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_body: Option<CapturedBody>,
+
+    /// 響應狀態碼（網絡錯誤時為空）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub response_status: Option<u16>,
+
+    /// 響應頭（已脫敏）
+    #[serde(default)]
+    pub response_headers: BTreeMap<String, String>,
+
+    /// 響應體
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub response_body: Option<CapturedBody>,
+
+    /// 網絡錯誤描述
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// 待記錄的 HTTP 交換
+///
+/// 由客戶端在請求完成後填寫並交給 [`HttpCapture::record`]
+#[derive(Debug, Default)]
+pub struct HttpExchange<'a> {
+    /// HTTP 方法
+    pub method: &'a str,
+    /// 請求 URL
+    pub url: &'a str,
+    /// 請求頭
+    pub request_headers: Option<&'a HeaderMap>,
+    /// 請求體
+    pub request_body: Option<&'a [u8]>,
+    /// 響應狀態碼
+    pub status: Option<u16>,
+    /// 響應頭
+    pub response_headers: Option<&'a HeaderMap>,
+    /// 響應體
+    pub response_body: Option<&'a [u8]>,
+    /// 耗時
+    pub duration: Duration,
+    /// 網絡錯誤
+    pub error: Option<String>,
+}
+
+/// 捕獲會話內部狀態
+struct CaptureState {
+    file: File,
+    next_seq: u64,
+}
+
+/// 單次審計的 HTTP 捕獲會話
+///
+/// 可克隆並在多個客戶端之間共享，寫入是串行化的。
+#[derive(Clone)]
+pub struct HttpCapture {
+    dir: PathBuf,
+    inline_limit: usize,
+    state: Arc<Mutex<CaptureState>>,
+}
+
+impl std::fmt::Debug for HttpCapture {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HttpCapture")
+            .field("dir", &self.dir)
+            .field("inline_limit", &self.inline_limit)
+            .finish()
+    }
+}
+
+impl HttpCapture {
+    /// 在指定目錄創建捕獲會話
+    ///
+    /// # 錯誤
+    /// - 目錄已包含捕獲文件時返回 `AuditorError::Config`（避免覆蓋證據）
+    pub fn create(dir: impl AsRef<Path>) -> Result<Self> {
+        let dir = dir.as_ref().to_path_buf();
+        fs::create_dir_all(dir.join(BODIES_DIR_NAME))?;
+
+        let file = OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(dir.join(CAPTURE_FILE_NAME))
+            .map_err(|e| {
+                AuditorError::Config(format!(
+                    "Cannot create capture file in {}: {}",
+                    dir.display(),
+                    e
+                ))
+            })?;
+
+        info!("HTTP capture enabled: {}", dir.display());
+
+        Ok(Self {
+            dir,
+            inline_limit: DEFAULT_INLINE_BODY_LIMIT,
+            state: Arc::new(Mutex::new(CaptureState { file, next_seq: 0 })),
+        })
+    }
+
+    /// 為某次審計在 `base_dir` 下創建獨立的捕獲目錄
+    pub fn for_audit(base_dir: impl AsRef<Path>, blob_id: &str) -> Result<Self> {
+        let safe_id: String = blob_id
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+            .collect();
+        let dir_name = format!("{}-{}", safe_id, Utc::now().timestamp_millis());
+
+        Self::create(base_dir.as_ref().join(dir_name))
+    }
+
+    /// 設置內聯閾值
+    pub fn with_inline_limit(mut self, inline_limit: usize) -> Self {
+        self.inline_limit = inline_limit;
+        self
+    }
+
+    /// 捕獲目錄
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// 記錄一次 HTTP 交換
+    pub fn record(&self, exchange: HttpExchange<'_>) -> Result<()> {
+        let started_at = Utc::now()
+            - chrono::Duration::from_std(exchange.duration).unwrap_or_else(|_| chrono::Duration::zero());
+
+        let request_body = exchange
+            .request_body
+            .map(|body| self.store_body(body))
+            .transpose()?;
+        let response_body = exchange
+            .response_body
+            .map(|body| self.store_body(body))
+            .transpose()?;
+
+        let mut state = self
+            .state
+            .lock()
+            .map_err(|_| AuditorError::Other(anyhow::anyhow!("capture state poisoned")))?;
+
+        let record = CaptureRecord {
+            seq: state.next_seq,
+            started_at: started_at.to_rfc3339(),
+            duration_ms: exchange.duration.as_millis() as u64,
+            method: exchange.method.to_string(),
+            url: exchange.url.to_string(),
+            request_headers: exchange.request_headers.map(redact_headers).unwrap_or_default(),
+            request_body,
+            response_status: exchange.status,
+            response_headers: exchange.response_headers.map(redact_headers).unwrap_or_default(),
+            response_body,
+            error: exchange.error,
+        };
+
+        let mut line = serde_json::to_vec(&record)?;
+        line.push(b'\n');
+        state.file.write_all(&line)?;
+        state.next_seq += 1;
+
+        debug!("Captured HTTP exchange #{}: {} {}", record.seq, record.method, record.url);
+
+        Ok(())
+    }
+
+    /// 結束捕獲並返回整個目錄的 Blake2b-256 摘要（hex）
+    pub fn finish(&self) -> Result<String> {
+        {
+            let mut state = self
+                .state
+                .lock()
+                .map_err(|_| AuditorError::Other(anyhow::anyhow!("capture state poisoned")))?;
+            state.file.flush()?;
+            state.file.sync_all()?;
+        }
+
+        let digest = capture_digest(&self.dir)?;
+        info!("HTTP capture finished: {} (digest {})", self.dir.display(), &digest[..16]);
+        Ok(digest)
+    }
+
+    /// 保存消息體：小於閾值內聯，否則寫入 bodies/ 目錄
+    fn store_body(&self, body: &[u8]) -> Result<CapturedBody> {
+        let digest = hex::encode(blake2b(body));
+
+        if body.len() <= self.inline_limit {
+            return Ok(CapturedBody {
+                len: body.len() as u64,
+                digest,
+                inline_b64: Some(base64::engine::general_purpose::STANDARD.encode(body)),
+                file: None,
+            });
+        }
+
+        let relative = format!("{}/{}.bin", BODIES_DIR_NAME, digest);
+        let path = self.dir.join(&relative);
+        if !path.exists() {
+            fs::write(&path, body)?;
+        }
+
+        Ok(CapturedBody {
+            len: body.len() as u64,
+            digest,
+            inline_b64: None,
+            file: Some(relative),
+        })
+    }
+}
+
+/// 將請求/響應頭轉為有序映射並脫敏
+pub fn redact_headers(headers: &HeaderMap) -> BTreeMap<String, String> {
+    let mut map = BTreeMap::new();
+
+    for (name, value) in headers {
+        let key = name.as_str().to_ascii_lowercase();
+        let value = if REDACTED_HEADERS.contains(&key.as_str()) {
+            REDACTED.to_string()
+        } else {
+            String::from_utf8_lossy(value.as_bytes()).into_owned()
+        };
+
+        map.entry(key)
+            .and_modify(|existing: &mut String| {
+                existing.push_str(", ");
+                existing.push_str(&value);
+            })
+            .or_insert(value);
+    }
+
+    map
+}
+
+/// 計算捕獲目錄的 Blake2b-256 摘要（hex）
+///
+/// 按相對路徑排序遍歷 `capture.jsonl` 和 `bodies/` 下的所有文件，
+/// 對每個文件依次哈希：路徑長度（u64 LE）、路徑、內容長度（u64 LE）、內容。
+pub fn capture_digest(dir: impl AsRef<Path>) -> Result<String> {
+    let dir = dir.as_ref();
+    let mut files = vec![CAPTURE_FILE_NAME.to_string()];
+
+    let bodies_dir = dir.join(BODIES_DIR_NAME);
+    if bodies_dir.is_dir() {
+        let mut bodies = Vec::new();
+        for entry in fs::read_dir(&bodies_dir)? {
+            let entry = entry?;
+            if entry.file_type()?.is_file() {
+                bodies.push(format!(
+                    "{}/{}",
+                    BODIES_DIR_NAME,
+                    entry.file_name().to_string_lossy()
+                ));
+            }
+        }
+        bodies.sort();
+        files.extend(bodies);
+    }
+
+    let mut hasher = Blake2b256::default();
+    for relative in &files {
+        let content = fs::read(dir.join(relative))?;
+        hasher.update((relative.len() as u64).to_le_bytes());
+        hasher.update(relative.as_bytes());
+        hasher.update((content.len() as u64).to_le_bytes());
+        hasher.update(&content);
+    }
+
+    Ok(hex::encode(hasher.finalize().digest))
+}
+
+/// 重新計算捕獲目錄摘要並與預期值比對（常量時間，大小寫不敏感）
+///
+/// # 錯誤
+/// 預期值不是合法的十六進制時返回 [`AuditorError::InvalidDigest`]
+pub fn verify_capture(dir: impl AsRef<Path>, expected_digest: &str) -> Result<bool> {
+    let actual = capture_digest(dir)?;
+    ct_eq_hex(&actual, expected_digest.trim())
+}
+
+/// 從審計報告 JSON 中提取 `capture_digest`
+///
+/// 支持頂層字段（`AuditReport`、`AuditData`）以及 `audit_data.capture_digest`（`SignedAuditReport`）
+pub fn capture_digest_from_report(report: &serde_json::Value) -> Option<String> {
+    report
+        .get("capture_digest")
+        .or_else(|| report.get("audit_data").and_then(|d| d.get("capture_digest")))
+        .and_then(|v| v.as_str())
+        .map(str::to_string)
+}
+
+fn blake2b(data: &[u8]) -> [u8; 32] {
+    let mut hasher = Blake2b256::default();
+    hasher.update(data);
+    hasher.finalize().digest
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::{HeaderValue, AUTHORIZATION, CONTENT_TYPE, COOKIE};
+
+    fn temp_capture_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "walrus_capture_test_{}_{}",
+            name,
+            rand::random::<u64>()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn read_records(dir: &Path) -> Vec<CaptureRecord> {
+        fs::read_to_string(dir.join(CAPTURE_FILE_NAME))
+            .unwrap()
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_redact_headers() {
+        let mut headers = HeaderMap::new();
+        headers.insert(AUTHORIZATION, HeaderValue::from_static("Bearer secret-token"));
+        headers.insert(COOKIE, HeaderValue::from_static("session=abc"));
+        headers.insert("X-Api-Key", HeaderValue::from_static("key123"));
+        headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+
+        let redacted = redact_headers(&headers);
+
+        assert_eq!(redacted["authorization"], REDACTED);
+        assert_eq!(redacted["cookie"], REDACTED);
+        assert_eq!(redacted["x-api-key"], REDACTED);
+        assert_eq!(redacted["content-type"], "application/json");
+    }
+
+    #[test]
+    fn test_record_redacts_and_stores_bodies() {
+        let dir = temp_capture_dir("record");
+        let capture = HttpCapture::create(&dir).unwrap().with_inline_limit(16);
+
+        let mut request_headers = HeaderMap::new();
+        request_headers.insert(AUTHORIZATION, HeaderValue::from_static("Bearer secret-token"));
+        let large_body = vec![0xABu8; 100];
+
+        capture
+            .record(HttpExchange {
+                method: "POST",
+                url: "http://node/v1/challenge",
+                request_headers: Some(&request_headers),
+                request_body: Some(b"{\"a\":1}"),
+                status: Some(200),
+                response_body: Some(&large_body),
+                duration: Duration::from_millis(12),
+                ..Default::default()
+            })
+            .unwrap();
+        capture.finish().unwrap();
+
+        let records = read_records(&dir);
+        assert_eq!(records.len(), 1);
+        let record = &records[0];
+        assert_eq!(record.request_headers["authorization"], REDACTED);
+        assert_eq!(record.duration_ms, 12);
+
+        // 小請求體內聯
+        let request_body = record.request_body.as_ref().unwrap();
+        assert!(request_body.inline_b64.is_some());
+        assert!(request_body.file.is_none());
+
+        // 大響應體單獨存放
+        let response_body = record.response_body.as_ref().unwrap();
+        assert_eq!(response_body.len, 100);
+        let file = response_body.file.as_ref().unwrap();
+        assert_eq!(fs::read(dir.join(file)).unwrap(), large_body);
+
+        // 原始密鑰不能出現在捕獲文件中
+        let raw = fs::read_to_string(dir.join(CAPTURE_FILE_NAME)).unwrap();
+        assert!(!raw.contains("secret-token"));
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_digest_binding_detects_tampering() {
+        let dir = temp_capture_dir("digest");
+        let capture = HttpCapture::create(&dir).unwrap().with_inline_limit(4);
+
+        capture
+            .record(HttpExchange {
+                method: "GET",
+                url: "http://aggregator/v1/blobs/abc",
+                status: Some(200),
+                response_body: Some(b"blob content that is stored separately"),
+                ..Default::default()
+            })
+            .unwrap();
+        let digest = capture.finish().unwrap();
+
+        assert!(verify_capture(&dir, &digest).unwrap());
+        assert!(verify_capture(&dir, &format!(" {} ", digest.to_uppercase())).unwrap());
+        assert!(!verify_capture(&dir, &"00".repeat(32)).unwrap());
+        assert!(matches!(
+            verify_capture(&dir, "not a digest"),
+            Err(AuditorError::InvalidDigest(_))
+        ));
+
+        // 篡改外部存放的響應體
+        let body_file = fs::read_dir(dir.join(BODIES_DIR_NAME))
+            .unwrap()
+            .next()
+            .unwrap()
+            .unwrap()
+            .path();
+        fs::write(&body_file, b"forged").unwrap();
+        assert!(!verify_capture(&dir, &digest).unwrap());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_create_refuses_to_overwrite() {
+        let dir = temp_capture_dir("overwrite");
+        let _first = HttpCapture::create(&dir).unwrap();
+
+        assert!(HttpCapture::create(&dir).is_err());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_for_audit_sanitizes_blob_id() {
+        let base = temp_capture_dir("for_audit");
+        let capture = HttpCapture::for_audit(&base, "abc/../def").unwrap();
+
+        assert!(capture.dir().starts_with(&base));
+        assert!(capture
+            .dir()
+            .file_name()
+            .unwrap()
+            .to_string_lossy()
+            .starts_with("abc____def-"));
+
+        let _ = fs::remove_dir_all(&base);
+    }
+
+    #[test]
+    fn test_capture_digest_from_report() {
+        let report = serde_json::json!({ "blob_id": "x", "capture_digest": "aa" });
+        assert_eq!(capture_digest_from_report(&report), Some("aa".to_string()));
+
+        let signed = serde_json::json!({ "audit_data": { "capture_digest": "bb" } });
+        assert_eq!(capture_digest_from_report(&signed), Some("bb".to_string()));
+
+        let none = serde_json::json!({ "blob_id": "x" });
+        assert_eq!(capture_digest_from_report(&none), None);
+    }
+}
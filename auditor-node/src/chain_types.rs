@@ -0,0 +1,897 @@
+//! 鏈上 Move 類型的 Rust 鏡像
+//!
+//! `audit_system` / `access_policy` 合約的入口函數對參數類型有嚴格要求
+//! （`u256` Blob ID、`u16` 計數器、`vector<u8>` 哈希等）。本模塊為這些入口函數的
+//! 參數和事件定義帶類型的 Rust 鏡像，所有交易構造（PTB 或 `unsafe_moveCall`）
+//! 都必須經由這些類型完成，避免與 Move ABI 漂移。
+//!
+//! # 類型對應
+//!
+//! | Move          | Rust                  | BCS 編碼               |
+//! |---------------|-----------------------|------------------------|
+//! | `u256`        | [`MoveU256`]          | 32 字節小端            |
+//! | `ID`/`address`| [`MoveId`]            | 32 字節                |
+//! | `u16`/`u32`   | `u16`/`u32`           | 小端定長               |
+//! | `vector<u8>`  | `Vec<u8>`             | ULEB128 長度 + 字節    |
+//! | `Option<u64>` | `Option<u64>`         | 0/1 標籤 + 值          |
+//!
+//! # 入口函數
+//!
+//! - [`AuditRecordParams`]：`audit_core::submit_audit_record`
+//! - [`ReportMetadataParams`]：`auditor_registry::submit_audit_report_metadata`
+//! - [`PolicyParams`]：`report_access::create_policy`
+//!
+//! # 鏈上對象
+//!
+//! - [`AuditCreatedEvent`]：`audit_core::AuditCreated` 事件（BCS 或 JSON-RPC `parsedJson`）
+//! - [`OnChainAuditRecord`]：`audit_core::AuditRecord` 共享對象（JSON-RPC `showContent`）
+//!
+//! 每個參數結構的 BCS 編碼（字段按聲明順序拼接）與 `tests/fixtures/chain_types.json`
+//! 中的固定值比對；這些固定值由 `contracts/audit_system/tests/abi_fixtures_tests.move`
+//! 在 Move 端以 `bcs::to_bytes` 生成並斷言，任何一端改動參數都會使測試失敗。
+//! 合約接受的簽名算法編號同樣記錄在該文件中（見 [`contract_pqc_algorithm`]）。
+
+use crate::audit_report::PqcAlgorithm;
+#[allow(deprecated)]
+use crate::audit_report::SignedAuditReport;
+use crate::error::{AuditorError, Result};
+use crate::types::AuditReport;
+use base64::{engine::general_purpose, Engine as _};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::fmt;
+
+/// 完整性哈希長度（SHA-256）
+pub const INTEGRITY_HASH_LEN: usize = 32;
+
+/// `audit_system` 模塊名
+pub const AUDIT_CORE_MODULE: &str = "audit_core";
+
+/// `auditor_registry` 模塊名
+pub const AUDITOR_REGISTRY_MODULE: &str = "auditor_registry";
+
+/// `access_policy` 模塊名
+pub const REPORT_ACCESS_MODULE: &str = "report_access";
+
+/// `report_access` 模塊的中止碼（與 `contracts/access_policy/sources/report_access.move` 一致）
+const REPORT_ACCESS_ABORT_CODES: &[(u64, &str)] = &[
+    (1, "E_POLICY_EXPIRED"),
+    (2, "E_UNAUTHORIZED_ACCESS"),
+    (3, "E_INVALID_ACCESS_TYPE"),
+    (4, "E_NOT_POLICY_CREATOR"),
+    (5, "E_POLICY_REVOKED"),
+    (6, "E_TOKEN_EXPIRED"),
+    (7, "E_REPORT_NOT_FOUND"),
+];
+
+/// Sui Clock 共享對象
+pub const SUI_CLOCK_OBJECT_ID: &str = "0x6";
+
+/// `audit_core::submit_audit_record` 接受的算法編號（`is_supported_pqc_algorithm`：
+/// 1=Falcon512, 2=Dilithium2, 3=Dilithium3）
+pub const CONTRACT_PQC_ALGORITHMS: &[u8] = &[1, 2, 3];
+
+/// 將報告的 `pqc_algorithm`（[`PqcAlgorithm::id`]）映射為 `audit_core` 的算法編號
+///
+/// 兩端的編號分別定義，新增簽名算法時必須在此登記
+///
+/// # 錯誤
+/// 沒有對應的合約編號時返回 `UnsupportedPqcAlgorithm`
+pub fn contract_pqc_algorithm(report_algorithm: u8) -> Result<u8> {
+    match PqcAlgorithm::from_id(report_algorithm) {
+        // ALGO_FALCON512
+        Some(PqcAlgorithm::Falcon512) => Ok(1),
+        // ALGO_DILITHIUM3
+        Some(PqcAlgorithm::Dilithium3) => Ok(3),
+        None => Err(AuditorError::UnsupportedPqcAlgorithm(report_algorithm)),
+    }
+}
+
+/// 將計數轉換為 Move `u16`，超出範圍時返回錯誤（而不是靜默截斷）
+pub fn checked_u16(field: &str, value: usize) -> Result<u16> {
+    u16::try_from(value).map_err(|_| {
+        AuditorError::ChainAbi(format!(
+            "{} = {} exceeds u16::MAX ({})",
+            field,
+            value,
+            u16::MAX
+        ))
+    })
+}
+
+/// Move `u256`（32 字節小端）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct MoveU256(pub [u8; 32]);
+
+impl MoveU256 {
+    /// 從 Walrus Blob ID（URL-safe Base64，32 字節小端）轉換
+    pub fn from_blob_id(blob_id: &str) -> Result<Self> {
+        let bytes = general_purpose::URL_SAFE_NO_PAD
+            .decode(blob_id.trim_end_matches('='))
+            .map_err(|e| AuditorError::ChainAbi(format!("Invalid blob ID {}: {}", blob_id, e)))?;
+
+        let bytes: [u8; 32] = bytes.try_into().map_err(|bytes: Vec<u8>| {
+            AuditorError::ChainAbi(format!("Blob ID must be 32 bytes, got {}", bytes.len()))
+        })?;
+
+        Ok(Self(bytes))
+    }
+
+    /// 轉換回 Walrus Blob ID（URL-safe Base64，無填充）
+    pub fn to_blob_id(self) -> String {
+        general_purpose::URL_SAFE_NO_PAD.encode(self.0)
+    }
+
+    /// 十進制字符串（JSON-RPC 參數格式）
+    pub fn to_decimal_string(self) -> String {
+        // 小端 → 大端，再反覆除以 10 得到十進制數字
+        let mut number: Vec<u8> = self.0.iter().rev().copied().collect();
+        let mut digits = Vec::new();
+
+        while number.iter().any(|&b| b != 0) {
+            let mut remainder = 0u32;
+            for byte in number.iter_mut() {
+                let value = (remainder << 8) | *byte as u32;
+                *byte = (value / 10) as u8;
+                remainder = value % 10;
+            }
+            digits.push(b'0' + remainder as u8);
+        }
+
+        if digits.is_empty() {
+            return "0".to_string();
+        }
+
+        digits.reverse();
+        String::from_utf8(digits).expect("ASCII digits")
+    }
+
+    /// 從十進制字符串解析（JSON-RPC 中 `u256` 的表示）
+    pub fn from_decimal_str(value: &str) -> Result<Self> {
+        if value.is_empty() || !value.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(AuditorError::ChainAbi(format!("Invalid u256: {:?}", value)));
+        }
+
+        // 大端字節，逐位乘 10 累加
+        let mut number = [0u8; 32];
+        for digit in value.bytes() {
+            let mut carry = (digit - b'0') as u32;
+            for byte in number.iter_mut().rev() {
+                let product = *byte as u32 * 10 + carry;
+                *byte = product as u8;
+                carry = product >> 8;
+            }
+            if carry != 0 {
+                return Err(AuditorError::ChainAbi(format!("u256 overflow: {}", value)));
+            }
+        }
+
+        number.reverse();
+        Ok(Self(number))
+    }
+}
+
+impl fmt::Display for MoveU256 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_decimal_string())
+    }
+}
+
+/// Move `ID` / `address`（32 字節）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct MoveId(pub [u8; 32]);
+
+impl MoveId {
+    /// 從 `0x` 前綴的十六進制字符串解析（短地址左側補零，如 `0x6`）
+    pub fn from_hex(value: &str) -> Result<Self> {
+        let digits = value.strip_prefix("0x").unwrap_or(value);
+        if digits.is_empty() || digits.len() > 64 {
+            return Err(AuditorError::ChainAbi(format!(
+                "Invalid object ID/address length: {}",
+                value
+            )));
+        }
+
+        let padded = format!("{:0>64}", digits);
+        let bytes = hex::decode(&padded)
+            .map_err(|e| AuditorError::ChainAbi(format!("Invalid object ID {}: {}", value, e)))?;
+
+        Ok(Self(bytes.try_into().expect("64 hex digits")))
+    }
+}
+
+impl fmt::Display for MoveId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "0x{}", hex::encode(self.0))
+    }
+}
+
+/// 將值編碼為 BCS（PTB 的 pure 參數）
+fn bcs_bytes<T: Serialize>(value: &T) -> Result<Vec<u8>> {
+    bcs::to_bytes(value).map_err(|e| AuditorError::ChainAbi(format!("BCS encoding failed: {}", e)))
+}
+
+/// `audit_core::submit_audit_record` 的參數
+///
+/// ```move
+/// public entry fun submit_audit_record(
+///     config: &mut AuditConfig,
+///     blob_id: u256,
+///     blob_object_id: ID,
+///     challenge_epoch: u32,
+///     total_challenges: u16,
+///     successful_verifications: u16,
+///     integrity_hash: vector<u8>,
+///     pqc_signature: vector<u8>,
+///     pqc_algorithm: u8,
+///     clock: &Clock,
+///     ctx: &mut TxContext
+/// )
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditRecordParams {
+    /// 被審計的 Blob ID
+    pub blob_id: MoveU256,
+
+    /// Blob 對象 ID
+    pub blob_object_id: MoveId,
+
+    /// 執行審計時的 epoch
+    pub challenge_epoch: u32,
+
+    /// 總挑戰次數
+    pub total_challenges: u16,
+
+    /// 成功驗證次數
+    pub successful_verifications: u16,
+
+    /// 完整性哈希（32 字節；未下載到內容時為空）
+    pub integrity_hash: Vec<u8>,
+
+    /// PQC 簽名
+    pub pqc_signature: Vec<u8>,
+
+    /// PQC 算法（合約編號，見 [`contract_pqc_algorithm`]）
+    pub pqc_algorithm: u8,
+}
+
+impl AuditRecordParams {
+    /// Move 函數名
+    pub const FUNCTION: &'static str = "submit_audit_record";
+
+    /// 從已簽名報告構建參數
+    ///
+    /// # 參數
+    /// - `report`: 已簽名的審計報告
+    /// - `blob_object_id`: Blob 對象 ID（`None` 時取報告中的 ID，仍未知則使用零 ID）
+    /// - `challenge_epoch`: 執行審計時的 epoch
+    pub fn from_report(
+        report: &AuditReport,
+        blob_object_id: Option<&str>,
+        challenge_epoch: u32,
+    ) -> Result<Self> {
+        let integrity_hash = Self::integrity_hash_for(report)?;
+
+        let params = Self {
+            blob_id: MoveU256::from_blob_id(&report.blob_id)?,
+            blob_object_id: blob_object_id
+                .or(Some(report.blob_object_id.as_str()).filter(|id| !id.is_empty()))
+                .map(MoveId::from_hex)
+                .transpose()?
+                .unwrap_or(MoveId([0u8; 32])),
+            challenge_epoch,
+            total_challenges: report.total_challenges,
+            successful_verifications: report.successful_verifications,
+            integrity_hash,
+            pqc_signature: report.pqc_signature.clone(),
+            pqc_algorithm: contract_pqc_algorithm(report.pqc_algorithm)?,
+        };
+
+        params.validate()?;
+        Ok(params)
+    }
+
+    /// 報告提交到鏈上的 `integrity_hash`
+    ///
+    /// 完整性審計的報告以內容哈希為準（非十六進制時拒絕），挑戰級報告使用 `integrity_hash`
+    pub fn integrity_hash_for(report: &AuditReport) -> Result<Vec<u8>> {
+        match &report.integrity {
+            Some(integrity) => hex::decode(&integrity.content_hash)
+                .map_err(|e| AuditorError::ChainAbi(format!("Invalid content hash: {}", e))),
+            None => Ok(report.integrity_hash.clone()),
+        }
+    }
+
+    /// 從舊版已簽名報告構建參數
+    #[deprecated(note = "use AuditRecordParams::from_report with types::AuditReport")]
+    #[allow(deprecated)]
+    pub fn from_signed_report(
+        report: &SignedAuditReport,
+        blob_object_id: Option<&str>,
+        challenge_epoch: u32,
+    ) -> Result<Self> {
+        Self::from_report(
+            &AuditReport::try_from(report.clone())?,
+            blob_object_id,
+            challenge_epoch,
+        )
+    }
+
+    /// 檢查 Move 端會中止（abort）或無法表示的參數
+    pub fn validate(&self) -> Result<()> {
+        // Move 端計算 total - successful，溢出會中止交易
+        if self.successful_verifications > self.total_challenges {
+            return Err(AuditorError::ChainAbi(format!(
+                "successful_verifications ({}) exceeds total_challenges ({})",
+                self.successful_verifications, self.total_challenges
+            )));
+        }
+
+        if !self.integrity_hash.is_empty() && self.integrity_hash.len() != INTEGRITY_HASH_LEN {
+            return Err(AuditorError::ChainAbi(format!(
+                "integrity_hash must be {} bytes, got {}",
+                INTEGRITY_HASH_LEN,
+                self.integrity_hash.len()
+            )));
+        }
+
+        if self.pqc_signature.is_empty() {
+            return Err(AuditorError::ChainAbi("pqc_signature is empty".to_string()));
+        }
+
+        if !CONTRACT_PQC_ALGORITHMS.contains(&self.pqc_algorithm) {
+            return Err(AuditorError::UnsupportedPqcAlgorithm(self.pqc_algorithm));
+        }
+
+        Ok(())
+    }
+
+    /// 每個 pure 參數的 BCS 編碼（按 Move 參數順序，不含對象參數）
+    pub fn pure_args(&self) -> Result<Vec<Vec<u8>>> {
+        Ok(vec![
+            bcs_bytes(&self.blob_id)?,
+            bcs_bytes(&self.blob_object_id)?,
+            bcs_bytes(&self.challenge_epoch)?,
+            bcs_bytes(&self.total_challenges)?,
+            bcs_bytes(&self.successful_verifications)?,
+            bcs_bytes(&self.integrity_hash)?,
+            bcs_bytes(&self.pqc_signature)?,
+            bcs_bytes(&self.pqc_algorithm)?,
+        ])
+    }
+
+    /// `unsafe_moveCall` 的 JSON 參數（含 AuditConfig 與 Clock 對象）
+    pub fn json_args(&self, audit_config_id: &str) -> Vec<Value> {
+        vec![
+            json!(audit_config_id),
+            json!(self.blob_id.to_decimal_string()),
+            json!(self.blob_object_id.to_string()),
+            json!(self.challenge_epoch),
+            json!(self.total_challenges),
+            json!(self.successful_verifications),
+            json!(self.integrity_hash),
+            json!(self.pqc_signature),
+            json!(self.pqc_algorithm),
+            json!(SUI_CLOCK_OBJECT_ID),
+        ]
+    }
+}
+
+impl TryFrom<&AuditReport> for AuditRecordParams {
+    type Error = AuditorError;
+
+    fn try_from(report: &AuditReport) -> Result<Self> {
+        let params = Self {
+            blob_id: MoveU256::from_blob_id(&report.blob_id)?,
+            blob_object_id: MoveId::from_hex(&report.blob_object_id)?,
+            challenge_epoch: report.challenge_epoch,
+            total_challenges: report.total_challenges,
+            successful_verifications: report.successful_verifications,
+            integrity_hash: report.integrity_hash.clone(),
+            pqc_signature: report.pqc_signature.clone(),
+            pqc_algorithm: contract_pqc_algorithm(report.pqc_algorithm)?,
+        };
+
+        params.validate()?;
+        Ok(params)
+    }
+}
+
+/// `auditor_registry::submit_audit_report_metadata` 的參數
+///
+/// ```move
+/// public entry fun submit_audit_report_metadata(
+///     registry: &AuditorRegistry,
+///     encrypted_report_blob_id: ID,
+///     audit_record_ids: vector<ID>,
+///     pqc_signature: vector<u8>,
+///     clock: &Clock,
+///     ctx: &mut TxContext
+/// )
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReportMetadataParams {
+    /// Walrus 上加密報告的 Blob 對象 ID
+    pub encrypted_report_blob_id: MoveId,
+
+    /// 報告涵蓋的審計記錄 ID
+    pub audit_record_ids: Vec<MoveId>,
+
+    /// 對報告的 PQC 簽名
+    pub pqc_signature: Vec<u8>,
+}
+
+impl ReportMetadataParams {
+    /// Move 函數名
+    pub const FUNCTION: &'static str = "submit_audit_report_metadata";
+
+    /// 從十六進制 ID 構建參數
+    pub fn new(
+        encrypted_report_blob_id: &str,
+        audit_record_ids: &[String],
+        pqc_signature: Vec<u8>,
+    ) -> Result<Self> {
+        if pqc_signature.is_empty() {
+            return Err(AuditorError::ChainAbi("pqc_signature is empty".to_string()));
+        }
+
+        Ok(Self {
+            encrypted_report_blob_id: MoveId::from_hex(encrypted_report_blob_id)?,
+            audit_record_ids: audit_record_ids
+                .iter()
+                .map(|id| MoveId::from_hex(id))
+                .collect::<Result<_>>()?,
+            pqc_signature,
+        })
+    }
+
+    /// 每個 pure 參數的 BCS 編碼
+    pub fn pure_args(&self) -> Result<Vec<Vec<u8>>> {
+        Ok(vec![
+            bcs_bytes(&self.encrypted_report_blob_id)?,
+            bcs_bytes(&self.audit_record_ids)?,
+            bcs_bytes(&self.pqc_signature)?,
+        ])
+    }
+}
+
+/// `report_access::create_policy` 的參數
+///
+/// ```move
+/// public entry fun create_policy(
+///     report_blob_id: u256,
+///     audit_record_id: ID,
+///     allowed_readers: vector<address>,
+///     allowed_auditors: vector<address>,
+///     expires_at_ms: Option<u64>,
+///     clock: &Clock,
+///     ctx: &mut TxContext
+/// )
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PolicyParams {
+    /// 報告的 Walrus Blob ID
+    pub report_blob_id: MoveU256,
+
+    /// 關聯的審計記錄 ID
+    pub audit_record_id: MoveId,
+
+    /// 允許讀取的地址
+    pub allowed_readers: Vec<MoveId>,
+
+    /// 允許讀取的審計員地址
+    pub allowed_auditors: Vec<MoveId>,
+
+    /// 過期時間（毫秒時間戳，`None` 表示永久）
+    pub expires_at_ms: Option<u64>,
+}
+
+impl PolicyParams {
+    /// Move 函數名
+    pub const FUNCTION: &'static str = "create_policy";
+
+    /// 每個 pure 參數的 BCS 編碼
+    pub fn pure_args(&self) -> Result<Vec<Vec<u8>>> {
+        Ok(vec![
+            bcs_bytes(&self.report_blob_id)?,
+            bcs_bytes(&self.audit_record_id)?,
+            bcs_bytes(&self.allowed_readers)?,
+            bcs_bytes(&self.allowed_auditors)?,
+            bcs_bytes(&self.expires_at_ms)?,
+        ])
+    }
+
+    /// `unsafe_moveCall` 的 JSON 參數（含 Clock 對象；`Option` 以零或一個元素的數組表示）
+    pub fn json_args(&self) -> Vec<Value> {
+        let ids = |ids: &[MoveId]| ids.iter().map(MoveId::to_string).collect::<Vec<_>>();
+        vec![
+            json!(self.report_blob_id.to_decimal_string()),
+            json!(self.audit_record_id.to_string()),
+            json!(ids(&self.allowed_readers)),
+            json!(ids(&self.allowed_auditors)),
+            json!(self
+                .expires_at_ms
+                .map(|ms| ms.to_string())
+                .into_iter()
+                .collect::<Vec<_>>()),
+            json!(SUI_CLOCK_OBJECT_ID),
+        ]
+    }
+
+    /// `report_access` 中止碼對應的錯誤常量名
+    pub fn abort_reason(code: u64) -> Option<&'static str> {
+        REPORT_ACCESS_ABORT_CODES
+            .iter()
+            .find(|(c, _)| *c == code)
+            .map(|(_, name)| *name)
+    }
+}
+
+/// `audit_core::AuditCreated` 事件
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditCreatedEvent {
+    pub audit_record_id: MoveId,
+    pub blob_id: MoveU256,
+    pub auditor: MoveId,
+    pub challenge_epoch: u32,
+    pub total_challenges: u16,
+    pub is_valid: bool,
+}
+
+impl AuditCreatedEvent {
+    /// Move 事件類型名
+    pub const EVENT: &'static str = "AuditCreated";
+
+    /// 從事件的 BCS 字節解析
+    pub fn from_bcs(bytes: &[u8]) -> Result<Self> {
+        bcs::from_bytes(bytes)
+            .map_err(|e| AuditorError::ChainAbi(format!("Invalid {} event: {}", Self::EVENT, e)))
+    }
+
+    /// 從 `suix_queryEvents` 返回的 `parsedJson` 解析
+    pub fn from_json(parsed: &Value) -> Result<Self> {
+        Ok(Self {
+            audit_record_id: MoveId::from_hex(json_str(parsed, "audit_record_id")?)?,
+            blob_id: MoveU256::from_decimal_str(json_str(parsed, "blob_id")?)?,
+            auditor: MoveId::from_hex(json_str(parsed, "auditor")?)?,
+            challenge_epoch: json_uint(parsed, "challenge_epoch")?,
+            total_challenges: json_uint(parsed, "total_challenges")?,
+            is_valid: json_bool(parsed, "is_valid")?,
+        })
+    }
+}
+
+/// `audit_core::AuditRecord` 共享對象中用於核對報告的字段
+///
+/// ```move
+/// public struct AuditRecord has key, store {
+///     id: UID,
+///     blob_id: u256,
+///     auditor: address,
+///     challenge_epoch: u32,
+///     total_challenges: u16,
+///     successful_verifications: u16,
+///     integrity_hash: vector<u8>,
+///     pqc_signature: vector<u8>,
+///     is_valid: bool,
+///     ...
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OnChainAuditRecord {
+    pub id: MoveId,
+    pub blob_id: MoveU256,
+    pub auditor: MoveId,
+    pub challenge_epoch: u32,
+    pub total_challenges: u16,
+    pub successful_verifications: u16,
+    pub integrity_hash: Vec<u8>,
+    pub pqc_signature: Vec<u8>,
+    pub is_valid: bool,
+}
+
+impl OnChainAuditRecord {
+    /// Move 結構名
+    pub const STRUCT: &'static str = "AuditRecord";
+
+    /// 從 `sui_getObject`（`showContent`）的 `result` 解析；對象不存在時返回 `None`
+    pub fn from_object_response(result: &Value) -> Result<Option<Self>> {
+        if result.get("error").is_some() {
+            return Ok(None);
+        }
+
+        let data = result
+            .get("data")
+            .ok_or_else(|| AuditorError::ChainAbi("Object response has no data".to_string()))?;
+        let fields = data.pointer("/content/fields").ok_or_else(|| {
+            AuditorError::ChainAbi(format!("{} object has no content fields", Self::STRUCT))
+        })?;
+
+        Ok(Some(Self {
+            id: MoveId::from_hex(json_str(data, "objectId")?)?,
+            blob_id: MoveU256::from_decimal_str(json_str(fields, "blob_id")?)?,
+            auditor: MoveId::from_hex(json_str(fields, "auditor")?)?,
+            challenge_epoch: json_uint(fields, "challenge_epoch")?,
+            total_challenges: json_uint(fields, "total_challenges")?,
+            successful_verifications: json_uint(fields, "successful_verifications")?,
+            integrity_hash: json_bytes(fields, "integrity_hash")?,
+            pqc_signature: json_bytes(fields, "pqc_signature")?,
+            is_valid: json_bool(fields, "is_valid")?,
+        }))
+    }
+}
+
+pub(crate) fn json_field<'a>(value: &'a Value, name: &str) -> Result<&'a Value> {
+    value
+        .get(name)
+        .ok_or_else(|| AuditorError::ChainAbi(format!("Missing field {}", name)))
+}
+
+pub(crate) fn json_str<'a>(value: &'a Value, name: &str) -> Result<&'a str> {
+    json_field(value, name)?
+        .as_str()
+        .ok_or_else(|| AuditorError::ChainAbi(format!("Field {} is not a string", name)))
+}
+
+fn json_bool(value: &Value, name: &str) -> Result<bool> {
+    json_field(value, name)?
+        .as_bool()
+        .ok_or_else(|| AuditorError::ChainAbi(format!("Field {} is not a boolean", name)))
+}
+
+/// 無符號整數字段（JSON-RPC 將 `u64` 及以上編碼為字符串）
+pub(crate) fn json_uint<T: TryFrom<u64>>(value: &Value, name: &str) -> Result<T> {
+    let field = json_field(value, name)?;
+    field
+        .as_u64()
+        .or_else(|| field.as_str()?.parse().ok())
+        .and_then(|n| T::try_from(n).ok())
+        .ok_or_else(|| AuditorError::ChainAbi(format!("Field {} is out of range: {}", name, field)))
+}
+
+/// `vector<u8>` 字段（JSON-RPC 中為數字數組）
+pub(crate) fn json_bytes(value: &Value, name: &str) -> Result<Vec<u8>> {
+    json_field(value, name)?
+        .as_array()
+        .and_then(|items| {
+            items
+                .iter()
+                .map(|item| u8::try_from(item.as_u64()?).ok())
+                .collect()
+        })
+        .ok_or_else(|| AuditorError::ChainAbi(format!("Field {} is not a byte vector", name)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIXTURES: &str = include_str!("../tests/fixtures/chain_types.json");
+
+    fn fixture(name: &str) -> Vec<u8> {
+        let fixtures: Value = serde_json::from_str(FIXTURES).unwrap();
+        hex::decode(fixtures[name].as_str().unwrap()).unwrap()
+    }
+
+    fn blob_id() -> MoveU256 {
+        // u256 = 257（小端 0x01 0x01）
+        let mut bytes = [0u8; 32];
+        bytes[0] = 1;
+        bytes[1] = 1;
+        MoveU256(bytes)
+    }
+
+    fn audit_record_params() -> AuditRecordParams {
+        AuditRecordParams {
+            blob_id: blob_id(),
+            blob_object_id: MoveId::from_hex("0xb10b").unwrap(),
+            challenge_epoch: 7,
+            total_challenges: 10,
+            successful_verifications: 9,
+            integrity_hash: vec![0xab; 32],
+            pqc_signature: vec![1, 2, 3],
+            pqc_algorithm: 3,
+        }
+    }
+
+    #[test]
+    fn test_audit_record_params_fixture() {
+        let params = audit_record_params();
+        let encoded = bcs::to_bytes(&params).unwrap();
+
+        assert_eq!(encoded, fixture("audit_record_params"));
+        assert_eq!(params.pure_args().unwrap().concat(), encoded);
+        assert_eq!(
+            bcs::from_bytes::<AuditRecordParams>(&encoded).unwrap(),
+            params
+        );
+    }
+
+    #[test]
+    fn test_pqc_algorithms_fixture() {
+        let fixtures: Value = serde_json::from_str(FIXTURES).unwrap();
+        let accepted: Vec<u8> = serde_json::from_value(fixtures["pqc_algorithms"].clone()).unwrap();
+        assert_eq!(CONTRACT_PQC_ALGORITHMS, accepted.as_slice());
+
+        // 每個簽名算法都有合約接受的編號
+        for algorithm in [PqcAlgorithm::Falcon512, PqcAlgorithm::Dilithium3] {
+            let id = contract_pqc_algorithm(algorithm.id()).unwrap();
+            assert!(accepted.contains(&id), "{:?} maps to {}", algorithm, id);
+        }
+
+        for id in [0, 2, 4, u8::MAX] {
+            assert!(matches!(
+                contract_pqc_algorithm(id),
+                Err(AuditorError::UnsupportedPqcAlgorithm(found)) if found == id
+            ));
+        }
+
+        let mut params = audit_record_params();
+        params.pqc_algorithm = 4;
+        assert!(matches!(
+            params.validate(),
+            Err(AuditorError::UnsupportedPqcAlgorithm(4))
+        ));
+    }
+
+    #[test]
+    fn test_report_metadata_params_fixture() {
+        let params = ReportMetadataParams::new(
+            "0xa11ce",
+            &["0x1".to_string(), "0x2".to_string()],
+            vec![9, 9],
+        )
+        .unwrap();
+        let encoded = bcs::to_bytes(&params).unwrap();
+
+        assert_eq!(encoded, fixture("report_metadata_params"));
+        assert_eq!(params.pure_args().unwrap().concat(), encoded);
+    }
+
+    #[test]
+    fn test_policy_params_fixture() {
+        let params = PolicyParams {
+            report_blob_id: blob_id(),
+            audit_record_id: MoveId::from_hex("0xb10b").unwrap(),
+            allowed_readers: vec![MoveId::from_hex("0xcafe").unwrap()],
+            allowed_auditors: vec![],
+            expires_at_ms: Some(1_700_000_000_000),
+        };
+        let encoded = bcs::to_bytes(&params).unwrap();
+
+        assert_eq!(encoded, fixture("policy_params"));
+        assert_eq!(params.pure_args().unwrap().concat(), encoded);
+    }
+
+    #[test]
+    fn test_audit_created_event_fixture() {
+        let event = AuditCreatedEvent::from_bcs(&fixture("audit_created_event")).unwrap();
+
+        assert_eq!(event.audit_record_id, MoveId::from_hex("0x1").unwrap());
+        assert_eq!(event.blob_id, blob_id());
+        assert_eq!(event.auditor, MoveId::from_hex("0xa11ce").unwrap());
+        assert_eq!(event.challenge_epoch, 7);
+        assert_eq!(event.total_challenges, 10);
+        assert!(!event.is_valid);
+    }
+
+    #[test]
+    fn test_audit_records_from_json_rpc() {
+        let event = AuditCreatedEvent::from_json(&json!({
+            "audit_record_id": "0x1",
+            "blob_id": "257",
+            "auditor": "0xa11ce",
+            "challenge_epoch": 7,
+            "total_challenges": 10,
+            "is_valid": false
+        }))
+        .unwrap();
+        assert_eq!(
+            event,
+            AuditCreatedEvent::from_bcs(&fixture("audit_created_event")).unwrap()
+        );
+
+        let object = json!({
+            "data": {
+                "objectId": "0x1",
+                "content": {
+                    "dataType": "moveObject",
+                    "type": "0xabc::audit_core::AuditRecord",
+                    "fields": {
+                        "id": { "id": "0x1" },
+                        "blob_id": "257",
+                        "auditor": "0xa11ce",
+                        "challenge_epoch": 7,
+                        "total_challenges": 10,
+                        "successful_verifications": 9,
+                        "integrity_hash": vec![0xab; 32],
+                        "pqc_signature": [1, 2, 3],
+                        "is_valid": false
+                    }
+                }
+            }
+        });
+        let record = OnChainAuditRecord::from_object_response(&object)
+            .unwrap()
+            .unwrap();
+        assert_eq!(record.blob_id, blob_id());
+        assert_eq!(record.successful_verifications, 9);
+        assert_eq!(record.integrity_hash, vec![0xab; 32]);
+
+        let missing = json!({ "error": { "code": "notExists", "object_id": "0x1" } });
+        assert_eq!(
+            OnChainAuditRecord::from_object_response(&missing).unwrap(),
+            None
+        );
+
+        let mut bad = object.clone();
+        bad["data"]["content"]["fields"]["total_challenges"] = json!(70_000);
+        assert!(OnChainAuditRecord::from_object_response(&bad).is_err());
+    }
+
+    #[test]
+    fn test_blob_id_conversion() {
+        let mut bytes = [0u8; 32];
+        bytes[0] = 1;
+        bytes[1] = 1;
+        let blob_id = general_purpose::URL_SAFE_NO_PAD.encode(bytes);
+
+        let value = MoveU256::from_blob_id(&blob_id).unwrap();
+        assert_eq!(value.0, bytes);
+        assert_eq!(value.to_string(), "257");
+        assert_eq!(value.to_blob_id(), blob_id);
+
+        assert_eq!(MoveU256([0u8; 32]).to_string(), "0");
+        assert_eq!(
+            MoveU256([0xff; 32]).to_string(),
+            "115792089237316195423570985008687907853269984665640564039457584007913129639935"
+        );
+        assert!(MoveU256::from_blob_id("short").is_err());
+
+        for value in [MoveU256([0u8; 32]), value, MoveU256([0xff; 32])] {
+            assert_eq!(
+                MoveU256::from_decimal_str(&value.to_decimal_string()).unwrap(),
+                value
+            );
+        }
+        assert!(MoveU256::from_decimal_str("").is_err());
+        assert!(MoveU256::from_decimal_str("-1").is_err());
+        assert!(MoveU256::from_decimal_str(
+            "115792089237316195423570985008687907853269984665640564039457584007913129639936"
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_move_id_parsing() {
+        assert_eq!(
+            MoveId::from_hex("0x6").unwrap().to_string(),
+            format!("0x{:0>64}", "6")
+        );
+        assert!(MoveId::from_hex("0x").is_err());
+        assert!(MoveId::from_hex("0xzz").is_err());
+        assert!(MoveId::from_hex(&format!("0x{}", "1".repeat(65))).is_err());
+    }
+
+    #[test]
+    fn test_range_and_length_checks() {
+        assert_eq!(checked_u16("total_challenges", 65_535).unwrap(), u16::MAX);
+        assert!(checked_u16("total_challenges", 65_536).is_err());
+
+        let mut params = audit_record_params();
+        params.successful_verifications = 11;
+        assert!(params.validate().is_err());
+
+        let mut params = audit_record_params();
+        params.integrity_hash = vec![0xab; 31];
+        assert!(params.validate().is_err());
+
+        let mut params = audit_record_params();
+        params.integrity_hash.clear();
+        assert!(params.validate().is_ok());
+
+        let mut params = audit_record_params();
+        params.pqc_signature.clear();
+        assert!(params.validate().is_err());
+    }
+}
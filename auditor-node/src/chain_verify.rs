@@ -0,0 +1,372 @@
+//! 無密鑰的鏈上報告核對
+//!
+//! 部分使用方並不關心 PQC 簽名，只想確認從 Walrus 下載的報告與審計員在鏈上
+//! 提交的 `AuditRecord` 一致。[`verify_against_chain`]：
+//!
+//! 1. 經 [`ingest`](crate::ingest) 加固層解析報告，計算報告摘要（緊湊 JSON 的 SHA-256）
+//! 2. 按 Blob ID + epoch + 審計員地址查找鏈上的 `AuditRecord`
+//!    （[`AuditSystemClient::find_audit_records`]，只用 JSON-RPC，無需 `sui-sdk` feature）
+//! 3. 比對報告應提交的字段（`integrity_hash`、PQC 簽名字節、挑戰計數）與鏈上存儲值
+//!
+//! 鏈上記錄不存儲報告摘要本身；`pqc_signature` 綁定了被簽名的完整報告，
+//! 與 `integrity_hash` 一起比對即可確認報告內容與鏈上承諾一致。
+
+use crate::chain_types::{AuditRecordParams, MoveId, MoveU256, OnChainAuditRecord};
+use crate::crypto::ct_eq;
+use crate::error::{AuditorError, Result};
+use crate::history::report_digest;
+use crate::ingest::{self, IngestLimits};
+use crate::sui_client::AuditSystemClient;
+use crate::types::AuditReport;
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+/// 核對結論
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChainVerdict {
+    /// 鏈上記錄與報告一致
+    DigestMatch,
+    /// 找到鏈上記錄，但存儲值與報告不符
+    DigestMismatch,
+    /// 沒有對應的鏈上記錄
+    NoRecordFound,
+}
+
+/// 核對結果
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChainVerification {
+    /// 結論
+    pub verdict: ChainVerdict,
+
+    /// 報告中的 Blob ID
+    pub blob_id: String,
+
+    /// 報告中的審計員地址
+    pub auditor: String,
+
+    /// 查找時使用的 epoch
+    pub challenge_epoch: u32,
+
+    /// 報告摘要（緊湊 JSON 的 SHA-256，hex）
+    pub report_digest: String,
+
+    /// 報告應提交到鏈上的 `integrity_hash`（hex）
+    pub integrity_hash: String,
+
+    /// 比對的鏈上記錄 ID（匹配時為一致的記錄，不匹配時為最新的記錄）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub audit_record_id: Option<String>,
+
+    /// 鏈上記錄的 `integrity_hash`（hex）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub onchain_integrity_hash: Option<String>,
+
+    /// 與鏈上記錄不一致的字段
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub mismatched_fields: Vec<String>,
+}
+
+impl ChainVerification {
+    /// 報告是否與鏈上記錄一致
+    pub fn is_match(&self) -> bool {
+        self.verdict == ChainVerdict::DigestMatch
+    }
+}
+
+/// 核對報告與其鏈上審計記錄（epoch 取自報告）
+///
+/// # 錯誤
+/// - 報告無法解析、Blob ID 或審計員地址無法映射到 Move 類型: 返回對應錯誤
+/// - JSON-RPC 請求失敗: 返回 `SuiClient` / `Network` 錯誤
+pub async fn verify_against_chain(
+    report_bytes: &[u8],
+    client: &AuditSystemClient,
+) -> Result<ChainVerification> {
+    verify_against_chain_at(report_bytes, client, None).await
+}
+
+/// 同 [`verify_against_chain`]，`challenge_epoch` 為 `Some` 時覆蓋報告中的 epoch
+///
+/// 完整性審計生成的報告不記錄 epoch（為 0），其鏈上記錄使用提交時配置的 epoch
+pub async fn verify_against_chain_at(
+    report_bytes: &[u8],
+    client: &AuditSystemClient,
+    challenge_epoch: Option<u32>,
+) -> Result<ChainVerification> {
+    let json = std::str::from_utf8(report_bytes)
+        .map_err(|e| AuditorError::Serialization(format!("Report is not UTF-8: {}", e)))?;
+    let report = ingest::parse_report(json, &IngestLimits::default())?;
+    let challenge_epoch = challenge_epoch.unwrap_or(report.challenge_epoch);
+
+    let records = client
+        .find_audit_records(
+            &MoveU256::from_blob_id(&report.blob_id)?,
+            challenge_epoch,
+            &MoveId::from_hex(&report.auditor)?,
+        )
+        .await?;
+
+    let verification = compare(&report, challenge_epoch, &records)?;
+    match verification.verdict {
+        ChainVerdict::DigestMatch => info!(
+            "Report for blob {} matches on-chain AuditRecord {}",
+            report.blob_id,
+            verification.audit_record_id.as_deref().unwrap_or_default()
+        ),
+        ChainVerdict::DigestMismatch => warn!(
+            "Report for blob {} differs from on-chain AuditRecord {} in {:?}",
+            report.blob_id,
+            verification.audit_record_id.as_deref().unwrap_or_default(),
+            verification.mismatched_fields
+        ),
+        ChainVerdict::NoRecordFound => warn!(
+            "No on-chain AuditRecord for blob {} (epoch {}, auditor {})",
+            report.blob_id, challenge_epoch, report.auditor
+        ),
+    }
+    Ok(verification)
+}
+
+/// 將報告與候選記錄（最新的在前）比對
+fn compare(
+    report: &AuditReport,
+    challenge_epoch: u32,
+    records: &[OnChainAuditRecord],
+) -> Result<ChainVerification> {
+    let integrity_hash = AuditRecordParams::integrity_hash_for(report)?;
+    let mut verification = ChainVerification {
+        verdict: ChainVerdict::NoRecordFound,
+        blob_id: report.blob_id.clone(),
+        auditor: report.auditor.clone(),
+        challenge_epoch,
+        report_digest: report_digest(report)?,
+        integrity_hash: hex::encode(&integrity_hash),
+        audit_record_id: None,
+        onchain_integrity_hash: None,
+        mismatched_fields: vec![],
+    };
+
+    // 同一 epoch 可能有多條記錄（重新審計），任一一致即視為匹配
+    let mismatches = |record: &OnChainAuditRecord| {
+        let mut fields = vec![];
+        if !ct_eq(&record.integrity_hash, &integrity_hash) {
+            fields.push("integrity_hash".to_string());
+        }
+        if !ct_eq(&record.pqc_signature, &report.pqc_signature) {
+            fields.push("pqc_signature".to_string());
+        }
+        if record.total_challenges != report.total_challenges {
+            fields.push("total_challenges".to_string());
+        }
+        if record.successful_verifications != report.successful_verifications {
+            fields.push("successful_verifications".to_string());
+        }
+        fields
+    };
+    let (record, mismatched) = match records.iter().find(|record| mismatches(record).is_empty()) {
+        Some(record) => (record, vec![]),
+        None => match records.first() {
+            Some(latest) => (latest, mismatches(latest)),
+            None => return Ok(verification),
+        },
+    };
+
+    verification.verdict = if mismatched.is_empty() {
+        ChainVerdict::DigestMatch
+    } else {
+        ChainVerdict::DigestMismatch
+    };
+    verification.audit_record_id = Some(record.id.to_string());
+    verification.onchain_integrity_hash = Some(hex::encode(&record.integrity_hash));
+    verification.mismatched_fields = mismatched;
+    Ok(verification)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chain_types::{AuditCreatedEvent, AUDIT_CORE_MODULE};
+    use crate::test_support::FakeSuiRpc;
+    use base64::{engine::general_purpose, Engine as _};
+    use serde_json::json;
+
+    const PACKAGE_ID: &str = "0xa0d17";
+    const AUDITOR: &str = "0xa11ce";
+
+    fn blob_id(seed: u8) -> String {
+        general_purpose::URL_SAFE_NO_PAD.encode([seed; 32])
+    }
+
+    fn report(blob_id: &str) -> AuditReport {
+        serde_json::from_value(json!({
+            "blob_id": blob_id,
+            "blob_object_id": "0xb10b",
+            "auditor": AUDITOR,
+            "timestamp": 1_700_000_000u64,
+            "challenge_epoch": 7,
+            "challenge_results": [],
+            "total_challenges": 10,
+            "successful_verifications": 10,
+            "failed_verifications": 0,
+            "integrity_hash": vec![0xab; 32],
+            "pqc_signature": [1, 2, 3],
+            "pqc_algorithm": 3,
+            "is_valid": true,
+            "failure_reason": null,
+        }))
+        .unwrap()
+    }
+
+    /// 按 Sui JSON-RPC 的形狀加入 `AuditCreated` 事件與 `AuditRecord` 對象
+    fn add_record(chain: &FakeSuiRpc, record_id: &str, report: &AuditReport, epoch: u32) {
+        let record_id = MoveId::from_hex(record_id).unwrap().to_string();
+        let blob_id = MoveU256::from_blob_id(&report.blob_id)
+            .unwrap()
+            .to_decimal_string();
+
+        chain.add_event(
+            format!(
+                "{}::{}::{}",
+                PACKAGE_ID,
+                AUDIT_CORE_MODULE,
+                AuditCreatedEvent::EVENT
+            ),
+            json!({
+                "audit_record_id": record_id,
+                "blob_id": blob_id,
+                "auditor": report.auditor,
+                "challenge_epoch": epoch,
+                "total_challenges": report.total_challenges,
+                "is_valid": report.is_valid
+            }),
+        );
+        chain.add_object(
+            record_id.clone(),
+            &format!(
+                "{}::{}::{}",
+                PACKAGE_ID,
+                AUDIT_CORE_MODULE,
+                OnChainAuditRecord::STRUCT
+            ),
+            json!({
+                "id": { "id": record_id },
+                "blob_id": blob_id,
+                "blob_object_id": report.blob_object_id,
+                "auditor": report.auditor,
+                "challenge_epoch": epoch,
+                "audit_timestamp": "1700000000000",
+                "total_challenges": report.total_challenges,
+                "successful_verifications": report.successful_verifications,
+                "failed_verifications": report.failed_verifications,
+                "integrity_hash": report.integrity_hash,
+                "pqc_signature": report.pqc_signature,
+                "pqc_algorithm": report.pqc_algorithm,
+                "is_valid": report.is_valid,
+                "failure_reason": null
+            }),
+        );
+    }
+
+    async fn client(chain: &FakeSuiRpc) -> AuditSystemClient {
+        AuditSystemClient::new(chain.url(), PACKAGE_ID, "0x0", "0x0", "0x0")
+            .await
+            .unwrap()
+    }
+
+    fn bytes(report: &AuditReport) -> Vec<u8> {
+        serde_json::to_vec(report).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_digest_match() {
+        let chain = FakeSuiRpc::start().await;
+        let report = report(&blob_id(1));
+        add_record(&chain, "0x1", &report, 7);
+        // 其他 Blob、其他 epoch 的記錄不參與比對
+        add_record(&chain, "0x2", &self::report(&blob_id(2)), 7);
+
+        let result = verify_against_chain(&bytes(&report), &client(&chain).await)
+            .await
+            .unwrap();
+
+        assert_eq!(result.verdict, ChainVerdict::DigestMatch);
+        assert!(result.is_match());
+        assert_eq!(
+            result.audit_record_id,
+            Some(MoveId::from_hex("0x1").unwrap().to_string())
+        );
+        assert_eq!(result.report_digest, report_digest(&report).unwrap());
+        assert_eq!(result.onchain_integrity_hash, Some(hex::encode([0xab; 32])));
+    }
+
+    #[tokio::test]
+    async fn test_digest_mismatch() {
+        let chain = FakeSuiRpc::start().await;
+        let committed = report(&blob_id(1));
+        add_record(&chain, "0x1", &committed, 7);
+
+        let mut tampered = committed.clone();
+        tampered.integrity_hash = vec![0xcd; 32];
+        tampered.successful_verifications = 9;
+
+        let result = verify_against_chain(&bytes(&tampered), &client(&chain).await)
+            .await
+            .unwrap();
+
+        assert_eq!(result.verdict, ChainVerdict::DigestMismatch);
+        assert_eq!(
+            result.mismatched_fields,
+            vec!["integrity_hash", "successful_verifications"]
+        );
+        assert_eq!(result.integrity_hash, hex::encode([0xcd; 32]));
+        assert_eq!(result.onchain_integrity_hash, Some(hex::encode([0xab; 32])));
+    }
+
+    #[tokio::test]
+    async fn test_no_record_found() {
+        let chain = FakeSuiRpc::start().await;
+        let report = report(&blob_id(1));
+        add_record(&chain, "0x1", &report, 8);
+
+        let client = client(&chain).await;
+        let result = verify_against_chain(&bytes(&report), &client)
+            .await
+            .unwrap();
+        assert_eq!(result.verdict, ChainVerdict::NoRecordFound);
+        assert_eq!(result.audit_record_id, None);
+
+        // 顯式指定 epoch 後找到記錄
+        let result = verify_against_chain_at(&bytes(&report), &client, Some(8))
+            .await
+            .unwrap();
+        assert_eq!(result.verdict, ChainVerdict::DigestMatch);
+    }
+
+    #[tokio::test]
+    async fn test_matching_record_found_across_event_pages() {
+        let chain = FakeSuiRpc::start().await;
+        let report = report(&blob_id(1));
+        add_record(&chain, "0x1", &report, 7);
+        for i in 0..120u8 {
+            add_record(
+                &chain,
+                &format!("0x1{:02x}", i),
+                &self::report(&blob_id(2)),
+                7,
+            );
+        }
+
+        let result = verify_against_chain(&bytes(&report), &client(&chain).await)
+            .await
+            .unwrap();
+        assert!(result.is_match());
+
+        let pages = chain
+            .requests()
+            .iter()
+            .filter(|r| r["method"] == "suix_queryEvents")
+            .count();
+        assert_eq!(pages, 3);
+    }
+}
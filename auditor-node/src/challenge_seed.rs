@@ -0,0 +1,446 @@
+//! 鏈上挑戰種子
+//!
+//! 挑戰索引若可預測，存儲節點就可以預先計算響應；審計員若可以任意抽取，也可能挑選容易通過的
+//! sliver。`challenge_seed_mode = "sui_checkpoint"` 時，每次審計開始前讀取最新的 Sui 檢查點，
+//! 由其摘要確定性地導出挑戰種子（[`derive_seed`]）：
+//!
+//! ```text
+//! seed = HKDF-SHA256(
+//!     salt = "WALRUS_AUDIT_CHALLENGE_SEED_V1",
+//!     ikm  = u32 LE len(checkpoint_digest) || checkpoint_digest (Base58 原文)
+//!         || u32 LE len(blob_id)           || blob_id (UTF-8)
+//!         || u32 LE len(auditor_address)   || auditor_address (UTF-8),
+//!     info = "challenge-indices",
+//!     L    = 32)
+//! ```
+//!
+//! 存儲節點挑戰以種子初始化 ChaCha20 隨機數生成器（[`ChallengeSeed::rng`]），Aggregator 審計以
+//! [`derive_indices`](crate::commitment::derive_indices) 由種子導出葉子索引。報告記錄種子來源
+//! （`challenge_seed_source`）與種子（`challenge_seed`），驗證者可以重新計算種子（[`verify_seed`]）
+//! 並由種子重現挑戰集，確認審計員沒有挑選挑戰。
+//!
+//! 讀取檢查點失敗時退回本地隨機種子，報告中的來源記為 `random`；`challenge_seed_mode = "random"`
+//! （默認）時沿用此前的隨機抽取，報告不記錄種子。
+
+use crate::commitment::CHALLENGE_SEED_LEN;
+use crate::crypto::{ct_eq, decode_hex_digest};
+use crate::error::{AuditorError, Result};
+use async_trait::async_trait;
+use hkdf::Hkdf;
+use rand::{RngCore, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sha2::Sha256;
+use tracing::{debug, warn};
+
+/// HKDF 的 salt（域分隔）
+pub const CHALLENGE_SEED_DOMAIN: &[u8] = b"WALRUS_AUDIT_CHALLENGE_SEED_V1";
+
+/// HKDF 的 info
+const CHALLENGE_SEED_INFO: &[u8] = b"challenge-indices";
+
+/// 挑戰種子模式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChallengeSeedMode {
+    /// 本地隨機抽取挑戰（報告不記錄種子）
+    #[default]
+    Random,
+
+    /// 由最新 Sui 檢查點的摘要導出種子
+    SuiCheckpoint,
+}
+
+/// Sui 檢查點
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SuiCheckpoint {
+    /// 檢查點序號
+    pub sequence_number: u64,
+
+    /// 檢查點摘要（Base58）
+    pub digest: String,
+}
+
+/// 挑戰種子的來源（報告的 `challenge_seed_source`）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChallengeSeedSource {
+    /// 讀取檢查點失敗，退回本地隨機種子（無法重新計算）
+    Random,
+
+    /// 由 Sui 檢查點導出
+    SuiCheckpoint {
+        /// 檢查點序號
+        sequence_number: u64,
+        /// 檢查點摘要（Base58）
+        digest: String,
+    },
+}
+
+/// 一次審計的挑戰種子
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChallengeSeed {
+    /// 種子來源
+    pub source: ChallengeSeedSource,
+
+    /// 種子
+    pub seed: [u8; CHALLENGE_SEED_LEN],
+}
+
+impl ChallengeSeed {
+    /// 本地隨機種子（來源記為 `random`）
+    pub fn random() -> Self {
+        let mut seed = [0u8; CHALLENGE_SEED_LEN];
+        rand::thread_rng().fill_bytes(&mut seed);
+        Self {
+            source: ChallengeSeedSource::Random,
+            seed,
+        }
+    }
+
+    /// 由檢查點、Blob ID 與審計員地址導出的種子
+    pub fn from_checkpoint(checkpoint: &SuiCheckpoint, blob_id: &str, auditor: &str) -> Self {
+        Self {
+            source: ChallengeSeedSource::SuiCheckpoint {
+                sequence_number: checkpoint.sequence_number,
+                digest: checkpoint.digest.clone(),
+            },
+            seed: derive_seed(&checkpoint.digest, blob_id, auditor),
+        }
+    }
+
+    /// 讀取最新檢查點並導出種子；讀取失敗時退回本地隨機種子
+    pub async fn resolve(source: &dyn CheckpointSource, blob_id: &str, auditor: &str) -> Self {
+        match source.latest_checkpoint().await {
+            Ok(checkpoint) => {
+                debug!(
+                    "Deriving challenge seed for blob {} from checkpoint {} ({})",
+                    blob_id, checkpoint.sequence_number, checkpoint.digest
+                );
+                Self::from_checkpoint(&checkpoint, blob_id, auditor)
+            }
+            Err(e) => {
+                warn!(
+                    "Failed to read latest Sui checkpoint, using a random challenge seed for blob {}: {}",
+                    blob_id, e
+                );
+                Self::random()
+            }
+        }
+    }
+
+    /// 種子（十六進制，報告的 `challenge_seed`）
+    pub fn seed_hex(&self) -> String {
+        hex::encode(self.seed)
+    }
+
+    /// 以種子初始化的隨機數生成器（同一種子產生同一序列）
+    pub fn rng(&self) -> ChaCha20Rng {
+        ChaCha20Rng::from_seed(self.seed)
+    }
+}
+
+/// 由檢查點摘要、Blob ID 與審計員地址導出種子（格式見模塊文檔）
+pub fn derive_seed(
+    checkpoint_digest: &str,
+    blob_id: &str,
+    auditor: &str,
+) -> [u8; CHALLENGE_SEED_LEN] {
+    let mut ikm = Vec::new();
+    for part in [checkpoint_digest, blob_id, auditor] {
+        ikm.extend_from_slice(&(part.len() as u32).to_le_bytes());
+        ikm.extend_from_slice(part.as_bytes());
+    }
+
+    let mut seed = [0u8; CHALLENGE_SEED_LEN];
+    Hkdf::<Sha256>::new(Some(CHALLENGE_SEED_DOMAIN), &ikm)
+        .expand(CHALLENGE_SEED_INFO, &mut seed)
+        .expect("32 bytes is a valid HKDF-SHA256 output length");
+    seed
+}
+
+/// 核對報告記錄的種子能否由其來源、Blob ID 與審計員地址重新導出
+///
+/// # 錯誤
+/// - 來源為 `random`（無法重新計算）或種子不符時返回 `AuditorError::Commitment`
+/// - 種子不是合法的十六進制時返回 `AuditorError::InvalidDigest`
+pub fn verify_seed(
+    source: &ChallengeSeedSource,
+    seed_hex: &str,
+    blob_id: &str,
+    auditor: &str,
+) -> Result<()> {
+    let ChallengeSeedSource::SuiCheckpoint { digest, .. } = source else {
+        return Err(AuditorError::Commitment(format!(
+            "Challenge seed for blob {} is locally random and cannot be recomputed",
+            blob_id
+        )));
+    };
+
+    let seed = decode_hex_digest(seed_hex)?;
+    if !ct_eq(&derive_seed(digest, blob_id, auditor), &seed) {
+        return Err(AuditorError::Commitment(format!(
+            "Challenge seed for blob {} does not derive from checkpoint {}",
+            blob_id, digest
+        )));
+    }
+    Ok(())
+}
+
+/// 最新檢查點的來源
+#[async_trait]
+pub trait CheckpointSource: Send + Sync {
+    /// 讀取最新的 Sui 檢查點
+    async fn latest_checkpoint(&self) -> Result<SuiCheckpoint>;
+}
+
+/// 基於 Sui JSON-RPC 的檢查點來源
+///
+/// 以 `sui_getLatestCheckpointSequenceNumber` 取得序號，再以 `sui_getCheckpoint` 取得摘要；
+/// 不依賴 `sui-sdk` feature
+#[derive(Debug, Clone)]
+pub struct SuiRpcCheckpointSource {
+    /// HTTP 客戶端
+    http_client: Client,
+
+    /// Sui RPC URL
+    rpc_url: String,
+}
+
+impl SuiRpcCheckpointSource {
+    /// 創建新的檢查點來源
+    pub fn new(rpc_url: impl Into<String>) -> Self {
+        let http_client = Client::builder()
+            .timeout(std::time::Duration::from_secs(15))
+            .build()
+            .expect("Failed to build HTTP client");
+
+        Self {
+            http_client,
+            rpc_url: rpc_url.into(),
+        }
+    }
+
+    async fn rpc(&self, method: &str, params: Value) -> Result<Value> {
+        let request = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": method,
+            "params": params
+        });
+
+        let mut response: Value = self
+            .http_client
+            .post(&self.rpc_url)
+            .json(&request)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        if let Some(error) = response.get("error") {
+            return Err(AuditorError::SuiClient(format!(
+                "{} failed: {}",
+                method, error
+            )));
+        }
+        Ok(response["result"].take())
+    }
+}
+
+#[async_trait]
+impl CheckpointSource for SuiRpcCheckpointSource {
+    async fn latest_checkpoint(&self) -> Result<SuiCheckpoint> {
+        let latest = self
+            .rpc("sui_getLatestCheckpointSequenceNumber", json!([]))
+            .await?;
+        let sequence_number = latest
+            .as_str()
+            .and_then(|seq| seq.parse::<u64>().ok())
+            .ok_or_else(|| {
+                AuditorError::SuiClient(format!("Invalid checkpoint sequence number: {}", latest))
+            })?;
+
+        let checkpoint = self
+            .rpc("sui_getCheckpoint", json!([sequence_number.to_string()]))
+            .await?;
+        let digest = checkpoint["digest"].as_str().ok_or_else(|| {
+            AuditorError::SuiClient(format!(
+                "Checkpoint {} has no digest: {}",
+                sequence_number, checkpoint
+            ))
+        })?;
+
+        Ok(SuiCheckpoint {
+            sequence_number,
+            digest: digest.to_string(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audit_report::AuditReportGenerator;
+    use crate::commitment::{derive_indices, CommitmentLog};
+    use crate::integrity::IntegrityVerifier;
+    use crate::report::ReportManager;
+    use crate::test_support::{deterministic_blob, AggregatorMode, FakeAggregator, FakeSuiRpc};
+    use crate::types::BlobId;
+    use pqc_signer::dilithium::Dilithium3Signer;
+    use pqc_signer::traits::Signer;
+    use std::sync::Arc;
+
+    const DIGEST: &str = "4vJ9JU1bJJE96FWSJKvHsmmFADCg4gpZQff4P3bkLKi";
+    const BLOB_ID: &str = "eRrTusk8yshFQpkemgDnbg0f4-qDo623V2NpeVG1Zcg";
+    const AUDITOR: &str = "0xab8e";
+
+    struct Unavailable;
+
+    #[async_trait]
+    impl CheckpointSource for Unavailable {
+        async fn latest_checkpoint(&self) -> Result<SuiCheckpoint> {
+            Err(AuditorError::SuiClient("offline".to_string()))
+        }
+    }
+
+    fn checkpoint(digest: &str) -> SuiCheckpoint {
+        SuiCheckpoint {
+            sequence_number: 42,
+            digest: digest.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_same_seed_reproduces_index_set() {
+        let first = ChallengeSeed::from_checkpoint(&checkpoint(DIGEST), BLOB_ID, AUDITOR);
+        let second = ChallengeSeed::from_checkpoint(&checkpoint(DIGEST), BLOB_ID, AUDITOR);
+        assert_eq!(first, second);
+        assert_eq!(
+            derive_indices(&first.seed, 20, 1000),
+            derive_indices(&second.seed, 20, 1000)
+        );
+
+        let other =
+            ChallengeSeed::from_checkpoint(&checkpoint("9xQeWvG816bUx9EPa"), BLOB_ID, AUDITOR);
+        assert_ne!(first.seed, other.seed);
+        assert_ne!(
+            derive_indices(&first.seed, 20, 1000),
+            derive_indices(&other.seed, 20, 1000)
+        );
+    }
+
+    #[test]
+    fn test_seed_binds_blob_and_auditor() {
+        let seed = derive_seed(DIGEST, BLOB_ID, AUDITOR);
+        assert_ne!(seed, derive_seed(DIGEST, BLOB_ID, "0x6"));
+        assert_ne!(
+            seed,
+            derive_seed(
+                DIGEST,
+                "M4hsZGQ1oCktdzegB6HnI6Mi28S2nqOPHxK-W7_4BUk",
+                AUDITOR
+            )
+        );
+        // 長度前綴：移動各部分的邊界得到不同的種子
+        assert_ne!(
+            derive_seed("ab", "c", AUDITOR),
+            derive_seed("a", "bc", AUDITOR)
+        );
+    }
+
+    #[test]
+    fn test_verify_seed() {
+        let seed = ChallengeSeed::from_checkpoint(&checkpoint(DIGEST), BLOB_ID, AUDITOR);
+        verify_seed(&seed.source, &seed.seed_hex(), BLOB_ID, AUDITOR).unwrap();
+
+        // 挑選過的種子或不同的審計員
+        assert!(verify_seed(&seed.source, &hex::encode([7u8; 32]), BLOB_ID, AUDITOR).is_err());
+        assert!(verify_seed(&seed.source, &seed.seed_hex(), BLOB_ID, "0x6").is_err());
+
+        let random = ChallengeSeed::random();
+        assert!(verify_seed(&random.source, &random.seed_hex(), BLOB_ID, AUDITOR).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_resolve_falls_back_to_random() {
+        let seed = ChallengeSeed::resolve(&Unavailable, BLOB_ID, AUDITOR).await;
+        assert_eq!(seed.source, ChallengeSeedSource::Random);
+    }
+
+    #[tokio::test]
+    async fn test_rpc_checkpoint_source() {
+        let rpc = FakeSuiRpc::start().await;
+        rpc.set_checkpoint(42, DIGEST);
+
+        let source = SuiRpcCheckpointSource::new(rpc.url());
+        assert_eq!(
+            source.latest_checkpoint().await.unwrap(),
+            checkpoint(DIGEST)
+        );
+
+        let seed = ChallengeSeed::resolve(&source, BLOB_ID, AUDITOR).await;
+        assert_eq!(
+            seed.source,
+            ChallengeSeedSource::SuiCheckpoint {
+                sequence_number: 42,
+                digest: DIGEST.to_string()
+            }
+        );
+        assert_eq!(seed.seed, derive_seed(DIGEST, BLOB_ID, AUDITOR));
+    }
+
+    #[tokio::test]
+    async fn test_integrity_audit_draws_challenges_from_checkpoint() {
+        let aggregator =
+            FakeAggregator::start(deterministic_blob(100 * 4096), AggregatorMode::Healthy).await;
+        let rpc = FakeSuiRpc::start().await;
+        rpc.set_checkpoint(42, DIGEST);
+        let verifier = IntegrityVerifier::new(aggregator.url().to_string())
+            .with_commitments(Arc::new(CommitmentLog::in_memory()))
+            .with_challenge_seed(Arc::new(SuiRpcCheckpointSource::new(rpc.url())), AUDITOR);
+
+        let mut signer = Dilithium3Signer::new();
+        signer.generate_keypair().unwrap();
+        let public_key = signer.public_key().to_vec();
+        let blob_id = BlobId::from_bytes([0xa; 32]);
+        let report = AuditReportGenerator::new(signer, Some(AUDITOR.to_string()))
+            .generate_report(verifier.audit_blob(&blob_id).await.unwrap())
+            .unwrap();
+
+        // 挑戰索引可由檢查點摘要、Blob ID 與審計員地址重新導出
+        let seed = derive_seed(DIGEST, &report.blob_id, AUDITOR);
+        assert_eq!(report.challenge_seed, Some(hex::encode(seed)));
+        let reveal = report.challenge_reveal.clone().unwrap();
+        assert_eq!(
+            reveal.indices,
+            derive_indices(&seed, reveal.indices.len(), reveal.leaf_count)
+        );
+        report.verify_challenge_seed().unwrap();
+
+        // 種子在簽名範圍內
+        let mut tampered = report.clone();
+        tampered.challenge_seed = Some(hex::encode([0u8; 32]));
+        assert!(tampered.verify_challenge_seed().is_err());
+        assert!(!ReportManager::verify_report(&tampered, &public_key).unwrap());
+    }
+
+    #[test]
+    fn test_source_serialization() {
+        let source = ChallengeSeedSource::SuiCheckpoint {
+            sequence_number: 42,
+            digest: DIGEST.to_string(),
+        };
+        assert_eq!(
+            serde_json::to_value(&source).unwrap(),
+            json!({ "sui_checkpoint": { "sequence_number": 42, "digest": DIGEST } })
+        );
+        assert_eq!(
+            serde_json::to_value(ChallengeSeedSource::Random).unwrap(),
+            json!("random")
+        );
+    }
+}
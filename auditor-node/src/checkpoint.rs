@@ -0,0 +1,290 @@
+//! 可恢復審計的檢查點
+//!
+//! 大 Blob 的審計先下載並哈希全部內容、構建 Merkle 樹，然後才執行挑戰；
+//! 進程在中途崩潰會丟掉全部工作。啟用檢查點後（配置 `checkpoint_dir`），
+//! [`IntegrityVerifier::audit_blob_resumable`](crate::integrity::IntegrityVerifier::audit_blob_resumable)
+//! 在以下時刻把進度寫入 `checkpoint_dir/<sha256(blob_id)>.ckpt`：
+//!
+//! 1. 下載並哈希完成後：內容哈希與全部葉子哈希
+//! 2. 挑戰集確定後（承諾已寫入）：挑戰索引、公開數據與挑戰種子
+//! 3. 挑戰驗證完成後：每個挑戰的結果
+//!
+//! 下次嘗試時，若 Aggregator 對同一 Blob 返回的 `ETag` 與 `Content-Length`
+//! 與檢查點一致（且切片大小未變），直接從葉子哈希恢復 Merkle 樹，跳過下載與哈希；
+//! 已確定的挑戰集原樣沿用，不重新生成（否則同一 Blob 會出現兩個承諾）。
+//! 審計成功完成後刪除檢查點。
+//!
+//! 文件格式為 `MAGIC || version (u32 LE) || SHA-256(payload) || payload`，
+//! payload 為 bincode 編碼的 [`AuditCheckpoint`]。版本不符、摘要不符或無法解碼的
+//! 檢查點視為不存在（並刪除），審計從頭開始。
+
+use crate::challenge_seed::ChallengeSeed;
+use crate::commitment::ChallengeReveal;
+use crate::error::Result;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use tracing::{debug, warn};
+
+/// 檢查點文件的魔數
+const CHECKPOINT_MAGIC: &[u8; 4] = b"WACK";
+
+/// 當前檢查點格式版本（格式或樹構建方式改變時遞增）
+pub const CHECKPOINT_VERSION: u32 = 3;
+
+/// 文件頭長度：魔數、版本與 payload 摘要
+const HEADER_LEN: usize = 4 + 4 + 32;
+
+/// 一次未完成審計的進度
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditCheckpoint {
+    /// Blob ID
+    pub blob_id: String,
+
+    /// 下載的字節數
+    pub content_length: u64,
+
+    /// 葉子哈希的切片大小（bytes）
+    pub chunk_size: u32,
+
+    /// 下載時 Aggregator 返回的 `ETag`
+    pub etag: Option<String>,
+
+    /// 內容哈希（SHA-256，十六進制）
+    pub content_hash: String,
+
+    /// Merkle 葉子哈希（按 chunk 順序）
+    pub leaf_hashes: Vec<[u8; 32]>,
+
+    /// 挑戰進度（挑戰集確定前為 `None`）
+    pub challenges: Option<ChallengeProgress>,
+}
+
+impl AuditCheckpoint {
+    /// 檢查點是否仍描述 Aggregator 當前提供的內容
+    ///
+    /// 只有 `ETag` 存在且一致、長度也一致時才可恢復：僅憑長度無法判斷內容未變
+    pub fn matches(&self, content_length: Option<u64>, etag: Option<&str>) -> bool {
+        etag.is_some()
+            && self.etag.as_deref() == etag
+            && content_length == Some(self.content_length)
+    }
+}
+
+/// 已確定的挑戰集與已完成的結果
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChallengeProgress {
+    /// 挑戰的葉子索引
+    pub indices: Vec<u64>,
+
+    /// 啟用承諾時公開的挑戰集
+    pub reveal: Option<ChallengeReveal>,
+
+    /// 以 Sui 檢查點導出挑戰時的種子
+    pub seed: Option<ChallengeSeed>,
+
+    /// `indices` 前綴的驗證結果
+    pub results: Vec<bool>,
+}
+
+/// 檢查點目錄
+#[derive(Debug, Clone)]
+pub struct CheckpointStore {
+    dir: PathBuf,
+}
+
+impl CheckpointStore {
+    /// 打開檢查點目錄（不存在時創建）
+    pub fn open(dir: impl AsRef<Path>) -> Result<Self> {
+        let dir = dir.as_ref().to_path_buf();
+        fs::create_dir_all(&dir)?;
+        Ok(Self { dir })
+    }
+
+    /// 讀取 Blob 的檢查點
+    ///
+    /// 不存在、已損壞或版本不符時返回 `None`；損壞的文件會被刪除
+    pub fn load(&self, blob_id: &str) -> Option<AuditCheckpoint> {
+        let path = self.path(blob_id);
+        let bytes = match fs::read(&path) {
+            Ok(bytes) => bytes,
+            Err(e) if e.kind() == ErrorKind::NotFound => return None,
+            Err(e) => {
+                warn!("Failed to read checkpoint {}: {}", path.display(), e);
+                return None;
+            }
+        };
+
+        match decode(&bytes) {
+            Ok(checkpoint) if checkpoint.blob_id == blob_id => {
+                debug!(
+                    "Loaded checkpoint for blob {}: {} leaves",
+                    blob_id,
+                    checkpoint.leaf_hashes.len()
+                );
+                Some(checkpoint)
+            }
+            Ok(checkpoint) => {
+                warn!(
+                    "Ignoring checkpoint {}: recorded for blob {}",
+                    path.display(),
+                    checkpoint.blob_id
+                );
+                self.discard(&path);
+                None
+            }
+            Err(reason) => {
+                warn!("Ignoring checkpoint {}: {}", path.display(), reason);
+                self.discard(&path);
+                None
+            }
+        }
+    }
+
+    /// 寫入檢查點（臨時文件 + 重命名，崩潰時不會留下半個文件）
+    pub fn save(&self, checkpoint: &AuditCheckpoint) -> Result<()> {
+        let payload = bincode::serialize(checkpoint)?;
+        let mut bytes = Vec::with_capacity(HEADER_LEN + payload.len());
+        bytes.extend_from_slice(CHECKPOINT_MAGIC);
+        bytes.extend_from_slice(&CHECKPOINT_VERSION.to_le_bytes());
+        bytes.extend_from_slice(&Sha256::digest(&payload));
+        bytes.extend_from_slice(&payload);
+
+        let path = self.path(&checkpoint.blob_id);
+        let tmp_path = path.with_extension("ckpt.tmp");
+        fs::write(&tmp_path, bytes)?;
+        fs::rename(&tmp_path, &path)?;
+        Ok(())
+    }
+
+    /// 刪除 Blob 的檢查點（不存在時忽略）
+    pub fn remove(&self, blob_id: &str) -> Result<()> {
+        match fs::remove_file(self.path(blob_id)) {
+            Err(e) if e.kind() != ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+
+    /// 檢查點文件路徑（Blob ID 可能含有不適合做文件名的字符，取其哈希）
+    pub fn path(&self, blob_id: &str) -> PathBuf {
+        self.dir
+            .join(format!("{}.ckpt", hex::encode(Sha256::digest(blob_id))))
+    }
+
+    fn discard(&self, path: &Path) {
+        if let Err(e) = fs::remove_file(path) {
+            debug!("Failed to remove checkpoint {}: {}", path.display(), e);
+        }
+    }
+}
+
+/// 校驗文件頭與摘要並解碼 payload
+fn decode(bytes: &[u8]) -> std::result::Result<AuditCheckpoint, String> {
+    if bytes.len() < HEADER_LEN || &bytes[..4] != CHECKPOINT_MAGIC {
+        return Err("not a checkpoint file".to_string());
+    }
+
+    let version = u32::from_le_bytes(bytes[4..8].try_into().expect("4 bytes"));
+    if version != CHECKPOINT_VERSION {
+        return Err(format!(
+            "version {} (expected {})",
+            version, CHECKPOINT_VERSION
+        ));
+    }
+
+    let payload = &bytes[HEADER_LEN..];
+    if Sha256::digest(payload)[..] != bytes[8..HEADER_LEN] {
+        return Err("digest mismatch".to_string());
+    }
+
+    bincode::deserialize(payload).map_err(|e| format!("undecodable payload: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn store() -> CheckpointStore {
+        let dir = std::env::temp_dir().join(format!("checkpoints_{}", rand::random::<u32>()));
+        CheckpointStore::open(dir).unwrap()
+    }
+
+    fn checkpoint(blob_id: &str) -> AuditCheckpoint {
+        AuditCheckpoint {
+            blob_id: blob_id.to_string(),
+            content_length: 3 * 4096,
+            chunk_size: 4096,
+            etag: Some("\"v1\"".to_string()),
+            content_hash: "c0ffee".to_string(),
+            leaf_hashes: vec![[1; 32], [2; 32], [3; 32]],
+            challenges: Some(ChallengeProgress {
+                indices: vec![2, 0],
+                reveal: None,
+                seed: None,
+                results: vec![true],
+            }),
+        }
+    }
+
+    #[test]
+    fn test_save_load_and_remove() {
+        let store = store();
+        assert!(store.load("blob-a").is_none());
+
+        store.save(&checkpoint("blob-a")).unwrap();
+        assert_eq!(store.load("blob-a"), Some(checkpoint("blob-a")));
+        assert!(store.load("blob-b").is_none());
+
+        store.remove("blob-a").unwrap();
+        assert!(store.load("blob-a").is_none());
+        store.remove("blob-a").unwrap();
+    }
+
+    #[test]
+    fn test_corrupt_or_foreign_checkpoints_are_ignored() {
+        let store = store();
+        let path = store.path("blob-a");
+
+        // payload 被改動：摘要不符
+        store.save(&checkpoint("blob-a")).unwrap();
+        let mut bytes = fs::read(&path).unwrap();
+        let last = bytes.len() - 1;
+        bytes[last] ^= 0xff;
+        fs::write(&path, &bytes).unwrap();
+        assert!(store.load("blob-a").is_none());
+        assert!(!path.exists());
+
+        // 截斷、未知版本
+        store.save(&checkpoint("blob-a")).unwrap();
+        let bytes = fs::read(&path).unwrap();
+        fs::write(&path, &bytes[..HEADER_LEN - 1]).unwrap();
+        assert!(store.load("blob-a").is_none());
+
+        let mut newer = bytes.clone();
+        newer[4..8].copy_from_slice(&(CHECKPOINT_VERSION + 1).to_le_bytes());
+        fs::write(&path, &newer).unwrap();
+        assert!(store.load("blob-a").is_none());
+
+        // 文件名相同但記錄的是另一個 Blob
+        fs::write(store.path("blob-b"), &bytes).unwrap();
+        assert!(store.load("blob-b").is_none());
+    }
+
+    #[test]
+    fn test_matches_requires_etag_and_length() {
+        let checkpoint = checkpoint("blob-a");
+        assert!(checkpoint.matches(Some(3 * 4096), Some("\"v1\"")));
+        assert!(!checkpoint.matches(Some(3 * 4096), Some("\"v2\"")));
+        assert!(!checkpoint.matches(Some(4096), Some("\"v1\"")));
+        assert!(!checkpoint.matches(None, Some("\"v1\"")));
+        assert!(!checkpoint.matches(Some(3 * 4096), None));
+
+        let without_etag = AuditCheckpoint {
+            etag: None,
+            ..checkpoint
+        };
+        assert!(!without_etag.matches(Some(3 * 4096), None));
+    }
+}
@@ -0,0 +1,406 @@
+//! Chunk 哈希布隆過濾器（審計記錄的旁路數據）
+//!
+//! 大型 Blob 的完整審計需要下載、建樹、生成並驗證證明。在此之前，
+//! 可以先用上一次審計留下的 [`ChunkFilter`] 做一次廉價的成員預檢：
+//!
+//! 1. 完整審計建樹時，將所有葉子哈希插入布隆過濾器，隨審計歷史一同保存
+//! 2. 再次審計時，抽樣若干 chunk 計算葉子哈希，檢查是否落在上次的過濾器中
+//! 3. 落在過濾器之外的 chunk **必定**與上次不同（布隆過濾器沒有假陰性），
+//!    可作為損壞的早期信號，並定位變化區域
+//!
+//! 反之，通過預檢並不代表內容未變：修改過的 chunk 有 `false_positive_rate`
+//! 的概率被誤判為存在，因此預檢只是信號，不能替代完整審計。
+//!
+//! 過濾器不在簽名範圍內（見 `AuditData::chunk_filter`），序列化格式帶版本號，
+//! 未知版本在反序列化時被拒絕。
+
+use crate::crypto::merkle::{hash_leaf, MerkleTree};
+use serde::{Deserialize, Serialize};
+use std::f64::consts::LN_2;
+
+/// 當前序列化格式版本
+pub const CHUNK_FILTER_VERSION: u8 = 1;
+
+/// 默認假陽性率
+pub const DEFAULT_FALSE_POSITIVE_RATE: f64 = 0.01;
+
+/// 默認快速比對的抽樣 chunk 數
+pub const DEFAULT_QUICK_COMPARE_SAMPLES: usize = 64;
+
+/// 哈希函數個數上限
+const MAX_NUM_HASHES: u32 = 32;
+
+/// Chunk 過濾器配置
+///
+/// ```toml
+/// [chunk_filter]
+/// enabled = true
+/// false_positive_rate = 0.01
+/// quick_compare_samples = 64
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ChunkFilterConfig {
+    /// 是否在審計時構建過濾器（保存在去重歷史中）
+    pub enabled: bool,
+
+    /// 目標假陽性率（0 < p < 1）
+    pub false_positive_rate: f64,
+
+    /// 快速比對時抽樣的 chunk 數
+    pub quick_compare_samples: usize,
+}
+
+impl Default for ChunkFilterConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            false_positive_rate: DEFAULT_FALSE_POSITIVE_RATE,
+            quick_compare_samples: DEFAULT_QUICK_COMPARE_SAMPLES,
+        }
+    }
+}
+
+/// 葉子哈希布隆過濾器
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "ChunkFilterRepr", into = "ChunkFilterRepr")]
+pub struct ChunkFilter {
+    /// 構建時的 chunk 大小（bytes）
+    chunk_size: usize,
+
+    /// 插入的葉子數
+    leaf_count: u64,
+
+    /// 哈希函數個數
+    num_hashes: u32,
+
+    /// 位數組長度（bits）
+    num_bits: u64,
+
+    /// 位數組
+    bits: Vec<u8>,
+}
+
+/// 序列化格式（`bits` 為 Base64）
+#[derive(Serialize, Deserialize)]
+struct ChunkFilterRepr {
+    version: u8,
+    chunk_size: usize,
+    leaf_count: u64,
+    num_hashes: u32,
+    num_bits: u64,
+    bits: String,
+}
+
+impl From<ChunkFilter> for ChunkFilterRepr {
+    fn from(filter: ChunkFilter) -> Self {
+        use base64::{engine::general_purpose, Engine as _};
+
+        Self {
+            version: CHUNK_FILTER_VERSION,
+            chunk_size: filter.chunk_size,
+            leaf_count: filter.leaf_count,
+            num_hashes: filter.num_hashes,
+            num_bits: filter.num_bits,
+            bits: general_purpose::STANDARD.encode(&filter.bits),
+        }
+    }
+}
+
+impl TryFrom<ChunkFilterRepr> for ChunkFilter {
+    type Error = String;
+
+    fn try_from(repr: ChunkFilterRepr) -> Result<Self, Self::Error> {
+        use base64::{engine::general_purpose, Engine as _};
+
+        if repr.version != CHUNK_FILTER_VERSION {
+            return Err(format!(
+                "unsupported chunk filter version {} (expected {})",
+                repr.version, CHUNK_FILTER_VERSION
+            ));
+        }
+        if repr.chunk_size == 0 || repr.num_bits == 0 {
+            return Err("chunk filter has zero chunk size or bit length".to_string());
+        }
+        if !(1..=MAX_NUM_HASHES).contains(&repr.num_hashes) {
+            return Err(format!(
+                "invalid chunk filter hash count {}",
+                repr.num_hashes
+            ));
+        }
+
+        let bits = general_purpose::STANDARD
+            .decode(&repr.bits)
+            .map_err(|e| format!("invalid chunk filter bits: {}", e))?;
+        if bits.len() as u64 != repr.num_bits.div_ceil(8) {
+            return Err(format!(
+                "chunk filter has {} bytes for {} bits",
+                bits.len(),
+                repr.num_bits
+            ));
+        }
+
+        Ok(Self {
+            chunk_size: repr.chunk_size,
+            leaf_count: repr.leaf_count,
+            num_hashes: repr.num_hashes,
+            num_bits: repr.num_bits,
+            bits,
+        })
+    }
+}
+
+impl ChunkFilter {
+    /// 為 `leaf_count` 個葉子創建空過濾器
+    ///
+    /// 位數組長度 `m = -n·ln(p) / ln(2)²`，哈希函數個數 `k = (m/n)·ln(2)`
+    pub fn with_capacity(leaf_count: usize, chunk_size: usize, false_positive_rate: f64) -> Self {
+        let n = leaf_count.max(1) as f64;
+        let p = false_positive_rate.clamp(1e-12, 0.5);
+
+        let num_bits = ((-n * p.ln()) / (LN_2 * LN_2)).ceil().max(8.0) as u64;
+        let num_hashes = ((num_bits as f64 / n) * LN_2)
+            .round()
+            .clamp(1.0, MAX_NUM_HASHES as f64) as u32;
+
+        Self {
+            chunk_size,
+            leaf_count: 0,
+            num_hashes,
+            num_bits,
+            bits: vec![0; num_bits.div_ceil(8) as usize],
+        }
+    }
+
+    /// 從 Merkle Tree 的葉子哈希構建過濾器
+    pub fn from_tree(tree: &MerkleTree, chunk_size: usize, false_positive_rate: f64) -> Self {
+        let mut filter = Self::with_capacity(tree.leaf_count(), chunk_size, false_positive_rate);
+        for leaf_hash in tree.leaf_hashes() {
+            filter.insert(leaf_hash);
+        }
+        filter
+    }
+
+    /// 插入葉子哈希
+    pub fn insert(&mut self, leaf_hash: &[u8; 32]) {
+        for index in self.bit_indices(leaf_hash) {
+            self.bits[(index / 8) as usize] |= 1 << (index % 8);
+        }
+        self.leaf_count += 1;
+    }
+
+    /// 葉子哈希是否可能在過濾器中
+    ///
+    /// 返回 `false` 時確定不在；返回 `true` 時有假陽性的可能
+    pub fn maybe_contains(&self, leaf_hash: &[u8; 32]) -> bool {
+        self.bit_indices(leaf_hash)
+            .all(|index| self.bits[(index / 8) as usize] & (1 << (index % 8)) != 0)
+    }
+
+    /// 構建時的 chunk 大小
+    pub fn chunk_size(&self) -> usize {
+        self.chunk_size
+    }
+
+    /// 插入的葉子數
+    pub fn leaf_count(&self) -> u64 {
+        self.leaf_count
+    }
+
+    /// 哈希函數個數
+    pub fn num_hashes(&self) -> u32 {
+        self.num_hashes
+    }
+
+    /// 位數組長度（bits）
+    pub fn num_bits(&self) -> u64 {
+        self.num_bits
+    }
+
+    /// 位數組大小（bytes）
+    pub fn size_bytes(&self) -> usize {
+        self.bits.len()
+    }
+
+    /// 按當前填充程度估算的假陽性率 `(1 - e^(-k·n/m))^k`
+    pub fn estimated_false_positive_rate(&self) -> f64 {
+        let k = self.num_hashes as f64;
+        let fill = 1.0 - (-k * self.leaf_count as f64 / self.num_bits as f64).exp();
+        fill.powf(k)
+    }
+
+    /// 雙重哈希：`h1 + i·h2 mod m`
+    ///
+    /// 葉子哈希本身是 Blake2b-256 輸出，直接取前 16 字節作為 `h1`、`h2`
+    fn bit_indices(&self, leaf_hash: &[u8; 32]) -> impl Iterator<Item = u64> {
+        let h1 = u64::from_le_bytes(leaf_hash[0..8].try_into().unwrap());
+        let h2 = u64::from_le_bytes(leaf_hash[8..16].try_into().unwrap()) | 1;
+        let num_bits = self.num_bits;
+
+        (0..self.num_hashes as u64).map(move |i| h1.wrapping_add(i.wrapping_mul(h2)) % num_bits)
+    }
+}
+
+/// 快速比對結果
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuickCompare {
+    /// 本次內容的 chunk 數
+    pub leaf_count: u64,
+
+    /// 上次審計的 chunk 數
+    pub prior_leaf_count: u64,
+
+    /// 抽樣的 chunk 數
+    pub sampled: usize,
+
+    /// 落在上次過濾器之外的 chunk 索引（升序）
+    pub outside_filter: Vec<u64>,
+}
+
+impl QuickCompare {
+    /// 是否有損壞信號（有 chunk 落在過濾器外，或 chunk 數變化）
+    pub fn is_suspect(&self) -> bool {
+        !self.outside_filter.is_empty() || self.leaf_count != self.prior_leaf_count
+    }
+
+    /// 抽樣中落在過濾器外的比例
+    pub fn outside_ratio(&self) -> f64 {
+        if self.sampled == 0 {
+            0.0
+        } else {
+            self.outside_filter.len() as f64 / self.sampled as f64
+        }
+    }
+}
+
+/// 抽樣 `samples` 個 chunk 與上次的過濾器比對（`samples` 不小於 chunk 數時檢查全部）
+pub fn quick_compare_content(prior: &ChunkFilter, content: &[u8], samples: usize) -> QuickCompare {
+    let leaf_hashes: Vec<[u8; 32]> = content.chunks(prior.chunk_size()).map(hash_leaf).collect();
+    quick_compare_leaves(prior, &leaf_hashes, samples)
+}
+
+/// 以已計算的葉子哈希抽樣比對（流式構建的樹不保留原始內容）
+///
+/// 葉子哈希必須按 `prior.chunk_size()` 切片計算
+pub fn quick_compare_leaves(
+    prior: &ChunkFilter,
+    leaf_hashes: &[[u8; 32]],
+    samples: usize,
+) -> QuickCompare {
+    let mut indices: Vec<usize> = if samples >= leaf_hashes.len() {
+        (0..leaf_hashes.len()).collect()
+    } else {
+        rand::seq::index::sample(&mut rand::thread_rng(), leaf_hashes.len(), samples).into_vec()
+    };
+    indices.sort_unstable();
+
+    let outside_filter = indices
+        .iter()
+        .filter(|&&index| !prior.maybe_contains(&leaf_hashes[index]))
+        .map(|&index| index as u64)
+        .collect();
+
+    QuickCompare {
+        leaf_count: leaf_hashes.len() as u64,
+        prior_leaf_count: prior.leaf_count(),
+        sampled: indices.len(),
+        outside_filter,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::deterministic_blob;
+
+    const CHUNK_SIZE: usize = 4096;
+
+    fn filter_for(content: &[u8], false_positive_rate: f64) -> ChunkFilter {
+        let tree = MerkleTree::from_blob(content, CHUNK_SIZE).unwrap();
+        ChunkFilter::from_tree(&tree, CHUNK_SIZE, false_positive_rate)
+    }
+
+    #[test]
+    fn test_inserted_leaves_are_always_contained() {
+        let content = deterministic_blob(500 * CHUNK_SIZE + 17);
+        let tree = MerkleTree::from_blob(&content, CHUNK_SIZE).unwrap();
+        let filter = ChunkFilter::from_tree(&tree, CHUNK_SIZE, 0.01);
+
+        assert_eq!(filter.leaf_count(), 501);
+        assert!(tree
+            .leaf_hashes()
+            .iter()
+            .all(|leaf| filter.maybe_contains(leaf)));
+    }
+
+    #[test]
+    fn test_size_and_false_positive_rate() {
+        let leaves = 10_000;
+        let mut filter = ChunkFilter::with_capacity(leaves, CHUNK_SIZE, 0.01);
+
+        // 約 9.6 bits / 元素，k ≈ 7
+        assert_eq!(filter.num_hashes(), 7);
+        let bits_per_leaf = filter.num_bits() as f64 / leaves as f64;
+        assert!((9.5..9.7).contains(&bits_per_leaf), "{}", bits_per_leaf);
+        assert_eq!(filter.size_bytes() as u64, filter.num_bits().div_ceil(8));
+
+        for i in 0..leaves as u64 {
+            filter.insert(&hash_leaf(&i.to_le_bytes()));
+        }
+        assert!(filter.estimated_false_positive_rate() < 0.011);
+
+        // 實測假陽性率接近目標
+        let trials = 20_000u64;
+        let false_positives = (0..trials)
+            .filter(|i| filter.maybe_contains(&hash_leaf(&(i + 1_000_000).to_le_bytes())))
+            .count();
+        let measured = false_positives as f64 / trials as f64;
+        assert!(measured < 0.02, "measured false positive rate {}", measured);
+
+        // 更低的目標需要更大的過濾器
+        let strict = ChunkFilter::with_capacity(leaves, CHUNK_SIZE, 0.000001);
+        assert!(strict.size_bytes() > 2 * filter.size_bytes());
+    }
+
+    #[test]
+    fn test_serialization_roundtrip_and_versioning() {
+        let filter = filter_for(&deterministic_blob(64 * CHUNK_SIZE), 0.01);
+
+        let json = serde_json::to_value(&filter).unwrap();
+        assert_eq!(json["version"], CHUNK_FILTER_VERSION);
+        let decoded: ChunkFilter = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(decoded, filter);
+
+        let mut future = json.clone();
+        future["version"] = serde_json::json!(CHUNK_FILTER_VERSION + 1);
+        let err = serde_json::from_value::<ChunkFilter>(future).unwrap_err();
+        assert!(err.to_string().contains("unsupported chunk filter version"));
+
+        let mut truncated = json;
+        truncated["num_bits"] = serde_json::json!(filter.num_bits() * 2);
+        assert!(serde_json::from_value::<ChunkFilter>(truncated).is_err());
+    }
+
+    #[test]
+    fn test_quick_compare_content() {
+        let original = deterministic_blob(200 * CHUNK_SIZE);
+        let filter = filter_for(&original, 0.0001);
+
+        let unchanged = quick_compare_content(&filter, &original, 32);
+        assert_eq!(unchanged.sampled, 32);
+        assert!(!unchanged.is_suspect());
+
+        // 修改第 100 個 chunk，全量比對必定檢出
+        let mut modified = original.clone();
+        modified[100 * CHUNK_SIZE + 5] ^= 0xff;
+        let result = quick_compare_content(&filter, &modified, usize::MAX);
+        assert_eq!(result.sampled, 200);
+        assert_eq!(result.outside_filter, vec![100]);
+        assert!(result.is_suspect());
+
+        // chunk 數變化同樣是信號
+        let truncated = quick_compare_content(&filter, &original[..150 * CHUNK_SIZE], 8);
+        assert!(truncated.outside_filter.is_empty());
+        assert!(truncated.is_suspect());
+    }
+}
@@ -0,0 +1,512 @@
+//! 挑戰承諾（commit-reveal）
+//!
+//! 若審計員可以在看到響應後重新抽取挑戰，就能反覆重抽直到損壞的 chunk 恰好沒被抽中。
+//! 因此挑戰集必須在收集任何響應之前承諾：
+//!
+//! 1. 生成隨機種子，由種子確定性地導出挑戰索引（[`derive_indices`]）
+//! 2. 計算承諾 `Blake2b-256(seed || indices)`，連同時間戳寫入承諾日誌
+//! 3. 只有承諾成功後才能拿到挑戰索引（[`CommittedChallenges`]），再發出第一個挑戰
+//! 4. 最終報告公開種子與索引，驗證者在日誌中找到承諾並核對（[`verify_reveal`]）
+//!
+//! 承諾日誌以 JSONL 追加寫入，每行一條 [`ChallengeCommitment`]，按
+//! `[rotation.outputs.commitments]` 輪轉；時間戳來自可替換的 [`Clock`]。鏈上尚無 `commit_audit_challenges` 入口，日誌是目前唯一的承諾存儲。
+
+use crate::error::{AuditorError, Result};
+use crate::rotating_writer::{self, Clock, RotatingWriter, RotationConfig, RotationStats};
+use fastcrypto::hash::{Blake2b256, HashFunction};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tracing::{debug, warn};
+
+/// `[rotation.outputs]` 中承諾日誌的名稱
+pub const ROTATION_OUTPUT: &str = "commitments";
+
+/// 種子長度（bytes）
+pub const CHALLENGE_SEED_LEN: usize = 32;
+
+/// 挑戰承諾配置
+///
+/// ```toml
+/// [commitment]
+/// enabled = true
+/// log_path = "./challenge_commitments.jsonl"
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CommitmentConfig {
+    /// 是否在挑戰前承諾挑戰集
+    pub enabled: bool,
+
+    /// 承諾日誌文件（JSONL）
+    pub log_path: String,
+}
+
+impl Default for CommitmentConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            log_path: "./challenge_commitments.jsonl".to_string(),
+        }
+    }
+}
+
+/// 由種子導出 `count` 個互不相同的挑戰索引（`count` 超過葉子數時取全部葉子）
+///
+/// 第 i 個候選為 `Blake2b-256(seed || i)` 前 8 字節（小端）對 `leaf_count` 取模，重複者跳過
+pub fn derive_indices(seed: &[u8], count: usize, leaf_count: u64) -> Vec<u64> {
+    let count = count.min(leaf_count as usize);
+    let mut seen = HashSet::with_capacity(count);
+    let mut indices = Vec::with_capacity(count);
+
+    let mut counter = 0u64;
+    while indices.len() < count {
+        let mut hasher = Blake2b256::default();
+        hasher.update(seed);
+        hasher.update(counter.to_le_bytes());
+        let digest = hasher.finalize().digest;
+        counter += 1;
+
+        let index = u64::from_le_bytes(digest[..8].try_into().unwrap()) % leaf_count;
+        if seen.insert(index) {
+            indices.push(index);
+        }
+    }
+
+    indices
+}
+
+/// 承諾摘要：`Blake2b-256(seed || indices)`，索引按 u64 小端拼接
+pub fn commitment_digest(seed: &[u8], indices: &[u64]) -> [u8; 32] {
+    let mut hasher = Blake2b256::default();
+    hasher.update(seed);
+    for index in indices {
+        hasher.update(index.to_le_bytes());
+    }
+    hasher.finalize().digest
+}
+
+/// 報告中公開的挑戰集
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChallengeReveal {
+    /// 種子（十六進制）
+    pub seed: String,
+
+    /// 導出索引時的葉子數
+    pub leaf_count: u64,
+
+    /// 挑戰索引（按導出順序）
+    pub indices: Vec<u64>,
+}
+
+impl ChallengeReveal {
+    /// 以隨機種子生成挑戰集
+    pub fn generate(count: usize, leaf_count: u64) -> Self {
+        let mut seed = [0u8; CHALLENGE_SEED_LEN];
+        rand::thread_rng().fill_bytes(&mut seed);
+        Self::from_seed(&seed, count, leaf_count)
+    }
+
+    /// 由指定種子生成挑戰集
+    pub fn from_seed(seed: &[u8], count: usize, leaf_count: u64) -> Self {
+        Self {
+            seed: hex::encode(seed),
+            leaf_count,
+            indices: derive_indices(seed, count, leaf_count),
+        }
+    }
+
+    /// 承諾摘要（十六進制）
+    pub fn commitment(&self) -> Result<String> {
+        Ok(hex::encode(commitment_digest(
+            &self.seed_bytes()?,
+            &self.indices,
+        )))
+    }
+
+    fn seed_bytes(&self) -> Result<Vec<u8>> {
+        hex::decode(&self.seed)
+            .map_err(|e| AuditorError::Commitment(format!("Invalid seed hex: {}", e)))
+    }
+}
+
+/// 挑戰承諾（承諾日誌中的一行）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChallengeCommitment {
+    /// 被審計的 Blob ID
+    pub blob_id: String,
+
+    /// `Blake2b-256(seed || indices)`（十六進制）
+    pub commitment: String,
+
+    /// 承諾時間（Unix 時間，秒）
+    pub committed_at: u64,
+}
+
+/// 已承諾的挑戰集
+///
+/// 只能由 [`CommitmentLog::commit`] 在承諾寫入成功後構造，
+/// 因此持有它即證明承諾先於任何挑戰
+#[derive(Debug, Clone)]
+pub struct CommittedChallenges {
+    reveal: ChallengeReveal,
+    commitment: ChallengeCommitment,
+}
+
+impl CommittedChallenges {
+    /// 挑戰索引
+    pub fn indices(&self) -> &[u64] {
+        &self.reveal.indices
+    }
+
+    /// 已寫入日誌的承諾
+    pub fn commitment(&self) -> &ChallengeCommitment {
+        &self.commitment
+    }
+
+    /// 取出用於報告的公開數據
+    pub fn into_reveal(self) -> ChallengeReveal {
+        self.reveal
+    }
+}
+
+/// 挑戰承諾日誌
+pub struct CommitmentLog {
+    /// 持久化文件（`None` 表示僅在內存中）
+    file: Option<RotatingWriter>,
+
+    /// 已記錄的承諾（按記錄順序）
+    entries: Mutex<Vec<ChallengeCommitment>>,
+
+    clock: Clock,
+}
+
+impl CommitmentLog {
+    /// 僅在內存中保存的日誌
+    pub fn in_memory() -> Self {
+        Self {
+            file: None,
+            entries: Mutex::new(Vec::new()),
+            clock: Arc::new(SystemTime::now),
+        }
+    }
+
+    /// 打開（或創建）JSONL 承諾日誌並加載已有條目（不輪轉）
+    ///
+    /// 無法解析的行會被跳過並記錄警告
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        Self::open_with_rotation(path, RotationConfig::disabled())
+    }
+
+    /// 打開（或創建）JSONL 承諾日誌，加載已有條目（包括仍保留的輪轉文件），之後按 `rotation` 輪轉
+    ///
+    /// 被保留策略刪除的承諾無法再用於核對舊報告的挑戰公開
+    pub fn open_with_rotation(path: impl AsRef<Path>, rotation: RotationConfig) -> Result<Self> {
+        let path = path.as_ref();
+        let mut entries = Vec::new();
+
+        for (line_no, line) in rotating_writer::read_to_string(path)?.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str::<ChallengeCommitment>(line) {
+                Ok(entry) => entries.push(entry),
+                Err(e) => warn!(
+                    "Skipping invalid commitment line {} in {}: {}",
+                    line_no + 1,
+                    path.display(),
+                    e
+                ),
+            }
+        }
+
+        debug!(
+            "Loaded {} challenge commitments from {}",
+            entries.len(),
+            path.display()
+        );
+
+        Ok(Self {
+            file: Some(RotatingWriter::open(path, rotation)?),
+            entries: Mutex::new(entries),
+            clock: Arc::new(SystemTime::now),
+        })
+    }
+
+    /// 承諾日誌的輪轉計數器（僅在內存中時為 `None`）
+    pub fn rotation_stats(&self) -> Option<Arc<RotationStats>> {
+        self.file.as_ref().map(RotatingWriter::stats)
+    }
+
+    /// 替換時鐘（測試用）
+    pub fn with_clock(mut self, clock: Clock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// 當前時間（Unix 秒）
+    fn now(&self) -> u64 {
+        (self.clock)()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0)
+    }
+
+    /// 承諾挑戰集
+    ///
+    /// 承諾寫入成功後才返回挑戰索引；寫入失敗時不得發出任何挑戰
+    pub fn commit(&self, blob_id: &str, reveal: ChallengeReveal) -> Result<CommittedChallenges> {
+        let commitment = ChallengeCommitment {
+            blob_id: blob_id.to_string(),
+            commitment: reveal.commitment()?,
+            committed_at: self.now(),
+        };
+
+        let mut entries = self.entries.lock().unwrap();
+
+        if let Some(file) = &self.file {
+            file.append_line(&serde_json::to_string(&commitment)?)?;
+        }
+
+        debug!(
+            "Committed {} challenges for blob {}: {}",
+            reveal.indices.len(),
+            blob_id,
+            commitment.commitment
+        );
+
+        entries.push(commitment.clone());
+        Ok(CommittedChallenges { reveal, commitment })
+    }
+
+    /// 查找承諾
+    pub fn find(&self, blob_id: &str, commitment: &str) -> Option<ChallengeCommitment> {
+        self.entries
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|entry| entry.blob_id == blob_id && entry.commitment == commitment)
+            .cloned()
+    }
+
+    /// 已記錄的承諾數
+    pub fn len(&self) -> usize {
+        self.entries.lock().unwrap().len()
+    }
+
+    /// 是否沒有任何承諾
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl std::fmt::Debug for CommitmentLog {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CommitmentLog")
+            .field("path", &self.file.as_ref().map(RotatingWriter::path))
+            .field("entries", &self.len())
+            .finish()
+    }
+}
+
+/// 核對報告公開的挑戰集與此前的承諾
+///
+/// 檢查：索引確由種子導出、日誌中存在相同 Blob 的承諾、承諾不晚於報告時間
+///
+/// # 返回
+/// 匹配的承諾
+pub fn verify_reveal(
+    log: &CommitmentLog,
+    blob_id: &str,
+    reveal: &ChallengeReveal,
+    reported_at: u64,
+) -> Result<ChallengeCommitment> {
+    let seed = reveal.seed_bytes()?;
+    let expected = derive_indices(&seed, reveal.indices.len(), reveal.leaf_count);
+    if expected != reveal.indices {
+        return Err(AuditorError::Commitment(format!(
+            "Challenge indices for blob {} were not derived from the revealed seed",
+            blob_id
+        )));
+    }
+
+    let digest = reveal.commitment()?;
+    let commitment = log.find(blob_id, &digest).ok_or_else(|| {
+        AuditorError::Commitment(format!(
+            "No prior commitment {} for blob {}",
+            digest, blob_id
+        ))
+    })?;
+
+    if commitment.committed_at > reported_at {
+        return Err(AuditorError::Commitment(format!(
+            "Commitment for blob {} at {} is later than the report at {}",
+            blob_id, commitment.committed_at, reported_at
+        )));
+    }
+
+    Ok(commitment)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audit_report::AuditReportGenerator;
+    use crate::integrity::IntegrityVerifier;
+    use crate::report::ReportManager;
+    use crate::test_support::{deterministic_blob, AggregatorMode, FakeAggregator};
+    use crate::types::BlobId;
+    use pqc_signer::dilithium::Dilithium3Signer;
+    use pqc_signer::traits::Signer;
+
+    fn fixed_clock(secs: u64) -> Clock {
+        Arc::new(move || UNIX_EPOCH + std::time::Duration::from_secs(secs))
+    }
+
+    #[test]
+    fn test_derive_indices_is_deterministic_and_unique() {
+        let seed = [7u8; CHALLENGE_SEED_LEN];
+        let indices = derive_indices(&seed, 10, 1000);
+        assert_eq!(indices, derive_indices(&seed, 10, 1000));
+        assert_eq!(indices.len(), 10);
+        assert_eq!(indices.iter().collect::<HashSet<_>>().len(), 10);
+        assert!(indices.iter().all(|&i| i < 1000));
+
+        // 挑戰數超過葉子數時覆蓋全部葉子
+        let mut all = derive_indices(&seed, 10, 3);
+        all.sort_unstable();
+        assert_eq!(all, vec![0, 1, 2]);
+
+        assert_ne!(
+            indices,
+            derive_indices(&[8u8; CHALLENGE_SEED_LEN], 10, 1000)
+        );
+    }
+
+    #[test]
+    fn test_matching_reveal_verifies() {
+        let log = CommitmentLog::in_memory().with_clock(fixed_clock(1_000));
+        let committed = log
+            .commit("blob-a", ChallengeReveal::generate(10, 500))
+            .unwrap();
+        assert_eq!(committed.commitment().committed_at, 1_000);
+        let reveal = committed.into_reveal();
+
+        let commitment = verify_reveal(&log, "blob-a", &reveal, 1_005).unwrap();
+        assert_eq!(commitment.commitment, reveal.commitment().unwrap());
+
+        // 承諾晚於報告時間
+        let err = verify_reveal(&log, "blob-a", &reveal, 999).unwrap_err();
+        assert!(err.to_string().contains("later than the report"));
+
+        // 不同 Blob 沒有承諾
+        assert!(verify_reveal(&log, "blob-b", &reveal, 1_005).is_err());
+    }
+
+    #[test]
+    fn test_mismatched_reveal_is_detected() {
+        let log = CommitmentLog::in_memory();
+        let reveal = log
+            .commit("blob-a", ChallengeReveal::generate(10, 500))
+            .unwrap()
+            .into_reveal();
+        let now = u64::MAX;
+
+        // 重抽：換一個種子，索引仍由種子導出，但沒有對應承諾
+        let rerolled = ChallengeReveal::generate(10, 500);
+        let err = verify_reveal(&log, "blob-a", &rerolled, now).unwrap_err();
+        assert!(err.to_string().contains("No prior commitment"));
+
+        // 保留種子但替換索引
+        let mut swapped = reveal.clone();
+        swapped.indices[0] = (swapped.indices[0] + 1) % 500;
+        let err = verify_reveal(&log, "blob-a", &swapped, now).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("not derived from the revealed seed"));
+
+        // 少公開一個挑戰
+        let mut truncated = reveal;
+        truncated.indices.pop();
+        assert!(verify_reveal(&log, "blob-a", &truncated, now).is_err());
+    }
+
+    #[test]
+    fn test_log_persists_across_reopen() {
+        let path = std::env::temp_dir().join(format!(
+            "challenge_commitments_{}.jsonl",
+            rand::random::<u32>()
+        ));
+
+        let reveal = CommitmentLog::open(&path)
+            .unwrap()
+            .commit("blob-a", ChallengeReveal::generate(4, 64))
+            .unwrap()
+            .into_reveal();
+
+        let reopened = CommitmentLog::open(&path).unwrap();
+        assert_eq!(reopened.len(), 1);
+        assert!(verify_reveal(&reopened, "blob-a", &reveal, u64::MAX).is_ok());
+
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[tokio::test]
+    async fn test_audit_commits_before_challenging() {
+        let aggregator =
+            FakeAggregator::start(deterministic_blob(100 * 4096), AggregatorMode::Healthy).await;
+        let log = Arc::new(CommitmentLog::in_memory());
+        let verifier =
+            IntegrityVerifier::new(aggregator.url().to_string()).with_commitments(Arc::clone(&log));
+
+        let mut signer = Dilithium3Signer::new();
+        signer.generate_keypair().unwrap();
+        let public_key = signer.public_key().to_vec();
+        let report = AuditReportGenerator::new(signer, None)
+            .generate_report(
+                verifier
+                    .audit_blob(&BlobId::from_bytes([0xa; 32]))
+                    .await
+                    .unwrap(),
+            )
+            .unwrap();
+
+        // 公開的挑戰集就是實際執行的挑戰，且與先前承諾一致
+        let reveal = report.challenge_reveal.clone().unwrap();
+        assert_eq!(reveal.leaf_count, 100);
+        assert_eq!(reveal.indices.len(), report.total_challenges as usize);
+        assert_eq!(log.len(), 1);
+        let commitment = report.verify_challenge_commitment(&log).unwrap();
+        assert!(commitment.committed_at <= report.timestamp);
+
+        // 公開數據在簽名範圍內
+        assert!(ReportManager::verify_report_with_commitments(&report, &public_key, &log).unwrap());
+        let mut tampered = report.clone();
+        tampered.challenge_reveal.as_mut().unwrap().seed = hex::encode([0u8; 32]);
+        assert!(!ReportManager::verify_report(&tampered, &public_key).unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_failed_commitment_aborts_before_challenges() {
+        let aggregator =
+            FakeAggregator::start(deterministic_blob(100 * 4096), AggregatorMode::Healthy).await;
+
+        // 打開後日誌路徑被替換為目錄：承諾無法寫入
+        let dir = std::env::temp_dir().join(format!("commitments_{}", rand::random::<u32>()));
+        let log = Arc::new(CommitmentLog::open(&dir).unwrap());
+        std::fs::create_dir_all(&dir).unwrap();
+        let verifier =
+            IntegrityVerifier::new(aggregator.url().to_string()).with_commitments(log.clone());
+
+        assert!(verifier
+            .audit_blob(&BlobId::from_bytes([0xa; 32]))
+            .await
+            .is_err());
+        assert!(log.is_empty());
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -1,13 +1,29 @@
 //! Configuration management module
 //!
-//! Responsible for loading and validating auditor node configuration
+//! Responsible for loading and validating auditor node configuration.
+//!
+//! Configuration is layered, later layers overriding earlier ones:
+//! built-in defaults < config file < environment (`<PREFIX>_<KEY>`, nested keys
+//! joined with `__`) < command line flags. Unknown keys in any layer are rejected.
 
+use crate::challenge_seed::ChallengeSeedMode;
+use crate::crypto::node_signature::NodePublicKey;
 use crate::error::{AuditorError, Result};
+use crate::local_encryption::EncryptionFallback;
 use crate::types::AuditorConfig;
-use config::{Config, ConfigError, File};
+use config::{Config, ConfigError, Environment, File};
 use serde::Deserialize;
 use std::path::Path;
 
+/// Environment variable prefix used by the auditor node binary
+///
+/// `AUDITOR_NODE_SUI_RPC_URL`, `AUDITOR_NODE_REAUDIT__ENABLED`, ... The shorter `AUDITOR_`
+/// would clash with unrelated variables in the shared `.env` (e.g. `AUDITOR_REGISTRY_ID`).
+pub const ENV_PREFIX: &str = "AUDITOR_NODE";
+
+/// Keys whose environment values are comma-separated lists
+const ENV_LIST_KEYS: &[&str] = &["storage_node_urls", "denied_producer_versions"];
+
 /// Load auditor configuration from file
 ///
 /// # Parameters
@@ -25,12 +41,52 @@ use std::path::Path;
 /// println!("Sui RPC: {}", config.sui_rpc_url);
 /// ```
 pub fn load_config<P: AsRef<Path>>(config_path: P) -> Result<AuditorConfig> {
-    let config = Config::builder()
-        .add_source(File::from(config_path.as_ref()))
-        .build()
-        .map_err(|e| AuditorError::Config(format!("Failed to load config file: {}", e)))?;
+    load_layers(Some(config_path.as_ref()), None, &[])
+}
+
+/// Build configuration from defaults, file, environment and CLI overrides
+///
+/// Missing keys fall back to the previous layer; the merged result is validated.
+fn load_layers(
+    config_path: Option<&Path>,
+    env_prefix: Option<&str>,
+    cli_overrides: &[(&str, String)],
+) -> Result<AuditorConfig> {
+    let defaults = Config::try_from(&AuditorConfig::default())
+        .map_err(|e| AuditorError::Config(format!("Failed to build defaults: {}", e)))?;
+    let mut builder = Config::builder().add_source(defaults);
+
+    if let Some(path) = config_path {
+        if !path.exists() {
+            return Err(AuditorError::Config(format!(
+                "Config file not found: {}",
+                path.display()
+            )));
+        }
+        builder = builder.add_source(File::from(path));
+    }
+
+    if let Some(prefix) = env_prefix {
+        let mut environment = Environment::with_prefix(prefix)
+            .prefix_separator("_")
+            .separator("__")
+            .list_separator(",")
+            .try_parsing(true);
+        for key in ENV_LIST_KEYS {
+            environment = environment.with_list_parse_key(key);
+        }
+        builder = builder.add_source(environment);
+    }
+
+    for (key, value) in cli_overrides {
+        builder = builder
+            .set_override(*key, value.as_str())
+            .map_err(|e| AuditorError::Config(format!("Invalid override {}: {}", key, e)))?;
+    }
 
-    let auditor_config: AuditorConfig = config
+    let auditor_config: AuditorConfig = builder
+        .build()
+        .map_err(|e| AuditorError::Config(format!("Failed to load config: {}", e)))?
         .try_deserialize()
         .map_err(|e| AuditorError::Config(format!("Failed to parse config: {}", e)))?;
 
@@ -39,31 +95,120 @@ pub fn load_config<P: AsRef<Path>>(config_path: P) -> Result<AuditorConfig> {
     Ok(auditor_config)
 }
 
+/// Write auditor configuration to a TOML file
+///
+/// The configuration is validated first, then written atomically (temp file + rename)
+/// so a crash never leaves a truncated config behind.
+///
+/// # Parameters
+/// - `config`: Configuration to write
+/// - `config_path`: Destination path (parent directories are created)
+/// - `header`: Comment lines written at the top of the file
+pub fn save_config<P: AsRef<Path>>(
+    config: &AuditorConfig,
+    config_path: P,
+    header: &str,
+) -> Result<()> {
+    config.validate()?;
+
+    let body = toml::to_string_pretty(config)
+        .map_err(|e| AuditorError::Config(format!("Failed to serialize config: {}", e)))?;
+
+    let path = config_path.as_ref();
+    if let Some(parent) = path.parent() {
+        if !parent.as_os_str().is_empty() {
+            std::fs::create_dir_all(parent)?;
+        }
+    }
+
+    let mut contents = String::new();
+    for line in header.lines() {
+        contents.push_str("# ");
+        contents.push_str(line);
+        contents.push('\n');
+    }
+    if !contents.is_empty() {
+        contents.push('\n');
+    }
+    contents.push_str(&body);
+
+    let tmp_path = path.with_extension("toml.tmp");
+    std::fs::write(&tmp_path, contents)?;
+    std::fs::rename(&tmp_path, path)?;
+
+    Ok(())
+}
+
 /// Load configuration from environment variables (for containerized deployment)
 ///
-/// Environment variable prefix: `AUDITOR_`
-/// Example: `AUDITOR_SUI_RPC_URL`, `AUDITOR_MIN_CHALLENGES`
+/// Environment variable prefix: [`ENV_PREFIX`]
+/// Example: `AUDITOR_NODE_SUI_RPC_URL`, `AUDITOR_NODE_MIN_CHALLENGES`
 pub fn load_config_from_env() -> Result<AuditorConfig> {
-    let config = Config::builder()
-        .add_source(config::Environment::with_prefix("AUDITOR"))
-        .build()
-        .map_err(|e| AuditorError::Config(format!("Failed to load env vars: {}", e)))?;
+    load_layers(None, Some(ENV_PREFIX), &[])
+}
 
-    let auditor_config: AuditorConfig = config
-        .try_deserialize()
-        .map_err(|e| AuditorError::Config(format!("Failed to parse env config: {}", e)))?;
+impl AuditorConfig {
+    /// Build layered configuration: defaults < `config_path` < environment < `cli_overrides`
+    ///
+    /// # Parameters
+    /// - `config_path`: Configuration file (an error if given but missing); `None` skips the layer
+    /// - `env_prefix`: Environment variable prefix, e.g. [`ENV_PREFIX`]
+    /// - `cli_overrides`: `(key, value)` pairs from command line flags, nested keys dotted
+    ///
+    /// # Returns
+    /// - `Err(AuditorError::Config)`: Unknown key (named in the message), bad value, or
+    ///   failed validation
+    pub fn from_layers(
+        config_path: Option<&Path>,
+        env_prefix: &str,
+        cli_overrides: &[(&str, String)],
+    ) -> Result<Self> {
+        load_layers(config_path, Some(env_prefix), cli_overrides)
+    }
 
-    validate_config(&auditor_config)?;
+    /// Validate configuration (see [`validate_config`])
+    pub fn validate(&self) -> Result<()> {
+        validate_config(self)
+    }
+}
 
-    Ok(auditor_config)
+/// Check that a value is a `0x`-prefixed Sui address / object ID (1-64 hex digits)
+pub fn is_sui_id(value: &str) -> bool {
+    value.strip_prefix("0x").is_some_and(|hex| {
+        !hex.is_empty() && hex.len() <= 64 && hex.chars().all(|c| c.is_ascii_hexdigit())
+    })
+}
+
+/// Check that a value is an absolute `http://` or `https://` URL with a host
+pub fn is_http_url(value: &str) -> bool {
+    reqwest::Url::parse(value)
+        .is_ok_and(|url| matches!(url.scheme(), "http" | "https") && url.has_host())
 }
 
 /// Validate configuration validity
 ///
 /// Checks:
 /// - Challenge count range is reasonable
-/// - URL format is correct
-/// - File paths exist
+/// - HTTP timeout is positive
+/// - HTTP connect/read timeouts are positive and the user agent is a valid header value
+/// - URLs (including proxies) parse as absolute http(s) URLs
+/// - Storage node public keys decode to an Ed25519 or BLS12-381 key
+/// - Surfaced error bodies have room for at least one character
+/// - Per-audit storage node subset is non-empty
+/// - Challenge parallelism, audit deadline and storage retry budget are positive
+/// - Blob ID verification has a positive size limit and shard count
+/// - Checkpoint directory, when set, is not empty
+/// - Dry-run directory, when dry-run is on, is not empty
+/// - Report prehash threshold, when set, is positive
+/// - Webhook notifications subscribe to at least one event
+/// - Local encryption fallback is only set together with Seal encryption
+/// - Sui addresses and object IDs are well-formed
+/// - Sui submission has the IDs it needs
+/// - Checkpoint-derived challenge seeds have an auditor address to bind
+/// - Re-audit backoff schedule is non-empty
+/// - Scheduler failure retry interval is positive
+/// - Report archive, when enabled, has a directory and a positive prune interval
+/// - Chunk filter false positive rate is within (0, 1)
 fn validate_config(config: &AuditorConfig) -> Result<()> {
     // Validate challenge count
     if config.min_challenges == 0 {
@@ -78,29 +223,298 @@ fn validate_config(config: &AuditorConfig) -> Result<()> {
         ));
     }
 
-    // Validate URL format
-    if !config.sui_rpc_url.starts_with("http://") && !config.sui_rpc_url.starts_with("https://") {
+    if config.http_timeout_secs == 0 {
+        return Err(AuditorError::Config(
+            "http_timeout_secs must be greater than 0".to_string(),
+        ));
+    }
+
+    if config.http.connect_timeout_secs == 0 || config.http.read_timeout_secs == 0 {
+        return Err(AuditorError::Config(
+            "http.connect_timeout_secs and http.read_timeout_secs must be greater than 0"
+                .to_string(),
+        ));
+    }
+
+    if reqwest::header::HeaderValue::from_str(&config.http.user_agent).is_err() {
         return Err(AuditorError::Config(format!(
-            "Invalid Sui RPC URL: {}",
-            config.sui_rpc_url
+            "Invalid http.user_agent: {:?}",
+            config.http.user_agent
         )));
     }
 
-    if !config.walrus_aggregator_url.starts_with("http://")
-        && !config.walrus_aggregator_url.starts_with("https://")
+    // Validate URL format
+    let urls = [
+        ("Sui RPC URL", Some(&config.sui_rpc_url)),
+        ("Walrus aggregator URL", Some(&config.walrus_aggregator_url)),
+        ("Walrus publisher URL", Some(&config.walrus_publisher_url)),
+        ("Seal API URL", config.seal_api_url.as_ref()),
+        ("Webhook URL", config.webhook_url.as_ref()),
+        ("HTTP proxy URL", config.http.http_proxy.as_ref()),
+        ("HTTPS proxy URL", config.http.https_proxy.as_ref()),
+    ];
+    for (name, url) in urls {
+        if let Some(url) = url.filter(|url| !is_http_url(url)) {
+            return Err(AuditorError::Config(format!("Invalid {}: {}", name, url)));
+        }
+    }
+
+    if let Some(url) = config
+        .storage_node_urls
+        .iter()
+        .find(|url| !is_http_url(url))
     {
         return Err(AuditorError::Config(format!(
-            "Invalid Walrus aggregator URL: {}",
-            config.walrus_aggregator_url
+            "Invalid storage node URL: {}",
+            url
+        )));
+    }
+
+    // Validate storage node public keys
+    for (url, key) in &config.storage_node_public_keys {
+        if !is_http_url(url) {
+            return Err(AuditorError::Config(format!(
+                "Invalid storage node URL in storage_node_public_keys: {}",
+                url
+            )));
+        }
+        NodePublicKey::parse(key).map_err(|e| {
+            AuditorError::Config(format!("Invalid public key for storage node {}: {}", url, e))
+        })?;
+    }
+
+    if config.storage_nodes_per_audit == Some(0) {
+        return Err(AuditorError::Config(
+            "storage_nodes_per_audit must be at least 1".to_string(),
+        ));
+    }
+
+    if !(0.0..=1.0).contains(&config.recovery_symbol_ratio) {
+        return Err(AuditorError::Config(format!(
+            "recovery_symbol_ratio must be between 0.0 and 1.0, got {}",
+            config.recovery_symbol_ratio
         )));
     }
 
+    if config.max_parallel_challenges == 0 {
+        return Err(AuditorError::Config(
+            "max_parallel_challenges must be at least 1".to_string(),
+        ));
+    }
+
+    if config.max_requests_per_sec_per_host > 0 && config.burst == 0 {
+        return Err(AuditorError::Config(
+            "burst must be at least 1 when max_requests_per_sec_per_host is set".to_string(),
+        ));
+    }
+
+    if config.audit_deadline_secs == 0 {
+        return Err(AuditorError::Config(
+            "audit_deadline_secs must be greater than 0".to_string(),
+        ));
+    }
+
+    if config.storage_retry_budget_secs == 0 {
+        return Err(AuditorError::Config(
+            "storage_retry_budget_secs must be greater than 0".to_string(),
+        ));
+    }
+
+    if config.verify_blob_id && config.verify_blob_id_max_bytes == 0 {
+        return Err(AuditorError::Config(
+            "verify_blob_id_max_bytes must be greater than 0 when verify_blob_id is set"
+                .to_string(),
+        ));
+    }
+
+    if config.verify_blob_id && config.verify_blob_id_n_shards == 0 {
+        return Err(AuditorError::Config(
+            "verify_blob_id_n_shards must be greater than 0 when verify_blob_id is set".to_string(),
+        ));
+    }
+
+    if config
+        .checkpoint_dir
+        .as_deref()
+        .is_some_and(|dir| dir.trim().is_empty())
+    {
+        return Err(AuditorError::Config(
+            "checkpoint_dir must not be empty (omit it to disable checkpoints)".to_string(),
+        ));
+    }
+
+    if config.dry_run && config.dry_run_dir.trim().is_empty() {
+        return Err(AuditorError::Config(
+            "dry_run enabled but dry_run_dir is empty".to_string(),
+        ));
+    }
+
+    if config.content_cache.enabled && config.content_cache.max_entries == 0 {
+        return Err(AuditorError::Config(
+            "content_cache.max_entries must be greater than 0 when the cache is enabled"
+                .to_string(),
+        ));
+    }
+    if config
+        .content_cache
+        .path
+        .as_deref()
+        .is_some_and(|path| path.trim().is_empty())
+    {
+        return Err(AuditorError::Config(
+            "content_cache.path must not be empty (omit it to cache in memory)".to_string(),
+        ));
+    }
+
+    if config.report_prehash_threshold == Some(0) {
+        return Err(AuditorError::Config(
+            "report_prehash_threshold must be at least 1 byte (omit it to sign reports directly)"
+                .to_string(),
+        ));
+    }
+
+    if config.webhook_url.is_some() && config.webhook_events.is_empty() {
+        return Err(AuditorError::Config(
+            "webhook_events must not be empty when webhook_url is set".to_string(),
+        ));
+    }
+
+    if let Some(addr) = &config.metrics_listen_addr {
+        if addr.parse::<std::net::SocketAddr>().is_err() {
+            return Err(AuditorError::Config(format!(
+                "Invalid metrics_listen_addr (expected host:port): {}",
+                addr
+            )));
+        }
+    }
+
+    if config.walrus_storage_epochs == Some(0) {
+        return Err(AuditorError::Config(
+            "walrus_storage_epochs must be at least 1".to_string(),
+        ));
+    }
+
+    if config.max_error_body_len < 2 {
+        return Err(AuditorError::Config(
+            "max_error_body_len must be at least 2".to_string(),
+        ));
+    }
+
     // Validate Seal configuration
     if config.enable_seal_encryption && config.seal_api_url.is_none() {
         return Err(AuditorError::Config(
             "Seal encryption enabled but seal_api_url not provided".to_string(),
         ));
     }
+    if !config.enable_seal_encryption && config.encryption_fallback != EncryptionFallback::None {
+        return Err(AuditorError::Config(
+            "encryption_fallback requires enable_seal_encryption".to_string(),
+        ));
+    }
+
+    // Validate Sui addresses and object IDs
+    let sui_ids = [
+        ("auditor_address", &config.auditor_address),
+        ("audit_system_package_id", &config.audit_system_package_id),
+        ("access_policy_package_id", &config.access_policy_package_id),
+        ("auditor_registry_id", &config.auditor_registry_id),
+        ("incentives_id", &config.incentives_id),
+    ];
+    for (name, value) in sui_ids {
+        if let Some(value) = value {
+            if !is_sui_id(value) {
+                return Err(AuditorError::Config(format!("Invalid {}: {}", name, value)));
+            }
+        }
+    }
+
+    // Validate Sui submission configuration
+    if config.submit_to_sui {
+        if config.audit_system_package_id.is_none() {
+            return Err(AuditorError::Config(
+                "submit_to_sui enabled but audit_system_package_id not provided".to_string(),
+            ));
+        }
+        if config.auditor_address.is_none() {
+            return Err(AuditorError::Config(
+                "submit_to_sui enabled but auditor_address not provided".to_string(),
+            ));
+        }
+    }
+
+    // Checkpoint-derived challenge seeds bind the auditor address
+    if config.challenge_seed_mode == ChallengeSeedMode::SuiCheckpoint
+        && config.auditor_address.is_none()
+    {
+        return Err(AuditorError::Config(
+            "challenge_seed_mode = \"sui_checkpoint\" requires auditor_address".to_string(),
+        ));
+    }
+
+    // Validate re-audit backoff schedule
+    if config.reaudit.enabled && config.reaudit.backoff_secs.is_empty() {
+        return Err(AuditorError::Config(
+            "reaudit enabled but backoff_secs is empty".to_string(),
+        ));
+    }
+
+    // Validate per-blob audit scheduling
+    if config.scheduler.enabled && config.scheduler.failed_retry_secs == 0 {
+        return Err(AuditorError::Config(
+            "scheduler enabled but failed_retry_secs is 0".to_string(),
+        ));
+    }
+
+    // Validate report archive
+    let archive = &config.report_archive;
+    if archive.enabled && (archive.dir.trim().is_empty() || archive.prune_interval_secs == 0) {
+        return Err(AuditorError::Config(
+            "report_archive enabled but dir is empty or prune_interval_secs is 0".to_string(),
+        ));
+    }
+
+    // Validate chunk filter false positive rate
+    let fpr = config.chunk_filter.false_positive_rate;
+    if config.chunk_filter.enabled && !(fpr > 0.0 && fpr < 1.0) {
+        return Err(AuditorError::Config(format!(
+            "chunk_filter.false_positive_rate must be between 0 and 1, got {}",
+            fpr
+        )));
+    }
+
+    // Validate circuit breaker thresholds
+    let rate = config.breaker.failure_rate;
+    if config.breaker.enabled && !(rate > 0.0 && rate <= 1.0) {
+        return Err(AuditorError::Config(format!(
+            "breaker.failure_rate must be in (0, 1], got {}",
+            rate
+        )));
+    }
+    if config.breaker.enabled
+        && (config.breaker.window_secs == 0 || config.breaker.min_requests == 0)
+    {
+        return Err(AuditorError::Config(
+            "breaker.window_secs and breaker.min_requests must be positive".to_string(),
+        ));
+    }
+
+    // Validate storage node health monitor thresholds
+    let health = &config.node_health;
+    if health.enabled && !(0.0..=1.0).contains(&health.min_success_rate) {
+        return Err(AuditorError::Config(format!(
+            "node_health.min_success_rate must be between 0 and 1, got {}",
+            health.min_success_rate
+        )));
+    }
+    if health.enabled
+        && (health.interval_secs == 0 || health.window == 0 || health.recovery_checks == 0)
+    {
+        return Err(AuditorError::Config(
+            "node_health.interval_secs, node_health.window and node_health.recovery_checks \
+             must be positive"
+                .to_string(),
+        ));
+    }
 
     Ok(())
 }
@@ -115,6 +529,13 @@ mod tests {
         assert!(validate_config(&config).is_ok());
     }
 
+    #[test]
+    fn test_invalid_max_error_body_len() {
+        let mut config = AuditorConfig::default();
+        config.max_error_body_len = 1;
+        assert!(validate_config(&config).is_err());
+    }
+
     #[test]
     fn test_invalid_min_challenges() {
         let mut config = AuditorConfig::default();
@@ -129,4 +550,450 @@ mod tests {
         config.min_challenges = 10;
         assert!(validate_config(&config).is_err());
     }
+
+    #[test]
+    fn test_invalid_walrus_publisher_settings() {
+        let mut config = AuditorConfig::default();
+        config.walrus_publisher_url = "publisher.walrus.space".to_string();
+        assert!(validate_config(&config).is_err());
+
+        config.walrus_publisher_url = "http://127.0.0.1:31416".to_string();
+        config.walrus_storage_epochs = Some(0);
+        assert!(validate_config(&config).is_err());
+
+        config.walrus_storage_epochs = Some(5);
+        assert!(validate_config(&config).is_ok());
+    }
+
+    #[test]
+    fn test_invalid_storage_node_url() {
+        let mut config = AuditorConfig::default();
+        config.storage_node_urls = vec!["https://node-1.walrus.space".to_string()];
+        assert!(validate_config(&config).is_ok());
+
+        config
+            .storage_node_urls
+            .push("node-2.walrus.space".to_string());
+        assert!(validate_config(&config).is_err());
+    }
+
+    #[test]
+    fn test_invalid_http_client_config() {
+        let mut config = AuditorConfig::default();
+        config.http.read_timeout_secs = 0;
+        assert!(validate_config(&config).is_err());
+
+        let mut config = AuditorConfig::default();
+        config.http.connect_timeout_secs = 0;
+        assert!(validate_config(&config).is_err());
+
+        let mut config = AuditorConfig::default();
+        config.http.user_agent = "auditor\r\nX-Injected: 1".to_string();
+        assert!(validate_config(&config).is_err());
+
+        // An idle pool of 0 disables connection reuse, which is allowed
+        let mut config = AuditorConfig::default();
+        config.http.pool_max_idle_per_host = 0;
+        assert!(validate_config(&config).is_ok());
+    }
+
+    #[test]
+    fn test_checkpoint_challenge_seed_requires_auditor_address() {
+        let mut config = AuditorConfig::default();
+        config.challenge_seed_mode = ChallengeSeedMode::SuiCheckpoint;
+        assert!(validate_config(&config).is_err());
+
+        config.auditor_address = Some("0x6".to_string());
+        assert!(validate_config(&config).is_ok());
+    }
+
+    #[test]
+    fn test_invalid_storage_node_public_keys() {
+        use fastcrypto::ed25519::Ed25519KeyPair;
+        use fastcrypto::traits::{KeyPair, ToFromBytes};
+
+        let keypair = Ed25519KeyPair::generate(&mut rand::thread_rng());
+        let key = format!("0x{}", hex::encode(keypair.public().as_bytes()));
+        let mut config = AuditorConfig::default();
+        let url = "https://node-1.walrus.space".to_string();
+        config
+            .storage_node_public_keys
+            .insert(url.clone(), key.clone());
+        assert!(validate_config(&config).is_ok());
+
+        // Neither an Ed25519 nor a BLS12-381 key length
+        config
+            .storage_node_public_keys
+            .insert(url.clone(), hex::encode([1u8; 48]));
+        assert!(validate_config(&config).is_err());
+
+        config.storage_node_public_keys.clear();
+        config
+            .storage_node_public_keys
+            .insert("node-1.walrus.space".to_string(), key);
+        assert!(validate_config(&config).is_err());
+    }
+
+    #[test]
+    fn test_invalid_storage_nodes_per_audit() {
+        let mut config = AuditorConfig::default();
+        config.storage_nodes_per_audit = Some(0);
+        assert!(validate_config(&config).is_err());
+
+        config.storage_nodes_per_audit = Some(2);
+        assert!(validate_config(&config).is_ok());
+    }
+
+    #[test]
+    fn test_invalid_recovery_symbol_ratio() {
+        let mut config = AuditorConfig::default();
+        for ratio in [-0.1, 1.5, f64::NAN] {
+            config.recovery_symbol_ratio = ratio;
+            assert!(validate_config(&config).is_err());
+        }
+
+        for ratio in [0.0, 0.25, 1.0] {
+            config.recovery_symbol_ratio = ratio;
+            assert!(validate_config(&config).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_invalid_rate_limit_burst() {
+        let mut config = AuditorConfig::default();
+        config.burst = 0;
+        assert!(validate_config(&config).is_ok());
+
+        config.max_requests_per_sec_per_host = 5;
+        assert!(validate_config(&config).is_err());
+
+        config.burst = 1;
+        assert!(validate_config(&config).is_ok());
+    }
+
+    #[test]
+    fn test_invalid_challenge_parallelism_and_time_limits() {
+        let mut config = AuditorConfig::default();
+        config.max_parallel_challenges = 0;
+        assert!(validate_config(&config).is_err());
+
+        config.max_parallel_challenges = 1;
+        config.audit_deadline_secs = 0;
+        assert!(validate_config(&config).is_err());
+
+        config.audit_deadline_secs = 1;
+        config.storage_retry_budget_secs = 0;
+        assert!(validate_config(&config).is_err());
+    }
+
+    #[test]
+    fn test_invalid_blob_id_verification_limit() {
+        let mut config = AuditorConfig::default();
+        config.verify_blob_id_max_bytes = 0;
+        assert!(validate_config(&config).is_ok());
+
+        config.verify_blob_id = true;
+        assert!(validate_config(&config).is_err());
+
+        config.verify_blob_id_max_bytes = 1024;
+        config.verify_blob_id_n_shards = 0;
+        assert!(validate_config(&config).is_err());
+    }
+
+    #[test]
+    fn test_invalid_checkpoint_dir() {
+        let mut config = AuditorConfig::default();
+        config.checkpoint_dir = Some(" ".to_string());
+        assert!(validate_config(&config).is_err());
+
+        config.checkpoint_dir = Some("./checkpoints".to_string());
+        assert!(validate_config(&config).is_ok());
+    }
+
+    #[test]
+    fn test_invalid_dry_run_dir() {
+        let mut config = AuditorConfig::default();
+        config.dry_run_dir = String::new();
+        assert!(validate_config(&config).is_ok());
+
+        config.dry_run = true;
+        assert!(validate_config(&config).is_err());
+
+        config.dry_run_dir = "./dry-run".to_string();
+        assert!(validate_config(&config).is_ok());
+    }
+
+    #[test]
+    fn test_invalid_content_cache() {
+        let mut config = AuditorConfig::default();
+        config.content_cache.max_entries = 0;
+        assert!(validate_config(&config).is_ok());
+
+        config.content_cache.enabled = true;
+        assert!(validate_config(&config).is_err());
+
+        config.content_cache.max_entries = 16;
+        config.content_cache.path = Some("".to_string());
+        assert!(validate_config(&config).is_err());
+
+        config.content_cache.path = Some("./content_cache.bin".to_string());
+        assert!(validate_config(&config).is_ok());
+    }
+
+    #[test]
+    fn test_invalid_report_prehash_threshold() {
+        let mut config = AuditorConfig::default();
+        config.report_prehash_threshold = Some(0);
+        assert!(validate_config(&config).is_err());
+
+        config.report_prehash_threshold = Some(64 * 1024 * 1024);
+        assert!(validate_config(&config).is_ok());
+    }
+
+    #[test]
+    fn test_webhook_validation() {
+        let mut config = AuditorConfig::default();
+        config.webhook_url = Some("hooks.example.com/audit".to_string());
+        assert!(validate_config(&config).is_err());
+
+        config.webhook_url = Some("https://hooks.example.com/audit".to_string());
+        assert!(validate_config(&config).is_ok());
+
+        config.webhook_events.clear();
+        assert!(validate_config(&config).is_err());
+    }
+
+    #[test]
+    fn test_invalid_metrics_listen_addr() {
+        let mut config = AuditorConfig::default();
+        config.metrics_listen_addr = Some("localhost".to_string());
+        assert!(validate_config(&config).is_err());
+
+        config.metrics_listen_addr = Some("127.0.0.1:9184".to_string());
+        assert!(validate_config(&config).is_ok());
+    }
+
+    #[test]
+    fn test_invalid_sui_ids() {
+        let mut config = AuditorConfig::default();
+        config.auditor_address = Some("not-an-address".to_string());
+        assert!(config.validate().is_err());
+
+        config.auditor_address = Some("0x6".to_string());
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_submit_to_sui_requires_ids() {
+        let mut config = AuditorConfig::default();
+        config.submit_to_sui = true;
+        config.audit_system_package_id = None;
+        assert!(config.validate().is_err());
+
+        config.audit_system_package_id = Some("0x1bc5".to_string());
+        config.auditor_address = Some("0xab8e".to_string());
+        assert!(config.validate().is_ok());
+    }
+
+    fn write_config(dir: &tempfile::TempDir, contents: &str) -> std::path::PathBuf {
+        let path = dir.path().join("config.toml");
+        std::fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_layer_precedence() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = write_config(
+            &dir,
+            "min_challenges = 20\nmax_challenges = 200\n\
+             audit_interval_secs = 120\nhttp_timeout_secs = 15\n",
+        );
+        // 每個測試使用獨立前綴，避免並行測試互相干擾
+        std::env::set_var("CFGTEST_PRECEDENCE_MAX_CHALLENGES", "300");
+        std::env::set_var("CFGTEST_PRECEDENCE_AUDIT_INTERVAL_SECS", "60");
+        std::env::set_var("CFGTEST_PRECEDENCE_REAUDIT__CONFIRMATIONS", "9");
+
+        let config = AuditorConfig::from_layers(
+            Some(&path),
+            "CFGTEST_PRECEDENCE",
+            &[("audit_interval_secs", "30".to_string())],
+        )
+        .unwrap();
+
+        assert_eq!(config.sui_rpc_url, AuditorConfig::default().sui_rpc_url);
+        assert_eq!(config.min_challenges, 20);
+        assert_eq!(config.http_timeout_secs, 15);
+        assert_eq!(config.max_challenges, 300);
+        assert_eq!(config.reaudit.confirmations, 9);
+        assert_eq!(config.audit_interval_secs, 30);
+    }
+
+    #[test]
+    fn test_max_challenges_beyond_u16_is_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = write_config(&dir, "max_challenges = 70000\n");
+
+        assert!(AuditorConfig::from_layers(Some(&path), "CFGTEST_U16_CHALLENGES", &[]).is_err());
+    }
+
+    #[test]
+    fn test_unknown_keys_are_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let path = write_config(&dir, "min_challenge = 5\n");
+        let err = AuditorConfig::from_layers(Some(&path), "CFGTEST_UNKNOWN_FILE", &[])
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("min_challenge"), "{}", err);
+
+        let path = write_config(&dir, "[breaker]\nfailure_ratio = 0.5\n");
+        let err = AuditorConfig::from_layers(Some(&path), "CFGTEST_UNKNOWN_FILE", &[])
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("failure_ratio"), "{}", err);
+
+        std::env::set_var("CFGTEST_UNKNOWN_ENV_SUI_RPC", "https://example.com");
+        let err = AuditorConfig::from_layers(None, "CFGTEST_UNKNOWN_ENV", &[])
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("sui_rpc"), "{}", err);
+    }
+
+    #[test]
+    fn test_storage_node_api_style() {
+        use crate::storage_node_client::ApiStyle;
+
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(
+            AuditorConfig::default().storage_node_api_style,
+            ApiStyle::Challenge
+        );
+
+        let path = write_config(&dir, "storage_node_api_style = \"walrus\"\n");
+        let config = AuditorConfig::from_layers(Some(&path), "CFGTEST_API_STYLE", &[]).unwrap();
+        assert_eq!(config.storage_node_api_style, ApiStyle::Walrus);
+
+        let path = write_config(&dir, "storage_node_api_style = \"grpc\"\n");
+        assert!(AuditorConfig::from_layers(Some(&path), "CFGTEST_API_STYLE", &[]).is_err());
+    }
+
+    #[test]
+    fn test_missing_config_file_is_an_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("missing.toml");
+        assert!(AuditorConfig::from_layers(Some(&path), "CFGTEST_MISSING", &[]).is_err());
+        assert!(AuditorConfig::from_layers(None, "CFGTEST_MISSING", &[]).is_ok());
+    }
+
+    #[test]
+    fn test_example_config_loads() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("config.example.toml");
+        AuditorConfig::from_layers(Some(&path), "CFGTEST_EXAMPLE", &[]).unwrap();
+    }
+
+    #[test]
+    fn test_invalid_urls_and_timeout() {
+        let mut config = AuditorConfig::default();
+        config.http_timeout_secs = 0;
+        assert!(validate_config(&config).is_err());
+
+        let mut config = AuditorConfig::default();
+        config.sui_rpc_url = "https://".to_string();
+        assert!(validate_config(&config).is_err());
+
+        config.sui_rpc_url = "ftp://fullnode.testnet.sui.io".to_string();
+        assert!(validate_config(&config).is_err());
+
+        let mut config = AuditorConfig::default();
+        config.seal_api_url = Some("localhost:3001".to_string());
+        assert!(validate_config(&config).is_err());
+
+        config.seal_api_url = Some("http://localhost:3001".to_string());
+        assert!(validate_config(&config).is_ok());
+    }
+
+    #[test]
+    fn test_invalid_proxy_url() {
+        let mut config = AuditorConfig::default();
+        config.http.https_proxy = Some("proxy.corp.example:3128".to_string());
+        assert!(validate_config(&config).is_err());
+
+        config.http.https_proxy = Some("http://proxy.corp.example:3128".to_string());
+        config.http.no_proxy = Some("localhost,.corp.example".to_string());
+        assert!(validate_config(&config).is_ok());
+    }
+
+    #[test]
+    fn test_seal_requires_api_url() {
+        let mut config = AuditorConfig::default();
+        config.enable_seal_encryption = true;
+        assert!(validate_config(&config).is_err());
+    }
+
+    #[test]
+    fn test_encryption_fallback_requires_seal() {
+        let mut config = AuditorConfig::default();
+        config.encryption_fallback = EncryptionFallback::LocalAes;
+        assert!(validate_config(&config).is_err());
+
+        config.enable_seal_encryption = true;
+        config.seal_api_url = Some("http://localhost:3001".to_string());
+        assert!(validate_config(&config).is_ok());
+    }
+
+    #[test]
+    fn test_invalid_breaker_thresholds() {
+        let mut config = AuditorConfig::default();
+        config.breaker.failure_rate = 0.0;
+        assert!(validate_config(&config).is_err());
+
+        config.breaker.failure_rate = 1.0;
+        config.breaker.min_requests = 0;
+        assert!(validate_config(&config).is_err());
+
+        config.breaker.enabled = false;
+        assert!(validate_config(&config).is_ok());
+    }
+
+    #[test]
+    fn test_invalid_node_health_thresholds() {
+        let mut config = AuditorConfig::default();
+        config.node_health.min_success_rate = 1.5;
+        assert!(validate_config(&config).is_err());
+
+        config.node_health.min_success_rate = 0.5;
+        config.node_health.recovery_checks = 0;
+        assert!(validate_config(&config).is_err());
+
+        config.node_health.enabled = false;
+        assert!(validate_config(&config).is_ok());
+    }
+
+    #[test]
+    fn test_invalid_scheduler_retry_interval() {
+        let mut config = AuditorConfig::default();
+        config.scheduler.failed_retry_secs = 0;
+        assert!(validate_config(&config).is_err());
+
+        config.scheduler.enabled = false;
+        assert!(validate_config(&config).is_ok());
+    }
+
+    #[test]
+    fn test_invalid_report_archive() {
+        let mut config = AuditorConfig::default();
+        config.report_archive.enabled = true;
+        assert!(validate_config(&config).is_ok());
+
+        config.report_archive.prune_interval_secs = 0;
+        assert!(validate_config(&config).is_err());
+
+        config.report_archive.prune_interval_secs = 60;
+        config.report_archive.dir = " ".to_string();
+        assert!(validate_config(&config).is_err());
+
+        config.report_archive.enabled = false;
+        assert!(validate_config(&config).is_ok());
+    }
 }
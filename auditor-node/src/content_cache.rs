@@ -0,0 +1,377 @@
+//! 以 `ETag` 重新驗證的 Blob 內容緩存
+//!
+//! Walrus Blob 不可變，但定期審計每次都重新下載並哈希整個 Blob。啟用內容緩存後，
+//! [`IntegrityVerifier`](crate::integrity::IntegrityVerifier) 按 Blob ID 記錄上次下載的
+//! `ETag`、內容哈希、Merkle 根、文件大小與葉子哈希（[`CachedContent`]），下次審計：
+//!
+//! 1. 以 `If-None-Match: <etag>` 發出條件 GET
+//! 2. Aggregator 返回 304 時沿用緩存的哈希，從葉子哈希恢復 Merkle 樹，只重新執行本地挑戰抽樣
+//! 3. 返回完整響應（200 等）時先使緩存條目失效，下載完成後以新的 `ETag` 重新寫入
+//!
+//! 緩存後端通過 [`ContentCache`] 插拔：默認為內存中的 [`MemoryContentCache`]，
+//! 配置 `path` 時使用持久化到文件的 [`FileContentCache`]。兩者都按條目數限制大小，
+//! 超出時淘汰最久未使用的條目（LRU）。緩存默認關閉；抓包模式需要完整響應體，不使用緩存。
+
+use crate::error::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tracing::{debug, warn};
+
+/// 默認最多緩存的 Blob 數
+pub const DEFAULT_CONTENT_CACHE_MAX_ENTRIES: usize = 1024;
+
+/// 內容緩存配置
+///
+/// ```toml
+/// [content_cache]
+/// enabled = false
+/// max_entries = 1024
+/// path = "./content_cache.bin"
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ContentCacheConfig {
+    /// 是否啟用緩存
+    pub enabled: bool,
+
+    /// 最多緩存的 Blob 數（超出時淘汰最久未使用的）
+    pub max_entries: usize,
+
+    /// 緩存文件（未設置時僅在內存中緩存）
+    pub path: Option<String>,
+}
+
+impl Default for ContentCacheConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_entries: DEFAULT_CONTENT_CACHE_MAX_ENTRIES,
+            path: None,
+        }
+    }
+}
+
+/// 一次完整下載的哈希結果
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CachedContent {
+    /// 下載時 Aggregator 返回的 `ETag`
+    pub etag: String,
+
+    /// 內容哈希（SHA-256，十六進制）
+    pub content_hash: String,
+
+    /// Merkle 根（十六進制）
+    pub merkle_root: String,
+
+    /// 文件大小（bytes）
+    pub file_size: u64,
+
+    /// 葉子哈希的切片大小（bytes）
+    pub chunk_size: u32,
+
+    /// Merkle 葉子哈希（按 chunk 順序，用於重新執行挑戰抽樣）
+    pub leaf_hashes: Vec<[u8; 32]>,
+}
+
+/// 內容緩存後端
+pub trait ContentCache: Send + Sync {
+    /// 查詢 Blob 的緩存條目（命中時更新其最近使用時間）
+    fn get(&self, blob_id: &str) -> Option<CachedContent>;
+
+    /// 寫入或替換 Blob 的緩存條目
+    fn put(&self, blob_id: &str, content: CachedContent);
+
+    /// 使 Blob 的緩存條目失效
+    fn remove(&self, blob_id: &str);
+}
+
+/// LRU 狀態：條目與其最近使用序號
+#[derive(Debug, Default)]
+struct LruEntries {
+    entries: HashMap<String, (u64, CachedContent)>,
+    tick: u64,
+}
+
+impl LruEntries {
+    fn next_tick(&mut self) -> u64 {
+        self.tick += 1;
+        self.tick
+    }
+
+    fn get(&mut self, blob_id: &str) -> Option<CachedContent> {
+        let tick = self.next_tick();
+        let (used, content) = self.entries.get_mut(blob_id)?;
+        *used = tick;
+        Some(content.clone())
+    }
+
+    /// 寫入條目，返回被淘汰的 Blob ID
+    fn put(&mut self, blob_id: &str, content: CachedContent, capacity: usize) -> Vec<String> {
+        let tick = self.next_tick();
+        self.entries.insert(blob_id.to_string(), (tick, content));
+
+        let mut evicted = Vec::new();
+        while self.entries.len() > capacity {
+            let oldest = self
+                .entries
+                .iter()
+                .min_by_key(|(_, (used, _))| *used)
+                .map(|(id, _)| id.clone());
+            match oldest {
+                Some(id) => {
+                    self.entries.remove(&id);
+                    evicted.push(id);
+                }
+                None => break,
+            }
+        }
+        evicted
+    }
+
+    fn remove(&mut self, blob_id: &str) -> bool {
+        self.entries.remove(blob_id).is_some()
+    }
+
+    /// 按最近使用時間排序的條目（最久未使用的在前）
+    fn ordered(&self) -> Vec<(String, CachedContent)> {
+        let mut entries: Vec<_> = self.entries.iter().collect();
+        entries.sort_by_key(|(_, (used, _))| *used);
+        entries
+            .into_iter()
+            .map(|(id, (_, content))| (id.clone(), content.clone()))
+            .collect()
+    }
+}
+
+/// 內存中的 LRU 內容緩存
+#[derive(Debug)]
+pub struct MemoryContentCache {
+    capacity: usize,
+    lru: Mutex<LruEntries>,
+}
+
+impl MemoryContentCache {
+    /// 最多緩存 `max_entries` 個 Blob（為 0 時按 1 處理）
+    pub fn new(max_entries: usize) -> Self {
+        Self {
+            capacity: max_entries.max(1),
+            lru: Mutex::new(LruEntries::default()),
+        }
+    }
+
+    /// 緩存的 Blob 數
+    pub fn len(&self) -> usize {
+        self.lru.lock().unwrap().entries.len()
+    }
+
+    /// 緩存是否為空
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl ContentCache for MemoryContentCache {
+    fn get(&self, blob_id: &str) -> Option<CachedContent> {
+        self.lru.lock().unwrap().get(blob_id)
+    }
+
+    fn put(&self, blob_id: &str, content: CachedContent) {
+        let evicted = self
+            .lru
+            .lock()
+            .unwrap()
+            .put(blob_id, content, self.capacity);
+        for id in evicted {
+            debug!("Evicted blob {} from content cache", id);
+        }
+    }
+
+    fn remove(&self, blob_id: &str) {
+        self.lru.lock().unwrap().remove(blob_id);
+    }
+}
+
+/// 持久化的緩存條目
+#[derive(Serialize, Deserialize)]
+struct FileEntry {
+    blob_id: String,
+    content: CachedContent,
+}
+
+/// 持久化到文件的 LRU 內容緩存
+///
+/// 文件為 bincode 編碼的條目列表（最久未使用的在前），每次寫入或失效後整體重寫；
+/// 無法解碼的文件視為空緩存
+#[derive(Debug)]
+pub struct FileContentCache {
+    path: PathBuf,
+    capacity: usize,
+    lru: Mutex<LruEntries>,
+}
+
+impl FileContentCache {
+    /// 打開（或創建）緩存文件，最多緩存 `max_entries` 個 Blob（為 0 時按 1 處理）
+    pub fn open(path: impl AsRef<Path>, max_entries: usize) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)?;
+        }
+
+        let capacity = max_entries.max(1);
+        let mut lru = LruEntries::default();
+        match fs::read(&path) {
+            Ok(bytes) => match bincode::deserialize::<Vec<FileEntry>>(&bytes) {
+                Ok(entries) => {
+                    for entry in entries {
+                        lru.put(&entry.blob_id, entry.content, capacity);
+                    }
+                }
+                Err(e) => warn!(
+                    "Ignoring undecodable content cache {}: {}",
+                    path.display(),
+                    e
+                ),
+            },
+            Err(e) if e.kind() == ErrorKind::NotFound => {}
+            Err(e) => return Err(e.into()),
+        }
+
+        Ok(Self {
+            path,
+            capacity,
+            lru: Mutex::new(lru),
+        })
+    }
+
+    /// 緩存的 Blob 數
+    pub fn len(&self) -> usize {
+        self.lru.lock().unwrap().entries.len()
+    }
+
+    /// 緩存是否為空
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// 整體重寫緩存文件（先寫臨時文件再重命名）
+    fn save(&self, lru: &LruEntries) -> Result<()> {
+        let entries: Vec<FileEntry> = lru
+            .ordered()
+            .into_iter()
+            .map(|(blob_id, content)| FileEntry { blob_id, content })
+            .collect();
+        let bytes = bincode::serialize(&entries)?;
+        let tmp_path = self.path.with_extension("tmp");
+        fs::write(&tmp_path, bytes)?;
+        fs::rename(&tmp_path, &self.path)?;
+        Ok(())
+    }
+
+    fn save_or_warn(&self, lru: &LruEntries) {
+        if let Err(e) = self.save(lru) {
+            warn!(
+                "Failed to write content cache {}: {}",
+                self.path.display(),
+                e
+            );
+        }
+    }
+}
+
+impl ContentCache for FileContentCache {
+    fn get(&self, blob_id: &str) -> Option<CachedContent> {
+        // 最近使用順序只在下次寫入時持久化
+        self.lru.lock().unwrap().get(blob_id)
+    }
+
+    fn put(&self, blob_id: &str, content: CachedContent) {
+        let mut lru = self.lru.lock().unwrap();
+        for id in lru.put(blob_id, content, self.capacity) {
+            debug!("Evicted blob {} from content cache", id);
+        }
+        self.save_or_warn(&lru);
+    }
+
+    fn remove(&self, blob_id: &str) {
+        let mut lru = self.lru.lock().unwrap();
+        if lru.remove(blob_id) {
+            self.save_or_warn(&lru);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn content(etag: &str) -> CachedContent {
+        CachedContent {
+            etag: etag.to_string(),
+            content_hash: "aa".repeat(32),
+            merkle_root: "bb".repeat(32),
+            file_size: 4096,
+            chunk_size: 4096,
+            leaf_hashes: vec![[7u8; 32]],
+        }
+    }
+
+    #[test]
+    fn test_memory_cache_evicts_least_recently_used() {
+        let cache = MemoryContentCache::new(2);
+        cache.put("a", content("\"a\""));
+        cache.put("b", content("\"b\""));
+
+        // 訪問 a 後，b 成為最久未使用的條目
+        assert!(cache.get("a").is_some());
+        cache.put("c", content("\"c\""));
+
+        assert_eq!(cache.len(), 2);
+        assert!(cache.get("b").is_none());
+        assert_eq!(cache.get("a").unwrap().etag, "\"a\"");
+        assert_eq!(cache.get("c").unwrap().etag, "\"c\"");
+
+        cache.remove("a");
+        assert!(cache.get("a").is_none());
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    fn test_file_cache_persists_entries_and_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("cache").join("content_cache.bin");
+
+        let cache = FileContentCache::open(&path, 2).unwrap();
+        assert!(cache.is_empty());
+        cache.put("a", content("\"a\""));
+        cache.put("b", content("\"b\""));
+        cache.get("a");
+        cache.put("a", content("\"a2\""));
+        drop(cache);
+
+        // 重新打開後 b 仍是最久未使用的條目
+        let reopened = FileContentCache::open(&path, 2).unwrap();
+        assert_eq!(reopened.len(), 2);
+        assert_eq!(reopened.get("a").unwrap().etag, "\"a2\"");
+        reopened.put("c", content("\"c\""));
+        assert!(reopened.get("b").is_none());
+
+        reopened.remove("c");
+        let reopened = FileContentCache::open(&path, 2).unwrap();
+        assert_eq!(reopened.len(), 1);
+        assert!(reopened.get("c").is_none());
+    }
+
+    #[test]
+    fn test_file_cache_ignores_undecodable_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("content_cache.bin");
+        fs::write(&path, b"not bincode").unwrap();
+
+        let cache = FileContentCache::open(&path, 4).unwrap();
+        assert!(cache.is_empty());
+    }
+}
@@ -0,0 +1,460 @@
+//! 多審計員聯署
+//!
+//! 高價值 Blob 有時需要兩名獨立審計員證明同一結果。流程：
+//!
+//! 1. 主審計員簽名報告後導出 [`CosignRequest`]（報告、規範簽名字節 + SHA-256 摘要）
+//! 2. 第二名審計員核對摘要、（可選）重新審計並比對結論，再用自己的密鑰簽名，返回 [`Cosignature`]
+//! 3. 主審計員將聯署附加到報告的 `cosignatures` 字段（[`attach`]），得到多簽名報告
+//! 4. 驗證者用 [`ReportManager::verify_report_with_cosignatures`] 按信任庫中登記的公鑰
+//!    逐一核對聯署，並要求有效簽名數不少於 `min_signatures`
+//!
+//! 聯署覆蓋的字節與主簽名相同（[`ReportManager::signing_payload`]，不含任何簽名），
+//! 因此聯署的先後順序不影響驗證。
+//!
+//! [`ReportManager::verify_report_with_cosignatures`]: crate::report::ReportManager::verify_report_with_cosignatures
+//! [`ReportManager::signing_payload`]: crate::report::ReportManager::signing_payload
+
+use crate::audit_report::PqcAlgorithm;
+use crate::crypto::{ct_eq, ct_eq_hex};
+use crate::error::{AuditorError, Result};
+use crate::report::ReportManager;
+use crate::trust::{self, TrustEntry};
+use crate::types::AuditReport;
+use base64::{engine::general_purpose, Engine as _};
+use pqc_signer::{Dilithium3Signer, Signer};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tracing::{debug, info};
+
+/// 聯署使用的 PQC 算法（Dilithium3）
+pub const COSIGN_ALGORITHM: u8 = 3;
+
+/// 聯署請求（由主審計員導出）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CosignRequest {
+    /// 被審計的 Blob ID
+    pub blob_id: String,
+
+    /// 主審計員地址
+    pub primary_auditor: String,
+
+    /// 規範化簽名字節（base64）
+    pub payload: String,
+
+    /// 簽名字節的 SHA-256 摘要（十六進制）
+    pub report_digest: String,
+
+    /// 被聯署的報告（其簽名字節必須與 `payload` 一致）
+    ///
+    /// 簽名字節改為規範編碼之前導出的請求沒有此字段，報告直接從 JSON 格式的 `payload` 解析
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub report: Option<AuditReport>,
+}
+
+impl CosignRequest {
+    /// 由已簽名的報告創建聯署請求
+    ///
+    /// # 錯誤
+    /// - 報告尚未由主審計員簽名: 返回 `Cosign` 錯誤
+    /// - 報告仍使用舊版簽名封套: 返回 `Cosign` 錯誤
+    pub fn from_report(report: &AuditReport) -> Result<Self> {
+        if report.pqc_signature.is_empty() {
+            return Err(AuditorError::Cosign(format!(
+                "Report for blob {} must be signed by its primary auditor before co-signing",
+                report.blob_id
+            )));
+        }
+        if report.legacy_envelope.is_some() {
+            return Err(AuditorError::Cosign(format!(
+                "Report for blob {} uses the legacy signed envelope; re-sign it before co-signing",
+                report.blob_id
+            )));
+        }
+
+        let payload = ReportManager::signing_payload(report)?;
+        Ok(Self {
+            blob_id: report.blob_id.clone(),
+            primary_auditor: report.auditor.clone(),
+            payload: general_purpose::STANDARD.encode(&payload),
+            report_digest: payload_digest(&payload),
+            report: Some(report.clone()),
+        })
+    }
+
+    /// 解碼簽名字節並核對摘要
+    pub fn payload_bytes(&self) -> Result<Vec<u8>> {
+        let payload = general_purpose::STANDARD
+            .decode(&self.payload)
+            .map_err(|e| AuditorError::Cosign(format!("Invalid payload base64: {}", e)))?;
+
+        let digest = payload_digest(&payload);
+        if !ct_eq_hex(&digest, &self.report_digest)? {
+            return Err(AuditorError::Cosign(format!(
+                "Payload digest {} does not match report digest {}",
+                digest, self.report_digest
+            )));
+        }
+
+        Ok(payload)
+    }
+
+    /// 取出被聯署的報告（供聯署人獨立核對結論）
+    ///
+    /// 報告的簽名字節必須與 `payload` 一致，Blob 與主審計員必須與請求聲明的一致
+    pub fn report(&self) -> Result<AuditReport> {
+        let payload = self.payload_bytes()?;
+        let report: AuditReport = match &self.report {
+            Some(report) => {
+                if !ct_eq(&ReportManager::signing_payload(report)?, &payload) {
+                    return Err(AuditorError::Cosign(format!(
+                        "Payload does not match the signing bytes of the enclosed report for blob {}",
+                        report.blob_id
+                    )));
+                }
+                report.clone()
+            }
+            None => serde_json::from_slice(&payload)?,
+        };
+        if report.blob_id != self.blob_id || report.auditor != self.primary_auditor {
+            return Err(AuditorError::Cosign(format!(
+                "Payload is a report for blob {} by {}, but the request names blob {} by {}",
+                report.blob_id, report.auditor, self.blob_id, self.primary_auditor
+            )));
+        }
+        Ok(report)
+    }
+}
+
+/// 聯署簽名
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Cosignature {
+    /// 聯署審計員地址
+    pub auditor: String,
+
+    /// 聯署公鑰指紋（SHA-256 前 8 字節，hex）
+    pub key_id: String,
+
+    /// Dilithium3 簽名
+    pub signature: Vec<u8>,
+}
+
+/// 簽名字節的 SHA-256 摘要（十六進制）
+pub fn payload_digest(payload: &[u8]) -> String {
+    hex::encode(Sha256::digest(payload))
+}
+
+/// 聯署：核對請求摘要後用聯署人的密鑰簽名
+///
+/// # 錯誤
+/// - 摘要不符或報告與請求不一致: 返回 `Cosign` 錯誤
+/// - 聯署人就是主審計員，或密鑰不是 [`COSIGN_ALGORITHM`]: 返回 `Cosign` 錯誤
+/// - 簽名失敗: 返回 `PqcSignature` 錯誤
+pub fn cosign(request: &CosignRequest, auditor: &str, signer: &dyn Signer) -> Result<Cosignature> {
+    if auditor == request.primary_auditor {
+        return Err(AuditorError::Cosign(format!(
+            "Auditor {} is the primary auditor and cannot co-sign its own report",
+            auditor
+        )));
+    }
+    if PqcAlgorithm::from_name(signer.algorithm_name()).map(|algorithm| algorithm.id())
+        != Some(COSIGN_ALGORITHM)
+    {
+        return Err(AuditorError::Cosign(format!(
+            "Co-signing requires a Dilithium3 key, but {} uses {}",
+            auditor,
+            signer.algorithm_name()
+        )));
+    }
+
+    let payload = request.payload_bytes()?;
+    request.report()?;
+
+    let signature = signer
+        .sign(&payload)
+        .map_err(|e| AuditorError::PqcSignature(format!("Co-signing failed: {}", e)))?;
+
+    info!(
+        "Co-signed report for blob {} (digest {}) as {}",
+        request.blob_id, request.report_digest, auditor
+    );
+
+    Ok(Cosignature {
+        auditor: auditor.to_string(),
+        key_id: trust::key_id(signer.public_key()),
+        signature,
+    })
+}
+
+/// 將聯署附加到報告
+///
+/// # 錯誤
+/// - 聯署人是主審計員或已聯署過: 返回 `Cosign` 錯誤
+pub fn attach(report: &mut AuditReport, cosignature: Cosignature) -> Result<()> {
+    if cosignature.auditor == report.auditor {
+        return Err(AuditorError::Cosign(format!(
+            "Auditor {} is the primary auditor of this report",
+            cosignature.auditor
+        )));
+    }
+    if report
+        .cosignatures
+        .iter()
+        .any(|existing| existing.auditor == cosignature.auditor)
+    {
+        return Err(AuditorError::Cosign(format!(
+            "Auditor {} has already co-signed this report",
+            cosignature.auditor
+        )));
+    }
+
+    debug!(
+        "Attaching cosignature from {} ({}) to report for blob {}",
+        cosignature.auditor, cosignature.key_id, report.blob_id
+    );
+    report.cosignatures.push(cosignature);
+    Ok(())
+}
+
+/// 按信任庫條目核對單個聯署
+///
+/// # 返回
+/// - `Ok(true)`: 簽名有效
+/// - `Ok(false)`: 簽名無效（負載被篡改或簽名錯誤）
+///
+/// # 錯誤
+/// - 聯署的公鑰指紋或算法與登記不符: 返回 `Trust` 錯誤
+pub fn verify_cosignature(
+    payload: &[u8],
+    cosignature: &Cosignature,
+    entry: &TrustEntry,
+) -> Result<bool> {
+    if entry.algorithm != COSIGN_ALGORITHM || entry.key_id != cosignature.key_id {
+        return Err(AuditorError::Trust(format!(
+            "Co-signer {} signed with key {} but trust store has {} (algorithm {})",
+            cosignature.auditor, cosignature.key_id, entry.key_id, entry.algorithm
+        )));
+    }
+
+    let verifier = Dilithium3Signer::from_public_key_only(&entry.public_key_bytes()?)
+        .map_err(|e| AuditorError::PqcSignature(format!("Invalid public key: {}", e)))?;
+
+    verifier
+        .verify(payload, &cosignature.signature)
+        .map_err(|e| AuditorError::PqcSignature(format!("Verification failed: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::trust::TrustStore;
+    use crate::types::AuditReport;
+
+    fn keypair() -> Dilithium3Signer {
+        let mut signer = Dilithium3Signer::new();
+        signer.generate_keypair().unwrap();
+        signer
+    }
+
+    fn signed_report(primary: &Dilithium3Signer) -> AuditReport {
+        let mut report: AuditReport = serde_json::from_value(serde_json::json!({
+            "blob_id": "0xhigh_value_blob",
+            "blob_object_id": "0xobject",
+            "auditor": "0xprimary",
+            "timestamp": 1_700_000_000u64,
+            "challenge_epoch": 7,
+            "challenge_results": [],
+            "total_challenges": 10,
+            "successful_verifications": 10,
+            "failed_verifications": 0,
+            "integrity_hash": vec![0u8; 32],
+            "pqc_signature": [],
+            "pqc_algorithm": 0,
+            "is_valid": true,
+            "failure_reason": null,
+        }))
+        .unwrap();
+
+        let manager =
+            ReportManager::from_keypair(primary.public_key(), primary.secret_key()).unwrap();
+        manager.sign_report(&mut report).unwrap();
+        report
+    }
+
+    fn trust_store(cosigners: &[(&str, &Dilithium3Signer)]) -> TrustStore {
+        let mut store = TrustStore::new();
+        for (address, signer) in cosigners {
+            store
+                .add(address, signer.public_key(), COSIGN_ALGORITHM, None)
+                .unwrap();
+        }
+        store
+    }
+
+    #[test]
+    fn test_request_roundtrip() {
+        let primary = keypair();
+        let report = signed_report(&primary);
+
+        let request = CosignRequest::from_report(&report).unwrap();
+        assert_eq!(request.blob_id, report.blob_id);
+        assert_eq!(request.primary_auditor, "0xprimary");
+        assert_eq!(
+            request.payload_bytes().unwrap(),
+            ReportManager::signing_payload(&report).unwrap()
+        );
+        assert!(request.report().unwrap().is_valid);
+
+        // 未簽名的報告不能請求聯署
+        let mut unsigned = report.clone();
+        unsigned.pqc_signature.clear();
+        assert!(CosignRequest::from_report(&unsigned).is_err());
+    }
+
+    #[test]
+    fn test_payload_digest_is_compared_as_hex() {
+        let report = signed_report(&keypair());
+        let mut request = CosignRequest::from_report(&report).unwrap();
+
+        request.report_digest = request.report_digest.to_uppercase();
+        assert!(request.payload_bytes().is_ok());
+
+        request.report_digest = "00".repeat(32);
+        assert!(matches!(
+            request.payload_bytes(),
+            Err(AuditorError::Cosign(_))
+        ));
+
+        request.report_digest = "not a digest".to_string();
+        assert!(matches!(
+            request.payload_bytes(),
+            Err(AuditorError::InvalidDigest(_))
+        ));
+    }
+
+    #[test]
+    fn test_one_and_two_signatures() {
+        let primary = keypair();
+        let second = keypair();
+        let mut report = signed_report(&primary);
+        let store = trust_store(&[("0xsecond", &second)]);
+
+        // 只有主簽名
+        assert!(ReportManager::verify_report_with_cosignatures(
+            &report,
+            primary.public_key(),
+            &store,
+            1
+        )
+        .unwrap());
+        assert!(!ReportManager::verify_report_with_cosignatures(
+            &report,
+            primary.public_key(),
+            &store,
+            2
+        )
+        .unwrap());
+
+        // 附加聯署後主簽名仍然有效
+        let request = CosignRequest::from_report(&report).unwrap();
+        attach(&mut report, cosign(&request, "0xsecond", &second).unwrap()).unwrap();
+        assert!(ReportManager::verify_report(&report, primary.public_key()).unwrap());
+        assert!(ReportManager::verify_report_with_cosignatures(
+            &report,
+            primary.public_key(),
+            &store,
+            2
+        )
+        .unwrap());
+        assert!(!ReportManager::verify_report_with_cosignatures(
+            &report,
+            primary.public_key(),
+            &store,
+            3
+        )
+        .unwrap());
+
+        // 同一聯署人不能重複計數
+        let duplicate = report.cosignatures[0].clone();
+        assert!(attach(&mut report, duplicate).is_err());
+    }
+
+    #[test]
+    fn test_cosignature_over_tampered_payload_is_rejected() {
+        let primary = keypair();
+        let second = keypair();
+        let mut report = signed_report(&primary);
+        let store = trust_store(&[("0xsecond", &second)]);
+
+        // 負載被篡改後摘要不符，聯署人拒絕簽名
+        let mut request = CosignRequest::from_report(&report).unwrap();
+        let mut tampered_report: AuditReport = request.report().unwrap();
+        tampered_report.is_valid = false;
+        let tampered = tampered_report.signing_bytes();
+        request.payload = general_purpose::STANDARD.encode(&tampered);
+        let err = cosign(&request, "0xsecond", &second).unwrap_err();
+        assert!(err.to_string().contains("does not match report digest"));
+
+        // 摘要一併篡改：負載與請求中的報告不符
+        request.report_digest = payload_digest(&tampered);
+        let err = cosign(&request, "0xsecond", &second).unwrap_err();
+        assert!(err.to_string().contains("does not match the signing bytes"));
+
+        // 報告也一併替換：聯署覆蓋的不是原報告的字節，驗證失敗
+        request.report = Some(tampered_report);
+        attach(&mut report, cosign(&request, "0xsecond", &second).unwrap()).unwrap();
+        assert!(!ReportManager::verify_report_with_cosignatures(
+            &report,
+            primary.public_key(),
+            &store,
+            2
+        )
+        .unwrap());
+    }
+
+    #[test]
+    fn test_cosigner_must_match_trust_store() {
+        let primary = keypair();
+        let second = keypair();
+        let impostor = keypair();
+        let mut report = signed_report(&primary);
+
+        let request = CosignRequest::from_report(&report).unwrap();
+        attach(
+            &mut report,
+            cosign(&request, "0xsecond", &impostor).unwrap(),
+        )
+        .unwrap();
+
+        // 未登記的聯署人
+        let err = ReportManager::verify_report_with_cosignatures(
+            &report,
+            primary.public_key(),
+            &TrustStore::new(),
+            2,
+        )
+        .unwrap_err();
+        assert!(matches!(err, AuditorError::Trust(_)));
+
+        // 登記的公鑰不同
+        let store = trust_store(&[("0xsecond", &second)]);
+        let err = ReportManager::verify_report_with_cosignatures(
+            &report,
+            primary.public_key(),
+            &store,
+            2,
+        )
+        .unwrap_err();
+        assert!(matches!(err, AuditorError::Trust(_)));
+
+        // 主審計員不能聯署自己的報告
+        assert!(cosign(&request, "0xprimary", &second).is_err());
+
+        // 非 Dilithium3 密鑰不能聯署
+        let mut falcon = pqc_signer::Falcon512Signer::new();
+        falcon.generate_keypair().unwrap();
+        assert!(matches!(
+            cosign(&request, "0xsecond", &falcon),
+            Err(AuditorError::Cosign(_))
+        ));
+    }
+}
@@ -0,0 +1,77 @@
+//! 常量時間比較
+//!
+//! 以 `==` 比較字節切片或字符串時，遇到第一個不同的字節即返回，
+//! 耗時洩露了兩者前綴相同的長度。審計結論依賴的摘要、默克爾根與簽名字節
+//! 都應以 [`ct_eq`] 比較（基於 `subtle`），使耗時只取決於長度。
+//!
+//! 十六進制摘要先解碼為字節再比較（[`ct_eq_hex`]）：大小寫不同的同一摘要視為相等，
+//! 無法解碼的輸入返回 [`AuditorError::InvalidDigest`]，不再退化為字符串比較。
+//!
+//! 長度不同時直接返回 `false`：摘要長度是公開的，不需要隱藏。
+
+use crate::error::{AuditorError, Result};
+use subtle::ConstantTimeEq;
+
+/// 以常量時間比較兩段字節
+pub fn ct_eq(a: &[u8], b: &[u8]) -> bool {
+    a.ct_eq(b).into()
+}
+
+/// 解碼十六進制摘要（允許 `0x` 前綴與大寫字母）
+///
+/// # 錯誤
+/// 輸入不是合法的十六進制時返回 [`AuditorError::InvalidDigest`]
+pub fn decode_hex_digest(digest: &str) -> Result<Vec<u8>> {
+    let hex_digits = digest.strip_prefix("0x").unwrap_or(digest);
+    hex::decode(hex_digits).map_err(|e| AuditorError::InvalidDigest(format!("{:?}: {}", digest, e)))
+}
+
+/// 解碼兩個十六進制摘要後以常量時間比較
+///
+/// # 錯誤
+/// 任一方不是合法的十六進制時返回 [`AuditorError::InvalidDigest`]
+pub fn ct_eq_hex(a: &str, b: &str) -> Result<bool> {
+    Ok(ct_eq(&decode_hex_digest(a)?, &decode_hex_digest(b)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ct_eq_matches_slice_equality() {
+        let cases: [(&[u8], &[u8]); 5] = [
+            (&[], &[]),
+            (&[1, 2, 3], &[1, 2, 3]),
+            (&[1, 2, 3], &[1, 2, 4]),
+            (&[1, 2, 3], &[1, 2]),
+            (&[0; 32], &[0xff; 32]),
+        ];
+        for (a, b) in cases {
+            assert_eq!(ct_eq(a, b), a == b, "{:?} vs {:?}", a, b);
+        }
+    }
+
+    #[test]
+    fn test_ct_eq_hex_decodes_before_comparing() {
+        let digest = hex::encode([0xab; 32]);
+
+        assert!(ct_eq_hex(&digest, &digest).unwrap());
+        assert!(ct_eq_hex(&digest, &digest.to_ascii_uppercase()).unwrap());
+        assert!(ct_eq_hex(&format!("0x{}", digest), &digest).unwrap());
+        assert!(!ct_eq_hex(&digest, &hex::encode([0xac; 32])).unwrap());
+        assert!(!ct_eq_hex(&digest, &digest[..62]).unwrap());
+    }
+
+    #[test]
+    fn test_ct_eq_hex_rejects_invalid_hex() {
+        let digest = hex::encode([0xab; 32]);
+
+        for invalid in ["not a hash", "abc", "0xzz"] {
+            assert!(matches!(
+                ct_eq_hex(&digest, invalid),
+                Err(AuditorError::InvalidDigest(_))
+            ));
+        }
+    }
+}
@@ -25,9 +25,47 @@
 //!    - 如果是 1,當前節點在右邊: `current = H(sibling || current)`
 //!    - 索引右移一位: `index >>= 1`
 //! 3. 比較最終計算出的根與提供的根是否相等
+//!
+//! 路徑長度在計算任何哈希之前先行檢查：超過 [`MAX_PROOF_DEPTH`] 或超過
+//! `leaf_count` 對應樹高（[`tree_depth`]）的證明直接拒絕，惡意節點無法以
+//! 數千個兄弟節點的超長路徑消耗 CPU，也無法以超出樹高的索引位偽造證明。
+//!
+//! # 奇數節點處理與樹格式版本
+//!
+//! 舊版格式（[`MerkleTreeVersion::Legacy`]）將某層最後一個未配對節點與自身配對
+//! （`H(node || node)`）。這會導致經典的默克爾歧義：N 個葉子的樹與「末葉重複一次」
+//! 的 N+1 個葉子的樹產生相同的根，惡意節點可藉此回應一個本不存在的葉子索引的挑戰。
+//!
+//! 當前格式（[`MerkleTreeVersion::V2`]）將未配對節點原樣提升到上一層，不再消耗
+//! 證明路徑中的兄弟節點；驗證時必須提供預期的葉子總數，超出範圍的索引一律拒絕。
+//! 舊版格式仍可通過 [`MerkleProof::verify_with_version`] 驗證歷史根。
+//!
+//! # 批量證明
+//!
+//! 同一棵樹的多個葉子可用一個 [`MerkleMultiProof`] 一起證明：被證明葉子能自行推出的
+//! 內部節點不再傳輸，共同祖先之上的路徑只出現一次。僅支持 V2 格式。
+//!
+//! # 字節範圍證明
+//!
+//! 由 blob 數據構建的樹記錄切片大小與數據長度，可用 [`MerkleTree::prove_range`]
+//! 證明任意字節範圍（如 blob 中內嵌的文檔）屬於該 blob：證明包含覆蓋範圍的每個 chunk 的
+//! 證明與這些 chunk 的邊界。驗證方提供覆蓋 chunk 的完整數據（首尾 chunk 含範圍外的字節，
+//! blob 末尾的 chunk 可不滿），由 [`verify_range`] 逐個重算 chunk 哈希並驗證。僅支持 V2 格式。
+//!
+//! # 並行構建
+//!
+//! 啟用 `parallel` feature（默認）時，[`MerkleTree::from_blob`] 以 rayon 並行計算葉子哈希，
+//! [`MerkleTree::from_leaf_hashes`] 並行計算每一層的節點對；節點數少於
+//! `PARALLEL_MIN_NODES` 的層仍串行計算。並行只改變計算順序，樹的形狀與根與串行實現完全相同。
 
+use super::ct::ct_eq;
 use serde::{Deserialize, Serialize};
 use fastcrypto::hash::{Blake2b256, HashFunction};
+use std::io::Read;
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
 
 /// Walrus 官方默克爾樹哈希前綴常量
 ///
@@ -39,6 +77,27 @@ use fastcrypto::hash::{Blake2b256, HashFunction};
 const LEAF_PREFIX: [u8; 1] = [0];
 const INNER_PREFIX: [u8; 1] = [1];
 
+/// 證明路徑長度上限
+///
+/// `leaf_index` 為 u64，任何合法的樹高都不超過 64 層
+pub const MAX_PROOF_DEPTH: usize = 64;
+
+/// [`MerkleMultiProof::from_bytes`] 接受的最大輸入（bytes）
+///
+/// 反序列化以此為上限，聲明的長度超出時在分配之前拒絕
+pub const MAX_MULTI_PROOF_BYTES: u64 = 64 * 1024 * 1024;
+
+/// `leaf_count` 個葉子的樹高（`ceil(log2(leaf_count))`，單葉子或空樹為 0）
+///
+/// 舊版格式的證明路徑長度恰為樹高；V2 格式中未配對節點不消耗兄弟節點，路徑可能更短
+pub fn tree_depth(leaf_count: u64) -> usize {
+    if leaf_count <= 1 {
+        0
+    } else {
+        (u64::BITS - (leaf_count - 1).leading_zeros()) as usize
+    }
+}
+
 /// 默克爾證明路徑
 ///
 /// # 示例
@@ -77,16 +136,32 @@ pub struct MerkleProof {
 /// 默克爾根類型別名
 pub type MerkleRoot = [u8; 32];
 
+/// 默克爾樹格式版本
+///
+/// 決定某層節點數為奇數時最後一個節點的處理方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum MerkleTreeVersion {
+    /// 舊版格式：未配對節點與自身配對 `H(node || node)`
+    ///
+    /// 存在重複末葉歧義，僅用於驗證歷史根
+    Legacy,
+
+    /// V2 格式：未配對節點原樣提升到上一層
+    #[default]
+    V2,
+}
+
 impl MerkleProof {
-    /// 驗證葉子節點確實屬於該默克爾樹
+    /// 驗證葉子節點確實屬於該默克爾樹（V2 格式）
     ///
     /// # 參數
     /// - `leaf_data`: 原始葉子數據（例如 Walrus sliver 數據）
     /// - `root`: 聲稱的默克爾根（從區塊鏈獲取）
+    /// - `leaf_count`: 預期的葉子總數，`leaf_index` 必須小於該值
     ///
     /// # 返回
     /// - `true`: 驗證通過,葉子數據確實屬於該樹
-    /// - `false`: 驗證失敗,數據被篡改或證明無效
+    /// - `false`: 驗證失敗,數據被篡改、證明無效或索引超出範圍
     ///
     /// # 示例
     ///
@@ -108,32 +183,121 @@ impl MerkleProof {
     ///     leaf_index: 0,  // leaf0 的索引
     /// };
     ///
-    /// assert!(proof.verify(b"leaf0", &root));
-    /// assert!(!proof.verify(b"wrong_data", &root));
+    /// assert!(proof.verify(b"leaf0", &root, 4));
+    /// assert!(!proof.verify(b"wrong_data", &root, 4));
     /// ```
-    pub fn verify(&self, leaf_data: &[u8], root: &MerkleRoot) -> bool {
+    pub fn verify(&self, leaf_data: &[u8], root: &MerkleRoot, leaf_count: u64) -> bool {
+        self.verify_with_version(leaf_data, root, leaf_count, MerkleTreeVersion::V2)
+    }
+
+    /// 按指定的樹格式版本驗證葉子節點
+    ///
+    /// 用於驗證以 [`MerkleTreeVersion::Legacy`] 格式生成的歷史根。
+    /// 兩種格式都會拒絕 `leaf_index >= leaf_count` 的證明，
+    /// 以及路徑長度與葉子總數不符（含超過 [`MAX_PROOF_DEPTH`]）的證明。
+    pub fn verify_with_version(
+        &self,
+        leaf_data: &[u8],
+        root: &MerkleRoot,
+        leaf_count: u64,
+        version: MerkleTreeVersion,
+    ) -> bool {
         // 1. 計算葉子節點哈希
-        let mut current_hash = hash_leaf(leaf_data);
+        self.verify_leaf_hash_with_version(&hash_leaf(leaf_data), root, leaf_count, version)
+    }
+
+    /// 以已計算的葉子哈希驗證證明（V2 格式）
+    ///
+    /// 流式構建的樹不保留原始數據，可用 [`MerkleTree::leaf_hashes`] 中的哈希驗證；
+    /// 以 sliver 哈希為葉子的樹也經此驗證（見 [`LeafEncoding`](crate::crypto::sliver::LeafEncoding)）
+    pub fn verify_leaf_hash(
+        &self,
+        leaf_hash: &[u8; 32],
+        root: &MerkleRoot,
+        leaf_count: u64,
+    ) -> bool {
+        self.verify_leaf_hash_with_version(leaf_hash, root, leaf_count, MerkleTreeVersion::V2)
+    }
+
+    /// 按指定的樹格式版本以葉子哈希驗證證明
+    pub fn verify_leaf_hash_with_version(
+        &self,
+        leaf_hash: &[u8; 32],
+        root: &MerkleRoot,
+        leaf_count: u64,
+        version: MerkleTreeVersion,
+    ) -> bool {
+        // 3. 比較計算出的根與提供的根
+        self.root_from_leaf_hash_with_version(leaf_hash, leaf_count, version)
+            .is_some_and(|computed| ct_eq(&computed, root))
+    }
+
+    /// 由葉子哈希沿證明路徑計算根（V2 格式）
+    ///
+    /// 用於驗證嵌套的樹：子樹的根不是已知值，而是外層樹證明中的葉子哈希
+    /// （見 [`RecoverySymbol::verify`](crate::crypto::sliver::RecoverySymbol::verify)）。
+    /// 索引超出範圍或路徑長度與葉子總數不符時返回 `None`
+    pub fn root_from_leaf_hash(&self, leaf_hash: &[u8; 32], leaf_count: u64) -> Option<MerkleRoot> {
+        self.root_from_leaf_hash_with_version(leaf_hash, leaf_count, MerkleTreeVersion::V2)
+    }
+
+    /// 按指定的樹格式版本由葉子哈希計算根
+    pub fn root_from_leaf_hash_with_version(
+        &self,
+        leaf_hash: &[u8; 32],
+        leaf_count: u64,
+        version: MerkleTreeVersion,
+    ) -> Option<MerkleRoot> {
+        if self.leaf_index >= leaf_count {
+            return None;
+        }
+
+        // 超長路徑在計算哈希之前拒絕
+        if self.path.len() > MAX_PROOF_DEPTH || self.path.len() > tree_depth(leaf_count) {
+            return None;
+        }
+
+        let mut current_hash = *leaf_hash;
 
         // 2. 使用證明路徑逐層向上計算
         let mut index = self.leaf_index;
+        let mut width = leaf_count;
+        let mut siblings = self.path.iter();
 
-        for sibling in &self.path {
-            // 根據索引的二進制位確定當前節點位置
-            if index & 1 == 0 {
-                // 當前節點在左邊
-                current_hash = hash_node(&current_hash, sibling);
+        while width > 1 {
+            let unpaired = index & 1 == 0 && index + 1 == width;
+
+            if unpaired && version == MerkleTreeVersion::V2 {
+                // 未配對節點原樣提升，不消耗兄弟節點
             } else {
-                // 當前節點在右邊
-                current_hash = hash_node(sibling, &current_hash);
+                let sibling = siblings.next()?;
+
+                if unpaired && !ct_eq(sibling, &current_hash) {
+                    // 舊版格式中未配對節點只能與自身配對
+                    return None;
+                }
+
+                // 根據索引的二進制位確定當前節點位置
+                if index & 1 == 0 {
+                    // 當前節點在左邊
+                    current_hash = hash_node(&current_hash, sibling);
+                } else {
+                    // 當前節點在右邊
+                    current_hash = hash_node(sibling, &current_hash);
+                }
             }
 
             // 移到父節點層
             index >>= 1;
+            width = width.div_ceil(2);
         }
 
-        // 3. 比較計算出的根與提供的根
-        &current_hash == root
+        // 路徑必須恰好用完
+        if siblings.next().is_some() {
+            return None;
+        }
+
+        Some(current_hash)
     }
 
     /// 從字節反序列化證明
@@ -143,6 +307,7 @@ impl MerkleProof {
     ///
     /// # 錯誤
     /// - 如果字節格式無效,返回 `Deserialization` 錯誤
+    /// - 路徑長度超過 [`MAX_PROOF_DEPTH`],返回 `Deserialization` 錯誤
     ///
     /// # 示例
     ///
@@ -160,9 +325,17 @@ impl MerkleProof {
     /// assert_eq!(original, restored);
     /// ```
     pub fn from_bytes(bytes: &[u8]) -> Result<Self, MerkleError> {
-        bincode::deserialize(bytes).map_err(|e| {
+        let proof: Self = bincode::deserialize(bytes).map_err(|e| {
             MerkleError::Deserialization(format!("Failed to deserialize MerkleProof: {}", e))
-        })
+        })?;
+        if proof.path.len() > MAX_PROOF_DEPTH {
+            return Err(MerkleError::Deserialization(format!(
+                "MerkleProof path has {} siblings (maximum {})",
+                proof.path.len(),
+                MAX_PROOF_DEPTH
+            )));
+        }
+        Ok(proof)
     }
 
     /// 序列化證明為字節
@@ -198,6 +371,263 @@ impl MerkleProof {
     }
 }
 
+/// 多葉子默克爾證明（V2 格式）
+///
+/// 一次證明同一棵樹中的一組葉子，共享公共的內部節點。
+/// 由 [`MerkleTree::generate_multi_proof`] 生成。
+///
+/// # 示例
+///
+/// ```
+/// use auditor_node::crypto::merkle::{MerkleMultiProof, MerkleTree};
+///
+/// let blob_data: Vec<u8> = (0..32 * 16).map(|i| i as u8).collect();
+/// let tree = MerkleTree::from_blob(&blob_data, 16).unwrap();
+///
+/// let proof = tree.generate_multi_proof(&[3, 17, 4]).unwrap();
+/// let restored = MerkleMultiProof::from_bytes(&proof.to_bytes()).unwrap();
+///
+/// let leaves: Vec<(usize, &[u8])> = [3, 4, 17]
+///     .iter()
+///     .map(|&i| (i, &blob_data[i * 16..(i + 1) * 16]))
+///     .collect();
+/// assert!(restored.verify(&leaves, &tree.root()));
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct MerkleMultiProof {
+    /// 被證明的葉子索引（升序、不重複）
+    pub leaf_indices: Vec<u64>,
+
+    /// 樹的葉子總數
+    ///
+    /// 決定每層哪個節點未配對；已知預期葉子數時，調用方應與之比對
+    pub leaf_count: u64,
+
+    /// 無法由被證明葉子推出的兄弟節點哈希
+    ///
+    /// 按層自下而上、層內按位置升序排列
+    pub nodes: Vec<[u8; 32]>,
+}
+
+impl MerkleMultiProof {
+    /// 驗證一組葉子數據都屬於該默克爾樹
+    ///
+    /// `leaves` 中的索引集合必須與 `leaf_indices` 完全一致（順序不限）
+    pub fn verify(&self, leaves: &[(usize, &[u8])], root: &MerkleRoot) -> bool {
+        let leaf_hashes: Vec<(usize, [u8; 32])> = leaves
+            .iter()
+            .map(|&(index, data)| (index, hash_leaf(data)))
+            .collect();
+        self.verify_leaf_hashes(&leaf_hashes, root)
+    }
+
+    /// 以已計算的葉子哈希驗證證明
+    pub fn verify_leaf_hashes(&self, leaves: &[(usize, [u8; 32])], root: &MerkleRoot) -> bool {
+        let mut layer: Vec<(u64, [u8; 32])> = leaves
+            .iter()
+            .map(|&(index, hash)| (index as u64, hash))
+            .collect();
+        layer.sort_unstable_by_key(|&(index, _)| index);
+
+        // 同一索引給出不同數據時拒絕；相同數據重複給出時合併
+        if layer
+            .windows(2)
+            .any(|pair| pair[0].0 == pair[1].0 && !ct_eq(&pair[0].1, &pair[1].1))
+        {
+            return false;
+        }
+        layer.dedup_by_key(|&mut (index, _)| index);
+
+        // 證明格式不合法，或索引集合與證明不符
+        if self.check_shape().is_err()
+            || layer.is_empty()
+            || layer.len() != self.leaf_indices.len()
+            || layer
+                .iter()
+                .zip(&self.leaf_indices)
+                .any(|(&(index, _), &expected)| index != expected)
+            || layer.iter().any(|&(index, _)| index >= self.leaf_count)
+        {
+            return false;
+        }
+
+        let mut nodes = self.nodes.iter();
+        let mut width = self.leaf_count;
+
+        while width > 1 {
+            let mut next = Vec::with_capacity(layer.len());
+            let mut i = 0;
+            while i < layer.len() {
+                let (index, hash) = layer[i];
+                let parent = if index & 1 == 0 && index + 1 == width {
+                    // 未配對節點原樣提升
+                    i += 1;
+                    hash
+                } else if index & 1 == 0 {
+                    // 右兄弟也被證明時直接合併，否則取證明中的節點
+                    let right = match layer.get(i + 1) {
+                        Some(&(right_index, right)) if right_index == index + 1 => {
+                            i += 1;
+                            right
+                        }
+                        _ => match nodes.next() {
+                            Some(sibling) => *sibling,
+                            None => return false,
+                        },
+                    };
+                    i += 1;
+                    hash_node(&hash, &right)
+                } else {
+                    let Some(sibling) = nodes.next() else {
+                        return false;
+                    };
+                    i += 1;
+                    hash_node(sibling, &hash)
+                };
+                next.push((index >> 1, parent));
+            }
+
+            layer = next;
+            width = width.div_ceil(2);
+        }
+
+        // 節點必須恰好用完，且所有葉子匯聚到同一個根
+        layer.len() == 1 && nodes.next().is_none() && ct_eq(&layer[0].1, root)
+    }
+
+    /// 從字節反序列化證明（`bincode`，與 [`MerkleProof::from_bytes`] 相同）
+    ///
+    /// # 錯誤
+    /// - 超過 [`MAX_MULTI_PROOF_BYTES`] 或字節格式無效: 返回 `Deserialization` 錯誤
+    /// - 葉子索引不嚴格遞增、超出 `leaf_count`，或兄弟節點多於每個葉子 [`MAX_PROOF_DEPTH`] 個:
+    ///   返回 `Deserialization` 錯誤
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, MerkleError> {
+        use bincode::Options;
+
+        let proof: Self = bincode::DefaultOptions::new()
+            .with_fixint_encoding()
+            .allow_trailing_bytes()
+            .with_limit(MAX_MULTI_PROOF_BYTES)
+            .deserialize(bytes)
+            .map_err(|e| {
+                MerkleError::Deserialization(format!(
+                    "Failed to deserialize MerkleMultiProof: {}",
+                    e
+                ))
+            })?;
+        proof.check_shape().map_err(MerkleError::Deserialization)?;
+        Ok(proof)
+    }
+
+    /// 在計算任何哈希之前拒絕不合法或超大的證明
+    ///
+    /// 葉子索引必須嚴格遞增且小於 `leaf_count`（重複索引會讓額外的葉子繞過根的比對），
+    /// 兄弟節點不超過每個葉子 [`MAX_PROOF_DEPTH`] 個
+    fn check_shape(&self) -> Result<(), String> {
+        if self.leaf_indices.len() as u64 > self.leaf_count {
+            return Err(format!(
+                "MerkleMultiProof has {} leaf indices for {} leaves",
+                self.leaf_indices.len(),
+                self.leaf_count
+            ));
+        }
+        if self.leaf_indices.windows(2).any(|pair| pair[0] >= pair[1]) {
+            return Err("MerkleMultiProof leaf indices are not strictly increasing".to_string());
+        }
+        if let Some(&last) = self.leaf_indices.last() {
+            if last >= self.leaf_count {
+                return Err(format!(
+                    "MerkleMultiProof leaf index {} is out of range for {} leaves",
+                    last, self.leaf_count
+                ));
+            }
+        }
+        let max_nodes = self.leaf_indices.len().saturating_mul(MAX_PROOF_DEPTH);
+        if self.nodes.len() > max_nodes {
+            return Err(format!(
+                "MerkleMultiProof has {} nodes (maximum {} for {} leaf indices)",
+                self.nodes.len(),
+                max_nodes,
+                self.leaf_indices.len()
+            ));
+        }
+        Ok(())
+    }
+
+    /// 序列化證明為字節
+    pub fn to_bytes(&self) -> Vec<u8> {
+        bincode::serialize(self).expect("MerkleMultiProof serialization should never fail")
+    }
+}
+
+/// 字節範圍的包含證明（[`MerkleTree::prove_range`]）
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RangeProof {
+    /// 切片大小（bytes）
+    pub chunk_size: u64,
+
+    /// 覆蓋範圍的第一個 chunk 的起始偏移
+    pub start: u64,
+
+    /// 覆蓋範圍的最後一個 chunk 的結束偏移（不超過 blob 末尾）
+    pub end: u64,
+
+    /// 樹的葉子總數（已知預期葉子數時，調用方應與之比對）
+    pub leaf_count: u64,
+
+    /// 覆蓋範圍的每個 chunk 的證明（按 chunk 順序）
+    pub proofs: Vec<MerkleProof>,
+}
+
+/// 驗證字節範圍 `offset..offset + len` 屬於根為 `root` 的 blob
+///
+/// `data` 是 `proof.start..proof.end` 的完整數據：首個 chunk 含範圍之前的字節，
+/// 最後一個 chunk 含範圍之後的字節；只有 blob 的最後一個 chunk 可以不滿。
+/// 範圍本身的數據為 `data[offset - proof.start..][..len]`。
+/// 證明必須恰好覆蓋範圍所需的 chunk，每個 chunk 的哈希由 `data` 重算後以
+/// [`MerkleProof::verify`] 驗證
+pub fn verify_range(
+    root: &MerkleRoot,
+    offset: u64,
+    len: u64,
+    data: &[u8],
+    proof: &RangeProof,
+) -> bool {
+    let chunk_size = proof.chunk_size;
+    let Some(end) = offset.checked_add(len) else {
+        return false;
+    };
+    if len == 0
+        || chunk_size == 0
+        || proof.start != offset / chunk_size * chunk_size
+        || proof.end < end
+        || proof.end - proof.start != data.len() as u64
+    {
+        return false;
+    }
+
+    // 證明的 chunk 必須恰好是覆蓋範圍的最少 chunk
+    let first = offset / chunk_size;
+    let last = (end - 1) / chunk_size;
+    if proof.proofs.len() as u64 != last - first + 1
+        || data.len() as u64 > (last - first + 1) * chunk_size
+    {
+        return false;
+    }
+
+    data.chunks(chunk_size as usize)
+        .zip(&proof.proofs)
+        .enumerate()
+        .all(|(i, (chunk, chunk_proof))| {
+            let index = first + i as u64;
+            // 不滿的 chunk 只能是 blob 的最後一個
+            let complete = chunk.len() as u64 == chunk_size || index + 1 == proof.leaf_count;
+            complete
+                && chunk_proof.leaf_index == index
+                && chunk_proof.verify(chunk, root, proof.leaf_count)
+        })
+}
+
 /// 計算單個內部節點的哈希（Walrus 使用的方式）
 ///
 /// # 參數
@@ -279,6 +709,22 @@ pub enum MerkleError {
     /// 無效的葉子索引
     #[error("Invalid leaf index: {index} (total leaves: {total})")]
     InvalidLeafIndex { index: usize, total: usize },
+
+    /// 多葉子證明未指定任何葉子
+    #[error("Multi-proof requires at least one leaf index")]
+    EmptyLeafSet,
+
+    /// 流式構建時讀取數據失敗
+    #[error("Failed to read blob data: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// 字節範圍為空或超出 blob
+    #[error("Invalid byte range: offset {offset}, length {len} (blob size: {total})")]
+    InvalidRange { offset: u64, len: u64, total: u64 },
+
+    /// 樹由葉子哈希構建，不知道 chunk 邊界
+    #[error("Chunk boundaries are unknown for a tree built from leaf hashes")]
+    UnknownChunking,
 }
 
 /// Merkle Tree 構建器
@@ -296,6 +742,12 @@ pub struct MerkleTree {
 
     /// 葉子總數
     leaf_count: usize,
+
+    /// 樹格式版本
+    version: MerkleTreeVersion,
+
+    /// 切片大小與數據總長度（bytes；由葉子哈希構建的樹沒有）
+    chunking: Option<(usize, u64)>,
 }
 
 impl MerkleTree {
@@ -325,64 +777,123 @@ impl MerkleTree {
     /// let proof = tree.generate_proof(0).unwrap();
     /// ```
     pub fn from_blob(blob_data: &[u8], chunk_size: usize) -> Result<Self, MerkleError> {
+        Self::from_blob_with_version(blob_data, chunk_size, MerkleTreeVersion::default())
+    }
+
+    /// 以指定的樹格式版本從 blob 數據構建 Merkle Tree
+    ///
+    /// 僅在需要重現舊版根時使用 [`MerkleTreeVersion::Legacy`]
+    pub fn from_blob_with_version(
+        blob_data: &[u8],
+        chunk_size: usize,
+        version: MerkleTreeVersion,
+    ) -> Result<Self, MerkleError> {
         if blob_data.is_empty() {
             return Err(MerkleError::EmptyData);
         }
 
-        // 步驟 1: 將 blob 切成 chunks
-        let chunks: Vec<&[u8]> = blob_data
-            .chunks(chunk_size)
-            .collect();
+        // 步驟 1-2: 將 blob 切成 chunks，計算葉子哈希
+        let leaves = hash_chunks(blob_data, chunk_size);
 
-        let leaf_count = chunks.len();
+        let tree = Self::from_leaf_hashes(leaves, version)?;
+        Ok(tree.with_chunking(chunk_size, blob_data.len() as u64))
+    }
 
-        // 步驟 2: 計算葉子哈希
-        let mut current_layer: Vec<[u8; 32]> = chunks
-            .iter()
-            .map(|chunk| hash_leaf(chunk))
-            .collect();
+    /// 從 `Read` 流式構建 Merkle Tree
+    ///
+    /// 邊讀邊計算葉子哈希，內存中只保留一個 chunk 和葉子哈希向量，
+    /// 適用於無法整體載入內存的大 blob。根與 [`MerkleTree::from_blob`] 相同。
+    ///
+    /// # 錯誤
+    /// - `MerkleError::EmptyData`: 流為空
+    /// - `MerkleError::Io`: 讀取失敗
+    pub fn from_reader<R: Read>(mut reader: R, chunk_size: usize) -> Result<Self, MerkleError> {
+        let mut builder = MerkleTreeBuilder::new(chunk_size);
+        let mut chunk = vec![0u8; chunk_size];
+        loop {
+            let filled = read_full(&mut reader, &mut chunk)?;
+            if filled == 0 {
+                break;
+            }
+            builder.update(&chunk[..filled]);
+            if filled < chunk_size {
+                break;
+            }
+        }
+        builder.finish()
+    }
 
-        let mut layers = vec![current_layer.clone()];
+    /// [`MerkleTree::from_reader`] 的異步版本
+    pub async fn from_async_reader<R: AsyncRead + Unpin>(
+        mut reader: R,
+        chunk_size: usize,
+    ) -> Result<Self, MerkleError> {
+        let mut builder = MerkleTreeBuilder::new(chunk_size);
+        let mut chunk = vec![0u8; chunk_size];
+        loop {
+            let n = reader.read(&mut chunk).await?;
+            if n == 0 {
+                break;
+            }
+            builder.update(&chunk[..n]);
+        }
+        builder.finish()
+    }
 
-        // 步驟 3: 逐層構建樹，直到根節點
-        while current_layer.len() > 1 {
-            let mut next_layer = Vec::new();
+    /// 從葉子哈希逐層構建樹，直到根節點
+    ///
+    /// 用於從先前記錄的葉子哈希（如審計檢查點）恢復樹，無需原始數據
+    ///
+    /// # 錯誤
+    /// - `MerkleError::EmptyData`: 葉子為空
+    pub fn from_leaf_hashes(
+        leaves: Vec<[u8; 32]>,
+        version: MerkleTreeVersion,
+    ) -> Result<Self, MerkleError> {
+        if leaves.is_empty() {
+            return Err(MerkleError::EmptyData);
+        }
 
-            // 如果當前層有奇數個節點，複製最後一個節點
-            let mut i = 0;
-            while i < current_layer.len() {
-                if i + 1 < current_layer.len() {
-                    // 正常配對
-                    let left = current_layer[i];
-                    let right = current_layer[i + 1];
-                    next_layer.push(hash_node(&left, &right));
-                    i += 2;
-                } else {
-                    // 奇數節點：與自己配對
-                    let node = current_layer[i];
-                    next_layer.push(hash_node(&node, &node));
-                    i += 1;
-                }
+        let leaf_count = leaves.len();
+        let mut layers = vec![leaves];
+
+        loop {
+            let current_layer = &layers[layers.len() - 1];
+            if current_layer.len() <= 1 {
+                break;
             }
 
-            layers.push(next_layer.clone());
-            current_layer = next_layer;
+            let next_layer = parent_layer(current_layer, version);
+            layers.push(next_layer);
         }
 
-        let root = current_layer[0];
+        let root = layers[layers.len() - 1][0];
 
         Ok(MerkleTree {
             layers,
             root,
             leaf_count,
+            version,
+            chunking: None,
         })
     }
 
+    /// 記錄構建時的切片大小與數據長度（字節範圍證明需要）
+    fn with_chunking(mut self, chunk_size: usize, data_len: u64) -> Self {
+        self.chunking = Some((chunk_size, data_len));
+        self
+    }
+
     /// 獲取 Merkle 根
     pub fn root(&self) -> [u8; 32] {
         self.root
     }
 
+    /// 獲取樹格式版本
+    pub fn version(&self) -> MerkleTreeVersion {
+        self.version
+    }
+
     /// 獲取葉子總數
     pub fn leaf_count(&self) -> usize {
         self.leaf_count
@@ -410,7 +921,7 @@ impl MerkleTree {
     ///
     /// // 驗證證明
     /// let root = tree.root();
-    /// assert!(proof.verify(blob_data, &root));
+    /// assert!(proof.verify(blob_data, &root, tree.leaf_count() as u64));
     /// ```
     pub fn generate_proof(&self, leaf_index: usize) -> Result<MerkleProof, MerkleError> {
         if leaf_index >= self.leaf_count {
@@ -435,10 +946,11 @@ impl MerkleTree {
             // 如果兄弟節點存在，加入路徑
             if sibling_index < layer.len() {
                 path.push(layer[sibling_index]);
-            } else {
-                // 如果是最後一個奇數節點，兄弟節點是自己
+            } else if self.version == MerkleTreeVersion::Legacy {
+                // 舊版格式：最後一個奇數節點的兄弟節點是自己
                 path.push(layer[current_index]);
             }
+            // V2 格式：未配對節點直接提升，不需要兄弟節點
 
             // 移動到父節點
             current_index /= 2;
@@ -450,12 +962,286 @@ impl MerkleTree {
         })
     }
 
+    /// 生成一組葉子的多葉子證明
+    ///
+    /// 索引可重複、無序；僅支持 V2 格式的樹
+    ///
+    /// # 錯誤
+    /// - `MerkleError::EmptyLeafSet`: 未指定葉子
+    /// - `MerkleError::InvalidLeafIndex`: 索引超出範圍
+    /// - `MerkleError::InvalidProof`: 樹為舊版格式
+    pub fn generate_multi_proof(
+        &self,
+        leaf_indices: &[usize],
+    ) -> Result<MerkleMultiProof, MerkleError> {
+        if self.version != MerkleTreeVersion::V2 {
+            return Err(MerkleError::InvalidProof);
+        }
+        if let Some(&index) = leaf_indices.iter().find(|&&index| index >= self.leaf_count) {
+            return Err(MerkleError::InvalidLeafIndex {
+                index,
+                total: self.leaf_count,
+            });
+        }
+
+        let mut indices = leaf_indices.to_vec();
+        indices.sort_unstable();
+        indices.dedup();
+        if indices.is_empty() {
+            return Err(MerkleError::EmptyLeafSet);
+        }
+        let leaf_indices = indices.iter().map(|&index| index as u64).collect();
+
+        // 與驗證相同的順序逐層收集無法推出的兄弟節點
+        let mut nodes = Vec::new();
+        for layer in &self.layers[..self.layers.len() - 1] {
+            let mut i = 0;
+            while i < indices.len() {
+                let index = indices[i];
+                if index & 1 == 0 && index + 1 == layer.len() {
+                    // 未配對節點原樣提升
+                } else if index & 1 == 0 {
+                    if indices.get(i + 1) == Some(&(index + 1)) {
+                        i += 1;
+                    } else {
+                        nodes.push(layer[index + 1]);
+                    }
+                } else {
+                    nodes.push(layer[index - 1]);
+                }
+                i += 1;
+            }
+
+            indices = indices.iter().map(|&index| index / 2).collect();
+            indices.dedup();
+        }
+
+        Ok(MerkleMultiProof {
+            leaf_indices,
+            leaf_count: self.leaf_count as u64,
+            nodes,
+        })
+    }
+
+    /// 生成字節範圍 `offset..offset + len` 的包含證明
+    ///
+    /// 返回覆蓋該範圍的最少 chunk 的證明（按 chunk 順序）與這些 chunk 的邊界；
+    /// 以 [`verify_range`] 驗證。僅支持由 blob 數據構建的 V2 格式樹
+    ///
+    /// # 錯誤
+    /// - `MerkleError::InvalidRange`: 範圍為空或超出 blob
+    /// - `MerkleError::UnknownChunking`: 樹由葉子哈希構建
+    /// - `MerkleError::InvalidProof`: 樹為舊版格式
+    ///
+    /// # 示例
+    ///
+    /// ```
+    /// use auditor_node::crypto::merkle::{verify_range, MerkleTree};
+    ///
+    /// let blob_data: Vec<u8> = (0..100).collect();
+    /// let tree = MerkleTree::from_blob(&blob_data, 16).unwrap();
+    ///
+    /// // 30..35 跨越 chunk 1 與 chunk 2
+    /// let proof = tree.prove_range(30, 5).unwrap();
+    /// assert_eq!((proof.start, proof.end), (16, 48));
+    ///
+    /// let covering = &blob_data[proof.start as usize..proof.end as usize];
+    /// assert!(verify_range(&tree.root(), 30, 5, covering, &proof));
+    /// ```
+    pub fn prove_range(&self, offset: u64, len: u64) -> Result<RangeProof, MerkleError> {
+        if self.version != MerkleTreeVersion::V2 {
+            return Err(MerkleError::InvalidProof);
+        }
+        let (chunk_size, data_len) = self.chunking.ok_or(MerkleError::UnknownChunking)?;
+        let end = offset
+            .checked_add(len)
+            .filter(|&end| len > 0 && end <= data_len)
+            .ok_or(MerkleError::InvalidRange {
+                offset,
+                len,
+                total: data_len,
+            })?;
+
+        let chunk_size = chunk_size as u64;
+        let first = offset / chunk_size;
+        let last = (end - 1) / chunk_size;
+        let proofs = (first..=last)
+            .map(|index| self.generate_proof(index as usize))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(RangeProof {
+            chunk_size,
+            start: first * chunk_size,
+            end: ((last + 1) * chunk_size).min(data_len),
+            leaf_count: self.leaf_count as u64,
+            proofs,
+        })
+    }
+
     /// 獲取所有葉子的哈希
     pub fn leaf_hashes(&self) -> &[[u8; 32]] {
         &self.layers[0]
     }
 }
 
+/// 增量構建 Merkle Tree
+///
+/// 數據可按任意大小分批寫入（例如 HTTP 響應的分塊），湊滿一個 chunk 即計算葉子哈希，
+/// 內存中只保留不足一個 chunk 的尾部數據與葉子哈希。
+///
+/// ```
+/// use auditor_node::crypto::merkle::{MerkleTree, MerkleTreeBuilder};
+///
+/// let blob_data = b"Hello Walrus!".repeat(1000);
+/// let mut builder = MerkleTreeBuilder::new(4096);
+/// for part in blob_data.chunks(1000) {
+///     builder.update(part);
+/// }
+///
+/// let tree = builder.finish().unwrap();
+/// assert_eq!(tree.root(), MerkleTree::from_blob(&blob_data, 4096).unwrap().root());
+/// ```
+#[derive(Debug, Clone)]
+pub struct MerkleTreeBuilder {
+    /// 切片大小（bytes）
+    chunk_size: usize,
+    /// 尚未湊滿一個 chunk 的數據
+    pending: Vec<u8>,
+    /// 已完成 chunk 的葉子哈希
+    leaves: Vec<[u8; 32]>,
+    /// 已寫入的總字節數
+    bytes: u64,
+    /// 樹格式版本
+    version: MerkleTreeVersion,
+}
+
+impl MerkleTreeBuilder {
+    /// 創建構建器
+    ///
+    /// # Panics
+    /// `chunk_size` 為 0 時 panic（與 `slice::chunks` 一致）
+    pub fn new(chunk_size: usize) -> Self {
+        assert!(chunk_size > 0, "chunk_size must be non-zero");
+        Self {
+            chunk_size,
+            pending: Vec::with_capacity(chunk_size),
+            leaves: Vec::new(),
+            bytes: 0,
+            version: MerkleTreeVersion::default(),
+        }
+    }
+
+    /// 設置樹格式版本
+    pub fn with_version(mut self, version: MerkleTreeVersion) -> Self {
+        self.version = version;
+        self
+    }
+
+    /// 寫入一段數據
+    pub fn update(&mut self, mut data: &[u8]) {
+        self.bytes += data.len() as u64;
+
+        // 先補滿上次剩下的不完整 chunk
+        if !self.pending.is_empty() {
+            let take = (self.chunk_size - self.pending.len()).min(data.len());
+            self.pending.extend_from_slice(&data[..take]);
+            data = &data[take..];
+            if self.pending.len() < self.chunk_size {
+                return;
+            }
+            self.leaves.push(hash_leaf(&self.pending));
+            self.pending.clear();
+        }
+
+        // 完整的 chunk 直接哈希，不複製
+        let mut chunks = data.chunks_exact(self.chunk_size);
+        self.leaves.extend(chunks.by_ref().map(hash_leaf));
+        self.pending.extend_from_slice(chunks.remainder());
+    }
+
+    /// 已寫入的總字節數
+    pub fn bytes_written(&self) -> u64 {
+        self.bytes
+    }
+
+    /// 完成構建（最後不足一個 chunk 的數據作為最後一個葉子）
+    ///
+    /// # 錯誤
+    /// - `MerkleError::EmptyData`: 未寫入任何數據
+    pub fn finish(mut self) -> Result<MerkleTree, MerkleError> {
+        if !self.pending.is_empty() {
+            self.leaves.push(hash_leaf(&self.pending));
+        }
+        let tree = MerkleTree::from_leaf_hashes(self.leaves, self.version)?;
+        Ok(tree.with_chunking(self.chunk_size, self.bytes))
+    }
+}
+
+/// 節點數不少於此值時才並行計算（更小的層調度開銷超過哈希本身）
+#[cfg(feature = "parallel")]
+const PARALLEL_MIN_NODES: usize = 256;
+
+/// 將數據切成 chunks 並計算葉子哈希（啟用 `parallel` 時並行）
+fn hash_chunks(data: &[u8], chunk_size: usize) -> Vec<[u8; 32]> {
+    #[cfg(feature = "parallel")]
+    if data.len() / chunk_size >= PARALLEL_MIN_NODES {
+        return data.par_chunks(chunk_size).map(hash_leaf).collect();
+    }
+    hash_chunks_serial(data, chunk_size)
+}
+
+fn hash_chunks_serial(data: &[u8], chunk_size: usize) -> Vec<[u8; 32]> {
+    data.chunks(chunk_size).map(hash_leaf).collect()
+}
+
+/// 計算上一層節點（啟用 `parallel` 時並行）
+///
+/// 如果當前層有奇數個節點，最後一個節點按版本處理（見 [`hash_pair`]）
+fn parent_layer(layer: &[[u8; 32]], version: MerkleTreeVersion) -> Vec<[u8; 32]> {
+    #[cfg(feature = "parallel")]
+    if layer.len() >= PARALLEL_MIN_NODES {
+        return layer
+            .par_chunks(2)
+            .map(|pair| hash_pair(pair, version))
+            .collect();
+    }
+    parent_layer_serial(layer, version)
+}
+
+fn parent_layer_serial(layer: &[[u8; 32]], version: MerkleTreeVersion) -> Vec<[u8; 32]> {
+    layer
+        .chunks(2)
+        .map(|pair| hash_pair(pair, version))
+        .collect()
+}
+
+/// 計算一對節點的父節點
+fn hash_pair(pair: &[[u8; 32]], version: MerkleTreeVersion) -> [u8; 32] {
+    match (pair, version) {
+        // 正常配對
+        ([left, right], _) => hash_node(left, right),
+        // 舊版：與自己配對
+        ([node], MerkleTreeVersion::Legacy) => hash_node(node, node),
+        // V2：原樣提升
+        ([node], MerkleTreeVersion::V2) => *node,
+        _ => unreachable!("chunks(2) yields one or two nodes"),
+    }
+}
+
+/// 讀滿緩衝區（流結束時可能不滿），返回讀到的字節數
+fn read_full<R: Read>(reader: &mut R, buf: &mut [u8]) -> std::io::Result<usize> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..]) {
+            Ok(0) => break,
+            Ok(n) => filled += n,
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(filled)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -503,8 +1289,8 @@ mod tests {
             leaf_index: 0,
         };
 
-        assert!(proof.verify(leaf_data, &root));
-        assert!(!proof.verify(b"wrong data", &root));
+        assert!(proof.verify(leaf_data, &root, 1));
+        assert!(!proof.verify(b"wrong data", &root, 1));
     }
 
     /// 測試兩葉子樹的驗證
@@ -523,17 +1309,17 @@ mod tests {
             path: vec![hash1], // 兄弟節點是 leaf1
             leaf_index: 0,
         };
-        assert!(proof0.verify(leaf0, &root));
+        assert!(proof0.verify(leaf0, &root, 2));
 
         // 驗證 leaf1
         let proof1 = MerkleProof {
             path: vec![hash0], // 兄弟節點是 leaf0
             leaf_index: 1,
         };
-        assert!(proof1.verify(leaf1, &root));
+        assert!(proof1.verify(leaf1, &root, 2));
 
         // 錯誤的數據應該驗證失敗
-        assert!(!proof0.verify(b"wrong", &root));
+        assert!(!proof0.verify(b"wrong", &root, 2));
     }
 
     /// 測試四葉子樹的完整驗證
@@ -558,28 +1344,28 @@ mod tests {
             path: vec![leaf_hashes[1], node23],
             leaf_index: 0,
         };
-        assert!(proof0.verify(leaves[0], &root));
+        assert!(proof0.verify(leaves[0], &root, 4));
 
         // 驗證 leaf1 (索引 1 = 0b01)
         let proof1 = MerkleProof {
             path: vec![leaf_hashes[0], node23],
             leaf_index: 1,
         };
-        assert!(proof1.verify(leaves[1], &root));
+        assert!(proof1.verify(leaves[1], &root, 4));
 
         // 驗證 leaf2 (索引 2 = 0b10)
         let proof2 = MerkleProof {
             path: vec![leaf_hashes[3], node01],
             leaf_index: 2,
         };
-        assert!(proof2.verify(leaves[2], &root));
+        assert!(proof2.verify(leaves[2], &root, 4));
 
         // 驗證 leaf3 (索引 3 = 0b11)
         let proof3 = MerkleProof {
             path: vec![leaf_hashes[2], node01],
             leaf_index: 3,
         };
-        assert!(proof3.verify(leaves[3], &root));
+        assert!(proof3.verify(leaves[3], &root, 4));
     }
 
     /// 測試篡改葉子數據導致驗證失敗
@@ -595,10 +1381,10 @@ mod tests {
         };
 
         // 原始數據驗證成功
-        assert!(proof.verify(leaves[0], &root));
+        assert!(proof.verify(leaves[0], &root, 2));
 
         // 篡改數據驗證失敗
-        assert!(!proof.verify(b"tampered", &root));
+        assert!(!proof.verify(b"tampered", &root, 2));
     }
 
     /// 測試篡改證明路徑導致驗證失敗
@@ -614,7 +1400,7 @@ mod tests {
             leaf_index: 0,
         };
 
-        assert!(!bad_proof.verify(leaves[0], &root));
+        assert!(!bad_proof.verify(leaves[0], &root, 2));
     }
 
     /// 測試錯誤的索引導致驗證失敗
@@ -630,7 +1416,7 @@ mod tests {
             leaf_index: 1,         // 但索引錯誤
         };
 
-        assert!(!bad_proof.verify(leaves[0], &root));
+        assert!(!bad_proof.verify(leaves[0], &root, 2));
     }
 
     /// 測試序列化和反序列化
@@ -671,6 +1457,85 @@ mod tests {
         assert_eq!(proof.depth(), 3);
     }
 
+    /// 測試樹高計算
+    #[test]
+    fn test_tree_depth() {
+        assert_eq!(tree_depth(0), 0);
+        assert_eq!(tree_depth(1), 0);
+        assert_eq!(tree_depth(2), 1);
+        assert_eq!(tree_depth(3), 2);
+        assert_eq!(tree_depth(4), 2);
+        assert_eq!(tree_depth(5), 3);
+        assert_eq!(tree_depth(1000), 10);
+        assert_eq!(tree_depth(u64::MAX), MAX_PROOF_DEPTH);
+    }
+
+    /// 測試超長路徑攻擊：多餘的兄弟節點在計算哈希前即被拒絕
+    #[test]
+    fn test_overlong_proof_path_rejected() {
+        let blob_data = b"A".repeat(4 * 32);
+        let tree = MerkleTree::from_blob(&blob_data, 32).unwrap();
+        let leaf = &blob_data[..32];
+
+        let mut proof = tree.generate_proof(0).unwrap();
+        assert!(proof.verify(leaf, &tree.root(), 4));
+
+        // 數千個兄弟節點
+        proof.path.extend(std::iter::repeat([0xAB; 32]).take(5000));
+        assert!(!proof.verify(leaf, &tree.root(), 4));
+        assert!(!proof.verify_with_version(leaf, &tree.root(), 4, MerkleTreeVersion::Legacy));
+
+        // 即使聲稱的葉子總數極大，路徑也不能超過上限
+        let overlong = MerkleProof::new(vec![[0u8; 32]; MAX_PROOF_DEPTH + 1], 0);
+        assert!(overlong
+            .root_from_leaf_hash(&hash_leaf(leaf), u64::MAX)
+            .is_none());
+
+        // 反序列化時同樣拒絕
+        let result = MerkleProof::from_bytes(&proof.to_bytes());
+        assert!(matches!(result, Err(MerkleError::Deserialization(_))));
+    }
+
+    /// 測試路徑長度與樹高不符的證明被拒絕
+    #[test]
+    fn test_wrong_depth_proof_rejected() {
+        let blob_data: Vec<u8> = (0..8 * 32).map(|i| i as u8).collect();
+        let tree = MerkleTree::from_blob(&blob_data, 32).unwrap();
+        let leaf = &blob_data[5 * 32..6 * 32];
+        let proof = tree.generate_proof(5).unwrap();
+        assert_eq!(proof.depth(), tree_depth(8));
+        assert!(proof.verify(leaf, &tree.root(), 8));
+
+        // 少一層
+        let mut short = proof.clone();
+        short.path.pop();
+        assert!(!short.verify(leaf, &tree.root(), 8));
+
+        // 多一層：以子樹根作為偽造根，高位索引位被移出
+        let mut long = proof.clone();
+        long.path.push([0u8; 32]);
+        assert!(!long.verify(leaf, &tree.root(), 8));
+        assert!(!long.verify(leaf, &hash_node(&tree.root(), &[0u8; 32]), 8));
+
+        // 聲稱錯誤的葉子總數
+        assert!(!proof.verify(leaf, &tree.root(), 4));
+    }
+
+    /// 測試越界索引被拒絕
+    #[test]
+    fn test_out_of_range_index_rejected() {
+        let blob_data = b"B".repeat(4 * 32);
+        let tree = MerkleTree::from_blob(&blob_data, 32).unwrap();
+        let leaf = &blob_data[..32];
+        let proof = tree.generate_proof(0).unwrap();
+
+        // 索引 4 與 0 在兩層路徑下的低位相同
+        let shifted = MerkleProof::new(proof.path.clone(), 4);
+        assert!(!shifted.verify(leaf, &tree.root(), 4));
+        assert!(!shifted.verify_with_version(leaf, &tree.root(), 4, MerkleTreeVersion::Legacy));
+        assert!(!MerkleProof::new(proof.path, u64::MAX).verify(leaf, &tree.root(), 4));
+    }
+
     /// 測試八葉子樹（更深的樹）
     #[test]
     fn test_eight_leaf_tree() {
@@ -699,7 +1564,7 @@ mod tests {
             path: vec![leaf_hashes[4], layer1[3], layer2[0]],
             leaf_index: 5,
         };
-        assert!(proof5.verify(leaves[5], &root));
+        assert!(proof5.verify(leaves[5], &root, 8));
         println!("✓ Verified leaf5 in 8-leaf tree");
 
         // 驗證其他葉子
@@ -707,13 +1572,13 @@ mod tests {
             path: vec![leaf_hashes[1], layer1[1], layer2[1]],
             leaf_index: 0,
         };
-        assert!(proof0.verify(leaves[0], &root));
+        assert!(proof0.verify(leaves[0], &root, 8));
 
         let proof7 = MerkleProof {
             path: vec![leaf_hashes[6], layer1[2], layer2[0]],
             leaf_index: 7,
         };
-        assert!(proof7.verify(leaves[7], &root));
+        assert!(proof7.verify(leaves[7], &root, 8));
     }
 
     /// 測試空數據的哈希
@@ -731,7 +1596,7 @@ mod tests {
             path: vec![],
             leaf_index: 0,
         };
-        assert!(proof.verify(empty, &root));
+        assert!(proof.verify(empty, &root, 1));
     }
 
     // ==================== MerkleTree 構建測試 ====================
@@ -755,7 +1620,7 @@ mod tests {
         // 生成並驗證證明
         let proof = tree.generate_proof(0).unwrap();
         assert!(proof.path.is_empty()); // 單葉子樹的路徑應該為空
-        assert!(proof.verify(&blob_data, &tree.root()));
+        assert!(proof.verify(&blob_data, &tree.root(), tree.leaf_count() as u64));
     }
 
     /// 測試從中型 blob 構建 Merkle Tree（多個 chunks）
@@ -777,7 +1642,11 @@ mod tests {
             let chunk = &blob_data[chunk_start..chunk_end];
 
             let proof = tree.generate_proof(i).unwrap();
-            assert!(proof.verify(chunk, &tree.root()), "Proof for chunk {} failed", i);
+            assert!(
+                proof.verify(chunk, &tree.root(), tree.leaf_count() as u64),
+                "Proof for chunk {} failed",
+                i
+            );
         }
     }
 
@@ -792,7 +1661,7 @@ mod tests {
         assert_eq!(tree.leaf_count(), 1);
 
         let proof = tree.generate_proof(0).unwrap();
-        assert!(proof.verify(&blob_data, &tree.root()));
+        assert!(proof.verify(&blob_data, &tree.root(), tree.leaf_count() as u64));
     }
 
     /// 測試超過 100KB 的大型 blob
@@ -812,7 +1681,11 @@ mod tests {
             let chunk = &blob_data[chunk_start..chunk_end];
 
             let proof = tree.generate_proof(i).unwrap();
-            assert!(proof.verify(chunk, &tree.root()), "Proof for chunk {} failed", i);
+            assert!(
+                proof.verify(chunk, &tree.root(), tree.leaf_count() as u64),
+                "Proof for chunk {} failed",
+                i
+            );
         }
     }
 
@@ -889,7 +1762,7 @@ mod tests {
             let chunk = &blob_data[chunk_start..chunk_end];
 
             let proof = tree.generate_proof(index).unwrap();
-            if proof.verify(chunk, &tree.root()) {
+            if proof.verify(chunk, &tree.root(), tree.leaf_count() as u64) {
                 successful_verifications += 1;
             }
         }
@@ -897,4 +1770,539 @@ mod tests {
         // 所有挑戰都應該驗證成功
         assert_eq!(successful_verifications, challenge_indices.len());
     }
+
+    // ==================== 奇數節點歧義測試 ====================
+
+    /// 三個葉子的 blob（第三個 chunk 與前一個不同）
+    fn three_leaf_blob() -> Vec<u8> {
+        let mut blob = b"A".repeat(8192);
+        blob.extend_from_slice(&b"B".repeat(4096));
+        blob
+    }
+
+    /// 測試舊版格式的重複末葉歧義
+    #[test]
+    fn test_legacy_duplicate_last_leaf_ambiguity() {
+        let blob = three_leaf_blob();
+        let mut extended = blob.clone();
+        extended.extend_from_slice(&b"B".repeat(4096)); // 重複最後一個 chunk
+
+        let tree3 = MerkleTree::from_blob_with_version(&blob, 4096, MerkleTreeVersion::Legacy).unwrap();
+        let tree4 =
+            MerkleTree::from_blob_with_version(&extended, 4096, MerkleTreeVersion::Legacy).unwrap();
+
+        // 3 個葉子與 4 個葉子的樹根相同
+        assert_eq!(tree3.leaf_count(), 3);
+        assert_eq!(tree4.leaf_count(), 4);
+        assert_eq!(tree3.root(), tree4.root());
+
+        // 在不檢查葉子總數的情況下，不存在的葉子 3 的證明能通過驗證
+        let forged = tree4.generate_proof(3).unwrap();
+        let chunk = b"B".repeat(4096);
+        assert!(forged.verify_with_version(&chunk, &tree3.root(), 4, MerkleTreeVersion::Legacy));
+
+        // 提供真實的葉子總數後，舊版驗證也會拒絕越界索引
+        assert!(!forged.verify_with_version(&chunk, &tree3.root(), 3, MerkleTreeVersion::Legacy));
+    }
+
+    /// 測試 V2 格式消除了重複末葉歧義
+    #[test]
+    fn test_v2_rejects_duplicate_last_leaf() {
+        let blob = three_leaf_blob();
+        let mut extended = blob.clone();
+        extended.extend_from_slice(&b"B".repeat(4096));
+
+        let tree3 = MerkleTree::from_blob(&blob, 4096).unwrap();
+        let tree4 = MerkleTree::from_blob(&extended, 4096).unwrap();
+
+        assert_eq!(tree3.version(), MerkleTreeVersion::V2);
+        assert_ne!(tree3.root(), tree4.root());
+
+        // 來自 4 葉子樹的證明無法針對 3 葉子樹的根通過驗證
+        let chunk = b"B".repeat(4096);
+        let forged = tree4.generate_proof(3).unwrap();
+        assert!(!forged.verify(&chunk, &tree3.root(), 3));
+        assert!(!forged.verify(&chunk, &tree3.root(), 4));
+
+        // 舊版格式構造的越界證明同樣被拒絕
+        let legacy4 =
+            MerkleTree::from_blob_with_version(&extended, 4096, MerkleTreeVersion::Legacy).unwrap();
+        let legacy_forged = legacy4.generate_proof(3).unwrap();
+        assert!(!legacy_forged.verify(&chunk, &tree3.root(), 3));
+    }
+
+    /// 測試 V2 格式中未配對節點的證明路徑更短
+    #[test]
+    fn test_v2_odd_node_promotion() {
+        let blob = three_leaf_blob();
+        let tree = MerkleTree::from_blob(&blob, 4096).unwrap();
+        let leaves = tree.leaf_hashes();
+
+        // 根 = H(H(L0, L1), L2)，L2 直接提升
+        let expected_root = hash_node(&hash_node(&leaves[0], &leaves[1]), &leaves[2]);
+        assert_eq!(tree.root(), expected_root);
+
+        let proof = tree.generate_proof(2).unwrap();
+        assert_eq!(proof.depth(), 1);
+        assert!(proof.verify(&b"B".repeat(4096), &tree.root(), 3));
+
+        // 附加多餘的兄弟節點會導致驗證失敗
+        let mut padded = proof.clone();
+        padded.path.insert(0, leaves[2]);
+        assert!(!padded.verify(&b"B".repeat(4096), &tree.root(), 3));
+    }
+
+    /// 測試舊版根仍可通過版本標誌驗證
+    #[test]
+    fn test_legacy_roots_still_verify() {
+        let blob = b"Legacy".repeat(3000); // 18000 bytes -> 5 chunks
+        let tree = MerkleTree::from_blob_with_version(&blob, 4096, MerkleTreeVersion::Legacy).unwrap();
+        assert_eq!(tree.leaf_count(), 5);
+
+        for i in 0..tree.leaf_count() {
+            let start = i * 4096;
+            let end = std::cmp::min(start + 4096, blob.len());
+            let proof = tree.generate_proof(i).unwrap();
+            assert!(proof.verify_with_version(&blob[start..end], &tree.root(), 5, MerkleTreeVersion::Legacy));
+            // 舊版證明不能冒充 V2 證明
+            if i == 4 {
+                assert!(!proof.verify(&blob[start..end], &tree.root(), 5));
+            }
+        }
+    }
+
+    /// 測試 V2 格式對各種葉子數量都能生成有效證明
+    #[test]
+    fn test_v2_proofs_for_all_leaf_counts() {
+        for leaf_count in 1..=17usize {
+            let blob: Vec<u8> = (0..leaf_count * 64).map(|i| (i / 64) as u8).collect();
+            let tree = MerkleTree::from_blob(&blob, 64).unwrap();
+            assert_eq!(tree.leaf_count(), leaf_count);
+
+            for i in 0..leaf_count {
+                let proof = tree.generate_proof(i).unwrap();
+                let chunk = &blob[i * 64..(i + 1) * 64];
+                assert!(proof.verify(chunk, &tree.root(), leaf_count as u64));
+            }
+
+            // 越界索引一律拒絕
+            let mut out_of_range = tree.generate_proof(leaf_count - 1).unwrap();
+            out_of_range.leaf_index = leaf_count as u64;
+            assert!(!out_of_range.verify(&blob[..64], &tree.root(), leaf_count as u64));
+        }
+    }
+
+    /// 按確定性模式生成數據的 reader，每次最多返回 `max_read` 字節
+    struct SyntheticReader {
+        position: usize,
+        len: usize,
+        max_read: usize,
+    }
+
+    impl Read for SyntheticReader {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            let n = buf.len().min(self.max_read).min(self.len - self.position);
+            for (offset, byte) in buf[..n].iter_mut().enumerate() {
+                *byte = ((self.position + offset) * 31 % 251) as u8;
+            }
+            self.position += n;
+            Ok(n)
+        }
+    }
+
+    #[test]
+    fn test_from_reader_matches_from_blob_for_100mb() {
+        const LEN: usize = 100 * 1024 * 1024 + 123;
+
+        // 不與 chunk 邊界對齊的短讀取
+        let reader = SyntheticReader { position: 0, len: LEN, max_read: 3000 };
+        let streamed = MerkleTree::from_reader(reader, 4096).unwrap();
+
+        let blob: Vec<u8> = (0..LEN).map(|i| (i * 31 % 251) as u8).collect();
+        let in_memory = MerkleTree::from_blob(&blob, 4096).unwrap();
+
+        assert_eq!(streamed.root(), in_memory.root());
+        assert_eq!(streamed.leaf_count(), in_memory.leaf_count());
+        assert_eq!(streamed.leaf_count(), LEN.div_ceil(4096));
+    }
+
+    #[tokio::test]
+    async fn test_async_reader_and_builder_match_from_blob() {
+        for len in [1usize, 4095, 4096, 4097, 3 * 4096, 18000] {
+            let blob: Vec<u8> = (0..len).map(|i| (i * 7 % 256) as u8).collect();
+            let expected = MerkleTree::from_blob(&blob, 4096).unwrap();
+
+            let streamed = MerkleTree::from_async_reader(&blob[..], 4096).await.unwrap();
+            assert_eq!(streamed.root(), expected.root(), "len {}", len);
+
+            let mut builder = MerkleTreeBuilder::new(4096).with_version(MerkleTreeVersion::Legacy);
+            for part in blob.chunks(1500) {
+                builder.update(part);
+            }
+            assert_eq!(builder.bytes_written(), len as u64);
+            let legacy = MerkleTree::from_blob_with_version(&blob, 4096, MerkleTreeVersion::Legacy);
+            assert_eq!(builder.finish().unwrap().root(), legacy.unwrap().root());
+        }
+
+        assert!(matches!(
+            MerkleTree::from_reader(std::io::empty(), 4096),
+            Err(MerkleError::EmptyData)
+        ));
+        assert!(matches!(MerkleTreeBuilder::new(64).finish(), Err(MerkleError::EmptyData)));
+    }
+
+    #[test]
+    fn test_verify_leaf_hash() {
+        let blob = b"Leaf hashes".repeat(2000);
+        let tree = MerkleTree::from_blob(&blob, 4096).unwrap();
+        let leaf_count = tree.leaf_count() as u64;
+
+        for (i, leaf_hash) in tree.leaf_hashes().iter().enumerate() {
+            let proof = tree.generate_proof(i).unwrap();
+            assert!(proof.verify_leaf_hash(leaf_hash, &tree.root(), leaf_count));
+            assert!(!proof.verify_leaf_hash(&[0u8; 32], &tree.root(), leaf_count));
+        }
+    }
+
+    /// 按 chunk 大小 16 構建 `leaf_count` 個葉子的樹
+    fn multi_proof_tree(leaf_count: usize) -> (Vec<u8>, MerkleTree) {
+        let blob: Vec<u8> = (0..leaf_count * 16).map(|i| (i * 7 % 251) as u8).collect();
+        let tree = MerkleTree::from_blob(&blob, 16).unwrap();
+        (blob, tree)
+    }
+
+    fn chunk(blob: &[u8], index: usize) -> (usize, &[u8]) {
+        (index, &blob[index * 16..((index + 1) * 16).min(blob.len())])
+    }
+
+    #[test]
+    fn test_multi_proof_all_subsets_small_trees() {
+        for leaf_count in 1..=9usize {
+            let (blob, tree) = multi_proof_tree(leaf_count);
+            for mask in 1u32..(1 << leaf_count) {
+                let indices: Vec<usize> =
+                    (0..leaf_count).filter(|i| mask & (1 << i) != 0).collect();
+                let proof = tree.generate_multi_proof(&indices).unwrap();
+                let leaves: Vec<(usize, &[u8])> =
+                    indices.iter().map(|&i| chunk(&blob, i)).collect();
+
+                assert!(proof.verify(&leaves, &tree.root()), "{} {:?}", leaf_count, indices);
+                let restored = MerkleMultiProof::from_bytes(&proof.to_bytes()).unwrap();
+                assert_eq!(restored, proof);
+            }
+        }
+    }
+
+    #[test]
+    fn test_multi_proof_rejects_tampering() {
+        let (blob, tree) = multi_proof_tree(32);
+        let root = tree.root();
+        let proof = tree.generate_multi_proof(&[2, 9, 10, 30]).unwrap();
+        let leaves: Vec<(usize, &[u8])> = [30, 2, 10, 9].iter().map(|&i| chunk(&blob, i)).collect();
+        assert!(proof.verify(&leaves, &root));
+
+        // 數據被篡改
+        let mut tampered = leaves.clone();
+        tampered[0].1 = b"not the leaf";
+        assert!(!proof.verify(&tampered, &root));
+
+        // 缺少或多出葉子
+        assert!(!proof.verify(&leaves[..3], &root));
+        let mut extra = leaves.clone();
+        extra.push(chunk(&blob, 11));
+        assert!(!proof.verify(&extra, &root));
+
+        // 同一索引給出兩份不同數據
+        let mut conflicting = leaves.clone();
+        conflicting.push((2, b"other data"));
+        assert!(!proof.verify(&conflicting, &root));
+
+        // 節點被篡改、截斷或追加
+        let mut bad = proof.clone();
+        bad.nodes[0][0] ^= 1;
+        assert!(!bad.verify(&leaves, &root));
+        let mut bad = proof.clone();
+        bad.nodes.pop();
+        assert!(!bad.verify(&leaves, &root));
+        let mut bad = proof.clone();
+        bad.nodes.push([0u8; 32]);
+        assert!(!bad.verify(&leaves, &root));
+
+        // 謊報葉子總數
+        let mut bad = proof.clone();
+        bad.leaf_count = 31;
+        assert!(!bad.verify(&leaves, &root));
+        assert!(!proof.verify(&[], &root));
+    }
+
+    #[test]
+    fn test_multi_proof_rejects_duplicate_index_forgery() {
+        let (blob, tree) = multi_proof_tree(32);
+        let root = tree.root();
+        let proof = tree.generate_multi_proof(&[3]).unwrap();
+        let real = chunk(&blob, 3);
+        assert!(proof.verify(&[real], &root));
+
+        // 同一索引的第二個葉子與加倍的兄弟節點：額外的葉子不能繞過根的比對
+        let forged = MerkleMultiProof {
+            leaf_indices: vec![3, 3],
+            leaf_count: proof.leaf_count,
+            nodes: [proof.nodes.clone(), proof.nodes.clone()].concat(),
+        };
+        let leaves = [real, (3, b"FORGED-LEAF-DATA".as_slice())];
+        assert!(!forged.verify(&leaves, &root));
+        assert!(MerkleMultiProof::from_bytes(&forged.to_bytes()).is_err());
+
+        // 合法證明也不接受同一索引的兩份不同數據
+        assert!(!proof.verify(&leaves, &root));
+        // 完全相同的重複葉子仍然合併
+        assert!(proof.verify(&[real, real], &root));
+    }
+
+    #[test]
+    fn test_multi_proof_errors() {
+        let (_, tree) = multi_proof_tree(5);
+        assert!(matches!(tree.generate_multi_proof(&[]), Err(MerkleError::EmptyLeafSet)));
+        assert!(matches!(
+            tree.generate_multi_proof(&[1, 5]),
+            Err(MerkleError::InvalidLeafIndex { index: 5, total: 5 })
+        ));
+
+        let legacy = MerkleTree::from_blob_with_version(&[1u8; 80], 16, MerkleTreeVersion::Legacy)
+            .unwrap();
+        assert!(matches!(legacy.generate_multi_proof(&[0]), Err(MerkleError::InvalidProof)));
+        assert!(MerkleMultiProof::from_bytes(&[1, 2, 3]).is_err());
+    }
+
+    #[test]
+    fn test_multi_proof_from_bytes_rejects_oversized_proofs() {
+        let (_, tree) = multi_proof_tree(32);
+        let proof = tree.generate_multi_proof(&[2, 9, 30]).unwrap();
+        assert_eq!(
+            MerkleMultiProof::from_bytes(&proof.to_bytes()).unwrap(),
+            proof
+        );
+
+        // 葉子索引多於葉子總數
+        let mut bad = proof.clone();
+        bad.leaf_count = 2;
+        assert!(MerkleMultiProof::from_bytes(&bad.to_bytes()).is_err());
+
+        // 兄弟節點多於每個葉子 MAX_PROOF_DEPTH 個
+        let bad = MerkleMultiProof {
+            leaf_indices: vec![0],
+            leaf_count: u64::MAX,
+            nodes: vec![[0u8; 32]; MAX_PROOF_DEPTH + 1],
+        };
+        assert!(MerkleMultiProof::from_bytes(&bad.to_bytes()).is_err());
+        assert!(!bad.verify_leaf_hashes(&[(0, [0u8; 32])], &tree.root()));
+
+        // 重複或超出範圍的索引
+        let mut bad = proof.clone();
+        bad.leaf_indices = vec![9, 2, 30];
+        assert!(MerkleMultiProof::from_bytes(&bad.to_bytes()).is_err());
+        bad.leaf_indices = vec![2, 9, 32];
+        assert!(MerkleMultiProof::from_bytes(&bad.to_bytes()).is_err());
+
+        // 聲明的長度超過大小上限時不分配
+        let mut huge = (MAX_MULTI_PROOF_BYTES / 8 + 1).to_le_bytes().to_vec();
+        huge.extend_from_slice(&[0u8; 64]);
+        assert!(matches!(
+            MerkleMultiProof::from_bytes(&huge),
+            Err(MerkleError::Deserialization(_))
+        ));
+    }
+
+    #[test]
+    fn test_multi_proof_smaller_than_individual_proofs() {
+        let (_, tree) = multi_proof_tree(32);
+        let mut rng = rand::thread_rng();
+        for count in 4..=32 {
+            for _ in 0..20 {
+                let indices = rand::seq::index::sample(&mut rng, 32, count).into_vec();
+                let multi = tree.generate_multi_proof(&indices).unwrap().to_bytes().len();
+                let individual: usize = indices
+                    .iter()
+                    .map(|&i| tree.generate_proof(i).unwrap().to_bytes().len())
+                    .sum();
+                assert!(multi < individual, "{:?}: {} >= {}", indices, multi, individual);
+            }
+        }
+    }
+
+    /// 100 字節、16 字節切片的 blob（7 個 chunk，最後一個 4 字節）
+    fn range_tree() -> (Vec<u8>, MerkleTree) {
+        let blob_data: Vec<u8> = (0..100).collect();
+        let tree = MerkleTree::from_blob(&blob_data, 16).unwrap();
+        (blob_data, tree)
+    }
+
+    /// 生成並以覆蓋 chunk 的數據驗證範圍證明，返回證明
+    fn prove_and_verify(blob_data: &[u8], tree: &MerkleTree, offset: u64, len: u64) -> RangeProof {
+        let proof = tree.prove_range(offset, len).unwrap();
+        let covering = &blob_data[proof.start as usize..proof.end as usize];
+        assert!(verify_range(&tree.root(), offset, len, covering, &proof));
+        proof
+    }
+
+    #[test]
+    fn test_range_within_one_chunk() {
+        let (blob_data, tree) = range_tree();
+        let proof = prove_and_verify(&blob_data, &tree, 18, 5);
+        assert_eq!((proof.start, proof.end, proof.chunk_size), (16, 32, 16));
+        assert_eq!(proof.proofs.len(), 1);
+        assert_eq!(proof.proofs[0].leaf_index, 1);
+
+        // 範圍外的填充字節同樣受證明保護
+        let mut covering = blob_data[16..32].to_vec();
+        covering[0] ^= 1;
+        assert!(!verify_range(&tree.root(), 18, 5, &covering, &proof));
+        // 只給出範圍本身的數據
+        assert!(!verify_range(&tree.root(), 18, 5, &blob_data[18..23], &proof));
+    }
+
+    #[test]
+    fn test_range_spanning_chunk_boundary() {
+        let (blob_data, tree) = range_tree();
+        let proof = prove_and_verify(&blob_data, &tree, 30, 5);
+        assert_eq!((proof.start, proof.end), (16, 48));
+        let indices: Vec<u64> = proof.proofs.iter().map(|p| p.leaf_index).collect();
+        assert_eq!(indices, vec![1, 2]);
+
+        // 恰好在邊界上開始與結束：只需要一個 chunk
+        let proof = prove_and_verify(&blob_data, &tree, 32, 16);
+        assert_eq!(proof.proofs.len(), 1);
+
+        // 範圍超出證明覆蓋的 chunk，或多給出一個 chunk 的證明
+        let covering = &blob_data[16..48];
+        let proof = tree.prove_range(30, 5).unwrap();
+        assert!(!verify_range(&tree.root(), 30, 20, covering, &proof));
+        assert!(!verify_range(&tree.root(), 40, 5, covering, &proof));
+        let mut extra = proof.clone();
+        extra.proofs.push(tree.generate_proof(3).unwrap());
+        assert!(!verify_range(&tree.root(), 30, 5, covering, &extra));
+    }
+
+    #[test]
+    fn test_range_ending_at_blob_end() {
+        let (blob_data, tree) = range_tree();
+        let proof = prove_and_verify(&blob_data, &tree, 90, 10);
+        assert_eq!((proof.start, proof.end), (80, 100));
+        assert_eq!(proof.proofs.len(), 2);
+        prove_and_verify(&blob_data, &tree, 0, 100);
+
+        // 不滿的 chunk 只能是最後一個葉子：截斷中間的 chunk 無法通過
+        let proof = tree.prove_range(20, 4).unwrap();
+        let mut truncated = proof.clone();
+        truncated.end = 24;
+        assert!(!verify_range(&tree.root(), 20, 4, &blob_data[16..24], &truncated));
+
+        // 流式構建的樹同樣支持
+        let mut builder = MerkleTreeBuilder::new(16);
+        builder.update(&blob_data);
+        let streamed = builder.finish().unwrap();
+        prove_and_verify(&blob_data, &streamed, 90, 10);
+    }
+
+    #[test]
+    fn test_range_errors() {
+        let (_, tree) = range_tree();
+        assert!(matches!(
+            tree.prove_range(10, 0),
+            Err(MerkleError::InvalidRange { offset: 10, len: 0, total: 100 })
+        ));
+        assert!(matches!(tree.prove_range(95, 6), Err(MerkleError::InvalidRange { .. })));
+        assert!(matches!(tree.prove_range(u64::MAX, 2), Err(MerkleError::InvalidRange { .. })));
+
+        let from_hashes =
+            MerkleTree::from_leaf_hashes(tree.leaf_hashes().to_vec(), MerkleTreeVersion::V2)
+                .unwrap();
+        assert!(matches!(from_hashes.prove_range(0, 1), Err(MerkleError::UnknownChunking)));
+
+        let legacy = MerkleTree::from_blob_with_version(&[1u8; 80], 16, MerkleTreeVersion::Legacy)
+            .unwrap();
+        assert!(matches!(legacy.prove_range(0, 1), Err(MerkleError::InvalidProof)));
+
+        // 空範圍不驗證
+        let proof = tree.prove_range(0, 1).unwrap();
+        assert!(!verify_range(&tree.root(), 0, 0, &[0; 16], &proof));
+    }
+
+    /// 只用串行路徑構建的根
+    fn serial_root(leaves: Vec<[u8; 32]>, version: MerkleTreeVersion) -> [u8; 32] {
+        let mut layer = leaves;
+        while layer.len() > 1 {
+            layer = parent_layer_serial(&layer, version);
+        }
+        layer[0]
+    }
+
+    proptest::proptest! {
+        #![proptest_config(proptest::prelude::ProptestConfig::with_cases(32))]
+
+        #[test]
+        fn test_parallel_root_matches_serial(
+            leaves in proptest::collection::vec(proptest::prelude::any::<[u8; 32]>(), 1..1200),
+            legacy in proptest::prelude::any::<bool>(),
+        ) {
+            let version = if legacy { MerkleTreeVersion::Legacy } else { MerkleTreeVersion::V2 };
+            let tree = MerkleTree::from_leaf_hashes(leaves.clone(), version).unwrap();
+            proptest::prop_assert_eq!(tree.root(), serial_root(leaves, version));
+        }
+
+        #[test]
+        fn test_parallel_leaf_hashes_match_serial(
+            data in proptest::collection::vec(proptest::prelude::any::<u8>(), 1..24_000),
+            chunk_size in 1usize..64,
+        ) {
+            let tree = MerkleTree::from_blob(&data, chunk_size).unwrap();
+            let leaves = hash_chunks_serial(&data, chunk_size);
+            proptest::prop_assert_eq!(tree.leaf_hashes(), leaves.as_slice());
+            proptest::prop_assert_eq!(tree.root(), serial_root(leaves, MerkleTreeVersion::V2));
+        }
+    }
+
+    #[test]
+    fn test_odd_nodes_above_parallel_threshold() {
+        // 葉子層與上一層的節點數均為奇數且足以走並行路徑（啟用 `parallel` 時）
+        let leaf_count = 2 * 256 + 1;
+        let leaves: Vec<[u8; 32]> = (0..leaf_count as u32)
+            .map(|i| hash_leaf(&i.to_le_bytes()))
+            .collect();
+
+        for version in [MerkleTreeVersion::V2, MerkleTreeVersion::Legacy] {
+            let tree = MerkleTree::from_leaf_hashes(leaves.clone(), version).unwrap();
+            assert_eq!(tree.root(), serial_root(leaves.clone(), version));
+            assert_eq!(tree.layers[1].len(), 257);
+
+            let last = leaf_count - 1;
+            let proof = tree.generate_proof(last).unwrap();
+            assert!(proof.verify_leaf_hash_with_version(
+                &leaves[last],
+                &tree.root(),
+                leaf_count as u64,
+                version
+            ));
+        }
+
+        // V2 將未配對的末節點原樣提升：末葉直接出現在上一層
+        let v2 = MerkleTree::from_leaf_hashes(leaves.clone(), MerkleTreeVersion::V2).unwrap();
+        assert_eq!(v2.layers[1][256], leaves[leaf_count - 1]);
+
+        // 舊版將其與自身配對：重複末葉的 N+1 個葉子得到同一個根，V2 則不會
+        let mut duplicated = leaves.clone();
+        duplicated.push(leaves[leaf_count - 1]);
+        let legacy = |leaves: Vec<[u8; 32]>| {
+            MerkleTree::from_leaf_hashes(leaves, MerkleTreeVersion::Legacy)
+                .unwrap()
+                .root()
+        };
+        assert_eq!(legacy(leaves.clone()), legacy(duplicated.clone()));
+        assert_ne!(
+            v2.root(),
+            MerkleTree::from_leaf_hashes(duplicated, MerkleTreeVersion::V2)
+                .unwrap()
+                .root()
+        );
+    }
 }
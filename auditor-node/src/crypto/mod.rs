@@ -3,10 +3,22 @@
 //! 包含所有密碼學相關功能:
 //! - 默克爾樹驗證
 //! - Sliver 數據解析
+//! - 存儲節點響應簽名驗證
+//! - 常量時間比較
+//!
+//! # 內部約定（lint）
+//!
+//! 摘要、默克爾根與簽名字節一律以 [`ct_eq`]（十六進制摘要以 [`ct_eq_hex`]）比較，
+//! 不要使用 `==` / `!=`：後者在第一個不同的字節處提前返回，耗時會洩露匹配的前綴長度。
+//! 評審時把對這類值的 `==` 視同 clippy 警告處理；長度、索引等公開值不受此限。
 
+pub mod ct;
 pub mod merkle;
+pub mod node_signature;
 pub mod sliver;
 
 // Re-export commonly used types
-pub use merkle::{hash_leaf, hash_node, MerkleError, MerkleProof, MerkleRoot};
+pub use ct::{ct_eq, ct_eq_hex, decode_hex_digest};
+pub use merkle::{hash_leaf, hash_node, MerkleError, MerkleProof, MerkleRoot, MerkleTreeVersion};
+pub use node_signature::NodePublicKey;
 pub use sliver::Sliver;
@@ -0,0 +1,159 @@
+//! 存儲節點響應簽名驗證
+//!
+//! 存儲節點可以對挑戰響應簽名（`ChallengeResponse.node_signature`），證明數據由該節點本身提供，
+//! 而不是由中間的代理或 Aggregator 代答。簽名覆蓋規範編碼的消息（[`challenge_message`]）：
+//!
+//! ```text
+//! "WALRUS_AUDIT_CHALLENGE_RESPONSE_V1"
+//!     || u32 LE len(blob_id)     || blob_id (UTF-8)
+//!     || u64 LE sliver_index
+//!     || u32 LE len(sliver_hash) || sliver_hash
+//!     || u64 LE timestamp
+//! ```
+//!
+//! recovery symbol 挑戰的 `sliver_hash` 為 symbol 的哈希；響應未帶時間戳時 `timestamp` 按 0 編碼。
+//!
+//! 節點公鑰的方案按長度識別（[`NodePublicKey::from_bytes`]）：
+//!
+//! - 32 字節：Ed25519（簽名 64 字節）
+//! - 96 字節：BLS12-381 min_pk（Walrus 存儲節點的協議密鑰，簽名 48 字節）
+
+use crate::error::{AuditorError, Result};
+use base64::{engine::general_purpose, Engine as _};
+use fastcrypto::bls12381::min_pk::{BLS12381PublicKey, BLS12381Signature};
+use fastcrypto::ed25519::{Ed25519PublicKey, Ed25519Signature};
+use fastcrypto::traits::{ToFromBytes, VerifyingKey};
+
+/// 簽名消息的域分隔前綴
+pub const CHALLENGE_RESPONSE_DOMAIN: &[u8] = b"WALRUS_AUDIT_CHALLENGE_RESPONSE_V1";
+
+/// Ed25519 公鑰長度（bytes）
+pub const ED25519_PUBLIC_KEY_LEN: usize = 32;
+
+/// BLS12-381 min_pk 公鑰長度（bytes）
+pub const BLS12381_PUBLIC_KEY_LEN: usize = 96;
+
+/// 存儲節點公鑰
+#[derive(Debug, Clone)]
+pub enum NodePublicKey {
+    Ed25519(Ed25519PublicKey),
+    Bls12381(BLS12381PublicKey),
+}
+
+impl NodePublicKey {
+    /// 從原始公鑰字節解析，方案按長度識別
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let invalid = |e| AuditorError::Config(format!("Invalid storage node public key: {}", e));
+        match bytes.len() {
+            ED25519_PUBLIC_KEY_LEN => Ed25519PublicKey::from_bytes(bytes)
+                .map(Self::Ed25519)
+                .map_err(invalid),
+            BLS12381_PUBLIC_KEY_LEN => BLS12381PublicKey::from_bytes(bytes)
+                .map(Self::Bls12381)
+                .map_err(invalid),
+            len => Err(AuditorError::Config(format!(
+                "Invalid storage node public key length {} (expected {} for Ed25519 or {} for BLS12-381)",
+                len, ED25519_PUBLIC_KEY_LEN, BLS12381_PUBLIC_KEY_LEN
+            ))),
+        }
+    }
+
+    /// 從十六進制（可帶 `0x` 前綴）或 Base64 字符串解析
+    pub fn parse(encoded: &str) -> Result<Self> {
+        let encoded = encoded.trim();
+        let bytes = hex::decode(encoded.trim_start_matches("0x"))
+            .or_else(|_| general_purpose::STANDARD.decode(encoded))
+            .map_err(|_| {
+                AuditorError::Config(format!(
+                    "Storage node public key is neither hex nor Base64: {}",
+                    encoded
+                ))
+            })?;
+        Self::from_bytes(&bytes)
+    }
+
+    /// 簽名方案名稱
+    pub fn scheme(&self) -> &'static str {
+        match self {
+            Self::Ed25519(_) => "ed25519",
+            Self::Bls12381(_) => "bls12381",
+        }
+    }
+
+    /// 驗證 `signature` 是否為此公鑰對 `message` 的有效簽名；無法解析的簽名視為無效
+    pub fn verify(&self, message: &[u8], signature: &[u8]) -> bool {
+        match self {
+            Self::Ed25519(pk) => Ed25519Signature::from_bytes(signature)
+                .map(|sig| pk.verify(message, &sig).is_ok())
+                .unwrap_or(false),
+            Self::Bls12381(pk) => BLS12381Signature::from_bytes(signature)
+                .map(|sig| pk.verify(message, &sig).is_ok())
+                .unwrap_or(false),
+        }
+    }
+}
+
+/// 存儲節點簽名的挑戰響應消息（格式見模塊文檔）
+pub fn challenge_message(
+    blob_id: &str,
+    sliver_index: u64,
+    sliver_hash: &[u8],
+    timestamp: u64,
+) -> Vec<u8> {
+    let mut message = Vec::with_capacity(
+        CHALLENGE_RESPONSE_DOMAIN.len() + 4 + blob_id.len() + 8 + 4 + sliver_hash.len() + 8,
+    );
+    message.extend_from_slice(CHALLENGE_RESPONSE_DOMAIN);
+    message.extend_from_slice(&(blob_id.len() as u32).to_le_bytes());
+    message.extend_from_slice(blob_id.as_bytes());
+    message.extend_from_slice(&sliver_index.to_le_bytes());
+    message.extend_from_slice(&(sliver_hash.len() as u32).to_le_bytes());
+    message.extend_from_slice(sliver_hash);
+    message.extend_from_slice(&timestamp.to_le_bytes());
+    message
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fastcrypto::bls12381::min_pk::BLS12381KeyPair;
+    use fastcrypto::ed25519::Ed25519KeyPair;
+    use fastcrypto::traits::{KeyPair, Signer};
+
+    #[test]
+    fn test_ed25519_signature_roundtrip() {
+        let keypair = Ed25519KeyPair::generate(&mut rand::thread_rng());
+        let key = NodePublicKey::parse(&hex::encode(keypair.public().as_bytes())).unwrap();
+        assert_eq!(key.scheme(), "ed25519");
+
+        let message = challenge_message("0xabcd", 3, &[1u8; 32], 1_700_000_000);
+        let signature = keypair.sign(&message);
+        assert!(key.verify(&message, signature.as_bytes()));
+
+        let other = challenge_message("0xabcd", 4, &[1u8; 32], 1_700_000_000);
+        assert!(!key.verify(&other, signature.as_bytes()));
+        assert!(!key.verify(&message, &[0u8; 10]));
+    }
+
+    #[test]
+    fn test_bls12381_signature_roundtrip() {
+        let keypair = BLS12381KeyPair::generate(&mut rand::thread_rng());
+        let encoded = general_purpose::STANDARD.encode(keypair.public().as_bytes());
+        let key = NodePublicKey::parse(&encoded).unwrap();
+        assert_eq!(key.scheme(), "bls12381");
+
+        let message = challenge_message("0xabcd", 3, &[1u8; 32], 0);
+        let signature = keypair.sign(&message);
+        assert!(key.verify(&message, signature.as_bytes()));
+
+        let mut tampered = signature.as_bytes().to_vec();
+        tampered[5] ^= 0xff;
+        assert!(!key.verify(&message, &tampered));
+    }
+
+    #[test]
+    fn test_public_key_length_is_checked() {
+        assert!(NodePublicKey::from_bytes(&[7u8; 48]).is_err());
+        assert!(NodePublicKey::parse("not a key!").is_err());
+    }
+}
@@ -28,24 +28,81 @@
 //! 2. 向存儲節點發送挑戰（請求該 Sliver）
 //! 3. 存儲節點返回 Sliver 數據 + 默克爾證明
 //! 4. 審計員驗證：
-//!    - 計算 sliver_hash = SHA3-256(sliver_data)
-//!    - 使用默克爾證明驗證 sliver_hash 在默克爾樹中
+//!    - 按 [`LeafEncoding`] 由 sliver_data 計算葉子哈希
+//!    - 使用默克爾證明驗證葉子哈希在默克爾樹中
 //!    - 驗證計算的默克爾根與鏈上記錄的根匹配
 //! 5. 如果驗證通過，證明該 Sliver 完整且未被篡改
 //!
+//! # Recovery Symbol 挑戰
+//!
+//! 完整 sliver 挑戰需要傳輸整個 sliver；recovery symbol 挑戰（類型 2）只請求 sliver 的一個 symbol：
+//!
+//! - 每個 sliver 的 n 個 symbol 構成該 sliver 的哈希樹（葉子 = hash_leaf(symbol)），
+//!   樹根即 sliver 哈希，是 Blob 默克爾樹中該 sliver 的葉子哈希
+//! - 存儲節點返回 symbol、symbol 到 sliver 哈希的證明，以及 sliver 哈希到 Blob 根的證明
+//! - 審計員由 symbol 沿第一個證明推出 sliver 哈希，再以第二個證明對照鏈上根驗證
+//!   （見 [`RecoverySymbol::verify`]）
+//!
 //! # 與默克爾樹驗證的關係
 //!
 //! - Sliver 驗證依賴於默克爾證明
-//! - 默克爾樹的葉子節點 = SHA3-256(sliver_data)
+//! - 默克爾樹的葉子哈希由 sliver_data 按 [`LeafEncoding`] 計算，默認為 Walrus 的葉子哈希
+//!   `Blake2b-256(LEAF_PREFIX || sliver_data)`（[`hash_leaf`]）
 //! - 默克爾根存儲在 Sui 區塊鏈的 Blob 對象中
 //! - 這提供了從鏈上到存儲層的完整信任鏈
+//!
+//! # 葉子編碼
+//!
+//! 存儲節點可能以原始 sliver 數據或 sliver 的哈希構建默克爾樹：
+//!
+//! | [`LeafEncoding`] | 葉子哈希 |
+//! |------------------|----------|
+//! | `raw_data`（默認） | `Blake2b-256(LEAF_PREFIX \|\| sliver_data)` |
+//! | `sha3_hash` | `SHA3-256(sliver_data)`（預哈希，不再加前綴） |
+//! | `blake2b_hash` | `Blake2b-256(sliver_data)`（預哈希，不再加前綴） |
+//!
+//! 證明只在與構建時相同的編碼下通過驗證。[`Sliver::compute_hash`]（SHA3-256）是記入報告
+//! 與節點簽名的響應哈希，與葉子編碼無關
 
-use crate::crypto::merkle::{MerkleProof, MerkleRoot};
+use crate::crypto::merkle::{hash_leaf, MerkleProof, MerkleRoot};
 use crate::error::{AuditorError, Result};
+use fastcrypto::hash::{Blake2b256, HashFunction};
 use serde::{Deserialize, Serialize};
 use sha3::{Digest, Sha3_256};
 use tracing::{debug, error, info, warn};
 
+/// 支持的最大 Sliver 數量（n 的上限）
+///
+/// 索引全程使用 `u64`，不再受 `u16` 限制；上限只用於拒絕明顯錯誤的元數據
+/// （2^20 個葉子對應 20 層的默克爾證明）
+pub const MAX_SLIVERS: u64 = 1 << 20;
+
+/// 默克爾樹葉子的編碼：由 sliver 數據計算葉子哈希的方式（見模塊文檔）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LeafEncoding {
+    /// 以原始 sliver 數據為葉子：`hash_leaf(data)`（Walrus 慣例）
+    #[default]
+    RawData,
+
+    /// 以 sliver 的 SHA3-256 哈希為葉子哈希
+    Sha3Hash,
+
+    /// 以 sliver 的 Blake2b-256 哈希為葉子哈希
+    Blake2bHash,
+}
+
+impl LeafEncoding {
+    /// 計算 `data` 在默克爾樹中的葉子哈希
+    pub fn leaf_hash(&self, data: &[u8]) -> [u8; 32] {
+        match self {
+            LeafEncoding::RawData => hash_leaf(data),
+            LeafEncoding::Sha3Hash => Sha3_256::digest(data).into(),
+            LeafEncoding::Blake2bHash => Blake2b256::digest(data).digest,
+        }
+    }
+}
+
 /// Sliver 元數據（從 BlobMetadata 中提取）
 ///
 /// 包含驗證 Sliver 所需的上下文信息
@@ -62,15 +119,19 @@ pub struct SliverMetadata {
     /// - k = 原始數據片段數（恢復所需的最小片段數）
     /// - n = 總編碼片段數（n > k，提供冗餘）
     /// 例如：(10, 15) 表示 10 個數據片段 + 5 個冗餘片段
-    pub erasure_params: (usize, usize),
+    pub erasure_params: (u64, u64),
+
+    /// 默克爾樹葉子的編碼
+    #[serde(default)]
+    pub leaf_encoding: LeafEncoding,
 }
 
 impl SliverMetadata {
     /// 創建新的 Sliver 元數據
-    pub fn new(merkle_root: MerkleRoot, total_slivers: u64, k: usize, n: usize) -> Result<Self> {
+    pub fn new(merkle_root: MerkleRoot, total_slivers: u64, k: u64, n: u64) -> Result<Self> {
         validate_erasure_params(k, n)?;
 
-        if total_slivers as usize != n {
+        if total_slivers != n {
             error!(
                 "Total slivers ({}) does not match erasure coding n parameter ({})",
                 total_slivers, n
@@ -85,21 +146,28 @@ impl SliverMetadata {
             merkle_root,
             total_slivers,
             erasure_params: (k, n),
+            leaf_encoding: LeafEncoding::default(),
         })
     }
 
+    /// 設置默克爾樹葉子的編碼
+    pub fn with_leaf_encoding(mut self, leaf_encoding: LeafEncoding) -> Self {
+        self.leaf_encoding = leaf_encoding;
+        self
+    }
+
     /// 獲取數據片段數（k）
-    pub fn k(&self) -> usize {
+    pub fn k(&self) -> u64 {
         self.erasure_params.0
     }
 
     /// 獲取總片段數（n）
-    pub fn n(&self) -> usize {
+    pub fn n(&self) -> u64 {
         self.erasure_params.1
     }
 
     /// 獲取冗餘片段數（n - k）
-    pub fn redundancy_count(&self) -> usize {
+    pub fn redundancy_count(&self) -> u64 {
         self.n() - self.k()
     }
 
@@ -137,9 +205,10 @@ impl Sliver {
     /// # 驗證邏輯
     ///
     /// 1. 檢查 Sliver 索引是否在有效範圍內
-    /// 2. 計算 sliver_hash = SHA3-256(sliver.data)
-    /// 3. 使用 merkle_proof 驗證 sliver_hash 確實在默克爾樹中
-    /// 4. 驗證計算出的默克爾根與 metadata.merkle_root 匹配
+    /// 2. 檢查 merkle_proof 的葉子索引與 Sliver 索引一致
+    /// 3. 按 `metadata.leaf_encoding` 計算葉子哈希
+    /// 4. 使用 merkle_proof 驗證葉子哈希確實在默克爾樹中
+    /// 5. 驗證計算出的默克爾根與 metadata.merkle_root 匹配
     ///
     /// # 參數
     /// - `metadata`: Blob 的元數據（包含默克爾根和 Erasure Coding 參數）
@@ -192,17 +261,30 @@ impl Sliver {
             ));
         }
 
-        // 3. 計算 Sliver 的哈希值
-        let sliver_hash = self.compute_hash();
+        // 3. 證明必須針對本 Sliver 的索引：另一索引的合法證明不能冒充
+        if merkle_proof.leaf_index != self.index {
+            warn!(
+                "Merkle proof leaf index {} does not match sliver {}",
+                merkle_proof.leaf_index, self.index
+            );
+            return Ok(false);
+        }
+
+        // 4. 按葉子編碼計算葉子哈希
+        let leaf_hash = metadata.leaf_encoding.leaf_hash(&self.data);
         debug!(
-            "Sliver {} hash: {:02x?}",
+            "Sliver {} leaf hash ({:?}): {:02x?}",
             self.index,
-            &sliver_hash[..8] // 只打印前 8 字節
+            metadata.leaf_encoding,
+            &leaf_hash[..8] // 只打印前 8 字節
         );
 
-        // 4. 使用默克爾證明驗證
-        // 注意：MerkleProof.verify() 內部會計算哈希並驗證整個路徑
-        let verified = merkle_proof.verify(&self.data, &metadata.merkle_root);
+        // 5. 使用默克爾證明驗證葉子哈希
+        let verified = merkle_proof.verify_leaf_hash(
+            &leaf_hash,
+            &metadata.merkle_root,
+            metadata.total_slivers,
+        );
 
         if verified {
             info!(
@@ -223,7 +305,8 @@ impl Sliver {
 
     /// 計算 Sliver 數據的 SHA3-256 哈希
     ///
-    /// 這個哈希值應該是默克爾樹的葉子節點
+    /// 作為響應哈希記入報告並由節點簽名覆蓋（見 [`crate::crypto::node_signature`]）；
+    /// 不一定是默克爾樹的葉子哈希，葉子哈希見 [`LeafEncoding::leaf_hash`]
     pub fn compute_hash(&self) -> [u8; 32] {
         let mut hasher = Sha3_256::new();
         hasher.update(&self.data);
@@ -267,12 +350,124 @@ impl Sliver {
     }
 }
 
+/// Recovery symbol（從存儲節點接收）
+///
+/// Sliver 哈希樹的一個葉子，每個 sliver 有 n 個 symbol
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecoverySymbol {
+    /// 所屬 Sliver 的索引（0 到 n-1）
+    pub sliver_index: u64,
+
+    /// Symbol 在 sliver 哈希樹中的索引（0 到 n-1）
+    pub symbol_index: u64,
+
+    /// Symbol 原始字節數據
+    pub data: Vec<u8>,
+}
+
+impl RecoverySymbol {
+    /// 創建新的 Recovery symbol
+    pub fn new(sliver_index: u64, symbol_index: u64, data: Vec<u8>) -> Self {
+        Self {
+            sliver_index,
+            symbol_index,
+            data,
+        }
+    }
+
+    /// 驗證 symbol 屬於該 Blob
+    ///
+    /// # 驗證邏輯
+    ///
+    /// 1. 檢查 Sliver 與 symbol 索引是否在有效範圍內
+    /// 2. 由 `hash_leaf(symbol)` 沿 `symbol_proof` 推出 sliver 哈希（n 個葉子的 sliver 哈希樹的根）
+    /// 3. 以 `sliver_proof` 驗證 sliver 哈希是 Blob 默克爾樹的葉子，且根與 metadata.merkle_root 匹配
+    ///
+    /// 兩個證明的葉子索引必須分別等於 `symbol_index` 與 `sliver_index`，
+    /// 否則節點可以用其他 symbol 或 sliver 的證明應答挑戰
+    ///
+    /// # 返回
+    /// - `Ok(true)`: 驗證通過
+    /// - `Ok(false)`: 證明與挑戰不符，或 symbol 被篡改
+    /// - `Err(_)`: 索引超出範圍或數據為空
+    pub fn verify(
+        &self,
+        metadata: &SliverMetadata,
+        symbol_proof: &MerkleProof,
+        sliver_proof: &MerkleProof,
+    ) -> Result<bool> {
+        validate_sliver_index(self.sliver_index, metadata.total_slivers)?;
+        if self.symbol_index >= metadata.n() {
+            return Err(AuditorError::InvalidSliver(format!(
+                "symbol index {} out of range (n = {})",
+                self.symbol_index,
+                metadata.n()
+            )));
+        }
+        if self.data.is_empty() {
+            error!(
+                "Recovery symbol {} of sliver {} has empty data",
+                self.symbol_index, self.sliver_index
+            );
+            return Err(AuditorError::InvalidSliver(
+                "empty recovery symbol data".to_string(),
+            ));
+        }
+
+        if symbol_proof.leaf_index != self.symbol_index
+            || sliver_proof.leaf_index != self.sliver_index
+        {
+            warn!(
+                "Recovery symbol proof indices ({}, {}) do not match challenge ({}, {})",
+                sliver_proof.leaf_index,
+                symbol_proof.leaf_index,
+                self.sliver_index,
+                self.symbol_index
+            );
+            return Ok(false);
+        }
+
+        let verified = symbol_proof
+            .root_from_leaf_hash(&hash_leaf(&self.data), metadata.n())
+            .is_some_and(|sliver_hash| {
+                sliver_proof.verify_leaf_hash(
+                    &sliver_hash,
+                    &metadata.merkle_root,
+                    metadata.total_slivers,
+                )
+            });
+
+        if verified {
+            info!(
+                "Recovery symbol {} of sliver {} verification PASSED ({} bytes)",
+                self.symbol_index,
+                self.sliver_index,
+                self.data.len()
+            );
+        } else {
+            warn!(
+                "Recovery symbol {} of sliver {} verification FAILED",
+                self.symbol_index, self.sliver_index
+            );
+        }
+
+        Ok(verified)
+    }
+
+    /// 計算 symbol 數據的 SHA3-256 哈希（與 [`Sliver::compute_hash`] 相同的算法，記入報告）
+    pub fn compute_hash(&self) -> [u8; 32] {
+        let mut hasher = Sha3_256::new();
+        hasher.update(&self.data);
+        hasher.finalize().into()
+    }
+}
+
 /// Erasure Coding 參數驗證
 ///
 /// 檢查 (k, n) 參數是否符合以下規則：
 /// - k >= 1（至少有一個原始片段）
 /// - n > k（必須有冗餘編碼，否則無法提供容錯）
-/// - n <= 1000（合理的上限，避免過度冗餘）
+/// - n <= [`MAX_SLIVERS`]（拒絕明顯錯誤的元數據）
 /// - k <= n（邏輯一致性）
 ///
 /// # 參數
@@ -285,13 +480,14 @@ impl Sliver {
 ///
 /// // 有效參數
 /// assert!(validate_erasure_params(10, 15).is_ok());
+/// assert!(validate_erasure_params(334, 70_001).is_ok()); // n 可超過 u16::MAX
 ///
 /// // 無效參數
 /// assert!(validate_erasure_params(0, 10).is_err());  // k 不能為 0
 /// assert!(validate_erasure_params(10, 5).is_err());  // n 必須大於 k
-/// assert!(validate_erasure_params(10, 2000).is_err()); // n 過大
+/// assert!(validate_erasure_params(10, 2_000_000).is_err()); // n 過大
 /// ```
-pub fn validate_erasure_params(k: usize, n: usize) -> Result<()> {
+pub fn validate_erasure_params(k: u64, n: u64) -> Result<()> {
     if k == 0 {
         error!("Erasure coding k parameter cannot be 0");
         return Err(AuditorError::InvalidSliver(
@@ -310,11 +506,11 @@ pub fn validate_erasure_params(k: usize, n: usize) -> Result<()> {
         )));
     }
 
-    if n > 1000 {
-        error!("Erasure coding n parameter ({}) exceeds maximum (1000)", n);
+    if n > MAX_SLIVERS {
+        error!("Erasure coding n parameter ({}) exceeds maximum ({})", n, MAX_SLIVERS);
         return Err(AuditorError::InvalidSliver(format!(
-            "n ({}) exceeds maximum (1000)",
-            n
+            "n ({}) exceeds maximum ({})",
+            n, MAX_SLIVERS
         )));
     }
 
@@ -322,6 +518,23 @@ pub fn validate_erasure_params(k: usize, n: usize) -> Result<()> {
     Ok(())
 }
 
+/// 檢查 Sliver 索引是否落在 `0..n` 內
+///
+/// 索引進入系統的每一處（挑戰生成、響應解析、報告反序列化）都以此校驗，
+/// 超出範圍時返回錯誤而不是截斷或取模
+///
+/// # 錯誤
+/// - `index >= n`: 返回 `InvalidSliver` 錯誤
+pub fn validate_sliver_index(index: u64, n: u64) -> Result<()> {
+    if index >= n {
+        return Err(AuditorError::InvalidSliver(format!(
+            "sliver index {} out of range (n = {})",
+            index, n
+        )));
+    }
+    Ok(())
+}
+
 /// 計算建議的挑戰數量
 ///
 /// 基於 Erasure Coding 參數和期望的置信度，計算審計時應該挑戰的 Sliver 數量。
@@ -376,6 +589,7 @@ pub fn calculate_challenge_count(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::crypto::merkle::{MerkleTree, MerkleTreeVersion};
 
     #[test]
     fn test_erasure_params_validation() {
@@ -383,12 +597,14 @@ mod tests {
         assert!(validate_erasure_params(5, 10).is_ok());
         assert!(validate_erasure_params(10, 15).is_ok());
         assert!(validate_erasure_params(1, 2).is_ok());
+        assert!(validate_erasure_params(334, 70_001).is_ok()); // n > u16::MAX
+        assert!(validate_erasure_params(10, MAX_SLIVERS).is_ok());
 
         // 無效參數
         assert!(validate_erasure_params(0, 10).is_err()); // k = 0
         assert!(validate_erasure_params(10, 5).is_err()); // n < k
         assert!(validate_erasure_params(10, 10).is_err()); // n = k (無冗餘)
-        assert!(validate_erasure_params(10, 2000).is_err()); // n 過大
+        assert!(validate_erasure_params(10, MAX_SLIVERS + 1).is_err()); // n 過大
     }
 
     #[test]
@@ -426,6 +642,14 @@ mod tests {
         assert!(metadata.is_valid_index(14));
         assert!(!metadata.is_valid_index(15));
         assert!(!metadata.is_valid_index(100));
+
+        // n 超過 u16 範圍時索引不截斷
+        let metadata = SliverMetadata::new(merkle_root, 70_001, 334, 70_001).unwrap();
+        assert!(metadata.is_valid_index(70_000));
+        assert!(!metadata.is_valid_index(70_001));
+        assert!(validate_sliver_index(70_000, 70_001).is_ok());
+        assert!(validate_sliver_index(70_001, 70_001).is_err());
+        assert!(validate_sliver_index(u64::from(u16::MAX) + 1, 15).is_err());
     }
 
     #[test]
@@ -505,6 +729,143 @@ mod tests {
         assert!(result.is_err());
     }
 
+    const LEAF_ENCODINGS: [LeafEncoding; 3] = [
+        LeafEncoding::RawData,
+        LeafEncoding::Sha3Hash,
+        LeafEncoding::Blake2bHash,
+    ];
+
+    #[test]
+    fn test_leaf_encoding_hashes() {
+        let data = b"sliver bytes";
+        assert_eq!(LeafEncoding::RawData.leaf_hash(data), hash_leaf(data));
+        assert_eq!(
+            LeafEncoding::Sha3Hash.leaf_hash(data),
+            Sliver::new(0, data.to_vec()).compute_hash()
+        );
+        assert_eq!(
+            LeafEncoding::Blake2bHash.leaf_hash(data),
+            Blake2b256::digest(data).digest
+        );
+        assert_ne!(
+            LeafEncoding::RawData.leaf_hash(data),
+            LeafEncoding::Blake2bHash.leaf_hash(data)
+        );
+        assert_eq!(
+            serde_json::to_value(LeafEncoding::Blake2bHash).unwrap(),
+            serde_json::json!("blake2b_hash")
+        );
+    }
+
+    #[test]
+    fn test_sliver_verifies_only_under_matching_leaf_encoding() {
+        let slivers: Vec<Vec<u8>> = (0..5u8).map(|i| vec![i; 32 + i as usize]).collect();
+
+        for built_with in LEAF_ENCODINGS {
+            let leaves = slivers.iter().map(|data| built_with.leaf_hash(data));
+            let tree =
+                MerkleTree::from_leaf_hashes(leaves.collect(), MerkleTreeVersion::V2).unwrap();
+            let proof = tree.generate_proof(3).unwrap();
+            let sliver = Sliver::new(3, slivers[3].clone());
+
+            for verified_with in LEAF_ENCODINGS {
+                let metadata = SliverMetadata::new(tree.root(), 5, 3, 5)
+                    .unwrap()
+                    .with_leaf_encoding(verified_with);
+                assert_eq!(
+                    sliver.verify(&metadata, &proof).unwrap(),
+                    built_with == verified_with,
+                    "tree built with {:?}, verified with {:?}",
+                    built_with,
+                    verified_with
+                );
+            }
+
+            // 篡改的數據在匹配的編碼下同樣失敗
+            let metadata = SliverMetadata::new(tree.root(), 5, 3, 5)
+                .unwrap()
+                .with_leaf_encoding(built_with);
+            let tampered = Sliver::new(3, vec![0xff; 35]);
+            assert!(!tampered.verify(&metadata, &proof).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_sliver_verify_rejects_proof_for_other_index() {
+        let slivers: Vec<Vec<u8>> = (0..5u8).map(|i| vec![i; 32]).collect();
+        let leaves = slivers.iter().map(|data| hash_leaf(data)).collect();
+        let tree = MerkleTree::from_leaf_hashes(leaves, MerkleTreeVersion::V2).unwrap();
+        let metadata = SliverMetadata::new(tree.root(), 5, 3, 5).unwrap();
+
+        // 索引 1 的數據與證明本身合法，但不能用來應答索引 3 的挑戰
+        let proof = tree.generate_proof(1).unwrap();
+        let honest = Sliver::new(1, slivers[1].clone());
+        let substituted = Sliver::new(3, slivers[1].clone());
+        assert!(honest.verify(&metadata, &proof).unwrap());
+        assert!(!substituted.verify(&metadata, &proof).unwrap());
+    }
+
+    #[test]
+    fn test_metadata_without_leaf_encoding_defaults_to_raw_data() {
+        let json = serde_json::json!({
+            "merkle_root": vec![0u8; 32],
+            "total_slivers": 15,
+            "erasure_params": [10, 15],
+        });
+        let metadata: SliverMetadata = serde_json::from_value(json).unwrap();
+        assert_eq!(metadata.leaf_encoding, LeafEncoding::RawData);
+    }
+
+    /// 構建 sliver 哈希樹以 symbol 樹根為葉子的 Blob，返回 (元數據, symbol 樹, Blob 樹)
+    fn symbol_fixture() -> (SliverMetadata, Vec<MerkleTree>, MerkleTree) {
+        let n = 5;
+        let symbol_trees: Vec<MerkleTree> = (0..n as u8)
+            .map(|sliver| {
+                let symbols = (0..n as u8).map(|symbol| hash_leaf(&[sliver, symbol]));
+                MerkleTree::from_leaf_hashes(symbols.collect(), MerkleTreeVersion::V2).unwrap()
+            })
+            .collect();
+        let blob_tree = MerkleTree::from_leaf_hashes(
+            symbol_trees.iter().map(MerkleTree::root).collect(),
+            MerkleTreeVersion::V2,
+        )
+        .unwrap();
+        let metadata = SliverMetadata::new(blob_tree.root(), n, 3, n).unwrap();
+        (metadata, symbol_trees, blob_tree)
+    }
+
+    #[test]
+    fn test_recovery_symbol_verify() {
+        let (metadata, symbol_trees, blob_tree) = symbol_fixture();
+        let symbol_proof = symbol_trees[2].generate_proof(4).unwrap();
+        let sliver_proof = blob_tree.generate_proof(2).unwrap();
+
+        let symbol = RecoverySymbol::new(2, 4, vec![2, 4]);
+        assert!(symbol.verify(&metadata, &symbol_proof, &sliver_proof).unwrap());
+
+        // 篡改的 symbol
+        let tampered = RecoverySymbol::new(2, 4, vec![2, 5]);
+        assert!(!tampered.verify(&metadata, &symbol_proof, &sliver_proof).unwrap());
+
+        // 另一個 symbol 的證明不能應答本挑戰
+        let other_proof = symbol_trees[2].generate_proof(3).unwrap();
+        assert!(!symbol.verify(&metadata, &other_proof, &sliver_proof).unwrap());
+
+        // 另一個 sliver 的 symbol 樹
+        let wrong_tree = symbol_trees[1].generate_proof(4).unwrap();
+        assert!(!symbol.verify(&metadata, &wrong_tree, &sliver_proof).unwrap());
+    }
+
+    #[test]
+    fn test_recovery_symbol_invalid_input() {
+        let (metadata, _, _) = symbol_fixture();
+        let proof = MerkleProof::new(vec![], 0);
+
+        assert!(RecoverySymbol::new(5, 0, vec![1]).verify(&metadata, &proof, &proof).is_err());
+        assert!(RecoverySymbol::new(0, 5, vec![1]).verify(&metadata, &proof, &proof).is_err());
+        assert!(RecoverySymbol::new(0, 0, vec![]).verify(&metadata, &proof, &proof).is_err());
+    }
+
     #[test]
     fn test_sliver_verify_empty_data() {
         let merkle_root = [0u8; 32];
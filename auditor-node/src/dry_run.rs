@@ -0,0 +1,167 @@
+//! 試運行（`--dry-run` / `dry_run = true`）
+//!
+//! 審計、Dilithium3 簽名與 Seal 加密（已啟用時）照常執行，只有對外寫入被替換：
+//! [`NoopRecorder`] 同時實現 [`ReportUploader`] 與 [`ChainSubmitter`]，
+//! 記錄本應發送的負載的大小、SHA-256 與目標端點，並把負載寫入 `dry_run_dir`：
+//!
+//! ```text
+//! dry_run_dir/
+//! ├── upload-<sha256>.bin             本應上傳到 Walrus Publisher 的字節（加密時為密文）
+//! └── submission-<report_blob_id>.json 本應提交到 Sui 的審計記錄參數
+//! ```
+//!
+//! 上傳返回由負載 SHA-256 得出的佔位 Blob ID（URL-safe Base64），
+//! 提交返回寫入的文件路徑，因此試運行與正常運行產生相同的已簽名報告。
+
+use crate::chain_types::AuditRecordParams;
+use crate::error::Result;
+use crate::pipeline::{ChainSubmitter, ReportUploader};
+use crate::types::AuditorConfig;
+use async_trait::async_trait;
+use base64::{engine::general_purpose, Engine as _};
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use tracing::info;
+
+/// 記錄而不發送報告上傳與鏈上提交
+#[derive(Debug, Clone)]
+pub struct NoopRecorder {
+    dir: PathBuf,
+    upload_target: String,
+    submit_target: String,
+}
+
+impl NoopRecorder {
+    /// 創建記錄器，負載寫入 `dir`（不存在時創建）
+    ///
+    /// `upload_target` 與 `submit_target` 只用於日誌與記錄文件
+    pub fn new(
+        dir: impl Into<PathBuf>,
+        upload_target: impl Into<String>,
+        submit_target: impl Into<String>,
+    ) -> Result<Self> {
+        let dir = dir.into();
+        std::fs::create_dir_all(&dir)?;
+        Ok(Self {
+            dir,
+            upload_target: upload_target.into(),
+            submit_target: submit_target.into(),
+        })
+    }
+
+    /// 按節點配置創建：寫入 `dry_run_dir`，目標為 Publisher 與 Sui RPC
+    pub fn from_config(config: &AuditorConfig) -> Result<Self> {
+        let submit_target = match &config.audit_system_package_id {
+            Some(package_id) => format!("{} (package {})", config.sui_rpc_url, package_id),
+            None => config.sui_rpc_url.clone(),
+        };
+        Self::new(
+            &config.dry_run_dir,
+            format!(
+                "{}/v1/blobs",
+                config.walrus_publisher_url.trim_end_matches('/')
+            ),
+            submit_target,
+        )
+    }
+
+    /// 負載寫入的目錄
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+}
+
+#[async_trait]
+impl ReportUploader for NoopRecorder {
+    async fn upload(&self, data: &[u8]) -> Result<String> {
+        let digest = Sha256::digest(data);
+        let path = self.dir.join(format!("upload-{}.bin", hex::encode(digest)));
+        std::fs::write(&path, data)?;
+        info!(
+            "DRY RUN: would upload {} bytes (sha256 {}) to {}; wrote {}",
+            data.len(),
+            hex::encode(digest),
+            self.upload_target,
+            path.display()
+        );
+        Ok(general_purpose::URL_SAFE_NO_PAD.encode(digest))
+    }
+}
+
+/// 返回記錄文件路徑（而非交易摘要）
+#[async_trait]
+impl ChainSubmitter for NoopRecorder {
+    async fn submit_record(
+        &self,
+        params: &AuditRecordParams,
+        report_blob_id: &str,
+    ) -> Result<Option<String>> {
+        let record = serde_json::to_vec_pretty(&json!({
+            "target": self.submit_target,
+            "function": AuditRecordParams::FUNCTION,
+            "report_blob_id": report_blob_id,
+            "params": params,
+        }))?;
+        let path = self.dir.join(format!("submission-{}.json", report_blob_id));
+        std::fs::write(&path, &record)?;
+        info!(
+            "DRY RUN: would submit {} ({} bytes, sha256 {}) to {}; wrote {}",
+            AuditRecordParams::FUNCTION,
+            record.len(),
+            hex::encode(Sha256::digest(&record)),
+            self.submit_target,
+            path.display()
+        );
+        Ok(Some(path.display().to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chain_types::{MoveId, MoveU256};
+
+    #[tokio::test]
+    async fn test_records_upload_and_submission() {
+        let dir = tempfile::tempdir().unwrap();
+        let recorder = NoopRecorder::new(
+            dir.path().join("dry-run"),
+            "http://publisher/v1/blobs",
+            "http://sui",
+        )
+        .unwrap();
+
+        let blob_id = recorder.upload(b"report").await.unwrap();
+        assert_eq!(
+            blob_id,
+            general_purpose::URL_SAFE_NO_PAD.encode(Sha256::digest(b"report"))
+        );
+        let upload = recorder.dir().join(format!(
+            "upload-{}.bin",
+            hex::encode(Sha256::digest(b"report"))
+        ));
+        assert_eq!(std::fs::read(upload).unwrap(), b"report");
+
+        let params = AuditRecordParams {
+            blob_id: MoveU256::from_blob_id(&blob_id).unwrap(),
+            blob_object_id: MoveId::from_hex(&format!("0x{}", "1".repeat(64))).unwrap(),
+            challenge_epoch: 1,
+            total_challenges: 10,
+            successful_verifications: 10,
+            integrity_hash: vec![0; 32],
+            pqc_signature: vec![1, 2, 3],
+            pqc_algorithm: 3,
+        };
+        let path = recorder
+            .submit_record(&params, &blob_id)
+            .await
+            .unwrap()
+            .unwrap();
+        let record: serde_json::Value =
+            serde_json::from_slice(&std::fs::read(&path).unwrap()).unwrap();
+        assert_eq!(record["report_blob_id"], blob_id.as_str());
+        assert_eq!(record["target"], "http://sui");
+        assert_eq!(record["params"], serde_json::to_value(&params).unwrap());
+    }
+}
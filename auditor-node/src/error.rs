@@ -3,6 +3,7 @@
 //! 本模塊定義了審計節點運行過程中可能遇到的所有錯誤類型，
 //! 使用 thiserror crate 提供良好的錯誤鏈和上下文信息。
 
+use pqc_signer::error::PqcError;
 use thiserror::Error;
 
 /// 審計節點錯誤類型
@@ -22,12 +23,50 @@ pub enum AuditorError {
     #[error("Sui client error: {0}")]
     SuiClient(String),
 
+    /// Move 中止
+    ///
+    /// 當鏈上交易因合約 `abort` 失敗時返回此錯誤；`reason` 為合約中的錯誤常量名（未知時為 `unknown`）
+    #[error("Sui client error: {module}::{function} aborted with code {code} ({reason})")]
+    MoveAbort {
+        /// Move 模塊名
+        module: String,
+        /// 中止的函數名
+        function: String,
+        /// 中止碼
+        code: u64,
+        /// 錯誤常量名
+        reason: &'static str,
+    },
+
+    /// Gas 不足
+    ///
+    /// 當簽名者沒有足夠餘額的 SUI 代幣支付 gas budget，或交易執行時 gas 耗盡時返回此錯誤
+    #[error("Insufficient gas: {0}")]
+    InsufficientGas(String),
+
+    /// 對象版本衝突
+    ///
+    /// 當交易引用的對象版本已被其他交易消耗（如 gas 代幣被並發使用）時返回此錯誤；重新構建交易後可重試
+    #[error("Object version conflict: {0}")]
+    ObjectVersionConflict(String),
+
     /// 存儲節點不可達
     ///
     /// 當無法連接到 Walrus 存儲節點時返回此錯誤
     #[error("Storage node unreachable: {0}")]
     StorageNodeUnreachable(String),
 
+    /// 存儲節點過載
+    ///
+    /// 當存儲節點返回 429 或 503 時返回此錯誤，可在 `retry_after` 之後重試
+    #[error("Storage node overloaded: {message}")]
+    StorageNodeOverloaded {
+        /// 錯誤信息（狀態碼與清洗後的響應體）
+        message: String,
+        /// 響應 `Retry-After` 頭要求的等待時間
+        retry_after: Option<std::time::Duration>,
+    },
+
     /// 默克爾證明驗證失敗
     ///
     /// 當 sliver 的默克爾證明無法通過驗證時返回此錯誤
@@ -35,6 +74,30 @@ pub enum AuditorError {
     #[error("Merkle proof verification failed")]
     MerkleVerificationFailed,
 
+    /// 無效的十六進制摘要
+    ///
+    /// 當待比較的哈希、默克爾根或摘要不是合法的十六進制字符串時返回此錯誤
+    #[error("Invalid hex digest: {0}")]
+    InvalidDigest(String),
+
+    /// 無效的 Blob ID
+    ///
+    /// 當 Blob ID 既不是 32 字節的 URL-safe Base64，也不是 `0x` 十六進制 `u256` 時返回此錯誤
+    #[error("Invalid blob ID: {0}")]
+    InvalidBlobId(String),
+
+    /// Blob 標識解析失敗
+    ///
+    /// 當 Blob 對象 ID 無法解析為 Blob ID，或 Blob ID 找不到對應的鏈上對象時返回此錯誤；
+    /// `step` 說明失敗的解析步驟
+    #[error("Failed to {step}: {reason}")]
+    BlobResolution {
+        /// 失敗的步驟（如 `read blob object 0x… from Sui`）
+        step: String,
+        /// 失敗原因
+        reason: String,
+    },
+
     /// 無效的 Sliver 數據
     ///
     /// 當 sliver 數據格式不正確或無法解析時返回此錯誤
@@ -59,6 +122,49 @@ pub enum AuditorError {
     #[error("Seal encryption error: {0}")]
     SealEncryption(String),
 
+    /// Seal 解密錯誤
+    ///
+    /// 當解密請求被 Seal API 拒絕或響應無法還原為報告時返回此錯誤
+    #[error("Seal decryption error: {0}")]
+    SealDecryption(String),
+
+    /// Seal 訪問被拒絕
+    ///
+    /// 當請求者不在報告訪問策略的允許名單中時返回此錯誤；重試不會成功
+    #[error("Seal access denied by policy: {0}")]
+    SealAccessDenied(String),
+
+    /// Seal 密鑰服務器不足
+    ///
+    /// 當可用的密鑰服務器少於門檻值、無法取回解密密鑰時返回此錯誤
+    #[error("Not enough Seal key servers: {0}")]
+    SealKeyServers(String),
+
+    /// Seal API 暫時不可用
+    ///
+    /// 當 Seal API 返回 5xx 或 429、請求超時或無法連接時返回此錯誤，可按重試策略重試
+    #[error("Seal API unavailable: {0}")]
+    SealUnavailable(String),
+
+    /// Seal API 拒絕請求
+    ///
+    /// 當 Seal API 返回 4xx（429 除外）或 `success: false` 時返回此錯誤，
+    /// 通常是 identity 或 Package ID 不被接受；重試不會成功
+    #[error("Seal API rejected the request ({status}): {message}")]
+    SealRejected { status: u16, message: String },
+
+    /// Seal API 響應格式錯誤
+    ///
+    /// 當響應無法解析、缺少字段或密文編碼無效時返回此錯誤
+    #[error("Unexpected Seal API response: {0}")]
+    SealProtocol(String),
+
+    /// 本地報告加密錯誤
+    ///
+    /// 當 Seal 不可用時的本地 AES-256-GCM 加密、密鑰保存或解密失敗時返回此錯誤
+    #[error("Local report encryption error: {0}")]
+    LocalEncryption(String),
+
     /// HTTP 請求錯誤
     ///
     /// 當向存儲節點發送 HTTP 請求失敗時返回此錯誤
@@ -83,6 +189,96 @@ pub enum AuditorError {
     #[error("Keystore error: {0}")]
     Keystore(String),
 
+    /// 密鑰對不匹配
+    ///
+    /// 當密鑰庫的公鑰與私鑰格式正確、但不屬於同一密鑰對（如混用了不同審計員的密鑰文件）時返回此錯誤
+    #[error("Key pair mismatch: public/secret key files do not belong together ({0})")]
+    KeyPairMismatch(String),
+
+    /// 審計員未註冊
+    ///
+    /// 當註冊預檢發現審計員地址不在 AuditorRegistry 中時返回此錯誤；
+    /// 此時提交的報告會在鏈上失敗，因此拒絕開始審計（本地測試可用 `--allow-unregistered` 跳過）
+    #[error(
+        "Auditor {auditor} is not registered in AuditorRegistry {registry_id}: register first with \
+         `sui client call --package {package_id} --module auditor_registry --function register_auditor \
+         --args {registry_id} <STAKE_COIN_ID> 0x<PQC_PUBLIC_KEY_HEX>` (public key from \
+         `auditor-node show-key --keystore <DIR>`), or pass --allow-unregistered for local testing"
+    )]
+    AuditorNotRegistered {
+        /// 審計員地址
+        auditor: String,
+        /// 審計系統合約的 Package ID
+        package_id: String,
+        /// AuditorRegistry 共享對象的 ID
+        registry_id: String,
+    },
+
+    /// 主機資源不足
+    ///
+    /// 當磁盤空間或內存不足以安全執行審計、且策略要求拒絕時返回此錯誤
+    #[error("Insufficient resources: {0}")]
+    InsufficientResources(String),
+
+    /// 信任庫錯誤
+    ///
+    /// 當審計員出示的公鑰與信任庫記錄不一致，或信任庫文件無法讀寫時返回此錯誤
+    #[error("Trust store error: {0}")]
+    Trust(String),
+
+    /// 鏈上 ABI 錯誤
+    ///
+    /// 當交易參數無法表示為合約要求的 Move 類型（計數溢出 u16、哈希長度錯誤等）時返回此錯誤
+    #[error("Chain ABI error: {0}")]
+    ChainAbi(String),
+
+    /// 不支持的鏈上簽名算法
+    ///
+    /// 當報告的 `pqc_algorithm` 沒有對應的 `audit_core` 算法編號（合約會以
+    /// `E_INVALID_SIGNATURE_ALGORITHM` 中止交易）時返回此錯誤
+    #[error("PQC algorithm {0} has no audit_core algorithm ID")]
+    UnsupportedPqcAlgorithm(u8),
+
+    /// 挑戰承諾錯誤
+    ///
+    /// 當報告公開的挑戰集與此前的承諾不符，或承諾無法寫入日誌時返回此錯誤
+    #[error("Challenge commitment error: {0}")]
+    Commitment(String),
+
+    /// 聯署錯誤
+    ///
+    /// 當聯署請求的負載與摘要不符，或聯署簽名無法附加到報告時返回此錯誤
+    #[error("Co-signing error: {0}")]
+    Cosign(String),
+
+    /// 報告包錯誤
+    ///
+    /// 當 `.warb` 報告包無法讀寫、成員缺失或損壞、清單與報告不符或簽名無效時返回此錯誤
+    #[error("Report bundle error: {0}")]
+    Bundle(String),
+
+    /// 不支持的報告版本
+    ///
+    /// 當報告記錄的 `schema_version` 高於本構建支持的版本（由更新的構建生成）時返回此錯誤
+    #[error("Unsupported report schema version {found} (this build supports up to {supported})")]
+    UnsupportedReportVersion {
+        /// 報告記錄的版本
+        found: u32,
+        /// 本構建支持的最高版本
+        supported: u32,
+    },
+
+    /// 熔斷打開
+    ///
+    /// 當端點近期錯誤率超過閾值、調用在發出前被熔斷器拒絕時返回此錯誤
+    #[error("Circuit open for {endpoint}, retry after {retry_after_secs}s")]
+    CircuitOpen {
+        /// 被熔斷的端點
+        endpoint: String,
+        /// 距離允許探測的秒數
+        retry_after_secs: u64,
+    },
+
     /// 通用錯誤
     ///
     /// 用於包裝其他未分類的錯誤
@@ -110,8 +306,15 @@ impl From<bincode::Error> for AuditorError {
 }
 
 /// 從 PQC 錯誤轉換
-impl From<pqc_signer::error::PqcError> for AuditorError {
-    fn from(err: pqc_signer::error::PqcError) -> Self {
-        AuditorError::PqcSignature(err.to_string())
+///
+/// 未加載密鑰歸為密鑰庫錯誤，密鑰對不匹配與 I/O 錯誤保持原樣；其他錯誤在信息後附上錯誤碼，便於日誌檢索
+impl From<PqcError> for AuditorError {
+    fn from(err: PqcError) -> Self {
+        match err {
+            PqcError::IoError(e) => AuditorError::Io(e),
+            PqcError::KeyNotInitialized(_) => AuditorError::Keystore(err.to_string()),
+            PqcError::KeyPairMismatch => AuditorError::KeyPairMismatch(err.to_string()),
+            _ => AuditorError::PqcSignature(format!("{} [{}]", err, err.code())),
+        }
     }
 }
@@ -0,0 +1,577 @@
+//! 審計歷史與跨 Blob 內容去重
+//!
+//! 同一份內容可能以多個 Blob ID 存儲。對每個副本都執行完整挑戰是重複勞動，
+//! 因此審計歷史按內容哈希（SHA-256）索引已簽名的報告：
+//!
+//! 1. 下載並計算哈希後，查詢新鮮度窗口內是否有**其他** Blob ID 的完整審計
+//! 2. 命中時跳過完整挑戰階段，只做少量 chunk 抽查，
+//!    並在 `AuditData::deduplicated_from` 中記錄去重依據
+//! 3. 驗證者可按 `report_digest` 找到被引用的完整審計報告
+//!
+//! 只有完整審計會被引用，去重審計本身不會成為去重依據，因此引用鏈長度始終為 1。
+//! Blob 被所有者刪除後，其條目由 [`AuditHistory::forget_blob`] 移除，不再被引用。
+//!
+//! 歷史以 JSONL 追加寫入，每行一條 [`HistoryEntry`]，啟動時重新加載；
+//! 文件按 `[rotation.outputs.history]` 輪轉（見 [`crate::rotating_writer`]）。
+//! 啟用 chunk 過濾器時，條目同時保存該次審計的 [`ChunkFilter`]，
+//! 供同一 Blob 的下次審計做快速比對（見 [`AuditHistory::latest_filter`]）。
+
+use crate::chunk_filter::ChunkFilter;
+use crate::error::Result;
+use crate::integrity::VerificationStatus;
+use crate::rotating_writer::{self, RotatingWriter, RotationConfig, RotationStats};
+use crate::types::AuditReport;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use tracing::{debug, warn};
+
+/// `[rotation.outputs]` 中歷史文件的名稱
+pub const ROTATION_OUTPUT: &str = "history";
+
+/// 默認新鮮度窗口：24 小時
+pub const DEFAULT_FRESHNESS_WINDOW_SECS: u64 = 24 * 60 * 60;
+
+/// 默認去重抽查的 chunk 數
+pub const DEFAULT_SPOT_CHECK_CHALLENGES: u16 = 2;
+
+/// 去重配置
+///
+/// ```toml
+/// [dedup]
+/// enabled = true
+/// freshness_window_secs = 86400
+/// spot_check_challenges = 2
+/// history_path = "./audit_history.jsonl"
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DedupConfig {
+    /// 是否啟用去重
+    pub enabled: bool,
+
+    /// 被引用的完整審計必須在此時間內完成（秒）
+    pub freshness_window_secs: u64,
+
+    /// 去重時抽查的 chunk 數（至少 1）
+    pub spot_check_challenges: u16,
+
+    /// 審計歷史文件（JSONL）
+    pub history_path: String,
+}
+
+impl Default for DedupConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            freshness_window_secs: DEFAULT_FRESHNESS_WINDOW_SECS,
+            spot_check_challenges: DEFAULT_SPOT_CHECK_CHALLENGES,
+            history_path: "./audit_history.jsonl".to_string(),
+        }
+    }
+}
+
+/// 去重依據：被引用的完整審計
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeduplicatedFrom {
+    /// 完整審計的 Blob ID
+    pub blob_id: String,
+
+    /// 完整審計報告的摘要（見 [`AuditReport::digest`]）
+    pub report_digest: String,
+
+    /// 完整審計的時間戳（Unix 時間，秒）
+    pub audited_at: u64,
+}
+
+/// 報告摘要：緊湊 JSON 序列化的 SHA-256（十六進制）
+pub fn report_digest<T: Serialize>(report: &T) -> Result<String> {
+    let json = serde_json::to_vec(report)?;
+    Ok(hex::encode(Sha256::digest(&json)))
+}
+
+/// 審計歷史條目
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    /// Blob ID
+    pub blob_id: String,
+
+    /// 內容哈希（SHA-256，十六進制）
+    pub content_hash: String,
+
+    /// 已簽名報告的摘要
+    pub report_digest: String,
+
+    /// 審計時間戳（Unix 時間，秒）
+    pub audited_at: u64,
+
+    /// 是否為去重審計（不可作為去重依據）
+    #[serde(default)]
+    pub deduplicated: bool,
+
+    /// 可選：該次審計的葉子哈希過濾器
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chunk_filter: Option<ChunkFilter>,
+}
+
+impl HistoryEntry {
+    /// 從已簽名報告構建條目
+    ///
+    /// 只有內容可訪問且完整的報告才會被記錄
+    pub fn from_report(report: &AuditReport) -> Result<Option<Self>> {
+        let content_hash = report.content_hash();
+        if report.verification_status() != VerificationStatus::Accessible
+            || report.failed_verifications > 0
+            || content_hash.is_empty()
+        {
+            return Ok(None);
+        }
+
+        Ok(Some(Self {
+            blob_id: report.blob_id.clone(),
+            content_hash,
+            report_digest: report.digest()?,
+            audited_at: report.timestamp,
+            deduplicated: report.deduplicated_from.is_some(),
+            chunk_filter: report.chunk_filter.clone(),
+        }))
+    }
+}
+
+/// 按內容哈希索引的審計歷史
+pub struct AuditHistory {
+    /// 持久化文件（`None` 表示僅在內存中）
+    file: Option<RotatingWriter>,
+
+    /// 內容哈希 → 條目（按記錄順序）
+    by_hash: Mutex<HashMap<String, Vec<HistoryEntry>>>,
+}
+
+impl AuditHistory {
+    /// 僅在內存中保存的歷史
+    pub fn in_memory() -> Self {
+        Self {
+            file: None,
+            by_hash: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// 打開（或創建）JSONL 歷史文件並加載已有條目（不輪轉）
+    ///
+    /// 無法解析的行會被跳過並記錄警告
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        Self::open_with_rotation(path, RotationConfig::disabled())
+    }
+
+    /// 打開（或創建）JSONL 歷史文件，加載已有條目（包括仍保留的輪轉文件），之後按 `rotation` 輪轉
+    pub fn open_with_rotation(path: impl AsRef<Path>, rotation: RotationConfig) -> Result<Self> {
+        let path = path.as_ref();
+        let mut by_hash: HashMap<String, Vec<HistoryEntry>> = HashMap::new();
+
+        for (line_no, line) in rotating_writer::read_to_string(path)?.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str::<HistoryEntry>(line) {
+                Ok(entry) => by_hash
+                    .entry(entry.content_hash.clone())
+                    .or_default()
+                    .push(entry),
+                Err(e) => warn!(
+                    "Skipping invalid history line {} in {}: {}",
+                    line_no + 1,
+                    path.display(),
+                    e
+                ),
+            }
+        }
+
+        debug!(
+            "Loaded audit history for {} content hashes from {}",
+            by_hash.len(),
+            path.display()
+        );
+
+        Ok(Self {
+            file: Some(RotatingWriter::open(path, rotation)?),
+            by_hash: Mutex::new(by_hash),
+        })
+    }
+
+    /// 歷史文件的輪轉計數器（僅在內存中時為 `None`）
+    pub fn rotation_stats(&self) -> Option<Arc<RotationStats>> {
+        self.file.as_ref().map(RotatingWriter::stats)
+    }
+
+    /// 記錄已簽名報告（不符合條件的報告被忽略）
+    pub fn record(&self, report: &AuditReport) -> Result<()> {
+        match HistoryEntry::from_report(report)? {
+            Some(entry) => self.record_entry(entry),
+            None => Ok(()),
+        }
+    }
+
+    /// 記錄歷史條目
+    pub fn record_entry(&self, entry: HistoryEntry) -> Result<()> {
+        let mut by_hash = self.by_hash.lock().unwrap();
+
+        if let Some(file) = &self.file {
+            file.append_line(&serde_json::to_string(&entry)?)?;
+        }
+
+        by_hash
+            .entry(entry.content_hash.clone())
+            .or_default()
+            .push(entry);
+        Ok(())
+    }
+
+    /// 移除某個 Blob 的全部條目
+    ///
+    /// Blob 已被所有者刪除時調用：被引用的報告所依據的內容已不存在，不能再作為去重依據。
+    /// 持久化文件以剩餘條目重寫（按審計時間排序）；返回移除的條目數
+    pub fn forget_blob(&self, blob_id: &str) -> Result<usize> {
+        let mut by_hash = self.by_hash.lock().unwrap();
+        let before: usize = by_hash.values().map(Vec::len).sum();
+
+        by_hash.retain(|_, entries| {
+            entries.retain(|entry| entry.blob_id != blob_id);
+            !entries.is_empty()
+        });
+
+        let removed = before - by_hash.values().map(Vec::len).sum::<usize>();
+        if removed == 0 {
+            return Ok(0);
+        }
+
+        if let Some(file) = &self.file {
+            let mut remaining: Vec<&HistoryEntry> = by_hash.values().flatten().collect();
+            remaining.sort_by_key(|entry| entry.audited_at);
+
+            let mut contents = String::new();
+            for entry in remaining {
+                contents.push_str(&serde_json::to_string(entry)?);
+                contents.push('\n');
+            }
+            file.replace_contents(&contents)?;
+        }
+
+        debug!(
+            "Removed {} history entries of deleted blob {}",
+            removed, blob_id
+        );
+        Ok(removed)
+    }
+
+    /// 查找可作為去重依據的完整審計
+    ///
+    /// # 參數
+    /// - `content_hash`: 當前 Blob 的內容哈希
+    /// - `blob_id`: 當前 Blob ID（同一 Blob 的歷史不算重複）
+    /// - `now`: 當前時間（Unix 時間，秒）
+    /// - `freshness_window_secs`: 新鮮度窗口
+    ///
+    /// # 返回
+    /// 窗口內最新的、來自其他 Blob ID 的完整審計
+    pub fn find_fresh(
+        &self,
+        content_hash: &str,
+        blob_id: &str,
+        now: u64,
+        freshness_window_secs: u64,
+    ) -> Option<DeduplicatedFrom> {
+        let not_before = now.saturating_sub(freshness_window_secs);
+
+        self.by_hash
+            .lock()
+            .unwrap()
+            .get(content_hash)?
+            .iter()
+            .filter(|entry| {
+                !entry.deduplicated && entry.blob_id != blob_id && entry.audited_at >= not_before
+            })
+            .max_by_key(|entry| entry.audited_at)
+            .map(|entry| DeduplicatedFrom {
+                blob_id: entry.blob_id.clone(),
+                report_digest: entry.report_digest.clone(),
+                audited_at: entry.audited_at,
+            })
+    }
+
+    /// 同一 Blob 最近一次帶過濾器的審計
+    pub fn latest_filter(&self, blob_id: &str) -> Option<ChunkFilter> {
+        self.by_hash
+            .lock()
+            .unwrap()
+            .values()
+            .flatten()
+            .filter(|entry| entry.blob_id == blob_id && entry.chunk_filter.is_some())
+            .max_by_key(|entry| entry.audited_at)
+            .and_then(|entry| entry.chunk_filter.clone())
+    }
+
+    /// 已記錄的條目總數
+    pub fn len(&self) -> usize {
+        self.by_hash.lock().unwrap().values().map(Vec::len).sum()
+    }
+
+    /// 是否沒有任何條目
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl std::fmt::Debug for AuditHistory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AuditHistory")
+            .field("path", &self.file.as_ref().map(RotatingWriter::path))
+            .field("entries", &self.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audit_report::AuditReportGenerator;
+    use crate::chunk_filter::ChunkFilterConfig;
+    use crate::integrity::IntegrityVerifier;
+    use crate::report::ReportManager;
+    use crate::test_support::{deterministic_blob, AggregatorMode, FakeAggregator};
+    use crate::types::BlobId;
+    use pqc_signer::dilithium::Dilithium3Signer;
+    use pqc_signer::traits::Signer;
+    use std::sync::Arc;
+
+    fn generator() -> AuditReportGenerator {
+        let mut signer = Dilithium3Signer::new();
+        signer.generate_keypair().unwrap();
+        AuditReportGenerator::new(signer, None)
+    }
+
+    fn entry(blob_id: &str, audited_at: u64, deduplicated: bool) -> HistoryEntry {
+        HistoryEntry {
+            blob_id: blob_id.to_string(),
+            content_hash: "c0ffee".to_string(),
+            report_digest: format!("digest-{}", blob_id),
+            audited_at,
+            deduplicated,
+            chunk_filter: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_identical_content_is_deduplicated() {
+        // 1000 個 4KB chunk：完整審計 10 次挑戰，抽查 2 次
+        let aggregator =
+            FakeAggregator::start(deterministic_blob(1000 * 4096), AggregatorMode::Healthy).await;
+        let history = Arc::new(AuditHistory::in_memory());
+        let verifier = IntegrityVerifier::new(aggregator.url().to_string())
+            .with_dedup(Arc::clone(&history), DedupConfig::default());
+        let generator = generator();
+        let (blob_a, blob_b) = (BlobId::from_bytes([0xa; 32]), BlobId::from_bytes([0xb; 32]));
+
+        let first = generator
+            .generate_report(verifier.audit_blob(&blob_a).await.unwrap())
+            .unwrap();
+        history.record(&first).unwrap();
+        assert!(first.deduplicated_from.is_none());
+        assert_eq!(first.total_challenges, 10);
+
+        let second = generator
+            .generate_report(verifier.audit_blob(&blob_b).await.unwrap())
+            .unwrap();
+        let source = second.deduplicated_from.clone().unwrap();
+        assert_eq!(source.blob_id, blob_a.to_string());
+        assert_eq!(source.report_digest, first.digest().unwrap());
+        assert_eq!(second.total_challenges, DEFAULT_SPOT_CHECK_CHALLENGES);
+        assert_eq!(second.content_hash(), first.content_hash());
+
+        // 去重依據在簽名範圍內
+        assert!(ReportManager::verify_report(&second, generator.public_key()).unwrap());
+        let mut tampered = second.clone();
+        tampered.deduplicated_from = None;
+        assert!(!ReportManager::verify_report(&tampered, generator.public_key()).unwrap());
+
+        // 同一 Blob 的重複審計不算去重
+        let again = verifier.audit_blob(&blob_a).await.unwrap();
+        assert!(again.deduplicated_from.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_disabled_dedup_runs_full_audit() {
+        let aggregator =
+            FakeAggregator::start(deterministic_blob(100 * 4096), AggregatorMode::Healthy).await;
+        let history = Arc::new(AuditHistory::in_memory());
+        let config = DedupConfig {
+            enabled: false,
+            ..DedupConfig::default()
+        };
+        let verifier =
+            IntegrityVerifier::new(aggregator.url().to_string()).with_dedup(history, config);
+        assert!(verifier.dedup_history().is_none());
+
+        let data = verifier
+            .audit_blob(&BlobId::from_bytes([0xb; 32]))
+            .await
+            .unwrap();
+        assert!(data.deduplicated_from.is_none());
+        assert_eq!(data.total_challenges, 10);
+    }
+
+    #[tokio::test]
+    async fn test_chunk_filter_is_recorded_and_reloaded() {
+        let path =
+            std::env::temp_dir().join(format!("audit_history_{}.jsonl", rand::random::<u32>()));
+        let aggregator =
+            FakeAggregator::start(deterministic_blob(50 * 4096), AggregatorMode::Healthy).await;
+        let history = Arc::new(AuditHistory::open(&path).unwrap());
+        let verifier = IntegrityVerifier::new(aggregator.url().to_string())
+            .with_dedup(Arc::clone(&history), DedupConfig::default())
+            .with_chunk_filter(ChunkFilterConfig::default());
+
+        let generator = generator();
+        let blob_a = BlobId::from_bytes([0xa; 32]);
+        let report = generator
+            .generate_report(verifier.audit_blob(&blob_a).await.unwrap())
+            .unwrap();
+        let filter = report.chunk_filter.clone().unwrap();
+        history.record(&report).unwrap();
+
+        // 過濾器是旁路數據，不影響簽名與摘要
+        assert!(ReportManager::verify_report(&report, generator.public_key()).unwrap());
+        assert!(!serde_json::to_string(&report)
+            .unwrap()
+            .contains("chunk_filter"));
+
+        let reopened = AuditHistory::open(&path).unwrap();
+        assert_eq!(reopened.latest_filter(&blob_a.to_string()), Some(filter));
+        assert!(reopened.latest_filter("blob-b").is_none());
+
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_expired_window_is_not_deduplicated() {
+        let history = AuditHistory::in_memory();
+        history.record_entry(entry("blob-a", 1_000, false)).unwrap();
+
+        let window = DEFAULT_FRESHNESS_WINDOW_SECS;
+        assert!(history
+            .find_fresh("c0ffee", "blob-b", 1_000 + window, window)
+            .is_some());
+        assert!(history
+            .find_fresh("c0ffee", "blob-b", 1_001 + window, window)
+            .is_none());
+    }
+
+    #[test]
+    fn test_only_full_audits_are_referenced() {
+        let history = AuditHistory::in_memory();
+        history.record_entry(entry("blob-a", 100, false)).unwrap();
+        history.record_entry(entry("blob-b", 200, true)).unwrap();
+
+        // blob-b 是去重審計，只能引用 blob-a
+        let source = history.find_fresh("c0ffee", "blob-c", 300, 1_000).unwrap();
+        assert_eq!(source.blob_id, "blob-a");
+        assert_eq!(source.report_digest, "digest-blob-a");
+
+        assert!(history.find_fresh("c0ffee", "blob-a", 300, 1_000).is_none());
+        assert!(history.find_fresh("beef", "blob-c", 300, 1_000).is_none());
+    }
+
+    #[test]
+    fn test_history_persists_across_reopen() {
+        let path =
+            std::env::temp_dir().join(format!("audit_history_{}.jsonl", rand::random::<u32>()));
+
+        let history = AuditHistory::open(&path).unwrap();
+        history.record_entry(entry("blob-a", 100, false)).unwrap();
+        history.record_entry(entry("blob-b", 200, true)).unwrap();
+
+        // 無法解析的行被跳過
+        std::fs::write(
+            &path,
+            std::fs::read_to_string(&path).unwrap() + "not json\n",
+        )
+        .unwrap();
+
+        let reopened = AuditHistory::open(&path).unwrap();
+        assert_eq!(reopened.len(), 2);
+        assert_eq!(
+            reopened
+                .find_fresh("c0ffee", "blob-c", 300, 1_000)
+                .unwrap()
+                .blob_id,
+            "blob-a"
+        );
+
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_rotated_history_is_reloaded() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("audit_history.jsonl");
+        let rotation = RotationConfig {
+            max_bytes: Some(1),
+            keep_files: Some(1),
+            ..RotationConfig::disabled()
+        };
+
+        let history = AuditHistory::open_with_rotation(&path, rotation.clone()).unwrap();
+        for (i, blob_id) in ["blob-a", "blob-b", "blob-c"].into_iter().enumerate() {
+            history
+                .record_entry(entry(blob_id, 100 * (i as u64 + 1), false))
+                .unwrap();
+        }
+        let stats = history.rotation_stats().unwrap();
+        assert_eq!(stats.rotated_files(), 2);
+        assert_eq!(stats.deleted_files(), 1);
+
+        // 輪轉文件中的條目仍會加載，被保留策略刪除的條目不再加載
+        let reopened = AuditHistory::open_with_rotation(&path, rotation).unwrap();
+        assert_eq!(reopened.len(), 2);
+        assert!(reopened
+            .find_fresh("c0ffee", "blob-x", 300, 1_000)
+            .is_some_and(|source| source.blob_id == "blob-c"));
+    }
+
+    #[test]
+    fn test_deleted_blob_is_not_a_dedup_basis() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("audit_history.jsonl");
+
+        let history = AuditHistory::open(&path).unwrap();
+        history.record_entry(entry("blob-a", 100, false)).unwrap();
+        history.record_entry(entry("blob-b", 200, false)).unwrap();
+        assert_eq!(
+            history
+                .find_fresh("c0ffee", "blob-c", 300, 1_000)
+                .unwrap()
+                .blob_id,
+            "blob-b"
+        );
+
+        assert_eq!(history.forget_blob("blob-b").unwrap(), 1);
+        assert_eq!(history.forget_blob("blob-b").unwrap(), 0);
+        assert_eq!(
+            history
+                .find_fresh("c0ffee", "blob-c", 300, 1_000)
+                .unwrap()
+                .blob_id,
+            "blob-a"
+        );
+
+        // 重新打開後仍不可引用
+        let reopened = AuditHistory::open(&path).unwrap();
+        assert_eq!(reopened.len(), 1);
+        assert_eq!(reopened.forget_blob("blob-a").unwrap(), 1);
+        assert!(reopened
+            .find_fresh("c0ffee", "blob-c", 300, 1_000)
+            .is_none());
+        assert!(AuditHistory::open(&path).unwrap().is_empty());
+    }
+}
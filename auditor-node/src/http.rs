@@ -0,0 +1,391 @@
+//! 共享 HTTP 客戶端構建
+//!
+//! [`crate::integrity::IntegrityVerifier`]、[`crate::storage_node_client::StorageNodeClient`]
+//! 與 [`crate::seal_client::SealClient`] 通過 [`build_client`] 以同一份 [`HttpClientConfig`]
+//! （配置文件的 `[http]` 表）創建 reqwest 客戶端；需要自定總超時的 Walrus 上傳
+//! （[`crate::pipeline::WalrusPublisher`]）從 [`client_builder`] 開始構建：
+//!
+//! - `connect_timeout_secs`：建立 TCP/TLS 連接的時限
+//! - `read_timeout_secs`：每次讀取（等待響應頭或下一塊響應體）的時限，而非整個請求的總時限——
+//!   慢速鏈路上的大 Blob 只要持續有數據就不會中途超時
+//! - `pool_max_idle_per_host`：每個主機保留的空閒連接上限（0 表示不保留）
+//! - `user_agent`：讓 Aggregator 運營者識別審計員
+//! - `http_proxy` / `https_proxy` / `no_proxy`：企業網絡中的出站代理（未設置時沿用
+//!   `HTTP_PROXY` 等環境變量）
+//! - `extra_ca_certs`：除內置根證書外信任的 PEM 證書包（如內部 Aggregator 的私有 CA）
+//! - `danger_accept_invalid_certs`：不驗證服務器證書，只用於測試環境
+//!
+//! reqwest 0.11 沒有按讀取計時的超時，讀取超時由流式讀取的調用方以 [`with_read_timeout`]
+//! 包裹每次讀取實施；一次性讀取小響應的存儲節點與 Seal 請求仍使用各自的總超時
+//! （`http_timeout_secs` 與 `SealApiConfig::timeout_secs`）。
+
+use crate::error::{AuditorError, Result};
+use reqwest::{Certificate, Client, ClientBuilder, NoProxy, Proxy};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tracing::warn;
+
+/// 默認 User-Agent
+pub const DEFAULT_USER_AGENT: &str = concat!("walrus-auditor-node/", env!("CARGO_PKG_VERSION"));
+
+/// HTTP 客戶端配置
+///
+/// ```toml
+/// [http]
+/// connect_timeout_secs = 10
+/// read_timeout_secs = 60
+/// pool_max_idle_per_host = 8
+/// user_agent = "walrus-auditor-node/0.1.0"
+/// https_proxy = "http://proxy.corp.example:3128"
+/// no_proxy = "localhost,127.0.0.1,.corp.example"
+/// extra_ca_certs = ["/etc/ssl/corp-root-ca.pem"]
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct HttpClientConfig {
+    /// 連接超時（秒）
+    pub connect_timeout_secs: u64,
+
+    /// 讀取超時（秒；每次讀取重新計時）
+    pub read_timeout_secs: u64,
+
+    /// 每個主機保留的空閒連接上限
+    pub pool_max_idle_per_host: usize,
+
+    /// 請求的 `User-Agent` 頭
+    pub user_agent: String,
+
+    /// `http://` 請求經由的代理
+    pub http_proxy: Option<String>,
+
+    /// `https://` 請求經由的代理
+    pub https_proxy: Option<String>,
+
+    /// 不經代理的主機（逗號分隔，格式同 `NO_PROXY` 環境變量）
+    pub no_proxy: Option<String>,
+
+    /// 額外信任的根證書（PEM 文件，每個文件可包含多張證書）
+    pub extra_ca_certs: Vec<PathBuf>,
+
+    /// 不驗證服務器證書（只用於測試環境）
+    pub danger_accept_invalid_certs: bool,
+}
+
+impl Default for HttpClientConfig {
+    fn default() -> Self {
+        Self {
+            connect_timeout_secs: 10,
+            read_timeout_secs: 60,
+            pool_max_idle_per_host: 8,
+            user_agent: DEFAULT_USER_AGENT.to_string(),
+            http_proxy: None,
+            https_proxy: None,
+            no_proxy: None,
+            extra_ca_certs: Vec::new(),
+            danger_accept_invalid_certs: false,
+        }
+    }
+}
+
+impl HttpClientConfig {
+    /// 連接超時
+    pub fn connect_timeout(&self) -> Duration {
+        Duration::from_secs(self.connect_timeout_secs)
+    }
+
+    /// 讀取超時
+    pub fn read_timeout(&self) -> Duration {
+        Duration::from_secs(self.read_timeout_secs)
+    }
+}
+
+/// 按配置創建 HTTP 客戶端（不設總超時，見模塊文檔）
+///
+/// # 錯誤
+/// - 代理或證書配置無效: 返回 `AuditorError::Config`（見 [`client_builder`]）
+/// - `user_agent` 不是合法的頭部值或 TLS 後端無法初始化: 返回 `AuditorError::HttpRequest`
+pub fn build_client(config: &HttpClientConfig) -> Result<Client> {
+    Ok(client_builder(config)?.build()?)
+}
+
+/// 按配置預設好連接、代理與 TLS 的客戶端構建器，調用方可再加上自己的設置（如總超時）
+///
+/// # 錯誤
+/// - 代理 URL 無效: 返回 `Config` 錯誤
+/// - 證書文件無法讀取或不含 PEM 證書: 返回指出文件的 `Config` 錯誤
+pub fn client_builder(config: &HttpClientConfig) -> Result<ClientBuilder> {
+    let mut builder = Client::builder()
+        .connect_timeout(config.connect_timeout())
+        .pool_max_idle_per_host(config.pool_max_idle_per_host)
+        .user_agent(config.user_agent.as_str());
+
+    let no_proxy = config.no_proxy.as_deref().and_then(NoProxy::from_string);
+    let invalid_proxy = |name: &str, url: &str, e: reqwest::Error| {
+        AuditorError::Config(format!("Invalid http.{} {:?}: {}", name, url, e))
+    };
+    if let Some(url) = &config.http_proxy {
+        let proxy = Proxy::http(url.as_str()).map_err(|e| invalid_proxy("http_proxy", url, e))?;
+        builder = builder.proxy(proxy.no_proxy(no_proxy.clone()));
+    }
+    if let Some(url) = &config.https_proxy {
+        let proxy = Proxy::https(url.as_str()).map_err(|e| invalid_proxy("https_proxy", url, e))?;
+        builder = builder.proxy(proxy.no_proxy(no_proxy.clone()));
+    }
+
+    for path in &config.extra_ca_certs {
+        for certificate in load_ca_certs(path)? {
+            builder = builder.add_root_certificate(certificate);
+        }
+    }
+
+    if config.danger_accept_invalid_certs {
+        warn!(
+            "⚠️  http.danger_accept_invalid_certs is set: server certificates are NOT verified. \
+             Use this only in test environments"
+        );
+        builder = builder.danger_accept_invalid_certs(true);
+    }
+
+    Ok(builder)
+}
+
+/// 讀取 PEM 證書包
+fn load_ca_certs(path: &Path) -> Result<Vec<Certificate>> {
+    let pem = fs::read(path).map_err(|e| {
+        AuditorError::Config(format!(
+            "Failed to read CA certificate file {}: {}",
+            path.display(),
+            e
+        ))
+    })?;
+    let certificates = Certificate::from_pem_bundle(&pem).map_err(|e| {
+        AuditorError::Config(format!(
+            "Failed to parse CA certificate file {}: {}",
+            path.display(),
+            e
+        ))
+    })?;
+    if certificates.is_empty() {
+        return Err(AuditorError::Config(format!(
+            "CA certificate file {} contains no PEM certificates",
+            path.display()
+        )));
+    }
+    Ok(certificates)
+}
+
+/// 帶讀取超時的一次讀取的錯誤
+#[derive(Debug, thiserror::Error)]
+pub enum ReadError {
+    /// 讀取超時內沒有收到數據
+    #[error("no data received within {}s", .0.as_secs_f64())]
+    TimedOut(Duration),
+
+    /// 傳輸錯誤
+    #[error(transparent)]
+    Http(#[from] reqwest::Error),
+}
+
+/// 在 `read_timeout` 內等待一次讀取（發送請求等待響應頭，或 `Response::chunk`）
+pub async fn with_read_timeout<T>(
+    read_timeout: Duration,
+    read: impl Future<Output = reqwest::Result<T>>,
+) -> std::result::Result<T, ReadError> {
+    match tokio::time::timeout(read_timeout, read).await {
+        Ok(result) => Ok(result?),
+        Err(_) => Err(ReadError::TimedOut(read_timeout)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{http::HeaderMap, routing::get, Router};
+
+    #[tokio::test]
+    async fn test_build_client_sends_configured_user_agent() {
+        let router = Router::new().route(
+            "/",
+            get(|headers: HeaderMap| async move {
+                headers
+                    .get("user-agent")
+                    .and_then(|value| value.to_str().ok())
+                    .unwrap_or_default()
+                    .to_string()
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, router).await });
+
+        let config = HttpClientConfig {
+            user_agent: "auditor-test/1.0 (ops@example.com)".to_string(),
+            ..Default::default()
+        };
+        let client = build_client(&config).unwrap();
+        let body = client.get(&url).send().await.unwrap().text().await.unwrap();
+        assert_eq!(body, "auditor-test/1.0 (ops@example.com)");
+    }
+
+    /// 以自簽名證書（`127.0.0.1`）提供 HTTPS 的最小服務器，每個請求返回 `ok`；返回 URL 與證書 PEM
+    async fn start_tls_server() -> (String, String) {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+        use tokio_rustls::rustls;
+
+        let rcgen::CertifiedKey { cert, key_pair } =
+            rcgen::generate_simple_self_signed(vec!["127.0.0.1".to_string()]).unwrap();
+        let tls = rustls::ServerConfig::builder()
+            .with_safe_defaults()
+            .with_no_client_auth()
+            .with_single_cert(
+                vec![rustls::Certificate(cert.der().to_vec())],
+                rustls::PrivateKey(key_pair.serialize_der()),
+            )
+            .unwrap();
+        let acceptor = tokio_rustls::TlsAcceptor::from(std::sync::Arc::new(tls));
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("https://{}/", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let acceptor = acceptor.clone();
+                tokio::spawn(async move {
+                    let Ok(mut stream) = acceptor.accept(stream).await else {
+                        return;
+                    };
+                    let mut request = [0u8; 4096];
+                    let _ = stream.read(&mut request).await;
+                    let _ = stream
+                        .write_all(
+                            b"HTTP/1.1 200 OK\r\ncontent-length: 2\r\nconnection: close\r\n\r\nok",
+                        )
+                        .await;
+                    let _ = stream.shutdown().await;
+                });
+            }
+        });
+        (url, cert.pem())
+    }
+
+    #[tokio::test]
+    async fn test_extra_ca_certs_trust_private_ca() {
+        let (url, pem) = start_tls_server().await;
+
+        // 默認只信任內置根證書
+        let client = build_client(&HttpClientConfig::default()).unwrap();
+        assert!(client.get(&url).send().await.is_err());
+
+        let dir = tempfile::tempdir().unwrap();
+        let ca_path = dir.path().join("private-ca.pem");
+        fs::write(&ca_path, pem).unwrap();
+        let config = HttpClientConfig {
+            extra_ca_certs: vec![ca_path],
+            ..Default::default()
+        };
+        let client = build_client(&config).unwrap();
+        let body = client.get(&url).send().await.unwrap().text().await.unwrap();
+        assert_eq!(body, "ok");
+    }
+
+    #[tokio::test]
+    async fn test_danger_accept_invalid_certs() {
+        let (url, _) = start_tls_server().await;
+        let config = HttpClientConfig {
+            danger_accept_invalid_certs: true,
+            ..Default::default()
+        };
+        let client = build_client(&config).unwrap();
+        assert_eq!(client.get(&url).send().await.unwrap().status(), 200);
+    }
+
+    #[test]
+    fn test_invalid_ca_files_are_named_in_errors() {
+        let dir = tempfile::tempdir().unwrap();
+        let missing = dir.path().join("missing.pem");
+        let not_pem = dir.path().join("not-a-cert.pem");
+        fs::write(&not_pem, "just some text\n").unwrap();
+
+        for path in [missing, not_pem] {
+            let config = HttpClientConfig {
+                extra_ca_certs: vec![path.clone()],
+                ..Default::default()
+            };
+            match build_client(&config) {
+                Err(AuditorError::Config(msg)) => {
+                    assert!(msg.contains(&path.display().to_string()), "{}", msg)
+                }
+                other => panic!("Expected Config error, got {:?}", other),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_http_proxy_and_no_proxy() {
+        use axum::http::Uri;
+
+        // 代理收到的是絕對形式的請求 URI
+        let router = Router::new().fallback(|uri: Uri| async move { format!("proxied {}", uri) });
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let proxy_url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, router).await });
+
+        let config = HttpClientConfig {
+            http_proxy: Some(proxy_url),
+            no_proxy: Some("direct.invalid".to_string()),
+            ..Default::default()
+        };
+        let client = build_client(&config).unwrap();
+        let body = client
+            .get("http://aggregator.invalid/v1/blobs/abc")
+            .send()
+            .await
+            .unwrap()
+            .text()
+            .await
+            .unwrap();
+        assert_eq!(body, "proxied http://aggregator.invalid/v1/blobs/abc");
+
+        // no_proxy 中的主機直接連接（無法解析，因而失敗）
+        assert!(client.get("http://direct.invalid/").send().await.is_err());
+    }
+
+    #[test]
+    fn test_invalid_proxy_url() {
+        let config = HttpClientConfig {
+            https_proxy: Some("http://[::1".to_string()),
+            ..Default::default()
+        };
+        assert!(matches!(
+            build_client(&config),
+            Err(AuditorError::Config(msg)) if msg.contains("https_proxy")
+        ));
+    }
+
+    #[test]
+    fn test_build_client_rejects_invalid_user_agent() {
+        let config = HttpClientConfig {
+            user_agent: "bad\nagent".to_string(),
+            ..Default::default()
+        };
+        assert!(build_client(&config).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_read_timeout_applies_per_read() {
+        let slow = async {
+            tokio::time::sleep(Duration::from_millis(200)).await;
+            Ok::<_, reqwest::Error>(())
+        };
+        let err = with_read_timeout(Duration::from_millis(20), slow)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ReadError::TimedOut(_)));
+
+        let fast = async { Ok::<_, reqwest::Error>(7) };
+        let value = with_read_timeout(Duration::from_millis(20), fast).await;
+        assert_eq!(value.unwrap(), 7);
+    }
+}
@@ -0,0 +1,496 @@
+//! 報告 JSON 的加固解析層
+//!
+//! 驗證服務解析的報告大多來自 Walrus 上下載的文件，內容可能由攻擊者構造。
+//! 解析中的 panic 或過深的遞歸都會讓驗證服務停擺，因此所有報告入口
+//! （[`ReportManager::load_json`](crate::report::ReportManager::load_json)、
+//! [`ReportManager::from_json`](crate::report::ReportManager::from_json)、
+//! `SignedAuditReport::from_json`、存檔驗證與 `verify` 命令）都經過本模組：
+//!
+//! 1. 文檔大小上限：讀取文件前先比對元數據中的長度；gzip 壓縮的文件
+//!    （如 [`crate::report_archive`] 壓縮的 `.json.gz`）透明解壓，解壓後的長度同樣受限
+//! 2. 嵌套深度上限：在交給 serde 之前逐字節掃描，超限直接拒絕
+//! 3. 解析為 `serde_json::Value`，按 `schema_version` 遷移（[`migrate_document`]），
+//!    再逐一檢查必需字段與類型，錯誤信息指出具體字段
+//! 4. 最後才轉換為強類型結構並執行語義校驗（如 Sliver 索引範圍）
+//!
+//! CBOR 報告（[`parse_report_cbor`]）同樣受大小上限約束，嵌套深度由解碼器的遞歸上限限制，
+//! 解碼後執行相同的版本檢查與語義校驗。
+//!
+//! 回歸語料位於 `fuzz/corpus/`，由 `tests/report_ingest.rs` 在常規測試中重放。
+
+#[allow(deprecated)]
+use crate::audit_report::SignedAuditReport;
+use crate::error::{AuditorError, Result};
+use crate::report::{check_schema_version, document_schema_version, migrate_document};
+use crate::types::AuditReport;
+use flate2::read::GzDecoder;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::fs;
+use std::io::{self, Read};
+use std::path::Path;
+use tracing::debug;
+
+/// 默認文檔大小上限（16 MiB）
+pub const DEFAULT_MAX_DOCUMENT_BYTES: usize = 16 * 1024 * 1024;
+
+/// 默認嵌套深度上限
+///
+/// 規範報告最深約 5 層（報告 → 挑戰結果數組 → 結果 → 挑戰 → 字段），留足餘量
+pub const DEFAULT_MAX_DEPTH: usize = 32;
+
+/// 解析限制
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IngestLimits {
+    /// 文檔最大字節數
+    pub max_bytes: usize,
+
+    /// 對象/數組最大嵌套深度
+    pub max_depth: usize,
+}
+
+impl Default for IngestLimits {
+    fn default() -> Self {
+        Self {
+            max_bytes: DEFAULT_MAX_DOCUMENT_BYTES,
+            max_depth: DEFAULT_MAX_DEPTH,
+        }
+    }
+}
+
+/// 字段的 JSON 類型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FieldKind {
+    String,
+    Bool,
+    Object,
+    /// 不超過給定上限的非負整數
+    Unsigned(u64),
+    /// 0..=255 的整數數組
+    Bytes,
+    Array,
+}
+
+impl FieldKind {
+    fn matches(self, value: &Value) -> bool {
+        match self {
+            FieldKind::String => value.is_string(),
+            FieldKind::Bool => value.is_boolean(),
+            FieldKind::Object => value.is_object(),
+            FieldKind::Unsigned(max) => value.as_u64().is_some_and(|n| n <= max),
+            FieldKind::Bytes => value.as_array().is_some_and(|items| {
+                items
+                    .iter()
+                    .all(|item| item.as_u64().is_some_and(|b| b <= u64::from(u8::MAX)))
+            }),
+            FieldKind::Array => value.is_array(),
+        }
+    }
+
+    fn describe(self) -> String {
+        match self {
+            FieldKind::String => "a string".to_string(),
+            FieldKind::Bool => "a boolean".to_string(),
+            FieldKind::Object => "an object".to_string(),
+            FieldKind::Unsigned(max) => format!("an integer in 0..={}", max),
+            FieldKind::Bytes => "an array of bytes (0..=255)".to_string(),
+            FieldKind::Array => "an array".to_string(),
+        }
+    }
+}
+
+/// 規範報告的必需字段
+const REPORT_FIELDS: &[(&str, FieldKind)] = &[
+    ("blob_id", FieldKind::String),
+    ("blob_object_id", FieldKind::String),
+    ("auditor", FieldKind::String),
+    ("timestamp", FieldKind::Unsigned(u64::MAX)),
+    ("challenge_epoch", FieldKind::Unsigned(u32::MAX as u64)),
+    ("challenge_results", FieldKind::Array),
+    ("total_challenges", FieldKind::Unsigned(u16::MAX as u64)),
+    (
+        "successful_verifications",
+        FieldKind::Unsigned(u16::MAX as u64),
+    ),
+    ("failed_verifications", FieldKind::Unsigned(u16::MAX as u64)),
+    ("integrity_hash", FieldKind::Bytes),
+    ("pqc_signature", FieldKind::Bytes),
+    ("pqc_algorithm", FieldKind::Unsigned(u8::MAX as u64)),
+    ("is_valid", FieldKind::Bool),
+];
+
+/// 舊版 `SignedAuditReport` 信封的必需字段
+const LEGACY_FIELDS: &[(&str, FieldKind)] = &[
+    ("audit_data", FieldKind::Object),
+    ("signature", FieldKind::String),
+    ("algorithm", FieldKind::String),
+    ("auditor_public_key", FieldKind::String),
+    ("report_timestamp", FieldKind::Unsigned(u64::MAX)),
+];
+
+/// 舊版 `audit_data` 的必需字段
+const AUDIT_DATA_FIELDS: &[(&str, FieldKind)] = &[
+    ("blob_id", FieldKind::String),
+    ("content_hash", FieldKind::String),
+    ("merkle_root", FieldKind::String),
+    ("total_challenges", FieldKind::Unsigned(u16::MAX as u64)),
+    (
+        "successful_verifications",
+        FieldKind::Unsigned(u16::MAX as u64),
+    ),
+    ("failed_verifications", FieldKind::Unsigned(u16::MAX as u64)),
+    ("file_size", FieldKind::Unsigned(u64::MAX)),
+    ("timestamp", FieldKind::Unsigned(u64::MAX)),
+];
+
+/// gzip 文件頭
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// 讀取報告文件，超過大小上限時不讀入內存
+///
+/// 以 gzip 文件頭開始的文件透明解壓（與擴展名無關）。
+///
+/// # 錯誤
+/// - 文件過大或解壓後過大: 返回 `Serialization` 錯誤
+/// - 讀取或解壓失敗、不是 UTF-8: 返回 `Io` 錯誤
+pub fn read_document(path: &Path, limits: &IngestLimits) -> Result<String> {
+    let mut bytes = read_document_bytes(path, limits)?;
+    if bytes.starts_with(&GZIP_MAGIC) {
+        bytes = decompress(path, &bytes, limits)?;
+    }
+    String::from_utf8(bytes)
+        .map_err(|e| AuditorError::Io(io::Error::new(io::ErrorKind::InvalidData, e)))
+}
+
+/// 在大小上限內解壓 gzip 文檔
+fn decompress(path: &Path, compressed: &[u8], limits: &IngestLimits) -> Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    GzDecoder::new(compressed)
+        .take(limits.max_bytes as u64 + 1)
+        .read_to_end(&mut bytes)?;
+    if bytes.len() > limits.max_bytes {
+        return Err(AuditorError::Serialization(format!(
+            "Report {} decompresses to more than the {} byte limit",
+            path.display(),
+            limits.max_bytes
+        )));
+    }
+    Ok(bytes)
+}
+
+/// 在大小上限內讀取二進制報告文件
+pub fn read_document_bytes(path: &Path, limits: &IngestLimits) -> Result<Vec<u8>> {
+    check_file_size(path, limits)?;
+    Ok(fs::read(path)?)
+}
+
+fn check_file_size(path: &Path, limits: &IngestLimits) -> Result<()> {
+    let len = fs::metadata(path)?.len();
+    if len > limits.max_bytes as u64 {
+        return Err(AuditorError::Serialization(format!(
+            "Report {} is {} bytes, exceeding the {} byte limit",
+            path.display(),
+            len,
+            limits.max_bytes
+        )));
+    }
+    Ok(())
+}
+
+/// 在限制內將文檔解析為 `serde_json::Value`
+///
+/// # 錯誤
+/// - 超過大小或深度上限、JSON 格式錯誤、頂層不是對象: 返回 `Serialization` 錯誤
+pub fn parse_document(json: &str, limits: &IngestLimits) -> Result<Value> {
+    if json.len() > limits.max_bytes {
+        return Err(AuditorError::Serialization(format!(
+            "Report JSON is {} bytes, exceeding the {} byte limit",
+            json.len(),
+            limits.max_bytes
+        )));
+    }
+    check_depth(json, limits.max_depth)?;
+
+    let value: Value = serde_json::from_str(json)
+        .map_err(|e| AuditorError::Serialization(format!("Failed to parse report JSON: {}", e)))?;
+    if !value.is_object() {
+        return Err(AuditorError::Serialization(
+            "Report JSON must be an object at the top level".to_string(),
+        ));
+    }
+    Ok(value)
+}
+
+/// 在限制內解析報告（同時接受舊版 `SignedAuditReport` 格式並遷移）
+pub fn parse_report(json: &str, limits: &IngestLimits) -> Result<AuditReport> {
+    report_from_value(parse_document(json, limits)?)
+}
+
+/// 在限制內解析 CBOR 編碼的規範報告
+///
+/// # 錯誤
+/// - 超過大小或深度上限、CBOR 格式錯誤或字段不符: 返回 `Serialization` 錯誤
+/// - Sliver 索引越界: 返回 `InvalidSliver` 錯誤
+pub fn parse_report_cbor(bytes: &[u8], limits: &IngestLimits) -> Result<AuditReport> {
+    if bytes.len() > limits.max_bytes {
+        return Err(AuditorError::Serialization(format!(
+            "Report CBOR is {} bytes, exceeding the {} byte limit",
+            bytes.len(),
+            limits.max_bytes
+        )));
+    }
+
+    let report: AuditReport =
+        ciborium::de::from_reader_with_recursion_limit(bytes, limits.max_depth).map_err(|e| {
+            AuditorError::Serialization(format!("Failed to parse report CBOR: {}", e))
+        })?;
+    check_schema_version(u64::from(report.schema_version))?;
+    report.validate_sliver_indices()?;
+    Ok(report)
+}
+
+/// 將已通過 [`parse_document`] 的文檔轉換為規範報告
+///
+/// 頂層帶 `audit_data` 的文檔按舊版信封處理，並無損遷移為 [`AuditReport`]
+///
+/// # 錯誤
+/// - `schema_version` 高於本構建支持的版本: 返回 `UnsupportedReportVersion` 錯誤
+/// - 缺少必需字段或類型不符: 返回 `Serialization` 錯誤（指出字段名）
+/// - Sliver 索引越界: 返回 `InvalidSliver` 錯誤
+#[allow(deprecated)]
+pub fn report_from_value(mut value: Value) -> Result<AuditReport> {
+    migrate_document(&mut value)?;
+    if value.get("audit_data").is_some() {
+        let legacy = signed_report_from_value(value)?;
+        debug!(
+            "Migrating legacy SignedAuditReport for blob {}",
+            legacy.audit_data.blob_id
+        );
+        return AuditReport::try_from(legacy);
+    }
+
+    check_fields(&value, "report", REPORT_FIELDS)?;
+    let report: AuditReport = typed(value, "report")?;
+    report.validate_sliver_indices()?;
+    Ok(report)
+}
+
+/// 在限制內解析舊版 `SignedAuditReport`
+#[allow(deprecated)]
+pub fn parse_signed_report(json: &str, limits: &IngestLimits) -> Result<SignedAuditReport> {
+    signed_report_from_value(parse_document(json, limits)?)
+}
+
+#[allow(deprecated)]
+fn signed_report_from_value(value: Value) -> Result<SignedAuditReport> {
+    document_schema_version(&value)?;
+    check_fields(&value, "legacy report", LEGACY_FIELDS)?;
+    check_fields(&value["audit_data"], "legacy audit_data", AUDIT_DATA_FIELDS)?;
+    typed(value, "legacy report")
+}
+
+fn typed<T: DeserializeOwned>(value: Value, context: &str) -> Result<T> {
+    serde_json::from_value(value)
+        .map_err(|e| AuditorError::Serialization(format!("Invalid {}: {}", context, e)))
+}
+
+/// 逐一檢查必需字段的存在與類型
+fn check_fields(value: &Value, context: &str, fields: &[(&str, FieldKind)]) -> Result<()> {
+    for (name, kind) in fields {
+        let field = value.get(name).ok_or_else(|| {
+            AuditorError::Serialization(format!("Invalid {}: missing field `{}`", context, name))
+        })?;
+        if !kind.matches(field) {
+            return Err(AuditorError::Serialization(format!(
+                "Invalid {}: field `{}` must be {}, got {}",
+                context,
+                name,
+                kind.describe(),
+                json_type(field)
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn json_type(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "a boolean",
+        Value::Number(n) if n.is_u64() => "an out-of-range integer",
+        Value::Number(_) => "a negative or fractional number",
+        Value::String(_) => "a string",
+        Value::Array(_) => "an array",
+        Value::Object(_) => "an object",
+    }
+}
+
+/// 不遞歸地掃描嵌套深度（字符串內的括號不計）
+fn check_depth(json: &str, max_depth: usize) -> Result<()> {
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let mut escaped = false;
+
+    for byte in json.bytes() {
+        if in_string {
+            match byte {
+                _ if escaped => escaped = false,
+                b'\\' => escaped = true,
+                b'"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match byte {
+            b'"' => in_string = true,
+            b'[' | b'{' => {
+                depth += 1;
+                if depth > max_depth {
+                    return Err(AuditorError::Serialization(format!(
+                        "Report JSON nests deeper than {} levels",
+                        max_depth
+                    )));
+                }
+            }
+            b']' | b'}' => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report_value() -> Value {
+        serde_json::json!({
+            "blob_id": "0xblob",
+            "blob_object_id": "0xobject",
+            "auditor": "0xauditor",
+            "timestamp": 1_700_000_000u64,
+            "challenge_epoch": 7,
+            "challenge_results": [],
+            "total_challenges": 10,
+            "successful_verifications": 10,
+            "failed_verifications": 0,
+            "integrity_hash": vec![0u8; 32],
+            "pqc_signature": [],
+            "pqc_algorithm": 3,
+            "is_valid": true,
+            "failure_reason": null,
+        })
+    }
+
+    fn parse(value: &Value) -> Result<AuditReport> {
+        parse_report(&value.to_string(), &IngestLimits::default())
+    }
+
+    fn error_message(result: Result<AuditReport>) -> String {
+        match result {
+            Err(AuditorError::Serialization(msg)) => msg,
+            other => panic!("Expected Serialization error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_valid_report_parses() {
+        let report = parse(&report_value()).unwrap();
+        assert_eq!(report.blob_id, "0xblob");
+        assert_eq!(report.total_challenges, 10);
+    }
+
+    #[test]
+    fn test_missing_and_mistyped_fields_are_named() {
+        let mut value = report_value();
+        value.as_object_mut().unwrap().remove("auditor");
+        assert!(error_message(parse(&value)).contains("missing field `auditor`"));
+
+        let mut value = report_value();
+        value["total_challenges"] = serde_json::json!(70_000);
+        let msg = error_message(parse(&value));
+        assert!(msg.contains("`total_challenges`"), "{}", msg);
+        assert!(msg.contains("0..=65535"), "{}", msg);
+
+        let mut value = report_value();
+        value["integrity_hash"] = serde_json::json!([1, 256]);
+        assert!(error_message(parse(&value)).contains("`integrity_hash`"));
+
+        let mut value = report_value();
+        value["timestamp"] = serde_json::json!(-1);
+        assert!(error_message(parse(&value)).contains("negative or fractional"));
+    }
+
+    #[test]
+    fn test_size_limit() {
+        let limits = IngestLimits {
+            max_bytes: 64,
+            ..Default::default()
+        };
+        let msg = error_message(parse_report(&report_value().to_string(), &limits));
+        assert!(msg.contains("byte limit"));
+    }
+
+    #[test]
+    fn test_depth_limit_without_recursion() {
+        let deep = format!("{}{}", "[".repeat(100_000), "]".repeat(100_000));
+        let msg = error_message(parse_report(&deep, &IngestLimits::default()));
+        assert!(msg.contains("nests deeper"));
+
+        // 字符串內的括號不計入深度
+        let mut value = report_value();
+        value["blob_id"] = serde_json::json!("[".repeat(1000));
+        assert!(parse(&value).is_ok());
+    }
+
+    #[test]
+    fn test_non_object_documents_rejected() {
+        for json in ["[]", "null", "42", "\"report\""] {
+            assert!(parse_report(json, &IngestLimits::default()).is_err());
+        }
+    }
+
+    #[test]
+    fn test_read_document_checks_size_before_reading() {
+        let path = std::env::temp_dir().join(format!("ingest_{}.json", rand::random::<u32>()));
+        fs::write(&path, vec![b' '; 1024]).unwrap();
+
+        let limits = IngestLimits {
+            max_bytes: 512,
+            ..Default::default()
+        };
+        assert!(read_document(&path, &limits).is_err());
+        assert!(read_document(&path, &IngestLimits::default()).is_ok());
+
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_read_document_decompresses_gzip_within_limit() {
+        use flate2::{write::GzEncoder, Compression};
+        use std::io::Write;
+
+        let path = std::env::temp_dir().join(format!("ingest_{}.json.gz", rand::random::<u32>()));
+        let json = report_value().to_string();
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(json.as_bytes()).unwrap();
+        fs::write(&path, encoder.finish().unwrap()).unwrap();
+
+        assert_eq!(
+            read_document(&path, &IngestLimits::default()).unwrap(),
+            json
+        );
+
+        // 壓縮後很小、解壓後超過上限的文件被拒絕
+        let limits = IngestLimits {
+            max_bytes: json.len() - 1,
+            ..Default::default()
+        };
+        match read_document(&path, &limits) {
+            Err(AuditorError::Serialization(msg)) => assert!(msg.contains("decompresses to")),
+            other => panic!("Expected Serialization error, got {:?}", other),
+        }
+
+        let _ = fs::remove_file(&path);
+    }
+}
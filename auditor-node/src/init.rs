@@ -0,0 +1,922 @@
+//! 首次運行初始化嚮導
+//!
+//! `auditor-node init` 引導新用戶生成一份可用的 `config.toml`：
+//!
+//! 1. 選擇網絡配置（testnet / mainnet / devnet / local）
+//! 2. 選擇 PQC 密鑰庫位置（不存在時通過 [`Keystore::generate_and_save`] 生成）
+//! 3. 輸入審計員 Sui 地址，或生成新的 Sui Ed25519 密鑰並派生地址
+//! 4. 開關 Seal 加密與 Sui 提交
+//! 5. 寫入經過驗證的 `config.toml`，並重新加載確認
+//! 6. 執行預檢（[`preflight`]）
+//!
+//! 每個問題都有對應的命令行參數（[`InitOptions`]）。提供參數時跳過提問；
+//! 使用 [`NonInteractive`] 時未提供的問題取默認值，沒有默認值的問題直接報錯，
+//! 因此嚮導可以在腳本中運行。
+//!
+//! 生成的配置在寫入前調用 `AuditorConfig::validate()`，嚮導只會產生有效配置。
+//! 已存在的密鑰永遠不會被覆蓋。
+
+use crate::audit_report::PqcAlgorithm;
+use crate::config::{is_sui_id, load_config, save_config};
+use crate::error::{AuditorError, Result};
+use crate::keystore::{keystore_exists, Keystore};
+use crate::seal_client::{SealApiConfig, SealClient};
+use crate::types::AuditorConfig;
+use base64::{engine::general_purpose, Engine as _};
+use fastcrypto::ed25519::Ed25519KeyPair;
+use fastcrypto::encoding::Bech32;
+use fastcrypto::hash::{Blake2b256, HashFunction};
+use fastcrypto::traits::{KeyPair, Signer, ToFromBytes};
+use serde_json::{json, Value};
+use std::fmt;
+use std::io::{self, BufRead, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use tracing::info;
+
+/// 默認 PQC 密鑰庫目錄（與 `AuditorConfig` 默認值一致）
+pub const DEFAULT_KEYSTORE_PATH: &str = "./keys/pqc_keystore";
+
+/// 默認 Seal API 地址（本地 seal-api-server）
+pub const DEFAULT_SEAL_API_URL: &str = "http://localhost:3001";
+
+/// Sui 密鑰文件名（位於密鑰庫目錄內）
+pub const SUI_KEY_FILE: &str = "sui.key";
+
+/// Sui 簽名方案標誌：Ed25519
+const ED25519_FLAG: u8 = 0x00;
+
+/// Bech32 私鑰的 human-readable part（`sui keytool export` 的輸出格式）
+const SUI_PRIVATE_KEY_HRP: &str = "suiprivkey";
+
+/// 交易簽名的 intent 前綴：`TransactionData` / V0 / Sui
+const TRANSACTION_INTENT: [u8; 3] = [0, 0, 0];
+
+/// 網絡配置
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NetworkProfile {
+    Testnet,
+    Mainnet,
+    Devnet,
+    Local,
+}
+
+/// 已部署合約的對象 ID
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Deployment {
+    pub audit_system_package_id: &'static str,
+    pub access_policy_package_id: &'static str,
+    pub auditor_registry_id: &'static str,
+    pub incentives_id: &'static str,
+}
+
+/// Testnet 部署（見 `contracts/deployed-contracts.json`）
+const TESTNET_DEPLOYMENT: Deployment = Deployment {
+    audit_system_package_id: "0x1bc5c277f6c0fd20f97cf555d83ea6f9df753d93fbf99b8890a97df31af21804",
+    access_policy_package_id: "0xbd9d7ce59601fc4a442f8ef8f087b402eab4d66c6acbb5aa9f6251bdde8eed2e",
+    auditor_registry_id: "0x3ff5961eae0235665d355293820459a8da4ce564bed87f8680a7552d5553227f",
+    incentives_id: "0x05a8faa074c68db55cd182213e8b972dd0c0fa0aecf5d0c61d0b904453420cbd",
+};
+
+impl NetworkProfile {
+    /// 所有可選網絡
+    pub const ALL: [NetworkProfile; 4] = [Self::Testnet, Self::Mainnet, Self::Devnet, Self::Local];
+
+    /// Sui RPC 端點
+    pub fn sui_rpc_url(&self) -> &'static str {
+        match self {
+            Self::Testnet => "https://fullnode.testnet.sui.io:443",
+            Self::Mainnet => "https://fullnode.mainnet.sui.io:443",
+            Self::Devnet => "https://fullnode.devnet.sui.io:443",
+            Self::Local => "http://127.0.0.1:9000",
+        }
+    }
+
+    /// Walrus Aggregator 端點
+    pub fn walrus_aggregator_url(&self) -> &'static str {
+        match self {
+            Self::Testnet | Self::Devnet => "https://aggregator.walrus-testnet.walrus.space",
+            Self::Mainnet => "https://aggregator.walrus-mainnet.walrus.space",
+            Self::Local => "http://127.0.0.1:31415",
+        }
+    }
+
+    /// Walrus Publisher 端點
+    ///
+    /// mainnet 沒有官方公共 Publisher，生成的配置需改為自建端點
+    pub fn walrus_publisher_url(&self) -> &'static str {
+        match self {
+            Self::Testnet | Self::Devnet => "https://publisher.walrus-testnet.walrus.space",
+            Self::Mainnet => "https://publisher.walrus-mainnet.walrus.space",
+            Self::Local => "http://127.0.0.1:31416",
+        }
+    }
+
+    /// 已知的合約部署（只有 testnet 已部署）
+    pub fn deployment(&self) -> Option<Deployment> {
+        match self {
+            Self::Testnet => Some(TESTNET_DEPLOYMENT),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for NetworkProfile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Testnet => "testnet",
+            Self::Mainnet => "mainnet",
+            Self::Devnet => "devnet",
+            Self::Local => "local",
+        })
+    }
+}
+
+impl FromStr for NetworkProfile {
+    type Err = AuditorError;
+
+    fn from_str(value: &str) -> Result<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|profile| profile.to_string() == value.trim().to_lowercase())
+            .ok_or_else(|| {
+                AuditorError::Config(format!(
+                    "Unknown network profile '{}' (expected testnet, mainnet, devnet or local)",
+                    value
+                ))
+            })
+    }
+}
+
+/// 嚮導提問接口
+pub trait Prompter {
+    /// 提問文本答案
+    ///
+    /// # 參數
+    /// - `question`: 問題
+    /// - `flag`: 對應的命令行參數（非交互模式下用於錯誤提示）
+    /// - `default`: 默認答案（空輸入時採用）
+    fn input(&mut self, question: &str, flag: &str, default: Option<&str>) -> Result<String>;
+
+    /// 提問是/否
+    fn confirm(&mut self, question: &str, flag: &str, default: bool) -> Result<bool>;
+}
+
+/// 從標準輸入讀取答案
+pub struct StdioPrompter;
+
+impl StdioPrompter {
+    fn read_line(prompt: &str) -> Result<String> {
+        print!("{}", prompt);
+        io::stdout().flush()?;
+
+        let mut line = String::new();
+        if io::stdin().lock().read_line(&mut line)? == 0 {
+            return Err(AuditorError::Config(
+                "Unexpected end of input (use --non-interactive for scripted setup)".to_string(),
+            ));
+        }
+        Ok(line.trim().to_string())
+    }
+}
+
+impl Prompter for StdioPrompter {
+    fn input(&mut self, question: &str, _flag: &str, default: Option<&str>) -> Result<String> {
+        loop {
+            let prompt = match default {
+                Some(default) => format!("{} [{}]: ", question, default),
+                None => format!("{}: ", question),
+            };
+
+            let answer = Self::read_line(&prompt)?;
+            match (answer.is_empty(), default) {
+                (false, _) => return Ok(answer),
+                (true, Some(default)) => return Ok(default.to_string()),
+                (true, None) => println!("  A value is required."),
+            }
+        }
+    }
+
+    fn confirm(&mut self, question: &str, _flag: &str, default: bool) -> Result<bool> {
+        let hint = if default { "Y/n" } else { "y/N" };
+        loop {
+            let answer = Self::read_line(&format!("{} [{}]: ", question, hint))?;
+            match answer.to_lowercase().as_str() {
+                "" => return Ok(default),
+                "y" | "yes" => return Ok(true),
+                "n" | "no" => return Ok(false),
+                _ => println!("  Please answer y or n."),
+            }
+        }
+    }
+}
+
+/// 非交互模式：採用默認值，沒有默認值時報錯
+pub struct NonInteractive;
+
+impl Prompter for NonInteractive {
+    fn input(&mut self, question: &str, flag: &str, default: Option<&str>) -> Result<String> {
+        default.map(str::to_string).ok_or_else(|| {
+            AuditorError::Config(format!(
+                "{} is required in non-interactive mode ({})",
+                flag, question
+            ))
+        })
+    }
+
+    fn confirm(&mut self, _question: &str, _flag: &str, default: bool) -> Result<bool> {
+        Ok(default)
+    }
+}
+
+/// 嚮導選項（每個問題對應一個參數，`None` 表示需要提問）
+#[derive(Debug, Clone, Default)]
+pub struct InitOptions {
+    /// 輸出的配置文件路徑
+    pub config_path: PathBuf,
+
+    /// 覆蓋已存在的配置文件
+    pub force: bool,
+
+    /// 網絡配置
+    pub network: Option<NetworkProfile>,
+
+    /// PQC 密鑰庫目錄
+    pub keystore_path: Option<PathBuf>,
+
+    /// 審計員 Sui 地址
+    pub auditor_address: Option<String>,
+
+    /// 生成新的 Sui 密鑰並派生審計員地址
+    pub generate_sui_key: Option<bool>,
+
+    /// 啟用 Seal 加密
+    pub enable_seal: Option<bool>,
+
+    /// Seal API 地址
+    pub seal_api_url: Option<String>,
+
+    /// 將審計記錄提交到 Sui
+    pub submit_to_sui: Option<bool>,
+
+    /// audit_system Package ID（覆蓋網絡配置）
+    pub package_id: Option<String>,
+
+    /// AuditorRegistry 對象 ID（覆蓋網絡配置）
+    pub registry_id: Option<String>,
+}
+
+/// 嚮導結果
+#[derive(Debug, Clone)]
+pub struct InitOutcome {
+    /// 寫入並重新加載的配置
+    pub config: AuditorConfig,
+
+    /// 配置文件路徑
+    pub config_path: PathBuf,
+
+    /// 是否新生成了 PQC 密鑰庫（否則復用已有密鑰）
+    pub keystore_generated: bool,
+
+    /// 新生成的 Sui 密鑰文件
+    pub generated_sui_key: Option<PathBuf>,
+}
+
+/// Sui Ed25519 密鑰
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SuiKey {
+    /// 私鑰（32 字節）
+    secret: Vec<u8>,
+
+    /// 公鑰（32 字節）
+    public: Vec<u8>,
+}
+
+impl SuiKey {
+    /// 生成新密鑰
+    pub fn generate() -> Self {
+        let keypair = Ed25519KeyPair::generate(&mut rand::thread_rng());
+        Self {
+            public: keypair.public().as_bytes().to_vec(),
+            secret: keypair.as_bytes().to_vec(),
+        }
+    }
+
+    /// Sui 地址：`Blake2b256(flag || public_key)`
+    pub fn address(&self) -> String {
+        let mut hasher = Blake2b256::default();
+        hasher.update([ED25519_FLAG]);
+        hasher.update(&self.public);
+        format!("0x{}", hex::encode(hasher.finalize().digest))
+    }
+
+    /// Sui keystore 格式：`Base64(flag || secret)`
+    pub fn to_keystore_string(&self) -> String {
+        let mut bytes = vec![ED25519_FLAG];
+        bytes.extend_from_slice(&self.secret);
+        general_purpose::STANDARD.encode(bytes)
+    }
+
+    /// 從 Sui keystore 格式解析
+    pub fn from_keystore_string(value: &str) -> Result<Self> {
+        let bytes = general_purpose::STANDARD
+            .decode(value.trim())
+            .map_err(|e| AuditorError::Keystore(format!("Invalid Sui key encoding: {}", e)))?;
+        Self::from_flagged_bytes(&bytes)
+    }
+
+    /// 解析 Sui 私鑰，支持以下格式：
+    /// - `sui.keystore` 文件：keystore 字符串的 JSON 數組，取其中第一個 Ed25519 密鑰
+    /// - Bech32：`suiprivkey1...`
+    /// - Base64：`Base64(flag || secret)`（見 [`to_keystore_string`](Self::to_keystore_string)）
+    pub fn parse(value: &str) -> Result<Self> {
+        let value = value.trim();
+
+        if value.starts_with('[') {
+            let entries: Vec<String> = serde_json::from_str(value)
+                .map_err(|e| AuditorError::Keystore(format!("Invalid Sui keystore: {}", e)))?;
+            return entries
+                .iter()
+                .find_map(|entry| Self::from_keystore_string(entry).ok())
+                .ok_or_else(|| {
+                    AuditorError::Keystore("Sui keystore contains no Ed25519 key".to_string())
+                });
+        }
+
+        if value.starts_with(SUI_PRIVATE_KEY_HRP) {
+            let bytes = Bech32::decode(value, SUI_PRIVATE_KEY_HRP)
+                .map_err(|e| AuditorError::Keystore(format!("Invalid Sui private key: {}", e)))?;
+            return Self::from_flagged_bytes(&bytes);
+        }
+
+        Self::from_keystore_string(value)
+    }
+
+    /// 從 `flag || secret` 解析（僅支持 Ed25519）
+    fn from_flagged_bytes(bytes: &[u8]) -> Result<Self> {
+        match bytes.split_first() {
+            Some((&ED25519_FLAG, secret)) => {
+                let keypair = Ed25519KeyPair::from_bytes(secret)
+                    .map_err(|e| AuditorError::Keystore(format!("Invalid Ed25519 key: {}", e)))?;
+                Ok(Self {
+                    public: keypair.public().as_bytes().to_vec(),
+                    secret: secret.to_vec(),
+                })
+            }
+            _ => Err(AuditorError::Keystore(
+                "Only Ed25519 Sui keys are supported".to_string(),
+            )),
+        }
+    }
+
+    /// 保存到文件（Unix 上權限 600）
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, self.to_keystore_string())?;
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))?;
+        }
+
+        Ok(())
+    }
+
+    /// 從文件加載（格式見 [`parse`](Self::parse)）
+    pub fn load(path: &Path) -> Result<Self> {
+        Self::parse(&std::fs::read_to_string(path)?)
+    }
+
+    /// 加載配置中的審計員 Sui 密鑰：`sui_key_path`，未設置時為 `auditor_private_key_path`
+    pub fn from_config(config: &AuditorConfig) -> Result<Self> {
+        let path = config
+            .sui_key_path
+            .as_deref()
+            .unwrap_or(&config.auditor_private_key_path);
+        Self::load(Path::new(path)).map_err(|e| {
+            AuditorError::Keystore(format!("Failed to load Sui key from {}: {}", path, e))
+        })
+    }
+
+    /// 公鑰（32 字節）
+    pub fn public_key(&self) -> &[u8] {
+        &self.public
+    }
+
+    /// 簽名交易，返回 Base64 編碼的 Sui 簽名（`flag || signature || public_key`）
+    ///
+    /// 簽名的消息為 `Blake2b256(intent || tx_bytes)`，`tx_bytes` 為 BCS 編碼的
+    /// `TransactionData`（即 `unsafe_moveCall` 返回的 `txBytes` 解碼後的字節）
+    pub fn sign_transaction(&self, tx_bytes: &[u8]) -> Result<String> {
+        let keypair = Ed25519KeyPair::from_bytes(&self.secret)
+            .map_err(|e| AuditorError::Keystore(format!("Invalid Ed25519 key: {}", e)))?;
+
+        let mut hasher = Blake2b256::default();
+        hasher.update(TRANSACTION_INTENT);
+        hasher.update(tx_bytes);
+        let signature = keypair.sign(&hasher.finalize().digest);
+
+        let mut bytes = vec![ED25519_FLAG];
+        bytes.extend_from_slice(signature.as_bytes());
+        bytes.extend_from_slice(&self.public);
+        Ok(general_purpose::STANDARD.encode(bytes))
+    }
+}
+
+/// 執行初始化嚮導
+///
+/// # 參數
+/// - `options`: 命令行提供的答案
+/// - `prompter`: 其餘問題的提問方式（[`StdioPrompter`] 或 [`NonInteractive`]）
+///
+/// # 錯誤
+/// - 配置文件已存在且未指定 `force`
+/// - 非交互模式缺少必要參數
+/// - 生成的配置未通過驗證或無法重新加載
+pub fn run_init(options: &InitOptions, prompter: &mut dyn Prompter) -> Result<InitOutcome> {
+    let config_path = options.config_path.clone();
+    if config_path.exists() && !options.force {
+        return Err(AuditorError::Config(format!(
+            "{} already exists (use --force to overwrite)",
+            config_path.display()
+        )));
+    }
+
+    // 1. 網絡
+    let network = match options.network {
+        Some(network) => network,
+        None => prompter
+            .input(
+                "Network profile (testnet/mainnet/devnet/local)",
+                "--network",
+                Some("testnet"),
+            )?
+            .parse()?,
+    };
+
+    // 2. PQC 密鑰庫
+    let keystore_path = match &options.keystore_path {
+        Some(path) => path.clone(),
+        None => PathBuf::from(prompter.input(
+            "PQC keystore directory",
+            "--keystore-path",
+            Some(DEFAULT_KEYSTORE_PATH),
+        )?),
+    };
+
+    let keystore_generated = if keystore_exists(&keystore_path) {
+        Keystore::open(&keystore_path)?;
+        info!("Reusing existing keystore at {}", keystore_path.display());
+        false
+    } else {
+        Keystore::generate_and_save(&keystore_path, PqcAlgorithm::default())?;
+        info!("Generated PQC keystore at {}", keystore_path.display());
+        true
+    };
+
+    // 3. 審計員地址
+    let sui_key_path = keystore_path.join(SUI_KEY_FILE);
+    let mut generated_sui_key = None;
+
+    let generate_sui_key = match (&options.auditor_address, options.generate_sui_key) {
+        (Some(_), _) => false,
+        (None, Some(generate)) => generate,
+        (None, None) => prompter.confirm(
+            "Generate a new Sui key for the auditor address?",
+            "--generate-sui-key",
+            false,
+        )?,
+    };
+
+    let (auditor_address, configured_sui_key) = if generate_sui_key {
+        let key = if sui_key_path.exists() {
+            info!("Reusing existing Sui key at {}", sui_key_path.display());
+            SuiKey::load(&sui_key_path)?
+        } else {
+            let key = SuiKey::generate();
+            key.save(&sui_key_path)?;
+            generated_sui_key = Some(sui_key_path.clone());
+            key
+        };
+        (key.address(), Some(sui_key_path.display().to_string()))
+    } else {
+        let address = match &options.auditor_address {
+            Some(address) => address.clone(),
+            None => prompter.input(
+                "Auditor Sui address (0x...)",
+                "--auditor-address or --generate-sui-key",
+                None,
+            )?,
+        };
+        if !is_sui_id(&address) {
+            return Err(AuditorError::Config(format!(
+                "Invalid auditor address: {}",
+                address
+            )));
+        }
+        (address, None)
+    };
+
+    // 4. Seal 加密
+    let enable_seal = match options.enable_seal {
+        Some(enable) => enable,
+        None => prompter.confirm("Enable Seal encryption of reports?", "--seal", false)?,
+    };
+
+    let seal_api_url = match (enable_seal, &options.seal_api_url) {
+        (false, _) => None,
+        (true, Some(url)) => Some(url.clone()),
+        (true, None) => {
+            Some(prompter.input("Seal API URL", "--seal-api-url", Some(DEFAULT_SEAL_API_URL))?)
+        }
+    };
+
+    // 5. Sui 提交
+    let deployment = network.deployment();
+    let submit_to_sui = match options.submit_to_sui {
+        Some(submit) => submit,
+        None => prompter.confirm(
+            "Submit audit records to Sui?",
+            "--submit-to-sui",
+            deployment.is_some(),
+        )?,
+    };
+
+    let package_id = match (&options.package_id, deployment) {
+        (Some(id), _) => Some(id.clone()),
+        (None, Some(deployment)) => Some(deployment.audit_system_package_id.to_string()),
+        (None, None) if submit_to_sui => {
+            Some(prompter.input("audit_system package ID", "--package-id", None)?)
+        }
+        (None, None) => None,
+    };
+
+    let registry_id = options
+        .registry_id
+        .clone()
+        .or_else(|| deployment.map(|d| d.auditor_registry_id.to_string()));
+
+    // 6. 構建並寫入配置
+    let config = AuditorConfig {
+        sui_rpc_url: network.sui_rpc_url().to_string(),
+        walrus_aggregator_url: network.walrus_aggregator_url().to_string(),
+        walrus_publisher_url: network.walrus_publisher_url().to_string(),
+        pqc_keystore_path: keystore_path.display().to_string(),
+        enable_seal_encryption: enable_seal,
+        seal_api_url,
+        audit_system_package_id: package_id,
+        access_policy_package_id: deployment.map(|d| d.access_policy_package_id.to_string()),
+        auditor_registry_id: registry_id,
+        incentives_id: deployment.map(|d| d.incentives_id.to_string()),
+        auditor_address: Some(auditor_address),
+        sui_key_path: configured_sui_key,
+        submit_to_sui,
+        ..AuditorConfig::default()
+    };
+
+    let header = format!(
+        "Walrus auditor node configuration\nGenerated by `auditor-node init` for {}",
+        network
+    );
+    save_config(&config, &config_path, &header)?;
+
+    // 重新加載，確認寫出的文件可被正常解析
+    let config = load_config(&config_path)?;
+    info!("Wrote {}", config_path.display());
+
+    Ok(InitOutcome {
+        config,
+        config_path,
+        keystore_generated,
+        generated_sui_key,
+    })
+}
+
+/// 單項預檢結果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PreflightCheck {
+    /// 檢查項
+    pub name: &'static str,
+
+    /// 是否通過
+    pub ok: bool,
+
+    /// 詳情（通過時為摘要，失敗時為錯誤）
+    pub detail: String,
+}
+
+impl PreflightCheck {
+    fn from_result(name: &'static str, result: std::result::Result<String, String>) -> Self {
+        match result {
+            Ok(detail) => Self {
+                name,
+                ok: true,
+                detail,
+            },
+            Err(detail) => Self {
+                name,
+                ok: false,
+                detail,
+            },
+        }
+    }
+}
+
+/// 對配置執行預檢：密鑰庫、Sui RPC、Walrus Aggregator、Seal API（如啟用）
+pub async fn preflight(config: &AuditorConfig) -> Vec<PreflightCheck> {
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(config.http_timeout_secs))
+        .build()
+        .expect("Failed to build HTTP client");
+
+    let mut checks = Vec::new();
+
+    checks.push(PreflightCheck::from_result(
+        "PQC keystore",
+        Keystore::open(Path::new(&config.pqc_keystore_path))
+            .map(|keystore| format!("{} byte public key", keystore.public_key_bytes().len()))
+            .map_err(|e| e.to_string()),
+    ));
+
+    if let Some(path) = &config.sui_key_path {
+        checks.push(PreflightCheck::from_result(
+            "Sui key",
+            SuiKey::load(Path::new(path))
+                .map_err(|e| e.to_string())
+                .and_then(|key| match &config.auditor_address {
+                    Some(address) if *address != key.address() => Err(format!(
+                        "key address {} does not match auditor_address {}",
+                        key.address(),
+                        address
+                    )),
+                    _ => Ok(key.address()),
+                }),
+        ));
+    }
+
+    let request = json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "sui_getChainIdentifier",
+        "params": []
+    });
+    let sui_result = async {
+        let response: Value = client
+            .post(&config.sui_rpc_url)
+            .json(&request)
+            .send()
+            .await
+            .map_err(|e| e.to_string())?
+            .json()
+            .await
+            .map_err(|e| e.to_string())?;
+
+        response["result"]
+            .as_str()
+            .map(|chain| format!("chain {}", chain))
+            .ok_or_else(|| format!("unexpected response: {}", response))
+    }
+    .await;
+    checks.push(PreflightCheck::from_result("Sui RPC", sui_result));
+
+    // 任何 HTTP 響應都說明 Aggregator 可達
+    let aggregator_result = client
+        .get(&config.walrus_aggregator_url)
+        .send()
+        .await
+        .map(|response| format!("HTTP {}", response.status()))
+        .map_err(|e| e.to_string());
+    checks.push(PreflightCheck::from_result(
+        "Walrus aggregator",
+        aggregator_result,
+    ));
+
+    if let (true, Some(url)) = (config.enable_seal_encryption, &config.seal_api_url) {
+        let seal_result = match SealClient::from_http_config(
+            SealApiConfig {
+                api_url: url.clone(),
+                timeout_secs: config.http_timeout_secs,
+            },
+            &config.http,
+        ) {
+            Ok(seal) => seal
+                .health_check()
+                .await
+                .map(|health| format!("{} {}", health.service, health.status))
+                .map_err(|e| e.to_string()),
+            Err(e) => Err(e.to_string()),
+        };
+        checks.push(PreflightCheck::from_result("Seal API", seal_result));
+    }
+
+    checks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{
+        deterministic_blob, AggregatorMode, FakeAggregator, FakeSealApi, FakeSuiRpc,
+    };
+
+    fn temp_dir(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("init_{}_{}", name, rand::random::<u32>()))
+    }
+
+    fn scripted(dir: &Path) -> InitOptions {
+        InitOptions {
+            config_path: dir.join("config.toml"),
+            network: Some(NetworkProfile::Testnet),
+            keystore_path: Some(dir.join("keys")),
+            generate_sui_key: Some(true),
+            enable_seal: Some(true),
+            ..InitOptions::default()
+        }
+    }
+
+    #[test]
+    fn test_non_interactive_init_writes_valid_config() {
+        let dir = temp_dir("scripted");
+        let outcome = run_init(&scripted(&dir), &mut NonInteractive).unwrap();
+
+        let loaded = load_config(dir.join("config.toml")).unwrap();
+        assert!(loaded.validate().is_ok());
+
+        assert_eq!(loaded.sui_rpc_url, NetworkProfile::Testnet.sui_rpc_url());
+        assert_eq!(loaded.seal_api_url.as_deref(), Some(DEFAULT_SEAL_API_URL));
+        assert!(loaded.submit_to_sui);
+        assert_eq!(
+            loaded.audit_system_package_id.as_deref(),
+            Some(TESTNET_DEPLOYMENT.audit_system_package_id)
+        );
+
+        // 地址由生成的 Sui 密鑰派生
+        assert!(outcome.keystore_generated);
+        let key = SuiKey::load(outcome.generated_sui_key.as_ref().unwrap()).unwrap();
+        assert_eq!(loaded.auditor_address, Some(key.address()));
+        assert!(Keystore::load(Path::new(&loaded.pqc_keystore_path)).is_ok());
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_rerun_reuses_keys_and_requires_force() {
+        let dir = temp_dir("rerun");
+        let first = run_init(&scripted(&dir), &mut NonInteractive).unwrap();
+
+        assert!(run_init(&scripted(&dir), &mut NonInteractive).is_err());
+
+        let mut options = scripted(&dir);
+        options.force = true;
+        let second = run_init(&options, &mut NonInteractive).unwrap();
+
+        assert!(!second.keystore_generated);
+        assert!(second.generated_sui_key.is_none());
+        assert_eq!(second.config.auditor_address, first.config.auditor_address);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_non_interactive_requires_missing_answers() {
+        let dir = temp_dir("missing");
+
+        // 未提供地址且未要求生成密鑰
+        let mut options = scripted(&dir);
+        options.generate_sui_key = None;
+        let err = run_init(&options, &mut NonInteractive).unwrap_err();
+        assert!(err.to_string().contains("--auditor-address"));
+
+        // local 網絡沒有已知部署，提交到 Sui 需要 package ID
+        let mut options = scripted(&dir);
+        options.network = Some(NetworkProfile::Local);
+        options.submit_to_sui = Some(true);
+        let err = run_init(&options, &mut NonInteractive).unwrap_err();
+        assert!(err.to_string().contains("--package-id"));
+
+        assert!(!dir.join("config.toml").exists());
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_explicit_address_and_local_profile() {
+        let dir = temp_dir("local");
+        let mut options = scripted(&dir);
+        options.network = Some(NetworkProfile::Local);
+        options.generate_sui_key = None;
+        options.auditor_address = Some("0xab8e".to_string());
+        options.enable_seal = Some(false);
+
+        let outcome = run_init(&options, &mut NonInteractive).unwrap();
+        assert_eq!(outcome.config.auditor_address.as_deref(), Some("0xab8e"));
+        assert!(!outcome.config.submit_to_sui);
+        assert!(!outcome.config.enable_seal_encryption);
+        assert!(outcome.config.audit_system_package_id.is_none());
+
+        options.auditor_address = Some("ab8e".to_string());
+        options.force = true;
+        assert!(run_init(&options, &mut NonInteractive).is_err());
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_sui_key_roundtrip() {
+        let key = SuiKey::generate();
+        let restored = SuiKey::from_keystore_string(&key.to_keystore_string()).unwrap();
+
+        assert_eq!(restored, key);
+        assert!(is_sui_id(&key.address()));
+        assert_eq!(key.address().len(), 66);
+    }
+
+    #[test]
+    fn test_sui_key_formats() {
+        let key = SuiKey::generate();
+        let mut flagged = vec![ED25519_FLAG];
+        flagged.extend_from_slice(&key.secret);
+
+        let bech32 = Bech32::encode(&flagged, SUI_PRIVATE_KEY_HRP).unwrap();
+        assert_eq!(SuiKey::parse(&bech32).unwrap(), key);
+
+        // sui.keystore：跳過非 Ed25519 的條目
+        let secp256k1 = general_purpose::STANDARD.encode([1u8; 33]);
+        let keystore = json!([secp256k1, key.to_keystore_string()]).to_string();
+        assert_eq!(SuiKey::parse(&keystore).unwrap(), key);
+        assert!(SuiKey::parse(&json!([secp256k1]).to_string()).is_err());
+
+        assert_eq!(SuiKey::parse(&format!("{}\n", key.to_keystore_string())).unwrap(), key);
+        assert!(SuiKey::parse(&bech32.replace(SUI_PRIVATE_KEY_HRP, "suipubkey")).is_err());
+    }
+
+    #[test]
+    fn test_sui_transaction_signature() {
+        use fastcrypto::ed25519::{Ed25519PublicKey, Ed25519Signature};
+        use fastcrypto::traits::VerifyingKey;
+
+        let key = SuiKey::generate();
+        let tx_bytes = b"transaction data";
+        let signature = general_purpose::STANDARD
+            .decode(key.sign_transaction(tx_bytes).unwrap())
+            .unwrap();
+
+        assert_eq!(signature.len(), 1 + 64 + 32);
+        assert_eq!(signature[0], ED25519_FLAG);
+        assert_eq!(&signature[65..], key.public_key());
+
+        let mut hasher = Blake2b256::default();
+        hasher.update(TRANSACTION_INTENT);
+        hasher.update(tx_bytes);
+        let digest = hasher.finalize().digest;
+        let public = Ed25519PublicKey::from_bytes(key.public_key()).unwrap();
+        let sig = Ed25519Signature::from_bytes(&signature[1..65]).unwrap();
+        assert!(public.verify(&digest, &sig).is_ok());
+        assert!(public.verify(b"other", &sig).is_err());
+    }
+
+    #[test]
+    fn test_network_profile_parsing() {
+        for profile in NetworkProfile::ALL {
+            assert_eq!(
+                profile.to_string().parse::<NetworkProfile>().unwrap(),
+                profile
+            );
+        }
+        assert_eq!(
+            " Testnet ".parse::<NetworkProfile>().unwrap(),
+            NetworkProfile::Testnet
+        );
+        assert!("moonnet".parse::<NetworkProfile>().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_preflight_against_fakes() {
+        let dir = temp_dir("preflight");
+        let outcome = run_init(&scripted(&dir), &mut NonInteractive).unwrap();
+
+        let aggregator =
+            FakeAggregator::start(deterministic_blob(16), AggregatorMode::Healthy).await;
+        let seal = FakeSealApi::start().await;
+        let sui = FakeSuiRpc::start().await;
+
+        let mut config = outcome.config;
+        config.sui_rpc_url = sui.url().to_string();
+        config.walrus_aggregator_url = aggregator.url().to_string();
+        config.seal_api_url = Some(seal.url().to_string());
+
+        let checks = preflight(&config).await;
+        assert_eq!(checks.len(), 5);
+        assert!(checks.iter().all(|c| c.ok), "{:?}", checks);
+
+        // 不可達的 Seal API 只影響對應項
+        config.seal_api_url = Some("http://127.0.0.1:1".to_string());
+        let checks = preflight(&config).await;
+        let failed: Vec<_> = checks.iter().filter(|c| !c.ok).map(|c| c.name).collect();
+        assert_eq!(failed, vec!["Seal API"]);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
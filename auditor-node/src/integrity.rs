@@ -28,6 +28,17 @@
 //! - **不經濟**：重新編碼大型數據消耗大量資源
 //! - **可替代**：我們的 SHA-256 哈希提供了等效的完整性保證
 //!
+//! 但 SHA-256 只能證明前後兩次下載一致，無法證明內容就是 `blob_id` 所承諾的：
+//! 惡意 Aggregator 可以始終返回同一份錯誤數據。高保證審計可以通過
+//! [`IntegrityVerifier::with_blob_id_verification`] 注入 RS2 編碼器（見 [`crate::blob_id`]；
+//! `walrus-encoder` feature 提供基於 `walrus-core` 的實現），
+//! 對不超過大小上限的 Blob 重新推導 `blob_id` 並比對。
+//!
+//! # 離線審計
+//!
+//! [`audit_local`] 以相同的切片與挑戰抽樣審計本地文件，不訪問網絡；
+//! [`verify_local`] 再將結果與已發布的審計（內容哈希、Merkle 根與大小）比對。
+//!
 //! # 架構優勢
 //!
 //! 這種雙層驗證架構提供了：
@@ -36,24 +47,138 @@
 //! - **可審計性**：SHA-256 是公認的標準，任何人都可以驗證
 //! - **量子抗性**：PQC 簽名保護審計記錄的長期真實性
 
-use crate::crypto::merkle::{MerkleTree, MerkleError};
+use crate::baseline::{AuditObservation, BaselineStore, HashDrift};
+use crate::blob_id::{blob_ids_match, BlobIdEncoder};
+use crate::blob_lookup::{BlobMetadataSource, BlobObjectLookup, BlobObjectState};
+use crate::breaker::{is_failure_status, CircuitBreaker};
+use crate::capture::{HttpCapture, HttpExchange};
+use crate::chain_types::{checked_u16, MoveId};
+use crate::challenge_seed::{ChallengeSeed, ChallengeSeedSource, CheckpointSource};
+use crate::checkpoint::{AuditCheckpoint, ChallengeProgress, CheckpointStore};
+use crate::commitment::{ChallengeReveal, CommitmentLog};
+use crate::content_cache::{CachedContent, ContentCache};
+use crate::chunk_filter::{
+    quick_compare_content, quick_compare_leaves, ChunkFilter, ChunkFilterConfig, QuickCompare,
+    DEFAULT_QUICK_COMPARE_SAMPLES,
+};
+use crate::crypto::merkle::{MerkleTree, MerkleTreeBuilder, MerkleError};
+use crate::crypto::{ct_eq, ct_eq_hex, decode_hex_digest};
+use crate::crypto::sliver::calculate_challenge_count;
 use crate::error::{AuditorError, Result};
+use crate::history::{AuditHistory, DedupConfig, DeduplicatedFrom};
+use crate::http::{build_client, with_read_timeout, HttpClientConfig, ReadError};
+use crate::metrics::{audit_result, Metrics, RESULT_ERROR};
+use crate::producer::Producer;
+use crate::progress::{AuditProgress, FinishedStatus, ProgressSink};
+use crate::rate_limit::RateLimiter;
+use crate::resources::{
+    AuditSlot, ResourceAction, ResourceDecision, ResourceGuard, ResourceRequirements,
+};
+use crate::types::{AuditMethod, BlobId, BlobMetadata};
 use chrono::Utc;
-use rand::Rng;
+use reqwest::header::{CONTENT_LENGTH, ETAG, IF_NONE_MATCH};
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
+use std::fmt;
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
 use tracing::{debug, info, warn};
 
 /// Walrus Aggregator 的基礎 URL（Testnet）
 pub const WALRUS_AGGREGATOR_TESTNET: &str = "https://aggregator.walrus-testnet.walrus.space";
 
+/// Merkle Tree 葉子的默認切片大小（4KB chunks）
+pub const DEFAULT_CHUNK_SIZE: usize = 4096;
+
+/// 每次審計的默認挑戰次數上限
+pub const DEFAULT_MAX_CHALLENGES: usize = 10;
+
+/// 離線審計讀取本地文件的緩衝區大小（bytes）
+const LOCAL_READ_BUFFER_SIZE: usize = 64 * 1024;
+
+/// 完整性審計的切片與挑戰參數
+///
+/// 挑戰次數由 [`calculate_challenge_count`] 按置信度與假定損壞率計算，
+/// 不超過 `max_challenges` 與葉子數。默認值重現引入本配置前的行為：
+/// 4KB chunk，挑戰 `min(10, 葉子數)` 次（95% 置信度、10% 損壞率需要 29 次，被上限截斷）
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct IntegrityVerifierConfig {
+    /// Merkle 葉子的切片大小（bytes）
+    pub chunk_size: usize,
+
+    /// 每次審計的挑戰次數上限
+    pub max_challenges: usize,
+
+    /// 發現損壞 chunk 的目標置信度，取值 (0, 1)
+    pub confidence_level: f64,
+
+    /// 假定的損壞 chunk 比例，取值 (0, 1)
+    pub assumed_corruption_rate: f64,
+}
+
+impl Default for IntegrityVerifierConfig {
+    fn default() -> Self {
+        Self {
+            chunk_size: DEFAULT_CHUNK_SIZE,
+            max_challenges: DEFAULT_MAX_CHALLENGES,
+            confidence_level: 0.95,
+            assumed_corruption_rate: 0.1,
+        }
+    }
+}
+
+impl IntegrityVerifierConfig {
+    /// 有 `leaf_count` 個葉子時的挑戰次數（至少 1 次，不超過葉子數）
+    ///
+    /// 置信度或損壞率超出 (0, 1) 時，[`calculate_challenge_count`] 退回挑戰 10% 的葉子
+    pub fn challenge_count(&self, leaf_count: usize) -> usize {
+        let computed = calculate_challenge_count(
+            leaf_count as u64,
+            self.confidence_level,
+            self.assumed_corruption_rate,
+        );
+        (computed as usize).min(self.max_challenges).clamp(1, leaf_count.max(1))
+    }
+
+    /// 檢查切片大小與挑戰次數上限
+    ///
+    /// 報告以 `u16` 記錄挑戰次數，上限超過 `u16::MAX` 的配置無法表示
+    ///
+    /// # 錯誤
+    /// `chunk_size` 或 `max_challenges` 為 0，或 `max_challenges` 超過 `u16::MAX` 時返回 `Config`
+    pub fn validate(&self) -> Result<()> {
+        if self.chunk_size == 0 {
+            return Err(AuditorError::Config(
+                "chunk_size must be non-zero".to_string(),
+            ));
+        }
+        if self.max_challenges == 0 {
+            return Err(AuditorError::Config(
+                "max_challenges must be non-zero".to_string(),
+            ));
+        }
+        if self.max_challenges > usize::from(u16::MAX) {
+            return Err(AuditorError::Config(format!(
+                "max_challenges = {} exceeds u16::MAX ({})",
+                self.max_challenges,
+                u16::MAX
+            )));
+        }
+        Ok(())
+    }
+}
+
 /// 審計數據結構
 ///
 /// 包含單次審計的所有關鍵信息
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AuditData {
-    /// Walrus Blob ID（用於索引和關聯）
+    /// Walrus Blob ID（用於索引和關聯；離線審計本地文件時為文件路徑）
     pub blob_id: String,
 
     /// 內容哈希（SHA-256）- 應用層完整性基準
@@ -63,7 +188,7 @@ pub struct AuditData {
 
     /// Merkle 根（Blake2b-256）- 協議層完整性證明
     ///
-    /// 使用 4KB chunks 構建的 Merkle Tree 根哈希
+    /// 按 `chunk_size` 切片構建的 Merkle Tree 根哈希
     pub merkle_root: String,
 
     /// 總挑戰次數
@@ -91,12 +216,127 @@ pub struct AuditData {
     ///
     /// - "ACCESSIBLE": Blob 可成功下載並完成哈希計算
     /// - "UNREACHABLE": Aggregator 無法訪問
-    /// - "CORRUPTED": 下載成功但哈希與記錄不符（僅在後續審計時出現）
+    /// - "CORRUPTED": 下載成功但哈希與記錄不符（與內容基準比對，僅在後續審計時出現）
+    /// - "DELETED": Aggregator 返回 404 且鏈上確認 Blob 已被所有者刪除或存儲已回收
     pub verification_status: VerificationStatus,
 
     /// 可選：Sui 對象 ID（如果已知）
     #[serde(skip_serializing_if = "Option::is_none")]
     pub sui_object_id: Option<String>,
+
+    /// 可選：下載前的資源檢查決策（啟用資源守衛時記錄）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resource_decision: Option<ResourceDecision>,
+
+    /// 可選：HTTP 捕獲目錄的 Blake2b-256 摘要（啟用捕獲模式時）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub capture_digest: Option<String>,
+
+    /// 可選：去重依據（相同內容近期已被完整審計，本次只做抽查）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deduplicated_from: Option<DeduplicatedFrom>,
+
+    /// 可選：生成報告的構建（簽名時寫入）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub producer: Option<Producer>,
+
+    /// 可選：公開的挑戰集（啟用承諾時，對應此前寫入承諾日誌的承諾）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub challenge_reveal: Option<ChallengeReveal>,
+
+    /// 可選：挑戰種子的來源（以 Sui 檢查點導出挑戰時，見 [`crate::challenge_seed`]）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub challenge_seed_source: Option<ChallengeSeedSource>,
+
+    /// 可選：導出挑戰索引的種子（十六進制）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub challenge_seed: Option<String>,
+
+    /// 可選：葉子哈希布隆過濾器（旁路數據，不序列化、不在簽名範圍內）
+    ///
+    /// 啟用 chunk 過濾器時構建，隨審計歷史保存，供下次審計做快速比對
+    #[serde(skip)]
+    pub chunk_filter: Option<ChunkFilter>,
+
+    /// 可選：與內容基準不符時的預期值與觀測值（此時狀態為 `CORRUPTED`）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hash_drift: Option<HashDrift>,
+
+    /// 可選：重新編碼推導的 `blob_id` 是否與被審計的一致
+    ///
+    /// 僅在啟用 blob_id 驗證時記錄；不一致時狀態為 `CORRUPTED`，
+    /// Blob 超過大小上限（跳過）或編碼失敗時為空
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blob_id_verified: Option<bool>,
+
+    /// 可選：構建 Merkle 樹的切片大小（bytes）
+    ///
+    /// `merkle_root` 只有與切片大小一起才有意義；未構建 Merkle 樹時為空。
+    /// 記錄此字段之前的數據沒有該值，均按 [`DEFAULT_CHUNK_SIZE`] 構建
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chunk_size: Option<u32>,
+
+    /// 可選：鏈上元數據中的 Blob 對象 ID
+    ///
+    /// 以下鏈上字段只由 [`IntegrityVerifier::audit_blob_with_metadata`] 填寫；
+    /// 與調用方提供的 `sui_object_id` 不同，這些值已從鏈上讀取並與 `blob_id` 核對
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blob_object_id: Option<String>,
+
+    /// 可選：Erasure coding 參數 - 數據 slivers 數量
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub encoding_k: Option<u64>,
+
+    /// 可選：Erasure coding 參數 - 總 slivers 數量
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub encoding_n: Option<u64>,
+
+    /// 可選：Blob 存儲期的開始 epoch
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start_epoch: Option<u32>,
+
+    /// 可選：Blob 存儲期的結束 epoch
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end_epoch: Option<u32>,
+
+    /// 可選：元數據中的默克爾根（十六進制；鏈上 Blob 對象不記錄根哈希，通常為空）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub onchain_merkle_root: Option<String>,
+
+    /// 可選：下載大小是否與鏈上 `blob_size` 一致（未下載到內容時為空）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub size_matches_chain: Option<bool>,
+
+    /// 審計方式：從 Aggregator 下載（默認，序列化時省略）或離線審計本地文件
+    #[serde(default, skip_serializing_if = "AuditMethod::is_aggregator")]
+    pub audit_method: AuditMethod,
+}
+
+impl AuditData {
+    /// 記錄 Blob 的鏈上元數據，並核對下載大小與鏈上 `blob_size`
+    ///
+    /// 大小不一致時記錄警告並將 `size_matches_chain` 設為 `false`，不改變驗證狀態
+    pub fn apply_chain_metadata(&mut self, metadata: &BlobMetadata) {
+        self.blob_object_id = Some(metadata.blob_object_id.to_string());
+        self.encoding_k = Some(metadata.encoding_k);
+        self.encoding_n = Some(metadata.encoding_n);
+        self.start_epoch = Some(metadata.start_epoch);
+        self.end_epoch = Some(metadata.end_epoch);
+        self.onchain_merkle_root =
+            Some(hex::encode(&metadata.merkle_root)).filter(|root| !root.is_empty());
+
+        let downloaded = matches!(
+            self.verification_status,
+            VerificationStatus::Accessible | VerificationStatus::Corrupted
+        );
+        self.size_matches_chain = downloaded.then_some(self.file_size == metadata.blob_size);
+        if self.size_matches_chain == Some(false) {
+            warn!(
+                "Blob {} size mismatch: downloaded {} bytes, on-chain blob_size is {}",
+                self.blob_id, self.file_size, metadata.blob_size
+            );
+        }
+    }
 }
 
 /// 驗證狀態枚舉
@@ -109,6 +349,176 @@ pub enum VerificationStatus {
     Unreachable,
     /// Blob 內容與預期哈希不符（數據損壞）
     Corrupted,
+    /// Blob 已被所有者刪除（不計為存儲節點故障）
+    Deleted,
+    /// Blob 的存儲期已結束（已過 `end_epoch` 或不再處於認證狀態，不計為存儲節點故障）
+    Expired,
+}
+
+/// 按 Aggregator 404 響應體中的原因判斷 Blob 狀態，無法識別時返回 `None`
+///
+/// Aggregator 對已刪除的 Blob 返回包含 `deleted` 的信息，對存儲期已結束的 Blob
+/// 返回 `expired` 或 `not certified`（過期的 Blob 不再處於認證狀態）；不區分大小寫
+pub fn classify_not_found_body(body: &[u8]) -> Option<VerificationStatus> {
+    let reason = String::from_utf8_lossy(body).to_ascii_lowercase();
+    if reason.contains("deleted") {
+        Some(VerificationStatus::Deleted)
+    } else if reason.contains("expired") || reason.contains("not certified") {
+        Some(VerificationStatus::Expired)
+    } else {
+        None
+    }
+}
+
+/// 流式審計的內存佔用估計：一個 chunk 加上所有層的節點哈希（約為葉子數的兩倍）
+fn streaming_buffer_bytes(blob_size: u64, chunk_size: usize) -> u64 {
+    let leaves = blob_size.div_ceil(chunk_size as u64);
+    chunk_size as u64 + leaves * 2 * 32
+}
+
+/// 以流式讀取時記錄的葉子哈希驗證挑戰集的多葉子證明（原始數據不保留在內存中）
+fn verify_multi_proof(tree: &MerkleTree, leaf_indices: &[usize], root: &[u8; 32]) -> bool {
+    let proof = match tree.generate_multi_proof(leaf_indices) {
+        Ok(proof) => proof,
+        Err(e) => {
+            warn!("Failed to generate multi-proof for {} chunks: {}", leaf_indices.len(), e);
+            return false;
+        }
+    };
+    let leaves: Vec<(usize, [u8; 32])> = leaf_indices
+        .iter()
+        .map(|&index| (index, tree.leaf_hashes()[index]))
+        .collect();
+
+    proof.leaf_count == tree.leaf_count() as u64 && proof.verify_leaf_hashes(&leaves, root)
+}
+
+/// 驗證挑戰集，返回每個挑戰的結果（順序與 `indices` 相同）
+///
+/// 整個挑戰集用一個多葉子證明驗證；失敗時逐個 chunk 驗證以定位失敗的 chunk
+fn verify_challenges(blob_id: &str, tree: &MerkleTree, indices: &[u64]) -> Vec<bool> {
+    let root = tree.root();
+    let leaf_indices: Vec<usize> = indices.iter().map(|&index| index as usize).collect();
+    if verify_multi_proof(tree, &leaf_indices, &root) {
+        debug!("✓ {} chunks verified with one multi-proof", leaf_indices.len());
+        return vec![true; leaf_indices.len()];
+    }
+
+    warn!(
+        "Multi-proof verification failed for blob {}, checking chunks individually",
+        blob_id
+    );
+    leaf_indices
+        .iter()
+        .enumerate()
+        .map(|(challenge_num, &leaf_index)| {
+            debug!(
+                "Challenge {}/{}: Testing chunk {}",
+                challenge_num + 1,
+                leaf_indices.len(),
+                leaf_index
+            );
+
+            // 生成 Merkle Proof
+            let proof = match tree.generate_proof(leaf_index) {
+                Ok(p) => p,
+                Err(e) => {
+                    warn!("Failed to generate proof for chunk {}: {}", leaf_index, e);
+                    return false;
+                }
+            };
+
+            // 以流式讀取時記錄的葉子哈希驗證 Merkle Proof（原始數據不保留在內存中）
+            let leaf_hash = &tree.leaf_hashes()[leaf_index];
+            let is_valid = proof.verify_leaf_hash(leaf_hash, &root, tree.leaf_count() as u64);
+
+            if is_valid {
+                debug!("✓ Chunk {} verification passed", leaf_index);
+            } else {
+                warn!("✗ Chunk {} verification FAILED", leaf_index);
+            }
+            is_valid
+        })
+        .collect()
+}
+
+/// 下載並哈希後的 Blob（挑戰階段的輸入）
+struct HashedBlob {
+    /// 內容哈希（SHA-256，十六進制）
+    content_hash: String,
+    /// 下載的字節數
+    file_size: u64,
+    /// Aggregator 返回的 `ETag`
+    etag: Option<String>,
+    /// 由葉子哈希構建的 Merkle 樹
+    tree: MerkleTree,
+    /// 為 blob_id 驗證保留的完整數據
+    retained: Option<Vec<u8>>,
+    /// 資源守衛決策
+    resource_decision: Option<ResourceDecision>,
+}
+
+impl HashedBlob {
+    /// 資源守衛的降級決策是否要求停用寫盤（內容緩存與檢查點）
+    fn caching_disabled(&self) -> bool {
+        self.resource_decision
+            .as_ref()
+            .is_some_and(|decision| decision.disable_caching)
+    }
+
+    /// 下載哈希完成時的檢查點（尚未確定挑戰集）
+    fn checkpoint(&self, blob_id: &str, chunk_size: usize) -> AuditCheckpoint {
+        AuditCheckpoint {
+            blob_id: blob_id.to_string(),
+            content_length: self.file_size,
+            chunk_size: chunk_size as u32,
+            etag: self.etag.clone(),
+            content_hash: self.content_hash.clone(),
+            leaf_hashes: self.tree.leaf_hashes().to_vec(),
+            challenges: None,
+        }
+    }
+}
+
+/// 下載階段的結果
+enum Download {
+    /// 下載並哈希完成，進入挑戰階段
+    Hashed(Box<HashedBlob>),
+    /// 無需挑戰（下載失敗或內容為空），審計已結束
+    Finished(Box<AuditData>),
+}
+
+/// 記錄一次審計的結果、挑戰數與耗時（審計出錯時結果記為 `error`）
+fn record_audit_metrics(metrics: &Metrics, result: &Result<AuditData>, duration: Duration) {
+    match result {
+        Ok(data) => {
+            metrics.record_audit(audit_result(&data.verification_status), duration);
+            metrics.record_challenges(
+                data.successful_verifications.into(),
+                data.failed_verifications.into(),
+            );
+        }
+        Err(_) => metrics.record_audit(RESULT_ERROR, duration),
+    }
+}
+
+/// 記錄快速比對結果
+fn log_quick_compare(blob_id: &str, result: &QuickCompare) {
+    if result.is_suspect() {
+        warn!(
+            "Quick compare for blob {}: {}/{} sampled chunks outside prior filter, {} → {} chunks",
+            blob_id,
+            result.outside_filter.len(),
+            result.sampled,
+            result.prior_leaf_count,
+            result.leaf_count
+        );
+    } else {
+        debug!(
+            "Quick compare for blob {}: {} sampled chunks match prior filter",
+            blob_id, result.sampled
+        );
+    }
 }
 
 /// 完整性驗證器
@@ -118,16 +528,68 @@ pub struct IntegrityVerifier {
     /// HTTP 客戶端
     http_client: Client,
 
+    /// 每次讀取的超時（見 [`crate::http`]）
+    read_timeout: Duration,
+
     /// Walrus Aggregator URL
     aggregator_url: String,
+
+    /// 可選的資源守衛（下載前檢查磁盤/內存）
+    resource_guard: Option<ResourceGuard>,
+
+    /// 可選的 HTTP 捕獲根目錄（每次審計在其下創建獨立子目錄）
+    capture_dir: Option<PathBuf>,
+
+    /// 可選的鏈上 Blob 對象查詢（404 時區分刪除與不可達）
+    object_lookup: Option<Arc<dyn BlobObjectLookup>>,
+
+    /// 可選的內容去重（審計歷史與配置）
+    dedup: Option<(Arc<AuditHistory>, DedupConfig)>,
+
+    /// 可選的 chunk 過濾器（構建並與上次審計快速比對）
+    chunk_filter: Option<ChunkFilterConfig>,
+
+    /// 可選的挑戰承諾日誌（挑戰前承諾挑戰集）
+    commitments: Option<Arc<CommitmentLog>>,
+
+    /// 可選的鏈上挑戰種子（檢查點來源與審計員地址）
+    challenge_seed: Option<(Arc<dyn CheckpointSource>, String)>,
+
+    /// 可選的熔斷器（與其他驗證器和存儲節點客戶端共享）
+    breaker: Option<Arc<CircuitBreaker>>,
+
+    /// 可選的按主機限流器（與訪問同一主機的其他客戶端共享）
+    rate_limiter: Option<Arc<RateLimiter>>,
+
+    /// 可選的內容基準（跨運行檢測哈希漂移）
+    baseline: Option<Arc<BaselineStore>>,
+
+    /// 可選的 Prometheus 指標（守護進程共享）
+    metrics: Option<Arc<Metrics>>,
+
+    /// 可選的 blob_id 重新編碼驗證（編碼器與 Blob 大小上限）
+    blob_id_check: Option<(Arc<dyn BlobIdEncoder>, u64)>,
+
+    /// 可選的審計檢查點（可恢復審計）
+    checkpoints: Option<Arc<CheckpointStore>>,
+
+    /// 可選的內容緩存（以 `ETag` 重新驗證，304 時跳過下載與哈希）
+    content_cache: Option<Arc<dyn ContentCache>>,
+
+    /// 切片與挑戰參數
+    config: IntegrityVerifierConfig,
 }
 
 impl IntegrityVerifier {
-    /// 創建新的完整性驗證器
+    /// 以默認 HTTP 配置創建新的完整性驗證器
     ///
     /// # 參數
     /// - `aggregator_url`: Walrus Aggregator 的基礎 URL
     ///
+    /// # Panics
+    /// HTTP 客戶端無法創建（TLS 後端初始化失敗）時 panic；
+    /// 需要處理錯誤或自定義超時時使用 [`IntegrityVerifier::from_http_config`]
+    ///
     /// # 示例
     /// ```no_run
     /// use auditor_node::integrity::IntegrityVerifier;
@@ -137,17 +599,224 @@ impl IntegrityVerifier {
     /// );
     /// ```
     pub fn new(aggregator_url: String) -> Self {
-        let http_client = Client::builder()
-            .timeout(std::time::Duration::from_secs(60))
-            .build()
-            .expect("Failed to build HTTP client");
+        Self::from_http_config(aggregator_url, &HttpClientConfig::default())
+            .expect("Failed to build HTTP client")
+    }
+
+    /// 按 HTTP 配置創建完整性驗證器
+    ///
+    /// 下載時每次讀取（響應頭或響應體的下一塊）都必須在 `http.read_timeout_secs` 內到達，
+    /// 整個下載不設總時限
+    ///
+    /// # 錯誤
+    /// HTTP 客戶端無法創建時返回 `AuditorError::HttpRequest`
+    pub fn from_http_config(aggregator_url: String, http: &HttpClientConfig) -> Result<Self> {
+        let http_client = build_client(http)?;
 
         info!("Created IntegrityVerifier for {}", aggregator_url);
 
-        Self {
+        Ok(Self {
             http_client,
+            read_timeout: http.read_timeout(),
             aggregator_url,
+            resource_guard: None,
+            capture_dir: None,
+            object_lookup: None,
+            dedup: None,
+            chunk_filter: None,
+            commitments: None,
+            challenge_seed: None,
+            breaker: None,
+            rate_limiter: None,
+            baseline: None,
+            metrics: None,
+            blob_id_check: None,
+            checkpoints: None,
+            content_cache: None,
+            config: IntegrityVerifierConfig::default(),
+        })
+    }
+
+    /// 設置切片大小與挑戰次數（見 [`IntegrityVerifierConfig`]）
+    ///
+    /// 改變切片大小會改變 Merkle 根：啟用內容基準時，同一 Blob 會被記為哈希漂移，
+    /// 需要先清除其基準
+    ///
+    /// # Panics
+    /// 配置未通過 [`IntegrityVerifierConfig::validate`] 時 panic
+    pub fn with_config(mut self, config: IntegrityVerifierConfig) -> Self {
+        if let Err(e) = config.validate() {
+            panic!("{}", e);
         }
+        self.config = config;
+        self
+    }
+
+    /// 設置鏈上 Blob 對象查詢
+    ///
+    /// 啟用後，Aggregator 返回 404 時會查詢 Blob 對象：已刪除或存儲已回收的 Blob
+    /// 記為 `VerificationStatus::Deleted`，而非 `Unreachable`
+    pub fn with_object_lookup(mut self, lookup: Arc<dyn BlobObjectLookup>) -> Self {
+        self.object_lookup = Some(lookup);
+        self
+    }
+
+    /// 啟用跨 Blob 內容去重
+    ///
+    /// 內容哈希與新鮮度窗口內其他 Blob 的完整審計相同時，只抽查
+    /// `spot_check_challenges` 個 chunk，並在 `AuditData::deduplicated_from` 中記錄依據。
+    /// `config.enabled` 為 false 時不啟用
+    pub fn with_dedup(mut self, history: Arc<AuditHistory>, config: DedupConfig) -> Self {
+        self.dedup = config.enabled.then_some((history, config));
+        self
+    }
+
+    /// 去重使用的審計歷史（報告簽名後應記錄到其中）
+    pub fn dedup_history(&self) -> Option<&Arc<AuditHistory>> {
+        self.dedup.as_ref().map(|(history, _)| history)
+    }
+
+    /// 啟用 chunk 過濾器
+    ///
+    /// 審計時將葉子哈希寫入 `AuditData::chunk_filter`；同時啟用去重時，
+    /// 挑戰前先與歷史中同一 Blob 上次的過濾器快速比對並記錄結果。
+    /// `config.enabled` 為 false 時不啟用
+    pub fn with_chunk_filter(mut self, config: ChunkFilterConfig) -> Self {
+        self.chunk_filter = config.enabled.then_some(config);
+        self
+    }
+
+    /// 啟用挑戰承諾
+    ///
+    /// 每次審計在第一個挑戰之前將 `Blake2b-256(seed || indices)` 寫入承諾日誌，
+    /// 並在 `AuditData::challenge_reveal` 中公開種子與索引；承諾寫入失敗時審計中止
+    pub fn with_commitments(mut self, log: Arc<CommitmentLog>) -> Self {
+        self.commitments = Some(log);
+        self
+    }
+
+    /// 以最新 Sui 檢查點導出挑戰種子（見 [`crate::challenge_seed`]）
+    ///
+    /// 挑戰索引由 `HKDF(checkpoint_digest || blob_id || auditor_address)` 確定性地導出，
+    /// 種子與來源記錄在 `AuditData::challenge_seed` / `challenge_seed_source` 中；
+    /// 讀取檢查點失敗時退回隨機種子
+    pub fn with_challenge_seed(
+        mut self,
+        source: Arc<dyn CheckpointSource>,
+        auditor_address: impl Into<String>,
+    ) -> Self {
+        self.challenge_seed = Some((source, auditor_address.into()));
+        self
+    }
+
+    /// 啟用熔斷
+    ///
+    /// Aggregator 錯誤率過高時，審計在下載前直接返回 `AuditorError::CircuitOpen`，
+    /// 調用方（守護進程）據此暫停調度，而不是把每個 Blob 都記為不可達
+    pub fn with_breaker(mut self, breaker: Arc<CircuitBreaker>) -> Self {
+        self.breaker = Some(breaker);
+        self
+    }
+
+    /// 啟用按主機限流：每個 Aggregator 請求發出前取一個令牌
+    pub fn with_rate_limiter(mut self, rate_limiter: Arc<RateLimiter>) -> Self {
+        self.rate_limiter = Some(rate_limiter);
+        self
+    }
+
+    /// 啟用內容基準（TOFU）
+    ///
+    /// 每次成功下載都記錄內容哈希、Merkle 根與文件大小；同一 Blob 的首次觀測為基準，
+    /// 之後與基準不符時記為 `VerificationStatus::Corrupted`，並在 `AuditData::hash_drift`
+    /// 中記錄預期值與觀測值
+    pub fn with_baseline(mut self, store: Arc<BaselineStore>) -> Self {
+        self.baseline = Some(store);
+        self
+    }
+
+    /// 啟用 blob_id 驗證
+    ///
+    /// 下載時保留不超過 `max_bytes` 的 Blob，以 `encoder` 重新編碼推導 `blob_id`
+    /// 並與被審計的比對，結果記錄在 `AuditData::blob_id_verified` 中；
+    /// 不一致時記為 `VerificationStatus::Corrupted`，超過上限的 Blob 跳過驗證
+    pub fn with_blob_id_verification(
+        mut self,
+        encoder: Arc<dyn BlobIdEncoder>,
+        max_bytes: u64,
+    ) -> Self {
+        self.blob_id_check = Some((encoder, max_bytes));
+        self
+    }
+
+    /// 啟用檢查點
+    ///
+    /// [`IntegrityVerifier::audit_blob_resumable`] 將審計進度寫入 `store`，
+    /// 中斷後的下一次嘗試可跳過下載與哈希
+    pub fn with_checkpoints(mut self, store: Arc<CheckpointStore>) -> Self {
+        self.checkpoints = Some(store);
+        self
+    }
+
+    /// 啟用內容緩存
+    ///
+    /// 下載時以緩存的 `ETag` 發出條件 GET：Aggregator 返回 304 時沿用緩存的內容哈希、
+    /// Merkle 根與葉子哈希，只重新執行挑戰抽樣（不重新驗證 blob_id）；完整響應使條目失效，
+    /// 下載完成後重新寫入。抓包模式不使用緩存
+    pub fn with_content_cache(mut self, cache: Arc<dyn ContentCache>) -> Self {
+        self.content_cache = Some(cache);
+        self
+    }
+
+    /// 記錄指標：每次審計的結果、挑戰數、耗時與下載字節數
+    ///
+    /// 已啟用的審計歷史、內容基準與承諾日誌的輪轉計數器同時登記導出
+    pub fn with_metrics(mut self, metrics: Arc<Metrics>) -> Self {
+        let rotations = [
+            (
+                crate::history::ROTATION_OUTPUT,
+                self.dedup
+                    .as_ref()
+                    .and_then(|(history, _)| history.rotation_stats()),
+            ),
+            (
+                crate::baseline::ROTATION_OUTPUT,
+                self.baseline
+                    .as_ref()
+                    .and_then(|store| store.rotation_stats()),
+            ),
+            (
+                crate::commitment::ROTATION_OUTPUT,
+                self.commitments
+                    .as_ref()
+                    .and_then(|log| log.rotation_stats()),
+            ),
+        ];
+        for (output, stats) in rotations {
+            if let Some(stats) = stats {
+                metrics.watch_rotation(output, stats);
+            }
+        }
+
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// 啟用 HTTP 捕獲模式
+    ///
+    /// 每次審計的請求/響應記錄寫入 `capture_dir/<blob_id>-<timestamp>/`，
+    /// 目錄摘要記錄在 `AuditData::capture_digest` 中
+    pub fn with_capture_dir(mut self, capture_dir: impl Into<PathBuf>) -> Self {
+        self.capture_dir = Some(capture_dir.into());
+        self
+    }
+
+    /// 設置資源守衛
+    ///
+    /// 啟用後，每次下載前會根據 `Content-Length` 檢查磁盤和內存餘量，
+    /// 決策結果記錄在 `AuditData::resource_decision` 中
+    pub fn with_resource_guard(mut self, guard: ResourceGuard) -> Self {
+        self.resource_guard = Some(guard);
+        self
     }
 
     /// 創建使用 Testnet 配置的驗證器
@@ -163,13 +832,14 @@ impl IntegrityVerifier {
     /// 3. 記錄時間戳和狀態
     ///
     /// # 參數
-    /// - `blob_id`: Walrus Blob ID（Base64 格式）
+    /// - `blob_id`: Walrus Blob ID
     ///
     /// # 返回
     /// - `Ok(AuditData)`: 審計成功，包含完整性數據
     /// - `Err(AuditorError)`: 審計失敗（網絡錯誤、超時等）
     ///
     /// # 錯誤處理
+    /// - HTTP 404 且鏈上對象已刪除 → `VerificationStatus::Deleted`
     /// - HTTP 4xx 錯誤 → `VerificationStatus::Unreachable`
     /// - HTTP 5xx 錯誤 → `VerificationStatus::Unreachable`
     /// - 網絡超時 → `AuditorError::StorageNodeUnreachable`
@@ -178,51 +848,436 @@ impl IntegrityVerifier {
     /// ```no_run
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// # use auditor_node::integrity::IntegrityVerifier;
+    /// # use auditor_node::types::BlobId;
     /// let verifier = IntegrityVerifier::new_testnet();
     ///
-    /// let audit_data = verifier.audit_blob("eRrTusk8yshFQpkemgDnbg0f4-qDo623V2NpeVG1Zcg").await?;
+    /// let blob_id = BlobId::parse("eRrTusk8yshFQpkemgDnbg0f4-qDo623V2NpeVG1Zcg")?;
+    /// let audit_data = verifier.audit_blob(&blob_id).await?;
     ///
     /// println!("Content hash: {}", audit_data.content_hash);
     /// println!("File size: {} bytes", audit_data.file_size);
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn audit_blob(&self, blob_id: &str) -> Result<AuditData> {
+    pub async fn audit_blob(&self, blob_id: &BlobId) -> Result<AuditData> {
+        self.audit_blob_with_object(blob_id, None).await
+    }
+
+    /// 審計單個 Blob（已知其 Sui 對象 ID）
+    ///
+    /// 對象 ID 用於在 Aggregator 返回 404 時查詢 Blob 是否已被刪除，
+    /// 並記錄在 `AuditData::sui_object_id` 中
+    pub async fn audit_blob_with_object(
+        &self,
+        blob_id: &BlobId,
+        sui_object_id: Option<&str>,
+    ) -> Result<AuditData> {
+        self.audit_blob_observed(blob_id, sui_object_id, &ProgressSink::default())
+            .await
+    }
+
+    /// 審計單個 Blob，並記錄其鏈上元數據（見 [`AuditData::apply_chain_metadata`]）
+    ///
+    /// 先從 `source`（如 [`crate::sui_client::AuditSystemClient`]）讀取 Blob 對象的元數據，
+    /// 再按 [`audit_blob_with_object`](Self::audit_blob_with_object) 審計，
+    /// 報告因此綁定到具體的已註冊 Blob：對象 ID、編碼參數、存儲期與鏈上大小的比對都在簽名範圍內
+    ///
+    /// # 錯誤
+    /// - 元數據查詢失敗: 返回查詢的錯誤（不下載 Blob）
+    /// - 對象記錄的 `blob_id` 與被審計的不同: 返回 `InvalidBlobId` 錯誤
+    pub async fn audit_blob_with_metadata(
+        &self,
+        blob_id: &BlobId,
+        blob_object_id: &MoveId,
+        source: &dyn BlobMetadataSource,
+    ) -> Result<AuditData> {
+        let metadata = source.blob_metadata(blob_object_id).await?;
+        if BlobId::parse(&metadata.blob_id)? != *blob_id {
+            return Err(AuditorError::InvalidBlobId(format!(
+                "Blob object {} holds blob {}, not {}",
+                blob_object_id, metadata.blob_id, blob_id
+            )));
+        }
+
+        let object_id = blob_object_id.to_string();
+        let mut audit_data = self
+            .audit_blob_with_object(blob_id, Some(&object_id))
+            .await?;
+        audit_data.apply_chain_metadata(&metadata);
+        Ok(audit_data)
+    }
+
+    /// 審計單個 Blob，並向 `progress` 發出進度事件（見 [`crate::progress`]）
+    ///
+    /// 事件以 `try_send` 發出：接收端處理不及或已丟棄時審計照常進行。
+    /// 最後一個事件總是 [`AuditProgress::Finished`]（包括審計出錯時）
+    pub async fn audit_blob_with_progress(
+        &self,
+        blob_id: &BlobId,
+        progress: mpsc::Sender<AuditProgress>,
+    ) -> Result<AuditData> {
+        self.audit_blob_observed(blob_id, None, &ProgressSink::new(progress))
+            .await
+    }
+
+    /// 審計單個 Blob，發出進度事件（以 `Finished` 結束）
+    async fn audit_blob_observed(
+        &self,
+        blob_id: &BlobId,
+        sui_object_id: Option<&str>,
+        events: &ProgressSink,
+    ) -> Result<AuditData> {
+        let result = self
+            .audit_blob_captured(blob_id, sui_object_id, events)
+            .await;
+        events.finish(&result, |audit_data| {
+            FinishedStatus::Verification(audit_data.verification_status.clone())
+        });
+        result
+    }
+
+    /// 審計單個 Blob，啟用捕獲時記錄 HTTP 交換並附上摘要
+    async fn audit_blob_captured(
+        &self,
+        blob_id: &BlobId,
+        sui_object_id: Option<&str>,
+        events: &ProgressSink,
+    ) -> Result<AuditData> {
+        let _slot = self.acquire_slot().await;
+        let blob_id = blob_id.to_string();
+        let blob_id = blob_id.as_str();
+        let capture = match &self.capture_dir {
+            Some(dir) => Some(HttpCapture::for_audit(dir, blob_id)?),
+            None => None,
+        };
+
+        let started = Instant::now();
+        let result = self
+            .audit_blob_inner(blob_id, sui_object_id, capture.as_ref(), events)
+            .await;
+        if let Some(metrics) = &self.metrics {
+            record_audit_metrics(metrics, &result, started.elapsed());
+        }
+
+        match capture {
+            Some(capture) => {
+                let digest = capture.finish()?;
+                let mut audit_data = result?;
+                audit_data.capture_digest = Some(digest);
+                Ok(audit_data)
+            }
+            None => result,
+        }
+    }
+
+    /// 審計單個 Blob 的實際流程（可選地記錄 HTTP 交換）
+    async fn audit_blob_inner(
+        &self,
+        blob_id: &str,
+        sui_object_id: Option<&str>,
+        capture: Option<&HttpCapture>,
+        events: &ProgressSink,
+    ) -> Result<AuditData> {
+        match self
+            .download_blob(blob_id, sui_object_id, capture, events)
+            .await?
+        {
+            Download::Hashed(hashed) => {
+                self.challenge_blob(blob_id, sui_object_id, *hashed, None, events)
+                    .await
+            }
+            Download::Finished(audit_data) => Ok(*audit_data),
+        }
+    }
+
+    /// 可恢復的審計
+    ///
+    /// 與 [`IntegrityVerifier::audit_blob`] 相同，但在下載哈希完成、挑戰集確定和挑戰驗證完成後
+    /// 寫入檢查點（見 [`crate::checkpoint`]）。存在檢查點且 Aggregator 返回的
+    /// `ETag`/`Content-Length` 與之一致時，從記錄的葉子哈希恢復，跳過下載與哈希；
+    /// 恢復的審計不保留原始數據，因此跳過 blob_id 重新編碼驗證。成功完成後刪除檢查點。
+    ///
+    /// 未配置檢查點目錄或啟用了 HTTP 捕獲（需要記錄完整的下載）時等同於 `audit_blob`
+    pub async fn audit_blob_resumable(&self, blob_id: &BlobId) -> Result<AuditData> {
+        self.audit_blob_resumable_observed(blob_id, &ProgressSink::default())
+            .await
+    }
+
+    /// 可恢復的審計，並向 `progress` 發出進度事件
+    ///
+    /// 見 [`IntegrityVerifier::audit_blob_resumable`] 與
+    /// [`IntegrityVerifier::audit_blob_with_progress`]；從檢查點恢復時不發出下載事件
+    pub async fn audit_blob_resumable_with_progress(
+        &self,
+        blob_id: &BlobId,
+        progress: mpsc::Sender<AuditProgress>,
+    ) -> Result<AuditData> {
+        self.audit_blob_resumable_observed(blob_id, &ProgressSink::new(progress))
+            .await
+    }
+
+    async fn audit_blob_resumable_observed(
+        &self,
+        blob_id: &BlobId,
+        events: &ProgressSink,
+    ) -> Result<AuditData> {
+        let Some(store) = self.checkpoints.as_ref().filter(|_| self.capture_dir.is_none()) else {
+            return self.audit_blob_observed(blob_id, None, events).await;
+        };
+        let _slot = self.acquire_slot().await;
+        let blob_id = blob_id.to_string();
+
+        let started = Instant::now();
+        let result = self.audit_blob_checkpointed(&blob_id, store, events).await;
+        if let Some(metrics) = &self.metrics {
+            record_audit_metrics(metrics, &result, started.elapsed());
+        }
+
+        let result = result.and_then(|audit_data| {
+            store.remove(&blob_id)?;
+            Ok(audit_data)
+        });
+        events.finish(&result, |audit_data| {
+            FinishedStatus::Verification(audit_data.verification_status.clone())
+        });
+        result
+    }
+
+    /// 從檢查點恢復或從頭執行審計，並在各階段寫入檢查點
+    async fn audit_blob_checkpointed(
+        &self,
+        blob_id: &str,
+        store: &CheckpointStore,
+        events: &ProgressSink,
+    ) -> Result<AuditData> {
+        let resumed = match store.load(blob_id) {
+            Some(checkpoint) => self.resume_checkpoint(blob_id, checkpoint).await,
+            None => None,
+        };
+
+        let (hashed, mut checkpoint) = match resumed {
+            Some(resumed) => resumed,
+            None => match self.download_blob(blob_id, None, None, events).await? {
+                Download::Hashed(hashed) if hashed.caching_disabled() => {
+                    info!(
+                        "Not checkpointing the audit of blob {}: disk space is short",
+                        blob_id
+                    );
+                    return self
+                        .challenge_blob(blob_id, None, *hashed, None, events)
+                        .await;
+                }
+                Download::Hashed(hashed) => {
+                    let checkpoint = hashed.checkpoint(blob_id, self.config.chunk_size);
+                    store.save(&checkpoint)?;
+                    (*hashed, checkpoint)
+                }
+                Download::Finished(audit_data) => return Ok(*audit_data),
+            },
+        };
+
+        self.challenge_blob(
+            blob_id,
+            None,
+            hashed,
+            Some((store, &mut checkpoint)),
+            events,
+        )
+        .await
+    }
+
+    /// 檢查點仍對應 Aggregator 當前的內容時，從葉子哈希恢復 Merkle 樹
+    ///
+    /// 內容已變化或無法確認時返回 `None`（調用方從頭審計）
+    async fn resume_checkpoint(
+        &self,
+        blob_id: &str,
+        checkpoint: AuditCheckpoint,
+    ) -> Option<(HashedBlob, AuditCheckpoint)> {
+        let url = format!("{}/v1/blobs/{}", self.aggregator_url, blob_id);
+        self.breaker_acquire().ok()?;
+        self.throttle().await;
+        let response =
+            match with_read_timeout(self.read_timeout, self.http_client.head(&url).send()).await {
+                Ok(response) => response,
+                Err(e) => {
+                    self.breaker_record(false);
+                    warn!("Cannot check checkpoint for blob {}: {}", blob_id, e);
+                    return None;
+                }
+            };
+        self.breaker_record(!is_failure_status(response.status().as_u16()));
+        if !response.status().is_success() {
+            return None;
+        }
+
+        let content_length = response
+            .headers()
+            .get(CONTENT_LENGTH)
+            .and_then(|value| value.to_str().ok()?.parse().ok());
+        let etag = response
+            .headers()
+            .get(ETAG)
+            .and_then(|value| value.to_str().ok());
+        if checkpoint.chunk_size as usize != self.config.chunk_size {
+            info!(
+                "Checkpoint for blob {} uses {}-byte chunks, re-downloading",
+                blob_id, checkpoint.chunk_size
+            );
+            return None;
+        }
+        if !checkpoint.matches(content_length, etag) {
+            info!(
+                "Checkpoint for blob {} is stale (ETag or length changed), re-downloading",
+                blob_id
+            );
+            return None;
+        }
+
+        let tree =
+            MerkleTree::from_leaf_hashes(checkpoint.leaf_hashes.clone(), Default::default())
+                .ok()?;
+        info!(
+            "Resuming audit of blob {} from checkpoint: {} leaves, {} bytes",
+            blob_id,
+            tree.leaf_count(),
+            checkpoint.content_length
+        );
+        let hashed = HashedBlob {
+            content_hash: checkpoint.content_hash.clone(),
+            file_size: checkpoint.content_length,
+            etag: checkpoint.etag.clone(),
+            tree,
+            retained: None,
+            resource_decision: None,
+        };
+        Some((hashed, checkpoint))
+    }
+
+    /// 下載 Blob，流式計算 SHA-256 與 Merkle 葉子哈希
+    ///
+    /// 下載失敗（HTTP 錯誤）或內容為空時，直接返回最終的審計數據
+    async fn download_blob(
+        &self,
+        blob_id: &str,
+        sui_object_id: Option<&str>,
+        capture: Option<&HttpCapture>,
+        events: &ProgressSink,
+    ) -> Result<Download> {
         info!("Starting integrity audit for blob: {}", blob_id);
 
         let url = format!("{}/v1/blobs/{}", self.aggregator_url, blob_id);
         debug!("Downloading from: {}", url);
 
-        // 1. 下載 Blob
-        let response = self
-            .http_client
-            .get(&url)
-            .send()
+        // 1. 下載 Blob（有可用的緩存條目時以 If-None-Match 發出條件請求；抓包需要完整響應體）
+        let cached = match capture {
+            Some(_) => None,
+            None => self.cached_content(blob_id),
+        };
+        self.breaker_acquire()?;
+        self.throttle().await;
+        let started = Instant::now();
+        let mut request = self.http_client.get(&url);
+        if let Some((entry, _)) = &cached {
+            request = request.header(IF_NONE_MATCH, &entry.etag);
+        }
+        let request = request.build()?;
+        let request_headers = capture.map(|_| request.headers().clone());
+
+        let response = with_read_timeout(self.read_timeout, self.http_client.execute(request))
             .await
             .map_err(|e| {
-                if e.is_timeout() {
-                    AuditorError::StorageNodeUnreachable(format!(
+                if let Some(capture) = capture {
+                    let _ = capture.record(HttpExchange {
+                        method: "GET",
+                        url: &url,
+                        request_headers: request_headers.as_ref(),
+                        duration: started.elapsed(),
+                        error: Some(e.to_string()),
+                        ..Default::default()
+                    });
+                }
+                self.breaker_record(false);
+
+                match e {
+                    ReadError::TimedOut(_) => AuditorError::StorageNodeUnreachable(format!(
                         "Aggregator timeout: {}",
                         self.aggregator_url
-                    ))
-                } else if e.is_connect() {
-                    AuditorError::StorageNodeUnreachable(format!(
-                        "Cannot connect to aggregator: {}",
-                        self.aggregator_url
-                    ))
-                } else {
-                    AuditorError::StorageNodeUnreachable(format!("Network error: {}", e))
+                    )),
+                    ReadError::Http(e) if e.is_timeout() => AuditorError::StorageNodeUnreachable(
+                        format!("Aggregator timeout: {}", self.aggregator_url),
+                    ),
+                    ReadError::Http(e) if e.is_connect() => AuditorError::StorageNodeUnreachable(
+                        format!("Cannot connect to aggregator: {}", self.aggregator_url),
+                    ),
+                    ReadError::Http(e) => {
+                        AuditorError::StorageNodeUnreachable(format!("Network error: {}", e))
+                    }
                 }
             })?;
 
-        if !response.status().is_success() {
+        let status = response.status();
+        let response_headers = capture.map(|_| response.headers().clone());
+        self.breaker_record(!is_failure_status(status.as_u16()));
+
+        if let Some(cache) = &self.content_cache {
+            match cached {
+                Some((entry, tree)) if status == reqwest::StatusCode::NOT_MODIFIED => {
+                    info!(
+                        "Blob {} not modified (ETag {}), reusing cached hashes",
+                        blob_id, entry.etag
+                    );
+                    return Ok(Download::Hashed(Box::new(HashedBlob {
+                        content_hash: entry.content_hash,
+                        file_size: entry.file_size,
+                        etag: Some(entry.etag),
+                        tree,
+                        retained: None,
+                        resource_decision: None,
+                    })));
+                }
+                // 完整響應：緩存的哈希不再可信
+                _ => cache.remove(blob_id),
+            }
+        }
+
+        if !status.is_success() {
             warn!(
                 "Failed to download blob {}: HTTP {}",
                 blob_id,
-                response.status()
+                status
             );
 
-            return Ok(AuditData {
+            // 404 的響應體可能說明 Blob 被刪除或已過期
+            let not_found = status == reqwest::StatusCode::NOT_FOUND;
+            let body = if not_found || capture.is_some() {
+                with_read_timeout(self.read_timeout, response.bytes())
+                    .await
+                    .unwrap_or_default()
+            } else {
+                Default::default()
+            };
+
+            if let Some(capture) = capture {
+                capture.record(HttpExchange {
+                    method: "GET",
+                    url: &url,
+                    request_headers: request_headers.as_ref(),
+                    status: Some(status.as_u16()),
+                    response_headers: response_headers.as_ref(),
+                    response_body: Some(&body),
+                    duration: started.elapsed(),
+                    ..Default::default()
+                })?;
+            }
+
+            let verification_status = if not_found {
+                self.classify_missing_blob(blob_id, sui_object_id, &body).await
+            } else {
+                VerificationStatus::Unreachable
+            };
+
+            return Ok(Download::Finished(Box::new(AuditData {
                 blob_id: blob_id.to_string(),
                 content_hash: String::new(),
                 merkle_root: String::new(),
@@ -231,22 +1286,121 @@ impl IntegrityVerifier {
                 failed_verifications: 0,
                 file_size: 0,
                 timestamp: Utc::now().timestamp() as u64,
-                verification_status: VerificationStatus::Unreachable,
-                sui_object_id: None,
+                verification_status,
+                sui_object_id: sui_object_id.map(str::to_string),
+                resource_decision: None,
+                capture_digest: None,
+                deduplicated_from: None,
+                producer: None,
+                chunk_filter: None,
+                challenge_reveal: None,
+                challenge_seed_source: None,
+                challenge_seed: None,
+                hash_drift: None,
+                blob_id_verified: None,
+                chunk_size: None,
+                blob_object_id: None,
+                encoding_k: None,
+                encoding_n: None,
+                start_epoch: None,
+                end_epoch: None,
+                onchain_merkle_root: None,
+                size_matches_chain: None,
+                audit_method: AuditMethod::Aggregator,
+            })));
+        }
+
+        // 下載前檢查資源（資源不足且策略為拒絕時返回錯誤）
+        // 流式構建只在內存中保留葉子哈希；抓包時仍需緩衝完整響應
+        let resource_decision = match &self.resource_guard {
+            Some(guard) => {
+                let expected_size = response.content_length().unwrap_or(0);
+                let requirements = if capture.is_some() {
+                    ResourceRequirements::in_memory_download(expected_size)
+                } else {
+                    ResourceRequirements::streaming(
+                        expected_size,
+                        streaming_buffer_bytes(expected_size, self.config.chunk_size),
+                    )
+                };
+                let result = guard.check(&requirements);
+                if let Some(metrics) = &self.metrics {
+                    metrics.record_resources(&guard.snapshot());
+                    metrics.record_resource_decision(
+                        result
+                            .as_ref()
+                            .map_or(ResourceAction::Refused, |decision| decision.action),
+                    );
+                }
+                let decision = result?;
+                debug!("Resource guard decision: {:?}", decision.action);
+                Some(decision)
+            }
+            None => None,
+        };
+        let etag = response
+            .headers()
+            .get(ETAG)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string);
+
+        // 2. 流式讀取響應：同時計算 SHA-256（應用層完整性基準）與 Merkle 葉子哈希
+        let size_hint = response.content_length();
+        events.emit(AuditProgress::DownloadStarted { size_hint });
+        let mut response = response;
+        let mut hasher = Sha256::new();
+        let mut builder = MerkleTreeBuilder::new(self.config.chunk_size);
+        let mut captured_body = capture.map(|_| Vec::new());
+        // blob_id 驗證需要完整數據：只保留不超過大小上限的 Blob
+        let retain_limit = self.blob_id_check.as_ref().map(|(_, max_bytes)| *max_bytes);
+        let mut retained = retain_limit
+            .filter(|max_bytes| response.content_length().is_none_or(|len| len <= *max_bytes))
+            .map(|_| Vec::new());
+
+        while let Some(bytes) = with_read_timeout(self.read_timeout, response.chunk())
+            .await
+            .map_err(|e| {
+                AuditorError::StorageNodeUnreachable(format!("Failed to read response body: {}", e))
+            })?
+        {
+            hasher.update(&bytes);
+            builder.update(&bytes);
+            events.emit(AuditProgress::DownloadedBytes {
+                done: builder.bytes_written(),
+                total: size_hint,
             });
+            if let Some(body) = captured_body.as_mut() {
+                body.extend_from_slice(&bytes);
+            }
+            if let (Some(body), Some(max_bytes)) = (retained.as_mut(), retain_limit) {
+                if (body.len() + bytes.len()) as u64 > max_bytes {
+                    retained = None;
+                } else {
+                    body.extend_from_slice(&bytes);
+                }
+            }
+        }
+        let file_size = builder.bytes_written();
+        if let Some(metrics) = &self.metrics {
+            metrics.record_download(file_size);
         }
 
-        let content = response.bytes().await.map_err(|e| {
-            AuditorError::StorageNodeUnreachable(format!("Failed to read response body: {}", e))
-        })?;
+        if let (Some(capture), Some(body)) = (capture, captured_body) {
+            capture.record(HttpExchange {
+                method: "GET",
+                url: &url,
+                request_headers: request_headers.as_ref(),
+                status: Some(status.as_u16()),
+                response_headers: response_headers.as_ref(),
+                response_body: Some(&body),
+                duration: started.elapsed(),
+                ..Default::default()
+            })?;
+        }
 
-        debug!("Downloaded {} bytes", content.len());
+        debug!("Downloaded {} bytes", file_size);
 
-        // 2. 計算 SHA-256 哈希（應用層完整性基準）
-        let mut hasher = Sha256::new();
-        hasher.update(&content);
-        let hash_result = hasher.finalize();
-        let content_hash = hex::encode(hash_result);
+        let content_hash = hex::encode(hasher.finalize());
 
         info!(
             "SHA-256 hash: {}",
@@ -254,27 +1408,118 @@ impl IntegrityVerifier {
         );
 
         // 3. 構建 Merkle Tree（協議層完整性證明）
-        const CHUNK_SIZE: usize = 4096; // 4KB chunks
-
-        let merkle_tree = match MerkleTree::from_blob(&content, CHUNK_SIZE) {
+        events.emit(AuditProgress::MerkleBuildStarted {
+            leaf_count: file_size.div_ceil(self.config.chunk_size as u64) as usize,
+        });
+        let tree = match builder.finish() {
             Ok(tree) => tree,
             Err(e) => {
                 warn!("Failed to build Merkle tree: {}", e);
-                return Ok(AuditData {
+                return Ok(Download::Finished(Box::new(AuditData {
                     blob_id: blob_id.to_string(),
                     content_hash,
                     merkle_root: String::new(),
                     total_challenges: 0,
                     successful_verifications: 0,
                     failed_verifications: 0,
-                    file_size: content.len() as u64,
+                    file_size,
                     timestamp: Utc::now().timestamp() as u64,
                     verification_status: VerificationStatus::Accessible,
-                    sui_object_id: None,
-                });
+                    sui_object_id: sui_object_id.map(str::to_string),
+                    resource_decision,
+                    capture_digest: None,
+                    deduplicated_from: None,
+                    producer: None,
+                    chunk_filter: None,
+                    challenge_reveal: None,
+                    challenge_seed_source: None,
+                    challenge_seed: None,
+                    hash_drift: None,
+                    blob_id_verified: None,
+                    chunk_size: None,
+                    blob_object_id: None,
+                    encoding_k: None,
+                    encoding_n: None,
+                    start_epoch: None,
+                    end_epoch: None,
+                    onchain_merkle_root: None,
+                    size_matches_chain: None,
+                    audit_method: AuditMethod::Aggregator,
+                })));
             }
         };
 
+        // 磁盤不足的降級決策停用緩存寫入
+        let caching_disabled = resource_decision
+            .as_ref()
+            .is_some_and(|decision| decision.disable_caching);
+        let cache = self.content_cache.as_ref().filter(|_| !caching_disabled);
+        if let (Some(cache), Some(etag)) = (cache, &etag) {
+            cache.put(
+                blob_id,
+                CachedContent {
+                    etag: etag.clone(),
+                    content_hash: content_hash.clone(),
+                    merkle_root: hex::encode(tree.root()),
+                    file_size,
+                    chunk_size: self.config.chunk_size as u32,
+                    leaf_hashes: tree.leaf_hashes().to_vec(),
+                },
+            );
+        }
+
+        Ok(Download::Hashed(Box::new(HashedBlob {
+            content_hash,
+            file_size,
+            etag,
+            tree,
+            retained,
+            resource_decision,
+        })))
+    }
+
+    /// 可用於條件請求的緩存條目及由其葉子哈希恢復的 Merkle 樹
+    ///
+    /// 切片大小已改變或葉子哈希與緩存的 Merkle 根不符的條目被移除
+    fn cached_content(&self, blob_id: &str) -> Option<(CachedContent, MerkleTree)> {
+        let cache = self.content_cache.as_ref()?;
+        let entry = cache.get(blob_id)?;
+        let tree = (entry.chunk_size as usize == self.config.chunk_size)
+            .then(|| MerkleTree::from_leaf_hashes(entry.leaf_hashes.clone(), Default::default()))
+            .and_then(|tree| tree.ok())
+            .filter(|tree| hex::encode(tree.root()) == entry.merkle_root);
+
+        match tree {
+            Some(tree) => Some((entry, tree)),
+            None => {
+                debug!("Dropping unusable content cache entry for blob {}", blob_id);
+                cache.remove(blob_id);
+                None
+            }
+        }
+    }
+
+    /// 對已哈希的 Blob 執行挑戰-響應驗證並生成審計數據
+    ///
+    /// 提供檢查點時沿用其中已確定的挑戰集與已完成的結果，並在挑戰集確定、
+    /// 驗證完成後更新檢查點
+    async fn challenge_blob(
+        &self,
+        blob_id: &str,
+        sui_object_id: Option<&str>,
+        hashed: HashedBlob,
+        mut checkpoint: Option<(&CheckpointStore, &mut AuditCheckpoint)>,
+        events: &ProgressSink,
+    ) -> Result<AuditData> {
+        let HashedBlob {
+            content_hash,
+            file_size,
+            tree: merkle_tree,
+            retained,
+            resource_decision,
+            ..
+        } = hashed;
+
         let merkle_root_bytes = merkle_tree.root();
         let merkle_root = hex::encode(merkle_root_bytes);
         let leaf_count = merkle_tree.leaf_count();
@@ -285,89 +1530,396 @@ impl IntegrityVerifier {
             &merkle_root[..16]
         );
 
-        // 4. 執行挑戰-響應驗證
-        let total_challenges = if leaf_count == 1 {
-            1 // 對於單 chunk 的 blob，只驗證一次
+        // 挑戰前與同一 Blob 上次的過濾器快速比對（早期損壞信號）
+        let chunk_filter = self.chunk_filter.as_ref().map(|config| {
+            if let Some(prior) = self
+                .dedup_history()
+                .and_then(|history| history.latest_filter(blob_id))
+            {
+                if prior.chunk_size() == self.config.chunk_size {
+                    let result = quick_compare_leaves(
+                        &prior,
+                        merkle_tree.leaf_hashes(),
+                        config.quick_compare_samples,
+                    );
+                    log_quick_compare(blob_id, &result);
+                } else {
+                    debug!(
+                        "Skipping quick compare for blob {}: prior filter uses {}-byte chunks",
+                        blob_id,
+                        prior.chunk_size()
+                    );
+                }
+            }
+
+            ChunkFilter::from_tree(&merkle_tree, self.config.chunk_size, config.false_positive_rate)
+        });
+
+        // 4. 執行挑戰-響應驗證（相同內容近期已完整審計時只做抽查）
+        let deduplicated_from = self.dedup.as_ref().and_then(|(history, config)| {
+            history.find_fresh(
+                &content_hash,
+                blob_id,
+                Utc::now().timestamp() as u64,
+                config.freshness_window_secs,
+            )
+        });
+
+        let progress = checkpoint
+            .as_ref()
+            .and_then(|(_, checkpoint)| checkpoint.challenges.clone());
+        let progress = match progress {
+            Some(progress) => {
+                info!(
+                    "Reusing checkpointed challenge set for blob {}: {}/{} challenges done",
+                    blob_id,
+                    progress.results.len(),
+                    progress.indices.len()
+                );
+                progress
+            }
+            None => {
+                let seed = match &self.challenge_seed {
+                    Some((source, auditor)) => {
+                        Some(ChallengeSeed::resolve(source.as_ref(), blob_id, auditor).await)
+                    }
+                    None => None,
+                };
+                let progress =
+                    self.select_challenges(blob_id, leaf_count, deduplicated_from.as_ref(), seed)?;
+                if let Some((store, checkpoint)) = checkpoint.as_mut() {
+                    checkpoint.challenges = Some(progress.clone());
+                    store.save(checkpoint)?;
+                }
+                progress
+            }
+        };
+        let ChallengeProgress {
+            indices,
+            reveal: challenge_reveal,
+            seed,
+            mut results,
+        } = progress;
+        let total_challenges = checked_u16("total_challenges", indices.len())?;
+
+        info!("Starting challenge-response verification with {} challenges", total_challenges);
+
+        if results.len() < indices.len() {
+            let pending = &indices[results.len()..];
+            let verified = verify_challenges(blob_id, &merkle_tree, pending);
+            for (&index, &verified) in pending.iter().zip(&verified) {
+                events.emit(AuditProgress::ChallengeCompleted { index, verified });
+            }
+            results.extend(verified);
+            if let Some((store, checkpoint)) = checkpoint.as_mut() {
+                if let Some(progress) = checkpoint.challenges.as_mut() {
+                    progress.results = results.clone();
+                }
+                store.save(checkpoint)?;
+            }
+        }
+
+        let successful_verifications = checked_u16(
+            "successful_verifications",
+            results.iter().filter(|&&verified| verified).count(),
+        )?;
+        let failed_verifications = total_challenges - successful_verifications;
+        let success_rate = (successful_verifications as f64 / total_challenges as f64) * 100.0;
+
+        info!(
+            "Audit completed for blob {}: {} bytes, {} challenges, {}/{} passed ({:.1}%)",
+            blob_id,
+            file_size,
+            total_challenges,
+            successful_verifications,
+            total_challenges,
+            success_rate
+        );
+
+        // 5. 與內容基準比對（首次審計記錄基準）
+        let timestamp = Utc::now().timestamp() as u64;
+        let hash_drift = match &self.baseline {
+            Some(store) => store.observe(AuditObservation {
+                blob_id: blob_id.to_string(),
+                content_hash: content_hash.clone(),
+                merkle_root: merkle_root.clone(),
+                file_size,
+                observed_at: timestamp,
+            })?,
+            None => None,
+        };
+        let verification_status = match &hash_drift {
+            Some(drift) => {
+                warn!(
+                    "Blob {} drifted from its baseline of {}: content hash {} → {}, {} → {} bytes",
+                    blob_id,
+                    drift.baseline_at,
+                    &drift.expected_content_hash[..16.min(drift.expected_content_hash.len())],
+                    &drift.observed_content_hash[..16.min(drift.observed_content_hash.len())],
+                    drift.expected_file_size,
+                    drift.observed_file_size
+                );
+                VerificationStatus::Corrupted
+            }
+            None => VerificationStatus::Accessible,
+        };
+
+        // 6. 重新編碼驗證 blob_id（啟用時）
+        let blob_id_verified = self.verify_blob_id(blob_id, file_size, retained.as_deref());
+        let verification_status = if blob_id_verified == Some(false) {
+            VerificationStatus::Corrupted
         } else {
-            std::cmp::min(10, leaf_count) as u16 // 最多 10 次挑戰，或全部 chunks
+            verification_status
+        };
+
+        // 7. 生成審計數據
+        Ok(AuditData {
+            blob_id: blob_id.to_string(),
+            content_hash,
+            merkle_root,
+            total_challenges,
+            successful_verifications,
+            failed_verifications,
+            file_size,
+            timestamp,
+            verification_status,
+            sui_object_id: sui_object_id.map(str::to_string),
+            resource_decision,
+            capture_digest: None,
+            deduplicated_from,
+            producer: None,
+            chunk_filter,
+            challenge_reveal,
+            challenge_seed_source: seed.as_ref().map(|seed| seed.source.clone()),
+            challenge_seed: seed.as_ref().map(ChallengeSeed::seed_hex),
+            hash_drift,
+            blob_id_verified,
+            chunk_size: Some(self.config.chunk_size as u32),
+            blob_object_id: None,
+            encoding_k: None,
+            encoding_n: None,
+            start_epoch: None,
+            end_epoch: None,
+            onchain_merkle_root: None,
+            size_matches_chain: None,
+            audit_method: AuditMethod::Aggregator,
+        })
+    }
+
+    /// 確定挑戰集
+    ///
+    /// 挑戰集由 `seed`（未提供時為隨機種子）導出；啟用承諾時，承諾寫入成功後才返回
+    /// （之後才發出第一個挑戰）
+    fn select_challenges(
+        &self,
+        blob_id: &str,
+        leaf_count: usize,
+        deduplicated_from: Option<&DeduplicatedFrom>,
+        seed: Option<ChallengeSeed>,
+    ) -> Result<ChallengeProgress> {
+        let total_challenges = match (deduplicated_from, &self.dedup) {
+            (Some(source), Some((_, config))) => {
+                info!(
+                    "Blob {} has the same content as {} (report {}); spot-checking only",
+                    blob_id,
+                    source.blob_id,
+                    &source.report_digest[..16.min(source.report_digest.len())]
+                );
+                (config.spot_check_challenges.max(1) as usize).min(leaf_count)
+            }
+            _ => self.config.challenge_count(leaf_count),
         };
 
-        let mut successful_verifications = 0u16;
-        let mut failed_verifications = 0u16;
+        let reveal = match &seed {
+            Some(seed) => {
+                ChallengeReveal::from_seed(&seed.seed, total_challenges, leaf_count as u64)
+            }
+            None => ChallengeReveal::generate(total_challenges, leaf_count as u64),
+        };
+        let (indices, reveal) = match &self.commitments {
+            Some(log) => {
+                let committed = log.commit(blob_id, reveal)?;
+                info!(
+                    "Committed challenge set for blob {}: {}",
+                    blob_id,
+                    &committed.commitment().commitment[..16]
+                );
+                (committed.indices().to_vec(), Some(committed.into_reveal()))
+            }
+            None => (reveal.indices, None),
+        };
 
-        info!("Starting challenge-response verification with {} challenges", total_challenges);
+        Ok(ChallengeProgress {
+            indices,
+            reveal,
+            seed,
+            results: Vec::new(),
+        })
+    }
 
-        let mut rng = rand::thread_rng();
-        let mut challenged_indices = std::collections::HashSet::new();
+    /// 以注入的編碼器重新推導 `blob_id` 並比對
+    ///
+    /// 未啟用、未保留數據（Blob 超過大小上限或從檢查點恢復）或編碼失敗時返回 `None`
+    fn verify_blob_id(&self, blob_id: &str, file_size: u64, data: Option<&[u8]>) -> Option<bool> {
+        let (encoder, max_bytes) = self.blob_id_check.as_ref()?;
+        let Some(data) = data else {
+            info!(
+                "Blob ID verification skipped for {}: content not retained ({} bytes, limit {})",
+                blob_id, file_size, max_bytes
+            );
+            return None;
+        };
 
-        for challenge_num in 0..total_challenges {
-            // 隨機選擇一個未被挑戰過的 chunk 索引
-            let leaf_index = if leaf_count == 1 {
-                0
-            } else {
-                loop {
-                    let idx = rng.gen_range(0..leaf_count);
-                    if !challenged_indices.contains(&idx) {
-                        challenged_indices.insert(idx);
-                        break idx;
-                    }
-                }
-            };
+        let verdict = encoder
+            .blob_id(data)
+            .and_then(|computed| Ok((blob_ids_match(&computed, blob_id)?, computed)));
+        match verdict {
+            Ok((true, _)) => {
+                info!("Blob ID {} verified by re-encoding", blob_id);
+                Some(true)
+            }
+            Ok((false, computed)) => {
+                warn!(
+                    "BLOB ID MISMATCH: downloaded content of {} re-encodes to {}",
+                    blob_id, computed
+                );
+                Some(false)
+            }
+            Err(e) => {
+                warn!("Blob ID verification failed for {}: {}", blob_id, e);
+                None
+            }
+        }
+    }
 
-            debug!("Challenge {}/{}: Testing chunk {}", challenge_num + 1, total_challenges, leaf_index);
+    /// 快速比對：重新下載 Blob，抽樣 chunk 與上次審計的過濾器比對
+    ///
+    /// 落在過濾器之外的 chunk 必定已變化，可在完整審計前作為損壞信號。
+    /// 抽樣數取自 [`ChunkFilterConfig::quick_compare_samples`]（未啟用時用默認值）
+    ///
+    /// # 錯誤
+    /// - Aggregator 無法訪問或返回非 2xx → `AuditorError::StorageNodeUnreachable`
+    pub async fn quick_compare(
+        &self,
+        blob_id: &str,
+        prior_filter: &ChunkFilter,
+    ) -> Result<QuickCompare> {
+        let url = format!("{}/v1/blobs/{}", self.aggregator_url, blob_id);
+        debug!("Quick compare download from: {}", url);
 
-            // 生成 Merkle Proof
-            let proof = match merkle_tree.generate_proof(leaf_index) {
-                Ok(p) => p,
-                Err(e) => {
-                    warn!("Failed to generate proof for chunk {}: {}", leaf_index, e);
-                    failed_verifications += 1;
-                    continue;
-                }
-            };
+        self.breaker_acquire()?;
+        self.throttle().await;
+        let mut response = with_read_timeout(self.read_timeout, self.http_client.get(&url).send())
+            .await
+            .map_err(|e| {
+                self.breaker_record(false);
+                AuditorError::StorageNodeUnreachable(format!("Network error: {}", e))
+            })?;
+
+        let status = response.status();
+        self.breaker_record(!is_failure_status(status.as_u16()));
+        if !status.is_success() {
+            return Err(AuditorError::StorageNodeUnreachable(format!(
+                "Quick compare download of {} failed: HTTP {}",
+                blob_id, status
+            )));
+        }
+
+        let mut content = Vec::new();
+        while let Some(bytes) = with_read_timeout(self.read_timeout, response.chunk())
+            .await
+            .map_err(|e| {
+                AuditorError::StorageNodeUnreachable(format!("Failed to read response body: {}", e))
+            })?
+        {
+            content.extend_from_slice(&bytes);
+        }
+
+        let samples = self
+            .chunk_filter
+            .as_ref()
+            .map_or(DEFAULT_QUICK_COMPARE_SAMPLES, |config| config.quick_compare_samples);
+        let result = quick_compare_content(prior_filter, &content, samples);
+        log_quick_compare(blob_id, &result);
+
+        Ok(result)
+    }
 
-            // 獲取對應的 chunk 數據
-            let chunk_start = leaf_index * CHUNK_SIZE;
-            let chunk_end = std::cmp::min(chunk_start + CHUNK_SIZE, content.len());
-            let chunk = &content[chunk_start..chunk_end];
+    /// 啟用資源守衛時取得審計名額（遵守降級決策的並發上限）
+    async fn acquire_slot(&self) -> Option<AuditSlot> {
+        match &self.resource_guard {
+            Some(guard) => Some(guard.acquire_slot().await),
+            None => None,
+        }
+    }
 
-            // 驗證 Merkle Proof
-            let is_valid = proof.verify(chunk, &merkle_root_bytes);
+    /// 向 Aggregator 發出請求前檢查熔斷器
+    fn breaker_acquire(&self) -> Result<()> {
+        match &self.breaker {
+            Some(breaker) => breaker.acquire(&self.aggregator_url),
+            None => Ok(()),
+        }
+    }
 
-            if is_valid {
-                successful_verifications += 1;
-                debug!("✓ Chunk {} verification passed", leaf_index);
-            } else {
-                failed_verifications += 1;
-                warn!("✗ Chunk {} verification FAILED", leaf_index);
-            }
+    /// 啟用限流時等待 Aggregator 主機的令牌
+    async fn throttle(&self) {
+        if let Some(rate_limiter) = &self.rate_limiter {
+            rate_limiter.acquire(&self.aggregator_url).await;
         }
+    }
 
-        let success_rate = (successful_verifications as f64 / total_challenges as f64) * 100.0;
+    /// 向熔斷器報告一次 Aggregator 請求的結果（429 與 5xx 計為失敗）
+    fn breaker_record(&self, success: bool) {
+        if let Some(breaker) = &self.breaker {
+            breaker.record(&self.aggregator_url, success);
+        }
+    }
 
-        info!(
-            "Audit completed for blob {}: {} bytes, {} challenges, {}/{} passed ({:.1}%)",
-            blob_id,
-            content.len(),
-            total_challenges,
-            successful_verifications,
-            total_challenges,
-            success_rate
-        );
+    /// Aggregator 返回 404 時判斷 Blob 是否已被刪除或已過期
+    ///
+    /// 鏈上對象狀態優先：對象存活時 404 屬於真正的可用性問題，不採信響應體。
+    /// 未配置查詢、查詢失敗或無法判斷時按響應體中的原因分類（見 [`classify_not_found_body`]），
+    /// 仍無法判斷時保守地返回 `Unreachable`
+    async fn classify_missing_blob(
+        &self,
+        blob_id: &str,
+        sui_object_id: Option<&str>,
+        body: &[u8],
+    ) -> VerificationStatus {
+        if let Some(lookup) = &self.object_lookup {
+            match lookup.blob_object_state(blob_id, sui_object_id).await {
+                Ok(BlobObjectState::Deleted) => {
+                    info!(
+                        "Blob {} returned 404 and its object is deleted; marking as DELETED",
+                        blob_id
+                    );
+                    return VerificationStatus::Deleted;
+                }
+                Ok(BlobObjectState::StorageReclaimed) => {
+                    info!(
+                        "Blob {} returned 404 and its storage has ended; marking as EXPIRED",
+                        blob_id
+                    );
+                    return VerificationStatus::Expired;
+                }
+                Ok(BlobObjectState::Live) => {
+                    warn!("Blob {} returned 404 but its object is still live", blob_id);
+                    return VerificationStatus::Unreachable;
+                }
+                Ok(BlobObjectState::Unknown) => {}
+                Err(e) => {
+                    warn!("Failed to query object state for blob {}: {}", blob_id, e);
+                }
+            }
+        }
 
-        // 5. 生成審計數據
-        Ok(AuditData {
-            blob_id: blob_id.to_string(),
-            content_hash,
-            merkle_root,
-            total_challenges,
-            successful_verifications,
-            failed_verifications,
-            file_size: content.len() as u64,
-            timestamp: Utc::now().timestamp() as u64,
-            verification_status: VerificationStatus::Accessible,
-            sui_object_id: None,
-        })
+        match classify_not_found_body(body) {
+            Some(status) => {
+                info!("Blob {} returned 404 with reason; marking as {:?}", blob_id, status);
+                status
+            }
+            None => VerificationStatus::Unreachable,
+        }
     }
 
     /// 驗證 Blob 的完整性（與已知哈希比對）
@@ -381,24 +1933,32 @@ impl IntegrityVerifier {
     /// # 返回
     /// - `Ok(AuditData)`: 審計完成，`verification_status` 指示是否匹配
     ///
+    /// # 錯誤
+    /// - `expected_hash` 不是合法的十六進制: 返回 `InvalidDigest`（在下載之前）
+    ///
+    /// 哈希解碼為字節後以常量時間比較（見 [`crate::crypto::ct_eq`]）
+    ///
     /// # 示例
     /// ```no_run
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// # use auditor_node::integrity::IntegrityVerifier;
+    /// # use auditor_node::types::BlobId;
     /// let verifier = IntegrityVerifier::new_testnet();
     ///
+    /// let blob_id = BlobId::parse("eRrTusk8yshFQpkemgDnbg0f4-qDo623V2NpeVG1Zcg")?;
     /// let expected = "bd9e5380f78734bc182e4bb8c464101d3baeb23387d701608901e64cd879e1f5";
-    /// let result = verifier.verify_blob("eRrTusk8yshFQpkemgDnbg0f4-qDo623V2NpeVG1Zcg", expected).await?;
+    /// let result = verifier.verify_blob(&blob_id, expected).await?;
     ///
     /// assert_eq!(result.verification_status, auditor_node::integrity::VerificationStatus::Accessible);
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn verify_blob(&self, blob_id: &str, expected_hash: &str) -> Result<AuditData> {
+    pub async fn verify_blob(&self, blob_id: &BlobId, expected_hash: &str) -> Result<AuditData> {
+        let expected = decode_hex_digest(expected_hash)?;
         info!(
             "Verifying blob {} against expected hash: {}...",
             blob_id,
-            &expected_hash[..16]
+            expected_hash.get(..16).unwrap_or(expected_hash)
         );
 
         // 執行審計（下載並計算哈希）
@@ -406,7 +1966,7 @@ impl IntegrityVerifier {
 
         // 比對哈希
         if audit_data.verification_status == VerificationStatus::Accessible {
-            if audit_data.content_hash != expected_hash {
+            if !ct_eq(&decode_hex_digest(&audit_data.content_hash)?, &expected) {
                 warn!(
                     "INTEGRITY VIOLATION: Blob {} hash mismatch!\n  Expected: {}\n  Got:      {}",
                     blob_id, expected_hash, audit_data.content_hash
@@ -434,12 +1994,12 @@ impl IntegrityVerifier {
     /// ```no_run
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// # use auditor_node::integrity::IntegrityVerifier;
+    /// # use auditor_node::types::BlobId;
     /// let verifier = IntegrityVerifier::new_testnet();
     ///
     /// let blob_ids = vec![
-    ///     "blob_id_1".to_string(),
-    ///     "blob_id_2".to_string(),
-    ///     "blob_id_3".to_string(),
+    ///     BlobId::parse("eRrTusk8yshFQpkemgDnbg0f4-qDo623V2NpeVG1Zcg")?,
+    ///     BlobId::parse("M4hsZGQ1oCktdzegB6HnI6Mi28S2nqOPHxK-W7_4BUk")?,
     /// ];
     ///
     /// let results = verifier.audit_blobs_batch(&blob_ids).await?;
@@ -450,13 +2010,13 @@ impl IntegrityVerifier {
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn audit_blobs_batch(&self, blob_ids: &[String]) -> Result<Vec<AuditData>> {
+    pub async fn audit_blobs_batch(&self, blob_ids: &[BlobId]) -> Result<Vec<AuditData>> {
         info!("Starting batch audit for {} blobs", blob_ids.len());
 
         let mut tasks = Vec::new();
 
         for blob_id in blob_ids {
-            let blob_id_clone = blob_id.clone();
+            let blob_id_clone = *blob_id;
             let verifier_clone = self.clone();
 
             tasks.push(tokio::spawn(async move {
@@ -490,11 +2050,241 @@ impl Clone for IntegrityVerifier {
     fn clone(&self) -> Self {
         Self {
             http_client: self.http_client.clone(),
+            read_timeout: self.read_timeout,
             aggregator_url: self.aggregator_url.clone(),
+            resource_guard: self.resource_guard.clone(),
+            capture_dir: self.capture_dir.clone(),
+            object_lookup: self.object_lookup.clone(),
+            dedup: self.dedup.clone(),
+            chunk_filter: self.chunk_filter.clone(),
+            commitments: self.commitments.clone(),
+            breaker: self.breaker.clone(),
+            rate_limiter: self.rate_limiter.clone(),
+            baseline: self.baseline.clone(),
+            metrics: self.metrics.clone(),
+            blob_id_check: self.blob_id_check.clone(),
+            checkpoints: self.checkpoints.clone(),
+            content_cache: self.content_cache.clone(),
+            config: self.config,
+        }
+    }
+}
+
+/// 離線審計本地文件
+///
+/// 以與 [`IntegrityVerifier::audit_blob`] 相同的切片流式計算 SHA-256 與 Merkle 根，
+/// 按默認挑戰參數在本地抽樣挑戰；不訪問網絡，也不寫入承諾日誌或內容基準。
+/// 返回的 `blob_id` 為文件路徑，`audit_method` 為 [`AuditMethod::LocalFile`]
+///
+/// # 錯誤
+/// 文件無法讀取時返回 [`AuditorError::Io`]，`chunk_size` 為 0 時返回 [`AuditorError::Config`]
+pub fn audit_local(path: &Path, chunk_size: usize) -> Result<AuditData> {
+    if chunk_size == 0 {
+        return Err(AuditorError::Config(
+            "chunk_size must be non-zero".to_string(),
+        ));
+    }
+    let config = IntegrityVerifierConfig {
+        chunk_size,
+        ..Default::default()
+    };
+    let blob_id = path.display().to_string();
+    info!("Starting local audit for file: {}", blob_id);
+
+    // 1. 流式讀取文件：同時計算 SHA-256 與 Merkle 葉子哈希
+    let mut file = File::open(path)?;
+    let mut hasher = Sha256::new();
+    let mut builder = MerkleTreeBuilder::new(chunk_size);
+    let mut buffer = vec![0u8; LOCAL_READ_BUFFER_SIZE];
+    loop {
+        let read = file.read(&mut buffer)?;
+        if read == 0 {
+            break;
         }
+        hasher.update(&buffer[..read]);
+        builder.update(&buffer[..read]);
+    }
+    let file_size = builder.bytes_written();
+    let content_hash = hex::encode(hasher.finalize());
+
+    let mut audit = AuditData {
+        blob_id,
+        content_hash,
+        merkle_root: String::new(),
+        total_challenges: 0,
+        successful_verifications: 0,
+        failed_verifications: 0,
+        file_size,
+        timestamp: Utc::now().timestamp() as u64,
+        verification_status: VerificationStatus::Accessible,
+        sui_object_id: None,
+        resource_decision: None,
+        capture_digest: None,
+        deduplicated_from: None,
+        producer: None,
+        chunk_filter: None,
+        challenge_reveal: None,
+        challenge_seed_source: None,
+        challenge_seed: None,
+        hash_drift: None,
+        blob_id_verified: None,
+        chunk_size: None,
+        blob_object_id: None,
+        encoding_k: None,
+        encoding_n: None,
+        start_epoch: None,
+        end_epoch: None,
+        onchain_merkle_root: None,
+        size_matches_chain: None,
+        audit_method: AuditMethod::LocalFile,
+    };
+
+    // 2. 構建 Merkle Tree（空文件沒有葉子，與空 Blob 一樣不挑戰）
+    let tree = match builder.finish() {
+        Ok(tree) => tree,
+        Err(e) => {
+            warn!("Failed to build Merkle tree for {}: {}", audit.blob_id, e);
+            return Ok(audit);
+        }
+    };
+
+    // 3. 本地抽樣挑戰
+    let leaf_count = tree.leaf_count();
+    let indices =
+        ChallengeReveal::generate(config.challenge_count(leaf_count), leaf_count as u64).indices;
+    let results = verify_challenges(&audit.blob_id, &tree, &indices);
+    let successful_verifications = checked_u16(
+        "successful_verifications",
+        results.iter().filter(|&&verified| verified).count(),
+    )?;
+
+    audit.merkle_root = hex::encode(tree.root());
+    audit.chunk_size = Some(chunk_size as u32);
+    audit.total_challenges = checked_u16("total_challenges", indices.len())?;
+    audit.successful_verifications = successful_verifications;
+    audit.failed_verifications = audit.total_challenges - successful_verifications;
+    if audit.failed_verifications > 0 {
+        audit.verification_status = VerificationStatus::Corrupted;
+    }
+
+    info!(
+        "Local audit completed for {}: {} bytes, {} leaves, {}/{} challenges passed",
+        audit.blob_id, file_size, leaf_count, successful_verifications, audit.total_challenges
+    );
+    Ok(audit)
+}
+
+/// 本地文件與已發布審計不符的字段
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LocalMismatch {
+    /// 字段名（`file_size`、`content_hash` 或 `merkle_root`）
+    pub field: &'static str,
+
+    /// 已發布審計記錄的值
+    pub expected: String,
+
+    /// 本地文件的值
+    pub actual: String,
+}
+
+impl fmt::Display for LocalMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: expected {}, got {}",
+            self.field, self.expected, self.actual
+        )
+    }
+}
+
+/// 本地文件與已發布審計的比對結果
+#[derive(Debug, Clone, Serialize)]
+pub struct LocalVerification {
+    /// 本地文件的審計數據（不符時狀態為 `CORRUPTED`，並在 `hash_drift` 記錄預期值與觀測值）
+    pub audit: AuditData,
+
+    /// 不符的字段（為空表示文件與審計一致）
+    pub mismatches: Vec<LocalMismatch>,
+}
+
+impl LocalVerification {
+    /// 文件是否與已發布的審計一致
+    pub fn is_match(&self) -> bool {
+        self.mismatches.is_empty()
     }
 }
 
+/// 離線驗證本地文件是否與已發布的審計一致
+///
+/// 按審計記錄的切片大小（未記錄時為 [`DEFAULT_CHUNK_SIZE`]）審計文件，再比對大小、
+/// 內容哈希與 Merkle 根；審計沒有 Merkle 根（未構建 Merkle 樹）時只比對前兩者
+///
+/// # 錯誤
+/// 文件無法讀取、審計沒有內容哈希或摘要不是合法的十六進制時返回錯誤
+pub fn verify_local(path: &Path, expected_report: &AuditData) -> Result<LocalVerification> {
+    if expected_report.content_hash.is_empty() {
+        return Err(AuditorError::InvalidDigest(format!(
+            "audit of blob {} has no content hash",
+            expected_report.blob_id
+        )));
+    }
+    let chunk_size = expected_report
+        .chunk_size
+        .map_or(DEFAULT_CHUNK_SIZE, |size| size as usize);
+    let mut audit = audit_local(path, chunk_size)?;
+
+    let mut mismatches = Vec::new();
+    if audit.file_size != expected_report.file_size {
+        mismatches.push(LocalMismatch {
+            field: "file_size",
+            expected: expected_report.file_size.to_string(),
+            actual: audit.file_size.to_string(),
+        });
+    }
+    if !ct_eq_hex(&audit.content_hash, &expected_report.content_hash)? {
+        mismatches.push(LocalMismatch {
+            field: "content_hash",
+            expected: expected_report.content_hash.clone(),
+            actual: audit.content_hash.clone(),
+        });
+    }
+    if !expected_report.merkle_root.is_empty()
+        && !ct_eq_hex(&audit.merkle_root, &expected_report.merkle_root)?
+    {
+        mismatches.push(LocalMismatch {
+            field: "merkle_root",
+            expected: expected_report.merkle_root.clone(),
+            actual: audit.merkle_root.clone(),
+        });
+    }
+
+    if mismatches.is_empty() {
+        info!(
+            "File {} matches the audit of blob {}",
+            audit.blob_id, expected_report.blob_id
+        );
+    } else {
+        warn!(
+            "File {} does not match the audit of blob {}: {} field(s) differ",
+            audit.blob_id,
+            expected_report.blob_id,
+            mismatches.len()
+        );
+        audit.verification_status = VerificationStatus::Corrupted;
+        audit.hash_drift = Some(HashDrift {
+            expected_content_hash: expected_report.content_hash.clone(),
+            observed_content_hash: audit.content_hash.clone(),
+            expected_merkle_root: expected_report.merkle_root.clone(),
+            observed_merkle_root: audit.merkle_root.clone(),
+            expected_file_size: expected_report.file_size,
+            observed_file_size: audit.file_size,
+            baseline_at: expected_report.timestamp,
+        });
+    }
+
+    Ok(LocalVerification { audit, mismatches })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -505,11 +2295,752 @@ mod tests {
         assert_eq!(verifier.aggregator_url, WALRUS_AGGREGATOR_TESTNET);
     }
 
+    #[test]
+    fn test_verify_multi_proof_for_challenge_set() {
+        let blob = crate::test_support::deterministic_blob(DEFAULT_CHUNK_SIZE * 20 + 100);
+        let tree = MerkleTree::from_blob(&blob, DEFAULT_CHUNK_SIZE).unwrap();
+        let root = tree.root();
+
+        assert!(verify_multi_proof(&tree, &[3, 0, 20, 7, 3], &root));
+        assert!(!verify_multi_proof(&tree, &[3, 0, 20], &[0u8; 32]));
+        assert!(!verify_multi_proof(&tree, &[21], &root));
+    }
+
     #[test]
     fn test_verification_status_serialization() {
         let status = VerificationStatus::Accessible;
         let json = serde_json::to_string(&status).unwrap();
         assert_eq!(json, "\"ACCESSIBLE\"");
+
+        let json = serde_json::to_string(&VerificationStatus::Deleted).unwrap();
+        assert_eq!(json, "\"DELETED\"");
+
+        let json = serde_json::to_string(&VerificationStatus::Expired).unwrap();
+        assert_eq!(json, "\"EXPIRED\"");
+    }
+
+    /// 固定返回指定狀態的對象查詢
+    struct FixedLookup(BlobObjectState);
+
+    #[async_trait::async_trait]
+    impl BlobObjectLookup for FixedLookup {
+        async fn blob_object_state(
+            &self,
+            _blob_id: &str,
+            _sui_object_id: Option<&str>,
+        ) -> Result<BlobObjectState> {
+            Ok(self.0)
+        }
+    }
+
+    /// 啟動對所有 Blob 返回 404 的本地 Aggregator
+    async fn spawn_not_found_aggregator() -> String {
+        spawn_not_found_aggregator_with_reason("").await
+    }
+
+    /// 啟動對所有 Blob 返回 404 且響應體為 `reason` 的本地 Aggregator
+    async fn spawn_not_found_aggregator_with_reason(reason: &'static str) -> String {
+        use axum::{http::StatusCode, routing::get, Router};
+
+        let app = Router::new().route(
+            "/v1/blobs/:id",
+            get(move || async move { (StatusCode::NOT_FOUND, reason) }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+
+        format!("http://{}", addr)
+    }
+
+    #[tokio::test]
+    async fn test_not_found_with_deleted_object_is_deleted() {
+        let url = spawn_not_found_aggregator().await;
+        let verifier = IntegrityVerifier::new(url)
+            .with_object_lookup(Arc::new(FixedLookup(BlobObjectState::Deleted)));
+
+        let audit_data = verifier
+            .audit_blob_with_object(&BlobId::from_bytes([1; 32]), Some("0xb10b"))
+            .await
+            .unwrap();
+
+        assert_eq!(audit_data.verification_status, VerificationStatus::Deleted);
+        assert_eq!(audit_data.sui_object_id.as_deref(), Some("0xb10b"));
+    }
+
+    #[tokio::test]
+    async fn test_not_found_with_live_object_is_unreachable() {
+        let url = spawn_not_found_aggregator().await;
+
+        let verifier = IntegrityVerifier::new(url.clone())
+            .with_object_lookup(Arc::new(FixedLookup(BlobObjectState::Live)));
+        let audit_data = verifier
+            .audit_blob_with_object(&BlobId::from_bytes([2; 32]), Some("0xb10b"))
+            .await
+            .unwrap();
+        assert_eq!(audit_data.verification_status, VerificationStatus::Unreachable);
+
+        // 未配置查詢時保持原有行為
+        let audit_data = IntegrityVerifier::new(url)
+            .audit_blob(&BlobId::from_bytes([2; 32]))
+            .await
+            .unwrap();
+        assert_eq!(audit_data.verification_status, VerificationStatus::Unreachable);
+    }
+
+    #[test]
+    fn test_classify_not_found_body() {
+        assert_eq!(
+            classify_not_found_body(b"blob has been deleted by its owner"),
+            Some(VerificationStatus::Deleted)
+        );
+        assert_eq!(
+            classify_not_found_body(br#"{"error":"Blob Not Certified"}"#),
+            Some(VerificationStatus::Expired)
+        );
+        assert_eq!(
+            classify_not_found_body(b"the blob has expired"),
+            Some(VerificationStatus::Expired)
+        );
+        assert_eq!(classify_not_found_body(b"not found"), None);
+        assert_eq!(classify_not_found_body(b""), None);
+    }
+
+    #[tokio::test]
+    async fn test_not_found_with_reclaimed_storage_is_expired() {
+        let url = spawn_not_found_aggregator().await;
+        let verifier = IntegrityVerifier::new(url)
+            .with_object_lookup(Arc::new(FixedLookup(BlobObjectState::StorageReclaimed)));
+
+        let audit_data = verifier
+            .audit_blob_with_object(&BlobId::from_bytes([3; 32]), Some("0xb10b"))
+            .await
+            .unwrap();
+        assert_eq!(audit_data.verification_status, VerificationStatus::Expired);
+    }
+
+    #[tokio::test]
+    async fn test_not_found_reason_is_used_when_chain_is_inconclusive() {
+        let url = spawn_not_found_aggregator_with_reason("blob not certified").await;
+        let blob_id = BlobId::from_bytes([4; 32]);
+
+        // 未配置查詢或查詢無法判斷時按響應體分類
+        let audit_data = IntegrityVerifier::new(url.clone()).audit_blob(&blob_id).await.unwrap();
+        assert_eq!(audit_data.verification_status, VerificationStatus::Expired);
+        let audit_data = IntegrityVerifier::new(url.clone())
+            .with_object_lookup(Arc::new(FixedLookup(BlobObjectState::Unknown)))
+            .audit_blob(&blob_id)
+            .await
+            .unwrap();
+        assert_eq!(audit_data.verification_status, VerificationStatus::Expired);
+
+        // 鏈上對象存活時不採信響應體
+        let audit_data = IntegrityVerifier::new(url)
+            .with_object_lookup(Arc::new(FixedLookup(BlobObjectState::Live)))
+            .audit_blob(&blob_id)
+            .await
+            .unwrap();
+        assert_eq!(audit_data.verification_status, VerificationStatus::Unreachable);
+    }
+
+    /// 返回固定元數據的鏈上查詢
+    struct FixedMetadata(BlobMetadata);
+
+    #[async_trait::async_trait]
+    impl BlobMetadataSource for FixedMetadata {
+        async fn blob_metadata(&self, _blob_object_id: &MoveId) -> Result<BlobMetadata> {
+            Ok(self.0.clone())
+        }
+    }
+
+    fn chain_metadata(blob_id: &BlobId, object_id: &MoveId, blob_size: u64) -> BlobMetadata {
+        BlobMetadata {
+            blob_object_id: object_id.to_string(),
+            blob_id: blob_id.to_base64_url(),
+            merkle_root: vec![],
+            blob_size,
+            encoding_type: 1,
+            encoding_k: 334,
+            encoding_n: 1000,
+            registered_epoch: 10,
+            certified_epoch: Some(10),
+            start_epoch: 10,
+            end_epoch: 60,
+            owner: "0x5678".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_audit_with_chain_metadata() {
+        use crate::test_support::{deterministic_blob, AggregatorMode, FakeAggregator};
+        use crate::types::AuditReport;
+
+        let blob = deterministic_blob(DEFAULT_CHUNK_SIZE * 3);
+        let blob_id = BlobId::from_bytes([5; 32]);
+        let object_id = MoveId::from_hex(&format!("0x{}", "b1".repeat(32))).unwrap();
+        let aggregator = FakeAggregator::start(blob.clone(), AggregatorMode::Healthy).await;
+        let verifier = IntegrityVerifier::new(aggregator.url().to_string());
+
+        let source = FixedMetadata(chain_metadata(&blob_id, &object_id, blob.len() as u64));
+        let audit_data = verifier
+            .audit_blob_with_metadata(&blob_id, &object_id, &source)
+            .await
+            .unwrap();
+        assert_eq!(audit_data.blob_object_id, Some(object_id.to_string()));
+        assert_eq!(audit_data.sui_object_id, Some(object_id.to_string()));
+        assert_eq!(audit_data.encoding_k, Some(334));
+        assert_eq!(audit_data.encoding_n, Some(1000));
+        assert_eq!(audit_data.start_epoch, Some(10));
+        assert_eq!(audit_data.end_epoch, Some(60));
+        // 鏈上對象不記錄默克爾根
+        assert_eq!(audit_data.onchain_merkle_root, None);
+        assert_eq!(audit_data.size_matches_chain, Some(true));
+
+        // 鏈上字段隨報告往返並在簽名範圍內
+        let report = AuditReport::from(audit_data.clone());
+        let back = AuditData::try_from(&report).unwrap();
+        assert_eq!(
+            serde_json::to_value(&back).unwrap(),
+            serde_json::to_value(&audit_data).unwrap()
+        );
+        let mut tampered = report.clone();
+        tampered.integrity.as_mut().unwrap().end_epoch = Some(61);
+        assert_ne!(tampered.signing_bytes(), report.signing_bytes());
+
+        // 未按鏈上元數據審計時不序列化這些字段，舊數據仍可解析
+        let plain = verifier.audit_blob(&blob_id).await.unwrap();
+        let json = serde_json::to_value(&plain).unwrap();
+        assert!(json.get("encoding_k").is_none());
+        assert!(json.get("size_matches_chain").is_none());
+        let parsed: AuditData = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed.blob_object_id, None);
+        assert_eq!(parsed.size_matches_chain, None);
+    }
+
+    #[tokio::test]
+    async fn test_audit_with_chain_metadata_size_mismatch() {
+        use crate::test_support::{deterministic_blob, AggregatorMode, FakeAggregator};
+
+        let blob = deterministic_blob(DEFAULT_CHUNK_SIZE * 3);
+        let blob_id = BlobId::from_bytes([6; 32]);
+        let object_id = MoveId::from_hex(&format!("0x{}", "b2".repeat(32))).unwrap();
+        let aggregator = FakeAggregator::start(blob.clone(), AggregatorMode::Healthy).await;
+        let verifier = IntegrityVerifier::new(aggregator.url().to_string());
+
+        let mut metadata = chain_metadata(&blob_id, &object_id, blob.len() as u64 + 1);
+        metadata.merkle_root = vec![0xab; 32];
+        let audit_data = verifier
+            .audit_blob_with_metadata(&blob_id, &object_id, &FixedMetadata(metadata))
+            .await
+            .unwrap();
+        assert_eq!(audit_data.size_matches_chain, Some(false));
+        assert_eq!(audit_data.onchain_merkle_root, Some("ab".repeat(32)));
+        // 只記錄不一致，不改變驗證結論
+        assert_eq!(
+            audit_data.verification_status,
+            VerificationStatus::Accessible
+        );
+
+        // 對象記錄的是另一個 Blob
+        let other = chain_metadata(&BlobId::from_bytes([7; 32]), &object_id, blob.len() as u64);
+        let err = verifier
+            .audit_blob_with_metadata(&blob_id, &object_id, &FixedMetadata(other))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, AuditorError::InvalidBlobId(_)));
+    }
+
+    /// 以內容 SHA-256 代替 RS2 推導 blob_id 的編碼器
+    struct Sha256Encoder;
+
+    impl BlobIdEncoder for Sha256Encoder {
+        fn blob_id(&self, data: &[u8]) -> Result<String> {
+            use base64::{engine::general_purpose, Engine as _};
+            Ok(general_purpose::URL_SAFE_NO_PAD.encode(Sha256::digest(data)))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_blob_id_verification() {
+        use crate::test_support::{deterministic_blob, AggregatorMode, FakeAggregator};
+
+        let blob = deterministic_blob(DEFAULT_CHUNK_SIZE * 4);
+        let blob_id = BlobId::parse(&Sha256Encoder.blob_id(&blob).unwrap()).unwrap();
+
+        let aggregator = FakeAggregator::start(blob.clone(), AggregatorMode::Healthy).await;
+        let verifier = IntegrityVerifier::new(aggregator.url().to_string())
+            .with_blob_id_verification(Arc::new(Sha256Encoder), blob.len() as u64);
+        let audit_data = verifier.audit_blob(&blob_id).await.unwrap();
+        assert_eq!(audit_data.blob_id_verified, Some(true));
+        assert_eq!(audit_data.verification_status, VerificationStatus::Accessible);
+
+        // Aggregator 返回了與 blob_id 不符的內容
+        let aggregator = FakeAggregator::start(blob.clone(), AggregatorMode::CorruptChunk(2)).await;
+        let verifier = IntegrityVerifier::new(aggregator.url().to_string())
+            .with_blob_id_verification(Arc::new(Sha256Encoder), blob.len() as u64);
+        let audit_data = verifier.audit_blob(&blob_id).await.unwrap();
+        assert_eq!(audit_data.blob_id_verified, Some(false));
+        assert_eq!(audit_data.verification_status, VerificationStatus::Corrupted);
+        let report = crate::types::AuditReport::from(audit_data);
+        assert!(report.failure_reason.unwrap().contains("Blob ID mismatch"));
+
+        // 超過大小上限：跳過
+        let verifier = IntegrityVerifier::new(aggregator.url().to_string())
+            .with_blob_id_verification(Arc::new(Sha256Encoder), blob.len() as u64 - 1);
+        let audit_data = verifier.audit_blob(&blob_id).await.unwrap();
+        assert_eq!(audit_data.blob_id_verified, None);
+        assert_eq!(audit_data.verification_status, VerificationStatus::Accessible);
+    }
+
+    #[tokio::test]
+    async fn test_slow_body_needs_raised_read_timeout() {
+        use crate::test_support::{deterministic_blob, AggregatorMode, FakeAggregator};
+
+        let blob = deterministic_blob(DEFAULT_CHUNK_SIZE * 4);
+        let aggregator = FakeAggregator::start(
+            blob.clone(),
+            AggregatorMode::SlowBody(Duration::from_millis(1500)),
+        )
+        .await;
+        let blob_id = BlobId::from_bytes([5; 32]);
+
+        // 響應體中途停頓超過讀取超時：下載失敗
+        let http = HttpClientConfig {
+            read_timeout_secs: 1,
+            ..Default::default()
+        };
+        let err = IntegrityVerifier::from_http_config(aggregator.url().to_string(), &http)
+            .unwrap()
+            .audit_blob(&blob_id)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("Failed to read response body"));
+
+        // 提高讀取超時後完成下載
+        let http = HttpClientConfig {
+            read_timeout_secs: 5,
+            ..Default::default()
+        };
+        let audit_data = IntegrityVerifier::from_http_config(aggregator.url().to_string(), &http)
+            .unwrap()
+            .audit_blob(&blob_id)
+            .await
+            .unwrap();
+        assert_eq!(audit_data.verification_status, VerificationStatus::Accessible);
+        assert_eq!(audit_data.content_hash, crate::test_support::content_hash(&blob));
+    }
+
+    #[test]
+    fn test_challenge_count_from_config() {
+        // 默認值與 min(10, 葉子數) 一致
+        let config = IntegrityVerifierConfig::default();
+        for leaf_count in [1, 2, 9, 10, 11, 1000] {
+            assert_eq!(config.challenge_count(leaf_count), leaf_count.min(10));
+        }
+
+        // 放寬上限後按置信度計算：95% 置信度、10% 損壞率需要 29 次
+        let thorough = IntegrityVerifierConfig {
+            max_challenges: 100,
+            ..config
+        };
+        assert_eq!(thorough.challenge_count(1000), 29);
+        assert_eq!(thorough.challenge_count(12), 12);
+
+        let strict = IntegrityVerifierConfig {
+            max_challenges: 1000,
+            confidence_level: 0.99,
+            assumed_corruption_rate: 0.01,
+            ..config
+        };
+        assert_eq!(strict.challenge_count(100_000), 459);
+    }
+
+    #[test]
+    fn test_config_rejects_unrepresentable_challenge_counts() {
+        let config = IntegrityVerifierConfig {
+            max_challenges: usize::from(u16::MAX),
+            ..IntegrityVerifierConfig::default()
+        };
+        assert!(config.validate().is_ok());
+
+        for invalid in [
+            IntegrityVerifierConfig {
+                max_challenges: usize::from(u16::MAX) + 1,
+                ..config
+            },
+            IntegrityVerifierConfig {
+                max_challenges: 0,
+                ..config
+            },
+            IntegrityVerifierConfig {
+                chunk_size: 0,
+                ..config
+            },
+        ] {
+            assert!(matches!(invalid.validate(), Err(AuditorError::Config(_))));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "exceeds u16::MAX")]
+    fn test_with_config_rejects_too_many_challenges() {
+        let _ = IntegrityVerifier::new("http://localhost:9000".to_string()).with_config(
+            IntegrityVerifierConfig {
+                max_challenges: usize::from(u16::MAX) + 1,
+                ..IntegrityVerifierConfig::default()
+            },
+        );
+    }
+
+    #[tokio::test]
+    async fn test_small_blob_challenges_every_leaf() {
+        use crate::test_support::{deterministic_blob, AggregatorMode, FakeAggregator};
+
+        let aggregator = FakeAggregator::start(
+            deterministic_blob(DEFAULT_CHUNK_SIZE * 3 - 1),
+            AggregatorMode::Healthy,
+        )
+        .await;
+        let config = IntegrityVerifierConfig {
+            max_challenges: 50,
+            ..IntegrityVerifierConfig::default()
+        };
+        let verifier = IntegrityVerifier::new(aggregator.url().to_string()).with_config(config);
+
+        let audit_data = verifier.audit_blob(&BlobId::from_bytes([7; 32])).await.unwrap();
+        assert_eq!(audit_data.total_challenges, 3);
+        assert_eq!(audit_data.successful_verifications, 3);
+        assert_eq!(audit_data.chunk_size, Some(DEFAULT_CHUNK_SIZE as u32));
+    }
+
+    #[tokio::test]
+    async fn test_custom_chunk_size() {
+        use crate::test_support::{deterministic_blob, AggregatorMode, FakeAggregator};
+
+        let blob = deterministic_blob(1024 * 20 + 5);
+        let aggregator = FakeAggregator::start(blob.clone(), AggregatorMode::Healthy).await;
+        let config = IntegrityVerifierConfig {
+            chunk_size: 1024,
+            ..IntegrityVerifierConfig::default()
+        };
+        let verifier = IntegrityVerifier::new(aggregator.url().to_string()).with_config(config);
+
+        let audit_data = verifier.audit_blob(&BlobId::from_bytes([8; 32])).await.unwrap();
+        let tree = MerkleTree::from_blob(&blob, 1024).unwrap();
+        assert_eq!(tree.leaf_count(), 21);
+        assert_eq!(audit_data.merkle_root, hex::encode(tree.root()));
+        assert_ne!(
+            audit_data.merkle_root,
+            hex::encode(MerkleTree::from_blob(&blob, DEFAULT_CHUNK_SIZE).unwrap().root())
+        );
+        assert_eq!(audit_data.chunk_size, Some(1024));
+        assert_eq!(audit_data.total_challenges, 10);
+        assert_eq!(audit_data.successful_verifications, 10);
+
+        // 切片大小隨報告簽名
+        let report = crate::types::AuditReport::from(audit_data);
+        assert_eq!(report.integrity.as_ref().unwrap().chunk_size, Some(1024));
+        let mut other = report.clone();
+        other.integrity.as_mut().unwrap().chunk_size = Some(4096);
+        assert_ne!(report.signing_bytes(), other.signing_bytes());
+    }
+
+    /// 臨時檢查點目錄
+    fn checkpoint_store() -> Arc<CheckpointStore> {
+        let dir = std::env::temp_dir().join(format!("checkpoints_{}", rand::random::<u32>()));
+        Arc::new(CheckpointStore::open(dir).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_resumable_audit_skips_hashing_after_interruption() {
+        use crate::test_support::{deterministic_blob, AggregatorMode, FakeAggregator};
+
+        let blob = deterministic_blob(DEFAULT_CHUNK_SIZE * 30 + 7);
+        let aggregator = FakeAggregator::start(blob, AggregatorMode::Healthy).await;
+        let store = checkpoint_store();
+        let verifier = IntegrityVerifier::new(aggregator.url().to_string())
+            .with_checkpoints(Arc::clone(&store));
+        let blob_id = BlobId::from_bytes([5; 32]);
+        let key = blob_id.to_string();
+
+        // 第一次嘗試：下載哈希完成、寫入檢查點後中斷（未執行挑戰）
+        let Download::Hashed(hashed) = verifier
+            .download_blob(&key, None, None, &ProgressSink::default())
+            .await
+            .unwrap()
+        else {
+            panic!("expected a hashed blob");
+        };
+        store.save(&hashed.checkpoint(&key, DEFAULT_CHUNK_SIZE)).unwrap();
+        assert_eq!(aggregator.downloads(), 1);
+
+        // 第二次嘗試：ETag 與長度一致，從葉子哈希恢復，不再下載
+        let audit_data = verifier.audit_blob_resumable(&blob_id).await.unwrap();
+        assert_eq!(aggregator.downloads(), 1);
+        assert_eq!(audit_data.verification_status, VerificationStatus::Accessible);
+        assert_eq!(audit_data.content_hash, hashed.content_hash);
+        assert_eq!(audit_data.merkle_root, hex::encode(hashed.tree.root()));
+        assert_eq!(audit_data.file_size, hashed.file_size);
+        assert_eq!(audit_data.total_challenges, 10);
+        assert_eq!(audit_data.successful_verifications, 10);
+
+        // 成功後刪除檢查點
+        assert!(store.load(&key).is_none());
+
+        // 挑戰集已確定且部分完成：沿用索引，只驗證剩餘的挑戰
+        let mut checkpoint = hashed.checkpoint(&key, DEFAULT_CHUNK_SIZE);
+        checkpoint.challenges = Some(ChallengeProgress {
+            indices: vec![4, 29, 30],
+            reveal: None,
+            seed: None,
+            results: vec![true],
+        });
+        store.save(&checkpoint).unwrap();
+        let audit_data = verifier.audit_blob_resumable(&blob_id).await.unwrap();
+        assert_eq!(aggregator.downloads(), 1);
+        assert_eq!(audit_data.total_challenges, 3);
+        assert_eq!(audit_data.successful_verifications, 3);
+        assert!(store.load(&key).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_resumable_audit_redownloads_stale_checkpoint() {
+        use crate::test_support::{deterministic_blob, AggregatorMode, FakeAggregator};
+
+        let blob = deterministic_blob(DEFAULT_CHUNK_SIZE * 8);
+        let aggregator = FakeAggregator::start(blob.clone(), AggregatorMode::Healthy).await;
+        let store = checkpoint_store();
+        let verifier = IntegrityVerifier::new(aggregator.url().to_string())
+            .with_checkpoints(Arc::clone(&store));
+        let blob_id = BlobId::from_bytes([6; 32]);
+        let key = blob_id.to_string();
+
+        // 檢查點記錄的是另一個版本的內容
+        let Download::Hashed(hashed) = verifier
+            .download_blob(&key, None, None, &ProgressSink::default())
+            .await
+            .unwrap()
+        else {
+            panic!("expected a hashed blob");
+        };
+        let mut checkpoint = hashed.checkpoint(&key, DEFAULT_CHUNK_SIZE);
+        checkpoint.etag = Some("\"previous\"".to_string());
+        checkpoint.leaf_hashes = vec![[0; 32]; 8];
+        store.save(&checkpoint).unwrap();
+
+        let audit_data = verifier.audit_blob_resumable(&blob_id).await.unwrap();
+        assert_eq!(aggregator.downloads(), 2);
+        assert_eq!(audit_data.merkle_root, hex::encode(hashed.tree.root()));
+        assert_eq!(audit_data.successful_verifications, audit_data.total_challenges);
+        assert!(store.load(&key).is_none());
+
+        // 內容未變，但檢查點的葉子按另一個切片大小計算
+        store.save(&hashed.checkpoint(&key, 1024)).unwrap();
+        let audit_data = verifier.audit_blob_resumable(&blob_id).await.unwrap();
+        assert_eq!(aggregator.downloads(), 3);
+        assert_eq!(audit_data.merkle_root, hex::encode(hashed.tree.root()));
+    }
+
+    #[tokio::test]
+    async fn test_content_cache_revalidates_with_etag() {
+        use crate::content_cache::MemoryContentCache;
+        use crate::test_support::{deterministic_blob, AggregatorMode, FakeAggregator};
+
+        let blob = deterministic_blob(DEFAULT_CHUNK_SIZE * 12 + 3);
+        let aggregator = FakeAggregator::start(blob.clone(), AggregatorMode::Healthy).await;
+        let cache = Arc::new(MemoryContentCache::new(8));
+        let verifier = IntegrityVerifier::new(aggregator.url().to_string())
+            .with_content_cache(Arc::clone(&cache) as Arc<dyn ContentCache>);
+        let blob_id = BlobId::from_bytes([9; 32]);
+
+        let first = verifier.audit_blob(&blob_id).await.unwrap();
+        assert_eq!(aggregator.not_modified(), 0);
+        let entry = cache.get(&blob_id.to_string()).unwrap();
+        assert_eq!(entry.content_hash, first.content_hash);
+        assert_eq!(entry.merkle_root, first.merkle_root);
+        assert_eq!(entry.file_size, blob.len() as u64);
+
+        // 304：沿用緩存的哈希，只重新執行挑戰抽樣
+        let second = verifier.audit_blob(&blob_id).await.unwrap();
+        assert_eq!(aggregator.downloads(), 2);
+        assert_eq!(aggregator.not_modified(), 1);
+        assert_eq!(second.verification_status, VerificationStatus::Accessible);
+        assert_eq!(second.content_hash, first.content_hash);
+        assert_eq!(second.merkle_root, first.merkle_root);
+        assert_eq!(second.file_size, first.file_size);
+        assert_eq!(second.total_challenges, 10);
+        assert_eq!(second.successful_verifications, 10);
+
+        // 內容改變：ETag 不再匹配，200 使條目失效並以新內容重新寫入
+        let changed = deterministic_blob(DEFAULT_CHUNK_SIZE * 5);
+        aggregator.set_served(changed.clone());
+        let third = verifier.audit_blob(&blob_id).await.unwrap();
+        assert_eq!(aggregator.not_modified(), 1);
+        assert_eq!(third.content_hash, crate::test_support::content_hash(&changed));
+        assert_eq!(third.file_size, changed.len() as u64);
+        let entry = cache.get(&blob_id.to_string()).unwrap();
+        assert_eq!(entry.content_hash, third.content_hash);
+        assert_eq!(entry.merkle_root, third.merkle_root);
+
+        // 抓包模式需要完整響應體，不發出條件請求
+        let capture_dir = tempfile::tempdir().unwrap();
+        let capturing = verifier.clone().with_capture_dir(capture_dir.path());
+        capturing.audit_blob(&blob_id).await.unwrap();
+        assert_eq!(aggregator.downloads(), 4);
+        assert_eq!(aggregator.not_modified(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_degraded_decision_skips_cache_and_checkpoints() {
+        use crate::content_cache::MemoryContentCache;
+        use crate::resources::{ResourceGuardConfig, ResourcePolicy, ResourceProbe};
+        use crate::test_support::{deterministic_blob, AggregatorMode, FakeAggregator};
+
+        /// 受監控目錄只剩 1 MiB 的探針（內存充足）
+        struct ShortDisk;
+
+        impl ResourceProbe for ShortDisk {
+            fn available_disk_bytes(&self, _path: &std::path::Path) -> Option<u64> {
+                Some(1024 * 1024)
+            }
+
+            fn available_memory_bytes(&self) -> Option<u64> {
+                Some(u64::MAX)
+            }
+        }
+
+        let aggregator = FakeAggregator::start(
+            deterministic_blob(DEFAULT_CHUNK_SIZE * 4),
+            AggregatorMode::Healthy,
+        )
+        .await;
+        let guard = ResourceGuard::with_probe(
+            ResourceGuardConfig {
+                policy: ResourcePolicy::Degrade,
+                ..ResourceGuardConfig::default()
+            },
+            Arc::new(ShortDisk),
+        )
+        .watch_dir("/cache");
+        let cache = Arc::new(MemoryContentCache::new(8));
+        let store = checkpoint_store();
+        let verifier = IntegrityVerifier::new(aggregator.url().to_string())
+            .with_resource_guard(guard)
+            .with_content_cache(Arc::clone(&cache) as Arc<dyn ContentCache>)
+            .with_checkpoints(Arc::clone(&store));
+        let blob_id = BlobId::from_bytes([6; 32]);
+        let key = blob_id.to_string();
+
+        let audit_data = verifier.audit_blob_resumable(&blob_id).await.unwrap();
+        let decision = audit_data.resource_decision.unwrap();
+        assert_eq!(decision.action, crate::resources::ResourceAction::Degraded);
+        assert!(decision.disable_caching);
+        assert!(cache.get(&key).is_none());
+        assert!(store.load(&key).is_none());
+
+        // 下載結果帶有停用寫盤的決策，可恢復的審計因此不寫檢查點
+        let Download::Hashed(hashed) = verifier
+            .download_blob(&key, None, None, &ProgressSink::default())
+            .await
+            .unwrap()
+        else {
+            panic!("expected a hashed blob");
+        };
+        assert!(hashed.caching_disabled());
+        assert!(cache.get(&key).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_content_cache_eviction_and_unusable_entries() {
+        use crate::content_cache::MemoryContentCache;
+        use crate::test_support::{deterministic_blob, AggregatorMode, FakeAggregator};
+
+        let aggregator = FakeAggregator::start(
+            deterministic_blob(DEFAULT_CHUNK_SIZE * 3),
+            AggregatorMode::Healthy,
+        )
+        .await;
+        let cache = Arc::new(MemoryContentCache::new(1));
+        let verifier = IntegrityVerifier::new(aggregator.url().to_string())
+            .with_content_cache(Arc::clone(&cache) as Arc<dyn ContentCache>);
+        let first = BlobId::from_bytes([10; 32]);
+        let second = BlobId::from_bytes([11; 32]);
+
+        // 容量為 1：第二個 Blob 淘汰第一個，第一個再次審計時完整下載
+        verifier.audit_blob(&first).await.unwrap();
+        verifier.audit_blob(&second).await.unwrap();
+        assert!(cache.get(&first.to_string()).is_none());
+        verifier.audit_blob(&first).await.unwrap();
+        assert_eq!(aggregator.downloads(), 3);
+        assert_eq!(aggregator.not_modified(), 0);
+        assert_eq!(cache.len(), 1);
+
+        // 葉子哈希與 Merkle 根不符的條目被丟棄，不發出條件請求
+        let key = first.to_string();
+        let mut entry = cache.get(&key).unwrap();
+        entry.leaf_hashes[0] = [0; 32];
+        cache.put(&key, entry);
+        let audit_data = verifier.audit_blob(&first).await.unwrap();
+        assert_eq!(aggregator.not_modified(), 0);
+        assert_eq!(audit_data.verification_status, VerificationStatus::Accessible);
+        verifier.audit_blob(&first).await.unwrap();
+        assert_eq!(aggregator.not_modified(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_aggregator_downloads_are_rate_limited() {
+        use crate::rate_limit::RateLimiter;
+        use crate::test_support::{deterministic_blob, AggregatorMode, FakeAggregator};
+
+        let aggregator =
+            FakeAggregator::start(deterministic_blob(100), AggregatorMode::Healthy).await;
+        let verifier = IntegrityVerifier::new(aggregator.url().to_string())
+            .with_rate_limiter(Arc::new(RateLimiter::new(5, 1)));
+
+        let audits = (0..4).map(|_| verifier.audit_blob(&BlobId::from_bytes([0; 32])));
+        for audit_data in futures::future::join_all(audits).await {
+            assert_eq!(audit_data.unwrap().verification_status, VerificationStatus::Accessible);
+        }
+
+        // 無突發：每 200ms 一個下載
+        let times = aggregator.download_times();
+        assert_eq!(times.len(), 4);
+        for (k, time) in times.iter().enumerate().skip(1) {
+            let observed = time.duration_since(times[0]);
+            assert!(
+                observed + Duration::from_millis(20) >= Duration::from_millis(200 * k as u64),
+                "download {} arrived after {:?}",
+                k,
+                observed
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_unavailable_aggregator_opens_circuit() {
+        use crate::breaker::{BreakerConfig, CircuitState};
+        use crate::test_support::{deterministic_blob, AggregatorMode, FakeAggregator};
+
+        let aggregator =
+            FakeAggregator::start(deterministic_blob(100), AggregatorMode::Unavailable).await;
+        let breaker = Arc::new(CircuitBreaker::new(BreakerConfig {
+            min_requests: 3,
+            ..BreakerConfig::default()
+        }));
+        let verifier = IntegrityVerifier::new(aggregator.url().to_string())
+            .with_breaker(Arc::clone(&breaker));
+
+        // 503 仍記為不可達，直到熔斷打開
+        for _ in 0..3 {
+            let audit_data = verifier.audit_blob(&BlobId::from_bytes([0; 32])).await.unwrap();
+            assert_eq!(audit_data.verification_status, VerificationStatus::Unreachable);
+        }
+        assert_eq!(breaker.state(aggregator.url()), CircuitState::Open);
+
+        let err = verifier.audit_blob(&BlobId::from_bytes([0; 32])).await.unwrap_err();
+        assert!(matches!(err, AuditorError::CircuitOpen { .. }));
     }
 
     #[tokio::test]
@@ -518,9 +3049,9 @@ mod tests {
         let verifier = IntegrityVerifier::new_testnet();
 
         // 使用我們上傳的測試 Blob
-        let blob_id = "eRrTusk8yshFQpkemgDnbg0f4-qDo623V2NpeVG1Zcg";
+        let blob_id = BlobId::parse("eRrTusk8yshFQpkemgDnbg0f4-qDo623V2NpeVG1Zcg").unwrap();
 
-        let result = verifier.audit_blob(blob_id).await;
+        let result = verifier.audit_blob(&blob_id).await;
         assert!(result.is_ok());
 
         let audit_data = result.unwrap();
@@ -536,10 +3067,10 @@ mod tests {
     async fn test_blob_verification_success() {
         let verifier = IntegrityVerifier::new_testnet();
 
-        let blob_id = "eRrTusk8yshFQpkemgDnbg0f4-qDo623V2NpeVG1Zcg";
+        let blob_id = BlobId::parse("eRrTusk8yshFQpkemgDnbg0f4-qDo623V2NpeVG1Zcg").unwrap();
         let expected_hash = "bd9e5380f78734bc182e4bb8c464101d3baeb23387d701608901e64cd879e1f5";
 
-        let result = verifier.verify_blob(blob_id, expected_hash).await;
+        let result = verifier.verify_blob(&blob_id, expected_hash).await;
         assert!(result.is_ok());
 
         let audit_data = result.unwrap();
@@ -551,13 +3082,168 @@ mod tests {
     async fn test_blob_verification_failure() {
         let verifier = IntegrityVerifier::new_testnet();
 
-        let blob_id = "eRrTusk8yshFQpkemgDnbg0f4-qDo623V2NpeVG1Zcg";
+        let blob_id = BlobId::parse("eRrTusk8yshFQpkemgDnbg0f4-qDo623V2NpeVG1Zcg").unwrap();
         let wrong_hash = "0000000000000000000000000000000000000000000000000000000000000000";
 
-        let result = verifier.verify_blob(blob_id, wrong_hash).await;
+        let result = verifier.verify_blob(&blob_id, wrong_hash).await;
         assert!(result.is_ok());
 
         let audit_data = result.unwrap();
         assert_eq!(audit_data.verification_status, VerificationStatus::Corrupted);
     }
+
+    #[tokio::test]
+    async fn test_blob_verification_rejects_invalid_expected_hash() {
+        // 無法解碼的預期哈希在下載之前即被拒絕，不再按字符串比較
+        let verifier = IntegrityVerifier::new("http://127.0.0.1:9".to_string());
+        let blob_id = BlobId::parse("eRrTusk8yshFQpkemgDnbg0f4-qDo623V2NpeVG1Zcg").unwrap();
+
+        let result = verifier.verify_blob(&blob_id, "not-a-sha256").await;
+        assert!(matches!(result, Err(AuditorError::InvalidDigest(_))));
+    }
+
+    #[tokio::test]
+    async fn test_local_audit_matches_aggregator_audit() {
+        use crate::test_support::{deterministic_blob, AggregatorMode, FakeAggregator};
+
+        // 多個讀取緩衝區，切片跨越緩衝區邊界
+        let blob = deterministic_blob(LOCAL_READ_BUFFER_SIZE * 2 + 1000 * 7 + 13);
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("blob.bin");
+        std::fs::write(&path, &blob).unwrap();
+
+        let aggregator = FakeAggregator::start(blob.clone(), AggregatorMode::Healthy).await;
+        let config = IntegrityVerifierConfig {
+            chunk_size: 1000,
+            ..IntegrityVerifierConfig::default()
+        };
+        let verifier = IntegrityVerifier::new(aggregator.url().to_string()).with_config(config);
+        let remote = verifier
+            .audit_blob(&BlobId::from_bytes([9; 32]))
+            .await
+            .unwrap();
+
+        let local = audit_local(&path, 1000).unwrap();
+        assert_eq!(local.blob_id, path.display().to_string());
+        assert_eq!(local.content_hash, remote.content_hash);
+        assert_eq!(local.merkle_root, remote.merkle_root);
+        assert_eq!(local.file_size, blob.len() as u64);
+        assert_eq!(local.chunk_size, Some(1000));
+        assert_eq!(local.total_challenges, 10);
+        assert_eq!(local.successful_verifications, 10);
+        assert_eq!(local.verification_status, VerificationStatus::Accessible);
+
+        // 審計方式區分本地與 Aggregator 審計，並隨報告簽名
+        assert_eq!(local.audit_method, AuditMethod::LocalFile);
+        assert_eq!(remote.audit_method, AuditMethod::Aggregator);
+        let json = serde_json::to_value(&local).unwrap();
+        assert_eq!(json["audit_method"], "local_file");
+        let json = serde_json::to_value(&remote).unwrap();
+        assert!(json.get("audit_method").is_none());
+        let report = crate::types::AuditReport::from(local);
+        assert_eq!(report.audit_method, Some(AuditMethod::LocalFile));
+        let mut other = report.clone();
+        other.audit_method = Some(AuditMethod::Aggregator);
+        assert_ne!(report.signing_bytes(), other.signing_bytes());
+    }
+
+    #[test]
+    fn test_verify_local_detects_corrupted_copy() {
+        use crate::test_support::deterministic_blob;
+
+        let blob = deterministic_blob(DEFAULT_CHUNK_SIZE * 6 + 100);
+        let dir = tempfile::tempdir().unwrap();
+        let original = dir.path().join("original.bin");
+        std::fs::write(&original, &blob).unwrap();
+        let expected = audit_local(&original, DEFAULT_CHUNK_SIZE).unwrap();
+
+        let verification = verify_local(&original, &expected).unwrap();
+        assert!(verification.is_match());
+        assert_eq!(
+            verification.audit.verification_status,
+            VerificationStatus::Accessible
+        );
+        assert!(verification.audit.hash_drift.is_none());
+
+        // 翻轉一個字節：大小不變，哈希與 Merkle 根不符
+        let mut corrupted_blob = blob.clone();
+        corrupted_blob[DEFAULT_CHUNK_SIZE * 3 + 5] ^= 0xff;
+        let corrupted = dir.path().join("corrupted.bin");
+        std::fs::write(&corrupted, &corrupted_blob).unwrap();
+
+        let verification = verify_local(&corrupted, &expected).unwrap();
+        assert!(!verification.is_match());
+        let fields: Vec<_> = verification.mismatches.iter().map(|m| m.field).collect();
+        assert_eq!(fields, ["content_hash", "merkle_root"]);
+        assert_eq!(verification.mismatches[0].expected, expected.content_hash);
+        assert_eq!(
+            verification.audit.verification_status,
+            VerificationStatus::Corrupted
+        );
+        let drift = verification.audit.hash_drift.as_ref().unwrap();
+        assert_eq!(drift.expected_merkle_root, expected.merkle_root);
+        assert_eq!(drift.observed_merkle_root, verification.audit.merkle_root);
+        assert_eq!(drift.baseline_at, expected.timestamp);
+
+        // 截斷的副本：大小也不符
+        let truncated = dir.path().join("truncated.bin");
+        std::fs::write(&truncated, &blob[..blob.len() - 1]).unwrap();
+        let verification = verify_local(&truncated, &expected).unwrap();
+        let fields: Vec<_> = verification.mismatches.iter().map(|m| m.field).collect();
+        assert_eq!(fields, ["file_size", "content_hash", "merkle_root"]);
+        assert_eq!(
+            verification.mismatches[0].to_string(),
+            format!("file_size: expected {}, got {}", blob.len(), blob.len() - 1)
+        );
+    }
+
+    #[test]
+    fn test_verify_local_uses_recorded_chunk_size() {
+        use crate::test_support::deterministic_blob;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("blob.bin");
+        std::fs::write(&path, deterministic_blob(1024 * 9 + 1)).unwrap();
+
+        let expected = audit_local(&path, 1024).unwrap();
+        let verification = verify_local(&path, &expected).unwrap();
+        assert!(verification.is_match());
+        assert_eq!(verification.audit.chunk_size, Some(1024));
+
+        // 未記錄切片大小的審計按默認切片構建
+        let mut legacy = audit_local(&path, DEFAULT_CHUNK_SIZE).unwrap();
+        legacy.chunk_size = None;
+        assert!(verify_local(&path, &legacy).unwrap().is_match());
+    }
+
+    #[test]
+    fn test_local_audit_edge_cases() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let empty = dir.path().join("empty.bin");
+        std::fs::write(&empty, b"").unwrap();
+        let audit = audit_local(&empty, DEFAULT_CHUNK_SIZE).unwrap();
+        assert_eq!(audit.file_size, 0);
+        assert_eq!(audit.total_challenges, 0);
+        assert!(audit.merkle_root.is_empty());
+        assert_eq!(audit.verification_status, VerificationStatus::Accessible);
+
+        let missing = dir.path().join("missing.bin");
+        assert!(matches!(
+            audit_local(&missing, DEFAULT_CHUNK_SIZE),
+            Err(AuditorError::Io(_))
+        ));
+        assert!(matches!(
+            audit_local(&empty, 0),
+            Err(AuditorError::Config(_))
+        ));
+
+        // 沒有內容哈希的審計（如 Blob 不可達）無法用於比對
+        let mut unreachable = audit;
+        unreachable.content_hash.clear();
+        assert!(matches!(
+            verify_local(&empty, &unreachable),
+            Err(AuditorError::InvalidDigest(_))
+        ));
+    }
 }
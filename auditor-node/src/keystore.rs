@@ -4,84 +4,420 @@
 //!
 //! ## 密鑰存儲
 //!
-//! 私鑰以**未加密**形式存儲在文件系統中：
-//! - `pqc_public.key`: 公鑰（1952 bytes，可公開）
-//! - `pqc_secret.key`: 私鑰（4032 bytes，**高度敏感**）
+//! 密鑰對保存在密鑰庫目錄的 `keystore.json` 中（格式版本 1）：
+//!
+//! - `version`: 文件格式版本
+//! - `algorithm`: 密鑰算法（`Dilithium3` 或 `Falcon512`，見 [`PqcAlgorithm`]）
+//! - `public_key`: 公鑰（Base64，可公開）
+//! - `secret_key`: 明文私鑰（Base64，**高度敏感**），或
+//! - `encrypted_secret_key`: 口令加密的私鑰（Base64；Argon2id 派生密鑰 + ChaCha20-Poly1305）
+//! - `created_at`: 密鑰生成時間（Unix 秒）
+//!
+//! 算法未知或格式版本高於當前版本的密鑰庫拒絕加載。加密私鑰格式（版本 1）：
+//!
+//! ```text
+//! magic "WAKS" (4) | version (1) | m_cost, t_cost, p_cost (各 4, LE) | salt (16) | nonce (12) | ciphertext
+//! ```
+//!
+//! 加密私鑰的文件頭與公鑰一起作為 AEAD 附加數據，篡改參數或替換公鑰都會導致解密失敗。
+//!
+//! 命令行的 `keygen` 通過 [`Keystore::create`] 生成密鑰庫；`show-key` 通過 [`public_key_info`]
+//! 只讀取公開字段（算法、公鑰、指紋與生成時間），不解碼也不輸出私鑰。
+//!
+//! ## 舊格式遷移
+//!
+//! 舊版密鑰庫以 `pqc_public.key` 與 `pqc_secret.key`（或 `pqc_secret.key.enc`）存儲
+//! Dilithium3 密鑰對。首次加載時寫入等價的 `keystore.json`（加密私鑰原樣保留為密文），
+//! 舊文件保留不刪除；之後以 `keystore.json` 為準。
+//!
+//! ## 密鑰輪換
+//!
+//! [`Keystore::rotate`] 以相同算法生成新密鑰對，用舊私鑰簽名輪換聲明（舊公鑰、新公鑰、時間戳），
+//! 將舊的 `keystore.json` 歸檔為 `keystore.json.N`（N 為舊密鑰的代數，從 1 開始；
+//! 遷移前歸檔的舊格式密鑰為 `pqc_public.key.N` / `pqc_secret.key.N`），
+//! 並把輪換記錄追加到 `rotation_log.json`。
+//! [`Keystore::verify_rotation_chain`] 從第一代密鑰逐條核對到當前密鑰，
+//! 驗證者可憑返回的 [`KeyChain`] 接受鏈上任一密鑰簽名的舊報告
+//! （[`ReportManager::verify_report_with_chain`]）。
+//!
+//! [`ReportManager::verify_report_with_chain`]: crate::report::ReportManager::verify_report_with_chain
 //!
 //! ## 文件權限（Unix/Linux）
 //!
-//! - 私鑰文件自動設置為 `0o600`（僅所有者可讀寫）
-//! - 公鑰文件設置為 `0o644`（所有者可讀寫，其他人只讀）
+//! - `keystore.json` 及其歸檔自動設置為 `0o600`（僅所有者可讀寫）
+//! - 加載時權限不是 `0o600` 會記錄警告
 //!
 //! ## 風險警告
 //!
 //! ⚠️ **當前實現的限制**:
-//! - 私鑰未加密存儲（生產環境應使用硬件 HSM 或加密存儲）
-//! - 沒有密鑰輪換機制
+//! - 默認仍以明文存儲私鑰，需顯式使用 `generate_and_save_encrypted`（生產環境應使用硬件 HSM）
 //! - 沒有審計日誌
 //! - 沒有訪問控制（依賴操作系統文件權限）
 //!
 //! ## 生產環境建議
 //!
 //! 1. **使用硬件安全模塊 (HSM)** 存儲私鑰
-//! 2. **加密私鑰文件**: 使用密碼或 KMS 加密
+//! 2. **加密私鑰文件**: 使用口令加密（見 [`Keystore::generate_and_save_encrypted`]）或 KMS
 //! 3. **訪問控制**: 記錄所有密鑰訪問操作
-//! 4. **密鑰輪換**: 定期更新密鑰對
+//! 4. **密鑰輪換**: 定期更新密鑰對（見 [`Keystore::rotate`]）
 //! 5. **備份**: 安全備份私鑰（加密後異地存儲）
 //!
 //! # 使用示例
 //!
 //! ```no_run
+//! use auditor_node::audit_report::PqcAlgorithm;
 //! use auditor_node::keystore::Keystore;
 //! use std::path::Path;
 //!
-//! // 生成新密鑰對
+//! // 生成新密鑰對（默認 Dilithium3）
 //! let keystore_path = Path::new("./keys");
-//! let keystore = Keystore::generate_and_save(keystore_path)?;
+//! let keystore = Keystore::generate_and_save(keystore_path, PqcAlgorithm::default())?;
 //!
 //! // 下次啟動時加載
 //! let keystore = Keystore::load(keystore_path)?;
 //!
 //! // 獲取公鑰用於分享
 //! let public_key = keystore.public_key_bytes();
+//!
+//! // 口令加密的密鑰庫
+//! let encrypted = Keystore::generate_and_save_encrypted(
+//!     Path::new("./keys-enc"),
+//!     "passphrase",
+//!     PqcAlgorithm::Falcon512,
+//! )?;
+//! let encrypted = Keystore::load_encrypted(Path::new("./keys-enc"), "passphrase")?;
 //! # Ok::<(), auditor_node::error::AuditorError>(())
 //! ```
 
+use crate::audit_report::PqcAlgorithm;
+use crate::blinding::{BlindingSalt, BLINDING_SALT_FILE};
 use crate::error::{AuditorError, Result};
-use pqc_signer::{Dilithium3Signer, Signer};
+use argon2::{Algorithm, Argon2, Params, Version};
+use base64::{engine::general_purpose, Engine as _};
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use pqc_signer::prehash::MessageDigest;
+use pqc_signer::{Dilithium3Signer, Falcon512Signer, PqcError, Signer};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::fs;
 use std::path::{Path, PathBuf};
 use tracing::{info, warn};
+use zeroize::{ZeroizeOnDrop, Zeroizing};
+
+/// 密鑰庫文件名
+pub const KEYSTORE_FILE: &str = "keystore.json";
+
+/// 當前 `keystore.json` 格式版本
+pub const KEYSTORE_FORMAT_VERSION: u32 = 1;
+
+/// 舊格式的公鑰文件名
+pub const PUBLIC_KEY_FILE: &str = "pqc_public.key";
+
+/// 舊格式的明文私鑰文件名
+pub const SECRET_KEY_FILE: &str = "pqc_secret.key";
+
+/// 舊格式的口令加密私鑰文件名
+pub const ENCRYPTED_SECRET_KEY_FILE: &str = "pqc_secret.key.enc";
+
+/// 密鑰輪換記錄文件名
+pub const ROTATION_LOG_FILE: &str = "rotation_log.json";
+
+/// 輪換聲明的域分隔標籤
+pub const ROTATION_STATEMENT_DOMAIN: &[u8] = b"WALRUS_AUDIT_KEY_ROTATION_V1";
+
+/// 讀取密鑰庫口令的環境變量（見 [`Keystore::open`]）
+pub const PASSPHRASE_ENV: &str = "PQC_KEYSTORE_PASSPHRASE";
+
+/// 加密私鑰的魔數與格式版本
+const ENCRYPTED_MAGIC: &[u8; 4] = b"WAKS";
+const ENCRYPTED_VERSION: u8 = 1;
+
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+const HEADER_LEN: usize = ENCRYPTED_MAGIC.len() + 1 + 12 + SALT_LEN + NONCE_LEN;
+
+/// 讀取文件時接受的 Argon2 內存上限（KiB），防止惡意文件頭耗盡內存
+const MAX_KDF_MEMORY_KIB: u32 = 1024 * 1024;
+const MAX_KDF_ITERATIONS: u32 = 64;
+
+/// 新文件使用的 Argon2id 參數（OWASP 推薦的 19 MiB / 2 輪）
+#[cfg(not(test))]
+const KDF_PARAMS: (u32, u32, u32) = (19 * 1024, 2, 1);
+
+/// 測試使用最小參數，避免 debug 構建下派生過慢
+#[cfg(test)]
+const KDF_PARAMS: (u32, u32, u32) = (Params::MIN_M_COST, Params::MIN_T_COST, 1);
+
+/// 密鑰庫中的簽名器：按密鑰算法包裝具體的簽名器
+///
+/// 通過 [`Signer`] trait 使用，調用方無需知道密鑰庫使用哪種算法。
+/// 私鑰在釋放時清零；`clone` 會複製一份私鑰，該副本同樣在釋放時清零
+#[derive(Clone)]
+pub enum KeystoreSigner {
+    /// Dilithium3 密鑰對
+    Dilithium3(Dilithium3Signer),
+    /// Falcon512 密鑰對
+    Falcon512(Falcon512Signer),
+}
+
+impl KeystoreSigner {
+    /// 生成 `algorithm` 的新密鑰對
+    pub fn generate(algorithm: PqcAlgorithm) -> Result<Self> {
+        let mut signer = match algorithm {
+            PqcAlgorithm::Dilithium3 => Self::Dilithium3(Dilithium3Signer::new()),
+            PqcAlgorithm::Falcon512 => Self::Falcon512(Falcon512Signer::new()),
+        };
+        signer.generate_keypair().map_err(|e| {
+            AuditorError::PqcSignature(format!("Failed to generate keypair: {}", e))
+        })?;
+        Ok(signer)
+    }
+
+    /// 從密鑰字節恢復 `algorithm` 的密鑰對
+    ///
+    /// # 錯誤
+    /// - 密鑰長度與算法不符: 返回 `PqcSignature` 錯誤
+    /// - 私鑰與公鑰不屬於同一密鑰對: 返回 `KeyPairMismatch` 錯誤
+    pub fn from_bytes(
+        algorithm: PqcAlgorithm,
+        public_key: &[u8],
+        secret_key: &[u8],
+    ) -> Result<Self> {
+        let restored = match algorithm {
+            PqcAlgorithm::Dilithium3 => {
+                Dilithium3Signer::from_bytes(public_key, secret_key).map(Self::Dilithium3)
+            }
+            PqcAlgorithm::Falcon512 => {
+                Falcon512Signer::from_bytes(public_key, secret_key).map(Self::Falcon512)
+            }
+        };
+        restored.map_err(|e| match e {
+            PqcError::KeyPairMismatch => AuditorError::KeyPairMismatch(format!(
+                "{} secret key does not match the public key",
+                algorithm.as_str()
+            )),
+            e => AuditorError::PqcSignature(format!(
+                "Failed to restore {} keypair: {}. Key file may be corrupted.",
+                algorithm.as_str(),
+                e
+            )),
+        })
+    }
+
+    /// 密鑰算法
+    pub fn algorithm(&self) -> PqcAlgorithm {
+        match self {
+            Self::Dilithium3(_) => PqcAlgorithm::Dilithium3,
+            Self::Falcon512(_) => PqcAlgorithm::Falcon512,
+        }
+    }
+
+    /// 私鑰字節（用於持久化）
+    pub fn secret_key(&self) -> &[u8] {
+        match self {
+            Self::Dilithium3(signer) => signer.secret_key(),
+            Self::Falcon512(signer) => signer.secret_key(),
+        }
+    }
+
+    fn inner(&self) -> &dyn Signer {
+        match self {
+            Self::Dilithium3(signer) => signer,
+            Self::Falcon512(signer) => signer,
+        }
+    }
+}
+
+impl ZeroizeOnDrop for KeystoreSigner {}
+
+impl Signer for KeystoreSigner {
+    fn generate_keypair(&mut self) -> pqc_signer::Result<()> {
+        match self {
+            Self::Dilithium3(signer) => signer.generate_keypair(),
+            Self::Falcon512(signer) => signer.generate_keypair(),
+        }
+    }
+
+    fn sign(&self, message: &[u8]) -> pqc_signer::Result<Vec<u8>> {
+        self.inner().sign(message)
+    }
+
+    fn verify(&self, message: &[u8], signature: &[u8]) -> pqc_signer::Result<bool> {
+        self.inner().verify(message, signature)
+    }
+
+    fn sign_digest(&self, digest: &MessageDigest) -> pqc_signer::Result<Vec<u8>> {
+        self.inner().sign_digest(digest)
+    }
+
+    fn verify_digest(&self, digest: &MessageDigest, signature: &[u8]) -> pqc_signer::Result<bool> {
+        self.inner().verify_digest(digest, signature)
+    }
 
-/// 密鑰庫：管理 Dilithium3 密鑰對的持久化存儲
+    fn verify_strict(&self, message: &[u8], signature: &[u8]) -> pqc_signer::Result<()> {
+        self.inner().verify_strict(message, signature)
+    }
+
+    fn public_key(&self) -> &[u8] {
+        self.inner().public_key()
+    }
+
+    fn algorithm_name(&self) -> &str {
+        self.inner().algorithm_name()
+    }
+
+    fn signature_bytes(&self) -> usize {
+        self.inner().signature_bytes()
+    }
+}
+
+impl From<Dilithium3Signer> for KeystoreSigner {
+    fn from(signer: Dilithium3Signer) -> Self {
+        Self::Dilithium3(signer)
+    }
+}
+
+impl From<Falcon512Signer> for KeystoreSigner {
+    fn from(signer: Falcon512Signer) -> Self {
+        Self::Falcon512(signer)
+    }
+}
+
+/// `keystore.json` 的內容
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct KeystoreFile {
+    /// 文件格式版本
+    version: u32,
+
+    /// 密鑰算法名稱（見 [`PqcAlgorithm::as_str`]）
+    algorithm: String,
+
+    /// 公鑰（Base64）
+    public_key: String,
+
+    /// 明文私鑰（Base64）；加密密鑰庫中不存在
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    secret_key: Option<Zeroizing<String>>,
+
+    /// 口令加密的私鑰（Base64，格式見模塊文檔）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    encrypted_secret_key: Option<String>,
+
+    /// 密鑰生成時間（Unix 秒）
+    created_at: u64,
+}
+
+impl KeystoreFile {
+    /// 明文私鑰的密鑰庫文件
+    fn plaintext(signer: &KeystoreSigner) -> Self {
+        Self {
+            secret_key: Some(Zeroizing::new(
+                general_purpose::STANDARD.encode(signer.secret_key()),
+            )),
+            ..Self::public_only(signer)
+        }
+    }
+
+    /// 口令加密私鑰的密鑰庫文件（`encrypted` 為 [`encrypt_secret_key`] 的輸出）
+    fn encrypted(signer: &KeystoreSigner, encrypted: &[u8]) -> Self {
+        Self {
+            encrypted_secret_key: Some(general_purpose::STANDARD.encode(encrypted)),
+            ..Self::public_only(signer)
+        }
+    }
+
+    fn public_only(signer: &KeystoreSigner) -> Self {
+        Self {
+            version: KEYSTORE_FORMAT_VERSION,
+            algorithm: signer.algorithm().as_str().to_string(),
+            public_key: general_purpose::STANDARD.encode(signer.public_key()),
+            secret_key: None,
+            encrypted_secret_key: None,
+            created_at: chrono::Utc::now().timestamp() as u64,
+        }
+    }
+
+    /// 讀取並檢查格式版本
+    fn read(path: &Path) -> Result<Self> {
+        let content = Zeroizing::new(fs::read_to_string(path).map_err(|e| {
+            AuditorError::Config(format!("Failed to read keystore {:?}: {}", path, e))
+        })?);
+        let file: Self = serde_json::from_str(&content)
+            .map_err(|e| AuditorError::Keystore(format!("Corrupt keystore {:?}: {}", path, e)))?;
+
+        if file.version > KEYSTORE_FORMAT_VERSION {
+            return Err(AuditorError::Keystore(format!(
+                "Unsupported keystore format version {} in {:?} (this build reads up to {})",
+                file.version, path, KEYSTORE_FORMAT_VERSION
+            )));
+        }
+        Ok(file)
+    }
+
+    /// 寫入文件（權限 600；序列化緩衝區寫入後清零）
+    fn write(&self, path: &Path) -> Result<()> {
+        let json = Zeroizing::new(serde_json::to_vec_pretty(self)?);
+        write_key_file(path, &json, 0o600)
+    }
+
+    /// 密鑰算法（未知算法返回錯誤）
+    fn algorithm(&self) -> Result<PqcAlgorithm> {
+        PqcAlgorithm::from_name(&self.algorithm).ok_or_else(|| {
+            AuditorError::Keystore(format!(
+                "Unsupported keystore algorithm: {}",
+                self.algorithm
+            ))
+        })
+    }
+
+    /// 私鑰是否以口令加密
+    fn is_encrypted(&self) -> bool {
+        self.secret_key.is_none() && self.encrypted_secret_key.is_some()
+    }
+
+    fn public_key_bytes(&self) -> Result<Vec<u8>> {
+        decode_key(&self.public_key, "public key")
+    }
+}
+
+/// 密鑰庫：管理 PQC 密鑰對（Dilithium3 或 Falcon512）的持久化存儲
 ///
 /// # 文件結構
 ///
 /// ```text
 /// {base_path}/
-///   ├── pqc_public.key  (1952 bytes, Dilithium3 公鑰)
-///   └── pqc_secret.key  (4032 bytes, Dilithium3 私鑰, 僅所有者可讀)
+///   ├── keystore.json      (算法、公鑰與私鑰, 僅所有者可讀)
+///   ├── blinding_salt.key  (32 bytes, Blob ID 盲化鹽, 按需生成, 僅所有者可讀)
+///   ├── keystore.json.N    (輪換後歸檔的第 N 代密鑰庫)
+///   ├── rotation_log.json  (輪換記錄，見 [`RotationRecord`])
+///   ├── pqc_public.key     (舊格式公鑰，遷移後保留)
+///   └── pqc_secret.key     (舊格式私鑰，遷移後保留)
 /// ```
 pub struct Keystore {
-    /// Dilithium3 簽名器（包含公鑰和私鑰）
-    signer: Dilithium3Signer,
+    /// 簽名器（包含公鑰和私鑰）
+    signer: KeystoreSigner,
     /// 密鑰存儲路徑（用於日誌和調試）
     base_path: PathBuf,
 }
 
+/// 私鑰由 [`KeystoreSigner`] 持有，隨密鑰庫釋放而清零
+impl ZeroizeOnDrop for Keystore {}
+
 impl Keystore {
-    /// 生成新的 Dilithium3 密鑰對並保存到文件
+    /// 生成新的密鑰對並保存到 `keystore.json`
     ///
     /// # 參數
     ///
     /// - `base_path`: 密鑰文件存儲目錄（會自動創建）
+    /// - `algorithm`: 密鑰算法（默認 [`PqcAlgorithm::Dilithium3`]）
     ///
     /// # 文件操作
     ///
     /// 1. 創建目錄（如果不存在）
-    /// 2. 生成 Dilithium3 密鑰對
-    /// 3. 保存公鑰到 `pqc_public.key`（權限 644）
-    /// 4. 保存私鑰到 `pqc_secret.key`（權限 600）
+    /// 2. 生成密鑰對
+    /// 3. 保存算法、公鑰與私鑰到 `keystore.json`（權限 600）
     ///
     /// # 錯誤
     ///
@@ -100,90 +436,31 @@ impl Keystore {
     /// # 示例
     ///
     /// ```no_run
+    /// # use auditor_node::audit_report::PqcAlgorithm;
     /// # use auditor_node::keystore::Keystore;
     /// # use std::path::Path;
-    /// let keystore = Keystore::generate_and_save(Path::new("./keys"))?;
+    /// let keystore = Keystore::generate_and_save(Path::new("./keys"), PqcAlgorithm::default())?;
     /// println!("公鑰長度: {} bytes", keystore.public_key_bytes().len());
     /// # Ok::<(), auditor_node::error::AuditorError>(())
     /// ```
-    pub fn generate_and_save(base_path: &Path) -> Result<Self> {
-        info!("Generating new Dilithium3 keypair at {:?}", base_path);
-
-        // 步驟 1: 確保目錄存在
-        fs::create_dir_all(base_path).map_err(|e| {
-            AuditorError::Config(format!(
-                "Failed to create keystore directory {:?}: {}",
-                base_path, e
-            ))
-        })?;
-
-        // 步驟 2: 生成 Dilithium3 密鑰對
-        let mut signer = Dilithium3Signer::new();
-        signer.generate_keypair().map_err(|e| {
-            AuditorError::PqcSignature(format!("Failed to generate keypair: {}", e))
-        })?;
-
-        let public_key = signer.public_key();
-        let secret_key = signer.secret_key();
-
+    pub fn generate_and_save(base_path: &Path, algorithm: PqcAlgorithm) -> Result<Self> {
         info!(
-            "Generated Dilithium3 keypair: pk={} bytes, sk={} bytes",
-            public_key.len(),
-            secret_key.len()
+            "Generating new {} keypair at {:?}",
+            algorithm.as_str(),
+            base_path
         );
 
-        // 步驟 3: 保存公鑰
-        let public_path = base_path.join("pqc_public.key");
-        fs::write(&public_path, public_key).map_err(|e| {
-            AuditorError::Config(format!("Failed to write public key to {:?}: {}", public_path, e))
-        })?;
-
-        info!("Public key saved to {:?}", public_path);
-
-        // 步驟 4: 保存私鑰
-        let secret_path = base_path.join("pqc_secret.key");
-        fs::write(&secret_path, secret_key).map_err(|e| {
-            AuditorError::Config(format!("Failed to write secret key to {:?}: {}", secret_path, e))
-        })?;
-
-        info!("Secret key saved to {:?}", secret_path);
-
-        // 步驟 5: 設置文件權限（僅 Unix/Linux）
-        #[cfg(unix)]
-        {
-            use std::os::unix::fs::PermissionsExt;
-
-            // 設置私鑰為僅所有者可讀寫 (600)
-            let mut secret_perms = fs::metadata(&secret_path)
-                .map_err(|e| {
-                    AuditorError::Config(format!("Failed to read secret key metadata: {}", e))
-                })?
-                .permissions();
-            secret_perms.set_mode(0o600);
-            fs::set_permissions(&secret_path, secret_perms).map_err(|e| {
-                AuditorError::Config(format!("Failed to set secret key permissions: {}", e))
-            })?;
-
-            info!("Secret key permissions set to 0o600 (owner read/write only)");
-
-            // 設置公鑰為所有者可讀寫，其他人只讀 (644)
-            let mut public_perms = fs::metadata(&public_path)
-                .map_err(|e| {
-                    AuditorError::Config(format!("Failed to read public key metadata: {}", e))
-                })?
-                .permissions();
-            public_perms.set_mode(0o644);
-            fs::set_permissions(&public_path, public_perms).map_err(|e| {
-                AuditorError::Config(format!("Failed to set public key permissions: {}", e))
-            })?;
+        create_keystore_dir(base_path)?;
+        let signer = KeystoreSigner::generate(algorithm)?;
 
-            info!("Public key permissions set to 0o644 (owner read/write, others read)");
-        }
+        info!(
+            "Generated {} keypair: pk={} bytes, sk={} bytes",
+            algorithm.as_str(),
+            signer.public_key().len(),
+            signer.secret_key().len()
+        );
 
-        #[cfg(not(unix))]
-        {
-            warn!("File permissions not set (non-Unix system). Ensure private key security manually!");
-        }
+        KeystoreFile::plaintext(&signer).write(&base_path.join(KEYSTORE_FILE))?;
 
         info!("Keypair successfully saved to {:?}", base_path);
 
@@ -193,24 +470,16 @@ impl Keystore {
         })
     }
 
-    /// 從文件加載現有的 Dilithium3 密鑰對
-    ///
-    /// # 參數
+    /// 從 `keystore.json` 加載密鑰對
     ///
-    /// - `base_path`: 密鑰文件存儲目錄
-    ///
-    /// # 文件要求
-    ///
-    /// 必須存在以下兩個文件：
-    /// - `{base_path}/pqc_public.key` (1952 bytes)
-    /// - `{base_path}/pqc_secret.key` (4032 bytes)
+    /// 目錄中只有舊格式的 `pqc_public.key` / `pqc_secret.key` 時加載它們，
+    /// 並寫入等價的 `keystore.json`（舊文件保留）
     ///
     /// # 錯誤
     ///
-    /// - 密鑰文件不存在
-    /// - 文件讀取失敗
-    /// - 密鑰格式無效（長度不正確）
-    /// - 密鑰反序列化失敗
+    /// - 密鑰庫不存在：`AuditorError::Config`
+    /// - 私鑰已加密（需要口令）、算法未知或格式版本不受支持：`AuditorError::Keystore`
+    /// - 密鑰長度與算法不符：`AuditorError::PqcSignature`
     ///
     /// # 示例
     ///
@@ -222,72 +491,237 @@ impl Keystore {
     /// # Ok::<(), auditor_node::error::AuditorError>(())
     /// ```
     pub fn load(base_path: &Path) -> Result<Self> {
-        info!("Loading Dilithium3 keypair from {:?}", base_path);
+        let path = base_path.join(KEYSTORE_FILE);
+        if !path.exists() {
+            return Self::migrate_legacy(base_path, None);
+        }
 
-        let public_path = base_path.join("pqc_public.key");
-        let secret_path = base_path.join("pqc_secret.key");
+        info!("Loading keystore from {:?}", path);
 
-        // 步驟 1: 檢查文件是否存在
-        if !public_path.exists() {
-            return Err(AuditorError::Config(format!(
-                "Public key file not found: {:?}",
-                public_path
-            )));
+        let file = KeystoreFile::read(&path)?;
+        let algorithm = file.algorithm()?;
+        warn_if_insecure_permissions(&path);
+
+        let secret_key = match &file.secret_key {
+            Some(secret_key) => Zeroizing::new(decode_key(secret_key, "secret key")?),
+            None if file.encrypted_secret_key.is_some() => {
+                return Err(AuditorError::Keystore(format!(
+                    "Secret key in {:?} is encrypted; a passphrase is required (set {})",
+                    base_path, PASSPHRASE_ENV
+                )));
+            }
+            None => {
+                return Err(AuditorError::Keystore(format!(
+                    "Keystore {:?} has no secret key",
+                    path
+                )));
+            }
+        };
+
+        let signer = KeystoreSigner::from_bytes(algorithm, &file.public_key_bytes()?, &secret_key)
+            .map_err(|e| match e {
+                AuditorError::KeyPairMismatch(detail) => {
+                    AuditorError::KeyPairMismatch(format!("{:?}: {}", path, detail))
+                }
+                e => e,
+            })?;
+
+        info!(
+            "{} keypair successfully loaded from {:?}",
+            algorithm.as_str(),
+            base_path
+        );
+
+        Ok(Self {
+            signer,
+            base_path: base_path.to_path_buf(),
+        })
+    }
+
+    /// 生成新的密鑰對，私鑰以口令加密後保存
+    ///
+    /// `keystore.json` 中只有加密私鑰（`encrypted_secret_key`），不會產生明文私鑰。
+    ///
+    /// # 錯誤
+    ///
+    /// - 口令為空
+    /// - 目錄中已有明文私鑰（避免新公鑰與舊私鑰混用）
+    /// - 密鑰生成、加密或文件寫入失敗
+    pub fn generate_and_save_encrypted(
+        base_path: &Path,
+        passphrase: &str,
+        algorithm: PqcAlgorithm,
+    ) -> Result<Self> {
+        if passphrase.is_empty() {
+            return Err(AuditorError::Keystore(
+                "Keystore passphrase must not be empty".to_string(),
+            ));
         }
 
-        if !secret_path.exists() {
-            return Err(AuditorError::Config(format!(
-                "Secret key file not found: {:?}",
-                secret_path
+        let keystore_path = base_path.join(KEYSTORE_FILE);
+        let has_plaintext = if keystore_path.exists() {
+            !KeystoreFile::read(&keystore_path)?.is_encrypted()
+        } else {
+            base_path.join(SECRET_KEY_FILE).exists()
+        };
+        if has_plaintext {
+            return Err(AuditorError::Keystore(format!(
+                "Refusing to create an encrypted keystore next to a plaintext secret key in {:?}",
+                base_path
             )));
         }
 
-        // 步驟 2: 讀取密鑰文件
-        let public_key = fs::read(&public_path).map_err(|e| {
-            AuditorError::Config(format!("Failed to read public key from {:?}: {}", public_path, e))
-        })?;
+        info!(
+            "Generating new encrypted {} keypair at {:?}",
+            algorithm.as_str(),
+            base_path
+        );
 
-        let secret_key = fs::read(&secret_path).map_err(|e| {
-            AuditorError::Config(format!("Failed to read secret key from {:?}: {}", secret_path, e))
-        })?;
+        create_keystore_dir(base_path)?;
+        let signer = KeystoreSigner::generate(algorithm)?;
+        let encrypted = encrypt_secret_key(signer.secret_key(), signer.public_key(), passphrase)?;
+        KeystoreFile::encrypted(&signer, &encrypted).write(&keystore_path)?;
+
+        info!("Encrypted keypair successfully saved to {:?}", base_path);
+
+        Ok(Self {
+            signer,
+            base_path: base_path.to_path_buf(),
+        })
+    }
+
+    /// 使用口令加載密鑰對
+    ///
+    /// 密鑰庫私鑰未加密時忽略口令並按 [`Keystore::load`] 加載；
+    /// 舊格式的 `pqc_secret.key.enc` 解密後遷移到 `keystore.json`（仍為密文）。
+    ///
+    /// # 錯誤
+    ///
+    /// - 口令錯誤或密文被篡改：`AuditorError::Keystore`
+    /// - 文件格式、版本或算法不受支持：`AuditorError::Keystore`
+    /// - 密鑰庫不存在：`AuditorError::Config`
+    pub fn load_encrypted(base_path: &Path, passphrase: &str) -> Result<Self> {
+        let path = base_path.join(KEYSTORE_FILE);
+        if !path.exists() {
+            return Self::migrate_legacy(base_path, Some(passphrase));
+        }
+
+        let file = KeystoreFile::read(&path)?;
+        let Some(encrypted) = file
+            .encrypted_secret_key
+            .as_ref()
+            .filter(|_| file.is_encrypted())
+        else {
+            warn!(
+                "Keystore at {:?} is not encrypted; ignoring passphrase",
+                base_path
+            );
+            return Self::load(base_path);
+        };
+
+        info!("Loading encrypted keystore from {:?}", path);
+
+        let algorithm = file.algorithm()?;
+        let public_key = file.public_key_bytes()?;
+        let secret_key = decrypt_secret_key(
+            &decode_key(encrypted, "encrypted secret key")?,
+            &public_key,
+            passphrase,
+        )?;
+        let signer = KeystoreSigner::from_bytes(algorithm, &public_key, &secret_key)?;
 
         info!(
-            "Read key files: pk={} bytes, sk={} bytes",
-            public_key.len(),
-            secret_key.len()
+            "Encrypted {} keypair successfully loaded from {:?}",
+            algorithm.as_str(),
+            base_path
         );
 
-        // 步驟 3: 驗證文件權限（僅 Unix）
-        #[cfg(unix)]
-        {
-            use std::os::unix::fs::PermissionsExt;
-
-            let secret_perms = fs::metadata(&secret_path)
-                .map_err(|e| {
-                    AuditorError::Config(format!("Failed to read secret key metadata: {}", e))
-                })?
-                .permissions();
+        Ok(Self {
+            signer,
+            base_path: base_path.to_path_buf(),
+        })
+    }
 
-            let mode = secret_perms.mode() & 0o777;
+    /// 加載舊格式的 Dilithium3 密鑰對並寫入 `keystore.json`（舊文件保留）
+    ///
+    /// 加密私鑰原樣寫入 `encrypted_secret_key`；需要 `passphrase` 解密以確認密鑰對有效
+    fn migrate_legacy(base_path: &Path, passphrase: Option<&str>) -> Result<Self> {
+        let public_path = base_path.join(PUBLIC_KEY_FILE);
+        let secret_path = base_path.join(SECRET_KEY_FILE);
+        let encrypted_path = base_path.join(ENCRYPTED_SECRET_KEY_FILE);
 
-            if mode != 0o600 {
-                warn!(
-                    "WARNING: Secret key file has insecure permissions: {:o} (should be 0o600)",
-                    mode
-                );
-                warn!("Run: chmod 600 {:?}", secret_path);
-            }
+        if !public_path.exists() {
+            return Err(AuditorError::Config(format!(
+                "Keystore file not found: {:?}",
+                base_path.join(KEYSTORE_FILE)
+            )));
         }
 
-        // 步驟 4: 從字節恢復密鑰對
-        let signer = Dilithium3Signer::from_bytes(&public_key, &secret_key).map_err(|e| {
-            AuditorError::PqcSignature(format!(
-                "Failed to restore keypair from files: {}. Files may be corrupted.",
-                e
+        info!("Loading legacy Dilithium3 keypair from {:?}", base_path);
+
+        let public_key = fs::read(&public_path).map_err(|e| {
+            AuditorError::Config(format!(
+                "Failed to read public key from {:?}: {}",
+                public_path, e
             ))
         })?;
 
-        info!("Keypair successfully loaded from {:?}", base_path);
+        let (secret_key, encrypted) = if secret_path.exists() {
+            if passphrase.is_some() {
+                warn!(
+                    "Keystore at {:?} is not encrypted; ignoring passphrase",
+                    base_path
+                );
+            }
+            warn_if_insecure_permissions(&secret_path);
+            let secret_key = fs::read(&secret_path).map_err(|e| {
+                AuditorError::Config(format!(
+                    "Failed to read secret key from {:?}: {}",
+                    secret_path, e
+                ))
+            })?;
+            (Zeroizing::new(secret_key), None)
+        } else if encrypted_path.exists() {
+            let Some(passphrase) = passphrase else {
+                return Err(AuditorError::Keystore(format!(
+                    "Secret key in {:?} is encrypted; a passphrase is required (set {})",
+                    base_path, PASSPHRASE_ENV
+                )));
+            };
+            let encrypted = fs::read(&encrypted_path).map_err(|e| {
+                AuditorError::Config(format!(
+                    "Failed to read encrypted secret key from {:?}: {}",
+                    encrypted_path, e
+                ))
+            })?;
+            (
+                decrypt_secret_key(&encrypted, &public_key, passphrase)?,
+                Some(encrypted),
+            )
+        } else {
+            return Err(AuditorError::Config(format!(
+                "Secret key file not found: {:?}",
+                secret_path
+            )));
+        };
+
+        let signer = KeystoreSigner::from_bytes(PqcAlgorithm::Dilithium3, &public_key, &secret_key)
+            .map_err(|e| match e {
+                AuditorError::KeyPairMismatch(detail) => {
+                    AuditorError::KeyPairMismatch(format!("{:?}: {}", base_path, detail))
+                }
+                e => e,
+            })?;
+        let file = match &encrypted {
+            Some(encrypted) => KeystoreFile::encrypted(&signer, encrypted),
+            None => KeystoreFile::plaintext(&signer),
+        };
+        file.write(&base_path.join(KEYSTORE_FILE))?;
+
+        info!(
+            "Migrated legacy keystore at {:?} to {} (legacy key files kept)",
+            base_path, KEYSTORE_FILE
+        );
 
         Ok(Self {
             signer,
@@ -295,26 +729,99 @@ impl Keystore {
         })
     }
 
-    /// 獲取 Dilithium3 簽名器的引用
+    /// 按存儲格式加載密鑰庫
+    ///
+    /// 明文私鑰直接加載；加密私鑰從環境變量 `PQC_KEYSTORE_PASSPHRASE` 讀取口令。
+    pub fn open(base_path: &Path) -> Result<Self> {
+        if !is_encrypted(base_path) {
+            return Self::load(base_path);
+        }
+
+        let passphrase = std::env::var(PASSPHRASE_ENV).map_err(|_| {
+            AuditorError::Keystore(format!(
+                "Keystore at {:?} is encrypted; set {} to unlock it",
+                base_path, PASSPHRASE_ENV
+            ))
+        })?;
+
+        Self::load_encrypted(base_path, &passphrase)
+    }
+
+    /// 在 `base_path` 生成新的密鑰庫（`keygen` 命令）
+    ///
+    /// `passphrase` 為 `Some` 時私鑰以口令加密。目錄中已有密鑰庫（含舊格式）時，
+    /// 除非 `force` 否則拒絕；`force` 刪除現有密鑰文件後重新生成，舊私鑰不可恢復。
+    ///
+    /// # 錯誤
+    ///
+    /// - 已有密鑰庫且未指定 `force`：`AuditorError::Keystore`
+    /// - 目錄中有輪換記錄（覆蓋會使輪換鏈斷裂，應使用 [`Keystore::rotate`]）：`AuditorError::Keystore`
+    /// - 密鑰生成、加密或文件寫入失敗
+    pub fn create(
+        base_path: &Path,
+        algorithm: PqcAlgorithm,
+        passphrase: Option<&str>,
+        force: bool,
+    ) -> Result<Self> {
+        if keystore_exists(base_path) {
+            if !force {
+                return Err(AuditorError::Keystore(format!(
+                    "A keystore already exists in {:?}; use --force to overwrite it",
+                    base_path
+                )));
+            }
+            if base_path.join(ROTATION_LOG_FILE).exists() {
+                return Err(AuditorError::Keystore(format!(
+                    "Keystore in {:?} has a rotation log; overwriting it would break the rotation chain, rotate the key instead",
+                    base_path
+                )));
+            }
+
+            warn!("Overwriting existing keystore in {:?}", base_path);
+            for file in [
+                KEYSTORE_FILE,
+                PUBLIC_KEY_FILE,
+                SECRET_KEY_FILE,
+                ENCRYPTED_SECRET_KEY_FILE,
+            ] {
+                let path = base_path.join(file);
+                if path.exists() {
+                    fs::remove_file(&path)?;
+                }
+            }
+        }
+
+        match passphrase {
+            Some(passphrase) => Self::generate_and_save_encrypted(base_path, passphrase, algorithm),
+            None => Self::generate_and_save(base_path, algorithm),
+        }
+    }
+
+    /// 獲取簽名器的引用
     ///
     /// # 返回
     ///
-    /// 包含公鑰和私鑰的 `Dilithium3Signer` 引用
+    /// 包含公鑰和私鑰的 [`KeystoreSigner`]；通過 [`Signer`] trait 使用，不依賴具體算法
     ///
     /// # 用途
     ///
     /// - 簽名審計報告
     /// - 驗證簽名
     /// - 獲取公鑰/私鑰
-    pub fn signer(&self) -> &Dilithium3Signer {
+    pub fn signer(&self) -> &KeystoreSigner {
         &self.signer
     }
 
+    /// 密鑰算法
+    pub fn algorithm(&self) -> PqcAlgorithm {
+        self.signer.algorithm()
+    }
+
     /// 獲取公鑰字節（用於分享給驗證者）
     ///
     /// # 返回
     ///
-    /// 1952 bytes 的 Dilithium3 公鑰
+    /// 公鑰字節（Dilithium3 為 1952 bytes，Falcon512 為 897 bytes）
     ///
     /// # 用途
     ///
@@ -344,27 +851,352 @@ impl Keystore {
     pub fn base_path(&self) -> &Path {
         &self.base_path
     }
-}
 
-/// 檢查密鑰文件是否存在
-///
-/// # 參數
-///
-/// - `base_path`: 密鑰存儲目錄
-///
-/// # 返回
-///
-/// - `true`: 公鑰和私鑰文件都存在
-/// - `false`: 至少有一個文件不存在
-///
-/// # 用途
-///
-/// 在應用啟動時決定是生成新密鑰還是加載現有密鑰
-///
-/// # 示例
-///
-/// ```no_run
-/// use auditor_node::keystore::{Keystore, keystore_exists};
+    /// 加載盲化 Blob ID 用的秘密鹽（`{base_path}/blinding_salt.key`），不存在時生成
+    ///
+    /// 鹽與私鑰同等敏感：持有者可以將盲化報告映射回真實 Blob ID
+    pub fn blinding_salt(&self) -> Result<BlindingSalt> {
+        BlindingSalt::load_or_create(&self.base_path.join(BLINDING_SALT_FILE))
+    }
+
+    /// 輪換密鑰對
+    ///
+    /// 先核對現有的輪換鏈，再以相同算法生成新密鑰對並用舊私鑰簽名輪換聲明。
+    /// 舊的 `keystore.json` 歸檔為 `keystore.json.N`，記錄追加到 `rotation_log.json`，
+    /// 最後寫入新密鑰。加密密鑰庫的新私鑰以同一口令（`PQC_KEYSTORE_PASSPHRASE`）加密。
+    ///
+    /// # 錯誤
+    ///
+    /// - 現有密鑰無法加載或輪換鏈無效
+    /// - 歸檔文件已存在（不覆蓋）
+    /// - 密鑰生成、簽名或文件寫入失敗
+    pub fn rotate(base_path: &Path) -> Result<Self> {
+        // 加載時舊格式密鑰庫已遷移，之後只處理 keystore.json
+        let current = Self::open(base_path)?;
+        let mut log = read_rotation_log(base_path)?;
+        Self::verify_rotation_chain(base_path)?;
+
+        let generation = log.len() + 1;
+        let keystore_path = base_path.join(KEYSTORE_FILE);
+        let archived = archived_key_path(base_path, KEYSTORE_FILE, generation);
+        if archived.exists() {
+            return Err(AuditorError::Keystore(format!(
+                "Refusing to overwrite archived key {:?}",
+                archived
+            )));
+        }
+
+        let algorithm = current.algorithm();
+        info!(
+            "Rotating {} keypair at {:?} (retiring generation {})",
+            algorithm.as_str(),
+            base_path,
+            generation
+        );
+
+        let signer = KeystoreSigner::generate(algorithm)?;
+        let new_file = if is_encrypted(base_path) {
+            let passphrase = std::env::var(PASSPHRASE_ENV).map_err(|_| {
+                AuditorError::Keystore(format!(
+                    "Keystore at {:?} is encrypted; set {} to rotate it",
+                    base_path, PASSPHRASE_ENV
+                ))
+            })?;
+            let encrypted =
+                encrypt_secret_key(signer.secret_key(), signer.public_key(), &passphrase)?;
+            KeystoreFile::encrypted(&signer, &encrypted)
+        } else {
+            KeystoreFile::plaintext(&signer)
+        };
+
+        let record = RotationRecord::sign(
+            &current.signer,
+            signer.public_key(),
+            chrono::Utc::now().timestamp() as u64,
+        )?;
+
+        // 歸檔舊密鑰庫（原樣複製，加密私鑰仍為密文）
+        let old = fs::read(&keystore_path).map_err(|e| {
+            AuditorError::Config(format!(
+                "Failed to read keystore {:?}: {}",
+                keystore_path, e
+            ))
+        })?;
+        write_key_file(&archived, &old, 0o600)?;
+
+        log.push(record);
+        write_rotation_log(base_path, &log)?;
+
+        new_file.write(&keystore_path)?;
+
+        info!(
+            "Key rotated: generation {} is now current (key {})",
+            generation + 1,
+            crate::trust::key_id(signer.public_key())
+        );
+
+        Ok(Self {
+            signer,
+            base_path: base_path.to_path_buf(),
+        })
+    }
+
+    /// 核對密鑰庫的輪換鏈，返回從第一代到當前密鑰的 [`KeyChain`]
+    ///
+    /// 每條記錄的舊公鑰必須與歸檔的第 N 代公鑰（`keystore.json.N`，遷移前為 `pqc_public.key.N`）
+    /// 及上一條記錄的新公鑰一致，簽名必須能用舊公鑰驗證，時間戳不得倒退；
+    /// 最後一條記錄的新公鑰必須是當前公鑰。沒有輪換記錄時，鏈只包含當前密鑰
+    ///
+    /// # 錯誤
+    ///
+    /// - 密鑰庫或輪換記錄無法讀取：`AuditorError::Config` / `AuditorError::Keystore`
+    /// - 鏈斷裂或簽名無效：`AuditorError::Keystore`
+    pub fn verify_rotation_chain(base_path: &Path) -> Result<KeyChain> {
+        let current = current_public_key(base_path)?;
+
+        let rotations = read_rotation_log(base_path)?;
+        for (index, record) in rotations.iter().enumerate() {
+            let generation = index + 1;
+            if archived_public_key(base_path, generation)? != record.old_public_key_bytes()? {
+                return Err(AuditorError::Keystore(format!(
+                    "Rotation {} does not start from the archived key of generation {}",
+                    generation, generation
+                )));
+            }
+        }
+
+        KeyChain::from_records(rotations, current)
+    }
+
+    /// 本密鑰庫的輪換鏈（見 [`Keystore::verify_rotation_chain`]）
+    pub fn rotation_chain(&self) -> Result<KeyChain> {
+        Self::verify_rotation_chain(&self.base_path)
+    }
+}
+
+/// 一次密鑰輪換的記錄（`rotation_log.json` 中的一項）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RotationRecord {
+    /// 被輪換的舊公鑰（hex）
+    pub old_public_key: String,
+
+    /// 新公鑰（hex）
+    pub new_public_key: String,
+
+    /// 輪換時間（Unix 秒）
+    pub timestamp: u64,
+
+    /// 舊私鑰對輪換聲明的簽名（hex）
+    pub signature: String,
+
+    /// 舊密鑰的算法名稱（缺省為 Dilithium3：早於算法字段的記錄）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub algorithm: Option<String>,
+}
+
+impl RotationRecord {
+    /// 用舊密鑰簽名輪換聲明
+    pub fn sign(old: &dyn Signer, new_public_key: &[u8], timestamp: u64) -> Result<Self> {
+        let statement = rotation_statement(old.public_key(), new_public_key, timestamp);
+        let signature = old.sign(&statement).map_err(|e| {
+            AuditorError::PqcSignature(format!("Failed to sign rotation statement: {}", e))
+        })?;
+
+        Ok(Self {
+            old_public_key: hex::encode(old.public_key()),
+            new_public_key: hex::encode(new_public_key),
+            timestamp,
+            signature: hex::encode(signature),
+            algorithm: Some(old.algorithm_name().to_string()),
+        })
+    }
+
+    /// 用記錄中的舊公鑰驗證簽名
+    pub fn verify(&self) -> Result<bool> {
+        let old_key = self.old_public_key_bytes()?;
+        let signature = hex::decode(&self.signature)
+            .map_err(|e| AuditorError::Keystore(format!("Corrupt rotation signature: {}", e)))?;
+        let statement = rotation_statement(&old_key, &self.new_public_key_bytes()?, self.timestamp);
+
+        let algorithm = match &self.algorithm {
+            Some(name) => PqcAlgorithm::from_name(name).ok_or_else(|| {
+                AuditorError::Keystore(format!("Unsupported algorithm in rotation log: {}", name))
+            })?,
+            None => PqcAlgorithm::Dilithium3,
+        };
+        let verifier = algorithm
+            .verifier(&old_key)
+            .map_err(|e| AuditorError::Keystore(format!("Invalid retired public key: {}", e)))?;
+        // 簽名格式錯誤視為無效簽名
+        Ok(verifier.verify(&statement, &signature).unwrap_or(false))
+    }
+
+    /// 舊公鑰字節
+    pub fn old_public_key_bytes(&self) -> Result<Vec<u8>> {
+        hex::decode(&self.old_public_key)
+            .map_err(|e| AuditorError::Keystore(format!("Corrupt key in rotation log: {}", e)))
+    }
+
+    /// 新公鑰字節
+    pub fn new_public_key_bytes(&self) -> Result<Vec<u8>> {
+        hex::decode(&self.new_public_key)
+            .map_err(|e| AuditorError::Keystore(format!("Corrupt key in rotation log: {}", e)))
+    }
+}
+
+/// 已核對的密鑰輪換鏈
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyChain {
+    /// 各代公鑰，從第一代到當前密鑰
+    keys: Vec<Vec<u8>>,
+
+    /// 輪換記錄
+    rotations: Vec<RotationRecord>,
+}
+
+impl KeyChain {
+    /// 從輪換記錄與當前公鑰重建並核對輪換鏈（不讀取密鑰庫目錄）
+    ///
+    /// 檢查與 [`Keystore::verify_rotation_chain`] 相同，只是沒有歸檔公鑰可供對照：
+    /// 記錄首尾相接、簽名能用舊公鑰驗證、時間戳不倒退，最後一條記錄的新公鑰是 `current`。
+    /// 用於核對密鑰庫以外的輪換鏈（如離線報告包中的副本）
+    ///
+    /// # 錯誤
+    ///
+    /// - 鏈斷裂、簽名無效或記錄損壞：`AuditorError::Keystore`
+    pub fn from_records(rotations: Vec<RotationRecord>, current: Vec<u8>) -> Result<Self> {
+        let mut keys: Vec<Vec<u8>> = Vec::with_capacity(rotations.len() + 1);
+        let mut last_timestamp = 0;
+
+        for (index, record) in rotations.iter().enumerate() {
+            let generation = index + 1;
+            let old_key = record.old_public_key_bytes()?;
+            let new_key = record.new_public_key_bytes()?;
+
+            if keys.last().is_some_and(|previous| *previous != old_key) {
+                return Err(AuditorError::Keystore(format!(
+                    "Rotation {} does not start from the key introduced by rotation {}",
+                    generation, index
+                )));
+            }
+            if record.timestamp < last_timestamp {
+                return Err(AuditorError::Keystore(format!(
+                    "Rotation {} is dated before the rotation preceding it",
+                    generation
+                )));
+            }
+            if !record.verify()? {
+                return Err(AuditorError::Keystore(format!(
+                    "Rotation {} is not signed by the key it retires",
+                    generation
+                )));
+            }
+
+            if keys.is_empty() {
+                keys.push(old_key);
+            }
+            keys.push(new_key);
+            last_timestamp = record.timestamp;
+        }
+
+        match keys.last() {
+            Some(last) if *last != current => {
+                return Err(AuditorError::Keystore(
+                    "Current key is not the key introduced by the last rotation".to_string(),
+                ));
+            }
+            Some(_) => {}
+            None => keys.push(current),
+        }
+
+        Ok(Self { keys, rotations })
+    }
+
+    /// 各代公鑰，從第一代到當前密鑰
+    pub fn keys(&self) -> &[Vec<u8>] {
+        &self.keys
+    }
+
+    /// 當前公鑰
+    pub fn current(&self) -> &[u8] {
+        self.keys.last().expect("key chain is never empty")
+    }
+
+    /// 公鑰的代數（從 1 開始），不在鏈上時為 `None`
+    pub fn generation(&self, public_key: &[u8]) -> Option<usize> {
+        self.keys
+            .iter()
+            .position(|key| key == public_key)
+            .map(|index| index + 1)
+    }
+
+    /// 輪換記錄
+    pub fn rotations(&self) -> &[RotationRecord] {
+        &self.rotations
+    }
+}
+
+/// 輪換聲明的簽名字節：域分隔標籤、舊公鑰與新公鑰（u32 長度前綴）、時間戳（u64），小端序
+fn rotation_statement(old_public_key: &[u8], new_public_key: &[u8], timestamp: u64) -> Vec<u8> {
+    let mut statement = Vec::with_capacity(
+        ROTATION_STATEMENT_DOMAIN.len() + 16 + old_public_key.len() + new_public_key.len(),
+    );
+    statement.extend_from_slice(ROTATION_STATEMENT_DOMAIN);
+    statement.extend_from_slice(&(old_public_key.len() as u32).to_le_bytes());
+    statement.extend_from_slice(old_public_key);
+    statement.extend_from_slice(&(new_public_key.len() as u32).to_le_bytes());
+    statement.extend_from_slice(new_public_key);
+    statement.extend_from_slice(&timestamp.to_le_bytes());
+    statement
+}
+
+/// 第 `generation` 代歸檔密鑰的路徑（`{file}.{generation}`）
+fn archived_key_path(base_path: &Path, file: &str, generation: usize) -> PathBuf {
+    base_path.join(format!("{}.{}", file, generation))
+}
+
+/// 讀取輪換記錄（文件不存在時為空）
+fn read_rotation_log(base_path: &Path) -> Result<Vec<RotationRecord>> {
+    let path = base_path.join(ROTATION_LOG_FILE);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = fs::read_to_string(&path).map_err(|e| {
+        AuditorError::Config(format!("Failed to read rotation log {:?}: {}", path, e))
+    })?;
+    serde_json::from_str(&content)
+        .map_err(|e| AuditorError::Keystore(format!("Corrupt rotation log {:?}: {}", path, e)))
+}
+
+/// 寫入輪換記錄（先寫臨時文件再重命名）
+fn write_rotation_log(base_path: &Path, log: &[RotationRecord]) -> Result<()> {
+    let path = base_path.join(ROTATION_LOG_FILE);
+    let tmp = path.with_extension("json.tmp");
+    fs::write(&tmp, serde_json::to_vec_pretty(log)?)?;
+    fs::rename(&tmp, &path)?;
+    Ok(())
+}
+
+/// 檢查密鑰文件是否存在
+///
+/// # 參數
+///
+/// - `base_path`: 密鑰存儲目錄
+///
+/// # 返回
+///
+/// - `true`: `keystore.json` 存在，或舊格式的公鑰與私鑰（明文或加密格式）都存在
+/// - `false`: 沒有可加載的密鑰庫
+///
+/// # 用途
+///
+/// 在應用啟動時決定是生成新密鑰還是加載現有密鑰
+///
+/// # 示例
+///
+/// ```no_run
+/// use auditor_node::keystore::{Keystore, keystore_exists};
 /// use std::path::Path;
 ///
 /// let keystore_path = Path::new("./keys");
@@ -374,20 +1206,285 @@ impl Keystore {
 ///     Keystore::load(keystore_path)?
 /// } else {
 ///     println!("Generating new keypair...");
-///     Keystore::generate_and_save(keystore_path)?
+///     Keystore::generate_and_save(keystore_path, Default::default())?
 /// };
 /// # Ok::<(), auditor_node::error::AuditorError>(())
 /// ```
 pub fn keystore_exists(base_path: &Path) -> bool {
-    let public_exists = base_path.join("pqc_public.key").exists();
-    let secret_exists = base_path.join("pqc_secret.key").exists();
+    if base_path.join(KEYSTORE_FILE).exists() {
+        return true;
+    }
+
+    let public_exists = base_path.join(PUBLIC_KEY_FILE).exists();
+    let secret_exists = base_path.join(SECRET_KEY_FILE).exists()
+        || base_path.join(ENCRYPTED_SECRET_KEY_FILE).exists();
 
     public_exists && secret_exists
 }
 
+/// 密鑰庫的私鑰是否以口令加密存儲
+///
+/// 讀取 `keystore.json`（無法讀取時視為未加密，由加載報告錯誤）；
+/// 未遷移的舊格式密鑰庫檢查 `pqc_secret.key.enc`
+pub fn is_encrypted(base_path: &Path) -> bool {
+    let path = base_path.join(KEYSTORE_FILE);
+    if path.exists() {
+        return KeystoreFile::read(&path).is_ok_and(|file| file.is_encrypted());
+    }
+    base_path.join(ENCRYPTED_SECRET_KEY_FILE).exists()
+}
+
+/// 密鑰庫的公開信息（`show-key` 命令）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PublicKeyInfo {
+    /// 密鑰算法
+    pub algorithm: PqcAlgorithm,
+
+    /// 公鑰
+    pub public_key: Vec<u8>,
+
+    /// 公鑰指紋（見 [`key_fingerprint`]）
+    pub fingerprint: String,
+
+    /// 密鑰生成時間（Unix 秒）；未遷移的舊格式密鑰庫沒有記錄
+    pub created_at: Option<u64>,
+}
+
+/// 讀取密鑰庫的公開信息
+///
+/// 只讀取 `keystore.json` 的公開字段（未遷移的舊格式密鑰庫讀取 `pqc_public.key`），
+/// 不解碼私鑰，因此加密密鑰庫無需口令，也不會觸發遷移
+pub fn public_key_info(base_path: &Path) -> Result<PublicKeyInfo> {
+    let path = base_path.join(KEYSTORE_FILE);
+    let (algorithm, public_key, created_at) = if path.exists() {
+        let file = KeystoreFile::read(&path)?;
+        (
+            file.algorithm()?,
+            file.public_key_bytes()?,
+            Some(file.created_at),
+        )
+    } else if keystore_exists(base_path) {
+        (
+            PqcAlgorithm::Dilithium3,
+            current_public_key(base_path)?,
+            None,
+        )
+    } else {
+        return Err(AuditorError::Config(format!(
+            "Keystore file not found: {:?}",
+            path
+        )));
+    };
+
+    Ok(PublicKeyInfo {
+        algorithm,
+        fingerprint: key_fingerprint(&public_key),
+        public_key,
+        created_at,
+    })
+}
+
+/// 公鑰指紋：公鑰的 SHA-256（hex）
+///
+/// 信任庫的 [`key_id`](crate::trust::key_id) 為其前 16 個十六進制字符
+pub fn key_fingerprint(public_key: &[u8]) -> String {
+    hex::encode(Sha256::digest(public_key))
+}
+
+/// 當前公鑰：`keystore.json` 中的公鑰；未遷移的舊格式密鑰庫讀取 `pqc_public.key`
+fn current_public_key(base_path: &Path) -> Result<Vec<u8>> {
+    let path = base_path.join(KEYSTORE_FILE);
+    if path.exists() {
+        return KeystoreFile::read(&path)?.public_key_bytes();
+    }
+
+    let public_path = base_path.join(PUBLIC_KEY_FILE);
+    fs::read(&public_path).map_err(|e| {
+        AuditorError::Config(format!(
+            "Failed to read public key from {:?}: {}",
+            public_path, e
+        ))
+    })
+}
+
+/// 第 `generation` 代歸檔公鑰：`keystore.json.N`，遷移前歸檔的為 `pqc_public.key.N`
+fn archived_public_key(base_path: &Path, generation: usize) -> Result<Vec<u8>> {
+    let archived = archived_key_path(base_path, KEYSTORE_FILE, generation);
+    if archived.exists() {
+        return KeystoreFile::read(&archived)?.public_key_bytes();
+    }
+
+    let legacy = archived_key_path(base_path, PUBLIC_KEY_FILE, generation);
+    fs::read(&legacy).map_err(|e| {
+        AuditorError::Keystore(format!(
+            "Archived key for generation {} is missing ({:?}): {}",
+            generation, archived, e
+        ))
+    })
+}
+
+/// 創建密鑰庫目錄
+fn create_keystore_dir(base_path: &Path) -> Result<()> {
+    fs::create_dir_all(base_path).map_err(|e| {
+        AuditorError::Config(format!(
+            "Failed to create keystore directory {:?}: {}",
+            base_path, e
+        ))
+    })
+}
+
+/// 解碼 Base64 密鑰字段
+fn decode_key(encoded: &str, what: &str) -> Result<Vec<u8>> {
+    general_purpose::STANDARD
+        .decode(encoded)
+        .map_err(|e| AuditorError::Keystore(format!("Corrupt {} in keystore: {}", what, e)))
+}
+
+/// 私鑰文件權限不是 600 時記錄警告（僅 Unix）
+fn warn_if_insecure_permissions(path: &Path) {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+
+        if let Ok(metadata) = fs::metadata(path) {
+            let mode = metadata.permissions().mode() & 0o777;
+            if mode != 0o600 {
+                warn!(
+                    "WARNING: Secret key file has insecure permissions: {:o} (should be 0o600)",
+                    mode
+                );
+                warn!("Run: chmod 600 {:?}", path);
+            }
+        }
+    }
+
+    #[cfg(not(unix))]
+    {
+        let _ = path;
+    }
+}
+
+/// 寫入密鑰文件並設置權限（僅 Unix）
+pub(crate) fn write_key_file(path: &Path, bytes: &[u8], mode: u32) -> Result<()> {
+    fs::write(path, bytes)
+        .map_err(|e| AuditorError::Config(format!("Failed to write key file {:?}: {}", path, e)))?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+
+        fs::set_permissions(path, fs::Permissions::from_mode(mode)).map_err(|e| {
+            AuditorError::Config(format!("Failed to set permissions on {:?}: {}", path, e))
+        })?;
+    }
+
+    #[cfg(not(unix))]
+    {
+        let _ = mode;
+        warn!("File permissions not set (non-Unix system). Ensure private key security manually!");
+    }
+
+    info!("Key file saved to {:?}", path);
+    Ok(())
+}
+
+/// 用 Argon2id 從口令派生 32 字節對稱密鑰
+fn derive_key(
+    passphrase: &str,
+    salt: &[u8],
+    (m_cost, t_cost, p_cost): (u32, u32, u32),
+) -> Result<[u8; 32]> {
+    let params = Params::new(m_cost, t_cost, p_cost, Some(32))
+        .map_err(|e| AuditorError::Keystore(format!("Invalid key derivation parameters: {}", e)))?;
+
+    let mut key = [0u8; 32];
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|e| AuditorError::Keystore(format!("Key derivation failed: {}", e)))?;
+
+    Ok(key)
+}
+
+/// 加密私鑰，返回完整的加密私鑰（文件頭與密文）
+fn encrypt_secret_key(secret_key: &[u8], public_key: &[u8], passphrase: &str) -> Result<Vec<u8>> {
+    let mut salt = [0u8; SALT_LEN];
+    let mut nonce = [0u8; NONCE_LEN];
+    rand::thread_rng().fill_bytes(&mut salt);
+    rand::thread_rng().fill_bytes(&mut nonce);
+
+    let (m_cost, t_cost, p_cost) = KDF_PARAMS;
+    let mut header = Vec::with_capacity(HEADER_LEN);
+    header.extend_from_slice(ENCRYPTED_MAGIC);
+    header.push(ENCRYPTED_VERSION);
+    header.extend_from_slice(&m_cost.to_le_bytes());
+    header.extend_from_slice(&t_cost.to_le_bytes());
+    header.extend_from_slice(&p_cost.to_le_bytes());
+    header.extend_from_slice(&salt);
+    header.extend_from_slice(&nonce);
+
+    let key = derive_key(passphrase, &salt, KDF_PARAMS)?;
+    let aad = [header.as_slice(), public_key].concat();
+    let ciphertext = ChaCha20Poly1305::new(&Key::from(key))
+        .encrypt(&Nonce::from(nonce), Payload { msg: secret_key, aad: &aad })
+        .map_err(|_| AuditorError::Keystore("Failed to encrypt secret key".to_string()))?;
+
+    header.extend_from_slice(&ciphertext);
+    Ok(header)
+}
+
+/// 解析並解密加密私鑰（`encrypted_secret_key` 或舊格式的 `pqc_secret.key.enc`）
+fn decrypt_secret_key(
+    data: &[u8],
+    public_key: &[u8],
+    passphrase: &str,
+) -> Result<Zeroizing<Vec<u8>>> {
+    if data.len() < HEADER_LEN {
+        return Err(AuditorError::Keystore(
+            "Encrypted secret key file is truncated".to_string(),
+        ));
+    }
+
+    let (header, ciphertext) = data.split_at(HEADER_LEN);
+    if &header[..4] != ENCRYPTED_MAGIC {
+        return Err(AuditorError::Keystore(
+            "Not an encrypted keystore file (bad magic)".to_string(),
+        ));
+    }
+    if header[4] != ENCRYPTED_VERSION {
+        return Err(AuditorError::Keystore(format!(
+            "Unsupported encrypted keystore version: {}",
+            header[4]
+        )));
+    }
+
+    let read_u32 = |at: usize| u32::from_le_bytes(header[at..at + 4].try_into().unwrap());
+    let params = (read_u32(5), read_u32(9), read_u32(13));
+    if params.0 > MAX_KDF_MEMORY_KIB || params.1 > MAX_KDF_ITERATIONS {
+        return Err(AuditorError::Keystore(format!(
+            "Key derivation parameters out of range: m_cost={} KiB, t_cost={}",
+            params.0, params.1
+        )));
+    }
+
+    let salt = &header[17..17 + SALT_LEN];
+    let nonce: [u8; NONCE_LEN] = header[17 + SALT_LEN..].try_into().unwrap();
+
+    let key = derive_key(passphrase, salt, params)?;
+    let aad = [header, public_key].concat();
+    ChaCha20Poly1305::new(&Key::from(key))
+        .decrypt(&Nonce::from(nonce), Payload { msg: ciphertext, aad: &aad })
+        .map(Zeroizing::new)
+        .map_err(|_| {
+            AuditorError::Keystore(
+                "Failed to decrypt secret key: wrong passphrase or corrupted key file".to_string(),
+            )
+        })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::report::ReportManager;
     use std::env;
 
     /// 創建臨時測試目錄
@@ -412,14 +1509,16 @@ mod tests {
         let temp_dir = create_temp_dir();
 
         // 生成並保存密鑰
-        let keystore = Keystore::generate_and_save(&temp_dir).unwrap();
+        let keystore = Keystore::generate_and_save(&temp_dir, PqcAlgorithm::default()).unwrap();
 
         // 驗證公鑰長度
         assert_eq!(keystore.public_key_bytes().len(), 1952);
 
-        // 驗證文件存在
-        assert!(temp_dir.join("pqc_public.key").exists());
-        assert!(temp_dir.join("pqc_secret.key").exists());
+        // 只寫入 keystore.json
+        assert!(temp_dir.join(KEYSTORE_FILE).exists());
+        assert!(!temp_dir.join(PUBLIC_KEY_FILE).exists());
+        assert!(!temp_dir.join(SECRET_KEY_FILE).exists());
+        assert_eq!(keystore.algorithm(), PqcAlgorithm::Dilithium3);
 
         // 清理
         fs::remove_dir_all(&temp_dir).ok();
@@ -430,7 +1529,7 @@ mod tests {
         let temp_dir = create_temp_dir();
 
         // 生成密鑰
-        let keystore1 = Keystore::generate_and_save(&temp_dir).unwrap();
+        let keystore1 = Keystore::generate_and_save(&temp_dir, PqcAlgorithm::default()).unwrap();
         let public1 = keystore1.public_key_bytes();
 
         // 加載密鑰
@@ -449,7 +1548,7 @@ mod tests {
         let temp_dir = create_temp_dir();
 
         // 生成密鑰
-        let keystore = Keystore::generate_and_save(&temp_dir).unwrap();
+        let keystore = Keystore::generate_and_save(&temp_dir, PqcAlgorithm::default()).unwrap();
 
         // 簽名消息
         let message = b"Test audit report";
@@ -471,7 +1570,7 @@ mod tests {
         assert!(!keystore_exists(&temp_dir));
 
         // 生成後存在
-        Keystore::generate_and_save(&temp_dir).unwrap();
+        Keystore::generate_and_save(&temp_dir, PqcAlgorithm::default()).unwrap();
         assert!(keystore_exists(&temp_dir));
 
         // 清理
@@ -505,21 +1604,14 @@ mod tests {
         let temp_dir = create_temp_dir();
 
         // 生成密鑰
-        Keystore::generate_and_save(&temp_dir).unwrap();
-
-        // 檢查私鑰權限
-        let secret_path = temp_dir.join("pqc_secret.key");
-        let perms = fs::metadata(&secret_path).unwrap().permissions();
-        let mode = perms.mode() & 0o777;
-
-        assert_eq!(mode, 0o600, "Secret key should have 0o600 permissions");
+        Keystore::generate_and_save(&temp_dir, PqcAlgorithm::default()).unwrap();
 
-        // 檢查公鑰權限
-        let public_path = temp_dir.join("pqc_public.key");
-        let perms = fs::metadata(&public_path).unwrap().permissions();
+        // 檢查密鑰庫權限（包含私鑰）
+        let keystore_path = temp_dir.join(KEYSTORE_FILE);
+        let perms = fs::metadata(&keystore_path).unwrap().permissions();
         let mode = perms.mode() & 0o777;
 
-        assert_eq!(mode, 0o644, "Public key should have 0o644 permissions");
+        assert_eq!(mode, 0o600, "Keystore should have 0o600 permissions");
 
         // 清理
         fs::remove_dir_all(&temp_dir).ok();
@@ -530,7 +1622,7 @@ mod tests {
         let temp_dir = create_temp_dir();
 
         // 生成並簽名
-        let keystore1 = Keystore::generate_and_save(&temp_dir).unwrap();
+        let keystore1 = Keystore::generate_and_save(&temp_dir, PqcAlgorithm::default()).unwrap();
         let message = b"Persistence test";
         let signature = keystore1.signer().sign(message).unwrap();
 
@@ -543,4 +1635,560 @@ mod tests {
         // 清理
         fs::remove_dir_all(&temp_dir).ok();
     }
+
+    #[test]
+    fn test_encrypted_keystore_roundtrip() {
+        let temp_dir = create_temp_dir();
+
+        let keystore1 = Keystore::generate_and_save_encrypted(
+            &temp_dir,
+            "correct horse",
+            PqcAlgorithm::default(),
+        )
+        .unwrap();
+        let message = b"Encrypted persistence test";
+        let signature = keystore1.signer().sign(message).unwrap();
+
+        // 不產生明文私鑰
+        let file = KeystoreFile::read(&temp_dir.join(KEYSTORE_FILE)).unwrap();
+        assert!(file.secret_key.is_none());
+        assert!(!temp_dir.join(SECRET_KEY_FILE).exists());
+        assert!(is_encrypted(&temp_dir));
+        assert!(keystore_exists(&temp_dir));
+
+        let keystore2 = Keystore::load_encrypted(&temp_dir, "correct horse").unwrap();
+        assert_eq!(keystore1.public_key_bytes(), keystore2.public_key_bytes());
+        assert!(keystore2.signer().verify(message, &signature).unwrap());
+
+        // 不帶口令加載給出明確錯誤
+        match Keystore::load(&temp_dir) {
+            Err(AuditorError::Keystore(msg)) => assert!(msg.contains("passphrase")),
+            _ => panic!("Expected Keystore error"),
+        }
+
+        fs::remove_dir_all(&temp_dir).ok();
+    }
+
+    #[test]
+    fn test_encrypted_keystore_wrong_passphrase() {
+        let temp_dir = create_temp_dir();
+        Keystore::generate_and_save_encrypted(&temp_dir, "correct horse", PqcAlgorithm::default())
+            .unwrap();
+
+        match Keystore::load_encrypted(&temp_dir, "battery staple") {
+            Err(AuditorError::Keystore(msg)) => assert!(msg.contains("wrong passphrase")),
+            _ => panic!("Expected Keystore error"),
+        }
+
+        fs::remove_dir_all(&temp_dir).ok();
+    }
+
+    /// 修改 `keystore.json` 中的字段
+    fn edit_keystore_file(dir: &Path, edit: impl FnOnce(&mut KeystoreFile)) {
+        let path = dir.join(KEYSTORE_FILE);
+        let mut file = KeystoreFile::read(&path).unwrap();
+        edit(&mut file);
+        file.write(&path).unwrap();
+    }
+
+    #[test]
+    fn test_encrypted_keystore_corruption_detected() {
+        let temp_dir = create_temp_dir();
+        Keystore::generate_and_save_encrypted(&temp_dir, "pw", PqcAlgorithm::default()).unwrap();
+        let original = KeystoreFile::read(&temp_dir.join(KEYSTORE_FILE)).unwrap();
+        let encrypted = decode_key(original.encrypted_secret_key.as_ref().unwrap(), "").unwrap();
+        let with_encrypted = |bytes: &[u8]| {
+            let bytes = general_purpose::STANDARD.encode(bytes);
+            move |file: &mut KeystoreFile| file.encrypted_secret_key = Some(bytes)
+        };
+
+        // 密文、文件頭中的鹽各翻轉一位，以及截斷
+        for corrupted in [
+            {
+                let mut bytes = encrypted.clone();
+                *bytes.last_mut().unwrap() ^= 1;
+                bytes
+            },
+            {
+                let mut bytes = encrypted.clone();
+                bytes[20] ^= 1;
+                bytes
+            },
+            encrypted[..HEADER_LEN - 1].to_vec(),
+        ] {
+            edit_keystore_file(&temp_dir, with_encrypted(&corrupted));
+            assert!(matches!(
+                Keystore::load_encrypted(&temp_dir, "pw"),
+                Err(AuditorError::Keystore(_))
+            ));
+        }
+
+        // 不支持的版本
+        let mut bytes = encrypted.clone();
+        bytes[4] = 99;
+        edit_keystore_file(&temp_dir, with_encrypted(&bytes));
+        match Keystore::load_encrypted(&temp_dir, "pw") {
+            Err(AuditorError::Keystore(msg)) => assert!(msg.contains("version")),
+            _ => panic!("Expected Keystore error"),
+        }
+
+        // 替換公鑰同樣無法解密
+        let mut other = Dilithium3Signer::new();
+        other.generate_keypair().unwrap();
+        edit_keystore_file(&temp_dir, |file| {
+            *file = original.clone();
+            file.public_key = general_purpose::STANDARD.encode(other.public_key());
+        });
+        assert!(matches!(
+            Keystore::load_encrypted(&temp_dir, "pw"),
+            Err(AuditorError::Keystore(_))
+        ));
+
+        fs::remove_dir_all(&temp_dir).ok();
+    }
+
+    #[test]
+    fn test_load_encrypted_accepts_plaintext_keystore() {
+        let temp_dir = create_temp_dir();
+        let keystore1 = Keystore::generate_and_save(&temp_dir, PqcAlgorithm::default()).unwrap();
+
+        let keystore2 = Keystore::load_encrypted(&temp_dir, "unused").unwrap();
+        assert_eq!(keystore1.public_key_bytes(), keystore2.public_key_bytes());
+        assert!(!is_encrypted(&temp_dir));
+
+        // 已有明文私鑰時拒絕生成加密密鑰庫
+        assert!(matches!(
+            Keystore::generate_and_save_encrypted(&temp_dir, "pw", PqcAlgorithm::default()),
+            Err(AuditorError::Keystore(_))
+        ));
+
+        fs::remove_dir_all(&temp_dir).ok();
+    }
+
+    #[test]
+    fn test_load_rejects_mismatched_key_pair() {
+        let temp_dir = create_temp_dir();
+        Keystore::generate_and_save(&temp_dir, PqcAlgorithm::default()).unwrap();
+
+        let mut other = Dilithium3Signer::new();
+        other.generate_keypair().unwrap();
+        edit_keystore_file(&temp_dir, |file| {
+            file.public_key = general_purpose::STANDARD.encode(other.public_key());
+        });
+        match Keystore::load(&temp_dir) {
+            Err(err @ AuditorError::KeyPairMismatch(_)) => {
+                assert!(err
+                    .to_string()
+                    .contains("public/secret key files do not belong together"));
+            }
+            _ => panic!("Expected KeyPairMismatch"),
+        }
+
+        // 舊格式：公鑰文件來自另一個審計員
+        let legacy_dir = create_temp_dir();
+        write_legacy_keystore(&legacy_dir, None);
+        fs::write(legacy_dir.join(PUBLIC_KEY_FILE), other.public_key()).unwrap();
+        assert!(matches!(
+            Keystore::load(&legacy_dir),
+            Err(AuditorError::KeyPairMismatch(_))
+        ));
+        assert!(!legacy_dir.join(KEYSTORE_FILE).exists());
+
+        fs::remove_dir_all(&temp_dir).ok();
+        fs::remove_dir_all(&legacy_dir).ok();
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_encrypted_secret_key_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = create_temp_dir();
+        Keystore::generate_and_save_encrypted(&temp_dir, "pw", PqcAlgorithm::default()).unwrap();
+
+        let perms = fs::metadata(temp_dir.join(KEYSTORE_FILE))
+            .unwrap()
+            .permissions();
+        assert_eq!(perms.mode() & 0o777, 0o600);
+
+        fs::remove_dir_all(&temp_dir).ok();
+    }
+
+    fn signed_report(signer: &dyn Signer) -> crate::types::AuditReport {
+        let mut report: crate::types::AuditReport = serde_json::from_value(serde_json::json!({
+            "blob_id": "blob-rotation",
+            "blob_object_id": "0xobject",
+            "auditor": "0xauditor",
+            "timestamp": 1_700_000_000u64,
+            "challenge_epoch": 7,
+            "challenge_results": [],
+            "total_challenges": 10,
+            "successful_verifications": 10,
+            "failed_verifications": 0,
+            "integrity_hash": vec![0u8; 32],
+            "pqc_signature": [],
+            "pqc_algorithm": 0,
+            "is_valid": true,
+            "failure_reason": null,
+        }))
+        .unwrap();
+        ReportManager::sign_report_with(signer, &mut report).unwrap();
+        report
+    }
+
+    #[test]
+    fn test_two_step_rotation() {
+        let temp_dir = create_temp_dir();
+        let first = Keystore::generate_and_save(&temp_dir, PqcAlgorithm::default()).unwrap();
+        let first_report = signed_report(first.signer());
+
+        let second = Keystore::rotate(&temp_dir).unwrap();
+        let third = Keystore::rotate(&temp_dir).unwrap();
+        let third_report = signed_report(third.signer());
+
+        // 舊密鑰已歸檔，當前密鑰為第三代
+        assert_eq!(
+            archived_public_key(&temp_dir, 1).unwrap(),
+            first.public_key_bytes()
+        );
+        assert!(temp_dir.join("keystore.json.2").exists());
+        let loaded = Keystore::load(&temp_dir).unwrap();
+        assert_eq!(loaded.public_key_bytes(), third.public_key_bytes());
+
+        let chain = loaded.rotation_chain().unwrap();
+        assert_eq!(
+            chain.keys(),
+            &[
+                first.public_key_bytes(),
+                second.public_key_bytes(),
+                third.public_key_bytes()
+            ]
+        );
+        assert_eq!(chain.current(), third.public_key_bytes());
+        assert_eq!(chain.rotations().len(), 2);
+        assert_eq!(chain.generation(&second.public_key_bytes()), Some(2));
+
+        // 鏈上任一密鑰簽名的報告都有效，其他密鑰不行
+        assert_eq!(
+            ReportManager::verify_report_with_chain(&first_report, &chain).unwrap(),
+            Some(1)
+        );
+        assert_eq!(
+            ReportManager::verify_report_with_chain(&third_report, &chain).unwrap(),
+            Some(3)
+        );
+        let mut outsider = Dilithium3Signer::new();
+        outsider.generate_keypair().unwrap();
+        assert_eq!(
+            ReportManager::verify_report_with_chain(&signed_report(&outsider), &chain).unwrap(),
+            None
+        );
+
+        fs::remove_dir_all(&temp_dir).ok();
+    }
+
+    #[test]
+    fn test_tampered_rotation_record_breaks_chain() {
+        let temp_dir = create_temp_dir();
+        Keystore::generate_and_save(&temp_dir, PqcAlgorithm::default()).unwrap();
+        Keystore::rotate(&temp_dir).unwrap();
+        Keystore::rotate(&temp_dir).unwrap();
+        let log_path = temp_dir.join(ROTATION_LOG_FILE);
+        let original = fs::read_to_string(&log_path).unwrap();
+
+        // 篡改時間戳：簽名不再覆蓋記錄
+        let mut log: Vec<RotationRecord> = serde_json::from_str(&original).unwrap();
+        log[0].timestamp += 1;
+        fs::write(&log_path, serde_json::to_vec(&log).unwrap()).unwrap();
+        let err = Keystore::verify_rotation_chain(&temp_dir).unwrap_err();
+        assert!(err.to_string().contains("not signed by the key it retires"));
+
+        // 斷鏈的密鑰庫不能繼續輪換
+        assert!(Keystore::rotate(&temp_dir).is_err());
+
+        // 用攻擊者的密鑰替換第二次輪換：舊公鑰與上一代不符
+        let mut attacker = Dilithium3Signer::new();
+        attacker.generate_keypair().unwrap();
+        let mut log: Vec<RotationRecord> = serde_json::from_str(&original).unwrap();
+        log[1] = RotationRecord::sign(&attacker, &log[1].new_public_key_bytes().unwrap(), 1)
+            .unwrap();
+        fs::write(&log_path, serde_json::to_vec(&log).unwrap()).unwrap();
+        assert!(matches!(
+            Keystore::verify_rotation_chain(&temp_dir),
+            Err(AuditorError::Keystore(_))
+        ));
+
+        fs::write(&log_path, original).unwrap();
+        assert_eq!(Keystore::verify_rotation_chain(&temp_dir).unwrap().keys().len(), 3);
+
+        fs::remove_dir_all(&temp_dir).ok();
+    }
+
+    #[test]
+    fn test_load_keystore_mid_chain() {
+        let temp_dir = create_temp_dir();
+        let first = Keystore::generate_and_save(&temp_dir, PqcAlgorithm::default()).unwrap();
+        let second = Keystore::rotate(&temp_dir).unwrap();
+
+        // 第一次輪換後的副本
+        let snapshot = create_temp_dir();
+        for entry in fs::read_dir(&temp_dir).unwrap() {
+            let path = entry.unwrap().path();
+            fs::copy(&path, snapshot.join(path.file_name().unwrap())).unwrap();
+        }
+
+        let third = Keystore::rotate(&temp_dir).unwrap();
+
+        let mid = Keystore::load(&snapshot).unwrap();
+        assert_eq!(mid.public_key_bytes(), second.public_key_bytes());
+        let chain = mid.rotation_chain().unwrap();
+        assert_eq!(
+            chain.keys(),
+            &[first.public_key_bytes(), second.public_key_bytes()]
+        );
+
+        // 副本的鏈是完整鏈的前綴，不認可之後輪換出的密鑰
+        let full = Keystore::verify_rotation_chain(&temp_dir).unwrap();
+        assert_eq!(&full.keys()[..2], chain.keys());
+        assert_eq!(
+            ReportManager::verify_report_with_chain(&signed_report(first.signer()), &chain)
+                .unwrap(),
+            Some(1)
+        );
+        assert_eq!(
+            ReportManager::verify_report_with_chain(&signed_report(third.signer()), &chain)
+                .unwrap(),
+            None
+        );
+
+        fs::remove_dir_all(&temp_dir).ok();
+        fs::remove_dir_all(&snapshot).ok();
+    }
+
+    /// 寫入舊格式的明文（或加密）Dilithium3 密鑰庫
+    fn write_legacy_keystore(dir: &Path, passphrase: Option<&str>) -> Dilithium3Signer {
+        let mut signer = Dilithium3Signer::new();
+        signer.generate_keypair().unwrap();
+        fs::write(dir.join(PUBLIC_KEY_FILE), signer.public_key()).unwrap();
+        match passphrase {
+            Some(passphrase) => {
+                let encrypted =
+                    encrypt_secret_key(signer.secret_key(), signer.public_key(), passphrase)
+                        .unwrap();
+                write_key_file(&dir.join(ENCRYPTED_SECRET_KEY_FILE), &encrypted, 0o600).unwrap();
+            }
+            None => write_key_file(&dir.join(SECRET_KEY_FILE), signer.secret_key(), 0o600).unwrap(),
+        }
+        signer
+    }
+
+    #[test]
+    fn test_legacy_keystore_migration() {
+        let temp_dir = create_temp_dir();
+        let legacy = write_legacy_keystore(&temp_dir, None);
+        assert!(keystore_exists(&temp_dir));
+
+        // 首次加載寫入 keystore.json，舊文件保留
+        let keystore = Keystore::load(&temp_dir).unwrap();
+        assert_eq!(keystore.public_key_bytes(), legacy.public_key());
+        assert_eq!(keystore.algorithm(), PqcAlgorithm::Dilithium3);
+        let file = KeystoreFile::read(&temp_dir.join(KEYSTORE_FILE)).unwrap();
+        assert_eq!(file.version, KEYSTORE_FORMAT_VERSION);
+        assert_eq!(file.algorithm, "Dilithium3");
+        assert_eq!(
+            decode_key(file.secret_key.as_ref().unwrap(), "").unwrap(),
+            legacy.secret_key()
+        );
+        assert!(temp_dir.join(PUBLIC_KEY_FILE).exists());
+        assert!(temp_dir.join(SECRET_KEY_FILE).exists());
+
+        // 之後從 keystore.json 加載：舊私鑰文件不再需要
+        fs::remove_file(temp_dir.join(SECRET_KEY_FILE)).unwrap();
+        let reloaded = Keystore::load(&temp_dir).unwrap();
+        let signature = reloaded.signer().sign(b"migrated").unwrap();
+        assert!(legacy.verify(b"migrated", &signature).unwrap());
+
+        // 遷移後可以繼續輪換
+        let rotated = Keystore::rotate(&temp_dir).unwrap();
+        let chain = rotated.rotation_chain().unwrap();
+        assert_eq!(
+            chain.keys(),
+            &[legacy.public_key().to_vec(), rotated.public_key_bytes()]
+        );
+
+        // 加密的舊格式密鑰庫：密文原樣遷移
+        let encrypted_dir = create_temp_dir();
+        let legacy = write_legacy_keystore(&encrypted_dir, Some("pw"));
+        assert!(is_encrypted(&encrypted_dir));
+        assert!(matches!(
+            Keystore::load(&encrypted_dir),
+            Err(AuditorError::Keystore(_))
+        ));
+        let keystore = Keystore::load_encrypted(&encrypted_dir, "pw").unwrap();
+        assert_eq!(keystore.public_key_bytes(), legacy.public_key());
+        let file = KeystoreFile::read(&encrypted_dir.join(KEYSTORE_FILE)).unwrap();
+        assert!(file.is_encrypted());
+        assert_eq!(
+            decode_key(file.encrypted_secret_key.as_ref().unwrap(), "").unwrap(),
+            fs::read(encrypted_dir.join(ENCRYPTED_SECRET_KEY_FILE)).unwrap()
+        );
+        assert!(is_encrypted(&encrypted_dir));
+        assert!(Keystore::load_encrypted(&encrypted_dir, "pw").is_ok());
+
+        fs::remove_dir_all(&temp_dir).ok();
+        fs::remove_dir_all(&encrypted_dir).ok();
+    }
+
+    #[test]
+    fn test_falcon_keystore_roundtrip() {
+        let temp_dir = create_temp_dir();
+        let keystore = Keystore::generate_and_save(&temp_dir, PqcAlgorithm::Falcon512).unwrap();
+        assert_eq!(keystore.algorithm(), PqcAlgorithm::Falcon512);
+        assert_eq!(keystore.public_key_bytes().len(), 897);
+        assert_eq!(keystore.signer().algorithm_name(), "Falcon512");
+
+        let loaded = Keystore::load(&temp_dir).unwrap();
+        assert_eq!(loaded.algorithm(), PqcAlgorithm::Falcon512);
+        assert_eq!(loaded.public_key_bytes(), keystore.public_key_bytes());
+        let signature = loaded.signer().sign(b"falcon").unwrap();
+        assert!(keystore.signer().verify(b"falcon", &signature).unwrap());
+
+        // 報告記錄 Falcon512 算法編號，並能用密鑰庫公鑰驗證
+        let report = signed_report(loaded.signer());
+        assert_eq!(report.pqc_algorithm, PqcAlgorithm::Falcon512.id());
+        assert!(ReportManager::verify_report(&report, &loaded.public_key_bytes()).unwrap());
+
+        // 輪換保持算法，輪換記錄用 Falcon512 驗證
+        let rotated = Keystore::rotate(&temp_dir).unwrap();
+        assert_eq!(rotated.algorithm(), PqcAlgorithm::Falcon512);
+        let chain = rotated.rotation_chain().unwrap();
+        assert_eq!(chain.generation(&keystore.public_key_bytes()), Some(1));
+        assert_eq!(chain.rotations()[0].algorithm.as_deref(), Some("Falcon512"));
+
+        // 加密的 Falcon512 密鑰庫
+        let encrypted_dir = create_temp_dir();
+        let encrypted =
+            Keystore::generate_and_save_encrypted(&encrypted_dir, "pw", PqcAlgorithm::Falcon512)
+                .unwrap();
+        let loaded = Keystore::load_encrypted(&encrypted_dir, "pw").unwrap();
+        assert_eq!(loaded.algorithm(), PqcAlgorithm::Falcon512);
+        assert_eq!(loaded.public_key_bytes(), encrypted.public_key_bytes());
+
+        fs::remove_dir_all(&temp_dir).ok();
+        fs::remove_dir_all(&encrypted_dir).ok();
+    }
+
+    #[test]
+    fn test_unknown_keystore_algorithm_is_rejected() {
+        let temp_dir = create_temp_dir();
+        Keystore::generate_and_save(&temp_dir, PqcAlgorithm::default()).unwrap();
+
+        edit_keystore_file(&temp_dir, |file| file.algorithm = "SphincsPlus".to_string());
+        match Keystore::load(&temp_dir) {
+            Err(AuditorError::Keystore(msg)) => {
+                assert!(msg.contains("Unsupported keystore algorithm: SphincsPlus"))
+            }
+            _ => panic!("Expected Keystore error"),
+        }
+
+        // 更高的格式版本同樣拒絕
+        edit_keystore_file(&temp_dir, |file| {
+            file.algorithm = "Dilithium3".to_string();
+            file.version = KEYSTORE_FORMAT_VERSION + 1;
+        });
+        match Keystore::load(&temp_dir) {
+            Err(AuditorError::Keystore(msg)) => assert!(msg.contains("format version")),
+            _ => panic!("Expected Keystore error"),
+        }
+
+        // 算法與密鑰長度不符
+        edit_keystore_file(&temp_dir, |file| {
+            file.version = KEYSTORE_FORMAT_VERSION;
+            file.algorithm = "Falcon512".to_string();
+        });
+        assert!(matches!(
+            Keystore::load(&temp_dir),
+            Err(AuditorError::PqcSignature(_))
+        ));
+
+        fs::remove_dir_all(&temp_dir).ok();
+    }
+    #[test]
+    fn test_create_refuses_to_overwrite_without_force() {
+        let temp_dir = create_temp_dir();
+
+        let first = Keystore::create(&temp_dir, PqcAlgorithm::Falcon512, None, false).unwrap();
+        assert_eq!(first.algorithm(), PqcAlgorithm::Falcon512);
+
+        match Keystore::create(&temp_dir, PqcAlgorithm::Dilithium3, None, false) {
+            Err(AuditorError::Keystore(msg)) => assert!(msg.contains("--force")),
+            _ => panic!("Expected Keystore error"),
+        }
+        // 拒絕後原密鑰不變
+        let loaded = Keystore::load(&temp_dir).unwrap();
+        assert_eq!(loaded.public_key_bytes(), first.public_key_bytes());
+
+        // --force 覆蓋為新的加密密鑰庫
+        let second =
+            Keystore::create(&temp_dir, PqcAlgorithm::Dilithium3, Some("pw"), true).unwrap();
+        assert_ne!(second.public_key_bytes(), first.public_key_bytes());
+        assert!(is_encrypted(&temp_dir));
+        let loaded = Keystore::load_encrypted(&temp_dir, "pw").unwrap();
+        assert_eq!(loaded.public_key_bytes(), second.public_key_bytes());
+
+        // 有輪換記錄時即使 --force 也拒絕
+        Keystore::create(&temp_dir, PqcAlgorithm::Dilithium3, None, true).unwrap();
+        Keystore::rotate(&temp_dir).unwrap();
+        assert!(Keystore::create(&temp_dir, PqcAlgorithm::Dilithium3, None, true).is_err());
+
+        fs::remove_dir_all(&temp_dir).ok();
+    }
+
+    #[test]
+    fn test_public_key_fingerprint_is_stable() {
+        // SHA-256("")
+        assert_eq!(
+            key_fingerprint(b""),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+
+        let temp_dir = create_temp_dir();
+        let keystore =
+            Keystore::create(&temp_dir, PqcAlgorithm::Dilithium3, Some("pw"), false).unwrap();
+
+        // 加密密鑰庫無需口令即可讀取公開信息，重複讀取結果一致
+        let info = public_key_info(&temp_dir).unwrap();
+        assert_eq!(info.algorithm, PqcAlgorithm::Dilithium3);
+        assert_eq!(info.public_key, keystore.public_key_bytes());
+        assert_eq!(
+            info.fingerprint,
+            key_fingerprint(&keystore.public_key_bytes())
+        );
+        assert_eq!(info.fingerprint.len(), 64);
+        assert!(info.created_at.is_some());
+        assert_eq!(public_key_info(&temp_dir).unwrap(), info);
+
+        fs::remove_dir_all(&temp_dir).ok();
+    }
+
+    #[test]
+    fn test_public_key_info_of_legacy_keystore() {
+        let temp_dir = create_temp_dir();
+        let signer = write_legacy_keystore(&temp_dir, None);
+
+        let info = public_key_info(&temp_dir).unwrap();
+        assert_eq!(info.public_key, signer.public_key());
+        assert_eq!(info.created_at, None);
+        // 只讀取，不遷移
+        assert!(!temp_dir.join(KEYSTORE_FILE).exists());
+
+        // 遷移後指紋不變
+        Keystore::load(&temp_dir).unwrap();
+        assert_eq!(
+            public_key_info(&temp_dir).unwrap().fingerprint,
+            info.fingerprint
+        );
+
+        fs::remove_dir_all(&temp_dir).ok();
+    }
 }
@@ -23,35 +23,106 @@
 //! # Example Usage
 //!
 //! ```no_run
-//! use auditor_node::{Auditor, config::load_config};
+//! use auditor_node::{Auditor, config::load_config, types::BlobRef};
 //!
 //! #[tokio::main]
 //! async fn main() -> Result<(), Box<dyn std::error::Error>> {
 //!     let config = load_config("config.toml")?;
-//!     let auditor = Auditor::new()?;
+//!     // No Sui connection is made until the first chain-dependent call
+//!     let auditor = Auditor::new(config, "0xauditor".to_string(), vec![])?;
 //!
-//!     let report = auditor.audit_blob("blob_id_here").await?;
+//!     // The blob object on Sui; BlobRef::BlobId looks the object up first
+//!     let blob = BlobRef::ObjectId("0x5c4e3a1f0b2d4e6f8a9b7c6d5e4f3a2b".to_string());
+//!     let report = auditor.audit_blob(&blob).await?;
 //!     println!("Audit result: {}", report.is_valid);
 //!
 //!     Ok(())
 //! }
 //! ```
+//!
+//! The full audit → sign → encrypt → upload → submit flow used by the CLI is available as
+//! [`pipeline::AuditPipeline`]:
+//!
+//! ```no_run
+//! use auditor_node::{config::load_config, keystore::Keystore, pipeline::AuditPipeline};
+//! use std::path::Path;
+//!
+//! #[tokio::main]
+//! async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//!     let config = load_config("config.toml")?;
+//!     let keystore = Keystore::open(Path::new(&config.pqc_keystore_path))?;
+//!     let pipeline = AuditPipeline::from_config(&config, &keystore)?;
+//!
+//!     let outcome = pipeline.run_once("blob_id_here").await?;
+//!     println!("Signed: {}, stored as {:?}", outcome.report.is_valid, outcome.report_blob_id);
+//!
+//!     Ok(())
+//! }
+//! ```
 
 // Public modules
+pub mod archive; // Parallel batch verification of report archives
 pub mod audit_report; // PQC-signed audit reports
 pub mod auditor;
+pub mod baseline; // Per-blob content baselines (hash drift across runs)
+pub mod batch; // Multi-blob CLI audits with a combined JSON summary
+pub mod blinding; // HMAC blinding of blob IDs in published reports
+pub mod blob_id; // Re-encoding check of downloaded blobs against their blob_id
+pub mod blob_lookup; // On-chain Blob object state (deleted vs unreachable)
+pub mod breaker; // Per-endpoint circuit breaker for aggregator/node calls
+pub mod capture; // HTTP request/response capture for node disputes
+pub mod chain_types; // Typed mirrors of Move entry function params and events
+pub mod chain_verify; // Keyless report check against on-chain AuditRecords
+pub mod challenge_seed; // Challenge seeds derived from the latest Sui checkpoint
+pub mod checkpoint; // Versioned checkpoints for resumable integrity audits
+pub mod chunk_filter; // Bloom filter of leaf hashes for quick compares
+pub mod commitment; // Commit-reveal of challenge sets before auditing
 pub mod config;
+pub mod content_cache; // ETag-revalidated cache of downloaded blob hashes
+pub mod cosign; // Multi-auditor co-signing of reports
 pub mod crypto;
+pub mod dry_run; // Record report uploads and chain submissions instead of sending them
 pub mod error;
+pub mod history; // Audit history and cross-blob content dedup
+pub mod http; // Shared HTTP client with connect/read timeouts and pooling
+pub mod ingest; // Hardened parsing of untrusted report JSON
+pub mod init; // First-run setup wizard and preflight checks
 pub mod integrity; // Application-layer integrity verification
 pub mod keystore; // PQC keystore
+pub mod local_encryption; // Local AES-256-GCM report encryption when Seal is unavailable
+pub mod logging; // JSON log format and per-audit correlation IDs
+pub mod metadata_cache; // TTL cache of on-chain blob metadata shared by the daemon and auditor
+pub mod metrics; // Prometheus metrics facade for daemon mode
+pub mod node_error; // Sanitized storage node error bodies for reports
+pub mod node_health; // Background storage node health checks for challenge routing
+pub mod notify; // Webhook notifications for audit results and node health
+pub mod onchain_keys; // On-chain auditor public keys (AuditorRegistry) with a TTL cache
+pub mod pending; // Per-epoch dedup of blobs queued by the daemon
+pub mod pipeline; // audit → sign → encrypt → upload → submit
+pub mod preflight; // Auditor registration and reputation check before audits
+pub mod producer; // Build metadata recorded in signed reports
+pub mod progress; // Non-blocking progress events for long-running audits
+pub mod rate_limit; // Per-host token bucket for outbound requests
+pub mod reaudit; // Backoff re-audits of failed blobs
 pub mod report;
+pub mod report_archive; // Dated report archive with index, compression and retention
+pub mod report_bundle; // Offline .warb bundle: signed report + key + rotation chain
+pub mod resources; // Disk/memory guard before large audits
 pub mod retry; // Network retry with exponential backoff
+pub mod rotating_writer; // Size/age rotation for append-only JSONL outputs
+pub mod scheduler; // Per-blob audit times with jitter and failure backoff (daemon)
 pub mod seal_client;
+pub mod shutdown; // Daemon shutdown: drain in-flight audits within a grace period
 pub mod storage_node_client;
 pub mod sui_client;
+pub mod trust; // Auditor key registry with TOFU pinning
 // pub mod sui_keystore; // Sui keystore integration (暫時禁用,sign_secure 用於交易簽名,不適合審計報告)
 pub mod types;
+pub mod verdict; // VALID/INVALID verdict of the verify subcommand
+
+// In-process fakes for end-to-end tests (also exported to downstream crates)
+#[cfg(any(test, feature = "test-util"))]
+pub mod test_support;
 
 // Re-export commonly used types
 pub use auditor::Auditor;
@@ -0,0 +1,368 @@
+//! 本地報告加密（Seal 不可用時的後備）
+//!
+//! 啟用 Seal 加密時，Seal API 宕機會讓整次審計在加密階段失敗，已簽名的報告隨之丟失。
+//! 配置 `encryption_fallback = "local-aes"` 後，Seal 健康檢查或加密請求失敗時改由
+//! [`LocalAesEncryptor`] 以新生成的 AES-256-GCM 密鑰加密報告，密文照常上傳到 Walrus。
+//!
+//! 上傳的密文自帶密鑰 ID，讀取方據此區分 Seal 密文與本地密文（見 [`is_local_ciphertext`]）：
+//!
+//! ```text
+//! "WALAES01" (8) || key_id (16) || nonce (12) || AES-256-GCM(report_json)
+//! ```
+//!
+//! 文件頭（前 36 字節）作為 AEAD 附加數據，密鑰 ID 被篡改時解密失敗。
+//!
+//! 每個密鑰在密文返回之前寫入 `<pqc_keystore_path>/fallback_keys/<key_id>.json`
+//! （權限 `0o600`，見 [`FallbackKey`]），同時記錄密文摘要與本應使用的 Seal identity、
+//! Package ID 和門檻值，Seal 恢復後可據此解密並重新以 Seal 加密遷移。
+//! 密鑰以明文保存在審計員主機上，保護強度等同密鑰庫目錄本身。
+
+use crate::error::{AuditorError, Result};
+use crate::keystore::write_key_file;
+use crate::seal_client::{EncryptMetadata, EncryptionScheme};
+use aes_gcm::aead::{Aead, KeyInit, Payload};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use base64::{engine::general_purpose, Engine as _};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+use tracing::info;
+use zeroize::Zeroizing;
+
+/// 本地密文的文件頭魔數
+pub const LOCAL_AES_MAGIC: &[u8; 8] = b"WALAES01";
+
+/// 密鑰庫目錄下保存後備密鑰的子目錄
+pub const FALLBACK_KEY_DIR: &str = "fallback_keys";
+
+/// 密鑰 ID 長度（bytes）
+const KEY_ID_LEN: usize = 16;
+
+/// AES-GCM nonce 長度（bytes）
+const NONCE_LEN: usize = 12;
+
+/// 文件頭長度（魔數、密鑰 ID 與 nonce）
+const HEADER_LEN: usize = LOCAL_AES_MAGIC.len() + KEY_ID_LEN + NONCE_LEN;
+
+/// Seal 加密失敗時的處理方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum EncryptionFallback {
+    /// 不降級，審計在加密階段失敗
+    #[default]
+    None,
+    /// 以本地 AES-256-GCM 密鑰加密
+    LocalAes,
+}
+
+/// 本地加密密鑰記錄（`fallback_keys/<key_id>.json`）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FallbackKey {
+    /// 密鑰 ID（十六進制，與密文文件頭相同）
+    pub key_id: String,
+
+    /// 加密方式
+    pub scheme: EncryptionScheme,
+
+    /// AES-256 密鑰（Base64）
+    pub key: String,
+
+    /// 生成時間（Unix 秒）
+    pub created_at: u64,
+
+    /// 完整密文（含文件頭）的 SHA-256，用於遷移時找到對應的 Walrus Blob
+    pub ciphertext_sha256: String,
+
+    /// 明文大小（bytes）
+    pub original_size: usize,
+
+    /// 遷移到 Seal 時使用的 IBE identity（審計員地址）
+    pub seal_identity: String,
+
+    /// 遷移到 Seal 時使用的審計合約 Package ID
+    pub seal_package_id: String,
+
+    /// 遷移到 Seal 時使用的門檻值
+    pub seal_threshold: u32,
+}
+
+impl FallbackKey {
+    /// 從 `key_dir` 讀取密鑰記錄
+    pub fn load(key_dir: &Path, key_id: &str) -> Result<Self> {
+        let path = key_path(key_dir, key_id)?;
+        let bytes = fs::read(&path).map_err(|e| {
+            AuditorError::LocalEncryption(format!("Failed to read fallback key {:?}: {}", path, e))
+        })?;
+        let record: Self = serde_json::from_slice(&bytes)?;
+        if record.key_id != key_id {
+            return Err(AuditorError::LocalEncryption(format!(
+                "Fallback key file {:?} holds key {}",
+                path, record.key_id
+            )));
+        }
+        Ok(record)
+    }
+
+    /// 解碼後的 AES-256 密鑰
+    pub fn key_bytes(&self) -> Result<Zeroizing<[u8; 32]>> {
+        let decoded = Zeroizing::new(general_purpose::STANDARD.decode(&self.key).map_err(|e| {
+            AuditorError::LocalEncryption(format!("Invalid fallback key encoding: {}", e))
+        })?);
+        let key: [u8; 32] = decoded.as_slice().try_into().map_err(|_| {
+            AuditorError::LocalEncryption(format!(
+                "Fallback key must be 32 bytes, got {}",
+                decoded.len()
+            ))
+        })?;
+        Ok(Zeroizing::new(key))
+    }
+}
+
+/// 以一次性 AES-256-GCM 密鑰加密報告，並把密鑰保存到密鑰庫目錄
+#[derive(Debug, Clone)]
+pub struct LocalAesEncryptor {
+    key_dir: PathBuf,
+    identity: String,
+    package_id: String,
+    threshold: u32,
+}
+
+impl LocalAesEncryptor {
+    /// 創建加密器
+    ///
+    /// # 參數
+    /// - `key_dir`: 密鑰保存目錄（通常為 `<pqc_keystore_path>/fallback_keys`，不存在時創建）
+    /// - `identity`、`package_id`、`threshold`: 本應使用的 Seal 參數，記錄在密鑰中供遷移
+    pub fn new(
+        key_dir: impl Into<PathBuf>,
+        identity: impl Into<String>,
+        package_id: impl Into<String>,
+        threshold: u32,
+    ) -> Self {
+        Self {
+            key_dir: key_dir.into(),
+            identity: identity.into(),
+            package_id: package_id.into(),
+            threshold,
+        }
+    }
+
+    /// 密鑰保存目錄
+    pub fn key_dir(&self) -> &Path {
+        &self.key_dir
+    }
+
+    /// 加密報告，返回密文（格式見模塊文檔）與加密元數據
+    ///
+    /// 密鑰寫入成功後才返回密文，上傳的密文總有對應的密鑰
+    pub fn encrypt(&self, plaintext: &[u8]) -> Result<(Vec<u8>, EncryptMetadata)> {
+        let start = Instant::now();
+        let mut key = Zeroizing::new([0u8; 32]);
+        let mut key_id = [0u8; KEY_ID_LEN];
+        let mut nonce = [0u8; NONCE_LEN];
+        rand::thread_rng().fill_bytes(key.as_mut());
+        rand::thread_rng().fill_bytes(&mut key_id);
+        rand::thread_rng().fill_bytes(&mut nonce);
+
+        let mut data = Vec::with_capacity(HEADER_LEN + plaintext.len() + 16);
+        data.extend_from_slice(LOCAL_AES_MAGIC);
+        data.extend_from_slice(&key_id);
+        data.extend_from_slice(&nonce);
+
+        let ciphertext = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key.as_ref()))
+            .encrypt(
+                Nonce::from_slice(&nonce),
+                Payload {
+                    msg: plaintext,
+                    aad: &data,
+                },
+            )
+            .map_err(|_| AuditorError::LocalEncryption("Failed to encrypt report".to_string()))?;
+        data.extend_from_slice(&ciphertext);
+
+        let key_id = hex::encode(key_id);
+        let encrypted_at = chrono::Utc::now().timestamp() as u64;
+        let record = FallbackKey {
+            key_id: key_id.clone(),
+            scheme: EncryptionScheme::LocalAes256Gcm,
+            key: general_purpose::STANDARD.encode(key.as_ref()),
+            created_at: encrypted_at,
+            ciphertext_sha256: hex::encode(Sha256::digest(&data)),
+            original_size: plaintext.len(),
+            seal_identity: self.identity.clone(),
+            seal_package_id: self.package_id.clone(),
+            seal_threshold: self.threshold,
+        };
+        self.save(&record)?;
+        info!(
+            "Report encrypted locally with AES-256-GCM (key {}, {} bytes)",
+            key_id,
+            data.len()
+        );
+
+        let metadata = EncryptMetadata {
+            identity: self.identity.clone(),
+            package_id: self.package_id.clone(),
+            threshold: self.threshold,
+            encrypted_at,
+            original_size: plaintext.len(),
+            encrypted_size: data.len(),
+            duration: start.elapsed().as_millis() as u64,
+            scheme: EncryptionScheme::LocalAes256Gcm,
+            key_id: Some(key_id),
+        };
+        Ok((data, metadata))
+    }
+
+    /// 寫入密鑰記錄（權限 `0o600`）
+    fn save(&self, record: &FallbackKey) -> Result<()> {
+        fs::create_dir_all(&self.key_dir)?;
+        let json = Zeroizing::new(serde_json::to_vec_pretty(record)?);
+        write_key_file(&key_path(&self.key_dir, &record.key_id)?, &json, 0o600)
+    }
+}
+
+/// 是否為本地加密的密文（以 [`LOCAL_AES_MAGIC`] 開頭）
+pub fn is_local_ciphertext(data: &[u8]) -> bool {
+    data.starts_with(LOCAL_AES_MAGIC)
+}
+
+/// 本地密文的密鑰 ID
+pub fn ciphertext_key_id(data: &[u8]) -> Result<String> {
+    if !is_local_ciphertext(data) || data.len() < HEADER_LEN {
+        return Err(AuditorError::LocalEncryption(
+            "Not a locally encrypted report (bad magic or truncated header)".to_string(),
+        ));
+    }
+    let start = LOCAL_AES_MAGIC.len();
+    Ok(hex::encode(&data[start..start + KEY_ID_LEN]))
+}
+
+/// 以 `key_dir` 中保存的密鑰解密本地密文，返回報告 JSON 的原始字節
+pub fn decrypt(key_dir: &Path, data: &[u8]) -> Result<Zeroizing<Vec<u8>>> {
+    let key_id = ciphertext_key_id(data)?;
+    let record = FallbackKey::load(key_dir, &key_id)?;
+    if record.scheme != EncryptionScheme::LocalAes256Gcm {
+        return Err(AuditorError::LocalEncryption(format!(
+            "Fallback key {} is not an AES-256-GCM key",
+            key_id
+        )));
+    }
+    let key = record.key_bytes()?;
+
+    let (header, ciphertext) = data.split_at(HEADER_LEN);
+    let nonce = &header[LOCAL_AES_MAGIC.len() + KEY_ID_LEN..];
+    Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key.as_ref()))
+        .decrypt(
+            Nonce::from_slice(nonce),
+            Payload {
+                msg: ciphertext,
+                aad: header,
+            },
+        )
+        .map(Zeroizing::new)
+        .map_err(|_| {
+            AuditorError::LocalEncryption(format!(
+                "Failed to decrypt report with key {}: ciphertext or key corrupted",
+                key_id
+            ))
+        })
+}
+
+/// 密鑰記錄路徑（只接受十六進制密鑰 ID，避免路徑穿越）
+fn key_path(key_dir: &Path, key_id: &str) -> Result<PathBuf> {
+    if key_id.len() != KEY_ID_LEN * 2 || !key_id.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return Err(AuditorError::LocalEncryption(format!(
+            "Invalid fallback key ID: {}",
+            key_id
+        )));
+    }
+    Ok(key_dir.join(format!("{}.json", key_id)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const IDENTITY: &str = "0x1234567890abcdef1234567890abcdef1234567890abcdef1234567890abcdef";
+    const PACKAGE: &str = "0x8afa5d31dbaa0a8fb07082692940ca3d56b5e856c5126cb5a3693f0a4de63b82";
+    const REPORT: &[u8] = b"{\"blob_id\":\"eRrTusk8\",  \"is_valid\":true}";
+
+    fn encryptor(dir: &Path) -> LocalAesEncryptor {
+        LocalAesEncryptor::new(dir.join(FALLBACK_KEY_DIR), IDENTITY, PACKAGE, 2)
+    }
+
+    #[test]
+    fn test_local_encryption_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let encryptor = encryptor(dir.path());
+
+        let (data, metadata) = encryptor.encrypt(REPORT).unwrap();
+        assert!(is_local_ciphertext(&data));
+        assert_eq!(metadata.scheme, EncryptionScheme::LocalAes256Gcm);
+        assert_eq!(
+            metadata.key_id.as_deref(),
+            Some(ciphertext_key_id(&data).unwrap().as_str())
+        );
+        assert_eq!(metadata.encrypted_size, data.len());
+
+        let plaintext = decrypt(encryptor.key_dir(), &data).unwrap();
+        assert_eq!(plaintext.as_slice(), REPORT);
+
+        // 密鑰記錄帶有遷移到 Seal 所需的參數
+        let record =
+            FallbackKey::load(encryptor.key_dir(), metadata.key_id.as_ref().unwrap()).unwrap();
+        assert_eq!(record.seal_identity, IDENTITY);
+        assert_eq!(record.seal_package_id, PACKAGE);
+        assert_eq!(record.seal_threshold, 2);
+        assert_eq!(record.ciphertext_sha256, hex::encode(Sha256::digest(&data)));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_fallback_key_is_owner_only() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let encryptor = encryptor(dir.path());
+        let (_, metadata) = encryptor.encrypt(REPORT).unwrap();
+
+        let path = key_path(encryptor.key_dir(), metadata.key_id.as_ref().unwrap()).unwrap();
+        let mode = fs::metadata(path).unwrap().permissions().mode() & 0o777;
+        assert_eq!(mode, 0o600);
+    }
+
+    #[test]
+    fn test_tampered_ciphertext_is_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let encryptor = encryptor(dir.path());
+        let (data, _) = encryptor.encrypt(REPORT).unwrap();
+
+        let mut tampered = data.clone();
+        *tampered.last_mut().unwrap() ^= 0x01;
+        assert!(decrypt(encryptor.key_dir(), &tampered).is_err());
+
+        // 另一個密鑰的密文找不到對應的密鑰記錄
+        let mut other_key = data.clone();
+        other_key[LOCAL_AES_MAGIC.len()] ^= 0x01;
+        assert!(decrypt(encryptor.key_dir(), &other_key).is_err());
+
+        assert!(decrypt(encryptor.key_dir(), b"not a local ciphertext").is_err());
+    }
+
+    #[test]
+    fn test_key_ids_cannot_escape_key_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(FallbackKey::load(dir.path(), "../keystore").is_err());
+    }
+
+    #[test]
+    fn test_fallback_mode_names() {
+        let mode: EncryptionFallback = serde_json::from_str("\"local-aes\"").unwrap();
+        assert_eq!(mode, EncryptionFallback::LocalAes);
+        assert_eq!(EncryptionFallback::default(), EncryptionFallback::None);
+    }
+}
@@ -0,0 +1,65 @@
+//! 日誌格式與審計關聯 ID
+//!
+//! 大量審計節點的日誌匯總到集中式系統時，自由文本日誌無法檢索。本模塊提供：
+//!
+//! - [`LogFormat`]：`text`（默認，人類可讀）或 `json`（每行一個 JSON 對象），
+//!   由 `--log-format` 或配置 `log_format` 選擇
+//! - [`new_audit_id`]：每次審計（單次或守護進程觸發）生成的 UUID v4 關聯 ID
+//! - [`audit_span`]：攜帶 `audit_id` 與 `blob_id` 字段的 tracing span；
+//!   審計流水線各階段（下載 / 挑戰 → 簽名 → 加密 → 上傳 → 提交）都在其中執行，
+//!   因此每行日誌都帶有這兩個字段
+//!
+//! 同一 `audit_id` 寫入報告的 `audit_id` 字段，報告可據此與日誌對應。
+
+use serde::{Deserialize, Serialize};
+use tracing::Span;
+
+/// 日誌輸出格式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    /// 人類可讀的文本
+    #[default]
+    Text,
+
+    /// 每行一個 JSON 對象（包含所在 span 的字段）
+    Json,
+}
+
+/// 生成新的審計關聯 ID（UUID v4）
+pub fn new_audit_id() -> String {
+    uuid::Uuid::new_v4().to_string()
+}
+
+/// 審計的根 span：其中記錄的每行日誌都帶有 `audit_id` 與 `blob_id`
+pub fn audit_span(audit_id: &str, blob_id: &str) -> Span {
+    tracing::info_span!("audit", audit_id = %audit_id, blob_id = %blob_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_audit_ids_are_unique_uuids() {
+        let first = new_audit_id();
+        let second = new_audit_id();
+
+        assert_ne!(first, second);
+        let parsed = uuid::Uuid::parse_str(&first).unwrap();
+        assert_eq!(parsed.get_version_num(), 4);
+    }
+
+    #[test]
+    fn test_log_format_from_config_value() {
+        #[derive(Deserialize)]
+        struct Wrapper {
+            log_format: LogFormat,
+        }
+
+        let parsed: Wrapper = toml::from_str("log_format = \"json\"").unwrap();
+        assert_eq!(parsed.log_format, LogFormat::Json);
+        assert_eq!(LogFormat::default(), LogFormat::Text);
+        assert!(toml::from_str::<Wrapper>("log_format = \"xml\"").is_err());
+    }
+}
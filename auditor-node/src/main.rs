@@ -8,40 +8,130 @@
 //! 5. Upload encrypted report to Walrus
 //! 6. Set access policy on Sui
 
+mod archive;
+mod audit_report;
 mod auditor;
+mod baseline;
+mod batch;
+mod blinding;
+mod blob_id;
+mod blob_lookup;
+mod breaker;
+mod capture;
+mod chain_types;
+mod chain_verify;
+mod challenge_seed;
+mod checkpoint;
+mod chunk_filter;
+mod commitment;
 mod config;
+mod content_cache;
+mod cosign;
 mod crypto;
+mod dry_run;
 mod error;
+mod history;
+mod http;
+mod ingest;
+mod init;
 mod integrity;
 mod keystore;
+mod local_encryption;
+mod logging;
+mod metadata_cache;
+mod metrics;
+mod node_error;
+mod node_health;
+mod notify;
+mod onchain_keys;
+mod pending;
+mod pipeline;
+mod preflight;
+mod producer;
+mod progress;
+mod rate_limit;
+mod reaudit;
 mod report;
+mod report_archive;
+mod report_bundle;
+mod resources;
+mod retry;
+mod rotating_writer;
+mod scheduler;
 mod seal_client;
+mod shutdown;
 mod storage_node_client;
 mod sui_client;
+#[cfg(test)]
+mod test_support;
+mod trust;
 mod types;
+mod verdict;
 
 use anyhow::{Context, Result};
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tokio::signal;
-use tracing::{debug, error, info, warn};
+use tracing::{error, info, warn};
 use tracing_subscriber;
 
 use crate::types::AuditorConfig;
 use pqc_signer::Signer; // Import Signer trait to use sign() method
 
+/// Configuration file used when `--config` is not given
+const DEFAULT_CONFIG_PATH: &str = "config.toml";
+
 /// Walrus Decentralized Storage Integrity Auditor Node
 #[derive(Parser, Debug)]
-#[command(author, version, about, long_about = None)]
+#[command(author, version, about, long_about = None, disable_version_flag = true)]
 struct Args {
-    /// Configuration file path
-    #[arg(short, long, default_value = "config.toml")]
-    config: PathBuf,
+    /// Print version and build information
+    #[arg(short = 'V', long)]
+    version: bool,
+
+    /// Output format for --version
+    #[arg(long, value_enum, default_value_t = OutputFormat::Text, requires = "version")]
+    output: OutputFormat,
+
+    /// Configuration file path [default: config.toml, skipped if it does not exist]
+    #[arg(short, long)]
+    config: Option<PathBuf>,
 
-    /// Blob ID to audit (optional, for single audit)
+    /// Blob ID to audit (optional, for single audit; URL-safe base64 or 0x hex,
+    /// or the blob object ID when challenging storage nodes)
+    ///
+    /// Repeat to audit several blobs in one run.
     #[arg(short, long)]
-    blob_id: Option<String>,
+    blob_id: Vec<String>,
+
+    /// Sui object ID (0x...) of the blob to audit, as shown by Sui explorers
+    ///
+    /// The blob ID is read from the object; the report records both identifiers.
+    #[arg(long, value_name = "ID", conflicts_with_all = ["blob_id", "blob_file", "summary_output", "daemon"])]
+    blob_object_id: Option<String>,
+
+    /// File with one blob ID per line to audit (`-` reads stdin; blank lines and `#` comments are skipped)
+    #[arg(long, value_name = "PATH")]
+    blob_file: Option<PathBuf>,
+
+    /// Number of blobs audited at the same time in a multi-blob run
+    #[arg(long, default_value_t = batch::DEFAULT_BATCH_CONCURRENCY)]
+    concurrency: usize,
+
+    /// Stop starting new audits after the first failed blob
+    #[arg(long, default_value_t = false)]
+    fail_fast: bool,
+
+    /// Directory for the signed report of each blob in a multi-blob run
+    #[arg(long, value_name = "DIR")]
+    report_dir: Option<PathBuf>,
+
+    /// Write the multi-blob JSON summary to this file instead of stdout
+    ///
+    /// (`--output` selects the format of `--version`.)
+    #[arg(long, value_name = "PATH")]
+    summary_output: Option<PathBuf>,
 
     /// Run in daemon mode (periodic audits)
     #[arg(short, long, default_value_t = false)]
@@ -51,6 +141,10 @@ struct Args {
     #[arg(long, default_value = "info")]
     log_level: String,
 
+    /// Log output format (overrides `log_format` in the config file)
+    #[arg(long, value_enum)]
+    log_format: Option<logging::LogFormat>,
+
     /// Seal API endpoint (overrides config file)
     #[arg(long)]
     seal_api: Option<String>,
@@ -62,27 +156,483 @@ struct Args {
     /// Audit contract Package ID (overrides config file)
     #[arg(long)]
     package_id: Option<String>,
+
+    /// Capture HTTP requests/responses of this audit as dispute evidence
+    #[arg(long, default_value_t = false)]
+    capture_http: bool,
+
+    /// Audit and sign as usual, but write the report upload and chain submission to
+    /// `dry_run_dir` instead of sending them; exits 1 if the report is invalid
+    #[arg(long, default_value_t = false)]
+    dry_run: bool,
+
+    /// Audit even if the auditor address is not registered in the AuditorRegistry
+    /// (local testing only: on-chain submissions from an unregistered auditor fail)
+    #[arg(long, default_value_t = false)]
+    allow_unregistered: bool,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+/// Output format for machine-readable modes
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+enum OutputFormat {
+    Text,
+    Json,
+}
+
+/// PQC key algorithm for `keygen`
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+enum KeyAlgorithm {
+    Dilithium3,
+    Falcon512,
+}
+
+impl From<KeyAlgorithm> for audit_report::PqcAlgorithm {
+    fn from(algorithm: KeyAlgorithm) -> Self {
+        match algorithm {
+            KeyAlgorithm::Dilithium3 => Self::Dilithium3,
+            KeyAlgorithm::Falcon512 => Self::Falcon512,
+        }
+    }
+}
+
+/// Public key encoding for `show-key`
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+enum KeyFormat {
+    Hex,
+    Base64,
+    Json,
+}
+
+/// Auxiliary subcommands
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Interactive first-run setup: writes config.toml and the keystore, then runs preflight
+    Init {
+        #[command(flatten)]
+        options: InitArgs,
+
+        /// Never prompt; use defaults and fail on answers without one
+        #[arg(long, default_value_t = false)]
+        non_interactive: bool,
+
+        /// Skip connectivity checks after writing the config
+        #[arg(long, default_value_t = false)]
+        skip_preflight: bool,
+    },
+
+    /// HTTP capture utilities
+    Capture {
+        #[command(subcommand)]
+        action: CaptureCommand,
+    },
+
+    /// Verify a signed audit report
+    ///
+    /// Exits 0 when the report is VALID, 1 when it is INVALID and 2 on errors.
+    Verify {
+        /// Signed report JSON (legacy SignedAuditReport envelopes are detected automatically)
+        #[arg(long = "report", value_name = "PATH", required_unless_present = "report_path")]
+        report: Option<PathBuf>,
+
+        /// Positional form of --report
+        #[arg(value_name = "REPORT", conflicts_with = "report")]
+        report_path: Option<PathBuf>,
+
+        /// Auditor public key: a key file, hex or base64
+        ///
+        /// Required for auditors not yet in the trust store.
+        #[arg(long)]
+        public_key: Option<String>,
+
+        /// Trust store mapping auditor addresses to pinned keys
+        #[arg(long)]
+        trust_store: Option<PathBuf>,
+
+        /// Known-bad auditor-node version or commit prefix (repeatable; adds to config)
+        #[arg(long = "deny-version")]
+        deny_versions: Vec<String>,
+
+        /// Challenge commitment log; the report's revealed challenge set must match a prior commitment
+        #[arg(long)]
+        commitment_log: Option<PathBuf>,
+
+        /// Minimum number of valid signatures (primary + cosignatures); above 1 requires --trust-store
+        #[arg(long, default_value_t = 1)]
+        min_signatures: usize,
+
+        /// Keyless check: compare the report with the auditor's AuditRecord on Sui
+        /// (signatures are also checked when --public-key or --trust-store is given)
+        #[arg(long)]
+        against_chain: bool,
+
+        /// Epoch of the on-chain AuditRecord (defaults to the report's challenge_epoch)
+        #[arg(long, requires = "against_chain")]
+        epoch: Option<u32>,
+
+        /// Print the verdict as JSON instead of text
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Verify every report in an archive directory in parallel
+    VerifyArchive {
+        /// Archive (or any directory of report JSON files), walked recursively
+        #[arg(long)]
+        dir: PathBuf,
+
+        /// Only verify reports with a timestamp on or after this date (YYYY-MM-DD or RFC 3339)
+        #[arg(long)]
+        since: Option<String>,
+
+        /// Parallel verification threads (0 = number of CPUs)
+        #[arg(long, default_value_t = 0)]
+        jobs: usize,
+
+        /// Trust store with the auditors' keys; reports by unknown auditors are unverifiable
+        #[arg(long, default_value = trust::DEFAULT_TRUST_STORE_PATH)]
+        trust_store: PathBuf,
+
+        /// Where to write details of invalid and unverifiable reports
+        #[arg(long, default_value = "verify-archive-failures.json")]
+        failures: PathBuf,
+    },
+
+    /// Audit a local file offline, optionally against a published audit
+    ///
+    /// Exits 0 when the file matches (or passes its challenges), 1 when it does not
+    /// and 2 on errors.
+    AuditFile {
+        /// File to audit
+        #[arg(long)]
+        path: PathBuf,
+
+        /// Published audit to compare with: AuditData JSON or a report (JSON or CBOR)
+        #[arg(long, value_name = "JSON")]
+        expect_report: Option<PathBuf>,
+
+        /// Merkle chunk size in bytes (taken from the report with --expect-report)
+        #[arg(
+            long,
+            default_value_t = integrity::DEFAULT_CHUNK_SIZE,
+            conflicts_with = "expect_report"
+        )]
+        chunk_size: usize,
+
+        /// Print the result as JSON instead of text
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Co-sign a report with a second auditor
+    Cosign {
+        #[command(subcommand)]
+        action: CosignCommand,
+    },
+
+    /// Package a signed report with its signing key for offline verification
+    Bundle {
+        #[command(subcommand)]
+        action: BundleCommand,
+    },
+
+    /// Generate a new PQC keystore
+    ///
+    /// The secret key is encrypted when PQC_KEYSTORE_PASSPHRASE is set.
+    Keygen {
+        /// Keystore directory
+        #[arg(long)]
+        out: PathBuf,
+
+        /// Signature algorithm
+        #[arg(long, value_enum, default_value_t = KeyAlgorithm::Dilithium3)]
+        algorithm: KeyAlgorithm,
+
+        /// Overwrite an existing keystore (its secret key is lost)
+        #[arg(long, default_value_t = false)]
+        force: bool,
+    },
+
+    /// Print a keystore's public key, algorithm, fingerprint and creation time
+    ///
+    /// Only public fields are read; encrypted keystores need no passphrase.
+    ShowKey {
+        /// Keystore directory
+        #[arg(long)]
+        keystore: PathBuf,
+
+        /// Output format
+        #[arg(long, value_enum, default_value_t = KeyFormat::Hex)]
+        format: KeyFormat,
+    },
+
+    /// Manage the auditor trust store
+    Trust {
+        /// Trust store file
+        #[arg(long, default_value = trust::DEFAULT_TRUST_STORE_PATH)]
+        trust_store: PathBuf,
+
+        #[command(subcommand)]
+        action: TrustCommand,
+    },
+}
+
+/// Answers to `init` prompts; any flag given skips its prompt
+#[derive(clap::Args, Debug)]
+struct InitArgs {
+    /// Overwrite an existing config file
+    #[arg(long, default_value_t = false)]
+    force: bool,
+
+    /// Network profile (testnet, mainnet, devnet, local)
+    #[arg(long)]
+    network: Option<init::NetworkProfile>,
+
+    /// PQC keystore directory
+    #[arg(long)]
+    keystore_path: Option<PathBuf>,
+
+    /// Auditor Sui address
+    #[arg(long, conflicts_with = "generate_sui_key")]
+    auditor_address: Option<String>,
+
+    /// Generate a Sui key and derive the auditor address from it
+    #[arg(long, num_args = 0..=1, default_missing_value = "true")]
+    generate_sui_key: Option<bool>,
+
+    /// Enable Seal encryption of reports
+    #[arg(long, num_args = 0..=1, default_missing_value = "true")]
+    seal: Option<bool>,
+
+    /// Seal API URL
+    #[arg(long)]
+    seal_api_url: Option<String>,
+
+    /// Submit audit records to Sui
+    #[arg(long, num_args = 0..=1, default_missing_value = "true")]
+    submit_to_sui: Option<bool>,
+
+    /// audit_system package ID (defaults to the network's deployment)
+    #[arg(long)]
+    package_id: Option<String>,
+
+    /// AuditorRegistry object ID (defaults to the network's deployment)
+    #[arg(long)]
+    registry_id: Option<String>,
+}
+
+impl InitArgs {
+    fn into_init_options(self, config_path: &Path) -> init::InitOptions {
+        init::InitOptions {
+            config_path: config_path.to_path_buf(),
+            force: self.force,
+            network: self.network,
+            keystore_path: self.keystore_path,
+            auditor_address: self.auditor_address,
+            generate_sui_key: self.generate_sui_key,
+            enable_seal: self.seal,
+            seal_api_url: self.seal_api_url,
+            submit_to_sui: self.submit_to_sui,
+            package_id: self.package_id,
+            registry_id: self.registry_id,
+        }
+    }
+}
+
+#[derive(Subcommand, Debug)]
+enum CaptureCommand {
+    /// Re-hash a capture directory and compare it with the digest bound in a report
+    Verify {
+        /// Capture directory (contains capture.jsonl)
+        dir: PathBuf,
+
+        /// Audit report JSON containing `capture_digest`
+        #[arg(long, required_unless_present = "digest", conflicts_with = "digest")]
+        report: Option<PathBuf>,
+
+        /// Expected capture digest (hex)
+        #[arg(long)]
+        digest: Option<String>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum CosignCommand {
+    /// Export a co-signing request (signing payload + digest) for a signed report
+    Request {
+        /// Signed report JSON
+        report: PathBuf,
+
+        /// Output file (defaults to stdout)
+        #[arg(long)]
+        out: Option<PathBuf>,
+    },
+
+    /// Co-sign a request with this node's keystore and auditor address
+    Sign {
+        /// Co-signing request JSON
+        request: PathBuf,
+
+        /// Re-run the audit and refuse to co-sign if the verdict differs
+        #[arg(long, default_value_t = false)]
+        reaudit: bool,
+
+        /// Output file (defaults to stdout)
+        #[arg(long)]
+        out: Option<PathBuf>,
+    },
+
+    /// Attach a cosignature to the primary report
+    Add {
+        /// Signed report JSON
+        report: PathBuf,
+
+        /// Cosignature JSON returned by the co-signer
+        cosignature: PathBuf,
+
+        /// Output file (defaults to overwriting the report)
+        #[arg(long)]
+        out: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum BundleCommand {
+    /// Bundle a signed report with this node's public key and key rotation chain
+    Create {
+        /// Signed report (JSON, CBOR or legacy SignedAuditReport)
+        report: PathBuf,
+
+        /// Output file (defaults to the report path with a .warb extension)
+        #[arg(long)]
+        out: Option<PathBuf>,
+    },
+
+    /// Verify a bundle offline with the key it carries
+    ///
+    /// Exit codes: 0 = VALID, 1 = INVALID, 2 = could not read the bundle.
+    /// The bundled key is not authenticated; confirm its key id via the on-chain registry.
+    Verify {
+        /// Bundle file (.warb)
+        bundle: PathBuf,
+
+        /// Print the result as JSON instead of text
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum TrustCommand {
+    /// List trusted auditors
+    List,
+
+    /// Add an auditor key (pinned)
+    Add {
+        /// Auditor Sui address
+        address: String,
+
+        /// Auditor public key: a key file, hex or base64
+        public_key: String,
+
+        /// PQC algorithm (1=Falcon512, 2=Dilithium2, 3=Dilithium3)
+        #[arg(long, default_value_t = 3)]
+        algorithm: u8,
+
+        /// Free-form note
+        #[arg(long)]
+        note: Option<String>,
+    },
+
+    /// Pin the recorded key, or re-pin the auditor to a new key
+    Pin {
+        /// Auditor Sui address
+        address: String,
+
+        /// New public key (key file, hex or base64) to replace the recorded one
+        #[arg(long)]
+        public_key: Option<String>,
+
+        /// PQC algorithm of the new key
+        #[arg(long, default_value_t = 3)]
+        algorithm: u8,
+    },
+
+    /// Remove an auditor
+    Remove {
+        /// Auditor Sui address
+        address: String,
+    },
 }
 
 #[tokio::main]
 async fn main() -> Result<()> {
     let args = Args::parse();
 
-    // 1. Initialize logging
-    init_logging(&args.log_level)?;
+    if args.version {
+        let producer = producer::Producer::current();
+        match args.output {
+            OutputFormat::Text => println!("{}", producer),
+            OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&producer)?),
+        }
+        return Ok(());
+    }
+
+    let config_path = args
+        .config
+        .clone()
+        .unwrap_or_else(|| PathBuf::from(DEFAULT_CONFIG_PATH));
+
+    // 1. Initialize logging (--log-format wins over the configured format)
+    let log_format = args
+        .log_format
+        .unwrap_or_else(|| configured_log_format(&config_path));
+    init_logging(&args.log_level, log_format)?;
+
+    if let Some(command) = args.command {
+        return run_command(command, &config_path).await;
+    }
 
     info!("🚀 Starting Walrus Auditor Node v{}", env!("CARGO_PKG_VERSION"));
     info!("──────────────────────────────────────────────");
 
-    // 2. Load configuration
-    let mut config = load_configuration(&args.config)?;
-
-    // Command line arguments override config file
-    if let Some(seal_api) = args.seal_api {
-        config.seal_api_url = Some(seal_api);
-        config.enable_seal_encryption = true;
+    // 2. Load configuration (defaults < file < environment < command line)
+    let mut overrides = Vec::new();
+    if let Some(seal_api) = &args.seal_api {
+        overrides.push(("seal_api_url", seal_api.clone()));
+        overrides.push(("enable_seal_encryption", "true".to_string()));
+    }
+    if args.capture_http {
+        overrides.push(("capture_http", "true".to_string()));
+    }
+    if args.dry_run {
+        overrides.push(("dry_run", "true".to_string()));
+    }
+    if args.allow_unregistered {
+        overrides.push(("allow_unregistered", "true".to_string()));
+    }
+    if let Some(auditor_address) = &args.auditor_address {
+        overrides.push(("auditor_address", auditor_address.clone()));
+    }
+    if let Some(package_id) = &args.package_id {
+        overrides.push(("audit_system_package_id", package_id.clone()));
     }
 
+    // An explicit --config must exist; the default file is optional
+    let config_file = if args.config.is_some() || config_path.exists() {
+        Some(config_path.as_path())
+    } else {
+        warn!(
+            "{} not found, using defaults, {}_* environment variables and flags",
+            DEFAULT_CONFIG_PATH,
+            config::ENV_PREFIX
+        );
+        None
+    };
+    let config = load_configuration(config_file, &overrides)?;
+
     // 3. Validate configuration
     validate_configuration(&config)?;
 
@@ -94,16 +644,44 @@ async fn main() -> Result<()> {
     let shutdown_signal = setup_shutdown_handler();
 
     // 6. Run based on mode
-    if let Some(blob_id) = args.blob_id {
+    let batch_mode =
+        args.blob_file.is_some() || args.blob_id.len() > 1 || args.summary_output.is_some();
+    if batch_mode {
+        // Multi-blob audit mode
+        let listed = match &args.blob_file {
+            Some(path) => batch::read_blob_list(path)?,
+            None => Vec::new(),
+        };
+        let blob_ids = batch::merge_blob_ids(args.blob_id, listed);
+        let options = batch::BatchOptions {
+            concurrency: args.concurrency,
+            fail_fast: args.fail_fast,
+            report_dir: args.report_dir,
+        };
+        let summary = run_batch_audit(&config, &keystore, blob_ids, &options).await?;
+
+        let json = serde_json::to_string_pretty(&summary)?;
+        match &args.summary_output {
+            Some(path) => std::fs::write(path, json + "\n")
+                .with_context(|| format!("Failed to write summary to {}", path.display()))?,
+            None => println!("{}", json),
+        }
+        if summary.has_failures() {
+            std::process::exit(1);
+        }
+    } else if let Some(blob) = single_blob(&args) {
         // Single audit mode
-        run_single_audit(
+        let is_valid = run_single_audit(
             &config,
             &keystore,
-            &blob_id,
+            &blob,
             args.auditor_address.as_deref(),
             args.package_id.as_deref(),
         )
         .await?;
+        if config.dry_run && !is_valid {
+            std::process::exit(1);
+        }
     } else if args.daemon {
         // Daemon mode
         run_daemon_mode(
@@ -116,7 +694,8 @@ async fn main() -> Result<()> {
         .await?;
     } else {
         error!("❌ No operation mode specified");
-        error!("   Use --blob-id <ID> for single audit");
+        error!("   Use --blob-id <ID> or --blob-object-id <ID> for single audit");
+        error!("   Use --blob-file <PATH> or repeated --blob-id to audit several blobs");
         error!("   Use --daemon to start daemon mode");
         std::process::exit(1);
     }
@@ -125,8 +704,793 @@ async fn main() -> Result<()> {
     Ok(())
 }
 
+/// Run an auxiliary subcommand
+async fn run_command(command: Command, config_path: &Path) -> Result<()> {
+    match command {
+        Command::Init {
+            options,
+            non_interactive,
+            skip_preflight,
+        } => {
+            let options = options.into_init_options(config_path);
+            init_command(options, non_interactive, skip_preflight).await
+        }
+        Command::Capture {
+            action: CaptureCommand::Verify { dir, report, digest },
+        } => verify_capture_command(&dir, report.as_deref(), digest.as_deref()),
+        Command::Verify {
+            report,
+            report_path,
+            public_key,
+            trust_store,
+            mut deny_versions,
+            commitment_log,
+            min_signatures,
+            against_chain,
+            epoch,
+            json,
+        } => {
+            let report_path = report.or(report_path).context("A report path is required")?;
+            let result = async {
+                let config_file = config_path.exists().then_some(config_path);
+                let config = load_configuration(config_file, &[])?;
+                let report = load_report(&report_path)?;
+                let mut verdict = verdict::ReportVerification::new(&report);
+
+                if against_chain {
+                    verdict.record_chain(
+                        verify_against_chain_command(&report_path, &config, epoch).await?,
+                    );
+                }
+                if !against_chain || public_key.is_some() || trust_store.is_some() {
+                    deny_versions.extend(config.denied_producer_versions);
+                    verify_report_command(
+                        &report,
+                        public_key.as_deref(),
+                        trust_store.as_deref(),
+                        &deny_versions,
+                        commitment_log.as_deref(),
+                        min_signatures,
+                        &mut verdict,
+                    )?;
+                }
+                anyhow::Ok(verdict)
+            }
+            .await;
+
+            // Exit codes: 0 = VALID, 1 = INVALID, 2 = could not verify
+            match result {
+                Ok(verdict) => {
+                    if json {
+                        write_json(&verdict, None)?;
+                    } else {
+                        print!("{}", verdict);
+                    }
+                    if !verdict.is_valid() {
+                        std::process::exit(1);
+                    }
+                    Ok(())
+                }
+                Err(e) => {
+                    error!("❌ Verification error: {:#}", e);
+                    std::process::exit(2);
+                }
+            }
+        }
+        Command::VerifyArchive {
+            dir,
+            since,
+            jobs,
+            trust_store,
+            failures,
+        } => verify_archive_command(&dir, since.as_deref(), jobs, &trust_store, &failures),
+        Command::AuditFile {
+            path,
+            expect_report,
+            chunk_size,
+            json,
+        } => {
+            // Exit codes: 0 = match, 1 = mismatch or failed challenges, 2 = could not audit
+            match audit_file_command(&path, expect_report.as_deref(), chunk_size, json) {
+                Ok(true) => Ok(()),
+                Ok(false) => std::process::exit(1),
+                Err(e) => {
+                    error!("❌ Local audit error: {:#}", e);
+                    std::process::exit(2);
+                }
+            }
+        }
+        Command::Cosign { action } => cosign_command(config_path, action).await,
+        Command::Bundle { action } => bundle_command(config_path, action),
+        Command::Keygen {
+            out,
+            algorithm,
+            force,
+        } => keygen_command(&out, algorithm.into(), force),
+        Command::ShowKey { keystore, format } => show_key_command(&keystore, format),
+        Command::Trust {
+            trust_store,
+            action,
+        } => trust_command(&trust_store, action),
+    }
+}
+
+/// `init`: write config.toml and keys, then check connectivity
+async fn init_command(
+    options: init::InitOptions,
+    non_interactive: bool,
+    skip_preflight: bool,
+) -> Result<()> {
+    let outcome = if non_interactive {
+        init::run_init(&options, &mut init::NonInteractive)?
+    } else {
+        init::run_init(&options, &mut init::StdioPrompter)?
+    };
+
+    info!("✅ Configuration written to {}", outcome.config_path.display());
+    info!(
+        "   - PQC keystore: {} ({})",
+        outcome.config.pqc_keystore_path,
+        if outcome.keystore_generated {
+            "generated"
+        } else {
+            "existing"
+        }
+    );
+    if let Some(path) = &outcome.generated_sui_key {
+        info!("   - Sui key: {} (generated)", path.display());
+    }
+    if let Some(address) = &outcome.config.auditor_address {
+        info!("   - Auditor address: {}", address);
+    }
+
+    if skip_preflight {
+        return Ok(());
+    }
+
+    info!("🔍 Running preflight checks...");
+    let checks = init::preflight(&outcome.config).await;
+    for check in &checks {
+        if check.ok {
+            info!("   ✅ {}: {}", check.name, check.detail);
+        } else {
+            error!("   ❌ {}: {}", check.name, check.detail);
+        }
+    }
+
+    let failed = checks.iter().filter(|check| !check.ok).count();
+    if failed > 0 {
+        anyhow::bail!(
+            "{} preflight check(s) failed; config was written, fix the issues and rerun your audit",
+            failed
+        );
+    }
+    Ok(())
+}
+
+/// `verify`: check a report signature, resolving the key through the trust store
+///
+/// Failed checks are recorded in `verdict`; errors are reserved for reports that
+/// could not be checked at all (missing or changed keys, unreadable inputs).
+#[allow(clippy::too_many_arguments)]
+fn verify_report_command(
+    report: &types::AuditReport,
+    public_key: Option<&str>,
+    trust_store: Option<&Path>,
+    deny_versions: &[String],
+    commitment_log: Option<&Path>,
+    min_signatures: usize,
+    verdict: &mut verdict::ReportVerification,
+) -> Result<()> {
+    let presented = public_key
+        .map(trust::parse_public_key)
+        .transpose()
+        .context("Invalid --public-key")?;
+
+    let key = match (trust_store, presented) {
+        (None, Some(key)) => key,
+        (None, None) => anyhow::bail!("Either --public-key or --trust-store is required"),
+        (Some(store_path), Some(key)) => {
+            // Fail on key change before spending time on signature verification
+            trust::TrustStore::load(store_path)?.check_or_tofu(
+                &report.auditor,
+                &key,
+                report.pqc_algorithm,
+            )?;
+            key
+        }
+        (Some(store_path), None) => trust::TrustStore::load(store_path)?
+            .get(&report.auditor)
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Auditor {} is not in the trust store; pass --public-key to trust on first use",
+                    report.auditor
+                )
+            })?
+            .public_key_bytes()?,
+    };
+
+    let signature_valid = match commitment_log {
+        Some(log_path) => report::ReportManager::verify_report_with_commitments(
+            report,
+            &key,
+            &commitment::CommitmentLog::open(log_path)?,
+        )?,
+        None => report::ReportManager::verify_report(report, &key)?,
+    };
+    verdict.record_signature(&key, signature_valid);
+    if !signature_valid {
+        error!("❌ Invalid signature on report for blob {}", report.blob_id);
+        return Ok(());
+    }
+
+    if min_signatures > 1 || !report.cosignatures.is_empty() {
+        match trust_store {
+            Some(store_path) => {
+                let cosignatures_valid = report::ReportManager::verify_report_with_cosignatures(
+                    report,
+                    &key,
+                    &trust::TrustStore::load(store_path)?,
+                    min_signatures,
+                )?;
+                verdict.record_cosignatures(cosignatures_valid, min_signatures);
+                if !cosignatures_valid {
+                    error!(
+                        "❌ Report for blob {} does not carry {} valid signatures",
+                        report.blob_id, min_signatures
+                    );
+                    return Ok(());
+                }
+                info!(
+                    "✅ {} cosignature(s) verified against {}",
+                    report.cosignatures.len(),
+                    store_path.display()
+                );
+            }
+            None if min_signatures > 1 => {
+                anyhow::bail!("--min-signatures above 1 requires --trust-store for co-signer keys")
+            }
+            None => warn!(
+                "⚠️  {} cosignature(s) not checked; pass --trust-store",
+                report.cosignatures.len()
+            ),
+        }
+    }
+
+    if let Some(store_path) = trust_store {
+        let outcome = trust::TrustStore::update(store_path, |store| {
+            store.check_or_tofu(&report.auditor, &key, report.pqc_algorithm)
+        })?;
+        if outcome == trust::TrustOutcome::FirstUse {
+            warn!(
+                "⚠️  Auditor {} was unknown; key {} recorded on first use in {}",
+                report.auditor,
+                trust::key_id(&key),
+                store_path.display()
+            );
+        }
+    }
+
+    info!(
+        "✅ Report for blob {} signed by auditor {} ({})",
+        report.blob_id,
+        report.auditor,
+        trust::key_id(&key)
+    );
+
+    match &report.producer {
+        Some(producer) => {
+            info!("   Produced by {}", producer);
+            if producer.is_denied(deny_versions) {
+                warn!(
+                    "⚠️  auditor-node {} is on the deny-list of known-bad releases; do not trust this verdict",
+                    producer.auditor_node_version
+                );
+            }
+        }
+        None => warn!("⚠️  Report has no producer metadata (produced by an older release)"),
+    }
+
+    if report.challenge_reveal.is_some() && commitment_log.is_none() {
+        warn!("⚠️  Challenge set not checked against its commitment; pass --commitment-log");
+    }
+    match &report.challenge_seed_source {
+        Some(challenge_seed::ChallengeSeedSource::SuiCheckpoint {
+            sequence_number, ..
+        }) => match report.verify_challenge_seed() {
+            Ok(()) => info!(
+                "✅ Challenge seed derives from Sui checkpoint {}",
+                sequence_number
+            ),
+            Err(e) => error!("❌ {}", e),
+        },
+        Some(challenge_seed::ChallengeSeedSource::Random) => warn!(
+            "⚠️  Challenge seed fell back to local randomness (checkpoint unavailable); it cannot be recomputed"
+        ),
+        None => {}
+    }
+    Ok(())
+}
+
+/// `verify --against-chain`: compare a report with its on-chain AuditRecord (no keys needed)
+async fn verify_against_chain_command(
+    report_path: &Path,
+    config: &AuditorConfig,
+    epoch: Option<u32>,
+) -> Result<chain_verify::ChainVerification> {
+    let package_id = config.audit_system_package_id.as_deref().ok_or_else(|| {
+        anyhow::anyhow!(
+            "--against-chain requires audit_system_package_id (config file or AUDIT_SYSTEM_PACKAGE_ID)"
+        )
+    })?;
+    let client = sui_client::AuditSystemClient::new(
+        &config.sui_rpc_url,
+        package_id,
+        config.access_policy_package_id.as_deref().unwrap_or_default(),
+        config.auditor_registry_id.as_deref().unwrap_or_default(),
+        config.incentives_id.as_deref().unwrap_or_default(),
+    )
+    .await?;
+
+    let json = ingest::read_document(report_path, &ingest::IngestLimits::default())
+        .with_context(|| format!("Failed to read report {}", report_path.display()))?;
+    let result = chain_verify::verify_against_chain_at(json.as_bytes(), &client, epoch).await?;
+
+    match result.verdict {
+        chain_verify::ChainVerdict::DigestMatch => info!(
+            "✅ Report for blob {} matches on-chain AuditRecord {}",
+            result.blob_id,
+            result.audit_record_id.as_deref().unwrap_or_default()
+        ),
+        chain_verify::ChainVerdict::DigestMismatch => error!(
+            "❌ Report for blob {} does not match on-chain AuditRecord {} ({})",
+            result.blob_id,
+            result.audit_record_id.as_deref().unwrap_or_default(),
+            result.mismatched_fields.join(", ")
+        ),
+        chain_verify::ChainVerdict::NoRecordFound => error!(
+            "❌ No on-chain AuditRecord for blob {} (epoch {}, auditor {})",
+            result.blob_id, result.challenge_epoch, result.auditor
+        ),
+    }
+    Ok(result)
+}
+
+/// `verify-archive`: fails when any report is invalid (unverifiable reports only warn)
+fn verify_archive_command(
+    dir: &Path,
+    since: Option<&str>,
+    jobs: usize,
+    trust_store: &Path,
+    failures_path: &Path,
+) -> Result<()> {
+    let options = archive::ArchiveVerifyOptions {
+        since: since.map(archive::parse_since).transpose()?,
+        jobs,
+    };
+    let store = trust::TrustStore::load(trust_store)?;
+    let summary = archive::verify_archive(dir, &store, &options)?;
+
+    println!("Reports:       {}", summary.total);
+    println!("Valid:         {}", summary.valid);
+    println!("Invalid:       {}", summary.invalid);
+    println!("Unverifiable:  {} (auditor key missing)", summary.unverifiable);
+    if summary.skipped > 0 {
+        println!("Skipped:       {} (before --since)", summary.skipped);
+    }
+
+    if !summary.failures.is_empty() {
+        write_json(&summary.failures, Some(failures_path))?;
+        println!("Failures:      {}", failures_path.display());
+    }
+
+    if summary.has_invalid() {
+        error!("❌ {} invalid report(s) in {}", summary.invalid, dir.display());
+        anyhow::bail!("Archive contains invalid reports");
+    }
+    if summary.unverifiable > 0 {
+        warn!(
+            "⚠️  {} report(s) could not be verified; add their auditors to {}",
+            summary.unverifiable,
+            trust_store.display()
+        );
+    }
+    Ok(())
+}
+
+/// `cosign request/sign/add`
+async fn cosign_command(config_path: &Path, action: CosignCommand) -> Result<()> {
+    match action {
+        CosignCommand::Request { report, out } => {
+            let report = load_report(&report)?;
+            let request = cosign::CosignRequest::from_report(&report)?;
+            write_json(&request, out.as_deref())?;
+            info!(
+                "✅ Co-signing request for blob {} (digest {})",
+                request.blob_id, request.report_digest
+            );
+        }
+        CosignCommand::Sign {
+            request,
+            reaudit,
+            out,
+        } => {
+            let content = std::fs::read_to_string(&request)
+                .with_context(|| format!("Failed to read request {}", request.display()))?;
+            let request: cosign::CosignRequest =
+                serde_json::from_str(&content).context("Invalid co-signing request")?;
+            let primary = request.report()?;
+
+            let config = load_configuration(Some(config_path), &[])?;
+            let auditor = config
+                .auditor_address
+                .clone()
+                .context("Auditor address not configured (run `init`)")?;
+
+            let keystore = keystore::Keystore::open(Path::new(&config.pqc_keystore_path))
+                .context("Failed to load keystore")?;
+
+            if reaudit {
+                info!("🔍 Re-auditing blob {} before co-signing", primary.blob_id);
+                let pipeline = pipeline::AuditPipeline::from_config(&config, &keystore)?;
+                let (own, _) = pipeline.audit(&primary.blob_id).await?;
+                if own.is_valid != primary.is_valid
+                    || !crypto::ct_eq(&own.integrity_hash, &primary.integrity_hash)
+                {
+                    error!(
+                        "❌ Independent audit disagrees: valid={} hash={} vs primary valid={} hash={}",
+                        own.is_valid,
+                        hex::encode(&own.integrity_hash),
+                        primary.is_valid,
+                        hex::encode(&primary.integrity_hash)
+                    );
+                    anyhow::bail!("Refusing to co-sign a verdict this node could not reproduce");
+                }
+                info!("   ✅ Independent audit agrees with the primary report");
+            }
+
+            let cosignature = cosign::cosign(&request, &auditor, keystore.signer())?;
+            write_json(&cosignature, out.as_deref())?;
+            info!(
+                "✅ Co-signed report for blob {} as {} ({})",
+                request.blob_id, cosignature.auditor, cosignature.key_id
+            );
+        }
+        CosignCommand::Add {
+            report: report_path,
+            cosignature,
+            out,
+        } => {
+            let mut report = load_report(&report_path)?;
+            let content = std::fs::read_to_string(&cosignature)
+                .with_context(|| format!("Failed to read cosignature {}", cosignature.display()))?;
+            let cosignature: cosign::Cosignature =
+                serde_json::from_str(&content).context("Invalid cosignature")?;
+            cosign::attach(&mut report, cosignature)?;
+
+            let out = out.unwrap_or(report_path);
+            write_json(&report, Some(&out))?;
+            info!(
+                "✅ Report for blob {} now carries {} cosignature(s): {}",
+                report.blob_id,
+                report.cosignatures.len(),
+                out.display()
+            );
+        }
+    }
+    Ok(())
+}
+
+/// `bundle create/verify`
+fn bundle_command(config_path: &Path, action: BundleCommand) -> Result<()> {
+    match action {
+        BundleCommand::Create { report, out } => {
+            let out = out.unwrap_or_else(|| report.with_extension(report_bundle::BUNDLE_EXTENSION));
+            let report = load_report(&report)?;
+
+            let config = load_configuration(Some(config_path), &[])?;
+            let keystore = keystore::Keystore::open(Path::new(&config.pqc_keystore_path))
+                .context("Failed to load keystore")?;
+
+            let bundle = report_bundle::ReportBundle::create(&report, &keystore)?;
+            bundle.write(&out)?;
+            info!(
+                "✅ Bundled report for blob {} with key {}: {}",
+                report.blob_id,
+                bundle.manifest().key_id,
+                out.display()
+            );
+            Ok(())
+        }
+        BundleCommand::Verify { bundle, json } => {
+            let bundle = match report_bundle::ReportBundle::open(&bundle) {
+                Ok(bundle) => bundle,
+                Err(e) => {
+                    error!("❌ Verification error: {}", e);
+                    std::process::exit(2);
+                }
+            };
+
+            // Exit codes: 0 = VALID, 1 = INVALID, 2 = could not read the bundle
+            match bundle.verify() {
+                Ok(verification) => {
+                    if json {
+                        let mut value = serde_json::to_value(&verification)?;
+                        value["verdict"] = "VALID".into();
+                        write_json(&value, None)?;
+                    } else {
+                        print!("{}", verification);
+                    }
+                    Ok(())
+                }
+                Err(e) => {
+                    if json {
+                        write_json(
+                            &serde_json::json!({
+                                "verdict": "INVALID",
+                                "blob_id": bundle.manifest().blob_id,
+                                "reason": e.to_string(),
+                            }),
+                            None,
+                        )?;
+                    } else {
+                        println!("INVALID  bundled report for blob {}", bundle.manifest().blob_id);
+                        println!("  Reason:        {}", e);
+                    }
+                    std::process::exit(1);
+                }
+            }
+        }
+    }
+}
+
+/// `audit-file`: audit a local file without network access; returns whether it passed
+fn audit_file_command(
+    path: &Path,
+    expect_report: Option<&Path>,
+    chunk_size: usize,
+    json: bool,
+) -> Result<bool> {
+    let Some(report_path) = expect_report else {
+        let audit = integrity::audit_local(path, chunk_size)
+            .with_context(|| format!("Failed to audit {}", path.display()))?;
+        let passed = audit.failed_verifications == 0;
+        if json {
+            write_json(&audit, None)?;
+        } else {
+            print_local_audit(&audit);
+        }
+        return Ok(passed);
+    };
+
+    let expected = load_expected_audit(report_path)?;
+    let verification = integrity::verify_local(path, &expected)
+        .with_context(|| format!("Failed to verify {}", path.display()))?;
+    let passed = verification.is_match() && verification.audit.failed_verifications == 0;
+    if json {
+        write_json(&verification, None)?;
+    } else {
+        let verdict = if verification.is_match() {
+            "MATCH   "
+        } else {
+            "MISMATCH"
+        };
+        println!(
+            "{}  {} against the audit of blob {}",
+            verdict,
+            path.display(),
+            expected.blob_id
+        );
+        for mismatch in &verification.mismatches {
+            println!("  {}", mismatch);
+        }
+        print_local_audit(&verification.audit);
+    }
+    Ok(passed)
+}
+
+/// Print the result of a local file audit
+fn print_local_audit(audit: &integrity::AuditData) {
+    println!("  File:          {}", audit.blob_id);
+    println!("  Size:          {} bytes", audit.file_size);
+    println!("  Content hash:  {}", audit.content_hash);
+    match audit.chunk_size {
+        Some(chunk_size) => println!(
+            "  Merkle root:   {} ({}-byte chunks)",
+            audit.merkle_root, chunk_size
+        ),
+        None => println!("  Merkle root:   none (empty file)"),
+    }
+    println!(
+        "  Challenges:    {}/{} verified, {} failed",
+        audit.successful_verifications, audit.total_challenges, audit.failed_verifications
+    );
+}
+
+/// Load the audit `audit-file --expect-report` compares with: AuditData JSON or a report
+fn load_expected_audit(path: &Path) -> Result<integrity::AuditData> {
+    let bytes =
+        std::fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    if let Ok(audit) = serde_json::from_slice::<integrity::AuditData>(&bytes) {
+        return Ok(audit);
+    }
+    let report = load_report(path)?;
+    integrity::AuditData::try_from(&report)
+        .with_context(|| format!("{} has no integrity audit to compare with", path.display()))
+}
+
+/// Load a report file (CBOR for `.cbor`, JSON otherwise)
+fn load_report(path: &Path) -> Result<types::AuditReport> {
+    let path = path
+        .to_str()
+        .ok_or_else(|| anyhow::anyhow!("Report path is not valid UTF-8"))?;
+    match report::ReportFormat::from_path(path) {
+        Ok(report::ReportFormat::Cbor) => Ok(report::ReportManager::load_cbor(path)?),
+        _ => Ok(report::ReportManager::load_json(path)?),
+    }
+}
+
+/// Write pretty JSON to a file, or stdout when no path is given
+fn write_json<T: serde::Serialize>(value: &T, out: Option<&Path>) -> Result<()> {
+    let json = serde_json::to_string_pretty(value)?;
+    match out {
+        Some(path) => std::fs::write(path, json)
+            .with_context(|| format!("Failed to write {}", path.display()))?,
+        None => println!("{}", json),
+    }
+    Ok(())
+}
+
+/// `keygen`: create a keystore, refusing to overwrite one unless `force`
+fn keygen_command(out: &Path, algorithm: audit_report::PqcAlgorithm, force: bool) -> Result<()> {
+    let passphrase = std::env::var(keystore::PASSPHRASE_ENV).ok();
+    if passphrase.is_some() {
+        info!(
+            "Encrypting secret key with passphrase from {}",
+            keystore::PASSPHRASE_ENV
+        );
+    }
+    keystore::Keystore::create(out, algorithm, passphrase.as_deref(), force)
+        .context("Failed to generate keystore")?;
+    info!(
+        "✅ Generated {} keystore at {}",
+        algorithm.as_str(),
+        out.display()
+    );
+    show_key_command(out, KeyFormat::Hex)
+}
+
+/// `show-key`: print the public half of a keystore (never the secret key)
+fn show_key_command(keystore_path: &Path, format: KeyFormat) -> Result<()> {
+    let info = keystore::public_key_info(keystore_path).context("Failed to read keystore")?;
+    let created_at = info.created_at.and_then(|secs| {
+        chrono::DateTime::from_timestamp(secs as i64, 0).map(|time| time.to_rfc3339())
+    });
+
+    let public_key = match format {
+        KeyFormat::Base64 => {
+            use base64::{engine::general_purpose, Engine as _};
+            general_purpose::STANDARD.encode(&info.public_key)
+        }
+        KeyFormat::Hex | KeyFormat::Json => hex::encode(&info.public_key),
+    };
+    if format == KeyFormat::Json {
+        return write_json(
+            &serde_json::json!({
+                "algorithm": info.algorithm.as_str(),
+                "public_key": public_key,
+                "fingerprint": info.fingerprint,
+                "key_id": trust::key_id(&info.public_key),
+                "created_at": created_at,
+            }),
+            None,
+        );
+    }
+
+    println!("Algorithm:   {}", info.algorithm.as_str());
+    println!("Public key:  {}", public_key);
+    println!("Fingerprint: SHA256:{}", info.fingerprint);
+    println!("Key ID:      {}", trust::key_id(&info.public_key));
+    println!(
+        "Created:     {}",
+        created_at.as_deref().unwrap_or("unknown (legacy keystore)")
+    );
+    Ok(())
+}
+
+/// `trust list/add/pin/remove`
+fn trust_command(store_path: &Path, action: TrustCommand) -> Result<()> {
+    match action {
+        TrustCommand::List => {
+            let store = trust::TrustStore::load(store_path)?;
+            if store.auditors.is_empty() {
+                println!("(no trusted auditors in {})", store_path.display());
+            }
+            for (address, entry) in &store.auditors {
+                println!(
+                    "{}  key_id={}  alg={}  first_seen={}  {}{}",
+                    address,
+                    entry.key_id,
+                    entry.algorithm,
+                    entry.first_seen,
+                    if entry.pinned { "pinned" } else { "tofu" },
+                    entry
+                        .note
+                        .as_deref()
+                        .map(|n| format!("  # {}", n))
+                        .unwrap_or_default()
+                );
+            }
+        }
+        TrustCommand::Add {
+            address,
+            public_key,
+            algorithm,
+            note,
+        } => {
+            let key = trust::parse_public_key(&public_key).context("Invalid public key")?;
+            trust::TrustStore::update(store_path, |store| {
+                store.add(&address, &key, algorithm, note)
+            })?;
+            info!("✅ Added auditor {} ({})", address, trust::key_id(&key));
+        }
+        TrustCommand::Pin {
+            address,
+            public_key,
+            algorithm,
+        } => {
+            let key = public_key
+                .as_deref()
+                .map(trust::parse_public_key)
+                .transpose()
+                .context("Invalid public key")?;
+            trust::TrustStore::update(store_path, |store| {
+                store.pin(&address, key.as_deref().map(|k| (k, algorithm)))
+            })?;
+            info!("✅ Pinned auditor {}", address);
+        }
+        TrustCommand::Remove { address } => {
+            let removed = trust::TrustStore::update(store_path, |store| Ok(store.remove(&address)))?;
+            if removed.is_none() {
+                anyhow::bail!("Auditor {} is not in the trust store", address);
+            }
+            info!("✅ Removed auditor {}", address);
+        }
+    }
+    Ok(())
+}
+
+/// `capture verify`: re-hash a capture directory against the digest in a report
+fn verify_capture_command(dir: &Path, report: Option<&Path>, digest: Option<&str>) -> Result<()> {
+    let expected = match (report, digest) {
+        (_, Some(digest)) => digest.to_string(),
+        (Some(report_path), None) => {
+            let limits = ingest::IngestLimits::default();
+            let content = ingest::read_document(report_path, &limits)
+                .with_context(|| format!("Failed to read report {}", report_path.display()))?;
+            let report =
+                ingest::parse_document(&content, &limits).context("Report is not valid JSON")?;
+            capture::capture_digest_from_report(&report)
+                .ok_or_else(|| anyhow::anyhow!("Report does not contain a capture_digest"))?
+        }
+        (None, None) => anyhow::bail!("Either --report or --digest is required"),
+    };
+
+    let actual = capture::capture_digest(dir).context("Failed to hash capture directory")?;
+
+    if crypto::ct_eq_hex(&actual, expected.trim()).context("Invalid expected capture digest")? {
+        info!("✅ Capture {} matches digest {}", dir.display(), actual);
+        Ok(())
+    } else {
+        error!("❌ Capture digest mismatch for {}", dir.display());
+        error!("   Expected: {}", expected);
+        error!("   Actual:   {}", actual);
+        Err(anyhow::anyhow!("Capture digest mismatch"))
+    }
+}
+
 /// Initialize logging system
-fn init_logging(log_level: &str) -> Result<()> {
+fn init_logging(log_level: &str, log_format: logging::LogFormat) -> Result<()> {
     let level = match log_level.to_lowercase().as_str() {
         "trace" => tracing::Level::TRACE,
         "debug" => tracing::Level::DEBUG,
@@ -139,34 +1503,54 @@ fn init_logging(log_level: &str) -> Result<()> {
         }
     };
 
-    tracing_subscriber::fmt()
+    // Logs go to stderr so JSON written to stdout stays machine-readable
+    let builder = tracing_subscriber::fmt()
+        .with_writer(std::io::stderr)
         .with_max_level(level)
         .with_target(false)
         .with_thread_ids(false)
         .with_file(false)
-        .with_line_number(false)
-        .init();
+        .with_line_number(false);
+
+    // JSON lines carry the fields of every enclosing span (audit_id, blob_id, stage)
+    match log_format {
+        logging::LogFormat::Text => builder.init(),
+        logging::LogFormat::Json => builder.json().init(),
+    }
 
     Ok(())
 }
 
-/// Load configuration file
-fn load_configuration(config_path: &Path) -> Result<AuditorConfig> {
-    info!("📋 Loading configuration: {}", config_path.display());
+/// `log_format` from the config file and environment, read before logging is set up
+///
+/// Errors are ignored here; they are reported once the configuration is loaded for real
+fn configured_log_format(config_path: &Path) -> logging::LogFormat {
+    let config_file = config_path.exists().then_some(config_path);
+    AuditorConfig::from_layers(config_file, config::ENV_PREFIX, &[])
+        .map(|config| config.log_format)
+        .unwrap_or_default()
+}
 
-    if !config_path.exists() {
-        warn!("Configuration file does not exist, using defaults");
-        return Ok(AuditorConfig::default());
+/// Load layered configuration (defaults < file < environment < overrides)
+fn load_configuration(
+    config_path: Option<&Path>,
+    overrides: &[(&str, String)],
+) -> Result<AuditorConfig> {
+    if let Some(path) = config_path {
+        info!("📋 Loading configuration: {}", path.display());
     }
 
-    config::load_config(config_path).context("Failed to load configuration")
+    AuditorConfig::from_layers(config_path, config::ENV_PREFIX, overrides)
+        .context("Failed to load configuration")
 }
 
 /// Validate configuration validity
 fn validate_configuration(config: &AuditorConfig) -> Result<()> {
     info!("🔍 Validating configuration...");
+    config.validate().context("Invalid configuration")?;
     info!("   - Sui RPC: {}", config.sui_rpc_url);
     info!("   - Walrus Aggregator: {}", config.walrus_aggregator_url);
+    info!("   - Walrus Publisher: {}", config.walrus_publisher_url);
     info!(
         "   - Seal Encryption: {}",
         if config.enable_seal_encryption {
@@ -201,7 +1585,7 @@ fn initialize_keystore(keystore_path: &str) -> Result<keystore::Keystore> {
 
     if path.exists() {
         info!("🔐 Loading existing keystore: {}", keystore_path);
-        keystore::Keystore::load(path).context("Failed to load keystore")
+        keystore::Keystore::open(path).context("Failed to load keystore")
     } else {
         info!("🔑 Generating new PQC keystore: {}", keystore_path);
 
@@ -210,11 +1594,28 @@ fn initialize_keystore(keystore_path: &str) -> Result<keystore::Keystore> {
             std::fs::create_dir_all(parent).context("Failed to create keystore directory")?;
         }
 
-        keystore::Keystore::generate_and_save(path).context("Failed to generate keystore")
+        match std::env::var(keystore::PASSPHRASE_ENV) {
+            Ok(passphrase) => {
+                info!("   Encrypting secret key with passphrase from {}", keystore::PASSPHRASE_ENV);
+                keystore::Keystore::generate_and_save_encrypted(
+                    path,
+                    &passphrase,
+                    audit_report::PqcAlgorithm::default(),
+                )
+                .context("Failed to generate keystore")
+            }
+            Err(_) => {
+                keystore::Keystore::generate_and_save(path, audit_report::PqcAlgorithm::default())
+                    .context("Failed to generate keystore")
+            }
+        }
     }
 }
 
 /// Setup graceful shutdown handler
+///
+/// The first Ctrl+C asks the daemon to stop scheduling audits and drain the in-flight one;
+/// a second Ctrl+C exits immediately.
 fn setup_shutdown_handler() -> Arc<tokio::sync::Notify> {
     let shutdown = Arc::new(tokio::sync::Notify::new());
     let shutdown_clone = shutdown.clone();
@@ -223,118 +1624,248 @@ fn setup_shutdown_handler() -> Arc<tokio::sync::Notify> {
         match signal::ctrl_c().await {
             Ok(()) => {
                 info!("\n🛑 Received Ctrl+C signal, preparing to shutdown...");
-                shutdown_clone.notify_waiters();
+                // Stores a permit if the daemon loop is not waiting at this moment
+                shutdown_clone.notify_one();
             }
             Err(err) => {
                 error!("❌ Cannot listen to shutdown signal: {}", err);
+                return;
             }
         }
+
+        if signal::ctrl_c().await.is_ok() {
+            warn!("🛑 Received second Ctrl+C signal, exiting immediately");
+            std::process::exit(130);
+        }
     });
 
     shutdown
 }
 
+/// What the operator can do about a Seal failure
+///
+/// Outages are transient and already retried; rejections and malformed responses
+/// point at configuration that retrying will not fix.
+fn seal_error_hint(error: &error::AuditorError) -> Option<&'static str> {
+    match error {
+        error::AuditorError::SealUnavailable(_) => Some(
+            "Seal API is unavailable; set encryption_fallback = \"local-aes\" to keep auditing during outages",
+        ),
+        error::AuditorError::SealRejected { .. } => {
+            Some("Seal API rejected the request; check auditor_address and audit_system_package_id")
+        }
+        error::AuditorError::SealProtocol(_) => {
+            Some("Unexpected Seal API response; check that seal_api_url points at the Seal API server")
+        }
+        _ => None,
+    }
+}
+
+/// Audit several blobs with one pipeline and return the combined summary
+async fn run_batch_audit(
+    config: &AuditorConfig,
+    keystore: &keystore::Keystore,
+    blob_ids: Vec<String>,
+    options: &batch::BatchOptions,
+) -> Result<batch::BatchSummary> {
+    info!("──────────────────────────────────────────────");
+    info!("📊 Multi-Blob Audit Mode");
+    info!("   Blobs: {}", blob_ids.len());
+    info!("──────────────────────────────────────────────\n");
+
+    if blob_ids.is_empty() {
+        anyhow::bail!("No blob IDs to audit");
+    }
+
+    let pipeline = pipeline::AuditPipeline::from_config(config, keystore)
+        .context("Failed to set up the audit pipeline")?;
+    check_registration(&pipeline).await?;
+    let summary = batch::run_batch(blob_ids, options, |blob_id| {
+        let pipeline = &pipeline;
+        async move { pipeline.run_once(&blob_id).await }
+    })
+    .await;
+
+    info!(
+        "✅ Multi-blob audit completed: {} passed, {} failed, {} skipped, {} errors, {} not run",
+        summary.passed, summary.failed, summary.skipped, summary.errors, summary.not_run
+    );
+    Ok(summary)
+}
+
+/// Run the auditor registration preflight before any audit and log the auditor's standing
+///
+/// Refuses to continue when the auditor is not registered (unless `allow_unregistered`);
+/// if the chain cannot be queried a warning is logged and the audits proceed.
+async fn check_registration(pipeline: &pipeline::AuditPipeline) -> Result<()> {
+    let Some(standing) = pipeline
+        .preflight()
+        .await
+        .context("Auditor registration preflight failed")?
+    else {
+        return Ok(());
+    };
+
+    if standing.registered {
+        match standing.reputation {
+            Some(reputation) => info!(
+                "✅ Auditor registered, reputation {} (epoch {})",
+                reputation, standing.epoch
+            ),
+            None => info!("✅ Auditor registered (reputation unavailable)"),
+        }
+    } else {
+        warn!("⚠️  Auditor is not registered (--allow-unregistered); on-chain submissions will fail");
+    }
+    Ok(())
+}
+
+/// The blob named by `--blob-object-id`, or the single `--blob-id`
+fn single_blob(args: &Args) -> Option<types::BlobRef> {
+    match (&args.blob_object_id, args.blob_id.first()) {
+        (Some(object_id), _) => Some(types::BlobRef::ObjectId(object_id.clone())),
+        (None, Some(blob_id)) => Some(types::BlobRef::BlobId(blob_id.clone())),
+        (None, None) => None,
+    }
+}
+
+/// Progress events buffered for the single-audit log before they are dropped
+const PROGRESS_CHANNEL_CAPACITY: usize = 256;
+
 /// Execute single audit
+///
+/// Returns whether the signed report is valid (`true` when no report is produced)
 async fn run_single_audit(
     config: &AuditorConfig,
     keystore: &keystore::Keystore,
-    blob_id: &str,
+    blob: &types::BlobRef,
     auditor_address: Option<&str>,
     package_id: Option<&str>,
-) -> Result<()> {
+) -> Result<bool> {
     info!("──────────────────────────────────────────────");
     info!("📊 Single Audit Mode");
-    info!("   Blob ID: {}", blob_id);
+    match blob {
+        types::BlobRef::BlobId(blob_id) => info!("   Blob ID: {}", blob_id),
+        types::BlobRef::ObjectId(object_id) => info!("   Blob object ID: {}", object_id),
+    }
+    if config.dry_run {
+        info!("   DRY RUN: nothing is uploaded or submitted");
+    }
     info!("──────────────────────────────────────────────\n");
 
-    // 1. Execute audit (TODO: Actual audit logic in auditor.rs)
-    info!("1️⃣ Executing integrity audit...");
-    let audit_report = execute_audit(config, blob_id).await?;
+    // Command-line addresses take precedence over the configuration
+    let mut config = config.clone();
+    if let Some(address) = auditor_address {
+        config.auditor_address = Some(address.to_string());
+    }
+    if let Some(package_id) = package_id {
+        config.audit_system_package_id = Some(package_id.to_string());
+    }
+
+    // Download and challenge progress is logged as it arrives; slow logging never stalls the audit
+    let (progress_tx, progress_rx) = tokio::sync::mpsc::channel(PROGRESS_CHANNEL_CAPACITY);
+    progress::spawn_progress_log(progress_rx);
+    let pipeline = pipeline::AuditPipeline::from_config(&config, keystore)
+        .context("Failed to set up the audit pipeline")?
+        .with_progress(progress_tx);
+    check_registration(&pipeline).await?;
+    // --blob-id keeps its meaning per audit method (storage node audits read 0x hex as the object)
+    let blob = match blob {
+        types::BlobRef::BlobId(blob_id) => pipeline.blob_ref(blob_id),
+        object => object.clone(),
+    };
+    let outcome = match pipeline.run_blob(&blob, None).await {
+        Ok(outcome) => outcome,
+        Err(e) => {
+            if let Some(hint) = seal_error_hint(&e) {
+                error!("   ❌ {}", hint);
+            }
+            return Err(anyhow::Error::new(e).context("Audit pipeline failed"));
+        }
+    };
+    let report = &outcome.report;
 
     info!(
         "   ✅ Audit completed: {} challenges, {} successes, {} failures",
-        audit_report.total_challenges,
-        audit_report.successful_verifications,
-        audit_report.failed_verifications
+        report.total_challenges, report.successful_verifications, report.failed_verifications
     );
-    info!(
-        "   - Audit result: {}",
-        if audit_report.is_valid {
-            "✅ PASS"
+    info!("   - Audit result: {}", if report.is_valid { "✅ PASS" } else { "❌ FAIL" });
+    info!("   - PQC signature: {} bytes (Dilithium3)", report.pqc_signature.len());
+    info!("   - Blob ID: {}", report.blob_id);
+    if !report.blob_object_id.is_empty() {
+        info!("   - Blob object ID: {}", report.blob_object_id);
+    }
+
+    let blob_id = &report.blob_id;
+    let Some(walrus_blob_id) = &outcome.report_blob_id else {
+        if outcome.status == integrity::VerificationStatus::Expired {
+            info!("   ⌛ Blob {} is past its end_epoch; not a storage node failure", blob_id);
         } else {
-            "❌ FAIL"
+            info!("   🗑️  Blob {} was deleted by its owner; not a storage node failure", blob_id);
         }
-    );
+        info!("   Skipping report (set report_deleted_blobs = true to report it anyway)");
+        return Ok(true);
+    };
+
+    if let Some(published) = &outcome.published {
+        info!("   🙈 Blob ID blinded for publication ({})", published.blob_id);
+    }
 
-    // 2. Sign report using Dilithium3
-    info!("\n2️⃣ Signing audit report (Dilithium3 PQC)...");
-    let signed_report = sign_report(audit_report, keystore)?;
-    info!("   ✅ PQC signature completed (signature length: {} bytes)", signed_report.pqc_signature.len());
-
-    // 3. Seal encrypt report (if enabled)
-    let encrypted_data = if config.enable_seal_encryption {
-        info!("\n3️⃣ Encrypting report using Seal (IBE threshold encryption)...");
-
-        let seal_api_url = config
-            .seal_api_url
-            .as_ref()
-            .context("Seal API URL not configured")?;
-
-        let auditor_addr = auditor_address
-            .or_else(|| {
-                // TODO: Get default address from config or keystore
-                Some("0x1234567890abcdef1234567890abcdef1234567890abcdef1234567890abcdef")
-            })
-            .context("Auditor address not provided")?;
-
-        let pkg_id = package_id
-            .or_else(|| {
-                // TODO: Get from config
-                Some("0x8afa5d31dbaa0a8fb07082692940ca3d56b5e856c5126cb5a3693f0a4de63b82")
-            })
-            .context("Package ID not provided")?;
-
-        let encrypted = encrypt_report(&signed_report, seal_api_url, auditor_addr, pkg_id).await?;
-
-        info!("   ✅ Encryption completed");
-        info!("      - Original size: {} bytes", encrypted.metadata.original_size);
-        info!("      - Encrypted size: {} bytes", encrypted.metadata.encrypted_size);
+    if let Some(metadata) = &outcome.encryption {
+        match metadata.scheme {
+            seal_client::EncryptionScheme::Seal => info!("   🔒 Report encrypted with Seal"),
+            seal_client::EncryptionScheme::LocalAes256Gcm => {
+                warn!("   🔒 Seal unavailable, report encrypted locally with AES-256-GCM");
+                if let Some(key_id) = &metadata.key_id {
+                    warn!(
+                        "      - Key {} kept in the keystore until it is migrated to Seal",
+                        key_id
+                    );
+                }
+            }
+        }
+        info!("      - Original size: {} bytes", metadata.original_size);
+        info!("      - Encrypted size: {} bytes", metadata.encrypted_size);
         info!(
             "      - Expansion ratio: {:.2}x",
-            encrypted.metadata.encrypted_size as f64 / encrypted.metadata.original_size as f64
+            metadata.encrypted_size as f64 / metadata.original_size as f64
         );
-        info!("      - Duration: {}ms", encrypted.metadata.duration);
-
-        Some(encrypted.encrypted_data)
-    } else {
-        info!("\n3️⃣ Seal encryption disabled, skipping");
-        None
-    };
-
-    // 4. Upload to Walrus
-    info!("\n4️⃣ Uploading report to Walrus...");
-    let data_to_upload = if let Some(encrypted) = encrypted_data.as_ref() {
-        base64::decode(encrypted).context("Failed to decode encrypted data")?
-    } else {
-        // Upload unencrypted report (JSON format)
-        serde_json::to_vec(&signed_report).context("Failed to serialize report")?
-    };
+        info!("      - Duration: {}ms", metadata.duration);
+    }
 
-    let walrus_blob_id = upload_to_walrus(&config.walrus_aggregator_url, &data_to_upload).await?;
-    info!("   ✅ Upload successful: Blob ID = {}", walrus_blob_id);
+    if config.dry_run {
+        if let Some(record) = &outcome.tx_digest {
+            info!("   📝 Submission recorded in {}", record);
+        }
+        info!("\n✅ DRY RUN completed (nothing uploaded or submitted)");
+        info!(
+            "   - Audit result: {}",
+            if report.is_valid { "VALID" } else { "INVALID" }
+        );
+        info!("   - Placeholder Walrus Blob ID: {}", walrus_blob_id);
+        info!("   - Payloads written to {}", config.dry_run_dir);
+        return Ok(report.is_valid);
+    }
 
-    // 5. Submit to Sui (set access policy)
-    info!("\n5️⃣ Setting access policy on Sui...");
-    info!("   ⚠️  This step requires actual Sui SDK integration (currently placeholder)");
-    // TODO: Actual Sui transaction submission
+    if let Some(digest) = &outcome.tx_digest {
+        info!("   ✅ Access policy created (tx {})", digest);
+    } else if config.submit_to_sui {
+        warn!("   ⚠️  No AuditRecord on chain; access policy not set");
+    }
 
     info!("\n✅ Single audit process completed!");
     info!("   - Walrus Blob ID: {}", walrus_blob_id);
-    if let Some(_) = encrypted_data {
-        info!("   - Report encrypted and protected by Seal access control");
+    match outcome.encryption.as_ref().map(|metadata| metadata.scheme) {
+        Some(seal_client::EncryptionScheme::Seal) => {
+            info!("   - Report encrypted and protected by Seal access control")
+        }
+        Some(seal_client::EncryptionScheme::LocalAes256Gcm) => {
+            info!("   - Report encrypted with a local key (not yet protected by Seal)")
+        }
+        None => {}
     }
 
-    Ok(())
+    Ok(report.is_valid)
 }
 
 /// Daemon mode
@@ -348,40 +1879,245 @@ async fn run_daemon_mode(
     info!("──────────────────────────────────────────────");
     info!("🔄 Daemon Mode");
     info!("   Audit interval: {} seconds", config.audit_interval_secs);
+    if config.dry_run {
+        info!("   DRY RUN: reports are written to {}", config.dry_run_dir);
+    }
     info!("──────────────────────────────────────────────\n");
 
     let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(
         config.audit_interval_secs,
     ));
 
+    // Pending blobs come from the audit_system contract's AuditCreated events
+    let package_id = config
+        .audit_system_package_id
+        .as_deref()
+        .context("Daemon mode requires audit_system_package_id (config file or --package-id)")?;
+    let mut sui = sui_client::AuditSystemClient::new(
+        &config.sui_rpc_url,
+        package_id,
+        config.access_policy_package_id.as_deref().unwrap_or_default(),
+        config.auditor_registry_id.as_deref().unwrap_or_default(),
+        config.incentives_id.as_deref().unwrap_or_default(),
+    )
+    .await
+    .context("Failed to create Sui client")?;
+    let auditor = config
+        .auditor_address
+        .as_deref()
+        .map(chain_types::MoveId::from_hex)
+        .transpose()
+        .context("Invalid auditor_address")?;
+
+    // Shared by every audit so aggregator/node error rates are tracked across blobs
+    let breaker = Arc::new(breaker::CircuitBreaker::new(config.breaker.clone()));
+
+    // Prometheus metrics are only collected when the endpoint is configured
+    let metrics = match &config.metrics_listen_addr {
+        Some(addr) => {
+            let listener = tokio::net::TcpListener::bind(addr)
+                .await
+                .with_context(|| format!("Failed to bind metrics endpoint on {}", addr))?;
+            info!("   Metrics: http://{}/metrics", listener.local_addr()?);
+            let metrics = Arc::new(metrics::Metrics::new());
+            tokio::spawn(serve_metrics(listener, Arc::clone(&metrics)));
+            Some(metrics)
+        }
+        None => None,
+    };
+
+    // One pipeline serves every audit (history, baselines and commitments stay open)
+    let mut audit_pipeline = pipeline::AuditPipeline::from_config(&config, &keystore)
+        .context("Failed to set up the audit pipeline")?
+        .with_breaker(Arc::clone(&breaker));
+    if let Some(metrics) = &metrics {
+        audit_pipeline = audit_pipeline.with_metrics(Arc::clone(metrics));
+    }
+    check_registration(&audit_pipeline).await?;
+
+    // Blob metadata prefetched for each page of pending blobs is reused by the audits
+    let metadata_cache = (config.blob_metadata_cache_ttl_secs > 0).then(|| {
+        let cache = metadata_cache::BlobMetadataCache::new(tokio::time::Duration::from_secs(
+            config.blob_metadata_cache_ttl_secs,
+        ));
+        Arc::new(match &metrics {
+            Some(metrics) => cache.with_metrics(Arc::clone(metrics)),
+            None => cache,
+        })
+    });
+    if let Some(cache) = &metadata_cache {
+        sui.set_metadata_cache(Arc::clone(cache));
+        audit_pipeline = audit_pipeline.with_metadata_cache(Arc::clone(cache));
+    }
+
+    // Audit results and excluded storage nodes are pushed to the webhook, if configured
+    let notifier = notify::NotificationDispatcher::from_config(&config, metrics.clone())
+        .context("Failed to set up webhook notifications")?
+        .map(|(dispatcher, _worker)| dispatcher);
+    if let Some(notifier) = &notifier {
+        info!("   Webhook notifications enabled");
+        audit_pipeline = audit_pipeline.with_notifier(notifier.clone());
+    }
+
+    // Storage nodes failing health checks are skipped when routing challenges
+    let health_monitor = audit_pipeline.spawn_health_monitor();
+
+    // Signed reports are kept in a dated local archive, compressed and pruned on a timer
+    let (archive, archive_retention) = if config.report_archive.enabled {
+        let archive = Arc::new(
+            report_archive::ReportArchive::open_with_rotation(
+                config.report_archive.clone(),
+                config.rotation.for_output(report_archive::ROTATION_OUTPUT),
+            )
+            .context("Failed to open the report archive")?,
+        );
+        info!("   Report archive: {}", archive.dir().display());
+        if let Some(metrics) = &metrics {
+            metrics.watch_rotation(report_archive::ROTATION_OUTPUT, archive.rotation_stats());
+        }
+        let retention = config
+            .report_archive
+            .has_retention()
+            .then(|| archive.spawn_retention(metrics.clone()));
+        (Some(archive), retention)
+    } else {
+        (None, None)
+    };
+
+    // Each blob is audited at its own scheduled time instead of in one batch per interval
+    let scheduler = if config.scheduler.enabled {
+        if config.reaudit.enabled {
+            info!("   Per-blob scheduling enabled, [reaudit] settings are not used");
+        }
+        Some(
+            scheduler::AuditScheduler::open(
+                config.scheduler.clone(),
+                tokio::time::Duration::from_secs(config.audit_interval_secs),
+                &config.scheduler.state_path,
+            )
+            .context("Failed to load audit schedule")?,
+        )
+    } else {
+        None
+    };
+
+    // Failed blobs are re-audited on a backoff schedule, ahead of the regular queue
+    let reaudit = if config.reaudit.enabled && scheduler.is_none() {
+        Some(
+            reaudit::ReauditPolicy::open(config.reaudit.clone(), &config.reaudit.state_path)
+                .context("Failed to load re-audit state")?,
+        )
+    } else {
+        None
+    };
+
+    // Audit cycles run as tracked tasks so shutdown can wait for the one in flight
+    let state = Arc::new(tokio::sync::Mutex::new(DaemonState {
+        // Blobs already audited in the current epoch are skipped until the epoch changes
+        tracker: pending::EpochAuditTracker::new(),
+        // Blobs deleted by their owners are dropped from the pending set for good
+        deleted_blobs: std::collections::HashSet::new(),
+        reaudit,
+        scheduler,
+    }));
+    let ctx = Arc::new(DaemonContext {
+        config,
+        pipeline: audit_pipeline,
+        sui,
+        auditor,
+        breaker,
+        metrics,
+        notifier,
+        archive,
+        metadata_cache,
+    });
+    let mut in_flight = shutdown::InFlightAudits::new();
+
     loop {
+        // One audit cycle runs at a time: timers are only armed while none is in flight
+        let idle = in_flight.is_empty();
+        let (next_reaudit, next_due) = if idle {
+            let state = state.lock().await;
+            let retry_after = || {
+                ctx.breaker
+                    .retry_after(&ctx.config.walrus_aggregator_url)
+                    .unwrap_or_default()
+            };
+            // Due re-audits wait for the aggregator circuit to allow a probe
+            let next_reaudit = state
+                .reaudit
+                .as_ref()
+                .and_then(|policy| policy.next_due_in())
+                .map(|due_in| due_in.max(retry_after()));
+            let next_due = state
+                .scheduler
+                .as_ref()
+                .and_then(|s| s.next_due_in())
+                .map(|due_in| due_in.max(retry_after()));
+            (next_reaudit, next_due)
+        } else {
+            (None, None)
+        };
+
         tokio::select! {
-            _ = interval.tick() => {
+            _ = interval.tick(), if idle => {
                 info!("⏰ Executing periodic audit...");
 
-                // TODO: Query Sui for pending blobs to audit
-                let blobs_to_audit = fetch_pending_blobs(&config).await?;
+                let (ctx, state) = (Arc::clone(&ctx), Arc::clone(&state));
+                in_flight.spawn(|progress| async move {
+                    let mut state = state.lock().await;
+                    let DaemonState { tracker, deleted_blobs, reaudit, scheduler } = &mut *state;
+                    if let Err(e) = audit_pending_blobs(
+                        &ctx,
+                        tracker,
+                        deleted_blobs,
+                        reaudit.as_mut(),
+                        scheduler.as_mut(),
+                        &progress,
+                    )
+                    .await
+                    {
+                        error!("   ❌ Audit cycle failed: {:#}", e);
+                    }
+                });
+            }
 
-                if blobs_to_audit.is_empty() {
-                    info!("   ℹ️  No blobs to audit");
-                    continue;
-                }
+            _ = tokio::time::sleep(next_reaudit.unwrap_or_default()), if next_reaudit.is_some() => {
+                let (ctx, state) = (Arc::clone(&ctx), Arc::clone(&state));
+                in_flight.spawn(|progress| async move {
+                    let mut state = state.lock().await;
+                    let DaemonState { deleted_blobs, reaudit, .. } = &mut *state;
+                    let due = reaudit.as_ref().map(|policy| policy.due()).unwrap_or_default();
+                    info!("🔁 Re-auditing {} previously failed blobs", due.len());
+                    audit_blobs(&ctx, due, deleted_blobs, reaudit.as_mut(), &progress).await;
+                });
+            }
 
-                info!("   Found {} blobs to audit", blobs_to_audit.len());
+            _ = tokio::time::sleep(next_due.unwrap_or_default()), if next_due.is_some() => {
+                let (ctx, state) = (Arc::clone(&ctx), Arc::clone(&state));
+                in_flight.spawn(|progress| async move {
+                    let mut state = state.lock().await;
+                    let DaemonState { deleted_blobs, scheduler, .. } = &mut *state;
+                    let Some(scheduler) = scheduler.as_mut() else {
+                        return;
+                    };
+                    let due = scheduler.due();
+                    if due.is_empty() {
+                        return;
+                    }
 
-                // Execute audits
-                for blob_id in blobs_to_audit {
-                    match execute_audit_cycle(&config, &keystore, &blob_id).await {
-                        Ok(_) => {
-                            info!("   ✅ Blob {} audit successful", blob_id);
-                        }
-                        Err(e) => {
-                            error!("   ❌ Blob {} audit failed: {}", blob_id, e);
+                    info!("🗓️  Auditing {} scheduled blobs", due.len());
+                    let outcomes = audit_blobs(&ctx, due, deleted_blobs, None, &progress).await;
+                    for (blob_id, outcome) in outcomes {
+                        if let Err(e) = scheduler.record(&blob_id, outcome) {
+                            error!("   ❌ Failed to persist audit schedule for {}: {}", blob_id, e);
                         }
                     }
-                }
+                });
             }
 
+            Some(()) = in_flight.join_next(), if !idle => {}
+
             _ = shutdown.notified() => {
                 info!("Received shutdown signal, stopping daemon");
                 break;
@@ -389,215 +2125,474 @@ async fn run_daemon_mode(
         }
     }
 
+    // Let the in-flight audit sign, upload and submit its report instead of losing the work
+    if !in_flight.is_empty() {
+        let grace = ctx.config.shutdown_grace_secs;
+        info!(
+            "⏳ Waiting up to {}s for the in-flight audit (press Ctrl+C again to exit now)",
+            grace
+        );
+        let drained = in_flight.drain(tokio::time::Duration::from_secs(grace)).await;
+        if drained.cancelled == 0 {
+            info!("   ✅ In-flight audit finished");
+        } else {
+            warn!(
+                "   ⚠️  Shutdown grace period expired, cancelled {} audit cycles",
+                drained.cancelled
+            );
+            for blob_id in &drained.abandoned {
+                warn!("   ⚠️  Abandoned audit of blob {}", blob_id);
+            }
+        }
+    }
+
+    if let Some(health_monitor) = health_monitor {
+        health_monitor.abort();
+    }
+    if let Some(archive_retention) = archive_retention {
+        archive_retention.abort();
+    }
     Ok(())
 }
 
-/// Execute audit (using real IntegrityVerifier)
-async fn execute_audit(
-    config: &AuditorConfig,
-    blob_id: &str,
-) -> Result<types::AuditReport> {
-    use crate::integrity::{IntegrityVerifier, VerificationStatus};
-
-    info!("🔍 Starting audit for Blob: {}", blob_id);
-
-    // Create integrity verifier
-    let verifier = IntegrityVerifier::new(config.walrus_aggregator_url.clone());
-
-    // Execute real Merkle verification
-    let audit_data = verifier.audit_blob(blob_id).await.context("Integrity audit failed")?;
+/// Services shared by the daemon loop and its audit cycle tasks
+struct DaemonContext {
+    config: AuditorConfig,
+    pipeline: pipeline::AuditPipeline,
+    sui: sui_client::AuditSystemClient,
+    auditor: Option<chain_types::MoveId>,
+    breaker: Arc<breaker::CircuitBreaker>,
+    metrics: Option<Arc<metrics::Metrics>>,
+    notifier: Option<notify::NotificationDispatcher>,
+    archive: Option<Arc<report_archive::ReportArchive>>,
+    metadata_cache: Option<Arc<metadata_cache::BlobMetadataCache>>,
+}
 
-    info!("✅ Merkle verification completed:");
-    info!("   - Content hash (SHA-256): {}", audit_data.content_hash);
-    info!("   - Merkle root (Blake2b-256): {}", audit_data.merkle_root);
-    info!("   - Challenge stats: {}/{} successful",
-        audit_data.successful_verifications,
-        audit_data.total_challenges
-    );
+/// Scheduling state updated by audit cycles (one cycle holds it at a time)
+struct DaemonState {
+    tracker: pending::EpochAuditTracker,
+    deleted_blobs: std::collections::HashSet<String>,
+    reaudit: Option<reaudit::ReauditPolicy>,
+    scheduler: Option<scheduler::AuditScheduler>,
+}
 
-    // Convert IntegrityVerifier result to AuditReport format
-    let is_valid = audit_data.verification_status == VerificationStatus::Accessible
-        && audit_data.failed_verifications == 0;
+/// Serve `GET /metrics` in the Prometheus text format
+async fn serve_metrics(listener: tokio::net::TcpListener, metrics: Arc<metrics::Metrics>) {
+    use axum::{extract::State, http::header, routing::get, Router};
 
-    let failure_reason = if !is_valid {
-        Some(format!("Verification status: {:?}, failures: {}",
-            audit_data.verification_status,
-            audit_data.failed_verifications
-        ))
-    } else {
-        None
-    };
+    let app = Router::new()
+        .route(
+            "/metrics",
+            get(|State(metrics): State<Arc<metrics::Metrics>>| async move {
+                ([(header::CONTENT_TYPE, "text/plain; version=0.0.4")], metrics.render())
+            }),
+        )
+        .with_state(metrics);
 
-    // Parse content hash to byte array
-    let integrity_hash = hex::decode(&audit_data.content_hash)
-        .unwrap_or_else(|_| vec![0u8; 32]);
-
-    Ok(types::AuditReport {
-        blob_id: blob_id.to_string(),
-        blob_object_id: "0x000000000000000000000000000000000000000000000000000000000000000"
-            .to_string(), // TODO: Query real blob_object_id from Sui
-        auditor: "0x0000000000000000000000000000000000000000000000000000000000000000"
-            .to_string(), // TODO: Use actual auditor address
-        timestamp: chrono::Utc::now().timestamp() as u64,
-        challenge_epoch: 0, // TODO: Get current epoch from Sui
-        challenge_results: vec![], // Simplified version doesn't include detailed challenge results
-        total_challenges: audit_data.total_challenges,
-        successful_verifications: audit_data.successful_verifications,
-        failed_verifications: audit_data.failed_verifications,
-        integrity_hash,
-        pqc_signature: vec![], // Will be filled in sign_report()
-        pqc_algorithm: 3, // Dilithium3
-        is_valid,
-        failure_reason,
-    })
+    if let Err(e) = axum::serve(listener, app).await {
+        error!("Metrics endpoint stopped: {}", e);
+    }
 }
 
-/// Sign report
-fn sign_report(
-    mut report: types::AuditReport,
-    keystore: &keystore::Keystore,
-) -> Result<types::AuditReport> {
-    // Serialize report for signing (excluding signature field)
-    let report_for_signing = serde_json::json!({
-        "blob_id": report.blob_id,
-        "blob_object_id": report.blob_object_id,
-        "auditor": report.auditor,
-        "timestamp": report.timestamp,
-        "challenge_epoch": report.challenge_epoch,
-        "total_challenges": report.total_challenges,
-        "successful_verifications": report.successful_verifications,
-        "failed_verifications": report.failed_verifications,
-        "integrity_hash": report.integrity_hash,
-        "is_valid": report.is_valid,
-    });
+/// Number of AuditCreated events fetched per page when scanning for pending blobs
+const PENDING_PAGE_LIMIT: usize = 50;
+
+/// Page through blobs not yet audited in the current epoch, auditing each page
+/// before fetching the next so large backlogs are never held in memory at once
+///
+/// With a scheduler, pages are added to the schedule instead of being audited here.
+async fn audit_pending_blobs(
+    ctx: &DaemonContext,
+    tracker: &mut pending::EpochAuditTracker,
+    deleted_blobs: &mut std::collections::HashSet<String>,
+    mut reaudit: Option<&mut reaudit::ReauditPolicy>,
+    mut scheduler: Option<&mut scheduler::AuditScheduler>,
+    progress: &shutdown::InFlightAudit,
+) -> Result<()> {
+    let sui = &ctx.sui;
+    let epoch = sui.current_epoch().await.context("Failed to query current epoch")?;
+    if tracker.begin_scan(epoch) {
+        info!("   Auditing blobs for epoch {}", epoch);
+    }
+    // Cached per epoch: a new epoch re-reads the registration and reputation
+    ctx.pipeline
+        .preflight()
+        .await
+        .context("Auditor registration preflight failed")?;
 
-    let report_bytes = serde_json::to_vec(&report_for_signing)?;
-    let signature = keystore.signer().sign(&report_bytes)?;
+    let mut cursor = None;
+    let mut found = 0;
+    loop {
+        let page = sui
+            .list_pending_audit_blobs(epoch, ctx.auditor.as_ref(), PENDING_PAGE_LIMIT, cursor)
+            .await?;
+
+        let mut pending = tracker.take_pending(&page);
+        pending.retain(|blob_id| !deleted_blobs.contains(blob_id));
+
+        if let Some(scheduler) = scheduler.as_deref_mut() {
+            let added = scheduler
+                .add_all(pending.iter().cloned())
+                .context("Failed to persist audit schedule")?;
+            if added > 0 {
+                found += added;
+                info!("   Scheduled {} new blobs ({} in total)", added, scheduler.len());
+            }
+            for blob_id in pending {
+                tracker.mark_audited(blob_id);
+            }
+            match page.next_cursor {
+                Some(next) => cursor = Some(next),
+                None => break,
+            }
+            continue;
+        }
 
-    report.pqc_signature = signature;
-    report.pqc_algorithm = 3; // Dilithium3
+        let blobs_to_audit = match reaudit.as_deref() {
+            Some(policy) => policy.plan(&pending),
+            None => pending,
+        };
+
+        if !blobs_to_audit.is_empty() {
+            found += blobs_to_audit.len();
+            info!("   Found {} blobs to audit", blobs_to_audit.len());
+            prefetch_blob_metadata(ctx, &page, &blobs_to_audit).await;
+            let audited = audit_blobs(
+                ctx,
+                blobs_to_audit,
+                deleted_blobs,
+                reaudit.as_deref_mut(),
+                progress,
+            )
+            .await;
+            for (blob_id, _) in audited {
+                tracker.mark_audited(blob_id);
+            }
+        }
 
-    debug!("Report signed: {} bytes", report.pqc_signature.len());
+        if ctx.breaker.retry_after(&ctx.config.walrus_aggregator_url).is_some() {
+            break;
+        }
+        match page.next_cursor {
+            Some(next) => cursor = Some(next),
+            None => break,
+        }
+    }
 
-    Ok(report)
+    if found == 0 {
+        info!("   ℹ️  No blobs to audit");
+    }
+    if let Some(cache) = &ctx.metadata_cache {
+        let stats = cache.stats();
+        info!(
+            "   Blob metadata cache: {} hits, {} misses ({} entries)",
+            stats.hits, stats.misses, stats.entries
+        );
+    }
+    Ok(())
 }
 
-/// Encrypt report (call Seal API)
-async fn encrypt_report(
-    report: &types::AuditReport,
-    seal_api_url: &str,
-    auditor_address: &str,
-    package_id: &str,
-) -> Result<seal_client::EncryptResult> {
-    let seal_config = seal_client::SealApiConfig {
-        api_url: seal_api_url.to_string(),
-        timeout_secs: 30,
-    };
+/// Fill the metadata cache for a page of blobs with batched `sui_multiGetObjects` calls,
+/// so the storage node audits that follow do not query the chain one blob at a time
+///
+/// Only storage node audits read chain metadata; failures are left to the audits themselves.
+async fn prefetch_blob_metadata(
+    ctx: &DaemonContext,
+    page: &sui_client::PendingBlobPage,
+    blob_ids: &[String],
+) {
+    if ctx.metadata_cache.is_none() || !ctx.config.use_storage_node_challenges {
+        return;
+    }
+    let object_ids: Vec<String> = page
+        .pending
+        .iter()
+        .filter(|blob| blob_ids.contains(&blob.blob_id))
+        .filter_map(|blob| blob.blob_object_id.map(|id| id.to_string()))
+        .collect();
+    if object_ids.is_empty() {
+        return;
+    }
+    let ids: Vec<&str> = object_ids.iter().map(String::as_str).collect();
+    if let Err(e) = ctx.sui.get_blob_metadata_batch(&ids).await {
+        warn!("   ⚠️  Failed to prefetch blob metadata: {}", e);
+    }
+}
 
-    let seal_client = seal_client::SealClient::new(seal_config)?;
+/// Audit blobs in order, feeding outcomes to the re-audit policy
+///
+/// Stops early while the aggregator circuit is open: the remaining blobs stay pending
+/// (and due re-audits stay due) instead of all being recorded as failures.
+/// Returns the blobs that reached an outcome, with the outcome. The blob being audited is
+/// reported through `progress` so a cancelled shutdown can name it.
+///
+/// Blobs are audited one at a time, and each audit takes a slot from the pipeline's resource
+/// guard, so a degraded decision's `max_concurrency` also holds back scheduled audits.
+async fn audit_blobs(
+    ctx: &DaemonContext,
+    blob_ids: Vec<String>,
+    deleted_blobs: &mut std::collections::HashSet<String>,
+    mut reaudit: Option<&mut reaudit::ReauditPolicy>,
+    progress: &shutdown::InFlightAudit,
+) -> Vec<(String, reaudit::AuditOutcome)> {
+    let DaemonContext { config, pipeline, breaker, metrics, notifier, archive, .. } = ctx;
+    let total = blob_ids.len();
+    let mut completed = Vec::new();
+    for (audited, blob_id) in blob_ids.into_iter().enumerate() {
+        if let Some(retry_after) = breaker.retry_after(&config.walrus_aggregator_url) {
+            warn!(
+                "   ⏸️  Aggregator circuit open, pausing scheduling for {}s ({} blobs deferred)",
+                retry_after.as_secs().max(1),
+                total - audited
+            );
+            break;
+        }
 
-    // First check health status
-    seal_client
-        .health_check()
-        .await
-        .context("Seal API health check failed")?;
+        progress.start(&blob_id);
+        let result = pipeline.run_once(&blob_id).await;
+        progress.finish();
 
-    // Serialize report to JSON
-    let report_json = serde_json::to_string_pretty(report).context("Failed to serialize report")?;
+        let outcome = match result {
+            Ok(outcome) => {
+                if let Some(metrics) = metrics {
+                    metrics.record_successful_audit(chrono::Utc::now().timestamp() as u64);
+                }
+                if let Some(archive) = archive {
+                    archive_report(archive, metrics.as_deref(), &outcome.report);
+                }
+                let audit_outcome = reaudit::AuditOutcome::from_audit(
+                    &outcome.status,
+                    outcome.report.failed_verifications,
+                );
+                if let Some(notifier) = notifier {
+                    notify_audit_outcome(notifier, audit_outcome, &outcome.report);
+                }
+                audit_outcome
+            }
+            Err(error::AuditorError::CircuitOpen { endpoint, retry_after_secs }) => {
+                warn!(
+                    "   ⏸️  Circuit open for {}, pausing scheduling for {}s ({} blobs deferred)",
+                    endpoint,
+                    retry_after_secs,
+                    total - audited
+                );
+                break;
+            }
+            Err(e) => {
+                error!("   ❌ Blob {} audit failed: {}", blob_id, e);
+                if let Some(hint) = seal_error_hint(&e) {
+                    error!("      {}", hint);
+                }
+                if let Some(notifier) = notifier {
+                    notifier.notify(notify::Notification::audit_error(&blob_id, &e));
+                }
+                continue;
+            }
+        };
 
-    debug!("Report JSON size: {} bytes", report_json.len());
+        match outcome {
+            reaudit::AuditOutcome::Deleted => {
+                info!("   🗑️  Blob {} deleted by owner, removed from pending set", blob_id);
+                deleted_blobs.insert(blob_id.clone());
+            }
+            reaudit::AuditOutcome::Pass => info!("   ✅ Blob {} audit successful", blob_id),
+            reaudit::AuditOutcome::Fail => warn!("   ⚠️  Blob {} failed integrity audit", blob_id),
+        }
 
-    // Call Seal API to encrypt
-    let (encrypted_data, symmetric_key, metadata) = seal_client
-        .encrypt_report(&report_json, auditor_address, package_id, 2)
-        .await
-        .context("Seal encryption failed")?;
+        if let Some(policy) = reaudit.as_deref_mut() {
+            if let Err(e) = policy.record(&blob_id, outcome) {
+                error!("   ❌ Failed to persist re-audit state for {}: {}", blob_id, e);
+            }
+        }
+        completed.push((blob_id, outcome));
+    }
 
-    Ok(seal_client::EncryptResult {
-        encrypted_data,
-        symmetric_key,
-        metadata,
-    })
+    log_circuit_status(breaker);
+    completed
 }
 
-/// Upload to Walrus (placeholder implementation)
-async fn upload_to_walrus(aggregator_url: &str, data: &[u8]) -> Result<String> {
-    debug!(
-        "Uploading {} bytes to Walrus ({})",
-        data.len(),
-        aggregator_url
-    );
-
-    // TODO: Actual Walrus API call
-    // Reference implementation in storage_node_client.rs
+/// Write a signed report to the local archive (failures are logged, the audit still counts)
+fn archive_report(
+    archive: &report_archive::ReportArchive,
+    metrics: Option<&metrics::Metrics>,
+    report: &types::AuditReport,
+) {
+    match archive.store(report) {
+        Ok(path) => {
+            info!("   🗄️  Report archived: {}", path.display());
+            if let Some(metrics) = metrics {
+                metrics.record_report_archived();
+            }
+        }
+        Err(e) => error!(
+            "   ❌ Failed to archive report of {}: {}",
+            report.blob_id, e
+        ),
+    }
+}
 
-    // Return example Blob ID
-    Ok("0xabcdef1234567890abcdef1234567890abcdef1234567890abcdef1234567890".to_string())
+/// Queue the notification for a completed audit (failures as `audit_failed`)
+fn notify_audit_outcome(
+    notifier: &notify::NotificationDispatcher,
+    outcome: reaudit::AuditOutcome,
+    report: &types::AuditReport,
+) {
+    let event = match outcome {
+        reaudit::AuditOutcome::Fail => notify::NotifyEvent::AuditFailed,
+        reaudit::AuditOutcome::Pass | reaudit::AuditOutcome::Deleted => {
+            notify::NotifyEvent::AuditCompleted
+        }
+    };
+    if !notifier.is_subscribed(event) {
+        return;
+    }
+    match notify::Notification::audit_report(event, report) {
+        Ok(notification) => notifier.notify(notification),
+        Err(e) => warn!(
+            "   ⚠️  Failed to build notification for {}: {}",
+            report.blob_id, e
+        ),
+    }
 }
 
-/// Get list of blobs pending audit
-async fn fetch_pending_blobs(_config: &AuditorConfig) -> Result<Vec<String>> {
-    // TODO: Query Sui for pending blobs
-    Ok(vec![])
+/// Log circuit state and transition counters for every endpoint that has ever tripped
+fn log_circuit_status(breaker: &breaker::CircuitBreaker) {
+    for status in breaker.status() {
+        if status.transitions == breaker::TransitionCounts::default() {
+            continue;
+        }
+        info!(
+            "   🔌 Circuit {}: {:?} (opened {}, half-opened {}, closed {}, rejected {})",
+            status.endpoint,
+            status.state,
+            status.transitions.opened,
+            status.transitions.half_opened,
+            status.transitions.closed,
+            status.transitions.rejected
+        );
+    }
 }
 
-/// Execute complete audit cycle (daemon mode)
-async fn execute_audit_cycle(
-    config: &AuditorConfig,
-    keystore: &keystore::Keystore,
-    blob_id: &str,
-) -> Result<()> {
-    // 1. Execute audit
-    let audit_report = execute_audit(config, blob_id).await?;
-
-    // 2. Sign
-    let signed_report = sign_report(audit_report, keystore)?;
-
-    // 3. Encrypt (if enabled)
-    let encrypted_data = if config.enable_seal_encryption {
-        let seal_api_url = config
-            .seal_api_url
-            .as_ref()
-            .context("Seal API URL not configured")?;
-
-        // TODO: Get actual addresses from config
-        let auditor_addr =
-            "0x1234567890abcdef1234567890abcdef1234567890abcdef1234567890abcdef";
-        let pkg_id = "0x8afa5d31dbaa0a8fb07082692940ca3d56b5e856c5126cb5a3693f0a4de63b82";
-
-        let encrypted = encrypt_report(&signed_report, seal_api_url, auditor_addr, pkg_id).await?;
-        Some(encrypted.encrypted_data)
-    } else {
-        None
-    };
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pqc_signer::Dilithium3Signer;
+
+    fn unsigned_report() -> types::AuditReport {
+        serde_json::from_value(serde_json::json!({
+            "blob_id": "blob-cross-sign",
+            "blob_object_id": "0xobject",
+            "auditor": "0xauditor",
+            "timestamp": 1_700_000_000u64,
+            "challenge_epoch": 7,
+            "challenge_results": [],
+            "total_challenges": 10,
+            "successful_verifications": 10,
+            "failed_verifications": 0,
+            "integrity_hash": vec![0u8; 32],
+            "pqc_signature": [],
+            "pqc_algorithm": 0,
+            "is_valid": true,
+            "failure_reason": null,
+        }))
+        .unwrap()
+    }
 
-    // 4. Upload
-    let data_to_upload = if let Some(encrypted) = encrypted_data.as_ref() {
-        base64::decode(encrypted).context("Failed to decode encrypted data")?
-    } else {
-        serde_json::to_vec(&signed_report).context("Failed to serialize report")?
-    };
+    fn verify_with_command(report: &types::AuditReport, public_key: &[u8]) -> bool {
+        let mut verdict = verdict::ReportVerification::new(report);
+        verify_report_command(
+            report,
+            Some(&hex::encode(public_key)),
+            None,
+            &[],
+            None,
+            1,
+            &mut verdict,
+        )
+        .unwrap();
+        verdict.is_valid()
+    }
 
-    let _walrus_blob_id = upload_to_walrus(&config.walrus_aggregator_url, &data_to_upload).await?;
+    #[test]
+    fn test_report_signed_by_node_verifies_with_report_manager() {
+        let dir = std::env::temp_dir().join(format!("cross_sign_{}", rand::random::<u32>()));
+        let keystore = keystore::Keystore::generate_and_save(&dir, audit_report::PqcAlgorithm::default()).unwrap();
+
+        let report = audit_report::AuditReportGenerator::new(keystore.signer().clone(), None)
+            .sign_report(unsigned_report())
+            .unwrap();
+        assert_eq!(
+            report::ReportManager::signing_payload(&report).unwrap(),
+            report.signing_bytes()
+        );
+        assert!(
+            report::ReportManager::verify_report(&report, &keystore.public_key_bytes()).unwrap()
+        );
 
-    // 5. Submit to Sui (TODO)
+        std::fs::remove_dir_all(&dir).ok();
+    }
 
-    Ok(())
-}
+    #[test]
+    fn test_report_signed_by_report_manager_verifies_with_node() {
+        let mut signer = Dilithium3Signer::new();
+        signer.generate_keypair().unwrap();
+        let public_key = signer.public_key().to_vec();
 
-/// Seal encryption result
-#[allow(dead_code)]
-struct EncryptResult {
-    encrypted_data: String,
-    symmetric_key: String,
-    metadata: seal_client::EncryptMetadata,
-}
+        let mut report = unsigned_report();
+        report::ReportManager::new(signer).sign_report(&mut report).unwrap();
+        assert!(verify_with_command(&report, &public_key));
 
-// Base64 decode helper
-mod base64 {
-    use base64::{engine::general_purpose, Engine as _};
+        report.failed_verifications = 1;
+        assert!(!verify_with_command(&report, &public_key));
+    }
 
-    pub fn decode(encoded: &str) -> Result<Vec<u8>, base64::DecodeError> {
-        general_purpose::STANDARD.decode(encoded)
+    #[tokio::test]
+    async fn test_metrics_endpoint_reports_mocked_audit() {
+        use crate::test_support::{AggregatorMode, FakeAggregator};
+
+        let aggregator = FakeAggregator::start(vec![7u8; 64 * 1024], AggregatorMode::Healthy).await;
+        let mut config = AuditorConfig {
+            walrus_aggregator_url: aggregator.url().to_string(),
+            use_storage_node_challenges: false,
+            capture_http: false,
+            enable_seal_encryption: false,
+            submit_to_sui: false,
+            blind_blob_ids: false,
+            ..AuditorConfig::default()
+        };
+        config.dedup.enabled = false;
+        config.baseline.enabled = false;
+        config.commitment.enabled = false;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/metrics", listener.local_addr().unwrap());
+        let metrics = Arc::new(metrics::Metrics::new());
+        tokio::spawn(serve_metrics(listener, Arc::clone(&metrics)));
+
+        let dir = std::env::temp_dir().join(format!("metrics_{}", rand::random::<u32>()));
+        let keystore = keystore::Keystore::generate_and_save(&dir, audit_report::PqcAlgorithm::default()).unwrap();
+        let pipeline = pipeline::AuditPipeline::from_config(&config, &keystore)
+            .unwrap()
+            .with_metrics(Arc::clone(&metrics));
+        let blob_id = "eRrTusk8yshFQpkemgDnbg0f4-qDo623V2NpeVG1Zcg";
+        let (report, _) = pipeline.audit(blob_id).await.unwrap();
+        assert!(report.is_valid);
+        std::fs::remove_dir_all(&dir).ok();
+
+        let response = reqwest::get(&url).await.unwrap();
+        assert!(response.status().is_success());
+        let body = response.text().await.unwrap();
+        let verified = format!(
+            "challenges_total{{verified=\"true\"}} {}",
+            report.successful_verifications
+        );
+        for line in [
+            "audits_total{result=\"accessible\"} 1",
+            verified.as_str(),
+            "audit_duration_seconds_count 1",
+            "walrus_download_bytes_sum 65536",
+        ] {
+            assert!(body.lines().any(|l| l == line), "missing {:?} in:\n{}", line, body);
+        }
     }
 }
@@ -0,0 +1,201 @@
+//! Blob 鏈上元數據緩存
+//!
+//! 守護模式下同一 Blob 會被反覆審計，每次都以 `sui_getObject` 重新讀取相同的鏈上元數據，
+//! 對公共全節點造成不必要的壓力（並偶爾觸發限流）。[`BlobMetadataCache`] 按 Blob 對象 ID
+//! 緩存 [`BlobMetadata`]，在 TTL 內不再請求 RPC；查詢失敗不緩存。
+//!
+//! Blob 元數據除存儲期（續期會推遲 `end_epoch`）外不可變，因此默認 TTL 較長
+//! （`blob_metadata_cache_ttl_secs`，默認一小時；設為 0 時不緩存）。
+//!
+//! 緩存通過 `Arc` 在守護進程的 [`AuditSystemClient`] 與審計器之間共享：守護進程在每輪開始時以
+//! [`AuditSystemClient::get_blob_metadata_batch`] 批量預取待審計 Blob 的元數據
+//! （`sui_multiGetObjects`），之後的逐個查詢直接命中緩存。命中與未命中次數記錄在
+//! [`MetadataCacheStats`] 中，並在設置了 [`Metrics`] 時導出為 `blob_metadata_cache_total`。
+//!
+//! [`AuditSystemClient`]: crate::sui_client::AuditSystemClient
+//! [`AuditSystemClient::get_blob_metadata_batch`]: crate::sui_client::AuditSystemClient::get_blob_metadata_batch
+
+use crate::chain_types::MoveId;
+use crate::metrics::Metrics;
+use crate::types::BlobMetadata;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// 默認的元數據緩存時間（秒）
+pub const DEFAULT_BLOB_METADATA_CACHE_TTL_SECS: u64 = 3600;
+
+/// 緩存命中統計
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MetadataCacheStats {
+    /// 命中次數
+    pub hits: u64,
+
+    /// 未命中（含已過期）次數
+    pub misses: u64,
+
+    /// 當前緩存的條目數（含尚未清理的過期條目）
+    pub entries: usize,
+}
+
+/// 按 Blob 對象 ID 緩存的鏈上元數據
+pub struct BlobMetadataCache {
+    ttl: Duration,
+    entries: Mutex<HashMap<MoveId, (Instant, BlobMetadata)>>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+    metrics: Option<Arc<Metrics>>,
+}
+
+impl BlobMetadataCache {
+    /// 創建緩存，結果保留 `ttl`
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: Mutex::new(HashMap::new()),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+            metrics: None,
+        }
+    }
+
+    /// 命中與未命中同時記錄到指標
+    pub fn with_metrics(mut self, metrics: Arc<Metrics>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// 查詢緩存（計入命中統計；過期條目被移除並計為未命中）
+    pub fn get(&self, blob_object_id: &MoveId) -> Option<BlobMetadata> {
+        let cached = {
+            let mut entries = self.entries.lock().unwrap();
+            match entries.get(blob_object_id) {
+                Some((fetched_at, metadata)) if fetched_at.elapsed() < self.ttl => {
+                    Some(metadata.clone())
+                }
+                Some(_) => {
+                    entries.remove(blob_object_id);
+                    None
+                }
+                None => None,
+            }
+        };
+
+        let hit = cached.is_some();
+        if hit {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+        } else {
+            self.misses.fetch_add(1, Ordering::Relaxed);
+        }
+        if let Some(metrics) = &self.metrics {
+            metrics.record_metadata_cache_lookup(hit);
+        }
+        cached
+    }
+
+    /// 記錄剛從鏈上讀取的元數據
+    pub fn insert(&self, blob_object_id: MoveId, metadata: BlobMetadata) {
+        self.entries
+            .lock()
+            .unwrap()
+            .insert(blob_object_id, (Instant::now(), metadata));
+    }
+
+    /// 丟棄某 Blob 的緩存（例如得知其已續期）
+    pub fn invalidate(&self, blob_object_id: &MoveId) {
+        self.entries.lock().unwrap().remove(blob_object_id);
+    }
+
+    /// 命中統計
+    pub fn stats(&self) -> MetadataCacheStats {
+        MetadataCacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            entries: self.entries.lock().unwrap().len(),
+        }
+    }
+}
+
+impl std::fmt::Debug for BlobMetadataCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BlobMetadataCache")
+            .field("ttl", &self.ttl)
+            .field("stats", &self.stats())
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metadata(blob_id: &str) -> BlobMetadata {
+        BlobMetadata {
+            blob_object_id: "0x1234".to_string(),
+            blob_id: blob_id.to_string(),
+            merkle_root: vec![],
+            blob_size: 1024,
+            encoding_type: 1,
+            encoding_k: 334,
+            encoding_n: 1000,
+            registered_epoch: 1,
+            certified_epoch: Some(1),
+            start_epoch: 1,
+            end_epoch: 10,
+            owner: "0x5678".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_hits_within_ttl() {
+        let cache = BlobMetadataCache::new(Duration::from_secs(60));
+        let id = MoveId([1; 32]);
+
+        assert!(cache.get(&id).is_none());
+        cache.insert(id, metadata("blob-1"));
+        assert_eq!(cache.get(&id).unwrap().blob_id, "blob-1");
+        assert!(cache.get(&MoveId([2; 32])).is_none());
+
+        assert_eq!(
+            cache.stats(),
+            MetadataCacheStats {
+                hits: 1,
+                misses: 2,
+                entries: 1
+            }
+        );
+
+        cache.invalidate(&id);
+        assert!(cache.get(&id).is_none());
+    }
+
+    #[test]
+    fn test_expires_after_ttl() {
+        let cache = BlobMetadataCache::new(Duration::from_millis(20));
+        let id = MoveId([1; 32]);
+        cache.insert(id, metadata("blob-1"));
+        assert!(cache.get(&id).is_some());
+
+        std::thread::sleep(Duration::from_millis(40));
+        assert!(cache.get(&id).is_none());
+        assert_eq!(cache.stats().entries, 0);
+        assert_eq!(cache.stats().misses, 1);
+    }
+
+    #[test]
+    fn test_lookups_recorded_in_metrics() {
+        let metrics = Arc::new(Metrics::new());
+        let cache =
+            BlobMetadataCache::new(Duration::from_secs(60)).with_metrics(Arc::clone(&metrics));
+        let id = MoveId([1; 32]);
+        cache.get(&id);
+        cache.insert(id, metadata("blob-1"));
+        cache.get(&id);
+        cache.get(&id);
+
+        let rendered = metrics.render();
+        assert!(rendered.contains("blob_metadata_cache_total{result=\"hit\"} 2"));
+        assert!(rendered.contains("blob_metadata_cache_total{result=\"miss\"} 1"));
+    }
+}
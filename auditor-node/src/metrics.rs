@@ -0,0 +1,506 @@
+//! Prometheus 指標
+//!
+//! 守護進程長時間運行時，日誌之外的唯一觀測手段。審計代碼只通過 [`Metrics`] 的
+//! `record_*` 方法記錄，不直接接觸 `prometheus`；指標保存在各自的註冊表中，
+//! 由 [`Metrics::render`] 以文本格式導出。HTTP 端點由守護進程在配置了
+//! `metrics_listen_addr` 時啟動，單次審計路徑不會啟動服務器。
+//!
+//! | 指標 | 類型 | 標籤 |
+//! |------|------|------|
+//! | `audits_total` | counter | `result`: accessible / unreachable / corrupted / deleted / expired / error |
+//! | `challenges_total` | counter | `verified`: true / false |
+//! | `audit_duration_seconds` | histogram | |
+//! | `walrus_download_bytes` | histogram | |
+//! | `storage_node_errors_total` | counter | `kind`: 見 [`storage_node_error_kind`] |
+//! | `sui_submission_failures_total` | counter | |
+//! | `last_successful_audit_timestamp` | gauge | |
+//! | `storage_node_healthy` | gauge | `node`: 存儲節點 URL（1 健康，0 已排除） |
+//! | `webhook_deliveries_total` | counter | `result`: delivered / failed / dropped（見 [`crate::notify`]） |
+//! | `reports_archived_total` | counter | |
+//! | `report_archive_bytes` | gauge | |
+//! | `blob_metadata_cache_total` | counter | `result`: hit / miss（見 [`crate::metadata_cache`]） |
+//! | `resource_disk_available_bytes` | gauge | `dir`: 資源守衛監控的目錄（見 [`crate::resources`]） |
+//! | `resource_memory_available_bytes` | gauge | |
+//! | `resource_decisions_total` | counter | `action`: degraded / refused |
+//! | `log_rotation_files_total` | counter | `output`: 見 [`crate::rotating_writer`]；`event`: rotated / deleted / compressed |
+//!
+//! 輪轉計數器保存在各個 [`RotatingWriter`](crate::rotating_writer::RotatingWriter) 中，
+//! 以 [`Metrics::watch_rotation`] 登記後在每次導出時同步。
+
+use crate::error::AuditorError;
+use crate::integrity::VerificationStatus;
+use crate::resources::{ResourceAction, ResourceSnapshot};
+use crate::rotating_writer::RotationStats;
+use prometheus::{
+    exponential_buckets, Encoder, Histogram, HistogramOpts, IntCounter, IntCounterVec, IntGauge,
+    IntGaugeVec, Opts, Registry, TextEncoder,
+};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// 審計耗時的桶邊界（秒）
+const AUDIT_DURATION_BUCKETS: &[f64] = &[0.1, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0, 120.0, 300.0];
+
+/// 審計結果標籤（審計返回錯誤時）
+pub const RESULT_ERROR: &str = "error";
+
+/// 通知發送結果標籤：已送達
+pub const WEBHOOK_DELIVERED: &str = "delivered";
+
+/// 通知發送結果標籤：重試後仍失敗
+pub const WEBHOOK_FAILED: &str = "failed";
+
+/// 通知發送結果標籤：隊列已滿被丟棄
+pub const WEBHOOK_DROPPED: &str = "dropped";
+
+/// 審計節點指標
+pub struct Metrics {
+    registry: Registry,
+    audits_total: IntCounterVec,
+    challenges_total: IntCounterVec,
+    audit_duration_seconds: Histogram,
+    walrus_download_bytes: Histogram,
+    storage_node_errors_total: IntCounterVec,
+    sui_submission_failures_total: IntCounter,
+    last_successful_audit_timestamp: IntGauge,
+    storage_node_healthy: IntGaugeVec,
+    webhook_deliveries_total: IntCounterVec,
+    reports_archived_total: IntCounter,
+    report_archive_bytes: IntGauge,
+    blob_metadata_cache_total: IntCounterVec,
+    resource_disk_available_bytes: IntGaugeVec,
+    resource_memory_available_bytes: IntGauge,
+    resource_decisions_total: IntCounterVec,
+    log_rotation_files_total: IntCounterVec,
+    /// 已登記的輪轉計數器（輸出名稱 → 計數器）
+    rotations: Mutex<Vec<(String, Arc<RotationStats>)>>,
+}
+
+impl Metrics {
+    /// 創建指標並註冊到新的註冊表
+    pub fn new() -> Self {
+        let audits_total = IntCounterVec::new(
+            Opts::new("audits_total", "Completed audits by result"),
+            &["result"],
+        )
+        .expect("valid metric");
+        let challenges_total = IntCounterVec::new(
+            Opts::new(
+                "challenges_total",
+                "Challenges issued, by whether the proof verified",
+            ),
+            &["verified"],
+        )
+        .expect("valid metric");
+        let audit_duration_seconds = Histogram::with_opts(
+            HistogramOpts::new("audit_duration_seconds", "Wall-clock duration of an audit")
+                .buckets(AUDIT_DURATION_BUCKETS.to_vec()),
+        )
+        .expect("valid metric");
+        // 4 KiB 到 4 GiB
+        let walrus_download_bytes = Histogram::with_opts(
+            HistogramOpts::new("walrus_download_bytes", "Bytes downloaded per blob audit")
+                .buckets(exponential_buckets(4096.0, 4.0, 11).expect("valid buckets")),
+        )
+        .expect("valid metric");
+        let storage_node_errors_total = IntCounterVec::new(
+            Opts::new(
+                "storage_node_errors_total",
+                "Failed storage node challenge attempts",
+            ),
+            &["kind"],
+        )
+        .expect("valid metric");
+        let sui_submission_failures_total = IntCounter::new(
+            "sui_submission_failures_total",
+            "Failed audit record submissions to Sui",
+        )
+        .expect("valid metric");
+        let last_successful_audit_timestamp = IntGauge::new(
+            "last_successful_audit_timestamp",
+            "Unix time of the last audit cycle that completed",
+        )
+        .expect("valid metric");
+        let storage_node_healthy = IntGaugeVec::new(
+            Opts::new(
+                "storage_node_healthy",
+                "Whether a storage node passes health checks and receives challenges",
+            ),
+            &["node"],
+        )
+        .expect("valid metric");
+        let webhook_deliveries_total = IntCounterVec::new(
+            Opts::new(
+                "webhook_deliveries_total",
+                "Audit notifications by delivery result",
+            ),
+            &["result"],
+        )
+        .expect("valid metric");
+        let reports_archived_total = IntCounter::new(
+            "reports_archived_total",
+            "Signed reports written to the local report archive",
+        )
+        .expect("valid metric");
+        let report_archive_bytes = IntGauge::new(
+            "report_archive_bytes",
+            "Total size of the local report archive after the last retention run",
+        )
+        .expect("valid metric");
+        let blob_metadata_cache_total = IntCounterVec::new(
+            Opts::new(
+                "blob_metadata_cache_total",
+                "Blob metadata lookups by whether the cache answered them",
+            ),
+            &["result"],
+        )
+        .expect("valid metric");
+        let resource_disk_available_bytes = IntGaugeVec::new(
+            Opts::new(
+                "resource_disk_available_bytes",
+                "Free disk space in each directory watched by the resource guard",
+            ),
+            &["dir"],
+        )
+        .expect("valid metric");
+        let resource_memory_available_bytes = IntGauge::new(
+            "resource_memory_available_bytes",
+            "Available memory at the last resource check",
+        )
+        .expect("valid metric");
+        let resource_decisions_total = IntCounterVec::new(
+            Opts::new(
+                "resource_decisions_total",
+                "Resource guard decisions that degraded or refused an audit",
+            ),
+            &["action"],
+        )
+        .expect("valid metric");
+        let log_rotation_files_total = IntCounterVec::new(
+            Opts::new(
+                "log_rotation_files_total",
+                "Files rotated, deleted by retention or compressed per append-only output",
+            ),
+            &["output", "event"],
+        )
+        .expect("valid metric");
+
+        let registry = Registry::new();
+        for collector in [
+            Box::new(audits_total.clone()) as Box<dyn prometheus::core::Collector>,
+            Box::new(challenges_total.clone()),
+            Box::new(audit_duration_seconds.clone()),
+            Box::new(walrus_download_bytes.clone()),
+            Box::new(storage_node_errors_total.clone()),
+            Box::new(sui_submission_failures_total.clone()),
+            Box::new(last_successful_audit_timestamp.clone()),
+            Box::new(storage_node_healthy.clone()),
+            Box::new(webhook_deliveries_total.clone()),
+            Box::new(reports_archived_total.clone()),
+            Box::new(report_archive_bytes.clone()),
+            Box::new(blob_metadata_cache_total.clone()),
+            Box::new(resource_disk_available_bytes.clone()),
+            Box::new(resource_memory_available_bytes.clone()),
+            Box::new(resource_decisions_total.clone()),
+            Box::new(log_rotation_files_total.clone()),
+        ] {
+            registry
+                .register(collector)
+                .expect("metric names are unique");
+        }
+
+        Self {
+            registry,
+            audits_total,
+            challenges_total,
+            audit_duration_seconds,
+            walrus_download_bytes,
+            storage_node_errors_total,
+            sui_submission_failures_total,
+            last_successful_audit_timestamp,
+            storage_node_healthy,
+            webhook_deliveries_total,
+            reports_archived_total,
+            report_archive_bytes,
+            blob_metadata_cache_total,
+            resource_disk_available_bytes,
+            resource_memory_available_bytes,
+            resource_decisions_total,
+            log_rotation_files_total,
+            rotations: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// 記錄一次審計的結果與耗時（`result` 通常來自 [`audit_result`]）
+    pub fn record_audit(&self, result: &str, duration: Duration) {
+        self.audits_total.with_label_values(&[result]).inc();
+        self.audit_duration_seconds.observe(duration.as_secs_f64());
+    }
+
+    /// 記錄挑戰結果
+    pub fn record_challenges(&self, verified: u64, failed: u64) {
+        self.challenges_total
+            .with_label_values(&["true"])
+            .inc_by(verified);
+        self.challenges_total
+            .with_label_values(&["false"])
+            .inc_by(failed);
+    }
+
+    /// 記錄一次從 Aggregator 下載的字節數
+    pub fn record_download(&self, bytes: u64) {
+        self.walrus_download_bytes.observe(bytes as f64);
+    }
+
+    /// 記錄一次失敗的存儲節點挑戰請求（每次嘗試各計一次）
+    pub fn record_storage_node_error(&self, error: &AuditorError) {
+        self.storage_node_errors_total
+            .with_label_values(&[storage_node_error_kind(error)])
+            .inc();
+    }
+
+    /// 記錄一次失敗的 Sui 提交
+    pub fn record_sui_submission_failure(&self) {
+        self.sui_submission_failures_total.inc();
+    }
+
+    /// 記錄一個審計週期成功完成的時間
+    pub fn record_successful_audit(&self, timestamp: u64) {
+        self.last_successful_audit_timestamp.set(timestamp as i64);
+    }
+
+    /// 記錄存儲節點的健康狀態
+    pub fn record_node_health(&self, node: &str, healthy: bool) {
+        self.storage_node_healthy
+            .with_label_values(&[node])
+            .set(i64::from(healthy));
+    }
+
+    /// 記錄一條通知的發送結果（`WEBHOOK_*` 標籤）
+    pub fn record_webhook_delivery(&self, result: &str) {
+        self.webhook_deliveries_total
+            .with_label_values(&[result])
+            .inc();
+    }
+
+    /// 記錄一份寫入本地存檔的報告
+    pub fn record_report_archived(&self) {
+        self.reports_archived_total.inc();
+    }
+
+    /// 記錄保留策略執行後的存檔總大小
+    pub fn record_report_archive_size(&self, bytes: u64) {
+        self.report_archive_bytes.set(bytes as i64);
+    }
+
+    /// 記錄一次 Blob 元數據緩存查詢
+    pub fn record_metadata_cache_lookup(&self, hit: bool) {
+        self.blob_metadata_cache_total
+            .with_label_values(&[if hit { "hit" } else { "miss" }])
+            .inc();
+    }
+
+    /// 記錄資源守衛查詢到的可用磁盤與內存（無法查詢的值保持不變）
+    pub fn record_resources(&self, snapshot: &ResourceSnapshot) {
+        for (dir, available) in &snapshot.disk_available_bytes {
+            if let Some(bytes) = available {
+                self.resource_disk_available_bytes
+                    .with_label_values(&[&dir.display().to_string()])
+                    .set(i64::try_from(*bytes).unwrap_or(i64::MAX));
+            }
+        }
+        if let Some(bytes) = snapshot.memory_available_bytes {
+            self.resource_memory_available_bytes
+                .set(i64::try_from(bytes).unwrap_or(i64::MAX));
+        }
+    }
+
+    /// 記錄一次資源檢查的決策（只計降級與拒絕）
+    pub fn record_resource_decision(&self, action: ResourceAction) {
+        let label = match action {
+            ResourceAction::Proceed => return,
+            ResourceAction::Degraded => "degraded",
+            ResourceAction::Refused => "refused",
+        };
+        self.resource_decisions_total
+            .with_label_values(&[label])
+            .inc();
+    }
+
+    /// 登記一個輸出的輪轉計數器，之後每次導出時同步
+    pub fn watch_rotation(&self, output: &str, stats: Arc<RotationStats>) {
+        self.rotations
+            .lock()
+            .unwrap()
+            .push((output.to_string(), stats));
+    }
+
+    /// 將已登記的輪轉計數器同步到 `log_rotation_files_total`
+    fn sync_rotations(&self) {
+        for (output, stats) in self.rotations.lock().unwrap().iter() {
+            for (event, total) in [
+                ("rotated", stats.rotated_files()),
+                ("deleted", stats.deleted_files()),
+                ("compressed", stats.compressed_files()),
+            ] {
+                let counter = self
+                    .log_rotation_files_total
+                    .with_label_values(&[output, event]);
+                counter.inc_by(total.saturating_sub(counter.get()));
+            }
+        }
+    }
+
+    /// 以 Prometheus 文本格式導出所有指標
+    pub fn render(&self) -> String {
+        self.sync_rotations();
+        let mut buffer = Vec::new();
+        TextEncoder::new()
+            .encode(&self.registry.gather(), &mut buffer)
+            .expect("text encoding into a Vec cannot fail");
+        String::from_utf8(buffer).expect("text exposition format is UTF-8")
+    }
+}
+
+impl std::fmt::Debug for Metrics {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Metrics").finish_non_exhaustive()
+    }
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 審計結果標籤
+pub fn audit_result(status: &VerificationStatus) -> &'static str {
+    match status {
+        VerificationStatus::Accessible => "accessible",
+        VerificationStatus::Unreachable => "unreachable",
+        VerificationStatus::Corrupted => "corrupted",
+        VerificationStatus::Deleted => "deleted",
+        VerificationStatus::Expired => "expired",
+    }
+}
+
+/// 存儲節點錯誤類別標籤
+///
+/// - `unreachable`：超時、連接失敗或 5xx（503 除外）
+/// - `overloaded`：429 或 503
+/// - `invalid_sliver`：4xx 或空 sliver
+/// - `bad_response`：響應無法解析
+/// - `circuit_open`：熔斷打開，請求未發出
+/// - `other`：其他錯誤
+pub fn storage_node_error_kind(error: &AuditorError) -> &'static str {
+    match error {
+        AuditorError::StorageNodeUnreachable(_) => "unreachable",
+        AuditorError::StorageNodeOverloaded { .. } => "overloaded",
+        AuditorError::InvalidSliver(_) => "invalid_sliver",
+        AuditorError::Serialization(_) => "bad_response",
+        AuditorError::CircuitOpen { .. } => "circuit_open",
+        _ => "other",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_includes_recorded_values() {
+        let metrics = Metrics::new();
+        metrics.record_audit(
+            audit_result(&VerificationStatus::Corrupted),
+            Duration::from_secs(3),
+        );
+        metrics.record_challenges(8, 2);
+        metrics.record_download(1 << 20);
+        metrics.record_storage_node_error(&AuditorError::StorageNodeUnreachable("x".into()));
+        metrics.record_storage_node_error(&AuditorError::InvalidSliver("y".into()));
+        metrics.record_sui_submission_failure();
+        metrics.record_successful_audit(1_700_000_000);
+        metrics.record_report_archived();
+        metrics.record_report_archive_size(4096);
+        metrics.record_resources(&ResourceSnapshot {
+            disk_available_bytes: vec![
+                ("/data/cache".into(), Some(1 << 30)),
+                ("/unknown".into(), None),
+            ],
+            memory_available_bytes: Some(1 << 28),
+        });
+        metrics.record_resource_decision(ResourceAction::Proceed);
+        metrics.record_resource_decision(ResourceAction::Degraded);
+        metrics.record_resource_decision(ResourceAction::Refused);
+        metrics.record_resource_decision(ResourceAction::Refused);
+
+        let text = metrics.render();
+        for line in [
+            "audits_total{result=\"corrupted\"} 1",
+            "challenges_total{verified=\"true\"} 8",
+            "challenges_total{verified=\"false\"} 2",
+            "audit_duration_seconds_count 1",
+            "walrus_download_bytes_sum 1048576",
+            "storage_node_errors_total{kind=\"unreachable\"} 1",
+            "storage_node_errors_total{kind=\"invalid_sliver\"} 1",
+            "sui_submission_failures_total 1",
+            "last_successful_audit_timestamp 1700000000",
+            "reports_archived_total 1",
+            "report_archive_bytes 4096",
+            "resource_disk_available_bytes{dir=\"/data/cache\"} 1073741824",
+            "resource_memory_available_bytes 268435456",
+            "resource_decisions_total{action=\"degraded\"} 1",
+            "resource_decisions_total{action=\"refused\"} 2",
+        ] {
+            assert!(
+                text.lines().any(|l| l == line),
+                "missing {:?} in:\n{}",
+                line,
+                text
+            );
+        }
+        assert!(!text.contains("/unknown"));
+        assert!(!text.contains("action=\"proceed\""));
+    }
+
+    #[test]
+    fn test_rotation_counters_follow_writers() {
+        use crate::rotating_writer::{RotatingWriter, RotationConfig};
+
+        let dir = tempfile::tempdir().unwrap();
+        let config = RotationConfig {
+            max_bytes: Some(1),
+            keep_files: Some(1),
+            ..RotationConfig::disabled()
+        };
+        let writer = RotatingWriter::open(dir.path().join("history.jsonl"), config).unwrap();
+        let metrics = Metrics::new();
+        metrics.watch_rotation("history", writer.stats());
+
+        for i in 0..3 {
+            writer.append_line(&format!("line-{}", i)).unwrap();
+        }
+        let text = metrics.render();
+        assert!(text.contains("log_rotation_files_total{event=\"rotated\",output=\"history\"} 2"));
+        assert!(text.contains("log_rotation_files_total{event=\"deleted\",output=\"history\"} 1"));
+
+        // 再次導出只累加新增的部分
+        writer.append_line("line-3").unwrap();
+        let text = metrics.render();
+        assert!(text.contains("log_rotation_files_total{event=\"rotated\",output=\"history\"} 3"));
+        assert!(
+            text.contains("log_rotation_files_total{event=\"compressed\",output=\"history\"} 0")
+        );
+    }
+
+    #[test]
+    fn test_registries_are_independent() {
+        let first = Metrics::new();
+        let second = Metrics::new();
+        first.record_sui_submission_failure();
+
+        assert!(first.render().contains("sui_submission_failures_total 1"));
+        assert!(second.render().contains("sui_submission_failures_total 0"));
+    }
+}
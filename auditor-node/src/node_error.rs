@@ -0,0 +1,204 @@
+//! 存儲節點錯誤響應體的清洗
+//!
+//! 存儲節點返回 4xx/5xx 時，響應體可能是 HTML 錯誤頁、本地化文本，甚至數 MB 的內容。
+//! 直接寫入 `failure_reason` 會把標記帶進報告、破壞 JSON 記錄的渲染，
+//! 而且同一故障在不同語言環境下產生不同文本，無法按原因歸類。
+//!
+//! [`NodeErrorBody::parse`] 將響應體轉為適合寫入報告的有界文本：
+//!
+//! 1. 嘗試解析結構化錯誤 `{"code": ..., "message": ...}`（也接受外層 `{"error": {...}}`），
+//!    有 `code` 時優先使用這個穩定、與語言無關的代碼
+//! 2. 否則去除 HTML 標籤，只保留可打印字符並合併空白
+//! 3. 截斷到配置的長度，末尾加 [`TRUNCATION_MARKER`]
+//!
+//! 完整的原始響應體只保存在 HTTP 捕獲文件中（啟用捕獲時），不進入報告。
+
+use serde::Deserialize;
+use std::fmt;
+
+/// 默認錯誤響應體上限（字符數）
+pub const DEFAULT_MAX_ERROR_BODY_LEN: usize = 256;
+
+/// 截斷標記
+pub const TRUNCATION_MARKER: &str = "…";
+
+/// 結構化錯誤代碼的最大長度（超過則視為非代碼）
+const MAX_CODE_LEN: usize = 64;
+
+/// 清洗後的存儲節點錯誤
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NodeErrorBody {
+    /// 結構化錯誤代碼（如 `blob_not_found`），響應體不是結構化錯誤時為空
+    pub code: Option<String>,
+
+    /// 清洗並截斷後的錯誤文本
+    pub message: String,
+}
+
+/// 結構化錯誤響應
+#[derive(Deserialize)]
+struct StructuredError {
+    #[serde(default)]
+    code: Option<serde_json::Value>,
+    #[serde(default)]
+    message: Option<String>,
+    #[serde(default)]
+    error: Option<Box<StructuredError>>,
+}
+
+impl NodeErrorBody {
+    /// 解析並清洗響應體，文本最多保留 `max_len` 個字符（含截斷標記）
+    pub fn parse(body: &[u8], max_len: usize) -> Self {
+        if let Ok(structured) = serde_json::from_slice::<StructuredError>(body) {
+            let structured = match structured.error {
+                Some(inner) if structured.code.is_none() && structured.message.is_none() => *inner,
+                _ => structured,
+            };
+            let code = structured.code.as_ref().and_then(normalize_code);
+            if code.is_some() || structured.message.is_some() {
+                return Self {
+                    code,
+                    message: sanitize_text(structured.message.as_deref().unwrap_or(""), max_len),
+                };
+            }
+        }
+
+        Self {
+            code: None,
+            message: sanitize_text(&String::from_utf8_lossy(body), max_len),
+        }
+    }
+
+    /// 寫入報告的描述：有代碼時只用代碼（與語言無關），否則用清洗後的文本
+    pub fn summary(&self) -> &str {
+        self.code.as_deref().unwrap_or(&self.message)
+    }
+}
+
+impl fmt::Display for NodeErrorBody {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.summary())
+    }
+}
+
+/// 代碼只允許 ASCII 字母數字與 `_ - . :`（數字代碼轉為字符串）
+fn normalize_code(value: &serde_json::Value) -> Option<String> {
+    let code = match value {
+        serde_json::Value::String(s) => s.trim().to_string(),
+        serde_json::Value::Number(n) => n.to_string(),
+        _ => return None,
+    };
+
+    let valid = !code.is_empty()
+        && code.len() <= MAX_CODE_LEN
+        && code
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.' | ':'));
+    valid.then_some(code)
+}
+
+/// 去除 HTML 標籤與控制字符、合併空白並截斷
+pub fn sanitize_text(text: &str, max_len: usize) -> String {
+    let mut cleaned = String::with_capacity(text.len().min(max_len * 4));
+    let mut in_tag = false;
+    let mut pending_space = false;
+
+    for c in text.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => {
+                in_tag = false;
+                pending_space = true;
+            }
+            _ if in_tag => {}
+            c if c.is_whitespace() => pending_space = true,
+            c if c.is_control() || c == char::REPLACEMENT_CHARACTER => {}
+            c => {
+                if pending_space && !cleaned.is_empty() {
+                    cleaned.push(' ');
+                }
+                pending_space = false;
+                cleaned.push(c);
+            }
+        }
+    }
+
+    truncate(cleaned, max_len)
+}
+
+/// 按字符截斷，超長時以 [`TRUNCATION_MARKER`] 結尾
+fn truncate(text: String, max_len: usize) -> String {
+    if text.chars().count() <= max_len {
+        return text;
+    }
+
+    let keep = max_len.saturating_sub(TRUNCATION_MARKER.chars().count());
+    let mut truncated: String = text.chars().take(keep).collect();
+    truncated.truncate(truncated.trim_end().len());
+    truncated.push_str(TRUNCATION_MARKER);
+    truncated
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_html_body_is_stripped() {
+        let body = b"<html><head><title>404 Not Found</title></head>\n<body><h1>Not Found</h1>\r\n<p>The blob was not found.</p></body></html>";
+        let parsed = NodeErrorBody::parse(body, DEFAULT_MAX_ERROR_BODY_LEN);
+
+        assert_eq!(parsed.code, None);
+        assert_eq!(
+            parsed.message,
+            "404 Not Found Not Found The blob was not found."
+        );
+    }
+
+    #[test]
+    fn test_oversized_body_is_truncated() {
+        let body = "x".repeat(10_000);
+        let parsed = NodeErrorBody::parse(body.as_bytes(), 32);
+
+        assert_eq!(parsed.message.chars().count(), 32);
+        assert!(parsed.message.ends_with(TRUNCATION_MARKER));
+    }
+
+    #[test]
+    fn test_truncation_respects_char_boundaries() {
+        let parsed = NodeErrorBody::parse("找不到對象".repeat(20).as_bytes(), 10);
+
+        assert_eq!(parsed.message.chars().count(), 10);
+        assert!(parsed.message.starts_with("找不到對象找不到對"));
+    }
+
+    #[test]
+    fn test_structured_error_prefers_code() {
+        let body = br#"{"code": "blob_not_found", "message": "Blob introuvable"}"#;
+        let parsed = NodeErrorBody::parse(body, DEFAULT_MAX_ERROR_BODY_LEN);
+
+        assert_eq!(parsed.code.as_deref(), Some("blob_not_found"));
+        assert_eq!(parsed.message, "Blob introuvable");
+        assert_eq!(parsed.to_string(), "blob_not_found");
+    }
+
+    #[test]
+    fn test_nested_and_numeric_codes() {
+        let nested = br#"{"error": {"code": 4041, "message": "missing"}}"#;
+        let parsed = NodeErrorBody::parse(nested, DEFAULT_MAX_ERROR_BODY_LEN);
+        assert_eq!(parsed.code.as_deref(), Some("4041"));
+
+        let markup_code = br#"{"code": "<b>bad</b>", "message": "bad code"}"#;
+        let parsed = NodeErrorBody::parse(markup_code, DEFAULT_MAX_ERROR_BODY_LEN);
+        assert_eq!(parsed.code, None);
+        assert_eq!(parsed.to_string(), "bad code");
+    }
+
+    #[test]
+    fn test_control_and_invalid_utf8_removed() {
+        let body = b"bad\x00\x1b[31m request\xff\xfe";
+        let parsed = NodeErrorBody::parse(body, DEFAULT_MAX_ERROR_BODY_LEN);
+
+        assert_eq!(parsed.message, "bad[31m request");
+    }
+}
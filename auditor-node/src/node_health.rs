@@ -0,0 +1,393 @@
+//! 存儲節點健康監控
+//!
+//! 存儲節點宕機時，發往它的每個挑戰都會耗盡整個重試預算。[`NodeHealthMonitor`]
+//! 在後台每隔 `interval_secs` 對每個節點調用 [`StorageNodeClient::health_check`]，
+//! 按最近 `window` 次檢查的成功率判定節點是否健康：
+//!
+//! ```text
+//!          成功率 < min_success_rate
+//! Healthy ───────────────────────────▶ Unhealthy（挑戰路由時跳過）
+//!    ▲                                     │
+//!    └──── 連續 recovery_checks 次檢查成功 ──┘
+//! ```
+//!
+//! 節點初始為健康，單次審計不受影響；窗口內不足 `window` 次時按已有的檢查計算。
+//! 恢復時清空窗口重新統計，剛恢復的節點不會因舊的失敗記錄立即再次被排除。
+//! 狀態轉換寫入日誌，配置了指標時更新 `storage_node_healthy` gauge，
+//! 配置了通知時節點被排除會發出 `node_unhealthy` 事件（見 [`crate::notify`]）。
+//! 健康檢查不經過熔斷器，熔斷打開的節點同樣會被探測。
+
+use crate::metrics::Metrics;
+use crate::notify::{Notification, NotificationDispatcher};
+use crate::storage_node_client::StorageNodeClient;
+use futures::future::join_all;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
+
+/// 存儲節點健康監控配置
+///
+/// ```toml
+/// [node_health]
+/// enabled = true
+/// interval_secs = 30
+/// window = 10
+/// min_success_rate = 0.5
+/// recovery_checks = 3
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct NodeHealthConfig {
+    /// 是否啟用健康監控（守護模式下在後台運行）
+    pub enabled: bool,
+
+    /// 健康檢查間隔（秒）
+    pub interval_secs: u64,
+
+    /// 計算成功率的滾動窗口（最近的檢查次數）
+    pub window: usize,
+
+    /// 低於此成功率（0.0 - 1.0）時排除節點
+    pub min_success_rate: f64,
+
+    /// 被排除的節點連續通過多少次檢查後重新納入
+    pub recovery_checks: u32,
+}
+
+impl Default for NodeHealthConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            interval_secs: 30,
+            window: 10,
+            min_success_rate: 0.5,
+            recovery_checks: 3,
+        }
+    }
+}
+
+/// 單個節點的健康狀態快照
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NodeHealthStatus {
+    /// 節點基礎 URL
+    pub node: String,
+    /// 是否參與挑戰路由
+    pub healthy: bool,
+    /// 窗口內的成功率（尚未檢查時為 1.0）
+    pub success_rate: f64,
+    /// 連續成功的檢查次數
+    pub consecutive_successes: u32,
+}
+
+/// 單個節點的內部狀態
+struct NodeState {
+    healthy: bool,
+    /// 窗口內的檢查結果（是否成功）
+    results: VecDeque<bool>,
+    consecutive_successes: u32,
+}
+
+impl NodeState {
+    fn success_rate(&self) -> f64 {
+        if self.results.is_empty() {
+            return 1.0;
+        }
+        let successes = self.results.iter().filter(|ok| **ok).count();
+        successes as f64 / self.results.len() as f64
+    }
+}
+
+/// 存儲節點健康監控器
+///
+/// 節點以索引標識，順序與創建時傳入的 URL 一致（即審計器中存儲節點客戶端的順序）
+pub struct NodeHealthMonitor {
+    config: NodeHealthConfig,
+    /// 健康檢查專用的客戶端（不重試、不經過熔斷器）
+    clients: Vec<StorageNodeClient>,
+    nodes: Mutex<Vec<NodeState>>,
+    metrics: Option<Arc<Metrics>>,
+    notifier: Option<NotificationDispatcher>,
+}
+
+impl NodeHealthMonitor {
+    /// 為 `urls` 中的節點創建監控器（所有節點初始為健康）
+    ///
+    /// # Panics
+    /// `window` 或 `recovery_checks` 為 0 時 panic（配置校驗會先拒絕）
+    pub fn new(config: NodeHealthConfig, urls: &[String], timeout_secs: u64) -> Self {
+        assert!(config.window > 0, "node_health.window must be positive");
+        assert!(
+            config.recovery_checks > 0,
+            "node_health.recovery_checks must be positive"
+        );
+
+        let clients = urls
+            .iter()
+            .map(|url| StorageNodeClient::with_config(url.clone(), timeout_secs, 0))
+            .collect();
+        let nodes = urls
+            .iter()
+            .map(|_| NodeState {
+                healthy: true,
+                results: VecDeque::with_capacity(config.window),
+                consecutive_successes: 0,
+            })
+            .collect();
+
+        Self {
+            config,
+            clients,
+            nodes: Mutex::new(nodes),
+            metrics: None,
+            notifier: None,
+        }
+    }
+
+    /// 狀態變化時更新 `storage_node_healthy` gauge
+    pub fn with_metrics(mut self, metrics: Arc<Metrics>) -> Self {
+        for client in &self.clients {
+            metrics.record_node_health(client.base_url(), true);
+        }
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// 節點被排除時發出 `node_unhealthy` 通知
+    pub fn with_notifier(mut self, notifier: NotificationDispatcher) -> Self {
+        self.notifier = Some(notifier);
+        self
+    }
+
+    /// 當前參與挑戰路由的節點索引（升序）
+    pub fn healthy_nodes(&self) -> Vec<usize> {
+        let nodes = self.nodes.lock().expect("node health lock poisoned");
+        nodes
+            .iter()
+            .enumerate()
+            .filter(|(_, node)| node.healthy)
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    /// 所有節點的狀態快照
+    pub fn status(&self) -> Vec<NodeHealthStatus> {
+        let nodes = self.nodes.lock().expect("node health lock poisoned");
+        nodes
+            .iter()
+            .zip(&self.clients)
+            .map(|(node, client)| NodeHealthStatus {
+                node: client.base_url().to_string(),
+                healthy: node.healthy,
+                success_rate: node.success_rate(),
+                consecutive_successes: node.consecutive_successes,
+            })
+            .collect()
+    }
+
+    /// 並發檢查所有節點一次，並更新其狀態
+    pub async fn check_all(&self) {
+        let results = join_all(self.clients.iter().map(|client| async move {
+            // health_check 只在構造請求失敗時返回錯誤，同樣計為失敗
+            client.health_check().await.unwrap_or(false)
+        }))
+        .await;
+
+        for (index, ok) in results.into_iter().enumerate() {
+            self.record(index, ok);
+        }
+    }
+
+    /// 記錄一次檢查結果，必要時轉換狀態
+    fn record(&self, index: usize, ok: bool) {
+        let mut nodes = self.nodes.lock().expect("node health lock poisoned");
+        let node = &mut nodes[index];
+        let url = self.clients[index].base_url();
+
+        if node.results.len() == self.config.window {
+            node.results.pop_front();
+        }
+        node.results.push_back(ok);
+        node.consecutive_successes = if ok {
+            node.consecutive_successes.saturating_add(1)
+        } else {
+            0
+        };
+
+        let success_rate = node.success_rate();
+        debug!(
+            "Storage node {} health check {}: success rate {:.2}",
+            url,
+            if ok { "passed" } else { "failed" },
+            success_rate
+        );
+
+        let transition = if node.healthy && success_rate < self.config.min_success_rate {
+            warn!(
+                "Storage node {} marked unhealthy (success rate {:.2} < {:.2}), \
+                 excluding it from challenge routing",
+                url, success_rate, self.config.min_success_rate
+            );
+            Some(false)
+        } else if !node.healthy && node.consecutive_successes >= self.config.recovery_checks {
+            info!(
+                "Storage node {} recovered after {} consecutive health checks",
+                url, node.consecutive_successes
+            );
+            node.results.clear();
+            node.results.push_back(true);
+            Some(true)
+        } else {
+            None
+        };
+
+        if let Some(healthy) = transition {
+            node.healthy = healthy;
+            if let Some(metrics) = &self.metrics {
+                metrics.record_node_health(url, healthy);
+            }
+            if let (false, Some(notifier)) = (healthy, &self.notifier) {
+                notifier.notify(Notification::node_unhealthy(url, success_rate));
+            }
+        }
+    }
+
+    /// 在後台每隔 `interval_secs` 檢查一次所有節點（首次檢查立即執行）
+    ///
+    /// 返回的任務句柄可用於停止監控（`abort`）
+    pub fn spawn(self: &Arc<Self>) -> JoinHandle<()> {
+        let monitor = Arc::clone(self);
+        let period = Duration::from_secs(self.config.interval_secs.max(1));
+        info!(
+            "Monitoring health of {} storage node(s) every {}s",
+            self.clients.len(),
+            period.as_secs()
+        );
+
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(period);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                interval.tick().await;
+                monitor.check_all().await;
+            }
+        })
+    }
+}
+
+impl std::fmt::Debug for NodeHealthMonitor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NodeHealthMonitor")
+            .field("config", &self.config)
+            .field("nodes", &self.clients.len())
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::FakeStorageNode;
+    use axum::http::StatusCode;
+
+    fn config() -> NodeHealthConfig {
+        NodeHealthConfig {
+            enabled: true,
+            interval_secs: 1,
+            window: 4,
+            min_success_rate: 0.6,
+            recovery_checks: 2,
+        }
+    }
+
+    async fn start_nodes(count: usize) -> Vec<FakeStorageNode> {
+        let mut nodes = Vec::with_capacity(count);
+        for _ in 0..count {
+            nodes.push(FakeStorageNode::start(StatusCode::OK, "text/plain", Vec::new()).await);
+        }
+        nodes
+    }
+
+    fn urls(nodes: &[FakeStorageNode]) -> Vec<String> {
+        nodes.iter().map(|node| node.url().to_string()).collect()
+    }
+
+    #[tokio::test]
+    async fn test_failing_node_is_excluded_and_recovers() {
+        let nodes = start_nodes(2).await;
+        let monitor = NodeHealthMonitor::new(config(), &urls(&nodes), 5);
+        assert_eq!(monitor.healthy_nodes(), vec![0, 1]);
+
+        monitor.check_all().await;
+        monitor.check_all().await;
+        assert_eq!(monitor.healthy_nodes(), vec![0, 1]);
+
+        // 窗口 [ok, ok, fail] 仍高於 0.6，第二次失敗後排除
+        nodes[1].set_health(StatusCode::INTERNAL_SERVER_ERROR);
+        monitor.check_all().await;
+        assert_eq!(monitor.healthy_nodes(), vec![0, 1]);
+        monitor.check_all().await;
+        assert_eq!(monitor.healthy_nodes(), vec![0]);
+
+        // 連續通過 recovery_checks 次後才重新納入
+        nodes[1].set_health(StatusCode::OK);
+        monitor.check_all().await;
+        assert_eq!(monitor.healthy_nodes(), vec![0]);
+        monitor.check_all().await;
+        assert_eq!(monitor.healthy_nodes(), vec![0, 1]);
+
+        let status = monitor.status();
+        assert_eq!(status[1].node, nodes[1].url());
+        assert!(status[1].healthy);
+        assert_eq!(status[1].success_rate, 1.0);
+    }
+
+    #[tokio::test]
+    async fn test_flapping_node_stays_excluded() {
+        let nodes = start_nodes(1).await;
+        let monitor = NodeHealthMonitor::new(config(), &urls(&nodes), 5);
+
+        nodes[0].set_health(StatusCode::INTERNAL_SERVER_ERROR);
+        monitor.check_all().await;
+        assert!(monitor.healthy_nodes().is_empty());
+
+        // 成功與失敗交替時從未連續通過兩次
+        for status in [
+            StatusCode::OK,
+            StatusCode::INTERNAL_SERVER_ERROR,
+            StatusCode::OK,
+        ] {
+            nodes[0].set_health(status);
+            monitor.check_all().await;
+            assert!(monitor.healthy_nodes().is_empty());
+        }
+        assert_eq!(monitor.status()[0].consecutive_successes, 1);
+    }
+
+    #[tokio::test]
+    async fn test_unreachable_node_is_excluded() {
+        let nodes = start_nodes(1).await;
+        let mut urls = urls(&nodes);
+        urls.push("http://127.0.0.1:1".to_string());
+        let monitor = NodeHealthMonitor::new(config(), &urls, 5);
+
+        monitor.check_all().await;
+        assert_eq!(monitor.healthy_nodes(), vec![0]);
+    }
+
+    #[tokio::test]
+    async fn test_transitions_update_metrics() {
+        let nodes = start_nodes(1).await;
+        let metrics = Arc::new(Metrics::new());
+        let monitor =
+            NodeHealthMonitor::new(config(), &urls(&nodes), 5).with_metrics(Arc::clone(&metrics));
+        let gauge = format!("storage_node_healthy{{node=\"{}\"}}", nodes[0].url());
+        assert!(metrics.render().contains(&format!("{} 1", gauge)));
+
+        nodes[0].set_health(StatusCode::INTERNAL_SERVER_ERROR);
+        monitor.check_all().await;
+        assert!(metrics.render().contains(&format!("{} 0", gauge)));
+    }
+}
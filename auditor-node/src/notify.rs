@@ -0,0 +1,603 @@
+//! 審計結果通知（webhook）
+//!
+//! 審計失敗時運營者需要被及時告警，而不是去翻日誌。守護進程在每個 Blob 審計完成後、
+//! 以及存儲節點被健康監控排除時生成一條 [`Notification`]，交給 [`NotificationDispatcher`]：
+//!
+//! ```text
+//! 審計 / 健康監控 ──notify──▶ 有界隊列 ──▶ 後台任務 ──retry──▶ Notifier（如 WebhookNotifier）
+//!                     │
+//!                     └─ 隊列已滿：丟棄並計數，不阻塞審計
+//! ```
+//!
+//! - 只發送 `webhook_events` 中訂閱的事件（[`NotifyEvent`]）
+//! - 發送失敗按 [`RetryConfig`] 指數退避重試（5xx、超時、連接失敗）；
+//!   超出重試預算或被拒絕（4xx）的通知記錄日誌，並計入 `webhook_deliveries_total{result="failed"}`
+//! - 配置了 `webhook_secret` 時，請求頭 `X-Audit-Signature` 為
+//!   `sha256=<hex(HMAC-SHA256(secret, body))>`，接收方以同一密鑰對原始請求體驗證（[`sign_body`]）
+//!
+//! 請求體示例：
+//!
+//! ```json
+//! {
+//!   "event": "audit_failed",
+//!   "timestamp": 1700000000,
+//!   "blob_id": "eRrTusk8yshFQpkemgDnbg0f4-qDo623V2NpeVG1Zcg",
+//!   "audit_id": "1f0c…",
+//!   "is_valid": false,
+//!   "failure_reason": "3 of 10 challenges failed",
+//!   "total_challenges": 10,
+//!   "successful_verifications": 7,
+//!   "failed_verifications": 3,
+//!   "report_digest": "9b1d…"
+//! }
+//! ```
+//!
+//! 其他渠道（如 Slack）實現 [`Notifier`] 即可接入同一隊列。
+
+use crate::error::{AuditorError, Result};
+use crate::history::report_digest;
+use crate::http::{build_client, HttpClientConfig};
+use crate::metrics::{Metrics, WEBHOOK_DELIVERED, WEBHOOK_DROPPED, WEBHOOK_FAILED};
+use crate::retry::{retry_with_exponential_backoff_if, RetryConfig};
+use crate::types::{AuditReport, AuditorConfig};
+use async_trait::async_trait;
+use hmac::{Hmac, Mac};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tracing::{debug, error, warn};
+
+/// 簽名請求頭
+pub const SIGNATURE_HEADER: &str = "X-Audit-Signature";
+
+/// 待發送通知的隊列容量
+pub const NOTIFICATION_QUEUE_CAPACITY: usize = 256;
+
+/// 單次 webhook 請求的超時
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// 通知事件
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NotifyEvent {
+    /// 審計完成且未發現故障（含已被刪除或存儲期已結束的 Blob）
+    AuditCompleted,
+    /// 審計未通過或審計過程出錯
+    AuditFailed,
+    /// 存儲節點未通過健康檢查，被排除出挑戰路由
+    NodeUnhealthy,
+}
+
+/// 默認訂閱的事件
+pub fn default_webhook_events() -> Vec<NotifyEvent> {
+    vec![NotifyEvent::AuditFailed]
+}
+
+/// 一條通知（webhook 請求體）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Notification {
+    /// 事件類型
+    pub event: NotifyEvent,
+
+    /// 生成時間（Unix 秒）
+    pub timestamp: u64,
+
+    /// 事件內容
+    #[serde(flatten)]
+    pub subject: NotificationSubject,
+}
+
+/// 通知的內容
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum NotificationSubject {
+    /// 一次 Blob 審計
+    Audit(AuditSummary),
+    /// 一個存儲節點
+    Node(NodeSummary),
+}
+
+/// 審計結果摘要
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditSummary {
+    /// Blob ID
+    pub blob_id: String,
+
+    /// 審計關聯 ID（見 [`crate::logging`]）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub audit_id: Option<String>,
+
+    /// 報告是否判定有效
+    pub is_valid: bool,
+
+    /// 失敗原因（審計出錯時為錯誤信息）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub failure_reason: Option<String>,
+
+    /// 挑戰總數
+    pub total_challenges: u16,
+
+    /// 通過的挑戰數
+    pub successful_verifications: u16,
+
+    /// 失敗的挑戰數
+    pub failed_verifications: u16,
+
+    /// 已簽名報告的 SHA-256 摘要（同 [`report_digest`]；審計出錯時沒有報告）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub report_digest: Option<String>,
+}
+
+/// 存儲節點狀態摘要
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NodeSummary {
+    /// 節點基礎 URL
+    pub node_url: String,
+
+    /// 健康檢查窗口內的成功率
+    pub success_rate: f64,
+}
+
+impl Notification {
+    /// 已簽名報告的審計結果通知
+    pub fn audit_report(event: NotifyEvent, report: &AuditReport) -> Result<Self> {
+        Ok(Self {
+            event,
+            timestamp: now_secs(),
+            subject: NotificationSubject::Audit(AuditSummary {
+                blob_id: report.blob_id.clone(),
+                audit_id: report.audit_id.clone(),
+                is_valid: report.is_valid,
+                failure_reason: report.failure_reason.clone(),
+                total_challenges: report.total_challenges,
+                successful_verifications: report.successful_verifications,
+                failed_verifications: report.failed_verifications,
+                report_digest: Some(report_digest(report)?),
+            }),
+        })
+    }
+
+    /// 審計出錯（沒有報告）的通知
+    pub fn audit_error(blob_id: &str, error: &AuditorError) -> Self {
+        Self {
+            event: NotifyEvent::AuditFailed,
+            timestamp: now_secs(),
+            subject: NotificationSubject::Audit(AuditSummary {
+                blob_id: blob_id.to_string(),
+                audit_id: None,
+                is_valid: false,
+                failure_reason: Some(error.to_string()),
+                total_challenges: 0,
+                successful_verifications: 0,
+                failed_verifications: 0,
+                report_digest: None,
+            }),
+        }
+    }
+
+    /// 存儲節點被排除的通知
+    pub fn node_unhealthy(node_url: &str, success_rate: f64) -> Self {
+        Self {
+            event: NotifyEvent::NodeUnhealthy,
+            timestamp: now_secs(),
+            subject: NotificationSubject::Node(NodeSummary {
+                node_url: node_url.to_string(),
+                success_rate,
+            }),
+        }
+    }
+}
+
+fn now_secs() -> u64 {
+    chrono::Utc::now().timestamp() as u64
+}
+
+/// 通知渠道
+#[async_trait]
+pub trait Notifier: Send + Sync {
+    /// 渠道名稱（用於日誌）
+    fn name(&self) -> &str;
+
+    /// 發送一條通知；返回的錯誤經 [`Notifier::is_transient`] 判定是否重試
+    async fn send(&self, notification: &Notification) -> Result<()>;
+
+    /// 錯誤是否為可重試的臨時錯誤（默認：5xx、超時、連接失敗）
+    fn is_transient(&self, error: &AuditorError) -> bool {
+        match error {
+            AuditorError::HttpRequest(e) => {
+                e.is_timeout()
+                    || e.is_connect()
+                    || e.status().is_some_and(|status| status.is_server_error())
+            }
+            _ => false,
+        }
+    }
+}
+
+/// `X-Audit-Signature` 的值：`sha256=<hex(HMAC-SHA256(secret, body))>`
+pub fn sign_body(secret: &str, body: &[u8]) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(body);
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+/// 以 JSON POST 通知的 webhook 渠道
+#[derive(Debug, Clone)]
+pub struct WebhookNotifier {
+    http_client: Client,
+    url: String,
+    secret: Option<String>,
+}
+
+impl WebhookNotifier {
+    /// 創建 webhook 渠道（設置了 `secret` 時對請求體簽名）
+    pub fn new(
+        url: impl Into<String>,
+        secret: Option<String>,
+        http: &HttpClientConfig,
+    ) -> Result<Self> {
+        Ok(Self {
+            http_client: build_client(http)?,
+            url: url.into(),
+            secret,
+        })
+    }
+}
+
+#[async_trait]
+impl Notifier for WebhookNotifier {
+    fn name(&self) -> &str {
+        "webhook"
+    }
+
+    async fn send(&self, notification: &Notification) -> Result<()> {
+        let body = serde_json::to_vec(notification)?;
+        let mut request = self
+            .http_client
+            .post(&self.url)
+            .timeout(WEBHOOK_TIMEOUT)
+            .header(reqwest::header::CONTENT_TYPE, "application/json");
+        if let Some(secret) = &self.secret {
+            request = request.header(SIGNATURE_HEADER, sign_body(secret, &body));
+        }
+        request.body(body).send().await?.error_for_status()?;
+        Ok(())
+    }
+}
+
+/// 通知分發器：按訂閱過濾後放入有界隊列，由後台任務發送
+///
+/// 可克隆，審計循環與健康監控共享同一隊列
+#[derive(Debug, Clone)]
+pub struct NotificationDispatcher {
+    sender: mpsc::Sender<Notification>,
+    events: Arc<HashSet<NotifyEvent>>,
+    metrics: Option<Arc<Metrics>>,
+}
+
+impl NotificationDispatcher {
+    /// 啟動發送任務，返回分發器與任務句柄
+    ///
+    /// 所有分發器被丟棄後，任務發送完隊列中剩餘的通知即退出
+    pub fn spawn(
+        notifier: Arc<dyn Notifier>,
+        events: impl IntoIterator<Item = NotifyEvent>,
+        capacity: usize,
+        retry: RetryConfig,
+        metrics: Option<Arc<Metrics>>,
+    ) -> (Self, JoinHandle<()>) {
+        let (sender, mut receiver) = mpsc::channel::<Notification>(capacity.max(1));
+        let worker_metrics = metrics.clone();
+        let handle = tokio::spawn(async move {
+            while let Some(notification) = receiver.recv().await {
+                let delivered = retry_with_exponential_backoff_if(
+                    notifier.name(),
+                    &retry,
+                    |e| notifier.is_transient(e),
+                    || notifier.send(&notification),
+                )
+                .await;
+                let result = match delivered {
+                    Ok(()) => {
+                        debug!(
+                            "Delivered {:?} notification via {}",
+                            notification.event,
+                            notifier.name()
+                        );
+                        WEBHOOK_DELIVERED
+                    }
+                    Err(e) => {
+                        error!(
+                            "Failed to deliver {:?} notification via {}: {}",
+                            notification.event,
+                            notifier.name(),
+                            e
+                        );
+                        WEBHOOK_FAILED
+                    }
+                };
+                if let Some(metrics) = &worker_metrics {
+                    metrics.record_webhook_delivery(result);
+                }
+            }
+        });
+
+        let dispatcher = Self {
+            sender,
+            events: Arc::new(events.into_iter().collect()),
+            metrics,
+        };
+        (dispatcher, handle)
+    }
+
+    /// 按配置啟動 webhook 通知（未配置 `webhook_url` 時返回 `None`）
+    pub fn from_config(
+        config: &AuditorConfig,
+        metrics: Option<Arc<Metrics>>,
+    ) -> Result<Option<(Self, JoinHandle<()>)>> {
+        let Some(url) = &config.webhook_url else {
+            return Ok(None);
+        };
+        let notifier = WebhookNotifier::new(url, config.webhook_secret.clone(), &config.http)?;
+        Ok(Some(Self::spawn(
+            Arc::new(notifier),
+            config.webhook_events.iter().copied(),
+            NOTIFICATION_QUEUE_CAPACITY,
+            RetryConfig::default(),
+            metrics,
+        )))
+    }
+
+    /// 是否訂閱了該事件
+    pub fn is_subscribed(&self, event: NotifyEvent) -> bool {
+        self.events.contains(&event)
+    }
+
+    /// 提交一條通知（不等待發送）；未訂閱的事件忽略，隊列已滿時丟棄並計數
+    pub fn notify(&self, notification: Notification) {
+        if !self.is_subscribed(notification.event) {
+            return;
+        }
+        if let Err(e) = self.sender.try_send(notification) {
+            let notification = match e {
+                mpsc::error::TrySendError::Full(notification)
+                | mpsc::error::TrySendError::Closed(notification) => notification,
+            };
+            warn!(
+                "Notification queue unavailable, dropping {:?} notification",
+                notification.event
+            );
+            if let Some(metrics) = &self.metrics {
+                metrics.record_webhook_delivery(WEBHOOK_DROPPED);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::integrity::{AuditData, VerificationStatus};
+    use crate::types::AuditMethod;
+    use axum::{
+        body::Bytes, extract::State, http::HeaderMap, http::StatusCode, routing::post, Router,
+    };
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Mutex;
+
+    const SECRET: &str = "webhook-secret";
+
+    /// 記錄收到的請求；前 `fail_first` 次返回 503
+    #[derive(Default)]
+    struct Received {
+        requests: Mutex<Vec<(Option<String>, Bytes)>>,
+        attempts: AtomicUsize,
+        fail_first: usize,
+    }
+
+    async fn start_webhook(fail_first: usize) -> (String, Arc<Received>) {
+        let received = Arc::new(Received {
+            fail_first,
+            ..Default::default()
+        });
+        let router = Router::new()
+            .route(
+                "/hook",
+                post(
+                    |State(received): State<Arc<Received>>, headers: HeaderMap, body: Bytes| async move {
+                        if received.attempts.fetch_add(1, Ordering::SeqCst) < received.fail_first {
+                            return StatusCode::SERVICE_UNAVAILABLE;
+                        }
+                        let signature = headers
+                            .get(SIGNATURE_HEADER)
+                            .and_then(|value| value.to_str().ok())
+                            .map(str::to_string);
+                        received.requests.lock().unwrap().push((signature, body));
+                        StatusCode::OK
+                    },
+                ),
+            )
+            .with_state(Arc::clone(&received));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, router).await });
+        (url, received)
+    }
+
+    fn fast_retry(max_retries: u32) -> RetryConfig {
+        RetryConfig {
+            max_retries,
+            initial_delay_ms: 1,
+            multiplier: 1.0,
+            max_delay_ms: 1,
+        }
+    }
+
+    fn failed_report() -> AuditReport {
+        AuditReport::from(AuditData {
+            blob_id: "0xblob".to_string(),
+            content_hash: "ab".repeat(32),
+            merkle_root: "cd".repeat(32),
+            total_challenges: 4,
+            successful_verifications: 3,
+            failed_verifications: 1,
+            file_size: 4096,
+            timestamp: 1_700_000_000,
+            verification_status: VerificationStatus::Corrupted,
+            sui_object_id: None,
+            resource_decision: None,
+            capture_digest: None,
+            deduplicated_from: None,
+            producer: None,
+            chunk_filter: None,
+            challenge_reveal: None,
+            challenge_seed_source: None,
+            challenge_seed: None,
+            hash_drift: None,
+            blob_id_verified: None,
+            chunk_size: None,
+            blob_object_id: None,
+            encoding_k: None,
+            encoding_n: None,
+            start_epoch: None,
+            end_epoch: None,
+            onchain_merkle_root: None,
+            size_matches_chain: None,
+            audit_method: AuditMethod::Aggregator,
+        })
+    }
+
+    #[tokio::test]
+    async fn test_webhook_body_is_signed_with_hmac() {
+        let (url, received) = start_webhook(1).await;
+        let notifier =
+            WebhookNotifier::new(&url, Some(SECRET.to_string()), &Default::default()).unwrap();
+        let (dispatcher, worker) = NotificationDispatcher::spawn(
+            Arc::new(notifier),
+            [NotifyEvent::AuditFailed],
+            8,
+            fast_retry(3),
+            None,
+        );
+
+        let report = failed_report();
+        dispatcher.notify(Notification::audit_report(NotifyEvent::AuditFailed, &report).unwrap());
+        // 未訂閱的事件不發送
+        dispatcher
+            .notify(Notification::audit_report(NotifyEvent::AuditCompleted, &report).unwrap());
+        drop(dispatcher);
+        worker.await.unwrap();
+
+        // 第一次 503 後重試成功
+        assert_eq!(received.attempts.load(Ordering::SeqCst), 2);
+        let requests = received.requests.lock().unwrap();
+        assert_eq!(requests.len(), 1);
+        let (signature, body) = &requests[0];
+        assert_eq!(signature.as_deref(), Some(sign_body(SECRET, body).as_str()));
+        assert_ne!(
+            signature.as_deref(),
+            Some(sign_body("other", body).as_str())
+        );
+
+        let payload: serde_json::Value = serde_json::from_slice(body).unwrap();
+        assert_eq!(payload["event"], "audit_failed");
+        assert_eq!(payload["blob_id"], "0xblob");
+        assert_eq!(payload["is_valid"], false);
+        assert_eq!(
+            payload["failure_reason"],
+            report.failure_reason.clone().unwrap()
+        );
+        assert_eq!(payload["failed_verifications"], 1);
+        assert_eq!(payload["report_digest"], report_digest(&report).unwrap());
+    }
+
+    #[test]
+    fn test_sign_body_matches_known_vector() {
+        // RFC 4231 測試用例 2
+        assert_eq!(
+            sign_body("Jefe", b"what do ya want for nothing?"),
+            "sha256=5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_undeliverable_notifications_are_counted() {
+        let (url, received) = start_webhook(usize::MAX).await;
+        let metrics = Arc::new(Metrics::new());
+        let notifier = WebhookNotifier::new(&url, None, &Default::default()).unwrap();
+        let (dispatcher, worker) = NotificationDispatcher::spawn(
+            Arc::new(notifier),
+            [NotifyEvent::NodeUnhealthy],
+            8,
+            fast_retry(2),
+            Some(Arc::clone(&metrics)),
+        );
+
+        dispatcher.notify(Notification::node_unhealthy("http://node-1", 0.2));
+        drop(dispatcher);
+        worker.await.unwrap();
+
+        // 重試預算用盡後放棄
+        assert_eq!(received.attempts.load(Ordering::SeqCst), 3);
+        assert!(metrics
+            .render()
+            .contains("webhook_deliveries_total{result=\"failed\"} 1"));
+    }
+
+    /// 永不完成的渠道，用於佔住發送任務
+    struct Stalled;
+
+    #[async_trait]
+    impl Notifier for Stalled {
+        fn name(&self) -> &str {
+            "stalled"
+        }
+
+        async fn send(&self, _: &Notification) -> Result<()> {
+            std::future::pending().await
+        }
+    }
+
+    #[tokio::test]
+    async fn test_full_queue_drops_instead_of_blocking() {
+        let metrics = Arc::new(Metrics::new());
+        let (dispatcher, worker) = NotificationDispatcher::spawn(
+            Arc::new(Stalled),
+            [NotifyEvent::NodeUnhealthy],
+            1,
+            fast_retry(0),
+            Some(Arc::clone(&metrics)),
+        );
+
+        // 一條被發送任務取走，一條進入隊列，其餘立即丟棄
+        for _ in 0..5 {
+            dispatcher.notify(Notification::node_unhealthy("http://node-1", 0.0));
+            tokio::task::yield_now().await;
+        }
+        let rendered = metrics.render();
+        let dropped = rendered
+            .lines()
+            .find(|line| line.starts_with("webhook_deliveries_total{result=\"dropped\"}"))
+            .and_then(|line| line.rsplit(' ').next())
+            .and_then(|count| count.parse::<u64>().ok())
+            .unwrap();
+        assert!(dropped >= 3, "dropped {}", dropped);
+        worker.abort();
+    }
+
+    #[test]
+    fn test_node_notification_payload() {
+        let notification = Notification::node_unhealthy("http://node-1", 0.25);
+        let payload = serde_json::to_value(&notification).unwrap();
+        assert_eq!(payload["event"], "node_unhealthy");
+        assert_eq!(payload["node_url"], "http://node-1");
+        assert_eq!(payload["success_rate"], 0.25);
+        assert!(payload.get("blob_id").is_none());
+    }
+}
@@ -0,0 +1,217 @@
+//! 鏈上審計員公鑰查詢
+//!
+//! 審計員註冊時把 PQC 公鑰寫入 `auditor_registry::AuditorRegistry` 的 `pqc_public_keys` 表
+//! （`Table<address, vector<u8>>`）。驗證報告時可按報告中的 `auditor` 地址從鏈上取回公鑰，
+//! 無需事先持有公鑰或本地信任庫（見 [`ReportManager::verify_report_onchain`]）。
+//!
+//! 合約只記錄公鑰字節，算法由公鑰長度確定（合約只接受 Dilithium3 與 Falcon-512 的長度）。
+//!
+//! 批量驗證時同一審計員的報告很多，[`CachedKeyLookup`] 按地址緩存查詢結果，
+//! 在 TTL 內不再請求 RPC；查詢失敗不緩存。
+//!
+//! [`ReportManager::verify_report_onchain`]: crate::report::ReportManager::verify_report_onchain
+
+use crate::audit_report::PqcAlgorithm;
+use crate::chain_types::MoveId;
+use crate::error::{AuditorError, Result};
+use crate::sui_client::AuditSystemClient;
+use crate::trust::key_id;
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tracing::debug;
+
+/// Dilithium3 公鑰長度（與 `auditor_registry::DILITHIUM3_PK_SIZE` 一致）
+pub const DILITHIUM3_PUBLIC_KEY_BYTES: usize = 1952;
+
+/// Falcon-512 公鑰長度（與 `auditor_registry::FALCON512_PK_SIZE` 一致）
+pub const FALCON512_PUBLIC_KEY_BYTES: usize = 897;
+
+/// 默認的公鑰緩存時間
+pub const DEFAULT_KEY_CACHE_TTL: Duration = Duration::from_secs(300);
+
+/// 審計員在鏈上登記的公鑰
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegisteredKey {
+    /// PQC 公鑰字節
+    pub public_key: Vec<u8>,
+
+    /// 算法編號（見 [`PqcAlgorithm::id`]）
+    pub algorithm: u8,
+}
+
+impl RegisteredKey {
+    /// 由登記的公鑰字節創建，按長度確定算法
+    ///
+    /// # 錯誤
+    /// - 長度不屬於任何支持的算法: 返回 `ChainAbi` 錯誤
+    pub fn from_public_key(public_key: Vec<u8>) -> Result<Self> {
+        let algorithm = match public_key.len() {
+            DILITHIUM3_PUBLIC_KEY_BYTES => PqcAlgorithm::Dilithium3,
+            FALCON512_PUBLIC_KEY_BYTES => PqcAlgorithm::Falcon512,
+            len => {
+                return Err(AuditorError::ChainAbi(format!(
+                    "Registered public key has unsupported length {} bytes",
+                    len
+                )))
+            }
+        };
+        Ok(Self {
+            public_key,
+            algorithm: algorithm.id(),
+        })
+    }
+
+    /// 公鑰指紋（同信任庫的 `key_id`）
+    pub fn key_id(&self) -> String {
+        key_id(&self.public_key)
+    }
+}
+
+/// 按審計員地址查詢登記公鑰的接口
+#[async_trait]
+pub trait PublicKeyLookup: Send + Sync {
+    /// 查詢審計員登記的公鑰
+    async fn auditor_public_key(&self, auditor: &MoveId) -> Result<RegisteredKey>;
+}
+
+#[async_trait]
+impl PublicKeyLookup for AuditSystemClient {
+    async fn auditor_public_key(&self, auditor: &MoveId) -> Result<RegisteredKey> {
+        self.get_auditor_public_key(auditor).await
+    }
+}
+
+/// 按地址緩存查詢結果的公鑰查詢
+pub struct CachedKeyLookup<L> {
+    inner: L,
+    ttl: Duration,
+    entries: Mutex<HashMap<MoveId, (Instant, RegisteredKey)>>,
+}
+
+impl<L: PublicKeyLookup> CachedKeyLookup<L> {
+    /// 創建緩存，結果保留 `ttl`
+    pub fn new(inner: L, ttl: Duration) -> Self {
+        Self {
+            inner,
+            ttl,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// 丟棄某審計員的緩存（例如得知其已輪換公鑰）
+    pub fn invalidate(&self, auditor: &MoveId) {
+        self.entries.lock().unwrap().remove(auditor);
+    }
+
+    fn cached(&self, auditor: &MoveId) -> Option<RegisteredKey> {
+        let mut entries = self.entries.lock().unwrap();
+        match entries.get(auditor) {
+            Some((fetched_at, key)) if fetched_at.elapsed() < self.ttl => Some(key.clone()),
+            Some(_) => {
+                entries.remove(auditor);
+                None
+            }
+            None => None,
+        }
+    }
+}
+
+#[async_trait]
+impl<L: PublicKeyLookup> PublicKeyLookup for CachedKeyLookup<L> {
+    async fn auditor_public_key(&self, auditor: &MoveId) -> Result<RegisteredKey> {
+        if let Some(key) = self.cached(auditor) {
+            debug!("Using cached public key of auditor {}", auditor);
+            return Ok(key);
+        }
+
+        let key = self.inner.auditor_public_key(auditor).await?;
+        self.entries
+            .lock()
+            .unwrap()
+            .insert(*auditor, (Instant::now(), key.clone()));
+        Ok(key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// 記錄調用次數的模擬查詢；`fail` 時返回錯誤
+    #[derive(Default)]
+    struct MockLookup {
+        calls: AtomicUsize,
+        fail: std::sync::atomic::AtomicBool,
+    }
+
+    #[async_trait]
+    impl PublicKeyLookup for MockLookup {
+        async fn auditor_public_key(&self, auditor: &MoveId) -> Result<RegisteredKey> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            if self.fail.load(Ordering::SeqCst) {
+                return Err(AuditorError::SuiClient("rpc down".to_string()));
+            }
+            RegisteredKey::from_public_key(vec![auditor.0[31]; FALCON512_PUBLIC_KEY_BYTES])
+        }
+    }
+
+    fn address(byte: u8) -> MoveId {
+        MoveId([byte; 32])
+    }
+
+    #[test]
+    fn test_algorithm_from_key_length() {
+        let key = RegisteredKey::from_public_key(vec![0; DILITHIUM3_PUBLIC_KEY_BYTES]).unwrap();
+        assert_eq!(key.algorithm, PqcAlgorithm::Dilithium3.id());
+        let key = RegisteredKey::from_public_key(vec![0; FALCON512_PUBLIC_KEY_BYTES]).unwrap();
+        assert_eq!(key.algorithm, PqcAlgorithm::Falcon512.id());
+
+        let err = RegisteredKey::from_public_key(vec![0; 32]).unwrap_err();
+        assert!(matches!(err, AuditorError::ChainAbi(_)), "{}", err);
+    }
+
+    #[tokio::test]
+    async fn test_cache_hits_within_ttl() {
+        let cache = CachedKeyLookup::new(MockLookup::default(), Duration::from_secs(60));
+
+        let first = cache.auditor_public_key(&address(1)).await.unwrap();
+        let second = cache.auditor_public_key(&address(1)).await.unwrap();
+        assert_eq!(first, second);
+        assert_eq!(cache.inner.calls.load(Ordering::SeqCst), 1);
+
+        // 不同地址分別緩存
+        let other = cache.auditor_public_key(&address(2)).await.unwrap();
+        assert_ne!(other, first);
+        assert_eq!(cache.inner.calls.load(Ordering::SeqCst), 2);
+
+        cache.invalidate(&address(1));
+        cache.auditor_public_key(&address(1)).await.unwrap();
+        assert_eq!(cache.inner.calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_cache_expires_after_ttl() {
+        let cache = CachedKeyLookup::new(MockLookup::default(), Duration::from_millis(20));
+
+        cache.auditor_public_key(&address(1)).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(40)).await;
+        cache.auditor_public_key(&address(1)).await.unwrap();
+        assert_eq!(cache.inner.calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_failed_lookups_are_not_cached() {
+        let cache = CachedKeyLookup::new(MockLookup::default(), Duration::from_secs(60));
+
+        cache.inner.fail.store(true, Ordering::SeqCst);
+        assert!(cache.auditor_public_key(&address(1)).await.is_err());
+
+        cache.inner.fail.store(false, Ordering::SeqCst);
+        assert!(cache.auditor_public_key(&address(1)).await.is_ok());
+        assert!(cache.auditor_public_key(&address(1)).await.is_ok());
+        assert_eq!(cache.inner.calls.load(Ordering::SeqCst), 2);
+    }
+}
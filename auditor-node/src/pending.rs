@@ -0,0 +1,132 @@
+//! 守護模式的待審計 Blob 去重
+//!
+//! 守護進程每輪通過 [`AuditSystemClient::list_pending_audit_blobs`] 分頁掃描鏈上審計事件。
+//! 同一 Blob 在多頁中可能重複出現，鏈上記錄也可能尚未反映本節點剛完成的審計，
+//! 因此在內存中記錄：
+//!
+//! - 本 epoch 已審計的 Blob（鏈上已有記錄或本節點已審計），進入新 epoch 時清空
+//! - 本輪掃描已排入隊列的 Blob，每輪掃描開始時清空
+//!
+//! 進程重啟後狀態丟失，已提交到鏈上的審計仍會通過事件被識別為已審計。
+//!
+//! [`AuditSystemClient::list_pending_audit_blobs`]: crate::sui_client::AuditSystemClient::list_pending_audit_blobs
+
+use crate::sui_client::PendingBlobPage;
+use std::collections::HashSet;
+
+/// 按 epoch 跟踪已審計的 Blob
+#[derive(Debug, Default)]
+pub struct EpochAuditTracker {
+    /// 當前審計輪次
+    epoch: Option<u32>,
+    /// 本 epoch 已審計的 Blob ID
+    audited: HashSet<String>,
+    /// 本輪掃描已排入隊列的 Blob ID
+    scheduled: HashSet<String>,
+}
+
+impl EpochAuditTracker {
+    /// 創建空的跟踪器
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 當前審計輪次
+    pub fn epoch(&self) -> Option<u32> {
+        self.epoch
+    }
+
+    /// 開始一輪掃描；epoch 變化時清空已審計記錄，返回是否進入了新 epoch
+    pub fn begin_scan(&mut self, epoch: u32) -> bool {
+        self.scheduled.clear();
+        if self.epoch == Some(epoch) {
+            return false;
+        }
+
+        self.epoch = Some(epoch);
+        self.audited.clear();
+        true
+    }
+
+    /// 從一頁結果中取出需要審計的 Blob ID
+    ///
+    /// 頁中的 `audited` 記為已審計；已審計或本輪已排隊的 Blob 被過濾掉。
+    pub fn take_pending(&mut self, page: &PendingBlobPage) -> Vec<String> {
+        self.audited.extend(page.audited.iter().cloned());
+
+        page.pending
+            .iter()
+            .map(|blob| &blob.blob_id)
+            .filter(|blob_id| !self.audited.contains(*blob_id))
+            .filter(|blob_id| self.scheduled.insert((*blob_id).clone()))
+            .cloned()
+            .collect()
+    }
+
+    /// 記錄本節點在當前 epoch 完成的審計
+    pub fn mark_audited(&mut self, blob_id: impl Into<String>) {
+        self.audited.insert(blob_id.into());
+    }
+
+    /// Blob 在當前 epoch 是否已審計
+    pub fn is_audited(&self, blob_id: &str) -> bool {
+        self.audited.contains(blob_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sui_client::PendingBlob;
+
+    fn page(pending: &[&str], audited: &[&str]) -> PendingBlobPage {
+        PendingBlobPage {
+            pending: pending
+                .iter()
+                .map(|blob_id| PendingBlob {
+                    blob_id: blob_id.to_string(),
+                    blob_object_id: None,
+                    last_audit_epoch: 0,
+                })
+                .collect(),
+            audited: audited.iter().map(|blob_id| blob_id.to_string()).collect(),
+            next_cursor: None,
+        }
+    }
+
+    #[test]
+    fn test_duplicates_across_pages_are_scheduled_once() {
+        let mut tracker = EpochAuditTracker::new();
+        assert!(tracker.begin_scan(7));
+
+        assert_eq!(
+            tracker.take_pending(&page(&["a", "b"], &["c"])),
+            vec!["a", "b"]
+        );
+        assert_eq!(
+            tracker.take_pending(&page(&["b", "c", "d"], &[])),
+            vec!["d"]
+        );
+        assert!(tracker.is_audited("c"));
+    }
+
+    #[test]
+    fn test_audited_blobs_skipped_until_next_epoch() {
+        let mut tracker = EpochAuditTracker::new();
+        tracker.begin_scan(7);
+        tracker.take_pending(&page(&["a", "b"], &[]));
+        tracker.mark_audited("a");
+
+        // 同一 epoch 的下一輪：未完成的 b 重新排隊，a 被跳過
+        assert!(!tracker.begin_scan(7));
+        assert_eq!(tracker.take_pending(&page(&["a", "b"], &[])), vec!["b"]);
+
+        // 新 epoch 清空記錄
+        assert!(tracker.begin_scan(8));
+        assert_eq!(tracker.epoch(), Some(8));
+        assert_eq!(
+            tracker.take_pending(&page(&["a", "b"], &[])),
+            vec!["a", "b"]
+        );
+    }
+}
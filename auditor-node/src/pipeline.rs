@@ -0,0 +1,1885 @@
+//! 審計流水線
+//!
+//! 將各子系統串成完整流程：
+//!
+//! ```text
+//! 註冊預檢（RegistrationPreflight，可選；按 epoch 緩存）
+//!     ↓
+//! 審計（IntegrityVerifier）
+//!     ├─ 承諾挑戰集（CommitmentLog，啟用時先於第一個挑戰）
+//!     └─ 挑戰-響應驗證
+//!     ↓
+//! 簽名（AuditReportGenerator, Dilithium3）
+//!     ↓
+//! 盲化 Blob ID（BlindingSalt，可選，發布副本重新簽名）
+//!     ↓
+//! 加密（SealClient，可選；Seal 不可用時可降級為本地 AES-256-GCM）
+//!     ↓
+//! 上傳（WalrusPublisher）
+//!     ↓
+//! 提交（SuiRpcSubmitter, audit_core::submit_audit_record）
+//! ```
+//!
+//! 每個階段都只通過 HTTP 與外部服務交互，因此可以在測試中替換為進程內假服務
+//! （見 `test_support` 模塊，需啟用 `test-util` feature）；加密、上傳與提交階段也可以
+//! 通過 [`ReportEncryptor`]、[`ReportUploader`] 與 [`ChainSubmitter`] 直接注入模擬實現。
+//!
+//! 每次審計生成一個關聯 ID（[`crate::logging`]）：各階段在帶 `audit_id` 與 `blob_id` 字段的
+//! span 中執行，同一 ID 寫入報告的 `audit_id`。
+//!
+//! 守護進程與單次審計命令都通過 [`AuditPipeline::from_config`] 構建流水線，
+//! 嵌入其他服務時用法相同：
+//!
+//! ```no_run
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! use auditor_node::config::load_config;
+//! use auditor_node::keystore::Keystore;
+//! use auditor_node::pipeline::AuditPipeline;
+//! use std::path::Path;
+//!
+//! let config = load_config("config.toml")?;
+//! let keystore = Keystore::open(Path::new(&config.pqc_keystore_path))?;
+//! let pipeline = AuditPipeline::from_config(&config, &keystore)?;
+//!
+//! let outcome = pipeline.run_once("eRrTusk8yshFQpkemgDnbg0f4-qDo623V2NpeVG1Zcg").await?;
+//! println!("Report stored as {:?}", outcome.report_blob_id);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::audit_report::AuditReportGenerator;
+use crate::auditor::Auditor;
+use crate::baseline::BaselineStore;
+use crate::blinding::{blind_report, BlindingSalt};
+use crate::blob_lookup::{
+    resolve_blob_ref, BlobMetadataSource, BlobObjectIdSource, ResolvedBlob, SuiBlobResolver,
+    SuiRpcBlobLookup,
+};
+use crate::breaker::CircuitBreaker;
+use crate::chain_types::{AuditRecordParams, MoveId, MoveU256, PolicyParams, AUDIT_CORE_MODULE};
+use crate::challenge_seed::{ChallengeSeedMode, SuiRpcCheckpointSource};
+use crate::checkpoint::CheckpointStore;
+use crate::commitment::CommitmentLog;
+use crate::content_cache::{ContentCache, FileContentCache, MemoryContentCache};
+use crate::dry_run::NoopRecorder;
+use crate::error::{AuditorError, Result};
+use crate::history::AuditHistory;
+use crate::http::client_builder;
+use crate::init::SuiKey;
+use crate::integrity::{AuditData, IntegrityVerifier, VerificationStatus};
+use crate::keystore::Keystore;
+use crate::local_encryption::{EncryptionFallback, LocalAesEncryptor, FALLBACK_KEY_DIR};
+use crate::logging::{audit_span, new_audit_id};
+use crate::metadata_cache::BlobMetadataCache;
+use crate::metrics::{audit_result, Metrics, RESULT_ERROR};
+use crate::notify::NotificationDispatcher;
+use crate::preflight::{AuditorStanding, RegistrationPreflight, SuiRegistryReader};
+use crate::progress::AuditProgress;
+use crate::rate_limit::RateLimiter;
+use crate::resources::ResourceGuard;
+use crate::retry::{retry_with_exponential_backoff_if, RetryConfig};
+use crate::seal_client::{EncryptMetadata, EncryptionScheme, SealApiConfig, SealClient};
+use crate::sui_client::AuditSystemClient;
+use crate::types::{AuditReport, AuditorConfig, BlobId, BlobRef};
+use async_trait::async_trait;
+use base64::{engine::general_purpose, Engine as _};
+use reqwest::Client;
+use serde_json::{json, Value};
+use std::sync::Arc;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tracing::{debug, error, info, info_span, warn, Instrument};
+
+/// 單次上傳請求的總超時
+const PUBLISHER_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(60);
+
+/// Walrus Publisher 客戶端
+///
+/// 通過 `PUT /v1/blobs` 將數據存儲到 Walrus。5xx 響應、超時與連接失敗按
+/// [`RetryConfig`] 指數退避重試，其餘錯誤立即返回
+#[derive(Debug, Clone)]
+pub struct WalrusPublisher {
+    http_client: Client,
+    publisher_url: String,
+    epochs: Option<u32>,
+    retry: RetryConfig,
+}
+
+impl WalrusPublisher {
+    /// 創建新的 Publisher 客戶端（存儲 epoch 數使用 Publisher 的默認值）
+    pub fn new(publisher_url: impl Into<String>) -> Self {
+        let http_client = Client::builder()
+            .timeout(PUBLISHER_TIMEOUT)
+            .build()
+            .expect("Failed to build HTTP client");
+        Self::with_client(http_client, publisher_url)
+    }
+
+    /// 按配置創建（`walrus_publisher_url`、`walrus_storage_epochs` 與 `[http]` 的代理和證書設置）
+    ///
+    /// # 錯誤
+    /// - `[http]` 的代理或證書配置無效: 返回 `Config` 錯誤
+    pub fn from_config(config: &AuditorConfig) -> Result<Self> {
+        let http_client = client_builder(&config.http)?
+            .timeout(PUBLISHER_TIMEOUT)
+            .build()?;
+        let publisher = Self::with_client(http_client, &config.walrus_publisher_url);
+        Ok(match config.walrus_storage_epochs {
+            Some(epochs) => publisher.with_epochs(epochs),
+            None => publisher,
+        })
+    }
+
+    fn with_client(http_client: Client, publisher_url: impl Into<String>) -> Self {
+        Self {
+            http_client,
+            publisher_url: publisher_url.into().trim_end_matches('/').to_string(),
+            epochs: None,
+            retry: RetryConfig::default(),
+        }
+    }
+
+    /// 設置存儲 epoch 數
+    pub fn with_epochs(mut self, epochs: u32) -> Self {
+        self.epochs = Some(epochs);
+        self
+    }
+
+    /// 設置臨時錯誤的重試策略
+    pub fn with_retry(mut self, retry: RetryConfig) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// 存儲數據，返回 Walrus Blob ID
+    pub async fn store(&self, data: &[u8]) -> Result<String> {
+        let url = match self.epochs {
+            Some(epochs) => format!("{}/v1/blobs?epochs={}", self.publisher_url, epochs),
+            None => format!("{}/v1/blobs", self.publisher_url),
+        };
+        debug!("Uploading {} bytes to {}", data.len(), url);
+
+        let response =
+            retry_with_exponential_backoff_if("walrus_store", &self.retry, is_transient, || {
+                self.put(&url, data)
+            })
+            .await?;
+
+        let blob_id = parse_store_response(&response).ok_or_else(|| {
+            AuditorError::Serialization(format!("Unexpected publisher response: {}", response))
+        })?;
+        info!("Stored {} bytes on Walrus: blob {}", data.len(), blob_id);
+        Ok(blob_id)
+    }
+
+    async fn put(&self, url: &str, data: &[u8]) -> Result<Value> {
+        Ok(self
+            .http_client
+            .put(url)
+            .body(data.to_vec())
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?)
+    }
+}
+
+/// 是否為可重試的臨時錯誤（5xx、超時、連接失敗）
+fn is_transient(error: &AuditorError) -> bool {
+    match error {
+        AuditorError::HttpRequest(e) => {
+            e.is_timeout()
+                || e.is_connect()
+                || e.status().is_some_and(|status| status.is_server_error())
+        }
+        _ => false,
+    }
+}
+
+/// 從 Publisher 響應中提取 Blob ID
+///
+/// 支持 `newlyCreated.blobObject.blobId` 和 `alreadyCertified.blobId` 兩種格式
+pub fn parse_store_response(response: &Value) -> Option<String> {
+    response
+        .pointer("/newlyCreated/blobObject/blobId")
+        .or_else(|| response.pointer("/alreadyCertified/blobId"))
+        .and_then(Value::as_str)
+        .map(str::to_string)
+}
+
+/// 基於 Sui JSON-RPC 的審計記錄提交器
+///
+/// 使用 `unsafe_moveCall` 構建 `audit_core::submit_audit_record` 交易。
+/// 返回未簽名的交易字節；交易簽名與執行需要審計員 Sui 私鑰（尚未集成）。
+#[derive(Debug, Clone)]
+pub struct SuiRpcSubmitter {
+    http_client: Client,
+    rpc_url: String,
+    signer: String,
+    package_id: String,
+    audit_config_id: String,
+    gas_budget: u64,
+    metrics: Option<Arc<Metrics>>,
+}
+
+impl SuiRpcSubmitter {
+    /// 創建新的提交器
+    ///
+    /// # 參數
+    /// - `rpc_url`: Sui RPC 端點
+    /// - `signer`: 審計員 Sui 地址
+    /// - `package_id`: audit_system 合約包 ID
+    /// - `audit_config_id`: AuditConfig 共享對象 ID
+    pub fn new(
+        rpc_url: impl Into<String>,
+        signer: impl Into<String>,
+        package_id: impl Into<String>,
+        audit_config_id: impl Into<String>,
+    ) -> Self {
+        let http_client = Client::builder()
+            .timeout(std::time::Duration::from_secs(30))
+            .build()
+            .expect("Failed to build HTTP client");
+
+        Self {
+            http_client,
+            rpc_url: rpc_url.into(),
+            signer: signer.into(),
+            package_id: package_id.into(),
+            audit_config_id: audit_config_id.into(),
+            gas_budget: 10_000_000, // 0.01 SUI
+            metrics: None,
+        }
+    }
+
+    /// 記錄指標：每次失敗的提交計入 `sui_submission_failures_total`
+    pub fn with_metrics(mut self, metrics: Arc<Metrics>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// 構建提交交易，返回 Base64 編碼的未簽名交易字節
+    pub async fn submit(&self, params: &AuditRecordParams) -> Result<String> {
+        let result = self.move_call(params).await;
+        if let (Err(_), Some(metrics)) = (&result, &self.metrics) {
+            metrics.record_sui_submission_failure();
+        }
+        result
+    }
+
+    /// 調用 `unsafe_moveCall` 構建 `submit_audit_record` 交易
+    async fn move_call(&self, params: &AuditRecordParams) -> Result<String> {
+        params.validate()?;
+
+        let request = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "unsafe_moveCall",
+            "params": [
+                self.signer,
+                self.package_id,
+                AUDIT_CORE_MODULE,
+                AuditRecordParams::FUNCTION,
+                [],
+                params.json_args(&self.audit_config_id),
+                null,
+                self.gas_budget.to_string(),
+                null
+            ]
+        });
+
+        let response: Value = self
+            .http_client
+            .post(&self.rpc_url)
+            .json(&request)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        if let Some(error) = response.get("error") {
+            return Err(AuditorError::SuiClient(format!(
+                "submit_audit_record failed: {}",
+                error
+            )));
+        }
+
+        response
+            .pointer("/result/txBytes")
+            .and_then(Value::as_str)
+            .map(str::to_string)
+            .ok_or_else(|| {
+                AuditorError::SuiClient(format!("Missing txBytes in response: {}", response))
+            })
+    }
+}
+
+/// 將 Walrus Blob ID（URL-safe Base64，32 字節小端）轉換為 u256 十進制字符串
+pub fn blob_id_to_u256(blob_id: &str) -> Result<String> {
+    Ok(MoveU256::from_blob_id(blob_id)?.to_decimal_string())
+}
+
+/// 報告加密階段
+#[async_trait]
+pub trait ReportEncryptor: Send + Sync {
+    /// 加密序列化後的報告，返回密文字節與加密元數據
+    async fn encrypt(&self, report_json: &str) -> Result<(Vec<u8>, EncryptMetadata)>;
+}
+
+/// 報告上傳階段
+#[async_trait]
+pub trait ReportUploader: Send + Sync {
+    /// 存儲數據，返回 Walrus Blob ID
+    async fn upload(&self, data: &[u8]) -> Result<String>;
+}
+
+/// 鏈上提交階段
+#[async_trait]
+pub trait ChainSubmitter: Send + Sync {
+    /// 提交審計記錄
+    ///
+    /// # 參數
+    /// - `params`: 由已簽名報告生成的審計記錄參數（使用真實 Blob ID）
+    /// - `report_blob_id`: 報告在 Walrus 上的 Blob ID
+    ///
+    /// # 返回
+    /// - `Ok(Some(tx))`: 交易標識
+    /// - `Ok(None)`: 鏈上沒有可關聯的記錄，未提交
+    async fn submit_record(
+        &self,
+        params: &AuditRecordParams,
+        report_blob_id: &str,
+    ) -> Result<Option<String>>;
+}
+
+#[async_trait]
+impl ReportUploader for WalrusPublisher {
+    async fn upload(&self, data: &[u8]) -> Result<String> {
+        self.store(data).await
+    }
+}
+
+/// 返回 Base64 未簽名交易字節（而非交易摘要）
+#[async_trait]
+impl ChainSubmitter for SuiRpcSubmitter {
+    async fn submit_record(
+        &self,
+        params: &AuditRecordParams,
+        _report_blob_id: &str,
+    ) -> Result<Option<String>> {
+        Ok(Some(self.submit(params).await?))
+    }
+}
+
+/// 默認的 Seal 門檻值
+pub const DEFAULT_SEAL_THRESHOLD: u32 = 2;
+
+/// 基於 Seal API 的報告加密器（IBE identity 為審計員地址）
+pub struct SealReportEncryptor {
+    client: SealClient,
+    auditor_address: String,
+    package_id: String,
+    threshold: u32,
+}
+
+impl SealReportEncryptor {
+    /// 創建加密器
+    pub fn new(
+        client: SealClient,
+        auditor_address: impl Into<String>,
+        package_id: impl Into<String>,
+        threshold: u32,
+    ) -> Self {
+        Self {
+            client,
+            auditor_address: auditor_address.into(),
+            package_id: package_id.into(),
+            threshold,
+        }
+    }
+
+    /// 檢查 Seal API 是否可用
+    pub async fn health_check(&self) -> Result<()> {
+        self.client.health_check().await.map(|_| ())
+    }
+}
+
+#[async_trait]
+impl ReportEncryptor for SealReportEncryptor {
+    async fn encrypt(&self, report_json: &str) -> Result<(Vec<u8>, EncryptMetadata)> {
+        let (ciphertext, _symmetric_key, metadata) = self
+            .client
+            .encrypt_report(
+                report_json,
+                &self.auditor_address,
+                &self.package_id,
+                self.threshold,
+            )
+            .await?;
+
+        let bytes = general_purpose::STANDARD.decode(&ciphertext).map_err(|e| {
+            AuditorError::SealProtocol(format!("Invalid ciphertext encoding: {}", e))
+        })?;
+        Ok((bytes, metadata))
+    }
+}
+
+/// Seal 健康檢查或加密失敗時改用本地 AES-256-GCM 加密的報告加密器
+/// （`encryption_fallback = "local-aes"`，見 [`crate::local_encryption`]）
+///
+/// 返回的元數據以 `scheme` 區分兩種密文
+pub struct FallbackEncryptor {
+    seal: SealReportEncryptor,
+    local: LocalAesEncryptor,
+}
+
+impl FallbackEncryptor {
+    /// 創建加密器
+    pub fn new(seal: SealReportEncryptor, local: LocalAesEncryptor) -> Self {
+        Self { seal, local }
+    }
+}
+
+#[async_trait]
+impl ReportEncryptor for FallbackEncryptor {
+    async fn encrypt(&self, report_json: &str) -> Result<(Vec<u8>, EncryptMetadata)> {
+        let sealed = match self.seal.health_check().await {
+            Ok(()) => self.seal.encrypt(report_json).await,
+            Err(e) => Err(e),
+        };
+        match sealed {
+            Ok(sealed) => Ok(sealed),
+            Err(e) => {
+                warn!(
+                    "Seal encryption failed ({}), encrypting the report locally instead",
+                    e
+                );
+                self.local.encrypt(report_json.as_bytes())
+            }
+        }
+    }
+}
+
+/// 為上傳的報告創建訪問策略（僅審計員本人可讀）
+///
+/// 策略引用本次審計的鏈上 AuditRecord；交易以 `sui_key_path`（或 `auditor_private_key_path`）
+/// 的 Sui 密鑰簽名並執行，返回交易摘要
+pub struct AccessPolicySubmitter {
+    rpc_url: String,
+    package_id: String,
+    access_package_id: String,
+    registry_id: String,
+    incentives_id: String,
+    auditor: MoveId,
+    key: SuiKey,
+}
+
+impl AccessPolicySubmitter {
+    /// 按配置創建（缺少合約 ID、審計員地址或 Sui 密鑰時返回錯誤）
+    pub fn from_config(config: &AuditorConfig) -> Result<Self> {
+        let required = |value: &Option<String>, name: &str| {
+            value
+                .clone()
+                .ok_or_else(|| AuditorError::Config(format!("{} not configured", name)))
+        };
+
+        let auditor = MoveId::from_hex(&required(&config.auditor_address, "auditor_address")?)?;
+        let key = SuiKey::from_config(config)?;
+
+        Ok(Self {
+            rpc_url: config.sui_rpc_url.clone(),
+            package_id: required(&config.audit_system_package_id, "audit_system_package_id")?,
+            access_package_id: required(
+                &config.access_policy_package_id,
+                "access_policy_package_id",
+            )?,
+            registry_id: config.auditor_registry_id.clone().unwrap_or_default(),
+            incentives_id: config.incentives_id.clone().unwrap_or_default(),
+            auditor,
+            key,
+        })
+    }
+}
+
+#[async_trait]
+impl ChainSubmitter for AccessPolicySubmitter {
+    async fn submit_record(
+        &self,
+        params: &AuditRecordParams,
+        report_blob_id: &str,
+    ) -> Result<Option<String>> {
+        let client = AuditSystemClient::new(
+            &self.rpc_url,
+            &self.package_id,
+            &self.access_package_id,
+            &self.registry_id,
+            &self.incentives_id,
+        )
+        .await?;
+
+        let records = client
+            .find_audit_records(&params.blob_id, params.challenge_epoch, &self.auditor)
+            .await?;
+        let Some(record) = records.first() else {
+            return Ok(None);
+        };
+
+        let policy = PolicyParams {
+            report_blob_id: MoveU256::from_blob_id(report_blob_id)?,
+            audit_record_id: record.id,
+            allowed_readers: vec![self.auditor],
+            allowed_auditors: vec![],
+            expires_at_ms: None,
+        };
+        Ok(Some(
+            client.set_report_access_policy(&self.key, &policy).await?,
+        ))
+    }
+}
+
+/// 流水線配置
+#[derive(Debug, Clone)]
+pub struct PipelineConfig {
+    /// 審計員 Sui 地址（Seal IBE identity）
+    pub auditor_address: String,
+
+    /// 審計合約 Package ID
+    pub package_id: String,
+
+    /// Seal 門檻值
+    pub seal_threshold: u32,
+
+    /// 提交審計記錄時使用的 epoch（`None` 時使用報告中的挑戰 epoch）
+    pub challenge_epoch: Option<u32>,
+
+    /// Blob 被所有者刪除或存儲期已結束時是否仍上傳並提交報告
+    pub report_deleted_blobs: bool,
+}
+
+/// 流水線執行結果
+#[derive(Debug, Clone)]
+pub struct PipelineOutcome {
+    /// 審計時 Blob 的可訪問狀態
+    pub status: VerificationStatus,
+
+    /// 已簽名的審計報告
+    pub report: AuditReport,
+
+    /// 盲化後的發布副本（啟用盲化時；`uploaded` 來自此副本）
+    pub published: Option<AuditReport>,
+
+    /// 實際上傳到 Walrus 的字節（加密時為密文；未上傳時為空）
+    pub uploaded: Vec<u8>,
+
+    /// 加密元數據（未加密時為 `None`；`scheme` 區分 Seal 與本地後備加密）
+    pub encryption: Option<EncryptMetadata>,
+
+    /// 報告在 Walrus 上的 Blob ID（已刪除的 Blob 不上報時為 `None`）
+    pub report_blob_id: Option<String>,
+
+    /// 提交到 Sui 的審計記錄參數（未配置提交階段時為 `None`）
+    pub submission: Option<AuditRecordParams>,
+
+    /// 提交交易的標識：執行後為交易摘要，僅構建交易時為 Base64 未簽名交易字節，
+    /// 試運行時為記錄文件路徑
+    pub tx_digest: Option<String>,
+}
+
+/// 完整審計流水線
+///
+/// 審計階段默認使用 Aggregator 完整性驗證；設置存儲節點審計器後改為挑戰存儲節點的 sliver。
+/// 加密、上傳與提交階段通過 [`ReportEncryptor`]、[`ReportUploader`] 與 [`ChainSubmitter`]
+/// 注入，測試可替換為模擬實現。
+pub struct AuditPipeline {
+    verifier: IntegrityVerifier,
+    storage_auditor: Option<Auditor>,
+    blob_metadata: Option<Arc<dyn BlobMetadataSource>>,
+    blob_object_ids: Option<Arc<dyn BlobObjectIdSource>>,
+    generator: AuditReportGenerator,
+    encryptor: Option<Arc<dyn ReportEncryptor>>,
+    blinding: Option<BlindingSalt>,
+    uploader: Arc<dyn ReportUploader>,
+    submitter: Option<Arc<dyn ChainSubmitter>>,
+    preflight: Option<RegistrationPreflight>,
+    metrics: Option<Arc<Metrics>>,
+    progress: Option<mpsc::Sender<AuditProgress>>,
+    config: PipelineConfig,
+}
+
+impl AuditPipeline {
+    /// 創建流水線（默認不加密）
+    pub fn new(
+        verifier: IntegrityVerifier,
+        generator: AuditReportGenerator,
+        uploader: impl ReportUploader + 'static,
+        submitter: impl ChainSubmitter + 'static,
+        config: PipelineConfig,
+    ) -> Self {
+        Self {
+            verifier,
+            storage_auditor: None,
+            blob_metadata: None,
+            blob_object_ids: None,
+            generator,
+            encryptor: None,
+            blinding: None,
+            uploader: Arc::new(uploader),
+            submitter: Some(Arc::new(submitter)),
+            preflight: None,
+            metrics: None,
+            progress: None,
+            config,
+        }
+    }
+
+    /// 按節點配置創建流水線，報告以密鑰庫中的 Dilithium3 密鑰簽名
+    ///
+    /// - `use_storage_node_challenges` 且配置了存儲節點時挑戰存儲節點，否則使用 Aggregator
+    /// - `enable_seal_encryption` 時以 Seal 加密（需要 `seal_api_url`、審計員地址與 Package ID）；
+    ///   `encryption_fallback = "local-aes"` 時 Seal 失敗改用本地加密，密鑰保存在密鑰庫目錄
+    /// - `blind_blob_ids` 時發布盲化副本
+    /// - `submit_to_sui` 時為上傳的報告創建訪問策略（見 [`AccessPolicySubmitter`]）
+    /// - `dry_run` 時上傳與提交改由 [`NoopRecorder`] 記錄到 `dry_run_dir`，
+    ///   不需要 Publisher 與 Sui 簽名密鑰
+    /// - 配置了 `audit_system_package_id` 時，Aggregator 審計可按 Blob 對象 ID 指定 Blob；
+    ///   啟用 `sui-sdk` feature 時還會為 Blob ID 查找對象（見 [`SuiBlobResolver`]）
+    /// - 啟用 `sui-sdk` feature 且配置了審計員地址、`audit_system_package_id` 與
+    ///   `auditor_registry_id` 時，審計前執行註冊預檢（見 [`crate::preflight`]）
+    ///
+    /// 啟用的去重歷史、內容基線與挑戰承諾在此打開，流水線的所有審計共享；
+    /// Aggregator 與存儲節點請求共用一個按 `max_requests_per_sec_per_host` 創建的限流器
+    pub fn from_config(config: &AuditorConfig, keystore: &Keystore) -> Result<Self> {
+        let auditor_address = config
+            .auditor_address
+            .clone()
+            .unwrap_or_else(|| format!("0x{}", "0".repeat(64)));
+
+        let storage_auditor = if !config.use_storage_node_challenges {
+            None
+        } else if config.storage_node_urls.is_empty() {
+            warn!("use_storage_node_challenges is set but no storage_node_urls are configured");
+            warn!("Falling back to the aggregator-based integrity audit");
+            None
+        } else {
+            Some(Auditor::new(
+                config.clone(),
+                auditor_address.clone(),
+                config.storage_node_urls.clone(),
+            )?)
+        };
+
+        if storage_auditor.is_none() && config.verify_blob_id && !cfg!(feature = "walrus-encoder") {
+            return Err(AuditorError::Config(
+                "verify_blob_id is set, but this build has no Walrus RS2 encoder to re-derive \
+                 blob IDs: rebuild with the walrus-encoder feature (see auditor_node::blob_id)"
+                    .to_string(),
+            ));
+        }
+
+        let encryptor: Option<Arc<dyn ReportEncryptor>> = if config.enable_seal_encryption {
+            let missing = |name: &str| AuditorError::Config(format!("{} not configured", name));
+            let api_url = config
+                .seal_api_url
+                .clone()
+                .ok_or_else(|| missing("seal_api_url"))?;
+            let identity = config
+                .auditor_address
+                .as_deref()
+                .ok_or_else(|| missing("auditor_address"))?;
+            let package_id = config
+                .audit_system_package_id
+                .as_deref()
+                .ok_or_else(|| missing("audit_system_package_id"))?;
+
+            let client = SealClient::from_http_config(
+                SealApiConfig {
+                    api_url,
+                    timeout_secs: 30,
+                },
+                &config.http,
+            )?;
+            let seal =
+                SealReportEncryptor::new(client, identity, package_id, DEFAULT_SEAL_THRESHOLD);
+            match config.encryption_fallback {
+                EncryptionFallback::None => Some(Arc::new(seal)),
+                EncryptionFallback::LocalAes => {
+                    let local = LocalAesEncryptor::new(
+                        keystore.base_path().join(FALLBACK_KEY_DIR),
+                        identity,
+                        package_id,
+                        DEFAULT_SEAL_THRESHOLD,
+                    );
+                    Some(Arc::new(FallbackEncryptor::new(seal, local)))
+                }
+            }
+        } else {
+            None
+        };
+
+        let blinding = if config.blind_blob_ids {
+            Some(keystore.blinding_salt()?)
+        } else {
+            None
+        };
+
+        let (uploader, submitter): (Arc<dyn ReportUploader>, Option<Arc<dyn ChainSubmitter>>) =
+            if config.dry_run {
+                let recorder = Arc::new(NoopRecorder::from_config(config)?);
+                info!(
+                    "DRY RUN: uploads and submissions are recorded in {}",
+                    recorder.dir().display()
+                );
+                let submitter = config
+                    .submit_to_sui
+                    .then(|| Arc::clone(&recorder) as Arc<dyn ChainSubmitter>);
+                (recorder, submitter)
+            } else {
+                let submitter: Option<Arc<dyn ChainSubmitter>> = if config.submit_to_sui {
+                    Some(Arc::new(AccessPolicySubmitter::from_config(config)?))
+                } else {
+                    None
+                };
+                (Arc::new(WalrusPublisher::from_config(config)?), submitter)
+            };
+
+        let mut generator =
+            AuditReportGenerator::new(keystore.signer().clone(), Some(auditor_address.clone()));
+        if let Some(threshold) = config.report_prehash_threshold {
+            generator = generator.with_prehash_threshold(threshold);
+        }
+
+        let resolver = config.audit_system_package_id.as_ref().map(|package_id| {
+            Arc::new(SuiBlobResolver::new(
+                config.sui_rpc_url.clone(),
+                package_id.clone(),
+                std::time::Duration::from_secs(config.blob_metadata_cache_ttl_secs),
+            ))
+        });
+
+        // 註冊狀態以 devInspect 讀取，與 Blob 對象查找一樣需要 sui-sdk feature
+        let preflight = match (
+            &config.auditor_address,
+            &config.audit_system_package_id,
+            &config.auditor_registry_id,
+        ) {
+            (Some(address), Some(package_id), Some(registry_id)) if cfg!(feature = "sui-sdk") => {
+                let reader = SuiRegistryReader::new(
+                    config.sui_rpc_url.clone(),
+                    package_id.clone(),
+                    registry_id.clone(),
+                );
+                Some(
+                    RegistrationPreflight::new(
+                        Arc::new(reader),
+                        MoveId::from_hex(address)?,
+                        package_id.clone(),
+                        registry_id.clone(),
+                    )
+                    .with_allow_unregistered(config.allow_unregistered),
+                )
+            }
+            _ => None,
+        };
+
+        let rate_limiter = Arc::new(RateLimiter::from_config(config));
+        Ok(Self {
+            verifier: Self::verifier_from_config(config)?
+                .with_rate_limiter(Arc::clone(&rate_limiter)),
+            storage_auditor: storage_auditor.map(|auditor| auditor.with_rate_limiter(rate_limiter)),
+            blob_metadata: resolver
+                .clone()
+                .map(|resolver| resolver as Arc<dyn BlobMetadataSource>),
+            // Blob ID → 對象的查找翻閱審計事件，與守護模式一樣需要 sui-sdk feature
+            blob_object_ids: resolver
+                .filter(|_| cfg!(feature = "sui-sdk"))
+                .map(|resolver| resolver as Arc<dyn BlobObjectIdSource>),
+            generator,
+            encryptor,
+            blinding,
+            uploader,
+            submitter,
+            preflight,
+            metrics: None,
+            progress: None,
+            config: PipelineConfig {
+                auditor_address,
+                package_id: config.audit_system_package_id.clone().unwrap_or_default(),
+                seal_threshold: DEFAULT_SEAL_THRESHOLD,
+                challenge_epoch: None,
+                report_deleted_blobs: config.report_deleted_blobs,
+            },
+        })
+    }
+
+    /// 按配置創建 Aggregator 完整性驗證器
+    fn verifier_from_config(config: &AuditorConfig) -> Result<IntegrityVerifier> {
+        let mut verifier = IntegrityVerifier::from_http_config(
+            config.walrus_aggregator_url.clone(),
+            &config.http,
+        )?
+        .with_resource_guard(ResourceGuard::from_config(config))
+        .with_object_lookup(Arc::new(SuiRpcBlobLookup::new(config.sui_rpc_url.clone())))
+        .with_chunk_filter(config.chunk_filter.clone());
+
+        if config.capture_http {
+            verifier = verifier.with_capture_dir(&config.capture_dir);
+        }
+
+        #[cfg(feature = "walrus-encoder")]
+        if config.verify_blob_id {
+            let encoder = crate::blob_id::WalrusRs2Encoder::new(config.verify_blob_id_n_shards)?;
+            verifier = verifier
+                .with_blob_id_verification(Arc::new(encoder), config.verify_blob_id_max_bytes);
+        }
+
+        if config.dedup.enabled {
+            let history = AuditHistory::open_with_rotation(
+                &config.dedup.history_path,
+                config.rotation.for_output(crate::history::ROTATION_OUTPUT),
+            )?;
+            verifier = verifier.with_dedup(Arc::new(history), config.dedup.clone());
+        }
+
+        if config.baseline.enabled {
+            let baselines = BaselineStore::open_with_rotation(
+                &config.baseline.path,
+                config.rotation.for_output(crate::baseline::ROTATION_OUTPUT),
+            )?;
+            let pruned = baselines.prune(
+                chrono::Utc::now().timestamp() as u64,
+                config.baseline.max_age_secs,
+            )?;
+            if pruned > 0 {
+                info!("Pruned {} expired content baseline observations", pruned);
+            }
+            verifier = verifier.with_baseline(Arc::new(baselines));
+        }
+
+        if config.commitment.enabled {
+            let commitments = CommitmentLog::open_with_rotation(
+                &config.commitment.log_path,
+                config
+                    .rotation
+                    .for_output(crate::commitment::ROTATION_OUTPUT),
+            )?;
+            verifier = verifier.with_commitments(Arc::new(commitments));
+        }
+
+        if config.challenge_seed_mode == ChallengeSeedMode::SuiCheckpoint {
+            let auditor_address = config.auditor_address.clone().ok_or_else(|| {
+                AuditorError::Config(
+                    "challenge_seed_mode = \"sui_checkpoint\" requires auditor_address".to_string(),
+                )
+            })?;
+            verifier = verifier.with_challenge_seed(
+                Arc::new(SuiRpcCheckpointSource::new(config.sui_rpc_url.clone())),
+                auditor_address,
+            );
+        }
+
+        if let Some(dir) = &config.checkpoint_dir {
+            verifier = verifier.with_checkpoints(Arc::new(CheckpointStore::open(dir)?));
+        }
+
+        if config.content_cache.enabled {
+            let max_entries = config.content_cache.max_entries;
+            let cache: Arc<dyn ContentCache> = match &config.content_cache.path {
+                Some(path) => Arc::new(FileContentCache::open(path, max_entries)?),
+                None => Arc::new(MemoryContentCache::new(max_entries)),
+            };
+            verifier = verifier.with_content_cache(cache);
+        }
+
+        Ok(verifier)
+    }
+
+    /// 啟用 Seal 加密（identity、Package ID 與門檻值取自 [`PipelineConfig`]）
+    pub fn with_seal(self, seal: SealClient) -> Self {
+        let encryptor = SealReportEncryptor::new(
+            seal,
+            self.config.auditor_address.clone(),
+            self.config.package_id.clone(),
+            self.config.seal_threshold,
+        );
+        self.with_encryptor(Arc::new(encryptor))
+    }
+
+    /// 使用自定義的加密階段
+    pub fn with_encryptor(mut self, encryptor: Arc<dyn ReportEncryptor>) -> Self {
+        self.encryptor = Some(encryptor);
+        self
+    }
+
+    /// 啟用 Blob ID 盲化：上傳的報告使用 `HMAC(blob_id, salt)` 代替真實 ID
+    pub fn with_blinding(mut self, salt: BlindingSalt) -> Self {
+        self.blinding = Some(salt);
+        self
+    }
+
+    /// 改為挑戰存儲節點的 sliver（代替 Aggregator 完整性驗證）
+    pub fn with_storage_auditor(mut self, auditor: Auditor) -> Self {
+        self.storage_auditor = Some(auditor);
+        self
+    }
+
+    /// Aggregator 審計按 Blob 對象 ID 指定 Blob 時，從 `source` 讀取對象（得到 Blob ID）
+    pub fn with_blob_metadata_source(mut self, source: Arc<dyn BlobMetadataSource>) -> Self {
+        self.blob_metadata = Some(source);
+        self
+    }
+
+    /// Aggregator 審計按 Blob ID 指定 Blob 時，以 `source` 查找其對象，報告同時記錄兩者
+    ///
+    /// 需同時設置 [`with_blob_metadata_source`](Self::with_blob_metadata_source)，否則不查找
+    pub fn with_blob_object_ids(mut self, source: Arc<dyn BlobObjectIdSource>) -> Self {
+        self.blob_object_ids = Some(source);
+        self
+    }
+
+    /// 審計前執行註冊預檢：未註冊的審計員拒絕審計，聲譽分數記入報告
+    pub fn with_preflight(mut self, preflight: RegistrationPreflight) -> Self {
+        self.preflight = Some(preflight);
+        self
+    }
+
+    /// 使用外部共享的熔斷器（如守護進程級別的熔斷器）
+    pub fn with_breaker(mut self, breaker: Arc<CircuitBreaker>) -> Self {
+        self.verifier = self.verifier.with_breaker(Arc::clone(&breaker));
+        self.storage_auditor = self
+            .storage_auditor
+            .map(|auditor| auditor.with_breaker(breaker));
+        self
+    }
+
+    /// 使用外部共享的限流器（Aggregator 與存儲節點請求共用）
+    pub fn with_rate_limiter(mut self, rate_limiter: Arc<RateLimiter>) -> Self {
+        self.verifier = self.verifier.with_rate_limiter(Arc::clone(&rate_limiter));
+        self.storage_auditor = self
+            .storage_auditor
+            .map(|auditor| auditor.with_rate_limiter(rate_limiter));
+        self
+    }
+
+    /// 存儲節點審計使用外部共享的 Blob 元數據緩存（如守護進程預取時填充的緩存）
+    pub fn with_metadata_cache(mut self, cache: Arc<BlobMetadataCache>) -> Self {
+        self.storage_auditor = self
+            .storage_auditor
+            .map(|auditor| auditor.with_metadata_cache(cache));
+        self
+    }
+
+    /// 在後台啟動存儲節點健康監控（挑戰存儲節點且啟用 `node_health` 時）
+    pub fn spawn_health_monitor(&self) -> Option<JoinHandle<()>> {
+        self.storage_auditor
+            .as_ref()
+            .and_then(Auditor::spawn_health_monitor)
+    }
+
+    /// 記錄審計指標
+    pub fn with_metrics(mut self, metrics: Arc<Metrics>) -> Self {
+        self.verifier = self.verifier.with_metrics(Arc::clone(&metrics));
+        self.storage_auditor = self
+            .storage_auditor
+            .map(|auditor| auditor.with_metrics(Arc::clone(&metrics)));
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// 審計階段向 `progress` 發出進度事件（見 [`crate::progress`]；比對已知哈希時不發出）
+    pub fn with_progress(mut self, progress: mpsc::Sender<AuditProgress>) -> Self {
+        self.progress = Some(progress);
+        self
+    }
+
+    /// 存儲節點被健康監控排除時發出通知（須在 [`spawn_health_monitor`](Self::spawn_health_monitor) 之前調用）
+    pub fn with_notifier(mut self, notifier: NotificationDispatcher) -> Self {
+        self.storage_auditor = self
+            .storage_auditor
+            .map(|auditor| auditor.with_notifier(notifier));
+        self
+    }
+
+    /// 執行註冊預檢（未設置預檢時返回 `None`）
+    ///
+    /// 同一 epoch 內使用緩存的結果。守護進程在每輪審計開始前調用，使新 epoch 的聲譽被重新讀取
+    ///
+    /// # 錯誤
+    /// - 審計員未註冊且不允許未註冊時返回 `AuditorNotRegistered`
+    pub async fn preflight(&self) -> Result<Option<AuditorStanding>> {
+        match &self.preflight {
+            Some(preflight) => preflight.check().instrument(info_span!("preflight")).await,
+            None => Ok(None),
+        }
+    }
+
+    /// 執行完整流水線（不比對已知的內容哈希）
+    pub async fn run_once(&self, blob_id: &str) -> Result<PipelineOutcome> {
+        self.run(blob_id, None).await
+    }
+
+    /// 執行完整流水線
+    ///
+    /// Blob 已被所有者刪除或存儲期已結束且未配置 `report_deleted_blobs` 時，報告仍簽名，但不上傳也不提交
+    ///
+    /// # 參數
+    /// - `blob_id`: 要審計的 Blob ID（URL-safe Base64 或 `0x` 十六進制 `u256`）；
+    ///   挑戰存儲節點時 `0x` 十六進制按 Blob 對象 ID 解釋（見 [`run_blob`](Self::run_blob)）
+    /// - `expected_hash`: 已知的內容哈希（提供時執行一致性比對；挑戰存儲節點時忽略）
+    pub async fn run(&self, blob_id: &str, expected_hash: Option<&str>) -> Result<PipelineOutcome> {
+        self.run_blob(&self.blob_ref(blob_id), expected_hash).await
+    }
+
+    /// 執行完整流水線，Blob 以 Blob ID 或 Blob 對象 ID 明確指定
+    ///
+    /// 審計前解析出另一個標識，使報告同時記錄兩者（見 [`resolve_blob_ref`]）
+    pub async fn run_blob(
+        &self,
+        blob: &BlobRef,
+        expected_hash: Option<&str>,
+    ) -> Result<PipelineOutcome> {
+        let audit_id = new_audit_id();
+        self.run_audit(&audit_id, blob, expected_hash)
+            .instrument(audit_span(&audit_id, blob.as_str()))
+            .await
+    }
+
+    /// 按審計方式解釋字符串形式的 Blob 標識（如 `--blob-id` 的值）
+    ///
+    /// 挑戰存儲節點時沿用既有約定：能解析為對象 ID 的 `0x` 十六進制是 Blob 對象 ID
+    pub fn blob_ref(&self, blob_id: &str) -> BlobRef {
+        if self.storage_auditor.is_some() && MoveId::from_hex(blob_id).is_ok() {
+            BlobRef::ObjectId(blob_id.to_string())
+        } else {
+            BlobRef::BlobId(blob_id.to_string())
+        }
+    }
+
+    async fn run_audit(
+        &self,
+        audit_id: &str,
+        blob: &BlobRef,
+        expected_hash: Option<&str>,
+    ) -> Result<PipelineOutcome> {
+        let blob_id = blob.as_str();
+
+        // 0. 註冊預檢（已有緩存的結果時不再查詢）
+        let cached = self
+            .preflight
+            .as_ref()
+            .and_then(RegistrationPreflight::standing);
+        let standing = match cached {
+            Some(standing) => Some(standing),
+            None => self.preflight().await?,
+        };
+
+        // 1. 審計
+        let (mut report, status) = self.audit_with(blob, expected_hash).await?;
+        report.audit_id = Some(audit_id.to_string());
+        report.auditor_reputation_at_audit = standing.and_then(|standing| standing.reputation);
+
+        // 2. 簽名
+        let report = info_span!("sign").in_scope(|| self.generator.sign_report(report))?;
+        if let Some(history) = self.verifier.dedup_history() {
+            if status == VerificationStatus::Deleted {
+                history.forget_blob(&report.blob_id)?;
+            } else {
+                history.record(&report)?;
+            }
+        }
+
+        let mut outcome = PipelineOutcome {
+            status,
+            report,
+            published: None,
+            uploaded: Vec::new(),
+            encryption: None,
+            report_blob_id: None,
+            submission: None,
+            tx_digest: None,
+        };
+        let removed = matches!(
+            outcome.status,
+            VerificationStatus::Deleted | VerificationStatus::Expired
+        );
+        if removed && !self.config.report_deleted_blobs {
+            info!(
+                "Blob {} was deleted by its owner or has expired ({:?}); report not published",
+                blob_id, outcome.status
+            );
+            return Ok(outcome);
+        }
+
+        outcome.published = self
+            .blinding
+            .as_ref()
+            .map(|salt| blind_report(&outcome.report, salt, self.generator.signer()))
+            .transpose()?;
+        let report_json =
+            serde_json::to_string(outcome.published.as_ref().unwrap_or(&outcome.report))?;
+
+        // 3. 加密
+        let (uploaded, encryption) = match &self.encryptor {
+            Some(encryptor) => {
+                let (ciphertext, metadata) = encryptor
+                    .encrypt(&report_json)
+                    .instrument(info_span!("encrypt"))
+                    .await?;
+                (ciphertext, Some(metadata))
+            }
+            None => (report_json.into_bytes(), None),
+        };
+
+        // 4. 上傳
+        let report_blob_id = self
+            .uploader
+            .upload(&uploaded)
+            .instrument(info_span!("upload"))
+            .await?;
+        info!("Report for blob {} stored as {}", blob_id, report_blob_id);
+
+        // 5. 提交（鏈上記錄仍使用真實 Blob ID）
+        if let Some(submitter) = &self.submitter {
+            let epoch = self
+                .config
+                .challenge_epoch
+                .unwrap_or(outcome.report.challenge_epoch);
+            let submission = AuditRecordParams::from_report(&outcome.report, None, epoch)?;
+            outcome.tx_digest = submitter
+                .submit_record(&submission, &report_blob_id)
+                .instrument(info_span!("submit"))
+                .await?;
+            if outcome.tx_digest.is_none() {
+                warn!(
+                    "No AuditRecord on chain for blob {}; nothing submitted",
+                    blob_id
+                );
+            }
+            outcome.submission = Some(submission);
+        }
+
+        outcome.uploaded = uploaded;
+        outcome.encryption = encryption;
+        outcome.report_blob_id = Some(report_blob_id);
+        Ok(outcome)
+    }
+
+    /// 只執行審計階段，返回未簽名的報告（已記錄 `audit_id`）與 Blob 的可訪問狀態
+    pub async fn audit(&self, blob_id: &str) -> Result<(AuditReport, VerificationStatus)> {
+        let audit_id = new_audit_id();
+        let (mut report, status) = self
+            .audit_with(&self.blob_ref(blob_id), None)
+            .instrument(audit_span(&audit_id, blob_id))
+            .await?;
+        report.audit_id = Some(audit_id);
+        Ok((report, status))
+    }
+
+    async fn audit_with(
+        &self,
+        blob: &BlobRef,
+        expected_hash: Option<&str>,
+    ) -> Result<(AuditReport, VerificationStatus)> {
+        info!("🔍 Starting audit for {}", blob);
+
+        if let Some(auditor) = &self.storage_auditor {
+            return self
+                .audit_storage_nodes(auditor, blob)
+                .instrument(info_span!("challenge"))
+                .await;
+        }
+
+        let resolved = self
+            .resolve_blob(blob)
+            .instrument(info_span!("resolve"))
+            .await?;
+        let blob_id = resolved.blob_id;
+        let mut audit_data = async {
+            match (expected_hash, &self.progress) {
+                (Some(expected), _) => self.verifier.verify_blob(&blob_id, expected).await,
+                (None, Some(progress)) => {
+                    self.verifier
+                        .audit_blob_resumable_with_progress(&blob_id, progress.clone())
+                        .await
+                }
+                (None, None) => self.verifier.audit_blob_resumable(&blob_id).await,
+            }
+        }
+        .instrument(info_span!("fetch"))
+        .await?;
+        if let Some(metadata) = &resolved.metadata {
+            audit_data.sui_object_id = Some(metadata.blob_object_id.clone());
+            audit_data.apply_chain_metadata(metadata);
+        }
+        log_audit_data(&audit_data);
+
+        let status = audit_data.verification_status.clone();
+        Ok((AuditReport::from(audit_data), status))
+    }
+
+    /// 解析 Aggregator 審計的 Blob 標識（見 [`resolve_blob_ref`]）
+    ///
+    /// 未設置 Blob 元數據來源時只接受 Blob ID
+    async fn resolve_blob(&self, blob: &BlobRef) -> Result<ResolvedBlob> {
+        let Some(metadata) = &self.blob_metadata else {
+            return match blob {
+                BlobRef::BlobId(blob_id) => Ok(ResolvedBlob {
+                    blob_id: BlobId::parse(blob_id)?,
+                    blob_object_id: None,
+                    metadata: None,
+                }),
+                BlobRef::ObjectId(object_id) => Err(AuditorError::BlobResolution {
+                    step: format!("read blob object {} from Sui", object_id),
+                    reason: "audit_system_package_id is not configured".to_string(),
+                }),
+            };
+        };
+
+        let resolved =
+            resolve_blob_ref(blob, metadata.as_ref(), self.blob_object_ids.as_deref()).await?;
+        match &resolved.blob_object_id {
+            Some(object_id) => info!("   - Blob {} is object {}", resolved.blob_id, object_id),
+            None if self.blob_object_ids.is_some() => {
+                warn!(
+                    "No Sui object found for blob {}; the report records the blob ID only",
+                    resolved.blob_id
+                )
+            }
+            None => {}
+        }
+        Ok(resolved)
+    }
+
+    /// 挑戰存儲節點的 sliver（證明按鏈上根驗證）
+    async fn audit_storage_nodes(
+        &self,
+        auditor: &Auditor,
+        blob: &BlobRef,
+    ) -> Result<(AuditReport, VerificationStatus)> {
+        let started = std::time::Instant::now();
+        let result = match &self.progress {
+            Some(progress) => {
+                auditor
+                    .audit_blob_with_progress(blob, progress.clone())
+                    .await
+            }
+            None => auditor.audit_blob(blob).await,
+        };
+        if let Some(metrics) = &self.metrics {
+            match &result {
+                Ok(report) => {
+                    let status = report.verification_status();
+                    metrics.record_audit(audit_result(&status), started.elapsed());
+                    metrics.record_challenges(
+                        report.successful_verifications.into(),
+                        report.failed_verifications.into(),
+                    );
+                }
+                Err(_) => metrics.record_audit(RESULT_ERROR, started.elapsed()),
+            }
+        }
+        let report = result?;
+
+        info!("✅ Sliver challenges completed:");
+        info!("   - Challenge epoch: {}", report.challenge_epoch);
+        info!(
+            "   - Challenge stats: {}/{} successful",
+            report.successful_verifications, report.total_challenges
+        );
+
+        // 存儲節點失敗按挑戰計數；Blob 本身在鏈上可達（存儲期已結束時為 `Expired`）
+        let status = report.blob_status.clone().unwrap_or(VerificationStatus::Accessible);
+        Ok((report, status))
+    }
+}
+
+/// 記錄完整性驗證的關鍵結果
+fn log_audit_data(audit_data: &AuditData) {
+    if let Some(decision) = &audit_data.resource_decision {
+        info!("   - Resource guard: {:?}", decision.action);
+    }
+
+    if let Some(digest) = &audit_data.capture_digest {
+        info!("   - HTTP capture digest (Blake2b-256): {}", digest);
+    }
+
+    if let Some(source) = &audit_data.deduplicated_from {
+        info!(
+            "   - Deduplicated from blob {} (report {}), spot-checked only",
+            source.blob_id, source.report_digest
+        );
+    }
+
+    if let Some(drift) = &audit_data.hash_drift {
+        error!(
+            "❌ Content drifted from the baseline recorded at {}:",
+            drift.baseline_at
+        );
+        error!(
+            "   - Expected content hash: {}",
+            drift.expected_content_hash
+        );
+        error!(
+            "   - Observed content hash: {}",
+            drift.observed_content_hash
+        );
+    }
+
+    info!("✅ Merkle verification completed:");
+    info!("   - Content hash (SHA-256): {}", audit_data.content_hash);
+    info!("   - Merkle root (Blake2b-256): {}", audit_data.merkle_root);
+    info!(
+        "   - Challenge stats: {}/{} successful",
+        audit_data.successful_verifications, audit_data.total_challenges
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::preflight::AuditorRegistryReader;
+    use crate::report::ReportManager;
+    use crate::test_support::{deterministic_blob, AggregatorMode, FakeAggregator, FakePublisher};
+    use crate::types::BlobMetadata;
+    use axum::http::StatusCode;
+    use pqc_signer::{Dilithium3Signer, Signer};
+    use std::collections::HashSet;
+    use std::sync::Mutex;
+
+    const BLOB_ID: &str = "eRrTusk8yshFQpkemgDnbg0f4-qDo623V2NpeVG1Zcg";
+    const AUDITOR: &str = "0x1234567890abcdef1234567890abcdef1234567890abcdef1234567890abcdef";
+
+    /// 記錄上傳內容，返回固定的 Blob ID
+    #[derive(Clone, Default)]
+    struct MockUploader(Arc<Mutex<Vec<Vec<u8>>>>);
+
+    #[async_trait]
+    impl ReportUploader for MockUploader {
+        async fn upload(&self, data: &[u8]) -> Result<String> {
+            self.0.lock().unwrap().push(data.to_vec());
+            Ok("report-blob".to_string())
+        }
+    }
+
+    /// 記錄提交時的報告 Blob ID，返回固定的交易摘要
+    #[derive(Clone, Default)]
+    struct MockSubmitter(Arc<Mutex<Vec<String>>>);
+
+    #[async_trait]
+    impl ChainSubmitter for MockSubmitter {
+        async fn submit_record(
+            &self,
+            _params: &AuditRecordParams,
+            report_blob_id: &str,
+        ) -> Result<Option<String>> {
+            self.0.lock().unwrap().push(report_blob_id.to_string());
+            Ok(Some("tx-digest".to_string()))
+        }
+    }
+
+    /// 以反轉字節代替加密
+    struct ReversingEncryptor;
+
+    #[async_trait]
+    impl ReportEncryptor for ReversingEncryptor {
+        async fn encrypt(&self, report_json: &str) -> Result<(Vec<u8>, EncryptMetadata)> {
+            let ciphertext: Vec<u8> = report_json.bytes().rev().collect();
+            let metadata = EncryptMetadata {
+                identity: AUDITOR.to_string(),
+                package_id: "0xpackage".to_string(),
+                threshold: 2,
+                encrypted_at: 0,
+                original_size: report_json.len(),
+                encrypted_size: ciphertext.len(),
+                duration: 0,
+                scheme: EncryptionScheme::Seal,
+                key_id: None,
+            };
+            Ok((ciphertext, metadata))
+        }
+    }
+
+    fn fast_retry() -> RetryConfig {
+        RetryConfig {
+            max_retries: 3,
+            initial_delay_ms: 1,
+            multiplier: 2.0,
+            max_delay_ms: 10,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_run_once_with_mock_stages() {
+        let aggregator =
+            FakeAggregator::start(deterministic_blob(2 * 4096), AggregatorMode::Healthy).await;
+        let mut signer = Dilithium3Signer::new();
+        signer.generate_keypair().unwrap();
+        let public_key = signer.public_key().to_vec();
+
+        let uploader = MockUploader::default();
+        let submitter = MockSubmitter::default();
+        let pipeline = AuditPipeline::new(
+            IntegrityVerifier::new(aggregator.url().to_string()),
+            AuditReportGenerator::new(signer, Some(AUDITOR.to_string())),
+            uploader.clone(),
+            submitter.clone(),
+            PipelineConfig {
+                auditor_address: AUDITOR.to_string(),
+                package_id: "0xpackage".to_string(),
+                seal_threshold: 2,
+                challenge_epoch: None,
+                report_deleted_blobs: false,
+            },
+        )
+        .with_encryptor(Arc::new(ReversingEncryptor));
+
+        let outcome = pipeline.run_once(BLOB_ID).await.unwrap();
+        assert!(ReportManager::verify_report(&outcome.report, &public_key).unwrap());
+        assert_eq!(outcome.report.auditor, AUDITOR);
+
+        // 上傳的是加密階段的輸出，解密後為已簽名報告
+        assert_eq!(*uploader.0.lock().unwrap(), vec![outcome.uploaded.clone()]);
+        let plaintext: Vec<u8> = outcome.uploaded.iter().rev().copied().collect();
+        let uploaded: AuditReport = serde_json::from_slice(&plaintext).unwrap();
+        assert_eq!(uploaded.pqc_signature, outcome.report.pqc_signature);
+        assert_eq!(outcome.encryption.as_ref().unwrap().threshold, 2);
+
+        // 提交階段收到上傳後的 Blob ID；未指定 epoch 時使用報告中的挑戰 epoch
+        assert_eq!(outcome.report_blob_id.as_deref(), Some("report-blob"));
+        assert_eq!(
+            *submitter.0.lock().unwrap(),
+            vec!["report-blob".to_string()]
+        );
+        assert_eq!(outcome.tx_digest.as_deref(), Some("tx-digest"));
+        assert_eq!(
+            outcome.submission.unwrap().challenge_epoch,
+            outcome.report.challenge_epoch
+        );
+    }
+
+    #[tokio::test]
+    async fn test_deleted_blob_is_removed_from_dedup_history() {
+        use crate::history::{DedupConfig, HistoryEntry};
+        use axum::{routing::get, Router};
+
+        let app = Router::new().route(
+            "/v1/blobs/:id",
+            get(|| async { (StatusCode::NOT_FOUND, "blob has been deleted by its owner") }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+
+        let history = Arc::new(AuditHistory::in_memory());
+        history
+            .record_entry(HistoryEntry {
+                blob_id: BLOB_ID.to_string(),
+                content_hash: "c0ffee".to_string(),
+                report_digest: "digest".to_string(),
+                audited_at: 100,
+                deduplicated: false,
+                chunk_filter: None,
+            })
+            .unwrap();
+
+        let mut signer = Dilithium3Signer::new();
+        signer.generate_keypair().unwrap();
+        let pipeline = AuditPipeline::new(
+            IntegrityVerifier::new(url).with_dedup(Arc::clone(&history), DedupConfig::default()),
+            AuditReportGenerator::new(signer, Some(AUDITOR.to_string())),
+            MockUploader::default(),
+            MockSubmitter::default(),
+            PipelineConfig {
+                auditor_address: AUDITOR.to_string(),
+                package_id: "0xpackage".to_string(),
+                seal_threshold: 2,
+                challenge_epoch: None,
+                report_deleted_blobs: false,
+            },
+        );
+
+        let outcome = pipeline.run_once(BLOB_ID).await.unwrap();
+        assert_eq!(outcome.status, VerificationStatus::Deleted);
+        assert!(history.is_empty());
+        assert!(history
+            .find_fresh("c0ffee", "other-blob", 200, 1_000)
+            .is_none());
+    }
+
+    /// 收集格式化後日誌的寫入端
+    #[derive(Clone, Default)]
+    struct CapturedLogs(Arc<Mutex<Vec<u8>>>);
+
+    impl std::io::Write for CapturedLogs {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_audit_id_is_attached_to_logs_and_report() {
+        let logs = CapturedLogs::default();
+        let writer = logs.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .json()
+            .with_writer(move || writer.clone())
+            .finish();
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let aggregator =
+            FakeAggregator::start(deterministic_blob(2 * 4096), AggregatorMode::Healthy).await;
+        let mut signer = Dilithium3Signer::new();
+        signer.generate_keypair().unwrap();
+        let public_key = signer.public_key().to_vec();
+        let pipeline = AuditPipeline::new(
+            IntegrityVerifier::new(aggregator.url().to_string()),
+            AuditReportGenerator::new(signer, Some(AUDITOR.to_string())),
+            MockUploader::default(),
+            MockSubmitter::default(),
+            PipelineConfig {
+                auditor_address: AUDITOR.to_string(),
+                package_id: "0xpackage".to_string(),
+                seal_threshold: 2,
+                challenge_epoch: None,
+                report_deleted_blobs: false,
+            },
+        )
+        .with_encryptor(Arc::new(ReversingEncryptor));
+
+        let outcome = pipeline.run_once(BLOB_ID).await.unwrap();
+        let audit_id = outcome.report.audit_id.clone().unwrap();
+        assert!(uuid::Uuid::parse_str(&audit_id).is_ok());
+        // 關聯 ID 在簽名範圍內
+        assert!(ReportManager::verify_report(&outcome.report, &public_key).unwrap());
+
+        let output = String::from_utf8(logs.0.lock().unwrap().clone()).unwrap();
+        let lines: Vec<Value> = output
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .filter(|line: &Value| {
+                line["target"]
+                    .as_str()
+                    .is_some_and(|target| target.starts_with("auditor_node"))
+            })
+            .collect();
+        assert!(!lines.is_empty());
+
+        // 每行日誌都在審計 span 中，帶有 audit_id 與 blob_id
+        let mut stages = HashSet::new();
+        for line in &lines {
+            let spans = line["spans"].as_array().expect("log line outside any span");
+            assert_eq!(spans[0]["name"], "audit");
+            assert_eq!(spans[0]["audit_id"], audit_id.as_str());
+            assert_eq!(spans[0]["blob_id"], BLOB_ID);
+            stages.extend(spans.iter().filter_map(|span| span["name"].as_str()));
+        }
+        assert!(stages.contains("fetch"));
+
+        // 每次審計的 ID 不同
+        let (report, _) = pipeline.audit(BLOB_ID).await.unwrap();
+        assert_ne!(report.audit_id.as_deref(), Some(audit_id.as_str()));
+    }
+
+    const BLOB_OBJECT_ID: &str = "0xb10b";
+
+    /// 只認識 `BLOB_ID` ↔ `BLOB_OBJECT_ID` 一對標識的解析器
+    struct StubBlobIndex;
+
+    #[async_trait]
+    impl BlobMetadataSource for StubBlobIndex {
+        async fn blob_metadata(&self, blob_object_id: &MoveId) -> Result<BlobMetadata> {
+            if *blob_object_id != MoveId::from_hex(BLOB_OBJECT_ID)? {
+                return Err(AuditorError::SuiClient("object not found".to_string()));
+            }
+            Ok(BlobMetadata {
+                blob_object_id: blob_object_id.to_string(),
+                blob_id: BLOB_ID.to_string(),
+                merkle_root: vec![],
+                blob_size: 2 * 4096,
+                encoding_type: 1,
+                encoding_k: 334,
+                encoding_n: 1000,
+                registered_epoch: 1,
+                certified_epoch: Some(1),
+                start_epoch: 1,
+                end_epoch: 10,
+                owner: AUDITOR.to_string(),
+            })
+        }
+    }
+
+    #[async_trait]
+    impl BlobObjectIdSource for StubBlobIndex {
+        async fn blob_object_id(&self, blob_id: &BlobId) -> Result<Option<MoveId>> {
+            Ok((*blob_id == BlobId::parse(BLOB_ID)?)
+                .then(|| MoveId::from_hex(BLOB_OBJECT_ID).unwrap()))
+        }
+    }
+
+    fn resolving_pipeline(aggregator: &FakeAggregator) -> AuditPipeline {
+        let mut signer = Dilithium3Signer::new();
+        signer.generate_keypair().unwrap();
+        AuditPipeline::new(
+            IntegrityVerifier::new(aggregator.url().to_string()),
+            AuditReportGenerator::new(signer, Some(AUDITOR.to_string())),
+            MockUploader::default(),
+            MockSubmitter::default(),
+            PipelineConfig {
+                auditor_address: AUDITOR.to_string(),
+                package_id: "0xpackage".to_string(),
+                seal_threshold: 2,
+                challenge_epoch: None,
+                report_deleted_blobs: false,
+            },
+        )
+    }
+
+    #[tokio::test]
+    async fn test_audit_by_blob_object_id_resolves_blob_id() {
+        let aggregator =
+            FakeAggregator::start(deterministic_blob(2 * 4096), AggregatorMode::Healthy).await;
+        let object = BlobRef::ObjectId(BLOB_OBJECT_ID.to_string());
+
+        // 沒有元數據來源時無法從對象得到 Blob ID
+        let err = resolving_pipeline(&aggregator)
+            .run_blob(&object, None)
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Failed to read blob object 0xb10b from Sui: audit_system_package_id is not configured"
+        );
+        assert_eq!(aggregator.downloads(), 0);
+
+        let pipeline =
+            resolving_pipeline(&aggregator).with_blob_metadata_source(Arc::new(StubBlobIndex));
+        let outcome = pipeline.run_blob(&object, None).await.unwrap();
+        assert_eq!(outcome.report.blob_id, BLOB_ID);
+        assert_eq!(
+            outcome.report.blob_object_id,
+            MoveId::from_hex(BLOB_OBJECT_ID).unwrap().to_string()
+        );
+        assert!(outcome.report.is_valid);
+
+        let unknown = BlobRef::ObjectId("0xdead".to_string());
+        let err = pipeline.run_blob(&unknown, None).await.unwrap_err();
+        assert!(
+            matches!(&err, AuditorError::BlobResolution { step, .. } if step.starts_with("read blob object")),
+            "{:?}",
+            err
+        );
+    }
+
+    #[tokio::test]
+    async fn test_audit_by_blob_id_looks_up_blob_object() {
+        let aggregator =
+            FakeAggregator::start(deterministic_blob(2 * 4096), AggregatorMode::Healthy).await;
+
+        // 不查找對象時報告只有 Blob ID
+        let outcome = resolving_pipeline(&aggregator)
+            .with_blob_metadata_source(Arc::new(StubBlobIndex))
+            .run_once(BLOB_ID)
+            .await
+            .unwrap();
+        assert!(outcome.report.blob_object_id.is_empty());
+
+        let outcome = resolving_pipeline(&aggregator)
+            .with_blob_metadata_source(Arc::new(StubBlobIndex))
+            .with_blob_object_ids(Arc::new(StubBlobIndex))
+            .run_blob(&BlobRef::BlobId(BLOB_ID.to_string()), None)
+            .await
+            .unwrap();
+        assert_eq!(outcome.report.blob_id, BLOB_ID);
+        assert_eq!(
+            outcome.report.blob_object_id,
+            MoveId::from_hex(BLOB_OBJECT_ID).unwrap().to_string()
+        );
+    }
+
+    /// 鏈上註冊狀態：已註冊（聲譽分數）、未註冊或 RPC 故障
+    enum StubRegistry {
+        Registered(u64),
+        Unregistered,
+        Unreachable,
+    }
+
+    #[async_trait]
+    impl AuditorRegistryReader for StubRegistry {
+        async fn current_epoch(&self) -> Result<u32> {
+            Ok(7)
+        }
+
+        async fn is_auditor_registered(&self, _auditor: &MoveId) -> Result<bool> {
+            match self {
+                StubRegistry::Registered(_) => Ok(true),
+                StubRegistry::Unregistered => Ok(false),
+                StubRegistry::Unreachable => {
+                    Err(AuditorError::SuiClient("connection refused".to_string()))
+                }
+            }
+        }
+
+        async fn auditor_reputation(&self, _auditor: &MoveId) -> Result<u64> {
+            match self {
+                StubRegistry::Registered(reputation) => Ok(*reputation),
+                _ => Ok(0),
+            }
+        }
+    }
+
+    fn preflight(registry: StubRegistry) -> RegistrationPreflight {
+        RegistrationPreflight::new(
+            Arc::new(registry),
+            MoveId::from_hex(AUDITOR).unwrap(),
+            "0xpackage",
+            "0xregistry",
+        )
+    }
+
+    #[tokio::test]
+    async fn test_preflight_signs_reputation_into_report() {
+        let aggregator =
+            FakeAggregator::start(deterministic_blob(2 * 4096), AggregatorMode::Healthy).await;
+        let pipeline =
+            resolving_pipeline(&aggregator).with_preflight(preflight(StubRegistry::Registered(42)));
+
+        let standing = pipeline.preflight().await.unwrap().unwrap();
+        assert_eq!((standing.epoch, standing.reputation), (7, Some(42)));
+
+        let outcome = pipeline.run_once(BLOB_ID).await.unwrap();
+        assert_eq!(outcome.report.auditor_reputation_at_audit, Some(42));
+        let public_key = pipeline.generator.public_key();
+        assert!(ReportManager::verify_report(&outcome.report, public_key).unwrap());
+
+        // 聲譽分數在簽名範圍內
+        let mut tampered = outcome.report.clone();
+        tampered.auditor_reputation_at_audit = Some(1000);
+        assert!(!ReportManager::verify_report(&tampered, public_key).unwrap_or(false));
+    }
+
+    #[tokio::test]
+    async fn test_preflight_refuses_unregistered_auditor() {
+        let aggregator =
+            FakeAggregator::start(deterministic_blob(2 * 4096), AggregatorMode::Healthy).await;
+        let pipeline =
+            resolving_pipeline(&aggregator).with_preflight(preflight(StubRegistry::Unregistered));
+
+        let err = pipeline.run_once(BLOB_ID).await.unwrap_err();
+        assert!(matches!(err, AuditorError::AuditorNotRegistered { .. }));
+        assert_eq!(aggregator.downloads(), 0);
+
+        // --allow-unregistered：繼續審計，報告記錄未註冊審計員的聲譽（0）
+        let pipeline = resolving_pipeline(&aggregator)
+            .with_preflight(preflight(StubRegistry::Unregistered).with_allow_unregistered(true));
+        let outcome = pipeline.run_once(BLOB_ID).await.unwrap();
+        assert_eq!(outcome.report.auditor_reputation_at_audit, Some(0));
+    }
+
+    #[tokio::test]
+    async fn test_preflight_rpc_error_does_not_block_audit() {
+        let aggregator =
+            FakeAggregator::start(deterministic_blob(2 * 4096), AggregatorMode::Healthy).await;
+        let pipeline =
+            resolving_pipeline(&aggregator).with_preflight(preflight(StubRegistry::Unreachable));
+
+        assert_eq!(pipeline.preflight().await.unwrap(), None);
+        let outcome = pipeline.run_once(BLOB_ID).await.unwrap();
+        assert!(outcome.report.is_valid);
+        assert_eq!(outcome.report.auditor_reputation_at_audit, None);
+    }
+
+    #[tokio::test]
+    async fn test_store_uploads_bytes_and_returns_blob_id() {
+        let fake = FakePublisher::start().await;
+        let mut config = AuditorConfig {
+            walrus_publisher_url: format!("{}/", fake.url()),
+            walrus_storage_epochs: Some(3),
+            ..AuditorConfig::default()
+        };
+
+        let data = b"{\"blob_id\":\"signed report\"}".to_vec();
+        let blob_id = WalrusPublisher::from_config(&config)
+            .unwrap()
+            .store(&data)
+            .await
+            .unwrap();
+
+        assert_eq!(blob_id, FakePublisher::blob_id_for(&data));
+        assert_eq!(fake.uploads(), vec![data]);
+        assert_eq!(fake.queries(), vec![Some("epochs=3".to_string())]);
+
+        // 未配置 epoch 數時不帶查詢參數
+        config.walrus_storage_epochs = None;
+        WalrusPublisher::from_config(&config)
+            .unwrap()
+            .store(b"x")
+            .await
+            .unwrap();
+        assert_eq!(fake.queries()[1], None);
+    }
+
+    #[tokio::test]
+    async fn test_store_retries_transient_server_errors() {
+        let fake = FakePublisher::start().await;
+        let publisher = WalrusPublisher::new(fake.url()).with_retry(fast_retry());
+
+        fake.fail_next(2, StatusCode::SERVICE_UNAVAILABLE);
+        let blob_id = publisher.store(b"report").await.unwrap();
+        assert_eq!(blob_id, FakePublisher::blob_id_for(b"report"));
+        assert_eq!(fake.queries().len(), 3);
+        assert_eq!(fake.uploads().len(), 1);
+
+        // 4xx 不重試
+        fake.fail_next(1, StatusCode::PAYLOAD_TOO_LARGE);
+        assert!(matches!(
+            publisher.store(b"report").await,
+            Err(AuditorError::HttpRequest(_))
+        ));
+        assert_eq!(fake.queries().len(), 4);
+
+        // 重試耗盡後返回最後一個錯誤
+        fake.fail_next(10, StatusCode::BAD_GATEWAY);
+        assert!(publisher.store(b"report").await.is_err());
+        assert_eq!(fake.queries().len(), 8);
+    }
+
+    #[test]
+    fn test_parse_store_response() {
+        let created = json!({ "newlyCreated": { "blobObject": { "blobId": "abc" } } });
+        assert_eq!(parse_store_response(&created).as_deref(), Some("abc"));
+
+        let certified = json!({ "alreadyCertified": { "blobId": "def" } });
+        assert_eq!(parse_store_response(&certified).as_deref(), Some("def"));
+
+        assert_eq!(parse_store_response(&json!({})), None);
+    }
+
+    #[test]
+    fn test_blob_id_to_u256() {
+        // 小端：第一個字節為最低位
+        let mut bytes = [0u8; 32];
+        bytes[0] = 1;
+        bytes[1] = 1;
+        let blob_id = general_purpose::URL_SAFE_NO_PAD.encode(bytes);
+        assert_eq!(blob_id_to_u256(&blob_id).unwrap(), "257");
+
+        let zero = general_purpose::URL_SAFE_NO_PAD.encode([0u8; 32]);
+        assert_eq!(blob_id_to_u256(&zero).unwrap(), "0");
+
+        let max = general_purpose::URL_SAFE_NO_PAD.encode([0xffu8; 32]);
+        assert_eq!(
+            blob_id_to_u256(&max).unwrap(),
+            "115792089237316195423570985008687907853269984665640564039457584007913129639935"
+        );
+
+        assert!(blob_id_to_u256("short").is_err());
+    }
+}
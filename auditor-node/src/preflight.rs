@@ -0,0 +1,371 @@
+//! 審計員註冊預檢
+//!
+//! 未註冊的審計員地址提交報告時會在鏈上失敗，而此時審計工作已經完成。流水線在開始審計前
+//! 以 [`RegistrationPreflight`] 查詢審計員是否已在 AuditorRegistry 註冊及其聲譽分數
+//! （`auditor_registry::is_auditor_registered` / `get_auditor_reputation`，以 devInspect 只讀調用）：
+//!
+//! - 已註冊：結果按 epoch 緩存，同一 epoch 內不再查詢；聲譽分數簽入報告的
+//!   `auditor_reputation_at_audit`
+//! - 未註冊：返回 `AuditorNotRegistered` 錯誤（附註冊命令與 Package ID），除非設置了
+//!   `allow_unregistered`（`--allow-unregistered`，僅用於本地測試）。未註冊的結果不緩存，
+//!   完成註冊後下一次預檢即通過
+//! - 查詢失敗（RPC 錯誤）：記錄警告後繼續審計，報告不記錄聲譽；預檢不應因節點故障阻塞審計
+//!
+//! 鏈上讀取通過 [`AuditorRegistryReader`] 注入，測試可替換為模擬實現。
+
+use crate::chain_types::MoveId;
+use crate::error::{AuditorError, Result};
+use crate::sui_client::AuditSystemClient;
+use async_trait::async_trait;
+use std::sync::{Arc, Mutex};
+use tokio::sync::OnceCell;
+use tracing::{info, warn};
+
+/// 讀取審計員在 AuditorRegistry 中的狀態
+#[async_trait]
+pub trait AuditorRegistryReader: Send + Sync {
+    /// 當前 Sui epoch（預檢結果的緩存鍵）
+    async fn current_epoch(&self) -> Result<u32>;
+
+    /// 審計員是否已註冊
+    async fn is_auditor_registered(&self, auditor: &MoveId) -> Result<bool>;
+
+    /// 審計員的聲譽分數
+    async fn auditor_reputation(&self, auditor: &MoveId) -> Result<u64>;
+}
+
+#[async_trait]
+impl AuditorRegistryReader for AuditSystemClient {
+    async fn current_epoch(&self) -> Result<u32> {
+        AuditSystemClient::current_epoch(self).await
+    }
+
+    async fn is_auditor_registered(&self, auditor: &MoveId) -> Result<bool> {
+        AuditSystemClient::is_auditor_registered(self, auditor).await
+    }
+
+    async fn auditor_reputation(&self, auditor: &MoveId) -> Result<u64> {
+        self.get_auditor_reputation(auditor).await
+    }
+}
+
+/// 按需連接 Sui 的 [`AuditorRegistryReader`]
+///
+/// 流水線在同步的 [`from_config`](crate::pipeline::AuditPipeline::from_config) 中創建，
+/// 客戶端在第一次預檢時才建立
+pub struct SuiRegistryReader {
+    rpc_url: String,
+    package_id: String,
+    registry_id: String,
+    client: OnceCell<AuditSystemClient>,
+}
+
+impl SuiRegistryReader {
+    /// 創建讀取器
+    pub fn new(
+        rpc_url: impl Into<String>,
+        package_id: impl Into<String>,
+        registry_id: impl Into<String>,
+    ) -> Self {
+        Self {
+            rpc_url: rpc_url.into(),
+            package_id: package_id.into(),
+            registry_id: registry_id.into(),
+            client: OnceCell::new(),
+        }
+    }
+
+    async fn client(&self) -> Result<&AuditSystemClient> {
+        self.client
+            .get_or_try_init(|| {
+                AuditSystemClient::new(&self.rpc_url, &self.package_id, "", &self.registry_id, "")
+            })
+            .await
+    }
+}
+
+#[async_trait]
+impl AuditorRegistryReader for SuiRegistryReader {
+    async fn current_epoch(&self) -> Result<u32> {
+        self.client().await?.current_epoch().await
+    }
+
+    async fn is_auditor_registered(&self, auditor: &MoveId) -> Result<bool> {
+        self.client().await?.is_auditor_registered(auditor).await
+    }
+
+    async fn auditor_reputation(&self, auditor: &MoveId) -> Result<u64> {
+        self.client().await?.get_auditor_reputation(auditor).await
+    }
+}
+
+/// 預檢得到的審計員狀態
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AuditorStanding {
+    /// 查詢時的 epoch
+    pub epoch: u32,
+
+    /// 是否已註冊（`false` 只在允許未註冊審計員時出現）
+    pub registered: bool,
+
+    /// 聲譽分數（查詢失敗時為 `None`）
+    pub reputation: Option<u64>,
+}
+
+/// 審計員註冊預檢
+pub struct RegistrationPreflight {
+    reader: Arc<dyn AuditorRegistryReader>,
+    auditor: MoveId,
+    package_id: String,
+    registry_id: String,
+    allow_unregistered: bool,
+    cached: Mutex<Option<AuditorStanding>>,
+}
+
+impl RegistrationPreflight {
+    /// 創建預檢；`package_id` 與 `registry_id` 用於未註冊時的錯誤信息
+    pub fn new(
+        reader: Arc<dyn AuditorRegistryReader>,
+        auditor: MoveId,
+        package_id: impl Into<String>,
+        registry_id: impl Into<String>,
+    ) -> Self {
+        Self {
+            reader,
+            auditor,
+            package_id: package_id.into(),
+            registry_id: registry_id.into(),
+            allow_unregistered: false,
+            cached: Mutex::new(None),
+        }
+    }
+
+    /// 未註冊時只記錄警告、繼續審計（本地測試用）
+    pub fn with_allow_unregistered(mut self, allow: bool) -> Self {
+        self.allow_unregistered = allow;
+        self
+    }
+
+    /// 最近一次緩存的狀態（只緩存已註冊且讀到聲譽的結果）
+    pub fn standing(&self) -> Option<AuditorStanding> {
+        *self.cached.lock().unwrap()
+    }
+
+    /// 執行預檢
+    ///
+    /// 同一 epoch 內返回緩存的結果；epoch 查詢失敗時沿用上一次緩存的結果（可能為 `None`）。
+    ///
+    /// # 返回
+    /// - `Ok(Some(standing))`: 已註冊，或未註冊但允許繼續
+    /// - `Ok(None)`: 無法查詢註冊狀態，已記錄警告
+    ///
+    /// # 錯誤
+    /// - 審計員未註冊且不允許未註冊時返回 `AuditorNotRegistered`
+    pub async fn check(&self) -> Result<Option<AuditorStanding>> {
+        let epoch = match self.reader.current_epoch().await {
+            Ok(epoch) => epoch,
+            Err(e) => {
+                warn!(
+                    "Could not query the current epoch for the registration preflight, \
+                     proceeding: {}",
+                    e
+                );
+                return Ok(self.standing());
+            }
+        };
+        if let Some(standing) = self.standing().filter(|standing| standing.epoch == epoch) {
+            return Ok(Some(standing));
+        }
+
+        let registered = match self.reader.is_auditor_registered(&self.auditor).await {
+            Ok(registered) => registered,
+            Err(e) => {
+                warn!(
+                    "Could not check whether auditor {} is registered, proceeding: {}",
+                    self.auditor, e
+                );
+                return Ok(None);
+            }
+        };
+        if !registered && !self.allow_unregistered {
+            return Err(AuditorError::AuditorNotRegistered {
+                auditor: self.auditor.to_string(),
+                package_id: self.package_id.clone(),
+                registry_id: self.registry_id.clone(),
+            });
+        }
+
+        let reputation = match self.reader.auditor_reputation(&self.auditor).await {
+            Ok(reputation) => Some(reputation),
+            Err(e) => {
+                warn!(
+                    "Could not read the reputation of auditor {}: {}",
+                    self.auditor, e
+                );
+                None
+            }
+        };
+        let standing = AuditorStanding {
+            epoch,
+            registered,
+            reputation,
+        };
+
+        if registered {
+            info!(
+                "Auditor {} is registered (epoch {}, reputation {})",
+                self.auditor,
+                epoch,
+                reputation.map_or_else(|| "unknown".to_string(), |r| r.to_string())
+            );
+            if reputation.is_some() {
+                *self.cached.lock().unwrap() = Some(standing);
+            }
+        } else {
+            warn!(
+                "Auditor {} is not registered; continuing because unregistered auditors are \
+                 allowed, but on-chain submissions will fail",
+                self.auditor
+            );
+        }
+        Ok(Some(standing))
+    }
+}
+
+impl std::fmt::Debug for RegistrationPreflight {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RegistrationPreflight")
+            .field("auditor", &self.auditor)
+            .field("allow_unregistered", &self.allow_unregistered)
+            .field("cached", &self.standing())
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicBool, AtomicU32, AtomicUsize, Ordering};
+
+    /// 模擬的鏈上讀取：記錄查詢次數，可切換註冊狀態與 RPC 故障
+    #[derive(Default)]
+    struct StubRegistry {
+        epoch: AtomicU32,
+        registered: AtomicBool,
+        failing: AtomicBool,
+        queries: AtomicUsize,
+    }
+
+    impl StubRegistry {
+        fn registered() -> Arc<Self> {
+            let registry = Self::default();
+            registry.registered.store(true, Ordering::SeqCst);
+            Arc::new(registry)
+        }
+
+        fn rpc<T>(&self, value: T) -> Result<T> {
+            self.queries.fetch_add(1, Ordering::SeqCst);
+            if self.failing.load(Ordering::SeqCst) {
+                return Err(AuditorError::SuiClient(
+                    "sui_devInspectTransactionBlock failed: 503".to_string(),
+                ));
+            }
+            Ok(value)
+        }
+    }
+
+    #[async_trait]
+    impl AuditorRegistryReader for StubRegistry {
+        async fn current_epoch(&self) -> Result<u32> {
+            Ok(self.epoch.load(Ordering::SeqCst))
+        }
+
+        async fn is_auditor_registered(&self, _auditor: &MoveId) -> Result<bool> {
+            self.rpc(self.registered.load(Ordering::SeqCst))
+        }
+
+        async fn auditor_reputation(&self, _auditor: &MoveId) -> Result<u64> {
+            self.rpc(17)
+        }
+    }
+
+    fn preflight(registry: &Arc<StubRegistry>) -> RegistrationPreflight {
+        RegistrationPreflight::new(
+            Arc::clone(registry) as Arc<dyn AuditorRegistryReader>,
+            MoveId([0xa1; 32]),
+            "0xpackage",
+            "0xregistry",
+        )
+    }
+
+    #[tokio::test]
+    async fn test_registered_auditor_is_cached_per_epoch() {
+        let registry = StubRegistry::registered();
+        let preflight = preflight(&registry);
+
+        let standing = preflight.check().await.unwrap().unwrap();
+        assert_eq!(
+            standing,
+            AuditorStanding {
+                epoch: 0,
+                registered: true,
+                reputation: Some(17)
+            }
+        );
+        assert_eq!(preflight.check().await.unwrap(), Some(standing));
+        assert_eq!(registry.queries.load(Ordering::SeqCst), 2);
+
+        // 新的 epoch 重新查詢
+        registry.epoch.store(1, Ordering::SeqCst);
+        assert_eq!(preflight.check().await.unwrap().unwrap().epoch, 1);
+        assert_eq!(registry.queries.load(Ordering::SeqCst), 4);
+    }
+
+    #[tokio::test]
+    async fn test_unregistered_auditor_is_refused() {
+        let registry = Arc::new(StubRegistry::default());
+        let preflight = preflight(&registry);
+
+        match preflight.check().await {
+            Err(e @ AuditorError::AuditorNotRegistered { .. }) => {
+                let message = e.to_string();
+                assert!(message.contains("0xpackage"));
+                assert!(message.contains("register_auditor"));
+                assert!(message.contains("--allow-unregistered"));
+            }
+            other => panic!("Expected AuditorNotRegistered, got {:?}", other),
+        }
+        assert!(preflight.standing().is_none());
+
+        // 註冊後立即生效，不等到下一個 epoch
+        registry.registered.store(true, Ordering::SeqCst);
+        assert!(preflight.check().await.unwrap().unwrap().registered);
+    }
+
+    #[tokio::test]
+    async fn test_unregistered_auditor_allowed_for_local_testing() {
+        let registry = Arc::new(StubRegistry::default());
+        let preflight = preflight(&registry).with_allow_unregistered(true);
+
+        let standing = preflight.check().await.unwrap().unwrap();
+        assert!(!standing.registered);
+        assert!(preflight.standing().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_rpc_error_warns_and_proceeds() {
+        let registry = StubRegistry::registered();
+        registry.failing.store(true, Ordering::SeqCst);
+        let preflight = preflight(&registry);
+
+        assert_eq!(preflight.check().await.unwrap(), None);
+        assert!(preflight.standing().is_none());
+
+        // 恢復後正常緩存
+        registry.failing.store(false, Ordering::SeqCst);
+        assert_eq!(
+            preflight.check().await.unwrap().unwrap().reputation,
+            Some(17)
+        );
+    }
+}
@@ -0,0 +1,172 @@
+//! 報告生產者元數據
+//!
+//! 某個版本的 bug 產生錯誤結論時，需要知道每份報告由哪個構建生成。
+//! 簽名時自動寫入報告的 `producer` 字段（在簽名範圍內）：
+//!
+//! - auditor-node 與 pqc-signer 的 crate 版本
+//! - git 提交哈希與 dirty 標誌（由 `build.rs` 注入，非 git 構建時缺省）
+//! - 目標三元組與啟用的 feature
+//!
+//! `auditor-node --version --output json` 輸出同樣的結構。
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// auditor-node 版本
+pub const AUDITOR_NODE_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// 構建時的 git 提交哈希（非 git 構建時為空）
+pub const GIT_COMMIT: &str = env!("AUDITOR_GIT_COMMIT");
+
+/// 構建時工作區是否有未提交修改（`true` / `false`，未知時為空）
+pub const GIT_DIRTY: &str = env!("AUDITOR_GIT_DIRTY");
+
+/// 目標三元組
+pub const BUILD_TARGET: &str = env!("AUDITOR_BUILD_TARGET");
+
+/// 啟用的 feature（逗號分隔）
+pub const FEATURES: &str = env!("AUDITOR_FEATURES");
+
+/// 報告生產者
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Producer {
+    /// auditor-node 版本
+    pub auditor_node_version: String,
+
+    /// pqc-signer 版本
+    pub pqc_signer_version: String,
+
+    /// git 提交哈希
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub git_commit: Option<String>,
+
+    /// 工作區是否有未提交修改
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub git_dirty: Option<bool>,
+
+    /// 目標三元組
+    pub target: String,
+
+    /// 啟用的 feature
+    #[serde(default)]
+    pub features: Vec<String>,
+}
+
+impl Producer {
+    /// 當前構建
+    pub fn current() -> Self {
+        Self {
+            auditor_node_version: AUDITOR_NODE_VERSION.to_string(),
+            pqc_signer_version: pqc_signer::VERSION.to_string(),
+            git_commit: Some(GIT_COMMIT)
+                .filter(|commit| !commit.is_empty())
+                .map(str::to_string),
+            git_dirty: GIT_DIRTY.parse().ok(),
+            target: BUILD_TARGET.to_string(),
+            features: FEATURES
+                .split(',')
+                .filter(|feature| !feature.is_empty())
+                .map(str::to_string)
+                .collect(),
+        }
+    }
+
+    /// auditor-node 版本是否在已知問題版本列表中
+    ///
+    /// 列表項可以是版本號（`0.1.0`）或提交哈希前綴（至少 7 位）
+    pub fn is_denied(&self, denied: &[String]) -> bool {
+        denied.iter().any(|entry| {
+            *entry == self.auditor_node_version
+                || (entry.len() >= 7
+                    && self
+                        .git_commit
+                        .as_deref()
+                        .is_some_and(|commit| commit.starts_with(entry.as_str())))
+        })
+    }
+}
+
+impl fmt::Display for Producer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "auditor-node {} (pqc-signer {}",
+            self.auditor_node_version, self.pqc_signer_version
+        )?;
+
+        if let Some(commit) = &self.git_commit {
+            write!(f, ", {}", &commit[..commit.len().min(12)])?;
+            if self.git_dirty == Some(true) {
+                write!(f, "-dirty")?;
+            }
+        }
+
+        write!(f, ", {}", self.target)?;
+        if !self.features.is_empty() {
+            write!(f, ", features: {}", self.features.join(","))?;
+        }
+        write!(f, ")")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_current_matches_build_constants() {
+        let producer = Producer::current();
+
+        assert_eq!(producer.auditor_node_version, env!("CARGO_PKG_VERSION"));
+        assert_eq!(producer.pqc_signer_version, pqc_signer::VERSION);
+        assert_eq!(producer.target, BUILD_TARGET);
+        assert!(!producer.target.is_empty());
+        assert_eq!(
+            producer.git_commit.as_deref().unwrap_or_default(),
+            GIT_COMMIT
+        );
+        assert_eq!(producer.git_dirty, GIT_DIRTY.parse().ok());
+        assert_eq!(producer.features.join(","), FEATURES);
+    }
+
+    #[test]
+    fn test_deny_list() {
+        let producer = Producer {
+            auditor_node_version: "0.1.0".to_string(),
+            pqc_signer_version: "0.1.0".to_string(),
+            git_commit: Some("44cc62d0a1b2c3d4".to_string()),
+            git_dirty: Some(false),
+            target: "x86_64-unknown-linux-gnu".to_string(),
+            features: vec![],
+        };
+
+        assert!(producer.is_denied(&["0.1.0".to_string()]));
+        assert!(producer.is_denied(&["44cc62d".to_string()]));
+        assert!(!producer.is_denied(&["44cc".to_string()]));
+        assert!(!producer.is_denied(&["0.1.1".to_string()]));
+        assert!(!producer.is_denied(&[]));
+    }
+
+    #[test]
+    fn test_display() {
+        let mut producer = Producer {
+            auditor_node_version: "0.1.0".to_string(),
+            pqc_signer_version: "0.2.0".to_string(),
+            git_commit: Some("44cc62d0a1b2c3d4e5f6".to_string()),
+            git_dirty: Some(true),
+            target: "x86_64-unknown-linux-gnu".to_string(),
+            features: vec!["test-util".to_string()],
+        };
+        assert_eq!(
+            producer.to_string(),
+            "auditor-node 0.1.0 (pqc-signer 0.2.0, 44cc62d0a1b2-dirty, x86_64-unknown-linux-gnu, features: test-util)"
+        );
+
+        producer.git_commit = None;
+        producer.features.clear();
+        assert_eq!(
+            producer.to_string(),
+            "auditor-node 0.1.0 (pqc-signer 0.2.0, x86_64-unknown-linux-gnu)"
+        );
+    }
+}
@@ -0,0 +1,211 @@
+//! 長時間審計的進度事件
+//!
+//! 調用方傳入 `mpsc::Sender<AuditProgress>`，審計在各階段以 `try_send` 發出事件：
+//!
+//! 1. [`AuditProgress::DownloadStarted`]：Aggregator 開始返回內容（完整性審計）
+//! 2. [`AuditProgress::DownloadedBytes`]：每讀取一段響應體
+//! 3. [`AuditProgress::MerkleBuildStarted`]：下載結束，完成 Merkle 樹
+//! 4. [`AuditProgress::ChallengeCompleted`]：每個挑戰驗證完成
+//! 5. [`AuditProgress::Finished`]：審計結束（包括出錯），總是最後一個事件
+//!
+//! 發送從不阻塞審計：接收端處理不及（通道已滿）時丟棄事件，接收端已丟棄時忽略。
+//! 內容來自緩存或檢查點時不下載也不構建 Merkle 樹，不發出前三類事件；
+//! 挑戰存儲節點（[`crate::auditor::Auditor`]）只發出後兩類事件。
+
+use crate::integrity::VerificationStatus;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tracing::{debug, info};
+
+/// 審計進度事件
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AuditProgress {
+    /// 開始下載 Blob；`size_hint` 為 `Content-Length`（未提供時為 `None`）
+    DownloadStarted { size_hint: Option<u64> },
+    /// 已下載 `done` 字節（`total` 同 `size_hint`）
+    DownloadedBytes { done: u64, total: Option<u64> },
+    /// 開始完成 `leaf_count` 個葉子的 Merkle 樹
+    MerkleBuildStarted { leaf_count: usize },
+    /// 挑戰完成；`index` 為完整性審計的葉子索引或存儲節點審計的 sliver 索引
+    ChallengeCompleted { index: u64, verified: bool },
+    /// 審計結束
+    Finished { status: FinishedStatus },
+}
+
+/// [`AuditProgress::Finished`] 的審計結論
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FinishedStatus {
+    /// 完整性審計完成，Blob 的驗證狀態
+    Verification(VerificationStatus),
+    /// 存儲節點審計完成，報告是否有效
+    Report { is_valid: bool },
+    /// 審計出錯（錯誤信息）
+    Error(String),
+}
+
+/// 進度事件的非阻塞發送端
+///
+/// 默認不發送任何事件
+#[derive(Debug, Clone, Default)]
+pub struct ProgressSink {
+    sender: Option<mpsc::Sender<AuditProgress>>,
+}
+
+impl ProgressSink {
+    /// 發往 `sender` 的發送端
+    pub fn new(sender: mpsc::Sender<AuditProgress>) -> Self {
+        Self {
+            sender: Some(sender),
+        }
+    }
+
+    /// 發出事件（不等待）；通道已滿或已關閉時丟棄
+    pub fn emit(&self, event: AuditProgress) {
+        let Some(sender) = &self.sender else {
+            return;
+        };
+        if let Err(mpsc::error::TrySendError::Full(event)) = sender.try_send(event) {
+            debug!("Progress receiver is behind, dropping {:?}", event);
+        }
+    }
+
+    /// 按審計結果發出 [`AuditProgress::Finished`]
+    pub fn finish<T, E: std::fmt::Display>(
+        &self,
+        result: &Result<T, E>,
+        status: impl FnOnce(&T) -> FinishedStatus,
+    ) {
+        let status = match result {
+            Ok(value) => status(value),
+            Err(e) => FinishedStatus::Error(e.to_string()),
+        };
+        self.emit(AuditProgress::Finished { status });
+    }
+}
+
+/// 以百分比日誌呈現進度事件，直到收到 `Finished` 或所有發送端被丟棄
+///
+/// 下載進度每增加 10% 記錄一次（大小未知時每 64 MiB），挑戰每完成 10 個記錄一次，
+/// 結束時匯總挑戰結果
+pub fn spawn_progress_log(mut receiver: mpsc::Receiver<AuditProgress>) -> JoinHandle<()> {
+    const UNKNOWN_SIZE_STEP: u64 = 64 * 1024 * 1024;
+    const CHALLENGE_STEP: usize = 10;
+
+    tokio::spawn(async move {
+        let mut logged_download = 0u64;
+        let mut challenges = 0usize;
+        let mut failed = 0usize;
+
+        while let Some(event) = receiver.recv().await {
+            match event {
+                AuditProgress::DownloadStarted { size_hint } => match size_hint {
+                    Some(size) => info!("   ⬇️  Downloading {} bytes", size),
+                    None => info!("   ⬇️  Downloading (size unknown)"),
+                },
+                AuditProgress::DownloadedBytes { done, total } => match total {
+                    Some(total) if total > 0 => {
+                        let step = done.min(total) * 10 / total;
+                        if step > logged_download {
+                            logged_download = step;
+                            info!("   ⬇️  {:>3}% ({}/{} bytes)", step * 10, done, total);
+                        }
+                    }
+                    _ => {
+                        let step = done / UNKNOWN_SIZE_STEP;
+                        if step > logged_download {
+                            logged_download = step;
+                            info!("   ⬇️  {} bytes", done);
+                        }
+                    }
+                },
+                AuditProgress::MerkleBuildStarted { leaf_count } => {
+                    info!("   🌳 Building Merkle tree ({} leaves)", leaf_count)
+                }
+                AuditProgress::ChallengeCompleted { verified, .. } => {
+                    challenges += 1;
+                    if !verified {
+                        failed += 1;
+                    }
+                    if challenges % CHALLENGE_STEP == 0 {
+                        info!("   🎯 {} challenges done, {} failed", challenges, failed);
+                    }
+                }
+                AuditProgress::Finished { status } => {
+                    if challenges > 0 {
+                        info!(
+                            "   🎯 {} challenges completed, {} failed",
+                            challenges, failed
+                        );
+                    }
+                    debug!("Audit finished: {:?}", status);
+                    break;
+                }
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_full_or_closed_channel_does_not_block() {
+        let (sender, mut receiver) = mpsc::channel(1);
+        let sink = ProgressSink::new(sender);
+        sink.emit(AuditProgress::MerkleBuildStarted { leaf_count: 1 });
+        // 通道已滿：丟棄
+        sink.emit(AuditProgress::MerkleBuildStarted { leaf_count: 2 });
+        assert_eq!(
+            receiver.recv().await,
+            Some(AuditProgress::MerkleBuildStarted { leaf_count: 1 })
+        );
+
+        drop(receiver);
+        sink.finish(&Ok::<_, String>(()), |_| FinishedStatus::Report {
+            is_valid: true,
+        });
+        ProgressSink::default().emit(AuditProgress::MerkleBuildStarted { leaf_count: 3 });
+    }
+
+    #[tokio::test]
+    async fn test_finish_reports_errors() {
+        let (sender, mut receiver) = mpsc::channel(4);
+        let sink = ProgressSink::new(sender);
+        sink.finish(&Err::<(), _>("boom"), |_| unreachable!());
+        assert_eq!(
+            receiver.recv().await,
+            Some(AuditProgress::Finished {
+                status: FinishedStatus::Error("boom".to_string())
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_progress_log_stops_at_finished() {
+        let (sender, receiver) = mpsc::channel(16);
+        let handle = spawn_progress_log(receiver);
+        let sink = ProgressSink::new(sender.clone());
+        sink.emit(AuditProgress::DownloadStarted {
+            size_hint: Some(10),
+        });
+        sink.emit(AuditProgress::DownloadedBytes {
+            done: 10,
+            total: Some(10),
+        });
+        sink.emit(AuditProgress::ChallengeCompleted {
+            index: 0,
+            verified: true,
+        });
+        sink.emit(AuditProgress::Finished {
+            status: FinishedStatus::Verification(VerificationStatus::Accessible),
+        });
+
+        // 發送端仍存在，任務在 `Finished` 後退出
+        tokio::time::timeout(std::time::Duration::from_secs(5), handle)
+            .await
+            .unwrap()
+            .unwrap();
+        drop(sender);
+    }
+}
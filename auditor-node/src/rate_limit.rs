@@ -0,0 +1,175 @@
+//! 出站請求的按主機限流
+//!
+//! 並發審計大量 Blob 時，審計員可能在短時間內向同一個存儲節點或 Aggregator 發出大量請求，
+//! 進而被對方封禁。[`RateLimiter`] 為每個主機（`host:port`）維護一個令牌桶：
+//!
+//! - 桶容量為 `burst`，每秒補充 `max_requests_per_sec_per_host` 個令牌
+//! - 每個 HTTP 請求發出前取一個令牌；桶空時等待到下一個令牌可用
+//! - 同一進程內所有訪問同一主機的客戶端共享一個 `Arc<RateLimiter>`，因此共享同一個桶
+//! - `max_requests_per_sec_per_host = 0` 表示不限流，請求不等待
+//!
+//! 等待在重試循環的單次嘗試內部進行：等待令牌不計為一次嘗試，
+//! 重試的退避等待之後再取令牌（見 [`crate::storage_node_client`]）。
+//! 令牌按預約分配（桶可以透支），等待中的請求按到達順序依次放行。
+
+use crate::types::AuditorConfig;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+use tokio::time::Instant;
+use tracing::debug;
+
+/// 單個主機的令牌桶
+#[derive(Debug)]
+struct Bucket {
+    /// 剩餘令牌（為負時表示已預約的未來令牌）
+    tokens: f64,
+    updated: Instant,
+}
+
+/// 按主機共享的令牌桶限流器
+#[derive(Debug)]
+pub struct RateLimiter {
+    /// 每秒補充的令牌數（0 = 不限流）
+    rate: u32,
+    /// 桶容量
+    burst: u32,
+    buckets: Mutex<HashMap<String, Bucket>>,
+}
+
+impl RateLimiter {
+    /// 創建限流器
+    ///
+    /// `max_requests_per_sec` 為 0 時不限流；`burst` 為 0 時按 1 處理
+    pub fn new(max_requests_per_sec: u32, burst: u32) -> Self {
+        Self {
+            rate: max_requests_per_sec,
+            burst: burst.max(1),
+            buckets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// 按 `max_requests_per_sec_per_host` 與 `burst` 創建限流器
+    pub fn from_config(config: &AuditorConfig) -> Self {
+        Self::new(config.max_requests_per_sec_per_host, config.burst)
+    }
+
+    /// 是否不限流
+    pub fn is_unlimited(&self) -> bool {
+        self.rate == 0
+    }
+
+    /// 為 `url` 所在的主機取一個令牌，必要時等待；返回等待的時間
+    pub async fn acquire(&self, url: &str) -> Duration {
+        if self.is_unlimited() {
+            return Duration::ZERO;
+        }
+
+        let host = host_key(url);
+        let wait = self.reserve(&host, Instant::now());
+        if !wait.is_zero() {
+            debug!(
+                "Rate limited: waiting {:?} before request to {}",
+                wait, host
+            );
+            tokio::time::sleep(wait).await;
+        }
+        wait
+    }
+
+    /// 在 `now` 為 `host` 預約一個令牌，返回令牌可用前需要等待的時間
+    fn reserve(&self, host: &str, now: Instant) -> Duration {
+        if self.is_unlimited() {
+            return Duration::ZERO;
+        }
+
+        let rate = f64::from(self.rate);
+        let capacity = f64::from(self.burst);
+        let mut buckets = self.buckets.lock().unwrap();
+        let bucket = buckets.entry(host.to_string()).or_insert(Bucket {
+            tokens: capacity,
+            updated: now,
+        });
+
+        let elapsed = now.saturating_duration_since(bucket.updated).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * rate).min(capacity);
+        bucket.updated = now;
+        bucket.tokens -= 1.0;
+
+        if bucket.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-bucket.tokens / rate)
+        }
+    }
+}
+
+/// 限流的主機鍵（`host:port`，缺省端口按協議補全）；無法解析時使用原字符串
+pub fn host_key(url: &str) -> String {
+    match reqwest::Url::parse(url) {
+        Ok(parsed) => match (parsed.host_str(), parsed.port_or_known_default()) {
+            (Some(host), Some(port)) => format!("{}:{}", host, port),
+            (Some(host), None) => host.to_string(),
+            _ => url.to_string(),
+        },
+        Err(_) => url.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_host_key() {
+        assert_eq!(
+            host_key("http://127.0.0.1:8080/v1/challenge"),
+            "127.0.0.1:8080"
+        );
+        assert_eq!(
+            host_key("https://node.example.com/v1/blobs/x"),
+            "node.example.com:443"
+        );
+        assert_eq!(host_key("http://node.example.com"), "node.example.com:80");
+        assert_eq!(host_key("not a url"), "not a url");
+    }
+
+    #[test]
+    fn test_burst_then_steady_rate() {
+        let limiter = RateLimiter::new(10, 3);
+        let start = Instant::now();
+
+        // 前 burst 個請求不等待
+        for _ in 0..3 {
+            assert_eq!(limiter.reserve("node:80", start), Duration::ZERO);
+        }
+        // 之後每個請求比前一個多等 100ms（預約未來的令牌）
+        assert_eq!(
+            limiter.reserve("node:80", start),
+            Duration::from_millis(100)
+        );
+        assert_eq!(
+            limiter.reserve("node:80", start),
+            Duration::from_millis(200)
+        );
+
+        // 其他主機有自己的桶
+        assert_eq!(limiter.reserve("other:80", start), Duration::ZERO);
+
+        // 補充的令牌不超過容量
+        let later = start + Duration::from_secs(60);
+        for _ in 0..3 {
+            assert_eq!(limiter.reserve("node:80", later), Duration::ZERO);
+        }
+        assert!(limiter.reserve("node:80", later) > Duration::ZERO);
+    }
+
+    #[tokio::test]
+    async fn test_zero_rate_is_unlimited() {
+        let limiter = RateLimiter::new(0, 0);
+        assert!(limiter.is_unlimited());
+        for _ in 0..1000 {
+            assert_eq!(limiter.acquire("http://node:8080").await, Duration::ZERO);
+        }
+    }
+}
@@ -0,0 +1,636 @@
+//! 失敗 Blob 的限速重審策略
+//!
+//! 審計失敗的 Blob 應盡快複查（確認或排除故障），但不能被持續轟炸。
+//! 每個 Blob 的狀態機如下：
+//!
+//! ```text
+//!             FAIL                       FAIL（確認次數 < N）
+//! (健康) ───────────▶ Suspect ───────────────────────┐
+//!   ▲                   │  ▲                         │
+//!   │       PASS        │  └─────────────────────────┘
+//!   ├───────────────────┘
+//!   │                   │ FAIL（第 N 次確認）
+//!   │       PASS        ▼
+//!   └────────────── ConfirmedCorrupted（停止重審，發出最終通知）
+//! ```
+//!
+//! - 首次失敗後按 `backoff_secs` 依次安排重審（如 10m、1h、6h、24h，用盡後重複最後一項）
+//! - 連續 `confirmations` 次重審仍失敗時標記為 `ConfirmedCorrupted`，不再安排重審
+//! - 任何一次 PASS 都會清除狀態
+//! - Blob 被所有者刪除時移除狀態
+//!
+//! 狀態保存在 JSON 文件中，每次轉換後原子寫入，重啟後恢復。
+//! 守護進程每輪先審計到期的重審（最早到期、失敗次數最多者優先），
+//! 再審計常規隊列；處於退避中或已確認損壞的 Blob 不會被常規隊列重複審計。
+
+use crate::error::Result;
+use crate::integrity::VerificationStatus;
+use crate::rotating_writer::Clock;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tracing::{debug, info, warn};
+
+/// 默認重審間隔：10 分鐘、1 小時、6 小時、24 小時
+pub const DEFAULT_BACKOFF_SECS: [u64; 4] = [600, 3600, 6 * 3600, 24 * 3600];
+
+/// 默認確認次數
+pub const DEFAULT_CONFIRMATIONS: u32 = 3;
+
+/// 重審配置
+///
+/// ```toml
+/// [reaudit]
+/// enabled = true
+/// backoff_secs = [600, 3600, 21600, 86400]
+/// confirmations = 3
+/// state_path = "./reaudit_state.json"
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ReauditConfig {
+    /// 是否啟用重審策略
+    pub enabled: bool,
+
+    /// 第 k 次重審距上次失敗的間隔（秒）
+    pub backoff_secs: Vec<u64>,
+
+    /// 連續多少次重審失敗後確認損壞
+    pub confirmations: u32,
+
+    /// 狀態文件（JSON）
+    pub state_path: String,
+}
+
+impl Default for ReauditConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            backoff_secs: DEFAULT_BACKOFF_SECS.to_vec(),
+            confirmations: DEFAULT_CONFIRMATIONS,
+            state_path: "./reaudit_state.json".to_string(),
+        }
+    }
+}
+
+impl ReauditConfig {
+    /// 第 `confirmed` 次確認之後的重審間隔
+    fn backoff(&self, confirmed: u32) -> u64 {
+        let index = (confirmed as usize).min(self.backoff_secs.len().saturating_sub(1));
+        self.backoff_secs.get(index).copied().unwrap_or(0)
+    }
+}
+
+/// 單次審計結果（重審策略的輸入）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuditOutcome {
+    /// 可訪問且所有挑戰通過
+    Pass,
+    /// 損壞、不可達或有挑戰失敗
+    Fail,
+    /// Blob 已被所有者刪除或存儲期已結束（不計為故障）
+    Deleted,
+}
+
+impl AuditOutcome {
+    /// 由驗證狀態與失敗挑戰數分類
+    pub fn from_audit(status: &VerificationStatus, failed_verifications: u16) -> Self {
+        match status {
+            VerificationStatus::Deleted | VerificationStatus::Expired => Self::Deleted,
+            VerificationStatus::Accessible if failed_verifications == 0 => Self::Pass,
+            _ => Self::Fail,
+        }
+    }
+}
+
+/// 單個 Blob 的重審狀態（健康的 Blob 沒有狀態）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "state", rename_all = "snake_case")]
+pub enum ReauditState {
+    /// 已失敗，等待重審確認
+    Suspect {
+        /// 首次失敗時間（Unix 秒）
+        first_failed_at: u64,
+        /// 已確認的重審失敗次數（不含首次失敗）
+        confirmations: u32,
+        /// 下次重審時間（Unix 秒）
+        next_audit_at: u64,
+    },
+    /// 連續確認失敗，停止重審
+    ConfirmedCorrupted {
+        /// 首次失敗時間（Unix 秒）
+        first_failed_at: u64,
+        /// 確認時間（Unix 秒）
+        confirmed_at: u64,
+    },
+}
+
+/// 狀態轉換結果（供調用方記錄或通知）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Transition {
+    /// 狀態不變
+    Unchanged,
+    /// 安排了重審
+    Scheduled {
+        /// 已確認的重審失敗次數
+        confirmations: u32,
+        /// 重審時間（Unix 秒）
+        next_audit_at: u64,
+    },
+    /// 確認損壞（最終通知）
+    Confirmed,
+    /// PASS 清除了失敗狀態
+    Cleared,
+    /// Blob 已刪除，狀態移除
+    Removed,
+}
+
+/// 純狀態轉換函數
+///
+/// # 參數
+/// - `state`: 當前狀態（`None` 表示健康）
+/// - `outcome`: 本次審計結果
+/// - `now`: 當前時間（Unix 秒）
+/// - `config`: 重審配置
+///
+/// # 返回
+/// 新狀態與轉換類型
+pub fn transition(
+    state: Option<&ReauditState>,
+    outcome: AuditOutcome,
+    now: u64,
+    config: &ReauditConfig,
+) -> (Option<ReauditState>, Transition) {
+    match (state, outcome) {
+        (None, AuditOutcome::Pass) => (None, Transition::Unchanged),
+        (None, AuditOutcome::Deleted) => (None, Transition::Unchanged),
+        (Some(_), AuditOutcome::Deleted) => (None, Transition::Removed),
+        (Some(_), AuditOutcome::Pass) => (None, Transition::Cleared),
+
+        (None, AuditOutcome::Fail) if config.confirmations == 0 => (
+            Some(ReauditState::ConfirmedCorrupted {
+                first_failed_at: now,
+                confirmed_at: now,
+            }),
+            Transition::Confirmed,
+        ),
+        (None, AuditOutcome::Fail) => {
+            let next_audit_at = now + config.backoff(0);
+            (
+                Some(ReauditState::Suspect {
+                    first_failed_at: now,
+                    confirmations: 0,
+                    next_audit_at,
+                }),
+                Transition::Scheduled {
+                    confirmations: 0,
+                    next_audit_at,
+                },
+            )
+        }
+
+        (
+            Some(ReauditState::Suspect {
+                first_failed_at,
+                confirmations,
+                ..
+            }),
+            AuditOutcome::Fail,
+        ) => {
+            let confirmations = confirmations + 1;
+            if confirmations >= config.confirmations {
+                (
+                    Some(ReauditState::ConfirmedCorrupted {
+                        first_failed_at: *first_failed_at,
+                        confirmed_at: now,
+                    }),
+                    Transition::Confirmed,
+                )
+            } else {
+                let next_audit_at = now + config.backoff(confirmations);
+                (
+                    Some(ReauditState::Suspect {
+                        first_failed_at: *first_failed_at,
+                        confirmations,
+                        next_audit_at,
+                    }),
+                    Transition::Scheduled {
+                        confirmations,
+                        next_audit_at,
+                    },
+                )
+            }
+        }
+
+        (Some(confirmed @ ReauditState::ConfirmedCorrupted { .. }), AuditOutcome::Fail) => {
+            (Some(confirmed.clone()), Transition::Unchanged)
+        }
+    }
+}
+
+/// 持久化的重審策略引擎
+pub struct ReauditPolicy {
+    config: ReauditConfig,
+    path: Option<PathBuf>,
+    states: BTreeMap<String, ReauditState>,
+    clock: Clock,
+}
+
+impl ReauditPolicy {
+    /// 僅在內存中保存狀態
+    pub fn in_memory(config: ReauditConfig) -> Self {
+        Self {
+            config,
+            path: None,
+            states: BTreeMap::new(),
+            clock: Arc::new(SystemTime::now),
+        }
+    }
+
+    /// 打開狀態文件（不存在時從空狀態開始）
+    pub fn open(config: ReauditConfig, path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let states = if path.exists() {
+            serde_json::from_str(&fs::read_to_string(&path)?)?
+        } else {
+            BTreeMap::new()
+        };
+
+        debug!(
+            "Loaded re-audit state for {} blobs from {}",
+            states.len(),
+            path.display()
+        );
+
+        Ok(Self {
+            config,
+            path: Some(path),
+            states,
+            clock: Arc::new(SystemTime::now),
+        })
+    }
+
+    /// 替換時鐘（測試用）
+    pub fn with_clock(mut self, clock: Clock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// 當前時間（Unix 秒）
+    fn now(&self) -> u64 {
+        (self.clock)()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0)
+    }
+
+    /// Blob 的當前狀態（`None` 表示健康）
+    pub fn state(&self, blob_id: &str) -> Option<&ReauditState> {
+        self.states.get(blob_id)
+    }
+
+    /// 已確認損壞的 Blob
+    pub fn confirmed_corrupted(&self) -> Vec<String> {
+        self.states
+            .iter()
+            .filter(|(_, state)| matches!(state, ReauditState::ConfirmedCorrupted { .. }))
+            .map(|(blob_id, _)| blob_id.clone())
+            .collect()
+    }
+
+    /// 記錄審計結果並推進狀態機
+    ///
+    /// 狀態變化後立即持久化；確認損壞時記錄最終通知
+    pub fn record(&mut self, blob_id: &str, outcome: AuditOutcome) -> Result<Transition> {
+        let now = self.now();
+        let (next, transition) = transition(self.states.get(blob_id), outcome, now, &self.config);
+
+        match &transition {
+            Transition::Unchanged => return Ok(transition),
+            Transition::Scheduled {
+                confirmations,
+                next_audit_at,
+            } => info!(
+                "Blob {} failed (confirmation {}/{}); re-audit in {}s",
+                blob_id,
+                confirmations,
+                self.config.confirmations,
+                next_audit_at.saturating_sub(now)
+            ),
+            Transition::Confirmed => warn!(
+                "🚨 Blob {} CONFIRMED CORRUPTED after {} consecutive failed re-audits; no further re-audits",
+                blob_id, self.config.confirmations
+            ),
+            Transition::Cleared => info!("Blob {} passed re-audit; failure cleared", blob_id),
+            Transition::Removed => debug!("Blob {} deleted; re-audit state removed", blob_id),
+        }
+
+        match next {
+            Some(state) => self.states.insert(blob_id.to_string(), state),
+            None => self.states.remove(blob_id),
+        };
+        self.save()?;
+
+        Ok(transition)
+    }
+
+    /// 已到期的重審（最早到期者優先，同時到期時失敗次數多者優先）
+    pub fn due(&self) -> Vec<String> {
+        let now = self.now();
+        let mut due: Vec<_> = self
+            .states
+            .iter()
+            .filter_map(|(blob_id, state)| match state {
+                ReauditState::Suspect {
+                    confirmations,
+                    next_audit_at,
+                    ..
+                } if *next_audit_at <= now => Some((*next_audit_at, *confirmations, blob_id)),
+                _ => None,
+            })
+            .collect();
+
+        due.sort_by(|a, b| a.0.cmp(&b.0).then(b.1.cmp(&a.1)).then(a.2.cmp(b.2)));
+        due.into_iter()
+            .map(|(_, _, blob_id)| blob_id.clone())
+            .collect()
+    }
+
+    /// 本輪審計順序：到期的重審在前，其後是不受策略控制的常規 Blob
+    pub fn plan(&self, pending: &[String]) -> Vec<String> {
+        let mut plan = self.due();
+        plan.extend(
+            pending
+                .iter()
+                .filter(|blob_id| !self.states.contains_key(*blob_id))
+                .cloned(),
+        );
+        plan
+    }
+
+    /// 距下一次重審到期的時間（沒有待重審時為 `None`）
+    pub fn next_due_in(&self) -> Option<Duration> {
+        let now = self.now();
+        self.states
+            .values()
+            .filter_map(|state| match state {
+                ReauditState::Suspect { next_audit_at, .. } => Some(*next_audit_at),
+                _ => None,
+            })
+            .min()
+            .map(|at| Duration::from_secs(at.saturating_sub(now)))
+    }
+
+    /// 原子寫入狀態文件
+    fn save(&self) -> Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+
+        if let Some(parent) = path.parent() {
+            if !parent.as_os_str().is_empty() {
+                fs::create_dir_all(parent)?;
+            }
+        }
+
+        let tmp_path = path.with_extension("json.tmp");
+        fs::write(&tmp_path, serde_json::to_vec_pretty(&self.states)?)?;
+        fs::rename(&tmp_path, path)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    fn config() -> ReauditConfig {
+        ReauditConfig {
+            backoff_secs: vec![600, 3600],
+            confirmations: 3,
+            ..ReauditConfig::default()
+        }
+    }
+
+    fn suspect_at(first_failed_at: u64, confirmations: u32, next_audit_at: u64) -> ReauditState {
+        ReauditState::Suspect {
+            first_failed_at,
+            confirmations,
+            next_audit_at,
+        }
+    }
+
+    fn suspect(confirmations: u32, next_audit_at: u64) -> ReauditState {
+        suspect_at(100, confirmations, next_audit_at)
+    }
+
+    fn confirmed() -> ReauditState {
+        ReauditState::ConfirmedCorrupted {
+            first_failed_at: 100,
+            confirmed_at: 900,
+        }
+    }
+
+    /// 可手動推進的時鐘
+    fn mock_clock(start: u64) -> (Clock, Arc<AtomicU64>) {
+        let now = Arc::new(AtomicU64::new(start));
+        let handle = Arc::clone(&now);
+        let clock: Clock =
+            Arc::new(move || UNIX_EPOCH + Duration::from_secs(handle.load(Ordering::SeqCst)));
+        (clock, now)
+    }
+
+    #[test]
+    fn test_outcome_classification() {
+        use VerificationStatus::*;
+        assert_eq!(AuditOutcome::from_audit(&Accessible, 0), AuditOutcome::Pass);
+        assert_eq!(AuditOutcome::from_audit(&Accessible, 1), AuditOutcome::Fail);
+        assert_eq!(AuditOutcome::from_audit(&Corrupted, 0), AuditOutcome::Fail);
+        assert_eq!(
+            AuditOutcome::from_audit(&Unreachable, 0),
+            AuditOutcome::Fail
+        );
+        assert_eq!(AuditOutcome::from_audit(&Deleted, 0), AuditOutcome::Deleted);
+        assert_eq!(AuditOutcome::from_audit(&Expired, 0), AuditOutcome::Deleted);
+    }
+
+    #[test]
+    fn test_transitions_from_healthy() {
+        let c = config();
+        assert_eq!(
+            transition(None, AuditOutcome::Pass, 100, &c),
+            (None, Transition::Unchanged)
+        );
+        assert_eq!(
+            transition(None, AuditOutcome::Deleted, 100, &c),
+            (None, Transition::Unchanged)
+        );
+        assert_eq!(
+            transition(None, AuditOutcome::Fail, 100, &c),
+            (
+                Some(suspect(0, 700)),
+                Transition::Scheduled {
+                    confirmations: 0,
+                    next_audit_at: 700
+                }
+            )
+        );
+
+        // 不需要確認時首次失敗即確認
+        let immediate = ReauditConfig {
+            confirmations: 0,
+            ..config()
+        };
+        let (state, t) = transition(None, AuditOutcome::Fail, 100, &immediate);
+        assert_eq!(t, Transition::Confirmed);
+        assert!(matches!(
+            state,
+            Some(ReauditState::ConfirmedCorrupted { .. })
+        ));
+    }
+
+    #[test]
+    fn test_transitions_from_suspect() {
+        let c = config();
+
+        // 第 1 次確認：使用第二個間隔
+        assert_eq!(
+            transition(Some(&suspect(0, 700)), AuditOutcome::Fail, 700, &c),
+            (
+                Some(suspect(1, 4300)),
+                Transition::Scheduled {
+                    confirmations: 1,
+                    next_audit_at: 4300
+                }
+            )
+        );
+
+        // 間隔用盡後重複最後一項
+        assert_eq!(
+            transition(Some(&suspect(1, 4300)), AuditOutcome::Fail, 4300, &c),
+            (
+                Some(suspect(2, 7900)),
+                Transition::Scheduled {
+                    confirmations: 2,
+                    next_audit_at: 7900
+                }
+            )
+        );
+
+        // 第 N 次確認
+        assert_eq!(
+            transition(Some(&suspect(2, 7900)), AuditOutcome::Fail, 7900, &c),
+            (
+                Some(ReauditState::ConfirmedCorrupted {
+                    first_failed_at: 100,
+                    confirmed_at: 7900
+                }),
+                Transition::Confirmed
+            )
+        );
+
+        assert_eq!(
+            transition(Some(&suspect(2, 7900)), AuditOutcome::Pass, 7900, &c),
+            (None, Transition::Cleared)
+        );
+        assert_eq!(
+            transition(Some(&suspect(2, 7900)), AuditOutcome::Deleted, 7900, &c),
+            (None, Transition::Removed)
+        );
+    }
+
+    #[test]
+    fn test_transitions_from_confirmed() {
+        let c = config();
+        assert_eq!(
+            transition(Some(&confirmed()), AuditOutcome::Fail, 9000, &c),
+            (Some(confirmed()), Transition::Unchanged)
+        );
+        assert_eq!(
+            transition(Some(&confirmed()), AuditOutcome::Pass, 9000, &c),
+            (None, Transition::Cleared)
+        );
+        assert_eq!(
+            transition(Some(&confirmed()), AuditOutcome::Deleted, 9000, &c),
+            (None, Transition::Removed)
+        );
+    }
+
+    #[test]
+    fn test_scheduling_with_mock_clock() {
+        let (clock, now) = mock_clock(1_000);
+        let mut policy = ReauditPolicy::in_memory(config()).with_clock(clock);
+        let pending = vec!["a".to_string(), "b".to_string(), "c".to_string()];
+
+        assert_eq!(policy.plan(&pending), pending);
+        assert_eq!(policy.next_due_in(), None);
+
+        policy.record("b", AuditOutcome::Fail).unwrap();
+        now.store(1_100, Ordering::SeqCst);
+        policy.record("c", AuditOutcome::Fail).unwrap();
+
+        // 退避期間不被常規隊列審計
+        assert_eq!(policy.plan(&pending), vec!["a"]);
+        assert_eq!(policy.next_due_in(), Some(Duration::from_secs(500)));
+
+        // b 先到期
+        now.store(1_600, Ordering::SeqCst);
+        assert_eq!(policy.plan(&pending), vec!["b", "a"]);
+
+        // 兩者都到期：更早到期者優先
+        now.store(1_700, Ordering::SeqCst);
+        assert_eq!(policy.plan(&pending), vec!["b", "c", "a"]);
+
+        // b 連續確認失敗直到 ConfirmedCorrupted
+        for expected_wait in [3_600, 3_600] {
+            let t = policy.record("b", AuditOutcome::Fail).unwrap();
+            assert!(matches!(t, Transition::Scheduled { .. }));
+            assert!(!policy.due().contains(&"b".to_string()));
+            now.fetch_add(expected_wait, Ordering::SeqCst);
+            assert!(policy.due().contains(&"b".to_string()));
+        }
+        assert_eq!(
+            policy.record("b", AuditOutcome::Fail).unwrap(),
+            Transition::Confirmed
+        );
+        assert_eq!(policy.confirmed_corrupted(), vec!["b"]);
+
+        // 確認後不再安排重審
+        now.fetch_add(100_000, Ordering::SeqCst);
+        assert_eq!(policy.plan(&pending), vec!["c", "a"]);
+
+        // PASS 清除狀態，回到常規隊列
+        assert_eq!(
+            policy.record("c", AuditOutcome::Pass).unwrap(),
+            Transition::Cleared
+        );
+        assert_eq!(policy.plan(&pending), vec!["a", "c"]);
+    }
+
+    #[test]
+    fn test_state_persists_across_restart() {
+        let path =
+            std::env::temp_dir().join(format!("reaudit_state_{}.json", rand::random::<u32>()));
+        let (clock, _) = mock_clock(1_000);
+
+        let mut policy = ReauditPolicy::open(config(), &path)
+            .unwrap()
+            .with_clock(Arc::clone(&clock));
+        policy.record("a", AuditOutcome::Fail).unwrap();
+        policy.record("b", AuditOutcome::Fail).unwrap();
+        policy.record("b", AuditOutcome::Fail).unwrap();
+        drop(policy);
+
+        let reopened = ReauditPolicy::open(config(), &path)
+            .unwrap()
+            .with_clock(clock);
+        assert_eq!(reopened.state("a"), Some(&suspect_at(1_000, 0, 1_600)));
+        assert_eq!(reopened.state("b"), Some(&suspect_at(1_000, 1, 4_600)));
+
+        std::fs::remove_file(&path).ok();
+    }
+}
@@ -4,15 +4,21 @@
 //!
 //! # 功能
 //!
-//! - **PQC 簽名**: 使用 Dilithium3 對審計報告進行後量子安全的數字簽名
-//! - **簽名驗證**: 驗證報告的 PQC 簽名是否有效
+//! - **PQC 簽名**: 使用任意 [`Signer`]（默認 Dilithium3，可選 Falcon512）對審計報告進行後量子安全的數字簽名
+//! - **簽名驗證**: 按報告中的 `pqc_algorithm` 選擇驗證器，驗證報告的 PQC 簽名是否有效
+//! - **多審計員聯署**: 按信任庫核對 `cosignatures` 並要求最少簽名數（見 [`crate::cosign`]）
 //! - **JSON 序列化**: 將報告導出為 JSON 格式（用於存檔和審計追蹤）
-//! - **報告加載**: 從 JSON 文件加載已簽名的審計報告
+//! - **CBOR 序列化**: 緊湊的二進制導出（[`ReportFormat::Cbor`]），按擴展名自動選擇格式
+//! - **報告加載**: 從 JSON 文件加載已簽名的審計報告（舊版 `SignedAuditReport` 自動遷移）
+//! - **Schema 版本**: 報告記錄 `schema_version`，加載時按版本遷移（見 [`migrate_document`]），
+//!   更新的構建生成的報告返回 `UnsupportedReportVersion` 而非模糊的解析錯誤
 //!
 //! # 安全性
 //!
 //! - **簽名順序**: 簽名時 `pqc_signature` 字段必須為空（避免循環依賴）
-//! - **完整性保證**: 簽名覆蓋整個報告內容（除簽名字段本身）
+//! - **完整性保證**: 簽名覆蓋整個報告內容（除簽名字段與聯署本身）
+//! - **規範編碼**: 簽名字節為帶域分隔標籤的確定性編碼（[`AuditReport::signing_bytes`]），
+//!   不依賴 JSON 字段順序；此前按報告 JSON 簽名的報告仍可驗證，但會記錄棄用警告
 //! - **量子抗性**: Dilithium3 提供 NIST Level 3 安全性
 //! - **長期有效性**: 簽名在量子計算時代仍然安全
 //!
@@ -51,27 +57,156 @@
 //! # }
 //! ```
 
+use crate::audit_report::PqcAlgorithm;
+use crate::chain_types::MoveId;
+use crate::commitment::CommitmentLog;
+use crate::cosign::verify_cosignature;
 use crate::error::{AuditorError, Result};
-use crate::types::AuditReport;
-use pqc_signer::{Dilithium3Signer, Signer};
-use serde_json;
+use crate::ingest::{self, IngestLimits};
+use crate::integrity::AuditData;
+use crate::keystore::KeyChain;
+use crate::onchain_keys::PublicKeyLookup;
+use crate::producer::Producer;
+use crate::trust::{key_id, TrustStore};
+use crate::types::{AuditReport, REPORT_SCHEMA_VERSION};
+use pqc_signer::prehash::{self, StreamingSigner};
+use pqc_signer::{Dilithium3Signer, SignedPayload, Signer};
+use serde_json::{self, json, Value};
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
 use std::fs;
 use std::path::Path;
 use tracing::{debug, info, warn};
 
+/// 報告文件格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportFormat {
+    /// 美化 JSON（`.json`）
+    Json,
+    /// CBOR 二進制編碼（`.cbor`），與 JSON 共用 [`AuditReport`] 結構
+    Cbor,
+}
+
+impl ReportFormat {
+    /// 按文件擴展名選擇格式（不區分大小寫）
+    ///
+    /// # 錯誤
+    /// - 擴展名不是 `.json` 或 `.cbor`: 返回 `Config` 錯誤
+    pub fn from_path(path: &str) -> Result<Self> {
+        let extension = Path::new(path)
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .map(str::to_ascii_lowercase);
+        match extension.as_deref() {
+            Some("json") => Ok(Self::Json),
+            Some("cbor") => Ok(Self::Cbor),
+            _ => Err(AuditorError::Config(format!(
+                "Unsupported report file extension: {} (expected .json or .cbor)",
+                path
+            ))),
+        }
+    }
+
+    /// 將報告編碼為該格式的字節
+    pub fn encode(self, report: &AuditReport) -> Result<Vec<u8>> {
+        match self {
+            Self::Json => serde_json::to_vec_pretty(report).map_err(|e| {
+                AuditorError::Serialization(format!("Failed to serialize report: {}", e))
+            }),
+            Self::Cbor => ReportManager::to_cbor(report),
+        }
+    }
+}
+
+/// v1 規範報告中早期構建未寫入的字段及其默認值
+///
+/// 其餘後來新增的字段在結構定義中本身帶 `#[serde(default)]`，無需遷移
+fn v1_field_defaults() -> [(&'static str, Value); 4] {
+    [
+        ("challenge_results", json!([])),
+        ("pqc_signature", json!([])),
+        ("pqc_algorithm", json!(0)),
+        ("failure_reason", Value::Null),
+    ]
+}
+
+/// 讀取文檔記錄的 schema 版本（未記錄時為 1）
+///
+/// # 錯誤
+/// - `schema_version` 不是正整數: 返回 `Serialization` 錯誤
+/// - 版本高於 [`REPORT_SCHEMA_VERSION`]: 返回 `UnsupportedReportVersion` 錯誤
+pub fn document_schema_version(value: &Value) -> Result<u32> {
+    let version = match value.get("schema_version") {
+        None => 1,
+        Some(field) => field
+            .as_u64()
+            .filter(|version| *version >= 1)
+            .ok_or_else(|| {
+                AuditorError::Serialization(format!(
+                    "Invalid report: field `schema_version` must be a positive integer, got {}",
+                    field
+                ))
+            })?,
+    };
+    check_schema_version(version)?;
+    Ok(version as u32)
+}
+
+/// 拒絕本構建不認識的（更高的）schema 版本
+pub(crate) fn check_schema_version(found: u64) -> Result<()> {
+    if found > u64::from(REPORT_SCHEMA_VERSION) {
+        return Err(AuditorError::UnsupportedReportVersion {
+            found: u32::try_from(found).unwrap_or(u32::MAX),
+            supported: REPORT_SCHEMA_VERSION,
+        });
+    }
+    Ok(())
+}
+
+/// 將報告文檔遷移到本構建能解析的形式，返回文檔記錄的 schema 版本
+///
+/// v1 規範報告中早期構建未寫入的字段按默認值補齊；舊版 `SignedAuditReport` 信封
+/// （頂層帶 `audit_data`）由 [`ingest`] 單獨遷移，這裡只檢查版本。
+/// 文檔記錄的版本保持不變，簽名字節按該版本計算（見 [`AuditReport::signing_bytes`]），
+/// 因此遷移不影響簽名驗證
+///
+/// # 錯誤
+/// - 同 [`document_schema_version`]
+pub fn migrate_document(value: &mut Value) -> Result<u32> {
+    let version = document_schema_version(value)?;
+
+    if version == 1 && value.get("audit_data").is_none() {
+        if let Some(fields) = value.as_object_mut() {
+            for (name, default) in v1_field_defaults() {
+                if !fields.contains_key(name) {
+                    debug!(
+                        "Filling missing v1 report field `{}` with its default",
+                        name
+                    );
+                    fields.insert(name.to_string(), default);
+                }
+            }
+        }
+    }
+
+    Ok(version)
+}
+
 /// 審計報告管理器
 ///
-/// 負責管理審計報告的簽名、驗證和持久化
-pub struct ReportManager {
-    /// PQC 簽名器（Dilithium3）
-    signer: Dilithium3Signer,
+/// 負責管理審計報告的簽名、驗證和持久化。
+/// 簽名器類型默認為 Dilithium3；驗證與加載等關聯函數不依賴簽名器，
+/// 統一定義在 `ReportManager`（即 `ReportManager<Dilithium3Signer>`）上
+pub struct ReportManager<S: Signer = Dilithium3Signer> {
+    /// PQC 簽名器
+    signer: S,
 }
 
-impl ReportManager {
+impl<S: Signer> ReportManager<S> {
     /// 創建新的報告管理器
     ///
     /// # 參數
-    /// - `signer`: 已初始化密鑰的 Dilithium3 簽名器
+    /// - `signer`: 已初始化密鑰的 PQC 簽名器（Dilithium3 或 Falcon512）
     ///
     /// # 示例
     /// ```no_run
@@ -83,39 +218,18 @@ impl ReportManager {
     ///
     /// let manager = ReportManager::new(signer);
     /// ```
-    pub fn new(signer: Dilithium3Signer) -> Self {
-        info!("Created ReportManager with Dilithium3 signer");
+    pub fn new(signer: S) -> Self {
+        info!("Created ReportManager with {} signer", signer.algorithm_name());
         Self { signer }
     }
 
-    /// 從密鑰字節創建報告管理器
-    ///
-    /// # 參數
-    /// - `public_key`: PQC 公鑰字節（1952 bytes）
-    /// - `secret_key`: PQC 私鑰字節（4032 bytes）
-    ///
-    /// # 錯誤
-    /// - 如果密鑰長度不正確，返回 `PqcSignature` 錯誤
-    pub fn from_keypair(public_key: &[u8], secret_key: &[u8]) -> Result<Self> {
-        let signer = Dilithium3Signer::from_bytes(public_key, secret_key)
-            .map_err(|e| AuditorError::PqcSignature(e.to_string()))?;
-
-        info!(
-            "Created ReportManager from keypair: pk_len={}, sk_len={}",
-            public_key.len(),
-            secret_key.len()
-        );
-
-        Ok(Self { signer })
-    }
-
     /// 對審計報告進行 PQC 簽名
     ///
     /// # 簽名流程
     /// 1. 創建報告副本，將 `pqc_signature` 設為空
     /// 2. 將報告序列化為 JSON 字節
-    /// 3. 使用 Dilithium3 對字節進行簽名
-    /// 4. 將簽名存儲到報告的 `pqc_signature` 字段
+    /// 3. 使用簽名器對字節進行簽名
+    /// 4. 將簽名存儲到報告的 `pqc_signature` 字段，算法編號寫入 `pqc_algorithm`
     ///
     /// # 參數
     /// - `report`: 要簽名的審計報告（會被修改）
@@ -142,33 +256,237 @@ impl ReportManager {
     /// # }
     /// ```
     pub fn sign_report(&self, report: &mut AuditReport) -> Result<()> {
+        ReportManager::sign_report_with(&self.signer, report)
+    }
+
+    /// 將報告導出為 JSON 文件
+    ///
+    /// # 參數
+    /// - `report`: 要導出的審計報告
+    /// - `path`: 輸出文件路徑
+    ///
+    /// # 錯誤
+    /// - 序列化失敗: 返回 `Serialization` 錯誤
+    /// - 文件寫入失敗: 返回 `Io` 錯誤
+    ///
+    /// # 示例
+    /// ```no_run
+    /// # use auditor_node::report::ReportManager;
+    /// # use auditor_node::types::AuditReport;
+    /// # fn example(manager: ReportManager, report: AuditReport) -> Result<(), Box<dyn std::error::Error>> {
+    /// manager.export_json(&report, "audit_report_2024-11-16.json")?;
+    /// println!("Report exported successfully!");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn export_json(&self, report: &AuditReport, path: &str) -> Result<()> {
+        self.export(report, path, ReportFormat::Json).map(|_| ())
+    }
+
+    /// 將報告導出為 CBOR 文件
+    ///
+    /// 字段與 JSON 導出相同，簽名等字節數組不經文本編碼，文件明顯小於 JSON
+    pub fn export_cbor(&self, report: &AuditReport, path: &str) -> Result<()> {
+        self.export(report, path, ReportFormat::Cbor).map(|_| ())
+    }
+
+    /// 按擴展名（`.json` / `.cbor`）選擇格式導出報告
+    pub fn export_auto(&self, report: &AuditReport, path: &str) -> Result<()> {
+        self.export_with_digest(report, path).map(|_| ())
+    }
+
+    /// 按擴展名選擇格式導出報告，返回寫入字節的 SHA-256（hex）
+    ///
+    /// 摘要覆蓋文件的實際內容，可直接作為報告摘要登記到鏈上
+    pub fn export_with_digest(&self, report: &AuditReport, path: &str) -> Result<String> {
+        let bytes = self.export(report, path, ReportFormat::from_path(path)?)?;
+        Ok(hex::encode(Sha256::digest(&bytes)))
+    }
+
+    /// 以指定格式寫入報告，返回寫入的字節
+    fn export(&self, report: &AuditReport, path: &str, format: ReportFormat) -> Result<Vec<u8>> {
+        info!("Exporting report to {:?}: {}", format, path);
+
+        let bytes = format.encode(report)?;
+
+        // 寫入文件
+        fs::write(path, &bytes).map_err(|e| {
+            AuditorError::Io(std::io::Error::new(
+                e.kind(),
+                format!("Failed to write report to {}: {}", path, e),
+            ))
+        })?;
+
+        info!("Report exported successfully: {} bytes", bytes.len());
+
+        Ok(bytes)
+    }
+
+    /// 獲取簽名器的公鑰
+    ///
+    /// # 返回
+    /// - PQC 公鑰字節（Dilithium3 為 1952 bytes，Falcon512 為 897 bytes）
+    pub fn public_key(&self) -> &[u8] {
+        self.signer.public_key()
+    }
+
+    /// 獲取算法信息
+    pub fn algorithm_name(&self) -> &str {
+        self.signer.algorithm_name()
+    }
+
+    /// 獲取簽名器
+    pub fn signer(&self) -> &S {
+        &self.signer
+    }
+}
+
+impl ReportManager {
+    /// 從密鑰字節創建報告管理器
+    ///
+    /// # 參數
+    /// - `public_key`: PQC 公鑰字節（1952 bytes）
+    /// - `secret_key`: PQC 私鑰字節（4032 bytes）
+    ///
+    /// # 錯誤
+    /// - 如果密鑰長度不正確，返回 `PqcSignature` 錯誤
+    pub fn from_keypair(public_key: &[u8], secret_key: &[u8]) -> Result<Self> {
+        let signer = Dilithium3Signer::from_bytes(public_key, secret_key)
+            .map_err(|e| AuditorError::PqcSignature(e.to_string()))?;
+
         info!(
-            "Signing audit report: blob_id={}, challenges={}",
-            report.blob_id, report.total_challenges
+            "Created ReportManager from keypair: pk_len={}, sk_len={}",
+            public_key.len(),
+            secret_key.len()
         );
 
-        // 步驟 1: 創建副本，清空簽名相關字段
+        Ok(Self { signer })
+    }
+
+    /// 報告的簽名字節
+    ///
+    /// 規範格式為 [`AuditReport::signing_bytes`]（帶域分隔標籤、不依賴 JSON 字段順序），
+    /// 不含 `pqc_signature`、`pqc_algorithm` 與聯署。
+    /// 主簽名與所有聯署都覆蓋同一份字節，因此附加聯署不會使主簽名失效。
+    /// 遷移自舊版 `SignedAuditReport` 的報告（帶 `legacy_envelope`）按原格式返回 `AuditData` 的 JSON。
+    /// 字節按報告記錄的 `schema_version` 計算，簽名時報告升級為 [`REPORT_SCHEMA_VERSION`]
+    ///
+    /// # 錯誤
+    /// - 序列化失敗: 返回 `Serialization` 錯誤
+    pub fn signing_payload(report: &AuditReport) -> Result<Vec<u8>> {
+        // 遷移自舊版 SignedAuditReport 的報告：簽名覆蓋 AuditData 的 JSON
+        if report.legacy_envelope.is_some() {
+            return serde_json::to_vec(&AuditData::try_from(report)?).map_err(|e| {
+                AuditorError::Serialization(format!("Failed to serialize audit data: {}", e))
+            });
+        }
+
+        Ok(report.signing_bytes())
+    }
+
+    /// 報告的自包含簽名數據（[`SignedPayload`]：簽名字節、簽名、算法與是否預哈希）
+    ///
+    /// 供無法重建規範簽名字節的驗證方（如瀏覽器儀表板經 `pqc_signer::verify_report_json`）
+    /// 直接驗證簽名；其中不含公鑰，驗證方仍須另行取得審計員公鑰
+    ///
+    /// # 錯誤
+    /// - 報告未簽名或算法編號未知: 返回 `PqcSignature` 錯誤
+    pub fn signed_payload(report: &AuditReport) -> Result<SignedPayload> {
+        if report.pqc_signature.is_empty() {
+            return Err(AuditorError::PqcSignature(
+                "Report has no signature".to_string(),
+            ));
+        }
+        let algorithm = PqcAlgorithm::from_id(report.pqc_algorithm).ok_or_else(|| {
+            AuditorError::PqcSignature(format!(
+                "Unsupported PQC algorithm: {}",
+                report.pqc_algorithm
+            ))
+        })?;
+
+        Ok(SignedPayload {
+            algorithm: algorithm.as_str().to_string(),
+            payload: Self::signing_payload(report)?,
+            signature: report.pqc_signature.clone(),
+            prehashed: report.pqc_prehashed,
+        })
+    }
+
+    /// 舊版簽名字節（已棄用）：清空 `pqc_signature`、`pqc_algorithm` 與聯署後的報告 JSON
+    ///
+    /// 字段順序取決於結構體定義，僅用於驗證規範格式之前簽名的報告
+    fn legacy_json_payload(report: &AuditReport) -> Result<Vec<u8>> {
         // 注意：必須同時清空 pqc_signature 和 pqc_algorithm，
         // 否則驗證時序列化會與簽名時不一致
         let mut temp_report = report.clone();
         temp_report.pqc_signature = vec![];
         temp_report.pqc_algorithm = 0;
+        temp_report.cosignatures = vec![];
 
-        // 步驟 2: 序列化為 JSON 字節
-        let serialized = serde_json::to_vec(&temp_report).map_err(|e| {
+        serde_json::to_vec(&temp_report).map_err(|e| {
             AuditorError::Serialization(format!("Failed to serialize report: {}", e))
+        })
+    }
+
+    /// 使用外部簽名器（如密鑰庫中的簽名器）對報告簽名
+    ///
+    /// 與 [`sign_report`](Self::sign_report) 使用同一份簽名字節；
+    /// 遷移而來的報告重新簽名後成為規範格式。`pqc_algorithm` 由簽名器的算法名稱決定
+    ///
+    /// # 錯誤
+    /// - 簽名器的算法沒有對應的 [`PqcAlgorithm`]: 返回 `PqcSignature` 錯誤
+    pub fn sign_report_with<T: Signer + ?Sized>(
+        signer: &T,
+        report: &mut AuditReport,
+    ) -> Result<()> {
+        Self::sign_report_with_prehash(signer, report, None)
+    }
+
+    /// 使用外部簽名器對報告簽名，簽名字節不少於 `prehash_threshold` 時改為簽名其摘要
+    ///
+    /// 摘要簽名經 [`StreamingSigner`] 計算（見 [`pqc_signer::prehash`]），避免 Dilithium3
+    /// 把整份簽名字節再複製一遍；報告記錄 `pqc_prehashed`，驗證時按同一方式計算摘要。
+    /// 摘要簽名與原始簽名有域分隔，改動 `pqc_prehashed` 不會使任何一種簽名通過驗證
+    pub fn sign_report_with_prehash<T: Signer + ?Sized>(
+        signer: &T,
+        report: &mut AuditReport,
+        prehash_threshold: Option<usize>,
+    ) -> Result<()> {
+        let algorithm = PqcAlgorithm::from_name(signer.algorithm_name()).ok_or_else(|| {
+            AuditorError::PqcSignature(format!(
+                "Unsupported signer algorithm: {}",
+                signer.algorithm_name()
+            ))
         })?;
 
+        info!(
+            "Signing audit report: blob_id={}, challenges={}",
+            report.blob_id, report.total_challenges
+        );
+
+        // 步驟 0: 記錄生產者（在簽名範圍內），並改用當前版本的規範簽名字節
+        report.producer = Some(Producer::current());
+        report.legacy_envelope = None;
+        report.schema_version = REPORT_SCHEMA_VERSION;
+
+        // 步驟 1-2: 規範簽名字節（不含簽名相關字段）
+        let serialized = Self::signing_payload(report)?;
+
         debug!(
             "Serialized report for signing: {} bytes",
             serialized.len()
         );
 
-        // 步驟 3: 使用 PQC 簽名
-        let signature = self
-            .signer
-            .sign(&serialized)
-            .map_err(|e| AuditorError::PqcSignature(format!("Signing failed: {}", e)))?;
+        // 步驟 3: 使用 PQC 簽名（大報告簽名其摘要）
+        let prehashed = prehash_threshold.is_some_and(|threshold| serialized.len() >= threshold);
+        let signature = if prehashed {
+            let mut streaming = StreamingSigner::new(signer);
+            streaming.update(&serialized);
+            streaming.finalize()
+        } else {
+            signer.sign(&serialized)
+        }
+        .map_err(|e| AuditorError::PqcSignature(format!("Signing failed: {}", e)))?;
 
         info!(
             "Report signed successfully: signature_len={} bytes",
@@ -177,7 +495,8 @@ impl ReportManager {
 
         // 步驟 4: 存儲簽名
         report.pqc_signature = signature;
-        report.pqc_algorithm = 3; // Dilithium3
+        report.pqc_algorithm = algorithm.id();
+        report.pqc_prehashed = prehashed;
 
         Ok(())
     }
@@ -186,7 +505,7 @@ impl ReportManager {
     ///
     /// # 參數
     /// - `report`: 要驗證的審計報告
-    /// - `public_key`: 審計員的 PQC 公鑰字節（1952 bytes）
+    /// - `public_key`: 審計員的 PQC 公鑰字節（算法由報告的 `pqc_algorithm` 決定）
     ///
     /// # 返回
     /// - `Ok(true)`: 簽名有效
@@ -226,27 +545,17 @@ impl ReportManager {
             ));
         }
 
-        // 檢查算法
-        if report.pqc_algorithm != 3 {
-            warn!(
-                "Unexpected PQC algorithm: expected 3 (Dilithium3), got {}",
-                report.pqc_algorithm
-            );
+        // 按報告記錄的算法編號選擇驗證器
+        let Some(algorithm) = PqcAlgorithm::from_id(report.pqc_algorithm) else {
+            warn!("Unsupported PQC algorithm id: {}", report.pqc_algorithm);
             return Err(AuditorError::PqcSignature(format!(
                 "Unsupported PQC algorithm: {}",
                 report.pqc_algorithm
             )));
-        }
-
-        // 創建臨時副本，清空簽名相關字段（必須與簽名時的清空邏輯一致）
-        let mut temp_report = report.clone();
-        temp_report.pqc_signature = vec![];
-        temp_report.pqc_algorithm = 0;
+        };
 
-        // 序列化
-        let serialized = serde_json::to_vec(&temp_report).map_err(|e| {
-            AuditorError::Serialization(format!("Failed to serialize report: {}", e))
-        })?;
+        // 規範簽名字節（與簽名時一致）
+        let serialized = Self::signing_payload(report)?;
 
         debug!(
             "Serialized report for verification: {} bytes",
@@ -254,17 +563,66 @@ impl ReportManager {
         );
 
         // 創建僅驗證的 Signer（只需公鑰，無需私鑰）
-        // 使用 from_public_key_only() 而非 from_bytes()，因為驗證不需要私鑰
-        let verifier = Dilithium3Signer::from_public_key_only(public_key).map_err(|e| {
+        let verifier = algorithm.verifier(public_key).map_err(|e| {
             AuditorError::PqcSignature(format!("Invalid public key: {}", e))
         })?;
 
-        debug!("Created verification-only signer with public key");
+        debug!(
+            "Created verification-only {} signer with public key",
+            algorithm.as_str()
+        );
+
+        // 長度不可能是該算法簽名的報告（截斷或拼接）直接報錯，不逐個嘗試舊版載荷
+        if report.pqc_signature.len() > verifier.signature_bytes() {
+            return Err(AuditorError::PqcSignature(format!(
+                "Invalid signature length: {} bytes exceeds {} for {}",
+                report.pqc_signature.len(),
+                verifier.signature_bytes(),
+                algorithm.as_str()
+            )));
+        }
 
         // 執行驗證
-        let is_valid = verifier
-            .verify(&serialized, &report.pqc_signature)
-            .map_err(|e| AuditorError::PqcSignature(format!("Verification failed: {}", e)))?;
+        let mut is_valid = if report.pqc_prehashed {
+            verifier.verify_digest(&prehash::digest(&serialized), &report.pqc_signature)
+        } else {
+            verifier.verify(&serialized, &report.pqc_signature)
+        }
+        .map_err(|e| AuditorError::PqcSignature(format!("Verification failed: {}", e)))?;
+
+        // 摘要簽名只存在於規範格式，記錄了 schema 版本的報告也只按規範格式簽名，不嘗試舊版載荷
+        let legacy_candidate =
+            report.schema_version == 1 && report.legacy_envelope.is_none() && !report.pqc_prehashed;
+
+        // 規範格式之前的報告：簽名覆蓋報告 JSON
+        if !is_valid && legacy_candidate {
+            is_valid = verifier
+                .verify(&Self::legacy_json_payload(report)?, &report.pqc_signature)
+                .map_err(|e| AuditorError::PqcSignature(format!("Verification failed: {}", e)))?;
+            if is_valid {
+                warn!(
+                    "Report for blob {} is signed over its JSON encoding, which is deprecated; re-sign it to use the canonical signing bytes",
+                    report.blob_id
+                );
+            }
+        }
+
+        // 升級前 main.rs 只簽名固定字段子集
+        if !is_valid && legacy_candidate {
+            for payload in Self::legacy_subset_payloads(report)? {
+                if verifier
+                    .verify(&payload, &report.pqc_signature)
+                    .map_err(|e| AuditorError::PqcSignature(format!("Verification failed: {}", e)))?
+                {
+                    warn!(
+                        "Report for blob {} uses the legacy field-subset signature; challenge results, failure reason and capture digest are not covered",
+                        report.blob_id
+                    );
+                    is_valid = true;
+                    break;
+                }
+            }
+        }
 
         if is_valid {
             info!("Report signature verification: VALID ✓");
@@ -275,47 +633,201 @@ impl ReportManager {
         Ok(is_valid)
     }
 
-    /// 將報告導出為 JSON 文件
+    /// 按審計員的密鑰輪換鏈驗證報告簽名
     ///
-    /// # 參數
-    /// - `report`: 要導出的審計報告
-    /// - `path`: 輸出文件路徑
+    /// 輪換前發布的報告由舊密鑰簽名；鏈上任一密鑰（從當前密鑰開始嘗試）能驗證即視為有效
+    ///
+    /// # 返回
+    /// - `Ok(Some(generation))`: 簽名有效，由第 `generation` 代密鑰簽名（從 1 開始）
+    /// - `Ok(None)`: 鏈上沒有密鑰能驗證簽名
+    pub fn verify_report_with_chain(
+        report: &AuditReport,
+        chain: &KeyChain,
+    ) -> Result<Option<usize>> {
+        for (index, public_key) in chain.keys().iter().enumerate().rev() {
+            if Self::verify_report(report, public_key)? {
+                let generation = index + 1;
+                if generation < chain.keys().len() {
+                    info!(
+                        "Report for blob {} is signed by retired key generation {} ({})",
+                        report.blob_id,
+                        generation,
+                        key_id(public_key)
+                    );
+                }
+                return Ok(Some(generation));
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// 按鏈上登記的公鑰驗證報告簽名
+    ///
+    /// 以報告的 `auditor` 地址在 AuditorRegistry 中查詢公鑰（可傳入 [`CachedKeyLookup`]
+    /// 避免重複 RPC），要求登記的算法與報告的 `pqc_algorithm` 一致，再按
+    /// [`verify_report`](Self::verify_report) 驗證
     ///
     /// # 錯誤
-    /// - 序列化失敗: 返回 `Serialization` 錯誤
-    /// - 文件寫入失敗: 返回 `Io` 錯誤
+    /// - `auditor` 不是合法的 Sui 地址: 返回 `ChainAbi` 錯誤
+    /// - 審計員未登記公鑰或查詢失敗: 返回查詢的錯誤
+    /// - 登記的算法與報告不符: 返回 `PqcSignature` 錯誤
     ///
-    /// # 示例
-    /// ```no_run
-    /// # use auditor_node::report::ReportManager;
-    /// # use auditor_node::types::AuditReport;
-    /// # fn example(manager: ReportManager, report: AuditReport) -> Result<(), Box<dyn std::error::Error>> {
-    /// manager.export_json(&report, "audit_report_2024-11-16.json")?;
-    /// println!("Report exported successfully!");
-    /// # Ok(())
-    /// # }
-    /// ```
-    pub fn export_json(&self, report: &AuditReport, path: &str) -> Result<()> {
-        info!("Exporting report to JSON: {}", path);
+    /// [`CachedKeyLookup`]: crate::onchain_keys::CachedKeyLookup
+    pub async fn verify_report_onchain<L: PublicKeyLookup + ?Sized>(
+        report: &AuditReport,
+        lookup: &L,
+    ) -> Result<bool> {
+        let auditor = MoveId::from_hex(&report.auditor)?;
+        let registered = lookup.auditor_public_key(&auditor).await?;
+
+        if registered.algorithm != report.pqc_algorithm {
+            warn!(
+                "Auditor {} registered algorithm {} but report uses {}",
+                auditor, registered.algorithm, report.pqc_algorithm
+            );
+            return Err(AuditorError::PqcSignature(format!(
+                "Algorithm mismatch: auditor {} registered algorithm {}, report uses {}",
+                auditor, registered.algorithm, report.pqc_algorithm
+            )));
+        }
 
-        // 序列化為美化的 JSON
-        let json = serde_json::to_string_pretty(report).map_err(|e| {
-            AuditorError::Serialization(format!("Failed to serialize report: {}", e))
-        })?;
+        debug!(
+            "Verifying report for blob {} with on-chain key {}",
+            report.blob_id,
+            registered.key_id()
+        );
+        Self::verify_report(report, &registered.public_key)
+    }
 
-        let json_len = json.len();
+    /// 升級前 `main.rs` 的簽名字節：固定字段子集的 JSON
+    ///
+    /// 該格式隨版本增加了 `producer` 與 `challenge_reveal` 字段，按報告內容返回可能的候選
+    fn legacy_subset_payloads(report: &AuditReport) -> Result<Vec<Vec<u8>>> {
+        let mut subset = serde_json::json!({
+            "blob_id": report.blob_id,
+            "blob_object_id": report.blob_object_id,
+            "auditor": report.auditor,
+            "timestamp": report.timestamp,
+            "challenge_epoch": report.challenge_epoch,
+            "total_challenges": report.total_challenges,
+            "successful_verifications": report.successful_verifications,
+            "failed_verifications": report.failed_verifications,
+            "integrity_hash": report.integrity_hash,
+            "is_valid": report.is_valid,
+        });
+
+        let mut candidates = Vec::new();
+        if report.producer.is_some() {
+            subset["producer"] = serde_json::json!(report.producer);
+            if report.challenge_reveal.is_none() {
+                candidates.push(serde_json::to_vec(&subset)?);
+            }
+            subset["challenge_reveal"] = serde_json::json!(report.challenge_reveal);
+        }
+        candidates.push(serde_json::to_vec(&subset)?);
 
-        // 寫入文件
-        fs::write(path, json).map_err(|e| {
-            AuditorError::Io(std::io::Error::new(
-                e.kind(),
-                format!("Failed to write report to {}: {}", path, e),
-            ))
-        })?;
+        Ok(candidates)
+    }
 
-        info!("Report exported successfully: {} bytes", json_len);
+    /// 驗證報告簽名並核對挑戰承諾
+    ///
+    /// 在 [`verify_report`](Self::verify_report) 的基礎上，要求報告公開挑戰集，
+    /// 且種子與索引與承諾日誌中此前的承諾一致
+    ///
+    /// # 錯誤
+    /// - 報告未公開挑戰集、或與承諾不符: 返回 `Commitment` 錯誤
+    pub fn verify_report_with_commitments(
+        report: &AuditReport,
+        public_key: &[u8],
+        commitments: &CommitmentLog,
+    ) -> Result<bool> {
+        if !Self::verify_report(report, public_key)? {
+            return Ok(false);
+        }
 
-        Ok(())
+        let commitment = report.verify_challenge_commitment(commitments)?;
+
+        info!(
+            "Challenge set matches commitment {} made at {} ✓",
+            commitment.commitment, commitment.committed_at
+        );
+        Ok(true)
+    }
+
+    /// 驗證主簽名及聯署，要求有效簽名數不少於 `min_signatures`
+    ///
+    /// 主簽名計為 1；每個聯署按信任庫中登記（或固定）的公鑰核對，
+    /// 同一審計員只計一次，主審計員的聯署不計入
+    ///
+    /// # 返回
+    /// - `Ok(true)`: 主簽名與所有聯署均有效，且簽名數足夠
+    /// - `Ok(false)`: 任一簽名無效，或簽名數不足
+    ///
+    /// # 錯誤
+    /// - 聯署人不在信任庫中，或公鑰與登記不符: 返回 `Trust` 錯誤
+    pub fn verify_report_with_cosignatures(
+        report: &AuditReport,
+        public_key: &[u8],
+        trust_store: &TrustStore,
+        min_signatures: usize,
+    ) -> Result<bool> {
+        if !Self::verify_report(report, public_key)? {
+            return Ok(false);
+        }
+
+        let payload = Self::signing_payload(report)?;
+        let mut signers = HashSet::from([report.auditor.as_str()]);
+
+        for cosignature in &report.cosignatures {
+            let entry = trust_store.get(&cosignature.auditor).ok_or_else(|| {
+                AuditorError::Trust(format!(
+                    "Co-signer {} is not in the trust store",
+                    cosignature.auditor
+                ))
+            })?;
+
+            // 規範格式之前的聯署覆蓋報告 JSON（僅 v1 報告）
+            let valid = verify_cosignature(&payload, cosignature, entry)?
+                || (report.schema_version == 1
+                    && report.legacy_envelope.is_none()
+                    && verify_cosignature(
+                        &Self::legacy_json_payload(report)?,
+                        cosignature,
+                        entry,
+                    )?);
+            if !valid {
+                warn!(
+                    "Cosignature by {} ({}) is INVALID ✗",
+                    cosignature.auditor, cosignature.key_id
+                );
+                return Ok(false);
+            }
+
+            if signers.insert(cosignature.auditor.as_str()) {
+                info!(
+                    "Cosignature by {} ({}) is valid ✓",
+                    cosignature.auditor, cosignature.key_id
+                );
+            } else {
+                warn!(
+                    "Ignoring repeated signature by {} on report for blob {}",
+                    cosignature.auditor, report.blob_id
+                );
+            }
+        }
+
+        if signers.len() < min_signatures {
+            warn!(
+                "Report for blob {} has {} valid signatures, {} required",
+                report.blob_id,
+                signers.len(),
+                min_signatures
+            );
+            return Ok(false);
+        }
+
+        Ok(true)
     }
 
     /// 從 JSON 文件加載報告
@@ -350,8 +862,11 @@ impl ReportManager {
             )));
         }
 
-        // 讀取文件
-        let json = fs::read_to_string(path).map_err(|e| {
+        // 讀取文件（超過大小上限時不讀入內存）
+        let json = ingest::read_document(Path::new(path), &IngestLimits::default()).map_err(|e| {
+            let AuditorError::Io(e) = e else {
+                return e;
+            };
             AuditorError::Io(std::io::Error::new(
                 e.kind(),
                 format!("Failed to read report from {}: {}", path, e),
@@ -359,9 +874,7 @@ impl ReportManager {
         })?;
 
         // 反序列化
-        let report: AuditReport = serde_json::from_str(&json).map_err(|e| {
-            AuditorError::Serialization(format!("Failed to parse report JSON: {}", e))
-        })?;
+        let report = Self::from_json(&json)?;
 
         info!(
             "Report loaded successfully: blob_id={}, challenges={}",
@@ -371,36 +884,85 @@ impl ReportManager {
         Ok(report)
     }
 
-    /// 獲取簽名器的公鑰
+    /// 從 CBOR 文件加載報告
     ///
-    /// # 返回
-    /// - PQC 公鑰字節（1952 bytes）
-    pub fn public_key(&self) -> &[u8] {
-        self.signer.public_key()
+    /// # 錯誤
+    /// - 文件不存在: 返回 `Config` 錯誤
+    /// - CBOR 格式錯誤或超出限制: 返回 `Serialization` 錯誤
+    pub fn load_cbor(path: &str) -> Result<AuditReport> {
+        info!("Loading report from CBOR: {}", path);
+
+        if !Path::new(path).exists() {
+            return Err(AuditorError::Config(format!(
+                "Report file not found: {}",
+                path
+            )));
+        }
+
+        let bytes = ingest::read_document_bytes(Path::new(path), &IngestLimits::default())?;
+        let report = Self::from_cbor(&bytes)?;
+
+        info!(
+            "Report loaded successfully: blob_id={}, challenges={}",
+            report.blob_id, report.total_challenges
+        );
+
+        Ok(report)
+    }
+
+    /// 按擴展名（`.json` / `.cbor`）選擇格式加載報告
+    pub fn load_auto(path: &str) -> Result<AuditReport> {
+        match ReportFormat::from_path(path)? {
+            ReportFormat::Json => Self::load_json(path),
+            ReportFormat::Cbor => Self::load_cbor(path),
+        }
+    }
+
+    /// 將報告編碼為 CBOR
+    pub fn to_cbor(report: &AuditReport) -> Result<Vec<u8>> {
+        let mut bytes = Vec::new();
+        ciborium::ser::into_writer(report, &mut bytes).map_err(|e| {
+            AuditorError::Serialization(format!("Failed to serialize report as CBOR: {}", e))
+        })?;
+        Ok(bytes)
+    }
+
+    /// 從 CBOR 解析報告（經 [`ingest`](crate::ingest) 的大小與深度限制）
+    pub fn from_cbor(bytes: &[u8]) -> Result<AuditReport> {
+        ingest::parse_report_cbor(bytes, &IngestLimits::default())
     }
 
-    /// 獲取簽名器的私鑰（用於密鑰庫持久化）
+    /// 從 JSON 解析報告
+    ///
+    /// 經 [`ingest`](crate::ingest) 加固層解析（大小、深度與字段類型檢查）。
+    /// 同時接受舊版 `SignedAuditReport` 格式（頂層帶 `audit_data`），並無損轉換為規範報告
+    ///
+    /// # 錯誤
+    /// - JSON 格式錯誤、超出限制或字段缺失: 返回 `Serialization` 錯誤
+    /// - Sliver 索引越界: 返回 `InvalidSliver` 錯誤
+    pub fn from_json(json: &str) -> Result<AuditReport> {
+        ingest::parse_report(json, &IngestLimits::default())
+    }
+
+    /// 獲取 Dilithium3 簽名器的私鑰（用於密鑰庫持久化）
     ///
     /// # 安全警告
     /// 私鑰應該安全存儲，不應該通過網路傳輸或記錄到日誌中
     pub fn secret_key(&self) -> &[u8] {
         self.signer.secret_key()
     }
-
-    /// 獲取算法信息
-    pub fn algorithm_name(&self) -> &str {
-        self.signer.algorithm_name()
-    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::types::{AuditChallenge, ChallengeResult};
+    use pqc_signer::Falcon512Signer;
 
     /// 創建測試用的審計報告
     fn create_test_report() -> AuditReport {
         AuditReport {
+            schema_version: REPORT_SCHEMA_VERSION,
             blob_id: "0xtest_blob_id".to_string(),
             blob_object_id: "0xtest_object_id".to_string(),
             auditor: "0xtest_auditor".to_string(),
@@ -412,20 +974,45 @@ mod tests {
                     shard_id: 0,
                     challenge_type: 1,
                     timestamp: 1700000000,
+                    symbol_index: None,
                 },
                 verified: true,
                 merkle_proof_valid: true,
                 response_hash: vec![1, 2, 3, 4],
                 failure_reason: None,
+                node_url: None,
+                node_signature_valid: None,
+                response_time_ms: 0,
+                sliver_size_bytes: 0,
+                attempts: 0,
             }],
+            encoding_n: None,
             total_challenges: 1,
             successful_verifications: 1,
             failed_verifications: 0,
             integrity_hash: vec![0u8; 32],
             pqc_signature: vec![],
             pqc_algorithm: 0,
+            pqc_prehashed: false,
             is_valid: true,
             failure_reason: None,
+            capture_digest: None,
+            deduplicated_from: None,
+            producer: None,
+            challenge_reveal: None,
+            challenge_seed_source: None,
+            challenge_seed: None,
+            chunk_filter: None,
+            cosignatures: vec![],
+            integrity: None,
+            legacy_envelope: None,
+            blinding_key_id: None,
+            audit_method: None,
+            response_stats: None,
+            blob_status: None,
+            audit_id: None,
+            shard_coverage: vec![],
+            auditor_reputation_at_audit: None,
         }
     }
 
@@ -489,6 +1076,294 @@ mod tests {
         assert!(!is_valid, "Tampered report signature should be invalid");
     }
 
+    #[test]
+    fn test_signed_payload_verifies_without_report_encoding() {
+        let mut signer = Dilithium3Signer::new();
+        signer.generate_keypair().unwrap();
+        let public_key = signer.public_key().to_vec();
+
+        // 未簽名的報告沒有可導出的簽名
+        assert!(ReportManager::signed_payload(&create_test_report()).is_err());
+
+        // 原始簽名與摘要簽名都可由僅驗證的構建直接驗證
+        for threshold in [None, Some(1)] {
+            let mut report = create_test_report();
+            ReportManager::sign_report_with_prehash(&signer, &mut report, threshold).unwrap();
+            let signed = ReportManager::signed_payload(&report).unwrap();
+            assert_eq!(signed.algorithm, "Dilithium3");
+            assert_eq!(signed.prehashed, threshold.is_some());
+            assert!(pqc_signer::verify_report_json(&signed.to_json(), &public_key).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_sign_and_verify_report_with_falcon512() {
+        let mut signer = Falcon512Signer::new();
+        signer.generate_keypair().unwrap();
+        let public_key = signer.public_key().to_vec();
+
+        let manager = ReportManager::new(signer);
+        assert_eq!(manager.algorithm_name(), "Falcon512");
+
+        let mut report = create_test_report();
+        manager.sign_report(&mut report).unwrap();
+        assert_eq!(report.pqc_algorithm, PqcAlgorithm::Falcon512.id());
+        assert!(ReportManager::verify_report(&report, &public_key).unwrap());
+
+        let mut tampered = report.clone();
+        tampered.successful_verifications = 0;
+        assert!(!ReportManager::verify_report(&tampered, &public_key).unwrap());
+
+        // 算法編號決定驗證器：Dilithium3 公鑰不能驗證 Falcon512 報告
+        let mut dilithium = Dilithium3Signer::new();
+        dilithium.generate_keypair().unwrap();
+        assert!(matches!(
+            ReportManager::verify_report(&report, dilithium.public_key()),
+            Err(AuditorError::PqcSignature(_))
+        ));
+
+        // 不支持的算法編號
+        report.pqc_algorithm = 2;
+        assert!(ReportManager::verify_report(&report, &public_key).is_err());
+    }
+
+    #[test]
+    fn test_sign_report_with_boxed_signer() {
+        let mut signer: Box<dyn Signer> = Box::new(Falcon512Signer::new());
+        signer.generate_keypair().unwrap();
+
+        let mut report = create_test_report();
+        ReportManager::sign_report_with(signer.as_ref(), &mut report).unwrap();
+        assert_eq!(report.pqc_algorithm, 1);
+        assert!(ReportManager::verify_report(&report, signer.public_key()).unwrap());
+    }
+
+    #[test]
+    fn test_legacy_subset_report_migrates_and_verifies() {
+        let mut signer = Dilithium3Signer::new();
+        signer.generate_keypair().unwrap();
+        let public_key = signer.public_key().to_vec();
+
+        // 按升級前 main.rs 的方式簽名：僅覆蓋固定字段子集（當時的報告不記錄 schema 版本）
+        let mut report = create_test_report();
+        report.schema_version = 1;
+        report.producer = Some(Producer::current());
+        let subset = serde_json::json!({
+            "blob_id": report.blob_id,
+            "blob_object_id": report.blob_object_id,
+            "auditor": report.auditor,
+            "timestamp": report.timestamp,
+            "challenge_epoch": report.challenge_epoch,
+            "total_challenges": report.total_challenges,
+            "successful_verifications": report.successful_verifications,
+            "failed_verifications": report.failed_verifications,
+            "integrity_hash": report.integrity_hash,
+            "is_valid": report.is_valid,
+            "producer": report.producer,
+            "challenge_reveal": report.challenge_reveal,
+        });
+        report.pqc_signature = signer.sign(&serde_json::to_vec(&subset).unwrap()).unwrap();
+        report.pqc_algorithm = 3;
+
+        let json = serde_json::to_string_pretty(&report).unwrap();
+        let mut loaded = ReportManager::from_json(&json).unwrap();
+        assert!(ReportManager::verify_report(&loaded, &public_key).unwrap());
+
+        loaded.successful_verifications -= 1;
+        assert!(!ReportManager::verify_report(&loaded, &public_key).unwrap());
+    }
+
+    #[test]
+    fn test_signing_bytes_are_canonical() {
+        let mut report = create_test_report();
+        report.producer = Some(Producer::current());
+        let bytes = report.signing_bytes();
+        assert!(bytes.starts_with(crate::types::REPORT_SIGNING_DOMAIN));
+        assert_eq!(ReportManager::signing_payload(&report).unwrap(), bytes);
+
+        // 經 JSON 往返（鍵按字母順序重排）後簽名字節不變
+        let value = serde_json::to_value(&report).unwrap();
+        let roundtrip: AuditReport = serde_json::from_value(value).unwrap();
+        assert_eq!(roundtrip.signing_bytes(), bytes);
+
+        // 簽名與聯署不在範圍內
+        report.pqc_signature = vec![7; 16];
+        report.pqc_algorithm = 3;
+        assert_eq!(report.signing_bytes(), bytes);
+
+        // 其他字段都在範圍內
+        report.capture_digest = Some("00".to_string());
+        assert_ne!(report.signing_bytes(), bytes);
+        report.capture_digest = None;
+        report.challenge_results[0].response_hash[0] ^= 1;
+        assert_ne!(report.signing_bytes(), bytes);
+        report.challenge_results[0].response_hash[0] ^= 1;
+
+        // 節點歸屬只在存在時簽入，不改變舊報告的簽名字節
+        assert_eq!(report.signing_bytes(), bytes);
+        report.challenge_results[0].node_url = Some("http://node-a:9000".to_string());
+        assert_ne!(report.signing_bytes(), bytes);
+        report.challenge_results[0].node_url = None;
+
+        // 審計關聯 ID 同樣只在存在時簽入
+        assert_eq!(report.signing_bytes(), bytes);
+        report.audit_id = Some("7f1c2a9e-0d4b-4c8e-9a51-3b6f2e8d1c40".to_string());
+        assert_ne!(report.signing_bytes(), bytes);
+    }
+
+    #[test]
+    fn test_blob_id_verification_is_signed_when_present() {
+        use crate::integrity::VerificationStatus;
+        use crate::types::IntegritySummary;
+
+        let mut report = create_test_report();
+        report.integrity = Some(IntegritySummary {
+            content_hash: "ab".repeat(32),
+            merkle_root: "cd".repeat(32),
+            file_size: 4096,
+            verification_status: VerificationStatus::Accessible,
+            resource_decision: None,
+            hash_drift: None,
+            blob_id_verified: None,
+            chunk_size: None,
+            blob_object_id: None,
+            encoding_k: None,
+            encoding_n: None,
+            start_epoch: None,
+            end_epoch: None,
+            onchain_merkle_root: None,
+            size_matches_chain: None,
+        });
+        let bytes = report.signing_bytes();
+
+        report.integrity.as_mut().unwrap().blob_id_verified = Some(true);
+        let verified = report.signing_bytes();
+        assert_ne!(verified, bytes);
+
+        report.integrity.as_mut().unwrap().blob_id_verified = Some(false);
+        assert_ne!(report.signing_bytes(), verified);
+    }
+
+    #[test]
+    fn test_transport_metrics_do_not_affect_signature() {
+        use crate::types::ResponseStats;
+
+        let mut signer = Dilithium3Signer::new();
+        signer.generate_keypair().unwrap();
+        let public_key = signer.public_key().to_vec();
+        let manager = ReportManager::new(signer);
+
+        // 未記錄傳輸指標的報告 JSON 與新增字段之前相同
+        let mut report = create_test_report();
+        let json = serde_json::to_string(&report).unwrap();
+        assert!(!json.contains("response_time_ms") && !json.contains("response_stats"));
+        let bytes = report.signing_bytes();
+
+        report.challenge_results[0].response_time_ms = 120;
+        report.challenge_results[0].sliver_size_bytes = 4096;
+        report.challenge_results[0].attempts = 2;
+        report.response_stats = ResponseStats::from_results(&report.challenge_results);
+        assert_eq!(report.signing_bytes(), bytes);
+
+        // 簽名後延遲不同（如重新測量）仍可驗證
+        manager.sign_report(&mut report).unwrap();
+        report.challenge_results[0].response_time_ms = 9_000;
+        report.challenge_results[0].attempts = 4;
+        report.response_stats = ResponseStats::from_results(&report.challenge_results);
+        assert!(ReportManager::verify_report(&report, &public_key).unwrap());
+
+        // 經 JSON 往返後指標保留
+        let roundtrip: AuditReport =
+            serde_json::from_str(&serde_json::to_string(&report).unwrap()).unwrap();
+        assert_eq!(roundtrip.challenge_results[0].response_time_ms, 9_000);
+        assert_eq!(roundtrip.response_stats, report.response_stats);
+        assert!(ReportManager::verify_report(&roundtrip, &public_key).unwrap());
+    }
+
+    #[test]
+    fn test_legacy_json_signature_still_verifies() {
+        let mut signer = Dilithium3Signer::new();
+        signer.generate_keypair().unwrap();
+        let public_key = signer.public_key().to_vec();
+
+        // 按規範編碼之前的方式簽名：覆蓋清空簽名字段後的報告 JSON（當時的報告不記錄 schema 版本）
+        let mut report = create_test_report();
+        report.schema_version = 1;
+        report.producer = Some(Producer::current());
+        report.pqc_signature = signer
+            .sign(&ReportManager::legacy_json_payload(&report).unwrap())
+            .unwrap();
+        report.pqc_algorithm = 3;
+        assert!(ReportManager::verify_report(&report, &public_key).unwrap());
+
+        report.is_valid = false;
+        assert!(!ReportManager::verify_report(&report, &public_key).unwrap());
+    }
+
+    #[test]
+    fn test_producer_is_recorded_and_signed() {
+        let mut signer = Dilithium3Signer::new();
+        signer.generate_keypair().unwrap();
+        let public_key = signer.public_key().to_vec();
+
+        let manager = ReportManager::new(signer);
+        let mut report = create_test_report();
+        assert!(report.producer.is_none());
+        manager.sign_report(&mut report).unwrap();
+
+        let producer = report.producer.clone().unwrap();
+        assert_eq!(producer, Producer::current());
+        assert_eq!(producer.auditor_node_version, crate::producer::AUDITOR_NODE_VERSION);
+        assert_eq!(producer.target, crate::producer::BUILD_TARGET);
+        assert!(ReportManager::verify_report(&report, &public_key).unwrap());
+
+        // 生產者在簽名範圍內
+        report.producer.as_mut().unwrap().auditor_node_version = "9.9.9".to_string();
+        assert!(!ReportManager::verify_report(&report, &public_key).unwrap());
+    }
+
+    #[test]
+    fn test_verify_report_with_commitments() {
+        use crate::commitment::{ChallengeReveal, CommitmentLog};
+
+        let mut signer = Dilithium3Signer::new();
+        signer.generate_keypair().unwrap();
+        let public_key = signer.public_key().to_vec();
+        let manager = ReportManager::new(signer);
+        let log = CommitmentLog::in_memory();
+
+        // 未公開挑戰集的報告不能通過承諾核對
+        let mut report = create_test_report();
+        report.timestamp = u64::MAX;
+        manager.sign_report(&mut report).unwrap();
+        assert!(matches!(
+            ReportManager::verify_report_with_commitments(&report, &public_key, &log),
+            Err(AuditorError::Commitment(_))
+        ));
+
+        let reveal = log
+            .commit(&report.blob_id, ChallengeReveal::generate(1, 1))
+            .unwrap()
+            .into_reveal();
+        let mut report = create_test_report();
+        report.timestamp = u64::MAX;
+        report.challenge_reveal = Some(reveal);
+        manager.sign_report(&mut report).unwrap();
+        assert!(
+            ReportManager::verify_report_with_commitments(&report, &public_key, &log).unwrap()
+        );
+
+        // 未承諾的挑戰集（重抽）
+        let mut rerolled = create_test_report();
+        rerolled.timestamp = u64::MAX;
+        rerolled.challenge_reveal = Some(ChallengeReveal::generate(1, 1));
+        manager.sign_report(&mut rerolled).unwrap();
+        assert!(matches!(
+            ReportManager::verify_report_with_commitments(&rerolled, &public_key, &log),
+            Err(AuditorError::Commitment(_))
+        ));
+    }
+
     #[test]
     fn test_verify_unsigned_report() {
         let report = create_test_report();
@@ -505,6 +1380,28 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_verify_report_rejects_wrong_signature_length() {
+        let mut signer = Dilithium3Signer::new();
+        signer.generate_keypair().unwrap();
+        let public_key = signer.public_key().to_vec();
+        let manager = ReportManager::new(signer);
+        let mut report = create_test_report();
+        manager.sign_report(&mut report).unwrap();
+
+        // 超長與截斷的簽名都報長度錯誤，而不是返回 Ok(false)
+        let signature = report.pqc_signature.clone();
+        for bad in [[signature.as_slice(), b"x"].concat(), signature[1..].to_vec()] {
+            report.pqc_signature = bad;
+            match ReportManager::verify_report(&report, &public_key) {
+                Err(AuditorError::PqcSignature(msg)) => {
+                    assert!(msg.contains("signature length"), "{}", msg)
+                }
+                other => panic!("Expected signature length error, got {:?}", other),
+            }
+        }
+    }
+
     #[test]
     fn test_export_and_load_json() {
         use tempfile::NamedTempFile;
@@ -535,6 +1432,249 @@ mod tests {
         assert_eq!(loaded_report.pqc_algorithm, report.pqc_algorithm);
     }
 
+    #[test]
+    fn test_cbor_round_trip_keeps_report_and_signature() {
+        let mut signer = Dilithium3Signer::new();
+        signer.generate_keypair().unwrap();
+        let public_key = signer.public_key().to_vec();
+        let manager = ReportManager::new(signer);
+
+        // 可選字段有的存在、有的省略
+        let mut report = create_test_report();
+        report.producer = Some(Producer::current());
+        report.capture_digest = Some("ab".repeat(32));
+        report.challenge_results[0].node_url = Some("http://node-a:9000".to_string());
+        manager.sign_report(&mut report).unwrap();
+
+        // JSON → 結構 → CBOR → 結構
+        let json = serde_json::to_string_pretty(&report).unwrap();
+        let from_json = ReportManager::from_json(&json).unwrap();
+        let cbor = ReportManager::to_cbor(&from_json).unwrap();
+        let from_cbor = ReportManager::from_cbor(&cbor).unwrap();
+
+        assert_eq!(
+            serde_json::to_value(&from_cbor).unwrap(),
+            serde_json::to_value(&report).unwrap()
+        );
+        assert_eq!(from_cbor.signing_bytes(), report.signing_bytes());
+        assert!(ReportManager::verify_report(&from_cbor, &public_key).unwrap());
+        assert!(cbor.len() < json.len());
+
+        assert!(matches!(
+            ReportManager::from_cbor(&cbor[..cbor.len() / 2]),
+            Err(AuditorError::Serialization(_))
+        ));
+    }
+
+    #[test]
+    fn test_export_auto_by_extension_with_digest() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut signer = Dilithium3Signer::new();
+        signer.generate_keypair().unwrap();
+        let public_key = signer.public_key().to_vec();
+        let manager = ReportManager::new(signer);
+        let mut report = create_test_report();
+        manager.sign_report(&mut report).unwrap();
+
+        let cases = [
+            ("report.json", ReportFormat::Json),
+            ("report.CBOR", ReportFormat::Cbor),
+        ];
+        for (name, format) in cases {
+            let path = dir.path().join(name);
+            let path = path.to_str().unwrap();
+            assert_eq!(ReportFormat::from_path(path).unwrap(), format);
+
+            let digest = manager.export_with_digest(&report, path).unwrap();
+            let written = fs::read(path).unwrap();
+            assert_eq!(digest, hex::encode(Sha256::digest(&written)));
+            assert_eq!(written, format.encode(&report).unwrap());
+
+            let loaded = ReportManager::load_auto(path).unwrap();
+            assert!(ReportManager::verify_report(&loaded, &public_key).unwrap());
+        }
+
+        let path = dir.path().join("report.bin");
+        let path = path.to_str().unwrap();
+        assert!(matches!(
+            manager.export_auto(&report, path),
+            Err(AuditorError::Config(_))
+        ));
+        assert!(!Path::new(path).exists());
+    }
+
+    #[test]
+    fn test_from_json_rejects_out_of_range_sliver_index() {
+        let mut report = create_test_report();
+        report.encoding_n = Some(70_001);
+        report.challenge_results[0].challenge.sliver_index = 70_000;
+        let json = serde_json::to_string(&report).unwrap();
+        assert_eq!(
+            ReportManager::from_json(&json).unwrap().challenge_results[0].challenge.sliver_index,
+            70_000
+        );
+
+        report.challenge_results[0].challenge.sliver_index = 70_001;
+        let json = serde_json::to_string(&report).unwrap();
+        assert!(matches!(
+            ReportManager::from_json(&json),
+            Err(AuditorError::InvalidSliver(_))
+        ));
+    }
+
+    #[test]
+    fn test_from_json_accepts_report_without_encoding_n() {
+        let mut value = serde_json::to_value(create_test_report()).unwrap();
+        value.as_object_mut().unwrap().remove("encoding_n");
+        value["challenge_results"][0]["challenge"]["sliver_index"] = serde_json::json!(5);
+
+        let report = ReportManager::from_json(&value.to_string()).unwrap();
+        assert_eq!(report.encoding_n, None);
+        assert_eq!(report.challenge_results[0].challenge.sliver_index, 5);
+    }
+
+    fn fixture_path(name: &str) -> String {
+        format!("{}/tests/fixtures/{}", env!("CARGO_MANIFEST_DIR"), name)
+    }
+
+    #[test]
+    fn test_load_v1_fixture_fills_missing_fields() {
+        let report = ReportManager::load_json(&fixture_path("report_v1.json")).unwrap();
+        assert_eq!(report.schema_version, 1);
+        assert_eq!(report.blob_id, "0xarchived_v1_blob");
+        assert!(report.challenge_results.is_empty());
+        assert!(report.pqc_signature.is_empty());
+        assert_eq!(report.pqc_algorithm, 0);
+        assert_eq!(report.failure_reason, None);
+
+        // 遷移不改寫記錄的版本：重新導出的 v1 報告仍不帶版本字段
+        let value = serde_json::to_value(&report).unwrap();
+        assert!(value.get("schema_version").is_none());
+    }
+
+    #[test]
+    fn test_load_v2_fixture() {
+        let report = ReportManager::load_json(&fixture_path("report_v2.json")).unwrap();
+        assert_eq!(report.schema_version, 2);
+        assert_eq!(report.encoding_n, Some(1000));
+        // 以 u16 範圍寫入的 sliver 索引按 u64 讀入，無需遷移
+        assert_eq!(report.challenge_results[0].challenge.sliver_index, 3u64);
+        assert_eq!(
+            report.challenge_results[0].node_url.as_deref(),
+            Some("http://node-a:9000")
+        );
+        assert_eq!(report.response_stats.unwrap().bytes_transferred, 4096);
+
+        // 版本本身在簽名範圍內
+        let mut downgraded = report.clone();
+        downgraded.schema_version = 1;
+        assert_ne!(downgraded.signing_bytes(), report.signing_bytes());
+    }
+
+    #[test]
+    fn test_unsupported_report_version_is_rejected() {
+        let json = fs::read_to_string(fixture_path("report_v2.json")).unwrap();
+        let mut value: Value = serde_json::from_str(&json).unwrap();
+        value["schema_version"] = json!(REPORT_SCHEMA_VERSION + 1);
+
+        let expected = REPORT_SCHEMA_VERSION + 1;
+        match ReportManager::from_json(&value.to_string()) {
+            Err(AuditorError::UnsupportedReportVersion { found, supported }) => {
+                assert_eq!(found, expected);
+                assert_eq!(supported, REPORT_SCHEMA_VERSION);
+            }
+            other => panic!("Expected UnsupportedReportVersion, got {:?}", other),
+        }
+
+        // CBOR 與舊版信封同樣檢查版本
+        let mut report = create_test_report();
+        report.schema_version = expected;
+        assert!(matches!(
+            ReportManager::from_cbor(&ReportManager::to_cbor(&report).unwrap()),
+            Err(AuditorError::UnsupportedReportVersion { .. })
+        ));
+        let legacy = json!({
+            "audit_data": {},
+            "signature": "",
+            "algorithm": "Dilithium3",
+            "auditor_public_key": "",
+            "report_timestamp": 0,
+            "schema_version": expected,
+        });
+        assert!(matches!(
+            ReportManager::from_json(&legacy.to_string()),
+            Err(AuditorError::UnsupportedReportVersion { .. })
+        ));
+
+        value["schema_version"] = json!("2");
+        assert!(matches!(
+            ReportManager::from_json(&value.to_string()),
+            Err(AuditorError::Serialization(_))
+        ));
+    }
+
+    #[test]
+    fn test_export_writes_current_schema_version() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("report.json");
+        let path = path.to_str().unwrap();
+
+        let mut signer = Dilithium3Signer::new();
+        signer.generate_keypair().unwrap();
+        let public_key = signer.public_key().to_vec();
+        let manager = ReportManager::new(signer);
+
+        // 簽名將遷移而來的 v1 報告升級為當前版本
+        let mut report = ReportManager::load_json(&fixture_path("report_v1.json")).unwrap();
+        manager.sign_report(&mut report).unwrap();
+        assert_eq!(report.schema_version, REPORT_SCHEMA_VERSION);
+
+        manager.export_json(&report, path).unwrap();
+        let written: Value = serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap();
+        assert_eq!(written["schema_version"], json!(REPORT_SCHEMA_VERSION));
+
+        let loaded = ReportManager::load_json(path).unwrap();
+        assert!(ReportManager::verify_report(&loaded, &public_key).unwrap());
+    }
+
+    #[test]
+    fn test_signature_is_bound_to_recorded_version() {
+        let mut signer = Dilithium3Signer::new();
+        signer.generate_keypair().unwrap();
+        let public_key = signer.public_key().to_vec();
+
+        // 引入版本字段之前按規範字節簽名的 v1 報告，經遷移後仍可驗證
+        let mut v1 = create_test_report();
+        v1.schema_version = 1;
+        v1.pqc_signature = signer.sign(&v1.signing_bytes()).unwrap();
+        v1.pqc_algorithm = 3;
+        let mut value = serde_json::to_value(&v1).unwrap();
+        assert!(value.get("schema_version").is_none());
+        value.as_object_mut().unwrap().remove("failure_reason");
+        let loaded = ReportManager::from_json(&value.to_string()).unwrap();
+        assert_eq!(loaded.schema_version, 1);
+        assert!(ReportManager::verify_report(&loaded, &public_key).unwrap());
+
+        // 改寫記錄的版本會使簽名失效
+        let manager = ReportManager::new(signer);
+        let mut v2 = create_test_report();
+        manager.sign_report(&mut v2).unwrap();
+        let mut value = serde_json::to_value(&v2).unwrap();
+        value.as_object_mut().unwrap().remove("schema_version");
+        let downgraded = ReportManager::from_json(&value.to_string()).unwrap();
+        assert_eq!(downgraded.schema_version, 1);
+        assert!(!ReportManager::verify_report(&downgraded, &public_key).unwrap());
+
+        // 記錄了版本的報告不接受舊版 JSON 簽名
+        let mut legacy = create_test_report();
+        legacy.pqc_signature = manager
+            .signer()
+            .sign(&ReportManager::legacy_json_payload(&legacy).unwrap())
+            .unwrap();
+        legacy.pqc_algorithm = 3;
+        assert!(!ReportManager::verify_report(&legacy, &public_key).unwrap());
+    }
+
     #[test]
     fn test_load_nonexistent_file() {
         let result = ReportManager::load_json("/nonexistent/path/report.json");
@@ -620,6 +1760,7 @@ mod tests {
 
         // 創建多個挑戰結果的報告
         let mut report = AuditReport {
+            schema_version: REPORT_SCHEMA_VERSION,
             blob_id: "0xcomplex_blob".to_string(),
             blob_object_id: "0xcomplex_object".to_string(),
             auditor: "0xtest_auditor".to_string(),
@@ -632,11 +1773,17 @@ mod tests {
                         shard_id: 0,
                         challenge_type: 1,
                         timestamp: 1700000000,
+                        symbol_index: None,
                     },
                     verified: true,
                     merkle_proof_valid: true,
                     response_hash: vec![1, 2, 3, 4],
                     failure_reason: None,
+                    node_url: None,
+                    node_signature_valid: None,
+                    response_time_ms: 0,
+                    sliver_size_bytes: 0,
+                    attempts: 0,
                 },
                 ChallengeResult {
                     challenge: AuditChallenge {
@@ -644,21 +1791,46 @@ mod tests {
                         shard_id: 1,
                         challenge_type: 1,
                         timestamp: 1700000001,
+                        symbol_index: None,
                     },
                     verified: false,
                     merkle_proof_valid: false,
                     response_hash: vec![5, 6, 7, 8],
                     failure_reason: Some("Merkle proof invalid".to_string()),
+                    node_url: None,
+                    node_signature_valid: None,
+                    response_time_ms: 0,
+                    sliver_size_bytes: 0,
+                    attempts: 0,
                 },
             ],
+            encoding_n: None,
             total_challenges: 2,
             successful_verifications: 1,
             failed_verifications: 1,
             integrity_hash: vec![0u8; 32],
             pqc_signature: vec![],
             pqc_algorithm: 0,
+            pqc_prehashed: false,
             is_valid: false,
             failure_reason: Some("1 challenge failed".to_string()),
+            capture_digest: None,
+            deduplicated_from: None,
+            producer: None,
+            challenge_reveal: None,
+            challenge_seed_source: None,
+            challenge_seed: None,
+            chunk_filter: None,
+            cosignatures: vec![],
+            integrity: None,
+            legacy_envelope: None,
+            blinding_key_id: None,
+            audit_method: None,
+            response_stats: None,
+            blob_status: None,
+            audit_id: None,
+            shard_coverage: vec![],
+            auditor_reputation_at_audit: None,
         };
 
         // 簽名
@@ -668,4 +1840,50 @@ mod tests {
         let is_valid = ReportManager::verify_report(&report, &public_key).unwrap();
         assert!(is_valid);
     }
+
+    /// 返回固定登記公鑰的模擬鏈上查詢
+    struct FixedKeyLookup(crate::onchain_keys::RegisteredKey);
+
+    #[async_trait::async_trait]
+    impl PublicKeyLookup for FixedKeyLookup {
+        async fn auditor_public_key(
+            &self,
+            _auditor: &MoveId,
+        ) -> Result<crate::onchain_keys::RegisteredKey> {
+            Ok(self.0.clone())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_verify_report_onchain() {
+        use crate::onchain_keys::RegisteredKey;
+
+        let mut signer = Dilithium3Signer::new();
+        signer.generate_keypair().unwrap();
+        let public_key = signer.public_key().to_vec();
+        let manager = ReportManager::new(signer);
+
+        let mut report = create_test_report();
+        report.auditor = "0xa11ce".to_string();
+        manager.sign_report(&mut report).unwrap();
+
+        let registered = FixedKeyLookup(RegisteredKey::from_public_key(public_key).unwrap());
+        assert!(ReportManager::verify_report_onchain(&report, &registered)
+            .await
+            .unwrap());
+
+        // 鏈上登記的是 Falcon-512 公鑰：拒絕，而不是用錯誤的驗證器嘗試
+        let falcon = RegisteredKey::from_public_key(vec![0; 897]).unwrap();
+        let err = ReportManager::verify_report_onchain(&report, &FixedKeyLookup(falcon))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, AuditorError::PqcSignature(_)), "{}", err);
+        assert!(err.to_string().contains("mismatch"), "{}", err);
+
+        // 報告的審計員不是合法地址
+        report.auditor = "0xtest_auditor".to_string();
+        assert!(ReportManager::verify_report_onchain(&report, &registered)
+            .await
+            .is_err());
+    }
 }
@@ -0,0 +1,649 @@
+//! 已簽名報告的本地存檔與保留策略
+//!
+//! 守護進程長期運行會產生成千上萬份報告。啟用 `[report_archive]` 時，每份簽名報告
+//! 寫入存檔目錄，並在索引中追加一行，按 `blob_id` 查詢時無需遍歷目錄：
+//!
+//! ```text
+//! <dir>/
+//! ├── index.jsonl                         每份報告一行（見 ArchiveEntry）
+//! ├── 2024-11-16/
+//! │   ├── <blob_id>_1731715200.json
+//! │   └── <blob_id>_1731718800.json.gz    超過 compress_after_days 後壓縮
+//! └── 2024-11-17/ …
+//! ```
+//!
+//! 日期與時間戳取自報告的 `timestamp`（UTC）。保留策略由後台任務定期執行
+//! （[`ReportArchive::spawn_retention`]），按以下順序處理索引中的報告：
+//!
+//! 1. 文件已不存在的條目從索引中移除
+//! 2. 超過 `max_age_days` 的報告刪除
+//! 3. 超過 `compress_after_days` 的報告壓縮為 `.json.gz`
+//! 4. 總大小超過 `max_total_bytes` 時從最舊的報告開始刪除
+//!
+//! 索引按 `[rotation.outputs.report_index]` 輪轉（見 [`crate::rotating_writer`]），
+//! 查詢與保留策略讀取仍保留的輪轉文件；保留策略重寫索引時合併為單個文件。
+//! 被輪轉保留策略刪除的索引行對應的報告不再受管理，因此索引的保留策略應寬於存檔本身。
+//!
+//! 只有索引中的文件受保留策略管理。壓縮後的報告仍可直接以
+//! [`ReportManager::load_json`](crate::report::ReportManager::load_json) 加載（見 [`crate::ingest`]）。
+
+use crate::error::Result;
+use crate::metrics::Metrics;
+use crate::report::ReportFormat;
+use crate::rotating_writer::{self, RotatingWriter, RotationConfig, RotationStats};
+use crate::types::AuditReport;
+use flate2::{write::GzEncoder, Compression};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tracing::{debug, error, info};
+
+/// 索引文件名
+pub const INDEX_FILE: &str = "index.jsonl";
+
+/// `[rotation.outputs]` 中索引文件的名稱
+pub const ROTATION_OUTPUT: &str = "report_index";
+
+/// 默認的保留策略執行間隔（秒）
+pub const DEFAULT_PRUNE_INTERVAL_SECS: u64 = 3600;
+
+const SECS_PER_DAY: u64 = 86_400;
+
+/// 報告存檔設置
+///
+/// ```toml
+/// [report_archive]
+/// enabled = true
+/// dir = "./reports"
+/// compress_after_days = 7
+/// max_age_days = 365
+/// max_total_bytes = 10737418240
+/// prune_interval_secs = 3600
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ReportArchiveConfig {
+    /// 是否存檔守護進程生成的報告
+    pub enabled: bool,
+
+    /// 存檔目錄
+    pub dir: String,
+
+    /// 超過此天數的報告壓縮為 `.json.gz`（未設置時不壓縮）
+    pub compress_after_days: Option<u64>,
+
+    /// 超過此天數的報告被刪除（未設置時不按時間清理）
+    pub max_age_days: Option<u64>,
+
+    /// 存檔總大小上限（bytes，未設置時不限制）
+    pub max_total_bytes: Option<u64>,
+
+    /// 保留策略的執行間隔（秒）
+    pub prune_interval_secs: u64,
+}
+
+impl Default for ReportArchiveConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            dir: "./reports".to_string(),
+            compress_after_days: None,
+            max_age_days: None,
+            max_total_bytes: None,
+            prune_interval_secs: DEFAULT_PRUNE_INTERVAL_SECS,
+        }
+    }
+}
+
+impl ReportArchiveConfig {
+    /// 是否配置了任何保留策略
+    pub fn has_retention(&self) -> bool {
+        self.compress_after_days.is_some()
+            || self.max_age_days.is_some()
+            || self.max_total_bytes.is_some()
+    }
+}
+
+/// 索引中的一條記錄
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArchiveEntry {
+    /// 報告的 Blob ID
+    pub blob_id: String,
+
+    /// 報告時間戳（Unix 秒）
+    pub timestamp: u64,
+
+    /// 報告是否通過審計
+    pub is_valid: bool,
+
+    /// 報告文件（索引中相對於存檔目錄，[`ReportArchive::find`] 返回完整路徑）
+    pub path: PathBuf,
+
+    /// 文件大小（bytes，壓縮後更新）
+    pub bytes: u64,
+}
+
+/// 一次保留策略執行的結果
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RetentionSummary {
+    /// 壓縮的報告數
+    pub compressed: usize,
+
+    /// 刪除的報告數（按時間或總大小）
+    pub pruned: usize,
+
+    /// 文件已不存在、從索引中移除的條目數
+    pub missing: usize,
+
+    /// 剩餘的報告數
+    pub remaining: usize,
+
+    /// 剩餘報告的總大小（bytes）
+    pub total_bytes: u64,
+}
+
+/// 報告存檔
+#[derive(Debug)]
+pub struct ReportArchive {
+    config: ReportArchiveConfig,
+    dir: PathBuf,
+    /// 索引文件
+    index: RotatingWriter,
+    /// 串行化索引的追加與重寫
+    index_lock: Mutex<()>,
+}
+
+impl ReportArchive {
+    /// 打開（必要時創建）存檔目錄（索引不輪轉）
+    pub fn open(config: ReportArchiveConfig) -> Result<Self> {
+        Self::open_with_rotation(config, RotationConfig::disabled())
+    }
+
+    /// 打開（必要時創建）存檔目錄，索引按 `rotation` 輪轉
+    pub fn open_with_rotation(
+        config: ReportArchiveConfig,
+        rotation: RotationConfig,
+    ) -> Result<Self> {
+        let dir = PathBuf::from(&config.dir);
+        fs::create_dir_all(&dir)?;
+        let index = RotatingWriter::open(dir.join(INDEX_FILE), rotation)?;
+        Ok(Self {
+            config,
+            dir,
+            index,
+            index_lock: Mutex::new(()),
+        })
+    }
+
+    /// 存檔目錄
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// 索引文件的輪轉計數器
+    pub fn rotation_stats(&self) -> Arc<RotationStats> {
+        self.index.stats()
+    }
+
+    /// 寫入一份報告並追加索引，返回報告文件路徑
+    ///
+    /// 同一 Blob 在同一秒內有多份報告時，文件名追加序號而不覆蓋。
+    ///
+    /// # 錯誤
+    /// - 序列化失敗: 返回 `Serialization` 錯誤
+    /// - 寫入失敗: 返回 `Io` 錯誤
+    pub fn store(&self, report: &AuditReport) -> Result<PathBuf> {
+        let bytes = ReportFormat::Json.encode(report)?;
+        let date = chrono::DateTime::from_timestamp(report.timestamp as i64, 0)
+            .unwrap_or_default()
+            .format("%Y-%m-%d")
+            .to_string();
+        fs::create_dir_all(self.dir.join(&date))?;
+
+        let _guard = self.index_lock.lock().unwrap();
+        let stem = format!("{}_{}", file_safe(&report.blob_id), report.timestamp);
+        let (relative, mut file) = create_unique(&self.dir, &date, &stem)?;
+        file.write_all(&bytes)?;
+        file.sync_all()?;
+
+        let entry = ArchiveEntry {
+            blob_id: report.blob_id.clone(),
+            timestamp: report.timestamp,
+            is_valid: report.is_valid,
+            path: relative.clone(),
+            bytes: bytes.len() as u64,
+        };
+        self.index.append_line(&serde_json::to_string(&entry)?)?;
+
+        let path = self.dir.join(relative);
+        debug!(
+            "Archived report of {} to {}",
+            report.blob_id,
+            path.display()
+        );
+        Ok(path)
+    }
+
+    /// 按 Blob ID 查詢存檔的報告（按時間戳升序，路徑已補全為完整路徑）
+    ///
+    /// # 錯誤
+    /// - 索引無法讀取或解析: 返回 `Io` / `Serialization` 錯誤
+    pub fn find(&self, blob_id: &str) -> Result<Vec<ArchiveEntry>> {
+        let _guard = self.index_lock.lock().unwrap();
+        let mut entries: Vec<ArchiveEntry> = self
+            .read_index()?
+            .into_iter()
+            .filter(|entry| entry.blob_id == blob_id)
+            .map(|mut entry| {
+                entry.path = self.dir.join(&entry.path);
+                entry
+            })
+            .collect();
+        entries.sort_by_key(|entry| entry.timestamp);
+        Ok(entries)
+    }
+
+    /// 按配置執行一次保留策略（壓縮與清理），有變化時重寫索引
+    pub fn enforce_retention(&self) -> Result<RetentionSummary> {
+        self.enforce_retention_at(chrono::Utc::now().timestamp() as u64)
+    }
+
+    fn enforce_retention_at(&self, now: u64) -> Result<RetentionSummary> {
+        let _guard = self.index_lock.lock().unwrap();
+        let mut entries = self.read_index()?;
+        let mut summary = RetentionSummary::default();
+        let before = entries.len();
+        let age = |entry: &ArchiveEntry| now.saturating_sub(entry.timestamp);
+
+        entries.retain(|entry| self.dir.join(&entry.path).exists());
+        summary.missing = before - entries.len();
+
+        if let Some(days) = self.config.max_age_days {
+            let max_age = days.saturating_mul(SECS_PER_DAY);
+            entries.retain(|entry| {
+                if age(entry) <= max_age {
+                    return true;
+                }
+                self.remove(entry);
+                summary.pruned += 1;
+                false
+            });
+        }
+
+        if let Some(days) = self.config.compress_after_days {
+            let compress_after = days.saturating_mul(SECS_PER_DAY);
+            for entry in entries.iter_mut() {
+                if age(entry) < compress_after || !is_uncompressed(&entry.path) {
+                    continue;
+                }
+                let path = self.dir.join(&entry.path);
+                match compress_file(&path) {
+                    Ok(gz_path) => {
+                        entry.bytes = fs::metadata(&gz_path)?.len();
+                        entry.path = gz_name(&entry.path);
+                        summary.compressed += 1;
+                    }
+                    Err(e) => error!("Failed to compress report {}: {}", path.display(), e),
+                }
+            }
+        }
+
+        if let Some(max_total) = self.config.max_total_bytes {
+            entries.sort_by_key(|entry| entry.timestamp);
+            let mut total: u64 = entries.iter().map(|entry| entry.bytes).sum();
+            let mut oldest = 0;
+            while total > max_total && oldest < entries.len() {
+                self.remove(&entries[oldest]);
+                total -= entries[oldest].bytes;
+                oldest += 1;
+            }
+            entries.drain(..oldest);
+            summary.pruned += oldest;
+        }
+
+        if summary.missing + summary.pruned + summary.compressed > 0 {
+            self.write_index(&entries)?;
+            self.remove_empty_dirs();
+        }
+
+        summary.remaining = entries.len();
+        summary.total_bytes = entries.iter().map(|entry| entry.bytes).sum();
+        Ok(summary)
+    }
+
+    /// 在後台每隔 `prune_interval_secs` 執行一次保留策略（首次立即執行）
+    ///
+    /// 返回的任務句柄可用於停止（`abort`）
+    pub fn spawn_retention(self: &Arc<Self>, metrics: Option<Arc<Metrics>>) -> JoinHandle<()> {
+        let archive = Arc::clone(self);
+        let period = Duration::from_secs(self.config.prune_interval_secs.max(1));
+        info!(
+            "Enforcing report archive retention every {}s",
+            period.as_secs()
+        );
+
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(period);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                interval.tick().await;
+                let worker = Arc::clone(&archive);
+                match tokio::task::spawn_blocking(move || worker.enforce_retention()).await {
+                    Ok(Ok(summary)) => {
+                        if summary.pruned + summary.compressed > 0 {
+                            info!(
+                                "Report archive: compressed {}, pruned {}, {} reports ({} bytes) kept",
+                                summary.compressed,
+                                summary.pruned,
+                                summary.remaining,
+                                summary.total_bytes
+                            );
+                        }
+                        if let Some(metrics) = &metrics {
+                            metrics.record_report_archive_size(summary.total_bytes);
+                        }
+                    }
+                    Ok(Err(e)) => error!("Failed to enforce report archive retention: {}", e),
+                    Err(e) => error!("Report archive retention task failed: {}", e),
+                }
+            }
+        })
+    }
+
+    fn read_index(&self) -> Result<Vec<ArchiveEntry>> {
+        let mut entries = Vec::new();
+        for line in rotating_writer::read_to_string(self.index.path())?.lines() {
+            if !line.trim().is_empty() {
+                entries.push(serde_json::from_str(line)?);
+            }
+        }
+        Ok(entries)
+    }
+
+    fn write_index(&self, entries: &[ArchiveEntry]) -> Result<()> {
+        let mut contents = String::new();
+        for entry in entries {
+            contents.push_str(&serde_json::to_string(entry)?);
+            contents.push('\n');
+        }
+        self.index.replace_contents(&contents)?;
+        Ok(())
+    }
+
+    fn remove(&self, entry: &ArchiveEntry) {
+        let path = self.dir.join(&entry.path);
+        if let Err(e) = fs::remove_file(&path) {
+            if e.kind() != io::ErrorKind::NotFound {
+                error!("Failed to remove archived report {}: {}", path.display(), e);
+            }
+        }
+    }
+
+    /// 刪除已清空的日期目錄
+    fn remove_empty_dirs(&self) {
+        let Ok(dirs) = fs::read_dir(&self.dir) else {
+            return;
+        };
+        for dir in dirs.flatten().map(|entry| entry.path()) {
+            if dir.is_dir() && fs::read_dir(&dir).is_ok_and(|mut files| files.next().is_none()) {
+                let _ = fs::remove_dir(&dir);
+            }
+        }
+    }
+}
+
+/// 在 `<dir>/<date>/` 下創建 `<stem>.json`（已存在時為 `<stem>_<n>.json`），返回相對路徑
+fn create_unique(dir: &Path, date: &str, stem: &str) -> io::Result<(PathBuf, File)> {
+    let mut attempt = 0;
+    loop {
+        let name = match attempt {
+            0 => format!("{}.json", stem),
+            n => format!("{}_{}.json", stem, n),
+        };
+        let relative = Path::new(date).join(name);
+        match OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(dir.join(&relative))
+        {
+            Ok(file) => return Ok((relative, file)),
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => attempt += 1,
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// Blob ID 中不能用於文件名的字符替換為 `_`（URL-safe base64 原樣保留）
+fn file_safe(blob_id: &str) -> String {
+    blob_id
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+fn is_uncompressed(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == "json")
+}
+
+fn gz_name(path: &Path) -> PathBuf {
+    PathBuf::from(format!("{}.gz", path.display()))
+}
+
+/// 壓縮文件為 `<file>.gz`（經由 `.gz.tmp` 原子替換），成功後刪除原文件
+fn compress_file(path: &Path) -> io::Result<PathBuf> {
+    let gz_path = gz_name(path);
+    let tmp_path = PathBuf::from(format!("{}.gz.tmp", path.display()));
+
+    {
+        let mut input = File::open(path)?;
+        let output = File::create(&tmp_path)?;
+        let mut encoder = GzEncoder::new(output, Compression::default());
+        io::copy(&mut input, &mut encoder)?;
+        encoder.finish()?.sync_all()?;
+    }
+
+    fs::rename(&tmp_path, &gz_path)?;
+    fs::remove_file(path)?;
+
+    Ok(gz_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::ReportManager;
+    use serde_json::json;
+
+    /// 2024-11-16T00:00:00Z
+    const DAY0: u64 = 1_731_715_200;
+
+    fn report(blob_id: &str, timestamp: u64) -> AuditReport {
+        serde_json::from_value(json!({
+            "blob_id": blob_id,
+            "blob_object_id": "0xb10b",
+            "auditor": "0xa11ce",
+            "timestamp": timestamp,
+            "challenge_epoch": 7,
+            "challenge_results": [],
+            "total_challenges": 10,
+            "successful_verifications": 10,
+            "failed_verifications": 0,
+            "integrity_hash": vec![0xab; 32],
+            "pqc_signature": [1, 2, 3],
+            "pqc_algorithm": 3,
+            "is_valid": true,
+            "failure_reason": null,
+        }))
+        .unwrap()
+    }
+
+    fn open_archive(dir: &Path, config: ReportArchiveConfig) -> ReportArchive {
+        ReportArchive::open(ReportArchiveConfig {
+            enabled: true,
+            dir: dir.display().to_string(),
+            ..config
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_store_names_and_indexes_reports() {
+        let dir = tempfile::tempdir().unwrap();
+        let archive = open_archive(dir.path(), ReportArchiveConfig::default());
+
+        let first = archive.store(&report("blob-a", DAY0 + 60)).unwrap();
+        assert_eq!(
+            first,
+            dir.path()
+                .join("2024-11-16")
+                .join(format!("blob-a_{}.json", DAY0 + 60))
+        );
+        // 同一秒的第二份報告不覆蓋第一份
+        let second = archive.store(&report("blob-a", DAY0 + 60)).unwrap();
+        assert_ne!(first, second);
+        archive
+            .store(&report("blob-b", DAY0 + SECS_PER_DAY))
+            .unwrap();
+
+        let found = archive.find("blob-a").unwrap();
+        assert_eq!(found.len(), 2);
+        assert_eq!(found[0].path, first);
+        assert_eq!(found[1].path, second);
+        assert_eq!(found[0].bytes, fs::metadata(&first).unwrap().len());
+        let loaded = ReportManager::load_json(second.to_str().unwrap()).unwrap();
+        assert_eq!(loaded.blob_id, "blob-a");
+        assert_eq!(loaded.timestamp, DAY0 + 60);
+
+        let found = archive.find("blob-b").unwrap();
+        assert_eq!(found.len(), 1);
+        assert!(found[0].path.starts_with(dir.path().join("2024-11-17")));
+        assert!(archive.find("blob-c").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_prune_by_age() {
+        let dir = tempfile::tempdir().unwrap();
+        let archive = open_archive(
+            dir.path(),
+            ReportArchiveConfig {
+                max_age_days: Some(30),
+                ..Default::default()
+            },
+        );
+        let old = archive.store(&report("blob-a", DAY0)).unwrap();
+        let recent = archive
+            .store(&report("blob-a", DAY0 + 20 * SECS_PER_DAY))
+            .unwrap();
+
+        let summary = archive
+            .enforce_retention_at(DAY0 + 40 * SECS_PER_DAY)
+            .unwrap();
+        assert_eq!(summary.pruned, 1);
+        assert_eq!(summary.remaining, 1);
+        assert!(!old.exists());
+        assert!(recent.exists());
+        // 清空的日期目錄一併刪除
+        assert!(!dir.path().join("2024-11-16").exists());
+
+        let found = archive.find("blob-a").unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].path, recent);
+    }
+
+    #[test]
+    fn test_prune_by_total_size_removes_oldest_first() {
+        let dir = tempfile::tempdir().unwrap();
+        let archive = open_archive(dir.path(), ReportArchiveConfig::default());
+        let paths: Vec<PathBuf> = (0..4)
+            .map(|day| {
+                archive
+                    .store(&report("blob-a", DAY0 + day * SECS_PER_DAY))
+                    .unwrap()
+            })
+            .collect();
+        let size = fs::metadata(&paths[0]).unwrap().len();
+
+        // 上限容得下兩份報告多一點
+        let archive = open_archive(
+            dir.path(),
+            ReportArchiveConfig {
+                max_total_bytes: Some(size * 2 + size / 2),
+                ..Default::default()
+            },
+        );
+        let summary = archive
+            .enforce_retention_at(DAY0 + 4 * SECS_PER_DAY)
+            .unwrap();
+        assert_eq!(summary.pruned, 2);
+        assert_eq!(summary.total_bytes, size * 2);
+        assert!(!paths[0].exists() && !paths[1].exists());
+        assert!(paths[2].exists() && paths[3].exists());
+        assert_eq!(archive.find("blob-a").unwrap().len(), 2);
+
+        // 已在上限內時不再清理
+        let summary = archive
+            .enforce_retention_at(DAY0 + 4 * SECS_PER_DAY)
+            .unwrap();
+        assert_eq!(summary.pruned, 0);
+    }
+
+    #[test]
+    fn test_compressed_reports_load_transparently() {
+        let dir = tempfile::tempdir().unwrap();
+        let archive = open_archive(
+            dir.path(),
+            ReportArchiveConfig {
+                compress_after_days: Some(7),
+                ..Default::default()
+            },
+        );
+        let old = archive.store(&report("blob-a", DAY0)).unwrap();
+        let recent = archive
+            .store(&report("blob-a", DAY0 + 5 * SECS_PER_DAY))
+            .unwrap();
+
+        let summary = archive
+            .enforce_retention_at(DAY0 + 10 * SECS_PER_DAY)
+            .unwrap();
+        assert_eq!(summary.compressed, 1);
+        assert!(!old.exists());
+        assert!(recent.exists());
+
+        let found = archive.find("blob-a").unwrap();
+        assert_eq!(found[0].path, gz_name(&old));
+        assert_eq!(found[0].bytes, fs::metadata(&found[0].path).unwrap().len());
+        assert!(found[0].bytes < fs::metadata(&recent).unwrap().len());
+        let loaded = ReportManager::load_json(found[0].path.to_str().unwrap()).unwrap();
+        assert_eq!(loaded.blob_id, "blob-a");
+        assert_eq!(loaded.timestamp, DAY0);
+        assert_eq!(loaded.integrity_hash, vec![0xab; 32]);
+
+        // 已壓縮的報告不再重複壓縮
+        let summary = archive
+            .enforce_retention_at(DAY0 + 10 * SECS_PER_DAY)
+            .unwrap();
+        assert_eq!(summary.compressed, 0);
+    }
+
+    #[test]
+    fn test_missing_files_are_dropped_from_index() {
+        let dir = tempfile::tempdir().unwrap();
+        let archive = open_archive(dir.path(), ReportArchiveConfig::default());
+        let path = archive.store(&report("blob-a", DAY0)).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        let summary = archive.enforce_retention_at(DAY0).unwrap();
+        assert_eq!(summary.missing, 1);
+        assert!(archive.find("blob-a").unwrap().is_empty());
+    }
+}
@@ -0,0 +1,664 @@
+//! 離線報告包（`.warb`）
+//!
+//! 驗證一份報告需要審計員的公鑰，若報告簽名後密鑰已輪換，還需要輪換鏈；
+//! 這些通常要向審計員另行索取。報告包把驗證所需的內容打包成一個 tar 文件：
+//!
+//! | 成員 | 內容 |
+//! |------|------|
+//! | `manifest.json` | [`BundleManifest`]：格式版本、創建時間、報告摘要信息、各成員的 SHA-256 |
+//! | `report.json` | 已簽名的報告（舊版 `SignedAuditReport` 先經 [`ReportManager::from_json`] 轉換） |
+//! | `public_key.bin` | 打包時審計員的當前 Dilithium3 公鑰 |
+//! | `rotation_log.json` | 密鑰輪換記錄（僅輪換過的密鑰庫；格式同密鑰庫的輪換日誌） |
+//!
+//! [`ReportBundle::verify`] 核對成員摘要、清單與報告一致、報告內部計數一致、
+//! 輪換鏈完整，並用鏈上的密鑰驗證報告簽名。
+//!
+//! # 信任
+//!
+//! 報告包只證明報告由包內的密鑰簽名：**包內的公鑰本身不可信**，
+//! 任何人都能用自己的密鑰簽名並打包一份報告。驗證通過後，仍須通過外部途徑
+//! （鏈上 AuditorRegistry、信任庫 [`crate::trust`] 或與審計員直接核對）確認
+//! [`BundleVerification::key_ids`] 中的某個密鑰屬於該審計員。
+
+use crate::archive;
+use crate::crypto::ct_eq_hex;
+use crate::error::{AuditorError, Result};
+use crate::keystore::{KeyChain, Keystore, RotationRecord, ROTATION_LOG_FILE};
+use crate::report::ReportManager;
+use crate::trust::key_id;
+use crate::types::AuditReport;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io::Read;
+use std::path::Path;
+use tracing::{debug, info};
+
+/// 當前報告包格式版本
+pub const BUNDLE_FORMAT_VERSION: u32 = 1;
+
+/// 報告包的文件擴展名
+pub const BUNDLE_EXTENSION: &str = "warb";
+
+/// 清單成員
+pub const MANIFEST_FILE: &str = "manifest.json";
+
+/// 報告成員
+pub const REPORT_FILE: &str = "report.json";
+
+/// 公鑰成員
+pub const PUBLIC_KEY_FILE: &str = "public_key.bin";
+
+/// 單個成員的大小上限
+const MAX_MEMBER_BYTES: u64 = 16 * 1024 * 1024;
+
+/// 報告包整體的大小上限
+const MAX_BUNDLE_BYTES: u64 = 4 * MAX_MEMBER_BYTES;
+
+/// 報告包提示的信任說明
+pub const TRUST_NOTICE: &str = "The bundle proves the report was signed by the key it carries, \
+not that the key belongs to the auditor; confirm one of the key ids via the on-chain registry \
+or a trust store";
+
+/// 報告包清單（`manifest.json`）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BundleManifest {
+    /// 格式版本（[`BUNDLE_FORMAT_VERSION`]）
+    pub format_version: u32,
+
+    /// 打包時間（Unix 秒）
+    pub created_at: u64,
+
+    /// 報告的 Blob ID
+    pub blob_id: String,
+
+    /// 報告的審計員地址
+    pub auditor: String,
+
+    /// 報告的 PQC 算法
+    pub pqc_algorithm: u8,
+
+    /// 包內公鑰的指紋（[`key_id`]）
+    pub key_id: String,
+
+    /// 其他成員的 SHA-256（十六進制），按文件名
+    pub files: BTreeMap<String, String>,
+}
+
+/// 離線報告包
+#[derive(Debug, Clone)]
+pub struct ReportBundle {
+    manifest: BundleManifest,
+    report: AuditReport,
+    public_key: Vec<u8>,
+    rotations: Vec<RotationRecord>,
+
+    /// 除清單外各成員的原始字節（按文件名）
+    members: BTreeMap<String, Vec<u8>>,
+}
+
+/// 報告包的驗證結果
+#[derive(Debug, Clone, Serialize)]
+pub struct BundleVerification {
+    /// Blob ID
+    pub blob_id: String,
+
+    /// 審計員地址
+    pub auditor: String,
+
+    /// 簽名報告的密鑰代數（從 1 開始）
+    pub generation: usize,
+
+    /// 各代密鑰的指紋，從第一代到打包時的當前密鑰
+    pub key_ids: Vec<String>,
+
+    /// 信任說明（見 [`TRUST_NOTICE`]）
+    pub note: &'static str,
+}
+
+impl BundleVerification {
+    /// 簽名報告的密鑰指紋
+    pub fn signing_key_id(&self) -> &str {
+        &self.key_ids[self.generation - 1]
+    }
+}
+
+impl fmt::Display for BundleVerification {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "VALID  bundled report for blob {}", self.blob_id)?;
+        writeln!(f, "  Auditor:       {}", self.auditor)?;
+        writeln!(
+            f,
+            "  Signed by:     key {} (generation {} of {})",
+            self.signing_key_id(),
+            self.generation,
+            self.key_ids.len()
+        )?;
+        writeln!(f, "  Key chain:     {}", self.key_ids.join(" -> "))?;
+        writeln!(f, "  Note:          {}", self.note)
+    }
+}
+
+impl ReportBundle {
+    /// 用密鑰庫的當前公鑰與輪換鏈打包已簽名的報告
+    ///
+    /// # 錯誤
+    /// - 報告未簽名，或不是由本密鑰庫（任一代）密鑰簽名: 返回 `Bundle` 錯誤
+    /// - 密鑰庫的輪換鏈無法核對: 返回 `Keystore` 錯誤
+    pub fn create(report: &AuditReport, keystore: &Keystore) -> Result<Self> {
+        if report.pqc_signature.is_empty() {
+            return Err(AuditorError::Bundle(format!(
+                "Report for blob {} is not signed",
+                report.blob_id
+            )));
+        }
+
+        let chain = keystore.rotation_chain()?;
+        if ReportManager::verify_report_with_chain(report, &chain)?.is_none() {
+            return Err(AuditorError::Bundle(format!(
+                "Report for blob {} is not signed by any key of keystore {:?}",
+                report.blob_id,
+                keystore.base_path()
+            )));
+        }
+
+        let public_key = chain.current().to_vec();
+        let rotations = chain.rotations().to_vec();
+
+        let mut members = BTreeMap::new();
+        members.insert(REPORT_FILE.to_string(), serde_json::to_vec_pretty(report)?);
+        members.insert(PUBLIC_KEY_FILE.to_string(), public_key.clone());
+        if !rotations.is_empty() {
+            members.insert(
+                ROTATION_LOG_FILE.to_string(),
+                serde_json::to_vec_pretty(&rotations)?,
+            );
+        }
+
+        let manifest = BundleManifest {
+            format_version: BUNDLE_FORMAT_VERSION,
+            created_at: chrono::Utc::now().timestamp() as u64,
+            blob_id: report.blob_id.clone(),
+            auditor: report.auditor.clone(),
+            pqc_algorithm: report.pqc_algorithm,
+            key_id: key_id(&public_key),
+            files: members
+                .iter()
+                .map(|(name, bytes)| (name.clone(), sha256_hex(bytes)))
+                .collect(),
+        };
+
+        Ok(Self {
+            manifest,
+            report: report.clone(),
+            public_key,
+            rotations,
+            members,
+        })
+    }
+
+    /// 讀取報告包
+    ///
+    /// 只解析成員，不核對摘要與簽名（見 [`verify`](Self::verify)）
+    ///
+    /// # 錯誤
+    /// - 文件無法讀取、過大或不是 tar 歸檔: 返回 `Bundle` 錯誤
+    /// - 格式版本不受支持、成員缺失、重複或未知: 返回 `Bundle` 錯誤
+    /// - 報告無法解析: 返回 `Serialization` 錯誤
+    pub fn open(path: &Path) -> Result<Self> {
+        let size = fs::metadata(path)
+            .map_err(|e| AuditorError::Bundle(format!("Failed to read {:?}: {}", path, e)))?
+            .len();
+        if size > MAX_BUNDLE_BYTES {
+            return Err(AuditorError::Bundle(format!(
+                "{:?} is {} bytes, larger than the {} byte limit",
+                path, size, MAX_BUNDLE_BYTES
+            )));
+        }
+
+        let bytes = fs::read(path)
+            .map_err(|e| AuditorError::Bundle(format!("Failed to read {:?}: {}", path, e)))?;
+        let bundle = Self::from_bytes(&bytes)?;
+        debug!(
+            "Opened bundle {:?} for blob {} ({} members)",
+            path,
+            bundle.manifest.blob_id,
+            bundle.members.len() + 1
+        );
+        Ok(bundle)
+    }
+
+    /// 從 tar 字節解析報告包
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let mut manifest = None;
+        let mut members = BTreeMap::new();
+
+        let mut archive = tar::Archive::new(bytes);
+        let entries = archive.entries().map_err(tar_error)?;
+        for entry in entries {
+            let mut entry = entry.map_err(tar_error)?;
+            let name = entry
+                .path()
+                .map_err(tar_error)?
+                .to_string_lossy()
+                .into_owned();
+
+            if !entry.header().entry_type().is_file() {
+                return Err(AuditorError::Bundle(format!(
+                    "Member {} is not a regular file",
+                    name
+                )));
+            }
+            if ![
+                MANIFEST_FILE,
+                REPORT_FILE,
+                PUBLIC_KEY_FILE,
+                ROTATION_LOG_FILE,
+            ]
+            .contains(&name.as_str())
+            {
+                return Err(AuditorError::Bundle(format!("Unknown member {}", name)));
+            }
+            if (name == MANIFEST_FILE && manifest.is_some()) || members.contains_key(&name) {
+                return Err(AuditorError::Bundle(format!("Duplicate member {}", name)));
+            }
+
+            let mut content = Vec::new();
+            (&mut entry)
+                .take(MAX_MEMBER_BYTES + 1)
+                .read_to_end(&mut content)
+                .map_err(tar_error)?;
+            if content.len() as u64 > MAX_MEMBER_BYTES {
+                return Err(AuditorError::Bundle(format!(
+                    "Member {} is larger than the {} byte limit",
+                    name, MAX_MEMBER_BYTES
+                )));
+            }
+
+            if name == MANIFEST_FILE {
+                manifest = Some(content);
+            } else {
+                members.insert(name, content);
+            }
+        }
+
+        let manifest: BundleManifest =
+            serde_json::from_slice(&manifest.ok_or_else(|| {
+                AuditorError::Bundle(format!("Missing member {}", MANIFEST_FILE))
+            })?)
+            .map_err(|e| AuditorError::Bundle(format!("Invalid {}: {}", MANIFEST_FILE, e)))?;
+        if manifest.format_version != BUNDLE_FORMAT_VERSION {
+            return Err(AuditorError::Bundle(format!(
+                "Unsupported bundle format version {} (expected {})",
+                manifest.format_version, BUNDLE_FORMAT_VERSION
+            )));
+        }
+
+        let member = |name: &str| {
+            members
+                .get(name)
+                .ok_or_else(|| AuditorError::Bundle(format!("Missing member {}", name)))
+        };
+        let report_json = std::str::from_utf8(member(REPORT_FILE)?)
+            .map_err(|e| AuditorError::Bundle(format!("{} is not UTF-8: {}", REPORT_FILE, e)))?;
+        let report = ReportManager::from_json(report_json)?;
+        let public_key = member(PUBLIC_KEY_FILE)?.clone();
+        let rotations = match members.get(ROTATION_LOG_FILE) {
+            Some(log) => serde_json::from_slice(log).map_err(|e| {
+                AuditorError::Bundle(format!("Invalid {}: {}", ROTATION_LOG_FILE, e))
+            })?,
+            None => Vec::new(),
+        };
+
+        Ok(Self {
+            manifest,
+            report,
+            public_key,
+            rotations,
+            members,
+        })
+    }
+
+    /// 編碼為 tar 字節（清單在前，其他成員按文件名排序）
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        let manifest = serde_json::to_vec_pretty(&self.manifest)?;
+        let mut builder = tar::Builder::new(Vec::new());
+
+        let members = std::iter::once((MANIFEST_FILE, &manifest)).chain(
+            self.members
+                .iter()
+                .map(|(name, bytes)| (name.as_str(), bytes)),
+        );
+        for (name, bytes) in members {
+            let mut header = tar::Header::new_ustar();
+            header.set_entry_type(tar::EntryType::Regular);
+            header.set_size(bytes.len() as u64);
+            header.set_mode(0o644);
+            header.set_mtime(self.manifest.created_at);
+            builder
+                .append_data(&mut header, name, bytes.as_slice())
+                .map_err(tar_error)?;
+        }
+
+        builder.into_inner().map_err(tar_error)
+    }
+
+    /// 寫入報告包（臨時文件 + 重命名）
+    pub fn write(&self, path: &Path) -> Result<()> {
+        let mut tmp_path = path.as_os_str().to_owned();
+        tmp_path.push(".tmp");
+        fs::write(&tmp_path, self.to_bytes()?)?;
+        fs::rename(&tmp_path, path)?;
+
+        info!(
+            "Wrote bundle for blob {} to {:?} (key {})",
+            self.manifest.blob_id, path, self.manifest.key_id
+        );
+        Ok(())
+    }
+
+    /// 離線驗證報告包
+    ///
+    /// 核對成員摘要、清單與報告一致、報告內部計數，重建輪換鏈並用鏈上密鑰驗證報告簽名。
+    /// 通過只說明報告由包內的密鑰簽名，密鑰歸屬仍須另行確認（見模塊文檔）
+    ///
+    /// # 錯誤
+    /// - 任一檢查不通過: 返回 `Bundle` 錯誤
+    pub fn verify(&self) -> Result<BundleVerification> {
+        let listed: Vec<&String> = self.manifest.files.keys().collect();
+        let present: Vec<&String> = self.members.keys().collect();
+        if listed != present {
+            return Err(AuditorError::Bundle(format!(
+                "Manifest lists members {:?} but the bundle contains {:?}",
+                listed, present
+            )));
+        }
+        for (name, bytes) in &self.members {
+            if !ct_eq_hex(&self.manifest.files[name], &sha256_hex(bytes))? {
+                return Err(AuditorError::Bundle(format!(
+                    "Member {} does not match its manifest digest",
+                    name
+                )));
+            }
+        }
+
+        let report = &self.report;
+        if self.manifest.blob_id != report.blob_id
+            || self.manifest.auditor != report.auditor
+            || self.manifest.pqc_algorithm != report.pqc_algorithm
+        {
+            return Err(AuditorError::Bundle(format!(
+                "Manifest describes blob {} by {} but the report is for blob {} by {}",
+                self.manifest.blob_id, self.manifest.auditor, report.blob_id, report.auditor
+            )));
+        }
+        if self.manifest.key_id != key_id(&self.public_key) {
+            return Err(AuditorError::Bundle(format!(
+                "Manifest names key {} but the bundle carries key {}",
+                self.manifest.key_id,
+                key_id(&self.public_key)
+            )));
+        }
+        archive::check_consistency(report)
+            .map_err(|e| AuditorError::Bundle(format!("Inconsistent report: {}", e)))?;
+
+        let chain = KeyChain::from_records(self.rotations.clone(), self.public_key.clone())
+            .map_err(|e| AuditorError::Bundle(format!("Invalid rotation chain: {}", e)))?;
+        let generation =
+            ReportManager::verify_report_with_chain(report, &chain)?.ok_or_else(|| {
+                AuditorError::Bundle(format!(
+                    "Report signature for blob {} does not verify with the bundled keys",
+                    report.blob_id
+                ))
+            })?;
+
+        Ok(BundleVerification {
+            blob_id: report.blob_id.clone(),
+            auditor: report.auditor.clone(),
+            generation,
+            key_ids: chain.keys().iter().map(|key| key_id(key)).collect(),
+            note: TRUST_NOTICE,
+        })
+    }
+
+    /// 清單
+    pub fn manifest(&self) -> &BundleManifest {
+        &self.manifest
+    }
+
+    /// 包內的報告
+    pub fn report(&self) -> &AuditReport {
+        &self.report
+    }
+
+    /// 包內的公鑰（打包時的當前密鑰）
+    pub fn public_key(&self) -> &[u8] {
+        &self.public_key
+    }
+
+    /// 包內的輪換記錄
+    pub fn rotations(&self) -> &[RotationRecord] {
+        &self.rotations
+    }
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    hex::encode(Sha256::digest(bytes))
+}
+
+fn tar_error(e: std::io::Error) -> AuditorError {
+    AuditorError::Bundle(format!("Malformed bundle archive: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audit_report::PqcAlgorithm;
+    use pqc_signer::{Dilithium3Signer, Signer};
+    use std::path::PathBuf;
+
+    fn temp_dir() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("report_bundle_{}", rand::random::<u32>()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn signed_report(signer: &dyn Signer) -> AuditReport {
+        let mut report: AuditReport = serde_json::from_value(serde_json::json!({
+            "blob_id": "blob-bundle",
+            "blob_object_id": "0xobject",
+            "auditor": "0xauditor",
+            "timestamp": 1_700_000_000u64,
+            "challenge_epoch": 7,
+            "challenge_results": [],
+            "total_challenges": 10,
+            "successful_verifications": 10,
+            "failed_verifications": 0,
+            "integrity_hash": vec![0u8; 32],
+            "pqc_signature": [],
+            "pqc_algorithm": 0,
+            "is_valid": true,
+            "failure_reason": null,
+        }))
+        .unwrap();
+        ReportManager::sign_report_with(signer, &mut report).unwrap();
+        report
+    }
+
+    /// 替換成員（可選同時更新清單摘要），再經 tar 編碼重新解析
+    fn replace_member(
+        bundle: &ReportBundle,
+        name: &str,
+        bytes: Vec<u8>,
+        update_manifest: bool,
+    ) -> ReportBundle {
+        let mut bundle = bundle.clone();
+        if update_manifest {
+            bundle
+                .manifest
+                .files
+                .insert(name.to_string(), sha256_hex(&bytes));
+        }
+        bundle.members.insert(name.to_string(), bytes);
+        ReportBundle::from_bytes(&bundle.to_bytes().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_create_write_open_verify() {
+        let dir = temp_dir();
+        let keystore =
+            Keystore::generate_and_save(&dir.join("keys"), PqcAlgorithm::default()).unwrap();
+        let report = signed_report(keystore.signer());
+
+        let bundle = ReportBundle::create(&report, &keystore).unwrap();
+        let path = dir.join("report.warb");
+        bundle.write(&path).unwrap();
+
+        let opened = ReportBundle::open(&path).unwrap();
+        assert_eq!(opened.manifest(), bundle.manifest());
+        assert_eq!(opened.report().signing_bytes(), report.signing_bytes());
+        assert_eq!(opened.report().pqc_signature, report.pqc_signature);
+        assert_eq!(opened.public_key(), keystore.public_key_bytes());
+        assert!(opened.rotations().is_empty());
+        assert_eq!(
+            opened.manifest().files.keys().collect::<Vec<_>>(),
+            [PUBLIC_KEY_FILE, REPORT_FILE]
+        );
+
+        let verification = opened.verify().unwrap();
+        assert_eq!(verification.generation, 1);
+        assert_eq!(
+            verification.signing_key_id(),
+            key_id(&keystore.public_key_bytes())
+        );
+        assert!(verification.to_string().contains(TRUST_NOTICE));
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_bundle_carries_rotation_chain_for_retired_keys() {
+        let dir = temp_dir();
+        let first = Keystore::generate_and_save(&dir, PqcAlgorithm::default()).unwrap();
+        let report = signed_report(first.signer());
+        Keystore::rotate(&dir).unwrap();
+        let current = Keystore::rotate(&dir).unwrap();
+
+        let bundle = ReportBundle::create(&report, &current).unwrap();
+        let bundle = ReportBundle::from_bytes(&bundle.to_bytes().unwrap()).unwrap();
+        assert_eq!(bundle.rotations().len(), 2);
+
+        let verification = bundle.verify().unwrap();
+        assert_eq!(verification.generation, 1);
+        assert_eq!(verification.key_ids.len(), 3);
+        assert_eq!(
+            verification.signing_key_id(),
+            key_id(&first.public_key_bytes())
+        );
+
+        // 去掉輪換日誌：當前密鑰不能驗證第一代密鑰的簽名
+        let mut truncated = bundle.clone();
+        truncated.members.remove(ROTATION_LOG_FILE);
+        truncated.manifest.files.remove(ROTATION_LOG_FILE);
+        let truncated = ReportBundle::from_bytes(&truncated.to_bytes().unwrap()).unwrap();
+        assert!(truncated.verify().is_err());
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_tampered_bundles_fail_verification() {
+        let dir = temp_dir();
+        let keystore = Keystore::generate_and_save(&dir, PqcAlgorithm::default()).unwrap();
+        let bundle = ReportBundle::create(&signed_report(keystore.signer()), &keystore).unwrap();
+
+        let mut tampered = bundle.report().clone();
+        tampered.is_valid = false;
+        tampered.failure_reason = Some("forged".to_string());
+        let tampered = serde_json::to_vec(&tampered).unwrap();
+
+        // 成員與清單摘要不符
+        let err = replace_member(&bundle, REPORT_FILE, tampered.clone(), false)
+            .verify()
+            .unwrap_err();
+        assert!(err.to_string().contains("manifest digest"));
+
+        // 清單一併更新：簽名不再覆蓋報告
+        let err = replace_member(&bundle, REPORT_FILE, tampered, true)
+            .verify()
+            .unwrap_err();
+        assert!(err.to_string().contains("does not verify"));
+
+        // 換成他人的公鑰
+        let mut outsider = Dilithium3Signer::new();
+        outsider.generate_keypair().unwrap();
+        let mut swapped = replace_member(
+            &bundle,
+            PUBLIC_KEY_FILE,
+            outsider.public_key().to_vec(),
+            true,
+        );
+        assert!(swapped.verify().is_err());
+        swapped.manifest.key_id = key_id(outsider.public_key());
+        assert!(swapped
+            .verify()
+            .unwrap_err()
+            .to_string()
+            .contains("does not verify"));
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_create_requires_report_signed_by_keystore() {
+        let dir = temp_dir();
+        let keystore = Keystore::generate_and_save(&dir, PqcAlgorithm::default()).unwrap();
+
+        let mut unsigned = signed_report(keystore.signer());
+        unsigned.pqc_signature.clear();
+        assert!(matches!(
+            ReportBundle::create(&unsigned, &keystore),
+            Err(AuditorError::Bundle(_))
+        ));
+
+        let mut outsider = Dilithium3Signer::new();
+        outsider.generate_keypair().unwrap();
+        assert!(matches!(
+            ReportBundle::create(&signed_report(&outsider), &keystore),
+            Err(AuditorError::Bundle(_))
+        ));
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_from_bytes_rejects_malformed_archives() {
+        let dir = temp_dir();
+        let keystore = Keystore::generate_and_save(&dir, PqcAlgorithm::default()).unwrap();
+        let bundle = ReportBundle::create(&signed_report(keystore.signer()), &keystore).unwrap();
+
+        let mut unknown = bundle.clone();
+        unknown.members.insert("extra.bin".to_string(), vec![1]);
+        assert!(ReportBundle::from_bytes(&unknown.to_bytes().unwrap())
+            .unwrap_err()
+            .to_string()
+            .contains("Unknown member"));
+
+        let mut missing = bundle.clone();
+        missing.members.remove(PUBLIC_KEY_FILE);
+        assert!(ReportBundle::from_bytes(&missing.to_bytes().unwrap()).is_err());
+
+        let mut newer = bundle.clone();
+        newer.manifest.format_version = BUNDLE_FORMAT_VERSION + 1;
+        assert!(ReportBundle::from_bytes(&newer.to_bytes().unwrap())
+            .unwrap_err()
+            .to_string()
+            .contains("Unsupported bundle format version"));
+
+        assert!(ReportBundle::from_bytes(b"not a tar archive").is_err());
+
+        fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -0,0 +1,772 @@
+//! 資源守衛模塊
+//!
+//! 在開始下載大型 blob 之前檢查主機資源，避免一次審計填滿磁盤或耗盡內存。
+//!
+//! # 檢查內容
+//!
+//! 1. **磁盤空間**：所有受監控目錄（內容緩存、斷點續傳、HTTP 捕獲、歸檔與下載臨時目錄）的可用空間
+//!    必須大於預期 blob 大小加上配置的餘量
+//! 2. **內存**：可用內存必須大於下載緩衝區需求加上配置的餘量
+//!
+//! # 策略
+//!
+//! - [`ResourcePolicy::Refuse`]：任何不足都拒絕審計
+//! - [`ResourcePolicy::Degrade`]：磁盤不足時停用緩存，內存不足時降低並發；
+//!   連緩衝區本身都放不下時仍然拒絕
+//!
+//! 降級決策由審計方執行：[`crate::integrity::IntegrityVerifier`] 在 `disable_caching` 時
+//! 不寫內容緩存與斷點續傳檢查點；`max_concurrency` 限制共享同一守衛的後續審計
+//! （批量審計與守護進程都經由 [`ResourceGuard::acquire_slot`] 取得名額），
+//! 資源恢復充足後解除限制
+//! - [`ResourcePolicy::WarnOnly`]：僅記錄警告，照常執行
+//!
+//! 檢查結果以 [`ResourceDecision`] 返回，調用方應將其寫入審計報告。
+//!
+//! # 可測試性
+//!
+//! 實際的磁盤/內存查詢通過 [`ResourceProbe`] trait 注入，
+//! 測試可以使用模擬探針來模擬小配額的文件系統。
+
+use crate::error::{AuditorError, Result};
+use crate::types::AuditorConfig;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use tokio::sync::Notify;
+use tracing::{debug, info, warn};
+
+/// 默認磁盤餘量：1 GiB
+pub const DEFAULT_DISK_HEADROOM_BYTES: u64 = 1024 * 1024 * 1024;
+
+/// 默認內存餘量：256 MiB
+pub const DEFAULT_MEMORY_HEADROOM_BYTES: u64 = 256 * 1024 * 1024;
+
+/// 資源探針
+///
+/// 查詢主機當前的可用資源。返回 `None` 表示該平台無法查詢，對應的檢查將被跳過。
+pub trait ResourceProbe: Send + Sync {
+    /// 查詢指定路徑所在文件系統的可用字節數
+    fn available_disk_bytes(&self, path: &Path) -> Option<u64>;
+
+    /// 查詢系統可用內存字節數
+    fn available_memory_bytes(&self) -> Option<u64>;
+}
+
+/// 基於操作系統接口的資源探針
+///
+/// - 磁盤：Unix 上使用 `statvfs`
+/// - 內存：Linux 上讀取 `/proc/meminfo` 的 `MemAvailable`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemProbe;
+
+impl ResourceProbe for SystemProbe {
+    #[cfg(unix)]
+    fn available_disk_bytes(&self, path: &Path) -> Option<u64> {
+        use std::ffi::CString;
+        use std::os::unix::ffi::OsStrExt;
+
+        // 目錄可能尚未創建，向上查找第一個存在的祖先目錄
+        let existing = path.ancestors().find(|p| p.exists())?;
+        let c_path = CString::new(existing.as_os_str().as_bytes()).ok()?;
+
+        let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+        // SAFETY: c_path 是有效的 NUL 結尾字符串，stat 指向有效的可寫內存
+        let ret = unsafe { libc::statvfs(c_path.as_ptr(), &mut stat) };
+        if ret != 0 {
+            return None;
+        }
+
+        Some((stat.f_bavail as u64).saturating_mul(stat.f_frsize as u64))
+    }
+
+    #[cfg(not(unix))]
+    fn available_disk_bytes(&self, _path: &Path) -> Option<u64> {
+        None
+    }
+
+    fn available_memory_bytes(&self) -> Option<u64> {
+        let meminfo = std::fs::read_to_string("/proc/meminfo").ok()?;
+        parse_mem_available(&meminfo)
+    }
+}
+
+/// 從 `/proc/meminfo` 內容中解析 `MemAvailable`（kB）
+fn parse_mem_available(meminfo: &str) -> Option<u64> {
+    meminfo
+        .lines()
+        .find_map(|line| line.strip_prefix("MemAvailable:"))
+        .and_then(|rest| rest.split_whitespace().next())
+        .and_then(|kb| kb.parse::<u64>().ok())
+        .map(|kb| kb * 1024)
+}
+
+/// 資源不足時的處理策略
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ResourcePolicy {
+    /// 拒絕審計
+    Refuse,
+    /// 降級執行（停用緩存、降低並發）
+    #[default]
+    Degrade,
+    /// 僅記錄警告
+    WarnOnly,
+}
+
+/// 資源守衛配置
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResourceGuardConfig {
+    /// 資源不足時的策略
+    pub policy: ResourcePolicy,
+
+    /// 磁盤餘量（在預期 blob 大小之外需要保留的字節數）
+    pub disk_headroom_bytes: u64,
+
+    /// 內存餘量（在緩衝區需求之外需要保留的字節數）
+    pub memory_headroom_bytes: u64,
+}
+
+impl Default for ResourceGuardConfig {
+    fn default() -> Self {
+        Self {
+            policy: ResourcePolicy::default(),
+            disk_headroom_bytes: DEFAULT_DISK_HEADROOM_BYTES,
+            memory_headroom_bytes: DEFAULT_MEMORY_HEADROOM_BYTES,
+        }
+    }
+}
+
+impl From<&AuditorConfig> for ResourceGuardConfig {
+    fn from(config: &AuditorConfig) -> Self {
+        Self {
+            policy: config.resource_policy,
+            disk_headroom_bytes: config.disk_headroom_bytes,
+            memory_headroom_bytes: config.memory_headroom_bytes,
+        }
+    }
+}
+
+/// 單次審計的資源需求
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ResourceRequirements {
+    /// 每個受監控目錄需要的磁盤空間
+    pub disk_bytes: u64,
+
+    /// 下載/處理所需的內存
+    pub memory_bytes: u64,
+}
+
+impl ResourceRequirements {
+    /// 整個 blob 下載到內存中的需求（當前 `IntegrityVerifier` 的行為）
+    pub fn in_memory_download(blob_size: u64) -> Self {
+        Self {
+            disk_bytes: blob_size,
+            memory_bytes: blob_size,
+        }
+    }
+
+    /// 流式處理的需求：磁盤按 blob 大小計算，內存只需緩衝區大小
+    pub fn streaming(blob_size: u64, buffer_bytes: u64) -> Self {
+        Self {
+            disk_bytes: blob_size,
+            memory_bytes: buffer_bytes,
+        }
+    }
+}
+
+/// 資源檢查的處理結果
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ResourceAction {
+    /// 資源充足，正常執行
+    Proceed,
+    /// 資源不足，降級執行
+    Degraded,
+    /// 資源不足，拒絕執行
+    Refused,
+}
+
+/// 資源檢查決策（寫入審計報告）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResourceDecision {
+    /// 處理結果
+    pub action: ResourceAction,
+
+    /// 是否停用磁盤緩存（緩存、斷點續傳等寫盤功能）
+    pub disable_caching: bool,
+
+    /// 降級後的最大並發數（`None` 表示不限制）
+    pub max_concurrency: Option<usize>,
+
+    /// 最緊張目錄的磁盤餘量（可用 - 需求，負數表示不足；無法查詢時為 `None`）
+    pub disk_headroom_bytes: Option<i64>,
+
+    /// 內存餘量（可用 - 需求，負數表示不足；無法查詢時為 `None`）
+    pub memory_headroom_bytes: Option<i64>,
+
+    /// 決策原因
+    pub reasons: Vec<String>,
+}
+
+impl ResourceDecision {
+    /// 是否允許繼續審計
+    pub fn allows_audit(&self) -> bool {
+        self.action != ResourceAction::Refused
+    }
+}
+
+/// 當前資源餘量快照（供監控指標使用）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResourceSnapshot {
+    /// 每個受監控目錄的可用磁盤字節數
+    pub disk_available_bytes: Vec<(PathBuf, Option<u64>)>,
+
+    /// 系統可用內存字節數
+    pub memory_available_bytes: Option<u64>,
+}
+
+/// 降級決策設置的並發上限與進行中的審計數
+struct ConcurrencyGate {
+    /// 並發上限（`usize::MAX` 表示不限制）
+    limit: AtomicUsize,
+    /// 持有名額的審計數
+    active: AtomicUsize,
+    /// 名額釋放或上限放寬時喚醒等待者
+    released: Notify,
+}
+
+impl ConcurrencyGate {
+    fn new() -> Self {
+        Self {
+            limit: AtomicUsize::new(usize::MAX),
+            active: AtomicUsize::new(0),
+            released: Notify::new(),
+        }
+    }
+
+    /// 進行中的審計數低於上限時佔用一個名額
+    fn try_acquire(&self) -> bool {
+        let limit = self.limit.load(Ordering::SeqCst);
+        self.active
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |active| {
+                (active < limit).then_some(active + 1)
+            })
+            .is_ok()
+    }
+
+    /// 設置並發上限（`None` 解除限制）
+    fn set_limit(&self, limit: Option<usize>) {
+        let limit = limit.map_or(usize::MAX, |limit| limit.max(1));
+        let previous = self.limit.swap(limit, Ordering::SeqCst);
+        if limit != previous {
+            if limit == usize::MAX {
+                info!("Resource guard: concurrency limit lifted");
+            } else {
+                warn!("Resource guard: limiting concurrent audits to {}", limit);
+            }
+        }
+        if limit > previous {
+            self.released.notify_waiters();
+        }
+    }
+}
+
+/// 審計名額（丟棄時釋放，見 [`ResourceGuard::acquire_slot`]）
+#[must_use = "the slot is released when dropped"]
+pub struct AuditSlot {
+    gate: Arc<ConcurrencyGate>,
+}
+
+impl Drop for AuditSlot {
+    fn drop(&mut self) {
+        self.gate.active.fetch_sub(1, Ordering::SeqCst);
+        self.gate.released.notify_waiters();
+    }
+}
+
+/// 資源守衛
+///
+/// 可在 pipeline 和 daemon 調度器之間共享（內部使用 `Arc`，克隆共享同一個並發上限）
+#[derive(Clone)]
+pub struct ResourceGuard {
+    config: ResourceGuardConfig,
+    watched_dirs: Vec<PathBuf>,
+    probe: Arc<dyn ResourceProbe>,
+    gate: Arc<ConcurrencyGate>,
+}
+
+impl std::fmt::Debug for ResourceGuard {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ResourceGuard")
+            .field("config", &self.config)
+            .field("watched_dirs", &self.watched_dirs)
+            .finish()
+    }
+}
+
+impl ResourceGuard {
+    /// 使用系統探針創建資源守衛
+    pub fn new(config: ResourceGuardConfig) -> Self {
+        Self::with_probe(config, Arc::new(SystemProbe))
+    }
+
+    /// 使用自定義探針創建資源守衛（用於測試）
+    pub fn with_probe(config: ResourceGuardConfig, probe: Arc<dyn ResourceProbe>) -> Self {
+        Self {
+            config,
+            watched_dirs: Vec::new(),
+            probe,
+            gate: Arc::new(ConcurrencyGate::new()),
+        }
+    }
+
+    /// 按配置創建資源守衛，並監控所有已啟用的寫盤目錄
+    ///
+    /// 包括斷點續傳目錄、內容緩存文件所在目錄、HTTP 捕獲目錄、報告歸檔目錄與下載臨時目錄
+    pub fn from_config(config: &AuditorConfig) -> Self {
+        let mut guard = Self::new(ResourceGuardConfig::from(config));
+        for dir in watched_dirs_from_config(config) {
+            guard = guard.watch_dir(dir);
+        }
+        guard
+    }
+
+    /// 添加需要檢查磁盤空間的目錄（緩存、斷點續傳、歸檔目錄等；重複的目錄只監控一次）
+    pub fn watch_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        let dir = dir.into();
+        if !self.watched_dirs.contains(&dir) {
+            self.watched_dirs.push(dir);
+        }
+        self
+    }
+
+    /// 獲取配置
+    pub fn config(&self) -> &ResourceGuardConfig {
+        &self.config
+    }
+
+    /// 獲取受監控目錄
+    pub fn watched_dirs(&self) -> &[PathBuf] {
+        &self.watched_dirs
+    }
+
+    /// 降級決策設置的並發上限（`None` 表示不限制）
+    pub fn concurrency_limit(&self) -> Option<usize> {
+        Some(self.gate.limit.load(Ordering::SeqCst)).filter(|&limit| limit != usize::MAX)
+    }
+
+    /// 取得一個審計名額
+    ///
+    /// 最近的降級決策限制了並發時，等待進行中的審計數降到上限以下；名額在返回值丟棄時釋放
+    pub async fn acquire_slot(&self) -> AuditSlot {
+        loop {
+            // 先登記喚醒再檢查，避免檢查與等待之間的釋放被錯過
+            let released = self.gate.released.notified();
+            tokio::pin!(released);
+            released.as_mut().enable();
+            if self.gate.try_acquire() {
+                return AuditSlot {
+                    gate: Arc::clone(&self.gate),
+                };
+            }
+            released.await;
+        }
+    }
+
+    /// 查詢當前資源餘量
+    pub fn snapshot(&self) -> ResourceSnapshot {
+        ResourceSnapshot {
+            disk_available_bytes: self
+                .watched_dirs
+                .iter()
+                .map(|dir| (dir.clone(), self.probe.available_disk_bytes(dir)))
+                .collect(),
+            memory_available_bytes: self.probe.available_memory_bytes(),
+        }
+    }
+
+    /// 評估資源需求，返回決策（不會因資源不足而返回錯誤）
+    pub fn evaluate(&self, requirements: &ResourceRequirements) -> ResourceDecision {
+        let mut reasons = Vec::new();
+
+        // 1. 磁盤檢查：取最緊張的目錄
+        let disk_needed = requirements
+            .disk_bytes
+            .saturating_add(self.config.disk_headroom_bytes);
+        let mut disk_headroom: Option<i64> = None;
+
+        for dir in &self.watched_dirs {
+            let Some(available) = self.probe.available_disk_bytes(dir) else {
+                debug!("Cannot query disk space for {}, skipping", dir.display());
+                continue;
+            };
+
+            let headroom = signed_diff(available, disk_needed);
+            if headroom < 0 {
+                reasons.push(format!(
+                    "insufficient disk space in {}: {} bytes available, {} bytes required",
+                    dir.display(),
+                    available,
+                    disk_needed
+                ));
+            }
+
+            disk_headroom = Some(disk_headroom.map_or(headroom, |h| h.min(headroom)));
+        }
+
+        let disk_short = disk_headroom.is_some_and(|h| h < 0);
+
+        // 2. 內存檢查
+        let memory_needed = requirements
+            .memory_bytes
+            .saturating_add(self.config.memory_headroom_bytes);
+        let memory_available = self.probe.available_memory_bytes();
+        let memory_headroom = memory_available.map(|available| signed_diff(available, memory_needed));
+        let memory_short = memory_headroom.is_some_and(|h| h < 0);
+        // 連緩衝區本身都放不下（不計餘量）
+        let memory_exhausted = memory_available.is_some_and(|a| a < requirements.memory_bytes);
+
+        if memory_short {
+            reasons.push(format!(
+                "insufficient memory: {} bytes available, {} bytes required",
+                memory_available.unwrap_or(0),
+                memory_needed
+            ));
+        }
+
+        // 3. 按策略決定
+        let short = disk_short || memory_short;
+        let (action, disable_caching, max_concurrency) = match self.config.policy {
+            _ if !short => (ResourceAction::Proceed, false, None),
+            ResourcePolicy::WarnOnly => (ResourceAction::Proceed, false, None),
+            ResourcePolicy::Refuse => (ResourceAction::Refused, false, None),
+            ResourcePolicy::Degrade if memory_exhausted => (ResourceAction::Refused, false, None),
+            ResourcePolicy::Degrade => {
+                (ResourceAction::Degraded, disk_short, memory_short.then_some(1))
+            }
+        };
+
+        if short {
+            warn!(
+                "Resource guard ({:?} policy): {:?} - {}",
+                self.config.policy,
+                action,
+                reasons.join("; ")
+            );
+        }
+        // 降級決策限制後續審計的並發，資源恢復充足時解除；拒絕不改變當前上限
+        if action != ResourceAction::Refused {
+            self.gate.set_limit(max_concurrency);
+        }
+
+        ResourceDecision {
+            action,
+            disable_caching,
+            max_concurrency,
+            disk_headroom_bytes: disk_headroom,
+            memory_headroom_bytes: memory_headroom,
+            reasons,
+        }
+    }
+
+    /// 檢查資源需求
+    ///
+    /// # 返回
+    /// - `Ok(ResourceDecision)`: 可以繼續（可能已降級）
+    ///
+    /// # 錯誤
+    /// - `AuditorError::InsufficientResources`: 策略拒絕了本次審計
+    pub fn check(&self, requirements: &ResourceRequirements) -> Result<ResourceDecision> {
+        let decision = self.evaluate(requirements);
+
+        if !decision.allows_audit() {
+            return Err(AuditorError::InsufficientResources(decision.reasons.join("; ")));
+        }
+
+        Ok(decision)
+    }
+}
+
+/// 配置中已啟用的寫盤目錄
+fn watched_dirs_from_config(config: &AuditorConfig) -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+    if let Some(dir) = &config.checkpoint_dir {
+        dirs.push(PathBuf::from(dir));
+    }
+    if let (true, Some(path)) = (config.content_cache.enabled, &config.content_cache.path) {
+        // 緩存是單個文件，監控其所在目錄
+        let parent = Path::new(path)
+            .parent()
+            .filter(|dir| !dir.as_os_str().is_empty());
+        dirs.push(parent.map_or_else(|| PathBuf::from("."), Path::to_path_buf));
+    }
+    if config.capture_http {
+        dirs.push(PathBuf::from(&config.capture_dir));
+    }
+    if config.report_archive.enabled {
+        dirs.push(PathBuf::from(&config.report_archive.dir));
+    }
+    dirs.push(std::env::temp_dir());
+    dirs
+}
+
+/// 計算 `available - needed`，飽和到 i64 範圍
+fn signed_diff(available: u64, needed: u64) -> i64 {
+    if available >= needed {
+        i64::try_from(available - needed).unwrap_or(i64::MAX)
+    } else {
+        i64::try_from(needed - available).map_or(i64::MIN, |d| -d)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    const MB: u64 = 1024 * 1024;
+
+    /// 模擬探針：每個目錄一個配額
+    struct FakeProbe {
+        disk: HashMap<PathBuf, u64>,
+        memory: Option<u64>,
+    }
+
+    impl ResourceProbe for FakeProbe {
+        fn available_disk_bytes(&self, path: &Path) -> Option<u64> {
+            self.disk.get(path).copied()
+        }
+
+        fn available_memory_bytes(&self) -> Option<u64> {
+            self.memory
+        }
+    }
+
+    fn guard(policy: ResourcePolicy, disk: &[(&str, u64)], memory: Option<u64>) -> ResourceGuard {
+        let probe = FakeProbe {
+            disk: disk.iter().map(|(p, b)| (PathBuf::from(p), *b)).collect(),
+            memory,
+        };
+        let config = ResourceGuardConfig {
+            policy,
+            disk_headroom_bytes: 10 * MB,
+            memory_headroom_bytes: 10 * MB,
+        };
+
+        let mut guard = ResourceGuard::with_probe(config, Arc::new(probe));
+        for (dir, _) in disk {
+            guard = guard.watch_dir(dir);
+        }
+        guard
+    }
+
+    #[test]
+    fn test_sufficient_resources_proceed() {
+        let guard = guard(ResourcePolicy::Refuse, &[("/cache", 500 * MB)], Some(500 * MB));
+        let decision = guard.check(&ResourceRequirements::in_memory_download(100 * MB)).unwrap();
+
+        assert_eq!(decision.action, ResourceAction::Proceed);
+        assert!(!decision.disable_caching);
+        assert_eq!(decision.disk_headroom_bytes, Some((390 * MB) as i64));
+        assert_eq!(decision.memory_headroom_bytes, Some((390 * MB) as i64));
+        assert!(decision.reasons.is_empty());
+    }
+
+    #[test]
+    fn test_refuse_policy_rejects_small_disk() {
+        let guard = guard(
+            ResourcePolicy::Refuse,
+            &[("/cache", 500 * MB), ("/resume", 50 * MB)],
+            Some(500 * MB),
+        );
+        let requirements = ResourceRequirements::in_memory_download(100 * MB);
+
+        let decision = guard.evaluate(&requirements);
+        assert_eq!(decision.action, ResourceAction::Refused);
+        assert_eq!(decision.disk_headroom_bytes, Some(-(60 * MB as i64)));
+        assert!(decision.reasons[0].contains("/resume"));
+
+        match guard.check(&requirements) {
+            Err(AuditorError::InsufficientResources(msg)) => assert!(msg.contains("disk")),
+            other => panic!("Expected InsufficientResources, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_degrade_policy_disables_caching() {
+        let guard = guard(ResourcePolicy::Degrade, &[("/cache", 50 * MB)], Some(500 * MB));
+        let decision = guard.check(&ResourceRequirements::in_memory_download(100 * MB)).unwrap();
+
+        assert_eq!(decision.action, ResourceAction::Degraded);
+        assert!(decision.disable_caching);
+        assert_eq!(decision.max_concurrency, None);
+    }
+
+    #[test]
+    fn test_degrade_policy_reduces_concurrency() {
+        // 可用內存足夠放下 blob，但不夠餘量
+        let guard = guard(ResourcePolicy::Degrade, &[("/cache", 500 * MB)], Some(105 * MB));
+        let decision = guard.check(&ResourceRequirements::in_memory_download(100 * MB)).unwrap();
+
+        assert_eq!(decision.action, ResourceAction::Degraded);
+        assert!(!decision.disable_caching);
+        assert_eq!(decision.max_concurrency, Some(1));
+    }
+
+    #[test]
+    fn test_degrade_policy_refuses_when_buffer_does_not_fit() {
+        let guard = guard(ResourcePolicy::Degrade, &[], Some(50 * MB));
+        let result = guard.check(&ResourceRequirements::in_memory_download(100 * MB));
+
+        assert!(matches!(result, Err(AuditorError::InsufficientResources(_))));
+    }
+
+    #[test]
+    fn test_streaming_requirements_need_less_memory() {
+        let guard = guard(ResourcePolicy::Refuse, &[("/cache", 200 * MB)], Some(50 * MB));
+
+        assert!(guard.check(&ResourceRequirements::in_memory_download(100 * MB)).is_err());
+        assert!(guard
+            .check(&ResourceRequirements::streaming(100 * MB, 8 * MB))
+            .is_ok());
+    }
+
+    #[test]
+    fn test_warn_only_policy_always_proceeds() {
+        let guard = guard(ResourcePolicy::WarnOnly, &[("/cache", MB)], Some(MB));
+        let decision = guard.check(&ResourceRequirements::in_memory_download(100 * MB)).unwrap();
+
+        assert_eq!(decision.action, ResourceAction::Proceed);
+        assert_eq!(decision.reasons.len(), 2);
+    }
+
+    #[test]
+    fn test_unknown_resources_are_skipped() {
+        let guard = guard(ResourcePolicy::Refuse, &[], None).watch_dir("/unknown");
+        let decision = guard.check(&ResourceRequirements::in_memory_download(100 * MB)).unwrap();
+
+        assert_eq!(decision.action, ResourceAction::Proceed);
+        assert_eq!(decision.disk_headroom_bytes, None);
+        assert_eq!(decision.memory_headroom_bytes, None);
+    }
+
+    #[test]
+    fn test_snapshot() {
+        let guard = guard(ResourcePolicy::Degrade, &[("/cache", 42)], Some(7));
+        let snapshot = guard.snapshot();
+
+        assert_eq!(snapshot.disk_available_bytes, vec![(PathBuf::from("/cache"), Some(42))]);
+        assert_eq!(snapshot.memory_available_bytes, Some(7));
+    }
+
+    #[tokio::test]
+    async fn test_degraded_decision_limits_concurrency() {
+        let probe = Arc::new(SwitchProbe(AtomicUsize::new(105)));
+        let config = ResourceGuardConfig {
+            policy: ResourcePolicy::Degrade,
+            disk_headroom_bytes: 10 * MB,
+            memory_headroom_bytes: 10 * MB,
+        };
+        let guard = ResourceGuard::with_probe(config, probe.clone());
+        let requirements = ResourceRequirements::in_memory_download(100 * MB);
+
+        let first = guard.acquire_slot().await;
+        let second = guard.acquire_slot().await;
+        assert_eq!(guard.concurrency_limit(), None);
+
+        // 降級到 1：兩個進行中的審計結束前，新的審計等待
+        assert_eq!(guard.check(&requirements).unwrap().max_concurrency, Some(1));
+        assert_eq!(guard.concurrency_limit(), Some(1));
+        let waiting = tokio::spawn({
+            let guard = guard.clone();
+            async move { guard.acquire_slot().await }
+        });
+        drop(first);
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        assert!(!waiting.is_finished());
+
+        drop(second);
+        let third = tokio::time::timeout(std::time::Duration::from_secs(1), waiting)
+            .await
+            .unwrap()
+            .unwrap();
+
+        // 資源恢復充足後解除限制
+        probe.0.store(500, Ordering::SeqCst);
+        let decision = guard.check(&requirements).unwrap();
+        assert_eq!(decision.action, ResourceAction::Proceed);
+        assert_eq!(guard.concurrency_limit(), None);
+        let _fourth = guard.acquire_slot().await;
+        drop(third);
+    }
+
+    /// 可用內存（MB）可在測試中修改的探針
+    struct SwitchProbe(AtomicUsize);
+
+    impl ResourceProbe for SwitchProbe {
+        fn available_disk_bytes(&self, _path: &Path) -> Option<u64> {
+            None
+        }
+
+        fn available_memory_bytes(&self) -> Option<u64> {
+            Some(self.0.load(Ordering::SeqCst) as u64 * MB)
+        }
+    }
+
+    #[test]
+    fn test_from_config_watches_enabled_dirs() {
+        let mut config = AuditorConfig::default();
+        let guard = ResourceGuard::from_config(&config);
+        assert_eq!(guard.watched_dirs(), [std::env::temp_dir()]);
+
+        config.checkpoint_dir = Some("/data/checkpoints".to_string());
+        config.content_cache.enabled = true;
+        config.content_cache.path = Some("/data/cache/content.json".to_string());
+        config.capture_http = true;
+        config.capture_dir = "/data/captures".to_string();
+        config.report_archive.enabled = true;
+        config.report_archive.dir = "/data/checkpoints".to_string();
+
+        let guard = ResourceGuard::from_config(&config);
+        assert_eq!(
+            guard.watched_dirs(),
+            [
+                PathBuf::from("/data/checkpoints"),
+                PathBuf::from("/data/cache"),
+                PathBuf::from("/data/captures"),
+                std::env::temp_dir(),
+            ]
+        );
+
+        // 文件名不含目錄的緩存監控當前目錄
+        config.content_cache.path = Some("content.json".to_string());
+        let guard = ResourceGuard::from_config(&config);
+        assert!(guard.watched_dirs().contains(&PathBuf::from(".")));
+    }
+
+    #[test]
+    fn test_parse_mem_available() {
+        let meminfo = "MemTotal:       16303428 kB\nMemFree:         1234567 kB\nMemAvailable:    8000000 kB\n";
+        assert_eq!(parse_mem_available(meminfo), Some(8_000_000 * 1024));
+        assert_eq!(parse_mem_available("MemTotal: 1 kB\n"), None);
+    }
+
+    #[test]
+    fn test_system_probe_on_temp_dir() {
+        let dir = std::env::temp_dir().join("walrus_resources_test_missing_subdir");
+        // 不存在的目錄會回退到已存在的祖先目錄
+        let available = SystemProbe.available_disk_bytes(&dir);
+
+        if cfg!(unix) {
+            assert!(available.is_some());
+        }
+    }
+
+    #[test]
+    fn test_policy_deserialization() {
+        let policy: ResourcePolicy = serde_json::from_str("\"warn_only\"").unwrap();
+        assert_eq!(policy, ResourcePolicy::WarnOnly);
+        assert_eq!(ResourcePolicy::default(), ResourcePolicy::Degrade);
+    }
+}
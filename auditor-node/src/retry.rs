@@ -96,6 +96,25 @@ pub async fn retry_with_exponential_backoff<F, Fut, T>(
 where
     F: Fn() -> Fut,
     Fut: std::future::Future<Output = Result<T>>,
+{
+    retry_with_exponential_backoff_if(operation_name, config, |_| true, operation).await
+}
+
+/// 使用指數退避策略重試操作，只重試 `should_retry` 判定為臨時性的錯誤
+///
+/// 其餘錯誤（如 4xx 響應）立即返回，不消耗重試次數。
+/// 錯誤類型不限於 `anyhow::Error`，可直接用於返回 `AuditorError` 的操作。
+pub async fn retry_with_exponential_backoff_if<F, Fut, T, E, P>(
+    operation_name: &str,
+    config: &RetryConfig,
+    should_retry: P,
+    operation: F,
+) -> std::result::Result<T, E>
+where
+    F: Fn() -> Fut,
+    Fut: std::future::Future<Output = std::result::Result<T, E>>,
+    E: std::fmt::Display,
+    P: Fn(&E) -> bool,
 {
     let mut attempt = 0;
     let mut delay_ms = config.initial_delay_ms;
@@ -122,6 +141,16 @@ where
                 return Ok(result);
             }
             Err(e) => {
+                if !should_retry(&e) {
+                    warn!(
+                        operation = operation_name,
+                        attempt = attempt,
+                        error = %e,
+                        "Operation failed with non-retryable error"
+                    );
+                    return Err(e);
+                }
+
                 if attempt > config.max_retries {
                     warn!(
                         operation = operation_name,
@@ -286,6 +315,39 @@ mod tests {
         assert_eq!(counter.load(Ordering::SeqCst), 3); // 1 initial + 2 retries
     }
 
+    #[tokio::test]
+    async fn test_retry_if_stops_on_non_retryable_error() {
+        let config = RetryConfig {
+            max_retries: 5,
+            initial_delay_ms: 10,
+            multiplier: 2.0,
+            max_delay_ms: 1000,
+        };
+
+        let counter = Arc::new(AtomicU32::new(0));
+        let counter_clone = counter.clone();
+
+        // 前兩次為臨時錯誤，第三次為永久錯誤
+        let result = retry_with_exponential_backoff_if(
+            "test_op",
+            &config,
+            |e: &String| e.starts_with("503"),
+            || {
+                let counter = counter_clone.clone();
+                async move {
+                    match counter.fetch_add(1, Ordering::SeqCst) {
+                        0 | 1 => Err::<i32, _>("503 Service Unavailable".to_string()),
+                        _ => Err("400 Bad Request".to_string()),
+                    }
+                }
+            },
+        )
+        .await;
+
+        assert_eq!(result.unwrap_err(), "400 Bad Request");
+        assert_eq!(counter.load(Ordering::SeqCst), 3);
+    }
+
     #[test]
     fn test_retry_config_defaults() {
         let config = RetryConfig::default();
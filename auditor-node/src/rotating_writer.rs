@@ -0,0 +1,855 @@
+//! 追加寫入文件的輪轉工具
+//!
+//! 守護進程長時間運行時，結構化事件日誌、索引等追加寫入（append-only）的 JSONL 文件
+//! 會無限增長。[`RotatingWriter`] 為所有此類輸出提供統一的輪轉策略：
+//!
+//! - **大小觸發**: 當前文件超過 `max_bytes` 時輪轉
+//! - **時間觸發**: 當前文件存在超過 `max_age_secs` 時輪轉
+//! - **保留策略**: 只保留最近 `keep_files` 個輪轉文件，並刪除超過 `keep_days` 天的文件
+//! - **可選壓縮**: 輪轉後的文件壓縮為 `.gz`
+//!
+//! # 崩潰安全
+//!
+//! 輪轉只發生在行邊界：未寫完的一行永遠不會被拆分到兩個文件中。
+//! 輪轉步驟為「flush + fsync → rename → 重新打開」，rename 是原子操作，
+//! 任一步驟崩潰後重啟都只會看到完整的舊文件和（可能為空的）新文件。
+//! 壓縮先寫入 `.gz.tmp` 再重命名，殘留的 `.tmp` 文件會在下次清理時刪除。
+//!
+//! # 讀回與重寫
+//!
+//! 審計歷史、內容基準、承諾日誌與報告存檔索引在啟動時重新加載。
+//! [`read_to_string`] 按從舊到新的順序讀取仍保留的輪轉文件（自動解壓 `.gz`）和當前文件；
+//! 被保留策略刪除的行不再加載。需要整體重寫（如清理過期條目）時使用
+//! [`RotatingWriter::replace_contents`]：新內容寫入當前文件後刪除所有輪轉文件，
+//! 崩潰在兩步之間時重啟會讀到重複的行，但不會丟失。
+//!
+//! # 文件命名
+//!
+//! ```text
+//! events.jsonl                          # 當前文件
+//! events.jsonl.20261016T120000.0000     # 輪轉文件（時間戳 + 序號）
+//! events.jsonl.20261015T120000.0000.gz  # 壓縮後的輪轉文件
+//! ```
+//!
+//! # 使用示例
+//!
+//! ```no_run
+//! use auditor_node::rotating_writer::{RotatingWriter, RotationConfig};
+//! use std::io::Write;
+//!
+//! # fn example() -> std::io::Result<()> {
+//! let mut writer = RotatingWriter::open("./logs/events.jsonl", RotationConfig::default())?;
+//! writeln!(writer, "{{\"event\":\"audit_started\"}}")?;
+//! writer.flush()?;
+//! # Ok(())
+//! # }
+//! ```
+
+use chrono::{DateTime, Utc};
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+use tracing::{debug, info, warn};
+
+/// 默認單個文件大小上限：64 MiB
+pub const DEFAULT_MAX_BYTES: u64 = 64 * 1024 * 1024;
+
+/// 默認保留的輪轉文件數
+pub const DEFAULT_KEEP_FILES: usize = 10;
+
+/// 單個輸出的輪轉配置
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RotationConfig {
+    /// 文件大小上限（字節），`None` 表示不按大小輪轉
+    pub max_bytes: Option<u64>,
+
+    /// 文件存在時間上限（秒），`None` 表示不按時間輪轉
+    pub max_age_secs: Option<u64>,
+
+    /// 保留的輪轉文件數，`None` 表示不限
+    pub keep_files: Option<usize>,
+
+    /// 輪轉文件保留天數，`None` 表示不限
+    pub keep_days: Option<u64>,
+
+    /// 是否 gzip 壓縮輪轉後的文件
+    pub compress: bool,
+}
+
+impl Default for RotationConfig {
+    fn default() -> Self {
+        Self {
+            max_bytes: Some(DEFAULT_MAX_BYTES),
+            max_age_secs: None,
+            keep_files: Some(DEFAULT_KEEP_FILES),
+            keep_days: None,
+            compress: false,
+        }
+    }
+}
+
+impl RotationConfig {
+    /// 不輪轉、不清理
+    pub fn disabled() -> Self {
+        Self {
+            max_bytes: None,
+            max_age_secs: None,
+            keep_files: None,
+            keep_days: None,
+            compress: false,
+        }
+    }
+}
+
+/// 所有追加寫入輸出的輪轉設置
+///
+/// ```toml
+/// [rotation.default]
+/// max_bytes = 67108864
+/// keep_files = 10
+///
+/// [rotation.outputs.events]
+/// max_age_secs = 86400
+/// compress = true
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RotationSettings {
+    /// 未單獨配置的輸出使用的默認設置
+    #[serde(default)]
+    pub default: RotationConfig,
+
+    /// 按輸出名稱（如 `events`）覆蓋的設置
+    #[serde(default)]
+    pub outputs: BTreeMap<String, RotationConfig>,
+}
+
+impl RotationSettings {
+    /// 獲取指定輸出的輪轉配置
+    pub fn for_output(&self, name: &str) -> RotationConfig {
+        self.outputs
+            .get(name)
+            .cloned()
+            .unwrap_or_else(|| self.default.clone())
+    }
+}
+
+/// 輪轉計數器（供指標導出）
+#[derive(Debug, Default)]
+pub struct RotationStats {
+    rotated_files: AtomicU64,
+    deleted_files: AtomicU64,
+    compressed_files: AtomicU64,
+}
+
+impl RotationStats {
+    /// 已輪轉的文件數
+    pub fn rotated_files(&self) -> u64 {
+        self.rotated_files.load(Ordering::Relaxed)
+    }
+
+    /// 因保留策略刪除的文件數
+    pub fn deleted_files(&self) -> u64 {
+        self.deleted_files.load(Ordering::Relaxed)
+    }
+
+    /// 已壓縮的文件數
+    pub fn compressed_files(&self) -> u64 {
+        self.compressed_files.load(Ordering::Relaxed)
+    }
+}
+
+/// 時鐘函數（測試中可替換）
+pub type Clock = Arc<dyn Fn() -> SystemTime + Send + Sync>;
+
+/// 寫入器內部狀態
+struct WriterState {
+    file: File,
+    /// 當前文件已寫入字節數
+    written: u64,
+    /// 當前文件的開始時間
+    opened_at: SystemTime,
+    /// 上一個寫入的字節是否為換行（只有在行首才允許輪轉）
+    at_line_start: bool,
+}
+
+/// 支持輪轉的追加寫入器
+///
+/// 實現 `std::io::Write`，可克隆並在多個線程間共享；所有寫入串行化，
+/// 每次 `write` 調用內的完整行不會與其他線程的寫入交錯。
+/// 文件（及其父目錄）在第一次寫入時才創建，只讀使用的存儲不會留下空文件。
+#[derive(Clone)]
+pub struct RotatingWriter {
+    path: PathBuf,
+    config: RotationConfig,
+    clock: Clock,
+    stats: Arc<RotationStats>,
+    /// 第一次寫入前為 `None`
+    state: Arc<Mutex<Option<WriterState>>>,
+}
+
+impl std::fmt::Debug for RotatingWriter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RotatingWriter")
+            .field("path", &self.path)
+            .field("config", &self.config)
+            .finish()
+    }
+}
+
+impl RotatingWriter {
+    /// 以追加模式打開文件（第一次寫入時創建）
+    pub fn open(path: impl AsRef<Path>, config: RotationConfig) -> io::Result<Self> {
+        Self::open_with_clock(path, config, Arc::new(SystemTime::now))
+    }
+
+    /// 使用指定時鐘打開文件
+    pub fn open_with_clock(
+        path: impl AsRef<Path>,
+        config: RotationConfig,
+        clock: Clock,
+    ) -> io::Result<Self> {
+        Ok(Self {
+            path: path.as_ref().to_path_buf(),
+            config,
+            clock,
+            stats: Arc::new(RotationStats::default()),
+            state: Arc::new(Mutex::new(None)),
+        })
+    }
+
+    /// 當前文件路徑
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// 輪轉計數器
+    pub fn stats(&self) -> Arc<RotationStats> {
+        Arc::clone(&self.stats)
+    }
+
+    /// 追加一行（`line` 不含換行符），整行寫入同一個文件
+    pub fn append_line(&self, line: &str) -> io::Result<()> {
+        self.append(format!("{}\n", line).as_bytes())
+    }
+
+    /// 以 `contents` 替換當前文件（臨時文件 + 重命名）並刪除所有輪轉文件
+    pub fn replace_contents(&self, contents: &str) -> io::Result<()> {
+        let mut state = self.lock()?;
+        self.create_parent()?;
+
+        let tmp_path = PathBuf::from(format!("{}.tmp", self.path.display()));
+        {
+            let mut file = File::create(&tmp_path)?;
+            file.write_all(contents.as_bytes())?;
+            file.sync_all()?;
+        }
+        fs::rename(&tmp_path, &self.path)?;
+
+        for rotated in self.rotated_files()? {
+            self.delete(&rotated);
+        }
+
+        *state = Some(Self::open_state(&self.path, (self.clock)())?);
+        Ok(())
+    }
+
+    /// 立即輪轉（無論是否達到閾值；當前行未寫完時推遲到行尾）
+    pub fn rotate_now(&self) -> io::Result<()> {
+        let mut guard = self.lock()?;
+        let state = self.opened(&mut guard)?;
+        if state.at_line_start && state.written > 0 {
+            self.rotate(state)?;
+        }
+        Ok(())
+    }
+
+    /// 列出現有的輪轉文件（按從舊到新排序）
+    pub fn rotated_files(&self) -> io::Result<Vec<PathBuf>> {
+        rotated_files(&self.path)
+    }
+
+    fn open_state(path: &Path, now: SystemTime) -> io::Result<WriterState> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        let metadata = file.metadata()?;
+        let written = metadata.len();
+
+        // 已有內容的文件以創建時間計齡（不支持時退回到當前時間）
+        let opened_at = if written > 0 {
+            metadata.created().unwrap_or(now)
+        } else {
+            now
+        };
+
+        Ok(WriterState {
+            file,
+            written,
+            opened_at,
+            at_line_start: true,
+        })
+    }
+
+    fn lock(&self) -> io::Result<std::sync::MutexGuard<'_, Option<WriterState>>> {
+        self.state
+            .lock()
+            .map_err(|_| io::Error::other("rotating writer poisoned"))
+    }
+
+    /// 已打開的狀態（第一次使用時創建父目錄並打開文件）
+    fn opened<'a>(&self, state: &'a mut Option<WriterState>) -> io::Result<&'a mut WriterState> {
+        if state.is_none() {
+            self.create_parent()?;
+            *state = Some(Self::open_state(&self.path, (self.clock)())?);
+        }
+        Ok(state.as_mut().expect("state opened above"))
+    }
+
+    fn create_parent(&self) -> io::Result<()> {
+        match self.path.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => fs::create_dir_all(parent),
+            _ => Ok(()),
+        }
+    }
+
+    fn should_rotate(&self, state: &WriterState, incoming: usize) -> bool {
+        if !state.at_line_start || state.written == 0 {
+            return false;
+        }
+
+        let size_exceeded = self
+            .config
+            .max_bytes
+            .is_some_and(|max| state.written + incoming as u64 > max);
+
+        let age_exceeded = self.config.max_age_secs.is_some_and(|max_age| {
+            (self.clock)()
+                .duration_since(state.opened_at)
+                .unwrap_or_default()
+                >= Duration::from_secs(max_age)
+        });
+
+        size_exceeded || age_exceeded
+    }
+
+    fn rotate(&self, state: &mut WriterState) -> io::Result<()> {
+        state.file.flush()?;
+        state.file.sync_all()?;
+
+        let now = (self.clock)();
+        let rotated = self.next_rotated_path(now)?;
+        fs::rename(&self.path, &rotated)?;
+
+        *state = Self::open_state(&self.path, now)?;
+        self.stats.rotated_files.fetch_add(1, Ordering::Relaxed);
+        info!("Rotated {} → {}", self.path.display(), rotated.display());
+
+        if self.config.compress {
+            match compress_file(&rotated) {
+                Ok(_) => {
+                    self.stats.compressed_files.fetch_add(1, Ordering::Relaxed);
+                }
+                Err(e) => warn!("Failed to compress {}: {}", rotated.display(), e),
+            }
+        }
+
+        self.prune(now)
+    }
+
+    /// 輪轉文件路徑：同一秒內的序號總是大於現有文件（包括已壓縮的），保證按名稱排序即按時間排序
+    fn next_rotated_path(&self, now: SystemTime) -> io::Result<PathBuf> {
+        let prefix = format!(
+            "{}.{}.",
+            self.file_name(),
+            DateTime::<Utc>::from(now).format("%Y%m%dT%H%M%S")
+        );
+
+        let next_seq = self
+            .sibling_files()?
+            .iter()
+            .filter_map(|p| {
+                let name = p.file_name()?.to_string_lossy().into_owned();
+                name.strip_prefix(&prefix)?.get(..4)?.parse::<u32>().ok()
+            })
+            .max()
+            .map_or(0, |seq| seq + 1);
+
+        Ok(self
+            .path
+            .with_file_name(format!("{}{:04}", prefix, next_seq)))
+    }
+
+    /// 按保留策略刪除舊文件
+    fn prune(&self, now: SystemTime) -> io::Result<()> {
+        // 清理壓縮中斷遺留的臨時文件
+        for temp in self
+            .sibling_files()?
+            .into_iter()
+            .filter(|p| is_temp_file(p))
+        {
+            let _ = fs::remove_file(temp);
+        }
+
+        let mut files = self.rotated_files()?;
+
+        if let Some(keep_days) = self.config.keep_days {
+            let max_age = Duration::from_secs(keep_days * 24 * 60 * 60);
+            files.retain(|file| {
+                let expired = fs::metadata(file)
+                    .and_then(|m| m.modified())
+                    .ok()
+                    .and_then(|modified| now.duration_since(modified).ok())
+                    .is_some_and(|age| age > max_age);
+
+                if expired {
+                    self.delete(file);
+                }
+                !expired
+            });
+        }
+
+        if let Some(keep_files) = self.config.keep_files {
+            let excess = files.len().saturating_sub(keep_files);
+            for file in files.iter().take(excess) {
+                self.delete(file);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn delete(&self, file: &Path) {
+        match fs::remove_file(file) {
+            Ok(()) => {
+                self.stats.deleted_files.fetch_add(1, Ordering::Relaxed);
+                debug!("Deleted rotated file {}", file.display());
+            }
+            Err(e) => warn!("Failed to delete {}: {}", file.display(), e),
+        }
+    }
+
+    fn file_name(&self) -> String {
+        self.path
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default()
+    }
+
+    fn sibling_files(&self) -> io::Result<Vec<PathBuf>> {
+        sibling_files(&self.path)
+    }
+
+    fn append(&self, buf: &[u8]) -> io::Result<()> {
+        let mut guard = self.lock()?;
+        let state = self.opened(&mut guard)?;
+
+        // 按行寫入，只在行首檢查輪轉
+        for segment in buf.split_inclusive(|&b| b == b'\n') {
+            if self.should_rotate(state, segment.len()) {
+                self.rotate(state)?;
+            }
+
+            state.file.write_all(segment)?;
+            state.written += segment.len() as u64;
+            state.at_line_start = segment.ends_with(b"\n");
+        }
+
+        Ok(())
+    }
+}
+
+impl Write for RotatingWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.append(buf)?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self.lock()?.as_mut() {
+            Some(state) => state.file.flush(),
+            None => Ok(()),
+        }
+    }
+}
+
+/// 按從舊到新的順序讀取 `path` 的輪轉文件（`.gz` 自動解壓）與當前文件
+///
+/// 文件或目錄不存在時返回空字符串
+pub fn read_to_string(path: impl AsRef<Path>) -> io::Result<String> {
+    let path = path.as_ref();
+    let mut files = match rotated_files(path) {
+        Ok(files) => files,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(String::new()),
+        Err(e) => return Err(e),
+    };
+    files.push(path.to_path_buf());
+
+    let mut contents = String::new();
+    for file in files {
+        let mut part = String::new();
+        match File::open(&file) {
+            Ok(f) if file.extension().is_some_and(|ext| ext == "gz") => {
+                GzDecoder::new(f).read_to_string(&mut part)?;
+            }
+            Ok(mut f) => {
+                f.read_to_string(&mut part)?;
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => continue,
+            Err(e) => return Err(e),
+        }
+        // 輪轉只發生在行邊界，但仍防止崩潰留下的半行與下一個文件的首行拼接
+        if !part.is_empty() && !part.ends_with('\n') {
+            part.push('\n');
+        }
+        contents.push_str(&part);
+    }
+    Ok(contents)
+}
+
+/// `path` 現有的輪轉文件（按從舊到新排序）
+fn rotated_files(path: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut files: Vec<PathBuf> = sibling_files(path)?
+        .into_iter()
+        .filter(|p| !is_temp_file(p))
+        .collect();
+    files.sort();
+    Ok(files)
+}
+
+/// 同目錄下所有以 `<name>.` 開頭的文件（不含當前文件）
+fn sibling_files(path: &Path) -> io::Result<Vec<PathBuf>> {
+    let prefix = format!(
+        "{}.",
+        path.file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default()
+    );
+    let dir = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+        _ => PathBuf::from("."),
+    };
+
+    let mut files = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        if entry.file_type()?.is_file() && entry.file_name().to_string_lossy().starts_with(&prefix)
+        {
+            files.push(entry.path());
+        }
+    }
+    Ok(files)
+}
+
+/// 壓縮文件為 `<file>.gz`（經由 `.gz.tmp` 原子替換），成功後刪除原文件
+fn compress_file(path: &Path) -> io::Result<PathBuf> {
+    let gz_path = PathBuf::from(format!("{}.gz", path.display()));
+    let tmp_path = PathBuf::from(format!("{}.gz.tmp", path.display()));
+
+    {
+        let mut input = File::open(path)?;
+        let output = File::create(&tmp_path)?;
+        let mut encoder = GzEncoder::new(output, Compression::default());
+        io::copy(&mut input, &mut encoder)?;
+        encoder.finish()?.sync_all()?;
+    }
+
+    fs::rename(&tmp_path, &gz_path)?;
+    fs::remove_file(path)?;
+
+    Ok(gz_path)
+}
+
+fn is_temp_file(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == "tmp")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicU64;
+
+    fn temp_log_path(name: &str) -> PathBuf {
+        std::env::temp_dir()
+            .join(format!("rotating_{}_{}", name, rand::random::<u32>()))
+            .join("events.jsonl")
+    }
+
+    fn read_all_lines(writer: &RotatingWriter) -> Vec<String> {
+        let mut files = writer.rotated_files().unwrap();
+        files.push(writer.path().to_path_buf());
+
+        let mut lines = Vec::new();
+        for file in files {
+            let mut content = String::new();
+            if file.extension().is_some_and(|ext| ext == "gz") {
+                GzDecoder::new(File::open(&file).unwrap())
+                    .read_to_string(&mut content)
+                    .unwrap();
+            } else {
+                content = fs::read_to_string(&file).unwrap();
+            }
+            assert!(
+                content.is_empty() || content.ends_with('\n'),
+                "split line in {:?}",
+                file
+            );
+            lines.extend(content.lines().map(str::to_string));
+        }
+        lines
+    }
+
+    fn cleanup(writer: &RotatingWriter) {
+        fs::remove_dir_all(writer.path().parent().unwrap()).ok();
+    }
+
+    #[test]
+    fn test_size_trigger() {
+        let config = RotationConfig {
+            max_bytes: Some(100),
+            ..RotationConfig::disabled()
+        };
+        let mut writer = RotatingWriter::open(temp_log_path("size"), config).unwrap();
+
+        for i in 0..10 {
+            writeln!(writer, "{{\"seq\":{:02},\"pad\":\"xxxxxxxxxxxx\"}}", i).unwrap();
+        }
+        writer.flush().unwrap();
+
+        // 每行 32 字節，每個文件最多 3 行
+        assert_eq!(writer.stats().rotated_files(), 3);
+        assert_eq!(writer.rotated_files().unwrap().len(), 3);
+        assert!(fs::metadata(writer.path()).unwrap().len() <= 100);
+
+        let lines = read_all_lines(&writer);
+        assert_eq!(lines.len(), 10);
+        assert!(lines[0].contains("\"seq\":00"));
+        assert!(lines[9].contains("\"seq\":09"));
+
+        cleanup(&writer);
+    }
+
+    #[test]
+    fn test_age_trigger() {
+        let now = Arc::new(AtomicU64::new(1_700_000_000));
+        let clock_now = Arc::clone(&now);
+        let clock: Clock = Arc::new(move || {
+            SystemTime::UNIX_EPOCH + Duration::from_secs(clock_now.load(Ordering::SeqCst))
+        });
+
+        let config = RotationConfig {
+            max_age_secs: Some(3600),
+            ..RotationConfig::disabled()
+        };
+        let mut writer =
+            RotatingWriter::open_with_clock(temp_log_path("age"), config, clock).unwrap();
+
+        writeln!(writer, "first").unwrap();
+        now.fetch_add(1800, Ordering::SeqCst);
+        writeln!(writer, "second").unwrap();
+        assert_eq!(writer.stats().rotated_files(), 0);
+
+        now.fetch_add(1800, Ordering::SeqCst);
+        writeln!(writer, "third").unwrap();
+        assert_eq!(writer.stats().rotated_files(), 1);
+
+        assert_eq!(fs::read_to_string(writer.path()).unwrap(), "third\n");
+        assert_eq!(read_all_lines(&writer), vec!["first", "second", "third"]);
+
+        cleanup(&writer);
+    }
+
+    #[test]
+    fn test_partial_line_is_never_split() {
+        let config = RotationConfig {
+            max_bytes: Some(10),
+            ..RotationConfig::disabled()
+        };
+        let mut writer = RotatingWriter::open(temp_log_path("partial"), config).unwrap();
+
+        writeln!(writer, "0123456789").unwrap();
+        // 一行分多次寫入，中途超過閾值也不輪轉
+        writer.write_all(b"abc").unwrap();
+        writer.flush().unwrap();
+        writer.write_all(b"defghijklmnop\n").unwrap();
+
+        assert_eq!(writer.stats().rotated_files(), 1);
+        assert_eq!(
+            fs::read_to_string(writer.path()).unwrap(),
+            "abcdefghijklmnop\n"
+        );
+
+        cleanup(&writer);
+    }
+
+    #[test]
+    fn test_retention_pruning_and_compression() {
+        let config = RotationConfig {
+            max_bytes: Some(1),
+            keep_files: Some(2),
+            compress: true,
+            ..RotationConfig::disabled()
+        };
+        let mut writer = RotatingWriter::open(temp_log_path("retention"), config).unwrap();
+
+        for i in 0..6 {
+            writeln!(writer, "line-{}", i).unwrap();
+        }
+
+        let stats = writer.stats();
+        assert_eq!(stats.rotated_files(), 5);
+        assert_eq!(stats.compressed_files(), 5);
+        assert_eq!(stats.deleted_files(), 3);
+
+        let rotated = writer.rotated_files().unwrap();
+        assert_eq!(rotated.len(), 2);
+        assert!(rotated
+            .iter()
+            .all(|p| p.extension().is_some_and(|ext| ext == "gz")));
+
+        // 只保留最新的兩個輪轉文件 + 當前文件
+        assert_eq!(read_all_lines(&writer), vec!["line-3", "line-4", "line-5"]);
+
+        cleanup(&writer);
+    }
+
+    #[test]
+    fn test_retention_by_age() {
+        let path = temp_log_path("retention_age");
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+
+        // 預先放置一個 10 天前的輪轉文件
+        let stale = path.with_file_name("events.jsonl.20200101T000000.0000");
+        fs::write(&stale, "old\n").unwrap();
+        File::options()
+            .write(true)
+            .open(&stale)
+            .unwrap()
+            .set_modified(SystemTime::now() - Duration::from_secs(10 * 24 * 60 * 60))
+            .unwrap();
+
+        let config = RotationConfig {
+            keep_days: Some(7),
+            ..RotationConfig::disabled()
+        };
+        let mut writer = RotatingWriter::open(&path, config).unwrap();
+        writeln!(writer, "new").unwrap();
+        writer.rotate_now().unwrap();
+
+        assert!(!stale.exists());
+        assert_eq!(writer.stats().deleted_files(), 1);
+        assert_eq!(writer.rotated_files().unwrap().len(), 1);
+
+        cleanup(&writer);
+    }
+
+    #[test]
+    fn test_concurrent_writes_during_rotation() {
+        let config = RotationConfig {
+            max_bytes: Some(512),
+            ..RotationConfig::disabled()
+        };
+        let writer = RotatingWriter::open(temp_log_path("concurrent"), config).unwrap();
+
+        let handles: Vec<_> = (0..8)
+            .map(|thread| {
+                let mut writer = writer.clone();
+                std::thread::spawn(move || {
+                    for i in 0..50 {
+                        let line = format!("{{\"thread\":{},\"i\":{}}}\n", thread, i);
+                        writer.write_all(line.as_bytes()).unwrap();
+                    }
+                })
+            })
+            .collect();
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        assert!(writer.stats().rotated_files() > 0);
+
+        let lines = read_all_lines(&writer);
+        assert_eq!(lines.len(), 400);
+        for line in &lines {
+            let value: serde_json::Value = serde_json::from_str(line).unwrap();
+            assert!(value["thread"].is_u64());
+        }
+
+        cleanup(&writer);
+    }
+
+    #[test]
+    fn test_file_is_created_on_first_write() {
+        let path = temp_log_path("lazy");
+        let writer = RotatingWriter::open(&path, RotationConfig::default()).unwrap();
+        assert!(!path.parent().unwrap().exists());
+
+        writer.append_line("first").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "first\n");
+
+        cleanup(&writer);
+    }
+
+    #[test]
+    fn test_read_to_string_includes_rotated_files() {
+        let config = RotationConfig {
+            max_bytes: Some(1),
+            compress: true,
+            ..RotationConfig::disabled()
+        };
+        let path = temp_log_path("read_back");
+        assert_eq!(read_to_string(&path).unwrap(), "");
+
+        let writer = RotatingWriter::open(&path, config).unwrap();
+        for i in 0..3 {
+            writer.append_line(&format!("line-{}", i)).unwrap();
+        }
+
+        assert_eq!(writer.rotated_files().unwrap().len(), 2);
+        assert_eq!(read_to_string(&path).unwrap(), "line-0\nline-1\nline-2\n");
+
+        cleanup(&writer);
+    }
+
+    #[test]
+    fn test_replace_contents_drops_rotated_files() {
+        let config = RotationConfig {
+            max_bytes: Some(1),
+            ..RotationConfig::disabled()
+        };
+        let writer = RotatingWriter::open(temp_log_path("replace"), config).unwrap();
+        for i in 0..3 {
+            writer.append_line(&format!("line-{}", i)).unwrap();
+        }
+
+        writer.replace_contents("line-1\n").unwrap();
+        assert!(writer.rotated_files().unwrap().is_empty());
+
+        // 替換後繼續追加到新文件
+        writer.append_line("line-3").unwrap();
+        assert_eq!(read_to_string(writer.path()).unwrap(), "line-1\nline-3\n");
+
+        cleanup(&writer);
+    }
+
+    #[test]
+    fn test_settings_per_output() {
+        let mut settings = RotationSettings::default();
+        settings.outputs.insert(
+            "events".to_string(),
+            RotationConfig {
+                compress: true,
+                ..RotationConfig::default()
+            },
+        );
+
+        assert!(settings.for_output("events").compress);
+        assert_eq!(settings.for_output("index"), RotationConfig::default());
+    }
+}
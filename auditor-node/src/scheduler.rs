@@ -0,0 +1,549 @@
+//! 守護進程的逐 Blob 審計調度
+//!
+//! 不啟用調度時，守護進程每個 `audit_interval_secs` 在同一輪審計所有待審計 Blob，
+//! 造成負載尖峰，已知有問題的 Blob 也與健康 Blob 以相同頻率重審。
+//! 調度器為每個 Blob 維護各自的下次審計時間：
+//!
+//! - 新發現的 Blob 在一個審計間隔內隨機分散
+//! - 審計通過後，一個間隔之後再審（±10% 抖動）
+//! - 審計失敗後從 `failed_retry_secs` 開始加倍退避重試（不超過審計間隔），最多
+//!   `max_failed_retries` 次；仍失敗則標記（flagged）並恢復正常頻率，直到下一次通過
+//! - Blob 被所有者刪除時移出調度
+//!
+//! 到期時間保存在 (下次審計時間, Blob ID) 的最小堆中，守護進程循環取出到期項審計。
+//! 狀態在每次變化後原子寫入 JSON 文件；重啟時停機期間已到期的條目重新分散，
+//! 不會同時重審所有 Blob。
+//!
+//! 時間以 tokio 時鐘推進（錨定到創建時的 Unix 時間），測試中可用 `tokio::time::pause` 模擬。
+
+use crate::error::Result;
+use crate::reaudit::AuditOutcome;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BinaryHeap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::time::Instant;
+use tracing::{debug, info, warn};
+
+/// 重新調度時的抖動比例（±10%）
+const RESCHEDULE_JITTER: f64 = 0.1;
+
+/// 調度配置
+///
+/// ```toml
+/// [scheduler]
+/// enabled = true
+/// failed_retry_secs = 300
+/// max_failed_retries = 5
+/// state_path = "./audit_schedule.json"
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SchedulerConfig {
+    /// 是否啟用逐 Blob 調度（啟用時取代 `[reaudit]` 重審策略）
+    pub enabled: bool,
+
+    /// 審計失敗後第一次重試的間隔（秒），之後每次加倍
+    pub failed_retry_secs: u64,
+
+    /// 連續失敗時最多按退避間隔重試幾次，之後標記並恢復正常頻率
+    pub max_failed_retries: u32,
+
+    /// 狀態文件（JSON）
+    pub state_path: String,
+}
+
+impl Default for SchedulerConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            failed_retry_secs: 300,
+            max_failed_retries: 5,
+            state_path: "./audit_schedule.json".to_string(),
+        }
+    }
+}
+
+/// 單個 Blob 的調度狀態
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlobSchedule {
+    /// 下次審計時間（Unix 秒）
+    pub next_audit_at: u64,
+
+    /// 連續失敗次數
+    #[serde(default)]
+    pub failed_attempts: u32,
+
+    /// 重試用盡仍失敗（按正常頻率審計，直到下一次通過）
+    #[serde(default)]
+    pub flagged: bool,
+}
+
+/// 逐 Blob 審計調度器
+pub struct AuditScheduler {
+    config: SchedulerConfig,
+    /// 審計間隔（秒）
+    interval: u64,
+    path: Option<PathBuf>,
+    entries: BTreeMap<String, BlobSchedule>,
+    /// (下次審計時間, Blob ID) 的最小堆；與 `entries` 不一致的項已過時，取出時跳過
+    queue: BinaryHeap<Reverse<(u64, String)>>,
+    /// 創建時的 Unix 時間（秒）與 tokio 時刻
+    started: (u64, Instant),
+}
+
+impl AuditScheduler {
+    /// 僅在內存中保存狀態
+    pub fn in_memory(config: SchedulerConfig, interval: Duration) -> Self {
+        let unix_now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+
+        Self {
+            config,
+            interval: interval.as_secs(),
+            path: None,
+            entries: BTreeMap::new(),
+            queue: BinaryHeap::new(),
+            started: (unix_now, Instant::now()),
+        }
+    }
+
+    /// 打開狀態文件（不存在時從空狀態開始）
+    ///
+    /// 停機期間已到期的 Blob 在一個窗口內重新分散：仍在重試的按其重試間隔，其餘按審計間隔
+    pub fn open(
+        config: SchedulerConfig,
+        interval: Duration,
+        path: impl AsRef<Path>,
+    ) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let entries: BTreeMap<String, BlobSchedule> = if path.exists() {
+            serde_json::from_str(&fs::read_to_string(&path)?)?
+        } else {
+            BTreeMap::new()
+        };
+
+        let mut scheduler = Self {
+            path: Some(path),
+            ..Self::in_memory(config, interval)
+        };
+        let now = scheduler.now();
+        let mut overdue = 0;
+        for (blob_id, mut entry) in entries {
+            if entry.next_audit_at < now {
+                let window = if entry.failed_attempts > 0 && !entry.flagged {
+                    scheduler.retry_delay(entry.failed_attempts)
+                } else {
+                    scheduler.interval
+                };
+                entry.next_audit_at = now + spread(window);
+                overdue += 1;
+            }
+            scheduler.schedule(blob_id, entry);
+        }
+
+        debug!(
+            "Loaded audit schedule for {} blobs ({} overdue, spread out again)",
+            scheduler.entries.len(),
+            overdue
+        );
+        if overdue > 0 {
+            scheduler.save()?;
+        }
+
+        Ok(scheduler)
+    }
+
+    /// 當前時間（Unix 秒，隨 tokio 時鐘推進）
+    pub fn now(&self) -> u64 {
+        self.started.0 + self.started.1.elapsed().as_secs()
+    }
+
+    /// 已調度的 Blob 數
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// 是否沒有調度任何 Blob
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Blob 的調度狀態
+    pub fn get(&self, blob_id: &str) -> Option<&BlobSchedule> {
+        self.entries.get(blob_id)
+    }
+
+    /// 重試用盡仍失敗、已被標記的 Blob
+    pub fn flagged(&self) -> Vec<String> {
+        self.entries
+            .iter()
+            .filter(|(_, entry)| entry.flagged)
+            .map(|(blob_id, _)| blob_id.clone())
+            .collect()
+    }
+
+    /// 加入新發現的 Blob，下次審計時間在一個間隔內隨機分散；已調度的 Blob 保持不變
+    ///
+    /// 返回新加入的數量（有新 Blob 時持久化一次）
+    pub fn add_all(&mut self, blob_ids: impl IntoIterator<Item = String>) -> Result<usize> {
+        let now = self.now();
+        let mut added = 0;
+        for blob_id in blob_ids {
+            if self.entries.contains_key(&blob_id) {
+                continue;
+            }
+
+            let entry = BlobSchedule {
+                next_audit_at: now + spread(self.interval),
+                failed_attempts: 0,
+                flagged: false,
+            };
+            self.schedule(blob_id, entry);
+            added += 1;
+        }
+
+        if added > 0 {
+            debug!(
+                "Scheduled {} new blobs over the next {}s",
+                added, self.interval
+            );
+            self.save()?;
+        }
+        Ok(added)
+    }
+
+    /// 取出所有到期的 Blob（最早到期者優先）
+    ///
+    /// 取出的 Blob 暫時順延 `failed_retry_secs`：審計出錯而沒有記錄結果時按此時間再試
+    pub fn due(&mut self) -> Vec<String> {
+        let now = self.now();
+        let mut due = Vec::new();
+        while self.queue.peek().is_some_and(|Reverse((at, _))| *at <= now) {
+            let Some(Reverse((at, blob_id))) = self.queue.pop() else {
+                break;
+            };
+            if !self.is_current(&blob_id, at) {
+                continue;
+            }
+
+            let retry_at = now + self.config.failed_retry_secs;
+            if let Some(entry) = self.entries.get_mut(&blob_id) {
+                entry.next_audit_at = retry_at;
+            }
+            self.queue.push(Reverse((retry_at, blob_id.clone())));
+            due.push(blob_id);
+        }
+
+        self.drop_stale();
+        due
+    }
+
+    /// 距下一個 Blob 到期的時間（沒有調度時為 `None`）
+    pub fn next_due_in(&self) -> Option<Duration> {
+        let now = self.now();
+        self.queue
+            .peek()
+            .map(|Reverse((at, _))| Duration::from_secs(at.saturating_sub(now)))
+    }
+
+    /// 記錄審計結果並安排下次審計
+    ///
+    /// - 通過：清除失敗次數與標記，一個間隔後再審
+    /// - 失敗：`max_failed_retries` 次以內按退避間隔重試，用盡後標記並恢復正常頻率
+    /// - 已刪除：移出調度
+    pub fn record(&mut self, blob_id: &str, outcome: AuditOutcome) -> Result<()> {
+        let now = self.now();
+        let mut entry = self.entries.get(blob_id).cloned().unwrap_or(BlobSchedule {
+            next_audit_at: now,
+            failed_attempts: 0,
+            flagged: false,
+        });
+
+        match outcome {
+            AuditOutcome::Deleted => {
+                debug!("Blob {} deleted; removed from the audit schedule", blob_id);
+                self.entries.remove(blob_id);
+                self.drop_stale();
+                return self.save();
+            }
+            AuditOutcome::Pass => {
+                if entry.failed_attempts > 0 {
+                    info!(
+                        "Blob {} passed after {} failed audits; back to the normal cadence",
+                        blob_id, entry.failed_attempts
+                    );
+                }
+                entry.failed_attempts = 0;
+                entry.flagged = false;
+                entry.next_audit_at = now + jittered(self.interval);
+            }
+            AuditOutcome::Fail => {
+                entry.failed_attempts = entry.failed_attempts.saturating_add(1);
+                if entry.failed_attempts <= self.config.max_failed_retries {
+                    let delay = jittered(self.retry_delay(entry.failed_attempts));
+                    info!(
+                        "Blob {} failed (retry {}/{}); re-audit in {}s",
+                        blob_id, entry.failed_attempts, self.config.max_failed_retries, delay
+                    );
+                    entry.next_audit_at = now + delay;
+                } else {
+                    if !entry.flagged {
+                        warn!(
+                            "🚩 Blob {} still failing after {} retries; flagged, back to the normal cadence",
+                            blob_id, self.config.max_failed_retries
+                        );
+                    }
+                    entry.flagged = true;
+                    entry.next_audit_at = now + jittered(self.interval);
+                }
+            }
+        }
+
+        self.schedule(blob_id.to_string(), entry);
+        self.save()
+    }
+
+    /// 第 `failed_attempts` 次失敗後的重試間隔（加倍退避，不超過審計間隔）
+    fn retry_delay(&self, failed_attempts: u32) -> u64 {
+        let factor = 2u64.saturating_pow(failed_attempts.saturating_sub(1));
+        self.config
+            .failed_retry_secs
+            .saturating_mul(factor)
+            .min(self.interval.max(self.config.failed_retry_secs))
+    }
+
+    /// 更新 Blob 的調度狀態並入堆
+    fn schedule(&mut self, blob_id: String, entry: BlobSchedule) {
+        self.queue
+            .push(Reverse((entry.next_audit_at, blob_id.clone())));
+        self.entries.insert(blob_id, entry);
+        self.drop_stale();
+    }
+
+    /// 堆中的項是否與 Blob 當前的調度一致
+    fn is_current(&self, blob_id: &str, at: u64) -> bool {
+        self.entries
+            .get(blob_id)
+            .is_some_and(|entry| entry.next_audit_at == at)
+    }
+
+    /// 丟棄堆頂的過時項，使堆頂總是有效的下一次到期
+    fn drop_stale(&mut self) {
+        while let Some(Reverse((at, blob_id))) = self.queue.peek() {
+            if self.is_current(blob_id, *at) {
+                break;
+            }
+            self.queue.pop();
+        }
+    }
+
+    /// 原子寫入狀態文件
+    fn save(&self) -> Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+
+        if let Some(parent) = path.parent() {
+            if !parent.as_os_str().is_empty() {
+                fs::create_dir_all(parent)?;
+            }
+        }
+
+        let tmp_path = path.with_extension("json.tmp");
+        fs::write(&tmp_path, serde_json::to_vec_pretty(&self.entries)?)?;
+        fs::rename(&tmp_path, path)?;
+        Ok(())
+    }
+}
+
+/// `[0, window)` 內的隨機偏移
+fn spread(window: u64) -> u64 {
+    if window == 0 {
+        return 0;
+    }
+    rand::thread_rng().gen_range(0..window)
+}
+
+/// 加 ±[`RESCHEDULE_JITTER`] 的隨機抖動
+fn jittered(secs: u64) -> u64 {
+    let factor = rand::thread_rng().gen_range(1.0 - RESCHEDULE_JITTER..=1.0 + RESCHEDULE_JITTER);
+    (secs as f64 * factor).round() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    const INTERVAL: Duration = Duration::from_secs(3600);
+
+    fn config() -> SchedulerConfig {
+        SchedulerConfig {
+            failed_retry_secs: 60,
+            max_failed_retries: 2,
+            ..SchedulerConfig::default()
+        }
+    }
+
+    fn blob_ids(count: usize) -> Vec<String> {
+        (0..count).map(|i| format!("blob-{}", i)).collect()
+    }
+
+    /// 在 `[secs * 0.9, secs * 1.1]` 之內（允許一秒取整誤差）
+    fn assert_about(actual: Duration, secs: u64) {
+        let low = (secs as f64 * (1.0 - RESCHEDULE_JITTER)) as u64;
+        let high = (secs as f64 * (1.0 + RESCHEDULE_JITTER)) as u64 + 1;
+        assert!(
+            (low..=high).contains(&actual.as_secs()),
+            "{:?} not within {}..={}s",
+            actual,
+            low,
+            high
+        );
+    }
+
+    /// 推進到下一個到期時間並取出到期的 Blob
+    async fn advance_to_due(scheduler: &mut AuditScheduler) -> Vec<String> {
+        tokio::time::sleep(scheduler.next_due_in().unwrap()).await;
+        scheduler.due()
+    }
+
+    #[tokio::test]
+    async fn test_new_blobs_are_spread_over_the_interval() {
+        tokio::time::pause();
+        let mut scheduler = AuditScheduler::in_memory(config(), INTERVAL);
+        let start = Instant::now();
+
+        assert_eq!(scheduler.add_all(blob_ids(50)).unwrap(), 50);
+        assert_eq!(scheduler.add_all(blob_ids(50)).unwrap(), 0);
+
+        let mut seen = HashSet::new();
+        let mut batches = 0;
+        while seen.len() < 50 {
+            let due = advance_to_due(&mut scheduler).await;
+            batches += 1;
+            for blob_id in due {
+                scheduler.record(&blob_id, AuditOutcome::Pass).unwrap();
+                seen.insert(blob_id);
+            }
+        }
+
+        // 所有 Blob 在一個間隔內各審計一次，而不是同一時刻
+        assert!(start.elapsed() < INTERVAL);
+        assert!(batches > 1);
+    }
+
+    #[tokio::test]
+    async fn test_passing_blob_is_rescheduled_after_the_interval() {
+        tokio::time::pause();
+        let mut scheduler = AuditScheduler::in_memory(config(), INTERVAL);
+        scheduler.add_all(blob_ids(1)).unwrap();
+
+        assert_eq!(advance_to_due(&mut scheduler).await, blob_ids(1));
+        scheduler.record("blob-0", AuditOutcome::Pass).unwrap();
+
+        assert_about(scheduler.next_due_in().unwrap(), INTERVAL.as_secs());
+        assert!(scheduler.due().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_failed_blob_backs_off_then_is_flagged() {
+        tokio::time::pause();
+        let mut scheduler = AuditScheduler::in_memory(config(), INTERVAL);
+        scheduler.add_all(blob_ids(1)).unwrap();
+        advance_to_due(&mut scheduler).await;
+
+        // 兩次加倍退避重試
+        scheduler.record("blob-0", AuditOutcome::Fail).unwrap();
+        assert_about(scheduler.next_due_in().unwrap(), 60);
+        assert_eq!(advance_to_due(&mut scheduler).await, blob_ids(1));
+
+        scheduler.record("blob-0", AuditOutcome::Fail).unwrap();
+        assert_about(scheduler.next_due_in().unwrap(), 120);
+        assert_eq!(advance_to_due(&mut scheduler).await, blob_ids(1));
+
+        // 重試用盡：標記並恢復正常頻率
+        scheduler.record("blob-0", AuditOutcome::Fail).unwrap();
+        assert_about(scheduler.next_due_in().unwrap(), INTERVAL.as_secs());
+        assert_eq!(scheduler.flagged(), blob_ids(1));
+        advance_to_due(&mut scheduler).await;
+
+        scheduler.record("blob-0", AuditOutcome::Pass).unwrap();
+        let entry = scheduler.get("blob-0").unwrap();
+        assert_eq!(entry.failed_attempts, 0);
+        assert!(!entry.flagged);
+    }
+
+    #[tokio::test]
+    async fn test_due_blob_without_outcome_is_retried() {
+        tokio::time::pause();
+        let mut scheduler = AuditScheduler::in_memory(config(), INTERVAL);
+        scheduler.add_all(blob_ids(1)).unwrap();
+        advance_to_due(&mut scheduler).await;
+
+        // 審計出錯、沒有記錄結果：按 failed_retry_secs 再取出
+        assert_eq!(scheduler.next_due_in(), Some(Duration::from_secs(60)));
+        assert_eq!(advance_to_due(&mut scheduler).await, blob_ids(1));
+    }
+
+    #[tokio::test]
+    async fn test_deleted_blob_is_unscheduled() {
+        tokio::time::pause();
+        let mut scheduler = AuditScheduler::in_memory(config(), INTERVAL);
+        scheduler.add_all(blob_ids(2)).unwrap();
+
+        scheduler.record("blob-1", AuditOutcome::Deleted).unwrap();
+        assert_eq!(scheduler.len(), 1);
+
+        scheduler.record("blob-0", AuditOutcome::Deleted).unwrap();
+        assert!(scheduler.is_empty());
+        assert_eq!(scheduler.next_due_in(), None);
+    }
+
+    #[tokio::test]
+    async fn test_schedule_persists_across_restart() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("audit_schedule.json");
+
+        let mut scheduler = AuditScheduler::open(config(), INTERVAL, &path).unwrap();
+        scheduler.add_all(blob_ids(3)).unwrap();
+        scheduler.record("blob-1", AuditOutcome::Fail).unwrap();
+        let saved = scheduler.entries.clone();
+        drop(scheduler);
+
+        let scheduler = AuditScheduler::open(config(), INTERVAL, &path).unwrap();
+        assert_eq!(scheduler.entries, saved);
+
+        // 停機期間全部到期：重新分散，而不是同時審計
+        let overdue: BTreeMap<_, _> = blob_ids(20)
+            .into_iter()
+            .map(|blob_id| {
+                let entry = BlobSchedule {
+                    next_audit_at: 0,
+                    failed_attempts: 0,
+                    flagged: false,
+                };
+                (blob_id, entry)
+            })
+            .collect();
+        fs::write(&path, serde_json::to_vec(&overdue).unwrap()).unwrap();
+
+        let scheduler = AuditScheduler::open(config(), INTERVAL, &path).unwrap();
+        let now = scheduler.now();
+        let times: HashSet<_> = scheduler
+            .entries
+            .values()
+            .map(|entry| entry.next_audit_at)
+            .collect();
+        assert!(times
+            .iter()
+            .all(|at| (now..now + INTERVAL.as_secs()).contains(at)));
+        assert!(times.len() > 1);
+    }
+}
@@ -1,12 +1,17 @@
 /**
  * Seal HTTP 客戶端
  *
- * 通過 HTTP 調用 TypeScript Seal API 服務來進行 IBE 門檻加密
+ * 通過 HTTP 調用 TypeScript Seal API 服務來進行 IBE 門檻加密與解密
  */
 
-use anyhow::{Context, Result};
-use reqwest::Client;
+use crate::error::{AuditorError, Result};
+use crate::http::{build_client, HttpClientConfig};
+use crate::retry::{retry_with_exponential_backoff_if, RetryConfig};
+use crate::types::validate_sui_address;
+use reqwest::{Client, RequestBuilder, StatusCode};
 use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::time::Duration;
 use tracing::{debug, info, warn};
 
 /// Seal API 端點配置
@@ -14,7 +19,7 @@ use tracing::{debug, info, warn};
 pub struct SealApiConfig {
     /// Seal API 服務器 URL（例如 http://localhost:3001）
     pub api_url: String,
-    /// HTTP 請求超時（秒）
+    /// 單個操作的總時限（秒，含重試）
     pub timeout_secs: u64,
 }
 
@@ -67,8 +72,68 @@ pub struct EncryptMetadata {
     #[serde(rename = "encryptedSize")]
     pub encrypted_size: usize,
     pub duration: u64,
+    /// 加密方式（Seal API 的響應不帶此字段，即 Seal）
+    #[serde(default)]
+    pub scheme: EncryptionScheme,
+    /// 本地加密的密鑰 ID（見 [`crate::local_encryption`]）
+    #[serde(default, rename = "keyId")]
+    pub key_id: Option<String>,
 }
 
+/// 上傳報告的加密方式，決定讀取方如何解密
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EncryptionScheme {
+    /// Seal IBE 門檻加密（經 Seal API 解密）
+    #[default]
+    Seal,
+    /// Seal 不可用時的本地 AES-256-GCM 加密（以密鑰庫中保存的密鑰解密）
+    LocalAes256Gcm,
+}
+
+/// 解密請求
+///
+/// 審計報告以審計員地址作為 IBE identity 加密，因此 `reportId`、`requesterAddress`
+/// 與 `objectId` 均取該地址
+#[derive(Debug, Serialize)]
+pub struct DecryptRequest {
+    /// Base64 編碼的密文
+    #[serde(rename = "encryptedData")]
+    pub encrypted_data: String,
+    /// 報告 ID（用於訪問策略檢查）
+    #[serde(rename = "reportId")]
+    pub report_id: String,
+    /// 請求者 Sui 地址
+    #[serde(rename = "requesterAddress")]
+    pub requester_address: String,
+    /// 審計合約 Package ID
+    #[serde(rename = "packageId")]
+    pub package_id: String,
+    /// Seal 加密對象 ID（向密鑰服務器請求密鑰時使用）
+    #[serde(rename = "objectId")]
+    pub object_id: String,
+}
+
+/// 解密響應
+#[derive(Debug, Deserialize)]
+pub struct DecryptResponse {
+    pub success: bool,
+    /// Base64 編碼的原始明文
+    pub data: Option<String>,
+    /// 解析後的報告（舊版服務器只返回此字段）
+    pub report: Option<serde_json::Value>,
+    /// `real-seal` 或 `fallback`
+    pub mode: Option<String>,
+    /// 機器可讀的錯誤碼（例如 `NOT_ENOUGH_KEY_SERVERS`）
+    pub code: Option<String>,
+    pub error: Option<String>,
+    #[serde(rename = "sealError")]
+    pub seal_error: Option<String>,
+}
+
+/// 密鑰服務器不足時 Seal API 返回的錯誤碼
+pub const NOT_ENOUGH_KEY_SERVERS: &str = "NOT_ENOUGH_KEY_SERVERS";
+
 /// 健康檢查響應
 #[derive(Debug, Deserialize)]
 pub struct HealthResponse {
@@ -85,21 +150,55 @@ pub struct EncryptResult {
     pub metadata: EncryptMetadata,
 }
 
+/// 單次 Seal API 請求的默認時限（秒）
+pub const DEFAULT_REQUEST_TIMEOUT_SECS: u64 = 10;
+
 /// Seal HTTP 客戶端
+///
+/// 兩層時限：每次 HTTP 請求受 [`Self::with_request_timeout`] 限制（默認
+/// [`DEFAULT_REQUEST_TIMEOUT_SECS`]，不超過總時限），整個操作（含重試）受
+/// `config.timeout_secs` 限制。5xx、429、超時與連接失敗按 [`RetryConfig`] 重試，
+/// 錯誤分類見 [`AuditorError::SealUnavailable`]、[`AuditorError::SealRejected`]
+/// 與 [`AuditorError::SealProtocol`]。
 pub struct SealClient {
     config: SealApiConfig,
     client: Client,
+    retry: RetryConfig,
+    request_timeout: Duration,
 }
 
 impl SealClient {
-    /// 創建新的 Seal 客戶端
+    /// 創建新的 Seal 客戶端（默認 HTTP 配置）
     pub fn new(config: SealApiConfig) -> Result<Self> {
-        let client = Client::builder()
-            .timeout(std::time::Duration::from_secs(config.timeout_secs))
-            .build()
-            .context("Failed to create HTTP client")?;
+        Self::from_http_config(config, &HttpClientConfig::default())
+    }
+
+    /// 按 HTTP 配置創建 Seal 客戶端
+    ///
+    /// 連接超時、連接池與 User-Agent 取自 `http`；每個操作的總時限仍為 `config.timeout_secs`
+    pub fn from_http_config(config: SealApiConfig, http: &HttpClientConfig) -> Result<Self> {
+        let client = build_client(http)?;
+        let request_timeout =
+            Duration::from_secs(DEFAULT_REQUEST_TIMEOUT_SECS.min(config.timeout_secs));
+
+        Ok(Self {
+            config,
+            client,
+            retry: RetryConfig::default(),
+            request_timeout,
+        })
+    }
 
-        Ok(Self { config, client })
+    /// 設置臨時錯誤的重試策略
+    pub fn with_retry(mut self, retry: RetryConfig) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// 設置單次 HTTP 請求的時限（超時的請求按臨時錯誤重試）
+    pub fn with_request_timeout(mut self, timeout: Duration) -> Self {
+        self.request_timeout = timeout;
+        self
     }
 
     /// 使用默認配置創建客戶端
@@ -107,37 +206,38 @@ impl SealClient {
         Self::new(SealApiConfig::default())
     }
 
+    /// 單個操作（含重試）的總時限
+    fn timeout(&self) -> Duration {
+        Duration::from_secs(self.config.timeout_secs)
+    }
+
     /// 健康檢查
     pub async fn health_check(&self) -> Result<HealthResponse> {
         let url = format!("{}/health", self.config.api_url);
         debug!("Checking Seal API health at {}", url);
 
-        let response = self
-            .client
-            .get(&url)
-            .send()
-            .await
-            .context("Failed to send health check request")?;
-
-        if !response.status().is_success() {
-            anyhow::bail!("Health check failed with status: {}", response.status());
-        }
-
-        let health: HealthResponse = response
-            .json()
-            .await
-            .context("Failed to parse health check response")?;
+        let health = self.call("seal_health", || self.get_health(&url)).await?;
 
         debug!("Health check successful: {:?}", health);
         Ok(health)
     }
 
+    async fn get_health(&self, url: &str) -> Result<HealthResponse> {
+        let (status, body) = self.send(self.client.get(url)).await?;
+        if !status.is_success() {
+            return Err(status_error(status, error_message(&body)));
+        }
+        serde_json::from_str(&body).map_err(|e| {
+            AuditorError::SealProtocol(format!("Invalid health check response: {}", e))
+        })
+    }
+
     /// 加密審計報告
     ///
     /// # Arguments
     /// * `report_json` - 審計報告 JSON 字串
-    /// * `auditor_address` - 審計員 Sui 地址（32 字節十六進制，0x 開頭）
-    /// * `package_id` - 審計合約 Package ID（32 字節十六進制，0x 開頭）
+    /// * `auditor_address` - 審計員 Sui 地址（0x 開頭，以規範形式作為 IBE identity）
+    /// * `package_id` - 審計合約 Package ID（0x 開頭）
     /// * `threshold` - 門檻值（默認 2）
     ///
     /// # Returns
@@ -150,19 +250,8 @@ impl SealClient {
         threshold: u32,
     ) -> Result<(String, String, EncryptMetadata)> {
         // 驗證地址格式
-        if !auditor_address.starts_with("0x") || auditor_address.len() != 66 {
-            anyhow::bail!(
-                "Invalid auditor address format (must be 32-byte hex with 0x prefix): {}",
-                auditor_address
-            );
-        }
-
-        if !package_id.starts_with("0x") || package_id.len() != 66 {
-            anyhow::bail!(
-                "Invalid package ID format (must be 32-byte hex with 0x prefix): {}",
-                package_id
-            );
-        }
+        let auditor_address = validate_sui_address("auditor address", auditor_address)?;
+        let package_id = validate_sui_address("package ID", package_id)?;
 
         info!(
             "Encrypting audit report for auditor {} using package {}",
@@ -171,14 +260,11 @@ impl SealClient {
         debug!("Report size: {} bytes", report_json.len());
         debug!("Threshold: {}", threshold);
 
-        // 將報告 JSON 編碼為 Base64
-        let data_base64 = base64::encode(report_json.as_bytes());
-
-        // 構建請求
+        // 構建請求（報告 JSON 編碼為 Base64）
         let request = EncryptRequest {
-            data: data_base64,
-            identity: auditor_address.to_string(),
-            package_id: package_id.to_string(),
+            data: base64::encode(report_json.as_bytes()),
+            identity: auditor_address,
+            package_id,
             threshold,
         };
 
@@ -186,56 +272,230 @@ impl SealClient {
         let url = format!("{}/api/seal/encrypt", self.config.api_url);
         debug!("Sending encrypt request to {}", url);
 
-        let response = self
-            .client
-            .post(&url)
-            .json(&request)
-            .send()
-            .await
-            .context("Failed to send encrypt request")?;
-
-        if !response.status().is_success() {
-            let status = response.status();
-            let error_text = response
-                .text()
-                .await
-                .unwrap_or_else(|_| "Unknown error".to_string());
-            anyhow::bail!("Encrypt request failed with status {}: {}", status, error_text);
-        }
+        let (encrypted_data, symmetric_key, metadata) = self
+            .call("seal_encrypt", || self.post_encrypt(&url, &request))
+            .await?;
 
-        // 解析響應
-        let encrypt_response: EncryptResponse = response
-            .json()
-            .await
-            .context("Failed to parse encrypt response")?;
+        info!(
+            "Report encrypted successfully (original: {} bytes, encrypted: {} bytes, duration: {}ms)",
+            metadata.original_size, metadata.encrypted_size, metadata.duration
+        );
+
+        Ok((encrypted_data, symmetric_key, metadata))
+    }
 
-        if !encrypt_response.success {
-            let error_msg = encrypt_response
-                .error
-                .unwrap_or_else(|| "Unknown error".to_string());
-            anyhow::bail!("Encryption failed: {}", error_msg);
+    async fn post_encrypt(
+        &self,
+        url: &str,
+        request: &EncryptRequest,
+    ) -> Result<(String, String, EncryptMetadata)> {
+        let (status, body) = self.send(self.client.post(url).json(request)).await?;
+        if !status.is_success() {
+            return Err(status_error(status, error_message(&body)));
         }
 
-        // 提取結果
-        let encrypted_data = encrypt_response
-            .encrypted_data
-            .context("Missing encrypted data in response")?;
+        let response: EncryptResponse = serde_json::from_str(&body)
+            .map_err(|e| AuditorError::SealProtocol(format!("Invalid encrypt response: {}", e)))?;
+        if !response.success {
+            return Err(AuditorError::SealRejected {
+                status: status.as_u16(),
+                message: response
+                    .error
+                    .unwrap_or_else(|| "Unknown error".to_string()),
+            });
+        }
 
-        let symmetric_key = encrypt_response
-            .symmetric_key
-            .context("Missing symmetric key in response")?;
+        let missing =
+            |field: &str| AuditorError::SealProtocol(format!("Missing {} in response", field));
+        Ok((
+            response
+                .encrypted_data
+                .ok_or_else(|| missing("encrypted data"))?,
+            response
+                .symmetric_key
+                .ok_or_else(|| missing("symmetric key"))?,
+            response.metadata.ok_or_else(|| missing("metadata"))?,
+        ))
+    }
 
-        let metadata = encrypt_response
-            .metadata
-            .context("Missing metadata in response")?;
+    /// 解密審計報告
+    ///
+    /// Seal API 先檢查鏈上訪問策略，再向密鑰服務器取回門檻數量的密鑰份額並解密。
+    /// 5xx、429、超時與連接失敗按 [`RetryConfig`] 重試；策略拒絕與密鑰服務器不足
+    /// 分別返回 [`AuditorError::SealAccessDenied`] 與 [`AuditorError::SealKeyServers`]。
+    ///
+    /// # Arguments
+    /// * `encrypted_data_base64` - [`Self::encrypt_report`] 返回的密文
+    /// * `identity` - 加密時使用的審計員 Sui 地址
+    /// * `package_id` - 審計合約 Package ID
+    ///
+    /// # Returns
+    /// 與加密前逐字節相同的報告 JSON
+    pub async fn decrypt_report(
+        &self,
+        encrypted_data_base64: &str,
+        identity: &str,
+        package_id: &str,
+    ) -> Result<String> {
+        let (identity, package_id) = match (
+            validate_sui_address("identity", identity),
+            validate_sui_address("package ID", package_id),
+        ) {
+            (Ok(identity), Ok(package_id)) => (identity, package_id),
+            (Err(e), _) | (_, Err(e)) => return Err(AuditorError::SealDecryption(e.to_string())),
+        };
 
         info!(
-            "Report encrypted successfully (original: {} bytes, encrypted: {} bytes, duration: {}ms)",
-            metadata.original_size, metadata.encrypted_size, metadata.duration
+            "Decrypting audit report for {} using package {}",
+            identity, package_id
         );
 
-        Ok((encrypted_data, symmetric_key, metadata))
+        let request = DecryptRequest {
+            encrypted_data: encrypted_data_base64.to_string(),
+            report_id: identity.clone(),
+            requester_address: identity.clone(),
+            package_id,
+            object_id: identity,
+        };
+        let url = format!("{}/api/seal/decrypt", self.config.api_url);
+        debug!("Sending decrypt request to {}", url);
+
+        let response = self
+            .call("seal_decrypt", || self.post_decrypt(&url, &request))
+            .await?;
+
+        if response.mode.as_deref() == Some("fallback") {
+            warn!(
+                "Seal API decrypted in fallback mode (Seal SDK error: {})",
+                response.seal_error.as_deref().unwrap_or("unknown")
+            );
+        }
+
+        let plaintext = match (response.data, response.report) {
+            (Some(data), _) => {
+                let bytes = base64::decode(&data).map_err(|e| {
+                    AuditorError::SealDecryption(format!("Invalid plaintext encoding: {}", e))
+                })?;
+                String::from_utf8(bytes).map_err(|e| {
+                    AuditorError::SealDecryption(format!("Plaintext is not UTF-8: {}", e))
+                })?
+            }
+            // 舊版服務器只返回解析後的報告，無法保證字節一致
+            (None, Some(report)) => serde_json::to_string(&report)?,
+            (None, None) => {
+                return Err(AuditorError::SealDecryption(
+                    "Missing plaintext in response".to_string(),
+                ))
+            }
+        };
+
+        info!("Report decrypted successfully ({} bytes)", plaintext.len());
+        Ok(plaintext)
     }
+
+    async fn post_decrypt(&self, url: &str, request: &DecryptRequest) -> Result<DecryptResponse> {
+        let (status, body) = self.send(self.client.post(url).json(request)).await?;
+        let parsed: Option<DecryptResponse> = serde_json::from_str(&body).ok();
+
+        let message = parsed
+            .as_ref()
+            .and_then(|r| r.seal_error.clone().or_else(|| r.error.clone()))
+            .unwrap_or_else(|| body.clone());
+        let key_servers = parsed
+            .as_ref()
+            .is_some_and(|r| r.code.as_deref() == Some(NOT_ENOUGH_KEY_SERVERS));
+
+        if status == StatusCode::FORBIDDEN {
+            return Err(AuditorError::SealAccessDenied(message));
+        }
+        if key_servers {
+            return Err(AuditorError::SealKeyServers(message));
+        }
+        if status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS {
+            return Err(status_error(status, message));
+        }
+        if !status.is_success() {
+            return Err(AuditorError::SealDecryption(format!(
+                "Decrypt request failed with status {}: {}",
+                status, message
+            )));
+        }
+
+        let response = parsed.ok_or_else(|| {
+            AuditorError::SealProtocol(format!("Unexpected decrypt response: {}", body))
+        })?;
+        if !response.success {
+            return Err(AuditorError::SealDecryption(message));
+        }
+        Ok(response)
+    }
+
+    /// 在總時限內按重試策略執行操作
+    async fn call<T, F, Fut>(&self, operation: &str, attempt: F) -> Result<T>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let deadline = self.timeout();
+        tokio::time::timeout(
+            deadline,
+            retry_with_exponential_backoff_if(operation, &self.retry, is_transient, attempt),
+        )
+        .await
+        .unwrap_or_else(|_| {
+            Err(AuditorError::SealUnavailable(format!(
+                "{} did not complete within {}s",
+                operation,
+                deadline.as_secs()
+            )))
+        })
+    }
+
+    /// 以單次請求時限發送請求，返回狀態碼與響應體
+    async fn send(&self, request: RequestBuilder) -> Result<(StatusCode, String)> {
+        let response = request
+            .timeout(self.request_timeout)
+            .send()
+            .await
+            .map_err(transport_error)?;
+        let status = response.status();
+        let body = response.text().await.map_err(transport_error)?;
+        Ok((status, body))
+    }
+}
+
+/// 將超時與連接失敗歸為 [`AuditorError::SealUnavailable`]，其餘保留為 HTTP 錯誤
+fn transport_error(error: reqwest::Error) -> AuditorError {
+    if error.is_timeout() || error.is_connect() {
+        AuditorError::SealUnavailable(error.to_string())
+    } else {
+        AuditorError::HttpRequest(error)
+    }
+}
+
+/// 按狀態碼分類失敗響應：5xx 與 429 可重試，其餘為拒絕
+fn status_error(status: StatusCode, message: String) -> AuditorError {
+    if status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS {
+        AuditorError::SealUnavailable(format!("{}: {}", status, message))
+    } else {
+        AuditorError::SealRejected {
+            status: status.as_u16(),
+            message,
+        }
+    }
+}
+
+/// 失敗響應中的錯誤信息（JSON `error` 字段，否則為整個響應體）
+fn error_message(body: &str) -> String {
+    serde_json::from_str::<serde_json::Value>(body)
+        .ok()
+        .and_then(|value| value["error"].as_str().map(str::to_string))
+        .unwrap_or_else(|| body.to_string())
+}
+
+/// 是否為可重試的臨時錯誤（5xx、429、超時、連接失敗）
+fn is_transient(error: &AuditorError) -> bool {
+    matches!(error, AuditorError::SealUnavailable(_))
 }
 
 // Base64 編碼/解碼輔助模塊
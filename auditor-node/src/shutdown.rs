@@ -0,0 +1,237 @@
+//! 守護進程的優雅停機
+//!
+//! 守護進程的審計週期作為受追蹤的 tokio 任務（[`InFlightAudits`]）運行，主循環在審計期間
+//! 仍能響應停機信號。收到停機信號後：
+//!
+//! 1. 不再調度新的審計
+//! 2. 最多等待 `shutdown_grace_secs` 秒（[`InFlightAudits::drain`]），讓已完成挑戰的審計
+//!    繼續簽名、上傳與提交，而不是丟棄已完成的工作
+//! 3. 寬限期到期後取消剩餘任務，並返回它們正在審計的 Blob（[`Drained::abandoned`]）
+//!
+//! 任務通過 [`InFlightAudit`] 報告正在審計的 Blob。再次按下 Ctrl+C 時進程立即退出，不再等待。
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::task::{Id, JoinSet};
+use tracing::error;
+
+/// 默認停機寬限期（秒）
+pub const DEFAULT_SHUTDOWN_GRACE_SECS: u64 = 60;
+
+/// 審計任務正在處理的 Blob
+#[derive(Debug, Clone, Default)]
+pub struct InFlightAudit(Arc<Mutex<Option<String>>>);
+
+impl InFlightAudit {
+    /// 開始審計 Blob
+    pub fn start(&self, blob_id: &str) {
+        *self.0.lock().unwrap() = Some(blob_id.to_string());
+    }
+
+    /// 當前 Blob 的審計已結束
+    pub fn finish(&self) {
+        *self.0.lock().unwrap() = None;
+    }
+
+    /// 正在審計的 Blob（兩次審計之間為 `None`）
+    pub fn current(&self) -> Option<String> {
+        self.0.lock().unwrap().clone()
+    }
+}
+
+/// 停機時等待在途審計的結果
+#[derive(Debug)]
+pub struct Drained<T> {
+    /// 寬限期內完成的任務結果
+    pub completed: Vec<T>,
+
+    /// 寬限期到期後被取消的任務數
+    pub cancelled: usize,
+
+    /// 被取消任務正在審計的 Blob
+    pub abandoned: Vec<String>,
+}
+
+/// 正在執行的審計任務
+pub struct InFlightAudits<T> {
+    tasks: JoinSet<T>,
+    progress: HashMap<Id, InFlightAudit>,
+}
+
+impl<T: Send + 'static> InFlightAudits<T> {
+    /// 創建空的任務集合
+    pub fn new() -> Self {
+        Self {
+            tasks: JoinSet::new(),
+            progress: HashMap::new(),
+        }
+    }
+
+    /// 啟動審計任務；`audit` 收到的 [`InFlightAudit`] 用於報告正在審計的 Blob
+    pub fn spawn<F, Fut>(&mut self, audit: F)
+    where
+        F: FnOnce(InFlightAudit) -> Fut,
+        Fut: Future<Output = T> + Send + 'static,
+    {
+        let progress = InFlightAudit::default();
+        let handle = self.tasks.spawn(audit(progress.clone()));
+        self.progress.insert(handle.id(), progress);
+    }
+
+    /// 在途任務數
+    pub fn len(&self) -> usize {
+        self.tasks.len()
+    }
+
+    /// 是否沒有在途任務
+    pub fn is_empty(&self) -> bool {
+        self.tasks.is_empty()
+    }
+
+    /// 等待下一個任務結束；沒有在途任務時返回 `None`
+    ///
+    /// panic 的任務記錄錯誤後跳過
+    pub async fn join_next(&mut self) -> Option<T> {
+        loop {
+            match self.tasks.join_next_with_id().await? {
+                Ok((id, result)) => {
+                    self.progress.remove(&id);
+                    return Some(result);
+                }
+                Err(e) => {
+                    self.progress.remove(&e.id());
+                    error!("Audit task failed: {}", e);
+                }
+            }
+        }
+    }
+
+    /// 最多等待 `grace` 讓在途任務完成，之後取消剩餘任務
+    pub async fn drain(mut self, grace: Duration) -> Drained<T> {
+        let mut completed = Vec::new();
+        let deadline = tokio::time::sleep(grace);
+        tokio::pin!(deadline);
+
+        loop {
+            tokio::select! {
+                next = self.join_next() => match next {
+                    Some(result) => completed.push(result),
+                    None => break,
+                },
+                _ = &mut deadline => break,
+            }
+        }
+
+        let cancelled = self.tasks.len();
+        let abandoned = self
+            .progress
+            .values()
+            .filter_map(InFlightAudit::current)
+            .collect();
+        self.tasks.shutdown().await;
+
+        Drained {
+            completed,
+            cancelled,
+            abandoned,
+        }
+    }
+}
+
+impl<T: Send + 'static> Default for InFlightAudits<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::sync::Notify;
+
+    /// 與守護進程主循環相同：停機信號到達後等待在途審計
+    async fn run_until_shutdown(
+        mut in_flight: InFlightAudits<String>,
+        shutdown: &Notify,
+        grace: Duration,
+    ) -> Drained<String> {
+        let mut results = Vec::new();
+        loop {
+            tokio::select! {
+                Some(result) = in_flight.join_next(), if !in_flight.is_empty() => {
+                    results.push(result);
+                }
+                _ = shutdown.notified() => break,
+            }
+        }
+        let mut drained = in_flight.drain(grace).await;
+        results.append(&mut drained.completed);
+        drained.completed = results;
+        drained
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_shutdown_waits_for_in_flight_audit() {
+        let mut in_flight = InFlightAudits::new();
+        in_flight.spawn(|progress| async move {
+            progress.start("blob-slow");
+            // 挑戰階段之後的簽名與上傳
+            tokio::time::sleep(Duration::from_secs(5)).await;
+            progress.finish();
+            "report for blob-slow".to_string()
+        });
+
+        let shutdown = Arc::new(Notify::new());
+        let notifier = Arc::clone(&shutdown);
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_secs(1)).await;
+            notifier.notify_one();
+        });
+
+        let started = tokio::time::Instant::now();
+        let drained = run_until_shutdown(in_flight, &shutdown, Duration::from_secs(60)).await;
+
+        assert_eq!(drained.completed, vec!["report for blob-slow".to_string()]);
+        assert_eq!(drained.cancelled, 0);
+        assert!(drained.abandoned.is_empty());
+        // 審計完成後立即返回，不等滿寬限期
+        assert_eq!(started.elapsed(), Duration::from_secs(5));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_grace_period_cancels_and_reports_abandoned_blob() {
+        let mut in_flight = InFlightAudits::new();
+        in_flight.spawn(|progress| async move {
+            progress.start("blob-fast");
+            tokio::time::sleep(Duration::from_secs(1)).await;
+            progress.finish();
+            "blob-fast".to_string()
+        });
+        in_flight.spawn(|progress| async move {
+            progress.start("blob-stuck");
+            tokio::time::sleep(Duration::from_secs(3600)).await;
+            "blob-stuck".to_string()
+        });
+
+        let started = tokio::time::Instant::now();
+        let drained = in_flight.drain(Duration::from_secs(10)).await;
+
+        assert_eq!(drained.completed, vec!["blob-fast".to_string()]);
+        assert_eq!(drained.cancelled, 1);
+        assert_eq!(drained.abandoned, vec!["blob-stuck".to_string()]);
+        assert_eq!(started.elapsed(), Duration::from_secs(10));
+    }
+
+    #[tokio::test]
+    async fn test_panicking_audit_is_skipped() {
+        let mut in_flight = InFlightAudits::new();
+        in_flight.spawn(|_| async { panic!("audit bug") });
+        in_flight.spawn(|_| async { 7 });
+
+        assert_eq!(in_flight.join_next().await, Some(7));
+        assert!(in_flight.is_empty());
+        assert_eq!(in_flight.join_next().await, None);
+    }
+}
@@ -1,27 +1,60 @@
 //! Walrus 存儲節點客戶端模塊
 //!
 //! 負責與 Walrus 存儲節點通信:
-//! - 發送審計挑戰（請求特定 sliver）
-//! - 接收 sliver 數據和默克爾證明
+//! - 發送審計挑戰（請求特定 sliver 或其 recovery symbol，可附審計員 PQC 簽名）
+//! - 接收 sliver（或 symbol）數據和默克爾證明
 //! - 驗證存儲節點健康狀態
 //! - 處理網絡錯誤和重試
 //!
 //! # API 端點
 //!
-//! 基於 Walrus 存儲節點的 HTTP API:
-//! - `POST /v1/challenge` - 挑戰特定 sliver
+//! 按 [`ApiStyle`] 選擇（配置 `storage_node_api_style`，或逐節點 [`StorageNodeClient::with_api_style`]）:
+//! - [`ApiStyle::Challenge`]: `POST /v1/challenge` - 節點返回 sliver 與默克爾證明；
+//!   請求帶 `symbol_index` 時返回該 recovery symbol 與 symbol 證明
+//! - [`ApiStyle::Walrus`]: Walrus 存儲節點的 REST API
+//!   - `GET /v1/blobs/{blob_id}/metadata` - Blob 元數據（每個 sliver pair 的哈希）
+//!   - `GET /v1/blobs/{blob_id}/slivers/{sliver_pair_index}/primary` - sliver 數據
+//!
+//!   默克爾證明由元數據中的哈希列表在本地構建，仍以 [`ChallengeResponse`] 返回；
+//!   審計員對照鏈上默克爾根驗證，因此節點無法以偽造的哈希列表通過驗證。
+//!   此風格不支持 recovery symbol 挑戰
 //! - `GET /health` - 健康檢查
 //!
 //! # 重試策略
 //!
 //! - 最多重試 3 次
-//! - 指數退避（1s, 2s, 4s）
-//! - 僅對網絡錯誤重試，不對邏輯錯誤重試
+//! - 指數退避（1s, 2s, 4s），每次等待加 ±20% 抖動，避免大量審計員同時重試
+//! - 429 / 503 視為過載：按響應的 `Retry-After`（秒數或 HTTP 日期）等待，缺省時按指數退避
+//! - 僅對網絡錯誤與過載重試，不對邏輯錯誤重試
+//! - 單個挑戰的重試總時長受 [`StorageNodeClient::with_retry_budget`] 限制，
+//!   下一次等待會超出時直接返回最後一次錯誤
+//! - 啟用熔斷器（[`crate::breaker`]）時，節點錯誤率過高後直接返回 `CircuitOpen`，不再重試
+//! - 啟用限流（[`crate::rate_limit`]）時，每個請求發出前等待該主機的令牌；
+//!   等待令牌不計為一次嘗試
+//!
+//! # 錯誤響應
+//!
+//! 非 2xx 響應體經 [`NodeErrorBody`](crate::node_error::NodeErrorBody) 清洗並截斷後
+//! 才寫入錯誤信息（進而進入報告的 `failure_reason`），完整響應體只記錄在 HTTP 捕獲中
 
+use crate::audit_report::PqcAlgorithm;
+use crate::breaker::{is_failure_status, CircuitBreaker};
+use crate::capture::{HttpCapture, HttpExchange};
+use crate::crypto::merkle::{MerkleTree, MerkleTreeVersion};
 use crate::error::{AuditorError, Result};
-use reqwest::Client;
+use crate::http::{build_client, HttpClientConfig};
+use crate::metrics::Metrics;
+use crate::node_error::{NodeErrorBody, DEFAULT_MAX_ERROR_BODY_LEN};
+use crate::rate_limit::RateLimiter;
+use crate::types::BlobId;
+use chrono::{DateTime, Utc};
+use pqc_signer::traits::Signer;
+use rand::Rng;
+use reqwest::header::{ACCEPT, RETRY_AFTER};
+use reqwest::{Client, Request, StatusCode};
 use serde::{Deserialize, Serialize};
-use std::time::Duration;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use tracing::{debug, error, info, warn};
 
 /// 默認重試次數
@@ -30,10 +63,47 @@ const DEFAULT_MAX_RETRIES: u32 = 3;
 /// 默認超時（秒）
 const DEFAULT_TIMEOUT_SECS: u64 = 30;
 
+/// 默認重試總時限（秒）
+const DEFAULT_RETRY_BUDGET_SECS: u64 = 60;
+
+/// 退避等待的抖動比例（±20%）
+const RETRY_JITTER: f64 = 0.2;
+
+/// 挑戰請求簽名載荷的域分隔前綴
+const CHALLENGE_SIGNING_DOMAIN: &[u8] = b"walrus-audit/challenge-request/v1";
+
+/// 存儲節點的 API 風格
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ApiStyle {
+    /// `POST /v1/challenge`（本項目定義的挑戰端點，節點返回證明）
+    #[default]
+    Challenge,
+
+    /// Walrus 存儲節點的 sliver 與元數據 GET 端點
+    Walrus,
+}
+
+/// Walrus `GET /v1/blobs/{blob_id}/metadata` 的 JSON 響應（其餘字段忽略）
+#[derive(Deserialize, Debug, Clone)]
+pub struct WalrusBlobMetadata {
+    /// 按 sliver pair 索引排列的哈希
+    pub hashes: Vec<SliverPairHashes>,
+}
+
+/// 單個 sliver pair 的哈希
+#[derive(Deserialize, Debug, Clone)]
+pub struct SliverPairHashes {
+    /// primary sliver 的默克爾葉子哈希（hex，即 `hash_leaf(sliver)`）
+    pub primary_hash: String,
+}
+
 /// 挑戰請求（發送給存儲節點）
 ///
-/// 請求特定 Blob 的特定 Sliver 數據和默克爾證明
-#[derive(Serialize, Debug, Clone)]
+/// 請求特定 Blob 的特定 Sliver 數據和默克爾證明。
+/// 簽名的請求（[`ChallengeRequest::sign`]）讓存儲節點可以按審計員限流與歸責，
+/// 並按 `timestamp` 拒絕重放窗口之外的請求。
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub struct ChallengeRequest {
     /// Blob ID（u256 as hex string 或 bytes）
     pub blob_id: String,
@@ -41,23 +111,157 @@ pub struct ChallengeRequest {
     /// 請求的 Sliver 索引（0 到 n-1）
     pub sliver_index: u64,
 
+    /// Recovery symbol 挑戰請求的 symbol 索引（0 到 n-1；未設置時請求完整 sliver）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub symbol_index: Option<u64>,
+
+    /// 挑戰發出時間（Unix 秒，簽名覆蓋，用於重放窗口檢查）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<u64>,
+
+    /// 發出挑戰的審計員 Sui 地址（簽名覆蓋）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auditor_address: Option<String>,
+
     /// 可選：請求者簽名（用於防止 DoS）
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub signature: Option<Vec<u8>>,
+
+    /// 簽名者的 PQC 公鑰
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auditor_public_key: Option<Vec<u8>>,
+
+    /// 簽名算法編號（與 `AuditReport::pqc_algorithm` 相同，1=Falcon512, 3=Dilithium3）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pqc_algorithm: Option<u8>,
+}
+
+impl ChallengeRequest {
+    /// 創建未簽名的挑戰請求
+    pub fn new(blob_id: impl Into<String>, sliver_index: u64) -> Self {
+        Self {
+            blob_id: blob_id.into(),
+            sliver_index,
+            symbol_index: None,
+            timestamp: None,
+            auditor_address: None,
+            signature: None,
+            auditor_public_key: None,
+            pqc_algorithm: None,
+        }
+    }
+
+    /// 創建 recovery symbol 挑戰請求
+    pub fn for_symbol(blob_id: impl Into<String>, sliver_index: u64, symbol_index: u64) -> Self {
+        Self {
+            symbol_index: Some(symbol_index),
+            ..Self::new(blob_id, sliver_index)
+        }
+    }
+
+    /// 簽名的規範字節
+    ///
+    /// 域分隔前綴後依次為：Blob ID（u32 長度前綴）、Sliver 索引（u64）、
+    /// 時間戳（u64）、審計員地址（u32 長度前綴），整數均為小端序；
+    /// recovery symbol 挑戰在末尾追加 symbol 索引（u64），完整 sliver 挑戰的字節不變
+    pub fn signing_payload(&self) -> Vec<u8> {
+        let auditor_address = self.auditor_address.as_deref().unwrap_or_default();
+        let mut payload = Vec::with_capacity(
+            CHALLENGE_SIGNING_DOMAIN.len() + 24 + self.blob_id.len() + auditor_address.len(),
+        );
+        payload.extend_from_slice(CHALLENGE_SIGNING_DOMAIN);
+        payload.extend_from_slice(&(self.blob_id.len() as u32).to_le_bytes());
+        payload.extend_from_slice(self.blob_id.as_bytes());
+        payload.extend_from_slice(&self.sliver_index.to_le_bytes());
+        payload.extend_from_slice(&self.timestamp.unwrap_or_default().to_le_bytes());
+        payload.extend_from_slice(&(auditor_address.len() as u32).to_le_bytes());
+        payload.extend_from_slice(auditor_address.as_bytes());
+        if let Some(symbol_index) = self.symbol_index {
+            payload.extend_from_slice(&symbol_index.to_le_bytes());
+        }
+        payload
+    }
+
+    /// 以審計員身份簽名（填寫時間戳、地址、簽名、公鑰與算法）
+    ///
+    /// # 錯誤
+    /// - 簽名器的算法不受支持或簽名失敗: 返回 `PqcSignature` 錯誤
+    pub fn sign(
+        &mut self,
+        signer: &dyn Signer,
+        auditor_address: &str,
+        timestamp: u64,
+    ) -> Result<()> {
+        let algorithm = PqcAlgorithm::from_name(signer.algorithm_name()).ok_or_else(|| {
+            AuditorError::PqcSignature(format!(
+                "Unsupported PQC algorithm: {}",
+                signer.algorithm_name()
+            ))
+        })?;
+
+        self.timestamp = Some(timestamp);
+        self.auditor_address = Some(auditor_address.to_string());
+        self.signature = Some(
+            signer
+                .sign(&self.signing_payload())
+                .map_err(|e| AuditorError::PqcSignature(e.to_string()))?,
+        );
+        self.auditor_public_key = Some(signer.public_key().to_vec());
+        self.pqc_algorithm = Some(algorithm.id());
+        Ok(())
+    }
+
+    /// 用給定公鑰驗證請求簽名
+    ///
+    /// 請求未簽名（缺少簽名或時間戳）時返回 `Ok(false)`。
+    /// 算法按 `pqc_algorithm` 選擇，未填寫時按 Dilithium3 處理。
+    ///
+    /// # 錯誤
+    /// - 算法編號未知，或公鑰與算法不符: 返回 `PqcSignature` 錯誤
+    pub fn verify_signature(&self, public_key: &[u8]) -> Result<bool> {
+        let (Some(signature), Some(_)) = (&self.signature, self.timestamp) else {
+            return Ok(false);
+        };
+
+        let algorithm_id = self.pqc_algorithm.unwrap_or(PqcAlgorithm::Dilithium3.id());
+        let algorithm = PqcAlgorithm::from_id(algorithm_id).ok_or_else(|| {
+            AuditorError::PqcSignature(format!("Unsupported PQC algorithm: {}", algorithm_id))
+        })?;
+
+        algorithm
+            .verifier(public_key)?
+            .verify(&self.signing_payload(), signature)
+            .map_err(|e| AuditorError::PqcSignature(e.to_string()))
+    }
+
+    /// 時間戳是否在 `now` 前後 `window_secs` 秒之內（未簽名的請求返回 `false`）
+    pub fn is_within_window(&self, now: u64, window_secs: u64) -> bool {
+        self.timestamp
+            .is_some_and(|timestamp| timestamp.abs_diff(now) <= window_secs)
+    }
 }
 
 /// 存儲節點的響應
 ///
-/// 包含請求的 Sliver 數據和對應的默克爾證明
+/// 包含請求的 Sliver 數據和對應的默克爾證明；recovery symbol 挑戰時為 symbol 數據、
+/// symbol 證明與 sliver 哈希的默克爾證明
 #[derive(Deserialize, Debug, Clone)]
 pub struct ChallengeResponse {
-    /// Sliver 原始數據（經過 erasure coding 的片段）
+    /// Sliver 原始數據（經過 erasure coding 的片段）；recovery symbol 挑戰時為 symbol 數據
     pub sliver_data: Vec<u8>,
 
     /// 默克爾證明（從該 sliver 到 merkle root 的路徑）
     /// 格式: Vec<[u8; 32]> 序列化後的字節
     pub merkle_proof: Vec<u8>,
 
+    /// 應答的 symbol 索引（僅 recovery symbol 挑戰）
+    #[serde(default)]
+    pub symbol_index: Option<u64>,
+
+    /// Symbol 證明（從該 symbol 到 sliver 哈希的路徑，格式同 `merkle_proof`；僅 recovery symbol 挑戰）
+    #[serde(default)]
+    pub symbol_proof: Vec<u8>,
+
     /// 可選：存儲節點簽名（用於證明數據來源）
     #[serde(skip_serializing_if = "Option::is_none")]
     pub node_signature: Option<Vec<u8>>,
@@ -67,6 +271,16 @@ pub struct ChallengeResponse {
     pub timestamp: Option<u64>,
 }
 
+/// 一次挑戰的傳輸統計（成功與失敗的挑戰都有）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ChallengeTiming {
+    /// 重試循環的嘗試次數（含首次；Walrus 風格下一次嘗試包含元數據與 sliver 兩個請求）
+    pub attempts: u8,
+
+    /// 從首次請求到最終響應（或放棄）的時間，含重試等待
+    pub elapsed: Duration,
+}
+
 /// 健康檢查響應
 #[derive(Deserialize, Debug)]
 pub struct HealthCheckResponse {
@@ -100,8 +314,29 @@ pub struct StorageNodeClient {
     /// 最大重試次數
     max_retries: u32,
 
+    /// 單個挑戰重試的總時限
+    retry_budget: Duration,
+
     /// 請求超時時間
     timeout: Duration,
+
+    /// 錯誤響應體寫入錯誤信息時的最大字符數
+    max_error_body_len: usize,
+
+    /// 可選的熔斷器（與其他客戶端共享）
+    breaker: Option<Arc<CircuitBreaker>>,
+
+    /// 可選的按主機限流器（與訪問同一主機的其他客戶端共享）
+    rate_limiter: Option<Arc<RateLimiter>>,
+
+    /// 可選的 Prometheus 指標（與其他客戶端共享）
+    metrics: Option<Arc<Metrics>>,
+
+    /// 節點的 API 風格
+    api_style: ApiStyle,
+
+    /// Walrus 風格下最近一個 Blob 的默克爾樹（由元數據構建，同一 Blob 的挑戰共用）
+    walrus_tree: Mutex<Option<(String, Arc<MerkleTree>)>>,
 }
 
 impl StorageNodeClient {
@@ -119,39 +354,92 @@ impl StorageNodeClient {
     /// );
     /// ```
     pub fn new(base_url: String) -> Self {
-        let http_client = Client::builder()
-            .timeout(Duration::from_secs(DEFAULT_TIMEOUT_SECS))
-            .build()
-            .expect("Failed to build HTTP client");
-
-        info!("Created StorageNodeClient for {}", base_url);
+        Self::with_config(base_url, DEFAULT_TIMEOUT_SECS, DEFAULT_MAX_RETRIES)
+    }
 
-        Self {
-            http_client,
+    /// 創建帶自定義配置的客戶端（默認 HTTP 配置）
+    ///
+    /// # Panics
+    /// HTTP 客戶端無法創建（TLS 後端初始化失敗）時 panic；
+    /// 需要處理錯誤時使用 [`StorageNodeClient::from_http_config`]
+    pub fn with_config(base_url: String, timeout_secs: u64, max_retries: u32) -> Self {
+        Self::from_http_config(
             base_url,
-            max_retries: DEFAULT_MAX_RETRIES,
-            timeout: Duration::from_secs(DEFAULT_TIMEOUT_SECS),
-        }
+            &HttpClientConfig::default(),
+            timeout_secs,
+            max_retries,
+        )
+        .expect("Failed to build HTTP client")
     }
 
-    /// 創建帶自定義配置的客戶端
-    pub fn with_config(base_url: String, timeout_secs: u64, max_retries: u32) -> Self {
-        let http_client = Client::builder()
-            .timeout(Duration::from_secs(timeout_secs))
-            .build()
-            .expect("Failed to build HTTP client");
+    /// 按 HTTP 配置創建客戶端
+    ///
+    /// 連接超時、連接池與 User-Agent 取自 `http`；每個請求的總時限為 `timeout_secs`
+    ///
+    /// # 錯誤
+    /// HTTP 客戶端無法創建時返回 `AuditorError::HttpRequest`
+    pub fn from_http_config(
+        base_url: String,
+        http: &HttpClientConfig,
+        timeout_secs: u64,
+        max_retries: u32,
+    ) -> Result<Self> {
+        let http_client = build_client(http)?;
 
         info!(
             "Created StorageNodeClient for {} (timeout: {}s, max_retries: {})",
             base_url, timeout_secs, max_retries
         );
 
-        Self {
+        Ok(Self {
             http_client,
             base_url,
             max_retries,
+            retry_budget: Duration::from_secs(DEFAULT_RETRY_BUDGET_SECS),
             timeout: Duration::from_secs(timeout_secs),
-        }
+            max_error_body_len: DEFAULT_MAX_ERROR_BODY_LEN,
+            breaker: None,
+            rate_limiter: None,
+            metrics: None,
+            api_style: ApiStyle::default(),
+            walrus_tree: Mutex::new(None),
+        })
+    }
+
+    /// 設置錯誤響應體的最大字符數（超出部分截斷，完整內容僅保存在 HTTP 捕獲中）
+    pub fn with_max_error_body_len(mut self, max_error_body_len: usize) -> Self {
+        self.max_error_body_len = max_error_body_len;
+        self
+    }
+
+    /// 設置單個挑戰重試的總時限（從首次請求起算）
+    pub fn with_retry_budget(mut self, retry_budget: Duration) -> Self {
+        self.retry_budget = retry_budget;
+        self
+    }
+
+    /// 啟用熔斷：節點錯誤率過高時挑戰直接返回 `CircuitOpen`，不再發出請求或重試
+    pub fn with_breaker(mut self, breaker: Arc<CircuitBreaker>) -> Self {
+        self.breaker = Some(breaker);
+        self
+    }
+
+    /// 啟用按主機限流：每個請求發出前取一個令牌，與共享同一限流器的客戶端共用配額
+    pub fn with_rate_limiter(mut self, rate_limiter: Arc<RateLimiter>) -> Self {
+        self.rate_limiter = Some(rate_limiter);
+        self
+    }
+
+    /// 記錄指標：每次失敗的挑戰請求按錯誤類別計入 `storage_node_errors_total`
+    pub fn with_metrics(mut self, metrics: Arc<Metrics>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// 設置節點的 API 風格（默認 [`ApiStyle::Challenge`]）
+    pub fn with_api_style(mut self, api_style: ApiStyle) -> Self {
+        self.api_style = api_style;
+        self
     }
 
     /// 向存儲節點發送挑戰
@@ -159,7 +447,7 @@ impl StorageNodeClient {
     /// 請求特定 Blob 的特定 Sliver，並獲取默克爾證明
     ///
     /// # 參數
-    /// - `blob_id`: Blob ID（字符串格式）
+    /// - `blob_id`: Walrus Blob ID
     /// - `sliver_index`: Sliver 索引（0 到 n-1）
     ///
     /// # 返回
@@ -176,23 +464,43 @@ impl StorageNodeClient {
     /// ```no_run
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// # use auditor_node::storage_node_client::StorageNodeClient;
+    /// # use auditor_node::types::BlobId;
     /// let client = StorageNodeClient::new("http://node.walrus.network:8080".to_string());
     ///
-    /// let response = client.challenge("blob_id_here", 0).await?;
+    /// let blob_id = BlobId::parse("eRrTusk8yshFQpkemgDnbg0f4-qDo623V2NpeVG1Zcg")?;
+    /// let response = client.challenge(&blob_id, 0).await?;
     /// println!("Received {} bytes of sliver data", response.sliver_data.len());
     /// # Ok(())
     /// # }
     /// ```
     pub async fn challenge(
         &self,
-        blob_id: &str,
+        blob_id: &BlobId,
         sliver_index: u64,
     ) -> Result<ChallengeResponse> {
-        let request = ChallengeRequest {
-            blob_id: blob_id.to_string(),
-            sliver_index,
-            signature: None,
-        };
+        self.challenge_captured(blob_id, sliver_index, None).await
+    }
+
+    /// 向存儲節點發送挑戰，並將每次嘗試的 HTTP 交換記錄到捕獲會話
+    ///
+    /// `capture` 為 `None` 時與 [`StorageNodeClient::challenge`] 完全相同
+    pub async fn challenge_captured(
+        &self,
+        blob_id: &BlobId,
+        sliver_index: u64,
+        capture: Option<&HttpCapture>,
+    ) -> Result<ChallengeResponse> {
+        self.challenge_timed(blob_id, sliver_index, capture).await.0
+    }
+
+    /// 與 [`StorageNodeClient::challenge_captured`] 相同，另返回嘗試次數與耗時（失敗時也有）
+    pub async fn challenge_timed(
+        &self,
+        blob_id: &BlobId,
+        sliver_index: u64,
+        capture: Option<&HttpCapture>,
+    ) -> (Result<ChallengeResponse>, ChallengeTiming) {
+        let request = ChallengeRequest::new(blob_id.to_string(), sliver_index);
 
         info!(
             "Challenging storage node {} for blob {} sliver {}",
@@ -200,24 +508,65 @@ impl StorageNodeClient {
         );
 
         // 帶重試的請求
-        self.challenge_with_retry(request).await
+        self.challenge_with_retry(request, capture).await
+    }
+
+    /// 發送 recovery symbol 挑戰，返回嘗試次數與耗時（失敗時也有）
+    ///
+    /// 響應的 `sliver_data` 為 symbol 數據；僅 [`ApiStyle::Challenge`] 風格的節點支持
+    pub async fn challenge_symbol_timed(
+        &self,
+        blob_id: &BlobId,
+        sliver_index: u64,
+        symbol_index: u64,
+        capture: Option<&HttpCapture>,
+    ) -> (Result<ChallengeResponse>, ChallengeTiming) {
+        let request = ChallengeRequest::for_symbol(blob_id.to_string(), sliver_index, symbol_index);
+
+        info!(
+            "Challenging storage node {} for blob {} sliver {} symbol {}",
+            self.base_url, blob_id, sliver_index, symbol_index
+        );
+
+        self.challenge_with_retry(request, capture).await
+    }
+
+    /// 發送經審計員 PQC 簽名的挑戰
+    ///
+    /// 簽名覆蓋 `(blob_id, sliver_index, timestamp, auditor_address)`，
+    /// 重試時重發同一請求（時間戳不變）。
+    /// Walrus 風格的 GET 端點不接受請求體，簽名不會發送
+    pub async fn challenge_signed(
+        &self,
+        blob_id: &BlobId,
+        sliver_index: u64,
+        auditor_address: &str,
+        signer: &dyn Signer,
+    ) -> Result<ChallengeResponse> {
+        let mut request = ChallengeRequest::new(blob_id.to_string(), sliver_index);
+        request.sign(signer, auditor_address, Utc::now().timestamp() as u64)?;
+
+        info!(
+            "Challenging storage node {} for blob {} sliver {} (signed by {})",
+            self.base_url, blob_id, sliver_index, auditor_address
+        );
+
+        self.challenge_with_retry(request, None).await.0
     }
 
     /// 帶重試邏輯的挑戰請求
-    async fn challenge_with_retry(&self, request: ChallengeRequest) -> Result<ChallengeResponse> {
-        let url = format!("{}/v1/challenge", self.base_url);
+    async fn challenge_with_retry(
+        &self,
+        request: ChallengeRequest,
+        capture: Option<&HttpCapture>,
+    ) -> (Result<ChallengeResponse>, ChallengeTiming) {
+        let started = Instant::now();
+        let timing = |attempt: u32| ChallengeTiming {
+            attempts: u8::try_from(attempt + 1).unwrap_or(u8::MAX),
+            elapsed: started.elapsed(),
+        };
 
         for attempt in 0..=self.max_retries {
-            if attempt > 0 {
-                // 指數退避: 2^(attempt-1) 秒
-                let delay_secs = 2u64.pow(attempt - 1);
-                warn!(
-                    "Retry attempt {}/{} after {}s delay",
-                    attempt, self.max_retries, delay_secs
-                );
-                tokio::time::sleep(Duration::from_secs(delay_secs)).await;
-            }
-
             debug!(
                 "Sending challenge (attempt {}/{}): {:?}",
                 attempt + 1,
@@ -225,28 +574,62 @@ impl StorageNodeClient {
                 request
             );
 
-            match self.send_challenge_request(&url, &request).await {
+            let outcome = match self.api_style {
+                ApiStyle::Challenge => self.send_challenge_request(&request, capture).await,
+                ApiStyle::Walrus => self.fetch_walrus_sliver(&request, capture).await,
+            };
+            match outcome {
                 Ok(response) => {
                     info!(
                         "Challenge successful on attempt {}: received {} bytes",
                         attempt + 1,
                         response.sliver_data.len()
                     );
-                    return Ok(response);
+                    return (Ok(response), timing(attempt));
                 }
                 Err(e) => {
+                    if let Some(metrics) = &self.metrics {
+                        metrics.record_storage_node_error(&e);
+                    }
+
                     if attempt == self.max_retries {
                         error!("Challenge failed after {} attempts: {}", attempt + 1, e);
-                        return Err(e);
+                        return (Err(e), timing(attempt));
                     }
 
                     // 判斷是否應該重試
                     if !self.should_retry(&e) {
                         error!("Non-retryable error: {}", e);
-                        return Err(e);
+                        return (Err(e), timing(attempt));
+                    }
+
+                    // 節點要求的等待時間優先，否則指數退避 2^attempt 秒（加抖動）
+                    let delay = match &e {
+                        AuditorError::StorageNodeOverloaded {
+                            retry_after: Some(retry_after),
+                            ..
+                        } => *retry_after,
+                        _ => with_jitter(Duration::from_secs(2u64.pow(attempt))),
+                    };
+                    if started.elapsed() + delay > self.retry_budget {
+                        error!(
+                            "Challenge retry budget of {}s exhausted after {} attempts \
+                             (next delay {:.1}s): {}",
+                            self.retry_budget.as_secs(),
+                            attempt + 1,
+                            delay.as_secs_f64(),
+                            e
+                        );
+                        return (Err(e), timing(attempt));
                     }
 
-                    warn!("Challenge attempt {} failed (retryable): {}", attempt + 1, e);
+                    warn!(
+                        "Challenge attempt {} failed (retryable), retrying in {:.1}s: {}",
+                        attempt + 1,
+                        delay.as_secs_f64(),
+                        e
+                    );
+                    tokio::time::sleep(delay).await;
                 }
             }
         }
@@ -254,19 +637,196 @@ impl StorageNodeClient {
         unreachable!()
     }
 
-    /// 實際發送 HTTP 請求
+    /// 發送 `POST /v1/challenge` 並解析響應
     async fn send_challenge_request(
         &self,
-        url: &str,
         request: &ChallengeRequest,
+        capture: Option<&HttpCapture>,
     ) -> Result<ChallengeResponse> {
+        let url = format!("{}/v1/challenge", self.base_url);
+        let http_request = self.http_client.post(&url).json(request).build()?;
+        let body = self.send(http_request, capture).await?;
+
+        let challenge_response = serde_json::from_slice::<ChallengeResponse>(&body).map_err(|e| {
+            AuditorError::Serialization(format!("Failed to parse challenge response: {}", e))
+        })?;
+
+        // 驗證響應有效性
+        if challenge_response.sliver_data.is_empty() {
+            return Err(AuditorError::InvalidSliver(
+                "Received empty sliver data".to_string(),
+            ));
+        }
+
+        if challenge_response.merkle_proof.is_empty() {
+            warn!("Received empty merkle proof - this may be invalid");
+        }
+
+        debug!(
+            "Challenge response: {} bytes sliver, {} bytes proof",
+            challenge_response.sliver_data.len(),
+            challenge_response.merkle_proof.len()
+        );
+
+        Ok(challenge_response)
+    }
+
+    /// 經 Walrus GET 端點取得 sliver，並由 Blob 元數據的哈希列表構建默克爾證明
+    ///
+    /// 哈希列表來自被審計的節點本身，並不可信：證明最終由審計員對照鏈上默克爾根驗證，
+    /// 列表與 sliver 不符或與鏈上根不符都會使驗證失敗
+    async fn fetch_walrus_sliver(
+        &self,
+        request: &ChallengeRequest,
+        capture: Option<&HttpCapture>,
+    ) -> Result<ChallengeResponse> {
+        if request.symbol_index.is_some() {
+            return Err(AuditorError::InvalidSliver(
+                "recovery symbol challenges are not supported by the walrus API style".to_string(),
+            ));
+        }
+
+        let tree = self.walrus_tree(&request.blob_id, capture).await?;
+        let leaf_index = usize::try_from(request.sliver_index)
+            .ok()
+            .filter(|&index| index < tree.leaf_count())
+            .ok_or_else(|| {
+                AuditorError::InvalidSliver(format!(
+                    "sliver index {} out of range: blob metadata lists {} sliver pairs",
+                    request.sliver_index,
+                    tree.leaf_count()
+                ))
+            })?;
+
+        let url = format!(
+            "{}/v1/blobs/{}/slivers/{}/primary",
+            self.base_url, request.blob_id, request.sliver_index
+        );
+        let http_request = self
+            .http_client
+            .get(&url)
+            .header(ACCEPT, "application/octet-stream")
+            .build()?;
+        let sliver_data = self.send(http_request, capture).await?;
+
+        if sliver_data.is_empty() {
+            return Err(AuditorError::InvalidSliver(
+                "Received empty sliver data".to_string(),
+            ));
+        }
+
+        let merkle_proof = tree
+            .generate_proof(leaf_index)
+            .map_err(|e| AuditorError::InvalidSliver(e.to_string()))?
+            .to_bytes();
+
+        debug!(
+            "Walrus sliver {}: {} bytes sliver, proof built from {} hashes",
+            request.sliver_index,
+            sliver_data.len(),
+            tree.leaf_count()
+        );
+
+        Ok(ChallengeResponse {
+            sliver_data,
+            merkle_proof,
+            symbol_index: None,
+            symbol_proof: Vec::new(),
+            node_signature: None,
+            timestamp: None,
+        })
+    }
+
+    /// Blob 的默克爾樹（由 `GET /v1/blobs/{blob_id}/metadata` 的哈希列表構建，按 Blob 緩存）
+    async fn walrus_tree(
+        &self,
+        blob_id: &str,
+        capture: Option<&HttpCapture>,
+    ) -> Result<Arc<MerkleTree>> {
+        if let Some((cached_id, tree)) = &*self.walrus_tree.lock().unwrap() {
+            if cached_id == blob_id {
+                return Ok(Arc::clone(tree));
+            }
+        }
+
+        let url = format!("{}/v1/blobs/{}/metadata", self.base_url, blob_id);
+        let http_request = self
+            .http_client
+            .get(&url)
+            .header(ACCEPT, "application/json")
+            .build()?;
+        let body = self.send(http_request, capture).await?;
+
+        let metadata = serde_json::from_slice::<WalrusBlobMetadata>(&body).map_err(|e| {
+            AuditorError::Serialization(format!("Failed to parse blob metadata: {}", e))
+        })?;
+        let leaves = metadata
+            .hashes
+            .iter()
+            .map(|pair| {
+                hex::decode(&pair.primary_hash)
+                    .ok()
+                    .and_then(|hash| <[u8; 32]>::try_from(hash).ok())
+                    .ok_or_else(|| {
+                        AuditorError::Serialization(format!(
+                            "Invalid sliver hash in blob metadata: {}",
+                            pair.primary_hash
+                        ))
+                    })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let tree = MerkleTree::from_leaf_hashes(leaves, MerkleTreeVersion::V2).map_err(|e| {
+            AuditorError::InvalidSliver(format!("Blob metadata lists no slivers: {}", e))
+        })?;
+
+        let tree = Arc::new(tree);
+        *self.walrus_tree.lock().unwrap() = Some((blob_id.to_string(), Arc::clone(&tree)));
+        Ok(tree)
+    }
+
+    /// 實際發送 HTTP 請求，返回 2xx 響應體
+    ///
+    /// 負責熔斷、HTTP 捕獲與按狀態碼映射錯誤（兩種 API 風格共用）
+    async fn send(
+        &self,
+        mut http_request: Request,
+        capture: Option<&HttpCapture>,
+    ) -> Result<Vec<u8>> {
+        *http_request.timeout_mut() = Some(self.timeout);
+        if let Some(breaker) = &self.breaker {
+            breaker.acquire(&self.base_url)?;
+        }
+        self.throttle().await;
+
+        let started = Instant::now();
+        let method = http_request.method().clone();
+        let url = http_request.url().to_string();
+        let request_headers = capture.map(|_| http_request.headers().clone());
+        let request_body = capture.and_then(|_| {
+            http_request
+                .body()
+                .and_then(|b| b.as_bytes())
+                .map(|b| b.to_vec())
+        });
+
         let response = self
             .http_client
-            .post(url)
-            .json(request)
-            .send()
+            .execute(http_request)
             .await
             .map_err(|e| {
+                if let Some(capture) = capture {
+                    let _ = capture.record(HttpExchange {
+                        method: method.as_str(),
+                        url: &url,
+                        request_headers: request_headers.as_ref(),
+                        request_body: request_body.as_deref(),
+                        duration: started.elapsed(),
+                        error: Some(e.to_string()),
+                        ..Default::default()
+                    });
+                }
+                self.record_outcome(false);
+
                 if e.is_timeout() {
                     AuditorError::StorageNodeUnreachable(format!(
                         "{}: request timeout after {}s",
@@ -284,14 +844,52 @@ impl StorageNodeClient {
             })?;
 
         let status = response.status();
+        let retry_after = response
+            .headers()
+            .get(RETRY_AFTER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| parse_retry_after(value, Utc::now()));
+        let response_headers = capture.map(|_| response.headers().clone());
+        self.record_outcome(!is_failure_status(status.as_u16()));
+
+        let body = response.bytes().await.map_err(|e| {
+            AuditorError::StorageNodeUnreachable(format!(
+                "{}: failed to read response body - {}",
+                self.base_url, e
+            ))
+        })?;
+
+        if let Some(capture) = capture {
+            capture.record(HttpExchange {
+                method: method.as_str(),
+                url: &url,
+                request_headers: request_headers.as_ref(),
+                request_body: request_body.as_deref(),
+                status: Some(status.as_u16()),
+                response_headers: response_headers.as_ref(),
+                response_body: Some(&body),
+                duration: started.elapsed(),
+                ..Default::default()
+            })?;
+        }
 
         if !status.is_success() {
-            let error_body = response
-                .text()
-                .await
-                .unwrap_or_else(|_| "Unable to read error body".to_string());
+            // 響應體可能是 HTML 或本地化文本：清洗並截斷後再寫入錯誤信息
+            let error_body = NodeErrorBody::parse(&body, self.max_error_body_len);
 
-            return Err(if status.is_client_error() {
+            return Err(if matches!(
+                status,
+                StatusCode::TOO_MANY_REQUESTS | StatusCode::SERVICE_UNAVAILABLE
+            ) {
+                // 429 / 503 - 過載（按 Retry-After 重試）
+                AuditorError::StorageNodeOverloaded {
+                    message: format!(
+                        "HTTP {}: {} - {}",
+                        status, status.canonical_reason().unwrap_or("Unknown"), error_body
+                    ),
+                    retry_after,
+                }
+            } else if status.is_client_error() {
                 // 4xx - 客戶端錯誤（不重試）
                 AuditorError::InvalidSliver(format!(
                     "HTTP {}: {} - {}",
@@ -306,28 +904,14 @@ impl StorageNodeClient {
             });
         }
 
-        let challenge_response = response.json::<ChallengeResponse>().await.map_err(|e| {
-            AuditorError::Serialization(format!("Failed to parse challenge response: {}", e))
-        })?;
-
-        // 驗證響應有效性
-        if challenge_response.sliver_data.is_empty() {
-            return Err(AuditorError::InvalidSliver(
-                "Received empty sliver data".to_string(),
-            ));
-        }
+        Ok(body.to_vec())
+    }
 
-        if challenge_response.merkle_proof.is_empty() {
-            warn!("Received empty merkle proof - this may be invalid");
+    /// 向熔斷器報告一次請求的結果（429 與 5xx 計為失敗）
+    fn record_outcome(&self, success: bool) {
+        if let Some(breaker) = &self.breaker {
+            breaker.record(&self.base_url, success);
         }
-
-        debug!(
-            "Challenge response: {} bytes sliver, {} bytes proof",
-            challenge_response.sliver_data.len(),
-            challenge_response.merkle_proof.len()
-        );
-
-        Ok(challenge_response)
     }
 
     /// 判斷錯誤是否應該重試
@@ -336,6 +920,12 @@ impl StorageNodeClient {
             // 網絡錯誤 - 重試
             AuditorError::StorageNodeUnreachable(_) => true,
 
+            // 過載 - 按 Retry-After 或退避重試
+            AuditorError::StorageNodeOverloaded { .. } => true,
+
+            // 熔斷打開 - 不重試（重試只會放大故障端點的負載）
+            AuditorError::CircuitOpen { .. } => false,
+
             // 邏輯錯誤 - 不重試
             AuditorError::InvalidSliver(_) => false,
             AuditorError::MerkleVerificationFailed => false,
@@ -356,8 +946,9 @@ impl StorageNodeClient {
         let url = format!("{}/health", self.base_url);
 
         debug!("Performing health check on {}", self.base_url);
+        self.throttle().await;
 
-        match self.http_client.get(&url).send().await {
+        match self.http_client.get(&url).timeout(self.timeout).send().await {
             Ok(response) => {
                 if response.status().is_success() {
                     // 嘗試解析詳細的健康狀態
@@ -394,10 +985,12 @@ impl StorageNodeClient {
     /// 獲取詳細的健康狀態
     pub async fn get_health_status(&self) -> Result<HealthCheckResponse> {
         let url = format!("{}/health", self.base_url);
+        self.throttle().await;
 
         let response = self
             .http_client
             .get(&url)
+            .timeout(self.timeout)
             .send()
             .await
             .map_err(|e| {
@@ -416,15 +1009,39 @@ impl StorageNodeClient {
         })
     }
 
+    /// 啟用限流時等待本節點主機的令牌
+    async fn throttle(&self) {
+        if let Some(rate_limiter) = &self.rate_limiter {
+            rate_limiter.acquire(&self.base_url).await;
+        }
+    }
+
     /// 獲取節點基礎 URL
     pub fn base_url(&self) -> &str {
         &self.base_url
     }
 }
 
+/// 對計算出的退避時間加 ±[`RETRY_JITTER`] 的隨機抖動
+fn with_jitter(delay: Duration) -> Duration {
+    delay.mul_f64(rand::thread_rng().gen_range(1.0 - RETRY_JITTER..=1.0 + RETRY_JITTER))
+}
+
+/// 解析 `Retry-After` 頭：秒數或 HTTP 日期（已過去的日期視為立即重試）
+pub fn parse_retry_after(value: &str, now: DateTime<Utc>) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(secs) = value.parse::<u64>() {
+        return Some(Duration::from_secs(secs));
+    }
+
+    let at = DateTime::parse_from_rfc2822(value).ok()?;
+    Some((at.with_timezone(&Utc) - now).to_std().unwrap_or(Duration::ZERO))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use pqc_signer::Dilithium3Signer;
 
     #[test]
     fn test_client_creation() {
@@ -441,6 +1058,104 @@ mod tests {
         assert_eq!(client.timeout, Duration::from_secs(10));
     }
 
+    #[test]
+    fn test_challenge_request_keeps_large_sliver_index() {
+        let request = ChallengeRequest::new("0xabcd", 70_000);
+        let json = serde_json::to_value(&request).unwrap();
+        assert_eq!(json["sliver_index"], 70_000);
+        assert!(json.get("signature").is_none());
+    }
+
+    #[test]
+    fn test_symbol_challenge_request_covers_symbol_index() {
+        let full = ChallengeRequest::new("0xabcd", 42);
+        assert!(serde_json::to_value(&full).unwrap().get("symbol_index").is_none());
+
+        let symbol = ChallengeRequest::for_symbol("0xabcd", 42, 7);
+        assert_eq!(serde_json::to_value(&symbol).unwrap()["symbol_index"], 7);
+
+        // 完整 sliver 挑戰的簽名字節不變，symbol 索引追加在末尾
+        let payload = symbol.signing_payload();
+        assert!(payload.starts_with(&full.signing_payload()));
+        assert_ne!(payload, ChallengeRequest::for_symbol("0xabcd", 42, 8).signing_payload());
+    }
+
+    fn signed_request(signer: &Dilithium3Signer) -> ChallengeRequest {
+        let mut request = ChallengeRequest::new("0xabcd", 42);
+        request.sign(signer, "0xa11ce", 1_700_000_000).unwrap();
+        request
+    }
+
+    #[test]
+    fn test_signed_challenge_request_verifies() {
+        let mut signer = Dilithium3Signer::new();
+        signer.generate_keypair().unwrap();
+
+        let request = signed_request(&signer);
+        assert_eq!(request.auditor_public_key.as_deref(), Some(signer.public_key()));
+        assert_eq!(request.pqc_algorithm, Some(3));
+        assert!(request.verify_signature(signer.public_key()).unwrap());
+
+        // 經 JSON 往返（存儲節點側）後仍可驗證
+        let json = serde_json::to_string(&request).unwrap();
+        let received: ChallengeRequest = serde_json::from_str(&json).unwrap();
+        assert!(received.verify_signature(signer.public_key()).unwrap());
+
+        // 其他審計員的公鑰
+        let mut other = Dilithium3Signer::new();
+        other.generate_keypair().unwrap();
+        assert!(!request.verify_signature(other.public_key()).unwrap());
+
+        // 未簽名的請求
+        let unsigned = ChallengeRequest::new("0xabcd", 42);
+        assert!(!unsigned.verify_signature(signer.public_key()).unwrap());
+    }
+
+    #[test]
+    fn test_tampered_challenge_request_fails() {
+        let mut signer = Dilithium3Signer::new();
+        signer.generate_keypair().unwrap();
+        let pk = signer.public_key();
+
+        let mut tampered = signed_request(&signer);
+        tampered.blob_id = "0xabce".to_string();
+        assert!(!tampered.verify_signature(pk).unwrap());
+
+        let mut tampered = signed_request(&signer);
+        tampered.sliver_index = 43;
+        assert!(!tampered.verify_signature(pk).unwrap());
+
+        let mut tampered = signed_request(&signer);
+        tampered.timestamp = Some(1_700_000_001);
+        assert!(!tampered.verify_signature(pk).unwrap());
+
+        let mut tampered = signed_request(&signer);
+        tampered.auditor_address = Some("0xb0b".to_string());
+        assert!(!tampered.verify_signature(pk).unwrap());
+
+        let mut tampered = signed_request(&signer);
+        tampered.signature.as_mut().unwrap()[0] ^= 0x01;
+        assert!(!tampered.verify_signature(pk).unwrap());
+
+        // 長度前綴使字段邊界無法移動
+        let mut shifted = signed_request(&signer);
+        shifted.blob_id = "0xabcd0".to_string();
+        shifted.auditor_address = Some("xa11ce".to_string());
+        assert!(!shifted.verify_signature(pk).unwrap());
+    }
+
+    #[test]
+    fn test_challenge_request_replay_window() {
+        let mut signer = Dilithium3Signer::new();
+        signer.generate_keypair().unwrap();
+        let request = signed_request(&signer);
+
+        assert!(request.is_within_window(1_700_000_000 + 300, 300));
+        assert!(request.is_within_window(1_700_000_000 - 300, 300));
+        assert!(!request.is_within_window(1_700_000_000 + 301, 300));
+        assert!(!ChallengeRequest::new("0xabcd", 42).is_within_window(1_700_000_000, 300));
+    }
+
     #[test]
     fn test_should_retry() {
         let client = StorageNodeClient::new("http://localhost:8080".to_string());
@@ -449,10 +1164,268 @@ mod tests {
         assert!(client.should_retry(&AuditorError::StorageNodeUnreachable(
             "timeout".to_string()
         )));
+        assert!(client.should_retry(&AuditorError::StorageNodeOverloaded {
+            message: "HTTP 429".to_string(),
+            retry_after: None,
+        }));
 
         // 不應該重試的錯誤
         assert!(!client.should_retry(&AuditorError::InvalidSliver("bad index".to_string())));
         assert!(!client.should_retry(&AuditorError::MerkleVerificationFailed));
+        assert!(!client.should_retry(&AuditorError::CircuitOpen {
+            endpoint: "http://localhost:8080".to_string(),
+            retry_after_secs: 30,
+        }));
+    }
+
+    #[tokio::test]
+    async fn test_overloaded_node_opens_circuit() {
+        use crate::breaker::{BreakerConfig, CircuitState};
+        use crate::test_support::FakeStorageNode;
+        use axum::http::StatusCode;
+
+        let node = FakeStorageNode::start(
+            StatusCode::TOO_MANY_REQUESTS,
+            "text/plain",
+            b"slow down".to_vec(),
+        )
+        .await;
+        let breaker = Arc::new(CircuitBreaker::new(BreakerConfig {
+            min_requests: 2,
+            ..BreakerConfig::default()
+        }));
+        let client = StorageNodeClient::with_config(node.url().to_string(), 5, 0)
+            .with_breaker(Arc::clone(&breaker));
+
+        for _ in 0..2 {
+            let err = client.challenge(&BlobId::from_bytes([0; 32]), 0).await.unwrap_err();
+            assert!(matches!(err, AuditorError::StorageNodeOverloaded { .. }));
+        }
+        assert_eq!(breaker.state(node.url()), CircuitState::Open);
+
+        // 熔斷打開後不再發出請求
+        let err = client.challenge(&BlobId::from_bytes([0; 32]), 0).await.unwrap_err();
+        assert!(matches!(err, AuditorError::CircuitOpen { .. }));
+        assert_eq!(breaker.status()[0].requests, 2);
+    }
+
+    #[tokio::test]
+    async fn test_failed_attempts_are_counted_by_kind() {
+        use crate::test_support::FakeStorageNode;
+        use axum::http::StatusCode;
+
+        let node = FakeStorageNode::start(
+            StatusCode::BAD_GATEWAY,
+            "text/plain",
+            b"upstream down".to_vec(),
+        )
+        .await;
+        let metrics = Arc::new(Metrics::new());
+        let client = StorageNodeClient::with_config(node.url().to_string(), 5, 1)
+            .with_metrics(Arc::clone(&metrics));
+
+        // 5xx 可重試：兩次嘗試都計入
+        let err = client.challenge(&BlobId::from_bytes([0; 32]), 0).await.unwrap_err();
+        assert!(matches!(err, AuditorError::StorageNodeUnreachable(_)));
+        assert!(metrics
+            .render()
+            .contains("storage_node_errors_total{kind=\"unreachable\"} 2"));
+    }
+
+    #[test]
+    fn test_parse_retry_after() {
+        let now = DateTime::parse_from_rfc2822("Wed, 21 Oct 2015 07:28:00 GMT")
+            .unwrap()
+            .with_timezone(&Utc);
+
+        assert_eq!(parse_retry_after("120", now), Some(Duration::from_secs(120)));
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2015 07:28:30 GMT", now),
+            Some(Duration::from_secs(30))
+        );
+        // 已過去的日期：立即重試
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2015 07:27:00 GMT", now),
+            Some(Duration::ZERO)
+        );
+        assert_eq!(parse_retry_after("-1", now), None);
+        assert_eq!(parse_retry_after("soon", now), None);
+    }
+
+    /// 非空 sliver 的有效挑戰響應
+    async fn healthy_node() -> crate::test_support::FakeStorageNode {
+        crate::test_support::FakeStorageNode::start(
+            axum::http::StatusCode::OK,
+            "application/json",
+            br#"{"sliver_data":[1,2,3],"merkle_proof":[4]}"#.to_vec(),
+        )
+        .await
+    }
+
+    #[tokio::test]
+    async fn test_retry_honors_retry_after_seconds() {
+        let node = healthy_node().await;
+        node.respond_next(axum::http::StatusCode::TOO_MANY_REQUESTS, Some("1"));
+        let client = StorageNodeClient::with_config(node.url().to_string(), 5, 3);
+
+        let started = Instant::now();
+        let response = client.challenge(&BlobId::from_bytes([0; 32]), 0).await.unwrap();
+
+        assert_eq!(response.sliver_data, vec![1, 2, 3]);
+        assert_eq!(node.requests(), 2);
+        assert!(started.elapsed() >= Duration::from_secs(1));
+    }
+
+    #[tokio::test]
+    async fn test_unavailable_without_retry_after_backs_off_with_jitter() {
+        let node = healthy_node().await;
+        node.respond_next(axum::http::StatusCode::SERVICE_UNAVAILABLE, None);
+        let client = StorageNodeClient::with_config(node.url().to_string(), 5, 3);
+
+        let started = Instant::now();
+        client.challenge(&BlobId::from_bytes([0; 32]), 0).await.unwrap();
+        let elapsed = started.elapsed();
+
+        // 首次退避 1s ± 20%
+        assert_eq!(node.requests(), 2);
+        assert!(elapsed >= Duration::from_millis(800), "{:?}", elapsed);
+        assert!(elapsed < Duration::from_millis(1_700), "{:?}", elapsed);
+    }
+
+    #[tokio::test]
+    async fn test_retry_budget_exhausted() {
+        let node = healthy_node().await;
+        node.respond_next(axum::http::StatusCode::TOO_MANY_REQUESTS, Some("30"));
+        let client = StorageNodeClient::with_config(node.url().to_string(), 5, 3)
+            .with_retry_budget(Duration::from_secs(2));
+
+        let started = Instant::now();
+        let err = client.challenge(&BlobId::from_bytes([0; 32]), 0).await.unwrap_err();
+
+        // 下一次等待超出時限：不睡眠，直接返回過載錯誤
+        match err {
+            AuditorError::StorageNodeOverloaded { retry_after, .. } => {
+                assert_eq!(retry_after, Some(Duration::from_secs(30)))
+            }
+            other => panic!("expected StorageNodeOverloaded, got {}", other),
+        }
+        assert_eq!(node.requests(), 1);
+        assert!(started.elapsed() < Duration::from_secs(2));
+    }
+
+    /// 觀察到的請求速率不超過令牌桶：第 k 個請求不早於 (k - burst + 1) / rate 秒到達
+    fn assert_within_rate(times: &[Instant], rate: u32, burst: u32) {
+        let first = times[0];
+        for (k, time) in times.iter().enumerate().skip(burst as usize) {
+            let earliest = Duration::from_secs_f64((k as u32 - burst + 1) as f64 / rate as f64);
+            let observed = time.duration_since(first);
+            assert!(
+                observed + Duration::from_millis(20) >= earliest,
+                "request {} arrived after {:?}, expected at least {:?}",
+                k,
+                observed,
+                earliest
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_clients_share_rate_limit_per_host() {
+        use crate::rate_limit::RateLimiter;
+
+        let node = healthy_node().await;
+        let limiter = Arc::new(RateLimiter::new(10, 2));
+        // 兩個客戶端訪問同一主機，共用一個桶
+        let clients: Vec<_> = (0..2)
+            .map(|_| {
+                StorageNodeClient::with_config(node.url().to_string(), 5, 0)
+                    .with_rate_limiter(Arc::clone(&limiter))
+            })
+            .collect();
+
+        let started = Instant::now();
+        let challenges = (0..8u64).map(|i| {
+            let client = &clients[i as usize % 2];
+            async move { client.challenge(&BlobId::from_bytes([0; 32]), i).await }
+        });
+        for result in futures::future::join_all(challenges).await {
+            result.unwrap();
+        }
+
+        // 突發 2 個，其餘 6 個每 100ms 一個
+        assert_eq!(node.requests(), 8);
+        assert!(started.elapsed() >= Duration::from_millis(580));
+        assert_within_rate(&node.request_times(), 10, 2);
+    }
+
+    #[tokio::test]
+    async fn test_unlimited_rate_does_not_wait() {
+        use crate::rate_limit::RateLimiter;
+
+        let node = healthy_node().await;
+        let client = StorageNodeClient::with_config(node.url().to_string(), 5, 0)
+            .with_rate_limiter(Arc::new(RateLimiter::new(0, 10)));
+
+        let started = Instant::now();
+        let challenges = (0..20u64).map(|i| client.challenge(&BlobId::from_bytes([0; 32]), i));
+        for result in futures::future::join_all(challenges).await {
+            result.unwrap();
+        }
+
+        assert_eq!(node.requests(), 20);
+        assert!(started.elapsed() < Duration::from_secs(1));
+    }
+
+    #[tokio::test]
+    async fn test_waiting_for_token_is_not_an_attempt() {
+        use crate::rate_limit::RateLimiter;
+
+        let node = healthy_node().await;
+        node.respond_next(axum::http::StatusCode::SERVICE_UNAVAILABLE, Some("0"));
+        let client = StorageNodeClient::with_config(node.url().to_string(), 5, 1)
+            .with_rate_limiter(Arc::new(RateLimiter::new(2, 1)));
+
+        // 重試立即進行，但要等 500ms 才有令牌；等待不消耗重試次數
+        let (result, timing) = client
+            .challenge_timed(&BlobId::from_bytes([0; 32]), 0, None)
+            .await;
+
+        result.unwrap();
+        assert_eq!(timing.attempts, 2);
+        assert_eq!(node.requests(), 2);
+        assert_within_rate(&node.request_times(), 2, 1);
+    }
+
+    #[tokio::test]
+    async fn test_walrus_style_builds_proof_from_metadata() {
+        use crate::crypto::merkle::{hash_leaf, MerkleProof};
+
+        let slivers: Vec<Vec<u8>> = (0..5u8).map(|i| vec![i; 64 + i as usize]).collect();
+        let root = MerkleTree::from_leaf_hashes(
+            slivers.iter().map(|sliver| hash_leaf(sliver)).collect(),
+            MerkleTreeVersion::V2,
+        )
+        .unwrap()
+        .root();
+        let node = crate::test_support::FakeStorageNode::start_walrus(slivers.clone()).await;
+        // 元數據請求先過載一次：兩個端點共用重試
+        node.respond_next(axum::http::StatusCode::SERVICE_UNAVAILABLE, Some("0"));
+        let client = StorageNodeClient::with_config(node.url().to_string(), 5, 3)
+            .with_api_style(ApiStyle::Walrus);
+        let blob_id = BlobId::from_bytes([7; 32]);
+
+        for index in [3, 0] {
+            let response = client.challenge(&blob_id, index).await.unwrap();
+            assert_eq!(response.sliver_data, slivers[index as usize]);
+            let proof = MerkleProof::from_bytes(&response.merkle_proof).unwrap();
+            assert!(proof.verify(&response.sliver_data, &root, slivers.len() as u64));
+        }
+        // 過載的元數據請求 + 重試 + 兩個 sliver：元數據只取一次
+        assert_eq!(node.requests(), 4);
+
+        let err = client.challenge(&blob_id, 5).await.unwrap_err();
+        assert!(matches!(err, AuditorError::InvalidSliver(_)), "{}", err);
+        assert_eq!(node.requests(), 4);
     }
 
     // 集成測試需要實際的存儲節點或 mockito
@@ -468,7 +1441,7 @@ mod tests {
     #[ignore] // 需要實際的存儲節點
     async fn test_challenge_integration() {
         let client = StorageNodeClient::new("http://localhost:8080".to_string());
-        let result = client.challenge("test_blob_id", 0).await;
+        let result = client.challenge(&BlobId::from_bytes([0; 32]), 0).await;
         // 根據實際情況驗證結果
         println!("{:?}", result);
     }
@@ -2,9 +2,13 @@
 //!
 //! 負責與 Sui 區塊鏈交互:
 //! - 查詢 Walrus Blob 對象元數據
-//! - 提交審計報告交易
+//! - 提交審計記錄（以審計員的 Sui 密鑰簽名，執行需要 `sui-sdk` feature）
+//! - 為審計報告創建訪問策略（構建與簽名走 JSON-RPC，執行需要 `sui-sdk` feature）
 //! - 查詢審計配置
 //! - 管理審計員聲譽
+//! - 查找審計記錄（JSON-RPC，不依賴 `sui-sdk` feature）
+//! - 由 Blob ID 查找 Blob 對象 ID（按審計記錄，需要 `sui-sdk` feature）
+//! - 分頁列出待審計的 Blob（守護模式使用）
 //!
 //! # 架構說明
 //!
@@ -18,20 +22,67 @@
 //! - 使用 Sui SDK 的事務塊 API 構造交易
 //! - 支持 gas budget 配置
 
+use crate::chain_types::{
+    json_bytes, json_field, json_str, json_uint, AuditCreatedEvent, AuditRecordParams, MoveId,
+    MoveU256, OnChainAuditRecord, PolicyParams, ReportMetadataParams, AUDIT_CORE_MODULE,
+    REPORT_ACCESS_MODULE, SUI_CLOCK_OBJECT_ID,
+};
 use crate::error::{AuditorError, Result};
-use crate::types::{BlobMetadata, ObjectID as LocalObjectID};
+use crate::init::SuiKey;
+use crate::metadata_cache::BlobMetadataCache;
+use crate::onchain_keys::RegisteredKey;
+use crate::retry::{retry_with_exponential_backoff_if, RetryConfig};
+use crate::types::{validate_sui_address, BlobId, BlobMetadata, ObjectID as LocalObjectID};
+use base64::{engine::general_purpose, Engine as _};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
 use tracing::{debug, info, warn};
 
+/// 查找審計記錄時最多翻閱的事件頁數（每頁 50 個，最新的在前）
+const MAX_EVENT_PAGES: usize = 20;
+
+/// `suix_queryEvents` 單頁事件數上限
+const EVENT_PAGE_LIMIT: usize = 50;
+
+/// `sui_multiGetObjects` 單次查詢的對象數上限
+pub const MULTI_GET_OBJECTS_LIMIT: usize = 50;
+
+/// Walrus 主網與測試網的分片數
+pub const DEFAULT_WALRUS_N_SHARDS: u64 = 1000;
+
+/// SUI 代幣類型（gas 代幣）
+const SUI_COIN_TYPE: &str = "0x2::sui::SUI";
+
+/// Walrus `blob::Blob` 的 Move 模塊與結構名
+const WALRUS_BLOB_TYPE_SUFFIX: &str = "::blob::Blob";
+
+/// `audit_core::AuditRecord` 的 Move 模塊與結構名
+const AUDIT_RECORD_TYPE_SUFFIX: &str = "::audit_core::AuditRecord";
+
+/// 交易因 gas 不足失敗時錯誤信息中的標記
+const INSUFFICIENT_GAS_MARKERS: &[&str] = &["InsufficientGas", "GasBalanceTooLow"];
+
+/// 交易引用的對象版本已被消耗或被其他交易鎖定時錯誤信息中的標記
+const VERSION_CONFLICT_MARKERS: &[&str] =
+    &["ObjectVersionUnavailableForConsumption", "ObjectLockConflict"];
+
 // 條件編譯：只在啟用 sui-sdk feature 時導入
 #[cfg(feature = "sui-sdk")]
 use {
+    crate::chain_types::AUDITOR_REGISTRY_MODULE,
     sui_sdk::{
-        rpc_types::{SuiObjectDataOptions, SuiTransactionBlockResponseOptions},
+        rpc_types::SuiTransactionBlockResponseOptions,
         types::{
-            base_types::{ObjectID, SuiAddress},
+            base_types::{ObjectID, SequenceNumber, SuiAddress},
             programmable_transaction_builder::ProgrammableTransactionBuilder,
             quorum_driver_types::ExecuteTransactionRequestType,
-            transaction::{Argument, CallArg, Command, Transaction, TransactionData},
+            transaction::{
+                Argument, CallArg, Command, ObjectArg, ProgrammableTransaction, Transaction,
+                TransactionData, TransactionKind,
+            },
             Identifier,
         },
         SuiClient, SuiClientBuilder,
@@ -48,6 +99,71 @@ type ObjectID = String;
 
 // BlobMetadata 已移至 types.rs，統一使用 types::BlobMetadata
 
+/// 待審計的 Blob
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingBlob {
+    /// Walrus Blob ID（URL-safe Base64）
+    pub blob_id: String,
+
+    /// Blob 的 Sui 對象 ID（從最近一次審計記錄讀取，讀不到時為 `None`）
+    pub blob_object_id: Option<MoveId>,
+
+    /// 最近一次審計所在的 epoch
+    pub last_audit_epoch: u32,
+}
+
+/// [`AuditSystemClient::list_pending_audit_blobs`] 返回的一頁結果
+#[derive(Debug, Clone, Default)]
+pub struct PendingBlobPage {
+    /// 本頁中在目標 epoch 尚未審計的 Blob
+    pub pending: Vec<PendingBlob>,
+
+    /// 本頁中在目標 epoch 已審計過的 Blob ID
+    pub audited: Vec<String>,
+
+    /// 下一頁的游標；`None` 表示已翻到最早的事件
+    pub next_cursor: Option<Value>,
+}
+
+/// 已構建並簽名、尚未提交的交易（試運行的結果）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignedTransaction {
+    /// Base64 編碼的 `TransactionData`（BCS）
+    pub tx_bytes: String,
+
+    /// Base64 編碼的 Sui 簽名（`flag || signature || public_key`）
+    pub signature: String,
+
+    /// 發送者地址
+    pub sender: String,
+
+    /// 支付 gas 的 SUI 代幣對象 ID
+    pub gas_coin: String,
+
+    /// Gas budget（MIST）
+    pub gas_budget: u64,
+}
+
+/// 已上鏈的審計記錄
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SubmittedAuditRecord {
+    /// 交易摘要
+    pub digest: String,
+
+    /// 新建的 `AuditRecord` 共享對象 ID
+    pub audit_record_id: MoveId,
+}
+
+/// 已執行成功的交易
+#[derive(Debug, Clone)]
+struct ExecutedTransaction {
+    /// 交易摘要
+    digest: String,
+
+    /// JSON-RPC 格式的 `objectChanges`
+    object_changes: Value,
+}
+
 /// 審計系統客戶端
 ///
 /// 封裝與 Sui 區塊鏈上審計系統合約的所有交互
@@ -55,9 +171,12 @@ pub struct AuditSystemClient {
     #[cfg(feature = "sui-sdk")]
     sui_client: SuiClient,
 
-    #[cfg(not(feature = "sui-sdk"))]
+    /// Sui RPC URL（JSON-RPC 查詢在兩種編譯配置下都使用）
     rpc_url: String,
 
+    /// JSON-RPC 查詢使用的 HTTP 客戶端
+    http_client: Client,
+
     /// 審計系統合約的 Package ID
     audit_package_id: String,
 
@@ -75,6 +194,12 @@ pub struct AuditSystemClient {
 
     /// Gas budget (默認 10M MIST = 0.01 SUI)
     gas_budget: u64,
+
+    /// Walrus 分片數（Blob 對象本身不記錄）
+    n_shards: u64,
+
+    /// Blob 元數據緩存（未設置時每次查詢都請求 RPC）
+    metadata_cache: Option<Arc<BlobMetadataCache>>,
 }
 
 impl AuditSystemClient {
@@ -98,6 +223,12 @@ impl AuditSystemClient {
         registry_id: &str,
         audit_config_id: &str,
     ) -> Result<Self> {
+        Self::validate_ids(
+            audit_package_id,
+            access_package_id,
+            registry_id,
+            audit_config_id,
+        )?;
         info!("Connecting to Sui RPC at {}", rpc_url);
 
         let sui_client = SuiClientBuilder::default()
@@ -116,12 +247,16 @@ impl AuditSystemClient {
 
         Ok(Self {
             sui_client,
+            rpc_url: rpc_url.to_string(),
+            http_client: Self::http_client(),
             audit_package_id: audit_package_id.to_string(),
             access_package_id: access_package_id.to_string(),
             registry_id: registry_id.to_string(),
             audit_config_id: audit_config_id.to_string(),
             reward_pool_id: None,
             gas_budget: 10_000_000, // 0.01 SUI
+            n_shards: DEFAULT_WALRUS_N_SHARDS,
+            metadata_cache: None,
         })
     }
 
@@ -134,20 +269,56 @@ impl AuditSystemClient {
         registry_id: &str,
         audit_config_id: &str,
     ) -> Result<Self> {
+        Self::validate_ids(
+            audit_package_id,
+            access_package_id,
+            registry_id,
+            audit_config_id,
+        )?;
         info!("Creating AuditSystemClient (sui-sdk feature disabled)");
         warn!("Sui SDK is disabled - all blockchain operations will fail");
 
         Ok(Self {
             rpc_url: rpc_url.to_string(),
+            http_client: Self::http_client(),
             audit_package_id: audit_package_id.to_string(),
             access_package_id: access_package_id.to_string(),
             registry_id: registry_id.to_string(),
             audit_config_id: audit_config_id.to_string(),
             reward_pool_id: None,
             gas_budget: 10_000_000,
+            n_shards: DEFAULT_WALRUS_N_SHARDS,
+            metadata_cache: None,
         })
     }
 
+    /// 驗證合約與共享對象 ID 格式（可選的 ID 為空時跳過）
+    fn validate_ids(
+        audit_package_id: &str,
+        access_package_id: &str,
+        registry_id: &str,
+        audit_config_id: &str,
+    ) -> Result<()> {
+        validate_sui_address("audit package ID", audit_package_id)?;
+        for (name, id) in [
+            ("access policy package ID", access_package_id),
+            ("auditor registry ID", registry_id),
+            ("audit config ID", audit_config_id),
+        ] {
+            if !id.is_empty() {
+                validate_sui_address(name, id)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn http_client() -> Client {
+        Client::builder()
+            .timeout(std::time::Duration::from_secs(15))
+            .build()
+            .expect("Failed to build HTTP client")
+    }
+
     /// 設置 Gas Budget
     pub fn set_gas_budget(&mut self, budget: u64) {
         self.gas_budget = budget;
@@ -158,200 +329,270 @@ impl AuditSystemClient {
         self.reward_pool_id = Some(pool_id);
     }
 
+    /// 設置 Walrus 分片數（決定 Blob 元數據中的 `encoding_n` / `encoding_k`）
+    pub fn set_n_shards(&mut self, n_shards: u64) {
+        self.n_shards = n_shards;
+    }
+
+    /// 設置 Blob 元數據緩存（可與其他客戶端共享，見 [`crate::metadata_cache`]）
+    pub fn set_metadata_cache(&mut self, cache: Arc<BlobMetadataCache>) {
+        self.metadata_cache = Some(cache);
+    }
+
+    /// Blob 元數據緩存（未設置時為 `None`）
+    pub fn metadata_cache(&self) -> Option<&Arc<BlobMetadataCache>> {
+        self.metadata_cache.as_ref()
+    }
+
     // ============ Blob 元數據查詢 ============
 
     /// 讀取 Walrus Blob 對象的元數據
     ///
+    /// 通過 JSON-RPC（`sui_getObject`）查詢，不依賴 `sui-sdk` feature；
+    /// 解析見 [`parse_blob_object`]。設置了元數據緩存時先查緩存，讀取成功後寫入緩存。
+    ///
     /// # 參數
     /// - `blob_object_id`: Walrus Blob 對象的 ID（注意不是 blob_id u256）
     ///
-    /// # 返回
-    /// - `BlobMetadata`: Blob 的元數據，包括默克爾根、大小、epoch 等
-    ///
-    /// # 實現邏輯
-    /// 1. 調用 Sui RPC 獲取對象數據
-    /// 2. 解析對象的 Move 結構
-    /// 3. 提取所需字段並轉換為 BlobMetadata
-    #[cfg(feature = "sui-sdk")]
-    pub async fn get_blob_metadata(&self, blob_object_id: &str) -> Result<BlobMetadata> {
-        info!("Fetching blob metadata for object {}", blob_object_id);
+    /// # 錯誤
+    /// - 對象不是 Walrus Blob、已刪除或不存在: 返回 `SuiClient` 錯誤
+    /// - 字段與 Move 結構不符: 返回 `ChainAbi` 錯誤
+    pub async fn get_blob_metadata(&self, blob_object_id: &MoveId) -> Result<BlobMetadata> {
+        if let Some(metadata) = self
+            .metadata_cache
+            .as_ref()
+            .and_then(|cache| cache.get(blob_object_id))
+        {
+            debug!("Using cached metadata for blob object {}", blob_object_id);
+            return Ok(metadata);
+        }
 
-        let object_id = ObjectID::from_str(blob_object_id)
-            .map_err(|e| AuditorError::SuiClient(format!("Invalid object ID: {}", e)))?;
+        info!("Fetching blob metadata for object {}", blob_object_id);
 
-        // 獲取對象數據（包含內容）
-        let object_response = self
-            .sui_client
-            .read_api()
-            .get_object_with_options(
-                object_id,
-                SuiObjectDataOptions::new()
-                    .with_content()
-                    .with_owner()
-                    .with_type(),
+        let object = self
+            .rpc(
+                "sui_getObject",
+                json!([
+                    blob_object_id.to_string(),
+                    { "showType": true, "showOwner": true, "showContent": true }
+                ]),
             )
-            .await
-            .map_err(|e| AuditorError::SuiClient(format!("Failed to fetch object: {}", e)))?;
-
-        let object_data = object_response
-            .data
-            .ok_or_else(|| AuditorError::SuiClient("Object not found".to_string()))?;
-
-        debug!("Blob object type: {:?}", object_data.object_type);
-
-        // 解析對象內容
-        // 注意: 實際實現需要根據 Walrus Blob 對象的 Move 結構解析
-        // 這裡提供簡化版本，實際需要使用 sui_sdk 的 MoveStruct 解析
-
-        // TODO: 實際解析 Walrus Blob Move 結構
-        // 參考: contracts/walrus/sources/system/blob.move
+            .await?;
+
+        let metadata = parse_blob_object(&object, self.n_shards)?;
+        debug!(
+            "Blob {} (object {}): {} bytes, epochs {}-{}",
+            metadata.blob_id,
+            metadata.blob_object_id,
+            metadata.blob_size,
+            metadata.start_epoch,
+            metadata.end_epoch
+        );
+        if let Some(cache) = &self.metadata_cache {
+            cache.insert(*blob_object_id, metadata.clone());
+        }
+        Ok(metadata)
+    }
 
-        warn!("Using mock blob metadata - actual parsing not yet implemented");
+    /// 批量讀取 Walrus Blob 對象的元數據
+    ///
+    /// 已緩存的對象不再查詢，其餘去重後以 `sui_multiGetObjects` 每次最多
+    /// [`MULTI_GET_OBJECTS_LIMIT`] 個查詢，讀取成功的寫入緩存。返回值與 `ids` 一一對應：
+    /// 單個對象的錯誤（ID 格式錯誤、已刪除、不是 Walrus Blob 等）只影響對應的條目
+    ///
+    /// # 錯誤
+    /// - RPC 請求失敗或響應條目數與請求不符: 返回 `SuiClient` 錯誤
+    pub async fn get_blob_metadata_batch(&self, ids: &[&str]) -> Result<Vec<Result<BlobMetadata>>> {
+        let parsed: Vec<Result<MoveId>> = ids.iter().map(|id| MoveId::from_hex(id)).collect();
+
+        let mut found: HashMap<MoveId, Result<BlobMetadata>> = HashMap::new();
+        let mut missing = Vec::new();
+        let mut seen = HashSet::new();
+        for object_id in parsed.iter().flatten() {
+            if !seen.insert(*object_id) {
+                continue;
+            }
+            match self
+                .metadata_cache
+                .as_ref()
+                .and_then(|cache| cache.get(object_id))
+            {
+                Some(metadata) => {
+                    found.insert(*object_id, Ok(metadata));
+                }
+                None => missing.push(*object_id),
+            }
+        }
 
-        Ok(BlobMetadata {
-            merkle_root: vec![0u8; 32],
-            size: 1024,
-            encoded_size: 5120,
-            epoch: 1,
-            storage_nodes: vec![],
-            erasure_k: 10,
-            erasure_n: 15,
-        })
-    }
+        for chunk in missing.chunks(MULTI_GET_OBJECTS_LIMIT) {
+            info!("Fetching blob metadata for {} objects", chunk.len());
+            let objects = self
+                .rpc(
+                    "sui_multiGetObjects",
+                    json!([
+                        chunk.iter().map(MoveId::to_string).collect::<Vec<_>>(),
+                        { "showType": true, "showOwner": true, "showContent": true }
+                    ]),
+                )
+                .await?;
+            let objects = objects
+                .as_array()
+                .filter(|objects| objects.len() == chunk.len());
+            let Some(objects) = objects else {
+                return Err(AuditorError::SuiClient(format!(
+                    "sui_multiGetObjects returned an unexpected result for {} objects",
+                    chunk.len()
+                )));
+            };
+
+            for (object_id, object) in chunk.iter().zip(objects) {
+                let metadata = parse_blob_object(object, self.n_shards);
+                if let (Ok(metadata), Some(cache)) = (&metadata, &self.metadata_cache) {
+                    cache.insert(*object_id, metadata.clone());
+                }
+                found.insert(*object_id, metadata);
+            }
+        }
 
-    #[cfg(not(feature = "sui-sdk"))]
-    pub async fn get_blob_metadata(&self, blob_object_id: &str) -> Result<BlobMetadata> {
-        warn!("get_blob_metadata called without sui-sdk feature");
-        Err(AuditorError::SuiClient(
-            "Sui SDK not enabled - cannot query blob metadata".to_string(),
-        ))
+        // 同一 ID 出現多次時每個條目各得一份結果
+        Ok(parsed
+            .into_iter()
+            .map(|object_id| match found.get(&object_id?) {
+                Some(Ok(metadata)) => Ok(metadata.clone()),
+                Some(Err(AuditorError::ChainAbi(msg))) => Err(AuditorError::ChainAbi(msg.clone())),
+                Some(Err(AuditorError::SuiClient(msg))) => {
+                    Err(AuditorError::SuiClient(msg.clone()))
+                }
+                Some(Err(e)) => Err(AuditorError::SuiClient(e.to_string())),
+                None => Err(AuditorError::SuiClient(
+                    "Blob object was not returned by sui_multiGetObjects".to_string(),
+                )),
+            })
+            .collect())
     }
 
     // ============ 審計記錄提交 ============
 
-    /// 提交審計記錄到鏈上
-    ///
-    /// 調用 `audit_core::submit_audit_record` 函數
+    /// 構造調用 `module::function` 的可編程交易塊
     ///
-    /// # 參數
-    /// - `signer`: 簽名者地址（審計員地址）
-    /// - `blob_id`: Blob ID (u256 as bytes)
-    /// - `blob_object_id`: Blob 對象 ID
-    /// - `challenge_epoch`: 執行審計的 epoch
-    /// - `total_challenges`: 總挑戰次數
-    /// - `successful_verifications`: 成功驗證次數
-    /// - `integrity_hash`: 完整性哈希
-    /// - `pqc_signature`: PQC 簽名
-    /// - `pqc_algorithm`: PQC 算法類型 (1=Falcon512, 2=Dilithium2, 3=Dilithium3)
+    /// 純參數全部來自 `chain_types` 的 BCS 編碼，順序與 Move 簽名一致：
+    /// `leading_objects` → `pure_args` → Clock
     #[cfg(feature = "sui-sdk")]
-    pub async fn submit_audit_record(
-        &self,
-        signer: SuiAddress,
-        blob_id: Vec<u8>,           // u256 as 32 bytes
-        blob_object_id: ObjectID,
-        challenge_epoch: u32,
-        total_challenges: u16,
-        successful_verifications: u16,
-        integrity_hash: Vec<u8>,
-        pqc_signature: Vec<u8>,
-        pqc_algorithm: u8,
-    ) -> Result<String> {
-        info!(
-            "Submitting audit record for blob {:?}, epoch {}",
-            blob_object_id, challenge_epoch
-        );
-
-        // 構造可編程交易塊
+    fn build_move_call(
+        package_id: &str,
+        module: &str,
+        function: &str,
+        leading_objects: &[&str],
+        pure_args: Vec<Vec<u8>>,
+    ) -> Result<ProgrammableTransaction> {
         let mut ptb = ProgrammableTransactionBuilder::new();
+        let mut arguments = Vec::with_capacity(leading_objects.len() + pure_args.len() + 1);
 
-        // 準備參數
-        let config_arg = ptb.obj(CallArg::Object(
-            ObjectID::from_str(&self.audit_config_id)
-                .map_err(|e| AuditorError::SuiClient(format!("Invalid config ID: {}", e)))?
-                .into(),
-        ))?;
-
-        // blob_id (u256)
-        let blob_id_arg = ptb.pure(blob_id)?;
-
-        // blob_object_id (ID)
-        let blob_obj_arg = ptb.pure(blob_object_id)?;
-
-        // challenge_epoch (u32)
-        let epoch_arg = ptb.pure(challenge_epoch)?;
-
-        // total_challenges (u16)
-        let total_arg = ptb.pure(total_challenges)?;
-
-        // successful_verifications (u16)
-        let success_arg = ptb.pure(successful_verifications)?;
-
-        // integrity_hash (vector<u8>)
-        let hash_arg = ptb.pure(integrity_hash)?;
-
-        // pqc_signature (vector<u8>)
-        let sig_arg = ptb.pure(pqc_signature)?;
+        for object_id in leading_objects {
+            let object_id = ObjectID::from_str(object_id)
+                .map_err(|e| AuditorError::SuiClient(format!("Invalid object ID: {}", e)))?;
+            arguments.push(ptb.obj(CallArg::Object(object_id.into()))?);
+        }
 
-        // pqc_algorithm (u8)
-        let algo_arg = ptb.pure(pqc_algorithm)?;
+        for bytes in pure_args {
+            arguments.push(ptb.pure_bytes(bytes, false));
+        }
 
         // Clock object (0x6)
-        let clock_arg = ptb.obj(CallArg::CLOCK)?;
+        arguments.push(ptb.obj(CallArg::CLOCK)?);
 
-        // 構造 Move 調用
-        let package_id = ObjectID::from_str(&self.audit_package_id)
+        let package_id = ObjectID::from_str(package_id)
             .map_err(|e| AuditorError::SuiClient(format!("Invalid package ID: {}", e)))?;
 
         ptb.command(Command::move_call(
             package_id,
-            Identifier::new("audit_core").map_err(|e| {
+            Identifier::new(module).map_err(|e| {
                 AuditorError::SuiClient(format!("Invalid module name: {}", e))
             })?,
-            Identifier::new("submit_audit_record").map_err(|e| {
+            Identifier::new(function).map_err(|e| {
                 AuditorError::SuiClient(format!("Invalid function name: {}", e))
             })?,
             vec![], // 無類型參數
-            vec![
-                config_arg,
-                blob_id_arg,
-                blob_obj_arg,
-                epoch_arg,
-                total_arg,
-                success_arg,
-                hash_arg,
-                sig_arg,
-                algo_arg,
-                clock_arg,
-            ],
+            arguments,
         ));
 
-        // 完成交易塊構建
-        let pt = ptb.finish();
-
-        // TODO: 實際簽名和提交交易
-        // 這需要審計員的私鑰，應該從配置或密鑰庫中獲取
+        Ok(ptb.finish())
+    }
 
-        warn!("Transaction building complete but not submitted - signer integration needed");
+    /// 構建並簽名 `audit_core::submit_audit_record` 交易，但不提交（試運行）
+    ///
+    /// AuditConfig 與 Clock 須為共享對象；gas 代幣的選擇與簽名同
+    /// [`build_report_access_policy`](Self::build_report_access_policy)。
+    /// 只使用 JSON-RPC，未啟用 `sui-sdk` feature 時同樣可用
+    ///
+    /// # 參數
+    /// - `signer`: 審計員的 Sui 密鑰
+    /// - `params`: 審計記錄參數（見 `chain_types::AuditRecordParams`）
+    pub async fn build_audit_record(
+        &self,
+        signer: &SuiKey,
+        params: &AuditRecordParams,
+    ) -> Result<SignedTransaction> {
+        params.validate()?;
+        info!(
+            "Building audit record for blob {}, epoch {}",
+            params.blob_id.to_blob_id(),
+            params.challenge_epoch
+        );
 
-        Ok("0x0000000000000000000000000000000000000000000000000000000000000000".to_string())
+        self.build_signed_move_call(
+            signer,
+            &self.audit_package_id,
+            AUDIT_CORE_MODULE,
+            AuditRecordParams::FUNCTION,
+            &[&self.audit_config_id, SUI_CLOCK_OBJECT_ID],
+            params.json_args(&self.audit_config_id),
+        )
+        .await
     }
 
-    #[cfg(not(feature = "sui-sdk"))]
+    /// 提交審計記錄到鏈上，返回交易摘要與新建的 `AuditRecord` 對象 ID
+    ///
+    /// 交易以 `WaitForLocalExecution` 執行。對象版本衝突（如 gas 代幣被並發交易使用）
+    /// 按 [`RetryConfig::conservative`] 重新構建並簽名後重試
+    ///
+    /// # 錯誤
+    /// - gas 代幣餘額不足或執行時 gas 耗盡: `AuditorError::InsufficientGas`
+    /// - 重試後仍然版本衝突: `AuditorError::ObjectVersionConflict`
+    /// - 合約中止: `AuditorError::MoveAbort`
+    /// - 未啟用 `sui-sdk` feature 時返回 `AuditorError::SuiClient`（試運行仍可用）
     pub async fn submit_audit_record(
         &self,
-        _signer: SuiAddress,
-        _blob_id: Vec<u8>,
-        _blob_object_id: LocalObjectID,
-        _challenge_epoch: u32,
-        _total_challenges: u16,
-        _successful_verifications: u16,
-        _integrity_hash: Vec<u8>,
-        _pqc_signature: Vec<u8>,
-        _pqc_algorithm: u8,
-    ) -> Result<String> {
-        Err(AuditorError::SuiClient(
-            "Sui SDK not enabled - cannot submit audit record".to_string(),
-        ))
+        signer: &SuiKey,
+        params: &AuditRecordParams,
+    ) -> Result<SubmittedAuditRecord> {
+        let executed = retry_with_exponential_backoff_if(
+            "submit_audit_record",
+            &RetryConfig::conservative(),
+            |e| matches!(e, AuditorError::ObjectVersionConflict(_)),
+            || async {
+                let transaction = self.build_audit_record(signer, params).await?;
+                self.execute_transaction(&transaction).await
+            },
+        )
+        .await?;
+
+        let audit_record_id = created_object(&executed.object_changes, AUDIT_RECORD_TYPE_SUFFIX)
+            .ok_or_else(|| {
+                AuditorError::SuiClient(format!(
+                    "Transaction {} did not create an AuditRecord",
+                    executed.digest
+                ))
+            })?;
+        info!(
+            "Audit record {} created in transaction {}",
+            audit_record_id, executed.digest
+        );
+
+        Ok(SubmittedAuditRecord {
+            digest: executed.digest,
+            audit_record_id,
+        })
     }
 
     // ============ 審計報告元數據提交 ============
@@ -361,25 +602,28 @@ impl AuditSystemClient {
     /// 調用 `auditor_registry::submit_audit_report_metadata`
     ///
     /// # 參數
-    /// - `encrypted_report_blob_id`: Walrus 上加密報告的 Blob ID
-    /// - `audit_record_ids`: 報告涵蓋的審計記錄 ID 列表
-    /// - `pqc_signature`: 對報告的 PQC 簽名
+    /// - `signer`: 簽名者地址（審計員地址）
+    /// - `params`: 報告元數據參數（見 `chain_types::ReportMetadataParams`）
     #[cfg(feature = "sui-sdk")]
     pub async fn submit_report_metadata(
         &self,
         _signer: SuiAddress,
-        encrypted_report_blob_id: ObjectID,
-        audit_record_ids: Vec<ObjectID>,
-        pqc_signature: Vec<u8>,
+        params: &ReportMetadataParams,
     ) -> Result<String> {
         info!(
             "Submitting audit report metadata for {} audit records",
-            audit_record_ids.len()
+            params.audit_record_ids.len()
         );
 
-        // TODO: 實現與 submit_audit_record 類似的交易構造邏輯
+        let _pt = Self::build_move_call(
+            &self.audit_package_id,
+            AUDITOR_REGISTRY_MODULE,
+            ReportMetadataParams::FUNCTION,
+            &[&self.registry_id],
+            params.pure_args()?,
+        )?;
 
-        warn!("submit_report_metadata not fully implemented");
+        warn!("Transaction building complete but not submitted - signer integration needed");
 
         Ok("0x0000000000000000000000000000000000000000000000000000000000000000".to_string())
     }
@@ -388,9 +632,7 @@ impl AuditSystemClient {
     pub async fn submit_report_metadata(
         &self,
         _signer: SuiAddress,
-        _encrypted_report_blob_id: LocalObjectID,
-        _audit_record_ids: Vec<LocalObjectID>,
-        _pqc_signature: Vec<u8>,
+        _params: &ReportMetadataParams,
     ) -> Result<String> {
         Err(AuditorError::SuiClient(
             "Sui SDK not enabled - cannot submit report metadata".to_string(),
@@ -399,84 +641,329 @@ impl AuditSystemClient {
 
     // ============ 訪問策略管理 ============
 
-    /// 為審計報告設置訪問策略
+    /// 構建並簽名 `report_access::create_policy` 交易，但不提交（試運行）
     ///
-    /// 調用 `report_access::create_policy`
-    #[cfg(feature = "sui-sdk")]
-    pub async fn set_report_access_policy(
+    /// 1. 解析交易用到的共享對象（Clock），確認其為共享對象
+    /// 2. 從簽名者的 SUI 代幣中選出餘額不低於 gas budget 的一枚作為 gas
+    /// 3. 通過 `unsafe_moveCall` 構建 `TransactionData`（BCS）
+    /// 4. 以簽名者的 Sui 密鑰簽名
+    ///
+    /// 只使用 JSON-RPC，未啟用 `sui-sdk` feature 時同樣可用；
+    /// 返回的 [`SignedTransaction`] 可序列化保存，或交給 `sui client execute-signed-tx` 執行
+    pub async fn build_report_access_policy(
         &self,
-        _signer: SuiAddress,
-        report_blob_id: u64,
-        audit_record_id: ObjectID,
-        authorized_readers: Vec<SuiAddress>,
-        validity_days: u64,
-    ) -> Result<String> {
+        signer: &SuiKey,
+        params: &PolicyParams,
+    ) -> Result<SignedTransaction> {
         info!(
-            "Setting access policy for report {} with {} authorized readers",
-            report_blob_id,
-            authorized_readers.len()
+            "Building access policy for report {} with {} authorized readers",
+            params.report_blob_id.to_blob_id(),
+            params.allowed_readers.len()
         );
 
-        // TODO: 構造調用 access_policy::report_access::create_policy 的交易
+        self.build_signed_move_call(
+            signer,
+            &self.access_package_id,
+            REPORT_ACCESS_MODULE,
+            PolicyParams::FUNCTION,
+            &[SUI_CLOCK_OBJECT_ID],
+            params.json_args(),
+        )
+        .await
+    }
 
-        warn!("set_report_access_policy not fully implemented");
+    /// 確認共享對象、選出 gas 代幣，通過 `unsafe_moveCall` 構建交易並簽名
+    async fn build_signed_move_call(
+        &self,
+        signer: &SuiKey,
+        package_id: &str,
+        module: &str,
+        function: &str,
+        shared_objects: &[&str],
+        args: Vec<Value>,
+    ) -> Result<SignedTransaction> {
+        let sender = signer.address();
+
+        for object_id in shared_objects {
+            let version = self.resolve_shared_object(object_id).await?;
+            debug!("{} initial shared version: {}", object_id, version);
+        }
 
-        Ok("0x0000000000000000000000000000000000000000000000000000000000000000".to_string())
+        let gas_coin = self.select_gas_coin(&sender).await?;
+        debug!("Selected gas coin {} for {}", gas_coin, sender);
+
+        let result = self
+            .rpc(
+                "unsafe_moveCall",
+                json!([
+                    sender,
+                    package_id,
+                    module,
+                    function,
+                    [],
+                    args,
+                    gas_coin,
+                    self.gas_budget.to_string(),
+                    null
+                ]),
+            )
+            .await?;
+        let tx_bytes = json_str(&result, "txBytes")?.to_string();
+        let decoded = general_purpose::STANDARD.decode(&tx_bytes).map_err(|e| {
+            AuditorError::SuiClient(format!("Invalid txBytes from unsafe_moveCall: {}", e))
+        })?;
+
+        Ok(SignedTransaction {
+            signature: signer.sign_transaction(&decoded)?,
+            tx_bytes,
+            sender,
+            gas_coin,
+            gas_budget: self.gas_budget,
+        })
     }
 
-    #[cfg(not(feature = "sui-sdk"))]
+    /// 為審計報告設置訪問策略，返回交易摘要
+    ///
+    /// 構建與簽名見 [`build_report_access_policy`](Self::build_report_access_policy)；
+    /// 交易以 `WaitForLocalExecution` 執行。合約中止時返回 `AuditorError::MoveAbort`，
+    /// 其 `reason` 為 `report_access` 中的錯誤常量名。
+    ///
+    /// # 錯誤
+    /// - 未啟用 `sui-sdk` feature 時返回 `AuditorError::SuiClient`（試運行仍可用）
     pub async fn set_report_access_policy(
         &self,
-        _signer: SuiAddress,
-        _report_blob_id: u64,
-        _audit_record_id: LocalObjectID,
-        _authorized_readers: Vec<SuiAddress>,
-        _validity_days: u64,
+        signer: &SuiKey,
+        params: &PolicyParams,
     ) -> Result<String> {
+        let transaction = self.build_report_access_policy(signer, params).await?;
+        let digest = self.execute_transaction(&transaction).await?.digest;
+        info!("Access policy created in transaction {}", digest);
+        Ok(digest)
+    }
+
+    /// 執行已簽名的交易（`WaitForLocalExecution`），失敗時解析 Move 中止碼
+    ///
+    /// 提交被拒絕的錯誤見 [`submission_failure`]，執行失敗的錯誤見 [`execution_failure`]
+    #[cfg(feature = "sui-sdk")]
+    async fn execute_transaction(
+        &self,
+        transaction: &SignedTransaction,
+    ) -> Result<ExecutedTransaction> {
+        use sui_sdk::rpc_types::{SuiExecutionStatus, SuiTransactionBlockEffectsAPI};
+        use sui_sdk::types::crypto::{Signature, ToFromBytes};
+
+        let decode = |value: &str| {
+            general_purpose::STANDARD
+                .decode(value)
+                .map_err(|e| AuditorError::SuiClient(format!("Invalid Base64: {}", e)))
+        };
+        let data: TransactionData = bcs::from_bytes(&decode(&transaction.tx_bytes)?)
+            .map_err(|e| AuditorError::SuiClient(format!("Invalid transaction data: {}", e)))?;
+        let signature = Signature::from_bytes(&decode(&transaction.signature)?)
+            .map_err(|e| AuditorError::SuiClient(format!("Invalid signature: {}", e)))?;
+
+        let response = self
+            .sui_client
+            .quorum_driver_api()
+            .execute_transaction_block(
+                Transaction::from_data(data, vec![signature]),
+                SuiTransactionBlockResponseOptions::new()
+                    .with_effects()
+                    .with_object_changes(),
+                Some(ExecuteTransactionRequestType::WaitForLocalExecution),
+            )
+            .await
+            .map_err(|e| submission_failure(&e.to_string()))?;
+
+        let digest = response.digest.to_string();
+        match response.effects.as_ref().map(|effects| effects.status()) {
+            Some(SuiExecutionStatus::Failure { error }) => {
+                Err(execution_failure(&digest, error))
+            }
+            Some(SuiExecutionStatus::Success) => Ok(ExecutedTransaction {
+                object_changes: serde_json::to_value(&response.object_changes)?,
+                digest,
+            }),
+            None => Err(AuditorError::SuiClient(format!(
+                "Transaction {} returned no effects",
+                digest
+            ))),
+        }
+    }
+
+    #[cfg(not(feature = "sui-sdk"))]
+    async fn execute_transaction(
+        &self,
+        _transaction: &SignedTransaction,
+    ) -> Result<ExecutedTransaction> {
         Err(AuditorError::SuiClient(
-            "Sui SDK not enabled - cannot set access policy".to_string(),
+            "Sui SDK not enabled - cannot execute transactions (dry run is available)".to_string(),
         ))
     }
 
+    /// 讀取共享對象，返回其 `initial_shared_version`
+    async fn resolve_shared_object(&self, object_id: &str) -> Result<u64> {
+        let object = self
+            .rpc("sui_getObject", json!([object_id, { "showOwner": true }]))
+            .await?;
+        let owner = object.pointer("/data/owner/Shared").ok_or_else(|| {
+            AuditorError::SuiClient(format!("Object {} is not a shared object", object_id))
+        })?;
+        json_uint(owner, "initial_shared_version")
+    }
+
+    /// 選出簽名者餘額最大、且不低於 gas budget 的 SUI 代幣
+    async fn select_gas_coin(&self, owner: &str) -> Result<String> {
+        let coins = self
+            .rpc("suix_getCoins", json!([owner, SUI_COIN_TYPE, null, EVENT_PAGE_LIMIT]))
+            .await?;
+
+        let mut best: Option<(u64, &str)> = None;
+        for coin in coins["data"].as_array().into_iter().flatten() {
+            let balance: u64 = json_uint(coin, "balance")?;
+            if balance >= self.gas_budget && best.map_or(true, |(b, _)| balance > b) {
+                best = Some((balance, json_str(coin, "coinObjectId")?));
+            }
+        }
+
+        best.map(|(_, id)| id.to_string()).ok_or_else(|| {
+            AuditorError::InsufficientGas(format!(
+                "{} has no SUI coin with at least {} MIST for gas",
+                owner, self.gas_budget
+            ))
+        })
+    }
+
     // ============ 審計員管理 ============
 
     /// 查詢審計員的聲譽分數
     ///
-    /// 調用只讀函數 `auditor_registry::get_auditor_reputation`
+    /// 以 `sui_devInspectTransactionBlock` 調用只讀函數 `auditor_registry::get_auditor_reputation`
+    /// （不簽名、不消耗 gas）；未註冊的審計員為 0
     #[cfg(feature = "sui-sdk")]
-    pub async fn get_auditor_reputation(&self, auditor: SuiAddress) -> Result<u64> {
-        info!("Querying reputation for auditor {}", auditor);
-
-        // TODO: 使用 devInspectTransactionBlock 調用只讀函數
-
-        warn!("get_auditor_reputation not fully implemented");
-
-        Ok(0)
+    pub async fn get_auditor_reputation(&self, auditor: &MoveId) -> Result<u64> {
+        debug!("Querying reputation for auditor {}", auditor);
+
+        let value = self
+            .inspect_registry_call("get_auditor_reputation", auditor)
+            .await?;
+        bcs::from_bytes(&value).map_err(|e| {
+            AuditorError::ChainAbi(format!("Invalid reputation of auditor {}: {}", auditor, e))
+        })
     }
 
     #[cfg(not(feature = "sui-sdk"))]
-    pub async fn get_auditor_reputation(&self, _auditor: SuiAddress) -> Result<u64> {
+    pub async fn get_auditor_reputation(&self, _auditor: &MoveId) -> Result<u64> {
         Err(AuditorError::SuiClient(
             "Sui SDK not enabled - cannot query reputation".to_string(),
         ))
     }
 
     /// 檢查審計員是否已註冊
+    ///
+    /// 以 `sui_devInspectTransactionBlock` 調用只讀函數 `auditor_registry::is_auditor_registered`
+    #[cfg(feature = "sui-sdk")]
+    pub async fn is_auditor_registered(&self, auditor: &MoveId) -> Result<bool> {
+        debug!("Checking if auditor {} is registered", auditor);
+
+        let value = self
+            .inspect_registry_call("is_auditor_registered", auditor)
+            .await?;
+        bcs::from_bytes(&value).map_err(|e| {
+            AuditorError::ChainAbi(format!(
+                "Invalid registration flag of auditor {}: {}",
+                auditor, e
+            ))
+        })
+    }
+
+    #[cfg(not(feature = "sui-sdk"))]
+    pub async fn is_auditor_registered(&self, _auditor: &MoveId) -> Result<bool> {
+        Err(AuditorError::SuiClient(
+            "Sui SDK not enabled - cannot check registration".to_string(),
+        ))
+    }
+
+    /// 以 devInspect 調用 `auditor_registry::function(&AuditorRegistry, auditor)`，返回其 BCS 返回值
+    ///
+    /// AuditorRegistry 以只讀共享對象傳入；交易以審計員為發送者，不簽名也不上鏈
     #[cfg(feature = "sui-sdk")]
-    pub async fn is_auditor_registered(&self, auditor: SuiAddress) -> Result<bool> {
-        info!("Checking if auditor {} is registered", auditor);
+    async fn inspect_registry_call(&self, function: &str, auditor: &MoveId) -> Result<Vec<u8>> {
+        if self.registry_id.is_empty() {
+            return Err(AuditorError::Config(
+                "auditor_registry_id not configured".to_string(),
+            ));
+        }
+        let initial_shared_version = self.resolve_shared_object(&self.registry_id).await?;
 
-        // TODO: 調用 auditor_registry::is_auditor_registered
+        let mut ptb = ProgrammableTransactionBuilder::new();
+        let registry = ptb.obj(ObjectArg::SharedObject {
+            id: ObjectID::from_str(&self.registry_id)
+                .map_err(|e| AuditorError::SuiClient(format!("Invalid registry ID: {}", e)))?,
+            initial_shared_version: SequenceNumber::from_u64(initial_shared_version),
+            mutable: false,
+        })?;
+        let auditor_arg = ptb.pure_bytes(auditor.0.to_vec(), false);
+        ptb.command(Command::move_call(
+            ObjectID::from_str(&self.audit_package_id)
+                .map_err(|e| AuditorError::SuiClient(format!("Invalid package ID: {}", e)))?,
+            Identifier::new(AUDITOR_REGISTRY_MODULE)
+                .map_err(|e| AuditorError::SuiClient(format!("Invalid module name: {}", e)))?,
+            Identifier::new(function)
+                .map_err(|e| AuditorError::SuiClient(format!("Invalid function name: {}", e)))?,
+            vec![],
+            vec![registry, auditor_arg],
+        ));
 
-        warn!("is_auditor_registered not fully implemented");
+        let kind = bcs::to_bytes(&TransactionKind::ProgrammableTransaction(ptb.finish()))
+            .map_err(|e| AuditorError::ChainAbi(format!("BCS encoding failed: {}", e)))?;
+        let result = self
+            .rpc(
+                "sui_devInspectTransactionBlock",
+                json!([
+                    auditor.to_string(),
+                    general_purpose::STANDARD.encode(kind),
+                    null,
+                    null
+                ]),
+            )
+            .await?;
+        parse_dev_inspect_return(&result, function)
+    }
 
-        Ok(false)
+    /// 查詢審計員在 AuditorRegistry 登記的 PQC 公鑰
+    ///
+    /// 讀取 `pqc_public_keys` 表的 ID，再以 `suix_getDynamicFieldObject` 取出該地址對應的條目。
+    /// 算法由公鑰長度確定（見 [`RegisteredKey::from_public_key`]）
+    ///
+    /// # 錯誤
+    /// - 審計員未登記公鑰: 返回 `SuiClient` 錯誤
+    /// - 對象字段格式不符: 返回 `ChainAbi` 錯誤
+    #[cfg(feature = "sui-sdk")]
+    pub async fn get_auditor_public_key(&self, auditor: &MoveId) -> Result<RegisteredKey> {
+        debug!("Looking up public key of auditor {}", auditor);
+
+        let registry = self
+            .rpc(
+                "sui_getObject",
+                json!([self.registry_id, { "showContent": true }]),
+            )
+            .await?;
+        let table_id = parse_registry_key_table(&registry)?;
+
+        let entry = self
+            .rpc(
+                "suix_getDynamicFieldObject",
+                json!([table_id, { "type": "address", "value": auditor.to_string() }]),
+            )
+            .await?;
+        parse_registered_key(&entry, auditor)
     }
 
     #[cfg(not(feature = "sui-sdk"))]
-    pub async fn is_auditor_registered(&self, _auditor: SuiAddress) -> Result<bool> {
+    pub async fn get_auditor_public_key(&self, _auditor: &MoveId) -> Result<RegisteredKey> {
         Err(AuditorError::SuiClient(
-            "Sui SDK not enabled - cannot check registration".to_string(),
+            "Sui SDK not enabled - cannot look up auditor public keys".to_string(),
         ))
     }
 
@@ -516,11 +1003,564 @@ impl AuditSystemClient {
             "Sui SDK not enabled - cannot claim reward".to_string(),
         ))
     }
+
+    // ============ 審計記錄查詢（JSON-RPC） ============
+
+    /// 查找某審計員在某 epoch 對某 Blob 提交的審計記錄（最新的在前）
+    ///
+    /// 通過 `suix_queryEvents` 翻閱 `audit_core::AuditCreated` 事件，再以
+    /// `sui_getObject` 讀取對應的 `AuditRecord`。只使用 JSON-RPC，
+    /// 未啟用 `sui-sdk` feature 時同樣可用。事件已存在但對象讀不到的記錄會被跳過。
+    pub async fn find_audit_records(
+        &self,
+        blob_id: &MoveU256,
+        challenge_epoch: u32,
+        auditor: &MoveId,
+    ) -> Result<Vec<OnChainAuditRecord>> {
+        let event_type = format!(
+            "{}::{}::{}",
+            self.audit_package_id,
+            AUDIT_CORE_MODULE,
+            AuditCreatedEvent::EVENT
+        );
+
+        let mut record_ids = Vec::new();
+        let mut cursor = Value::Null;
+        for _ in 0..MAX_EVENT_PAGES {
+            let page = self
+                .rpc(
+                    "suix_queryEvents",
+                    json!([{ "MoveEventType": event_type }, cursor, 50, true]),
+                )
+                .await?;
+
+            for event in page["data"].as_array().into_iter().flatten() {
+                let event = AuditCreatedEvent::from_json(&event["parsedJson"])?;
+                if event.blob_id == *blob_id
+                    && event.auditor == *auditor
+                    && event.challenge_epoch == challenge_epoch
+                {
+                    record_ids.push(event.audit_record_id);
+                }
+            }
+
+            if page["hasNextPage"].as_bool() != Some(true) {
+                break;
+            }
+            cursor = page["nextCursor"].clone();
+        }
+
+        debug!(
+            "Found {} AuditCreated event(s) for blob {} epoch {} auditor {}",
+            record_ids.len(),
+            blob_id,
+            challenge_epoch,
+            auditor
+        );
+
+        let mut records = Vec::with_capacity(record_ids.len());
+        for id in record_ids {
+            let object = self
+                .rpc("sui_getObject", json!([id.to_string(), { "showContent": true }]))
+                .await?;
+            match OnChainAuditRecord::from_object_response(&object)? {
+                Some(record) => records.push(record),
+                None => warn!("AuditRecord {} from AuditCreated event is not readable", id),
+            }
+        }
+        Ok(records)
+    }
+
+    /// 查找 Blob 的 Sui 對象 ID（Blob ID → Blob 對象 ID）
+    ///
+    /// Blob ID 不能直接定位鏈上對象：翻閱 `audit_core::AuditCreated` 事件（最新的在前），
+    /// 讀取該 Blob 最新一條可讀的 `AuditRecord` 中記錄的 `blob_object_id`。
+    /// 審計系統從未記錄過該 Blob 時返回 `None`。
+    ///
+    /// # 錯誤
+    /// - 未啟用 `sui-sdk` feature 時返回 `AuditorError::SuiClient`
+    #[cfg(feature = "sui-sdk")]
+    pub async fn find_blob_object_id(&self, blob_id: &BlobId) -> Result<Option<MoveId>> {
+        self.scan_blob_object_id(blob_id).await
+    }
+
+    #[cfg(not(feature = "sui-sdk"))]
+    pub async fn find_blob_object_id(&self, _blob_id: &BlobId) -> Result<Option<MoveId>> {
+        Err(AuditorError::SuiClient(
+            "Sui SDK not enabled - cannot look up blob objects".to_string(),
+        ))
+    }
+
+    #[cfg_attr(not(feature = "sui-sdk"), allow(dead_code))]
+    async fn scan_blob_object_id(&self, blob_id: &BlobId) -> Result<Option<MoveId>> {
+        let event_type = format!(
+            "{}::{}::{}",
+            self.audit_package_id,
+            AUDIT_CORE_MODULE,
+            AuditCreatedEvent::EVENT
+        );
+        let target = MoveU256::from(*blob_id);
+
+        let mut cursor = Value::Null;
+        for _ in 0..MAX_EVENT_PAGES {
+            let page = self
+                .rpc(
+                    "suix_queryEvents",
+                    json!([{ "MoveEventType": event_type }, cursor, EVENT_PAGE_LIMIT, true]),
+                )
+                .await?;
+
+            for event in page["data"].as_array().into_iter().flatten() {
+                let event = AuditCreatedEvent::from_json(&event["parsedJson"])?;
+                if event.blob_id != target {
+                    continue;
+                }
+
+                let object = self
+                    .rpc(
+                        "sui_getObject",
+                        json!([event.audit_record_id.to_string(), { "showContent": true }]),
+                    )
+                    .await?;
+                match object
+                    .pointer("/data/content/fields/blob_object_id")
+                    .and_then(Value::as_str)
+                {
+                    Some(id) => {
+                        let object_id = MoveId::from_hex(id)?;
+                        debug!("Blob {} is object {}", blob_id, object_id);
+                        return Ok(Some(object_id));
+                    }
+                    None => warn!(
+                        "AuditRecord {} has no readable blob_object_id",
+                        event.audit_record_id
+                    ),
+                }
+            }
+
+            if page["hasNextPage"].as_bool() != Some(true) {
+                break;
+            }
+            cursor = page["nextCursor"].clone();
+        }
+
+        debug!("No AuditRecord references blob {}", blob_id);
+        Ok(None)
+    }
+
+    // ============ 待審計 Blob（守護模式） ============
+
+    /// 查詢當前 Sui epoch（`suix_getLatestSuiSystemState`）
+    ///
+    /// 守護模式以此作為審計輪次：每個 Blob 在同一 epoch 內只審計一次。
+    pub async fn current_epoch(&self) -> Result<u32> {
+        let state = self.rpc("suix_getLatestSuiSystemState", json!([])).await?;
+        state["epoch"]
+            .as_str()
+            .and_then(|epoch| epoch.parse::<u32>().ok())
+            .ok_or_else(|| {
+                AuditorError::SuiClient(format!(
+                    "Invalid epoch in system state: {}",
+                    state["epoch"]
+                ))
+            })
+    }
+
+    /// 分頁列出在 `epoch` 尚未審計的 Blob（最新的審計事件在前）
+    ///
+    /// 審計系統登記過的 Blob 來自 `audit_core::AuditCreated` 事件。某頁中同一 Blob
+    /// 只返回一次；若該 Blob 在本頁有 `challenge_epoch >= epoch` 的審計
+    /// （指定 `auditor` 時只看該審計員的記錄），則歸入 `audited`。
+    ///
+    /// # 參數
+    /// - `epoch`: 目標審計輪次
+    /// - `auditor`: 只把該審計員的記錄視為已審計（`None` 時任何審計員都算）
+    /// - `limit`: 每頁事件數（上限 50）
+    /// - `cursor`: 上一頁返回的 `next_cursor`，首頁傳 `None`
+    ///
+    /// # 錯誤
+    /// - 未啟用 `sui-sdk` feature 時返回 `AuditorError::SuiClient`
+    #[cfg(feature = "sui-sdk")]
+    pub async fn list_pending_audit_blobs(
+        &self,
+        epoch: u32,
+        auditor: Option<&MoveId>,
+        limit: usize,
+        cursor: Option<Value>,
+    ) -> Result<PendingBlobPage> {
+        self.scan_pending_audit_blobs(epoch, auditor, limit, cursor).await
+    }
+
+    #[cfg(not(feature = "sui-sdk"))]
+    pub async fn list_pending_audit_blobs(
+        &self,
+        _epoch: u32,
+        _auditor: Option<&MoveId>,
+        _limit: usize,
+        _cursor: Option<Value>,
+    ) -> Result<PendingBlobPage> {
+        Err(AuditorError::SuiClient(
+            "Sui SDK not enabled - cannot list pending blobs".to_string(),
+        ))
+    }
+
+    #[cfg_attr(not(feature = "sui-sdk"), allow(dead_code))]
+    async fn scan_pending_audit_blobs(
+        &self,
+        epoch: u32,
+        auditor: Option<&MoveId>,
+        limit: usize,
+        cursor: Option<Value>,
+    ) -> Result<PendingBlobPage> {
+        let event_type = format!(
+            "{}::{}::{}",
+            self.audit_package_id,
+            AUDIT_CORE_MODULE,
+            AuditCreatedEvent::EVENT
+        );
+
+        let page = self
+            .rpc(
+                "suix_queryEvents",
+                json!([
+                    { "MoveEventType": event_type },
+                    cursor.unwrap_or(Value::Null),
+                    limit.clamp(1, EVENT_PAGE_LIMIT),
+                    true
+                ]),
+            )
+            .await?;
+
+        // Blob → (最新事件, 本頁是否已在目標 epoch 審計)，保持首次出現的順序
+        let mut order = Vec::new();
+        let mut blobs: HashMap<MoveU256, (AuditCreatedEvent, bool)> = HashMap::new();
+        for event in page["data"].as_array().into_iter().flatten() {
+            let event = AuditCreatedEvent::from_json(&event["parsedJson"])?;
+            let audited = event.challenge_epoch >= epoch
+                && auditor.map_or(true, |auditor| event.auditor == *auditor);
+
+            match blobs.get_mut(&event.blob_id) {
+                Some((_, seen_audited)) => *seen_audited |= audited,
+                None => {
+                    order.push(event.blob_id);
+                    blobs.insert(event.blob_id, (event, audited));
+                }
+            }
+        }
+
+        let mut result = PendingBlobPage {
+            next_cursor: (page["hasNextPage"].as_bool() == Some(true))
+                .then(|| page["nextCursor"].clone()),
+            ..PendingBlobPage::default()
+        };
+
+        for blob_id in order {
+            let (latest, audited) = blobs.remove(&blob_id).expect("collected above");
+            if audited {
+                result.audited.push(blob_id.to_blob_id());
+                continue;
+            }
+
+            let object = self
+                .rpc(
+                    "sui_getObject",
+                    json!([latest.audit_record_id.to_string(), { "showContent": true }]),
+                )
+                .await?;
+            let blob_object_id = match object
+                .pointer("/data/content/fields/blob_object_id")
+                .and_then(Value::as_str)
+            {
+                Some(id) => Some(MoveId::from_hex(id)?),
+                None => {
+                    warn!(
+                        "AuditRecord {} has no readable blob_object_id",
+                        latest.audit_record_id
+                    );
+                    None
+                }
+            };
+
+            result.pending.push(PendingBlob {
+                blob_id: blob_id.to_blob_id(),
+                blob_object_id,
+                last_audit_epoch: latest.challenge_epoch,
+            });
+        }
+
+        debug!(
+            "Pending blob page for epoch {}: {} pending, {} audited, more={}",
+            epoch,
+            result.pending.len(),
+            result.audited.len(),
+            result.next_cursor.is_some()
+        );
+        Ok(result)
+    }
+
+    /// 發送 JSON-RPC 請求，返回 `result`
+    async fn rpc(&self, method: &str, params: Value) -> Result<Value> {
+        let request = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": method,
+            "params": params
+        });
+
+        let mut response: Value = self
+            .http_client
+            .post(&self.rpc_url)
+            .json(&request)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        if let Some(error) = response.get("error") {
+            return Err(AuditorError::SuiClient(format!("{} failed: {}", method, error)));
+        }
+        Ok(response["result"].take())
+    }
+}
+
+/// 將提交交易時被驗證者拒絕的錯誤信息轉換為錯誤
+///
+/// gas 代幣餘額不足為 `AuditorError::InsufficientGas`，引用的對象版本已被消耗或被其他交易鎖定
+/// 為 `AuditorError::ObjectVersionConflict`，其餘為 `SuiClient`
+pub fn submission_failure(error: &str) -> AuditorError {
+    classify_failure(error).unwrap_or_else(|| {
+        AuditorError::SuiClient(format!("Failed to execute transaction: {}", error))
+    })
+}
+
+/// 將交易執行失敗的錯誤信息轉換為錯誤
+///
+/// `MoveAbort(MoveLocation { module: ModuleId { .., name: Identifier("m") }, ..,
+/// function_name: Some("f") }, code) in command 0` 形式的中止轉換為
+/// `AuditorError::MoveAbort`（`report_access` 的中止碼附帶錯誤常量名）；
+/// gas 耗盡與版本衝突同 [`submission_failure`]，其餘為 `SuiClient`
+pub fn execution_failure(digest: &str, error: &str) -> AuditorError {
+    let Some(abort) = error.strip_prefix("MoveAbort(") else {
+        return classify_failure(error).unwrap_or_else(|| {
+            AuditorError::SuiClient(format!("Transaction {} failed: {}", digest, error))
+        });
+    };
+
+    let quoted_after = |marker: &str| {
+        abort
+            .split_once(marker)
+            .and_then(|(_, rest)| rest.split('"').nth(1))
+            .map(str::to_string)
+    };
+    let module = quoted_after("name: Identifier(").unwrap_or_default();
+    let function = quoted_after("function_name: Some(").unwrap_or_default();
+    let code = abort
+        .rsplit_once("}, ")
+        .and_then(|(_, rest)| rest.split(')').next())
+        .and_then(|code| code.trim().parse::<u64>().ok());
+
+    match code {
+        Some(code) => AuditorError::MoveAbort {
+            reason: match module.as_str() {
+                REPORT_ACCESS_MODULE => PolicyParams::abort_reason(code),
+                _ => None,
+            }
+            .unwrap_or("unknown"),
+            module,
+            function,
+            code,
+        },
+        None => AuditorError::SuiClient(format!("Transaction {} failed: {}", digest, error)),
+    }
+}
+
+/// 按錯誤信息中的標記識別 gas 不足與對象版本衝突
+fn classify_failure(error: &str) -> Option<AuditorError> {
+    let contains_any = |markers: &[&str]| markers.iter().any(|marker| error.contains(marker));
+    if contains_any(INSUFFICIENT_GAS_MARKERS) {
+        Some(AuditorError::InsufficientGas(error.to_string()))
+    } else if contains_any(VERSION_CONFLICT_MARKERS) {
+        Some(AuditorError::ObjectVersionConflict(error.to_string()))
+    } else {
+        None
+    }
+}
+
+/// 從交易的 `objectChanges` 中找出新建的、類型以 `type_suffix` 結尾的對象 ID
+pub fn created_object(object_changes: &Value, type_suffix: &str) -> Option<MoveId> {
+    object_changes
+        .as_array()?
+        .iter()
+        .filter(|change| change["type"] == "created")
+        .filter(|change| {
+            change["objectType"]
+                .as_str()
+                .is_some_and(|object_type| object_type.ends_with(type_suffix))
+        })
+        .find_map(|change| MoveId::from_hex(change["objectId"].as_str()?).ok())
+}
+
+/// 解析 `sui_getObject`（`showType`、`showOwner`、`showContent`）返回的 Walrus Blob 對象
+///
+/// Move 結構（`blob::Blob`）中的 `blob_id`（u256）、`size`、`encoding_type`、
+/// `registered_epoch`、`certified_epoch` 與 `storage` 的起止 epoch 映射到 [`BlobMetadata`]。
+/// 分片數不在 Blob 對象中，由調用方傳入：`encoding_n` 為分片數，`encoding_k`
+/// 為 RedStuff 主源符號數 `n - 2f`（`f = ⌊(n - 1) / 3⌋`）。
+/// 鏈上不記錄根哈希，`merkle_root` 為空（見 [`BlobMetadata::merkle_root`]）
+///
+/// # 錯誤
+/// - 對象已刪除、不存在或不是 Walrus Blob: 返回 `SuiClient` 錯誤
+/// - 字段缺失或格式不符: 返回 `ChainAbi` 錯誤
+pub fn parse_blob_object(result: &Value, n_shards: u64) -> Result<BlobMetadata> {
+    if let Some(error) = result.get("error") {
+        let object_id = error["object_id"].as_str().unwrap_or("<unknown>");
+        return Err(AuditorError::SuiClient(match error["code"].as_str() {
+            Some("deleted") => format!("Blob object {} has been deleted", object_id),
+            Some("notExists") => format!(
+                "Blob object {} does not exist (or has been pruned by this RPC node)",
+                object_id
+            ),
+            _ => format!("Failed to read blob object: {}", error),
+        }));
+    }
+
+    let data = result
+        .get("data")
+        .ok_or_else(|| AuditorError::SuiClient("Object response has no data".to_string()))?;
+    let object_id = json_str(data, "objectId")?;
+    let object_type = data
+        .pointer("/content/type")
+        .or_else(|| data.get("type"))
+        .and_then(Value::as_str)
+        .unwrap_or_default();
+    if !object_type.ends_with(WALRUS_BLOB_TYPE_SUFFIX) {
+        return Err(AuditorError::SuiClient(format!(
+            "Object {} is not a Walrus Blob (type {:?})",
+            object_id, object_type
+        )));
+    }
+
+    let fields = data.pointer("/content/fields").ok_or_else(|| {
+        AuditorError::SuiClient(format!("Blob object {} has no content fields", object_id))
+    })?;
+    let storage = fields.pointer("/storage/fields").ok_or_else(|| {
+        AuditorError::ChainAbi(format!("Blob object {} has no storage resource", object_id))
+    })?;
+    let certified_epoch = match json_field(fields, "certified_epoch")? {
+        Value::Null => None,
+        _ => Some(json_uint(fields, "certified_epoch")?),
+    };
+
+    let fault_tolerance = n_shards.saturating_sub(1) / 3;
+
+    Ok(BlobMetadata {
+        blob_object_id: object_id.to_string(),
+        blob_id: MoveU256::from_decimal_str(json_str(fields, "blob_id")?)?.to_blob_id(),
+        merkle_root: Vec::new(),
+        blob_size: json_uint(fields, "size")?,
+        encoding_type: json_uint(fields, "encoding_type")?,
+        encoding_k: n_shards - 2 * fault_tolerance,
+        encoding_n: n_shards,
+        registered_epoch: json_uint(fields, "registered_epoch")?,
+        certified_epoch,
+        start_epoch: json_uint(storage, "start_epoch")?,
+        end_epoch: json_uint(storage, "end_epoch")?,
+        owner: object_owner(data.get("owner").unwrap_or(&Value::Null)),
+    })
+}
+
+/// 取出 `sui_devInspectTransactionBlock` 結果中第一條命令的第一個返回值（BCS 字節）
+///
+/// 執行失敗（如合約中止）時返回 `SuiClient` 錯誤
+#[cfg_attr(not(feature = "sui-sdk"), allow(dead_code))]
+fn parse_dev_inspect_return(result: &Value, function: &str) -> Result<Vec<u8>> {
+    if let Some(error) = result.get("error").and_then(Value::as_str) {
+        return Err(AuditorError::SuiClient(format!(
+            "devInspect of {} failed: {}",
+            function, error
+        )));
+    }
+
+    result
+        .pointer("/results/0/returnValues/0/0")
+        .and_then(Value::as_array)
+        .and_then(|items| {
+            items
+                .iter()
+                .map(|item| u8::try_from(item.as_u64()?).ok())
+                .collect()
+        })
+        .ok_or_else(|| {
+            AuditorError::ChainAbi(format!("devInspect of {} returned no value", function))
+        })
+}
+
+/// 從 `AuditorRegistry` 對象中取出 `pqc_public_keys` 表的 ID
+#[cfg_attr(not(feature = "sui-sdk"), allow(dead_code))]
+fn parse_registry_key_table(result: &Value) -> Result<String> {
+    if let Some(error) = result.get("error") {
+        return Err(AuditorError::SuiClient(format!(
+            "Failed to read AuditorRegistry: {}",
+            error
+        )));
+    }
+
+    result
+        .pointer("/data/content/fields/pqc_public_keys/fields/id/id")
+        .and_then(Value::as_str)
+        .map(str::to_string)
+        .ok_or_else(|| {
+            AuditorError::ChainAbi("AuditorRegistry has no pqc_public_keys table".to_string())
+        })
+}
+
+/// 解析 `pqc_public_keys` 表中某審計員的條目（`dynamic_field::Field<address, vector<u8>>`）
+#[cfg_attr(not(feature = "sui-sdk"), allow(dead_code))]
+fn parse_registered_key(result: &Value, auditor: &MoveId) -> Result<RegisteredKey> {
+    if result.get("error").is_some() {
+        return Err(AuditorError::SuiClient(format!(
+            "Auditor {} has no registered public key",
+            auditor
+        )));
+    }
+
+    let fields = result.pointer("/data/content/fields").ok_or_else(|| {
+        AuditorError::ChainAbi(format!(
+            "Public key entry of auditor {} has no content fields",
+            auditor
+        ))
+    })?;
+    RegisteredKey::from_public_key(json_bytes(fields, "value")?)
+}
+
+/// 對象所有者：地址或父對象 ID；共享與不可變對象記為 `shared` / `immutable`
+fn object_owner(owner: &Value) -> String {
+    if let Some(address) = owner
+        .get("AddressOwner")
+        .or_else(|| owner.get("ObjectOwner"))
+        .and_then(Value::as_str)
+    {
+        return address.to_string();
+    }
+    if owner.get("Shared").is_some() {
+        return "shared".to_string();
+    }
+    match owner.as_str() {
+        Some("Immutable") => "immutable".to_string(),
+        _ => owner.to_string(),
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_support::FakeSuiRpc;
+    use base64::{engine::general_purpose, Engine as _};
+
+    const PACKAGE_ID: &str = "0xa0d17";
+    const AUDITOR: &str = "0xa11ce";
+    const OTHER_AUDITOR: &str = "0xb0b";
 
     #[tokio::test]
     #[cfg(not(feature = "sui-sdk"))]
@@ -536,4 +1576,689 @@ mod tests {
 
         assert!(client.is_ok());
     }
+
+    #[tokio::test]
+    async fn test_client_rejects_malformed_ids() {
+        let rpc = "https://fullnode.testnet.sui.io:443";
+        let err = AuditSystemClient::new(rpc, "audit_system", "", "", "")
+            .await
+            .err()
+            .unwrap();
+        assert!(
+            err.to_string().contains("Invalid audit package ID"),
+            "{}",
+            err
+        );
+
+        let err = AuditSystemClient::new(rpc, PACKAGE_ID, "", "0xregistry", "")
+            .await
+            .err()
+            .unwrap();
+        assert!(
+            err.to_string().contains("Invalid auditor registry ID"),
+            "{}",
+            err
+        );
+    }
+
+    fn blob_id(seed: u8) -> String {
+        general_purpose::URL_SAFE_NO_PAD.encode([seed; 32])
+    }
+
+    /// 加入一條 `AuditCreated` 事件及其 `AuditRecord` 對象（記錄 ID 由 `seq` 決定）
+    fn add_audit(chain: &FakeSuiRpc, seq: u8, blob: u8, auditor: &str, epoch: u32) {
+        let record_id = MoveId([seq; 32]).to_string();
+        let blob_id = MoveU256([blob; 32]).to_decimal_string();
+
+        chain.add_event(
+            format!("{}::{}::{}", PACKAGE_ID, AUDIT_CORE_MODULE, AuditCreatedEvent::EVENT),
+            json!({
+                "audit_record_id": record_id,
+                "blob_id": blob_id,
+                "auditor": auditor,
+                "challenge_epoch": epoch,
+                "total_challenges": 10,
+                "is_valid": true
+            }),
+        );
+        chain.add_object(
+            record_id,
+            "0xa0d17::audit_core::AuditRecord",
+            json!({ "blob_id": blob_id, "blob_object_id": format!("0x{:02x}", blob) }),
+        );
+    }
+
+    async fn client(chain: &FakeSuiRpc) -> AuditSystemClient {
+        AuditSystemClient::new(chain.url(), PACKAGE_ID, "0x0", "0x0", "0x0")
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_pending_blobs_paginate_and_skip_current_epoch() {
+        let chain = FakeSuiRpc::start().await;
+        chain.set_epoch(7);
+        add_audit(&chain, 1, 1, AUDITOR, 5);
+        add_audit(&chain, 2, 2, AUDITOR, 6);
+        add_audit(&chain, 3, 1, AUDITOR, 6);
+        add_audit(&chain, 4, 2, AUDITOR, 7);
+        add_audit(&chain, 5, 3, OTHER_AUDITOR, 7);
+
+        let client = client(&chain).await;
+        let epoch = client.current_epoch().await.unwrap();
+        assert_eq!(epoch, 7);
+
+        let auditor = MoveId::from_hex(AUDITOR).unwrap();
+        let first = client
+            .scan_pending_audit_blobs(epoch, Some(&auditor), 3, None)
+            .await
+            .unwrap();
+
+        // 最新的事件在前：blob 3 只被其他審計員審計過，blob 2 已在 epoch 7 審計
+        assert_eq!(
+            first.pending,
+            vec![
+                PendingBlob {
+                    blob_id: blob_id(3),
+                    blob_object_id: Some(MoveId::from_hex("0x03").unwrap()),
+                    last_audit_epoch: 7,
+                },
+                PendingBlob {
+                    blob_id: blob_id(1),
+                    blob_object_id: Some(MoveId::from_hex("0x01").unwrap()),
+                    last_audit_epoch: 6,
+                },
+            ]
+        );
+        assert_eq!(first.audited, vec![blob_id(2)]);
+        assert!(first.next_cursor.is_some());
+
+        let second = client
+            .scan_pending_audit_blobs(epoch, Some(&auditor), 3, first.next_cursor)
+            .await
+            .unwrap();
+        assert_eq!(second.pending.len(), 2);
+        assert_eq!(second.pending[0].blob_id, blob_id(2));
+        assert_eq!(second.pending[1].blob_id, blob_id(1));
+        assert!(second.next_cursor.is_none());
+
+        // 不限定審計員時，任何人在 epoch 7 的審計都算
+        let any = client.scan_pending_audit_blobs(epoch, None, 50, None).await.unwrap();
+        assert_eq!(any.audited, vec![blob_id(3), blob_id(2)]);
+        assert_eq!(any.pending.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_blob_object_id_found_through_latest_audit_record() {
+        let chain = FakeSuiRpc::start().await;
+        add_audit(&chain, 1, 1, AUDITOR, 5);
+        add_audit(&chain, 2, 2, AUDITOR, 6);
+        add_audit(&chain, 3, 1, OTHER_AUDITOR, 7);
+        chain.add_object(
+            MoveId([3; 32]).to_string(),
+            "0xa0d17::audit_core::AuditRecord",
+            json!({ "blob_id": MoveU256([1; 32]).to_decimal_string() }),
+        );
+        let client = client(&chain).await;
+
+        // 最新的記錄讀不到 blob_object_id 時退回較早的記錄
+        let blob = BlobId::parse(&blob_id(1)).unwrap();
+        assert_eq!(
+            client.scan_blob_object_id(&blob).await.unwrap(),
+            Some(MoveId::from_hex("0x01").unwrap())
+        );
+
+        let unknown = BlobId::parse(&blob_id(9)).unwrap();
+        assert_eq!(client.scan_blob_object_id(&unknown).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    #[cfg(not(feature = "sui-sdk"))]
+    async fn test_find_blob_object_id_requires_sui_sdk() {
+        let chain = FakeSuiRpc::start().await;
+        let client = client(&chain).await;
+
+        let blob = BlobId::parse(&blob_id(1)).unwrap();
+        match client.find_blob_object_id(&blob).await {
+            Err(AuditorError::SuiClient(msg)) => assert!(msg.contains("not enabled")),
+            other => panic!("Expected SuiClient error, got {:?}", other),
+        }
+        assert!(chain.requests().is_empty());
+    }
+
+    #[tokio::test]
+    #[cfg(not(feature = "sui-sdk"))]
+    async fn test_list_pending_requires_sui_sdk() {
+        let chain = FakeSuiRpc::start().await;
+        let client = client(&chain).await;
+
+        match client.list_pending_audit_blobs(7, None, 50, None).await {
+            Err(AuditorError::SuiClient(msg)) => assert!(msg.contains("not enabled")),
+            other => panic!("Expected SuiClient error, got {:?}", other),
+        }
+        assert!(chain.requests().is_empty());
+    }
+
+    fn blob_fixture(name: &str) -> Value {
+        let fixtures: Value =
+            serde_json::from_str(include_str!("../tests/fixtures/walrus_blob_object.json"))
+                .unwrap();
+        fixtures[name]["result"].clone()
+    }
+
+    #[test]
+    fn test_parse_blob_object() {
+        let metadata =
+            parse_blob_object(&blob_fixture("certified_blob"), DEFAULT_WALRUS_N_SHARDS).unwrap();
+
+        assert_eq!(metadata.blob_id, "eGlaSzwtHg8QMlR2mLrc_u_Nq4lnRSMBiHlqW0w9Lh8");
+        assert!(metadata.blob_object_id.starts_with("0x5c4e3a1f"));
+        assert_eq!(metadata.blob_size, 2_097_152);
+        assert_eq!(metadata.encoding_type, 1);
+        assert_eq!(metadata.registered_epoch, 40);
+        assert_eq!(metadata.certified_epoch, Some(41));
+        assert_eq!((metadata.start_epoch, metadata.end_epoch), (40, 93));
+        assert!(metadata.owner.starts_with("0x8b3c9f1e"));
+        // n = 1000 時 f = 333，主 sliver 需 n - 2f = 334 個
+        assert_eq!((metadata.encoding_k, metadata.encoding_n), (334, 1000));
+        // 鏈上 Blob 對象不含根哈希
+        assert!(metadata.merkle_root.is_empty());
+    }
+
+    #[test]
+    fn test_parse_blob_object_rejects_other_types() {
+        match parse_blob_object(&blob_fixture("not_a_blob"), DEFAULT_WALRUS_N_SHARDS) {
+            Err(AuditorError::SuiClient(msg)) => assert!(msg.contains("not a Walrus Blob")),
+            other => panic!("Expected SuiClient error, got {:?}", other),
+        }
+        match parse_blob_object(&blob_fixture("deleted_blob"), DEFAULT_WALRUS_N_SHARDS) {
+            Err(AuditorError::SuiClient(msg)) => assert!(msg.contains("has been deleted")),
+            other => panic!("Expected SuiClient error, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_blob_metadata_reads_blob_object() {
+        let chain = FakeSuiRpc::start().await;
+        let fixture = blob_fixture("certified_blob");
+        let object_id = fixture["data"]["objectId"].as_str().unwrap();
+        chain.add_object(
+            object_id,
+            fixture["data"]["content"]["type"].as_str().unwrap(),
+            fixture["data"]["content"]["fields"].clone(),
+        );
+        let mut client = client(&chain).await;
+        client.set_n_shards(7);
+
+        let metadata = client
+            .get_blob_metadata(&MoveId::from_hex(object_id).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(metadata.blob_id, "eGlaSzwtHg8QMlR2mLrc_u_Nq4lnRSMBiHlqW0w9Lh8");
+        assert_eq!((metadata.encoding_k, metadata.encoding_n), (3, 7));
+        assert_eq!(chain.requests()[0]["method"], "sui_getObject");
+
+        match client.get_blob_metadata(&MoveId::from_hex("0xdead").unwrap()).await {
+            Err(AuditorError::SuiClient(msg)) => assert!(msg.contains("does not exist")),
+            other => panic!("Expected SuiClient error, got {:?}", other),
+        }
+    }
+
+    /// 以 `certified_blob` 樣例的字段加入 `count` 個 Blob 對象，返回其 ID
+    fn add_blob_objects(chain: &FakeSuiRpc, count: usize) -> Vec<String> {
+        let fixture = blob_fixture("certified_blob");
+        (0..count)
+            .map(|i| {
+                let object_id = MoveId([i as u8 + 1; 32]).to_string();
+                chain.add_object(
+                    object_id.clone(),
+                    fixture["data"]["content"]["type"].as_str().unwrap(),
+                    fixture["data"]["content"]["fields"].clone(),
+                );
+                object_id
+            })
+            .collect()
+    }
+
+    /// `sui_multiGetObjects` 請求的對象數（按請求順序）
+    fn multi_get_sizes(chain: &FakeSuiRpc) -> Vec<usize> {
+        chain
+            .requests()
+            .iter()
+            .filter(|request| request["method"] == "sui_multiGetObjects")
+            .map(|request| request["params"][0].as_array().unwrap().len())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_get_blob_metadata_batch_chunks_requests() {
+        let chain = FakeSuiRpc::start().await;
+        let object_ids = add_blob_objects(&chain, 120);
+        let mut ids: Vec<&str> = object_ids.iter().map(String::as_str).collect();
+        ids.push("0xdead");
+        ids.push("not-an-id");
+        ids.push(&object_ids[0]);
+
+        let client = client(&chain).await;
+        let results = client.get_blob_metadata_batch(&ids).await.unwrap();
+        assert_eq!(results.len(), ids.len());
+        assert_eq!(multi_get_sizes(&chain), vec![50, 50, 21]);
+
+        for result in &results[..120] {
+            assert_eq!(
+                result.as_ref().unwrap().blob_id,
+                "eGlaSzwtHg8QMlR2mLrc_u_Nq4lnRSMBiHlqW0w9Lh8"
+            );
+        }
+        match &results[120] {
+            Err(AuditorError::SuiClient(msg)) => assert!(msg.contains("does not exist")),
+            other => panic!("Expected SuiClient error, got {:?}", other),
+        }
+        assert!(matches!(results[121], Err(AuditorError::ChainAbi(_))));
+        assert!(results[122].is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_metadata_cache_shared_by_batch_and_single_lookups() {
+        let chain = FakeSuiRpc::start().await;
+        let object_ids = add_blob_objects(&chain, 3);
+        let ids: Vec<&str> = object_ids.iter().map(String::as_str).collect();
+
+        let cache = Arc::new(BlobMetadataCache::new(std::time::Duration::from_secs(3600)));
+        let mut client = client(&chain).await;
+        client.set_metadata_cache(Arc::clone(&cache));
+
+        client.get_blob_metadata_batch(&ids[..2]).await.unwrap();
+        assert_eq!(multi_get_sizes(&chain), vec![2]);
+
+        // 預取的對象直接命中緩存，只查詢缺少的
+        client.get_blob_metadata_batch(&ids).await.unwrap();
+        assert_eq!(multi_get_sizes(&chain), vec![2, 1]);
+        let requests = chain.requests().len();
+        client
+            .get_blob_metadata(&MoveId::from_hex(ids[0]).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(chain.requests().len(), requests);
+
+        let stats = cache.stats();
+        assert_eq!((stats.hits, stats.misses, stats.entries), (3, 3, 3));
+    }
+
+    #[tokio::test]
+    async fn test_metadata_cache_expires_after_ttl() {
+        let chain = FakeSuiRpc::start().await;
+        let object_ids = add_blob_objects(&chain, 1);
+        let object_id = MoveId::from_hex(&object_ids[0]).unwrap();
+
+        let mut client = client(&chain).await;
+        client.set_metadata_cache(Arc::new(BlobMetadataCache::new(
+            std::time::Duration::from_millis(20),
+        )));
+        let fetches = || {
+            chain
+                .requests()
+                .iter()
+                .filter(|request| request["method"] == "sui_getObject")
+                .count()
+        };
+
+        client.get_blob_metadata(&object_id).await.unwrap();
+        client.get_blob_metadata(&object_id).await.unwrap();
+        assert_eq!(fetches(), 1);
+
+        tokio::time::sleep(std::time::Duration::from_millis(40)).await;
+        client.get_blob_metadata(&object_id).await.unwrap();
+        assert_eq!(fetches(), 2);
+    }
+
+    /// 加入 Clock 共享對象與 `key` 的 SUI 代幣
+    fn fund_signer(chain: &FakeSuiRpc, key: &SuiKey, balances: &[u64]) {
+        chain.add_object(SUI_CLOCK_OBJECT_ID, "0x2::clock::Clock", json!({ "timestamp_ms": "0" }));
+        chain.set_owner(SUI_CLOCK_OBJECT_ID, json!({ "Shared": { "initial_shared_version": 1 } }));
+        for (i, balance) in balances.iter().enumerate() {
+            chain.add_coin(key.address(), MoveId([i as u8 + 1; 32]).to_string(), *balance);
+        }
+    }
+
+    fn policy_params() -> PolicyParams {
+        PolicyParams {
+            report_blob_id: MoveU256([9; 32]),
+            audit_record_id: MoveId([1; 32]),
+            allowed_readers: vec![MoveId::from_hex(AUDITOR).unwrap()],
+            allowed_auditors: vec![],
+            expires_at_ms: Some(1_700_000_000_000),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_report_access_policy_dry_run() {
+        let chain = FakeSuiRpc::start().await;
+        let key = SuiKey::generate();
+        fund_signer(&chain, &key, &[1_000, 50_000_000, 20_000_000]);
+        let client = client(&chain).await;
+        let params = policy_params();
+
+        let tx = client.build_report_access_policy(&key, &params).await.unwrap();
+        assert_eq!(tx.sender, key.address());
+        assert_eq!(tx.gas_coin, MoveId([2; 32]).to_string());
+        assert_eq!(tx.gas_budget, 10_000_000);
+
+        // 簽名覆蓋 unsafe_moveCall 返回的交易字節
+        let tx_bytes = general_purpose::STANDARD.decode(&tx.tx_bytes).unwrap();
+        assert_eq!(tx.signature, key.sign_transaction(&tx_bytes).unwrap());
+
+        let move_call = chain
+            .requests()
+            .into_iter()
+            .find(|r| r["method"] == "unsafe_moveCall")
+            .unwrap();
+        let call = &move_call["params"];
+        assert_eq!(call[0], key.address());
+        assert_eq!(call[2], REPORT_ACCESS_MODULE);
+        assert_eq!(call[3], PolicyParams::FUNCTION);
+        assert_eq!(call[5], json!(params.json_args()));
+        assert_eq!(call[5][4], json!(["1700000000000"]));
+        assert_eq!(call[6], json!(tx.gas_coin));
+
+        // 試運行結果可以序列化保存
+        let saved = serde_json::to_string(&tx).unwrap();
+        assert_eq!(serde_json::from_str::<SignedTransaction>(&saved).unwrap(), tx);
+    }
+
+    #[tokio::test]
+    async fn test_report_access_policy_requires_gas_and_shared_clock() {
+        let chain = FakeSuiRpc::start().await;
+        let key = SuiKey::generate();
+        fund_signer(&chain, &key, &[1_000]);
+        let client = client(&chain).await;
+
+        match client.build_report_access_policy(&key, &policy_params()).await {
+            Err(AuditorError::InsufficientGas(msg)) => assert!(msg.contains("no SUI coin")),
+            other => panic!("Expected InsufficientGas error, got {:?}", other),
+        }
+
+        chain.set_owner(SUI_CLOCK_OBJECT_ID, json!({ "AddressOwner": AUDITOR }));
+        match client.build_report_access_policy(&key, &policy_params()).await {
+            Err(AuditorError::SuiClient(msg)) => assert!(msg.contains("not a shared object")),
+            other => panic!("Expected SuiClient error, got {:?}", other),
+        }
+        assert!(!chain.requests().iter().any(|r| r["method"] == "unsafe_moveCall"));
+    }
+
+    const AUDIT_CONFIG_ID: &str = "0xc0f19";
+
+    fn audit_record_params() -> AuditRecordParams {
+        AuditRecordParams {
+            blob_id: MoveU256::from_decimal_str("257").unwrap(),
+            blob_object_id: MoveId::from_hex("0xb10b").unwrap(),
+            challenge_epoch: 7,
+            total_challenges: 10,
+            successful_verifications: 9,
+            integrity_hash: vec![0xab; 32],
+            pqc_signature: vec![1, 2, 3],
+            pqc_algorithm: 3,
+        }
+    }
+
+    /// 帶 AuditConfig 共享對象的客戶端
+    async fn audit_client(chain: &FakeSuiRpc) -> AuditSystemClient {
+        chain.add_object(AUDIT_CONFIG_ID, "0xa0d17::audit_core::AuditConfig", json!({}));
+        chain.set_owner(AUDIT_CONFIG_ID, json!({ "Shared": { "initial_shared_version": 3 } }));
+        AuditSystemClient::new(chain.url(), PACKAGE_ID, "0x0", "0x0", AUDIT_CONFIG_ID)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_audit_record_dry_run() {
+        let chain = FakeSuiRpc::start().await;
+        let key = SuiKey::generate();
+        fund_signer(&chain, &key, &[50_000_000]);
+        let client = audit_client(&chain).await;
+
+        let tx = client.build_audit_record(&key, &audit_record_params()).await.unwrap();
+        assert_eq!(tx.sender, key.address());
+        assert_eq!(tx.gas_coin, MoveId([1; 32]).to_string());
+        let tx_bytes = general_purpose::STANDARD.decode(&tx.tx_bytes).unwrap();
+        assert_eq!(tx.signature, key.sign_transaction(&tx_bytes).unwrap());
+
+        let move_call = chain
+            .requests()
+            .into_iter()
+            .find(|r| r["method"] == "unsafe_moveCall")
+            .unwrap();
+        let call = &move_call["params"];
+        assert_eq!(call[1], PACKAGE_ID);
+        assert_eq!(call[2], AUDIT_CORE_MODULE);
+        assert_eq!(call[3], AuditRecordParams::FUNCTION);
+        assert_eq!(call[7], "10000000");
+
+        // Move 調用參數逐字節固定（順序與 `submit_audit_record` 的 Move 簽名一致）
+        let expected = format!(
+            "[\"0xc0f19\",\"257\",\"0x{:0>64}\",7,10,9,[{}],[1,2,3],3,\"0x6\"]",
+            "b10b",
+            vec!["171"; 32].join(",")
+        );
+        assert_eq!(call[5].to_string(), expected);
+    }
+
+    #[tokio::test]
+    async fn test_audit_record_requires_shared_config_and_valid_params() {
+        let chain = FakeSuiRpc::start().await;
+        let key = SuiKey::generate();
+        fund_signer(&chain, &key, &[50_000_000]);
+        let client = audit_client(&chain).await;
+
+        let mut params = audit_record_params();
+        params.successful_verifications = 11;
+        match client.build_audit_record(&key, &params).await {
+            Err(AuditorError::ChainAbi(msg)) => assert!(msg.contains("exceeds")),
+            other => panic!("Expected ChainAbi error, got {:?}", other),
+        }
+
+        chain.set_owner(AUDIT_CONFIG_ID, json!({ "AddressOwner": AUDITOR }));
+        match client.build_audit_record(&key, &audit_record_params()).await {
+            Err(AuditorError::SuiClient(msg)) => assert!(msg.contains("not a shared object")),
+            other => panic!("Expected SuiClient error, got {:?}", other),
+        }
+        assert!(!chain.requests().iter().any(|r| r["method"] == "unsafe_moveCall"));
+    }
+
+    #[tokio::test]
+    #[cfg(not(feature = "sui-sdk"))]
+    async fn test_submit_audit_record_requires_sui_sdk() {
+        let chain = FakeSuiRpc::start().await;
+        let key = SuiKey::generate();
+        fund_signer(&chain, &key, &[50_000_000]);
+        let client = audit_client(&chain).await;
+
+        match client.submit_audit_record(&key, &audit_record_params()).await {
+            Err(AuditorError::SuiClient(msg)) => assert!(msg.contains("not enabled")),
+            other => panic!("Expected SuiClient error, got {:?}", other),
+        }
+        // 未執行的交易不重試
+        let built = chain.requests().iter().filter(|r| r["method"] == "unsafe_moveCall").count();
+        assert_eq!(built, 1);
+    }
+
+    #[tokio::test]
+    #[cfg(not(feature = "sui-sdk"))]
+    async fn test_set_report_access_policy_requires_sui_sdk() {
+        let chain = FakeSuiRpc::start().await;
+        let key = SuiKey::generate();
+        fund_signer(&chain, &key, &[50_000_000]);
+        let client = client(&chain).await;
+
+        match client.set_report_access_policy(&key, &policy_params()).await {
+            Err(AuditorError::SuiClient(msg)) => assert!(msg.contains("not enabled")),
+            other => panic!("Expected SuiClient error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_execution_failure_maps_move_abort() {
+        let abort = |module: &str, code: u64| {
+            format!(
+                "MoveAbort(MoveLocation {{ module: ModuleId {{ address: {}, name: Identifier(\"{}\") }}, \
+                 function: 0, instruction: 14, function_name: Some(\"create_policy\") }}, {}) \
+                 in command 0",
+                PACKAGE_ID, module, code
+            )
+        };
+
+        match execution_failure("digest", &abort(REPORT_ACCESS_MODULE, 4)) {
+            AuditorError::MoveAbort { module, function, code, reason } => {
+                assert_eq!(module, REPORT_ACCESS_MODULE);
+                assert_eq!(function, "create_policy");
+                assert_eq!(code, 4);
+                assert_eq!(reason, "E_NOT_POLICY_CREATOR");
+            }
+            other => panic!("Expected MoveAbort, got {:?}", other),
+        }
+
+        match execution_failure("digest", &abort("other_module", 4)) {
+            AuditorError::MoveAbort { reason, .. } => assert_eq!(reason, "unknown"),
+            other => panic!("Expected MoveAbort, got {:?}", other),
+        }
+
+        match execution_failure("digest", "InsufficientGas") {
+            AuditorError::InsufficientGas(msg) => assert_eq!(msg, "InsufficientGas"),
+            other => panic!("Expected InsufficientGas error, got {:?}", other),
+        }
+
+        let argument_error = "CommandArgumentError { arg_idx: 2, kind: TypeMismatch }";
+        match execution_failure("digest", argument_error) {
+            AuditorError::SuiClient(msg) => assert!(msg.contains("TypeMismatch")),
+            other => panic!("Expected SuiClient error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_submission_failure_maps_conflicts_and_gas() {
+        let conflict = "Transaction is rejected as invalid by more than 1/3 of validators by stake \
+                        (non-retriable). Non-retriable errors: [UserInputError { error: \
+                        ObjectVersionUnavailableForConsumption { provided_obj_ref: (0x1, \
+                        SequenceNumber(3), o#1), current_version: SequenceNumber(4) } }]";
+        assert!(matches!(
+            submission_failure(conflict),
+            AuditorError::ObjectVersionConflict(_)
+        ));
+        assert!(matches!(
+            submission_failure("UserInputError { error: GasBalanceTooLow { .. } }"),
+            AuditorError::InsufficientGas(_)
+        ));
+        assert!(matches!(
+            submission_failure("connection reset"),
+            AuditorError::SuiClient(_)
+        ));
+    }
+
+    #[test]
+    fn test_created_object_finds_audit_record() {
+        let record = MoveId([7; 32]);
+        let changes = json!([
+            { "type": "mutated", "objectType": "0x2::coin::Coin<0x2::sui::SUI>",
+              "objectId": MoveId([1; 32]).to_string() },
+            { "type": "mutated", "objectType": format!("{}::audit_core::AuditConfig", PACKAGE_ID),
+              "objectId": MoveId([2; 32]).to_string() },
+            { "type": "created", "objectType": format!("{}::audit_core::AuditRecord", PACKAGE_ID),
+              "objectId": record.to_string() }
+        ]);
+
+        assert_eq!(created_object(&changes, AUDIT_RECORD_TYPE_SUFFIX), Some(record));
+        assert_eq!(created_object(&changes, "::report_access::ReportAccessPolicy"), None);
+        assert_eq!(created_object(&Value::Null, AUDIT_RECORD_TYPE_SUFFIX), None);
+    }
+
+    #[test]
+    fn test_parse_registered_key() {
+        use crate::audit_report::PqcAlgorithm;
+        use crate::onchain_keys::FALCON512_PUBLIC_KEY_BYTES;
+
+        let registry = json!({
+            "data": { "content": { "fields": {
+                "pqc_public_keys": { "fields": { "id": { "id": "0x7ab1e" }, "size": "1" } }
+            } } }
+        });
+        assert_eq!(parse_registry_key_table(&registry).unwrap(), "0x7ab1e");
+        assert!(matches!(
+            parse_registry_key_table(&json!({ "data": { "content": { "fields": {} } } })),
+            Err(AuditorError::ChainAbi(_))
+        ));
+
+        let auditor = MoveId([0xa1; 32]);
+        let entry = json!({
+            "data": { "content": { "fields": {
+                "name": auditor.to_string(),
+                "value": vec![7u8; FALCON512_PUBLIC_KEY_BYTES]
+            } } }
+        });
+        let key = parse_registered_key(&entry, &auditor).unwrap();
+        assert_eq!(key.algorithm, PqcAlgorithm::Falcon512.id());
+        assert_eq!(key.public_key.len(), FALCON512_PUBLIC_KEY_BYTES);
+
+        let missing = json!({ "error": { "code": "dynamicFieldNotFound" } });
+        match parse_registered_key(&missing, &auditor) {
+            Err(AuditorError::SuiClient(msg)) => assert!(msg.contains("no registered public key")),
+            other => panic!("Expected SuiClient error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_dev_inspect_return() {
+        let registered = json!({
+            "effects": { "status": { "status": "success" } },
+            "results": [ { "returnValues": [ [ [1], "bool" ] ] } ]
+        });
+        let value = parse_dev_inspect_return(&registered, "is_auditor_registered").unwrap();
+        assert!(bcs::from_bytes::<bool>(&value).unwrap());
+
+        let reputation = json!({
+            "results": [ { "returnValues": [ [ bcs::to_bytes(&42u64).unwrap(), "u64" ] ] } ]
+        });
+        let value = parse_dev_inspect_return(&reputation, "get_auditor_reputation").unwrap();
+        assert_eq!(bcs::from_bytes::<u64>(&value).unwrap(), 42);
+
+        let aborted = json!({ "error": "MoveAbort(..., 3) in command 0", "results": null });
+        match parse_dev_inspect_return(&aborted, "get_auditor_reputation") {
+            Err(AuditorError::SuiClient(msg)) => assert!(msg.contains("MoveAbort")),
+            other => panic!("Expected SuiClient error, got {:?}", other),
+        }
+        assert!(matches!(
+            parse_dev_inspect_return(&json!({ "results": [] }), "is_auditor_registered"),
+            Err(AuditorError::ChainAbi(_))
+        ));
+    }
+
+    #[tokio::test]
+    #[cfg(not(feature = "sui-sdk"))]
+    async fn test_registry_queries_require_sui_sdk() {
+        let chain = FakeSuiRpc::start().await;
+        let client = client(&chain).await;
+        let auditor = MoveId([1; 32]);
+
+        assert!(matches!(
+            client.is_auditor_registered(&auditor).await,
+            Err(AuditorError::SuiClient(_))
+        ));
+        assert!(matches!(
+            client.get_auditor_reputation(&auditor).await,
+            Err(AuditorError::SuiClient(_))
+        ));
+        assert!(chain.requests().is_empty());
+    }
+
+    #[tokio::test]
+    #[cfg(not(feature = "sui-sdk"))]
+    async fn test_get_auditor_public_key_requires_sui_sdk() {
+        let chain = FakeSuiRpc::start().await;
+        let client = client(&chain).await;
+
+        match client.get_auditor_public_key(&MoveId([1; 32])).await {
+            Err(AuditorError::SuiClient(msg)) => assert!(msg.contains("not enabled")),
+            other => panic!("Expected SuiClient error, got {:?}", other),
+        }
+        assert!(chain.requests().is_empty());
+    }
 }
@@ -0,0 +1,990 @@
+//! 進程內假服務（測試支援）
+//!
+//! 為端到端測試提供無需 Docker 的外部依賴替身，每個假服務都是綁定在
+//! `127.0.0.1:0` 上的 axum 路由：
+//!
+//! - [`FakeAggregator`]：提供確定性 Blob 內容（可模擬損壞 chunk、不可用或緩慢的響應體，
+//!   內容可隨時替換），支持 `If-None-Match` 條件請求，並統計下載次數與時間
+//! - [`FakeSealApi`]：以可逆 XOR「加密」模擬 Seal API（可模擬策略拒絕、密鑰服務器故障、
+//!   加密請求失敗或延遲與服務宕機）
+//! - [`FakePublisher`]：記錄所有上傳內容
+//! - [`FakeSuiRpc`]：記錄所有 JSON-RPC 請求，並提供固定的鏈上事件與對象
+//! - [`FakeStorageNode`]：對挑戰請求返回固定響應（可延遲響應、插入過載響應並記錄並發數與請求時間），
+//!   或以 Walrus GET 端點提供 sliver 與元數據；`GET /health` 的狀態碼可隨時切換
+//!
+//! 本模塊僅在測試或啟用 `test-util` feature 時編譯，下游 crate 可通過
+//! `auditor-node = { features = ["test-util"] }` 復用。
+
+use crate::crypto::merkle::hash_leaf;
+use axum::{
+    body::{Body, Bytes},
+    extract::{Path, State},
+    http::{header, HeaderMap, Method, StatusCode, Uri},
+    response::{IntoResponse, Response},
+    routing::{get, post, put},
+    Json, Router,
+};
+use base64::{engine::general_purpose, Engine as _};
+use futures::StreamExt;
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// 在隨機端口上啟動路由，返回基礎 URL
+async fn spawn(router: Router) -> String {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("Failed to bind fake server");
+    let addr = listener.local_addr().expect("Fake server has no address");
+
+    tokio::spawn(async move {
+        let _ = axum::serve(listener, router).await;
+    });
+
+    format!("http://{}", addr)
+}
+
+/// 生成確定性 Blob 內容
+pub fn deterministic_blob(len: usize) -> Vec<u8> {
+    (0..len).map(|i| (i * 31 % 251) as u8).collect()
+}
+
+/// 計算內容的 SHA-256（hex），與 `AuditData::content_hash` 一致
+pub fn content_hash(data: &[u8]) -> String {
+    hex::encode(Sha256::digest(data))
+}
+
+/// 假 Aggregator 的行為
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AggregatorMode {
+    /// 返回原始內容
+    Healthy,
+    /// 翻轉第 N 個 4KB chunk 的首字節
+    CorruptChunk(usize),
+    /// 所有請求返回 503
+    Unavailable,
+    /// 返回原始內容，但響應體分兩半發送，中間暫停指定時長
+    SlowBody(Duration),
+}
+
+/// 假 Aggregator 的共享狀態
+struct AggregatorState {
+    /// 返回的內容（`None` 表示不可用）
+    served: Mutex<Option<Vec<u8>>>,
+    /// 響應體前後兩半之間的暫停
+    body_pause: Option<Duration>,
+    /// 收到的 GET 請求數（HEAD 不計）
+    downloads: AtomicUsize,
+    /// 以 304 應答的條件 GET 請求數
+    not_modified: AtomicUsize,
+    /// 每個 GET 請求的到達時間
+    download_times: Mutex<Vec<std::time::Instant>>,
+}
+
+/// 假 Walrus Aggregator
+///
+/// 響應帶有 `Content-Length` 與由內容導出的 `ETag`；HEAD 請求只返回頭部，
+/// `If-None-Match` 與當前 `ETag` 一致的 GET 請求返回 304
+pub struct FakeAggregator {
+    url: String,
+    blob: Vec<u8>,
+    state: Arc<AggregatorState>,
+}
+
+impl FakeAggregator {
+    /// 啟動假 Aggregator，對任意 Blob ID 返回 `blob`（按 `mode` 處理）
+    pub async fn start(blob: Vec<u8>, mode: AggregatorMode) -> Self {
+        let served = match mode {
+            AggregatorMode::CorruptChunk(chunk) => {
+                let mut corrupted = blob.clone();
+                let offset = chunk * 4096;
+                if let Some(byte) = corrupted.get_mut(offset) {
+                    *byte ^= 0xff;
+                }
+                Some(corrupted)
+            }
+            AggregatorMode::Healthy | AggregatorMode::SlowBody(_) => Some(blob.clone()),
+            AggregatorMode::Unavailable => None,
+        };
+        let body_pause = match mode {
+            AggregatorMode::SlowBody(pause) => Some(pause),
+            _ => None,
+        };
+
+        let state = Arc::new(AggregatorState {
+            served: Mutex::new(served),
+            body_pause,
+            downloads: AtomicUsize::new(0),
+            not_modified: AtomicUsize::new(0),
+            download_times: Mutex::new(Vec::new()),
+        });
+        let router = Router::new()
+            .route("/v1/blobs/:id", get(serve_blob))
+            .with_state(Arc::clone(&state));
+
+        Self {
+            url: spawn(router).await,
+            blob,
+            state,
+        }
+    }
+
+    /// 基礎 URL
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// 原始（未損壞）內容
+    pub fn blob(&self) -> &[u8] {
+        &self.blob
+    }
+
+    /// 已收到的下載（GET）請求數
+    pub fn downloads(&self) -> usize {
+        self.state.downloads.load(Ordering::SeqCst)
+    }
+
+    /// 各下載（GET）請求的到達時間（按到達順序）
+    pub fn download_times(&self) -> Vec<std::time::Instant> {
+        self.state.download_times.lock().unwrap().clone()
+    }
+
+    /// 以 304 應答的條件 GET 請求數（也計入 [`FakeAggregator::downloads`]）
+    pub fn not_modified(&self) -> usize {
+        self.state.not_modified.load(Ordering::SeqCst)
+    }
+
+    /// 替換之後返回的內容（`ETag` 隨之改變）
+    pub fn set_served(&self, blob: Vec<u8>) {
+        *self.state.served.lock().unwrap() = Some(blob);
+    }
+}
+
+async fn serve_blob(
+    method: Method,
+    headers: HeaderMap,
+    State(state): State<Arc<AggregatorState>>,
+) -> Response {
+    if method == Method::GET {
+        state.downloads.fetch_add(1, Ordering::SeqCst);
+        state
+            .download_times
+            .lock()
+            .unwrap()
+            .push(std::time::Instant::now());
+    }
+    let served = state.served.lock().unwrap().clone();
+    match served {
+        Some(blob) => {
+            let etag = format!("\"{}\"", content_hash(&blob));
+            let if_none_match = headers
+                .get(header::IF_NONE_MATCH)
+                .and_then(|value| value.to_str().ok());
+            if method == Method::GET && if_none_match == Some(etag.as_str()) {
+                state.not_modified.fetch_add(1, Ordering::SeqCst);
+                return (StatusCode::NOT_MODIFIED, [(header::ETAG, etag)]).into_response();
+            }
+            let headers = [
+                (header::CONTENT_LENGTH, blob.len().to_string()),
+                (header::ETAG, etag),
+            ];
+            match state.body_pause.filter(|_| method == Method::GET) {
+                Some(pause) => (headers, slow_body(blob, pause)).into_response(),
+                None => (headers, blob).into_response(),
+            }
+        }
+        None => StatusCode::SERVICE_UNAVAILABLE.into_response(),
+    }
+}
+
+/// 分兩半發送的響應體，兩半之間暫停 `pause`
+fn slow_body(blob: Vec<u8>, pause: Duration) -> Body {
+    let mut blob = Bytes::from(blob);
+    let head = blob.split_to(blob.len() / 2);
+    let parts = futures::stream::iter([(head, Duration::ZERO), (blob, pause)]);
+    Body::from_stream(parts.then(|(part, pause)| async move {
+        tokio::time::sleep(pause).await;
+        Ok::<_, std::convert::Infallible>(part)
+    }))
+}
+
+/// 由 identity 和 package ID 派生 XOR 密鑰
+pub fn fake_seal_key(identity: &str, package_id: &str) -> Vec<u8> {
+    let mut hasher = Sha256::new();
+    hasher.update(identity.as_bytes());
+    hasher.update(package_id.as_bytes());
+    hasher.finalize().to_vec()
+}
+
+/// 可逆 XOR「加密」（加密與解密相同）
+pub fn fake_seal_xor(data: &[u8], identity: &str, package_id: &str) -> Vec<u8> {
+    let key = fake_seal_key(identity, package_id);
+    data.iter()
+        .zip(key.iter().cycle())
+        .map(|(byte, k)| byte ^ k)
+        .collect()
+}
+
+/// 假 Seal API 的解密狀態
+#[derive(Default)]
+struct SealState {
+    /// 被訪問策略拒絕的請求者地址
+    denied: HashSet<String>,
+    /// 接下來要返回錯誤的解密請求數與狀態碼
+    failures: Option<(usize, StatusCode)>,
+    /// 密鑰服務器是否不足門檻
+    key_servers_down: bool,
+    /// 收到的解密請求數（包括失敗的請求）
+    decrypt_requests: usize,
+    /// 整個服務不可用（健康檢查與加密請求返回 503）
+    unavailable: bool,
+    /// 接下來要返回錯誤的加密請求數與狀態碼
+    encrypt_failures: Option<(usize, StatusCode)>,
+    /// 加密請求的響應延遲
+    encrypt_delay: Duration,
+    /// 收到的加密請求數（包括失敗的請求）
+    encrypt_requests: usize,
+    /// 收到的健康檢查請求數
+    health_requests: usize,
+}
+
+/// 假 Seal API
+pub struct FakeSealApi {
+    url: String,
+    state: Arc<Mutex<SealState>>,
+}
+
+impl FakeSealApi {
+    /// 啟動假 Seal API（`/health`、`/api/seal/encrypt` 與 `/api/seal/decrypt`）
+    pub async fn start() -> Self {
+        let state = Arc::new(Mutex::new(SealState::default()));
+        let router = Router::new()
+            .route("/health", get(seal_health))
+            .route("/api/seal/encrypt", post(seal_encrypt))
+            .route("/api/seal/decrypt", post(seal_decrypt))
+            .with_state(state.clone());
+
+        Self {
+            url: spawn(router).await,
+            state,
+        }
+    }
+
+    /// 基礎 URL
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// 讓訪問策略拒絕該地址的解密請求（403）
+    pub fn deny(&self, requester: impl Into<String>) {
+        self.state.lock().unwrap().denied.insert(requester.into());
+    }
+
+    /// 讓接下來 `count` 個解密請求返回 `status`
+    pub fn fail_next(&self, count: usize, status: StatusCode) {
+        self.state.lock().unwrap().failures = (count > 0).then_some((count, status));
+    }
+
+    /// 模擬可用密鑰服務器少於門檻
+    pub fn set_key_servers_down(&self, down: bool) {
+        self.state.lock().unwrap().key_servers_down = down;
+    }
+
+    /// 收到的解密請求數
+    pub fn decrypt_requests(&self) -> usize {
+        self.state.lock().unwrap().decrypt_requests
+    }
+
+    /// 模擬 Seal API 宕機（健康檢查與加密請求返回 503）
+    pub fn set_unavailable(&self, unavailable: bool) {
+        self.state.lock().unwrap().unavailable = unavailable;
+    }
+
+    /// 讓接下來 `count` 個加密請求返回 `status`
+    pub fn fail_next_encrypt(&self, count: usize, status: StatusCode) {
+        self.state.lock().unwrap().encrypt_failures = (count > 0).then_some((count, status));
+    }
+
+    /// 延遲之後的加密響應（模擬請求超時）
+    pub fn set_encrypt_delay(&self, delay: Duration) {
+        self.state.lock().unwrap().encrypt_delay = delay;
+    }
+
+    /// 收到的加密請求數
+    pub fn encrypt_requests(&self) -> usize {
+        self.state.lock().unwrap().encrypt_requests
+    }
+
+    /// 收到的健康檢查請求數
+    pub fn health_requests(&self) -> usize {
+        self.state.lock().unwrap().health_requests
+    }
+}
+
+/// Seal API 宕機時的響應
+fn seal_unavailable() -> Response {
+    (
+        StatusCode::SERVICE_UNAVAILABLE,
+        Json(json!({ "success": false, "error": "seal api unavailable" })),
+    )
+        .into_response()
+}
+
+async fn seal_health(State(state): State<Arc<Mutex<SealState>>>) -> Response {
+    let mut state = state.lock().unwrap();
+    state.health_requests += 1;
+    if state.unavailable {
+        return seal_unavailable();
+    }
+    Json(json!({
+        "status": "healthy",
+        "service": "seal-api-server",
+        "version": "test",
+        "timestamp": "0"
+    }))
+    .into_response()
+}
+
+async fn seal_encrypt(
+    State(state): State<Arc<Mutex<SealState>>>,
+    Json(request): Json<Value>,
+) -> Response {
+    let delay = {
+        let mut state = state.lock().unwrap();
+        state.encrypt_requests += 1;
+        if let Some((remaining, status)) = state.encrypt_failures {
+            state.encrypt_failures = (remaining > 1).then_some((remaining - 1, status));
+            return (
+                status,
+                Json(json!({ "success": false, "error": "seal api failure" })),
+            )
+                .into_response();
+        }
+        if state.unavailable {
+            return seal_unavailable();
+        }
+        state.encrypt_delay
+    };
+    tokio::time::sleep(delay).await;
+
+    let field = |name: &str| request[name].as_str().unwrap_or_default().to_string();
+    let (identity, package_id) = (field("identity"), field("packageId"));
+
+    let Ok(plaintext) = general_purpose::STANDARD.decode(field("data")) else {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(json!({ "success": false, "error": "invalid base64" })),
+        )
+            .into_response();
+    };
+
+    let ciphertext = fake_seal_xor(&plaintext, &identity, &package_id);
+
+    Json(json!({
+        "success": true,
+        "encryptedData": general_purpose::STANDARD.encode(&ciphertext),
+        "symmetricKey": general_purpose::STANDARD.encode(fake_seal_key(&identity, &package_id)),
+        "metadata": {
+            "identity": identity,
+            "packageId": package_id,
+            "threshold": request["threshold"].as_u64().unwrap_or(0),
+            "encryptedAt": 0,
+            "originalSize": plaintext.len(),
+            "encryptedSize": ciphertext.len(),
+            "duration": 0
+        }
+    }))
+    .into_response()
+}
+
+async fn seal_decrypt(
+    State(state): State<Arc<Mutex<SealState>>>,
+    Json(request): Json<Value>,
+) -> Response {
+    let field = |name: &str| request[name].as_str().unwrap_or_default().to_string();
+    let (requester, package_id) = (field("requesterAddress"), field("packageId"));
+
+    let mut state = state.lock().unwrap();
+    state.decrypt_requests += 1;
+
+    if let Some((remaining, status)) = state.failures {
+        state.failures = (remaining > 1).then_some((remaining - 1, status));
+        return (
+            status,
+            Json(json!({ "success": false, "error": "seal api failure" })),
+        )
+            .into_response();
+    }
+    if state.denied.contains(&requester) {
+        return (
+            StatusCode::FORBIDDEN,
+            Json(json!({
+                "success": false,
+                "error": "Access denied",
+                "reportId": field("reportId"),
+                "requester": requester
+            })),
+        )
+            .into_response();
+    }
+    if state.key_servers_down {
+        return (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(json!({
+                "success": false,
+                "code": "NOT_ENOUGH_KEY_SERVERS",
+                "error": "Failed to fetch keys",
+                "sealError": "1 of 3 key servers responded, threshold is 2"
+            })),
+        )
+            .into_response();
+    }
+
+    let Ok(ciphertext) = general_purpose::STANDARD.decode(field("encryptedData")) else {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(json!({ "success": false, "error": "invalid base64" })),
+        )
+            .into_response();
+    };
+    let plaintext = fake_seal_xor(&ciphertext, &field("objectId"), &package_id);
+
+    Json(json!({
+        "success": true,
+        "data": general_purpose::STANDARD.encode(&plaintext),
+        "report": serde_json::from_slice::<Value>(&plaintext).unwrap_or(Value::Null),
+        "mode": "real-seal",
+        "metadata": {
+            "reportId": field("reportId"),
+            "requester": requester,
+            "decryptedAt": 0,
+            "sizeBytes": plaintext.len()
+        }
+    }))
+    .into_response()
+}
+
+/// 假 Publisher 的狀態
+#[derive(Default)]
+struct PublisherState {
+    /// 成功存儲的內容
+    uploads: Vec<Vec<u8>>,
+    /// 每個請求的查詢字符串（按到達順序，包括失敗的請求）
+    queries: Vec<Option<String>>,
+    /// 接下來要返回錯誤的請求數與狀態碼
+    failures: Option<(usize, StatusCode)>,
+}
+
+/// 假 Walrus Publisher
+pub struct FakePublisher {
+    url: String,
+    state: Arc<Mutex<PublisherState>>,
+}
+
+impl FakePublisher {
+    /// 啟動假 Publisher，Blob ID 為內容 SHA-256 的 URL-safe Base64
+    pub async fn start() -> Self {
+        let state = Arc::new(Mutex::new(PublisherState::default()));
+        let router = Router::new()
+            .route("/v1/blobs", put(store_blob))
+            .with_state(Arc::clone(&state));
+
+        Self {
+            url: spawn(router).await,
+            state,
+        }
+    }
+
+    /// 基礎 URL
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// 已記錄的上傳內容
+    pub fn uploads(&self) -> Vec<Vec<u8>> {
+        self.state.lock().unwrap().uploads.clone()
+    }
+
+    /// 每個請求的查詢字符串（如 `Some("epochs=3")`），包括失敗的請求
+    pub fn queries(&self) -> Vec<Option<String>> {
+        self.state.lock().unwrap().queries.clone()
+    }
+
+    /// 接下來的 `count` 個請求返回 `status`（不記錄上傳內容）
+    pub fn fail_next(&self, count: usize, status: StatusCode) {
+        self.state.lock().unwrap().failures = (count > 0).then_some((count, status));
+    }
+
+    /// 計算假 Publisher 為內容分配的 Blob ID
+    pub fn blob_id_for(data: &[u8]) -> String {
+        general_purpose::URL_SAFE_NO_PAD.encode(Sha256::digest(data))
+    }
+}
+
+async fn store_blob(
+    State(state): State<Arc<Mutex<PublisherState>>>,
+    uri: Uri,
+    body: Bytes,
+) -> Response {
+    let mut state = state.lock().unwrap();
+    state.queries.push(uri.query().map(str::to_string));
+
+    if let Some((remaining, status)) = state.failures {
+        state.failures = (remaining > 1).then_some((remaining - 1, status));
+        return (status, "publisher failure").into_response();
+    }
+
+    let blob_id = FakePublisher::blob_id_for(&body);
+    state.uploads.push(body.to_vec());
+
+    Json(json!({
+        "newlyCreated": {
+            "blobObject": { "blobId": blob_id, "size": body.len() }
+        }
+    }))
+    .into_response()
+}
+
+/// 假 Sui RPC 返回的鏈 ID
+pub const FAKE_CHAIN_IDENTIFIER: &str = "4c78adac";
+
+/// `suix_queryEvents` 每頁的默認事件數
+const FAKE_EVENT_PAGE_LIMIT: usize = 50;
+
+/// 假 Sui RPC 的狀態
+#[derive(Default)]
+struct FakeChain {
+    requests: Vec<Value>,
+    /// (事件類型, parsedJson)，按發出順序
+    events: Vec<(String, Value)>,
+    /// 對象 ID → (類型, 字段)
+    objects: HashMap<String, (String, Value)>,
+    /// 對象 ID → `owner`（未設置時響應中不含 `owner`）
+    owners: HashMap<String, Value>,
+    /// 所有者地址 → [(代幣對象 ID, 餘額)]
+    coins: HashMap<String, Vec<(String, u64)>>,
+    /// `suix_getLatestSuiSystemState` 返回的 epoch
+    epoch: u64,
+    /// 最新檢查點（序號, 摘要）；未設置時檢查點查詢返回錯誤
+    checkpoint: Option<(u64, String)>,
+}
+
+impl FakeChain {
+    /// `sui_getObject` 對 `object_id` 的結果（不存在時為 `notExists` 錯誤）
+    fn object_response(&self, object_id: &str) -> Value {
+        match self.objects.get(object_id) {
+            Some((object_type, fields)) => {
+                let mut data = json!({
+                    "objectId": object_id,
+                    "version": "1",
+                    "content": {
+                        "dataType": "moveObject",
+                        "type": object_type,
+                        "fields": fields
+                    }
+                });
+                if let Some(owner) = self.owners.get(object_id) {
+                    data["owner"] = owner.clone();
+                }
+                json!({ "data": data })
+            }
+            None => json!({ "error": { "code": "notExists", "object_id": object_id } }),
+        }
+    }
+}
+
+/// 假 Sui JSON-RPC
+pub struct FakeSuiRpc {
+    url: String,
+    chain: Arc<Mutex<FakeChain>>,
+}
+
+impl FakeSuiRpc {
+    /// 啟動假 Sui RPC，記錄請求並對 `unsafe_moveCall` 返回確定性交易字節
+    /// （`sui_getChainIdentifier` 返回固定的鏈 ID；`suix_queryEvents` 與
+    /// `sui_getObject` / `sui_multiGetObjects` 返回經 [`add_event`](Self::add_event) /
+    /// [`add_object`](Self::add_object) 加入的固定值，`suix_getCoins` 返回經
+    /// [`add_coin`](Self::add_coin) 加入的代幣）
+    pub async fn start() -> Self {
+        let chain = Arc::new(Mutex::new(FakeChain::default()));
+        let router = Router::new()
+            .route("/", post(sui_rpc))
+            .with_state(Arc::clone(&chain));
+
+        Self {
+            url: spawn(router).await,
+            chain,
+        }
+    }
+
+    /// 基礎 URL
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// 已記錄的 JSON-RPC 請求
+    pub fn requests(&self) -> Vec<Value> {
+        self.chain.lock().unwrap().requests.clone()
+    }
+
+    /// 加入一個 Move 事件（`event_type` 形如 `0xpkg::module::Event`）
+    pub fn add_event(&self, event_type: impl Into<String>, parsed_json: Value) {
+        self.chain
+            .lock()
+            .unwrap()
+            .events
+            .push((event_type.into(), parsed_json));
+    }
+
+    /// 設置 `suix_getLatestSuiSystemState` 返回的當前 epoch（默認 0）
+    pub fn set_epoch(&self, epoch: u64) {
+        self.chain.lock().unwrap().epoch = epoch;
+    }
+
+    /// 設置 `sui_getLatestCheckpointSequenceNumber` / `sui_getCheckpoint` 返回的最新檢查點
+    pub fn set_checkpoint(&self, sequence_number: u64, digest: impl Into<String>) {
+        self.chain.lock().unwrap().checkpoint = Some((sequence_number, digest.into()));
+    }
+
+    /// 加入一個 Move 對象（`sui_getObject` 按 `object_id` 原樣匹配）
+    pub fn add_object(&self, object_id: impl Into<String>, object_type: &str, fields: Value) {
+        self.chain
+            .lock()
+            .unwrap()
+            .objects
+            .insert(object_id.into(), (object_type.to_string(), fields));
+    }
+
+    /// 設置對象的 `owner`（如 `{"Shared": {"initial_shared_version": 1}}`）
+    pub fn set_owner(&self, object_id: impl Into<String>, owner: Value) {
+        self.chain
+            .lock()
+            .unwrap()
+            .owners
+            .insert(object_id.into(), owner);
+    }
+
+    /// 給 `owner` 加入一枚 SUI 代幣（`suix_getCoins` 返回）
+    pub fn add_coin(&self, owner: impl Into<String>, coin_id: impl Into<String>, balance: u64) {
+        self.chain
+            .lock()
+            .unwrap()
+            .coins
+            .entry(owner.into())
+            .or_default()
+            .push((coin_id.into(), balance));
+    }
+}
+
+async fn sui_rpc(
+    State(chain): State<Arc<Mutex<FakeChain>>>,
+    Json(request): Json<Value>,
+) -> Json<Value> {
+    let mut chain = chain.lock().unwrap();
+    chain.requests.push(request.clone());
+    let params = &request["params"];
+
+    let result = match request["method"].as_str() {
+        Some("sui_getChainIdentifier") => json!(FAKE_CHAIN_IDENTIFIER),
+        Some("suix_getLatestSuiSystemState") => json!({ "epoch": chain.epoch.to_string() }),
+        Some("sui_getLatestCheckpointSequenceNumber") if chain.checkpoint.is_some() => {
+            json!(chain.checkpoint.as_ref().unwrap().0.to_string())
+        }
+        Some("sui_getCheckpoint") if chain.checkpoint.is_some() => {
+            let (sequence_number, digest) = chain.checkpoint.as_ref().unwrap();
+            json!({ "sequenceNumber": sequence_number.to_string(), "digest": digest })
+        }
+        Some("suix_queryEvents") => {
+            let event_type = params.pointer("/0/MoveEventType").and_then(Value::as_str);
+            let mut events: Vec<_> = chain
+                .events
+                .iter()
+                .enumerate()
+                .filter(|(_, (ty, _))| Some(ty.as_str()) == event_type)
+                .map(|(seq, (ty, parsed))| {
+                    json!({
+                        "id": { "txDigest": format!("tx{}", seq), "eventSeq": seq.to_string() },
+                        "type": ty,
+                        "parsedJson": parsed
+                    })
+                })
+                .collect();
+            if params[3].as_bool() == Some(true) {
+                events.reverse();
+            }
+
+            // 游標為已返回的事件數
+            let start = params[1].as_str().and_then(|c| c.parse().ok()).unwrap_or(0);
+            let limit = params[2]
+                .as_u64()
+                .map_or(FAKE_EVENT_PAGE_LIMIT, |l| l as usize);
+            let end = (start + limit).min(events.len());
+            json!({
+                "data": events.get(start..end).unwrap_or_default(),
+                "nextCursor": (end < events.len()).then(|| end.to_string()),
+                "hasNextPage": end < events.len()
+            })
+        }
+        Some("sui_getObject") => chain.object_response(params[0].as_str().unwrap_or_default()),
+        Some("sui_multiGetObjects") => params[0]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .map(|object_id| chain.object_response(object_id.as_str().unwrap_or_default()))
+            .collect(),
+        Some("suix_getCoins") => {
+            let owner = params[0].as_str().unwrap_or_default();
+            let coins: Vec<_> = chain
+                .coins
+                .get(owner)
+                .into_iter()
+                .flatten()
+                .map(|(id, balance)| {
+                    json!({
+                        "coinType": "0x2::sui::SUI",
+                        "coinObjectId": id,
+                        "version": "1",
+                        "balance": balance.to_string()
+                    })
+                })
+                .collect();
+            json!({ "data": coins, "nextCursor": null, "hasNextPage": false })
+        }
+        Some("unsafe_moveCall") => {
+            let digest = Sha256::digest(request["params"].to_string().as_bytes());
+            json!({
+                "txBytes": general_purpose::STANDARD.encode(digest),
+                "gas": [],
+                "inputObjects": []
+            })
+        }
+        _ => {
+            return Json(json!({
+                "jsonrpc": "2.0",
+                "id": request["id"],
+                "error": { "code": -32601, "message": "Method not found" }
+            }))
+        }
+    };
+
+    Json(json!({ "jsonrpc": "2.0", "id": request["id"], "result": result }))
+}
+
+/// 假存儲節點：對每個 `POST /v1/challenge` 返回固定的狀態碼、Content-Type 與響應體
+///
+/// [`FakeStorageNode::start_walrus`] 啟動的節點改為提供 Walrus 的 sliver 與元數據端點
+pub struct FakeStorageNode {
+    url: String,
+    state: Arc<StorageNodeState>,
+}
+
+struct StorageNodeState {
+    status: StatusCode,
+    content_type: &'static str,
+    body: Vec<u8>,
+    delay: std::time::Duration,
+    /// 優先於固定響應返回的 (狀態碼, Retry-After)
+    scripted: Mutex<VecDeque<(StatusCode, Option<&'static str>)>>,
+    /// (進行中, 最大並發, 總請求數)
+    counters: Mutex<(usize, usize, usize)>,
+    /// 每個請求的到達時間
+    request_times: Mutex<Vec<std::time::Instant>>,
+    /// Walrus 風格提供的 primary sliver（按 sliver pair 索引）
+    slivers: Vec<Vec<u8>>,
+    /// `GET /health` 返回的狀態碼
+    health: Mutex<StatusCode>,
+}
+
+impl FakeStorageNode {
+    /// 啟動假存儲節點
+    pub async fn start(status: StatusCode, content_type: &'static str, body: Vec<u8>) -> Self {
+        Self::start_delayed(status, content_type, body, std::time::Duration::ZERO).await
+    }
+
+    /// 啟動每個響應都延遲 `delay` 的假存儲節點
+    pub async fn start_delayed(
+        status: StatusCode,
+        content_type: &'static str,
+        body: Vec<u8>,
+        delay: std::time::Duration,
+    ) -> Self {
+        let state = Arc::new(StorageNodeState {
+            status,
+            content_type,
+            body,
+            delay,
+            scripted: Mutex::new(VecDeque::new()),
+            counters: Mutex::new((0, 0, 0)),
+            request_times: Mutex::new(Vec::new()),
+            slivers: Vec::new(),
+            health: Mutex::new(StatusCode::OK),
+        });
+        let router = Router::new()
+            .route("/v1/challenge", post(challenge_error))
+            .route("/health", get(node_health))
+            .with_state(Arc::clone(&state));
+
+        Self {
+            url: spawn(router).await,
+            state,
+        }
+    }
+
+    /// 啟動提供 Walrus GET 端點的假存儲節點（沒有 `/v1/challenge`）
+    ///
+    /// 對任意 Blob ID，元數據列出每個 sliver 的葉子哈希（`hash_leaf`，hex），
+    /// `slivers/{index}/primary` 返回第 `index` 個 sliver；其他 sliver 類型返回 404
+    pub async fn start_walrus(slivers: Vec<Vec<u8>>) -> Self {
+        let state = Arc::new(StorageNodeState {
+            status: StatusCode::OK,
+            content_type: "application/octet-stream",
+            body: Vec::new(),
+            delay: std::time::Duration::ZERO,
+            scripted: Mutex::new(VecDeque::new()),
+            counters: Mutex::new((0, 0, 0)),
+            request_times: Mutex::new(Vec::new()),
+            slivers,
+            health: Mutex::new(StatusCode::OK),
+        });
+        let router = Router::new()
+            .route("/health", get(node_health))
+            .route("/v1/blobs/:id/metadata", get(walrus_metadata))
+            .route("/v1/blobs/:id/slivers/:index/:kind", get(walrus_sliver))
+            .with_state(Arc::clone(&state));
+
+        Self {
+            url: spawn(router).await,
+            state,
+        }
+    }
+
+    /// 基礎 URL
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// 下一個請求返回 `status`（可帶 `Retry-After` 頭），之後恢復固定響應；可多次調用排隊
+    pub fn respond_next(&self, status: StatusCode, retry_after: Option<&'static str>) {
+        self.state
+            .scripted
+            .lock()
+            .unwrap()
+            .push_back((status, retry_after));
+    }
+
+    /// 之後的 `GET /health` 返回 `status`（200 時響應體報告 `healthy`）
+    pub fn set_health(&self, status: StatusCode) {
+        *self.state.health.lock().unwrap() = status;
+    }
+
+    /// 收到的請求數（Walrus 風格下元數據與 sliver 請求分別計數；不含健康檢查）
+    pub fn requests(&self) -> usize {
+        self.state.counters.lock().unwrap().2
+    }
+
+    /// 各請求的到達時間（按到達順序；不含健康檢查）
+    pub fn request_times(&self) -> Vec<std::time::Instant> {
+        self.state.request_times.lock().unwrap().clone()
+    }
+
+    /// 同時處理中的挑戰請求數的峰值
+    pub fn max_in_flight(&self) -> usize {
+        self.state.counters.lock().unwrap().1
+    }
+}
+
+/// 請求結束（包括客戶端斷開、處理被取消）時減少進行中計數
+struct InFlight(Arc<StorageNodeState>);
+
+impl Drop for InFlight {
+    fn drop(&mut self) {
+        self.0.counters.lock().unwrap().0 -= 1;
+    }
+}
+
+/// 計數並延遲請求，返回排隊的響應（若有）；守衛在請求結束時減少進行中計數
+async fn enter(state: &Arc<StorageNodeState>) -> (InFlight, Option<Response>) {
+    {
+        let mut counters = state.counters.lock().unwrap();
+        counters.0 += 1;
+        counters.1 = counters.1.max(counters.0);
+        counters.2 += 1;
+    }
+    state
+        .request_times
+        .lock()
+        .unwrap()
+        .push(std::time::Instant::now());
+    let in_flight = InFlight(Arc::clone(state));
+    tokio::time::sleep(state.delay).await;
+
+    let scripted = state.scripted.lock().unwrap().pop_front();
+    let response = scripted.map(|(status, retry_after)| {
+        let mut response = (status, "node busy").into_response();
+        if let Some(retry_after) = retry_after {
+            response.headers_mut().insert(
+                axum::http::header::RETRY_AFTER,
+                axum::http::HeaderValue::from_static(retry_after),
+            );
+        }
+        response
+    });
+    (in_flight, response)
+}
+
+async fn challenge_error(State(state): State<Arc<StorageNodeState>>) -> Response {
+    let (_in_flight, scripted) = enter(&state).await;
+    if let Some(response) = scripted {
+        return response;
+    }
+
+    (
+        state.status,
+        [(axum::http::header::CONTENT_TYPE, state.content_type)],
+        state.body.clone(),
+    )
+        .into_response()
+}
+
+async fn node_health(State(state): State<Arc<StorageNodeState>>) -> Response {
+    let status = *state.health.lock().unwrap();
+    if status.is_success() {
+        Json(json!({ "status": "healthy" })).into_response()
+    } else {
+        (status, "unhealthy").into_response()
+    }
+}
+
+async fn walrus_metadata(State(state): State<Arc<StorageNodeState>>) -> Response {
+    let (_in_flight, scripted) = enter(&state).await;
+    if let Some(response) = scripted {
+        return response;
+    }
+
+    let hashes: Vec<Value> = state
+        .slivers
+        .iter()
+        .map(|sliver| json!({ "primary_hash": hex::encode(hash_leaf(sliver)) }))
+        .collect();
+    Json(json!({ "hashes": hashes })).into_response()
+}
+
+async fn walrus_sliver(
+    State(state): State<Arc<StorageNodeState>>,
+    Path((_id, index, kind)): Path<(String, usize, String)>,
+) -> Response {
+    let (_in_flight, scripted) = enter(&state).await;
+    if let Some(response) = scripted {
+        return response;
+    }
+
+    match state.slivers.get(index) {
+        Some(sliver) if kind == "primary" => {
+            ([(header::CONTENT_TYPE, state.content_type)], sliver.clone()).into_response()
+        }
+        _ => (StatusCode::NOT_FOUND, "sliver not found").into_response(),
+    }
+}
@@ -0,0 +1,682 @@
+//! 審計員公鑰信任庫（驗證者註冊表）
+//!
+//! 報告消費者需要驗證大量審計員的報告，逐次在命令行傳入原始公鑰並不可行。
+//! 本模塊定義本地信任庫文件，將審計員地址映射到其 PQC 公鑰：
+//!
+//! ```text
+//! {
+//!   "version": 1,
+//!   "auditors": {
+//!     "0xabc...": {
+//!       "public_key": "hex...",
+//!       "algorithm": 3,
+//!       "key_id": "1a2b3c4d5e6f7081",
+//!       "first_seen": 1700000000,
+//!       "pinned": false,
+//!       "note": "TOFU"
+//!     }
+//!   }
+//! }
+//! ```
+//!
+//! # 信任模型
+//!
+//! - **TOFU（首次使用即信任）**: 驗證未知審計員的報告時自動記錄其公鑰並發出警告
+//! - **密鑰變更檢測**: 已知審計員出示不同公鑰時直接失敗
+//! - **重新固定（re-pin）**: 只有顯式執行 `pin` 才能替換已記錄的公鑰
+//!
+//! # 並發安全
+//!
+//! 多個 verify 進程可能同時更新同一信任庫。所有寫入都通過 [`TrustStore::update`]
+//! 在 `<path>.lock` 鎖文件保護下進行「讀取 → 修改 → 原子替換」。
+//!
+//! # 使用示例
+//!
+//! ```no_run
+//! use auditor_node::trust::{TrustStore, TrustOutcome};
+//!
+//! # fn example(public_key: &[u8]) -> auditor_node::Result<()> {
+//! let outcome = TrustStore::update("trust_store.json", |store| {
+//!     store.check_or_tofu("0xauditor", public_key, 3)
+//! })?;
+//!
+//! if outcome == TrustOutcome::FirstUse {
+//!     println!("New auditor recorded on first use");
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::error::{AuditorError, Result};
+use base64::{engine::general_purpose, Engine as _};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tracing::{debug, info, warn};
+
+/// 信任庫文件格式版本
+pub const TRUST_STORE_VERSION: u32 = 1;
+
+/// 默認信任庫路徑
+pub const DEFAULT_TRUST_STORE_PATH: &str = "trust_store.json";
+
+/// 等待鎖文件的最長時間
+const LOCK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// 重試獲取鎖的間隔
+const LOCK_RETRY_INTERVAL: Duration = Duration::from_millis(50);
+
+/// 超過此時間的鎖文件視為崩潰進程遺留，可以清除
+const STALE_LOCK_AGE: Duration = Duration::from_secs(60);
+
+/// 信任庫中的單個審計員條目
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TrustEntry {
+    /// PQC 公鑰（hex 編碼）
+    pub public_key: String,
+
+    /// PQC 算法（1=Falcon512, 2=Dilithium2, 3=Dilithium3）
+    pub algorithm: u8,
+
+    /// 公鑰指紋（SHA-256 前 8 字節，hex）
+    pub key_id: String,
+
+    /// 首次記錄時間（Unix 秒）
+    pub first_seen: u64,
+
+    /// 是否由操作員顯式固定（false 表示 TOFU 自動記錄）
+    pub pinned: bool,
+
+    /// 備註
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+impl TrustEntry {
+    /// 由公鑰字節創建條目
+    pub fn new(public_key: &[u8], algorithm: u8, pinned: bool, note: Option<String>) -> Self {
+        Self {
+            public_key: hex::encode(public_key),
+            algorithm,
+            key_id: key_id(public_key),
+            first_seen: now_secs(),
+            pinned,
+            note,
+        }
+    }
+
+    /// 解碼公鑰字節
+    pub fn public_key_bytes(&self) -> Result<Vec<u8>> {
+        hex::decode(&self.public_key)
+            .map_err(|e| AuditorError::Trust(format!("Corrupt public key in trust store: {}", e)))
+    }
+
+    /// 判斷條目是否記錄了給定公鑰
+    pub fn matches(&self, public_key: &[u8], algorithm: u8) -> bool {
+        self.algorithm == algorithm
+            && self
+                .public_key
+                .eq_ignore_ascii_case(&hex::encode(public_key))
+    }
+}
+
+/// 查詢審計員公鑰的結果（只讀，不修改信任庫）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrustStatus {
+    /// 審計員未記錄
+    Unknown,
+    /// 公鑰與記錄一致
+    Match,
+    /// 公鑰與記錄不一致
+    Mismatch,
+}
+
+/// [`TrustStore::check_or_tofu`] 的結果
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrustOutcome {
+    /// 已知審計員，公鑰一致
+    Trusted,
+    /// 未知審計員，已按 TOFU 記錄
+    FirstUse,
+}
+
+/// 審計員公鑰信任庫
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TrustStore {
+    /// 文件格式版本
+    pub version: u32,
+
+    /// 審計員地址 → 信任條目
+    #[serde(default)]
+    pub auditors: BTreeMap<String, TrustEntry>,
+}
+
+impl TrustStore {
+    /// 創建空信任庫
+    pub fn new() -> Self {
+        Self {
+            version: TRUST_STORE_VERSION,
+            auditors: BTreeMap::new(),
+        }
+    }
+
+    /// 從文件加載信任庫（文件不存在時返回空信任庫）
+    ///
+    /// 只讀加載不獲取鎖；需要修改時請使用 [`TrustStore::update`]。
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+
+        if !path.exists() {
+            debug!(
+                "Trust store {} does not exist, starting empty",
+                path.display()
+            );
+            return Ok(Self::new());
+        }
+
+        let content = fs::read_to_string(path)?;
+        let store: Self = serde_json::from_str(&content).map_err(|e| {
+            AuditorError::Trust(format!("Invalid trust store {}: {}", path.display(), e))
+        })?;
+
+        if store.version > TRUST_STORE_VERSION {
+            return Err(AuditorError::Trust(format!(
+                "Unsupported trust store version {} (max {})",
+                store.version, TRUST_STORE_VERSION
+            )));
+        }
+
+        Ok(store)
+    }
+
+    /// 原子寫入信任庫（先寫臨時文件再重命名）
+    ///
+    /// 調用者應持有鎖；並發場景請使用 [`TrustStore::update`]。
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+
+        if let Some(parent) = path.parent() {
+            if !parent.as_os_str().is_empty() {
+                fs::create_dir_all(parent)?;
+            }
+        }
+
+        let tmp_path = sibling_path(path, &format!("tmp.{}", std::process::id()));
+        let json = serde_json::to_vec_pretty(self)?;
+
+        {
+            let mut file = fs::File::create(&tmp_path)?;
+            file.write_all(&json)?;
+            file.sync_all()?;
+        }
+        fs::rename(&tmp_path, path)?;
+
+        Ok(())
+    }
+
+    /// 在鎖保護下加載、修改並保存信任庫
+    ///
+    /// 閉包返回錯誤時不寫回文件。
+    pub fn update<T, F>(path: impl AsRef<Path>, f: F) -> Result<T>
+    where
+        F: FnOnce(&mut TrustStore) -> Result<T>,
+    {
+        let path = path.as_ref();
+        let _lock = LockFile::acquire(path)?;
+
+        let mut store = Self::load(path)?;
+        let value = f(&mut store)?;
+        store.save(path)?;
+
+        Ok(value)
+    }
+
+    /// 查詢審計員條目
+    pub fn get(&self, address: &str) -> Option<&TrustEntry> {
+        self.auditors.get(address)
+    }
+
+    /// 查詢公鑰是否與記錄一致
+    pub fn status(&self, address: &str, public_key: &[u8], algorithm: u8) -> TrustStatus {
+        match self.auditors.get(address) {
+            None => TrustStatus::Unknown,
+            Some(entry) if entry.matches(public_key, algorithm) => TrustStatus::Match,
+            Some(_) => TrustStatus::Mismatch,
+        }
+    }
+
+    /// 添加審計員（顯式添加的條目視為已固定）
+    ///
+    /// # 錯誤
+    /// - 審計員已存在且公鑰不同: 返回 `Trust` 錯誤（請改用 [`TrustStore::pin`]）
+    pub fn add(
+        &mut self,
+        address: &str,
+        public_key: &[u8],
+        algorithm: u8,
+        note: Option<String>,
+    ) -> Result<()> {
+        match self.status(address, public_key, algorithm) {
+            TrustStatus::Mismatch => Err(key_change_error(
+                address,
+                &self.auditors[address],
+                public_key,
+            )),
+            TrustStatus::Match => {
+                let entry = self.auditors.get_mut(address).expect("entry exists");
+                entry.pinned = true;
+                if note.is_some() {
+                    entry.note = note;
+                }
+                Ok(())
+            }
+            TrustStatus::Unknown => {
+                info!(
+                    "Trust store: added auditor {} ({})",
+                    address,
+                    key_id(public_key)
+                );
+                self.auditors.insert(
+                    address.to_string(),
+                    TrustEntry::new(public_key, algorithm, true, note),
+                );
+                Ok(())
+            }
+        }
+    }
+
+    /// 固定審計員公鑰
+    ///
+    /// - `public_key` 為 `None`: 固定當前記錄的公鑰（審計員必須已存在）
+    /// - `public_key` 為 `Some`: 以新公鑰替換記錄（顯式 re-pin）
+    pub fn pin(&mut self, address: &str, public_key: Option<(&[u8], u8)>) -> Result<()> {
+        match public_key {
+            None => {
+                let entry = self.auditors.get_mut(address).ok_or_else(|| {
+                    AuditorError::Trust(format!("Auditor {} is not in the trust store", address))
+                })?;
+                entry.pinned = true;
+                info!("Trust store: pinned auditor {} ({})", address, entry.key_id);
+            }
+            Some((key, algorithm)) => {
+                let note = self.auditors.get(address).and_then(|e| e.note.clone());
+                if let Some(previous) = self.auditors.get(address) {
+                    if !previous.matches(key, algorithm) {
+                        warn!(
+                            "Trust store: re-pinning auditor {} from {} to {}",
+                            address,
+                            previous.key_id,
+                            key_id(key)
+                        );
+                    }
+                }
+                self.auditors.insert(
+                    address.to_string(),
+                    TrustEntry::new(key, algorithm, true, note),
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// 移除審計員，返回被移除的條目
+    pub fn remove(&mut self, address: &str) -> Option<TrustEntry> {
+        let removed = self.auditors.remove(address);
+        if removed.is_some() {
+            info!("Trust store: removed auditor {}", address);
+        }
+        removed
+    }
+
+    /// 驗證報告時檢查審計員公鑰
+    ///
+    /// - 未知審計員: 按 TOFU 記錄（未固定）並警告
+    /// - 已知審計員且公鑰一致: 信任
+    /// - 已知審計員但公鑰不同: 返回 `Trust` 錯誤
+    pub fn check_or_tofu(
+        &mut self,
+        address: &str,
+        public_key: &[u8],
+        algorithm: u8,
+    ) -> Result<TrustOutcome> {
+        match self.status(address, public_key, algorithm) {
+            TrustStatus::Match => Ok(TrustOutcome::Trusted),
+            TrustStatus::Mismatch => Err(key_change_error(
+                address,
+                &self.auditors[address],
+                public_key,
+            )),
+            TrustStatus::Unknown => {
+                warn!(
+                    "⚠️  Trust on first use: recording unknown auditor {} with key {}",
+                    address,
+                    key_id(public_key)
+                );
+                self.auditors.insert(
+                    address.to_string(),
+                    TrustEntry::new(public_key, algorithm, false, Some("TOFU".to_string())),
+                );
+                Ok(TrustOutcome::FirstUse)
+            }
+        }
+    }
+}
+
+/// 解析命令行傳入的公鑰：文件路徑、hex（可帶 `0x`）或 Base64
+///
+/// 文件內容可以是原始公鑰字節（如 `pqc_public.key`），也可以是 hex/Base64 文本。
+pub fn parse_public_key(value: &str) -> Result<Vec<u8>> {
+    let path = Path::new(value);
+    if path.is_file() {
+        let bytes = fs::read(path).map_err(|e| {
+            AuditorError::Trust(format!("Failed to read public key {}: {}", path.display(), e))
+        })?;
+        return Ok(std::str::from_utf8(&bytes)
+            .ok()
+            .and_then(|text| decode_key_text(text.trim()))
+            .unwrap_or(bytes));
+    }
+
+    decode_key_text(value.trim()).ok_or_else(|| {
+        AuditorError::Trust(format!(
+            "Public key is neither an existing file, hex nor base64: {}",
+            value
+        ))
+    })
+}
+
+fn decode_key_text(text: &str) -> Option<Vec<u8>> {
+    if text.is_empty() {
+        return None;
+    }
+
+    let digits = text.strip_prefix("0x").unwrap_or(text);
+    if let Ok(bytes) = hex::decode(digits) {
+        return Some(bytes);
+    }
+
+    general_purpose::STANDARD
+        .decode(text)
+        .or_else(|_| general_purpose::URL_SAFE_NO_PAD.decode(text.trim_end_matches('=')))
+        .ok()
+}
+
+/// 計算公鑰指紋（SHA-256 前 8 字節，hex）
+pub fn key_id(public_key: &[u8]) -> String {
+    hex::encode(&Sha256::digest(public_key)[..8])
+}
+
+fn key_change_error(address: &str, entry: &TrustEntry, presented: &[u8]) -> AuditorError {
+    AuditorError::Trust(format!(
+        "Auditor {} presented key {} but trust store has {}{}; re-pin explicitly to accept",
+        address,
+        key_id(presented),
+        entry.key_id,
+        if entry.pinned { " (pinned)" } else { "" }
+    ))
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// `trust_store.json` → `trust_store.json.<suffix>`
+fn sibling_path(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".");
+    name.push(suffix);
+    path.with_file_name(name)
+}
+
+/// 信任庫鎖文件（Drop 時釋放）
+struct LockFile {
+    path: PathBuf,
+}
+
+impl LockFile {
+    fn acquire(store_path: &Path) -> Result<Self> {
+        let path = sibling_path(store_path, "lock");
+        if let Some(parent) = path.parent() {
+            if !parent.as_os_str().is_empty() {
+                fs::create_dir_all(parent)?;
+            }
+        }
+
+        let deadline = Instant::now() + LOCK_TIMEOUT;
+
+        loop {
+            match OpenOptions::new().write(true).create_new(true).open(&path) {
+                Ok(mut file) => {
+                    let _ = writeln!(file, "{}", std::process::id());
+                    return Ok(Self { path });
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {
+                    if Self::is_stale(&path) {
+                        warn!("Removing stale trust store lock {}", path.display());
+                        let _ = fs::remove_file(&path);
+                        continue;
+                    }
+                    if Instant::now() >= deadline {
+                        return Err(AuditorError::Trust(format!(
+                            "Timed out waiting for trust store lock {}",
+                            path.display()
+                        )));
+                    }
+                    std::thread::sleep(LOCK_RETRY_INTERVAL);
+                }
+                Err(e) => return Err(e.into()),
+            }
+        }
+    }
+
+    fn is_stale(path: &Path) -> bool {
+        fs::metadata(path)
+            .and_then(|m| m.modified())
+            .ok()
+            .and_then(|modified| modified.elapsed().ok())
+            .map(|age| age > STALE_LOCK_AGE)
+            .unwrap_or(false)
+    }
+}
+
+impl Drop for LockFile {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    fn temp_store_path() -> PathBuf {
+        std::env::temp_dir()
+            .join(format!("trust_test_{}", rand::random::<u32>()))
+            .join("trust_store.json")
+    }
+
+    #[test]
+    fn test_parse_public_key_formats() {
+        let key: Vec<u8> = (0..=255).collect();
+        let hex_key = hex::encode(&key);
+        let base64_key = general_purpose::STANDARD.encode(&key);
+
+        assert_eq!(parse_public_key(&hex_key).unwrap(), key);
+        assert_eq!(parse_public_key(&format!("0x{}", hex_key)).unwrap(), key);
+        assert_eq!(parse_public_key(&base64_key).unwrap(), key);
+        assert_eq!(
+            parse_public_key(&general_purpose::URL_SAFE_NO_PAD.encode(&key)).unwrap(),
+            key
+        );
+
+        // 文件：原始字節與文本編碼都接受
+        let dir = temp_store_path().parent().unwrap().to_path_buf();
+        fs::create_dir_all(&dir).unwrap();
+        let raw = dir.join("pqc_public.key");
+        fs::write(&raw, &key).unwrap();
+        assert_eq!(parse_public_key(raw.to_str().unwrap()).unwrap(), key);
+        let text = dir.join("pqc_public.hex");
+        fs::write(&text, format!("{}\n", hex_key)).unwrap();
+        assert_eq!(parse_public_key(text.to_str().unwrap()).unwrap(), key);
+        fs::remove_dir_all(&dir).ok();
+
+        assert!(matches!(
+            parse_public_key("not a key!"),
+            Err(AuditorError::Trust(_))
+        ));
+        assert!(parse_public_key("").is_err());
+    }
+
+    #[test]
+    fn test_tofu_inserts_unknown_auditor() {
+        let mut store = TrustStore::new();
+        let key = vec![1u8; 32];
+
+        let outcome = store.check_or_tofu("0xaaa", &key, 3).unwrap();
+        assert_eq!(outcome, TrustOutcome::FirstUse);
+
+        let entry = store.get("0xaaa").unwrap();
+        assert!(!entry.pinned);
+        assert_eq!(entry.key_id, key_id(&key));
+        assert_eq!(entry.public_key_bytes().unwrap(), key);
+
+        // 第二次驗證同一公鑰 → 已信任
+        let outcome = store.check_or_tofu("0xaaa", &key, 3).unwrap();
+        assert_eq!(outcome, TrustOutcome::Trusted);
+    }
+
+    #[test]
+    fn test_key_change_is_rejected() {
+        let mut store = TrustStore::new();
+        store.check_or_tofu("0xaaa", &[1u8; 32], 3).unwrap();
+
+        let result = store.check_or_tofu("0xaaa", &[2u8; 32], 3);
+        assert!(matches!(result, Err(AuditorError::Trust(_))));
+
+        // 相同公鑰但算法不同也視為變更
+        assert_eq!(store.status("0xaaa", &[1u8; 32], 2), TrustStatus::Mismatch);
+
+        // 顯式 add 也不能覆蓋
+        assert!(store.add("0xaaa", &[2u8; 32], 3, None).is_err());
+        assert_eq!(
+            store.get("0xaaa").unwrap().public_key_bytes().unwrap(),
+            vec![1u8; 32]
+        );
+    }
+
+    #[test]
+    fn test_repin_overrides_key() {
+        let mut store = TrustStore::new();
+        store
+            .add("0xaaa", &[1u8; 32], 3, Some("ops team".to_string()))
+            .unwrap();
+        assert!(store.get("0xaaa").unwrap().pinned);
+
+        store.pin("0xaaa", Some((&[2u8; 32], 3))).unwrap();
+
+        let entry = store.get("0xaaa").unwrap();
+        assert!(entry.pinned);
+        assert_eq!(entry.note.as_deref(), Some("ops team"));
+        assert_eq!(
+            store.check_or_tofu("0xaaa", &[2u8; 32], 3).unwrap(),
+            TrustOutcome::Trusted
+        );
+        assert!(store.check_or_tofu("0xaaa", &[1u8; 32], 3).is_err());
+    }
+
+    #[test]
+    fn test_pin_and_remove() {
+        let mut store = TrustStore::new();
+        assert!(store.pin("0xaaa", None).is_err());
+
+        store.check_or_tofu("0xaaa", &[1u8; 32], 3).unwrap();
+        store.pin("0xaaa", None).unwrap();
+        assert!(store.get("0xaaa").unwrap().pinned);
+
+        assert!(store.remove("0xaaa").is_some());
+        assert!(store.remove("0xaaa").is_none());
+        assert_eq!(store.status("0xaaa", &[1u8; 32], 3), TrustStatus::Unknown);
+    }
+
+    #[test]
+    fn test_update_persists_and_releases_lock() {
+        let path = temp_store_path();
+
+        let outcome =
+            TrustStore::update(&path, |s| s.check_or_tofu("0xaaa", &[1u8; 32], 3)).unwrap();
+        assert_eq!(outcome, TrustOutcome::FirstUse);
+        assert!(!sibling_path(&path, "lock").exists());
+
+        let loaded = TrustStore::load(&path).unwrap();
+        assert_eq!(loaded.version, TRUST_STORE_VERSION);
+        assert_eq!(loaded.status("0xaaa", &[1u8; 32], 3), TrustStatus::Match);
+
+        // 閉包失敗時不寫回
+        let result = TrustStore::update(&path, |s| {
+            s.remove("0xaaa");
+            s.check_or_tofu("0xbbb", &[2u8; 32], 3)?;
+            Err::<(), _>(AuditorError::Trust("abort".to_string()))
+        });
+        assert!(result.is_err());
+        let loaded = TrustStore::load(&path).unwrap();
+        assert!(loaded.get("0xaaa").is_some());
+        assert!(loaded.get("0xbbb").is_none());
+
+        fs::remove_dir_all(path.parent().unwrap()).ok();
+    }
+
+    #[test]
+    fn test_concurrent_updates_are_serialized() {
+        let path = Arc::new(temp_store_path());
+
+        let handles: Vec<_> = (0..8u8)
+            .map(|i| {
+                let path = Arc::clone(&path);
+                std::thread::spawn(move || {
+                    TrustStore::update(path.as_path(), |s| {
+                        s.check_or_tofu(&format!("0x{:02x}", i), &[i; 32], 3)
+                    })
+                    .unwrap()
+                })
+            })
+            .collect();
+
+        for handle in handles {
+            assert_eq!(handle.join().unwrap(), TrustOutcome::FirstUse);
+        }
+
+        let loaded = TrustStore::load(path.as_path()).unwrap();
+        assert_eq!(loaded.auditors.len(), 8);
+
+        fs::remove_dir_all(path.parent().unwrap()).ok();
+    }
+
+    #[test]
+    fn test_stale_lock_is_recovered() {
+        let path = temp_store_path();
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+
+        let lock_path = sibling_path(&path, "lock");
+        fs::write(&lock_path, "99999").unwrap();
+        let old = SystemTime::now() - STALE_LOCK_AGE - Duration::from_secs(5);
+        fs::File::options()
+            .write(true)
+            .open(&lock_path)
+            .unwrap()
+            .set_modified(old)
+            .unwrap();
+
+        TrustStore::update(&path, |s| s.add("0xaaa", &[1u8; 32], 3, None)).unwrap();
+        assert!(!lock_path.exists());
+
+        fs::remove_dir_all(path.parent().unwrap()).ok();
+    }
+}
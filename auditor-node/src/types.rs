@@ -2,7 +2,45 @@
 //!
 //! 本模塊定義審計節點中各個子系統共享的數據結構
 
+use crate::baseline::{BaselineConfig, HashDrift};
+use crate::blob_id::{DEFAULT_VERIFY_BLOB_ID_MAX_BYTES, DEFAULT_VERIFY_BLOB_ID_N_SHARDS};
+use crate::breaker::BreakerConfig;
+use crate::chain_types::MoveU256;
+use crate::challenge_seed::{verify_seed, ChallengeSeedMode, ChallengeSeedSource};
+use crate::chunk_filter::{ChunkFilter, ChunkFilterConfig};
+use crate::commitment::{
+    verify_reveal, ChallengeCommitment, ChallengeReveal, CommitmentConfig, CommitmentLog,
+};
+use crate::content_cache::ContentCacheConfig;
+use crate::cosign::Cosignature;
+use crate::crypto::ct_eq_hex;
+use crate::crypto::sliver::{validate_sliver_index, LeafEncoding};
+use crate::error::{AuditorError, Result};
+use crate::history::{report_digest, DedupConfig, DeduplicatedFrom};
+use crate::http::HttpClientConfig;
+use crate::integrity::{AuditData, VerificationStatus};
+use crate::local_encryption::EncryptionFallback;
+use crate::logging::LogFormat;
+use crate::metadata_cache::DEFAULT_BLOB_METADATA_CACHE_TTL_SECS;
+use crate::node_error::DEFAULT_MAX_ERROR_BODY_LEN;
+use crate::node_health::NodeHealthConfig;
+use crate::notify::{default_webhook_events, NotifyEvent};
+use crate::producer::Producer;
+use crate::reaudit::ReauditConfig;
+use crate::report_archive::ReportArchiveConfig;
+use crate::resources::{
+    ResourceAction, ResourceDecision, ResourcePolicy, DEFAULT_DISK_HEADROOM_BYTES,
+    DEFAULT_MEMORY_HEADROOM_BYTES,
+};
+use crate::rotating_writer::RotationSettings;
+use crate::scheduler::SchedulerConfig;
+use crate::shutdown::DEFAULT_SHUTDOWN_GRACE_SECS;
+use crate::storage_node_client::ApiStyle;
+use base64::{engine::general_purpose, Engine as _};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
 
 // 暫時使用 String 代替 ObjectID（實際部署時啟用 Sui SDK）
 #[cfg(feature = "sui-sdk")]
@@ -11,6 +49,200 @@ use sui_types::base_types::ObjectID;
 #[cfg(not(feature = "sui-sdk"))]
 pub type ObjectID = String;
 
+/// 驗證 Sui 地址或對象 ID，返回規範形式（`0x` 加 64 位小寫十六進制）
+///
+/// 接受 `0x` 前綴的 1 到 64 位十六進制數字（前導零可省略，與 Sui 一致）；
+/// `name` 用於錯誤信息（例如 `"auditor address"`）
+pub fn validate_sui_address(name: &str, value: &str) -> Result<String> {
+    let digits = value
+        .strip_prefix("0x")
+        .filter(|hex| {
+            !hex.is_empty() && hex.len() <= 64 && hex.chars().all(|c| c.is_ascii_hexdigit())
+        })
+        .ok_or_else(|| {
+            AuditorError::Config(format!(
+                "Invalid {} (expected 0x followed by up to 64 hex digits): {}",
+                name, value
+            ))
+        })?;
+    Ok(format!("0x{:0>64}", digits.to_ascii_lowercase()))
+}
+
+/// Walrus Blob ID（32 字節）
+///
+/// 同一個 Blob ID 有三種表示：Aggregator API 使用的 URL-safe Base64、鏈上 `u256`
+/// （字節按小端解釋，`0x` 十六進制）以及原始字節。以獨立類型傳遞，
+/// 避免把一種格式當作另一種（如把 Blob ID 當作 Sui 對象 ID 查詢）。
+/// `Display` 與序列化使用 URL-safe Base64（無填充）。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct BlobId([u8; 32]);
+
+impl BlobId {
+    /// Blob ID 的字節長度
+    pub const LENGTH: usize = 32;
+
+    /// 從原始字節創建
+    pub fn from_bytes(bytes: [u8; 32]) -> Self {
+        Self(bytes)
+    }
+
+    /// 解析任一字符串表示：`0x` 前綴按十六進制 `u256`，否則按 URL-safe Base64（填充可選）
+    pub fn parse(value: &str) -> Result<Self> {
+        let value = value.trim();
+        if value.starts_with("0x") {
+            Self::from_hex_u256(value)
+        } else {
+            Self::from_base64_url(value)
+        }
+    }
+
+    /// 從 URL-safe Base64 解析（Aggregator API 的格式）
+    pub fn from_base64_url(value: &str) -> Result<Self> {
+        let bytes = general_purpose::URL_SAFE_NO_PAD
+            .decode(value.trim_end_matches('='))
+            .map_err(|e| AuditorError::InvalidBlobId(format!("{}: {}", value, e)))?;
+        Self::from_slice(&bytes)
+            .map_err(|e| AuditorError::InvalidBlobId(format!("{}: {}", value, e)))
+    }
+
+    /// 從 `0x` 前綴的十六進制 `u256` 解析（鏈上格式，前導零可省略）
+    pub fn from_hex_u256(value: &str) -> Result<Self> {
+        let digits = value
+            .strip_prefix("0x")
+            .ok_or_else(|| AuditorError::InvalidBlobId(format!("{}: missing 0x prefix", value)))?;
+        if digits.is_empty() || digits.len() > 2 * Self::LENGTH {
+            return Err(AuditorError::InvalidBlobId(format!(
+                "{}: expected 1 to 64 hex digits",
+                value
+            )));
+        }
+
+        let mut bytes = hex::decode(format!("{:0>64}", digits))
+            .map_err(|e| AuditorError::InvalidBlobId(format!("{}: {}", value, e)))?;
+        bytes.reverse();
+        Self::from_slice(&bytes)
+    }
+
+    /// 從原始字節切片創建（長度必須為 32）
+    pub fn from_slice(bytes: &[u8]) -> Result<Self> {
+        let bytes: [u8; 32] = bytes.try_into().map_err(|_| {
+            AuditorError::InvalidBlobId(format!(
+                "expected {} bytes, got {}",
+                Self::LENGTH,
+                bytes.len()
+            ))
+        })?;
+        Ok(Self(bytes))
+    }
+
+    /// URL-safe Base64（無填充）
+    pub fn to_base64_url(&self) -> String {
+        general_purpose::URL_SAFE_NO_PAD.encode(self.0)
+    }
+
+    /// `0x` 前綴的 64 位十六進制 `u256`（大端書寫）
+    pub fn to_hex_u256(&self) -> String {
+        let big_endian: Vec<u8> = self.0.iter().rev().copied().collect();
+        format!("0x{}", hex::encode(big_endian))
+    }
+
+    /// 原始字節
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+}
+
+impl fmt::Display for BlobId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_base64_url())
+    }
+}
+
+impl FromStr for BlobId {
+    type Err = AuditorError;
+
+    fn from_str(value: &str) -> Result<Self> {
+        Self::parse(value)
+    }
+}
+
+impl TryFrom<&str> for BlobId {
+    type Error = AuditorError;
+
+    fn try_from(value: &str) -> Result<Self> {
+        Self::parse(value)
+    }
+}
+
+impl TryFrom<&String> for BlobId {
+    type Error = AuditorError;
+
+    fn try_from(value: &String) -> Result<Self> {
+        Self::parse(value)
+    }
+}
+
+impl From<MoveU256> for BlobId {
+    fn from(value: MoveU256) -> Self {
+        Self(value.0)
+    }
+}
+
+impl From<BlobId> for MoveU256 {
+    fn from(value: BlobId) -> Self {
+        MoveU256(value.0)
+    }
+}
+
+impl Serialize for BlobId {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_base64_url())
+    }
+}
+
+impl<'de> Deserialize<'de> for BlobId {
+    fn deserialize<D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> std::result::Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        Self::parse(&value).map_err(serde::de::Error::custom)
+    }
+}
+
+/// 被審計 Blob 的標識
+///
+/// 同一個 Blob 可以用 Walrus Blob ID 或其 Sui 對象 ID 指定。兩者都可能寫成 `0x` 十六進制，
+/// 無法從字符串本身區分，因此由調用方（如 `--blob-id` 與 `--blob-object-id`）明確指定。
+/// 審計前會解析出另一個標識，使報告同時記錄兩者（見 [`crate::blob_lookup::resolve_blob_ref`]）
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum BlobRef {
+    /// Walrus Blob ID（URL-safe Base64 或 `0x` 十六進制 `u256`）
+    BlobId(String),
+    /// Blob 在 Sui 上的對象 ID（`0x` 十六進制）
+    ObjectId(String),
+}
+
+impl BlobRef {
+    /// 調用方給出的原始字符串
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::BlobId(id) | Self::ObjectId(id) => id,
+        }
+    }
+}
+
+impl fmt::Display for BlobRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::BlobId(id) => write!(f, "blob {}", id),
+            Self::ObjectId(id) => write!(f, "blob object {}", id),
+        }
+    }
+}
+
 /// Walrus Blob 元數據
 ///
 /// 從 Sui 區塊鏈查詢 Walrus Blob 對象得到的元數據
@@ -19,20 +251,32 @@ pub struct BlobMetadata {
     /// Blob 對象 ID（Sui 對象 ID）
     pub blob_object_id: ObjectID,
 
-    /// Blob ID（u256，Blake2b-256 哈希）
+    /// Blob ID（u256，Blake2b-256 哈希；Walrus 的 URL-safe Base64 形式）
     pub blob_id: String,
 
     /// 默克爾根哈希
+    ///
+    /// 鏈上 Blob 對象不記錄根哈希（只記錄由根哈希、編碼類型與大小派生的 `blob_id`），
+    /// 從鏈上讀取的元數據中為空，需要從存儲節點的 Blob 元數據取得
     pub merkle_root: Vec<u8>,
 
-    /// Blob 大小（字節）
+    /// Blob 大小（字節，未編碼）
     pub blob_size: u64,
 
+    /// 編碼類型（Move `encoding_type`）
+    pub encoding_type: u8,
+
     /// Erasure coding 參數 - 數據 slivers 數量
-    pub encoding_k: u16,
+    pub encoding_k: u64,
 
-    /// Erasure coding 參數 - 總 slivers 數量（包含冗餘）
-    pub encoding_n: u16,
+    /// Erasure coding 參數 - 總 slivers 數量（包含冗餘，可超過 `u16::MAX`）
+    pub encoding_n: u64,
+
+    /// Blob 註冊時的 epoch
+    pub registered_epoch: u32,
+
+    /// Blob 認證時的 epoch（尚未認證時為 `None`）
+    pub certified_epoch: Option<u32>,
 
     /// Blob 開始 epoch
     pub start_epoch: u32,
@@ -60,17 +304,29 @@ pub struct StorageNodeInfo {
 
     /// 節點是否在線
     pub is_online: bool,
+
+    /// 節點持有的 shard（為空時挑戰按輪詢分配到節點）
+    #[serde(default)]
+    pub shards: Vec<u16>,
 }
 
+/// 挑戰類型：完整 sliver
+pub const CHALLENGE_FULL_SLIVER: u8 = 1;
+
+/// 挑戰類型：recovery symbol（見 [`crate::crypto::sliver::RecoverySymbol`]）
+pub const CHALLENGE_RECOVERY_SYMBOL: u8 = 2;
+
 /// 審計挑戰
 ///
 /// 對存儲節點發起的單次挑戰
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AuditChallenge {
     /// Sliver 索引（0 到 n-1）
-    pub sliver_index: u16,
+    ///
+    /// 舊版報告以 `u16` 記錄索引，JSON 數值可直接反序列化為 `u64`
+    pub sliver_index: u64,
 
-    /// 目標 Shard ID（存儲節點 ID）
+    /// 目標 Shard ID（持有該 sliver 的 shard，見 `auditor::shard_for_sliver`）
     pub shard_id: u16,
 
     /// 挑戰類型（1=完整 sliver, 2=recovery symbol）
@@ -78,6 +334,10 @@ pub struct AuditChallenge {
 
     /// 挑戰時間戳
     pub timestamp: u64,
+
+    /// Recovery symbol 挑戰請求的 symbol 索引（0 到 n-1；完整 sliver 挑戰為 `None`）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub symbol_index: Option<u64>,
 }
 
 /// 審計響應
@@ -120,13 +380,111 @@ pub struct ChallengeResult {
 
     /// 失敗原因（如有）
     pub failure_reason: Option<String>,
+
+    /// 應答（或未能應答）該挑戰的存儲節點 URL；內容級審計與舊版報告中為空
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub node_url: Option<String>,
+
+    /// 存儲節點對響應的簽名是否有效；響應未簽名或節點公鑰未知時為空
+    ///
+    /// 簽名無效的挑戰記為失敗（見 [`crate::crypto::node_signature`]）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub node_signature_valid: Option<bool>,
+
+    /// 從首次請求到收到最終響應的時間（毫秒，含重試等待）
+    ///
+    /// 本字段與 `sliver_size_bytes`、`attempts` 為傳輸指標：不在簽名與完整性哈希範圍內，
+    /// 同一報告的簽名不隨網絡延遲變化。未發出請求（內容級審計、舊版報告）時為 0
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub response_time_ms: u64,
+
+    /// 收到的 sliver 大小（bytes）
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub sliver_size_bytes: u64,
+
+    /// 發出的請求次數（含重試）
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub attempts: u8,
+}
+
+/// 存儲節點響應的聚合統計（按挑戰結果的傳輸指標計算，供 SLA 報告）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResponseStats {
+    /// 計入統計的挑戰數（發出過請求的挑戰）
+    pub sampled_challenges: u32,
+
+    /// 響應時間中位數（毫秒）
+    pub p50_response_ms: u64,
+
+    /// 響應時間 95 分位（毫秒）
+    pub p95_response_ms: u64,
+
+    /// 收到的 sliver 總字節數
+    pub bytes_transferred: u64,
+}
+
+impl ResponseStats {
+    /// 按發出過請求的挑戰計算統計（分位數取最近秩）；沒有這樣的挑戰時返回 `None`
+    pub fn from_results(results: &[ChallengeResult]) -> Option<Self> {
+        let mut times: Vec<u64> = results
+            .iter()
+            .filter(|result| result.attempts > 0)
+            .map(|result| result.response_time_ms)
+            .collect();
+        if times.is_empty() {
+            return None;
+        }
+        times.sort_unstable();
+
+        let percentile = |p: usize| times[(times.len() * p).div_ceil(100).max(1) - 1];
+        Some(Self {
+            sampled_challenges: times.len() as u32,
+            p50_response_ms: percentile(50),
+            p95_response_ms: percentile(95),
+            bytes_transferred: results.iter().map(|result| result.sliver_size_bytes).sum(),
+        })
+    }
+}
+
+/// 數值字段為 0 時不序列化（新增的計數字段不改變既有報告的 JSON）
+fn is_zero<T: Default + PartialEq>(value: &T) -> bool {
+    *value == T::default()
+}
+
+/// 當前的報告 schema 版本（新生成的報告記錄此版本，見 [`crate::report`] 中的遷移）
+///
+/// - 1：未記錄 `schema_version` 的報告
+/// - 2：記錄 `schema_version`，且版本本身在簽名範圍內
+pub const REPORT_SCHEMA_VERSION: u32 = 2;
+
+/// 未記錄 `schema_version` 的報告按 v1 解析
+pub(crate) fn default_schema_version() -> u32 {
+    1
+}
+
+/// v1 報告不寫入 `schema_version`（JSON 與摘要與引入版本字段之前一致）
+pub(crate) fn is_v1_schema(version: &u32) -> bool {
+    *version == 1
 }
 
 /// 審計報告
 ///
-/// 完整的審計報告（提交到鏈上前的完整版本）
+/// 完整的審計報告（提交到鏈上前的完整版本）。這是唯一的規範報告模型：
+/// `main.rs`、[`Auditor`](crate::auditor::Auditor) 與
+/// [`AuditReportGenerator`](crate::audit_report::AuditReportGenerator) 都生成此類型，
+/// 簽名、驗證、聯署、歷史與鏈上提交都只接受此類型。
+///
+/// 完整性層數據（[`AuditData`]）以 [`IntegritySummary`] 的形式作為報告的組成部分，
+/// 兩者之間可以無損轉換。
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AuditReport {
+    /// 報告 schema 版本（未記錄時為 1；決定簽名字節，見 [`signing_bytes`](Self::signing_bytes)）
+    #[serde(
+        default = "default_schema_version",
+        skip_serializing_if = "is_v1_schema"
+    )]
+    pub schema_version: u32,
+
     /// 審計的 Blob ID
     pub blob_id: String,
 
@@ -145,6 +503,10 @@ pub struct AuditReport {
     /// 所有挑戰結果
     pub challenge_results: Vec<ChallengeResult>,
 
+    /// 挑戰所針對的 Sliver 總數 n（挑戰級報告；用於校驗 `challenge_results` 中的索引）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub encoding_n: Option<u64>,
+
     /// 總挑戰次數
     pub total_challenges: u16,
 
@@ -163,17 +525,708 @@ pub struct AuditReport {
     /// PQC 算法（1=Falcon512, 2=Dilithium2, 3=Dilithium3）
     pub pqc_algorithm: u8,
 
+    /// 簽名覆蓋簽名字節的摘要而非字節本身（大報告，見 [`pqc_signer::prehash`]）
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub pqc_prehashed: bool,
+
     /// 審計是否通過
     pub is_valid: bool,
 
     /// 失敗原因（如有）
     pub failure_reason: Option<String>,
+
+    /// HTTP 捕獲目錄的 Blake2b-256 摘要（啟用捕獲模式時）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub capture_digest: Option<String>,
+
+    /// 去重依據（相同內容近期已被完整審計，本次只做抽查）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deduplicated_from: Option<DeduplicatedFrom>,
+
+    /// 生成報告的構建（簽名時寫入）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub producer: Option<Producer>,
+
+    /// 公開的挑戰集（啟用挑戰承諾時）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub challenge_reveal: Option<ChallengeReveal>,
+
+    /// 挑戰種子的來源（`challenge_seed_mode = "sui_checkpoint"` 時，見 [`crate::challenge_seed`]）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub challenge_seed_source: Option<ChallengeSeedSource>,
+
+    /// 導出挑戰索引的種子（十六進制）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub challenge_seed: Option<String>,
+
+    /// 葉子哈希布隆過濾器（旁路數據，不序列化、不在簽名範圍內，記錄到審計歷史）
+    #[serde(skip)]
+    pub chunk_filter: Option<ChunkFilter>,
+
+    /// 其他審計員對同一報告的聯署（不在主簽名範圍內）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub cosignatures: Vec<Cosignature>,
+
+    /// 完整性層數據（由 [`AuditData`] 生成的報告）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub integrity: Option<IntegritySummary>,
+
+    /// 舊版 `SignedAuditReport` 的信封字段（僅遷移而來的報告，決定簽名字節）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub legacy_envelope: Option<LegacyEnvelope>,
+
+    /// 盲化鹽的指紋（僅盲化發布副本；此時 `blob_id` 為 HMAC 值，見 [`crate::blinding`]）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blinding_key_id: Option<String>,
+
+    /// 審計方式（Aggregator 下載或存儲節點 sliver 挑戰；較早的報告未記錄）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub audit_method: Option<AuditMethod>,
+
+    /// 存儲節點響應的聚合統計（挑戰級報告；不在簽名範圍內）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub response_stats: Option<ResponseStats>,
+
+    /// 未發出挑戰即結束的挑戰級報告中 Blob 的狀態（如存儲期已結束時為 `Expired`）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blob_status: Option<VerificationStatus>,
+
+    /// 審計關聯 ID（與審計過程中每行日誌的 `audit_id` 字段相同，見 [`crate::logging`]）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub audit_id: Option<String>,
+
+    /// 每個被挑戰 shard 的挑戰數 `(shard_id, count)`，按 shard_id 升序（挑戰按 shard 分層抽樣）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub shard_coverage: Vec<(u16, u16)>,
+
+    /// 審計時審計員在 AuditorRegistry 中的聲譽分數（註冊預檢未能讀取時為空，見 [`crate::preflight`]）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auditor_reputation_at_audit: Option<u64>,
+}
+
+/// 存儲期已結束的 Blob 報告的原因（報告仍有效，不計為存儲節點故障）
+pub const EXPIRED_REASON: &str = "blob past end_epoch";
+
+/// 審計方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditMethod {
+    /// 從 Aggregator 下載完整 Blob，挑戰本地構建的 Merkle Tree（不涉及存儲節點）
+    #[default]
+    Aggregator,
+    /// 向存儲節點發出 sliver 挑戰，按鏈上 Merkle 根驗證響應
+    StorageNodeChallenge,
+    /// 離線審計本地文件（不涉及網絡，見 [`crate::integrity::audit_local`]）
+    LocalFile,
+}
+
+impl AuditMethod {
+    /// 是否為默認的 Aggregator 審計（序列化 [`AuditData`] 時省略）
+    pub fn is_aggregator(&self) -> bool {
+        *self == Self::Aggregator
+    }
+}
+
+/// 完整性層摘要：[`AuditData`] 中報告頂層沒有的字段
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IntegritySummary {
+    /// 內容哈希（SHA-256，十六進制）
+    pub content_hash: String,
+
+    /// Merkle 根（Blake2b-256，十六進制）
+    pub merkle_root: String,
+
+    /// 文件大小（bytes）
+    pub file_size: u64,
+
+    /// 驗證狀態
+    pub verification_status: VerificationStatus,
+
+    /// 下載前的資源檢查決策
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resource_decision: Option<ResourceDecision>,
+
+    /// 與內容基準不符時的預期值與觀測值
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hash_drift: Option<HashDrift>,
+
+    /// 重新編碼推導的 `blob_id` 是否與被審計的一致（未驗證或已跳過時為空）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blob_id_verified: Option<bool>,
+
+    /// 構建 Merkle 樹的切片大小（bytes；記錄此字段之前的報告為空，按 4096 構建）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chunk_size: Option<u32>,
+
+    /// 鏈上元數據中的 Blob 對象 ID（以下鏈上字段只在按鏈上元數據審計時記錄）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blob_object_id: Option<String>,
+
+    /// Erasure coding 參數 - 數據 slivers 數量
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub encoding_k: Option<u64>,
+
+    /// Erasure coding 參數 - 總 slivers 數量
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub encoding_n: Option<u64>,
+
+    /// Blob 存儲期的開始 epoch
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start_epoch: Option<u32>,
+
+    /// Blob 存儲期的結束 epoch
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end_epoch: Option<u32>,
+
+    /// 元數據中的默克爾根（十六進制）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub onchain_merkle_root: Option<String>,
+
+    /// 下載大小是否與鏈上 `blob_size` 一致
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub size_matches_chain: Option<bool>,
+}
+
+/// 舊版 `SignedAuditReport` 的信封字段
+///
+/// 舊版簽名覆蓋 `AuditData` 的 JSON 而非整個報告；保留這些字段，
+/// 遷移後的報告才能按原字節驗證並無損轉換回舊格式
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LegacyEnvelope {
+    /// 內嵌的審計員公鑰（Base64）
+    pub auditor_public_key: String,
+
+    /// 報告生成時間戳（Unix 時間，秒）
+    pub report_timestamp: u64,
+}
+
+impl AuditReport {
+    /// 內容哈希（十六進制）：有完整性摘要時取摘要，否則編碼 `integrity_hash`
+    pub fn content_hash(&self) -> String {
+        match &self.integrity {
+            Some(integrity) => integrity.content_hash.clone(),
+            None => hex::encode(&self.integrity_hash),
+        }
+    }
+
+    /// 驗證狀態：沒有完整性摘要的挑戰級報告取 `blob_status`，否則按 `is_valid` 推斷
+    pub fn verification_status(&self) -> VerificationStatus {
+        match (&self.integrity, &self.blob_status) {
+            (Some(integrity), _) => integrity.verification_status.clone(),
+            (None, Some(status)) => status.clone(),
+            (None, None) if self.is_valid => VerificationStatus::Accessible,
+            (None, None) => VerificationStatus::Corrupted,
+        }
+    }
+
+    /// 報告摘要（緊湊 JSON 序列化的 SHA-256，十六進制）
+    ///
+    /// 去重審計通過此摘要引用被依據的完整審計報告
+    pub fn digest(&self) -> Result<String> {
+        report_digest(self)
+    }
+
+    /// 核對公開的挑戰集與承諾日誌中此前的承諾
+    ///
+    /// 報告未公開挑戰集時返回 `Commitment` 錯誤
+    pub fn verify_challenge_commitment(
+        &self,
+        commitments: &CommitmentLog,
+    ) -> Result<ChallengeCommitment> {
+        let reveal = self.challenge_reveal.as_ref().ok_or_else(|| {
+            AuditorError::Commitment(format!(
+                "Report for blob {} does not reveal its challenge set",
+                self.blob_id
+            ))
+        })?;
+        verify_reveal(commitments, &self.blob_id, reveal, self.timestamp)
+    }
+
+    /// 核對挑戰種子由記錄的 Sui 檢查點、Blob ID 與審計員地址導出
+    ///
+    /// 公開了挑戰集時還要求其種子與挑戰種子一致。報告未記錄種子、種子來源為 `random`
+    /// 或種子不符時返回 `Commitment` 錯誤
+    pub fn verify_challenge_seed(&self) -> Result<()> {
+        let (Some(source), Some(seed)) = (&self.challenge_seed_source, &self.challenge_seed) else {
+            return Err(AuditorError::Commitment(format!(
+                "Report for blob {} does not record a challenge seed",
+                self.blob_id
+            )));
+        };
+        verify_seed(source, seed, &self.blob_id, &self.auditor)?;
+
+        if let Some(reveal) = &self.challenge_reveal {
+            if !ct_eq_hex(&reveal.seed, seed)? {
+                return Err(AuditorError::Commitment(format!(
+                    "Revealed challenge set for blob {} was not drawn from the challenge seed",
+                    self.blob_id
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// 校驗挑戰結果中的 Sliver 索引都落在 `0..encoding_n` 內
+    ///
+    /// 未記錄 `encoding_n` 的報告（舊版或內容級審計）無從校驗，直接通過
+    pub fn validate_sliver_indices(&self) -> Result<()> {
+        let Some(n) = self.encoding_n else {
+            return Ok(());
+        };
+        for result in &self.challenge_results {
+            validate_sliver_index(result.challenge.sliver_index, n)?;
+        }
+        Ok(())
+    }
+
+    /// 簽名的規範字節
+    ///
+    /// 以 [`REPORT_SIGNING_DOMAIN`] 開頭，之後每個字段寫作「1 字節標籤 + 值」，按標籤遞增排列；
+    /// 值為 `None` 的可選字段整體省略，因此新增可選字段不影響既有報告的字節。
+    /// 不包含 `pqc_signature`、`pqc_algorithm`、`pqc_prehashed`、`cosignatures`、`legacy_envelope` 與旁路數據，
+    /// 也不包含傳輸指標（挑戰結果的響應時間、sliver 大小與請求次數，以及 `response_stats`）。
+    /// 字節按報告記錄的 `schema_version` 計算：v2 起簽入版本本身，v1 報告的字節與引入版本之前一致。
+    ///
+    /// 值的編碼：整數為定長小端序，`bool` 為 1 字節，字符串與字節串帶 u32 長度前綴，
+    /// 序列帶 u32 元素數前綴，嵌套結構按字段聲明順序編碼（其中 `Option` 以 0/1 標記），
+    /// 枚舉按固定編號
+    pub fn signing_bytes(&self) -> Vec<u8> {
+        let mut out = SigningEncoder::new();
+
+        out.tag(1).str(&self.blob_id);
+        out.tag(2).str(&self.blob_object_id.to_string());
+        out.tag(3).str(&self.auditor);
+        out.tag(4).u64(self.timestamp);
+        out.tag(5).u32(self.challenge_epoch);
+        out.tag(6).seq(&self.challenge_results, |out, result| {
+            out.u64(result.challenge.sliver_index)
+                .u16(result.challenge.shard_id)
+                .u8(result.challenge.challenge_type)
+                .u64(result.challenge.timestamp)
+                .bool(result.verified)
+                .bool(result.merkle_proof_valid)
+                .bytes(&result.response_hash)
+                .option(result.failure_reason.as_deref(), |out, reason| {
+                    out.str(reason);
+                });
+        });
+        if let Some(n) = self.encoding_n {
+            out.tag(7).u64(n);
+        }
+        out.tag(8).u16(self.total_challenges);
+        out.tag(9).u16(self.successful_verifications);
+        out.tag(10).u16(self.failed_verifications);
+        out.tag(11).bytes(&self.integrity_hash);
+        out.tag(12).bool(self.is_valid);
+        if let Some(reason) = &self.failure_reason {
+            out.tag(13).str(reason);
+        }
+        if let Some(digest) = &self.capture_digest {
+            out.tag(14).str(digest);
+        }
+        if let Some(source) = &self.deduplicated_from {
+            out.tag(15)
+                .str(&source.blob_id)
+                .str(&source.report_digest)
+                .u64(source.audited_at);
+        }
+        if let Some(producer) = &self.producer {
+            out.tag(16)
+                .str(&producer.auditor_node_version)
+                .str(&producer.pqc_signer_version)
+                .option(producer.git_commit.as_deref(), |out, commit| {
+                    out.str(commit);
+                })
+                .option(producer.git_dirty, |out, dirty| {
+                    out.bool(dirty);
+                })
+                .str(&producer.target)
+                .seq(&producer.features, |out, feature| {
+                    out.str(feature);
+                });
+        }
+        if let Some(reveal) = &self.challenge_reveal {
+            out.tag(17).str(&reveal.seed).u64(reveal.leaf_count).seq(
+                &reveal.indices,
+                |out, index| {
+                    out.u64(*index);
+                },
+            );
+        }
+        if let Some(integrity) = &self.integrity {
+            out.tag(18).integrity(integrity);
+        }
+        if let Some(key_id) = &self.blinding_key_id {
+            out.tag(19).str(key_id);
+        }
+        if let Some(method) = self.audit_method {
+            out.tag(20).u8(match method {
+                AuditMethod::Aggregator => 0,
+                AuditMethod::StorageNodeChallenge => 1,
+                AuditMethod::LocalFile => 2,
+            });
+        }
+        if self.challenge_results.iter().any(|r| r.node_url.is_some()) {
+            out.tag(21).seq(&self.challenge_results, |out, result| {
+                out.option(result.node_url.as_deref(), |out, url| {
+                    out.str(url);
+                });
+            });
+        }
+        if let Some(verified) = self
+            .integrity
+            .as_ref()
+            .and_then(|integrity| integrity.blob_id_verified)
+        {
+            out.tag(22).bool(verified);
+        }
+        if let Some(chunk_size) = self
+            .integrity
+            .as_ref()
+            .and_then(|integrity| integrity.chunk_size)
+        {
+            out.tag(23).u32(chunk_size);
+        }
+        if self.schema_version >= 2 {
+            out.tag(24).u32(self.schema_version);
+        }
+        if let Some(status) = &self.blob_status {
+            out.tag(25).status(status);
+        }
+        if self
+            .challenge_results
+            .iter()
+            .any(|r| r.challenge.symbol_index.is_some())
+        {
+            out.tag(26).seq(&self.challenge_results, |out, result| {
+                out.option(result.challenge.symbol_index, |out, index| {
+                    out.u64(index);
+                });
+            });
+        }
+        if let Some(audit_id) = &self.audit_id {
+            out.tag(27).str(audit_id);
+        }
+        if self
+            .challenge_results
+            .iter()
+            .any(|r| r.node_signature_valid.is_some())
+        {
+            out.tag(28).seq(&self.challenge_results, |out, result| {
+                out.option(result.node_signature_valid, |out, valid| {
+                    out.bool(valid);
+                });
+            });
+        }
+        if let Some(source) = &self.challenge_seed_source {
+            out.tag(29);
+            match source {
+                ChallengeSeedSource::Random => out.u8(0),
+                ChallengeSeedSource::SuiCheckpoint {
+                    sequence_number,
+                    digest,
+                } => out.u8(1).u64(*sequence_number).str(digest),
+            };
+        }
+        if let Some(seed) = &self.challenge_seed {
+            out.tag(30).str(seed);
+        }
+        if let Some(integrity) = &self.integrity {
+            if let Some(object_id) = &integrity.blob_object_id {
+                out.tag(31).str(object_id);
+            }
+            if let Some(k) = integrity.encoding_k {
+                out.tag(32).u64(k);
+            }
+            if let Some(n) = integrity.encoding_n {
+                out.tag(33).u64(n);
+            }
+            if let Some(epoch) = integrity.start_epoch {
+                out.tag(34).u32(epoch);
+            }
+            if let Some(epoch) = integrity.end_epoch {
+                out.tag(35).u32(epoch);
+            }
+            if let Some(root) = &integrity.onchain_merkle_root {
+                out.tag(36).str(root);
+            }
+            if let Some(matches) = integrity.size_matches_chain {
+                out.tag(37).bool(matches);
+            }
+        }
+        if !self.shard_coverage.is_empty() {
+            out.tag(38)
+                .seq(&self.shard_coverage, |out, (shard, count)| {
+                    out.u16(*shard).u16(*count);
+                });
+        }
+        if let Some(reputation) = self.auditor_reputation_at_audit {
+            out.tag(39).u64(reputation);
+        }
+
+        out.finish()
+    }
+}
+
+/// 報告簽名字節的域分隔標籤（[`AuditReport::signing_bytes`]）
+pub const REPORT_SIGNING_DOMAIN: &[u8] = b"WALRUS_AUDIT_REPORT_V1";
+
+/// [`AuditReport::signing_bytes`] 的編碼器
+struct SigningEncoder(Vec<u8>);
+
+impl SigningEncoder {
+    fn new() -> Self {
+        Self(REPORT_SIGNING_DOMAIN.to_vec())
+    }
+
+    fn finish(self) -> Vec<u8> {
+        self.0
+    }
+
+    fn tag(&mut self, tag: u8) -> &mut Self {
+        self.u8(tag)
+    }
+
+    fn u8(&mut self, value: u8) -> &mut Self {
+        self.0.push(value);
+        self
+    }
+
+    fn u16(&mut self, value: u16) -> &mut Self {
+        self.0.extend_from_slice(&value.to_le_bytes());
+        self
+    }
+
+    fn u32(&mut self, value: u32) -> &mut Self {
+        self.0.extend_from_slice(&value.to_le_bytes());
+        self
+    }
+
+    fn u64(&mut self, value: u64) -> &mut Self {
+        self.0.extend_from_slice(&value.to_le_bytes());
+        self
+    }
+
+    fn i64(&mut self, value: i64) -> &mut Self {
+        self.0.extend_from_slice(&value.to_le_bytes());
+        self
+    }
+
+    fn bool(&mut self, value: bool) -> &mut Self {
+        self.u8(value as u8)
+    }
+
+    fn bytes(&mut self, value: &[u8]) -> &mut Self {
+        self.u32(value.len() as u32);
+        self.0.extend_from_slice(value);
+        self
+    }
+
+    fn str(&mut self, value: &str) -> &mut Self {
+        self.bytes(value.as_bytes())
+    }
+
+    fn option<T>(&mut self, value: Option<T>, encode: impl FnOnce(&mut Self, T)) -> &mut Self {
+        match value {
+            Some(value) => {
+                self.u8(1);
+                encode(self, value);
+            }
+            None => {
+                self.u8(0);
+            }
+        }
+        self
+    }
+
+    fn seq<T>(&mut self, items: &[T], mut encode: impl FnMut(&mut Self, &T)) -> &mut Self {
+        self.u32(items.len() as u32);
+        for item in items {
+            encode(self, item);
+        }
+        self
+    }
+
+    fn status(&mut self, status: &VerificationStatus) -> &mut Self {
+        self.u8(match status {
+            VerificationStatus::Accessible => 0,
+            VerificationStatus::Unreachable => 1,
+            VerificationStatus::Corrupted => 2,
+            VerificationStatus::Deleted => 3,
+            VerificationStatus::Expired => 4,
+        })
+    }
+
+    fn integrity(&mut self, integrity: &IntegritySummary) -> &mut Self {
+        self.str(&integrity.content_hash)
+            .str(&integrity.merkle_root)
+            .u64(integrity.file_size)
+            .status(&integrity.verification_status)
+            .option(integrity.resource_decision.as_ref(), |out, decision| {
+                out.u8(match decision.action {
+                    ResourceAction::Proceed => 0,
+                    ResourceAction::Degraded => 1,
+                    ResourceAction::Refused => 2,
+                })
+                .bool(decision.disable_caching)
+                .option(decision.max_concurrency, |out, limit| {
+                    out.u64(limit as u64);
+                })
+                .option(decision.disk_headroom_bytes, |out, bytes| {
+                    out.i64(bytes);
+                })
+                .option(decision.memory_headroom_bytes, |out, bytes| {
+                    out.i64(bytes);
+                })
+                .seq(&decision.reasons, |out, reason| {
+                    out.str(reason);
+                });
+            })
+            .option(integrity.hash_drift.as_ref(), |out, drift| {
+                out.str(&drift.expected_content_hash)
+                    .str(&drift.observed_content_hash)
+                    .str(&drift.expected_merkle_root)
+                    .str(&drift.observed_merkle_root)
+                    .u64(drift.expected_file_size)
+                    .u64(drift.observed_file_size)
+                    .u64(drift.baseline_at);
+            })
+    }
+}
+
+impl From<AuditData> for AuditReport {
+    /// 由完整性審計數據生成未簽名的報告（審計員地址為空，由簽名方填寫）
+    fn from(data: AuditData) -> Self {
+        // 存儲期已結束的 Blob 沒有可審計的內容，報告有效但記錄原因
+        let expired = data.verification_status == VerificationStatus::Expired;
+        let is_valid = expired
+            || (data.verification_status == VerificationStatus::Accessible
+                && data.failed_verifications == 0);
+
+        let failure_reason = if data.verification_status == VerificationStatus::Deleted {
+            Some("Blob deleted by owner (not a storage node failure)".to_string())
+        } else if expired {
+            Some(EXPIRED_REASON.to_string())
+        } else if data.blob_id_verified == Some(false) {
+            Some(
+                "Blob ID mismatch: downloaded content does not re-encode to the blob ID"
+                    .to_string(),
+            )
+        } else if !is_valid {
+            Some(format!(
+                "Verification status: {:?}, failures: {}",
+                data.verification_status, data.failed_verifications
+            ))
+        } else {
+            None
+        };
+
+        Self {
+            schema_version: REPORT_SCHEMA_VERSION,
+            integrity_hash: hex::decode(&data.content_hash).unwrap_or_default(),
+            blob_id: data.blob_id,
+            blob_object_id: data.sui_object_id.unwrap_or_default(),
+            auditor: String::new(),
+            timestamp: data.timestamp,
+            challenge_epoch: 0,
+            challenge_results: vec![],
+            encoding_n: None,
+            total_challenges: data.total_challenges,
+            successful_verifications: data.successful_verifications,
+            failed_verifications: data.failed_verifications,
+            pqc_signature: vec![],
+            pqc_algorithm: 0,
+            pqc_prehashed: false,
+            is_valid,
+            failure_reason,
+            capture_digest: data.capture_digest,
+            deduplicated_from: data.deduplicated_from,
+            producer: data.producer,
+            challenge_reveal: data.challenge_reveal,
+            challenge_seed_source: data.challenge_seed_source,
+            challenge_seed: data.challenge_seed,
+            chunk_filter: data.chunk_filter,
+            cosignatures: vec![],
+            integrity: Some(IntegritySummary {
+                content_hash: data.content_hash,
+                merkle_root: data.merkle_root,
+                file_size: data.file_size,
+                verification_status: data.verification_status,
+                resource_decision: data.resource_decision,
+                hash_drift: data.hash_drift,
+                blob_id_verified: data.blob_id_verified,
+                chunk_size: data.chunk_size,
+                blob_object_id: data.blob_object_id,
+                encoding_k: data.encoding_k,
+                encoding_n: data.encoding_n,
+                start_epoch: data.start_epoch,
+                end_epoch: data.end_epoch,
+                onchain_merkle_root: data.onchain_merkle_root,
+                size_matches_chain: data.size_matches_chain,
+            }),
+            legacy_envelope: None,
+            blinding_key_id: None,
+            audit_method: Some(data.audit_method),
+            response_stats: None,
+            blob_status: None,
+            audit_id: None,
+            shard_coverage: vec![],
+            auditor_reputation_at_audit: None,
+        }
+    }
+}
+
+impl TryFrom<&AuditReport> for AuditData {
+    type Error = AuditorError;
+
+    /// 取回完整性審計數據（報告必須帶有完整性摘要）
+    fn try_from(report: &AuditReport) -> Result<Self> {
+        let integrity = report.integrity.as_ref().ok_or_else(|| {
+            AuditorError::Serialization(format!(
+                "Report for blob {} has no integrity summary",
+                report.blob_id
+            ))
+        })?;
+
+        Ok(Self {
+            blob_id: report.blob_id.clone(),
+            content_hash: integrity.content_hash.clone(),
+            merkle_root: integrity.merkle_root.clone(),
+            total_challenges: report.total_challenges,
+            successful_verifications: report.successful_verifications,
+            failed_verifications: report.failed_verifications,
+            file_size: integrity.file_size,
+            timestamp: report.timestamp,
+            verification_status: integrity.verification_status.clone(),
+            sui_object_id: Some(report.blob_object_id.clone()).filter(|id| !id.is_empty()),
+            resource_decision: integrity.resource_decision.clone(),
+            capture_digest: report.capture_digest.clone(),
+            deduplicated_from: report.deduplicated_from.clone(),
+            producer: report.producer.clone(),
+            challenge_reveal: report.challenge_reveal.clone(),
+            challenge_seed_source: report.challenge_seed_source.clone(),
+            challenge_seed: report.challenge_seed.clone(),
+            chunk_filter: report.chunk_filter.clone(),
+            hash_drift: integrity.hash_drift.clone(),
+            blob_id_verified: integrity.blob_id_verified,
+            chunk_size: integrity.chunk_size,
+            blob_object_id: integrity.blob_object_id.clone(),
+            encoding_k: integrity.encoding_k,
+            encoding_n: integrity.encoding_n,
+            start_epoch: integrity.start_epoch,
+            end_epoch: integrity.end_epoch,
+            onchain_merkle_root: integrity.onchain_merkle_root.clone(),
+            size_matches_chain: integrity.size_matches_chain,
+            audit_method: report.audit_method.unwrap_or_default(),
+        })
+    }
 }
 
 /// 配置結構（將在 config.rs 中使用）
 ///
-/// 審計節點運行時配置
+/// 審計節點運行時配置。未知鍵會導致反序列化失敗，拼寫錯誤不會被靜默忽略
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct AuditorConfig {
     /// Sui RPC 端點
     pub sui_rpc_url: String,
@@ -181,6 +1234,14 @@ pub struct AuditorConfig {
     /// Walrus 聚合器 API 端點
     pub walrus_aggregator_url: String,
 
+    /// Walrus Publisher API 端點（上傳簽名報告）
+    #[serde(default = "default_walrus_publisher_url")]
+    pub walrus_publisher_url: String,
+
+    /// 上傳報告的存儲 epoch 數（未設置時使用 Publisher 的默認值）
+    #[serde(default)]
+    pub walrus_storage_epochs: Option<u32>,
+
     /// 審計員私鑰路徑
     pub auditor_private_key_path: String,
 
@@ -196,15 +1257,31 @@ pub struct AuditorConfig {
     /// 審計間隔（秒）
     pub audit_interval_secs: u64,
 
-    /// HTTP 請求超時（秒）
+    /// 守護進程停機時等待在途審計完成的時限（秒；見 [`crate::shutdown`]）
+    #[serde(default = "default_shutdown_grace_secs")]
+    pub shutdown_grace_secs: u64,
+
+    /// HTTP 請求超時（秒；存儲節點請求的總時限）
     pub http_timeout_secs: u64,
 
+    /// HTTP 客戶端的連接/讀取超時、連接池與 User-Agent（見 [`crate::http`]）
+    #[serde(default)]
+    pub http: HttpClientConfig,
+
+    /// 存儲節點錯誤響應體寫入報告時的最大字符數（完整內容僅保存在 HTTP 捕獲中）
+    #[serde(default = "default_max_error_body_len")]
+    pub max_error_body_len: usize,
+
     /// 是否啟用 Seal 加密
     pub enable_seal_encryption: bool,
 
     /// Seal API 端點（可選）
     pub seal_api_url: Option<String>,
 
+    /// Seal 健康檢查或加密失敗時的處理方式（`none` 或 `local-aes`；見 [`crate::local_encryption`]）
+    #[serde(default)]
+    pub encryption_fallback: EncryptionFallback,
+
     /// Audit System 合約 Package ID
     pub audit_system_package_id: Option<String>,
 
@@ -216,44 +1293,473 @@ pub struct AuditorConfig {
 
     /// Incentives Object ID
     pub incentives_id: Option<String>,
+
+    /// 審計員 Sui 地址（Seal identity 與鏈上提交的發送者）
+    #[serde(default)]
+    pub auditor_address: Option<String>,
+
+    /// 審計員 Sui 私鑰文件（Sui keystore 格式：Base64(flag || 私鑰)）
+    #[serde(default)]
+    pub sui_key_path: Option<String>,
+
+    /// 是否將審計記錄提交到 Sui（需要 audit_system_package_id 與 auditor_address）
+    #[serde(default)]
+    pub submit_to_sui: bool,
+
+    /// 資源不足時的處理策略（refuse / degrade / warn_only）
+    #[serde(default)]
+    pub resource_policy: ResourcePolicy,
+
+    /// 磁盤餘量（字節）
+    #[serde(default = "default_disk_headroom_bytes")]
+    pub disk_headroom_bytes: u64,
+
+    /// 內存餘量（字節）
+    #[serde(default = "default_memory_headroom_bytes")]
+    pub memory_headroom_bytes: u64,
+
+    /// 日誌輸出格式（`text` 或 `json`；命令行 `--log-format` 優先）
+    #[serde(default)]
+    pub log_format: LogFormat,
+
+    /// 是否捕獲 HTTP 請求/響應（用於節點爭議取證）
+    #[serde(default)]
+    pub capture_http: bool,
+
+    /// HTTP 捕獲文件的根目錄
+    #[serde(default = "default_capture_dir")]
+    pub capture_dir: String,
+
+    /// 試運行：審計與簽名照常執行，上傳與鏈上提交改為記錄（見 [`crate::dry_run`]）
+    #[serde(default)]
+    pub dry_run: bool,
+
+    /// 試運行時寫入本應發送的負載的目錄
+    #[serde(default = "default_dry_run_dir")]
+    pub dry_run_dir: String,
+
+    /// 註冊預檢發現審計員未註冊時仍繼續審計（本地測試用，見 [`crate::preflight`]）
+    #[serde(default)]
+    pub allow_unregistered: bool,
+
+    /// 是否仍為已被所有者刪除或存儲期已結束的 Blob 發布報告（默認否：不計為節點故障）
+    #[serde(default)]
+    pub report_deleted_blobs: bool,
+
+    /// 追加寫入文件（審計歷史、內容基準、承諾日誌、存檔索引）的輪轉設置
+    #[serde(default)]
+    pub rotation: RotationSettings,
+
+    /// 跨 Blob 內容去重設置
+    #[serde(default)]
+    pub dedup: DedupConfig,
+
+    /// 失敗 Blob 的重審策略（啟用調度器時不使用）
+    #[serde(default)]
+    pub reaudit: ReauditConfig,
+
+    /// 守護進程的逐 Blob 審計調度
+    #[serde(default)]
+    pub scheduler: SchedulerConfig,
+
+    /// 守護進程報告的本地存檔與保留策略
+    #[serde(default)]
+    pub report_archive: ReportArchiveConfig,
+
+    /// 每個 Blob 的內容基準（跨運行檢測哈希漂移）
+    #[serde(default)]
+    pub baseline: BaselineConfig,
+
+    /// Chunk 哈希布隆過濾器（保存在去重歷史中，用於快速比對）
+    #[serde(default)]
+    pub chunk_filter: ChunkFilterConfig,
+
+    /// 挑戰前承諾挑戰集（commit-reveal）
+    #[serde(default)]
+    pub commitment: CommitmentConfig,
+
+    /// 挑戰種子模式（`random` 或 `sui_checkpoint`，見 [`crate::challenge_seed`]）
+    #[serde(default)]
+    pub challenge_seed_mode: ChallengeSeedMode,
+
+    /// 存儲節點默克爾樹葉子的編碼（見 [`LeafEncoding`]）
+    #[serde(default)]
+    pub leaf_encoding: LeafEncoding,
+
+    /// Aggregator 與存儲節點調用的熔斷器
+    #[serde(default)]
+    pub breaker: BreakerConfig,
+
+    /// 存儲節點健康監控（守護模式下排除不健康的節點）
+    #[serde(default)]
+    pub node_health: NodeHealthConfig,
+
+    /// 發布的報告是否以 HMAC 盲化 Blob ID（真實 ID 只保留在本地）
+    #[serde(default)]
+    pub blind_blob_ids: bool,
+
+    /// 已知有問題的 auditor-node 版本或提交前綴（`verify` 遇到時發出警告）
+    #[serde(default)]
+    pub denied_producer_versions: Vec<String>,
+
+    /// 是否向存儲節點發出 sliver 挑戰（否則、或未配置存儲節點時，使用 Aggregator 審計）
+    #[serde(default)]
+    pub use_storage_node_challenges: bool,
+
+    /// 存儲節點 API 端點（sliver 挑戰的目標）
+    #[serde(default)]
+    pub storage_node_urls: Vec<String>,
+
+    /// 存儲節點的 API 風格（`challenge` 或 `walrus`，見 [`ApiStyle`]）
+    #[serde(default)]
+    pub storage_node_api_style: ApiStyle,
+
+    /// 存儲節點 URL → 節點公鑰（十六進制或 Base64；32 字節 Ed25519 或 96 字節 BLS12-381），
+    /// 用於驗證挑戰響應的節點簽名（見 [`crate::crypto::node_signature`]）
+    #[serde(default)]
+    pub storage_node_public_keys: BTreeMap<String, String>,
+
+    /// 每次審計隨機挑選的存儲節點數（未設置時挑戰所有節點）
+    #[serde(default)]
+    pub storage_nodes_per_audit: Option<usize>,
+
+    /// 存儲節點挑戰中 recovery symbol 挑戰的比例（0.0 到 1.0；其餘為完整 sliver 挑戰）
+    #[serde(default)]
+    pub recovery_symbol_ratio: f64,
+
+    /// 同時執行的存儲節點挑戰數
+    #[serde(default = "default_max_parallel_challenges")]
+    pub max_parallel_challenges: usize,
+
+    /// 單次審計全部挑戰的時限（秒）；到期仍未完成的挑戰被取消並記為失敗
+    #[serde(default = "default_audit_deadline_secs")]
+    pub audit_deadline_secs: u64,
+
+    /// 單個挑戰重試的總時限（秒）；下一次等待會超出時放棄重試
+    #[serde(default = "default_storage_retry_budget_secs")]
+    pub storage_retry_budget_secs: u64,
+
+    /// 每個主機（存儲節點或 Aggregator）每秒最多發出的請求數（0 = 不限流；見 [`crate::rate_limit`]）
+    #[serde(default)]
+    pub max_requests_per_sec_per_host: u32,
+
+    /// 每個主機限流令牌桶的容量（允許的突發請求數）
+    #[serde(default = "default_burst")]
+    pub burst: u32,
+
+    /// 驗證內容確實屬於 `blob_id`（見 [`crate::blob_id`]）
+    ///
+    /// Aggregator 審計下載後以 RS2 重新編碼並比對（需要 `walrus-encoder` feature）；
+    /// 存儲節點挑戰在發出前確認元數據的默克爾根推導出 `blob_id`
+    #[serde(default)]
+    pub verify_blob_id: bool,
+
+    /// `verify_blob_id` 的 Blob 大小上限（bytes）；超過時跳過驗證
+    #[serde(default = "default_verify_blob_id_max_bytes")]
+    pub verify_blob_id_max_bytes: u64,
+
+    /// RS2 重新編碼使用的分片數（必須與 Walrus 網絡一致）
+    #[serde(default = "default_verify_blob_id_n_shards")]
+    pub verify_blob_id_n_shards: u16,
+
+    /// 守護進程 Prometheus 指標端點的監聽地址（如 `127.0.0.1:9184`；未設置時不啟動）
+    #[serde(default)]
+    pub metrics_listen_addr: Option<String>,
+
+    /// 審計結果通知的 webhook URL（未設置時不發送；見 [`crate::notify`]）
+    #[serde(default)]
+    pub webhook_url: Option<String>,
+
+    /// 發送到 webhook 的事件（默認只有 `audit_failed`）
+    #[serde(default = "default_webhook_events")]
+    pub webhook_events: Vec<NotifyEvent>,
+
+    /// webhook 請求體的 HMAC-SHA256 簽名密鑰（設置時帶 `X-Audit-Signature` 頭）
+    #[serde(default)]
+    pub webhook_secret: Option<String>,
+
+    /// 審計檢查點目錄（未設置時不寫檢查點，中斷的審計從頭開始；見 [`crate::checkpoint`]）
+    #[serde(default)]
+    pub checkpoint_dir: Option<String>,
+
+    /// 以 `ETag` 重新驗證的 Blob 內容緩存（見 [`crate::content_cache`]）
+    #[serde(default)]
+    pub content_cache: ContentCacheConfig,
+
+    /// Blob 鏈上元數據的緩存時間（秒；0 表示不緩存，見 [`crate::metadata_cache`]）
+    #[serde(default = "default_blob_metadata_cache_ttl_secs")]
+    pub blob_metadata_cache_ttl_secs: u64,
+
+    /// 簽名字節達到此大小（字節）的報告改為簽名其摘要（未設置時始終直接簽名；
+    /// 見 [`pqc_signer::prehash`]）
+    #[serde(default)]
+    pub report_prehash_threshold: Option<usize>,
+}
+
+fn default_disk_headroom_bytes() -> u64 {
+    DEFAULT_DISK_HEADROOM_BYTES
+}
+
+fn default_memory_headroom_bytes() -> u64 {
+    DEFAULT_MEMORY_HEADROOM_BYTES
+}
+
+fn default_shutdown_grace_secs() -> u64 {
+    DEFAULT_SHUTDOWN_GRACE_SECS
+}
+
+fn default_max_error_body_len() -> usize {
+    DEFAULT_MAX_ERROR_BODY_LEN
+}
+
+fn default_walrus_publisher_url() -> String {
+    "https://publisher.walrus-testnet.walrus.space".to_string()
+}
+
+fn default_capture_dir() -> String {
+    "./captures".to_string()
+}
+
+fn default_dry_run_dir() -> String {
+    "./dry-run".to_string()
+}
+
+fn default_max_parallel_challenges() -> usize {
+    8
+}
+
+fn default_audit_deadline_secs() -> u64 {
+    600
+}
+
+fn default_storage_retry_budget_secs() -> u64 {
+    60
+}
+
+fn default_burst() -> u32 {
+    10
+}
+
+fn default_verify_blob_id_max_bytes() -> u64 {
+    DEFAULT_VERIFY_BLOB_ID_MAX_BYTES
+}
+
+fn default_verify_blob_id_n_shards() -> u16 {
+    DEFAULT_VERIFY_BLOB_ID_N_SHARDS
+}
+
+fn default_blob_metadata_cache_ttl_secs() -> u64 {
+    DEFAULT_BLOB_METADATA_CACHE_TTL_SECS
 }
 
 impl Default for AuditorConfig {
+    /// 內置默認值（不讀取環境變量；環境變量層見 [`AuditorConfig::from_layers`]）
     fn default() -> Self {
         Self {
-            sui_rpc_url: std::env::var("SUI_RPC_URL")
-                .unwrap_or_else(|_| "https://fullnode.testnet.sui.io:443".to_string()),
-            walrus_aggregator_url: std::env::var("WALRUS_AGGREGATOR_URL")
-                .unwrap_or_else(|_| "https://aggregator.walrus-testnet.walrus.space".to_string()),
-            auditor_private_key_path: std::env::var("AUDITOR_PRIVATE_KEY_PATH")
-                .unwrap_or_else(|_| "./keys/auditor.key".to_string()),
-            pqc_keystore_path: std::env::var("PQC_KEYSTORE_PATH")
-                .unwrap_or_else(|_| "./keys/pqc_keystore".to_string()),
-            min_challenges: std::env::var("MIN_CHALLENGES")
-                .ok()
-                .and_then(|s| s.parse().ok())
-                .unwrap_or(10),
-            max_challenges: std::env::var("MAX_CHALLENGES")
-                .ok()
-                .and_then(|s| s.parse().ok())
-                .unwrap_or(100),
-            audit_interval_secs: std::env::var("AUDIT_INTERVAL_SECS")
-                .ok()
-                .and_then(|s| s.parse().ok())
-                .unwrap_or(3600),
-            http_timeout_secs: std::env::var("HTTP_TIMEOUT_SECS")
-                .ok()
-                .and_then(|s| s.parse().ok())
-                .unwrap_or(30),
-            enable_seal_encryption: std::env::var("ENABLE_SEAL_ENCRYPTION")
-                .ok()
-                .and_then(|s| s.parse().ok())
-                .unwrap_or(false),
-            seal_api_url: std::env::var("SEAL_API_URL").ok(),
-            audit_system_package_id: std::env::var("AUDIT_SYSTEM_PACKAGE_ID").ok(),
-            access_policy_package_id: std::env::var("ACCESS_POLICY_PACKAGE_ID").ok(),
-            auditor_registry_id: std::env::var("AUDITOR_REGISTRY_ID").ok(),
-            incentives_id: std::env::var("INCENTIVES_ID").ok(),
+            sui_rpc_url: "https://fullnode.testnet.sui.io:443".to_string(),
+            walrus_aggregator_url: "https://aggregator.walrus-testnet.walrus.space".to_string(),
+            walrus_publisher_url: default_walrus_publisher_url(),
+            walrus_storage_epochs: None,
+            auditor_private_key_path: "./keys/auditor.key".to_string(),
+            pqc_keystore_path: "./keys/pqc_keystore".to_string(),
+            min_challenges: 10,
+            max_challenges: 100,
+            audit_interval_secs: 3600,
+            shutdown_grace_secs: DEFAULT_SHUTDOWN_GRACE_SECS,
+            http_timeout_secs: 30,
+            http: HttpClientConfig::default(),
+            max_error_body_len: DEFAULT_MAX_ERROR_BODY_LEN,
+            enable_seal_encryption: false,
+            seal_api_url: None,
+            encryption_fallback: EncryptionFallback::default(),
+            audit_system_package_id: None,
+            access_policy_package_id: None,
+            auditor_registry_id: None,
+            incentives_id: None,
+            auditor_address: None,
+            sui_key_path: None,
+            submit_to_sui: false,
+            resource_policy: ResourcePolicy::default(),
+            disk_headroom_bytes: DEFAULT_DISK_HEADROOM_BYTES,
+            memory_headroom_bytes: DEFAULT_MEMORY_HEADROOM_BYTES,
+            log_format: LogFormat::default(),
+            capture_http: false,
+            capture_dir: default_capture_dir(),
+            dry_run: false,
+            dry_run_dir: default_dry_run_dir(),
+            allow_unregistered: false,
+            report_deleted_blobs: false,
+            rotation: RotationSettings::default(),
+            dedup: DedupConfig::default(),
+            reaudit: ReauditConfig::default(),
+            scheduler: SchedulerConfig::default(),
+            report_archive: ReportArchiveConfig::default(),
+            baseline: BaselineConfig::default(),
+            chunk_filter: ChunkFilterConfig::default(),
+            commitment: CommitmentConfig::default(),
+            challenge_seed_mode: ChallengeSeedMode::default(),
+            leaf_encoding: LeafEncoding::default(),
+            breaker: BreakerConfig::default(),
+            node_health: NodeHealthConfig::default(),
+            blind_blob_ids: false,
+            denied_producer_versions: Vec::new(),
+            use_storage_node_challenges: false,
+            storage_node_urls: Vec::new(),
+            storage_node_api_style: ApiStyle::default(),
+            storage_node_public_keys: BTreeMap::new(),
+            storage_nodes_per_audit: None,
+            recovery_symbol_ratio: 0.0,
+            max_parallel_challenges: default_max_parallel_challenges(),
+            audit_deadline_secs: default_audit_deadline_secs(),
+            storage_retry_budget_secs: default_storage_retry_budget_secs(),
+            max_requests_per_sec_per_host: 0,
+            burst: default_burst(),
+            verify_blob_id: false,
+            verify_blob_id_max_bytes: DEFAULT_VERIFY_BLOB_ID_MAX_BYTES,
+            verify_blob_id_n_shards: DEFAULT_VERIFY_BLOB_ID_N_SHARDS,
+            metrics_listen_addr: None,
+            webhook_url: None,
+            webhook_events: default_webhook_events(),
+            webhook_secret: None,
+            checkpoint_dir: None,
+            content_cache: ContentCacheConfig::default(),
+            blob_metadata_cache_ttl_secs: DEFAULT_BLOB_METADATA_CACHE_TTL_SECS,
+            report_prehash_threshold: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Walrus Testnet 上的 Blob ID 及其鏈上 `u256` 十六進制表示
+    const TESTNET_BLOB_IDS: [(&str, &str); 2] = [
+        (
+            "eRrTusk8yshFQpkemgDnbg0f4-qDo623V2NpeVG1Zcg",
+            "0xc865b55179696357b7ada383eae31f0d6ee7009a1e994245c8ca3cc9bad31a79",
+        ),
+        (
+            "M4hsZGQ1oCktdzegB6HnI6Mi28S2nqOPHxK-W7_4BUk",
+            "0x4905f8bf5bbe121f8fa39eb6c4db22a323e7a107a037772d29a03564646c8833",
+        ),
+    ];
+
+    fn timed_result(response_time_ms: u64, attempts: u8) -> ChallengeResult {
+        ChallengeResult {
+            challenge: AuditChallenge {
+                sliver_index: 0,
+                shard_id: 0,
+                challenge_type: 1,
+                timestamp: 0,
+                symbol_index: None,
+            },
+            verified: true,
+            merkle_proof_valid: true,
+            response_hash: vec![],
+            failure_reason: None,
+            node_url: None,
+            node_signature_valid: None,
+            response_time_ms,
+            sliver_size_bytes: 100,
+            attempts,
+        }
+    }
+
+    #[test]
+    fn test_response_stats_percentiles() {
+        assert_eq!(ResponseStats::from_results(&[]), None);
+        assert_eq!(ResponseStats::from_results(&[timed_result(0, 0)]), None);
+
+        // 1..=20 毫秒，另有一個未發出請求的挑戰（不計入）
+        let mut results: Vec<ChallengeResult> =
+            (1..=20).rev().map(|ms| timed_result(ms, 1)).collect();
+        results.push(timed_result(0, 0));
+        let stats = ResponseStats::from_results(&results).unwrap();
+        assert_eq!(stats.sampled_challenges, 20);
+        assert_eq!(stats.p50_response_ms, 10);
+        assert_eq!(stats.p95_response_ms, 19);
+        assert_eq!(stats.bytes_transferred, 2_100);
+
+        let single = ResponseStats::from_results(&[timed_result(42, 3)]).unwrap();
+        assert_eq!((single.p50_response_ms, single.p95_response_ms), (42, 42));
+    }
+
+    #[test]
+    fn test_validate_sui_address() {
+        let full = "0x1234567890abcdef1234567890abcdef1234567890abcdef1234567890abcdef";
+        assert_eq!(validate_sui_address("auditor address", full).unwrap(), full);
+        assert_eq!(
+            validate_sui_address("package ID", "0xA0D17").unwrap(),
+            format!("0x{:0>64}", "a0d17")
+        );
+
+        for invalid in ["", "0x", "1234", "0xzz", &format!("0x{}", "1".repeat(65))] {
+            let err = validate_sui_address("auditor address", invalid).unwrap_err();
+            assert!(matches!(err, AuditorError::Config(_)), "{}", err);
+            assert!(err.to_string().contains("Invalid auditor address"));
+        }
+    }
+
+    #[test]
+    fn test_blob_id_round_trips_between_encodings() {
+        for (base64_url, hex_u256) in TESTNET_BLOB_IDS {
+            let from_base64 = BlobId::parse(base64_url).unwrap();
+            let from_hex = BlobId::parse(hex_u256).unwrap();
+            assert_eq!(from_base64, from_hex);
+
+            assert_eq!(from_base64.to_base64_url(), base64_url);
+            assert_eq!(from_base64.to_hex_u256(), hex_u256);
+            assert_eq!(from_base64.to_string(), base64_url);
+            assert_eq!(
+                BlobId::from_slice(from_hex.as_bytes()).unwrap(),
+                from_base64
+            );
+
+            // 與鏈上 u256 參數一致
+            let value = MoveU256::from(from_base64);
+            assert_eq!(value, MoveU256::from_blob_id(base64_url).unwrap());
+            assert_eq!(BlobId::from(value), from_base64);
+        }
+    }
+
+    #[test]
+    fn test_blob_id_parsing_variants() {
+        let (base64_url, _) = TESTNET_BLOB_IDS[0];
+        let id = BlobId::parse(base64_url).unwrap();
+
+        // 填充與首尾空白不影響解析；短十六進制左側補零
+        assert_eq!(BlobId::parse(&format!(" {}= ", base64_url)).unwrap(), id);
+        assert_eq!(
+            BlobId::parse("0x101").unwrap().as_bytes()[..3],
+            [0x01, 0x01, 0x00]
+        );
+        assert_eq!(base64_url.parse::<BlobId>().unwrap(), id);
+        assert_eq!(BlobId::try_from(base64_url).unwrap(), id);
+
+        let json = serde_json::to_string(&id).unwrap();
+        assert_eq!(json, format!("\"{}\"", base64_url));
+        assert_eq!(serde_json::from_str::<BlobId>(&json).unwrap(), id);
+    }
+
+    #[test]
+    fn test_blob_id_rejects_wrong_lengths_and_formats() {
+        for invalid in [
+            "",
+            "blob-a",
+            "AAAA",
+            "0x",
+            "0xzz",
+            // 33 字節
+            "0x010000000000000000000000000000000000000000000000000000000000000000",
+        ] {
+            assert!(
+                matches!(BlobId::parse(invalid), Err(AuditorError::InvalidBlobId(_))),
+                "{:?} should be rejected",
+                invalid
+            );
         }
+        assert!(BlobId::from_slice(&[0u8; 31]).is_err());
+        assert!(serde_json::from_str::<BlobId>("\"blob-a\"").is_err());
     }
 }
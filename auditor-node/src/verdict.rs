@@ -0,0 +1,307 @@
+//! `verify` 子命令的驗證結論
+//!
+//! 報告消費者通常不寫 Rust 代碼，只需要一個明確的 VALID/INVALID 結論及其依據。
+//! [`ReportVerification`] 匯總報告的關鍵字段（Blob ID、時間戳、挑戰統計）與各項檢查結果：
+//!
+//! - PQC 簽名（`--public-key` / `--trust-store`）
+//! - 聯署簽名數量（`--min-signatures`）
+//! - 鏈上 `AuditRecord` 核對（`--against-chain`）
+//!
+//! 任何一項檢查失敗，結論即為 INVALID。結論可以人類可讀文本（[`fmt::Display`]）
+//! 或 JSON 輸出。
+
+use crate::audit_report::PqcAlgorithm;
+use crate::chain_verify::ChainVerification;
+use crate::trust::key_id;
+use crate::types::{AuditMethod, AuditReport};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// 總體結論
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum Verdict {
+    Valid,
+    Invalid,
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Verdict::Valid => "VALID",
+            Verdict::Invalid => "INVALID",
+        })
+    }
+}
+
+/// 一份報告的驗證結論
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReportVerification {
+    /// 總體結論
+    pub verdict: Verdict,
+
+    /// 報告中的 Blob ID
+    pub blob_id: String,
+
+    /// 報告中的審計員地址
+    pub auditor: String,
+
+    /// 審計執行時間戳
+    pub timestamp: u64,
+
+    /// 執行審計時的 epoch
+    pub challenge_epoch: u32,
+
+    /// 總挑戰次數
+    pub total_challenges: u16,
+
+    /// 成功驗證次數
+    pub successful_verifications: u16,
+
+    /// 失敗驗證次數
+    pub failed_verifications: u16,
+
+    /// 審計本身是否通過（報告內容，與簽名是否有效無關）
+    pub audit_passed: bool,
+
+    /// 簽名算法名稱（未知編號時為 `unknown(<id>)`）
+    pub pqc_algorithm: String,
+
+    /// 審計方式（報告未記錄時為 `None`）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub audit_method: Option<AuditMethod>,
+
+    /// 驗證所用公鑰的指紋
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key_id: Option<String>,
+
+    /// 主簽名是否有效（未檢查時為 `None`）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature_valid: Option<bool>,
+
+    /// 聯署簽名是否滿足要求（未檢查時為 `None`）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cosignatures_valid: Option<bool>,
+
+    /// 鏈上核對結果（`--against-chain`）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chain: Option<ChainVerification>,
+
+    /// 導致 INVALID 的原因
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub failures: Vec<String>,
+}
+
+impl ReportVerification {
+    /// 從報告創建結論（尚未執行任何檢查）
+    pub fn new(report: &AuditReport) -> Self {
+        Self {
+            verdict: Verdict::Valid,
+            blob_id: report.blob_id.clone(),
+            auditor: report.auditor.clone(),
+            timestamp: report.timestamp,
+            challenge_epoch: report.challenge_epoch,
+            total_challenges: report.total_challenges,
+            successful_verifications: report.successful_verifications,
+            failed_verifications: report.failed_verifications,
+            audit_passed: report.is_valid,
+            pqc_algorithm: PqcAlgorithm::from_id(report.pqc_algorithm)
+                .map(|algorithm| algorithm.as_str().to_string())
+                .unwrap_or_else(|| format!("unknown({})", report.pqc_algorithm)),
+            audit_method: report.audit_method,
+            key_id: None,
+            signature_valid: None,
+            cosignatures_valid: None,
+            chain: None,
+            failures: Vec::new(),
+        }
+    }
+
+    /// 所有已執行的檢查是否都通過
+    pub fn is_valid(&self) -> bool {
+        self.verdict == Verdict::Valid
+    }
+
+    /// 記錄主簽名檢查結果
+    pub fn record_signature(&mut self, public_key: &[u8], valid: bool) {
+        self.key_id = Some(key_id(public_key));
+        self.signature_valid = Some(valid);
+        if !valid {
+            self.fail("PQC signature is invalid");
+        }
+    }
+
+    /// 記錄聯署簽名檢查結果
+    pub fn record_cosignatures(&mut self, valid: bool, min_signatures: usize) {
+        self.cosignatures_valid = Some(valid);
+        if !valid {
+            self.fail(format!(
+                "Report does not carry {} valid signatures",
+                min_signatures
+            ));
+        }
+    }
+
+    /// 記錄鏈上核對結果
+    pub fn record_chain(&mut self, result: ChainVerification) {
+        if !result.is_match() {
+            self.fail(format!("On-chain check failed: {:?}", result.verdict));
+        }
+        self.chain = Some(result);
+    }
+
+    fn fail(&mut self, reason: impl Into<String>) {
+        self.verdict = Verdict::Invalid;
+        self.failures.push(reason.into());
+    }
+}
+
+impl fmt::Display for ReportVerification {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}  report for blob {}", self.verdict, self.blob_id)?;
+        writeln!(f, "  Auditor:       {}", self.auditor)?;
+        let time = i64::try_from(self.timestamp)
+            .ok()
+            .and_then(|secs| chrono::DateTime::from_timestamp(secs, 0))
+            .map(|time| format!(" ({})", time.to_rfc3339()))
+            .unwrap_or_default();
+        writeln!(f, "  Timestamp:     {}{}", self.timestamp, time)?;
+        writeln!(f, "  Epoch:         {}", self.challenge_epoch)?;
+        writeln!(
+            f,
+            "  Challenges:    {}/{} verified, {} failed",
+            self.successful_verifications, self.total_challenges, self.failed_verifications
+        )?;
+        if let Some(method) = self.audit_method {
+            writeln!(
+                f,
+                "  Method:        {}",
+                match method {
+                    AuditMethod::Aggregator => "aggregator download (no storage node challenges)",
+                    AuditMethod::StorageNodeChallenge => "storage node sliver challenges",
+                    AuditMethod::LocalFile => "local file (offline, no network)",
+                }
+            )?;
+        }
+        writeln!(
+            f,
+            "  Audit result:  {}",
+            if self.audit_passed { "PASS" } else { "FAIL" }
+        )?;
+
+        if let Some(valid) = self.signature_valid {
+            writeln!(
+                f,
+                "  Signature:     {} ({}, key {})",
+                if valid { "valid" } else { "INVALID" },
+                self.pqc_algorithm,
+                self.key_id.as_deref().unwrap_or("-")
+            )?;
+        }
+        if let Some(valid) = self.cosignatures_valid {
+            writeln!(
+                f,
+                "  Cosignatures:  {}",
+                if valid { "valid" } else { "INVALID" }
+            )?;
+        }
+        if let Some(chain) = &self.chain {
+            writeln!(
+                f,
+                "  On-chain:      {}",
+                if chain.is_match() {
+                    format!(
+                        "matches AuditRecord {}",
+                        chain.audit_record_id.as_deref().unwrap_or("-")
+                    )
+                } else {
+                    "MISMATCH".to_string()
+                }
+            )?;
+        }
+        for failure in &self.failures {
+            writeln!(f, "  Failure:       {}", failure)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chain_verify::ChainVerdict;
+    use serde_json::json;
+
+    fn report() -> AuditReport {
+        serde_json::from_value(json!({
+            "blob_id": "blob-1",
+            "blob_object_id": "0xb10b",
+            "auditor": "0xa11ce",
+            "timestamp": 1_700_000_000u64,
+            "challenge_epoch": 7,
+            "challenge_results": [],
+            "total_challenges": 10,
+            "successful_verifications": 9,
+            "failed_verifications": 1,
+            "integrity_hash": [],
+            "pqc_signature": [],
+            "pqc_algorithm": 3,
+            "is_valid": false,
+            "failure_reason": "1 challenge failed",
+            "audit_method": "storage_node_challenge",
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_valid_verdict_json() {
+        let mut verdict = ReportVerification::new(&report());
+        verdict.record_signature(&[1, 2, 3], true);
+        assert!(verdict.is_valid());
+
+        let value = serde_json::to_value(&verdict).unwrap();
+        assert_eq!(value["verdict"], "VALID");
+        assert_eq!(value["blob_id"], "blob-1");
+        assert_eq!(value["timestamp"], 1_700_000_000u64);
+        assert_eq!(value["successful_verifications"], 9);
+        assert_eq!(value["audit_passed"], false);
+        assert_eq!(value["pqc_algorithm"], "Dilithium3");
+        assert_eq!(value["audit_method"], "storage_node_challenge");
+        assert_eq!(value["signature_valid"], true);
+        assert!(value.get("failures").is_none());
+        assert!(value.get("chain").is_none());
+
+        let text = verdict.to_string();
+        assert!(text.starts_with("VALID  report for blob blob-1"));
+        assert!(text.contains("9/10 verified, 1 failed"));
+        assert!(text.contains("storage node sliver challenges"));
+        assert!(text.contains("2023-11-14T22:13:20+00:00"));
+    }
+
+    #[test]
+    fn test_any_failed_check_makes_verdict_invalid() {
+        let mut verdict = ReportVerification::new(&report());
+        verdict.record_signature(&[1, 2, 3], true);
+        verdict.record_cosignatures(false, 2);
+        verdict.record_chain(ChainVerification {
+            verdict: ChainVerdict::NoRecordFound,
+            blob_id: "blob-1".to_string(),
+            auditor: "0xa11ce".to_string(),
+            challenge_epoch: 7,
+            report_digest: String::new(),
+            integrity_hash: String::new(),
+            audit_record_id: None,
+            onchain_integrity_hash: None,
+            mismatched_fields: Vec::new(),
+        });
+
+        assert!(!verdict.is_valid());
+        assert_eq!(verdict.failures.len(), 2);
+        assert!(verdict.failures[1].contains("NoRecordFound"));
+
+        let value = serde_json::to_value(&verdict).unwrap();
+        assert_eq!(value["verdict"], "INVALID");
+        assert_eq!(value["chain"]["verdict"], "no_record_found");
+        assert!(verdict.to_string().starts_with("INVALID"));
+    }
+}
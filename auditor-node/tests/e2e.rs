@@ -0,0 +1,348 @@
+//! 端到端流水線測試
+//!
+//! 使用進程內假服務（`test_support`）串起 審計 → 簽名 → 加密 → 上傳 → 提交，
+//! 無需 Testnet 或 Docker。
+
+use auditor_node::audit_report::AuditReportGenerator;
+use auditor_node::blinding::BlindingSalt;
+use auditor_node::chunk_filter::ChunkFilterConfig;
+use auditor_node::dry_run::NoopRecorder;
+use auditor_node::integrity::{AuditData, IntegrityVerifier, VerificationStatus};
+use auditor_node::pipeline::{
+    blob_id_to_u256, AuditPipeline, PipelineConfig, PipelineOutcome, SuiRpcSubmitter,
+    WalrusPublisher,
+};
+use auditor_node::report::ReportManager;
+use auditor_node::seal_client::{SealApiConfig, SealClient};
+use auditor_node::test_support::{
+    content_hash, deterministic_blob, fake_seal_xor, AggregatorMode, FakeAggregator, FakePublisher,
+    FakeSealApi, FakeSuiRpc,
+};
+use auditor_node::types::BlobId;
+use pqc_signer::{Dilithium3Signer, Signer};
+use serde_json::Value;
+
+const BLOB_ID: &str = "eRrTusk8yshFQpkemgDnbg0f4-qDo623V2NpeVG1Zcg";
+const AUDITOR: &str = "0x1234567890abcdef1234567890abcdef1234567890abcdef1234567890abcdef";
+const PACKAGE: &str = "0x8afa5d31dbaa0a8fb07082692940ca3d56b5e856c5126cb5a3693f0a4de63b82";
+const AUDIT_CONFIG: &str = "0x00000000000000000000000000000000000000000000000000000000000000cf";
+
+/// 一組假服務與連接它們的流水線
+struct Harness {
+    aggregator: FakeAggregator,
+    seal: FakeSealApi,
+    publisher: FakePublisher,
+    sui: FakeSuiRpc,
+    pipeline: AuditPipeline,
+    signer: Dilithium3Signer,
+    public_key: Vec<u8>,
+}
+
+impl Harness {
+    async fn start(mode: AggregatorMode) -> Self {
+        let aggregator = FakeAggregator::start(deterministic_blob(3 * 4096 + 100), mode).await;
+        let seal = FakeSealApi::start().await;
+        let publisher = FakePublisher::start().await;
+        let sui = FakeSuiRpc::start().await;
+
+        let mut signer = Dilithium3Signer::new();
+        signer.generate_keypair().unwrap();
+        let public_key = signer.public_key().to_vec();
+
+        let pipeline = AuditPipeline::new(
+            IntegrityVerifier::new(aggregator.url().to_string()),
+            AuditReportGenerator::new(signer.clone(), Some(AUDITOR.to_string())),
+            WalrusPublisher::new(publisher.url()),
+            SuiRpcSubmitter::new(sui.url(), AUDITOR, PACKAGE, AUDIT_CONFIG),
+            pipeline_config(),
+        )
+        .with_seal(seal_client(&seal));
+
+        Self {
+            aggregator,
+            seal,
+            publisher,
+            sui,
+            pipeline,
+            signer,
+            public_key,
+        }
+    }
+
+    /// 以同一組假服務與簽名密鑰創建試運行流水線，上傳與提交記錄到 `recorder`
+    fn dry_run_pipeline(&self, recorder: NoopRecorder) -> AuditPipeline {
+        AuditPipeline::new(
+            IntegrityVerifier::new(self.aggregator.url().to_string()),
+            AuditReportGenerator::new(self.signer.clone(), Some(AUDITOR.to_string())),
+            recorder.clone(),
+            recorder,
+            pipeline_config(),
+        )
+        .with_seal(seal_client(&self.seal))
+    }
+
+    async fn run(&self) -> PipelineOutcome {
+        let expected = content_hash(self.aggregator.blob());
+        self.pipeline.run(BLOB_ID, Some(&expected)).await.unwrap()
+    }
+
+    /// 檢查上傳、加密綁定與鏈上提交，返回提交的 Move 調用參數
+    fn assert_delivered(&self, outcome: &PipelineOutcome) -> Vec<Value> {
+        assert!(ReportManager::verify_report(&outcome.report, &self.public_key).unwrap());
+
+        // 上傳的正是流水線產生的密文，且綁定到審計員 identity 與 package
+        let uploads = self.publisher.uploads();
+        assert_eq!(uploads, vec![outcome.uploaded.clone()]);
+        assert!(outcome.encryption.is_some());
+        assert_eq!(
+            outcome.report_blob_id,
+            Some(FakePublisher::blob_id_for(&uploads[0]))
+        );
+
+        let plaintext = fake_seal_xor(&uploads[0], AUDITOR, PACKAGE);
+        let decrypted: Value = serde_json::from_slice(&plaintext).unwrap();
+        let published = outcome.published.as_ref().unwrap_or(&outcome.report);
+        assert_eq!(decrypted, serde_json::to_value(published).unwrap());
+
+        let wrong_binding = fake_seal_xor(&uploads[0], AUDITOR, AUDIT_CONFIG);
+        assert!(serde_json::from_slice::<Value>(&wrong_binding).is_err());
+
+        // 提交 audit_core::submit_audit_record
+        let requests = self.sui.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0]["method"], "unsafe_moveCall");
+
+        let params = requests[0]["params"].as_array().unwrap();
+        assert_eq!(params[0], AUDITOR);
+        assert_eq!(params[1], PACKAGE);
+        assert_eq!(params[2], "audit_core");
+        assert_eq!(params[3], "submit_audit_record");
+
+        let args = params[5].as_array().unwrap().clone();
+        assert_eq!(args[0], AUDIT_CONFIG);
+        assert_eq!(args[1], blob_id_to_u256(BLOB_ID).unwrap());
+        assert_eq!(args[3], 7);
+        args
+    }
+}
+
+fn pipeline_config() -> PipelineConfig {
+    PipelineConfig {
+        auditor_address: AUDITOR.to_string(),
+        package_id: PACKAGE.to_string(),
+        seal_threshold: 2,
+        challenge_epoch: Some(7),
+        report_deleted_blobs: false,
+    }
+}
+
+fn seal_client(seal: &FakeSealApi) -> SealClient {
+    SealClient::new(SealApiConfig {
+        api_url: seal.url().to_string(),
+        timeout_secs: 5,
+    })
+    .unwrap()
+}
+
+fn bytes_arg(value: &Value) -> Vec<u8> {
+    serde_json::from_value(value.clone()).unwrap()
+}
+
+#[tokio::test]
+async fn test_pipeline_pass() {
+    let harness = Harness::start(AggregatorMode::Healthy).await;
+    let outcome = harness.run().await;
+
+    let audit_data = AuditData::try_from(&outcome.report).unwrap();
+    assert_eq!(
+        audit_data.verification_status,
+        VerificationStatus::Accessible
+    );
+    assert_eq!(audit_data.failed_verifications, 0);
+    assert_eq!(
+        audit_data.content_hash,
+        content_hash(harness.aggregator.blob())
+    );
+
+    let args = harness.assert_delivered(&outcome);
+    assert_eq!(
+        bytes_arg(&args[6]),
+        hex::decode(content_hash(harness.aggregator.blob())).unwrap()
+    );
+    assert_eq!(args[5], audit_data.successful_verifications);
+    assert_eq!(
+        bytes_arg(&args[7]),
+        outcome.submission.as_ref().unwrap().pqc_signature
+    );
+}
+
+#[tokio::test]
+async fn test_pipeline_corrupted_chunk() {
+    let harness = Harness::start(AggregatorMode::CorruptChunk(1)).await;
+    let outcome = harness.run().await;
+
+    let audit_data = AuditData::try_from(&outcome.report).unwrap();
+    assert_eq!(
+        audit_data.verification_status,
+        VerificationStatus::Corrupted
+    );
+    assert_ne!(
+        audit_data.content_hash,
+        content_hash(harness.aggregator.blob())
+    );
+
+    // 提交的完整性哈希是實際觀察到的（損壞）內容哈希
+    let args = harness.assert_delivered(&outcome);
+    assert_eq!(
+        bytes_arg(&args[6]),
+        hex::decode(&audit_data.content_hash).unwrap()
+    );
+}
+
+#[tokio::test]
+async fn test_pipeline_unreachable_aggregator() {
+    let harness = Harness::start(AggregatorMode::Unavailable).await;
+    let outcome = harness.run().await;
+
+    let audit_data = AuditData::try_from(&outcome.report).unwrap();
+    assert_eq!(
+        audit_data.verification_status,
+        VerificationStatus::Unreachable
+    );
+    assert_eq!(audit_data.total_challenges, 0);
+
+    let args = harness.assert_delivered(&outcome);
+    assert_eq!(args[5], 0);
+    assert!(bytes_arg(&args[6]).is_empty());
+}
+
+#[tokio::test]
+async fn test_pipeline_uploads_blinded_report() {
+    let harness = Harness::start(AggregatorMode::Healthy).await;
+    let salt = BlindingSalt::generate();
+    let harness = Harness {
+        pipeline: harness.pipeline.with_blinding(salt.clone()),
+        ..harness
+    };
+    let outcome = harness.run().await;
+
+    // 發布副本只含盲化 ID，並由審計員重新簽名
+    let published = outcome.published.as_ref().expect("blinded copy published");
+    assert_eq!(published.blob_id, salt.blind(BLOB_ID));
+    assert_eq!(published.blinding_key_id, Some(salt.key_id()));
+    assert!(ReportManager::verify_report(published, &harness.public_key).unwrap());
+    assert_eq!(outcome.report.blob_id, BLOB_ID);
+
+    let plaintext = fake_seal_xor(&outcome.uploaded, AUDITOR, PACKAGE);
+    assert!(!String::from_utf8_lossy(&plaintext).contains(BLOB_ID));
+
+    // 鏈上記錄仍使用真實 Blob ID
+    harness.assert_delivered(&outcome);
+}
+
+#[tokio::test]
+async fn test_dry_run_records_same_signed_report() {
+    let harness = Harness::start(AggregatorMode::Healthy).await;
+    let normal = harness.run().await;
+    harness.assert_delivered(&normal);
+
+    let dir = tempfile::tempdir().unwrap();
+    let recorder =
+        NoopRecorder::new(dir.path(), harness.publisher.url(), harness.sui.url()).unwrap();
+    let expected = content_hash(harness.aggregator.blob());
+    let dry_run = harness
+        .dry_run_pipeline(recorder)
+        .run(BLOB_ID, Some(&expected))
+        .await
+        .unwrap();
+
+    // 沒有再發往 Publisher 與 Sui RPC 的請求
+    assert_eq!(harness.publisher.uploads().len(), 1);
+    assert_eq!(harness.sui.requests().len(), 1);
+
+    // 記錄的負載即密文，解密後是同一密鑰簽名的報告
+    let recorded = std::fs::read(
+        dir.path()
+            .join(format!("upload-{}.bin", content_hash(&dry_run.uploaded))),
+    )
+    .unwrap();
+    assert_eq!(recorded, dry_run.uploaded);
+    let decrypted: Value =
+        serde_json::from_slice(&fake_seal_xor(&recorded, AUDITOR, PACKAGE)).unwrap();
+    assert_eq!(decrypted, serde_json::to_value(&dry_run.report).unwrap());
+    assert!(ReportManager::verify_report(&dry_run.report, &harness.public_key).unwrap());
+
+    // 結論與正常運行相同（audit_id、時間戳、挑戰集與簽名每次審計都不同）
+    let normal_data = AuditData::try_from(&normal.report).unwrap();
+    let dry_run_data = AuditData::try_from(&dry_run.report).unwrap();
+    assert_eq!(dry_run.report.is_valid, normal.report.is_valid);
+    assert_eq!(dry_run_data.blob_id, normal_data.blob_id);
+    assert_eq!(
+        dry_run_data.verification_status,
+        normal_data.verification_status
+    );
+    assert_eq!(dry_run_data.content_hash, normal_data.content_hash);
+    assert_eq!(dry_run_data.merkle_root, normal_data.merkle_root);
+    assert_eq!(dry_run_data.total_challenges, normal_data.total_challenges);
+    assert_eq!(
+        dry_run_data.successful_verifications,
+        normal_data.successful_verifications
+    );
+
+    // 記錄的提交即流水線生成的審計記錄參數
+    let submission = dry_run.submission.as_ref().unwrap();
+    let record: Value =
+        serde_json::from_slice(&std::fs::read(dry_run.tx_digest.as_ref().unwrap()).unwrap())
+            .unwrap();
+    assert_eq!(record["params"], serde_json::to_value(submission).unwrap());
+    assert_eq!(
+        record["report_blob_id"],
+        dry_run.report_blob_id.as_deref().unwrap()
+    );
+    let normal_submission = normal.submission.as_ref().unwrap();
+    assert_eq!(submission.blob_id, normal_submission.blob_id);
+    assert_eq!(submission.integrity_hash, normal_submission.integrity_hash);
+    assert_eq!(
+        submission.successful_verifications,
+        normal_submission.successful_verifications
+    );
+}
+
+#[tokio::test]
+async fn test_quick_compare_flags_modified_region() {
+    let config = ChunkFilterConfig {
+        false_positive_rate: 0.0001,
+        quick_compare_samples: 1024,
+        ..ChunkFilterConfig::default()
+    };
+
+    // 首次審計建樹時構建過濾器
+    let original = deterministic_blob(64 * 4096);
+    let healthy = FakeAggregator::start(original.clone(), AggregatorMode::Healthy).await;
+    let verifier =
+        IntegrityVerifier::new(healthy.url().to_string()).with_chunk_filter(config.clone());
+    let prior = verifier
+        .audit_blob(&BlobId::parse(BLOB_ID).unwrap())
+        .await
+        .unwrap()
+        .chunk_filter
+        .expect("chunk filter built during audit");
+    assert_eq!(prior.leaf_count(), 64);
+
+    let unchanged = verifier.quick_compare(BLOB_ID, &prior).await.unwrap();
+    assert_eq!(unchanged.sampled, 64);
+    assert!(!unchanged.is_suspect());
+
+    // 第 20..28 個 chunk 被改寫
+    let mut modified = original;
+    for byte in &mut modified[20 * 4096..28 * 4096] {
+        *byte ^= 0x5a;
+    }
+    let tampered = FakeAggregator::start(modified, AggregatorMode::Healthy).await;
+    let verifier = IntegrityVerifier::new(tampered.url().to_string()).with_chunk_filter(config);
+
+    let result = verifier.quick_compare(BLOB_ID, &prior).await.unwrap();
+    assert!(result.is_suspect());
+    assert_eq!(result.outside_filter, (20..28).collect::<Vec<u64>>());
+    assert_eq!(result.leaf_count, result.prior_leaf_count);
+}
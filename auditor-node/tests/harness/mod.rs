@@ -0,0 +1,319 @@
+//! 集成測試用的假 Walrus 存儲節點
+//!
+//! 進程內的 axum 服務，對同一份合成 Blob 同時提供：
+//!
+//! - `GET /v1/blobs/{id}`：Blob 原始內容（Aggregator 端點，供 `IntegrityVerifier` 下載）
+//! - `POST /v1/challenge`：sliver 與真實的默克爾證明（以 `crypto::merkle` 對同一數據構建）
+//! - `GET /health`：節點健康狀態
+//!
+//! Blob 按 sliver 數均分，第 `i` 個 sliver 的葉子哈希為 `hash_leaf(sliver_i)`，
+//! 根哈希由 [`MockStorageNode::metadata`] 給出。通過 [`MockStorageNodeBuilder`]
+//! 可注入損壞（sliver 或下載內容）與延遲，供後續測試復用：
+//!
+//! ```ignore
+//! mod harness;
+//!
+//! let node = harness::MockStorageNode::builder()
+//!     .corrupt_sliver(3)
+//!     .stall_first(1, Duration::from_secs(3))
+//!     .start()
+//!     .await;
+//! ```
+
+#![allow(dead_code)]
+
+use auditor_node::blob_id::BlobIdEncoder;
+use auditor_node::crypto::merkle::{hash_leaf, MerkleTree, MerkleTreeVersion};
+use auditor_node::error::Result;
+use auditor_node::test_support::deterministic_blob;
+use auditor_node::types::BlobMetadata;
+use axum::extract::{Path, State};
+use axum::http::{header, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use base64::{engine::general_purpose, Engine as _};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// 默認的 Blob 大小（bytes）
+pub const DEFAULT_BLOB_SIZE: usize = 3 * 4096 + 100;
+
+/// 默認的 sliver 數（`n`）
+pub const DEFAULT_SLIVERS: u64 = 10;
+
+/// 以 SHA-256 代替 RS2 編碼推導 `blob_id`（與 [`MockStorageNode::blob_id`] 一致）
+pub struct Sha256BlobIdEncoder;
+
+impl BlobIdEncoder for Sha256BlobIdEncoder {
+    fn blob_id(&self, data: &[u8]) -> Result<String> {
+        Ok(general_purpose::URL_SAFE_NO_PAD.encode(Sha256::digest(data)))
+    }
+}
+
+/// [`MockStorageNode`] 的構建器
+pub struct MockStorageNodeBuilder {
+    blob: Vec<u8>,
+    slivers: u64,
+    corrupt_slivers: HashSet<u64>,
+    corrupt_offsets: Vec<usize>,
+    latency: Duration,
+    stalled: usize,
+    stall: Duration,
+}
+
+impl Default for MockStorageNodeBuilder {
+    fn default() -> Self {
+        Self {
+            blob: deterministic_blob(DEFAULT_BLOB_SIZE),
+            slivers: DEFAULT_SLIVERS,
+            corrupt_slivers: HashSet::new(),
+            corrupt_offsets: Vec::new(),
+            latency: Duration::ZERO,
+            stalled: 0,
+            stall: Duration::ZERO,
+        }
+    }
+}
+
+impl MockStorageNodeBuilder {
+    /// 使用指定的 Blob 內容
+    pub fn with_blob(mut self, blob: Vec<u8>) -> Self {
+        self.blob = blob;
+        self
+    }
+
+    /// Blob 切分成 `slivers` 個 sliver（至少 2 個，且不超過 Blob 字節數）
+    pub fn with_slivers(mut self, slivers: u64) -> Self {
+        self.slivers = slivers;
+        self
+    }
+
+    /// 挑戰第 `index` 個 sliver 時返回首字節被翻轉的數據（證明不變，驗證應失敗）
+    pub fn corrupt_sliver(mut self, index: u64) -> Self {
+        self.corrupt_slivers.insert(index);
+        self
+    }
+
+    /// `GET /v1/blobs/{id}` 返回的內容在 `offset` 處翻轉一個字節（挑戰數據不受影響）
+    pub fn corrupt_blob_at(mut self, offset: usize) -> Self {
+        self.corrupt_offsets.push(offset);
+        self
+    }
+
+    /// 每個挑戰響應延遲 `latency`
+    pub fn with_latency(mut self, latency: Duration) -> Self {
+        self.latency = latency;
+        self
+    }
+
+    /// 前 `count` 個挑戰請求延遲 `stall` 後才響應（用於觸發客戶端超時）
+    pub fn stall_first(mut self, count: usize, stall: Duration) -> Self {
+        self.stalled = count;
+        self.stall = stall;
+        self
+    }
+
+    /// 啟動假存儲節點
+    pub async fn start(self) -> MockStorageNode {
+        assert!(self.slivers >= 2, "a blob needs at least two slivers");
+        assert!(
+            self.blob.len() as u64 >= self.slivers,
+            "every sliver needs at least one byte"
+        );
+
+        let sliver_len = self.blob.len().div_ceil(self.slivers as usize);
+        let slivers: Vec<Vec<u8>> = self.blob.chunks(sliver_len).map(<[u8]>::to_vec).collect();
+        assert_eq!(
+            slivers.len() as u64,
+            self.slivers,
+            "blob does not split evenly"
+        );
+        let tree = MerkleTree::from_leaf_hashes(
+            slivers.iter().map(|sliver| hash_leaf(sliver)).collect(),
+            MerkleTreeVersion::V2,
+        )
+        .expect("Failed to build sliver tree");
+
+        let mut served = self.blob.clone();
+        for &offset in &self.corrupt_offsets {
+            served[offset] ^= 0xff;
+        }
+
+        let state = Arc::new(NodeState {
+            served,
+            slivers,
+            tree,
+            corrupt_slivers: self.corrupt_slivers,
+            latency: self.latency,
+            stalled: self.stalled,
+            stall: self.stall,
+            challenges: AtomicUsize::new(0),
+            downloads: AtomicUsize::new(0),
+            challenged: Mutex::new(Vec::new()),
+            healthy: Mutex::new(true),
+        });
+        let router = Router::new()
+            .route("/v1/blobs/:id", get(serve_blob))
+            .route("/v1/challenge", post(challenge))
+            .route("/health", get(health))
+            .with_state(Arc::clone(&state));
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("Failed to bind mock storage node");
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            let _ = axum::serve(listener, router).await;
+        });
+
+        MockStorageNode {
+            url,
+            blob: self.blob,
+            state,
+        }
+    }
+}
+
+struct NodeState {
+    /// `GET /v1/blobs/{id}` 返回的內容（可能已損壞）
+    served: Vec<u8>,
+    slivers: Vec<Vec<u8>>,
+    tree: MerkleTree,
+    corrupt_slivers: HashSet<u64>,
+    latency: Duration,
+    stalled: usize,
+    stall: Duration,
+    /// 收到的挑戰請求數
+    challenges: AtomicUsize,
+    /// 收到的下載請求數
+    downloads: AtomicUsize,
+    /// 被挑戰的 sliver 索引（按到達順序）
+    challenged: Mutex<Vec<u64>>,
+    healthy: Mutex<bool>,
+}
+
+/// 假 Walrus 存儲節點（同時充當 Aggregator）
+pub struct MockStorageNode {
+    url: String,
+    blob: Vec<u8>,
+    state: Arc<NodeState>,
+}
+
+impl MockStorageNode {
+    /// 構建器（默認：確定性 Blob、[`DEFAULT_SLIVERS`] 個 sliver、無損壞、無延遲）
+    pub fn builder() -> MockStorageNodeBuilder {
+        MockStorageNodeBuilder::default()
+    }
+
+    /// 以默認配置啟動
+    pub async fn start() -> Self {
+        Self::builder().start().await
+    }
+
+    /// 基礎 URL
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// 原始（未損壞的）Blob 內容
+    pub fn blob(&self) -> &[u8] {
+        &self.blob
+    }
+
+    /// 由原始內容推導的 `blob_id`（見 [`Sha256BlobIdEncoder`]）
+    pub fn blob_id(&self) -> String {
+        Sha256BlobIdEncoder.blob_id(&self.blob).unwrap()
+    }
+
+    /// 與節點數據一致的 Blob 元數據（默克爾根為 sliver 樹的根）
+    pub fn metadata(&self) -> BlobMetadata {
+        let n = self.state.slivers.len() as u64;
+        let fault_tolerance = (n - 1) / 3;
+        BlobMetadata {
+            blob_object_id: format!("0x{:064x}", 1),
+            blob_id: self.blob_id(),
+            merkle_root: self.state.tree.root().to_vec(),
+            blob_size: self.blob.len() as u64,
+            encoding_type: 1,
+            encoding_k: n - 2 * fault_tolerance,
+            encoding_n: n,
+            registered_epoch: 1,
+            certified_epoch: Some(1),
+            start_epoch: 1,
+            end_epoch: 100,
+            owner: format!("0x{:064x}", 2),
+        }
+    }
+
+    /// `/health` 之後報告 `healthy`
+    pub fn set_healthy(&self, healthy: bool) {
+        *self.state.healthy.lock().unwrap() = healthy;
+    }
+
+    /// 收到的挑戰請求數（包括被延遲或超時的請求）
+    pub fn challenge_requests(&self) -> usize {
+        self.state.challenges.load(Ordering::SeqCst)
+    }
+
+    /// 收到的 Blob 下載請求數
+    pub fn downloads(&self) -> usize {
+        self.state.downloads.load(Ordering::SeqCst)
+    }
+
+    /// 被挑戰的 sliver 索引（按到達順序，重試會重複出現）
+    pub fn challenged(&self) -> Vec<u64> {
+        self.state.challenged.lock().unwrap().clone()
+    }
+}
+
+async fn serve_blob(Path(_id): Path<String>, State(state): State<Arc<NodeState>>) -> Response {
+    state.downloads.fetch_add(1, Ordering::SeqCst);
+    (
+        [(header::CONTENT_LENGTH, state.served.len().to_string())],
+        state.served.clone(),
+    )
+        .into_response()
+}
+
+async fn challenge(State(state): State<Arc<NodeState>>, Json(request): Json<Value>) -> Response {
+    let arrival = state.challenges.fetch_add(1, Ordering::SeqCst);
+    if arrival < state.stalled {
+        tokio::time::sleep(state.stall).await;
+    }
+    tokio::time::sleep(state.latency).await;
+
+    let Some(index) = request["sliver_index"].as_u64() else {
+        return (StatusCode::BAD_REQUEST, "missing sliver_index").into_response();
+    };
+    state.challenged.lock().unwrap().push(index);
+    let Some(sliver) = state.slivers.get(index as usize) else {
+        return (StatusCode::NOT_FOUND, "sliver not found").into_response();
+    };
+
+    let mut sliver_data = sliver.clone();
+    if state.corrupt_slivers.contains(&index) {
+        sliver_data[0] ^= 0xff;
+    }
+    let proof = state
+        .tree
+        .generate_proof(index as usize)
+        .expect("Sliver index within tree");
+    Json(json!({
+        "sliver_data": sliver_data,
+        "merkle_proof": proof.to_bytes(),
+    }))
+    .into_response()
+}
+
+async fn health(State(state): State<Arc<NodeState>>) -> Response {
+    if *state.healthy.lock().unwrap() {
+        Json(json!({ "status": "healthy" })).into_response()
+    } else {
+        (StatusCode::SERVICE_UNAVAILABLE, "unhealthy").into_response()
+    }
+}
@@ -0,0 +1,257 @@
+//! 挑戰 → 驗證 → 報告 的集成測試
+//!
+//! 兩條審計路徑（`Auditor` 的 sliver 挑戰與 `IntegrityVerifier` 的下載驗證）都對
+//! `harness` 中的假存儲節點執行，無需 Testnet。
+
+mod harness;
+
+use auditor_node::auditor::Auditor;
+use auditor_node::integrity::{IntegrityVerifier, VerificationStatus, DEFAULT_CHUNK_SIZE};
+use auditor_node::progress::{AuditProgress, FinishedStatus};
+use auditor_node::storage_node_client::StorageNodeClient;
+use auditor_node::test_support::content_hash;
+use auditor_node::types::{AuditReport, AuditorConfig, BlobId};
+use harness::{MockStorageNode, Sha256BlobIdEncoder, DEFAULT_SLIVERS};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+const AUDITOR: &str = "0x1234567890abcdef1234567890abcdef1234567890abcdef1234567890abcdef";
+
+/// 只連接 `node` 的審計員；默認配置的挑戰數覆蓋全部 sliver
+fn auditor(node: &MockStorageNode, config: AuditorConfig) -> Auditor {
+    Auditor::new(config, AUDITOR.to_string(), vec![node.url().to_string()]).unwrap()
+}
+
+async fn audit(node: &MockStorageNode, config: AuditorConfig) -> AuditReport {
+    auditor(node, config)
+        .audit_blob_with_metadata(&node.metadata())
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_auditor_happy_path() {
+    let node = MockStorageNode::start().await;
+    let report = audit(&node, AuditorConfig::default()).await;
+
+    assert!(report.is_valid, "{:?}", report.failure_reason);
+    assert_eq!(report.blob_id, node.blob_id());
+    assert_eq!(u64::from(report.total_challenges), DEFAULT_SLIVERS);
+    assert_eq!(report.successful_verifications, report.total_challenges);
+    assert!(report
+        .challenge_results
+        .iter()
+        .all(|result| result.verified && result.merkle_proof_valid && result.attempts == 1));
+
+    let mut challenged = node.challenged();
+    challenged.sort_unstable();
+    assert_eq!(challenged, (0..DEFAULT_SLIVERS).collect::<Vec<_>>());
+}
+
+#[tokio::test]
+async fn test_auditor_detects_corrupted_sliver() {
+    let node = MockStorageNode::builder().corrupt_sliver(3).start().await;
+    let report = audit(&node, AuditorConfig::default()).await;
+
+    assert!(!report.is_valid);
+    assert_eq!(report.failed_verifications, 1);
+    for result in &report.challenge_results {
+        if result.challenge.sliver_index == 3 {
+            assert!(!result.verified && !result.merkle_proof_valid);
+            assert_eq!(
+                result.failure_reason.as_deref(),
+                Some("Merkle proof verification failed")
+            );
+        } else {
+            assert!(result.verified, "{:?}", result);
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_auditor_retries_after_timeout() {
+    let node = MockStorageNode::builder()
+        .stall_first(1, Duration::from_secs(3))
+        .start()
+        .await;
+    let config = AuditorConfig {
+        http_timeout_secs: 1,
+        min_challenges: 1,
+        max_challenges: 1,
+        ..Default::default()
+    };
+    let report = audit(&node, config).await;
+
+    assert!(report.is_valid, "{:?}", report.failure_reason);
+    assert_eq!(report.challenge_results.len(), 1);
+    assert_eq!(report.challenge_results[0].attempts, 2);
+    assert_eq!(node.challenge_requests(), 2);
+}
+
+#[tokio::test]
+async fn test_health_endpoint() {
+    let node = MockStorageNode::start().await;
+    let client = StorageNodeClient::new(node.url().to_string());
+    assert!(client.health_check().await.unwrap());
+
+    node.set_healthy(false);
+    assert!(!client.health_check().await.unwrap_or(false));
+}
+
+fn verifier(node: &MockStorageNode) -> IntegrityVerifier {
+    IntegrityVerifier::new(node.url().to_string())
+        .with_blob_id_verification(Arc::new(Sha256BlobIdEncoder), node.blob().len() as u64)
+}
+
+#[tokio::test]
+async fn test_integrity_happy_path() {
+    let node = MockStorageNode::start().await;
+    let blob_id = BlobId::parse(&node.blob_id()).unwrap();
+    let audit_data = verifier(&node).audit_blob(&blob_id).await.unwrap();
+
+    assert_eq!(
+        audit_data.verification_status,
+        VerificationStatus::Accessible
+    );
+    assert_eq!(audit_data.content_hash, content_hash(node.blob()));
+    assert_eq!(audit_data.blob_id_verified, Some(true));
+    assert_eq!(audit_data.failed_verifications, 0);
+    assert_eq!(node.downloads(), 1);
+}
+
+#[tokio::test]
+async fn test_integrity_detects_corrupted_content() {
+    let node = MockStorageNode::builder()
+        .corrupt_blob_at(5000)
+        .start()
+        .await;
+    let blob_id = BlobId::parse(&node.blob_id()).unwrap();
+    let audit_data = verifier(&node).audit_blob(&blob_id).await.unwrap();
+
+    assert_eq!(
+        audit_data.verification_status,
+        VerificationStatus::Corrupted
+    );
+    assert_ne!(audit_data.content_hash, content_hash(node.blob()));
+    assert_eq!(audit_data.blob_id_verified, Some(false));
+}
+
+/// 審計結束後取出通道中的全部事件
+fn drain(mut receiver: mpsc::Receiver<AuditProgress>) -> Vec<AuditProgress> {
+    let mut events = Vec::new();
+    while let Ok(event) = receiver.try_recv() {
+        events.push(event);
+    }
+    events
+}
+
+#[tokio::test]
+async fn test_auditor_progress_events() {
+    let node = MockStorageNode::builder().corrupt_sliver(3).start().await;
+    let (sender, receiver) = mpsc::channel(1024);
+    let report = auditor(&node, AuditorConfig::default())
+        .audit_blob_with_metadata_and_progress(&node.metadata(), sender)
+        .await
+        .unwrap();
+
+    let events = drain(receiver);
+    let (last, challenges) = events.split_last().unwrap();
+    assert_eq!(
+        *last,
+        AuditProgress::Finished {
+            status: FinishedStatus::Report { is_valid: false }
+        }
+    );
+    assert!(!report.is_valid);
+
+    let mut completed: Vec<(u64, bool)> = challenges
+        .iter()
+        .map(|event| match event {
+            AuditProgress::ChallengeCompleted { index, verified } => (*index, *verified),
+            other => panic!("unexpected event {:?}", other),
+        })
+        .collect();
+    completed.sort_unstable();
+    let expected: Vec<(u64, bool)> = (0..DEFAULT_SLIVERS).map(|i| (i, i != 3)).collect();
+    assert_eq!(completed, expected);
+}
+
+#[tokio::test]
+async fn test_integrity_progress_events_in_order() {
+    let node = MockStorageNode::start().await;
+    let blob_id = BlobId::parse(&node.blob_id()).unwrap();
+    let size = node.blob().len() as u64;
+    let (sender, receiver) = mpsc::channel(1024);
+    let audit_data = verifier(&node)
+        .audit_blob_with_progress(&blob_id, sender)
+        .await
+        .unwrap();
+
+    let events = drain(receiver);
+    assert_eq!(
+        events[0],
+        AuditProgress::DownloadStarted {
+            size_hint: Some(size)
+        }
+    );
+
+    let mut done = 0;
+    let mut rest = events[1..].iter().peekable();
+    while let Some(AuditProgress::DownloadedBytes { done: now, total }) = rest.peek() {
+        assert!(*now > done, "download progress must increase");
+        assert_eq!(*total, Some(size));
+        done = *now;
+        rest.next();
+    }
+    assert_eq!(done, size);
+
+    assert_eq!(
+        rest.next(),
+        Some(&AuditProgress::MerkleBuildStarted {
+            leaf_count: node.blob().len().div_ceil(DEFAULT_CHUNK_SIZE)
+        })
+    );
+    let rest: Vec<_> = rest.collect();
+    let (last, challenges) = rest.split_last().unwrap();
+    assert_eq!(challenges.len(), usize::from(audit_data.total_challenges));
+    assert!(challenges.iter().all(|event| matches!(
+        event,
+        AuditProgress::ChallengeCompleted { verified: true, .. }
+    )));
+    assert_eq!(
+        **last,
+        AuditProgress::Finished {
+            status: FinishedStatus::Verification(VerificationStatus::Accessible)
+        }
+    );
+}
+
+#[tokio::test]
+async fn test_slow_or_dropped_progress_receiver_does_not_block_audit() {
+    let node = MockStorageNode::start().await;
+    let blob_id = BlobId::parse(&node.blob_id()).unwrap();
+
+    // 容量為 1 且從不讀取：除第一個事件外全部丟棄
+    let (sender, receiver) = mpsc::channel(1);
+    let audit_data = tokio::time::timeout(
+        Duration::from_secs(10),
+        verifier(&node).audit_blob_with_progress(&blob_id, sender),
+    )
+    .await
+    .unwrap()
+    .unwrap();
+    assert_eq!(
+        audit_data.verification_status,
+        VerificationStatus::Accessible
+    );
+    assert_eq!(drain(receiver).len(), 1);
+
+    let (sender, receiver) = mpsc::channel(16);
+    drop(receiver);
+    let report = auditor(&node, AuditorConfig::default())
+        .audit_blob_with_metadata_and_progress(&node.metadata(), sender)
+        .await
+        .unwrap();
+    assert!(report.is_valid, "{:?}", report.failure_reason);
+}
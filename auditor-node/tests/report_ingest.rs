@@ -0,0 +1,273 @@
+//! 報告 JSON 解析入口的屬性測試與回歸語料重放
+//!
+//! 生成結構合法但取值極端的報告（超長數組、任意 Unicode、邊界整數），
+//! 以及在此基礎上變異出的非法文檔，確認兩個入口只返回錯誤、從不 panic。
+//! `fuzz/corpus/` 下的語料同時作為回歸用例在常規測試中重放。
+
+#![allow(deprecated)]
+
+use auditor_node::audit_report::SignedAuditReport;
+use auditor_node::report::ReportManager;
+use proptest::prelude::*;
+use serde_json::{json, Value};
+use std::fs;
+use std::path::Path;
+
+/// 兩個入口都不得 panic
+fn ingest_both(json: &str) {
+    let _ = ReportManager::from_json(json);
+    let _ = SignedAuditReport::from_json(json);
+}
+
+fn weird_string() -> impl Strategy<Value = String> {
+    prop_oneof![
+        any::<String>(),
+        Just(String::new()),
+        Just("\u{FEFF}\u{202E}\u{0}".to_string()),
+        "[\\[\\]{}\"\\\\]{0,64}",
+        "\\PC{1000,4000}",
+    ]
+}
+
+fn extreme_u64() -> impl Strategy<Value = u64> {
+    prop_oneof![Just(0), Just(u64::MAX), any::<u64>()]
+}
+
+fn extreme_u16() -> impl Strategy<Value = u16> {
+    prop_oneof![Just(0), Just(u16::MAX), any::<u16>()]
+}
+
+fn bytes(max: usize) -> impl Strategy<Value = Vec<u8>> {
+    prop::collection::vec(any::<u8>(), 0..max)
+}
+
+fn challenge_result() -> impl Strategy<Value = Value> {
+    (
+        extreme_u64(),
+        extreme_u16(),
+        any::<u8>(),
+        extreme_u64(),
+        any::<bool>(),
+        bytes(64),
+        prop::option::of(weird_string()),
+    )
+        .prop_map(
+            |(sliver_index, shard_id, challenge_type, timestamp, verified, hash, reason)| {
+                json!({
+                    "challenge": {
+                        "sliver_index": sliver_index,
+                        "shard_id": shard_id,
+                        "challenge_type": challenge_type,
+                        "timestamp": timestamp,
+                    },
+                    "verified": verified,
+                    "merkle_proof_valid": verified,
+                    "response_hash": hash,
+                    "failure_reason": reason,
+                })
+            },
+        )
+}
+
+/// 結構合法但取值極端的規範報告
+fn weird_report() -> impl Strategy<Value = Value> {
+    (
+        (weird_string(), weird_string(), weird_string()),
+        (extreme_u64(), any::<u32>()),
+        prop::collection::vec(challenge_result(), 0..64),
+        (extreme_u16(), extreme_u16(), extreme_u16()),
+        (bytes(50_000), bytes(4_000), any::<u8>()),
+        (
+            any::<bool>(),
+            prop::option::of(weird_string()),
+            any::<bool>(),
+        ),
+    )
+        .prop_map(
+            |(
+                (blob_id, blob_object_id, auditor),
+                (timestamp, challenge_epoch),
+                challenge_results,
+                (total, successful, failed),
+                (integrity_hash, pqc_signature, pqc_algorithm),
+                (is_valid, failure_reason, with_encoding_n),
+            )| {
+                let mut report = json!({
+                    "blob_id": blob_id,
+                    "blob_object_id": blob_object_id,
+                    "auditor": auditor,
+                    "timestamp": timestamp,
+                    "challenge_epoch": challenge_epoch,
+                    "total_challenges": total,
+                    "successful_verifications": successful,
+                    "failed_verifications": failed,
+                    "integrity_hash": integrity_hash,
+                    "pqc_signature": pqc_signature,
+                    "pqc_algorithm": pqc_algorithm,
+                    "is_valid": is_valid,
+                    "failure_reason": failure_reason,
+                });
+
+                // 只在所有索引都能落在範圍內時記錄 encoding_n
+                let max_index = challenge_results
+                    .iter()
+                    .filter_map(|r| r["challenge"]["sliver_index"].as_u64())
+                    .max();
+                if with_encoding_n && max_index.is_none_or(|i| i < u64::MAX) {
+                    report["encoding_n"] = json!(max_index.map_or(1, |i| i + 1));
+                }
+                report["challenge_results"] = Value::Array(challenge_results);
+                report
+            },
+        )
+}
+
+/// 替換字段時使用的非法或極端取值
+fn hostile_value() -> impl Strategy<Value = Value> {
+    prop_oneof![
+        Just(Value::Null),
+        Just(json!(-1)),
+        Just(json!(f64::MAX)),
+        Just(json!(0.5)),
+        Just(json!(u64::MAX)),
+        Just(json!("")),
+        Just(json!([])),
+        Just(json!({})),
+        Just(json!([256, -1, 1.5])),
+        weird_string().prop_map(Value::String),
+    ]
+}
+
+/// 對 JSON 文本的變異
+#[derive(Debug, Clone)]
+enum TextMutation {
+    Truncate(usize),
+    LoneSurrogate(usize),
+    HugeNumber(usize),
+    DeepNesting(usize),
+}
+
+fn text_mutation() -> impl Strategy<Value = TextMutation> {
+    prop_oneof![
+        any::<usize>().prop_map(TextMutation::Truncate),
+        any::<usize>().prop_map(TextMutation::LoneSurrogate),
+        any::<usize>().prop_map(TextMutation::HugeNumber),
+        (1usize..20_000).prop_map(TextMutation::DeepNesting),
+    ]
+}
+
+fn char_boundary(text: &str, seed: usize) -> usize {
+    let mut index = seed % (text.len() + 1);
+    while !text.is_char_boundary(index) {
+        index -= 1;
+    }
+    index
+}
+
+fn mutate(text: &str, mutation: &TextMutation) -> String {
+    match *mutation {
+        TextMutation::Truncate(seed) => text[..char_boundary(text, seed)].to_string(),
+        TextMutation::LoneSurrogate(seed) => {
+            // 替換某個字符串值為未配對的代理項轉義
+            let quotes: Vec<_> = text.match_indices("\":\"").collect();
+            match quotes.get(seed % quotes.len().max(1)) {
+                Some((at, _)) => format!("{}\":\"\\udc00{}", &text[..*at], &text[at + 3..]),
+                None => format!("\"\\ud800\"{}", text),
+            }
+        }
+        TextMutation::HugeNumber(seed) => {
+            let at = char_boundary(text, seed);
+            format!("{}1e99999{}", &text[..at], &text[at..])
+        }
+        TextMutation::DeepNesting(depth) => {
+            format!(
+                "{},\"x\":{}{}}}",
+                &text[..text.len() - 1],
+                "[".repeat(depth),
+                "]".repeat(depth)
+            )
+        }
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn prop_weird_but_valid_reports_roundtrip(report in weird_report()) {
+        let json = serde_json::to_string_pretty(&report).unwrap();
+        let parsed = ReportManager::from_json(&json).unwrap();
+        prop_assert_eq!(&parsed.blob_id, report["blob_id"].as_str().unwrap());
+        prop_assert_eq!(parsed.challenge_results.len(), report["challenge_results"].as_array().unwrap().len());
+
+        let reparsed = ReportManager::from_json(&serde_json::to_string(&parsed).unwrap()).unwrap();
+        prop_assert_eq!(serde_json::to_value(&parsed).unwrap(), serde_json::to_value(&reparsed).unwrap());
+    }
+
+    #[test]
+    fn prop_hostile_field_values_never_panic(
+        report in weird_report(),
+        field in 0usize..16,
+        value in hostile_value(),
+    ) {
+        let mut report = report;
+        let object = report.as_object_mut().unwrap();
+        let key = object.keys().nth(field % object.len()).unwrap().clone();
+        object.insert(key, value);
+        ingest_both(&report.to_string());
+
+        // 同樣的取值放進舊版信封
+        let legacy = json!({
+            "audit_data": report,
+            "signature": "AAAA",
+            "algorithm": "Dilithium3",
+            "auditor_public_key": "AAAA",
+            "report_timestamp": 0,
+        });
+        ingest_both(&legacy.to_string());
+    }
+
+    #[test]
+    fn prop_mutated_text_never_panics(report in weird_report(), mutation in text_mutation()) {
+        let json = serde_json::to_string(&report).unwrap();
+        ingest_both(&mutate(&json, &mutation));
+    }
+
+    #[test]
+    fn prop_arbitrary_text_never_panics(text in any::<String>()) {
+        ingest_both(&text);
+    }
+}
+
+/// 重放 `fuzz/corpus/`：`valid_` 開頭的語料必須被對應入口接受，其餘只要求不 panic
+#[test]
+fn test_regression_corpus() {
+    let corpus = Path::new(env!("CARGO_MANIFEST_DIR")).join("fuzz/corpus");
+    let mut replayed = 0;
+
+    for (target, accepts) in [
+        (
+            "report_json",
+            (|json: &str| ReportManager::from_json(json).is_ok()) as fn(&str) -> bool,
+        ),
+        ("signed_report_json", |json: &str| {
+            SignedAuditReport::from_json(json).is_ok()
+        }),
+    ] {
+        for entry in fs::read_dir(corpus.join(target)).unwrap() {
+            let path = entry.unwrap().path();
+            let json = String::from_utf8_lossy(&fs::read(&path).unwrap()).into_owned();
+            let name = path.file_name().unwrap().to_string_lossy();
+
+            ingest_both(&json);
+            assert_eq!(
+                accepts(&json),
+                name.starts_with("valid_"),
+                "unexpected result for {}",
+                path.display()
+            );
+            replayed += 1;
+        }
+    }
+    assert!(replayed > 0);
+}
@@ -0,0 +1,257 @@
+//! Seal 加密 → 解密往返測試
+//!
+//! 對假 Seal API（`test_support::FakeSealApi`）調用 `SealClient`，核對明文逐字節還原，
+//! 以及策略拒絕、密鑰服務器不足、請求被拒與臨時故障的錯誤分類和重試行為；
+//! Seal 宕機時本地 AES-256-GCM 後備加密的密文可還原為原始報告。
+
+use auditor_node::error::AuditorError;
+use auditor_node::local_encryption::{self, LocalAesEncryptor};
+use auditor_node::pipeline::{FallbackEncryptor, ReportEncryptor, SealReportEncryptor};
+use auditor_node::retry::RetryConfig;
+use auditor_node::seal_client::{EncryptionScheme, SealApiConfig, SealClient};
+use auditor_node::test_support::FakeSealApi;
+use axum::http::StatusCode;
+use std::time::{Duration, Instant};
+
+const AUDITOR: &str = "0x1234567890abcdef1234567890abcdef1234567890abcdef1234567890abcdef";
+const PACKAGE: &str = "0x8afa5d31dbaa0a8fb07082692940ca3d56b5e856c5126cb5a3693f0a4de63b82";
+
+/// 鍵序、空白與轉義都不是 serde_json 的規範輸出，重新序列化會改變字節
+const REPORT: &str = "{\"version\":\"1.0\",  \"blob_id\":\"eRrTusk8\",\n\
+                      \"note\":\"審計 \\u00e9\",\"timestamp\":1234567890}";
+
+fn client(seal: &FakeSealApi) -> SealClient {
+    SealClient::new(SealApiConfig {
+        api_url: seal.url().to_string(),
+        timeout_secs: 5,
+    })
+    .unwrap()
+    .with_retry(RetryConfig {
+        max_retries: 2,
+        initial_delay_ms: 1,
+        multiplier: 1.0,
+        max_delay_ms: 1,
+    })
+}
+
+#[tokio::test]
+async fn test_encrypt_decrypt_round_trips_exact_bytes() {
+    let seal = FakeSealApi::start().await;
+    let client = client(&seal);
+
+    let (ciphertext, _, _) = client
+        .encrypt_report(REPORT, AUDITOR, PACKAGE, 2)
+        .await
+        .unwrap();
+    let plaintext = client
+        .decrypt_report(&ciphertext, AUDITOR, PACKAGE)
+        .await
+        .unwrap();
+
+    assert_eq!(plaintext.as_bytes(), REPORT.as_bytes());
+}
+
+#[tokio::test]
+async fn test_policy_denied_is_not_retried() {
+    let seal = FakeSealApi::start().await;
+    let client = client(&seal);
+    let (ciphertext, _, _) = client
+        .encrypt_report(REPORT, AUDITOR, PACKAGE, 2)
+        .await
+        .unwrap();
+
+    seal.deny(AUDITOR);
+    let err = client
+        .decrypt_report(&ciphertext, AUDITOR, PACKAGE)
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, AuditorError::SealAccessDenied(_)), "{}", err);
+    assert_eq!(seal.decrypt_requests(), 1);
+}
+
+#[tokio::test]
+async fn test_not_enough_key_servers() {
+    let seal = FakeSealApi::start().await;
+    let client = client(&seal);
+    let (ciphertext, _, _) = client
+        .encrypt_report(REPORT, AUDITOR, PACKAGE, 2)
+        .await
+        .unwrap();
+
+    seal.set_key_servers_down(true);
+    let err = client
+        .decrypt_report(&ciphertext, AUDITOR, PACKAGE)
+        .await
+        .unwrap_err();
+
+    match err {
+        AuditorError::SealKeyServers(message) => assert!(message.contains("threshold")),
+        other => panic!("expected SealKeyServers, got {}", other),
+    }
+    assert_eq!(seal.decrypt_requests(), 1);
+}
+
+#[tokio::test]
+async fn test_transient_failures_are_retried() {
+    let seal = FakeSealApi::start().await;
+    let client = client(&seal);
+    let (ciphertext, _, _) = client
+        .encrypt_report(REPORT, AUDITOR, PACKAGE, 2)
+        .await
+        .unwrap();
+
+    seal.fail_next(2, StatusCode::BAD_GATEWAY);
+    let plaintext = client
+        .decrypt_report(&ciphertext, AUDITOR, PACKAGE)
+        .await
+        .unwrap();
+    assert_eq!(plaintext, REPORT);
+    assert_eq!(seal.decrypt_requests(), 3);
+
+    seal.fail_next(3, StatusCode::SERVICE_UNAVAILABLE);
+    let err = client
+        .decrypt_report(&ciphertext, AUDITOR, PACKAGE)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, AuditorError::SealUnavailable(_)), "{}", err);
+    assert_eq!(seal.decrypt_requests(), 6);
+}
+
+#[tokio::test]
+async fn test_encrypt_retries_server_errors() {
+    let seal = FakeSealApi::start().await;
+    let client = client(&seal);
+
+    seal.fail_next_encrypt(2, StatusCode::BAD_GATEWAY);
+    let (ciphertext, _, _) = client
+        .encrypt_report(REPORT, AUDITOR, PACKAGE, 2)
+        .await
+        .unwrap();
+    assert_eq!(seal.encrypt_requests(), 3);
+    let plaintext = client
+        .decrypt_report(&ciphertext, AUDITOR, PACKAGE)
+        .await
+        .unwrap();
+    assert_eq!(plaintext, REPORT);
+
+    seal.fail_next_encrypt(3, StatusCode::TOO_MANY_REQUESTS);
+    let err = client
+        .encrypt_report(REPORT, AUDITOR, PACKAGE, 2)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, AuditorError::SealUnavailable(_)), "{}", err);
+    assert_eq!(seal.encrypt_requests(), 6);
+}
+
+#[tokio::test]
+async fn test_encrypt_rejection_is_not_retried() {
+    let seal = FakeSealApi::start().await;
+    let client = client(&seal);
+
+    seal.fail_next_encrypt(1, StatusCode::BAD_REQUEST);
+    let err = client
+        .encrypt_report(REPORT, AUDITOR, PACKAGE, 2)
+        .await
+        .unwrap_err();
+
+    match err {
+        AuditorError::SealRejected { status, message } => {
+            assert_eq!(status, 400);
+            assert_eq!(message, "seal api failure");
+        }
+        other => panic!("expected SealRejected, got {}", other),
+    }
+    assert_eq!(seal.encrypt_requests(), 1);
+}
+
+#[tokio::test]
+async fn test_invalid_address_is_rejected_before_sending() {
+    let seal = FakeSealApi::start().await;
+    let err = client(&seal)
+        .encrypt_report(REPORT, "0xnot-an-address", PACKAGE, 2)
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, AuditorError::Config(_)), "{}", err);
+    assert_eq!(seal.encrypt_requests(), 0);
+}
+
+#[tokio::test]
+async fn test_health_check_retries_until_unavailable() {
+    let seal = FakeSealApi::start().await;
+    let client = client(&seal);
+    assert_eq!(client.health_check().await.unwrap().status, "healthy");
+    assert_eq!(seal.health_requests(), 1);
+
+    seal.set_unavailable(true);
+    let err = client.health_check().await.unwrap_err();
+    assert!(matches!(err, AuditorError::SealUnavailable(_)), "{}", err);
+    assert_eq!(seal.health_requests(), 4);
+}
+
+#[tokio::test]
+async fn test_request_timeout_is_retried_within_deadline() {
+    let seal = FakeSealApi::start().await;
+    seal.set_encrypt_delay(Duration::from_millis(500));
+
+    // 每次請求 50ms 超時，重試耗盡後歸為暫時不可用
+    let err = client(&seal)
+        .with_request_timeout(Duration::from_millis(50))
+        .encrypt_report(REPORT, AUDITOR, PACKAGE, 2)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, AuditorError::SealUnavailable(_)), "{}", err);
+    assert_eq!(seal.encrypt_requests(), 3);
+
+    // 單次請求時限長於總時限時，以總時限為準
+    seal.set_encrypt_delay(Duration::from_secs(3));
+    let started = Instant::now();
+    let err = SealClient::new(SealApiConfig {
+        api_url: seal.url().to_string(),
+        timeout_secs: 1,
+    })
+    .unwrap()
+    .with_request_timeout(Duration::from_secs(10))
+    .encrypt_report(REPORT, AUDITOR, PACKAGE, 2)
+    .await
+    .unwrap_err();
+    assert!(
+        err.to_string().contains("did not complete within 1s"),
+        "{}",
+        err
+    );
+    assert!(started.elapsed() < Duration::from_secs(3));
+}
+
+fn fallback_encryptor(seal: &FakeSealApi, key_dir: &std::path::Path) -> FallbackEncryptor {
+    FallbackEncryptor::new(
+        SealReportEncryptor::new(client(seal), AUDITOR, PACKAGE, 2),
+        LocalAesEncryptor::new(key_dir, AUDITOR, PACKAGE, 2),
+    )
+}
+
+#[tokio::test]
+async fn test_seal_outage_falls_back_to_local_encryption() {
+    let seal = FakeSealApi::start().await;
+    let keys = tempfile::tempdir().unwrap();
+    let encryptor = fallback_encryptor(&seal, keys.path());
+
+    seal.set_unavailable(true);
+    let (ciphertext, metadata) = encryptor.encrypt(REPORT).await.unwrap();
+    assert_eq!(metadata.scheme, EncryptionScheme::LocalAes256Gcm);
+    assert!(local_encryption::is_local_ciphertext(&ciphertext));
+    assert!(!ciphertext
+        .windows(REPORT.len())
+        .any(|window| window == REPORT.as_bytes()));
+
+    let plaintext = local_encryption::decrypt(keys.path(), &ciphertext).unwrap();
+    assert_eq!(plaintext.as_slice(), REPORT.as_bytes());
+
+    // Seal 恢復後照常使用 Seal
+    seal.set_unavailable(false);
+    let (ciphertext, metadata) = encryptor.encrypt(REPORT).await.unwrap();
+    assert_eq!(metadata.scheme, EncryptionScheme::Seal);
+    assert!(metadata.key_id.is_none());
+    assert!(!local_encryption::is_local_ciphertext(&ciphertext));
+}
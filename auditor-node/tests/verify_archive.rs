@@ -0,0 +1,116 @@
+//! `verify-archive` 命令行測試
+//!
+//! 構建包含一份有效、一份被篡改、一份缺少公鑰的小型存檔，
+//! 核對分類結果、失敗詳情文件與退出碼。
+
+use auditor_node::archive::{ReportOutcome, ReportVerdict};
+use auditor_node::report::ReportManager;
+use auditor_node::trust::{TrustEntry, TrustStore};
+use auditor_node::types::AuditReport;
+use pqc_signer::{Dilithium3Signer, Signer};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+fn keypair() -> Dilithium3Signer {
+    let mut signer = Dilithium3Signer::new();
+    signer.generate_keypair().unwrap();
+    signer
+}
+
+fn signed_report(signer: &Dilithium3Signer, auditor: &str, blob_id: &str) -> AuditReport {
+    let mut report: AuditReport = serde_json::from_value(serde_json::json!({
+        "blob_id": blob_id,
+        "blob_object_id": "0xobject",
+        "auditor": auditor,
+        "timestamp": 1_700_000_000u64,
+        "challenge_epoch": 7,
+        "challenge_results": [],
+        "total_challenges": 10,
+        "successful_verifications": 10,
+        "failed_verifications": 0,
+        "integrity_hash": vec![0u8; 32],
+        "pqc_signature": [],
+        "pqc_algorithm": 3,
+        "is_valid": true,
+        "failure_reason": null,
+    }))
+    .unwrap();
+    ReportManager::sign_report_with(signer, &mut report).unwrap();
+    report
+}
+
+fn write_report(dir: &Path, name: &str, report: &AuditReport) {
+    fs::write(dir.join(name), serde_json::to_vec_pretty(report).unwrap()).unwrap();
+}
+
+fn verify_archive(dir: &Path, trust_store: &Path, failures: &Path) -> std::process::ExitStatus {
+    Command::new(env!("CARGO_BIN_EXE_auditor-node"))
+        .arg("verify-archive")
+        .arg("--dir")
+        .arg(dir)
+        .arg("--trust-store")
+        .arg(trust_store)
+        .arg("--failures")
+        .arg(failures)
+        .args(["--jobs", "2"])
+        .output()
+        .unwrap()
+        .status
+}
+
+#[test]
+fn test_verify_archive_classifies_reports_and_sets_exit_code() {
+    let root: PathBuf =
+        std::env::temp_dir().join(format!("verify_archive_{}", rand::random::<u32>()));
+    let archive = root.join("archive");
+    fs::create_dir_all(&archive).unwrap();
+
+    let known = keypair();
+    let unknown = keypair();
+
+    let mut store = TrustStore::new();
+    store.auditors.insert(
+        "0xknown".to_string(),
+        TrustEntry::new(known.public_key(), 3, true, None),
+    );
+    let trust_store = root.join("trust_store.json");
+    store.save(&trust_store).unwrap();
+
+    write_report(
+        &archive,
+        "good.json",
+        &signed_report(&known, "0xknown", "0xgood"),
+    );
+    write_report(
+        &archive,
+        "missing_key.json",
+        &signed_report(&unknown, "0xunknown", "0xmissing"),
+    );
+
+    // 只有無法驗證的報告時退出碼為 0
+    let failures = root.join("failures.json");
+    assert!(verify_archive(&archive, &trust_store, &failures).success());
+
+    let mut tampered = signed_report(&known, "0xknown", "0xtampered");
+    tampered.is_valid = false;
+    write_report(&archive, "tampered.json", &tampered);
+
+    assert!(!verify_archive(&archive, &trust_store, &failures).success());
+
+    let failures: Vec<ReportOutcome> =
+        serde_json::from_slice(&fs::read(&failures).unwrap()).unwrap();
+    let verdicts: Vec<_> = failures
+        .iter()
+        .map(|f| (f.blob_id.as_deref().unwrap(), f.verdict))
+        .collect();
+    assert_eq!(
+        verdicts,
+        vec![
+            ("0xmissing", ReportVerdict::Unverifiable),
+            ("0xtampered", ReportVerdict::Invalid),
+        ]
+    );
+
+    let _ = fs::remove_dir_all(&root);
+}
@@ -0,0 +1,190 @@
+//! `verify` 命令行測試
+//!
+//! 用真實簽名的報告核對三種退出碼（0 = VALID、1 = INVALID、2 = 錯誤）、
+//! `--json` 輸出，以及公鑰以文件、hex、Base64 傳入和舊版信封的自動識別。
+
+#![allow(deprecated)]
+
+use auditor_node::audit_report::SignedAuditReport;
+use auditor_node::integrity::AuditData;
+use auditor_node::report::ReportManager;
+use auditor_node::types::AuditReport;
+use base64::{engine::general_purpose, Engine as _};
+use pqc_signer::{Dilithium3Signer, Signer};
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+fn keypair() -> Dilithium3Signer {
+    let mut signer = Dilithium3Signer::new();
+    signer.generate_keypair().unwrap();
+    signer
+}
+
+fn signed_report(signer: &Dilithium3Signer) -> AuditReport {
+    let mut report: AuditReport = serde_json::from_value(serde_json::json!({
+        "blob_id": "blob-verify-cli",
+        "blob_object_id": "0xobject",
+        "auditor": "0xauditor",
+        "timestamp": 1_700_000_000u64,
+        "challenge_epoch": 7,
+        "challenge_results": [],
+        "total_challenges": 10,
+        "successful_verifications": 9,
+        "failed_verifications": 1,
+        "integrity_hash": vec![0u8; 32],
+        "pqc_signature": [],
+        "pqc_algorithm": 3,
+        "is_valid": false,
+        "failure_reason": "1 challenge failed",
+    }))
+    .unwrap();
+    ReportManager::sign_report_with(signer, &mut report).unwrap();
+    report
+}
+
+fn temp_dir() -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("verify_cli_{}", rand::random::<u32>()));
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn verify(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_auditor-node"))
+        .arg("--config")
+        .arg("/nonexistent/config.toml")
+        .arg("verify")
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+fn path_str(path: &Path) -> &str {
+    path.to_str().unwrap()
+}
+
+#[test]
+fn test_verify_exit_codes_and_json_verdict() {
+    let dir = temp_dir();
+    let signer = keypair();
+    let report_path = dir.join("report.json");
+    fs::write(
+        &report_path,
+        serde_json::to_vec_pretty(&signed_report(&signer)).unwrap(),
+    )
+    .unwrap();
+
+    // 有效：hex 公鑰，JSON 輸出
+    let hex_key = hex::encode(signer.public_key());
+    let output = verify(&[
+        "--report",
+        path_str(&report_path),
+        "--public-key",
+        &hex_key,
+        "--json",
+    ]);
+    assert_eq!(output.status.code(), Some(0));
+    let verdict: Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(verdict["verdict"], "VALID");
+    assert_eq!(verdict["blob_id"], "blob-verify-cli");
+    assert_eq!(verdict["timestamp"], 1_700_000_000u64);
+    assert_eq!(verdict["total_challenges"], 10);
+    assert_eq!(verdict["failed_verifications"], 1);
+    assert_eq!(verdict["signature_valid"], true);
+
+    // 有效：公鑰文件（原始字節），位置參數形式，文本輸出
+    let key_path = dir.join("pqc_public.key");
+    fs::write(&key_path, signer.public_key()).unwrap();
+    let output = verify(&[path_str(&report_path), "--public-key", path_str(&key_path)]);
+    assert_eq!(output.status.code(), Some(0));
+    let text = String::from_utf8(output.stdout).unwrap();
+    assert!(text.starts_with("VALID"));
+    assert!(text.contains("9/10 verified, 1 failed"));
+
+    // 無效：另一把密鑰（Base64）
+    let other = general_purpose::STANDARD.encode(keypair().public_key());
+    let output = verify(&[
+        "--report",
+        path_str(&report_path),
+        "--public-key",
+        &other,
+        "--json",
+    ]);
+    assert_eq!(output.status.code(), Some(1));
+    let verdict: Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(verdict["verdict"], "INVALID");
+    assert_eq!(verdict["signature_valid"], false);
+
+    // 錯誤：報告不存在、缺少公鑰、公鑰無法解析
+    let missing = dir.join("missing.json");
+    let output = verify(&["--report", path_str(&missing), "--public-key", &hex_key]);
+    assert_eq!(output.status.code(), Some(2));
+    let output = verify(&["--report", path_str(&report_path)]);
+    assert_eq!(output.status.code(), Some(2));
+    let output = verify(&[
+        "--report",
+        path_str(&report_path),
+        "--public-key",
+        "not a key!",
+    ]);
+    assert_eq!(output.status.code(), Some(2));
+
+    fs::remove_dir_all(&dir).ok();
+}
+
+#[test]
+fn test_verify_detects_legacy_envelope() {
+    let dir = temp_dir();
+    let signer = keypair();
+
+    let audit_data: AuditData = serde_json::from_value(serde_json::json!({
+        "blob_id": "blob-verify-cli",
+        "content_hash": "00",
+        "merkle_root": "00",
+        "total_challenges": 10,
+        "successful_verifications": 9,
+        "failed_verifications": 1,
+        "file_size": 0,
+        "timestamp": 1_700_000_000u64,
+        "verification_status": "ACCESSIBLE",
+    }))
+    .unwrap();
+    let signature = signer
+        .sign(&serde_json::to_vec(&audit_data).unwrap())
+        .unwrap();
+    let legacy = serde_json::json!({
+        "audit_data": audit_data,
+        "signature": general_purpose::STANDARD.encode(signature),
+        "algorithm": "Dilithium3",
+        "auditor_public_key": general_purpose::STANDARD.encode(signer.public_key()),
+        "report_timestamp": 1_700_000_000u64,
+    });
+    let legacy_path = dir.join("legacy.json");
+    fs::write(&legacy_path, legacy.to_string()).unwrap();
+
+    // 確認構造的信封確實是舊版格式
+    assert!(SignedAuditReport::from_json(&legacy.to_string())
+        .unwrap()
+        .verify_signature()
+        .unwrap());
+
+    let output = verify(&[
+        "--report",
+        path_str(&legacy_path),
+        "--public-key",
+        &hex::encode(signer.public_key()),
+        "--json",
+    ]);
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let verdict: Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(verdict["verdict"], "VALID");
+    assert_eq!(verdict["blob_id"], "blob-verify-cli");
+
+    fs::remove_dir_all(&dir).ok();
+}
@@ -47,6 +47,9 @@ module audit_system::audit_core {
     /// Dilithium-2 algorithm identifier
     const ALGO_DILITHIUM2: u8 = 2;
 
+    /// Dilithium-3 algorithm identifier (the auditor node's default signer)
+    const ALGO_DILITHIUM3: u8 = 3;
+
     // ============ Core Data Structures ============
 
     /// Audit Record (complete version)
@@ -75,7 +78,7 @@ module audit_system::audit_core {
         // === Integrity Proof ===
         integrity_hash: vector<u8>,         // Aggregated hash of all challenges (32 bytes Blake2b-256)
         pqc_signature: vector<u8>,          // PQC signature over integrity_hash
-        pqc_algorithm: u8,                  // Signature algorithm (1=Falcon512, 2=Dilithium2)
+        pqc_algorithm: u8,                  // Signature algorithm (1=Falcon512, 2=Dilithium2, 3=Dilithium3)
 
         // === Verification Result ===
         is_valid: bool,                     // Whether audit passed
@@ -268,7 +271,7 @@ module audit_system::audit_core {
     /// - successful_verifications: Number of successful verifications
     /// - integrity_hash: Aggregated hash of all challenges
     /// - pqc_signature: PQC signature
-    /// - pqc_algorithm: Signature algorithm (1=Falcon512, 2=Dilithium2)
+    /// - pqc_algorithm: Signature algorithm (1=Falcon512, 2=Dilithium2, 3=Dilithium3)
     ///
     /// Security checks:
     /// 1. Auditor must be in the authorized list
@@ -303,10 +306,7 @@ module audit_system::audit_core {
         );
 
         // Verify PQC algorithm
-        assert!(
-            pqc_algorithm == ALGO_FALCON512 || pqc_algorithm == ALGO_DILITHIUM2,
-            E_INVALID_SIGNATURE_ALGORITHM
-        );
+        assert!(is_supported_pqc_algorithm(pqc_algorithm), E_INVALID_SIGNATURE_ALGORITHM);
 
         // Calculate failure count and verification result
         let failed_verifications = total_challenges - successful_verifications;
@@ -441,6 +441,13 @@ module audit_system::audit_core {
         )
     }
 
+    /// Check if a signature algorithm identifier is accepted by submit_audit_record
+    public fun is_supported_pqc_algorithm(pqc_algorithm: u8): bool {
+        pqc_algorithm == ALGO_FALCON512 ||
+        pqc_algorithm == ALGO_DILITHIUM2 ||
+        pqc_algorithm == ALGO_DILITHIUM3
+    }
+
     /// Check if auditor is authorized
     public fun is_auditor_authorized(config: &AuditConfig, auditor: address): bool {
         vector::contains(&config.authorized_auditors, &auditor)
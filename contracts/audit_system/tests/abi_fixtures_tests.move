@@ -0,0 +1,102 @@
+/// ABI Fixture Tests
+///
+/// Produces the BCS encodings in `auditor-node/tests/fixtures/chain_types.json`.
+/// Each fixture is the concatenation of the entry function's pure arguments
+/// (or the event's fields) in declaration order, which is exactly what the
+/// Rust mirrors in `auditor-node/src/chain_types.rs` serialize to.
+///
+/// If a signature below changes, update the fixture here and in the JSON file.
+#[test_only]
+module audit_system::abi_fixtures_tests {
+    use std::bcs;
+    use std::option;
+    use std::vector;
+    use sui::object;
+    use audit_system::audit_core;
+
+    /// u256 with little-endian bytes 0x01 0x01
+    const BLOB_ID: u256 = 257;
+
+    fun append<T>(bytes: &mut vector<u8>, value: &T) {
+        vector::append(bytes, bcs::to_bytes(value));
+    }
+
+    fun repeat(byte: u8, len: u64): vector<u8> {
+        let mut out = vector[];
+        let mut i = 0;
+        while (i < len) {
+            vector::push_back(&mut out, byte);
+            i = i + 1;
+        };
+        out
+    }
+
+    /// audit_core::submit_audit_record(blob_id, blob_object_id, challenge_epoch,
+    /// total_challenges, successful_verifications, integrity_hash, pqc_signature, pqc_algorithm)
+    #[test]
+    fun test_audit_record_params() {
+        let mut bytes = vector[];
+        append(&mut bytes, &BLOB_ID);
+        append(&mut bytes, &object::id_from_address(@0xb10b));
+        append(&mut bytes, &7u32);
+        append(&mut bytes, &10u16);
+        append(&mut bytes, &9u16);
+        append(&mut bytes, &repeat(0xab, 32));
+        append(&mut bytes, &vector[1u8, 2u8, 3u8]);
+        append(&mut bytes, &3u8);
+
+        assert!(bytes == x"0101000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000b10b070000000a00090020abababababababababababababababababababababababababababababababab0301020303", 0);
+    }
+
+    /// auditor_registry::submit_audit_report_metadata(encrypted_report_blob_id,
+    /// audit_record_ids, pqc_signature)
+    #[test]
+    fun test_report_metadata_params() {
+        let mut bytes = vector[];
+        append(&mut bytes, &object::id_from_address(@0xa11ce));
+        append(&mut bytes, &vector[object::id_from_address(@0x1), object::id_from_address(@0x2)]);
+        append(&mut bytes, &vector[9u8, 9u8]);
+
+        assert!(bytes == x"00000000000000000000000000000000000000000000000000000000000a11ce0200000000000000000000000000000000000000000000000000000000000000010000000000000000000000000000000000000000000000000000000000000002020909", 0);
+    }
+
+    /// report_access::create_policy(report_blob_id, audit_record_id, allowed_readers,
+    /// allowed_auditors, expires_at_ms)
+    #[test]
+    fun test_policy_params() {
+        let mut bytes = vector[];
+        append(&mut bytes, &BLOB_ID);
+        append(&mut bytes, &object::id_from_address(@0xb10b));
+        append(&mut bytes, &vector[@0xcafe]);
+        append(&mut bytes, &vector<address>[]);
+        append(&mut bytes, &option::some(1_700_000_000_000u64));
+
+        assert!(bytes == x"0101000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000b10b01000000000000000000000000000000000000000000000000000000000000cafe00010068e5cf8b010000", 0);
+    }
+
+    /// audit_core::AuditCreated { audit_record_id, blob_id, auditor, challenge_epoch,
+    /// total_challenges, is_valid }
+    #[test]
+    fun test_audit_created_event() {
+        let mut bytes = vector[];
+        append(&mut bytes, &object::id_from_address(@0x1));
+        append(&mut bytes, &BLOB_ID);
+        append(&mut bytes, &@0xa11ce);
+        append(&mut bytes, &7u32);
+        append(&mut bytes, &10u16);
+        append(&mut bytes, &false);
+
+        assert!(bytes == x"0000000000000000000000000000000000000000000000000000000000000001010100000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000a11ce070000000a0000", 0);
+    }
+
+    /// audit_core::submit_audit_record accepts exactly the `pqc_algorithms` IDs
+    #[test]
+    fun test_pqc_algorithms() {
+        let accepted = vector[1u8, 2u8, 3u8];
+        let mut id = 0u8;
+        while (id < 8) {
+            assert!(audit_core::is_supported_pqc_algorithm(id) == vector::contains(&accepted, &id), 0);
+            id = id + 1;
+        };
+    }
+}
@@ -0,0 +1,47 @@
+//! Dilithium3 batch signing benchmark
+//!
+//! Compares signing with a freshly restored signer per message (the secret key is
+//! parsed every time) against `sign_batch` on one signer (parsed once).
+//!
+//! Run with: `cargo bench -p pqc-signer --bench sign_batch`
+
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+use pqc_signer::{Dilithium3Signer, Signer};
+
+const BATCH_SIZE: usize = 100;
+
+fn bench_sign_batch(c: &mut Criterion) {
+    let mut signer = Dilithium3Signer::new();
+    signer.generate_keypair().unwrap();
+    let (public_key, secret_key) = (signer.public_key().to_vec(), signer.secret_key().to_vec());
+
+    let messages: Vec<Vec<u8>> = (0..BATCH_SIZE)
+        .map(|i| format!("{{\"blob_id\":\"blob-{}\",\"success_rate\":98}}", i).into_bytes())
+        .collect();
+    let refs: Vec<&[u8]> = messages.iter().map(Vec::as_slice).collect();
+
+    let mut group = c.benchmark_group("dilithium3_sign_100");
+
+    group.bench_function("parse_per_message", |b| {
+        b.iter(|| {
+            for message in &refs {
+                let signer =
+                    Dilithium3Signer::from_bytes_unchecked(&public_key, &secret_key).unwrap();
+                black_box(signer.sign(message).unwrap());
+            }
+        })
+    });
+
+    group.bench_function("sign_batch", |b| {
+        b.iter_batched(
+            || Dilithium3Signer::from_bytes_unchecked(&public_key, &secret_key).unwrap(),
+            |signer| black_box(signer.sign_batch(&refs).unwrap()),
+            BatchSize::SmallInput,
+        )
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_sign_batch);
+criterion_main!(benches);
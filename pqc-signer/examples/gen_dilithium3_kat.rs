@@ -0,0 +1,51 @@
+//! Generate the Dilithium3 known-answer test fixture
+//!
+//! Writes `tests/fixtures/dilithium3_kat.json` (or the path given as the first argument) with
+//! fresh throwaway keypairs. Rerun after bumping `pqcrypto-dilithium` and commit the result.
+//!
+//! ```text
+//! cargo run -p pqc-signer --example gen_dilithium3_kat
+//! ```
+
+use pqc_signer::dilithium::Dilithium3Signer;
+use pqc_signer::kat::{KatFile, DILITHIUM3_KAT_FIXTURE};
+use pqc_signer::traits::Signer;
+use std::path::PathBuf;
+
+/// A report-shaped payload, as the auditor node signs it
+const AUDIT_REPORT: &str = r#"{"blob_id":"0x1234567890abcdef","audit_epoch":42,"total_challenges":50,"successful_challenges":48,"timestamp":1699459200}"#;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let path = std::env::args()
+        .nth(1)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(DILITHIUM3_KAT_FIXTURE));
+
+    let mut signers = Vec::new();
+    for _ in 0..2 {
+        let mut signer = Dilithium3Signer::new();
+        signer.generate_keypair()?;
+        signers.push(signer);
+    }
+
+    let pattern: Vec<u8> = (0..1024u32).map(|i| (i % 251) as u8).collect();
+    let messages: [&[u8]; 4] = [b"", b"walrus audit", AUDIT_REPORT.as_bytes(), &pattern];
+    let kat = KatFile::generate(&signers, &messages)?;
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(&path, kat.to_json() + "\n")?;
+
+    println!(
+        "Wrote {} vectors and {} negative vectors to {}",
+        kat.vectors.len(),
+        kat.negative.len(),
+        path.display()
+    );
+    println!(
+        "  pqcrypto-dilithium {}, pqc-signer {}",
+        kat.pqcrypto_dilithium, kat.pqc_signer
+    );
+    Ok(())
+}
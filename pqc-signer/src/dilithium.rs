@@ -29,10 +29,18 @@
 //! - Performance overhead too high (Level 3 is sufficient for audit scenarios)
 //! - Increased storage cost (1.3 KB more per audit report)
 
-use crate::error::{PqcError, Result};
+use crate::error::{KeyKind, PqcError, Result};
+use crate::kat::TestVector;
+use crate::prehash::{self, MessageDigest};
 use crate::traits::Signer;
 use pqcrypto_dilithium::dilithium3;
 use pqcrypto_traits::sign::{PublicKey, SecretKey, SignedMessage};
+use std::sync::OnceLock;
+use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
+
+/// Message signed and verified by [`Dilithium3Signer::from_bytes`] to check that the
+/// public and secret key belong together (never exposed as a signature)
+const KEY_PAIR_PROBE: &[u8] = b"pqc-signer/dilithium3/key-pair-probe";
 
 /// Dilithium3 signer
 ///
@@ -51,13 +59,26 @@ use pqcrypto_traits::sign::{PublicKey, SecretKey, SignedMessage};
 /// let signature = signer.sign(message).unwrap();
 ///
 /// // Verify signature
-/// let is_valid = signer.verify(message, &signature).unwrap();
+/// let is_valid = signer.verify_detached(message, &signature).unwrap();
 /// assert!(is_valid);
 /// ```
+///
+/// The parsed keys are cached on first use, so repeated `sign`/`verify` calls on the
+/// same signer skip key deserialization (see [`Dilithium3Signer::sign_batch`]).
+///
+/// # Secret Key Memory
+///
+/// The secret key (raw bytes and the parsed cache) is zeroized when the signer is dropped.
+/// Cloning creates another copy of the secret key, which is likewise zeroized on drop;
+/// prefer moving or borrowing a signer over cloning it.
 #[derive(Clone)]
 pub struct Dilithium3Signer {
     public_key: Vec<u8>,
-    secret_key: Vec<u8>,
+    secret_key: Zeroizing<Vec<u8>>,
+    /// Parsed `public_key` (filled on first verify)
+    parsed_public_key: OnceLock<dilithium3::PublicKey>,
+    /// Parsed `secret_key` (filled on first sign)
+    parsed_secret_key: OnceLock<dilithium3::SecretKey>,
 }
 
 impl Dilithium3Signer {
@@ -67,39 +88,71 @@ impl Dilithium3Signer {
     pub fn new() -> Self {
         Self {
             public_key: Vec::new(),
-            secret_key: Vec::new(),
+            secret_key: Zeroizing::new(Vec::new()),
+            parsed_public_key: OnceLock::new(),
+            parsed_secret_key: OnceLock::new(),
         }
     }
 
     /// Restore keypair from bytes
     ///
+    /// After the length checks, a fixed probe message is signed with `secret_key` and
+    /// verified with `public_key`, so a public key and secret key from different keypairs
+    /// (e.g. mixed-up keystore files) are rejected here instead of producing signatures
+    /// that fail verification later. An empty `secret_key` yields a verification-only
+    /// signer and skips the probe.
+    ///
     /// # Parameters
     /// - `public_key`: Public key bytes (1952 bytes)
-    /// - `secret_key`: Secret key bytes (4032 bytes)
+    /// - `secret_key`: Secret key bytes (4032 bytes, or empty)
     ///
     /// # Errors
-    /// - Returns `KeyGenerationError` if key length is incorrect
+    /// - Returns `InvalidKeyLength` if key length is incorrect
+    /// - Returns `InvalidKeyFormat` if a key cannot be parsed
+    /// - Returns `KeyPairMismatch` if the secret key does not belong to the public key
+    ///
+    /// # Performance
+    /// - The probe costs one signature and one verification (~9 ms); use
+    ///   [`from_bytes_unchecked`](Self::from_bytes_unchecked) when the keys are known to match
     pub fn from_bytes(public_key: &[u8], secret_key: &[u8]) -> Result<Self> {
+        let signer = Self::from_bytes_unchecked(public_key, secret_key)?;
+        if !signer.secret_key.is_empty() {
+            signer.check_key_pair()?;
+        }
+        Ok(signer)
+    }
+
+    /// Restore keypair from bytes without checking that the keys belong together
+    ///
+    /// Same as [`from_bytes`](Self::from_bytes) minus the probe signature; only the key
+    /// lengths are checked. A mismatched keypair produces signatures that fail verification
+    /// against [`public_key`](Signer::public_key).
+    ///
+    /// # Errors
+    /// - Returns `InvalidKeyLength` if key length is incorrect
+    pub fn from_bytes_unchecked(public_key: &[u8], secret_key: &[u8]) -> Result<Self> {
         // Verify key length
         if public_key.len() != dilithium3::public_key_bytes() {
-            return Err(PqcError::KeyGenerationError(format!(
-                "Invalid public key length: expected {} bytes, got {}",
-                dilithium3::public_key_bytes(),
-                public_key.len()
-            )));
+            return Err(PqcError::InvalidKeyLength {
+                key: KeyKind::Public,
+                expected: dilithium3::public_key_bytes(),
+                got: public_key.len(),
+            });
         }
 
-        if secret_key.len() != dilithium3::secret_key_bytes() {
-            return Err(PqcError::KeyGenerationError(format!(
-                "Invalid secret key length: expected {} bytes, got {}",
-                dilithium3::secret_key_bytes(),
-                secret_key.len()
-            )));
+        if !secret_key.is_empty() && secret_key.len() != dilithium3::secret_key_bytes() {
+            return Err(PqcError::InvalidKeyLength {
+                key: KeyKind::Secret,
+                expected: dilithium3::secret_key_bytes(),
+                got: secret_key.len(),
+            });
         }
 
         Ok(Self {
             public_key: public_key.to_vec(),
-            secret_key: secret_key.to_vec(),
+            secret_key: Zeroizing::new(secret_key.to_vec()),
+            parsed_public_key: OnceLock::new(),
+            parsed_secret_key: OnceLock::new(),
         })
     }
 
@@ -118,8 +171,8 @@ impl Dilithium3Signer {
     /// - `Dilithium3Signer` instance containing only public key (secret_key is empty)
     ///
     /// # Errors
-    /// - Returns `KeyGenerationError` if public key length is incorrect
-    /// - Returns `KeyGenerationError` if public key format is invalid (deserialization fails)
+    /// - Returns `InvalidKeyLength` if public key length is incorrect
+    /// - Returns `InvalidKeyFormat` if public key format is invalid (deserialization fails)
     ///
     /// # Security
     /// - Created Signer **cannot perform signing operations** (private key is empty)
@@ -142,7 +195,7 @@ impl Dilithium3Signer {
     /// // Can verify signatures
     /// let message = b"Audit report data";
     /// let signature = // ... obtained from somewhere ...
-    /// # vec![0u8; 100];
+    /// # vec![0u8; 3309];
     /// let is_valid = verifier.verify(message, &signature)?;
     ///
     /// // Cannot sign (will return error)
@@ -152,110 +205,103 @@ impl Dilithium3Signer {
     pub fn from_public_key_only(public_key: &[u8]) -> Result<Self> {
         // 1. Verify public key length
         if public_key.len() != dilithium3::public_key_bytes() {
-            return Err(PqcError::KeyGenerationError(format!(
-                "Invalid public key length: expected {} bytes, got {}",
-                dilithium3::public_key_bytes(),
-                public_key.len()
-            )));
+            return Err(PqcError::InvalidKeyLength {
+                key: KeyKind::Public,
+                expected: dilithium3::public_key_bytes(),
+                got: public_key.len(),
+            });
         }
 
         // 2. Verify public key format (attempt deserialization)
-        dilithium3::PublicKey::from_bytes(public_key).map_err(|e| {
-            PqcError::KeyGenerationError(format!(
-                "Invalid public key format (failed to deserialize): {:?}",
-                e
-            ))
-        })?;
+        let pk = dilithium3::PublicKey::from_bytes(public_key)
+            .map_err(|_| PqcError::InvalidKeyFormat(KeyKind::Public))?;
 
         // 3. Create verification-only Signer
-        tracing::debug!(
+        debug!(
             "Created verification-only Dilithium3Signer: pk_len={} bytes (sk=empty)",
             public_key.len()
         );
 
         Ok(Self {
             public_key: public_key.to_vec(),
-            secret_key: Vec::new(), // Empty private key, for verification only
+            secret_key: Zeroizing::new(Vec::new()), // Empty private key, for verification only
+            parsed_public_key: OnceLock::from(pk),
+            parsed_secret_key: OnceLock::new(),
         })
     }
 
-    /// Get secret key bytes (for persistence)
+    /// Sign several messages, parsing the secret key at most once
     ///
-    /// # Security Warning
-    /// Private keys should be stored securely, not transmitted over network or logged
-    pub fn secret_key(&self) -> &[u8] {
-        &self.secret_key
-    }
-
-    /// Return algorithm information
-    pub fn algorithm_info() -> AlgorithmInfo {
-        AlgorithmInfo {
-            name: "Dilithium3",
-            nist_level: 3,
-            public_key_size: dilithium3::public_key_bytes(),
-            secret_key_size: dilithium3::secret_key_bytes(),
-            signature_size: dilithium3::signature_bytes(),
+    /// Equivalent to calling [`Signer::sign`] on each message in order; each signature
+    /// verifies individually against its own message.
+    ///
+    /// # Errors
+    /// - Returns `KeyNotInitialized` if keys not initialized
+    /// - Returns `InvalidKeyFormat` if the secret key is malformed
+    /// - Returns `ReservedMessage` if any message has the form of a digest envelope
+    pub fn sign_batch(&self, messages: &[&[u8]]) -> Result<Vec<Vec<u8>>> {
+        for message in messages {
+            prehash::check_raw_message(message)?;
         }
+        let sk = self.parsed_secret_key()?;
+        let signatures = messages
+            .iter()
+            .map(|message| Self::sign_with(sk, message))
+            .collect();
+
+        debug!("Signed batch of {} messages", messages.len());
+
+        Ok(signatures)
     }
-}
 
-impl Default for Dilithium3Signer {
-    fn default() -> Self {
-        Self::new()
+    /// Sign [`KEY_PAIR_PROBE`] with the secret key and verify it with the public key
+    fn check_key_pair(&self) -> Result<()> {
+        let signature = Self::sign_with(self.parsed_secret_key()?, KEY_PAIR_PROBE);
+        if self.verify_message(KEY_PAIR_PROBE, &signature)? {
+            Ok(())
+        } else {
+            Err(PqcError::KeyPairMismatch)
+        }
     }
-}
 
-impl Signer for Dilithium3Signer {
-    /// Generate new Dilithium3 keypair
-    ///
-    /// # Errors
-    /// - Returns `KeyGenerationError` when key generation fails
-    ///
-    /// # Performance
-    /// - Average time: ~10-20 ms (depends on system entropy source)
-    fn generate_keypair(&mut self) -> Result<()> {
-        let (pk, sk) = dilithium3::keypair();
+    /// Parsed secret key (parsed on first call, then cached)
+    fn parsed_secret_key(&self) -> Result<&dilithium3::SecretKey> {
+        if let Some(sk) = self.parsed_secret_key.get() {
+            return Ok(sk);
+        }
 
-        self.public_key = pk.as_bytes().to_vec();
-        self.secret_key = sk.as_bytes().to_vec();
+        if self.secret_key.is_empty() {
+            return Err(PqcError::KeyNotInitialized(KeyKind::Secret));
+        }
 
-        tracing::info!(
-            "Generated Dilithium3 keypair: pk_len={} bytes, sk_len={} bytes",
-            self.public_key.len(),
-            self.secret_key.len()
-        );
+        // Rebuild key from bytes
+        let sk = dilithium3::SecretKey::from_bytes(&self.secret_key)
+            .map_err(|_| PqcError::InvalidKeyFormat(KeyKind::Secret))?;
 
-        Ok(())
+        Ok(self.parsed_secret_key.get_or_init(|| sk))
     }
 
-    /// Sign message with Dilithium3
-    ///
-    /// # Parameters
-    /// - `message`: Message to sign (any length)
-    ///
-    /// # Returns
-    /// - Signature bytes (~3,293 bytes)
-    ///
-    /// # Errors
-    /// - Returns `SigningError` if keys not initialized
-    /// - Returns `SigningError` if signing fails
-    ///
-    /// # Performance
-    /// - Average time: ~7 ms (1 KB message)
-    fn sign(&self, message: &[u8]) -> Result<Vec<u8>> {
-        if self.secret_key.is_empty() {
-            return Err(PqcError::SigningError(
-                "Secret key not initialized. Call generate_keypair() first.".to_string(),
-            ));
+    /// Parsed public key (parsed on first call, then cached)
+    fn parsed_public_key(&self) -> Result<&dilithium3::PublicKey> {
+        if let Some(pk) = self.parsed_public_key.get() {
+            return Ok(pk);
         }
 
-        // Rebuild key from bytes
-        let sk = dilithium3::SecretKey::from_bytes(&self.secret_key).map_err(|e| {
-            PqcError::SigningError(format!("Failed to parse secret key: {:?}", e))
-        })?;
+        if self.public_key.is_empty() {
+            return Err(PqcError::KeyNotInitialized(KeyKind::Public));
+        }
+
+        // Rebuild public key from bytes
+        let pk = dilithium3::PublicKey::from_bytes(&self.public_key)
+            .map_err(|_| PqcError::InvalidKeyFormat(KeyKind::Public))?;
+
+        Ok(self.parsed_public_key.get_or_init(|| pk))
+    }
 
+    /// Produce a detached signature with an already parsed secret key
+    fn sign_with(sk: &dilithium3::SecretKey, message: &[u8]) -> Vec<u8> {
         // Execute signing
-        let signed_message = dilithium3::sign(message, &sk);
+        let signed_message = dilithium3::sign(message, sk);
 
         // pqcrypto-dilithium returns SignedMessage = [signature] + [message]
         // We only need the signature part (first signature_bytes() bytes) for detached signature
@@ -265,43 +311,56 @@ impl Signer for Dilithium3Signer {
         // Extract pure signature (detached signature)
         let detached_signature = &signed_bytes[..sig_len];
 
-        tracing::debug!(
+        debug!(
             "Signed message: msg_len={} bytes, detached_sig_len={} bytes (from SignedMessage {} bytes)",
             message.len(),
             detached_signature.len(),
             signed_bytes.len()
         );
 
-        Ok(detached_signature.to_vec())
+        detached_signature.to_vec()
     }
 
-    /// Verify Dilithium3 signature
+    /// Verify a detached Dilithium3 signature (the canonical verification API)
+    ///
+    /// `signature` must be exactly what [`Signer::sign`] returned. Its length is checked
+    /// before the signed message is reassembled: a truncated or overlong signature would
+    /// otherwise shift the boundary between signature and message.
     ///
     /// # Parameters
     /// - `message`: Original message
-    /// - `signature`: Signature bytes (~3,293 bytes)
+    /// - `signature`: Detached signature bytes (exactly `signature_bytes()`)
     ///
     /// # Returns
     /// - `Ok(true)`: Signature is valid
     /// - `Ok(false)`: Signature is invalid
-    /// - `Err`: Error occurred during verification
+    ///
+    /// # Errors
+    /// - Returns `InvalidSignatureLength` unless the signature is exactly `signature_bytes()`
+    /// - Returns `KeyNotInitialized` / `InvalidKeyFormat` if the public key is missing or
+    ///   unparseable
+    /// - Returns `ReservedMessage` if the message has the form of a digest envelope
     ///
     /// # Performance
     /// - Average time: ~1.5 ms
     ///
     /// # Note
     /// Verification only requires public key, can be executed in environments without private key
-    fn verify(&self, message: &[u8], signature: &[u8]) -> Result<bool> {
-        if self.public_key.is_empty() {
-            return Err(PqcError::VerificationError(
-                "Public key not initialized".to_string(),
-            ));
+    pub fn verify_detached(&self, message: &[u8], signature: &[u8]) -> Result<bool> {
+        prehash::check_raw_message(message)?;
+        self.verify_message(message, signature)
+    }
+
+    /// Verify a detached signature over the exact bytes signed (raw message or digest envelope)
+    fn verify_message(&self, message: &[u8], signature: &[u8]) -> Result<bool> {
+        if signature.len() != dilithium3::signature_bytes() {
+            return Err(PqcError::InvalidSignatureLength {
+                expected: dilithium3::signature_bytes(),
+                got: signature.len(),
+            });
         }
 
-        // Rebuild public key from bytes
-        let pk = dilithium3::PublicKey::from_bytes(&self.public_key).map_err(|e| {
-            PqcError::VerificationError(format!("Failed to parse public key: {:?}", e))
-        })?;
+        let pk = self.parsed_public_key()?;
 
         // Our sign() returns detached signature
         // Need to rebuild SignedMessage = [signature] + [message] for verification
@@ -310,21 +369,16 @@ impl Signer for Dilithium3Signer {
         signed_message_bytes.extend_from_slice(message);
 
         // Rebuild SignedMessage from bytes
-        let signed_msg =
-            dilithium3::SignedMessage::from_bytes(&signed_message_bytes).map_err(|e| {
-                PqcError::VerificationError(format!(
-                    "Failed to reconstruct SignedMessage: {:?}",
-                    e
-                ))
-            })?;
+        let signed_msg = dilithium3::SignedMessage::from_bytes(&signed_message_bytes)
+            .map_err(|_| PqcError::MalformedSignature)?;
 
         // Execute verification
-        match dilithium3::open(&signed_msg, &pk) {
+        match dilithium3::open(&signed_msg, pk) {
             Ok(verified_message) => {
                 // Check if message matches
                 let is_valid = verified_message == message;
 
-                tracing::debug!(
+                debug!(
                     "Signature verification: valid={}, msg_len={} bytes",
                     is_valid,
                     message.len()
@@ -334,12 +388,155 @@ impl Signer for Dilithium3Signer {
             }
             Err(_) => {
                 // Signature verification failed
-                tracing::warn!("Dilithium3 signature verification failed");
+                warn!("Dilithium3 signature verification failed");
                 Ok(false)
             }
         }
     }
 
+    /// Get secret key bytes (for persistence)
+    ///
+    /// # Security Warning
+    /// Private keys should be stored securely, not transmitted over network or logged
+    pub fn secret_key(&self) -> &[u8] {
+        &self.secret_key
+    }
+
+    /// Sign `message` and capture keypair, message and signature as a known-answer vector
+    ///
+    /// Used to (re)generate the committed KAT fixture; see the [`kat`](crate::kat) module.
+    ///
+    /// # Errors
+    /// - Same as [`sign`](Signer::sign)
+    ///
+    /// # Security Warning
+    /// The vector contains the secret key; only export throwaway test keys
+    pub fn export_test_vector(&self, message: &[u8]) -> Result<TestVector> {
+        let signature = self.sign(message)?;
+        Ok(TestVector {
+            public_key: self.public_key.clone(),
+            secret_key: self.secret_key.to_vec(),
+            message: message.to_vec(),
+            signature,
+        })
+    }
+
+    /// Return algorithm information
+    pub fn algorithm_info() -> AlgorithmInfo {
+        AlgorithmInfo {
+            name: "Dilithium3",
+            nist_level: 3,
+            public_key_size: dilithium3::public_key_bytes(),
+            secret_key_size: dilithium3::secret_key_bytes(),
+            signature_size: dilithium3::signature_bytes(),
+        }
+    }
+}
+
+impl Default for Dilithium3Signer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Zeroize for Dilithium3Signer {
+    /// Zeroize the secret key (raw bytes and parsed cache), leaving a verification-only signer
+    fn zeroize(&mut self) {
+        self.secret_key.zeroize();
+        if let Some(sk) = self.parsed_secret_key.get_mut() {
+            zeroize_parsed_secret_key(sk);
+        }
+        self.parsed_secret_key = OnceLock::new();
+    }
+}
+
+impl Drop for Dilithium3Signer {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for Dilithium3Signer {}
+
+/// Overwrite a parsed secret key in place (pqcrypto offers no zeroizing API)
+fn zeroize_parsed_secret_key(sk: &mut dilithium3::SecretKey) {
+    // SAFETY: `SecretKey` is a newtype over a byte array: every byte is initialized,
+    // and all-zero bytes are a valid value. The slice covers exactly the borrowed key.
+    let bytes = unsafe {
+        std::slice::from_raw_parts_mut(
+            (sk as *mut dilithium3::SecretKey).cast::<u8>(),
+            std::mem::size_of::<dilithium3::SecretKey>(),
+        )
+    };
+    bytes.zeroize();
+}
+
+impl Signer for Dilithium3Signer {
+    /// Generate new Dilithium3 keypair
+    ///
+    /// # Errors
+    /// - Returns `Backend` when key generation fails
+    ///
+    /// # Performance
+    /// - Average time: ~10-20 ms (depends on system entropy source)
+    fn generate_keypair(&mut self) -> Result<()> {
+        let (pk, sk) = dilithium3::keypair();
+
+        self.public_key = pk.as_bytes().to_vec();
+        // Wipe the previous secret key before replacing it
+        self.zeroize();
+        self.secret_key = Zeroizing::new(sk.as_bytes().to_vec());
+        self.parsed_public_key = OnceLock::from(pk);
+        self.parsed_secret_key = OnceLock::from(sk);
+
+        info!(
+            "Generated Dilithium3 keypair: pk_len={} bytes, sk_len={} bytes",
+            self.public_key.len(),
+            self.secret_key.len()
+        );
+
+        Ok(())
+    }
+
+    /// Sign message with Dilithium3
+    ///
+    /// # Parameters
+    /// - `message`: Message to sign (any length)
+    ///
+    /// # Returns
+    /// - Signature bytes (~3,293 bytes)
+    ///
+    /// # Errors
+    /// - Returns `KeyNotInitialized` if keys not initialized
+    /// - Returns `InvalidKeyFormat` if the secret key is malformed
+    /// - Returns `ReservedMessage` if the message has the form of a digest envelope
+    ///
+    /// # Performance
+    /// - Average time: ~7 ms (1 KB message)
+    fn sign(&self, message: &[u8]) -> Result<Vec<u8>> {
+        prehash::check_raw_message(message)?;
+        let sk = self.parsed_secret_key()?;
+        Ok(Self::sign_with(sk, message))
+    }
+
+    /// Verify Dilithium3 signature
+    ///
+    /// Same as [`Dilithium3Signer::verify_detached`].
+    fn verify(&self, message: &[u8], signature: &[u8]) -> Result<bool> {
+        self.verify_detached(message, signature)
+    }
+
+    /// Sign a message digest (signs `DIGEST_DOMAIN || digest`)
+    fn sign_digest(&self, digest: &MessageDigest) -> Result<Vec<u8>> {
+        let sk = self.parsed_secret_key()?;
+        Ok(Self::sign_with(sk, &prehash::envelope(digest)))
+    }
+
+    /// Verify a digest signature
+    fn verify_digest(&self, digest: &MessageDigest, signature: &[u8]) -> Result<bool> {
+        self.verify_message(&prehash::envelope(digest), signature)
+    }
+
     /// Get public key bytes
     ///
     /// # Returns
@@ -352,6 +549,11 @@ impl Signer for Dilithium3Signer {
     fn algorithm_name(&self) -> &str {
         "Dilithium3"
     }
+
+    /// Detached signature size (3,293 bytes)
+    fn signature_bytes(&self) -> usize {
+        dilithium3::signature_bytes()
+    }
 }
 
 /// Algorithm information structure
@@ -400,18 +602,138 @@ mod tests {
         assert!(!is_invalid);
     }
 
+    #[test]
+    fn test_verify_strict_reports_reason() {
+        let mut signer = Dilithium3Signer::new();
+        signer.generate_keypair().unwrap();
+        let message = b"audit report";
+        let signature = signer.sign(message).unwrap();
+
+        assert!(signer.verify_strict(message, &signature).is_ok());
+        assert!(matches!(
+            signer.verify_strict(b"Tampered message", &signature),
+            Err(PqcError::VerificationFailed)
+        ));
+        assert!(matches!(
+            signer.verify_strict(message, &signature[..10]),
+            Err(PqcError::InvalidSignatureLength { got: 10, .. })
+        ));
+        assert!(matches!(
+            Dilithium3Signer::new().verify_strict(message, &signature),
+            Err(PqcError::KeyNotInitialized(KeyKind::Public))
+        ));
+    }
+
+    #[test]
+    fn test_verify_rejects_wrong_signature_length() {
+        let mut signer = Dilithium3Signer::new();
+        signer.generate_keypair().unwrap();
+        let message = b"audit report";
+        let signature = signer.sign(message).unwrap();
+        assert_eq!(signer.signature_bytes(), signature.len());
+
+        let truncated = &signature[..signature.len() - 1];
+        let mut extended = signature.clone();
+        extended.extend_from_slice(b"audit");
+
+        for bad in [truncated, &extended[..], &[][..]] {
+            for result in [
+                signer.verify_detached(message, bad),
+                signer.verify(message, bad),
+            ] {
+                match result {
+                    Err(PqcError::InvalidSignatureLength { expected, got }) => {
+                        assert_eq!(expected, dilithium3::signature_bytes());
+                        assert_eq!(got, bad.len());
+                    }
+                    other => panic!("expected InvalidSignatureLength, got {:?}", other),
+                }
+            }
+        }
+
+        assert!(signer.verify_detached(message, &signature).unwrap());
+    }
+
+    #[test]
+    fn test_sign_batch_signatures_verify_individually() {
+        let mut signer = Dilithium3Signer::new();
+        signer.generate_keypair().unwrap();
+
+        let messages: Vec<Vec<u8>> = (0..5)
+            .map(|i| format!("report {}", i).into_bytes())
+            .collect();
+        let refs: Vec<&[u8]> = messages.iter().map(Vec::as_slice).collect();
+        let signatures = signer.sign_batch(&refs).unwrap();
+        assert_eq!(signatures.len(), messages.len());
+
+        // A fresh signer (no cached keys) must accept every signature
+        let verifier = Dilithium3Signer::from_bytes(signer.public_key(), signer.secret_key())
+            .unwrap();
+        for (message, signature) in messages.iter().zip(&signatures) {
+            assert!(verifier.verify(message, signature).unwrap());
+        }
+        assert!(!verifier.verify(&messages[1], &signatures[0]).unwrap());
+
+        assert!(signer.sign_batch(&[]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_sign_batch_without_keypair() {
+        let signer = Dilithium3Signer::new();
+        assert!(matches!(
+            signer.sign_batch(&[b"message"]),
+            Err(PqcError::KeyNotInitialized(KeyKind::Secret))
+        ));
+    }
+
+    #[test]
+    fn test_cached_keys_follow_regenerated_keypair() {
+        let mut signer = Dilithium3Signer::new();
+        signer.generate_keypair().unwrap();
+        let message = b"audit report";
+        let first = signer.sign(message).unwrap();
+
+        signer.generate_keypair().unwrap();
+        let second = signer.sign(message).unwrap();
+
+        assert!(signer.verify(message, &second).unwrap());
+        assert!(!signer.verify(message, &first).unwrap());
+    }
+
     #[test]
     fn test_sign_without_keypair() {
         let signer = Dilithium3Signer::new();
         let result = signer.sign(b"test message");
 
         assert!(result.is_err());
-        match result {
-            Err(PqcError::SigningError(msg)) => {
-                assert!(msg.contains("not initialized"));
-            }
-            _ => panic!("Expected SigningError"),
-        }
+        assert!(matches!(
+            result,
+            Err(PqcError::KeyNotInitialized(KeyKind::Secret))
+        ));
+    }
+
+    #[test]
+    fn test_zeroize_wipes_secret_key() {
+        let mut signer = Dilithium3Signer::new();
+        signer.generate_keypair().unwrap();
+        let copy = signer.clone();
+        // Fill the parsed key cache as well
+        signer.sign(b"audit report").unwrap();
+
+        // `zeroize` (also run on drop) clears the buffer but keeps its allocation, so the
+        // snapshot stays readable while `signer` is alive; reading it after drop would be UB
+        let (ptr, len) = (signer.secret_key().as_ptr(), signer.secret_key().len());
+        let snapshot = || unsafe { std::slice::from_raw_parts(ptr, len) }.to_vec();
+        assert!(snapshot().iter().any(|&b| b != 0));
+        signer.zeroize();
+        assert!(snapshot().iter().all(|&b| b == 0));
+
+        // Verification-only from now on; the clone is an independent copy
+        assert!(matches!(
+            signer.sign(b"audit report"),
+            Err(PqcError::KeyNotInitialized(KeyKind::Secret))
+        ));
+        assert!(copy.sign(b"audit report").is_ok());
     }
 
     #[test]
@@ -446,6 +768,36 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_from_bytes_rejects_mismatched_keypair() {
+        let mut signer_a = Dilithium3Signer::new();
+        signer_a.generate_keypair().unwrap();
+        let mut signer_b = Dilithium3Signer::new();
+        signer_b.generate_keypair().unwrap();
+
+        let err = Dilithium3Signer::from_bytes(signer_a.public_key(), signer_b.secret_key())
+            .err()
+            .unwrap();
+        assert!(matches!(err, PqcError::KeyPairMismatch));
+        assert_eq!(err.code(), "key_pair_mismatch");
+
+        // Unchecked restore accepts the pair, but its signatures do not verify
+        let mixed =
+            Dilithium3Signer::from_bytes_unchecked(signer_a.public_key(), signer_b.secret_key())
+                .unwrap();
+        let signature = mixed.sign(b"report").unwrap();
+        assert!(!mixed.verify(b"report", &signature).unwrap());
+
+        // Empty secret key: verification-only, no probe
+        let verifier = Dilithium3Signer::from_bytes(signer_a.public_key(), &[]).unwrap();
+        let signature = signer_a.sign(b"report").unwrap();
+        assert!(verifier.verify(b"report", &signature).unwrap());
+        assert!(matches!(
+            verifier.sign(b"report"),
+            Err(PqcError::KeyNotInitialized(KeyKind::Secret))
+        ));
+    }
+
     #[test]
     fn test_signature_size() {
         let mut signer = Dilithium3Signer::new();
@@ -512,12 +864,10 @@ mod tests {
         let result = verifier.sign(b"test message");
         assert!(result.is_err(), "Verification-only signer should not sign");
 
-        match result {
-            Err(PqcError::SigningError(msg)) => {
-                assert!(msg.contains("not initialized"));
-            }
-            _ => panic!("Expected SigningError"),
-        }
+        assert!(matches!(
+            result,
+            Err(PqcError::KeyNotInitialized(KeyKind::Secret))
+        ));
     }
 
     #[test]
@@ -528,10 +878,12 @@ mod tests {
 
         assert!(result.is_err());
         match result {
-            Err(PqcError::KeyGenerationError(msg)) => {
-                assert!(msg.contains("Invalid public key length"));
-            }
-            _ => panic!("Expected KeyGenerationError for invalid length"),
+            Err(PqcError::InvalidKeyLength {
+                key: KeyKind::Public,
+                expected,
+                got: 100,
+            }) => assert_eq!(expected, dilithium3::public_key_bytes()),
+            _ => panic!("Expected InvalidKeyLength for invalid length"),
         }
     }
 
@@ -544,8 +896,8 @@ mod tests {
         // This should fail because all zeros is not a valid Dilithium public key
         // Note: pqcrypto-dilithium may not validate this, depends on implementation
         // If test fails, it means the library doesn't validate public key format
-        if let Err(PqcError::KeyGenerationError(msg)) = result {
-            assert!(msg.contains("Invalid public key format"));
+        if let Err(err) = result {
+            assert!(matches!(err, PqcError::InvalidKeyFormat(KeyKind::Public)));
         } else {
             // If library accepts all-zero public key, at least verify creation succeeded
             // This is a known limitation
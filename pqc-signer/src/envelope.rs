@@ -0,0 +1,103 @@
+//! Self-contained signed payloads
+//!
+//! A verifier that cannot rebuild the signed bytes itself (e.g. a browser dashboard that does
+//! not implement the audit report's canonical encoding) needs those bytes shipped alongside the
+//! signature. [`SignedPayload`] is that JSON document:
+//!
+//! ```json
+//! {
+//!   "algorithm": "Dilithium3",
+//!   "payload": "<Base64 of the signed bytes>",
+//!   "signature": "<Base64 of the detached signature>",
+//!   "prehashed": false
+//! }
+//! ```
+//!
+//! With `prehashed` the signature was made by [`crate::traits::Signer::sign_digest`] over the
+//! digest of `payload` (see [`crate::prehash`]). The document carries no key: verify it against
+//! a public key obtained out of band.
+
+use crate::error::{PqcError, Result};
+use serde::{Deserialize, Serialize};
+
+/// Signed bytes together with their detached signature
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignedPayload {
+    /// Signature algorithm name (as returned by `Signer::algorithm_name`)
+    pub algorithm: String,
+
+    /// Exact bytes that were signed
+    #[serde(with = "base64_bytes")]
+    pub payload: Vec<u8>,
+
+    /// Detached signature
+    #[serde(with = "base64_bytes")]
+    pub signature: Vec<u8>,
+
+    /// Whether the signature covers the digest of `payload` instead of the payload itself
+    #[serde(default)]
+    pub prehashed: bool,
+}
+
+impl SignedPayload {
+    /// Parse a signed payload document
+    ///
+    /// # Errors
+    /// - Returns `InvalidEnvelope` if the JSON or its Base64 fields are malformed
+    pub fn from_json(json: &str) -> Result<Self> {
+        serde_json::from_str(json).map_err(|e| PqcError::InvalidEnvelope(e.to_string()))
+    }
+
+    /// Serialize as a signed payload document
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("SignedPayload serializes to JSON")
+    }
+}
+
+/// Base64 (standard alphabet, padded) encoding for byte fields
+mod base64_bytes {
+    use base64::{engine::general_purpose, Engine as _};
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&general_purpose::STANDARD.encode(bytes))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+        let encoded = String::deserialize(deserializer)?;
+        general_purpose::STANDARD
+            .decode(encoded)
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_roundtrip() {
+        let signed = SignedPayload {
+            algorithm: "Dilithium3".to_string(),
+            payload: b"report bytes".to_vec(),
+            signature: vec![0xab; 4],
+            prehashed: true,
+        };
+        let json = signed.to_json();
+        assert_eq!(
+            json,
+            r#"{"algorithm":"Dilithium3","payload":"cmVwb3J0IGJ5dGVz","signature":"q6urqw==","prehashed":true}"#
+        );
+        assert_eq!(SignedPayload::from_json(&json).unwrap(), signed);
+    }
+
+    #[test]
+    fn test_malformed_document_is_rejected() {
+        let err = SignedPayload::from_json(
+            r#"{"algorithm":"Dilithium3","payload":"not base64!","signature":""}"#,
+        )
+        .unwrap_err();
+        assert_eq!(err.code(), "invalid_envelope");
+        assert!(SignedPayload::from_json("{}").is_err());
+    }
+}
@@ -1,25 +1,118 @@
 /// Error type definitions
+use std::fmt;
 use thiserror::Error;
 
+/// Which half of a keypair an error refers to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyKind {
+    /// Public (verification) key
+    Public,
+    /// Secret (signing) key
+    Secret,
+}
+
+impl fmt::Display for KeyKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            KeyKind::Public => "public",
+            KeyKind::Secret => "secret",
+        })
+    }
+}
+
 #[derive(Error, Debug)]
 pub enum PqcError {
-    #[error("Signing failed: {0}")]
-    SigningError(String),
+    /// The operation needs a key that has not been generated or loaded
+    #[error("Key not initialized: no {0} key. Call generate_keypair() first.")]
+    KeyNotInitialized(KeyKind),
+
+    /// Key bytes have the wrong length for the algorithm
+    #[error("Invalid {key} key length: expected {expected} bytes, got {got}")]
+    InvalidKeyLength {
+        key: KeyKind,
+        expected: usize,
+        got: usize,
+    },
+
+    /// Key bytes have the right length but cannot be parsed
+    #[error("Invalid {0} key format (failed to deserialize)")]
+    InvalidKeyFormat(KeyKind),
+
+    /// Signature bytes have the wrong length for the algorithm
+    ///
+    /// For variable-length signatures (Falcon-512) `expected` is the maximum.
+    #[error("Invalid signature length: expected {expected} bytes, got {got}")]
+    InvalidSignatureLength { expected: usize, got: usize },
+
+    /// Signature bytes cannot be parsed as a signature of the algorithm
+    #[error("Malformed signature")]
+    MalformedSignature,
 
-    #[error("Verification failed: {0}")]
-    VerificationError(String),
+    /// Well-formed signature that does not match the message and public key
+    #[error("Verification failed: signature does not match")]
+    VerificationFailed,
 
-    #[error("Key generation failed: {0}")]
-    KeyGenerationError(String),
+    /// Public and secret key are well-formed but do not belong to the same keypair
+    #[error("Key pair mismatch: secret key does not match public key")]
+    KeyPairMismatch,
 
-    #[error("Encoding error: {0}")]
-    EncodingError(String),
+    /// Raw message has the form of a digest signature envelope (see [`crate::prehash`])
+    #[error("Message is reserved for digest signatures; use sign_digest/verify_digest")]
+    ReservedMessage,
 
-    #[error("Unsupported algorithm: {0}")]
-    UnsupportedAlgorithm(String),
+    /// Signed payload document cannot be parsed or names an unsupported algorithm
+    /// (see [`crate::envelope`])
+    #[error("Invalid signed payload: {0}")]
+    InvalidEnvelope(String),
+
+    /// Any other failure reported by the underlying implementation
+    #[error("PQC backend error: {0}")]
+    Backend(String),
 
     #[error("IO error: {0}")]
     IoError(#[from] std::io::Error),
 }
 
+impl PqcError {
+    /// Stable identifier of the error kind (for logs and metric labels)
+    pub fn code(&self) -> &'static str {
+        match self {
+            PqcError::KeyNotInitialized(_) => "key_not_initialized",
+            PqcError::InvalidKeyLength { .. } => "invalid_key_length",
+            PqcError::InvalidKeyFormat(_) => "invalid_key_format",
+            PqcError::InvalidSignatureLength { .. } => "invalid_signature_length",
+            PqcError::MalformedSignature => "malformed_signature",
+            PqcError::VerificationFailed => "verification_failed",
+            PqcError::KeyPairMismatch => "key_pair_mismatch",
+            PqcError::ReservedMessage => "reserved_message",
+            PqcError::InvalidEnvelope(_) => "invalid_envelope",
+            PqcError::Backend(_) => "backend",
+            PqcError::IoError(_) => "io",
+        }
+    }
+}
+
 pub type Result<T> = std::result::Result<T, PqcError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display_keeps_detail() {
+        let err = PqcError::InvalidKeyLength {
+            key: KeyKind::Public,
+            expected: 1952,
+            got: 100,
+        };
+        assert_eq!(
+            err.to_string(),
+            "Invalid public key length: expected 1952 bytes, got 100"
+        );
+        assert_eq!(err.code(), "invalid_key_length");
+
+        let err = PqcError::KeyNotInitialized(KeyKind::Secret);
+        assert!(err.to_string().contains("no secret key"));
+        assert_eq!(err.code(), "key_not_initialized");
+    }
+}
@@ -1,46 +1,308 @@
-/// Falcon-512 signature implementation
-use crate::error::{PqcError, Result};
+//! Falcon-512 post-quantum digital signature implementation
+//!
+//! # About Falcon-512
+//!
+//! Falcon is the second lattice-based signature scheme selected in the NIST post-quantum
+//! cryptography standardization competition (FN-DSA, FIPS 206 draft).
+//!
+//! ## Security
+//! - **NIST Security Level**: Level 1 (equivalent to AES-128)
+//! - **Mathematical Foundation**: NTRU lattices (short integer solution over NTRU rings)
+//!
+//! ## Size Comparison
+//! | Algorithm | Public Key Size | Signature Size |
+//! |-----------|----------------|----------------|
+//! | **Falcon-512** | **897 bytes** | **~690 bytes (variable)** |
+//! | Dilithium3 | 1,952 bytes | ~3,293 bytes |
+//!
+//! ## When to Use Falcon-512
+//! Dilithium3 remains the default. Falcon-512 trades a lower security level for
+//! signatures roughly 5x smaller, which matters when the storage cost of audit
+//! reports on Walrus dominates.
+//!
+//! Falcon signatures are variable-length, so `sign()` returns the detached
+//! signature as produced by the library instead of slicing a `SignedMessage`.
+
+use crate::dilithium::AlgorithmInfo;
+use crate::error::{KeyKind, PqcError, Result};
+use crate::prehash::{self, MessageDigest};
 use crate::traits::Signer;
+use pqcrypto_falcon::falcon512;
+use pqcrypto_traits::sign::{DetachedSignature, PublicKey, SecretKey};
+use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
 
-pub struct FalconSigner {
+/// Falcon-512 signer
+///
+/// # Example
+///
+/// ```rust
+/// use pqc_signer::falcon::Falcon512Signer;
+/// use pqc_signer::traits::Signer;
+///
+/// let mut signer = Falcon512Signer::new();
+/// signer.generate_keypair().unwrap();
+///
+/// let message = b"Audit report: blob_id=0x1234, success_rate=98%";
+/// let signature = signer.sign(message).unwrap();
+///
+/// assert!(signer.verify(message, &signature).unwrap());
+/// ```
+///
+/// The secret key is zeroized on drop; clones hold their own copy, likewise zeroized
+/// (see [`crate::dilithium::Dilithium3Signer`]).
+#[derive(Clone)]
+pub struct Falcon512Signer {
     public_key: Vec<u8>,
-    secret_key: Vec<u8>,
+    secret_key: Zeroizing<Vec<u8>>,
 }
 
-impl FalconSigner {
+impl Falcon512Signer {
+    /// Create new Falcon-512 signer (keys not initialized)
+    ///
+    /// Must call `generate_keypair()` or `from_bytes()` to initialize keys
     pub fn new() -> Self {
         Self {
             public_key: Vec::new(),
-            secret_key: Vec::new(),
+            secret_key: Zeroizing::new(Vec::new()),
+        }
+    }
+
+    /// Restore keypair from bytes
+    ///
+    /// # Parameters
+    /// - `public_key`: Public key bytes (897 bytes)
+    /// - `secret_key`: Secret key bytes (1281 bytes)
+    ///
+    /// # Errors
+    /// - Returns `InvalidKeyLength` if key length is incorrect (e.g. a Dilithium3 key)
+    pub fn from_bytes(public_key: &[u8], secret_key: &[u8]) -> Result<Self> {
+        check_public_key_length(public_key)?;
+
+        if secret_key.len() != falcon512::secret_key_bytes() {
+            return Err(PqcError::InvalidKeyLength {
+                key: KeyKind::Secret,
+                expected: falcon512::secret_key_bytes(),
+                got: secret_key.len(),
+            });
+        }
+
+        Ok(Self {
+            public_key: public_key.to_vec(),
+            secret_key: Zeroizing::new(secret_key.to_vec()),
+        })
+    }
+
+    /// Create verification-only Signer from public key (no signing capability)
+    ///
+    /// # Errors
+    /// - Returns `InvalidKeyLength` if public key length is incorrect
+    /// - Returns `InvalidKeyFormat` if public key format is invalid (deserialization fails)
+    pub fn from_public_key_only(public_key: &[u8]) -> Result<Self> {
+        check_public_key_length(public_key)?;
+
+        falcon512::PublicKey::from_bytes(public_key)
+            .map_err(|_| PqcError::InvalidKeyFormat(KeyKind::Public))?;
+
+        debug!(
+            "Created verification-only Falcon512Signer: pk_len={} bytes (sk=empty)",
+            public_key.len()
+        );
+
+        Ok(Self {
+            public_key: public_key.to_vec(),
+            secret_key: Zeroizing::new(Vec::new()),
+        })
+    }
+
+    /// Get secret key bytes (for persistence)
+    ///
+    /// # Security Warning
+    /// Private keys should be stored securely, not transmitted over network or logged
+    pub fn secret_key(&self) -> &[u8] {
+        &self.secret_key
+    }
+
+    /// Parse the stored public key
+    fn parsed_public_key(&self) -> Result<falcon512::PublicKey> {
+        if self.public_key.is_empty() {
+            return Err(PqcError::KeyNotInitialized(KeyKind::Public));
+        }
+
+        falcon512::PublicKey::from_bytes(&self.public_key)
+            .map_err(|_| PqcError::InvalidKeyFormat(KeyKind::Public))
+    }
+
+    /// Sign the exact bytes given (raw message or digest envelope)
+    fn sign_message(&self, message: &[u8]) -> Result<Vec<u8>> {
+        if self.secret_key.is_empty() {
+            return Err(PqcError::KeyNotInitialized(KeyKind::Secret));
+        }
+
+        let mut sk = falcon512::SecretKey::from_bytes(&self.secret_key)
+            .map_err(|_| PqcError::InvalidKeyFormat(KeyKind::Secret))?;
+
+        let signature = falcon512::detached_sign(message, &sk);
+        zeroize_parsed_secret_key(&mut sk);
+
+        debug!(
+            "Signed message: msg_len={} bytes, detached_sig_len={} bytes",
+            message.len(),
+            signature.as_bytes().len()
+        );
+
+        Ok(signature.as_bytes().to_vec())
+    }
+
+    /// Verify a detached signature over the exact bytes signed
+    fn verify_message(&self, message: &[u8], signature: &[u8]) -> Result<bool> {
+        let pk = self.parsed_public_key()?;
+
+        let signature = match falcon512::DetachedSignature::from_bytes(signature) {
+            Ok(signature) => signature,
+            Err(e) => {
+                warn!("Malformed Falcon-512 signature: {:?}", e);
+                return Ok(false);
+            }
+        };
+
+        match falcon512::verify_detached_signature(&signature, message, &pk) {
+            Ok(()) => Ok(true),
+            Err(_) => {
+                warn!("Falcon-512 signature verification failed");
+                Ok(false)
+            }
+        }
+    }
+
+    /// Return algorithm information
+    ///
+    /// `signature_size` is the maximum; actual signatures are usually shorter
+    pub fn algorithm_info() -> AlgorithmInfo {
+        AlgorithmInfo {
+            name: "Falcon512",
+            nist_level: 1,
+            public_key_size: falcon512::public_key_bytes(),
+            secret_key_size: falcon512::secret_key_bytes(),
+            signature_size: falcon512::signature_bytes(),
         }
     }
 }
 
-impl Default for FalconSigner {
+fn check_public_key_length(public_key: &[u8]) -> Result<()> {
+    if public_key.len() != falcon512::public_key_bytes() {
+        return Err(PqcError::InvalidKeyLength {
+            key: KeyKind::Public,
+            expected: falcon512::public_key_bytes(),
+            got: public_key.len(),
+        });
+    }
+    Ok(())
+}
+
+impl Default for Falcon512Signer {
     fn default() -> Self {
         Self::new()
     }
 }
 
-impl Signer for FalconSigner {
+impl Zeroize for Falcon512Signer {
+    /// Zeroize the secret key, leaving a verification-only signer
+    fn zeroize(&mut self) {
+        self.secret_key.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for Falcon512Signer {}
+
+/// Overwrite a parsed secret key in place (pqcrypto offers no zeroizing API)
+fn zeroize_parsed_secret_key(sk: &mut falcon512::SecretKey) {
+    // SAFETY: `SecretKey` is a newtype over a byte array: every byte is initialized,
+    // and all-zero bytes are a valid value. The slice covers exactly the borrowed key.
+    let bytes = unsafe {
+        std::slice::from_raw_parts_mut(
+            (sk as *mut falcon512::SecretKey).cast::<u8>(),
+            std::mem::size_of::<falcon512::SecretKey>(),
+        )
+    };
+    bytes.zeroize();
+}
+
+impl Signer for Falcon512Signer {
+    /// Generate new Falcon-512 keypair
     fn generate_keypair(&mut self) -> Result<()> {
-        // TODO: Implement Falcon-512 key generation
-        // Use pqcrypto-falcon crate
-        Err(PqcError::KeyGenerationError(
-            "Falcon key generation not yet implemented".to_string(),
-        ))
+        let (pk, sk) = falcon512::keypair();
+
+        self.public_key = pk.as_bytes().to_vec();
+        self.secret_key = Zeroizing::new(sk.as_bytes().to_vec());
+
+        info!(
+            "Generated Falcon-512 keypair: pk_len={} bytes, sk_len={} bytes",
+            self.public_key.len(),
+            self.secret_key.len()
+        );
+
+        Ok(())
+    }
+
+    /// Sign message with Falcon-512
+    ///
+    /// # Returns
+    /// - Detached signature bytes (variable length, at most `signature_bytes()`)
+    ///
+    /// # Errors
+    /// - Returns `KeyNotInitialized` if keys not initialized
+    /// - Returns `InvalidKeyFormat` if the secret key is malformed
+    /// - Returns `ReservedMessage` if the message has the form of a digest envelope
+    fn sign(&self, message: &[u8]) -> Result<Vec<u8>> {
+        prehash::check_raw_message(message)?;
+        self.sign_message(message)
     }
 
-    fn sign(&self, _message: &[u8]) -> Result<Vec<u8>> {
-        // TODO: Implement Falcon-512 signing
-        Err(PqcError::SigningError("Falcon signing not yet implemented".to_string()))
+    /// Verify Falcon-512 detached signature
+    ///
+    /// # Returns
+    /// - `Ok(true)`: Signature is valid
+    /// - `Ok(false)`: Signature is invalid (including malformed signature bytes)
+    /// - `Err`: Public key missing or unparseable, or a message with the form of a digest
+    ///   envelope (`ReservedMessage`)
+    fn verify(&self, message: &[u8], signature: &[u8]) -> Result<bool> {
+        prehash::check_raw_message(message)?;
+        self.verify_message(message, signature)
     }
 
-    fn verify(&self, _message: &[u8], _signature: &[u8]) -> Result<bool> {
-        // TODO: Implement Falcon-512 verification
-        Err(PqcError::VerificationError(
-            "Falcon verification not yet implemented".to_string(),
-        ))
+    /// Sign a message digest (signs `DIGEST_DOMAIN || digest`)
+    fn sign_digest(&self, digest: &MessageDigest) -> Result<Vec<u8>> {
+        self.sign_message(&prehash::envelope(digest))
+    }
+
+    /// Verify a digest signature
+    fn verify_digest(&self, digest: &MessageDigest, signature: &[u8]) -> Result<bool> {
+        self.verify_message(&prehash::envelope(digest), signature)
+    }
+
+    /// Verify Falcon-512 detached signature, reporting malformed signature bytes as errors
+    ///
+    /// # Errors
+    /// - Returns `InvalidSignatureLength` if the signature is empty or longer than
+    ///   `signature_bytes()`
+    /// - Returns `MalformedSignature` if the signature bytes cannot be parsed
+    /// - Returns `VerificationFailed` if the signature does not match
+    fn verify_strict(&self, message: &[u8], signature: &[u8]) -> Result<()> {
+        prehash::check_raw_message(message)?;
+        let pk = self.parsed_public_key()?;
+
+        if signature.is_empty() || signature.len() > falcon512::signature_bytes() {
+            return Err(PqcError::InvalidSignatureLength {
+                expected: falcon512::signature_bytes(),
+                got: signature.len(),
+            });
+        }
+
+        let signature = falcon512::DetachedSignature::from_bytes(signature)
+            .map_err(|_| PqcError::MalformedSignature)?;
+
+        falcon512::verify_detached_signature(&signature, message, &pk)
+            .map_err(|_| PqcError::VerificationFailed)
     }
 
     fn public_key(&self) -> &[u8] {
@@ -48,6 +310,139 @@ impl Signer for FalconSigner {
     }
 
     fn algorithm_name(&self) -> &str {
-        "Falcon-512"
+        "Falcon512"
+    }
+
+    /// Maximum detached signature size (Falcon-512 signatures are variable-length)
+    fn signature_bytes(&self) -> usize {
+        falcon512::signature_bytes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dilithium::Dilithium3Signer;
+
+    #[test]
+    fn test_keypair_generation() {
+        let mut signer = Falcon512Signer::new();
+        signer.generate_keypair().unwrap();
+
+        assert_eq!(signer.public_key().len(), falcon512::public_key_bytes());
+        assert_eq!(signer.secret_key().len(), falcon512::secret_key_bytes());
+    }
+
+    #[test]
+    fn test_sign_and_verify() {
+        let mut signer = Falcon512Signer::new();
+        signer.generate_keypair().unwrap();
+
+        let message = b"Test audit report: blob_id=0xABCD, success_rate=98%";
+        let signature = signer.sign(message).unwrap();
+
+        assert!(signature.len() <= falcon512::signature_bytes());
+        assert!(signer.verify(message, &signature).unwrap());
+        assert!(!signer.verify(b"Tampered message", &signature).unwrap());
+        assert!(!signer.verify(message, &signature[..10]).unwrap());
+    }
+
+    #[test]
+    fn test_verify_strict_reports_reason() {
+        let mut signer = Falcon512Signer::new();
+        signer.generate_keypair().unwrap();
+        let message = b"audit report";
+        let signature = signer.sign(message).unwrap();
+
+        assert!(signer.verify_strict(message, &signature).is_ok());
+        assert!(matches!(
+            signer.verify_strict(b"Tampered message", &signature),
+            Err(PqcError::VerificationFailed)
+        ));
+        assert!(matches!(
+            signer.verify_strict(message, &[]),
+            Err(PqcError::InvalidSignatureLength { got: 0, .. })
+        ));
+        assert!(matches!(
+            signer.verify_strict(message, &vec![0u8; falcon512::signature_bytes() + 1]),
+            Err(PqcError::InvalidSignatureLength { .. })
+        ));
+    }
+
+    #[test]
+    fn test_sign_without_keypair() {
+        let signer = Falcon512Signer::new();
+
+        assert!(matches!(
+            signer.sign(b"test message"),
+            Err(PqcError::KeyNotInitialized(KeyKind::Secret))
+        ));
+    }
+
+    #[test]
+    fn test_from_bytes_roundtrip() {
+        let mut original = Falcon512Signer::new();
+        original.generate_keypair().unwrap();
+
+        let restored =
+            Falcon512Signer::from_bytes(original.public_key(), original.secret_key()).unwrap();
+        assert_eq!(restored.public_key(), original.public_key());
+
+        let message = b"test message";
+        let signature = restored.sign(message).unwrap();
+        assert!(original.verify(message, &signature).unwrap());
+    }
+
+    #[test]
+    fn test_dilithium_keys_rejected() {
+        let mut dilithium = Dilithium3Signer::new();
+        dilithium.generate_keypair().unwrap();
+
+        match Falcon512Signer::from_bytes(dilithium.public_key(), dilithium.secret_key()) {
+            Err(PqcError::InvalidKeyLength {
+                key: KeyKind::Public,
+                expected,
+                got,
+            }) => {
+                assert_eq!(expected, falcon512::public_key_bytes());
+                assert_eq!(got, dilithium.public_key().len());
+            }
+            _ => panic!("Expected InvalidKeyLength for Dilithium3 keys"),
+        }
+
+        assert!(matches!(
+            Falcon512Signer::from_public_key_only(dilithium.public_key()),
+            Err(PqcError::InvalidKeyLength {
+                key: KeyKind::Public,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_from_public_key_only_cannot_sign() {
+        let mut signer = Falcon512Signer::new();
+        signer.generate_keypair().unwrap();
+        let message = b"Test message for verification-only signer";
+        let signature = signer.sign(message).unwrap();
+
+        let verifier = Falcon512Signer::from_public_key_only(signer.public_key()).unwrap();
+        assert!(verifier.secret_key().is_empty());
+        assert!(verifier.verify(message, &signature).unwrap());
+        assert!(matches!(
+            verifier.sign(message),
+            Err(PqcError::KeyNotInitialized(KeyKind::Secret))
+        ));
+    }
+
+    #[test]
+    fn test_algorithm_info() {
+        let info = Falcon512Signer::algorithm_info();
+
+        assert_eq!(info.name, "Falcon512");
+        assert_eq!(info.nist_level, 1);
+        assert_eq!(info.public_key_size, 897);
+        assert_eq!(info.secret_key_size, 1281);
+        assert!(info.signature_size < Dilithium3Signer::algorithm_info().signature_size);
     }
 }
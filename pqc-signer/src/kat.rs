@@ -0,0 +1,265 @@
+//! Known-answer test (KAT) vectors for Dilithium3 detached signatures
+//!
+//! [`Dilithium3Signer::sign`](crate::traits::Signer::sign) keeps the first `signature_bytes()`
+//! bytes of the PQClean signed message, and verification reassembles `signature || message`.
+//! Fixed vectors pin that construction: a signature produced elsewhere for the same key and
+//! message must verify here, and signing with a fixed secret key must reproduce the committed
+//! signature byte for byte (Dilithium3 signing is deterministic).
+//!
+//! The committed fixture (`tests/fixtures/dilithium3_kat.json`) is a [`KatFile`]:
+//!
+//! ```json
+//! {
+//!   "algorithm": "Dilithium3",
+//!   "pqcrypto_dilithium": "0.5.0",
+//!   "pqc_signer": "0.1.0",
+//!   "vectors": [
+//!     { "public_key": "<hex>", "secret_key": "<hex>", "message": "<hex>", "signature": "<hex>" }
+//!   ],
+//!   "negative": [
+//!     { "kind": "bit_flipped_signature", "source": 0, "public_key": "<hex>", "message": "<hex>", "signature": "<hex>" }
+//!   ]
+//! }
+//! ```
+//!
+//! Negative vectors are derived from the positive ones ([`NegativeVector::derive`]) and must be
+//! rejected. After bumping `pqcrypto-dilithium`, regenerate the fixture with
+//!
+//! ```text
+//! cargo run -p pqc-signer --example gen_dilithium3_kat
+//! ```
+//!
+//! and review the diff: a changed signature for an unchanged key and message means the
+//! dependency changed behavior.
+
+use crate::dilithium::Dilithium3Signer;
+use crate::error::{PqcError, Result};
+use serde::{Deserialize, Serialize};
+
+/// `pqcrypto-dilithium` version the signer is built against (keep in sync with Cargo.toml)
+pub const PQCRYPTO_DILITHIUM_VERSION: &str = "0.5.0";
+
+/// Fixture path relative to the `pqc-signer` crate root
+pub const DILITHIUM3_KAT_FIXTURE: &str = "tests/fixtures/dilithium3_kat.json";
+
+/// A keypair, message and the signature it must produce
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TestVector {
+    /// Public key
+    #[serde(with = "hex_bytes")]
+    pub public_key: Vec<u8>,
+
+    /// Secret key
+    #[serde(with = "hex_bytes")]
+    pub secret_key: Vec<u8>,
+
+    /// Signed message
+    #[serde(with = "hex_bytes")]
+    pub message: Vec<u8>,
+
+    /// Detached signature of `message` under `secret_key`
+    #[serde(with = "hex_bytes")]
+    pub signature: Vec<u8>,
+}
+
+impl TestVector {
+    /// Signer holding the vector's keypair
+    pub fn signer(&self) -> Result<Dilithium3Signer> {
+        Dilithium3Signer::from_bytes(&self.public_key, &self.secret_key)
+    }
+}
+
+/// How a negative vector was derived from a positive one
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NegativeKind {
+    /// One bit of the signature flipped
+    BitFlippedSignature,
+    /// One bit of the message flipped
+    BitFlippedMessage,
+    /// Signature checked against another vector's public key
+    SwappedKeys,
+}
+
+/// A public key, message and signature that must not verify
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NegativeVector {
+    /// Derivation
+    pub kind: NegativeKind,
+
+    /// Index of the positive vector it was derived from
+    pub source: usize,
+
+    /// Public key to verify against
+    #[serde(with = "hex_bytes")]
+    pub public_key: Vec<u8>,
+
+    /// Message
+    #[serde(with = "hex_bytes")]
+    pub message: Vec<u8>,
+
+    /// Signature
+    #[serde(with = "hex_bytes")]
+    pub signature: Vec<u8>,
+}
+
+impl NegativeVector {
+    /// Derive the negative vectors of `vectors`
+    ///
+    /// Every vector yields a bit-flipped signature; non-empty messages also yield a bit-flipped
+    /// message, and each signature is paired with the next vector's public key where that key
+    /// differs.
+    pub fn derive(vectors: &[TestVector]) -> Vec<NegativeVector> {
+        let mut negative = Vec::new();
+        for (source, vector) in vectors.iter().enumerate() {
+            let mut signature = vector.signature.clone();
+            let middle = signature.len() / 2;
+            signature[middle] ^= 0x01;
+            negative.push(NegativeVector {
+                kind: NegativeKind::BitFlippedSignature,
+                source,
+                public_key: vector.public_key.clone(),
+                message: vector.message.clone(),
+                signature,
+            });
+
+            if let Some(last) = vector.message.len().checked_sub(1) {
+                let mut message = vector.message.clone();
+                message[last] ^= 0x80;
+                negative.push(NegativeVector {
+                    kind: NegativeKind::BitFlippedMessage,
+                    source,
+                    public_key: vector.public_key.clone(),
+                    message,
+                    signature: vector.signature.clone(),
+                });
+            }
+
+            let other = &vectors[(source + 1) % vectors.len()];
+            if other.public_key != vector.public_key {
+                negative.push(NegativeVector {
+                    kind: NegativeKind::SwappedKeys,
+                    source,
+                    public_key: other.public_key.clone(),
+                    message: vector.message.clone(),
+                    signature: vector.signature.clone(),
+                });
+            }
+        }
+        negative
+    }
+
+    /// Whether the signature is rejected (an error counts as a rejection)
+    pub fn is_rejected(&self) -> bool {
+        Dilithium3Signer::from_public_key_only(&self.public_key)
+            .and_then(|verifier| verifier.verify_detached(&self.message, &self.signature))
+            .map_or(true, |valid| !valid)
+    }
+}
+
+/// A KAT fixture file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KatFile {
+    /// Signature algorithm (`Dilithium3`)
+    pub algorithm: String,
+
+    /// `pqcrypto-dilithium` version that generated the vectors
+    pub pqcrypto_dilithium: String,
+
+    /// `pqc-signer` version that generated the vectors
+    pub pqc_signer: String,
+
+    /// Vectors that must verify and be reproduced by signing
+    pub vectors: Vec<TestVector>,
+
+    /// Vectors that must be rejected
+    pub negative: Vec<NegativeVector>,
+}
+
+impl KatFile {
+    /// Sign every message with every signer and derive the negative vectors
+    ///
+    /// # Errors
+    /// - Returns `KeyNotInitialized` if a signer has no secret key
+    pub fn generate(signers: &[Dilithium3Signer], messages: &[&[u8]]) -> Result<Self> {
+        let mut vectors = Vec::with_capacity(signers.len() * messages.len());
+        for signer in signers {
+            for message in messages {
+                vectors.push(signer.export_test_vector(message)?);
+            }
+        }
+
+        Ok(Self {
+            algorithm: "Dilithium3".to_string(),
+            pqcrypto_dilithium: PQCRYPTO_DILITHIUM_VERSION.to_string(),
+            pqc_signer: crate::VERSION.to_string(),
+            negative: NegativeVector::derive(&vectors),
+            vectors,
+        })
+    }
+
+    /// Parse a fixture file
+    ///
+    /// # Errors
+    /// - Returns `Backend` if the JSON or its hex fields are malformed
+    pub fn from_json(json: &str) -> Result<Self> {
+        serde_json::from_str(json)
+            .map_err(|e| PqcError::Backend(format!("Invalid KAT file: {}", e)))
+    }
+
+    /// Serialize as a fixture file (pretty-printed so diffs stay reviewable)
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("KatFile serializes to JSON")
+    }
+}
+
+/// Lowercase hex encoding for byte fields
+mod hex_bytes {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&hex::encode(bytes))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+        let encoded = String::deserialize(deserializer)?;
+        hex::decode(encoded).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::Signer;
+
+    fn signer() -> Dilithium3Signer {
+        let mut signer = Dilithium3Signer::new();
+        signer.generate_keypair().unwrap();
+        signer
+    }
+
+    #[test]
+    fn test_generated_vectors_roundtrip_and_reproduce() {
+        let signers = [signer(), signer()];
+        let kat = KatFile::generate(&signers, &[b"", b"audit report"]).unwrap();
+        assert_eq!(kat.vectors.len(), 4);
+        // 4 flipped signatures, 2 flipped messages, 2 swapped keys (only across signers)
+        assert_eq!(kat.negative.len(), 8);
+
+        let parsed = KatFile::from_json(&kat.to_json()).unwrap();
+        assert_eq!(parsed, kat);
+
+        for vector in &parsed.vectors {
+            let signer = vector.signer().unwrap();
+            assert!(signer.verify(&vector.message, &vector.signature).unwrap());
+            assert_eq!(signer.sign(&vector.message).unwrap(), vector.signature);
+        }
+        assert!(parsed.negative.iter().all(NegativeVector::is_rejected));
+    }
+
+    #[test]
+    fn test_malformed_kat_file_is_rejected() {
+        let err = KatFile::from_json(r#"{"algorithm":"Dilithium3"}"#).unwrap_err();
+        assert_eq!(err.code(), "backend");
+    }
+}
@@ -20,16 +20,43 @@
 //! let is_valid = signer.verify(message, &signature).unwrap();
 //! assert!(is_valid);
 //! ```
+//!
+//! # Features
+//!
+//! - `tracing` (default): log through `tracing`
+//! - `verify-only`: pure-Rust Dilithium3 verification (the `verify` module), the only signature API
+//!   on `wasm32`, where key generation and signing are not built
+
+#[macro_use]
+mod log;
 
+#[cfg(not(target_arch = "wasm32"))]
+pub mod dilithium;
+pub mod envelope;
 pub mod error;
+#[cfg(not(target_arch = "wasm32"))]
 pub mod falcon;
-pub mod dilithium;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod kat;
+pub mod prehash;
 pub mod traits;
+#[cfg(feature = "verify-only")]
+pub mod verify;
 
 // Re-export commonly used types
-pub use error::{PqcError, Result};
+#[cfg(not(target_arch = "wasm32"))]
 pub use dilithium::Dilithium3Signer;
+pub use envelope::SignedPayload;
+pub use error::{PqcError, Result};
+#[cfg(not(target_arch = "wasm32"))]
+pub use falcon::Falcon512Signer;
+pub use prehash::{DigestHasher, StreamingSigner};
 pub use traits::Signer;
+#[cfg(feature = "verify-only")]
+pub use verify::{verify_report_json, Dilithium3Verifier};
+
+/// Crate version (recorded in audit report producer metadata)
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
 
 #[cfg(test)]
 mod tests {
@@ -46,4 +73,16 @@ mod tests {
 
         assert!(is_valid);
     }
+
+    #[test]
+    fn test_falcon512_integration() {
+        let mut signer = Falcon512Signer::new();
+        signer.generate_keypair().unwrap();
+
+        let message = b"Integration test message";
+        let signature = signer.sign(message).unwrap();
+        let is_valid = signer.verify(message, &signature).unwrap();
+
+        assert!(is_valid);
+    }
 }
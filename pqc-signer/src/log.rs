@@ -0,0 +1,29 @@
+//! Logging shims
+//!
+//! `debug!`, `info!` and `warn!` forward to `tracing` when the `tracing` feature is enabled.
+//! Without it they compile to nothing, but the arguments are still type-checked, so values
+//! that are only logged do not trigger unused-variable warnings.
+
+// The wasm32 build does not use every level
+#![allow(unused_macros)]
+
+macro_rules! log_event {
+    ($level:ident, $($arg:tt)*) => {{
+        #[cfg(feature = "tracing")]
+        tracing::$level!($($arg)*);
+        #[cfg(not(feature = "tracing"))]
+        let _ = format_args!($($arg)*);
+    }};
+}
+
+macro_rules! debug {
+    ($($arg:tt)*) => { log_event!(debug, $($arg)*) };
+}
+
+macro_rules! info {
+    ($($arg:tt)*) => { log_event!(info, $($arg)*) };
+}
+
+macro_rules! warn {
+    ($($arg:tt)*) => { log_event!(warn, $($arg)*) };
+}
@@ -0,0 +1,250 @@
+//! Hash-then-sign support for large messages
+//!
+//! [`Signer::sign`] takes the whole message as one slice, and Dilithium3 additionally copies it
+//! into a `SignedMessage`, so signing a multi-hundred-MB payload holds it in memory twice.
+//! [`Signer::sign_digest`] signs a fixed-size digest of the message instead:
+//!
+//! ```text
+//! digest    = SHAKE-256(DIGEST_DOMAIN || message), 64 bytes
+//! signature = sign(DIGEST_DOMAIN || digest)
+//! ```
+//!
+//! [`DigestHasher`] computes the digest incrementally and [`StreamingSigner`] signs it, so the
+//! message never has to be assembled in memory.
+//!
+//! # Domain separation
+//!
+//! A digest signature is an ordinary signature over the *envelope* `DIGEST_DOMAIN || digest`.
+//! [`Signer::sign`] and [`Signer::verify`] reject messages that are envelopes
+//! (`ReservedMessage`), so the two kinds of signature cannot be confused:
+//! - a raw-message signature made through this crate never verifies with
+//!   [`Signer::verify_digest`]
+//! - a digest signature never verifies with [`Signer::verify`]
+//!
+//! # Example
+//!
+//! ```rust
+//! use pqc_signer::prehash::{DigestHasher, StreamingSigner};
+//! use pqc_signer::{Dilithium3Signer, Signer};
+//!
+//! let mut signer = Dilithium3Signer::new();
+//! signer.generate_keypair().unwrap();
+//!
+//! let mut streaming = StreamingSigner::new(&signer);
+//! streaming.update(b"evidence part 1").update(b"evidence part 2");
+//! let signature = streaming.finalize().unwrap();
+//!
+//! let mut hasher = DigestHasher::new();
+//! hasher.update(b"evidence part 1").update(b"evidence part 2");
+//! assert!(signer.verify_digest(&hasher.finalize(), &signature).unwrap());
+//! ```
+
+use crate::error::{PqcError, Result};
+use crate::traits::Signer;
+use sha3::digest::{ExtendableOutput, Update, XofReader};
+use sha3::Shake256;
+use std::io;
+
+/// Domain separator for message digests and digest signatures
+pub const DIGEST_DOMAIN: &[u8] = b"pqc-signer/prehash/shake256/v1\0";
+
+/// Digest length in bytes
+pub const DIGEST_LEN: usize = 64;
+
+/// Digest of a message, as signed by [`Signer::sign_digest`]
+pub type MessageDigest = [u8; DIGEST_LEN];
+
+/// Digest of a message held in memory
+pub fn digest(message: &[u8]) -> MessageDigest {
+    let mut hasher = DigestHasher::new();
+    hasher.update(message);
+    hasher.finalize()
+}
+
+/// Bytes actually signed for a digest: `DIGEST_DOMAIN || digest`
+pub fn envelope(digest: &MessageDigest) -> Vec<u8> {
+    let mut envelope = Vec::with_capacity(DIGEST_DOMAIN.len() + DIGEST_LEN);
+    envelope.extend_from_slice(DIGEST_DOMAIN);
+    envelope.extend_from_slice(digest);
+    envelope
+}
+
+/// Whether a message has the form of a digest envelope
+pub fn is_envelope(message: &[u8]) -> bool {
+    message.len() == DIGEST_DOMAIN.len() + DIGEST_LEN && message.starts_with(DIGEST_DOMAIN)
+}
+
+/// Reject raw messages reserved for digest signatures
+pub(crate) fn check_raw_message(message: &[u8]) -> Result<()> {
+    if is_envelope(message) {
+        return Err(PqcError::ReservedMessage);
+    }
+    Ok(())
+}
+
+/// Incremental message digest (SHAKE-256 with [`DIGEST_DOMAIN`])
+///
+/// Also implements [`io::Write`], so a file can be hashed with [`io::copy`].
+#[derive(Clone)]
+pub struct DigestHasher {
+    shake: Shake256,
+}
+
+impl DigestHasher {
+    /// Start a new digest
+    pub fn new() -> Self {
+        let mut shake = Shake256::default();
+        shake.update(DIGEST_DOMAIN);
+        Self { shake }
+    }
+
+    /// Absorb the next part of the message
+    pub fn update(&mut self, data: &[u8]) -> &mut Self {
+        self.shake.update(data);
+        self
+    }
+
+    /// Finish the digest
+    pub fn finalize(self) -> MessageDigest {
+        let mut digest = [0u8; DIGEST_LEN];
+        self.shake.finalize_xof().read(&mut digest);
+        digest
+    }
+}
+
+impl Default for DigestHasher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl io::Write for DigestHasher {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.update(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Hash a message incrementally, then sign its digest
+///
+/// The signature verifies with [`Signer::verify_digest`] against the digest of the same bytes.
+pub struct StreamingSigner<'a, S: Signer + ?Sized> {
+    signer: &'a S,
+    hasher: DigestHasher,
+}
+
+impl<'a, S: Signer + ?Sized> StreamingSigner<'a, S> {
+    /// Start signing a new message
+    pub fn new(signer: &'a S) -> Self {
+        Self {
+            signer,
+            hasher: DigestHasher::new(),
+        }
+    }
+
+    /// Absorb the next part of the message
+    pub fn update(&mut self, data: &[u8]) -> &mut Self {
+        self.hasher.update(data);
+        self
+    }
+
+    /// Sign the digest of everything absorbed so far
+    ///
+    /// # Errors
+    /// - Same as [`Signer::sign_digest`]
+    pub fn finalize(self) -> Result<Vec<u8>> {
+        self.signer.sign_digest(&self.hasher.finalize())
+    }
+}
+
+impl<S: Signer + ?Sized> io::Write for StreamingSigner<'_, S> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.update(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Dilithium3Signer, Falcon512Signer};
+
+    fn signers() -> Vec<Box<dyn Signer>> {
+        let mut dilithium = Dilithium3Signer::new();
+        dilithium.generate_keypair().unwrap();
+        let mut falcon = Falcon512Signer::new();
+        falcon.generate_keypair().unwrap();
+        vec![Box::new(dilithium), Box::new(falcon)]
+    }
+
+    #[test]
+    fn test_streaming_matches_one_shot_digest() {
+        let message: Vec<u8> = (0..10_000u32).map(|i| i as u8).collect();
+
+        let mut hasher = DigestHasher::new();
+        for chunk in message.chunks(333) {
+            hasher.update(chunk);
+        }
+        assert_eq!(hasher.finalize(), digest(&message));
+        assert_ne!(digest(&message), digest(&message[1..]));
+
+        let mut hasher = DigestHasher::new();
+        io::copy(&mut &message[..], &mut hasher).unwrap();
+        assert_eq!(hasher.finalize(), digest(&message));
+
+        for signer in signers() {
+            let mut streaming = StreamingSigner::new(signer.as_ref());
+            for chunk in message.chunks(1024) {
+                streaming.update(chunk);
+            }
+            let signature = streaming.finalize().unwrap();
+            assert!(signer.verify_digest(&digest(&message), &signature).unwrap());
+            assert!(!signer.verify_digest(&digest(b"other"), &signature).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_raw_and_digest_signatures_are_not_interchangeable() {
+        let message = b"audit report";
+        let message_digest = digest(message);
+
+        for signer in signers() {
+            // A raw signature is not a digest signature, whether of the message's digest or of
+            // the message bytes taken as a digest
+            let raw_signature = signer.sign(message).unwrap();
+            assert!(!signer
+                .verify_digest(&message_digest, &raw_signature)
+                .unwrap_or(false));
+            let mut as_digest = [0u8; DIGEST_LEN];
+            as_digest[..message.len()].copy_from_slice(message);
+            assert!(!signer
+                .verify_digest(&as_digest, &raw_signature)
+                .unwrap_or(false));
+
+            // A digest signature is not a signature of any raw message
+            let digest_signature = signer.sign_digest(&message_digest).unwrap();
+            assert!(!signer.verify(message, &digest_signature).unwrap_or(false));
+            assert!(!signer
+                .verify(&message_digest, &digest_signature)
+                .unwrap_or(false));
+            assert!(matches!(
+                signer.verify(&envelope(&message_digest), &digest_signature),
+                Err(PqcError::ReservedMessage)
+            ));
+
+            // Digest signatures cannot be produced through the raw-message API
+            assert!(matches!(
+                signer.sign(&envelope(&message_digest)),
+                Err(PqcError::ReservedMessage)
+            ));
+        }
+    }
+}
@@ -1,5 +1,6 @@
 /// Unified interface for post-quantum signatures
-use crate::error::Result;
+use crate::error::{PqcError, Result};
+use crate::prehash::MessageDigest;
 
 /// Signer trait
 pub trait Signer {
@@ -7,14 +8,45 @@ pub trait Signer {
     fn generate_keypair(&mut self) -> Result<()>;
 
     /// Sign message
+    ///
+    /// Messages with the form of a digest envelope are rejected with `ReservedMessage`.
     fn sign(&self, message: &[u8]) -> Result<Vec<u8>>;
 
     /// Verify signature
+    ///
+    /// Messages with the form of a digest envelope are rejected with `ReservedMessage`.
     fn verify(&self, message: &[u8], signature: &[u8]) -> Result<bool>;
 
+    /// Sign a message digest (hash-then-sign, see [`crate::prehash`])
+    ///
+    /// Signs `DIGEST_DOMAIN || digest`. Digest signatures are domain-separated from
+    /// raw-message signatures: neither verifies as the other.
+    fn sign_digest(&self, digest: &MessageDigest) -> Result<Vec<u8>>;
+
+    /// Verify a signature made by [`Signer::sign_digest`]
+    fn verify_digest(&self, digest: &MessageDigest, signature: &[u8]) -> Result<bool>;
+
+    /// Verify signature, reporting why it was rejected
+    ///
+    /// Unlike [`Signer::verify`], a rejected signature is an error: `InvalidSignatureLength` or
+    /// `MalformedSignature` for bytes that are not a signature of this algorithm, and
+    /// `VerificationFailed` for a well-formed signature that does not match.
+    fn verify_strict(&self, message: &[u8], signature: &[u8]) -> Result<()> {
+        if self.verify(message, signature)? {
+            Ok(())
+        } else {
+            Err(PqcError::VerificationFailed)
+        }
+    }
+
     /// Get public key
     fn public_key(&self) -> &[u8];
 
     /// Algorithm name
     fn algorithm_name(&self) -> &str;
+
+    /// Signature size in bytes (the maximum for variable-length signatures)
+    ///
+    /// Lets callers reject stored signatures of impossible length before verifying them.
+    fn signature_bytes(&self) -> usize;
 }
@@ -0,0 +1,245 @@
+//! Verification-only API (`verify-only` feature)
+//!
+//! Verifies Dilithium3 signatures with the pure-Rust `crystals-dilithium` implementation, so it
+//! builds for `wasm32-unknown-unknown`, where the PQClean C code behind `Dilithium3Signer`
+//! (and with it key generation and signing) is unavailable. Signatures made by
+//! `Dilithium3Signer` verify here with the same rules:
+//!
+//! - [`Dilithium3Verifier::verify`] rejects digest envelopes (`ReservedMessage`)
+//! - [`Dilithium3Verifier::verify_digest`] verifies [`Signer::sign_digest`] signatures
+//!
+//! [`verify_report_json`] verifies a [`SignedPayload`] document in one call; on wasm32 it is
+//! exported to JavaScript as `verifyReportJson(json, publicKey)`.
+//!
+//! ```sh
+//! cargo check --target wasm32-unknown-unknown --features verify-only
+//! ```
+//!
+//! [`Signer::sign_digest`]: crate::traits::Signer::sign_digest
+
+use crate::envelope::SignedPayload;
+use crate::error::{KeyKind, PqcError, Result};
+use crate::prehash::{self, MessageDigest};
+use crystals_dilithium::dilithium3;
+
+/// Algorithm name accepted by [`verify_report_json`]
+pub const ALGORITHM: &str = "Dilithium3";
+
+/// Dilithium3 public key that can only verify
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use pqc_signer::verify::Dilithium3Verifier;
+///
+/// let verifier = Dilithium3Verifier::from_public_key_only(&public_key)?;
+/// assert!(verifier.verify(b"Audit report data", &signature)?);
+/// ```
+pub struct Dilithium3Verifier {
+    public_key: Vec<u8>,
+    parsed_public_key: dilithium3::PublicKey,
+}
+
+impl Dilithium3Verifier {
+    /// Create a verifier from public key bytes (1952 bytes)
+    ///
+    /// # Errors
+    /// - Returns `InvalidKeyLength` if public key length is incorrect
+    pub fn from_public_key_only(public_key: &[u8]) -> Result<Self> {
+        if public_key.len() != dilithium3::PUBLICKEYBYTES {
+            return Err(PqcError::InvalidKeyLength {
+                key: KeyKind::Public,
+                expected: dilithium3::PUBLICKEYBYTES,
+                got: public_key.len(),
+            });
+        }
+
+        debug!(
+            "Created Dilithium3Verifier: pk_len={} bytes",
+            public_key.len()
+        );
+
+        Ok(Self {
+            public_key: public_key.to_vec(),
+            parsed_public_key: dilithium3::PublicKey::from_bytes(public_key),
+        })
+    }
+
+    /// Public key bytes
+    pub fn public_key(&self) -> &[u8] {
+        &self.public_key
+    }
+
+    /// Verify a detached signature over a raw message
+    ///
+    /// # Errors
+    /// - Returns `InvalidSignatureLength` unless the signature is exactly 3293 bytes
+    /// - Returns `ReservedMessage` if the message has the form of a digest envelope
+    pub fn verify(&self, message: &[u8], signature: &[u8]) -> Result<bool> {
+        prehash::check_raw_message(message)?;
+        self.verify_message(message, signature)
+    }
+
+    /// Verify a digest signature (made by `Signer::sign_digest`)
+    pub fn verify_digest(&self, digest: &MessageDigest, signature: &[u8]) -> Result<bool> {
+        self.verify_message(&prehash::envelope(digest), signature)
+    }
+
+    /// Verify a detached signature over the exact bytes signed
+    fn verify_message(&self, message: &[u8], signature: &[u8]) -> Result<bool> {
+        if signature.len() != dilithium3::SIGNBYTES {
+            return Err(PqcError::InvalidSignatureLength {
+                expected: dilithium3::SIGNBYTES,
+                got: signature.len(),
+            });
+        }
+
+        let is_valid = self.parsed_public_key.verify(message, signature);
+        if !is_valid {
+            warn!("Dilithium3 signature verification failed");
+        }
+        Ok(is_valid)
+    }
+}
+
+/// Verify a [`SignedPayload`] JSON document against a Dilithium3 public key
+///
+/// # Returns
+/// - `Ok(true)`: the signature covers `payload` (or its digest, if `prehashed`)
+/// - `Ok(false)`: the signature does not match
+///
+/// # Errors
+/// - Returns `InvalidEnvelope` if the document is malformed or not a Dilithium3 signature
+/// - Returns `InvalidKeyLength` / `InvalidSignatureLength` for wrongly sized key or signature
+/// - Returns `ReservedMessage` if a raw payload has the form of a digest envelope
+pub fn verify_report_json(json: &str, public_key: &[u8]) -> Result<bool> {
+    let signed = SignedPayload::from_json(json)?;
+    if signed.algorithm != ALGORITHM {
+        return Err(PqcError::InvalidEnvelope(format!(
+            "unsupported algorithm {} (expected {})",
+            signed.algorithm, ALGORITHM
+        )));
+    }
+
+    let verifier = Dilithium3Verifier::from_public_key_only(public_key)?;
+    if signed.prehashed {
+        verifier.verify_digest(&prehash::digest(&signed.payload), &signed.signature)
+    } else {
+        verifier.verify(&signed.payload, &signed.signature)
+    }
+}
+
+/// JavaScript bindings
+#[cfg(target_arch = "wasm32")]
+mod wasm {
+    use wasm_bindgen::prelude::*;
+
+    /// `verifyReportJson(json, publicKey)`: errors are thrown as strings
+    #[wasm_bindgen(js_name = verifyReportJson)]
+    pub fn verify_report_json(json: &str, public_key: &[u8]) -> Result<bool, JsValue> {
+        super::verify_report_json(json, public_key).map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+}
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use super::*;
+    use crate::{Dilithium3Signer, Falcon512Signer, Signer};
+
+    fn new_signer() -> Dilithium3Signer {
+        let mut signer = Dilithium3Signer::new();
+        signer.generate_keypair().unwrap();
+        signer
+    }
+
+    #[test]
+    fn test_verifies_signatures_from_full_build() {
+        let signer = new_signer();
+        let verifier = Dilithium3Verifier::from_public_key_only(signer.public_key()).unwrap();
+        assert_eq!(verifier.public_key(), signer.public_key());
+
+        let message = b"Audit report: blob_id=0x1234, success_rate=98%";
+        let signature = signer.sign(message).unwrap();
+        assert!(verifier.verify(message, &signature).unwrap());
+        assert!(!verifier.verify(b"Tampered message", &signature).unwrap());
+
+        let mut tampered = signature.clone();
+        tampered[100] ^= 0x01;
+        assert!(!verifier.verify(message, &tampered).unwrap());
+        assert!(matches!(
+            verifier.verify(message, &signature[..10]),
+            Err(PqcError::InvalidSignatureLength { got: 10, .. })
+        ));
+
+        let other = Dilithium3Verifier::from_public_key_only(new_signer().public_key()).unwrap();
+        assert!(!other.verify(message, &signature).unwrap());
+    }
+
+    #[test]
+    fn test_digest_signatures_stay_domain_separated() {
+        let signer = new_signer();
+        let verifier = Dilithium3Verifier::from_public_key_only(signer.public_key()).unwrap();
+
+        let digest = prehash::digest(b"large evidence");
+        let signature = signer.sign_digest(&digest).unwrap();
+        assert!(verifier.verify_digest(&digest, &signature).unwrap());
+        assert!(matches!(
+            verifier.verify(&prehash::envelope(&digest), &signature),
+            Err(PqcError::ReservedMessage)
+        ));
+    }
+
+    #[test]
+    fn test_verify_report_json() {
+        let signer = new_signer();
+        let payload = b"canonical report bytes".to_vec();
+
+        let raw = SignedPayload {
+            algorithm: signer.algorithm_name().to_string(),
+            signature: signer.sign(&payload).unwrap(),
+            payload: payload.clone(),
+            prehashed: false,
+        };
+        assert!(verify_report_json(&raw.to_json(), signer.public_key()).unwrap());
+
+        let prehashed = SignedPayload {
+            signature: signer.sign_digest(&prehash::digest(&payload)).unwrap(),
+            prehashed: true,
+            ..raw.clone()
+        };
+        assert!(verify_report_json(&prehashed.to_json(), signer.public_key()).unwrap());
+
+        // `prehashed` is part of what is verified, not a hint that can be flipped
+        let flipped = SignedPayload {
+            prehashed: false,
+            ..prehashed
+        };
+        assert!(!verify_report_json(&flipped.to_json(), signer.public_key()).unwrap());
+
+        let tampered = SignedPayload {
+            payload: b"canonical report bytez".to_vec(),
+            ..raw.clone()
+        };
+        assert!(!verify_report_json(&tampered.to_json(), signer.public_key()).unwrap());
+
+        assert!(matches!(
+            verify_report_json(&raw.to_json(), &[0u8; 10]),
+            Err(PqcError::InvalidKeyLength { got: 10, .. })
+        ));
+        assert!(verify_report_json("not json", signer.public_key()).is_err());
+    }
+
+    #[test]
+    fn test_rejects_other_algorithms() {
+        let mut falcon = Falcon512Signer::new();
+        falcon.generate_keypair().unwrap();
+        let signed = SignedPayload {
+            algorithm: falcon.algorithm_name().to_string(),
+            payload: b"report".to_vec(),
+            signature: falcon.sign(b"report").unwrap(),
+            prehashed: false,
+        };
+        let err = verify_report_json(&signed.to_json(), new_signer().public_key()).unwrap_err();
+        assert_eq!(err.code(), "invalid_envelope");
+    }
+}
@@ -0,0 +1,89 @@
+//! Dilithium3 known-answer tests against the committed fixture
+//!
+//! A failure here after a dependency bump means signatures are no longer byte-compatible with
+//! those already published; regenerate the fixture only once that is understood
+//! (`cargo run -p pqc-signer --example gen_dilithium3_kat`).
+
+use pqc_signer::dilithium::Dilithium3Signer;
+use pqc_signer::kat::{KatFile, NegativeKind, DILITHIUM3_KAT_FIXTURE, PQCRYPTO_DILITHIUM_VERSION};
+use pqc_signer::traits::Signer;
+use std::path::Path;
+
+fn fixture() -> KatFile {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join(DILITHIUM3_KAT_FIXTURE);
+    let json = std::fs::read_to_string(&path).unwrap_or_else(|e| {
+        panic!(
+            "Failed to read {} ({}); generate it with `cargo run -p pqc-signer --example gen_dilithium3_kat`",
+            path.display(),
+            e
+        )
+    });
+    KatFile::from_json(&json).unwrap()
+}
+
+#[test]
+fn test_fixture_matches_dependency() {
+    let kat = fixture();
+    assert_eq!(kat.algorithm, "Dilithium3");
+    assert_eq!(
+        kat.pqcrypto_dilithium, PQCRYPTO_DILITHIUM_VERSION,
+        "pqcrypto-dilithium changed; check the vectors below, then regenerate the fixture"
+    );
+    assert!(kat.vectors.len() >= 2);
+    for kind in [
+        NegativeKind::BitFlippedSignature,
+        NegativeKind::BitFlippedMessage,
+        NegativeKind::SwappedKeys,
+    ] {
+        assert!(kat.negative.iter().any(|n| n.kind == kind), "{:?}", kind);
+    }
+}
+
+#[test]
+fn test_known_signatures_verify() {
+    for (i, vector) in fixture().vectors.iter().enumerate() {
+        let verifier = Dilithium3Signer::from_public_key_only(&vector.public_key).unwrap();
+        assert!(
+            verifier
+                .verify_detached(&vector.message, &vector.signature)
+                .unwrap(),
+            "vector {} does not verify",
+            i
+        );
+    }
+}
+
+#[test]
+fn test_signing_reproduces_known_signatures() {
+    for (i, vector) in fixture().vectors.iter().enumerate() {
+        let signer = vector.signer().unwrap();
+        assert_eq!(signer.public_key(), vector.public_key.as_slice());
+        assert!(
+            signer.sign(&vector.message).unwrap() == vector.signature,
+            "vector {} signs differently",
+            i
+        );
+    }
+}
+
+#[test]
+fn test_negative_vectors_rejected() {
+    for negative in &fixture().negative {
+        assert!(
+            negative.is_rejected(),
+            "{:?} of vector {} verifies",
+            negative.kind,
+            negative.source
+        );
+    }
+}
+
+#[test]
+fn test_export_test_vector_requires_secret_key() {
+    let mut signer = Dilithium3Signer::new();
+    signer.generate_keypair().unwrap();
+    let verifier = Dilithium3Signer::from_public_key_only(signer.public_key()).unwrap();
+
+    let err = verifier.export_test_vector(b"message").unwrap_err();
+    assert_eq!(err.code(), "key_not_initialized");
+}
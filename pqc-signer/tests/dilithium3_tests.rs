@@ -104,11 +104,7 @@ fn test_cross_signer_verification() {
     let signature = signer_a.sign(message).unwrap();
 
     // Signer B uses A's public key to verify signature
-    // Fix: Create signer with only public key for verification
-    let signer_b = Dilithium3Signer::from_bytes(
-        signer_a.public_key(),
-        &vec![0u8; 4032], // Fill with fake private key (not used during verification)
-    ).unwrap();
+    let signer_b = Dilithium3Signer::from_public_key_only(signer_a.public_key()).unwrap();
 
     // Verification should succeed (only uses public key)
     let is_valid = signer_b.verify(message, &signature).unwrap();
@@ -125,11 +121,11 @@ fn test_error_handling() {
 
     assert!(result.is_err());
     match result {
-        Err(PqcError::SigningError(msg)) => {
-            assert!(msg.contains("not initialized"));
+        Err(err @ PqcError::KeyNotInitialized(_)) => {
+            assert_eq!(err.code(), "key_not_initialized");
             println!("✓ Correctly handles uninitialized key error");
         }
-        _ => panic!("Expected SigningError"),
+        _ => panic!("Expected KeyNotInitialized"),
     }
 
     // Test invalid key lengths
@@ -137,7 +133,10 @@ fn test_error_handling() {
     let invalid_sk = vec![0u8; 10];
     let result = Dilithium3Signer::from_bytes(&invalid_pk, &invalid_sk);
 
-    assert!(result.is_err());
+    assert!(matches!(
+        result,
+        Err(PqcError::InvalidKeyLength { got: 10, .. })
+    ));
     println!("✓ Correctly rejects invalid key lengths");
 }
 
@@ -0,0 +1,22 @@
+#!/bin/bash
+
+# 檢查 pqc-signer 的僅驗證構建（verify-only）能否編譯到 wasm32
+# 並運行原生測試：僅驗證 API 須能驗證完整構建生成的簽名
+
+set -e
+
+cd "$(dirname "$0")/../pqc-signer"
+
+if ! rustup target list --installed | grep -q '^wasm32-unknown-unknown$'; then
+    echo "安裝 wasm32-unknown-unknown 目標..."
+    rustup target add wasm32-unknown-unknown
+fi
+
+echo "檢查 wasm32 構建（verify-only）..."
+cargo check --target wasm32-unknown-unknown --features verify-only
+cargo check --target wasm32-unknown-unknown --no-default-features --features verify-only
+
+echo "運行原生測試（verify-only）..."
+cargo test --features verify-only
+
+echo "✓ verify-only 構建檢查通過"
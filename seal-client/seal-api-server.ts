@@ -537,6 +537,7 @@ app.post('/api/seal/set-signature', async (req: Request, res: Response) => {
  * Response:
  * {
  *   "success": true,
+ *   "data": "base64...", // 解密後的原始字節（逐字節還原，供 Rust 客戶端使用）
  *   "report": { ... },  // 解密後的 JSON 報告
  *   "mode": "real-seal", // "real-seal" 或 "fallback"
  *   "metadata": {
@@ -706,6 +707,7 @@ app.post('/api/seal/decrypt', async (req: Request, res: Response) => {
 
       res.json({
         success: true,
+        data: Buffer.from(decryptedBytes).toString('base64'),
         report: decryptedReport,
         message: '✅ 真實的 Seal 解密成功',
         mode: 'real-seal',
@@ -722,6 +724,16 @@ app.post('/api/seal/decrypt', async (req: Request, res: Response) => {
       console.error('   錯誤類型:', sealError.constructor.name);
       console.error('   完整錯誤:', sealError);
 
+      // 可用密鑰服務器少於門檻：降級解密也無意義，直接返回
+      if (sealError.constructor.name === 'TooManyFailedFetchKeyRequestsError') {
+        return res.status(503).json({
+          success: false,
+          code: 'NOT_ENOUGH_KEY_SERVERS',
+          error: 'Not enough key servers responded',
+          sealError: sealError.message
+        });
+      }
+
       // 降級到 fallback 模式
       console.log('\n⚠️  降級到 fallback 解密...');
       try {
@@ -729,6 +741,7 @@ app.post('/api/seal/decrypt', async (req: Request, res: Response) => {
 
         res.json({
           success: true,
+          data: Buffer.from(encryptedData, 'base64').toString('base64'),
           report: decryptedReport,
           message: '⚠️ 降級到模擬解密 (Seal SDK 錯誤)',
           mode: 'fallback',